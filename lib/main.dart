@@ -23,9 +23,15 @@ import 'providers/task_state.dart';
 import 'providers/settings_state.dart';
 import 'providers/log_state.dart';
 import 'providers/app_state.dart';
+import 'providers/update_state.dart';
 import 'navigation.dart';
+import 'utils/utils.dart';
 import 'widgets/common/status_bar.dart';
 import 'widgets/dialogs/active_tasks_close_dialog.dart';
+import 'widgets/dialogs/install_script_dialog.dart';
+import 'widgets/dialogs/task_list_dialog.dart';
+import 'widgets/dialogs/transfer_app_data_dialog.dart';
+import 'widgets/dialogs/update_all_dialog.dart';
 
 void main() async {
   WidgetsFlutterBinding.ensureInitialized();
@@ -48,6 +54,7 @@ void main() async {
         ChangeNotifierProvider(create: (_) => TaskState()),
         ChangeNotifierProvider(create: (_) => SettingsState()),
         ChangeNotifierProvider(create: (_) => LogState()),
+        ChangeNotifierProvider(create: (_) => UpdateState()),
       ],
       child: const SDTFScope(child: YAASApp()),
     ),
@@ -69,6 +76,241 @@ void main() async {
     );
   });
 
+  messages.InsufficientDeviceSpace.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final space = event.message;
+    final l10n = AppLocalizations.of(context);
+    toastification.show(
+      type: ToastificationType.error,
+      title: Text(l10n.insufficientSpaceTitle),
+      description: Text(
+        l10n.insufficientSpaceDescription(
+          formatSize(space.required.toInt(), 2),
+          formatSize(space.available.toInt(), 2),
+          formatSize(space.missing.toInt(), 2),
+        ),
+      ),
+      autoCloseDuration: const Duration(seconds: 8),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
+  messages.UpdateAvailable.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final l10n = AppLocalizations.of(context);
+    final version = event.message.update.version;
+    toastification.show(
+      type: ToastificationType.info,
+      title: Text(l10n.appUpdateAvailableTitle),
+      description: Text(l10n.appUpdateAvailableDescription(version)),
+      autoCloseDuration: const Duration(seconds: 8),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
+  messages.ExportInstalledListResponse.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final l10n = AppLocalizations.of(context);
+    final response = event.message;
+    final error = response.error;
+    toastification.show(
+      type: error == null
+          ? ToastificationType.success
+          : ToastificationType.error,
+      title: Text(error == null
+          ? l10n.installedListExported(
+              response.count.toString(), response.path)
+          : l10n.installedListExportFailed),
+      description: error == null ? null : Text(error),
+      autoCloseDuration: const Duration(seconds: 5),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
+  messages.DonationUploaded.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final l10n = AppLocalizations.of(context);
+    final upload = event.message;
+    toastification.show(
+      type: ToastificationType.success,
+      title: Text(l10n.donationUploadedTitle(upload.archiveName)),
+      description: Text(l10n.donationUploadedDescription(upload.md5)),
+      autoCloseDuration: const Duration(seconds: 8),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
+  messages.AppTrashed.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final l10n = AppLocalizations.of(context);
+    final app = event.message.app;
+    toastification.show(
+      type: ToastificationType.info,
+      title: Text(l10n.appTrashedTitle(app.displayName ?? app.packageName)),
+      description: Text(l10n.appTrashedDescription),
+      autoCloseDuration: const Duration(seconds: 10),
+      callbacks: ToastificationCallbacks(
+        onTap: (item) {
+          toastification.dismiss(item);
+          messages.TaskRequest(
+            task: messages.TaskReinstallFromTrash(
+              path: app.path,
+              packageName: app.packageName,
+              displayName: app.displayName,
+            ),
+            priority: messages.TaskPriority.normal,
+          ).sendSignalToRust();
+        },
+      ),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
+  messages.BackupsPruned.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final l10n = AppLocalizations.of(context);
+    final result = event.message;
+    final error = result.error;
+    toastification.show(
+      type: error == null
+          ? ToastificationType.success
+          : ToastificationType.error,
+      title: Text(error == null
+          ? l10n.backupsPruned(result.deleted.toString(),
+              formatSize(result.freedBytes.toInt(), 1))
+          : l10n.backupsPruneFailed),
+      description: error == null ? null : Text(error),
+      autoCloseDuration: const Duration(seconds: 5),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
+  messages.TasksRestored.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null || event.message.tasks.isEmpty) return;
+    final l10n = AppLocalizations.of(context);
+    final count = event.message.tasks.length;
+    toastification.show(
+      type: ToastificationType.info,
+      title: Text(l10n.tasksResumedTitle(count.toString())),
+      description: Text(l10n.tasksResumedDescription),
+      autoCloseDuration: const Duration(seconds: 8),
+      callbacks: ToastificationCallbacks(
+        onTap: (item) {
+          toastification.dismiss(item);
+          final context = YAASApp.navigatorKey.currentContext;
+          if (context == null) return;
+          showDialog<void>(
+            context: context,
+            builder: (context) => const TaskListDialog(),
+          );
+        },
+      ),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
+  messages.UpdateAllCanaryResult.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) {
+      messages.ContinueUpdateAllRequest(
+        batchId: event.message.batchId,
+        proceed: false,
+      ).sendSignalToRust();
+      return;
+    }
+    showUpdateAllCanaryDialog(context, event.message);
+  });
+
+  messages.TransferOverwriteReview.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) {
+      messages.TransferOverwriteDecision(
+        taskId: event.message.taskId,
+        approved: false,
+      ).sendSignalToRust();
+      return;
+    }
+    showTransferOverwriteDialog(context, event.message);
+  });
+
+  messages.InstallScriptReview.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) {
+      messages.InstallScriptDecision(
+        reviewId: event.message.reviewId,
+        approved: false,
+      ).sendSignalToRust();
+      return;
+    }
+    showInstallScriptReviewDialog(context, event.message);
+  });
+
+  messages.InstallScriptUnsupported.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    showInstallScriptUnsupportedDialog(context, event.message);
+  });
+
+  messages.DeviceWakeReport.rustSignalStream.listen((event) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final l10n = AppLocalizations.of(context);
+    final report = event.message;
+    final steps = report.steps.map((step) {
+      final label = switch (step.kind) {
+        messages.WakeStepKind.reconnect => l10n.wakeStepReconnect,
+        messages.WakeStepKind.keyevent => l10n.wakeStepKeyevent,
+        messages.WakeStepKind.proximityClose => l10n.wakeStepProximity,
+        messages.WakeStepKind.connect => l10n.wakeStepConnect,
+      };
+      final error = step.error;
+      return '${step.success ? '✓' : '✗'} $label'
+          '${error == null ? '' : ': $error'}';
+    }).join('\n');
+    toastification.show(
+      type: report.recovered
+          ? ToastificationType.success
+          : ToastificationType.error,
+      title: Text(report.recovered
+          ? l10n.deviceWakeRecovered(report.serial)
+          : l10n.deviceWakeFailed(report.serial)),
+      description: Text(steps),
+      autoCloseDuration: Duration(seconds: report.recovered ? 5 : 10),
+      style: ToastificationStyle.flat,
+      backgroundColor: null,
+      borderSide: BorderSide.none,
+      alignment: Alignment.bottomRight,
+    );
+  });
+
   messages.RustPanic.rustSignalStream.listen((panic) {
     final appState = YAASApp.navigatorKey.currentContext?.read<AppState>();
     if (appState != null) {
@@ -76,6 +318,28 @@ void main() async {
     }
   });
 
+  messages.BackendCapabilities.rustSignalStream.listen((pack) {
+    final context = YAASApp.navigatorKey.currentContext;
+    if (context == null) return;
+    final appState = context.read<AppState>();
+    final wasCompatible = appState.coreApiCompatible;
+    appState.setCoreCapabilities(pack.message);
+    if (wasCompatible && !appState.coreApiCompatible) {
+      final l10n = AppLocalizations.of(context);
+      toastification.show(
+        type: ToastificationType.warning,
+        title: Text(l10n.coreApiMismatchTitle),
+        description: Text(l10n.coreApiMismatchDescription),
+        style: ToastificationStyle.flat,
+        backgroundColor: null,
+        borderSide: BorderSide.none,
+        alignment: Alignment.bottomRight,
+      );
+    }
+  });
+  messages.ApiHandshakeRequest(uiApiVersion: AppState.uiApiVersion)
+      .sendSignalToRust();
+
   // Receive core build/version info
   messages.AppVersionInfo.rustSignalStream.listen((pack) {
     final info = pack.message;
@@ -113,6 +377,11 @@ class _YAASAppState extends State<YAASApp> {
       context.read<
           CloudAppsState>(); // Do not call load() here, it will be called when the downloader becomes available.
       context.read<SettingsState>().load();
+      context.read<DeviceState>();
+      context.read<AdbStateProvider>();
+      context.read<TaskState>();
+      // Picks up tasks and state the core kept if only the UI restarted
+      messages.ResyncStateRequest().sendSignalToRust();
     });
 
     // Best-effort read of KDE accent color on Linux
@@ -7,6 +7,14 @@ class AdbStateProvider extends ChangeNotifier {
   AdbState get state => _state;
   List<AdbDeviceBrief> _devicesList = const [];
   int get devicesCount => _devicesList.length;
+  HostConflictsChanged _hostConflicts = HostConflictsChanged(
+    streamingClients: const [],
+    foreignAdbServers: const [],
+  );
+  AdbHealthChanged _health = AdbHealthChanged(
+    health: AdbHealth.healthy,
+    recoveryAttempts: 0,
+  );
 
   AdbStateProvider() {
     AdbState.rustSignalStream.listen((event) {
@@ -17,8 +25,30 @@ class AdbStateProvider extends ChangeNotifier {
       _devicesList = List.unmodifiable(event.message.value);
       notifyListeners();
     });
+    HostConflictsChanged.rustSignalStream.listen((event) {
+      _hostConflicts = event.message;
+      notifyListeners();
+    });
+    AdbHealthChanged.rustSignalStream.listen((event) {
+      _health = event.message;
+      notifyListeners();
+    });
   }
 
+  /// Status of the watchdog restarting a stalled ADB server
+  AdbHealthChanged get health => _health;
+
+  /// Streaming apps running on this computer
+  List<StreamingClient> get streamingClients =>
+      _hostConflicts.streamingClients;
+
+  /// ADB servers started by other apps
+  List<ForeignAdbServer> get foreignAdbServers =>
+      _hostConflicts.foreignAdbServers;
+
+  bool get hasHostConflicts =>
+      streamingClients.isNotEmpty || foreignAdbServers.isNotEmpty;
+
   bool get isConnected => _state is AdbStateDeviceConnected;
 
   List<AdbDeviceBrief> get availableDevices => _devicesList;
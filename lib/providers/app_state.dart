@@ -210,6 +210,28 @@ class AppState extends ChangeNotifier {
     );
     notifyListeners();
   }
+
+  // Signal API compatibility between the UI and the core
+  static const int uiApiVersion = 3;
+  static const int minCoreApiVersion = 3;
+  messages.BackendCapabilities? _coreCapabilities;
+  messages.BackendCapabilities? get coreCapabilities => _coreCapabilities;
+  void setCoreCapabilities(messages.BackendCapabilities capabilities) {
+    _coreCapabilities = capabilities;
+    notifyListeners();
+  }
+
+  /// Whether the core speaks a signal API this UI understands, and vice versa.
+  /// Assumed true until the core has reported its capabilities.
+  bool get coreApiCompatible {
+    final capabilities = _coreCapabilities;
+    if (capabilities == null) return true;
+    return capabilities.apiVersion >= minCoreApiVersion &&
+        capabilities.uiSupported != false;
+  }
+
+  bool coreSupportsTask(String taskKind) =>
+      _coreCapabilities?.taskKinds.contains(taskKind) ?? false;
 }
 
 class CoreVersionInfo {
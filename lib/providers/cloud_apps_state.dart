@@ -1,6 +1,7 @@
 import 'dart:async';
 
 import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
 import '../src/bindings/bindings.dart';
 
 class CloudAppsState extends ChangeNotifier {
@@ -15,6 +16,10 @@ class CloudAppsState extends ChangeNotifier {
   final Map<String, int> _maxVersionCodeByPackage = {};
   final Map<String, List<CloudApp>> _appsByPackage = {};
   final Set<String> _donationBlacklist = {};
+  final Map<String, AppVersionPin> _versionPins = {};
+  final Map<String, String> _nameOverrides = {};
+  final Map<String, UpdatableApp> _updatableApps = {};
+  final Set<String> _watchedPackages = {};
   Timer? _slowLoadingTimer;
 
   List<CloudApp> get apps => _apps;
@@ -29,6 +34,15 @@ class CloudAppsState extends ChangeNotifier {
   int? newestVersionCodeForPackage(String packageName) =>
       _maxVersionCodeByPackage[packageName];
 
+  /// Installed apps with newer catalog versions, as diffed by the backend
+  Iterable<UpdatableApp> get updatableApps => _updatableApps.values;
+
+  UpdatableApp? updatableApp(String packageName) =>
+      _updatableApps[packageName];
+
+  CloudApp? appByFullName(String fullName) =>
+      _apps.where((app) => app.fullName == fullName).firstOrNull;
+
   List<CloudApp> matchingAppsForPackage(String packageName) =>
       List.unmodifiable(_appsByPackage[packageName] ?? const []);
 
@@ -38,14 +52,6 @@ class CloudAppsState extends ChangeNotifier {
     return apps.first;
   }
 
-  String thumbnailUrlFor(String packageName) {
-    return '${_mediaBaseUrl}thumbnails/$packageName.jpg';
-  }
-
-  String trailerUrlFor(String packageName) {
-    return '${_mediaBaseUrl}videos/$packageName.mp4';
-  }
-
   CloudAppsState() {
     CloudAppsChangedEvent.rustSignalStream.listen((event) {
       _isLoading = event.message.isLoading;
@@ -69,6 +75,23 @@ class CloudAppsState extends ChangeNotifier {
       notifyListeners();
     });
 
+    CatalogDelta.rustSignalStream.listen((event) {
+      final delta = event.message;
+      if (_apps.length != delta.baseCount) {
+        // Out of sync with the backend, ask for the whole list again
+        LoadCloudAppsRequest(refresh: false).sendSignalToRust();
+        return;
+      }
+      final removed = delta.removed.toSet();
+      final changed = {for (final app in delta.changed) app.fullName: app};
+      _setApps([
+        for (final app in _apps)
+          if (!removed.contains(app.fullName)) changed[app.fullName] ?? app,
+        ...delta.added,
+      ]);
+      notifyListeners();
+    });
+
     // Reset state when downloader becomes unavailable, and auto-load when it becomes available
     DownloaderAvailabilityChanged.rustSignalStream.listen((event) {
       final msg = event.message;
@@ -89,6 +112,40 @@ class CloudAppsState extends ChangeNotifier {
       }
     });
 
+    AppVersionPinsChanged.rustSignalStream.listen((event) {
+      _versionPins
+        ..clear()
+        ..addEntries(event.message.pins.map((p) => MapEntry(p.packageName, p)));
+      notifyListeners();
+    });
+    GetAppVersionPinsRequest().sendSignalToRust();
+
+    AppNameOverridesChanged.rustSignalStream.listen((event) {
+      _nameOverrides
+        ..clear()
+        ..addEntries(event.message.overrides
+            .map((o) => MapEntry(o.packageName, o.name)));
+      notifyListeners();
+    });
+    GetAppNameOverridesRequest().sendSignalToRust();
+
+    WatchedAppsChanged.rustSignalStream.listen((event) {
+      _watchedPackages
+        ..clear()
+        ..addAll(event.message.apps.map((a) => a.packageName));
+      notifyListeners();
+    });
+    GetWatchedAppsRequest().sendSignalToRust();
+
+    UpdatableApps.rustSignalStream.listen((event) {
+      _updatableApps
+        ..clear()
+        ..addEntries(
+            event.message.apps.map((a) => MapEntry(a.packageName, a)));
+      notifyListeners();
+    });
+    GetUpdatableAppsRequest().sendSignalToRust();
+
     // Receive media config from Rust
     MediaConfigChanged.rustSignalStream.listen((event) {
       final cfg = event.message;
@@ -156,6 +213,48 @@ class CloudAppsState extends ChangeNotifier {
     }
   }
 
+  AppVersionPin? versionPinFor(String packageName) => _versionPins[packageName];
+
+  /// Pins an installed app to a version code, so newer catalog versions are
+  /// not offered as updates. `null` unpins it.
+  void setVersionPin(String packageName, int? versionCode) {
+    SetAppVersionPinRequest(
+      packageName: packageName,
+      versionCode: versionCode == null
+          ? null
+          : Uint64.fromBigInt(BigInt.from(versionCode)),
+    ).sendSignalToRust();
+  }
+
+  /// Stops offering a catalog version of an installed app as an update, or
+  /// offers it again
+  void setIgnoredVersion(String packageName, int versionCode, bool ignored) {
+    SetIgnoredAppVersionRequest(
+      packageName: packageName,
+      versionCode: Uint64.fromBigInt(BigInt.from(versionCode)),
+      ignored: ignored,
+    ).sendSignalToRust();
+  }
+
+  /// Name set by the user for a package, shown instead of its own name
+  String? nameOverrideFor(String packageName) => _nameOverrides[packageName];
+
+  /// Renames an app in the catalog and the installed apps. `null` or a blank
+  /// name restores its own name.
+  void setNameOverride(String packageName, String? name) {
+    SetAppNameOverrideRequest(packageName: packageName, name: name)
+        .sendSignalToRust();
+  }
+
+  /// Whether new catalog versions of an app are announced
+  bool isWatched(String truePackageName) =>
+      _watchedPackages.contains(truePackageName);
+
+  void setWatched(String truePackageName, bool watched) {
+    SetAppWatchedRequest(packageName: truePackageName, watched: watched)
+        .sendSignalToRust();
+  }
+
   bool isDonationBlacklisted(String packageName) {
     return _donationBlacklist.contains(packageName);
   }
@@ -6,6 +6,7 @@ class DeviceState extends ChangeNotifier {
   AdbDevice? _device;
   AdbDevice? get device => _device;
   Map<String, InstalledPackage> _installedByPackage = const {};
+  Map<AutoRevertKind, DateTime> _autoReverts = const {};
 
   DeviceState() {
     DeviceChangedEvent.rustSignalStream.listen((event) {
@@ -15,6 +16,14 @@ class DeviceState extends ChangeNotifier {
       _installedByPackage = {for (final p in pkgs) p.packageName: p};
       notifyListeners();
     });
+    AutoRevertStatus.rustSignalStream.listen((event) {
+      final now = DateTime.now();
+      _autoReverts = {
+        for (final revert in event.message.pending)
+          revert.kind: now.add(Duration(seconds: revert.remainingSecs.toInt())),
+      };
+      notifyListeners();
+    });
   }
 
   bool get isConnected => _device != null;
@@ -36,15 +45,37 @@ class DeviceState extends ChangeNotifier {
 
   SpaceInfo? get spaceInfo => _device?.spaceInfo;
   bool? get guardianPaused => _device?.guardianPaused;
+
+  /// When a temporary state is restored automatically, null if not scheduled
+  DateTime? autoRevertAt(AutoRevertKind kind) => _autoReverts[kind];
   String? get usbSpeed => _device?.usbSpeed;
+  FirmwareInfo? get firmware => _device?.firmware;
+  DeviceTweaks? get tweaks => _device?.tweaks;
   bool? get isStorageConnected => _device?.storageConnected;
 
+  /// Version of the device helper, null if it is not installed
+  int? get agentVersion => _device?.agentVersion?.toInt();
+
   /// Whether the proximity sensor is currently disabled (faked/overridden).
   /// - true: sensor is disabled (faked as close)
   /// - false: sensor is enabled (real sensor active)
   /// - null: state unknown
   bool? get proximityDisabled => _device?.proximityDisabled;
 
+  /// Optional features of the connected device. Nothing is supported when disconnected.
+  DeviceCapabilities? get capabilities => _device?.capabilities;
+
+  /// Whether the device is a VR headset rather than a phone or tablet.
+  bool get isVrHeadset => capabilities?.vrHeadset ?? false;
+  bool get supportsGuardianPause => capabilities?.guardianPause ?? false;
+  bool get supportsProximityOverride =>
+      capabilities?.proximityOverride ?? false;
+  bool get supportsCasting => capabilities?.casting ?? false;
+  bool get supportsWirelessEnable => capabilities?.wirelessEnable ?? false;
+  bool get supportsDeviceTweaks => capabilities?.deviceTweaks ?? false;
+  bool get supportsControllerPairing =>
+      capabilities?.controllerPairing ?? false;
+
   String controllerStatusString(
       BuildContext context, ControllerInfo? controller) {
     final l10n = AppLocalizations.of(context);
@@ -20,6 +20,16 @@ class LogInfo {
     this.spanTrace,
   });
 
+  factory LogInfo.fromEntry(LogEntry entry) => LogInfo(
+        timestamp: DateTime.fromMillisecondsSinceEpoch(entry.timestamp.toInt()),
+        level: entry.level,
+        target: entry.target,
+        message: entry.message,
+        kind: entry.kind,
+        fields: entry.fields,
+        spanTrace: entry.spanTrace,
+      );
+
   Color get levelColor {
     switch (level) {
       case LogLevel.trace:
@@ -132,16 +142,7 @@ class LogState extends ChangeNotifier {
       final logEntries = event.message.entries;
 
       for (final logEntry in logEntries) {
-        final logInfo = LogInfo(
-          timestamp:
-              DateTime.fromMillisecondsSinceEpoch(logEntry.timestamp.toInt()),
-          level: logEntry.level,
-          target: logEntry.target,
-          message: logEntry.message,
-          kind: logEntry.kind,
-          fields: logEntry.fields,
-          spanTrace: logEntry.spanTrace,
-        );
+        final logInfo = LogInfo.fromEntry(logEntry);
 
         _logs.add(logInfo);
       }
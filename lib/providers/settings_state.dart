@@ -12,10 +12,16 @@ class SettingsState extends ChangeNotifier {
     preferredConnectionType: ConnectionKind.usb,
     downloadsLocation: '',
     backupsLocation: '',
+    catalogBaseUrl: '',
+    mediaBaseUrl: '',
+    pinnedSpki: const [],
+    logFileFormat: LogFileFormat.pretty,
+    installedDownloadsLocation: '',
     bandwidthLimit: '',
     cleanupPolicy: DownloadCleanupPolicy.deleteAfterInstall,
     downloadMode: DownloadMode.staged,
     writeLegacyReleaseJson: false,
+    dedupeDownloads: false,
     localeCode: 'system',
     navigationRailLabelVisibility: NavigationRailLabelVisibility.selected,
     startupPageKey: 'home',
@@ -24,8 +30,36 @@ class SettingsState extends ChangeNotifier {
     themePreference: ThemePreference.dark,
     favoritePackages: const [],
     mdnsAutoConnect: true,
+    reconnectWirelessDevices: true,
+    knownWirelessTargets: const [],
     popularityRange: PopularityRange.day7,
     autoReinstallOnConflict: true,
+    autoApproveInstallScripts: false,
+    maxConcurrentDownloads: 1,
+    maxConcurrentAdbOperations: 1,
+    extractionThreads: 0,
+    taskKindLimits: const [],
+    checkForUpdates: true,
+    castingDownloadPolicy: CastingDownloadPolicy.ignore,
+    castingBandwidthLimit: '1M',
+    backupRetentionMaxPerPackage: 0,
+    backupRetentionMaxTotalGb: 0,
+    backupRetentionMaxAgeDays: 0,
+    trashBeforeUninstall: false,
+    trashRetentionDays: 7,
+    wakeOfflineDevices: false,
+    offlineWakeAction: OfflineWakeAction.keyevent,
+    deviceReconnectGraceSecs: 5,
+    downloadLayout: '',
+    deviceAgentEnabled: false,
+    watchPackageChanges: false,
+    probeWirelessLink: true,
+    commandMacros: const [],
+    downloadMirrors: const [],
+    mirrorMinSpeedKbps: 0,
+    mirrorSlowSecs: 30,
+    stopForeignAdbServers: false,
+    shellConsoleEnabled: false,
   );
 
   bool _isLoading = false;
@@ -46,6 +80,7 @@ class SettingsState extends ChangeNotifier {
   bool _downloaderSupportsRemoteSelection = false;
   bool _downloaderSupportsBandwidthLimit = false;
   bool _downloaderSupportsDownloadModeSelection = false;
+  bool _downloaderSupportsPartialDownloads = false;
 
   List<String> _rcloneRemotes = const [];
   bool _isRemotesLoading = true;
@@ -119,6 +154,8 @@ class SettingsState extends ChangeNotifier {
           msg.capabilities.supportsBandwidthLimit;
       _downloaderSupportsDownloadModeSelection =
           msg.capabilities.supportsDownloadModeSelection;
+      _downloaderSupportsPartialDownloads =
+          msg.capabilities.supportsPartialDownloads;
       if (!msg.initializing) {
         _downloaderInitBytes = 0;
         _downloaderInitTotal = null;
@@ -193,6 +230,8 @@ class SettingsState extends ChangeNotifier {
       _downloaderSupportsBandwidthLimit;
   bool get downloaderSupportsDownloadModeSelection =>
       _downloaderSupportsDownloadModeSelection;
+  bool get downloaderSupportsPartialDownloads =>
+      _downloaderSupportsPartialDownloads;
   InstalledDownloaderConfig? get activeDownloaderConfig {
     final currentId = _downloaderConfigId;
     if (currentId == null) return null;
@@ -346,4 +385,29 @@ class SettingsState extends ChangeNotifier {
     notifyListeners();
     SaveSettingsRequest(settings: _settings).sendSignalToRust();
   }
+
+  // Command macros
+  List<CommandMacro> get commandMacros => _settings.commandMacros;
+
+  /// Adds [commandMacro], replacing the macro named [previousName] if given
+  void saveCommandMacro(CommandMacro commandMacro, {String? previousName}) {
+    final macros = _settings.commandMacros.toList(growable: true);
+    final index = macros.indexWhere(
+        (m) => m.name == (previousName ?? commandMacro.name));
+    if (index >= 0) {
+      macros[index] = commandMacro;
+    } else {
+      macros.add(commandMacro);
+    }
+    _settings = _settings.copyWith(commandMacros: List.unmodifiable(macros));
+    notifyListeners();
+    SaveSettingsRequest(settings: _settings).sendSignalToRust();
+  }
+
+  void removeCommandMacro(String name) {
+    final macros = _settings.commandMacros.where((m) => m.name != name);
+    _settings = _settings.copyWith(commandMacros: List.unmodifiable(macros));
+    notifyListeners();
+    SaveSettingsRequest(settings: _settings).sendSignalToRust();
+  }
 }
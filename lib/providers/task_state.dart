@@ -7,6 +7,7 @@ class TaskInfo {
   final TaskKind kind;
   final String? taskName;
   final TaskStatus status;
+  final TaskPriority priority;
   final double totalProgress;
   final int currentStep;
   final int totalSteps;
@@ -15,11 +16,27 @@ class TaskInfo {
   final DateTime startTime;
   DateTime? endTime;
 
+  /// Transfer stats of the download step, set once a downloading task finished
+  final DownloadSummary? downloadSummary;
+
+  /// Estimated seconds until the task finishes, if known
+  final int? etaSecs;
+
+  /// Live stats of the transfer the current step is making, if any
+  final TransferProgress? transfer;
+
+  /// Whether the task was re-queued from a previous session
+  final bool restored;
+
+  /// Outputs of the task, set once it finished
+  final List<TaskArtifact> artifacts;
+
   TaskInfo({
     required this.taskId,
     required this.kind,
     this.taskName,
     required this.status,
+    required this.priority,
     required this.totalProgress,
     required this.currentStep,
     required this.totalSteps,
@@ -27,23 +44,34 @@ class TaskInfo {
     required this.message,
     required this.startTime,
     this.endTime,
+    this.downloadSummary,
+    this.etaSecs,
+    this.transfer,
+    this.restored = false,
+    this.artifacts = const [],
   });
 
   TaskInfo copyWith({
     String? taskName,
     TaskStatus? status,
+    TaskPriority? priority,
     double? totalProgress,
     int? currentStep,
     int? totalSteps,
     double? Function()? stepProgress,
     String? message,
     DateTime? endTime,
+    DownloadSummary? downloadSummary,
+    int? Function()? etaSecs,
+    TransferProgress? Function()? transfer,
+    List<TaskArtifact>? artifacts,
   }) {
     return TaskInfo(
       taskId: taskId,
       kind: kind,
       taskName: taskName ?? this.taskName,
       status: status ?? this.status,
+      priority: priority ?? this.priority,
       totalProgress: totalProgress ?? this.totalProgress,
       currentStep: currentStep ?? this.currentStep,
       totalSteps: totalSteps ?? this.totalSteps,
@@ -51,6 +79,11 @@ class TaskInfo {
       message: message ?? this.message,
       startTime: startTime,
       endTime: endTime ?? this.endTime,
+      downloadSummary: downloadSummary ?? this.downloadSummary,
+      etaSecs: etaSecs == null ? this.etaSecs : etaSecs(),
+      transfer: transfer == null ? this.transfer : transfer(),
+      restored: restored,
+      artifacts: artifacts ?? this.artifacts,
     );
   }
 
@@ -63,6 +96,7 @@ class TaskInfo {
 class TaskState extends ChangeNotifier {
   final Map<int, TaskInfo> _tasks = {};
   final int _maxFinishedTasks = 50;
+  final Set<int> _restoredTaskIds = {};
 
   List<TaskInfo> get activeTasks =>
       _tasks.values.where((task) => !task.isFinished).toList();
@@ -74,12 +108,36 @@ class TaskState extends ChangeNotifier {
   int get failedTaskCount =>
       _tasks.values.where((task) => task.status == TaskStatus.failed).length;
 
+  /// Moves an unfinished task ahead of or behind other waiting tasks
+  void setTaskPriority(int taskId, TaskPriority priority) {
+    final task = _tasks[taskId];
+    if (task == null || task.isFinished) return;
+    TaskReorderRequest(
+      taskId: Uint64.fromBigInt(BigInt.from(taskId)),
+      priority: priority,
+    ).sendSignalToRust();
+    _tasks[taskId] = task.copyWith(priority: priority);
+    notifyListeners();
+  }
+
   void clearRecentTasks() {
     _tasks.removeWhere((_, task) => task.isFinished);
     notifyListeners();
   }
 
   TaskState() {
+    TasksRestored.rustSignalStream.listen((event) {
+      for (final task in event.message.tasks) {
+        _restoredTaskIds.add(task.taskId.toInt());
+      }
+      debugPrint('[TaskState] Restored ${event.message.tasks.length} tasks '
+          'from previous session');
+    });
+
+    TaskListSnapshot.rustSignalStream.listen((event) {
+      _applySnapshot(event.message.tasks);
+    });
+
     TaskProgress.rustSignalStream.listen((event) {
       final progress = event.message;
       final taskId = progress.taskId.toInt();
@@ -89,6 +147,7 @@ class TaskState extends ChangeNotifier {
         _tasks[taskId] = oldTask.copyWith(
           taskName: progress.taskName,
           status: progress.status,
+          priority: progress.priority,
           totalProgress: progress.totalProgress,
           currentStep: progress.currentStep,
           totalSteps: progress.totalSteps,
@@ -99,6 +158,10 @@ class TaskState extends ChangeNotifier {
                   progress.status == TaskStatus.cancelled
               ? DateTime.now()
               : null,
+          downloadSummary: progress.downloadSummary,
+          etaSecs: () => progress.etaSecs?.toInt(),
+          transfer: () => progress.transfer,
+          artifacts: progress.artifacts,
         );
 
         if (oldTask.status != progress.status) {
@@ -122,12 +185,16 @@ class TaskState extends ChangeNotifier {
           kind: progress.taskKind,
           taskName: progress.taskName,
           status: progress.status,
+          priority: progress.priority,
           totalProgress: progress.totalProgress,
           currentStep: progress.currentStep,
           totalSteps: progress.totalSteps,
           stepProgress: progress.stepProgress,
           message: progress.message,
+          etaSecs: progress.etaSecs?.toInt(),
+          transfer: progress.transfer,
           startTime: DateTime.now(),
+          restored: _restoredTaskIds.remove(taskId),
         );
 
         debugPrint(
@@ -139,6 +206,43 @@ class TaskState extends ChangeNotifier {
     });
   }
 
+  /// Adds live tasks this state has not seen and drops unfinished tasks
+  /// that are no longer live, whose final progress was missed
+  void _applySnapshot(List<TaskSnapshot> snapshot) {
+    final liveIds = <int>{};
+    for (final task in snapshot) {
+      final taskId = task.taskId.toInt();
+      liveIds.add(taskId);
+      _tasks.putIfAbsent(
+        taskId,
+        () => TaskInfo(
+          taskId: taskId,
+          kind: task.taskKind,
+          taskName: task.taskName,
+          status: task.status,
+          priority: task.priority,
+          totalProgress: task.totalProgress,
+          currentStep: task.currentStep,
+          totalSteps: task.totalSteps,
+          stepProgress: null,
+          message: '',
+          startTime: DateTime.now(),
+          restored: _restoredTaskIds.remove(taskId),
+        ),
+      );
+    }
+    final stale = _tasks.values
+        .where((task) => !task.isFinished && !liveIds.contains(task.taskId))
+        .map((task) => task.taskId)
+        .toList();
+    if (stale.isNotEmpty) {
+      debugPrint('[TaskState] Dropping ${stale.length} tasks missing from '
+          'task list snapshot');
+      _tasks.removeWhere((id, _) => stale.contains(id));
+    }
+    notifyListeners();
+  }
+
   void _cleanupOldTasks() {
     final finishedTasks = _tasks.values
         .where((task) => task.isFinished)
@@ -0,0 +1,87 @@
+import 'package:flutter/foundation.dart';
+import '../src/bindings/bindings.dart';
+
+/// Tracks update checks and downloads of YAAS itself.
+class UpdateState extends ChangeNotifier {
+  UpdateInfo? _availableUpdate;
+  bool _checking = false;
+  bool _checked = false;
+  String? _checkError;
+  bool _downloading = false;
+  int _downloadReceived = 0;
+  int? _downloadTotal;
+  String? _downloadedPath;
+  String? _downloadError;
+
+  UpdateInfo? get availableUpdate => _availableUpdate;
+  bool get checking => _checking;
+
+  /// Whether a check requested from the UI has finished
+  bool get checked => _checked;
+  String? get checkError => _checkError;
+  bool get downloading => _downloading;
+  int get downloadReceived => _downloadReceived;
+  int? get downloadTotal => _downloadTotal;
+  String? get downloadedPath => _downloadedPath;
+  String? get downloadError => _downloadError;
+
+  double? get downloadProgress {
+    final total = _downloadTotal;
+    if (total == null || total == 0) return null;
+    return (_downloadReceived / total).clamp(0.0, 1.0);
+  }
+
+  UpdateState() {
+    UpdateAvailable.rustSignalStream.listen((event) {
+      _availableUpdate = event.message.update;
+      notifyListeners();
+    });
+
+    UpdateCheckResponse.rustSignalStream.listen((event) {
+      final response = event.message;
+      _checking = false;
+      _checked = true;
+      _checkError = response.error;
+      if (response.error == null) {
+        _availableUpdate = response.update;
+      }
+      notifyListeners();
+    });
+
+    UpdateDownloadProgress.rustSignalStream.listen((event) {
+      _downloadReceived = event.message.received.toInt();
+      _downloadTotal = event.message.total?.toInt();
+      notifyListeners();
+    });
+
+    UpdateDownloadResponse.rustSignalStream.listen((event) {
+      final response = event.message;
+      _downloading = false;
+      _downloadedPath = response.path;
+      _downloadError = response.error;
+      if (response.error != null) {
+        debugPrint('[UpdateState] Update download failed: ${response.error}');
+      }
+      notifyListeners();
+    });
+  }
+
+  void checkForUpdate() {
+    if (_checking) return;
+    _checking = true;
+    _checkError = null;
+    notifyListeners();
+    CheckForUpdateRequest().sendSignalToRust();
+  }
+
+  void downloadUpdate() {
+    if (_downloading || _availableUpdate?.downloadUrl == null) return;
+    _downloading = true;
+    _downloadReceived = 0;
+    _downloadTotal = _availableUpdate?.assetSize?.toInt();
+    _downloadedPath = null;
+    _downloadError = null;
+    notifyListeners();
+    DownloadUpdateRequest().sendSignalToRust();
+  }
+}
@@ -260,12 +260,108 @@ abstract class AppLocalizations {
   /// **'Downloads Location'**
   String get settingsDownloadsLocation;
 
+  /// No description provided for @settingsDownloadLayout.
+  ///
+  /// In en, this message translates to:
+  /// **'Download Folder Layout'**
+  String get settingsDownloadLayout;
+
+  /// No description provided for @settingsDownloadLayoutHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Folders apps are sorted into, separated by /. Supports {placeholders}. Leave empty to keep all apps directly in the downloads location.'**
+  String settingsDownloadLayoutHelp(String placeholders);
+
+  /// No description provided for @settingsDownloadLayoutPresets.
+  ///
+  /// In en, this message translates to:
+  /// **'Layout presets'**
+  String get settingsDownloadLayoutPresets;
+
+  /// No description provided for @settingsDownloadLayoutFlat.
+  ///
+  /// In en, this message translates to:
+  /// **'Flat'**
+  String get settingsDownloadLayoutFlat;
+
+  /// No description provided for @settingsDownloadLayoutByLetter.
+  ///
+  /// In en, this message translates to:
+  /// **'By first letter'**
+  String get settingsDownloadLayoutByLetter;
+
+  /// No description provided for @settingsDownloadLayoutByName.
+  ///
+  /// In en, this message translates to:
+  /// **'By app name'**
+  String get settingsDownloadLayoutByName;
+
+  /// No description provided for @settingsDownloadLayoutByPackage.
+  ///
+  /// In en, this message translates to:
+  /// **'By package name'**
+  String get settingsDownloadLayoutByPackage;
+
   /// No description provided for @settingsBackupsLocation.
   ///
   /// In en, this message translates to:
   /// **'Backups Location'**
   String get settingsBackupsLocation;
 
+  /// No description provided for @settingsBackupRetentionPerApp.
+  ///
+  /// In en, this message translates to:
+  /// **'Backups Kept per App'**
+  String get settingsBackupRetentionPerApp;
+
+  /// No description provided for @settingsBackupRetentionTotalSize.
+  ///
+  /// In en, this message translates to:
+  /// **'Maximum Total Backup Size'**
+  String get settingsBackupRetentionTotalSize;
+
+  /// No description provided for @settingsBackupRetentionMaxAge.
+  ///
+  /// In en, this message translates to:
+  /// **'Delete Backups Older Than'**
+  String get settingsBackupRetentionMaxAge;
+
+  /// No description provided for @settingsBackupRetentionUnlimited.
+  ///
+  /// In en, this message translates to:
+  /// **'No limit'**
+  String get settingsBackupRetentionUnlimited;
+
+  /// No description provided for @settingsBackupRetentionHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Older backups are deleted automatically. The newest backup of each app is always kept.'**
+  String get settingsBackupRetentionHelp;
+
+  /// No description provided for @settingsBackupRetentionDays.
+  ///
+  /// In en, this message translates to:
+  /// **'{days} days'**
+  String settingsBackupRetentionDays(String days);
+
+  /// No description provided for @settingsTrashBeforeUninstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Move Uninstalled Apps to Trash'**
+  String get settingsTrashBeforeUninstall;
+
+  /// No description provided for @settingsTrashBeforeUninstallHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Back up the APK and data before uninstalling, so the app can be reinstalled'**
+  String get settingsTrashBeforeUninstallHelp;
+
+  /// No description provided for @settingsTrashRetention.
+  ///
+  /// In en, this message translates to:
+  /// **'Keep Apps in Trash For'**
+  String get settingsTrashRetention;
+
   /// No description provided for @settingsSectionAdb.
   ///
   /// In en, this message translates to:
@@ -308,6 +404,42 @@ abstract class AppLocalizations {
   /// **'Discover devices via mDNS on the local network and attempt \'adb connect\' automatically. Takes effect after restart.'**
   String get settingsMdnsAutoConnectHelp;
 
+  /// No description provided for @settingsReconnectWirelessDevices.
+  ///
+  /// In en, this message translates to:
+  /// **'Reconnect wireless devices'**
+  String get settingsReconnectWirelessDevices;
+
+  /// No description provided for @settingsReconnectWirelessDevicesHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Keep Wi-Fi connections alive and reconnect to the last wireless headset when no device is connected'**
+  String get settingsReconnectWirelessDevicesHelp;
+
+  /// No description provided for @settingsStopForeignAdbServers.
+  ///
+  /// In en, this message translates to:
+  /// **'Stop ADB servers of other apps'**
+  String get settingsStopForeignAdbServers;
+
+  /// No description provided for @settingsStopForeignAdbServersHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Apps bundling their own ADB, such as ALVR, can drop the device connection while both servers run'**
+  String get settingsStopForeignAdbServersHelp;
+
+  /// No description provided for @settingsShellConsole.
+  ///
+  /// In en, this message translates to:
+  /// **'Shell console'**
+  String get settingsShellConsole;
+
+  /// No description provided for @settingsShellConsoleHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Allow running any shell command on the connected device. Careless commands can break the device'**
+  String get settingsShellConsoleHelp;
+
   /// No description provided for @settingsAutoReinstallOnConflict.
   ///
   /// In en, this message translates to:
@@ -320,6 +452,198 @@ abstract class AppLocalizations {
   /// **'Automatically backup data, reinstall, and restore when encountering version conflicts (downgrade or incompatible update). Requires the app to be debuggable.'**
   String get settingsAutoReinstallOnConflictHelp;
 
+  /// No description provided for @settingsAutoApproveInstallScripts.
+  ///
+  /// In en, this message translates to:
+  /// **'Run install scripts without asking'**
+  String get settingsAutoApproveInstallScripts;
+
+  /// No description provided for @settingsAutoApproveInstallScriptsHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Skip the review of the commands an install.txt runs on the device. Scripts from untrusted sources can run any shell command.'**
+  String get settingsAutoApproveInstallScriptsHelp;
+
+  /// No description provided for @settingsWakeOfflineDevices.
+  ///
+  /// In en, this message translates to:
+  /// **'Wake Offline Devices'**
+  String get settingsWakeOfflineDevices;
+
+  /// No description provided for @settingsWakeOfflineDevicesHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'When a headset is plugged in but stays offline (often until it is worn), reconnect it, send the wake action and connect to it.'**
+  String get settingsWakeOfflineDevicesHelp;
+
+  /// No description provided for @settingsDeviceAgent.
+  ///
+  /// In en, this message translates to:
+  /// **'Install device helper'**
+  String get settingsDeviceAgent;
+
+  /// No description provided for @settingsDeviceAgentHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Installs and updates a small helper app on connected headsets for exact app sizes and usage stats. Without it, the usual shell commands are used.'**
+  String get settingsDeviceAgentHelp;
+
+  /// No description provided for @settingsWatchPackageChanges.
+  ///
+  /// In en, this message translates to:
+  /// **'Watch for app changes'**
+  String get settingsWatchPackageChanges;
+
+  /// No description provided for @settingsWatchPackageChangesHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Updates the installed apps list as soon as apps are installed, updated or removed on the headset, including outside of this app. Runs a lightweight check on the headset while connected.'**
+  String get settingsWatchPackageChangesHelp;
+
+  /// No description provided for @settingsProbeWirelessLink.
+  ///
+  /// In en, this message translates to:
+  /// **'Check Wi-Fi speed before large installs'**
+  String get settingsProbeWirelessLink;
+
+  /// No description provided for @settingsProbeWirelessLinkHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Measures the wireless connection before installing large apps over Wi-Fi and warns when it is slow'**
+  String get settingsProbeWirelessLinkHelp;
+
+  /// No description provided for @settingsOfflineWakeAction.
+  ///
+  /// In en, this message translates to:
+  /// **'Wake Action'**
+  String get settingsOfflineWakeAction;
+
+  /// No description provided for @settingsOfflineWakeActionKeyevent.
+  ///
+  /// In en, this message translates to:
+  /// **'Wake key event'**
+  String get settingsOfflineWakeActionKeyevent;
+
+  /// No description provided for @settingsOfflineWakeActionProximity.
+  ///
+  /// In en, this message translates to:
+  /// **'Proximity sensor (as if worn)'**
+  String get settingsOfflineWakeActionProximity;
+
+  /// No description provided for @settingsDeviceReconnectGrace.
+  ///
+  /// In en, this message translates to:
+  /// **'Reconnect grace period'**
+  String get settingsDeviceReconnectGrace;
+
+  /// No description provided for @settingsDeviceReconnectGraceHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'How long a device that briefly drops off (e.g. on sleep and wake) stays connected before it is disconnected'**
+  String get settingsDeviceReconnectGraceHelp;
+
+  /// No description provided for @settingsDeviceReconnectGraceOff.
+  ///
+  /// In en, this message translates to:
+  /// **'Off'**
+  String get settingsDeviceReconnectGraceOff;
+
+  /// No description provided for @settingsCheckForUpdates.
+  ///
+  /// In en, this message translates to:
+  /// **'Check for updates on startup'**
+  String get settingsCheckForUpdates;
+
+  /// No description provided for @settingsLogFileFormat.
+  ///
+  /// In en, this message translates to:
+  /// **'Log file format'**
+  String get settingsLogFileFormat;
+
+  /// No description provided for @settingsLogFileFormatHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'JSON lines files can be ingested by external log tools'**
+  String get settingsLogFileFormatHelp;
+
+  /// No description provided for @settingsLogFileFormatPretty.
+  ///
+  /// In en, this message translates to:
+  /// **'Readable text'**
+  String get settingsLogFileFormatPretty;
+
+  /// No description provided for @settingsLogFileFormatJson.
+  ///
+  /// In en, this message translates to:
+  /// **'JSON lines'**
+  String get settingsLogFileFormatJson;
+
+  /// No description provided for @settingsLogFileFormatBoth.
+  ///
+  /// In en, this message translates to:
+  /// **'Text and JSON lines'**
+  String get settingsLogFileFormatBoth;
+
+  /// No description provided for @settingsSectionTasks.
+  ///
+  /// In en, this message translates to:
+  /// **'Tasks'**
+  String get settingsSectionTasks;
+
+  /// No description provided for @settingsMaxConcurrentDownloads.
+  ///
+  /// In en, this message translates to:
+  /// **'Simultaneous downloads'**
+  String get settingsMaxConcurrentDownloads;
+
+  /// No description provided for @settingsMaxConcurrentAdbOperations.
+  ///
+  /// In en, this message translates to:
+  /// **'Simultaneous device operations'**
+  String get settingsMaxConcurrentAdbOperations;
+
+  /// No description provided for @settingsMaxConcurrentAdbOperationsHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Installs, backups and restores share this limit. Running several at once may slow down or destabilize the device.'**
+  String get settingsMaxConcurrentAdbOperationsHelp;
+
+  /// No description provided for @settingsExtractionThreads.
+  ///
+  /// In en, this message translates to:
+  /// **'Archive extraction threads'**
+  String get settingsExtractionThreads;
+
+  /// No description provided for @settingsExtractionThreadsHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Threads used to unpack zip and 7z archives before installing. More threads finish large archives faster but use more CPU.'**
+  String get settingsExtractionThreadsHelp;
+
+  /// No description provided for @settingsExtractionThreadsAuto.
+  ///
+  /// In en, this message translates to:
+  /// **'Automatic'**
+  String get settingsExtractionThreadsAuto;
+
+  /// No description provided for @settingsTaskKindLimits.
+  ///
+  /// In en, this message translates to:
+  /// **'Limits per task type'**
+  String get settingsTaskKindLimits;
+
+  /// No description provided for @settingsTaskKindLimitsHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Tasks of a type beyond its limit wait until another one finishes'**
+  String get settingsTaskKindLimitsHelp;
+
+  /// No description provided for @settingsTaskKindUnlimited.
+  ///
+  /// In en, this message translates to:
+  /// **'No limit'**
+  String get settingsTaskKindUnlimited;
+
   /// No description provided for @settingsSectionDownloader.
   ///
   /// In en, this message translates to:
@@ -716,6 +1040,90 @@ abstract class AppLocalizations {
   /// **'No remotes found'**
   String get settingsNoRemotesFound;
 
+  /// No description provided for @customRemotes.
+  ///
+  /// In en, this message translates to:
+  /// **'Custom remotes'**
+  String get customRemotes;
+
+  /// No description provided for @customRemotesEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'No custom remotes yet. Added remotes can be selected as the rclone remote.'**
+  String get customRemotesEmpty;
+
+  /// No description provided for @customRemoteAdd.
+  ///
+  /// In en, this message translates to:
+  /// **'Add remote'**
+  String get customRemoteAdd;
+
+  /// No description provided for @customRemoteEdit.
+  ///
+  /// In en, this message translates to:
+  /// **'Edit remote'**
+  String get customRemoteEdit;
+
+  /// No description provided for @customRemoteDelete.
+  ///
+  /// In en, this message translates to:
+  /// **'Delete remote'**
+  String get customRemoteDelete;
+
+  /// No description provided for @customRemoteName.
+  ///
+  /// In en, this message translates to:
+  /// **'Name'**
+  String get customRemoteName;
+
+  /// No description provided for @customRemoteKind.
+  ///
+  /// In en, this message translates to:
+  /// **'Type'**
+  String get customRemoteKind;
+
+  /// No description provided for @customRemoteEndpointUrl.
+  ///
+  /// In en, this message translates to:
+  /// **'URL'**
+  String get customRemoteEndpointUrl;
+
+  /// No description provided for @customRemoteEndpointHost.
+  ///
+  /// In en, this message translates to:
+  /// **'Host'**
+  String get customRemoteEndpointHost;
+
+  /// No description provided for @customRemoteUsername.
+  ///
+  /// In en, this message translates to:
+  /// **'User name or access key ID'**
+  String get customRemoteUsername;
+
+  /// No description provided for @customRemotePassword.
+  ///
+  /// In en, this message translates to:
+  /// **'Password or secret key'**
+  String get customRemotePassword;
+
+  /// No description provided for @customRemoteKeepPassword.
+  ///
+  /// In en, this message translates to:
+  /// **'Leave empty to keep the saved password'**
+  String get customRemoteKeepPassword;
+
+  /// No description provided for @customRemoteSave.
+  ///
+  /// In en, this message translates to:
+  /// **'Save'**
+  String get customRemoteSave;
+
+  /// No description provided for @customRemoteSaveFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to save remote: {error}'**
+  String customRemoteSaveFailed(String error);
+
   /// No description provided for @settingsFailedToLoadRemotes.
   ///
   /// In en, this message translates to:
@@ -734,12 +1142,90 @@ abstract class AppLocalizations {
   /// **'Value in KiB/s or with B|K|M|G|T|P suffix or more (click for documentation)'**
   String get settingsBandwidthHelper;
 
+  /// No description provided for @settingsDownloadMirrors.
+  ///
+  /// In en, this message translates to:
+  /// **'Fallback mirrors'**
+  String get settingsDownloadMirrors;
+
+  /// No description provided for @settingsDownloadMirrorsRemotesHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Comma-separated remotes tried in order when a download fails or stays slow'**
+  String get settingsDownloadMirrorsRemotesHelp;
+
+  /// No description provided for @settingsDownloadMirrorsUrlsHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Comma-separated base URLs tried in order when a download fails or stays slow'**
+  String get settingsDownloadMirrorsUrlsHelp;
+
+  /// No description provided for @settingsMirrorMinSpeed.
+  ///
+  /// In en, this message translates to:
+  /// **'Switch mirrors below'**
+  String get settingsMirrorMinSpeed;
+
+  /// No description provided for @settingsMirrorMinSpeedOff.
+  ///
+  /// In en, this message translates to:
+  /// **'Only on failure'**
+  String get settingsMirrorMinSpeedOff;
+
+  /// No description provided for @settingsMirrorSpeedKbps.
+  ///
+  /// In en, this message translates to:
+  /// **'{speed} KB/s'**
+  String settingsMirrorSpeedKbps(String speed);
+
+  /// No description provided for @settingsMirrorSlowFor.
+  ///
+  /// In en, this message translates to:
+  /// **'Slow for at least'**
+  String get settingsMirrorSlowFor;
+
+  /// No description provided for @settingsMirrorSlowSeconds.
+  ///
+  /// In en, this message translates to:
+  /// **'{seconds} s'**
+  String settingsMirrorSlowSeconds(String seconds);
+
   /// No description provided for @settingsDownloadsCleanup.
   ///
   /// In en, this message translates to:
   /// **'Downloads Cleanup'**
   String get settingsDownloadsCleanup;
 
+  /// No description provided for @settingsCastingDownloads.
+  ///
+  /// In en, this message translates to:
+  /// **'Downloads While Casting'**
+  String get settingsCastingDownloads;
+
+  /// No description provided for @settingsCastingDownloadsIgnore.
+  ///
+  /// In en, this message translates to:
+  /// **'Keep downloading'**
+  String get settingsCastingDownloadsIgnore;
+
+  /// No description provided for @settingsCastingDownloadsThrottle.
+  ///
+  /// In en, this message translates to:
+  /// **'Limit speed'**
+  String get settingsCastingDownloadsThrottle;
+
+  /// No description provided for @settingsCastingDownloadsPause.
+  ///
+  /// In en, this message translates to:
+  /// **'Pause downloads'**
+  String get settingsCastingDownloadsPause;
+
+  /// No description provided for @settingsCastingBandwidthLimit.
+  ///
+  /// In en, this message translates to:
+  /// **'Download Limit While Casting'**
+  String get settingsCastingBandwidthLimit;
+
   /// No description provided for @settingsDownloadMode.
   ///
   /// In en, this message translates to:
@@ -776,6 +1262,18 @@ abstract class AppLocalizations {
   /// **'Write release.json in legacy format for compatibility with QLoader'**
   String get settingsWriteLegacyReleaseJsonHelp;
 
+  /// No description provided for @settingsDedupeDownloads.
+  ///
+  /// In en, this message translates to:
+  /// **'Deduplicate downloads'**
+  String get settingsDedupeDownloads;
+
+  /// No description provided for @settingsDedupeDownloadsHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Link large files shared by several downloaded versions to a single copy on disk'**
+  String get settingsDedupeDownloadsHelp;
+
   /// No description provided for @settingsCleanupDeleteAfterInstall.
   ///
   /// In en, this message translates to:
@@ -800,6 +1298,18 @@ abstract class AppLocalizations {
   /// **'Keep all versions'**
   String get settingsCleanupKeepAllVersions;
 
+  /// No description provided for @settingsCleanupMoveAfterInstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Move to another folder after installation'**
+  String get settingsCleanupMoveAfterInstall;
+
+  /// No description provided for @settingsInstalledDownloadsLocation.
+  ///
+  /// In en, this message translates to:
+  /// **'Installed downloads location'**
+  String get settingsInstalledDownloadsLocation;
+
   /// No description provided for @settingsBrowse.
   ///
   /// In en, this message translates to:
@@ -878,6 +1388,48 @@ abstract class AppLocalizations {
   /// **'Offline'**
   String get statusAdbStateOffline;
 
+  /// No description provided for @statusAdbWakeDevice.
+  ///
+  /// In en, this message translates to:
+  /// **'Select to try waking it'**
+  String get statusAdbWakeDevice;
+
+  /// No description provided for @wakeStepReconnect.
+  ///
+  /// In en, this message translates to:
+  /// **'Reconnect offline devices'**
+  String get wakeStepReconnect;
+
+  /// No description provided for @wakeStepKeyevent.
+  ///
+  /// In en, this message translates to:
+  /// **'Send wake key event'**
+  String get wakeStepKeyevent;
+
+  /// No description provided for @wakeStepProximity.
+  ///
+  /// In en, this message translates to:
+  /// **'Send proximity sensor broadcast'**
+  String get wakeStepProximity;
+
+  /// No description provided for @wakeStepConnect.
+  ///
+  /// In en, this message translates to:
+  /// **'Connect'**
+  String get wakeStepConnect;
+
+  /// No description provided for @deviceWakeRecovered.
+  ///
+  /// In en, this message translates to:
+  /// **'{device} is back online'**
+  String deviceWakeRecovered(String device);
+
+  /// No description provided for @deviceWakeFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Could not wake {device}'**
+  String deviceWakeFailed(String device);
+
   /// No description provided for @statusAdbStateBootloader.
   ///
   /// In en, this message translates to:
@@ -1019,7 +1571,7 @@ abstract class AppLocalizations {
   /// No description provided for @dragDropInvalidFile.
   ///
   /// In en, this message translates to:
-  /// **'Dropped file is not a valid APK file'**
+  /// **'Dropped file is not a valid APK file or bundle'**
   String get dragDropInvalidFile;
 
   /// No description provided for @dragDropDownloaderConfigTitle.
@@ -1136,6 +1688,24 @@ abstract class AppLocalizations {
   /// **'Failed to obtain battery state dump'**
   String get batteryDumpFailed;
 
+  /// No description provided for @batteryHistoryTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Battery history'**
+  String get batteryHistoryTitle;
+
+  /// No description provided for @batteryHistoryEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'Battery levels are sampled every minute while the device is connected. Check back in a few minutes.'**
+  String get batteryHistoryEmpty;
+
+  /// No description provided for @batteryHistoryHint.
+  ///
+  /// In en, this message translates to:
+  /// **'Click to copy the battery dump, hold to show the battery history'**
+  String get batteryHistoryHint;
+
   /// No description provided for @commonSuccess.
   ///
   /// In en, this message translates to:
@@ -1160,6 +1730,42 @@ abstract class AppLocalizations {
   /// **'ADB server'**
   String get diagnosticsAdbServer;
 
+  /// No description provided for @diagnosticsAdbHealth.
+  ///
+  /// In en, this message translates to:
+  /// **'ADB watchdog'**
+  String get diagnosticsAdbHealth;
+
+  /// No description provided for @diagnosticsAdbHealthy.
+  ///
+  /// In en, this message translates to:
+  /// **'ADB server and device tracking are responding'**
+  String get diagnosticsAdbHealthy;
+
+  /// No description provided for @diagnosticsAdbUnresponsive.
+  ///
+  /// In en, this message translates to:
+  /// **'ADB server is not responding, it will be restarted if this continues'**
+  String get diagnosticsAdbUnresponsive;
+
+  /// No description provided for @diagnosticsAdbRecovering.
+  ///
+  /// In en, this message translates to:
+  /// **'Restarted the ADB server (attempt {attempt})'**
+  String diagnosticsAdbRecovering(String attempt);
+
+  /// No description provided for @diagnosticsAdbRecoveryFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to restart the ADB server: {error}'**
+  String diagnosticsAdbRecoveryFailed(String error);
+
+  /// No description provided for @diagnosticsAdbRetryIn.
+  ///
+  /// In en, this message translates to:
+  /// **'Next attempt in {seconds} s'**
+  String diagnosticsAdbRetryIn(String seconds);
+
   /// No description provided for @diagnosticsDevices.
   ///
   /// In en, this message translates to:
@@ -1178,6 +1784,36 @@ abstract class AppLocalizations {
   /// **'Active device'**
   String get diagnosticsActiveDevice;
 
+  /// No description provided for @diagnosticsHostConflicts.
+  ///
+  /// In en, this message translates to:
+  /// **'Streaming apps'**
+  String get diagnosticsHostConflicts;
+
+  /// No description provided for @diagnosticsHostConflictsNone.
+  ///
+  /// In en, this message translates to:
+  /// **'No streaming apps or other ADB servers running'**
+  String get diagnosticsHostConflictsNone;
+
+  /// No description provided for @diagnosticsStreamingClientsRunning.
+  ///
+  /// In en, this message translates to:
+  /// **'{apps} running. Streaming shares the USB connection, so transfers may be slower or get interrupted.'**
+  String diagnosticsStreamingClientsRunning(String apps);
+
+  /// No description provided for @diagnosticsForeignAdbServers.
+  ///
+  /// In en, this message translates to:
+  /// **'ADB servers of other apps are running and may drop the device connection:'**
+  String get diagnosticsForeignAdbServers;
+
+  /// No description provided for @diagnosticsStopForeignAdbServers.
+  ///
+  /// In en, this message translates to:
+  /// **'Stop other ADB servers'**
+  String get diagnosticsStopForeignAdbServers;
+
   /// No description provided for @diagnosticsAdbPath.
   ///
   /// In en, this message translates to:
@@ -1322,371 +1958,1451 @@ abstract class AppLocalizations {
   /// **'Active'**
   String get guardianStatusActive;
 
-  /// No description provided for @deviceWirelessAdb.
+  /// No description provided for @guardianPauseFor.
   ///
   /// In en, this message translates to:
-  /// **'Wireless ADB'**
-  String get deviceWirelessAdb;
+  /// **'Pause for a while'**
+  String get guardianPauseFor;
 
-  /// No description provided for @deviceEnableWirelessAdb.
+  /// No description provided for @guardianPause15m.
   ///
   /// In en, this message translates to:
-  /// **'Enable ADB over Wi‑Fi'**
-  String get deviceEnableWirelessAdb;
+  /// **'Pause for 15 minutes'**
+  String get guardianPause15m;
 
-  /// No description provided for @deviceStorageConnection.
+  /// No description provided for @guardianPause30m.
   ///
   /// In en, this message translates to:
-  /// **'Storage connection'**
-  String get deviceStorageConnection;
+  /// **'Pause for 30 minutes'**
+  String get guardianPause30m;
 
-  /// No description provided for @deviceStorageConnectionTooltip.
+  /// No description provided for @guardianPause1h.
   ///
   /// In en, this message translates to:
-  /// **'Connect storage with MTP.'**
-  String get deviceStorageConnectionTooltip;
+  /// **'Pause for 1 hour'**
+  String get guardianPause1h;
 
-  /// No description provided for @deviceStorageResetTooltip.
+  /// No description provided for @guardianPause2h.
   ///
   /// In en, this message translates to:
-  /// **'Reset USB functions.'**
-  String get deviceStorageResetTooltip;
+  /// **'Pause for 2 hours'**
+  String get guardianPause2h;
 
-  /// No description provided for @deviceStorageStatusConnected.
+  /// No description provided for @autoRevertIn.
   ///
   /// In en, this message translates to:
-  /// **'Connected'**
-  String get deviceStorageStatusConnected;
+  /// **'Restores in {time}'**
+  String autoRevertIn(String time);
 
-  /// No description provided for @deviceStorageStatusDisconnected.
+  /// No description provided for @deviceWirelessAdb.
   ///
   /// In en, this message translates to:
-  /// **'Disconnected'**
-  String get deviceStorageStatusDisconnected;
+  /// **'Wireless ADB'**
+  String get deviceWirelessAdb;
 
-  /// No description provided for @deviceStorageStatusUnknown.
+  /// No description provided for @deviceMaintenanceLog.
   ///
   /// In en, this message translates to:
-  /// **'Unknown'**
-  String get deviceStorageStatusUnknown;
+  /// **'Maintenance log'**
+  String get deviceMaintenanceLog;
 
-  /// No description provided for @deviceStorageConnectedMessage.
+  /// No description provided for @deviceTweaks.
   ///
   /// In en, this message translates to:
-  /// **'MTP is enabled. The USB connection will restart.'**
-  String get deviceStorageConnectedMessage;
+  /// **'Rendering tweaks'**
+  String get deviceTweaks;
 
-  /// No description provided for @deviceStorageResetMessage.
+  /// No description provided for @deviceTweaksHint.
   ///
   /// In en, this message translates to:
-  /// **'USB functions were reset. The USB connection will restart.'**
-  String get deviceStorageResetMessage;
+  /// **'Changes apply to apps started afterwards and reset when the headset reboots.'**
+  String get deviceTweaksHint;
 
-  /// No description provided for @deviceStorageConnectionFailed.
+  /// No description provided for @deviceTweakDefault.
   ///
   /// In en, this message translates to:
-  /// **'Failed to update the storage connection.'**
-  String get deviceStorageConnectionFailed;
+  /// **'Default'**
+  String get deviceTweakDefault;
 
-  /// No description provided for @usbSpeedLabel.
+  /// No description provided for @deviceTweakRefreshRate.
   ///
   /// In en, this message translates to:
-  /// **'USB speed'**
-  String get usbSpeedLabel;
+  /// **'Refresh rate'**
+  String get deviceTweakRefreshRate;
 
-  /// No description provided for @copiedToClipboard.
+  /// No description provided for @deviceTweakCpuLevel.
   ///
   /// In en, this message translates to:
-  /// **'Copied to clipboard'**
-  String get copiedToClipboard;
+  /// **'CPU level'**
+  String get deviceTweakCpuLevel;
 
-  /// No description provided for @clickToCopy.
+  /// No description provided for @deviceTweakGpuLevel.
   ///
   /// In en, this message translates to:
-  /// **'Click to copy'**
-  String get clickToCopy;
+  /// **'GPU level'**
+  String get deviceTweakGpuLevel;
 
-  /// No description provided for @detailsPackageName.
+  /// No description provided for @deviceTweakTextureSize.
   ///
   /// In en, this message translates to:
-  /// **'Package Name:'**
-  String get detailsPackageName;
+  /// **'Texture size'**
+  String get deviceTweakTextureSize;
 
-  /// No description provided for @detailsVersion.
+  /// No description provided for @deviceTweakFoveation.
   ///
   /// In en, this message translates to:
-  /// **'Version:'**
-  String get detailsVersion;
+  /// **'Fixed foveation'**
+  String get deviceTweakFoveation;
 
-  /// No description provided for @detailsVersionCode.
+  /// No description provided for @commandMacros.
   ///
   /// In en, this message translates to:
-  /// **'Version Code:'**
-  String get detailsVersionCode;
+  /// **'Command macros'**
+  String get commandMacros;
 
-  /// No description provided for @detailsIsVr.
+  /// No description provided for @shellConsole.
   ///
   /// In en, this message translates to:
-  /// **'Is VR:'**
-  String get detailsIsVr;
+  /// **'Shell console'**
+  String get shellConsole;
 
-  /// No description provided for @detailsIsLaunchable.
+  /// No description provided for @shellConsoleTitle.
   ///
   /// In en, this message translates to:
-  /// **'Is Launchable:'**
-  String get detailsIsLaunchable;
+  /// **'Shell on {serial}'**
+  String shellConsoleTitle(String serial);
 
-  /// No description provided for @detailsIsSystem.
+  /// No description provided for @shellConsoleOpening.
   ///
   /// In en, this message translates to:
-  /// **'Is System:'**
-  String get detailsIsSystem;
+  /// **'Opening shell…'**
+  String get shellConsoleOpening;
 
-  /// No description provided for @detailsStorageUsage.
+  /// No description provided for @shellConsoleHint.
   ///
   /// In en, this message translates to:
-  /// **'Storage Usage:'**
-  String get detailsStorageUsage;
+  /// **'Enter a command'**
+  String get shellConsoleHint;
 
-  /// No description provided for @detailsApp.
+  /// No description provided for @shellConsoleExitCode.
   ///
   /// In en, this message translates to:
-  /// **'App:'**
-  String get detailsApp;
+  /// **'Exit code {code}'**
+  String shellConsoleExitCode(String code);
 
-  /// No description provided for @detailsData.
+  /// No description provided for @shellConsoleEndClosed.
   ///
   /// In en, this message translates to:
-  /// **'Data:'**
-  String get detailsData;
+  /// **'Session closed'**
+  String get shellConsoleEndClosed;
 
-  /// No description provided for @detailsCache.
+  /// No description provided for @shellConsoleEndExited.
   ///
   /// In en, this message translates to:
-  /// **'Cache:'**
-  String get detailsCache;
+  /// **'Shell exited'**
+  String get shellConsoleEndExited;
 
-  /// No description provided for @detailsTotal.
+  /// No description provided for @shellConsoleEndTimedOut.
   ///
   /// In en, this message translates to:
-  /// **'Total:'**
-  String get detailsTotal;
+  /// **'Command timed out, session closed'**
+  String get shellConsoleEndTimedOut;
 
-  /// No description provided for @detailsRating.
+  /// No description provided for @shellConsoleEndIdle.
   ///
   /// In en, this message translates to:
-  /// **'Rating:'**
-  String get detailsRating;
+  /// **'Session closed after inactivity'**
+  String get shellConsoleEndIdle;
 
-  /// No description provided for @detailsReviewsTitle.
+  /// No description provided for @shellConsoleEndDisabled.
   ///
   /// In en, this message translates to:
-  /// **'Recent reviews'**
-  String get detailsReviewsTitle;
+  /// **'Shell console was disabled in settings'**
+  String get shellConsoleEndDisabled;
 
-  /// No description provided for @detailsReviewsUnavailable.
+  /// No description provided for @shellConsoleReopen.
   ///
   /// In en, this message translates to:
-  /// **'Reviews are unavailable for this app.'**
-  String get detailsReviewsUnavailable;
+  /// **'Reopen'**
+  String get shellConsoleReopen;
 
-  /// No description provided for @detailsReviewsError.
+  /// No description provided for @shellConsoleClear.
   ///
   /// In en, this message translates to:
-  /// **'Failed to load reviews.'**
-  String get detailsReviewsError;
+  /// **'Clear'**
+  String get shellConsoleClear;
 
-  /// No description provided for @detailsReviewsEmpty.
+  /// No description provided for @diskUsage.
   ///
   /// In en, this message translates to:
-  /// **'No reviews available yet.'**
-  String get detailsReviewsEmpty;
+  /// **'Disk usage'**
+  String get diskUsage;
 
-  /// No description provided for @detailsDeveloperResponse.
+  /// No description provided for @diskUsageDownloads.
   ///
   /// In en, this message translates to:
-  /// **'Developer response'**
-  String get detailsDeveloperResponse;
+  /// **'Downloads'**
+  String get diskUsageDownloads;
 
-  /// No description provided for @detailsReviewHelpful.
+  /// No description provided for @diskUsageMediaCache.
   ///
   /// In en, this message translates to:
-  /// **'Helpful'**
-  String get detailsReviewHelpful;
+  /// **'Media cache'**
+  String get diskUsageMediaCache;
 
-  /// No description provided for @detailsReviewHelpfulCount.
+  /// No description provided for @diskUsageLogs.
   ///
   /// In en, this message translates to:
-  /// **'{count, plural, one {{count} person found this helpful} other {{count} people found this helpful}}'**
-  String detailsReviewHelpfulCount(int count);
+  /// **'Logs'**
+  String get diskUsageLogs;
 
-  /// No description provided for @reviewsSortBy.
+  /// No description provided for @diskUsageBackups.
   ///
   /// In en, this message translates to:
-  /// **'Sort by'**
-  String get reviewsSortBy;
+  /// **'Backups'**
+  String get diskUsageBackups;
 
-  /// No description provided for @reviewsSortHelpful.
+  /// No description provided for @diskUsageTrash.
   ///
   /// In en, this message translates to:
-  /// **'Most helpful'**
-  String get reviewsSortHelpful;
+  /// **'Trash'**
+  String get diskUsageTrash;
 
-  /// No description provided for @reviewsSortNewest.
+  /// No description provided for @diskUsageDonationUploads.
   ///
   /// In en, this message translates to:
-  /// **'Newest'**
-  String get reviewsSortNewest;
+  /// **'Donation uploads'**
+  String get diskUsageDonationUploads;
 
-  /// No description provided for @previous.
+  /// No description provided for @diskUsageTotal.
   ///
   /// In en, this message translates to:
-  /// **'Previous'**
-  String get previous;
+  /// **'Total'**
+  String get diskUsageTotal;
 
-  /// No description provided for @next.
+  /// No description provided for @diskUsageClear.
   ///
   /// In en, this message translates to:
-  /// **'Next'**
-  String get next;
+  /// **'Clear'**
+  String get diskUsageClear;
 
-  /// No description provided for @reviewsReadAll.
+  /// No description provided for @diskUsageClearTitle.
   ///
   /// In en, this message translates to:
-  /// **'Read all reviews'**
-  String get reviewsReadAll;
+  /// **'Clear {category}?'**
+  String diskUsageClearTitle(String category);
 
-  /// No description provided for @commonCopy.
+  /// No description provided for @diskUsageClearPrompt.
   ///
   /// In en, this message translates to:
-  /// **'Copy'**
-  String get commonCopy;
+  /// **'Delete {size} of {category} in {path}? This cannot be undone.'**
+  String diskUsageClearPrompt(String category, String size, String path);
 
-  /// No description provided for @commonClose.
+  /// No description provided for @storageMigrationTitle.
   ///
   /// In en, this message translates to:
-  /// **'Close'**
-  String get commonClose;
+  /// **'Move storage'**
+  String get storageMigrationTitle;
 
-  /// No description provided for @commonCancel.
+  /// No description provided for @storageMigrationMoveDownloads.
   ///
   /// In en, this message translates to:
-  /// **'Cancel'**
-  String get commonCancel;
+  /// **'Move downloads…'**
+  String get storageMigrationMoveDownloads;
 
-  /// No description provided for @commonDownload.
+  /// No description provided for @storageMigrationMoveBackups.
   ///
   /// In en, this message translates to:
-  /// **'Download'**
-  String get commonDownload;
+  /// **'Move backups…'**
+  String get storageMigrationMoveBackups;
 
-  /// No description provided for @availableVersions.
+  /// No description provided for @storageMigrationMoving.
   ///
   /// In en, this message translates to:
-  /// **'Available Versions'**
-  String get availableVersions;
+  /// **'Moving files to {path}. Keep YAAS open until this finishes.'**
+  String storageMigrationMoving(String path);
 
-  /// No description provided for @installNewerVersion.
+  /// No description provided for @storageMigrationEntry.
   ///
   /// In en, this message translates to:
-  /// **'Install newer version'**
-  String get installNewerVersion;
+  /// **'{current} of {total}: {name}'**
+  String storageMigrationEntry(String current, String total, String name);
 
-  /// No description provided for @reinstallThisVersion.
+  /// No description provided for @storageMigrationDone.
   ///
   /// In en, this message translates to:
-  /// **'Reinstall this version'**
-  String get reinstallThisVersion;
+  /// **'All files were moved. The location is now {path}.'**
+  String storageMigrationDone(String path);
 
-  /// No description provided for @holdShiftToReinstall.
+  /// No description provided for @storageMigrationRolledBack.
   ///
   /// In en, this message translates to:
-  /// **'Hold Shift to reinstall this version'**
-  String get holdShiftToReinstall;
+  /// **'Files moved so far were returned. The location is still {path}.'**
+  String storageMigrationRolledBack(String path);
 
-  /// No description provided for @cannotDowngrade.
+  /// No description provided for @storageMigrationUnchanged.
   ///
   /// In en, this message translates to:
-  /// **'Cannot downgrade to older version'**
-  String get cannotDowngrade;
+  /// **'The location is still {path}.'**
+  String storageMigrationUnchanged(String path);
 
-  /// No description provided for @newerVersion.
+  /// No description provided for @settingsProfiles.
   ///
   /// In en, this message translates to:
-  /// **'Newer version'**
-  String get newerVersion;
+  /// **'Settings profiles'**
+  String get settingsProfiles;
 
-  /// No description provided for @sameVersion.
+  /// No description provided for @settingsProfilesEmpty.
   ///
   /// In en, this message translates to:
-  /// **'Same version'**
-  String get sameVersion;
+  /// **'No profiles yet. Save the current settings as a profile to switch back to them later.'**
+  String get settingsProfilesEmpty;
 
-  /// No description provided for @olderVersion.
+  /// No description provided for @settingsProfilesName.
   ///
   /// In en, this message translates to:
-  /// **'Older version'**
-  String get olderVersion;
+  /// **'Profile name'**
+  String get settingsProfilesName;
 
-  /// No description provided for @update.
+  /// No description provided for @settingsProfilesSaveCurrent.
   ///
   /// In en, this message translates to:
-  /// **'Update'**
-  String get update;
+  /// **'Save current'**
+  String get settingsProfilesSaveCurrent;
 
-  /// No description provided for @install.
+  /// No description provided for @settingsProfilesActive.
   ///
   /// In en, this message translates to:
-  /// **'Install'**
-  String get install;
+  /// **'Active'**
+  String get settingsProfilesActive;
 
-  /// No description provided for @checkForUpdates.
+  /// No description provided for @settingsProfilesOverwrite.
   ///
   /// In en, this message translates to:
-  /// **'Check for updates'**
-  String get checkForUpdates;
+  /// **'Replace with current settings'**
+  String get settingsProfilesOverwrite;
 
-  /// No description provided for @noMatchingCloudApp.
+  /// No description provided for @settingsProfilesDelete.
   ///
   /// In en, this message translates to:
-  /// **'No matching app found in cloud repository'**
-  String get noMatchingCloudApp;
+  /// **'Delete'**
+  String get settingsProfilesDelete;
 
-  /// No description provided for @updateTo.
+  /// No description provided for @settingsProfilesDeleteTitle.
   ///
   /// In en, this message translates to:
-  /// **'Update to {to}'**
-  String updateTo(String to);
+  /// **'Delete profile "{name}"?'**
+  String settingsProfilesDeleteTitle(String name);
 
-  /// No description provided for @downgradeAppTitle.
+  /// No description provided for @settingsCatalogBaseUrl.
   ///
   /// In en, this message translates to:
-  /// **'Downgrade App'**
-  String get downgradeAppTitle;
+  /// **'Catalog URL'**
+  String get settingsCatalogBaseUrl;
 
-  /// No description provided for @downgradeConfirmMessage.
+  /// No description provided for @settingsCatalogBaseUrlHelp.
   ///
   /// In en, this message translates to:
-  /// **'Attempt to downgrade to version {versionCode}? This may cause issues.'**
-  String downgradeConfirmMessage(String versionCode);
+  /// **'Base URL of a self-hosted mirror or LAN cache. Leave empty to use the configured source.'**
+  String get settingsCatalogBaseUrlHelp;
 
-  /// No description provided for @holdShiftToDowngrade.
+  /// No description provided for @settingsMediaBaseUrl.
   ///
   /// In en, this message translates to:
-  /// **'Hold Shift to downgrade to {to}'**
-  String holdShiftToDowngrade(String to);
+  /// **'Media URL'**
+  String get settingsMediaBaseUrl;
 
-  /// No description provided for @downgradeToThisVersion.
+  /// No description provided for @settingsMediaBaseUrlHelp.
   ///
   /// In en, this message translates to:
-  /// **'Downgrade to this version'**
-  String get downgradeToThisVersion;
+  /// **'Server thumbnails and trailers are loaded from. Leave empty to use the default one.'**
+  String get settingsMediaBaseUrlHelp;
 
-  /// No description provided for @downgradeTo.
+  /// No description provided for @settingsPinnedSpki.
   ///
   /// In en, this message translates to:
-  /// **'Downgrade to {to}'**
-  String downgradeTo(String to);
+  /// **'Pinned server keys'**
+  String get settingsPinnedSpki;
+
+  /// No description provided for @settingsPinnedSpkiHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Comma-separated sha256/<base64> SPKI hashes. When set, the self-hosted catalog and media servers must use https and present one of these keys.'**
+  String get settingsPinnedSpkiHelp;
+
+  /// No description provided for @settingsTestConnection.
+  ///
+  /// In en, this message translates to:
+  /// **'Test connection'**
+  String get settingsTestConnection;
+
+  /// No description provided for @settingsTestConnectionOk.
+  ///
+  /// In en, this message translates to:
+  /// **'Server is reachable'**
+  String get settingsTestConnectionOk;
+
+  /// No description provided for @settingsTestConnectionDetails.
+  ///
+  /// In en, this message translates to:
+  /// **'HTTP {status} in {ms} ms'**
+  String settingsTestConnectionDetails(String status, String ms);
+
+  /// No description provided for @settingsTestConnectionTimeout.
+  ///
+  /// In en, this message translates to:
+  /// **'No answer from the server'**
+  String get settingsTestConnectionTimeout;
+
+  /// No description provided for @lanShareTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Local network sharing'**
+  String get lanShareTitle;
+
+  /// No description provided for @lanShareApp.
+  ///
+  /// In en, this message translates to:
+  /// **'Share on local network'**
+  String get lanShareApp;
+
+  /// No description provided for @lanShareOwn.
+  ///
+  /// In en, this message translates to:
+  /// **'Shared by you'**
+  String get lanShareOwn;
+
+  /// No description provided for @lanShareOwnEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'Nothing shared yet. Use the share button on a download to offer it to others on your network.'**
+  String get lanShareOwnEmpty;
+
+  /// No description provided for @lanShareStop.
+  ///
+  /// In en, this message translates to:
+  /// **'Stop sharing'**
+  String get lanShareStop;
+
+  /// No description provided for @lanSharePeers.
+  ///
+  /// In en, this message translates to:
+  /// **'Shared by others'**
+  String get lanSharePeers;
+
+  /// No description provided for @lanSharePeersEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'Looking for other YAAS instances on your network...'**
+  String get lanSharePeersEmpty;
+
+  /// No description provided for @lanShareReceive.
+  ///
+  /// In en, this message translates to:
+  /// **'Receive'**
+  String get lanShareReceive;
+
+  /// No description provided for @lanShareQueued.
+  ///
+  /// In en, this message translates to:
+  /// **'Queued'**
+  String get lanShareQueued;
+
+  /// No description provided for @transferAppData.
+  ///
+  /// In en, this message translates to:
+  /// **'Transfer to another headset'**
+  String get transferAppData;
+
+  /// No description provided for @transferAppDataTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Transfer {name}'**
+  String transferAppDataTitle(String name);
+
+  /// No description provided for @transferAppDataDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'The app is backed up with its APK, data and OBB files now. The backup is restored once the selected headset is the connected device, so you can switch headsets after the backup finishes.'**
+  String get transferAppDataDescription;
+
+  /// No description provided for @transferAppDataNoTargets.
+  ///
+  /// In en, this message translates to:
+  /// **'No other headsets found. Connect the target headset to this computer first.'**
+  String get transferAppDataNoTargets;
+
+  /// No description provided for @transferAppDataConflictWarning.
+  ///
+  /// In en, this message translates to:
+  /// **'If the app is already installed on the target headset, you are asked before its data there is replaced, and that data is backed up first. A newer installed version is kept and only the data is restored.'**
+  String get transferAppDataConflictWarning;
+
+  /// No description provided for @transferOverwriteTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Replace data on {target}?'**
+  String transferOverwriteTitle(String target);
+
+  /// No description provided for @transferOverwritePrompt.
+  ///
+  /// In en, this message translates to:
+  /// **'{app} is already installed on {target}. Its current data there is backed up first, then replaced with the transferred data.'**
+  String transferOverwritePrompt(String app, String target);
+
+  /// No description provided for @transferOverwriteReplace.
+  ///
+  /// In en, this message translates to:
+  /// **'Replace'**
+  String get transferOverwriteReplace;
+
+  /// No description provided for @commandMacrosEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'No macros yet. A macro runs several device commands in a row.'**
+  String get commandMacrosEmpty;
+
+  /// No description provided for @commandMacroNew.
+  ///
+  /// In en, this message translates to:
+  /// **'New macro'**
+  String get commandMacroNew;
+
+  /// No description provided for @commandMacroEdit.
+  ///
+  /// In en, this message translates to:
+  /// **'Edit macro'**
+  String get commandMacroEdit;
+
+  /// No description provided for @commandMacroName.
+  ///
+  /// In en, this message translates to:
+  /// **'Name'**
+  String get commandMacroName;
+
+  /// No description provided for @commandMacroNameTaken.
+  ///
+  /// In en, this message translates to:
+  /// **'A macro with this name already exists'**
+  String get commandMacroNameTaken;
+
+  /// No description provided for @commandMacroRun.
+  ///
+  /// In en, this message translates to:
+  /// **'Run'**
+  String get commandMacroRun;
+
+  /// No description provided for @commandMacroStop.
+  ///
+  /// In en, this message translates to:
+  /// **'Stop'**
+  String get commandMacroStop;
+
+  /// No description provided for @commandMacroDelete.
+  ///
+  /// In en, this message translates to:
+  /// **'Delete macro'**
+  String get commandMacroDelete;
+
+  /// No description provided for @commandMacroSave.
+  ///
+  /// In en, this message translates to:
+  /// **'Save'**
+  String get commandMacroSave;
+
+  /// No description provided for @commandMacroAddStep.
+  ///
+  /// In en, this message translates to:
+  /// **'Add step'**
+  String get commandMacroAddStep;
+
+  /// No description provided for @commandMacroNoSteps.
+  ///
+  /// In en, this message translates to:
+  /// **'Add at least one step.'**
+  String get commandMacroNoSteps;
+
+  /// No description provided for @commandMacroStepCount.
+  ///
+  /// In en, this message translates to:
+  /// **'Steps: {count}'**
+  String commandMacroStepCount(String count);
+
+  /// No description provided for @commandMacroFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Macro failed: {error}'**
+  String commandMacroFailed(String error);
+
+  /// No description provided for @commandMacroPackageName.
+  ///
+  /// In en, this message translates to:
+  /// **'Package name'**
+  String get commandMacroPackageName;
+
+  /// No description provided for @commandMacroDelaySeconds.
+  ///
+  /// In en, this message translates to:
+  /// **'Delay in seconds'**
+  String get commandMacroDelaySeconds;
+
+  /// No description provided for @commandMacroStepLaunch.
+  ///
+  /// In en, this message translates to:
+  /// **'Launch {package}'**
+  String commandMacroStepLaunch(String package);
+
+  /// No description provided for @commandMacroStepForceStop.
+  ///
+  /// In en, this message translates to:
+  /// **'Force stop {package}'**
+  String commandMacroStepForceStop(String package);
+
+  /// No description provided for @commandMacroStepDelay.
+  ///
+  /// In en, this message translates to:
+  /// **'Wait {seconds} s'**
+  String commandMacroStepDelay(String seconds);
+
+  /// No description provided for @commandMacroStepGuardianPause.
+  ///
+  /// In en, this message translates to:
+  /// **'Pause Guardian'**
+  String get commandMacroStepGuardianPause;
+
+  /// No description provided for @commandMacroStepGuardianResume.
+  ///
+  /// In en, this message translates to:
+  /// **'Resume Guardian'**
+  String get commandMacroStepGuardianResume;
+
+  /// No description provided for @commandMacroStepProximityOff.
+  ///
+  /// In en, this message translates to:
+  /// **'Disable proximity sensor'**
+  String get commandMacroStepProximityOff;
+
+  /// No description provided for @commandMacroStepProximityOn.
+  ///
+  /// In en, this message translates to:
+  /// **'Enable proximity sensor'**
+  String get commandMacroStepProximityOn;
+
+  /// No description provided for @commandMacroStepRefresh.
+  ///
+  /// In en, this message translates to:
+  /// **'Refresh device'**
+  String get commandMacroStepRefresh;
+
+  /// No description provided for @commandMacroStepReboot.
+  ///
+  /// In en, this message translates to:
+  /// **'Reboot'**
+  String get commandMacroStepReboot;
+
+  /// No description provided for @commandMacroStepOther.
+  ///
+  /// In en, this message translates to:
+  /// **'Run {command}'**
+  String commandMacroStepOther(String command);
+
+  /// No description provided for @deviceAgentNotInstalled.
+  ///
+  /// In en, this message translates to:
+  /// **'Device helper: not installed'**
+  String get deviceAgentNotInstalled;
+
+  /// No description provided for @deviceAgentInstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Install device helper'**
+  String get deviceAgentInstall;
+
+  /// No description provided for @deviceAgentUpdate.
+  ///
+  /// In en, this message translates to:
+  /// **'Update device helper'**
+  String get deviceAgentUpdate;
+
+  /// No description provided for @deviceAgentRemove.
+  ///
+  /// In en, this message translates to:
+  /// **'Remove device helper'**
+  String get deviceAgentRemove;
+
+  /// No description provided for @deviceAgentInstalled.
+  ///
+  /// In en, this message translates to:
+  /// **'Device helper: version {version}'**
+  String deviceAgentInstalled(String version);
+
+  /// No description provided for @deviceLogNoteHint.
+  ///
+  /// In en, this message translates to:
+  /// **'Add a note'**
+  String get deviceLogNoteHint;
+
+  /// No description provided for @deviceLogAddNote.
+  ///
+  /// In en, this message translates to:
+  /// **'Add'**
+  String get deviceLogAddNote;
+
+  /// No description provided for @deviceLogEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'No entries yet'**
+  String get deviceLogEmpty;
+
+  /// No description provided for @deviceLogDeleteEntry.
+  ///
+  /// In en, this message translates to:
+  /// **'Delete entry'**
+  String get deviceLogDeleteEntry;
+
+  /// No description provided for @deviceLogTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Maintenance log: {device}'**
+  String deviceLogTitle(String device);
+
+  /// No description provided for @deviceEnableWirelessAdb.
+  ///
+  /// In en, this message translates to:
+  /// **'Enable ADB over Wi‑Fi'**
+  String get deviceEnableWirelessAdb;
+
+  /// No description provided for @deviceStorageConnection.
+  ///
+  /// In en, this message translates to:
+  /// **'Storage connection'**
+  String get deviceStorageConnection;
+
+  /// No description provided for @deviceStorageConnectionTooltip.
+  ///
+  /// In en, this message translates to:
+  /// **'Connect storage with MTP.'**
+  String get deviceStorageConnectionTooltip;
+
+  /// No description provided for @deviceStorageResetTooltip.
+  ///
+  /// In en, this message translates to:
+  /// **'Reset USB functions.'**
+  String get deviceStorageResetTooltip;
+
+  /// No description provided for @deviceStorageStatusConnected.
+  ///
+  /// In en, this message translates to:
+  /// **'Connected'**
+  String get deviceStorageStatusConnected;
+
+  /// No description provided for @deviceStorageStatusDisconnected.
+  ///
+  /// In en, this message translates to:
+  /// **'Disconnected'**
+  String get deviceStorageStatusDisconnected;
+
+  /// No description provided for @deviceStorageStatusUnknown.
+  ///
+  /// In en, this message translates to:
+  /// **'Unknown'**
+  String get deviceStorageStatusUnknown;
+
+  /// No description provided for @deviceStorageConnectedMessage.
+  ///
+  /// In en, this message translates to:
+  /// **'MTP is enabled. The USB connection will restart.'**
+  String get deviceStorageConnectedMessage;
+
+  /// No description provided for @deviceStorageResetMessage.
+  ///
+  /// In en, this message translates to:
+  /// **'USB functions were reset. The USB connection will restart.'**
+  String get deviceStorageResetMessage;
+
+  /// No description provided for @deviceStorageConnectionFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to update the storage connection.'**
+  String get deviceStorageConnectionFailed;
+
+  /// No description provided for @usbSpeedLabel.
+  ///
+  /// In en, this message translates to:
+  /// **'USB speed'**
+  String get usbSpeedLabel;
+
+  /// No description provided for @osVersionLabel.
+  ///
+  /// In en, this message translates to:
+  /// **'OS version'**
+  String get osVersionLabel;
+
+  /// No description provided for @androidVersionLabel.
+  ///
+  /// In en, this message translates to:
+  /// **'Android version'**
+  String get androidVersionLabel;
+
+  /// No description provided for @buildFingerprintLabel.
+  ///
+  /// In en, this message translates to:
+  /// **'Build fingerprint'**
+  String get buildFingerprintLabel;
+
+  /// No description provided for @copiedToClipboard.
+  ///
+  /// In en, this message translates to:
+  /// **'Copied to clipboard'**
+  String get copiedToClipboard;
+
+  /// No description provided for @clickToCopy.
+  ///
+  /// In en, this message translates to:
+  /// **'Click to copy'**
+  String get clickToCopy;
+
+  /// No description provided for @detailsPackageName.
+  ///
+  /// In en, this message translates to:
+  /// **'Package Name:'**
+  String get detailsPackageName;
+
+  /// No description provided for @detailsVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Version:'**
+  String get detailsVersion;
+
+  /// No description provided for @detailsVersionCode.
+  ///
+  /// In en, this message translates to:
+  /// **'Version Code:'**
+  String get detailsVersionCode;
+
+  /// No description provided for @detailsIsVr.
+  ///
+  /// In en, this message translates to:
+  /// **'Is VR:'**
+  String get detailsIsVr;
+
+  /// No description provided for @detailsIsLaunchable.
+  ///
+  /// In en, this message translates to:
+  /// **'Is Launchable:'**
+  String get detailsIsLaunchable;
+
+  /// No description provided for @detailsIsSystem.
+  ///
+  /// In en, this message translates to:
+  /// **'Is System:'**
+  String get detailsIsSystem;
+
+  /// No description provided for @detailsPinnedVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Pinned version:'**
+  String get detailsPinnedVersion;
+
+  /// No description provided for @detailsIgnoredVersions.
+  ///
+  /// In en, this message translates to:
+  /// **'Ignored versions:'**
+  String get detailsIgnoredVersions;
+
+  /// No description provided for @detailsStorageUsage.
+  ///
+  /// In en, this message translates to:
+  /// **'Storage Usage:'**
+  String get detailsStorageUsage;
+
+  /// No description provided for @detailsApp.
+  ///
+  /// In en, this message translates to:
+  /// **'App:'**
+  String get detailsApp;
+
+  /// No description provided for @detailsData.
+  ///
+  /// In en, this message translates to:
+  /// **'Data:'**
+  String get detailsData;
+
+  /// No description provided for @detailsCache.
+  ///
+  /// In en, this message translates to:
+  /// **'Cache:'**
+  String get detailsCache;
+
+  /// No description provided for @detailsTotal.
+  ///
+  /// In en, this message translates to:
+  /// **'Total:'**
+  String get detailsTotal;
+
+  /// No description provided for @detailsRating.
+  ///
+  /// In en, this message translates to:
+  /// **'Rating:'**
+  String get detailsRating;
+
+  /// No description provided for @detailsReviewsTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Recent reviews'**
+  String get detailsReviewsTitle;
+
+  /// No description provided for @detailsReviewsUnavailable.
+  ///
+  /// In en, this message translates to:
+  /// **'Reviews are unavailable for this app.'**
+  String get detailsReviewsUnavailable;
+
+  /// No description provided for @detailsReviewsError.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to load reviews.'**
+  String get detailsReviewsError;
+
+  /// No description provided for @detailsReviewsEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'No reviews available yet.'**
+  String get detailsReviewsEmpty;
+
+  /// No description provided for @detailsDeveloperResponse.
+  ///
+  /// In en, this message translates to:
+  /// **'Developer response'**
+  String get detailsDeveloperResponse;
+
+  /// No description provided for @detailsReviewHelpful.
+  ///
+  /// In en, this message translates to:
+  /// **'Helpful'**
+  String get detailsReviewHelpful;
+
+  /// No description provided for @detailsReviewHelpfulCount.
+  ///
+  /// In en, this message translates to:
+  /// **'{count, plural, one {{count} person found this helpful} other {{count} people found this helpful}}'**
+  String detailsReviewHelpfulCount(int count);
+
+  /// No description provided for @reviewsSortBy.
+  ///
+  /// In en, this message translates to:
+  /// **'Sort by'**
+  String get reviewsSortBy;
+
+  /// No description provided for @reviewsSortHelpful.
+  ///
+  /// In en, this message translates to:
+  /// **'Most helpful'**
+  String get reviewsSortHelpful;
+
+  /// No description provided for @reviewsSortNewest.
+  ///
+  /// In en, this message translates to:
+  /// **'Newest'**
+  String get reviewsSortNewest;
+
+  /// No description provided for @previous.
+  ///
+  /// In en, this message translates to:
+  /// **'Previous'**
+  String get previous;
+
+  /// No description provided for @next.
+  ///
+  /// In en, this message translates to:
+  /// **'Next'**
+  String get next;
+
+  /// No description provided for @reviewsReadAll.
+  ///
+  /// In en, this message translates to:
+  /// **'Read all reviews'**
+  String get reviewsReadAll;
+
+  /// No description provided for @commonCopy.
+  ///
+  /// In en, this message translates to:
+  /// **'Copy'**
+  String get commonCopy;
+
+  /// No description provided for @commonClose.
+  ///
+  /// In en, this message translates to:
+  /// **'Close'**
+  String get commonClose;
+
+  /// No description provided for @crashReportCollect.
+  ///
+  /// In en, this message translates to:
+  /// **'Crash report'**
+  String get crashReportCollect;
+
+  /// No description provided for @appPermissions.
+  ///
+  /// In en, this message translates to:
+  /// **'Permissions'**
+  String get appPermissions;
+
+  /// No description provided for @installRecipe.
+  ///
+  /// In en, this message translates to:
+  /// **'Post-install recipe'**
+  String get installRecipe;
+
+  /// No description provided for @versionPin.
+  ///
+  /// In en, this message translates to:
+  /// **'Pin version'**
+  String get versionPin;
+
+  /// No description provided for @versionUnpin.
+  ///
+  /// In en, this message translates to:
+  /// **'Unpin version'**
+  String get versionUnpin;
+
+  /// No description provided for @updateIgnoreVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Ignore version {version}'**
+  String updateIgnoreVersion(String version);
+
+  /// No description provided for @updateStopIgnoring.
+  ///
+  /// In en, this message translates to:
+  /// **'Offer ignored versions again'**
+  String get updateStopIgnoring;
+
+  /// No description provided for @appRename.
+  ///
+  /// In en, this message translates to:
+  /// **'Rename'**
+  String get appRename;
+
+  /// No description provided for @appRenameTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Rename {name}'**
+  String appRenameTitle(String name);
+
+  /// No description provided for @appRenameHint.
+  ///
+  /// In en, this message translates to:
+  /// **'Name shown in the catalog and app lists'**
+  String get appRenameHint;
+
+  /// No description provided for @appRenameReset.
+  ///
+  /// In en, this message translates to:
+  /// **'Restore original name'**
+  String get appRenameReset;
+
+  /// No description provided for @appRenameSave.
+  ///
+  /// In en, this message translates to:
+  /// **'Save'**
+  String get appRenameSave;
+
+  /// No description provided for @installRecipeHelp.
+  ///
+  /// In en, this message translates to:
+  /// **'Steps run on the headset every time this app is installed'**
+  String get installRecipeHelp;
+
+  /// No description provided for @installRecipeEnabled.
+  ///
+  /// In en, this message translates to:
+  /// **'Run after install'**
+  String get installRecipeEnabled;
+
+  /// No description provided for @installRecipeNoSteps.
+  ///
+  /// In en, this message translates to:
+  /// **'No steps yet'**
+  String get installRecipeNoSteps;
+
+  /// No description provided for @installRecipeAddStep.
+  ///
+  /// In en, this message translates to:
+  /// **'Add step'**
+  String get installRecipeAddStep;
+
+  /// No description provided for @installRecipeStepGrant.
+  ///
+  /// In en, this message translates to:
+  /// **'Grant {permission}'**
+  String installRecipeStepGrant(String permission);
+
+  /// No description provided for @installRecipeStepPush.
+  ///
+  /// In en, this message translates to:
+  /// **'Push {local} to {remote}'**
+  String installRecipeStepPush(String local, String remote);
+
+  /// No description provided for @installRecipeStepLaunch.
+  ///
+  /// In en, this message translates to:
+  /// **'Launch once'**
+  String get installRecipeStepLaunch;
+
+  /// No description provided for @installRecipeStepProperty.
+  ///
+  /// In en, this message translates to:
+  /// **'Set {name} to {value}'**
+  String installRecipeStepProperty(String name, String value);
+
+  /// No description provided for @installRecipeGrantPermission.
+  ///
+  /// In en, this message translates to:
+  /// **'Grant permission'**
+  String get installRecipeGrantPermission;
+
+  /// No description provided for @installRecipePushFile.
+  ///
+  /// In en, this message translates to:
+  /// **'Push file'**
+  String get installRecipePushFile;
+
+  /// No description provided for @installRecipeSetProperty.
+  ///
+  /// In en, this message translates to:
+  /// **'Set property'**
+  String get installRecipeSetProperty;
+
+  /// No description provided for @installRecipePermissionName.
+  ///
+  /// In en, this message translates to:
+  /// **'Permission name'**
+  String get installRecipePermissionName;
+
+  /// No description provided for @installRecipeLocalPath.
+  ///
+  /// In en, this message translates to:
+  /// **'Local file or folder'**
+  String get installRecipeLocalPath;
+
+  /// No description provided for @installRecipeRemotePath.
+  ///
+  /// In en, this message translates to:
+  /// **'Path on the headset'**
+  String get installRecipeRemotePath;
+
+  /// No description provided for @installRecipePropertyName.
+  ///
+  /// In en, this message translates to:
+  /// **'Property name'**
+  String get installRecipePropertyName;
+
+  /// No description provided for @installRecipePropertyValue.
+  ///
+  /// In en, this message translates to:
+  /// **'Value'**
+  String get installRecipePropertyValue;
+
+  /// No description provided for @installRecipeSave.
+  ///
+  /// In en, this message translates to:
+  /// **'Save'**
+  String get installRecipeSave;
+
+  /// No description provided for @installScriptReviewTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Run install script?'**
+  String get installScriptReviewTitle;
+
+  /// No description provided for @installScriptShellWarning.
+  ///
+  /// In en, this message translates to:
+  /// **'This script runs shell commands on the device. Only run scripts from sources you trust.'**
+  String get installScriptShellWarning;
+
+  /// No description provided for @installScriptLine.
+  ///
+  /// In en, this message translates to:
+  /// **'Line {line}'**
+  String installScriptLine(String line);
+
+  /// No description provided for @installScriptOpInstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Install'**
+  String get installScriptOpInstall;
+
+  /// No description provided for @installScriptOpUninstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Uninstall'**
+  String get installScriptOpUninstall;
+
+  /// No description provided for @installScriptOpShell.
+  ///
+  /// In en, this message translates to:
+  /// **'Shell command'**
+  String get installScriptOpShell;
+
+  /// No description provided for @installScriptOpPush.
+  ///
+  /// In en, this message translates to:
+  /// **'Copy to device'**
+  String get installScriptOpPush;
+
+  /// No description provided for @installScriptOpPull.
+  ///
+  /// In en, this message translates to:
+  /// **'Copy from device'**
+  String get installScriptOpPull;
+
+  /// No description provided for @installScriptOpPause.
+  ///
+  /// In en, this message translates to:
+  /// **'Pause'**
+  String get installScriptOpPause;
+
+  /// No description provided for @installScriptRun.
+  ///
+  /// In en, this message translates to:
+  /// **'Run script'**
+  String get installScriptRun;
+
+  /// No description provided for @installScriptUnsupportedTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Install script not run'**
+  String get installScriptUnsupportedTitle;
+
+  /// No description provided for @installScriptUnsupportedDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'Nothing was run because these lines cannot be run safely:'**
+  String get installScriptUnsupportedDescription;
+
+  /// No description provided for @appPermissionsTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Permissions of {name}'**
+  String appPermissionsTitle(String name);
+
+  /// No description provided for @appPermissionsRuntime.
+  ///
+  /// In en, this message translates to:
+  /// **'Runtime permissions'**
+  String get appPermissionsRuntime;
+
+  /// No description provided for @appPermissionsOther.
+  ///
+  /// In en, this message translates to:
+  /// **'Other permissions'**
+  String get appPermissionsOther;
+
+  /// No description provided for @appPermissionsGrantedAtInstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Granted at install'**
+  String get appPermissionsGrantedAtInstall;
+
+  /// No description provided for @appPermissionsNotGranted.
+  ///
+  /// In en, this message translates to:
+  /// **'Not granted'**
+  String get appPermissionsNotGranted;
+
+  /// No description provided for @appPermissionsNone.
+  ///
+  /// In en, this message translates to:
+  /// **'This app requests no permissions'**
+  String get appPermissionsNone;
+
+  /// No description provided for @crashReportSaved.
+  ///
+  /// In en, this message translates to:
+  /// **'Crash report saved, path copied to clipboard'**
+  String get crashReportSaved;
+
+  /// No description provided for @crashReportFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to collect crash report: {error}'**
+  String crashReportFailed(String error);
+
+  /// No description provided for @commonCancel.
+  ///
+  /// In en, this message translates to:
+  /// **'Cancel'**
+  String get commonCancel;
+
+  /// No description provided for @commonDownload.
+  ///
+  /// In en, this message translates to:
+  /// **'Download'**
+  String get commonDownload;
+
+  /// No description provided for @availableVersions.
+  ///
+  /// In en, this message translates to:
+  /// **'Available Versions'**
+  String get availableVersions;
+
+  /// No description provided for @installNewerVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Install newer version'**
+  String get installNewerVersion;
+
+  /// No description provided for @reinstallThisVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Reinstall this version'**
+  String get reinstallThisVersion;
+
+  /// No description provided for @holdShiftToReinstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Hold Shift to reinstall this version'**
+  String get holdShiftToReinstall;
+
+  /// No description provided for @cannotDowngrade.
+  ///
+  /// In en, this message translates to:
+  /// **'Cannot downgrade to older version'**
+  String get cannotDowngrade;
+
+  /// No description provided for @newerVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Newer version'**
+  String get newerVersion;
+
+  /// No description provided for @sameVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Same version'**
+  String get sameVersion;
+
+  /// No description provided for @olderVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Older version'**
+  String get olderVersion;
+
+  /// No description provided for @update.
+  ///
+  /// In en, this message translates to:
+  /// **'Update'**
+  String get update;
+
+  /// No description provided for @install.
+  ///
+  /// In en, this message translates to:
+  /// **'Install'**
+  String get install;
+
+  /// No description provided for @checkForUpdates.
+  ///
+  /// In en, this message translates to:
+  /// **'Check for updates'**
+  String get checkForUpdates;
+
+  /// No description provided for @noMatchingCloudApp.
+  ///
+  /// In en, this message translates to:
+  /// **'No matching app found in cloud repository'**
+  String get noMatchingCloudApp;
+
+  /// No description provided for @updateTo.
+  ///
+  /// In en, this message translates to:
+  /// **'Update to {to}'**
+  String updateTo(String to);
+
+  /// No description provided for @updateAll.
+  ///
+  /// In en, this message translates to:
+  /// **'Update all'**
+  String get updateAll;
+
+  /// No description provided for @updateAllTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Update {count} apps'**
+  String updateAllTitle(String count);
+
+  /// No description provided for @updateAllCanary.
+  ///
+  /// In en, this message translates to:
+  /// **'Try the smallest app first'**
+  String get updateAllCanary;
+
+  /// No description provided for @updateAllCanaryDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'Updates and launches one app, then asks before updating the rest'**
+  String get updateAllCanaryDescription;
+
+  /// No description provided for @updateAllCanarySucceeded.
+  ///
+  /// In en, this message translates to:
+  /// **'{app} updated and launched'**
+  String updateAllCanarySucceeded(String app);
+
+  /// No description provided for @updateAllCanaryFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Update of {app} did not go through'**
+  String updateAllCanaryFailed(String app);
+
+  /// No description provided for @updateAllContinuePrompt.
+  ///
+  /// In en, this message translates to:
+  /// **'Update the remaining {count} apps?'**
+  String updateAllContinuePrompt(String count);
+
+  /// No description provided for @updateAllContinue.
+  ///
+  /// In en, this message translates to:
+  /// **'Continue'**
+  String get updateAllContinue;
+
+  /// No description provided for @updateAllStop.
+  ///
+  /// In en, this message translates to:
+  /// **'Stop'**
+  String get updateAllStop;
+
+  /// No description provided for @downgradeAppTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Downgrade App'**
+  String get downgradeAppTitle;
+
+  /// No description provided for @downgradeConfirmMessage.
+  ///
+  /// In en, this message translates to:
+  /// **'Attempt to downgrade to version {versionCode}? This may cause issues.'**
+  String downgradeConfirmMessage(String versionCode);
+
+  /// No description provided for @holdShiftToDowngrade.
+  ///
+  /// In en, this message translates to:
+  /// **'Hold Shift to downgrade to {to}'**
+  String holdShiftToDowngrade(String to);
+
+  /// No description provided for @downgradeToThisVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Downgrade to this version'**
+  String get downgradeToThisVersion;
+
+  /// No description provided for @downgradeTo.
+  ///
+  /// In en, this message translates to:
+  /// **'Downgrade to {to}'**
+  String downgradeTo(String to);
 
   /// No description provided for @holdShiftToViewVersions.
   ///
@@ -1784,6 +3500,18 @@ abstract class AppLocalizations {
   /// **'Add to favorites'**
   String get addToFavorites;
 
+  /// No description provided for @watchApp.
+  ///
+  /// In en, this message translates to:
+  /// **'Notify about new versions'**
+  String get watchApp;
+
+  /// No description provided for @stopWatchingApp.
+  ///
+  /// In en, this message translates to:
+  /// **'Stop notifying'**
+  String get stopWatchingApp;
+
   /// No description provided for @removeFromFavorites.
   ///
   /// In en, this message translates to:
@@ -1802,35 +3530,95 @@ abstract class AppLocalizations {
   /// **'Clear Favorites'**
   String get clearFavoritesTitle;
 
-  /// No description provided for @clearFavoritesConfirm.
+  /// No description provided for @clearFavoritesConfirm.
+  ///
+  /// In en, this message translates to:
+  /// **'Remove all apps from favorites?'**
+  String get clearFavoritesConfirm;
+
+  /// No description provided for @sizeAndDate.
+  ///
+  /// In en, this message translates to:
+  /// **'Size: {size} • Last Updated: {date}'**
+  String sizeAndDate(String size, String date);
+
+  /// No description provided for @downloadToComputer.
+  ///
+  /// In en, this message translates to:
+  /// **'Download to computer'**
+  String get downloadToComputer;
+
+  /// No description provided for @downloadAndInstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Download and install on device'**
+  String get downloadAndInstall;
+
+  /// No description provided for @downloadAndInstallNotConnected.
+  ///
+  /// In en, this message translates to:
+  /// **'Download and install on device (not connected)'**
+  String get downloadAndInstallNotConnected;
+
+  /// No description provided for @releaseFilesChoose.
+  ///
+  /// In en, this message translates to:
+  /// **'Choose files…'**
+  String get releaseFilesChoose;
+
+  /// No description provided for @releaseFilesTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Choose files to download'**
+  String get releaseFilesTitle;
+
+  /// No description provided for @releaseFilesLoading.
+  ///
+  /// In en, this message translates to:
+  /// **'Listing release files…'**
+  String get releaseFilesLoading;
+
+  /// No description provided for @releaseFilesFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to list files: {error}'**
+  String releaseFilesFailed(String error);
+
+  /// No description provided for @releaseFilesAll.
+  ///
+  /// In en, this message translates to:
+  /// **'All'**
+  String get releaseFilesAll;
+
+  /// No description provided for @releaseFilesApkOnly.
   ///
   /// In en, this message translates to:
-  /// **'Remove all apps from favorites?'**
-  String get clearFavoritesConfirm;
+  /// **'APK only'**
+  String get releaseFilesApkOnly;
 
-  /// No description provided for @sizeAndDate.
+  /// No description provided for @releaseFilesObbOnly.
   ///
   /// In en, this message translates to:
-  /// **'Size: {size} • Last Updated: {date}'**
-  String sizeAndDate(String size, String date);
+  /// **'OBB only'**
+  String get releaseFilesObbOnly;
 
-  /// No description provided for @downloadToComputer.
+  /// No description provided for @releaseFilesSelected.
   ///
   /// In en, this message translates to:
-  /// **'Download to computer'**
-  String get downloadToComputer;
+  /// **'{count} file(s) selected, {size}'**
+  String releaseFilesSelected(String count, String size);
 
-  /// No description provided for @downloadAndInstall.
+  /// No description provided for @releaseFilesDownloaded.
   ///
   /// In en, this message translates to:
-  /// **'Download and install on device'**
-  String get downloadAndInstall;
+  /// **'Already downloaded'**
+  String get releaseFilesDownloaded;
 
-  /// No description provided for @downloadAndInstallNotConnected.
+  /// No description provided for @releaseFilesToTransfer.
   ///
   /// In en, this message translates to:
-  /// **'Download and install on device (not connected)'**
-  String get downloadAndInstallNotConnected;
+  /// **'{size} to transfer'**
+  String releaseFilesToTransfer(String size);
 
   /// No description provided for @cloudStatusInstalled.
   ///
@@ -1898,6 +3686,36 @@ abstract class AppLocalizations {
   /// **'Size (Largest first)'**
   String get sortSizeLargest;
 
+  /// No description provided for @exportInstalledList.
+  ///
+  /// In en, this message translates to:
+  /// **'Export installed apps'**
+  String get exportInstalledList;
+
+  /// No description provided for @exportInstalledListCsv.
+  ///
+  /// In en, this message translates to:
+  /// **'Export as CSV'**
+  String get exportInstalledListCsv;
+
+  /// No description provided for @exportInstalledListJson.
+  ///
+  /// In en, this message translates to:
+  /// **'Export as JSON'**
+  String get exportInstalledListJson;
+
+  /// No description provided for @installedListExportFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to export installed apps'**
+  String get installedListExportFailed;
+
+  /// No description provided for @installedListExported.
+  ///
+  /// In en, this message translates to:
+  /// **'Exported {count} apps to {path}'**
+  String installedListExported(String count, String path);
+
   /// No description provided for @sortUpdateAvailableFirst.
   ///
   /// In en, this message translates to:
@@ -2132,6 +3950,18 @@ abstract class AppLocalizations {
   /// **'Battery'**
   String get batteryLabel;
 
+  /// No description provided for @firmwareLabel.
+  ///
+  /// In en, this message translates to:
+  /// **'Firmware'**
+  String get firmwareLabel;
+
+  /// No description provided for @lastConnectedLabel.
+  ///
+  /// In en, this message translates to:
+  /// **'Last connected'**
+  String get lastConnectedLabel;
+
   /// No description provided for @powerOffDevice.
   ///
   /// In en, this message translates to:
@@ -2156,6 +3986,24 @@ abstract class AppLocalizations {
   /// **'Reboot...'**
   String get rebootMenu;
 
+  /// No description provided for @pairControllersMenu.
+  ///
+  /// In en, this message translates to:
+  /// **'Pair controllers...'**
+  String get pairControllersMenu;
+
+  /// No description provided for @pairControllersTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Pair controllers'**
+  String get pairControllersTitle;
+
+  /// No description provided for @pairControllersConfirm.
+  ///
+  /// In en, this message translates to:
+  /// **'Put the controllers into pairing mode? They stay disconnected until they are paired again.'**
+  String get pairControllersConfirm;
+
   /// No description provided for @rebootOptions.
   ///
   /// In en, this message translates to:
@@ -2348,6 +4196,24 @@ abstract class AppLocalizations {
   /// **'Deleted {removed}, skipped {skipped}'**
   String deleteAllDownloadsResult(String removed, String skipped);
 
+  /// No description provided for @dedupeDownloads.
+  ///
+  /// In en, this message translates to:
+  /// **'Deduplicate downloads'**
+  String get dedupeDownloads;
+
+  /// No description provided for @dedupeDownloadsRunning.
+  ///
+  /// In en, this message translates to:
+  /// **'Hashing downloaded files, this may take a while'**
+  String get dedupeDownloadsRunning;
+
+  /// No description provided for @dedupeDownloadsResult.
+  ///
+  /// In en, this message translates to:
+  /// **'Linked {linked} files, {saved} saved in total'**
+  String dedupeDownloadsResult(String linked, String saved);
+
   /// No description provided for @deleteDownloadTitle.
   ///
   /// In en, this message translates to:
@@ -2378,6 +4244,18 @@ abstract class AppLocalizations {
   /// **'No downloads found.'**
   String get noDownloadsFound;
 
+  /// No description provided for @searchDownloadsHint.
+  ///
+  /// In en, this message translates to:
+  /// **'Search downloads...'**
+  String get searchDownloadsHint;
+
+  /// No description provided for @noMatchingDownloads.
+  ///
+  /// In en, this message translates to:
+  /// **'No downloads match the search.'**
+  String get noMatchingDownloads;
+
   /// No description provided for @unsupportedPlatform.
   ///
   /// In en, this message translates to:
@@ -2402,6 +4280,108 @@ abstract class AppLocalizations {
   /// **'Open Folder'**
   String get openFolderTooltip;
 
+  /// No description provided for @taskArtifactReveal.
+  ///
+  /// In en, this message translates to:
+  /// **'Show in Folder'**
+  String get taskArtifactReveal;
+
+  /// No description provided for @downloadHealthCheck.
+  ///
+  /// In en, this message translates to:
+  /// **'Check folder'**
+  String get downloadHealthCheck;
+
+  /// No description provided for @downloadHealthChecking.
+  ///
+  /// In en, this message translates to:
+  /// **'Checking folder…'**
+  String get downloadHealthChecking;
+
+  /// No description provided for @downloadHealthReady.
+  ///
+  /// In en, this message translates to:
+  /// **'Ready to install'**
+  String get downloadHealthReady;
+
+  /// No description provided for @downloadHealthFixIssues.
+  ///
+  /// In en, this message translates to:
+  /// **'Fix these issues before installing:'**
+  String get downloadHealthFixIssues;
+
+  /// No description provided for @downloadHealthFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Could not check the folder: {error}'**
+  String downloadHealthFailed(String error);
+
+  /// No description provided for @downloadHealthUsesScript.
+  ///
+  /// In en, this message translates to:
+  /// **'Installed with install.txt'**
+  String get downloadHealthUsesScript;
+
+  /// No description provided for @healthIssueNoApk.
+  ///
+  /// In en, this message translates to:
+  /// **'No APK or install.txt found'**
+  String get healthIssueNoApk;
+
+  /// No description provided for @healthIssueMultipleApks.
+  ///
+  /// In en, this message translates to:
+  /// **'Several APKs and no install.txt: {files}'**
+  String healthIssueMultipleApks(String files);
+
+  /// No description provided for @healthIssueApkUnreadable.
+  ///
+  /// In en, this message translates to:
+  /// **'{file} could not be read: {error}'**
+  String healthIssueApkUnreadable(String file, String error);
+
+  /// No description provided for @healthIssueObbFolderMismatch.
+  ///
+  /// In en, this message translates to:
+  /// **'OBB folder {folder} should be named {package}'**
+  String healthIssueObbFolderMismatch(String folder, String package);
+
+  /// No description provided for @healthIssueObbFileMismatch.
+  ///
+  /// In en, this message translates to:
+  /// **'{file} belongs to a package other than {package}'**
+  String healthIssueObbFileMismatch(String file, String package);
+
+  /// No description provided for @healthIssueLooseObb.
+  ///
+  /// In en, this message translates to:
+  /// **'OBB files must be in a folder named after the package: {files}'**
+  String healthIssueLooseObb(String files);
+
+  /// No description provided for @healthIssueScriptUnreadable.
+  ///
+  /// In en, this message translates to:
+  /// **'install.txt could not be read: {error}'**
+  String healthIssueScriptUnreadable(String error);
+
+  /// No description provided for @healthIssueScriptFileMissing.
+  ///
+  /// In en, this message translates to:
+  /// **'install.txt line {line}: {path} is missing'**
+  String healthIssueScriptFileMissing(String line, String path);
+
+  /// No description provided for @healthIssueScriptUnsupported.
+  ///
+  /// In en, this message translates to:
+  /// **'install.txt line {line}: unsupported command {command}'**
+  String healthIssueScriptUnsupported(String line, String command);
+
+  /// No description provided for @healthIssueArchiveUnreadable.
+  ///
+  /// In en, this message translates to:
+  /// **'{file} could not be listed: {error}'**
+  String healthIssueArchiveUnreadable(String file, String error);
+
   /// No description provided for @unknownTime.
   ///
   /// In en, this message translates to:
@@ -2432,6 +4412,12 @@ abstract class AppLocalizations {
   /// **'OBB'**
   String get partOBB;
 
+  /// No description provided for @backupCompressed.
+  ///
+  /// In en, this message translates to:
+  /// **'Compressed'**
+  String get backupCompressed;
+
   /// No description provided for @noPartsDetected.
   ///
   /// In en, this message translates to:
@@ -2444,35 +4430,287 @@ abstract class AppLocalizations {
   /// **'Delete Backup'**
   String get deleteBackupTitle;
 
-  /// No description provided for @deleteBackupConfirm.
+  /// No description provided for @deleteBackupConfirm.
+  ///
+  /// In en, this message translates to:
+  /// **'Are you sure you want to delete \"{name}\"?'**
+  String deleteBackupConfirm(String name);
+
+  /// No description provided for @backupDeletedTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Backup deleted'**
+  String get backupDeletedTitle;
+
+  /// No description provided for @backupsCleanUp.
+  ///
+  /// In en, this message translates to:
+  /// **'Clean up old backups'**
+  String get backupsCleanUp;
+
+  /// No description provided for @backupRestoreToPackage.
+  ///
+  /// In en, this message translates to:
+  /// **'Restore to another app'**
+  String get backupRestoreToPackage;
+
+  /// No description provided for @backupRestoreTargetPackage.
+  ///
+  /// In en, this message translates to:
+  /// **'Target package name'**
+  String get backupRestoreTargetPackage;
+
+  /// No description provided for @backupRestoreInvalidPackage.
+  ///
+  /// In en, this message translates to:
+  /// **'Not a valid package name'**
+  String get backupRestoreInvalidPackage;
+
+  /// No description provided for @backupRestorePackageNotInstalled.
+  ///
+  /// In en, this message translates to:
+  /// **'This package is not installed on the device'**
+  String get backupRestorePackageNotInstalled;
+
+  /// No description provided for @backupRestoreSamePackage.
+  ///
+  /// In en, this message translates to:
+  /// **'The backup already belongs to this package'**
+  String get backupRestoreSamePackage;
+
+  /// No description provided for @backupRestoreToPackageDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'Data from {name} will be restored into the selected app, which must already be installed. OBB files are renamed to match it.'**
+  String backupRestoreToPackageDescription(String name);
+
+  /// No description provided for @backupVerify.
+  ///
+  /// In en, this message translates to:
+  /// **'Verify backup'**
+  String get backupVerify;
+
+  /// No description provided for @backupVerifyOk.
+  ///
+  /// In en, this message translates to:
+  /// **'Backup is intact'**
+  String get backupVerifyOk;
+
+  /// No description provided for @backupVerifyOkFiles.
+  ///
+  /// In en, this message translates to:
+  /// **'{count} files match the manifest'**
+  String backupVerifyOkFiles(String count);
+
+  /// No description provided for @backupVerifyNoFileList.
+  ///
+  /// In en, this message translates to:
+  /// **'This backup was made before file lists were recorded, only its layout was checked'**
+  String get backupVerifyNoFileList;
+
+  /// No description provided for @backupVerifyProblemsTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Problems in {name}'**
+  String backupVerifyProblemsTitle(String name);
+
+  /// No description provided for @backupVerifyProblemsWarning.
+  ///
+  /// In en, this message translates to:
+  /// **'This backup may not restore correctly. Keep the app installed until you have a backup without problems.'**
+  String get backupVerifyProblemsWarning;
+
+  /// No description provided for @backupFromDevice.
+  ///
+  /// In en, this message translates to:
+  /// **'from {device}'**
+  String backupFromDevice(String device);
+
+  /// No description provided for @searchBackupsHint.
+  ///
+  /// In en, this message translates to:
+  /// **'Search backups...'**
+  String get searchBackupsHint;
+
+  /// No description provided for @noMatchingBackups.
+  ///
+  /// In en, this message translates to:
+  /// **'No backups match the search and filters.'**
+  String get noMatchingBackups;
+
+  /// No description provided for @backupsFilter.
+  ///
+  /// In en, this message translates to:
+  /// **'Filter'**
+  String get backupsFilter;
+
+  /// No description provided for @backupsFilterApk.
+  ///
+  /// In en, this message translates to:
+  /// **'With APK'**
+  String get backupsFilterApk;
+
+  /// No description provided for @backupsFilterData.
+  ///
+  /// In en, this message translates to:
+  /// **'With app data'**
+  String get backupsFilterData;
+
+  /// No description provided for @backupsFilterObb.
+  ///
+  /// In en, this message translates to:
+  /// **'With OBB files'**
+  String get backupsFilterObb;
+
+  /// No description provided for @backupsDateRange.
+  ///
+  /// In en, this message translates to:
+  /// **'Date range'**
+  String get backupsDateRange;
+
+  /// No description provided for @backupsClearDateRange.
+  ///
+  /// In en, this message translates to:
+  /// **'Clear date range'**
+  String get backupsClearDateRange;
+
+  /// No description provided for @backupsSummary.
+  ///
+  /// In en, this message translates to:
+  /// **'{count} of {total} backups, {size}'**
+  String backupsSummary(String count, String total, String size);
+
+  /// No description provided for @backupsPruneTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Clean up backups'**
+  String get backupsPruneTitle;
+
+  /// No description provided for @backupsPruneNothing.
+  ///
+  /// In en, this message translates to:
+  /// **'No backups exceed the retention limits set in Settings.'**
+  String get backupsPruneNothing;
+
+  /// No description provided for @backupsPruneFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Some backups could not be deleted'**
+  String get backupsPruneFailed;
+
+  /// No description provided for @backupsPruned.
+  ///
+  /// In en, this message translates to:
+  /// **'Deleted {count} old backups, freed {size}'**
+  String backupsPruned(String count, String size);
+
+  /// No description provided for @backupsPruneConfirm.
+  ///
+  /// In en, this message translates to:
+  /// **'{count} backups will be deleted, freeing {size}:'**
+  String backupsPruneConfirm(String count, String size);
+
+  /// No description provided for @fatalErrorTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Fatal Error'**
+  String get fatalErrorTitle;
+
+  /// No description provided for @exitApplication.
+  ///
+  /// In en, this message translates to:
+  /// **'Exit Application'**
+  String get exitApplication;
+
+  /// No description provided for @errorCopied.
+  ///
+  /// In en, this message translates to:
+  /// **'Error message copied to clipboard'**
+  String get errorCopied;
+
+  /// No description provided for @coreApiMismatchTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Incompatible app components'**
+  String get coreApiMismatchTitle;
+
+  /// No description provided for @coreApiMismatchDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'The interface and the core of the app are from different versions, some features may not work. Reinstall the app to fix this.'**
+  String get coreApiMismatchDescription;
+
+  /// No description provided for @appUpdateAvailableTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Update available'**
+  String get appUpdateAvailableTitle;
+
+  /// No description provided for @appUpdatesTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Updates'**
+  String get appUpdatesTitle;
+
+  /// No description provided for @checkForAppUpdates.
+  ///
+  /// In en, this message translates to:
+  /// **'Check for updates'**
+  String get checkForAppUpdates;
+
+  /// No description provided for @appUpToDate.
+  ///
+  /// In en, this message translates to:
+  /// **'You are using the latest version'**
+  String get appUpToDate;
+
+  /// No description provided for @appUpdateDownload.
+  ///
+  /// In en, this message translates to:
+  /// **'Download update'**
+  String get appUpdateDownload;
+
+  /// No description provided for @appUpdateReleasePage.
+  ///
+  /// In en, this message translates to:
+  /// **'View release page'**
+  String get appUpdateReleasePage;
+
+  /// No description provided for @appUpdateNoBuild.
+  ///
+  /// In en, this message translates to:
+  /// **'This release has no build for your platform'**
+  String get appUpdateNoBuild;
+
+  /// No description provided for @appUpdateAvailableDescription.
   ///
   /// In en, this message translates to:
-  /// **'Are you sure you want to delete \"{name}\"?'**
-  String deleteBackupConfirm(String name);
+  /// **'YAAS {version} is available. Open the About page to download it.'**
+  String appUpdateAvailableDescription(String version);
 
-  /// No description provided for @backupDeletedTitle.
+  /// No description provided for @appUpdateNewVersion.
   ///
   /// In en, this message translates to:
-  /// **'Backup deleted'**
-  String get backupDeletedTitle;
+  /// **'Version {version} is available'**
+  String appUpdateNewVersion(String version);
 
-  /// No description provided for @fatalErrorTitle.
+  /// No description provided for @appUpdateCheckFailed.
   ///
   /// In en, this message translates to:
-  /// **'Fatal Error'**
-  String get fatalErrorTitle;
+  /// **'Update check failed: {error}'**
+  String appUpdateCheckFailed(String error);
 
-  /// No description provided for @exitApplication.
+  /// No description provided for @appUpdateDownloaded.
   ///
   /// In en, this message translates to:
-  /// **'Exit Application'**
-  String get exitApplication;
+  /// **'Downloaded to {path}'**
+  String appUpdateDownloaded(String path);
 
-  /// No description provided for @errorCopied.
+  /// No description provided for @appUpdateDownloadFailed.
   ///
   /// In en, this message translates to:
-  /// **'Error message copied to clipboard'**
-  String get errorCopied;
+  /// **'Download failed: {error}'**
+  String appUpdateDownloadFailed(String error);
 
   /// No description provided for @copyError.
   ///
@@ -2489,7 +4727,7 @@ abstract class AppLocalizations {
   /// No description provided for @selectApkFileTitle.
   ///
   /// In en, this message translates to:
-  /// **'Select APK file'**
+  /// **'Select APK file or bundle'**
   String get selectApkFileTitle;
 
   /// No description provided for @selectedInvalidDir.
@@ -2501,7 +4739,7 @@ abstract class AppLocalizations {
   /// No description provided for @selectedInvalidApk.
   ///
   /// In en, this message translates to:
-  /// **'Selected path is not a valid APK file'**
+  /// **'Selected path is not a valid APK file or bundle'**
   String get selectedInvalidApk;
 
   /// No description provided for @singleApk.
@@ -2537,7 +4775,7 @@ abstract class AppLocalizations {
   /// No description provided for @pathHintApk.
   ///
   /// In en, this message translates to:
-  /// **'Select or enter APK file path'**
+  /// **'Select or enter APK or XAPK/APKS/APKM bundle path'**
   String get pathHintApk;
 
   /// No description provided for @directoryRequirements.
@@ -2570,6 +4808,42 @@ abstract class AppLocalizations {
   /// **'Install APK'**
   String get installApk;
 
+  /// No description provided for @apkPreviewNoNativeLibs.
+  ///
+  /// In en, this message translates to:
+  /// **'No native libraries'**
+  String get apkPreviewNoNativeLibs;
+
+  /// No description provided for @apkPreviewFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Could not read APK: {error}'**
+  String apkPreviewFailed(String error);
+
+  /// No description provided for @apkPreviewVersion.
+  ///
+  /// In en, this message translates to:
+  /// **'Version {version}'**
+  String apkPreviewVersion(String version);
+
+  /// No description provided for @apkPreviewSdk.
+  ///
+  /// In en, this message translates to:
+  /// **'Android SDK: min {min}, target {target}'**
+  String apkPreviewSdk(String min, String target);
+
+  /// No description provided for @apkPreviewAbis.
+  ///
+  /// In en, this message translates to:
+  /// **'Native libraries: {abis}'**
+  String apkPreviewAbis(String abis);
+
+  /// No description provided for @apkPreviewPermissions.
+  ///
+  /// In en, this message translates to:
+  /// **'Requested permissions ({count})'**
+  String apkPreviewPermissions(String count);
+
   /// No description provided for @tasksTitle.
   ///
   /// In en, this message translates to:
@@ -2600,6 +4874,54 @@ abstract class AppLocalizations {
   /// **'No recent tasks'**
   String get tasksEmptyRecent;
 
+  /// No description provided for @tasksTabHistory.
+  ///
+  /// In en, this message translates to:
+  /// **'History'**
+  String get tasksTabHistory;
+
+  /// No description provided for @tasksEmptyHistory.
+  ///
+  /// In en, this message translates to:
+  /// **'No operations recorded yet'**
+  String get tasksEmptyHistory;
+
+  /// No description provided for @historyLoadFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Could not load history: {error}'**
+  String historyLoadFailed(String error);
+
+  /// No description provided for @historyActionInstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Install'**
+  String get historyActionInstall;
+
+  /// No description provided for @historyActionUninstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Uninstall'**
+  String get historyActionUninstall;
+
+  /// No description provided for @historyActionBackup.
+  ///
+  /// In en, this message translates to:
+  /// **'Backup'**
+  String get historyActionBackup;
+
+  /// No description provided for @historyActionRestore.
+  ///
+  /// In en, this message translates to:
+  /// **'Restore'**
+  String get historyActionRestore;
+
+  /// No description provided for @historyActionDownload.
+  ///
+  /// In en, this message translates to:
+  /// **'Download'**
+  String get historyActionDownload;
+
   /// No description provided for @clearRecentTasks.
   ///
   /// In en, this message translates to:
@@ -2618,6 +4940,30 @@ abstract class AppLocalizations {
   /// **'Cancel Task'**
   String get cancelTask;
 
+  /// No description provided for @taskPriority.
+  ///
+  /// In en, this message translates to:
+  /// **'Priority'**
+  String get taskPriority;
+
+  /// No description provided for @taskPriorityHigh.
+  ///
+  /// In en, this message translates to:
+  /// **'High'**
+  String get taskPriorityHigh;
+
+  /// No description provided for @taskPriorityNormal.
+  ///
+  /// In en, this message translates to:
+  /// **'Normal'**
+  String get taskPriorityNormal;
+
+  /// No description provided for @taskPriorityLow.
+  ///
+  /// In en, this message translates to:
+  /// **'Low'**
+  String get taskPriorityLow;
+
   /// No description provided for @taskKindDownload.
   ///
   /// In en, this message translates to:
@@ -2666,6 +5012,54 @@ abstract class AppLocalizations {
   /// **'Donate App'**
   String get taskKindDonateApp;
 
+  /// No description provided for @taskKindResumeDownload.
+  ///
+  /// In en, this message translates to:
+  /// **'Resume Download'**
+  String get taskKindResumeDownload;
+
+  /// No description provided for @taskKindCaptureModSet.
+  ///
+  /// In en, this message translates to:
+  /// **'Capture Mod Set'**
+  String get taskKindCaptureModSet;
+
+  /// No description provided for @taskKindApplyModSet.
+  ///
+  /// In en, this message translates to:
+  /// **'Apply Mod Set'**
+  String get taskKindApplyModSet;
+
+  /// No description provided for @taskKindBackupAll.
+  ///
+  /// In en, this message translates to:
+  /// **'Backup All Apps'**
+  String get taskKindBackupAll;
+
+  /// No description provided for @taskKindPushObb.
+  ///
+  /// In en, this message translates to:
+  /// **'Push OBB'**
+  String get taskKindPushObb;
+
+  /// No description provided for @taskKindReinstallFromTrash.
+  ///
+  /// In en, this message translates to:
+  /// **'Reinstall From Trash'**
+  String get taskKindReinstallFromTrash;
+
+  /// No description provided for @taskKindReceiveFromPeer.
+  ///
+  /// In en, this message translates to:
+  /// **'Receive From Peer'**
+  String get taskKindReceiveFromPeer;
+
+  /// No description provided for @taskKindTransferAppData.
+  ///
+  /// In en, this message translates to:
+  /// **'Transfer App Data'**
+  String get taskKindTransferAppData;
+
   /// No description provided for @taskStatusWaiting.
   ///
   /// In en, this message translates to:
@@ -2702,12 +5096,150 @@ abstract class AppLocalizations {
   /// **'Unknown'**
   String get taskUnknown;
 
+  /// No description provided for @taskRestored.
+  ///
+  /// In en, this message translates to:
+  /// **'Resumed from previous session'**
+  String get taskRestored;
+
+  /// No description provided for @tasksResumedTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Resumed {count} unfinished tasks'**
+  String tasksResumedTitle(String count);
+
+  /// No description provided for @tasksResumedDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'Click to open the task list'**
+  String get tasksResumedDescription;
+
+  /// No description provided for @taskDownloadSummary.
+  ///
+  /// In en, this message translates to:
+  /// **'{size} in {duration} · {average}/s average, {peak}/s peak'**
+  String taskDownloadSummary(String size, String duration, String average, String peak);
+
+  /// No description provided for @taskDownloadSource.
+  ///
+  /// In en, this message translates to:
+  /// **'Source: {source}'**
+  String taskDownloadSource(String source);
+
+  /// No description provided for @taskDownloadRetries.
+  ///
+  /// In en, this message translates to:
+  /// **'Resumed after {count} interrupted attempts'**
+  String taskDownloadRetries(String count);
+
+  /// No description provided for @taskTimeLeft.
+  ///
+  /// In en, this message translates to:
+  /// **'{time} left'**
+  String taskTimeLeft(String time);
+
+  /// No description provided for @taskTransferBytes.
+  ///
+  /// In en, this message translates to:
+  /// **'{done} / {total}'**
+  String taskTransferBytes(String done, String total);
+
+  /// No description provided for @taskTransferSpeed.
+  ///
+  /// In en, this message translates to:
+  /// **'{speed}/s'**
+  String taskTransferSpeed(String speed);
+
+  /// No description provided for @taskTransferFiles.
+  ///
+  /// In en, this message translates to:
+  /// **'file {done} of {total}'**
+  String taskTransferFiles(String done, String total);
+
+  /// No description provided for @transferStatsTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Transfer statistics'**
+  String get transferStatsTitle;
+
+  /// No description provided for @transferStatsSession.
+  ///
+  /// In en, this message translates to:
+  /// **'This session'**
+  String get transferStatsSession;
+
+  /// No description provided for @transferStatsLifetime.
+  ///
+  /// In en, this message translates to:
+  /// **'Since {date}'**
+  String transferStatsLifetime(String date);
+
+  /// No description provided for @transferStatsDownloaded.
+  ///
+  /// In en, this message translates to:
+  /// **'Downloaded'**
+  String get transferStatsDownloaded;
+
+  /// No description provided for @transferStatsPushed.
+  ///
+  /// In en, this message translates to:
+  /// **'Pushed to devices'**
+  String get transferStatsPushed;
+
+  /// No description provided for @transferStatsDownloads.
+  ///
+  /// In en, this message translates to:
+  /// **'Downloads completed'**
+  String get transferStatsDownloads;
+
+  /// No description provided for @transferStatsInstalls.
+  ///
+  /// In en, this message translates to:
+  /// **'Installs completed'**
+  String get transferStatsInstalls;
+
+  /// No description provided for @transferStatsAverageSpeed.
+  ///
+  /// In en, this message translates to:
+  /// **'{size} at {speed}/s on average'**
+  String transferStatsAverageSpeed(String size, String speed);
+
+  /// No description provided for @transferStatsReset.
+  ///
+  /// In en, this message translates to:
+  /// **'Reset lifetime totals'**
+  String get transferStatsReset;
+
   /// No description provided for @backupOptionsTitle.
   ///
   /// In en, this message translates to:
   /// **'Backup Options'**
   String get backupOptionsTitle;
 
+  /// No description provided for @backupAllAppsTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Backup All Apps'**
+  String get backupAllAppsTitle;
+
+  /// No description provided for @backupAllApps.
+  ///
+  /// In en, this message translates to:
+  /// **'Back up all apps'**
+  String get backupAllApps;
+
+  /// No description provided for @pushObb.
+  ///
+  /// In en, this message translates to:
+  /// **'Push OBB files from a folder'**
+  String get pushObb;
+
+  /// No description provided for @pushObbSelectFolder.
+  ///
+  /// In en, this message translates to:
+  /// **'Select the OBB folder for {app}'**
+  String pushObbSelectFolder(String app);
+
   /// No description provided for @backupSelectParts.
   ///
   /// In en, this message translates to:
@@ -2744,6 +5276,30 @@ abstract class AppLocalizations {
   /// **'e.g. pre-update'**
   String get backupNameSuffixHint;
 
+  /// No description provided for @backupCompress.
+  ///
+  /// In en, this message translates to:
+  /// **'Compress into a single file'**
+  String get backupCompress;
+
+  /// No description provided for @backupEstimating.
+  ///
+  /// In en, this message translates to:
+  /// **'Measuring backup size…'**
+  String get backupEstimating;
+
+  /// No description provided for @backupEstimate.
+  ///
+  /// In en, this message translates to:
+  /// **'Estimated size: {size}, about {time}'**
+  String backupEstimate(String size, String time);
+
+  /// No description provided for @backupEstimateFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Could not estimate backup size: {error}'**
+  String backupEstimateFailed(String error);
+
   /// No description provided for @startBackup.
   ///
   /// In en, this message translates to:
@@ -2852,6 +5408,72 @@ abstract class AppLocalizations {
   /// **'Unable to open logs directory (copied to clipboard): {path}'**
   String logsOpenFailed(String path);
 
+  /// No description provided for @logHistory.
+  ///
+  /// In en, this message translates to:
+  /// **'Log history'**
+  String get logHistory;
+
+  /// No description provided for @logHistoryModuleHint.
+  ///
+  /// In en, this message translates to:
+  /// **'Module, e.g. hub::adb'**
+  String get logHistoryModuleHint;
+
+  /// No description provided for @logHistoryMinLevel.
+  ///
+  /// In en, this message translates to:
+  /// **'Minimum level'**
+  String get logHistoryMinLevel;
+
+  /// No description provided for @logHistoryAnyDate.
+  ///
+  /// In en, this message translates to:
+  /// **'Any date'**
+  String get logHistoryAnyDate;
+
+  /// No description provided for @logHistoryLoadMore.
+  ///
+  /// In en, this message translates to:
+  /// **'Load more'**
+  String get logHistoryLoadMore;
+
+  /// No description provided for @logHistoryEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'No log entries match the filters'**
+  String get logHistoryEmpty;
+
+  /// No description provided for @logHistoryCount.
+  ///
+  /// In en, this message translates to:
+  /// **'{shown} of {total} entries'**
+  String logHistoryCount(String shown, String total);
+
+  /// No description provided for @createDiagnosticsBundle.
+  ///
+  /// In en, this message translates to:
+  /// **'Create diagnostics bundle'**
+  String get createDiagnosticsBundle;
+
+  /// No description provided for @diagnosticsBundleCreating.
+  ///
+  /// In en, this message translates to:
+  /// **'Creating diagnostics bundle...'**
+  String get diagnosticsBundleCreating;
+
+  /// No description provided for @diagnosticsBundleSaved.
+  ///
+  /// In en, this message translates to:
+  /// **'Diagnostics bundle saved, path copied to clipboard'**
+  String get diagnosticsBundleSaved;
+
+  /// No description provided for @diagnosticsBundleFailed.
+  ///
+  /// In en, this message translates to:
+  /// **'Failed to create diagnostics bundle: {error}'**
+  String diagnosticsBundleFailed(String error);
+
   /// No description provided for @uninstallAppTitle.
   ///
   /// In en, this message translates to:
@@ -3021,6 +5643,78 @@ abstract class AppLocalizations {
   /// **'Donate'**
   String get donateDonateButton;
 
+  /// No description provided for @donationUploadedTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Uploaded {archive}'**
+  String donationUploadedTitle(String archive);
+
+  /// No description provided for @donationUploadedDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'Thank you for donating! MD5: {md5}'**
+  String donationUploadedDescription(String md5);
+
+  /// No description provided for @appTrashedTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'{name} moved to trash'**
+  String appTrashedTitle(String name);
+
+  /// No description provided for @appTrashedDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'Click to reinstall it with its data'**
+  String get appTrashedDescription;
+
+  /// No description provided for @trashTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Trash'**
+  String get trashTitle;
+
+  /// No description provided for @trashEmpty.
+  ///
+  /// In en, this message translates to:
+  /// **'The trash is empty'**
+  String get trashEmpty;
+
+  /// No description provided for @trashTrashedAt.
+  ///
+  /// In en, this message translates to:
+  /// **'Moved to trash {date}'**
+  String trashTrashedAt(String date);
+
+  /// No description provided for @trashExpires.
+  ///
+  /// In en, this message translates to:
+  /// **'deleted after {date}'**
+  String trashExpires(String date);
+
+  /// No description provided for @trashReinstall.
+  ///
+  /// In en, this message translates to:
+  /// **'Reinstall with data'**
+  String get trashReinstall;
+
+  /// No description provided for @trashDelete.
+  ///
+  /// In en, this message translates to:
+  /// **'Delete from trash'**
+  String get trashDelete;
+
+  /// No description provided for @trashDeleteTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Delete {name} from trash?'**
+  String trashDeleteTitle(String name);
+
+  /// No description provided for @trashDeletePrompt.
+  ///
+  /// In en, this message translates to:
+  /// **'The backup of its APK and data will be deleted.'**
+  String get trashDeletePrompt;
+
   /// No description provided for @donateNoAppsAvailable.
   ///
   /// In en, this message translates to:
@@ -3111,6 +5805,22 @@ abstract class AppLocalizations {
   /// **'Continue anyway'**
   String get lowSpaceWarningContinue;
 
+  /// No description provided for @insufficientSpaceTitle.
+  ///
+  /// In en, this message translates to:
+  /// **'Not enough space on the device'**
+  String get insufficientSpaceTitle;
+
+  /// No description provided for @insufficientSpaceDescription.
+  ///
+  /// In en, this message translates to:
+  /// **'Needs {needed}, but only {available} is available. Free up at least {missing}.'**
+  String insufficientSpaceDescription(
+    String needed,
+    String available,
+    String missing,
+  );
+
   /// No description provided for @proximityStatusEnabled.
   ///
   /// In en, this message translates to:
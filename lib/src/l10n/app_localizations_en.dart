@@ -92,9 +92,63 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get settingsDownloadsLocation => 'Downloads Location';
 
+  @override
+  String get settingsDownloadLayout => 'Download Folder Layout';
+
+  @override
+  String settingsDownloadLayoutHelp(String placeholders) {
+    return 'Folders apps are sorted into, separated by /. Supports $placeholders. Leave empty to keep all apps directly in the downloads location.';
+  }
+
+  @override
+  String get settingsDownloadLayoutPresets => 'Layout presets';
+
+  @override
+  String get settingsDownloadLayoutFlat => 'Flat';
+
+  @override
+  String get settingsDownloadLayoutByLetter => 'By first letter';
+
+  @override
+  String get settingsDownloadLayoutByName => 'By app name';
+
+  @override
+  String get settingsDownloadLayoutByPackage => 'By package name';
+
   @override
   String get settingsBackupsLocation => 'Backups Location';
 
+  @override
+  String get settingsBackupRetentionPerApp => 'Backups Kept per App';
+
+  @override
+  String get settingsBackupRetentionTotalSize => 'Maximum Total Backup Size';
+
+  @override
+  String get settingsBackupRetentionMaxAge => 'Delete Backups Older Than';
+
+  @override
+  String get settingsBackupRetentionUnlimited => 'No limit';
+
+  @override
+  String get settingsBackupRetentionHelp =>
+      'Older backups are deleted automatically. The newest backup of each app is always kept.';
+
+  @override
+  String settingsBackupRetentionDays(String days) {
+    return '$days days';
+  }
+
+  @override
+  String get settingsTrashBeforeUninstall => 'Move Uninstalled Apps to Trash';
+
+  @override
+  String get settingsTrashBeforeUninstallHelp =>
+      'Back up the APK and data before uninstalling, so the app can be reinstalled';
+
+  @override
+  String get settingsTrashRetention => 'Keep Apps in Trash For';
+
   @override
   String get settingsSectionAdb => 'ADB';
 
@@ -117,6 +171,27 @@ class AppLocalizationsEn extends AppLocalizations {
   String get settingsMdnsAutoConnectHelp =>
       'Discover devices via mDNS on the local network and attempt \'adb connect\' automatically. Takes effect after restart.';
 
+  @override
+  String get settingsReconnectWirelessDevices => 'Reconnect wireless devices';
+
+  @override
+  String get settingsReconnectWirelessDevicesHelp =>
+      'Keep Wi-Fi connections alive and reconnect to the last wireless headset when no device is connected';
+
+  @override
+  String get settingsStopForeignAdbServers => 'Stop ADB servers of other apps';
+
+  @override
+  String get settingsStopForeignAdbServersHelp =>
+      'Apps bundling their own ADB, such as ALVR, can drop the device connection while both servers run';
+
+  @override
+  String get settingsShellConsole => 'Shell console';
+
+  @override
+  String get settingsShellConsoleHelp =>
+      'Allow running any shell command on the connected device. Careless commands can break the device';
+
   @override
   String get settingsAutoReinstallOnConflict =>
       'Auto reinstall on incompatible update';
@@ -125,6 +200,116 @@ class AppLocalizationsEn extends AppLocalizations {
   String get settingsAutoReinstallOnConflictHelp =>
       'Automatically backup data, reinstall, and restore when encountering version conflicts (downgrade or incompatible update). Requires the app to be debuggable.';
 
+  @override
+  String get settingsAutoApproveInstallScripts =>
+      'Run install scripts without asking';
+
+  @override
+  String get settingsAutoApproveInstallScriptsHelp =>
+      'Skip the review of the commands an install.txt runs on the device. Scripts from untrusted sources can run any shell command.';
+
+  @override
+  String get settingsWakeOfflineDevices => 'Wake Offline Devices';
+
+  @override
+  String get settingsWakeOfflineDevicesHelp =>
+      'When a headset is plugged in but stays offline (often until it is worn), reconnect it, send the wake action and connect to it.';
+
+  @override
+  String get settingsDeviceAgent => 'Install device helper';
+
+  @override
+  String get settingsDeviceAgentHelp =>
+      'Installs and updates a small helper app on connected headsets for exact app sizes and usage stats. Without it, the usual shell commands are used.';
+
+  @override
+  String get settingsWatchPackageChanges => 'Watch for app changes';
+
+  @override
+  String get settingsWatchPackageChangesHelp =>
+      'Updates the installed apps list as soon as apps are installed, updated or removed on the headset, including outside of this app. Runs a lightweight check on the headset while connected.';
+
+  @override
+  String get settingsProbeWirelessLink =>
+      'Check Wi-Fi speed before large installs';
+
+  @override
+  String get settingsProbeWirelessLinkHelp =>
+      'Measures the wireless connection before installing large apps over Wi-Fi and warns when it is slow';
+
+  @override
+  String get settingsOfflineWakeAction => 'Wake Action';
+
+  @override
+  String get settingsOfflineWakeActionKeyevent => 'Wake key event';
+
+  @override
+  String get settingsOfflineWakeActionProximity =>
+      'Proximity sensor (as if worn)';
+
+  @override
+  String get settingsDeviceReconnectGrace => 'Reconnect grace period';
+
+  @override
+  String get settingsDeviceReconnectGraceHelp =>
+      'How long a device that briefly drops off (e.g. on sleep and wake) stays connected before it is disconnected';
+
+  @override
+  String get settingsDeviceReconnectGraceOff => 'Off';
+
+  @override
+  String get settingsCheckForUpdates => 'Check for updates on startup';
+
+  @override
+  String get settingsLogFileFormat => 'Log file format';
+
+  @override
+  String get settingsLogFileFormatHelp =>
+      'JSON lines files can be ingested by external log tools';
+
+  @override
+  String get settingsLogFileFormatPretty => 'Readable text';
+
+  @override
+  String get settingsLogFileFormatJson => 'JSON lines';
+
+  @override
+  String get settingsLogFileFormatBoth => 'Text and JSON lines';
+
+  @override
+  String get settingsSectionTasks => 'Tasks';
+
+  @override
+  String get settingsMaxConcurrentDownloads => 'Simultaneous downloads';
+
+  @override
+  String get settingsMaxConcurrentAdbOperations =>
+      'Simultaneous device operations';
+
+  @override
+  String get settingsMaxConcurrentAdbOperationsHelp =>
+      'Installs, backups and restores share this limit. Running several at once may slow down or destabilize the device.';
+
+  @override
+  String get settingsExtractionThreads => 'Archive extraction threads';
+
+  @override
+  String get settingsExtractionThreadsHelp =>
+      'Threads used to unpack zip and 7z archives before installing. More threads finish large archives faster but use more CPU.';
+
+  @override
+  String get settingsExtractionThreadsAuto => 'Automatic';
+
+  @override
+  String get settingsTaskKindLimits => 'Limits per task type';
+
+  @override
+  String get settingsTaskKindLimitsHelp =>
+      'Tasks of a type beyond its limit wait until another one finishes';
+
+  @override
+  String get settingsTaskKindUnlimited => 'No limit';
+
   @override
   String get settingsSectionDownloader => 'Downloader';
 
@@ -339,6 +524,52 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get settingsNoRemotesFound => 'No remotes found';
 
+  @override
+  String get customRemotes => 'Custom remotes';
+
+  @override
+  String get customRemotesEmpty =>
+      'No custom remotes yet. Added remotes can be selected as the rclone remote.';
+
+  @override
+  String get customRemoteAdd => 'Add remote';
+
+  @override
+  String get customRemoteEdit => 'Edit remote';
+
+  @override
+  String get customRemoteDelete => 'Delete remote';
+
+  @override
+  String get customRemoteName => 'Name';
+
+  @override
+  String get customRemoteKind => 'Type';
+
+  @override
+  String get customRemoteEndpointUrl => 'URL';
+
+  @override
+  String get customRemoteEndpointHost => 'Host';
+
+  @override
+  String get customRemoteUsername => 'User name or access key ID';
+
+  @override
+  String get customRemotePassword => 'Password or secret key';
+
+  @override
+  String get customRemoteKeepPassword =>
+      'Leave empty to keep the saved password';
+
+  @override
+  String get customRemoteSave => 'Save';
+
+  @override
+  String customRemoteSaveFailed(String error) {
+    return 'Failed to save remote: $error';
+  }
+
   @override
   String get settingsFailedToLoadRemotes => 'Failed to list remotes';
 
@@ -349,9 +580,54 @@ class AppLocalizationsEn extends AppLocalizations {
   String get settingsBandwidthHelper =>
       'Value in KiB/s or with B|K|M|G|T|P suffix or more (click for documentation)';
 
+  @override
+  String get settingsDownloadMirrors => 'Fallback mirrors';
+
+  @override
+  String get settingsDownloadMirrorsRemotesHelp =>
+      'Comma-separated remotes tried in order when a download fails or stays slow';
+
+  @override
+  String get settingsDownloadMirrorsUrlsHelp =>
+      'Comma-separated base URLs tried in order when a download fails or stays slow';
+
+  @override
+  String get settingsMirrorMinSpeed => 'Switch mirrors below';
+
+  @override
+  String get settingsMirrorMinSpeedOff => 'Only on failure';
+
+  @override
+  String settingsMirrorSpeedKbps(String speed) {
+    return '$speed KB/s';
+  }
+
+  @override
+  String get settingsMirrorSlowFor => 'Slow for at least';
+
+  @override
+  String settingsMirrorSlowSeconds(String seconds) {
+    return '$seconds s';
+  }
+
   @override
   String get settingsDownloadsCleanup => 'Downloads Cleanup';
 
+  @override
+  String get settingsCastingDownloads => 'Downloads While Casting';
+
+  @override
+  String get settingsCastingDownloadsIgnore => 'Keep downloading';
+
+  @override
+  String get settingsCastingDownloadsThrottle => 'Limit speed';
+
+  @override
+  String get settingsCastingDownloadsPause => 'Pause downloads';
+
+  @override
+  String get settingsCastingBandwidthLimit => 'Download Limit While Casting';
+
   @override
   String get settingsDownloadMode => 'Download Mode';
 
@@ -374,6 +650,13 @@ class AppLocalizationsEn extends AppLocalizations {
   String get settingsWriteLegacyReleaseJsonHelp =>
       'Write release.json in legacy format for compatibility with QLoader';
 
+  @override
+  String get settingsDedupeDownloads => 'Deduplicate downloads';
+
+  @override
+  String get settingsDedupeDownloadsHelp =>
+      'Link large files shared by several downloaded versions to a single copy on disk';
+
   @override
   String get settingsCleanupDeleteAfterInstall => 'Remove after installation';
 
@@ -386,6 +669,14 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get settingsCleanupKeepAllVersions => 'Keep all versions';
 
+  @override
+  String get settingsCleanupMoveAfterInstall =>
+      'Move to another folder after installation';
+
+  @override
+  String get settingsInstalledDownloadsLocation =>
+      'Installed downloads location';
+
   @override
   String get settingsBrowse => 'Browse';
 
@@ -435,6 +726,31 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get statusAdbStateOffline => 'Offline';
 
+  @override
+  String get statusAdbWakeDevice => 'Select to try waking it';
+
+  @override
+  String get wakeStepReconnect => 'Reconnect offline devices';
+
+  @override
+  String get wakeStepKeyevent => 'Send wake key event';
+
+  @override
+  String get wakeStepProximity => 'Send proximity sensor broadcast';
+
+  @override
+  String get wakeStepConnect => 'Connect';
+
+  @override
+  String deviceWakeRecovered(String device) {
+    return '$device is back online';
+  }
+
+  @override
+  String deviceWakeFailed(String device) {
+    return 'Could not wake $device';
+  }
+
   @override
   String get statusAdbStateBootloader => 'Bootloader';
 
@@ -530,7 +846,7 @@ class AppLocalizationsEn extends AppLocalizations {
       'Dropped directory is not a valid app directory or backup folder';
 
   @override
-  String get dragDropInvalidFile => 'Dropped file is not a valid APK file';
+  String get dragDropInvalidFile => 'Dropped file is not a valid APK file or bundle';
 
   @override
   String get dragDropDownloaderConfigTitle => 'Downloader config';
@@ -593,6 +909,17 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get batteryDumpFailed => 'Failed to obtain battery state dump';
 
+  @override
+  String get batteryHistoryTitle => 'Battery history';
+
+  @override
+  String get batteryHistoryEmpty =>
+      'Battery levels are sampled every minute while the device is connected. Check back in a few minutes.';
+
+  @override
+  String get batteryHistoryHint =>
+      'Click to copy the battery dump, hold to show the battery history';
+
   @override
   String get commonSuccess => 'Success';
 
@@ -605,6 +932,32 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get diagnosticsAdbServer => 'ADB server';
 
+  @override
+  String get diagnosticsAdbHealth => 'ADB watchdog';
+
+  @override
+  String get diagnosticsAdbHealthy =>
+      'ADB server and device tracking are responding';
+
+  @override
+  String get diagnosticsAdbUnresponsive =>
+      'ADB server is not responding, it will be restarted if this continues';
+
+  @override
+  String diagnosticsAdbRecovering(String attempt) {
+    return 'Restarted the ADB server (attempt $attempt)';
+  }
+
+  @override
+  String diagnosticsAdbRecoveryFailed(String error) {
+    return 'Failed to restart the ADB server: $error';
+  }
+
+  @override
+  String diagnosticsAdbRetryIn(String seconds) {
+    return 'Next attempt in $seconds s';
+  }
+
   @override
   String get diagnosticsDevices => 'Devices';
 
@@ -614,6 +967,25 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get diagnosticsActiveDevice => 'Active device';
 
+  @override
+  String get diagnosticsHostConflicts => 'Streaming apps';
+
+  @override
+  String get diagnosticsHostConflictsNone =>
+      'No streaming apps or other ADB servers running';
+
+  @override
+  String diagnosticsStreamingClientsRunning(String apps) {
+    return '$apps running. Streaming shares the USB connection, so transfers may be slower or get interrupted.';
+  }
+
+  @override
+  String get diagnosticsForeignAdbServers =>
+      'ADB servers of other apps are running and may drop the device connection:';
+
+  @override
+  String get diagnosticsStopForeignAdbServers => 'Stop other ADB servers';
+
   @override
   String get diagnosticsAdbPath => 'ADB path';
 
@@ -695,85 +1067,509 @@ class AppLocalizationsEn extends AppLocalizations {
   String get guardianStatusActive => 'Active';
 
   @override
-  String get deviceWirelessAdb => 'Wireless ADB';
+  String get guardianPauseFor => 'Pause for a while';
 
   @override
-  String get deviceEnableWirelessAdb => 'Enable ADB over Wi‑Fi';
+  String get guardianPause15m => 'Pause for 15 minutes';
 
   @override
-  String get deviceStorageConnection => 'Storage connection';
+  String get guardianPause30m => 'Pause for 30 minutes';
 
   @override
-  String get deviceStorageConnectionTooltip => 'Connect storage with MTP.';
+  String get guardianPause1h => 'Pause for 1 hour';
 
   @override
-  String get deviceStorageResetTooltip => 'Reset USB functions.';
+  String get guardianPause2h => 'Pause for 2 hours';
 
   @override
-  String get deviceStorageStatusConnected => 'Connected';
+  String autoRevertIn(String time) {
+    return 'Restores in $time';
+  }
 
   @override
-  String get deviceStorageStatusDisconnected => 'Disconnected';
+  String get deviceWirelessAdb => 'Wireless ADB';
 
   @override
-  String get deviceStorageStatusUnknown => 'Unknown';
+  String get deviceMaintenanceLog => 'Maintenance log';
 
   @override
-  String get deviceStorageConnectedMessage =>
-      'MTP is enabled. The USB connection will restart.';
+  String get deviceTweaks => 'Rendering tweaks';
 
   @override
-  String get deviceStorageResetMessage =>
-      'USB functions were reset. The USB connection will restart.';
+  String get deviceTweaksHint =>
+      'Changes apply to apps started afterwards and reset when the headset reboots.';
 
   @override
-  String get deviceStorageConnectionFailed =>
-      'Failed to update the storage connection.';
+  String get deviceTweakDefault => 'Default';
 
   @override
-  String get usbSpeedLabel => 'USB speed';
+  String get deviceTweakRefreshRate => 'Refresh rate';
 
   @override
-  String get copiedToClipboard => 'Copied to clipboard';
+  String get deviceTweakCpuLevel => 'CPU level';
 
   @override
-  String get clickToCopy => 'Click to copy';
+  String get deviceTweakGpuLevel => 'GPU level';
 
   @override
-  String get detailsPackageName => 'Package Name:';
+  String get deviceTweakTextureSize => 'Texture size';
 
   @override
-  String get detailsVersion => 'Version:';
+  String get deviceTweakFoveation => 'Fixed foveation';
 
   @override
-  String get detailsVersionCode => 'Version Code:';
+  String get commandMacros => 'Command macros';
 
   @override
-  String get detailsIsVr => 'Is VR:';
+  String get shellConsole => 'Shell console';
 
   @override
-  String get detailsIsLaunchable => 'Is Launchable:';
+  String shellConsoleTitle(String serial) {
+    return 'Shell on $serial';
+  }
 
   @override
-  String get detailsIsSystem => 'Is System:';
+  String get shellConsoleOpening => 'Opening shell…';
 
   @override
-  String get detailsStorageUsage => 'Storage Usage:';
+  String get shellConsoleHint => 'Enter a command';
 
   @override
-  String get detailsApp => 'App:';
+  String shellConsoleExitCode(String code) {
+    return 'Exit code $code';
+  }
 
   @override
-  String get detailsData => 'Data:';
+  String get shellConsoleEndClosed => 'Session closed';
 
   @override
-  String get detailsCache => 'Cache:';
+  String get shellConsoleEndExited => 'Shell exited';
 
   @override
-  String get detailsTotal => 'Total:';
+  String get shellConsoleEndTimedOut => 'Command timed out, session closed';
 
   @override
-  String get detailsRating => 'Rating:';
+  String get shellConsoleEndIdle => 'Session closed after inactivity';
+
+  @override
+  String get shellConsoleEndDisabled =>
+      'Shell console was disabled in settings';
+
+  @override
+  String get shellConsoleReopen => 'Reopen';
+
+  @override
+  String get shellConsoleClear => 'Clear';
+
+  @override
+  String get diskUsage => 'Disk usage';
+
+  @override
+  String get diskUsageDownloads => 'Downloads';
+
+  @override
+  String get diskUsageMediaCache => 'Media cache';
+
+  @override
+  String get diskUsageLogs => 'Logs';
+
+  @override
+  String get diskUsageBackups => 'Backups';
+
+  @override
+  String get diskUsageTrash => 'Trash';
+
+  @override
+  String get diskUsageDonationUploads => 'Donation uploads';
+
+  @override
+  String get diskUsageTotal => 'Total';
+
+  @override
+  String get diskUsageClear => 'Clear';
+
+  @override
+  String diskUsageClearTitle(String category) {
+    return 'Clear $category?';
+  }
+
+  @override
+  String diskUsageClearPrompt(String category, String size, String path) {
+    return 'Delete $size of $category in $path? This cannot be undone.';
+  }
+
+  @override
+  String get storageMigrationTitle => 'Move storage';
+
+  @override
+  String get storageMigrationMoveDownloads => 'Move downloads…';
+
+  @override
+  String get storageMigrationMoveBackups => 'Move backups…';
+
+  @override
+  String storageMigrationMoving(String path) {
+    return 'Moving files to $path. Keep YAAS open until this finishes.';
+  }
+
+  @override
+  String storageMigrationEntry(String current, String total, String name) {
+    return '$current of $total: $name';
+  }
+
+  @override
+  String storageMigrationDone(String path) {
+    return 'All files were moved. The location is now $path.';
+  }
+
+  @override
+  String storageMigrationRolledBack(String path) {
+    return 'Files moved so far were returned. The location is still $path.';
+  }
+
+  @override
+  String storageMigrationUnchanged(String path) {
+    return 'The location is still $path.';
+  }
+
+  @override
+  String get settingsProfiles => 'Settings profiles';
+
+  @override
+  String get settingsProfilesEmpty =>
+      'No profiles yet. Save the current settings as a profile to switch back to them later.';
+
+  @override
+  String get settingsProfilesName => 'Profile name';
+
+  @override
+  String get settingsProfilesSaveCurrent => 'Save current';
+
+  @override
+  String get settingsProfilesActive => 'Active';
+
+  @override
+  String get settingsProfilesOverwrite => 'Replace with current settings';
+
+  @override
+  String get settingsProfilesDelete => 'Delete';
+
+  @override
+  String settingsProfilesDeleteTitle(String name) {
+    return 'Delete profile "$name"?';
+  }
+
+  @override
+  String get settingsCatalogBaseUrl => 'Catalog URL';
+
+  @override
+  String get settingsCatalogBaseUrlHelp =>
+      'Base URL of a self-hosted mirror or LAN cache. Leave empty to use the configured source.';
+
+  @override
+  String get settingsMediaBaseUrl => 'Media URL';
+
+  @override
+  String get settingsMediaBaseUrlHelp =>
+      'Server thumbnails and trailers are loaded from. Leave empty to use the default one.';
+
+  @override
+  String get settingsPinnedSpki => 'Pinned server keys';
+
+  @override
+  String get settingsPinnedSpkiHelp =>
+      'Comma-separated sha256/<base64> SPKI hashes. When set, the self-hosted catalog and media servers must use https and present one of these keys.';
+
+  @override
+  String get settingsTestConnection => 'Test connection';
+
+  @override
+  String get settingsTestConnectionOk => 'Server is reachable';
+
+  @override
+  String settingsTestConnectionDetails(String status, String ms) {
+    return 'HTTP $status in $ms ms';
+  }
+
+  @override
+  String get settingsTestConnectionTimeout => 'No answer from the server';
+
+  @override
+  String get lanShareTitle => 'Local network sharing';
+
+  @override
+  String get lanShareApp => 'Share on local network';
+
+  @override
+  String get lanShareOwn => 'Shared by you';
+
+  @override
+  String get lanShareOwnEmpty =>
+      'Nothing shared yet. Use the share button on a download to offer it to others on your network.';
+
+  @override
+  String get lanShareStop => 'Stop sharing';
+
+  @override
+  String get lanSharePeers => 'Shared by others';
+
+  @override
+  String get lanSharePeersEmpty =>
+      'Looking for other YAAS instances on your network...';
+
+  @override
+  String get lanShareReceive => 'Receive';
+
+  @override
+  String get lanShareQueued => 'Queued';
+
+  @override
+  String get transferAppData => 'Transfer to another headset';
+
+  @override
+  String transferAppDataTitle(String name) {
+    return 'Transfer $name';
+  }
+
+  @override
+  String get transferAppDataDescription =>
+      'The app is backed up with its APK, data and OBB files now. The backup is restored once the selected headset is the connected device, so you can switch headsets after the backup finishes.';
+
+  @override
+  String get transferAppDataNoTargets =>
+      'No other headsets found. Connect the target headset to this computer first.';
+
+  @override
+  String get transferAppDataConflictWarning =>
+      'If the app is already installed on the target headset, you are asked before its data there is replaced, and that data is backed up first. A newer installed version is kept and only the data is restored.';
+
+  @override
+  String transferOverwriteTitle(String target) {
+    return 'Replace data on $target?';
+  }
+
+  @override
+  String transferOverwritePrompt(String app, String target) {
+    return '$app is already installed on $target. Its current data there is backed up first, then replaced with the transferred data.';
+  }
+
+  @override
+  String get transferOverwriteReplace => 'Replace';
+
+  @override
+  String get commandMacrosEmpty =>
+      'No macros yet. A macro runs several device commands in a row.';
+
+  @override
+  String get commandMacroNew => 'New macro';
+
+  @override
+  String get commandMacroEdit => 'Edit macro';
+
+  @override
+  String get commandMacroName => 'Name';
+
+  @override
+  String get commandMacroNameTaken => 'A macro with this name already exists';
+
+  @override
+  String get commandMacroRun => 'Run';
+
+  @override
+  String get commandMacroStop => 'Stop';
+
+  @override
+  String get commandMacroDelete => 'Delete macro';
+
+  @override
+  String get commandMacroSave => 'Save';
+
+  @override
+  String get commandMacroAddStep => 'Add step';
+
+  @override
+  String get commandMacroNoSteps => 'Add at least one step.';
+
+  @override
+  String commandMacroStepCount(String count) {
+    return 'Steps: $count';
+  }
+
+  @override
+  String commandMacroFailed(String error) {
+    return 'Macro failed: $error';
+  }
+
+  @override
+  String get commandMacroPackageName => 'Package name';
+
+  @override
+  String get commandMacroDelaySeconds => 'Delay in seconds';
+
+  @override
+  String commandMacroStepLaunch(String package) {
+    return 'Launch $package';
+  }
+
+  @override
+  String commandMacroStepForceStop(String package) {
+    return 'Force stop $package';
+  }
+
+  @override
+  String commandMacroStepDelay(String seconds) {
+    return 'Wait $seconds s';
+  }
+
+  @override
+  String get commandMacroStepGuardianPause => 'Pause Guardian';
+
+  @override
+  String get commandMacroStepGuardianResume => 'Resume Guardian';
+
+  @override
+  String get commandMacroStepProximityOff => 'Disable proximity sensor';
+
+  @override
+  String get commandMacroStepProximityOn => 'Enable proximity sensor';
+
+  @override
+  String get commandMacroStepRefresh => 'Refresh device';
+
+  @override
+  String get commandMacroStepReboot => 'Reboot';
+
+  @override
+  String commandMacroStepOther(String command) {
+    return 'Run $command';
+  }
+
+  @override
+  String get deviceAgentNotInstalled => 'Device helper: not installed';
+
+  @override
+  String get deviceAgentInstall => 'Install device helper';
+
+  @override
+  String get deviceAgentUpdate => 'Update device helper';
+
+  @override
+  String get deviceAgentRemove => 'Remove device helper';
+
+  @override
+  String deviceAgentInstalled(String version) {
+    return 'Device helper: version $version';
+  }
+
+  @override
+  String get deviceLogNoteHint => 'Add a note';
+
+  @override
+  String get deviceLogAddNote => 'Add';
+
+  @override
+  String get deviceLogEmpty => 'No entries yet';
+
+  @override
+  String get deviceLogDeleteEntry => 'Delete entry';
+
+  @override
+  String deviceLogTitle(String device) {
+    return 'Maintenance log: $device';
+  }
+
+  @override
+  String get deviceEnableWirelessAdb => 'Enable ADB over Wi‑Fi';
+
+  @override
+  String get deviceStorageConnection => 'Storage connection';
+
+  @override
+  String get deviceStorageConnectionTooltip => 'Connect storage with MTP.';
+
+  @override
+  String get deviceStorageResetTooltip => 'Reset USB functions.';
+
+  @override
+  String get deviceStorageStatusConnected => 'Connected';
+
+  @override
+  String get deviceStorageStatusDisconnected => 'Disconnected';
+
+  @override
+  String get deviceStorageStatusUnknown => 'Unknown';
+
+  @override
+  String get deviceStorageConnectedMessage =>
+      'MTP is enabled. The USB connection will restart.';
+
+  @override
+  String get deviceStorageResetMessage =>
+      'USB functions were reset. The USB connection will restart.';
+
+  @override
+  String get deviceStorageConnectionFailed =>
+      'Failed to update the storage connection.';
+
+  @override
+  String get usbSpeedLabel => 'USB speed';
+
+  @override
+  String get osVersionLabel => 'OS version';
+
+  @override
+  String get androidVersionLabel => 'Android version';
+
+  @override
+  String get buildFingerprintLabel => 'Build fingerprint';
+
+  @override
+  String get copiedToClipboard => 'Copied to clipboard';
+
+  @override
+  String get clickToCopy => 'Click to copy';
+
+  @override
+  String get detailsPackageName => 'Package Name:';
+
+  @override
+  String get detailsVersion => 'Version:';
+
+  @override
+  String get detailsVersionCode => 'Version Code:';
+
+  @override
+  String get detailsIsVr => 'Is VR:';
+
+  @override
+  String get detailsIsLaunchable => 'Is Launchable:';
+
+  @override
+  String get detailsIsSystem => 'Is System:';
+
+  @override
+  String get detailsPinnedVersion => 'Pinned version:';
+
+  @override
+  String get detailsIgnoredVersions => 'Ignored versions:';
+
+  @override
+  String get detailsStorageUsage => 'Storage Usage:';
+
+  @override
+  String get detailsApp => 'App:';
+
+  @override
+  String get detailsData => 'Data:';
+
+  @override
+  String get detailsCache => 'Cache:';
+
+  @override
+  String get detailsTotal => 'Total:';
+
+  @override
+  String get detailsRating => 'Rating:';
 
   @override
   String get detailsReviewsTitle => 'Recent reviews';
@@ -829,6 +1625,172 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get commonClose => 'Close';
 
+  @override
+  String get crashReportCollect => 'Crash report';
+
+  @override
+  String get appPermissions => 'Permissions';
+
+  @override
+  String get installRecipe => 'Post-install recipe';
+
+  @override
+  String get versionPin => 'Pin version';
+
+  @override
+  String get versionUnpin => 'Unpin version';
+
+  @override
+  String updateIgnoreVersion(String version) {
+    return 'Ignore version $version';
+  }
+
+  @override
+  String get updateStopIgnoring => 'Offer ignored versions again';
+
+  @override
+  String get appRename => 'Rename';
+
+  @override
+  String appRenameTitle(String name) {
+    return 'Rename $name';
+  }
+
+  @override
+  String get appRenameHint => 'Name shown in the catalog and app lists';
+
+  @override
+  String get appRenameReset => 'Restore original name';
+
+  @override
+  String get appRenameSave => 'Save';
+
+  @override
+  String get installRecipeHelp =>
+      'Steps run on the headset every time this app is installed';
+
+  @override
+  String get installRecipeEnabled => 'Run after install';
+
+  @override
+  String get installRecipeNoSteps => 'No steps yet';
+
+  @override
+  String get installRecipeAddStep => 'Add step';
+
+  @override
+  String installRecipeStepGrant(String permission) {
+    return 'Grant $permission';
+  }
+
+  @override
+  String installRecipeStepPush(String local, String remote) {
+    return 'Push $local to $remote';
+  }
+
+  @override
+  String get installRecipeStepLaunch => 'Launch once';
+
+  @override
+  String installRecipeStepProperty(String name, String value) {
+    return 'Set $name to $value';
+  }
+
+  @override
+  String get installRecipeGrantPermission => 'Grant permission';
+
+  @override
+  String get installRecipePushFile => 'Push file';
+
+  @override
+  String get installRecipeSetProperty => 'Set property';
+
+  @override
+  String get installRecipePermissionName => 'Permission name';
+
+  @override
+  String get installRecipeLocalPath => 'Local file or folder';
+
+  @override
+  String get installRecipeRemotePath => 'Path on the headset';
+
+  @override
+  String get installRecipePropertyName => 'Property name';
+
+  @override
+  String get installRecipePropertyValue => 'Value';
+
+  @override
+  String get installRecipeSave => 'Save';
+
+  @override
+  String get installScriptReviewTitle => 'Run install script?';
+
+  @override
+  String get installScriptShellWarning =>
+      'This script runs shell commands on the device. Only run scripts from sources you trust.';
+
+  @override
+  String installScriptLine(String line) {
+    return 'Line $line';
+  }
+
+  @override
+  String get installScriptOpInstall => 'Install';
+
+  @override
+  String get installScriptOpUninstall => 'Uninstall';
+
+  @override
+  String get installScriptOpShell => 'Shell command';
+
+  @override
+  String get installScriptOpPush => 'Copy to device';
+
+  @override
+  String get installScriptOpPull => 'Copy from device';
+
+  @override
+  String get installScriptOpPause => 'Pause';
+
+  @override
+  String get installScriptRun => 'Run script';
+
+  @override
+  String get installScriptUnsupportedTitle => 'Install script not run';
+
+  @override
+  String get installScriptUnsupportedDescription =>
+      'Nothing was run because these lines cannot be run safely:';
+
+  @override
+  String appPermissionsTitle(String name) {
+    return 'Permissions of $name';
+  }
+
+  @override
+  String get appPermissionsRuntime => 'Runtime permissions';
+
+  @override
+  String get appPermissionsOther => 'Other permissions';
+
+  @override
+  String get appPermissionsGrantedAtInstall => 'Granted at install';
+
+  @override
+  String get appPermissionsNotGranted => 'Not granted';
+
+  @override
+  String get appPermissionsNone => 'This app requests no permissions';
+
+  @override
+  String get crashReportSaved => 'Crash report saved, path copied to clipboard';
+
+  @override
+  String crashReportFailed(String error) {
+    return 'Failed to collect crash report: $error';
+  }
+
   @override
   String get commonCancel => 'Cancel';
 
@@ -842,39 +1804,75 @@ class AppLocalizationsEn extends AppLocalizations {
   String get installNewerVersion => 'Install newer version';
 
   @override
-  String get reinstallThisVersion => 'Reinstall this version';
+  String get reinstallThisVersion => 'Reinstall this version';
+
+  @override
+  String get holdShiftToReinstall => 'Hold Shift to reinstall this version';
+
+  @override
+  String get cannotDowngrade => 'Cannot downgrade to older version';
+
+  @override
+  String get newerVersion => 'Newer version';
+
+  @override
+  String get sameVersion => 'Same version';
+
+  @override
+  String get olderVersion => 'Older version';
+
+  @override
+  String get update => 'Update';
+
+  @override
+  String get install => 'Install';
+
+  @override
+  String get checkForUpdates => 'Check for updates';
+
+  @override
+  String get noMatchingCloudApp => 'No matching app found in cloud repository';
 
   @override
-  String get holdShiftToReinstall => 'Hold Shift to reinstall this version';
+  String updateTo(String to) {
+    return 'Update to $to';
+  }
 
   @override
-  String get cannotDowngrade => 'Cannot downgrade to older version';
+  String get updateAll => 'Update all';
 
   @override
-  String get newerVersion => 'Newer version';
+  String updateAllTitle(String count) {
+    return 'Update $count apps';
+  }
 
   @override
-  String get sameVersion => 'Same version';
+  String get updateAllCanary => 'Try the smallest app first';
 
   @override
-  String get olderVersion => 'Older version';
+  String get updateAllCanaryDescription =>
+      'Updates and launches one app, then asks before updating the rest';
 
   @override
-  String get update => 'Update';
+  String updateAllCanarySucceeded(String app) {
+    return '$app updated and launched';
+  }
 
   @override
-  String get install => 'Install';
+  String updateAllCanaryFailed(String app) {
+    return 'Update of $app did not go through';
+  }
 
   @override
-  String get checkForUpdates => 'Check for updates';
+  String updateAllContinuePrompt(String count) {
+    return 'Update the remaining $count apps?';
+  }
 
   @override
-  String get noMatchingCloudApp => 'No matching app found in cloud repository';
+  String get updateAllContinue => 'Continue';
 
   @override
-  String updateTo(String to) {
-    return 'Update to $to';
-  }
+  String get updateAllStop => 'Stop';
 
   @override
   String get downgradeAppTitle => 'Downgrade App';
@@ -951,6 +1949,12 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get addToFavorites => 'Add to favorites';
 
+  @override
+  String get watchApp => 'Notify about new versions';
+
+  @override
+  String get stopWatchingApp => 'Stop notifying';
+
   @override
   String get removeFromFavorites => 'Remove from favorites';
 
@@ -978,6 +1982,42 @@ class AppLocalizationsEn extends AppLocalizations {
   String get downloadAndInstallNotConnected =>
       'Download and install on device (not connected)';
 
+  @override
+  String get releaseFilesChoose => 'Choose files…';
+
+  @override
+  String get releaseFilesTitle => 'Choose files to download';
+
+  @override
+  String get releaseFilesLoading => 'Listing release files…';
+
+  @override
+  String releaseFilesFailed(String error) {
+    return 'Failed to list files: $error';
+  }
+
+  @override
+  String get releaseFilesAll => 'All';
+
+  @override
+  String get releaseFilesApkOnly => 'APK only';
+
+  @override
+  String get releaseFilesObbOnly => 'OBB only';
+
+  @override
+  String releaseFilesSelected(String count, String size) {
+    return '$count file(s) selected, $size';
+  }
+
+  @override
+  String get releaseFilesDownloaded => 'Already downloaded';
+
+  @override
+  String releaseFilesToTransfer(String size) {
+    return '$size to transfer';
+  }
+
   @override
   String get cloudStatusInstalled => 'Installed';
 
@@ -1013,6 +2053,23 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get sortSizeLargest => 'Size (Largest first)';
 
+  @override
+  String get exportInstalledList => 'Export installed apps';
+
+  @override
+  String get exportInstalledListCsv => 'Export as CSV';
+
+  @override
+  String get exportInstalledListJson => 'Export as JSON';
+
+  @override
+  String get installedListExportFailed => 'Failed to export installed apps';
+
+  @override
+  String installedListExported(String count, String path) {
+    return 'Exported $count apps to $path';
+  }
+
   @override
   String get sortUpdateAvailableFirst =>
       'Update availability (Available first)';
@@ -1134,6 +2191,12 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get batteryLabel => 'Battery';
 
+  @override
+  String get firmwareLabel => 'Firmware';
+
+  @override
+  String get lastConnectedLabel => 'Last connected';
+
   @override
   String get powerOffDevice => 'Power off device';
 
@@ -1147,6 +2210,16 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get rebootMenu => 'Reboot...';
 
+  @override
+  String get pairControllersMenu => 'Pair controllers...';
+
+  @override
+  String get pairControllersTitle => 'Pair controllers';
+
+  @override
+  String get pairControllersConfirm =>
+      'Put the controllers into pairing mode? They stay disconnected until they are paired again.';
+
   @override
   String get rebootOptions => 'Reboot options';
 
@@ -1169,151 +2242,399 @@ class AppLocalizationsEn extends AppLocalizations {
   String get rebootNowConfirm => 'Reboot the device now?';
 
   @override
-  String get rebootToBootloader => 'Reboot to bootloader';
+  String get rebootToBootloader => 'Reboot to bootloader';
+
+  @override
+  String get rebootToBootloaderConfirm => 'Reboot the device to bootloader?';
+
+  @override
+  String get rebootToRecovery => 'Reboot to recovery';
+
+  @override
+  String get rebootToRecoveryConfirm => 'Reboot the device to recovery?';
+
+  @override
+  String get rebootToFastboot => 'Reboot to fastboot';
+
+  @override
+  String get rebootToFastbootConfirm => 'Reboot the device to fastboot?';
+
+  @override
+  String get commonConfirm => 'Confirm';
+
+  @override
+  String get delete => 'Delete';
+
+  @override
+  String get remove => 'Remove';
+
+  @override
+  String get restore => 'Restore';
+
+  @override
+  String get mute => 'Mute';
+
+  @override
+  String get unmute => 'Unmute';
+
+  @override
+  String get close => 'Close';
+
+  @override
+  String get pause => 'Pause';
+
+  @override
+  String get checkingTrailerAvailability => 'Checking trailer availability...';
+
+  @override
+  String get trailerAvailable => 'Trailer available';
+
+  @override
+  String get noTrailer => 'No trailer';
+
+  @override
+  String get backupsTitle => 'Backups';
+
+  @override
+  String get openBackupsFolder => 'Open Backups Folder';
+
+  @override
+  String get openDownloadsFolder => 'Open Downloads Folder';
+
+  @override
+  String get downloadsTitle => 'Downloads';
+
+  @override
+  String get deleteAllDownloads => 'Delete all downloads';
+
+  @override
+  String get deleteAllDownloadsTitle => 'Delete All Downloads';
+
+  @override
+  String get deleteAllDownloadsConfirm =>
+      'Are you sure you want to delete all downloads?';
+
+  @override
+  String deleteAllDownloadsResult(String removed, String skipped) {
+    return 'Deleted $removed, skipped $skipped';
+  }
+
+  @override
+  String get dedupeDownloads => 'Deduplicate downloads';
+
+  @override
+  String get dedupeDownloadsRunning =>
+      'Hashing downloaded files, this may take a while';
+
+  @override
+  String dedupeDownloadsResult(String linked, String saved) {
+    return 'Linked $linked files, $saved saved in total';
+  }
+
+  @override
+  String get deleteDownloadTitle => 'Delete Download';
+
+  @override
+  String deleteDownloadConfirm(String name) {
+    return 'Are you sure you want to delete \"$name\"?';
+  }
+
+  @override
+  String get downloadDeletedTitle => 'Download deleted';
+
+  @override
+  String get noBackupsFound => 'No backups found.';
+
+  @override
+  String get noDownloadsFound => 'No downloads found.';
+
+  @override
+  String get searchDownloadsHint => 'Search downloads...';
+
+  @override
+  String get noMatchingDownloads => 'No downloads match the search.';
+
+  @override
+  String get unsupportedPlatform => 'Platform not supported';
+
+  @override
+  String get folderPathCopied => 'Folder path copied to clipboard';
+
+  @override
+  String unableToOpenFolder(String path) {
+    return 'Unable to open folder: $path';
+  }
+
+  @override
+  String get openFolderTooltip => 'Open Folder';
+
+  @override
+  String get taskArtifactReveal => 'Show in Folder';
+
+  @override
+  String get downloadHealthCheck => 'Check folder';
+
+  @override
+  String get downloadHealthChecking => 'Checking folder…';
+
+  @override
+  String get downloadHealthReady => 'Ready to install';
+
+  @override
+  String get downloadHealthFixIssues => 'Fix these issues before installing:';
+
+  @override
+  String downloadHealthFailed(String error) {
+    return 'Could not check the folder: $error';
+  }
+
+  @override
+  String get downloadHealthUsesScript => 'Installed with install.txt';
+
+  @override
+  String get healthIssueNoApk => 'No APK or install.txt found';
+
+  @override
+  String healthIssueMultipleApks(String files) {
+    return 'Several APKs and no install.txt: $files';
+  }
+
+  @override
+  String healthIssueApkUnreadable(String file, String error) {
+    return '$file could not be read: $error';
+  }
+
+  @override
+  String healthIssueObbFolderMismatch(String folder, String package) {
+    return 'OBB folder $folder should be named $package';
+  }
+
+  @override
+  String healthIssueObbFileMismatch(String file, String package) {
+    return '$file belongs to a package other than $package';
+  }
+
+  @override
+  String healthIssueLooseObb(String files) {
+    return 'OBB files must be in a folder named after the package: $files';
+  }
+
+  @override
+  String healthIssueScriptUnreadable(String error) {
+    return 'install.txt could not be read: $error';
+  }
+
+  @override
+  String healthIssueScriptFileMissing(String line, String path) {
+    return 'install.txt line $line: $path is missing';
+  }
+
+  @override
+  String healthIssueScriptUnsupported(String line, String command) {
+    return 'install.txt line $line: unsupported command $command';
+  }
+
+  @override
+  String healthIssueArchiveUnreadable(String file, String error) {
+    return '$file could not be listed: $error';
+  }
+
+  @override
+  String get unknownTime => 'Unknown time';
+
+  @override
+  String get partAPK => 'APK';
+
+  @override
+  String get partPrivate => 'Private';
+
+  @override
+  String get partShared => 'Shared';
+
+  @override
+  String get partOBB => 'OBB';
+
+  @override
+  String get backupCompressed => 'Compressed';
+
+  @override
+  String get noPartsDetected => 'No parts detected';
+
+  @override
+  String get deleteBackupTitle => 'Delete Backup';
+
+  @override
+  String deleteBackupConfirm(String name) {
+    return 'Are you sure you want to delete \"$name\"?';
+  }
 
   @override
-  String get rebootToBootloaderConfirm => 'Reboot the device to bootloader?';
+  String get backupDeletedTitle => 'Backup deleted';
 
   @override
-  String get rebootToRecovery => 'Reboot to recovery';
+  String get backupsCleanUp => 'Clean up old backups';
 
   @override
-  String get rebootToRecoveryConfirm => 'Reboot the device to recovery?';
+  String get backupRestoreToPackage => 'Restore to another app';
 
   @override
-  String get rebootToFastboot => 'Reboot to fastboot';
+  String get backupRestoreTargetPackage => 'Target package name';
 
   @override
-  String get rebootToFastbootConfirm => 'Reboot the device to fastboot?';
+  String get backupRestoreInvalidPackage => 'Not a valid package name';
 
   @override
-  String get commonConfirm => 'Confirm';
+  String get backupRestorePackageNotInstalled =>
+      'This package is not installed on the device';
 
   @override
-  String get delete => 'Delete';
+  String get backupRestoreSamePackage =>
+      'The backup already belongs to this package';
 
   @override
-  String get remove => 'Remove';
+  String backupRestoreToPackageDescription(String name) {
+    return 'Data from $name will be restored into the selected app, which must already be installed. OBB files are renamed to match it.';
+  }
 
   @override
-  String get restore => 'Restore';
+  String get backupVerify => 'Verify backup';
 
   @override
-  String get mute => 'Mute';
+  String get backupVerifyOk => 'Backup is intact';
 
   @override
-  String get unmute => 'Unmute';
+  String backupVerifyOkFiles(String count) {
+    return '$count files match the manifest';
+  }
 
   @override
-  String get close => 'Close';
+  String get backupVerifyNoFileList =>
+      'This backup was made before file lists were recorded, only its layout was checked';
 
   @override
-  String get pause => 'Pause';
+  String backupVerifyProblemsTitle(String name) {
+    return 'Problems in $name';
+  }
 
   @override
-  String get checkingTrailerAvailability => 'Checking trailer availability...';
+  String get backupVerifyProblemsWarning =>
+      'This backup may not restore correctly. Keep the app installed until you have a backup without problems.';
 
   @override
-  String get trailerAvailable => 'Trailer available';
+  String backupFromDevice(String device) {
+    return 'from $device';
+  }
 
   @override
-  String get noTrailer => 'No trailer';
+  String get searchBackupsHint => 'Search backups...';
 
   @override
-  String get backupsTitle => 'Backups';
+  String get noMatchingBackups => 'No backups match the search and filters.';
 
   @override
-  String get openBackupsFolder => 'Open Backups Folder';
+  String get backupsFilter => 'Filter';
 
   @override
-  String get openDownloadsFolder => 'Open Downloads Folder';
+  String get backupsFilterApk => 'With APK';
 
   @override
-  String get downloadsTitle => 'Downloads';
+  String get backupsFilterData => 'With app data';
 
   @override
-  String get deleteAllDownloads => 'Delete all downloads';
+  String get backupsFilterObb => 'With OBB files';
 
   @override
-  String get deleteAllDownloadsTitle => 'Delete All Downloads';
+  String get backupsDateRange => 'Date range';
 
   @override
-  String get deleteAllDownloadsConfirm =>
-      'Are you sure you want to delete all downloads?';
+  String get backupsClearDateRange => 'Clear date range';
 
   @override
-  String deleteAllDownloadsResult(String removed, String skipped) {
-    return 'Deleted $removed, skipped $skipped';
+  String backupsSummary(String count, String total, String size) {
+    return '$count of $total backups, $size';
   }
 
   @override
-  String get deleteDownloadTitle => 'Delete Download';
+  String get backupsPruneTitle => 'Clean up backups';
 
   @override
-  String deleteDownloadConfirm(String name) {
-    return 'Are you sure you want to delete \"$name\"?';
-  }
+  String get backupsPruneNothing =>
+      'No backups exceed the retention limits set in Settings.';
 
   @override
-  String get downloadDeletedTitle => 'Download deleted';
+  String get backupsPruneFailed => 'Some backups could not be deleted';
 
   @override
-  String get noBackupsFound => 'No backups found.';
+  String backupsPruned(String count, String size) {
+    return 'Deleted $count old backups, freed $size';
+  }
 
   @override
-  String get noDownloadsFound => 'No downloads found.';
+  String backupsPruneConfirm(String count, String size) {
+    return '$count backups will be deleted, freeing $size:';
+  }
 
   @override
-  String get unsupportedPlatform => 'Platform not supported';
+  String get fatalErrorTitle => 'Fatal Error';
 
   @override
-  String get folderPathCopied => 'Folder path copied to clipboard';
+  String get exitApplication => 'Exit Application';
 
   @override
-  String unableToOpenFolder(String path) {
-    return 'Unable to open folder: $path';
-  }
+  String get errorCopied => 'Error message copied to clipboard';
 
   @override
-  String get openFolderTooltip => 'Open Folder';
+  String get coreApiMismatchTitle => 'Incompatible app components';
 
   @override
-  String get unknownTime => 'Unknown time';
+  String get coreApiMismatchDescription =>
+      'The interface and the core of the app are from different versions, some features may not work. Reinstall the app to fix this.';
 
   @override
-  String get partAPK => 'APK';
+  String get appUpdateAvailableTitle => 'Update available';
 
   @override
-  String get partPrivate => 'Private';
+  String get appUpdatesTitle => 'Updates';
 
   @override
-  String get partShared => 'Shared';
+  String get checkForAppUpdates => 'Check for updates';
 
   @override
-  String get partOBB => 'OBB';
+  String get appUpToDate => 'You are using the latest version';
 
   @override
-  String get noPartsDetected => 'No parts detected';
+  String get appUpdateDownload => 'Download update';
 
   @override
-  String get deleteBackupTitle => 'Delete Backup';
+  String get appUpdateReleasePage => 'View release page';
 
   @override
-  String deleteBackupConfirm(String name) {
-    return 'Are you sure you want to delete \"$name\"?';
+  String get appUpdateNoBuild => 'This release has no build for your platform';
+
+  @override
+  String appUpdateAvailableDescription(String version) {
+    return 'YAAS $version is available. Open the About page to download it.';
   }
 
   @override
-  String get backupDeletedTitle => 'Backup deleted';
+  String appUpdateNewVersion(String version) {
+    return 'Version $version is available';
+  }
 
   @override
-  String get fatalErrorTitle => 'Fatal Error';
+  String appUpdateCheckFailed(String error) {
+    return 'Update check failed: $error';
+  }
 
   @override
-  String get exitApplication => 'Exit Application';
+  String appUpdateDownloaded(String path) {
+    return 'Downloaded to $path';
+  }
 
   @override
-  String get errorCopied => 'Error message copied to clipboard';
+  String appUpdateDownloadFailed(String error) {
+    return 'Download failed: $error';
+  }
 
   @override
   String get copyError => 'Copy Error';
@@ -1322,13 +2643,13 @@ class AppLocalizationsEn extends AppLocalizations {
   String get selectAppDirectoryTitle => 'Select app directory';
 
   @override
-  String get selectApkFileTitle => 'Select APK file';
+  String get selectApkFileTitle => 'Select APK file or bundle';
 
   @override
   String get selectedInvalidDir => 'Selected path is not a valid app directory';
 
   @override
-  String get selectedInvalidApk => 'Selected path is not a valid APK file';
+  String get selectedInvalidApk => 'Selected path is not a valid APK file or bundle';
 
   @override
   String get singleApk => 'Single APK';
@@ -1346,7 +2667,7 @@ class AppLocalizationsEn extends AppLocalizations {
   String get pathHintDirectory => 'Select or enter app directory path';
 
   @override
-  String get pathHintApk => 'Select or enter APK file path';
+  String get pathHintApk => 'Select or enter APK or XAPK/APKS/APKM bundle path';
 
   @override
   String get directoryRequirements =>
@@ -1365,6 +2686,34 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get installApk => 'Install APK';
 
+  @override
+  String get apkPreviewNoNativeLibs => 'No native libraries';
+
+  @override
+  String apkPreviewFailed(String error) {
+    return 'Could not read APK: $error';
+  }
+
+  @override
+  String apkPreviewVersion(String version) {
+    return 'Version $version';
+  }
+
+  @override
+  String apkPreviewSdk(String min, String target) {
+    return 'Android SDK: min $min, target $target';
+  }
+
+  @override
+  String apkPreviewAbis(String abis) {
+    return 'Native libraries: $abis';
+  }
+
+  @override
+  String apkPreviewPermissions(String count) {
+    return 'Requested permissions ($count)';
+  }
+
   @override
   String get tasksTitle => 'Tasks';
 
@@ -1380,6 +2729,32 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get tasksEmptyRecent => 'No recent tasks';
 
+  @override
+  String get tasksTabHistory => 'History';
+
+  @override
+  String get tasksEmptyHistory => 'No operations recorded yet';
+
+  @override
+  String historyLoadFailed(String error) {
+    return 'Could not load history: $error';
+  }
+
+  @override
+  String get historyActionInstall => 'Install';
+
+  @override
+  String get historyActionUninstall => 'Uninstall';
+
+  @override
+  String get historyActionBackup => 'Backup';
+
+  @override
+  String get historyActionRestore => 'Restore';
+
+  @override
+  String get historyActionDownload => 'Download';
+
   @override
   String get clearRecentTasks => 'Clear recent tasks';
 
@@ -1397,6 +2772,18 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get cancelTask => 'Cancel Task';
 
+  @override
+  String get taskPriority => 'Priority';
+
+  @override
+  String get taskPriorityHigh => 'High';
+
+  @override
+  String get taskPriorityNormal => 'Normal';
+
+  @override
+  String get taskPriorityLow => 'Low';
+
   @override
   String get taskKindDownload => 'Download';
 
@@ -1421,6 +2808,30 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get taskKindDonateApp => 'Donate App';
 
+  @override
+  String get taskKindResumeDownload => 'Resume Download';
+
+  @override
+  String get taskKindCaptureModSet => 'Capture Mod Set';
+
+  @override
+  String get taskKindApplyModSet => 'Apply Mod Set';
+
+  @override
+  String get taskKindBackupAll => 'Backup All Apps';
+
+  @override
+  String get taskKindPushObb => 'Push OBB';
+
+  @override
+  String get taskKindReinstallFromTrash => 'Reinstall From Trash';
+
+  @override
+  String get taskKindReceiveFromPeer => 'Receive From Peer';
+
+  @override
+  String get taskKindTransferAppData => 'Transfer App Data';
+
   @override
   String get taskStatusWaiting => 'Waiting';
 
@@ -1439,9 +2850,100 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get taskUnknown => 'Unknown';
 
+  @override
+  String get taskRestored => 'Resumed from previous session';
+
+  @override
+  String tasksResumedTitle(String count) {
+    return 'Resumed $count unfinished tasks';
+  }
+
+  @override
+  String get tasksResumedDescription => 'Click to open the task list';
+
+  @override
+  String taskDownloadSummary(String size, String duration, String average, String peak) {
+    return '$size in $duration · $average/s average, $peak/s peak';
+  }
+
+  @override
+  String taskDownloadSource(String source) {
+    return 'Source: $source';
+  }
+
+  @override
+  String taskDownloadRetries(String count) {
+    return 'Resumed after $count interrupted attempts';
+  }
+
+  @override
+  String taskTimeLeft(String time) {
+    return '$time left';
+  }
+
+  @override
+  String taskTransferBytes(String done, String total) {
+    return '$done / $total';
+  }
+
+  @override
+  String taskTransferSpeed(String speed) {
+    return '$speed/s';
+  }
+
+  @override
+  String taskTransferFiles(String done, String total) {
+    return 'file $done of $total';
+  }
+
+  @override
+  String get transferStatsTitle => 'Transfer statistics';
+
+  @override
+  String get transferStatsSession => 'This session';
+
+  @override
+  String transferStatsLifetime(String date) {
+    return 'Since $date';
+  }
+
+  @override
+  String get transferStatsDownloaded => 'Downloaded';
+
+  @override
+  String get transferStatsPushed => 'Pushed to devices';
+
+  @override
+  String get transferStatsDownloads => 'Downloads completed';
+
+  @override
+  String get transferStatsInstalls => 'Installs completed';
+
+  @override
+  String transferStatsAverageSpeed(String size, String speed) {
+    return '$size at $speed/s on average';
+  }
+
+  @override
+  String get transferStatsReset => 'Reset lifetime totals';
+
   @override
   String get backupOptionsTitle => 'Backup Options';
 
+  @override
+  String get backupAllAppsTitle => 'Backup All Apps';
+
+  @override
+  String get backupAllApps => 'Back up all apps';
+
+  @override
+  String get pushObb => 'Push OBB files from a folder';
+
+  @override
+  String pushObbSelectFolder(String app) {
+    return 'Select the OBB folder for $app';
+  }
+
   @override
   String get backupSelectParts => 'Select parts to back up:';
 
@@ -1460,6 +2962,22 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get backupNameSuffixHint => 'e.g. pre-update';
 
+  @override
+  String get backupCompress => 'Compress into a single file';
+
+  @override
+  String get backupEstimating => 'Measuring backup size…';
+
+  @override
+  String backupEstimate(String size, String time) {
+    return 'Estimated size: $size, about $time';
+  }
+
+  @override
+  String backupEstimateFailed(String error) {
+    return 'Could not estimate backup size: $error';
+  }
+
   @override
   String get startBackup => 'Start Backup';
 
@@ -1521,6 +3039,44 @@ class AppLocalizationsEn extends AppLocalizations {
     return 'Unable to open logs directory (copied to clipboard): $path';
   }
 
+  @override
+  String get logHistory => 'Log history';
+
+  @override
+  String get logHistoryModuleHint => 'Module, e.g. hub::adb';
+
+  @override
+  String get logHistoryMinLevel => 'Minimum level';
+
+  @override
+  String get logHistoryAnyDate => 'Any date';
+
+  @override
+  String get logHistoryLoadMore => 'Load more';
+
+  @override
+  String get logHistoryEmpty => 'No log entries match the filters';
+
+  @override
+  String logHistoryCount(String shown, String total) {
+    return '$shown of $total entries';
+  }
+
+  @override
+  String get createDiagnosticsBundle => 'Create diagnostics bundle';
+
+  @override
+  String get diagnosticsBundleCreating => 'Creating diagnostics bundle...';
+
+  @override
+  String get diagnosticsBundleSaved =>
+      'Diagnostics bundle saved, path copied to clipboard';
+
+  @override
+  String diagnosticsBundleFailed(String error) {
+    return 'Failed to create diagnostics bundle: $error';
+  }
+
   @override
   String get uninstallAppTitle => 'Uninstall App';
 
@@ -1615,6 +3171,55 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get donateDonateButton => 'Donate';
 
+  @override
+  String donationUploadedTitle(String archive) {
+    return 'Uploaded $archive';
+  }
+
+  @override
+  String donationUploadedDescription(String md5) {
+    return 'Thank you for donating! MD5: $md5';
+  }
+
+  @override
+  String appTrashedTitle(String name) {
+    return '$name moved to trash';
+  }
+
+  @override
+  String get appTrashedDescription => 'Click to reinstall it with its data';
+
+  @override
+  String get trashTitle => 'Trash';
+
+  @override
+  String get trashEmpty => 'The trash is empty';
+
+  @override
+  String trashTrashedAt(String date) {
+    return 'Moved to trash $date';
+  }
+
+  @override
+  String trashExpires(String date) {
+    return 'deleted after $date';
+  }
+
+  @override
+  String get trashReinstall => 'Reinstall with data';
+
+  @override
+  String get trashDelete => 'Delete from trash';
+
+  @override
+  String trashDeleteTitle(String name) {
+    return 'Delete $name from trash?';
+  }
+
+  @override
+  String get trashDeletePrompt =>
+      'The backup of its APK and data will be deleted.';
+
   @override
   String get donateNoAppsAvailable => 'No apps available for donation';
 
@@ -1664,6 +3269,18 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get lowSpaceWarningContinue => 'Continue anyway';
 
+  @override
+  String get insufficientSpaceTitle => 'Not enough space on the device';
+
+  @override
+  String insufficientSpaceDescription(
+    String needed,
+    String available,
+    String missing,
+  ) {
+    return 'Needs $needed, but only $available is available. Free up at least $missing.';
+  }
+
   @override
   String get proximityStatusEnabled => 'Enabled';
 
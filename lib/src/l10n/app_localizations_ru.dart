@@ -93,9 +93,65 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get settingsDownloadsLocation => 'Папка загрузок';
 
+  @override
+  String get settingsDownloadLayout => 'Структура папки загрузок';
+
+  @override
+  String settingsDownloadLayoutHelp(String placeholders) {
+    return 'Папки, по которым распределяются приложения, через /. Поддерживаются $placeholders. Оставьте пустым, чтобы хранить все приложения прямо в папке загрузок.';
+  }
+
+  @override
+  String get settingsDownloadLayoutPresets => 'Готовые варианты';
+
+  @override
+  String get settingsDownloadLayoutFlat => 'Без папок';
+
+  @override
+  String get settingsDownloadLayoutByLetter => 'По первой букве';
+
+  @override
+  String get settingsDownloadLayoutByName => 'По названию приложения';
+
+  @override
+  String get settingsDownloadLayoutByPackage => 'По имени пакета';
+
   @override
   String get settingsBackupsLocation => 'Папка резервных копий';
 
+  @override
+  String get settingsBackupRetentionPerApp => 'Резервных копий на приложение';
+
+  @override
+  String get settingsBackupRetentionTotalSize =>
+      'Максимальный общий размер резервных копий';
+
+  @override
+  String get settingsBackupRetentionMaxAge => 'Удалять резервные копии старше';
+
+  @override
+  String get settingsBackupRetentionUnlimited => 'Без ограничений';
+
+  @override
+  String get settingsBackupRetentionHelp =>
+      'Старые резервные копии удаляются автоматически. Самая новая копия каждого приложения всегда сохраняется.';
+
+  @override
+  String settingsBackupRetentionDays(String days) {
+    return '$days дн.';
+  }
+
+  @override
+  String get settingsTrashBeforeUninstall =>
+      'Перемещать удалённые приложения в корзину';
+
+  @override
+  String get settingsTrashBeforeUninstallHelp =>
+      'Сохранять APK и данные перед удалением, чтобы приложение можно было переустановить';
+
+  @override
+  String get settingsTrashRetention => 'Хранить приложения в корзине';
+
   @override
   String get settingsSectionAdb => 'ADB';
 
@@ -118,6 +174,29 @@ class AppLocalizationsRu extends AppLocalizations {
   String get settingsMdnsAutoConnectHelp =>
       'Обнаруживать устройства через mDNS в локальной сети и автоматически выполнять \'adb connect\'. Изменение вступит в силу после перезапуска.';
 
+  @override
+  String get settingsReconnectWirelessDevices =>
+      'Переподключать беспроводные устройства';
+
+  @override
+  String get settingsReconnectWirelessDevicesHelp =>
+      'Поддерживать Wi-Fi подключения и переподключаться к последнему беспроводному шлему, когда нет подключённых устройств';
+
+  @override
+  String get settingsStopForeignAdbServers =>
+      'Останавливать серверы ADB других приложений';
+
+  @override
+  String get settingsStopForeignAdbServersHelp =>
+      'Приложения со своим ADB, например ALVR, могут обрывать подключение к устройству, пока работают оба сервера';
+
+  @override
+  String get settingsShellConsole => 'Консоль shell';
+
+  @override
+  String get settingsShellConsoleHelp =>
+      'Разрешить выполнение любых команд shell на подключённом устройстве. Неосторожные команды могут повредить устройство';
+
   @override
   String get settingsAutoReinstallOnConflict =>
       'Автопереустановка при конфликте версий';
@@ -126,6 +205,117 @@ class AppLocalizationsRu extends AppLocalizations {
   String get settingsAutoReinstallOnConflictHelp =>
       'Автоматически создавать резервную копию, переустанавливать и восстанавливать при конфликте версий (откат или несовместимое обновление). Требуется отладочная версия приложения.';
 
+  @override
+  String get settingsAutoApproveInstallScripts =>
+      'Выполнять скрипты установки без подтверждения';
+
+  @override
+  String get settingsAutoApproveInstallScriptsHelp =>
+      'Не показывать команды, которые install.txt выполнит на устройстве. Скрипты из ненадёжных источников могут выполнить любую команду.';
+
+  @override
+  String get settingsWakeOfflineDevices =>
+      'Пробуждать устройства в состоянии оффлайн';
+
+  @override
+  String get settingsWakeOfflineDevicesHelp =>
+      'Если шлем подключён, но остаётся в состоянии оффлайн (часто пока его не наденут), переподключить его, отправить действие пробуждения и подключиться.';
+
+  @override
+  String get settingsDeviceAgent => 'Устанавливать помощник на устройство';
+
+  @override
+  String get settingsDeviceAgentHelp =>
+      'Устанавливает и обновляет небольшое приложение-помощник на подключённых шлемах для точных размеров приложений и статистики использования. Без него используются обычные команды оболочки.';
+
+  @override
+  String get settingsWatchPackageChanges => 'Отслеживать изменения приложений';
+
+  @override
+  String get settingsWatchPackageChangesHelp =>
+      'Обновляет список установленных приложений сразу после установки, обновления или удаления приложений на шлеме, в том числе вне этой программы. Пока шлем подключён, на нём выполняется лёгкая проверка.';
+
+  @override
+  String get settingsProbeWirelessLink =>
+      'Проверять скорость Wi-Fi перед большими установками';
+
+  @override
+  String get settingsProbeWirelessLinkHelp =>
+      'Измеряет беспроводное подключение перед установкой больших приложений по Wi-Fi и предупреждает, если оно медленное';
+
+  @override
+  String get settingsOfflineWakeAction => 'Действие пробуждения';
+
+  @override
+  String get settingsOfflineWakeActionKeyevent => 'Клавиша пробуждения';
+
+  @override
+  String get settingsOfflineWakeActionProximity =>
+      'Датчик приближения (как будто надет)';
+
+  @override
+  String get settingsDeviceReconnectGrace => 'Период ожидания переподключения';
+
+  @override
+  String get settingsDeviceReconnectGraceHelp =>
+      'Сколько устройство, ненадолго пропавшее (например, при засыпании и пробуждении), остаётся подключённым до отключения';
+
+  @override
+  String get settingsDeviceReconnectGraceOff => 'Выкл.';
+
+  @override
+  String get settingsCheckForUpdates => 'Проверять обновления при запуске';
+
+  @override
+  String get settingsLogFileFormat => 'Формат файлов журнала';
+
+  @override
+  String get settingsLogFileFormatHelp =>
+      'Файлы JSON Lines можно загружать во внешние инструменты для работы с журналами';
+
+  @override
+  String get settingsLogFileFormatPretty => 'Читаемый текст';
+
+  @override
+  String get settingsLogFileFormatJson => 'JSON Lines';
+
+  @override
+  String get settingsLogFileFormatBoth => 'Текст и JSON Lines';
+
+  @override
+  String get settingsSectionTasks => 'Задачи';
+
+  @override
+  String get settingsMaxConcurrentDownloads => 'Одновременные загрузки';
+
+  @override
+  String get settingsMaxConcurrentAdbOperations =>
+      'Одновременные операции с устройством';
+
+  @override
+  String get settingsMaxConcurrentAdbOperationsHelp =>
+      'Установки, резервные копии и восстановления делят этот лимит. Одновременное выполнение нескольких операций может замедлить устройство или сделать его работу нестабильной.';
+
+  @override
+  String get settingsExtractionThreads => 'Потоки распаковки архивов';
+
+  @override
+  String get settingsExtractionThreadsHelp =>
+      'Сколько потоков используется для распаковки zip и 7z архивов перед установкой. Больше потоков быстрее распаковывают большие архивы, но сильнее нагружают процессор.';
+
+  @override
+  String get settingsExtractionThreadsAuto => 'Автоматически';
+
+  @override
+  String get settingsTaskKindLimits => 'Лимиты по типам задач';
+
+  @override
+  String get settingsTaskKindLimitsHelp =>
+      'Задачи сверх лимита своего типа ждут завершения других';
+
+  @override
+  String get settingsTaskKindUnlimited => 'Без ограничений';
+
   @override
   String get settingsSectionDownloader => 'Загрузчик';
 
@@ -343,6 +533,52 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get settingsNoRemotesFound => 'Хранилища не найдены';
 
+  @override
+  String get customRemotes => 'Свои удалённые хранилища';
+
+  @override
+  String get customRemotesEmpty =>
+      'Своих хранилищ пока нет. Добавленные хранилища можно выбрать как удалённое хранилище rclone.';
+
+  @override
+  String get customRemoteAdd => 'Добавить хранилище';
+
+  @override
+  String get customRemoteEdit => 'Изменить хранилище';
+
+  @override
+  String get customRemoteDelete => 'Удалить хранилище';
+
+  @override
+  String get customRemoteName => 'Название';
+
+  @override
+  String get customRemoteKind => 'Тип';
+
+  @override
+  String get customRemoteEndpointUrl => 'URL';
+
+  @override
+  String get customRemoteEndpointHost => 'Хост';
+
+  @override
+  String get customRemoteUsername => 'Имя пользователя или ID ключа доступа';
+
+  @override
+  String get customRemotePassword => 'Пароль или секретный ключ';
+
+  @override
+  String get customRemoteKeepPassword =>
+      'Оставьте пустым, чтобы сохранить текущий пароль';
+
+  @override
+  String get customRemoteSave => 'Сохранить';
+
+  @override
+  String customRemoteSaveFailed(String error) {
+    return 'Не удалось сохранить хранилище: $error';
+  }
+
   @override
   String get settingsFailedToLoadRemotes => 'Не удалось перечислить хранилища';
 
@@ -353,9 +589,55 @@ class AppLocalizationsRu extends AppLocalizations {
   String get settingsBandwidthHelper =>
       'Значение в КиБ/с или с суффиксами B|K|M|G|T|P и др. (нажмите для справки)';
 
+  @override
+  String get settingsDownloadMirrors => 'Резервные зеркала';
+
+  @override
+  String get settingsDownloadMirrorsRemotesHelp =>
+      'Удалённые хранилища через запятую, используемые по порядку, если загрузка не удалась или идёт слишком медленно';
+
+  @override
+  String get settingsDownloadMirrorsUrlsHelp =>
+      'Базовые URL через запятую, используемые по порядку, если загрузка не удалась или идёт слишком медленно';
+
+  @override
+  String get settingsMirrorMinSpeed => 'Переключать зеркало при скорости ниже';
+
+  @override
+  String get settingsMirrorMinSpeedOff => 'Только при ошибке';
+
+  @override
+  String settingsMirrorSpeedKbps(String speed) {
+    return '$speed КБ/с';
+  }
+
+  @override
+  String get settingsMirrorSlowFor => 'Медленно в течение';
+
+  @override
+  String settingsMirrorSlowSeconds(String seconds) {
+    return '$seconds с';
+  }
+
   @override
   String get settingsDownloadsCleanup => 'Очистка загрузок';
 
+  @override
+  String get settingsCastingDownloads => 'Загрузки во время трансляции';
+
+  @override
+  String get settingsCastingDownloadsIgnore => 'Продолжать загрузку';
+
+  @override
+  String get settingsCastingDownloadsThrottle => 'Ограничивать скорость';
+
+  @override
+  String get settingsCastingDownloadsPause => 'Приостанавливать загрузки';
+
+  @override
+  String get settingsCastingBandwidthLimit =>
+      'Ограничение скорости во время трансляции';
+
   @override
   String get settingsDownloadMode => 'Режим загрузки';
 
@@ -379,6 +661,13 @@ class AppLocalizationsRu extends AppLocalizations {
   String get settingsWriteLegacyReleaseJsonHelp =>
       'Сохранять release.json в старом формате для совместимости со QLoader';
 
+  @override
+  String get settingsDedupeDownloads => 'Убирать дубликаты в загрузках';
+
+  @override
+  String get settingsDedupeDownloadsHelp =>
+      'Хранить на диске одну копию больших файлов, общих для нескольких загруженных версий';
+
   @override
   String get settingsCleanupDeleteAfterInstall => 'Удалять после установки';
 
@@ -391,6 +680,14 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get settingsCleanupKeepAllVersions => 'Хранить все версии';
 
+  @override
+  String get settingsCleanupMoveAfterInstall =>
+      'Перемещать в другую папку после установки';
+
+  @override
+  String get settingsInstalledDownloadsLocation =>
+      'Папка для установленных загрузок';
+
   @override
   String get settingsBrowse => 'Обзор';
 
@@ -440,6 +737,31 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get statusAdbStateOffline => 'Оффлайн';
 
+  @override
+  String get statusAdbWakeDevice => 'Выберите, чтобы попробовать разбудить';
+
+  @override
+  String get wakeStepReconnect => 'Переподключение оффлайн-устройств';
+
+  @override
+  String get wakeStepKeyevent => 'Отправка клавиши пробуждения';
+
+  @override
+  String get wakeStepProximity => 'Отправка сигнала датчика приближения';
+
+  @override
+  String get wakeStepConnect => 'Подключение';
+
+  @override
+  String deviceWakeRecovered(String device) {
+    return '$device снова в сети';
+  }
+
+  @override
+  String deviceWakeFailed(String device) {
+    return 'Не удалось разбудить $device';
+  }
+
   @override
   String get statusAdbStateBootloader => 'Bootloader';
 
@@ -543,7 +865,7 @@ class AppLocalizationsRu extends AppLocalizations {
       'Папка не является корректной директорией приложения или бэкапом';
 
   @override
-  String get dragDropInvalidFile => 'Файл не является корректным APK';
+  String get dragDropInvalidFile => 'Файл не является корректным APK или бандлом';
 
   @override
   String get dragDropDownloaderConfigTitle => 'Конфиг загрузчика';
@@ -609,6 +931,17 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get batteryDumpFailed => 'Не удалось получить состояние батареи';
 
+  @override
+  String get batteryHistoryTitle => 'История заряда';
+
+  @override
+  String get batteryHistoryEmpty =>
+      'Уровень заряда записывается каждую минуту, пока устройство подключено. Загляните через несколько минут.';
+
+  @override
+  String get batteryHistoryHint =>
+      'Нажмите, чтобы скопировать дамп батареи, удерживайте, чтобы открыть историю заряда';
+
   @override
   String get commonSuccess => 'Успешно';
 
@@ -621,6 +954,32 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get diagnosticsAdbServer => 'Сервер ADB';
 
+  @override
+  String get diagnosticsAdbHealth => 'Контроль ADB';
+
+  @override
+  String get diagnosticsAdbHealthy =>
+      'Сервер ADB и отслеживание устройств отвечают';
+
+  @override
+  String get diagnosticsAdbUnresponsive =>
+      'Сервер ADB не отвечает, он будет перезапущен, если это продолжится';
+
+  @override
+  String diagnosticsAdbRecovering(String attempt) {
+    return 'Сервер ADB перезапущен (попытка $attempt)';
+  }
+
+  @override
+  String diagnosticsAdbRecoveryFailed(String error) {
+    return 'Не удалось перезапустить сервер ADB: $error';
+  }
+
+  @override
+  String diagnosticsAdbRetryIn(String seconds) {
+    return 'Следующая попытка через $seconds с';
+  }
+
   @override
   String get diagnosticsDevices => 'Устройства';
 
@@ -630,6 +989,26 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get diagnosticsActiveDevice => 'Активное устройство';
 
+  @override
+  String get diagnosticsHostConflicts => 'Приложения для стриминга';
+
+  @override
+  String get diagnosticsHostConflictsNone =>
+      'Приложения для стриминга и другие серверы ADB не запущены';
+
+  @override
+  String diagnosticsStreamingClientsRunning(String apps) {
+    return 'Запущено: $apps. Стриминг использует то же USB-подключение, поэтому передача может замедлиться или прерваться.';
+  }
+
+  @override
+  String get diagnosticsForeignAdbServers =>
+      'Запущены серверы ADB других приложений, они могут обрывать подключение к устройству:';
+
+  @override
+  String get diagnosticsStopForeignAdbServers =>
+      'Остановить другие серверы ADB';
+
   @override
   String get diagnosticsAdbPath => 'Путь к ADB';
 
@@ -711,83 +1090,507 @@ class AppLocalizationsRu extends AppLocalizations {
   String get guardianStatusActive => 'Активен';
 
   @override
-  String get deviceWirelessAdb => 'Беспроводной ADB';
+  String get guardianPauseFor => 'Приостановить на время';
 
   @override
-  String get deviceEnableWirelessAdb => 'Включить ADB по Wi‑Fi';
+  String get guardianPause15m => 'Приостановить на 15 минут';
 
   @override
-  String get deviceStorageConnection => 'Подключeние хранилища';
+  String get guardianPause30m => 'Приостановить на 30 минут';
 
   @override
-  String get deviceStorageConnectionTooltip =>
-      'Подключить хранилище через MTP.';
+  String get guardianPause1h => 'Приостановить на 1 час';
 
   @override
-  String get deviceStorageResetTooltip => 'Сбросить функции USB.';
+  String get guardianPause2h => 'Приостановить на 2 часа';
 
   @override
-  String get deviceStorageStatusConnected => 'Подключено';
+  String autoRevertIn(String time) {
+    return 'Восстановится через $time';
+  }
 
   @override
-  String get deviceStorageStatusDisconnected => 'Отключено';
+  String get deviceWirelessAdb => 'Беспроводной ADB';
 
   @override
-  String get deviceStorageStatusUnknown => 'Неизвестно';
+  String get deviceMaintenanceLog => 'Журнал обслуживания';
 
   @override
-  String get deviceStorageConnectedMessage =>
-      'MTP включен. USB будет перезапущен.';
+  String get deviceTweaks => 'Настройки рендеринга';
 
   @override
-  String get deviceStorageResetMessage =>
-      'Функции USB сброшены. USB будет перезапущен.';
+  String get deviceTweaksHint =>
+      'Изменения применяются к приложениям, запущенным после них, и сбрасываются при перезагрузке шлема.';
 
   @override
-  String get deviceStorageConnectionFailed =>
-      'Не удалось обновить подключение хранилища.';
+  String get deviceTweakDefault => 'По умолчанию';
 
   @override
-  String get usbSpeedLabel => 'Скорость USB';
+  String get deviceTweakRefreshRate => 'Частота обновления';
 
   @override
-  String get copiedToClipboard => 'Скопировано в буфер обмена';
+  String get deviceTweakCpuLevel => 'Уровень CPU';
 
   @override
-  String get clickToCopy => 'Нажмите, чтобы скопировать';
+  String get deviceTweakGpuLevel => 'Уровень GPU';
 
   @override
-  String get detailsPackageName => 'Имя пакета:';
+  String get deviceTweakTextureSize => 'Размер текстуры';
 
   @override
-  String get detailsVersion => 'Версия:';
+  String get deviceTweakFoveation => 'Фиксированная фовеация';
 
   @override
-  String get detailsVersionCode => 'Код версии:';
+  String get commandMacros => 'Макросы команд';
 
   @override
-  String get detailsIsVr => 'VR-приложение:';
+  String get shellConsole => 'Консоль shell';
 
   @override
-  String get detailsIsLaunchable => 'Запускаемое:';
+  String shellConsoleTitle(String serial) {
+    return 'Shell на $serial';
+  }
 
   @override
-  String get detailsIsSystem => 'Системное:';
+  String get shellConsoleOpening => 'Открытие shell…';
 
   @override
-  String get detailsStorageUsage => 'Использование хранилища:';
+  String get shellConsoleHint => 'Введите команду';
 
   @override
-  String get detailsApp => 'Приложение:';
+  String shellConsoleExitCode(String code) {
+    return 'Код выхода $code';
+  }
 
   @override
-  String get detailsData => 'Данные:';
+  String get shellConsoleEndClosed => 'Сеанс закрыт';
 
   @override
-  String get detailsCache => 'Кэш:';
+  String get shellConsoleEndExited => 'Shell завершился';
 
   @override
-  String get detailsTotal => 'Итого:';
+  String get shellConsoleEndTimedOut =>
+      'Время выполнения команды истекло, сеанс закрыт';
+
+  @override
+  String get shellConsoleEndIdle => 'Сеанс закрыт из-за бездействия';
+
+  @override
+  String get shellConsoleEndDisabled => 'Консоль shell отключена в настройках';
+
+  @override
+  String get shellConsoleReopen => 'Открыть снова';
+
+  @override
+  String get shellConsoleClear => 'Очистить';
+
+  @override
+  String get diskUsage => 'Использование диска';
+
+  @override
+  String get diskUsageDownloads => 'Загрузки';
+
+  @override
+  String get diskUsageMediaCache => 'Кэш медиа';
+
+  @override
+  String get diskUsageLogs => 'Журналы';
+
+  @override
+  String get diskUsageBackups => 'Резервные копии';
+
+  @override
+  String get diskUsageTrash => 'Корзина';
+
+  @override
+  String get diskUsageDonationUploads => 'Выгрузки пожертвований';
+
+  @override
+  String get diskUsageTotal => 'Всего';
+
+  @override
+  String get diskUsageClear => 'Очистить';
+
+  @override
+  String diskUsageClearTitle(String category) {
+    return 'Очистить «$category»?';
+  }
+
+  @override
+  String diskUsageClearPrompt(String category, String size, String path) {
+    return 'Удалить $size данных «$category» в $path? Это действие нельзя отменить.';
+  }
+
+  @override
+  String get storageMigrationTitle => 'Перемещение хранилища';
+
+  @override
+  String get storageMigrationMoveDownloads => 'Переместить загрузки…';
+
+  @override
+  String get storageMigrationMoveBackups => 'Переместить резервные копии…';
+
+  @override
+  String storageMigrationMoving(String path) {
+    return 'Перемещение файлов в $path. Не закрывайте YAAS до завершения.';
+  }
+
+  @override
+  String storageMigrationEntry(String current, String total, String name) {
+    return '$current из $total: $name';
+  }
+
+  @override
+  String storageMigrationDone(String path) {
+    return 'Все файлы перемещены. Новое расположение: $path.';
+  }
+
+  @override
+  String storageMigrationRolledBack(String path) {
+    return 'Уже перемещённые файлы возвращены. Расположение осталось прежним: $path.';
+  }
+
+  @override
+  String storageMigrationUnchanged(String path) {
+    return 'Расположение осталось прежним: $path.';
+  }
+
+  @override
+  String get settingsProfiles => 'Профили настроек';
+
+  @override
+  String get settingsProfilesEmpty =>
+      'Профилей пока нет. Сохраните текущие настройки как профиль, чтобы вернуться к ним позже.';
+
+  @override
+  String get settingsProfilesName => 'Название профиля';
+
+  @override
+  String get settingsProfilesSaveCurrent => 'Сохранить текущие';
+
+  @override
+  String get settingsProfilesActive => 'Активен';
+
+  @override
+  String get settingsProfilesOverwrite => 'Заменить текущими настройками';
+
+  @override
+  String get settingsProfilesDelete => 'Удалить';
+
+  @override
+  String settingsProfilesDeleteTitle(String name) {
+    return 'Удалить профиль «$name»?';
+  }
+
+  @override
+  String get settingsCatalogBaseUrl => 'URL каталога';
+
+  @override
+  String get settingsCatalogBaseUrlHelp =>
+      'Базовый URL собственного зеркала или кэша в локальной сети. Оставьте пустым, чтобы использовать настроенный источник.';
+
+  @override
+  String get settingsMediaBaseUrl => 'URL медиа';
+
+  @override
+  String get settingsMediaBaseUrlHelp =>
+      'Сервер, с которого загружаются миниатюры и трейлеры. Оставьте пустым, чтобы использовать сервер по умолчанию.';
+
+  @override
+  String get settingsPinnedSpki => 'Закреплённые ключи серверов';
+
+  @override
+  String get settingsPinnedSpkiHelp =>
+      'SPKI-хеши sha256/<base64> через запятую. Если заданы, собственные серверы каталога и медиа должны использовать https и предъявлять один из этих ключей.';
+
+  @override
+  String get settingsTestConnection => 'Проверить соединение';
+
+  @override
+  String get settingsTestConnectionOk => 'Сервер доступен';
+
+  @override
+  String settingsTestConnectionDetails(String status, String ms) {
+    return 'HTTP $status за $ms мс';
+  }
+
+  @override
+  String get settingsTestConnectionTimeout => 'Сервер не ответил';
+
+  @override
+  String get lanShareTitle => 'Обмен по локальной сети';
+
+  @override
+  String get lanShareApp => 'Поделиться по локальной сети';
+
+  @override
+  String get lanShareOwn => 'Вы делитесь';
+
+  @override
+  String get lanShareOwnEmpty =>
+      'Пока ничего не раздаётся. Нажмите кнопку «Поделиться» у загрузки, чтобы предложить её другим в вашей сети.';
+
+  @override
+  String get lanShareStop => 'Прекратить раздачу';
+
+  @override
+  String get lanSharePeers => 'Раздают другие';
+
+  @override
+  String get lanSharePeersEmpty =>
+      'Поиск других экземпляров YAAS в вашей сети...';
+
+  @override
+  String get lanShareReceive => 'Получить';
+
+  @override
+  String get lanShareQueued => 'В очереди';
+
+  @override
+  String get transferAppData => 'Перенести на другой шлем';
+
+  @override
+  String transferAppDataTitle(String name) {
+    return 'Перенос $name';
+  }
+
+  @override
+  String get transferAppDataDescription =>
+      'Приложение сейчас будет сохранено вместе с APK, данными и OBB-файлами. Резервная копия будет восстановлена, когда выбранный шлем станет подключённым устройством, поэтому шлемы можно сменить после завершения копирования.';
+
+  @override
+  String get transferAppDataNoTargets =>
+      'Другие шлемы не найдены. Сначала подключите целевой шлем к этому компьютеру.';
+
+  @override
+  String get transferAppDataConflictWarning =>
+      'Если приложение уже установлено на целевом шлеме, перед заменой его данных будет запрошено подтверждение, а сами данные сначала сохранятся в резервную копию. Более новая установленная версия сохраняется, восстанавливаются только данные.';
+
+  @override
+  String transferOverwriteTitle(String target) {
+    return 'Заменить данные на $target?';
+  }
+
+  @override
+  String transferOverwritePrompt(String app, String target) {
+    return '$app уже установлено на $target. Текущие данные на нём сначала сохраняются в резервную копию, затем заменяются перенесёнными.';
+  }
+
+  @override
+  String get transferOverwriteReplace => 'Заменить';
+
+  @override
+  String get commandMacrosEmpty =>
+      'Макросов пока нет. Макрос выполняет несколько команд устройства подряд.';
+
+  @override
+  String get commandMacroNew => 'Новый макрос';
+
+  @override
+  String get commandMacroEdit => 'Изменить макрос';
+
+  @override
+  String get commandMacroName => 'Название';
+
+  @override
+  String get commandMacroNameTaken => 'Макрос с таким названием уже существует';
+
+  @override
+  String get commandMacroRun => 'Запустить';
+
+  @override
+  String get commandMacroStop => 'Остановить';
+
+  @override
+  String get commandMacroDelete => 'Удалить макрос';
+
+  @override
+  String get commandMacroSave => 'Сохранить';
+
+  @override
+  String get commandMacroAddStep => 'Добавить шаг';
+
+  @override
+  String get commandMacroNoSteps => 'Добавьте хотя бы один шаг.';
+
+  @override
+  String commandMacroStepCount(String count) {
+    return 'Шагов: $count';
+  }
+
+  @override
+  String commandMacroFailed(String error) {
+    return 'Ошибка макроса: $error';
+  }
+
+  @override
+  String get commandMacroPackageName => 'Имя пакета';
+
+  @override
+  String get commandMacroDelaySeconds => 'Задержка в секундах';
+
+  @override
+  String commandMacroStepLaunch(String package) {
+    return 'Запустить $package';
+  }
+
+  @override
+  String commandMacroStepForceStop(String package) {
+    return 'Остановить $package';
+  }
+
+  @override
+  String commandMacroStepDelay(String seconds) {
+    return 'Ждать $seconds с';
+  }
+
+  @override
+  String get commandMacroStepGuardianPause => 'Приостановить Guardian';
+
+  @override
+  String get commandMacroStepGuardianResume => 'Возобновить Guardian';
+
+  @override
+  String get commandMacroStepProximityOff => 'Отключить датчик приближения';
+
+  @override
+  String get commandMacroStepProximityOn => 'Включить датчик приближения';
+
+  @override
+  String get commandMacroStepRefresh => 'Обновить устройство';
+
+  @override
+  String get commandMacroStepReboot => 'Перезагрузить';
+
+  @override
+  String commandMacroStepOther(String command) {
+    return 'Выполнить $command';
+  }
+
+  @override
+  String get deviceAgentNotInstalled => 'Помощник: не установлен';
+
+  @override
+  String get deviceAgentInstall => 'Установить помощник';
+
+  @override
+  String get deviceAgentUpdate => 'Обновить помощник';
+
+  @override
+  String get deviceAgentRemove => 'Удалить помощник';
+
+  @override
+  String deviceAgentInstalled(String version) {
+    return 'Помощник: версия $version';
+  }
+
+  @override
+  String get deviceLogNoteHint => 'Добавить заметку';
+
+  @override
+  String get deviceLogAddNote => 'Добавить';
+
+  @override
+  String get deviceLogEmpty => 'Записей пока нет';
+
+  @override
+  String get deviceLogDeleteEntry => 'Удалить запись';
+
+  @override
+  String deviceLogTitle(String device) {
+    return 'Журнал обслуживания: $device';
+  }
+
+  @override
+  String get deviceEnableWirelessAdb => 'Включить ADB по Wi‑Fi';
+
+  @override
+  String get deviceStorageConnection => 'Подключeние хранилища';
+
+  @override
+  String get deviceStorageConnectionTooltip =>
+      'Подключить хранилище через MTP.';
+
+  @override
+  String get deviceStorageResetTooltip => 'Сбросить функции USB.';
+
+  @override
+  String get deviceStorageStatusConnected => 'Подключено';
+
+  @override
+  String get deviceStorageStatusDisconnected => 'Отключено';
+
+  @override
+  String get deviceStorageStatusUnknown => 'Неизвестно';
+
+  @override
+  String get deviceStorageConnectedMessage =>
+      'MTP включен. USB будет перезапущен.';
+
+  @override
+  String get deviceStorageResetMessage =>
+      'Функции USB сброшены. USB будет перезапущен.';
+
+  @override
+  String get deviceStorageConnectionFailed =>
+      'Не удалось обновить подключение хранилища.';
+
+  @override
+  String get usbSpeedLabel => 'Скорость USB';
+
+  @override
+  String get osVersionLabel => 'Версия ОС';
+
+  @override
+  String get androidVersionLabel => 'Версия Android';
+
+  @override
+  String get buildFingerprintLabel => 'Отпечаток сборки';
+
+  @override
+  String get copiedToClipboard => 'Скопировано в буфер обмена';
+
+  @override
+  String get clickToCopy => 'Нажмите, чтобы скопировать';
+
+  @override
+  String get detailsPackageName => 'Имя пакета:';
+
+  @override
+  String get detailsVersion => 'Версия:';
+
+  @override
+  String get detailsVersionCode => 'Код версии:';
+
+  @override
+  String get detailsIsVr => 'VR-приложение:';
+
+  @override
+  String get detailsIsLaunchable => 'Запускаемое:';
+
+  @override
+  String get detailsIsSystem => 'Системное:';
+
+  @override
+  String get detailsPinnedVersion => 'Закреплённая версия:';
+
+  @override
+  String get detailsIgnoredVersions => 'Пропущенные версии:';
+
+  @override
+  String get detailsStorageUsage => 'Использование хранилища:';
+
+  @override
+  String get detailsApp => 'Приложение:';
+
+  @override
+  String get detailsData => 'Данные:';
+
+  @override
+  String get detailsCache => 'Кэш:';
+
+  @override
+  String get detailsTotal => 'Итого:';
 
   @override
   String get detailsRating => 'Рейтинг:';
@@ -848,6 +1651,173 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get commonClose => 'Закрыть';
 
+  @override
+  String get crashReportCollect => 'Отчёт о сбоях';
+
+  @override
+  String get appPermissions => 'Разрешения';
+
+  @override
+  String get installRecipe => 'Сценарий после установки';
+
+  @override
+  String get versionPin => 'Закрепить версию';
+
+  @override
+  String get versionUnpin => 'Открепить версию';
+
+  @override
+  String updateIgnoreVersion(String version) {
+    return 'Пропустить версию $version';
+  }
+
+  @override
+  String get updateStopIgnoring => 'Снова предлагать пропущенные версии';
+
+  @override
+  String get appRename => 'Переименовать';
+
+  @override
+  String appRenameTitle(String name) {
+    return 'Переименовать $name';
+  }
+
+  @override
+  String get appRenameHint => 'Название в каталоге и списках приложений';
+
+  @override
+  String get appRenameReset => 'Вернуть исходное название';
+
+  @override
+  String get appRenameSave => 'Сохранить';
+
+  @override
+  String get installRecipeHelp =>
+      'Шаги, выполняемые на шлеме при каждой установке этого приложения';
+
+  @override
+  String get installRecipeEnabled => 'Выполнять после установки';
+
+  @override
+  String get installRecipeNoSteps => 'Шагов пока нет';
+
+  @override
+  String get installRecipeAddStep => 'Добавить шаг';
+
+  @override
+  String installRecipeStepGrant(String permission) {
+    return 'Выдать $permission';
+  }
+
+  @override
+  String installRecipeStepPush(String local, String remote) {
+    return 'Отправить $local в $remote';
+  }
+
+  @override
+  String get installRecipeStepLaunch => 'Запустить один раз';
+
+  @override
+  String installRecipeStepProperty(String name, String value) {
+    return 'Установить $name = $value';
+  }
+
+  @override
+  String get installRecipeGrantPermission => 'Выдать разрешение';
+
+  @override
+  String get installRecipePushFile => 'Отправить файл';
+
+  @override
+  String get installRecipeSetProperty => 'Установить свойство';
+
+  @override
+  String get installRecipePermissionName => 'Имя разрешения';
+
+  @override
+  String get installRecipeLocalPath => 'Локальный файл или папка';
+
+  @override
+  String get installRecipeRemotePath => 'Путь на шлеме';
+
+  @override
+  String get installRecipePropertyName => 'Имя свойства';
+
+  @override
+  String get installRecipePropertyValue => 'Значение';
+
+  @override
+  String get installRecipeSave => 'Сохранить';
+
+  @override
+  String get installScriptReviewTitle => 'Выполнить скрипт установки?';
+
+  @override
+  String get installScriptShellWarning =>
+      'Этот скрипт выполняет команды оболочки на устройстве. Запускайте только скрипты из надёжных источников.';
+
+  @override
+  String installScriptLine(String line) {
+    return 'Строка $line';
+  }
+
+  @override
+  String get installScriptOpInstall => 'Установка';
+
+  @override
+  String get installScriptOpUninstall => 'Удаление';
+
+  @override
+  String get installScriptOpShell => 'Команда оболочки';
+
+  @override
+  String get installScriptOpPush => 'Копирование на устройство';
+
+  @override
+  String get installScriptOpPull => 'Копирование с устройства';
+
+  @override
+  String get installScriptOpPause => 'Пауза';
+
+  @override
+  String get installScriptRun => 'Выполнить скрипт';
+
+  @override
+  String get installScriptUnsupportedTitle => 'Скрипт установки не запущен';
+
+  @override
+  String get installScriptUnsupportedDescription =>
+      'Ничего не запущено, так как эти строки нельзя безопасно выполнить:';
+
+  @override
+  String appPermissionsTitle(String name) {
+    return 'Разрешения $name';
+  }
+
+  @override
+  String get appPermissionsRuntime => 'Разрешения времени выполнения';
+
+  @override
+  String get appPermissionsOther => 'Другие разрешения';
+
+  @override
+  String get appPermissionsGrantedAtInstall => 'Выдано при установке';
+
+  @override
+  String get appPermissionsNotGranted => 'Не выдано';
+
+  @override
+  String get appPermissionsNone => 'Приложение не запрашивает разрешений';
+
+  @override
+  String get crashReportSaved =>
+      'Отчёт о сбоях сохранён, путь скопирован в буфер обмена';
+
+  @override
+  String crashReportFailed(String error) {
+    return 'Не удалось собрать отчёт о сбоях: $error';
+  }
+
   @override
   String get commonCancel => 'Отмена';
 
@@ -873,28 +1843,65 @@ class AppLocalizationsRu extends AppLocalizations {
   String get newerVersion => 'Более новая версия';
 
   @override
-  String get sameVersion => 'Эта же версия';
+  String get sameVersion => 'Эта же версия';
+
+  @override
+  String get olderVersion => 'Более старая версия';
+
+  @override
+  String get update => 'Обновить';
+
+  @override
+  String get install => 'Установить';
+
+  @override
+  String get checkForUpdates => 'Проверить обновления';
+
+  @override
+  String get noMatchingCloudApp =>
+      'Нет подходящего приложения в облачном репозитории';
+
+  @override
+  String updateTo(String to) {
+    return 'Обновить до $to';
+  }
+
+  @override
+  String get updateAll => 'Обновить все';
+
+  @override
+  String updateAllTitle(String count) {
+    return 'Обновить приложения: $count';
+  }
+
+  @override
+  String get updateAllCanary =>
+      'Сначала проверить на самом маленьком приложении';
 
   @override
-  String get olderVersion => 'Более старая версия';
+  String get updateAllCanaryDescription =>
+      'Обновляет и запускает одно приложение, затем спрашивает, обновлять ли остальные';
 
   @override
-  String get update => 'Обновить';
+  String updateAllCanarySucceeded(String app) {
+    return '$app обновлено и запущено';
+  }
 
   @override
-  String get install => 'Установить';
+  String updateAllCanaryFailed(String app) {
+    return 'Не удалось обновить $app';
+  }
 
   @override
-  String get checkForUpdates => 'Проверить обновления';
+  String updateAllContinuePrompt(String count) {
+    return 'Обновить оставшиеся приложения ($count)?';
+  }
 
   @override
-  String get noMatchingCloudApp =>
-      'Нет подходящего приложения в облачном репозитории';
+  String get updateAllContinue => 'Продолжить';
 
   @override
-  String updateTo(String to) {
-    return 'Обновить до $to';
-  }
+  String get updateAllStop => 'Остановить';
 
   @override
   String get downgradeAppTitle => 'Откат приложения';
@@ -972,6 +1979,12 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get addToFavorites => 'Добавить в избранное';
 
+  @override
+  String get watchApp => 'Уведомлять о новых версиях';
+
+  @override
+  String get stopWatchingApp => 'Не уведомлять';
+
   @override
   String get removeFromFavorites => 'Убрать из избранного';
 
@@ -999,6 +2012,42 @@ class AppLocalizationsRu extends AppLocalizations {
   String get downloadAndInstallNotConnected =>
       'Скачать и установить на устройство (не подключено)';
 
+  @override
+  String get releaseFilesChoose => 'Выбрать файлы…';
+
+  @override
+  String get releaseFilesTitle => 'Выбор файлов для загрузки';
+
+  @override
+  String get releaseFilesLoading => 'Получение списка файлов…';
+
+  @override
+  String releaseFilesFailed(String error) {
+    return 'Не удалось получить список файлов: $error';
+  }
+
+  @override
+  String get releaseFilesAll => 'Все';
+
+  @override
+  String get releaseFilesApkOnly => 'Только APK';
+
+  @override
+  String get releaseFilesObbOnly => 'Только OBB';
+
+  @override
+  String releaseFilesSelected(String count, String size) {
+    return 'Выбрано файлов: $count, $size';
+  }
+
+  @override
+  String get releaseFilesDownloaded => 'Уже загружен';
+
+  @override
+  String releaseFilesToTransfer(String size) {
+    return 'Будет загружено: $size';
+  }
+
   @override
   String get cloudStatusInstalled => 'Установлено';
 
@@ -1034,6 +2083,24 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get sortSizeLargest => 'Размер (сначала большие)';
 
+  @override
+  String get exportInstalledList => 'Экспорт установленных приложений';
+
+  @override
+  String get exportInstalledListCsv => 'Экспорт в CSV';
+
+  @override
+  String get exportInstalledListJson => 'Экспорт в JSON';
+
+  @override
+  String get installedListExportFailed =>
+      'Не удалось экспортировать установленные приложения';
+
+  @override
+  String installedListExported(String count, String path) {
+    return 'Экспортировано приложений: $count в $path';
+  }
+
   @override
   String get sortUpdateAvailableFirst => 'Обновления (сначала доступные)';
 
@@ -1153,6 +2220,12 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get batteryLabel => 'Батарея';
 
+  @override
+  String get firmwareLabel => 'Прошивка';
+
+  @override
+  String get lastConnectedLabel => 'Последнее подключение';
+
   @override
   String get powerOffDevice => 'Выключить устройство';
 
@@ -1165,6 +2238,16 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get rebootMenu => 'Перезагрузить...';
 
+  @override
+  String get pairControllersMenu => 'Сопряжение контроллеров...';
+
+  @override
+  String get pairControllersTitle => 'Сопряжение контроллеров';
+
+  @override
+  String get pairControllersConfirm =>
+      'Перевести контроллеры в режим сопряжения? Они будут отключены, пока не будут сопряжены снова.';
+
   @override
   String get rebootOptions => 'Параметры перезагрузки';
 
@@ -1184,155 +2267,405 @@ class AppLocalizationsRu extends AppLocalizations {
   String get rebootDevice => 'Перезагрузить устройство';
 
   @override
-  String get rebootNowConfirm => 'Перезагрузить устройство сейчас?';
+  String get rebootNowConfirm => 'Перезагрузить устройство сейчас?';
+
+  @override
+  String get rebootToBootloader => 'Перезагрузить в загрузчик';
+
+  @override
+  String get rebootToBootloaderConfirm =>
+      'Перезагрузить устройство в загрузчик?';
+
+  @override
+  String get rebootToRecovery => 'Перезагрузить в recovery';
+
+  @override
+  String get rebootToRecoveryConfirm => 'Перезагрузить устройство в recovery?';
+
+  @override
+  String get rebootToFastboot => 'Перезагрузить в fastboot';
+
+  @override
+  String get rebootToFastbootConfirm => 'Перезагрузить устройство в fastboot?';
+
+  @override
+  String get commonConfirm => 'Подтвердить';
+
+  @override
+  String get delete => 'Удалить';
+
+  @override
+  String get remove => 'Убрать';
+
+  @override
+  String get restore => 'Восстановить';
+
+  @override
+  String get mute => 'Выключить звук';
+
+  @override
+  String get unmute => 'Включить звук';
+
+  @override
+  String get close => 'Закрыть';
+
+  @override
+  String get pause => 'Пауза';
+
+  @override
+  String get checkingTrailerAvailability => 'Проверка доступности трейлера...';
+
+  @override
+  String get trailerAvailable => 'Трейлер доступен';
+
+  @override
+  String get noTrailer => 'Трейлер не доступен';
+
+  @override
+  String get backupsTitle => 'Резервные копии';
+
+  @override
+  String get openBackupsFolder => 'Открыть папку резервных копий';
+
+  @override
+  String get openDownloadsFolder => 'Открыть папку загрузок';
+
+  @override
+  String get downloadsTitle => 'Загрузки';
+
+  @override
+  String get deleteAllDownloads => 'Удалить все загрузки';
+
+  @override
+  String get deleteAllDownloadsTitle => 'Удалить все загрузки';
+
+  @override
+  String get deleteAllDownloadsConfirm =>
+      'Вы уверены, что хотите удалить все загрузки?';
+
+  @override
+  String deleteAllDownloadsResult(String removed, String skipped) {
+    return 'Удалено $removed, пропущено $skipped';
+  }
+
+  @override
+  String get dedupeDownloads => 'Убрать дубликаты в загрузках';
+
+  @override
+  String get dedupeDownloadsRunning =>
+      'Подсчёт контрольных сумм файлов, это может занять время';
+
+  @override
+  String dedupeDownloadsResult(String linked, String saved) {
+    return 'Связано файлов: $linked, всего сэкономлено $saved';
+  }
+
+  @override
+  String get deleteDownloadTitle => 'Удалить загрузку';
+
+  @override
+  String deleteDownloadConfirm(String name) {
+    return 'Удалить \"$name\"?';
+  }
+
+  @override
+  String get downloadDeletedTitle => 'Загрузка удалена';
+
+  @override
+  String get noBackupsFound => 'Резервные копии не найдены.';
+
+  @override
+  String get noDownloadsFound => 'Загрузки не найдены.';
+
+  @override
+  String get searchDownloadsHint => 'Поиск загрузок...';
+
+  @override
+  String get noMatchingDownloads => 'Нет загрузок, соответствующих запросу.';
+
+  @override
+  String get unsupportedPlatform => 'Платформа не поддерживается';
+
+  @override
+  String get folderPathCopied => 'Путь к папке скопирован в буфер обмена';
+
+  @override
+  String unableToOpenFolder(String path) {
+    return 'Не удалось открыть папку: $path';
+  }
+
+  @override
+  String get openFolderTooltip => 'Открыть папку';
+
+  @override
+  String get taskArtifactReveal => 'Показать в папке';
+
+  @override
+  String get downloadHealthCheck => 'Проверить папку';
+
+  @override
+  String get downloadHealthChecking => 'Проверка папки…';
+
+  @override
+  String get downloadHealthReady => 'Готово к установке';
+
+  @override
+  String get downloadHealthFixIssues => 'Перед установкой исправьте следующее:';
+
+  @override
+  String downloadHealthFailed(String error) {
+    return 'Не удалось проверить папку: $error';
+  }
+
+  @override
+  String get downloadHealthUsesScript => 'Устанавливается через install.txt';
+
+  @override
+  String get healthIssueNoApk => 'Не найден ни APK, ни install.txt';
+
+  @override
+  String healthIssueMultipleApks(String files) {
+    return 'Несколько APK без install.txt: $files';
+  }
+
+  @override
+  String healthIssueApkUnreadable(String file, String error) {
+    return 'Не удалось прочитать $file: $error';
+  }
+
+  @override
+  String healthIssueObbFolderMismatch(String folder, String package) {
+    return 'Папка OBB $folder должна называться $package';
+  }
+
+  @override
+  String healthIssueObbFileMismatch(String file, String package) {
+    return '$file относится не к пакету $package';
+  }
+
+  @override
+  String healthIssueLooseObb(String files) {
+    return 'Файлы OBB должны лежать в папке с именем пакета: $files';
+  }
+
+  @override
+  String healthIssueScriptUnreadable(String error) {
+    return 'Не удалось прочитать install.txt: $error';
+  }
+
+  @override
+  String healthIssueScriptFileMissing(String line, String path) {
+    return 'install.txt, строка $line: отсутствует $path';
+  }
+
+  @override
+  String healthIssueScriptUnsupported(String line, String command) {
+    return 'install.txt, строка $line: неподдерживаемая команда $command';
+  }
+
+  @override
+  String healthIssueArchiveUnreadable(String file, String error) {
+    return 'Не удалось прочитать содержимое $file: $error';
+  }
+
+  @override
+  String get unknownTime => 'Неизвестное время';
+
+  @override
+  String get partAPK => 'APK';
+
+  @override
+  String get partPrivate => 'Приватные';
+
+  @override
+  String get partShared => 'Общие';
+
+  @override
+  String get partOBB => 'OBB';
+
+  @override
+  String get backupCompressed => 'Сжато';
+
+  @override
+  String get noPartsDetected => 'Компоненты не обнаружены';
+
+  @override
+  String get deleteBackupTitle => 'Удалить резервную копию';
+
+  @override
+  String deleteBackupConfirm(String name) {
+    return 'Удалить \"$name\"?';
+  }
+
+  @override
+  String get backupDeletedTitle => 'Резервная копия удалена';
 
   @override
-  String get rebootToBootloader => 'Перезагрузить в загрузчик';
+  String get backupsCleanUp => 'Очистить старые резервные копии';
 
   @override
-  String get rebootToBootloaderConfirm =>
-      'Перезагрузить устройство в загрузчик?';
+  String get backupRestoreToPackage => 'Восстановить в другое приложение';
 
   @override
-  String get rebootToRecovery => 'Перезагрузить в recovery';
+  String get backupRestoreTargetPackage => 'Имя целевого пакета';
 
   @override
-  String get rebootToRecoveryConfirm => 'Перезагрузить устройство в recovery?';
+  String get backupRestoreInvalidPackage => 'Некорректное имя пакета';
 
   @override
-  String get rebootToFastboot => 'Перезагрузить в fastboot';
+  String get backupRestorePackageNotInstalled =>
+      'Этот пакет не установлен на устройстве';
 
   @override
-  String get rebootToFastbootConfirm => 'Перезагрузить устройство в fastboot?';
+  String get backupRestoreSamePackage =>
+      'Резервная копия уже относится к этому пакету';
 
   @override
-  String get commonConfirm => 'Подтвердить';
+  String backupRestoreToPackageDescription(String name) {
+    return 'Данные из $name будут восстановлены в выбранное приложение, которое уже должно быть установлено. OBB-файлы будут переименованы под него.';
+  }
 
   @override
-  String get delete => 'Удалить';
+  String get backupVerify => 'Проверить резервную копию';
 
   @override
-  String get remove => 'Убрать';
+  String get backupVerifyOk => 'Резервная копия в порядке';
 
   @override
-  String get restore => 'Восстановить';
+  String backupVerifyOkFiles(String count) {
+    return 'Файлов, совпадающих с манифестом: $count';
+  }
 
   @override
-  String get mute => 'Выключить звук';
+  String get backupVerifyNoFileList =>
+      'Эта резервная копия создана до появления списков файлов, проверена только её структура';
 
   @override
-  String get unmute => 'Включить звук';
+  String backupVerifyProblemsTitle(String name) {
+    return 'Проблемы в $name';
+  }
 
   @override
-  String get close => 'Закрыть';
+  String get backupVerifyProblemsWarning =>
+      'Эта резервная копия может восстановиться с ошибками. Не удаляйте приложение, пока не появится копия без проблем.';
 
   @override
-  String get pause => 'Пауза';
+  String backupFromDevice(String device) {
+    return 'с $device';
+  }
 
   @override
-  String get checkingTrailerAvailability => 'Проверка доступности трейлера...';
+  String get searchBackupsHint => 'Поиск резервных копий...';
 
   @override
-  String get trailerAvailable => 'Трейлер доступен';
+  String get noMatchingBackups =>
+      'Нет резервных копий, соответствующих запросу и фильтрам.';
 
   @override
-  String get noTrailer => 'Трейлер не доступен';
+  String get backupsFilter => 'Фильтр';
 
   @override
-  String get backupsTitle => 'Резервные копии';
+  String get backupsFilterApk => 'С APK';
 
   @override
-  String get openBackupsFolder => 'Открыть папку резервных копий';
+  String get backupsFilterData => 'С данными приложения';
 
   @override
-  String get openDownloadsFolder => 'Открыть папку загрузок';
+  String get backupsFilterObb => 'С файлами OBB';
 
   @override
-  String get downloadsTitle => 'Загрузки';
+  String get backupsDateRange => 'Период';
 
   @override
-  String get deleteAllDownloads => 'Удалить все загрузки';
+  String get backupsClearDateRange => 'Сбросить период';
 
   @override
-  String get deleteAllDownloadsTitle => 'Удалить все загрузки';
+  String backupsSummary(String count, String total, String size) {
+    return 'Резервных копий: $count из $total, $size';
+  }
 
   @override
-  String get deleteAllDownloadsConfirm =>
-      'Вы уверены, что хотите удалить все загрузки?';
+  String get backupsPruneTitle => 'Очистка резервных копий';
 
   @override
-  String deleteAllDownloadsResult(String removed, String skipped) {
-    return 'Удалено $removed, пропущено $skipped';
-  }
+  String get backupsPruneNothing =>
+      'Нет резервных копий, превышающих ограничения хранения из настроек.';
 
   @override
-  String get deleteDownloadTitle => 'Удалить загрузку';
+  String get backupsPruneFailed =>
+      'Не удалось удалить некоторые резервные копии';
 
   @override
-  String deleteDownloadConfirm(String name) {
-    return 'Удалить \"$name\"?';
+  String backupsPruned(String count, String size) {
+    return 'Удалено старых резервных копий: $count, освобождено $size';
   }
 
   @override
-  String get downloadDeletedTitle => 'Загрузка удалена';
-
-  @override
-  String get noBackupsFound => 'Резервные копии не найдены.';
+  String backupsPruneConfirm(String count, String size) {
+    return 'Будет удалено резервных копий: $count, освободится $size:';
+  }
 
   @override
-  String get noDownloadsFound => 'Загрузки не найдены.';
+  String get fatalErrorTitle => 'Критическая ошибка';
 
   @override
-  String get unsupportedPlatform => 'Платформа не поддерживается';
+  String get exitApplication => 'Выход из приложения';
 
   @override
-  String get folderPathCopied => 'Путь к папке скопирован в буфер обмена';
+  String get errorCopied => 'Сообщение об ошибке скопировано';
 
   @override
-  String unableToOpenFolder(String path) {
-    return 'Не удалось открыть папку: $path';
-  }
+  String get coreApiMismatchTitle => 'Несовместимые компоненты приложения';
 
   @override
-  String get openFolderTooltip => 'Открыть папку';
+  String get coreApiMismatchDescription =>
+      'Интерфейс и ядро приложения относятся к разным версиям, некоторые функции могут не работать. Переустановите приложение, чтобы исправить это.';
 
   @override
-  String get unknownTime => 'Неизвестное время';
+  String get appUpdateAvailableTitle => 'Доступно обновление';
 
   @override
-  String get partAPK => 'APK';
+  String get appUpdatesTitle => 'Обновления';
 
   @override
-  String get partPrivate => 'Приватные';
+  String get checkForAppUpdates => 'Проверить обновления';
 
   @override
-  String get partShared => 'Общие';
+  String get appUpToDate => 'Установлена последняя версия';
 
   @override
-  String get partOBB => 'OBB';
+  String get appUpdateDownload => 'Скачать обновление';
 
   @override
-  String get noPartsDetected => 'Компоненты не обнаружены';
+  String get appUpdateReleasePage => 'Открыть страницу релиза';
 
   @override
-  String get deleteBackupTitle => 'Удалить резервную копию';
+  String get appUpdateNoBuild => 'В этом релизе нет сборки для вашей платформы';
 
   @override
-  String deleteBackupConfirm(String name) {
-    return 'Удалить \"$name\"?';
+  String appUpdateAvailableDescription(String version) {
+    return 'Доступна YAAS $version. Скачать её можно на странице «О программе».';
   }
 
   @override
-  String get backupDeletedTitle => 'Резервная копия удалена';
+  String appUpdateNewVersion(String version) {
+    return 'Доступна версия $version';
+  }
 
   @override
-  String get fatalErrorTitle => 'Критическая ошибка';
+  String appUpdateCheckFailed(String error) {
+    return 'Не удалось проверить обновления: $error';
+  }
 
   @override
-  String get exitApplication => 'Выход из приложения';
+  String appUpdateDownloaded(String path) {
+    return 'Скачано в $path';
+  }
 
   @override
-  String get errorCopied => 'Сообщение об ошибке скопировано';
+  String appUpdateDownloadFailed(String error) {
+    return 'Не удалось скачать: $error';
+  }
 
   @override
   String get copyError => 'Копировать ошибку';
@@ -1341,14 +2674,14 @@ class AppLocalizationsRu extends AppLocalizations {
   String get selectAppDirectoryTitle => 'Выберите папку приложения';
 
   @override
-  String get selectApkFileTitle => 'Выберите файл APK';
+  String get selectApkFileTitle => 'Выберите файл APK или бандл';
 
   @override
   String get selectedInvalidDir =>
       'Выбранный путь не является директорией приложения';
 
   @override
-  String get selectedInvalidApk => 'Выбранный путь не является корректным APK';
+  String get selectedInvalidApk => 'Выбранный путь не является корректным APK или бандлом';
 
   @override
   String get singleApk => 'Один APK';
@@ -1367,7 +2700,7 @@ class AppLocalizationsRu extends AppLocalizations {
       'Выберите или введите путь к папке приложения';
 
   @override
-  String get pathHintApk => 'Выберите или введите путь к файлу APK';
+  String get pathHintApk => 'Выберите или введите путь к APK или бандлу XAPK/APKS/APKM';
 
   @override
   String get directoryRequirements =>
@@ -1386,6 +2719,34 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get installApk => 'Установить APK';
 
+  @override
+  String get apkPreviewNoNativeLibs => 'Без нативных библиотек';
+
+  @override
+  String apkPreviewFailed(String error) {
+    return 'Не удалось прочитать APK: $error';
+  }
+
+  @override
+  String apkPreviewVersion(String version) {
+    return 'Версия $version';
+  }
+
+  @override
+  String apkPreviewSdk(String min, String target) {
+    return 'Android SDK: мин. $min, целевой $target';
+  }
+
+  @override
+  String apkPreviewAbis(String abis) {
+    return 'Нативные библиотеки: $abis';
+  }
+
+  @override
+  String apkPreviewPermissions(String count) {
+    return 'Запрашиваемые разрешения ($count)';
+  }
+
   @override
   String get tasksTitle => 'Задачи';
 
@@ -1401,6 +2762,32 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get tasksEmptyRecent => 'Нет недавних задач';
 
+  @override
+  String get tasksTabHistory => 'История';
+
+  @override
+  String get tasksEmptyHistory => 'Операций пока нет';
+
+  @override
+  String historyLoadFailed(String error) {
+    return 'Не удалось загрузить историю: $error';
+  }
+
+  @override
+  String get historyActionInstall => 'Установка';
+
+  @override
+  String get historyActionUninstall => 'Удаление';
+
+  @override
+  String get historyActionBackup => 'Резервное копирование';
+
+  @override
+  String get historyActionRestore => 'Восстановление';
+
+  @override
+  String get historyActionDownload => 'Загрузка';
+
   @override
   String get clearRecentTasks => 'Очистить недавние задачи';
 
@@ -1420,6 +2807,18 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get cancelTask => 'Отменить задачу';
 
+  @override
+  String get taskPriority => 'Приоритет';
+
+  @override
+  String get taskPriorityHigh => 'Высокий';
+
+  @override
+  String get taskPriorityNormal => 'Обычный';
+
+  @override
+  String get taskPriorityLow => 'Низкий';
+
   @override
   String get taskKindDownload => 'Скачать';
 
@@ -1444,6 +2843,30 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get taskKindDonateApp => 'Пожертвование';
 
+  @override
+  String get taskKindResumeDownload => 'Возобновление загрузки';
+
+  @override
+  String get taskKindCaptureModSet => 'Сохранение набора модов';
+
+  @override
+  String get taskKindApplyModSet => 'Применение набора модов';
+
+  @override
+  String get taskKindBackupAll => 'Резервное копирование всех приложений';
+
+  @override
+  String get taskKindPushObb => 'Загрузка OBB';
+
+  @override
+  String get taskKindReinstallFromTrash => 'Переустановка из корзины';
+
+  @override
+  String get taskKindReceiveFromPeer => 'Получение от соседа';
+
+  @override
+  String get taskKindTransferAppData => 'Перенос данных приложения';
+
   @override
   String get taskStatusWaiting => 'Ожидание';
 
@@ -1462,9 +2885,100 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get taskUnknown => 'Неизвестно';
 
+  @override
+  String get taskRestored => 'Возобновлено из предыдущей сессии';
+
+  @override
+  String tasksResumedTitle(String count) {
+    return 'Возобновлено незавершённых задач: $count';
+  }
+
+  @override
+  String get tasksResumedDescription => 'Нажмите, чтобы открыть список задач';
+
+  @override
+  String taskDownloadSummary(String size, String duration, String average, String peak) {
+    return '$size за $duration · в среднем $average/с, пик $peak/с';
+  }
+
+  @override
+  String taskDownloadSource(String source) {
+    return 'Источник: $source';
+  }
+
+  @override
+  String taskDownloadRetries(String count) {
+    return 'Продолжено после прерванных попыток: $count';
+  }
+
+  @override
+  String taskTimeLeft(String time) {
+    return 'осталось $time';
+  }
+
+  @override
+  String taskTransferBytes(String done, String total) {
+    return '$done / $total';
+  }
+
+  @override
+  String taskTransferSpeed(String speed) {
+    return '$speed/с';
+  }
+
+  @override
+  String taskTransferFiles(String done, String total) {
+    return 'файл $done из $total';
+  }
+
+  @override
+  String get transferStatsTitle => 'Статистика передачи';
+
+  @override
+  String get transferStatsSession => 'Этот сеанс';
+
+  @override
+  String transferStatsLifetime(String date) {
+    return 'С $date';
+  }
+
+  @override
+  String get transferStatsDownloaded => 'Скачано';
+
+  @override
+  String get transferStatsPushed => 'Передано на устройства';
+
+  @override
+  String get transferStatsDownloads => 'Завершено загрузок';
+
+  @override
+  String get transferStatsInstalls => 'Завершено установок';
+
+  @override
+  String transferStatsAverageSpeed(String size, String speed) {
+    return '$size, в среднем $speed/с';
+  }
+
+  @override
+  String get transferStatsReset => 'Сбросить общую статистику';
+
   @override
   String get backupOptionsTitle => 'Параметры резервного копирования';
 
+  @override
+  String get backupAllAppsTitle => 'Резервное копирование всех приложений';
+
+  @override
+  String get backupAllApps => 'Создать резервные копии всех приложений';
+
+  @override
+  String get pushObb => 'Загрузить OBB-файлы из папки';
+
+  @override
+  String pushObbSelectFolder(String app) {
+    return 'Выберите папку OBB для $app';
+  }
+
   @override
   String get backupSelectParts => 'Выберите части для копирования:';
 
@@ -1483,6 +2997,22 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get backupNameSuffixHint => 'например: перед обновлением';
 
+  @override
+  String get backupCompress => 'Сжать в один файл';
+
+  @override
+  String get backupEstimating => 'Измерение размера копии…';
+
+  @override
+  String backupEstimate(String size, String time) {
+    return 'Ожидаемый размер: $size, примерно $time';
+  }
+
+  @override
+  String backupEstimateFailed(String error) {
+    return 'Не удалось оценить размер копии: $error';
+  }
+
   @override
   String get startBackup => 'Начать копирование';
 
@@ -1543,6 +3073,44 @@ class AppLocalizationsRu extends AppLocalizations {
     return 'Не удалось открыть папку логов (скопировано): $path';
   }
 
+  @override
+  String get logHistory => 'История логов';
+
+  @override
+  String get logHistoryModuleHint => 'Модуль, например hub::adb';
+
+  @override
+  String get logHistoryMinLevel => 'Минимальный уровень';
+
+  @override
+  String get logHistoryAnyDate => 'Любая дата';
+
+  @override
+  String get logHistoryLoadMore => 'Загрузить ещё';
+
+  @override
+  String get logHistoryEmpty => 'Нет записей, соответствующих фильтрам';
+
+  @override
+  String logHistoryCount(String shown, String total) {
+    return 'Записей: $shown из $total';
+  }
+
+  @override
+  String get createDiagnosticsBundle => 'Создать диагностический архив';
+
+  @override
+  String get diagnosticsBundleCreating => 'Создание диагностического архива...';
+
+  @override
+  String get diagnosticsBundleSaved =>
+      'Диагностический архив сохранён, путь скопирован в буфер обмена';
+
+  @override
+  String diagnosticsBundleFailed(String error) {
+    return 'Не удалось создать диагностический архив: $error';
+  }
+
   @override
   String get uninstallAppTitle => 'Удалить приложение';
 
@@ -1637,6 +3205,55 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get donateDonateButton => 'Загрузить';
 
+  @override
+  String donationUploadedTitle(String archive) {
+    return 'Выгружено: $archive';
+  }
+
+  @override
+  String donationUploadedDescription(String md5) {
+    return 'Спасибо, что поделились! MD5: $md5';
+  }
+
+  @override
+  String appTrashedTitle(String name) {
+    return '$name перемещено в корзину';
+  }
+
+  @override
+  String get appTrashedDescription =>
+      'Нажмите, чтобы переустановить его с данными';
+
+  @override
+  String get trashTitle => 'Корзина';
+
+  @override
+  String get trashEmpty => 'Корзина пуста';
+
+  @override
+  String trashTrashedAt(String date) {
+    return 'Перемещено в корзину $date';
+  }
+
+  @override
+  String trashExpires(String date) {
+    return 'будет удалено после $date';
+  }
+
+  @override
+  String get trashReinstall => 'Переустановить с данными';
+
+  @override
+  String get trashDelete => 'Удалить из корзины';
+
+  @override
+  String trashDeleteTitle(String name) {
+    return 'Удалить $name из корзины?';
+  }
+
+  @override
+  String get trashDeletePrompt => 'Резервная копия APK и данных будет удалена.';
+
   @override
   String get donateNoAppsAvailable => 'Нет приложений для загрузки';
 
@@ -1687,6 +3304,18 @@ class AppLocalizationsRu extends AppLocalizations {
   @override
   String get lowSpaceWarningContinue => 'Всё равно продолжить';
 
+  @override
+  String get insufficientSpaceTitle => 'Недостаточно места на устройстве';
+
+  @override
+  String insufficientSpaceDescription(
+    String needed,
+    String available,
+    String missing,
+  ) {
+    return 'Требуется $needed, но доступно только $available. Освободите не менее $missing.';
+  }
+
   @override
   String get proximityStatusEnabled => 'Включён';
 
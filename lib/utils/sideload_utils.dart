@@ -11,9 +11,18 @@ import 'utils.dart';
 const int _lowSpaceThresholdBytes = 2 * 1000 * 1000 * 1000;
 
 class SideloadUtils {
+  /// Extensions of split APK bundles, installed like single APK files
+  static const apkBundleExtensions = ['xapk', 'apks', 'apkm'];
+
+  static bool isApkBundle(String path) {
+    final lower = path.toLowerCase();
+    return apkBundleExtensions.any((ext) => lower.endsWith('.$ext'));
+  }
+
   static bool isValidApkFile(String path) {
     final file = File(path);
-    return file.existsSync() && file.path.toLowerCase().endsWith('.apk');
+    return file.existsSync() &&
+        (file.path.toLowerCase().endsWith('.apk') || isApkBundle(path));
   }
 
   static bool isDirectoryValid(String path) {
@@ -42,16 +51,37 @@ class SideloadUtils {
     (isDirectory
             ? TaskRequest(
                 task: TaskInstallLocalApp(value: path),
+                priority: TaskPriority.normal,
               )
             : TaskRequest(
                 task: TaskInstallApk(value: path),
+                priority: TaskPriority.normal,
               ))
         .sendSignalToRust();
   }
 
-  static void restoreBackup(String backupPath) {
+  static void restoreBackup(String backupPath, {String? targetPackage}) {
+    TaskRequest(
+      task: TaskRestoreBackup(path: backupPath, targetPackage: targetPackage),
+      priority: TaskPriority.normal,
+    ).sendSignalToRust();
+  }
+
+  static void pushObb(String packageName, String localDir) {
     TaskRequest(
-      task: TaskRestoreBackup(value: backupPath),
+      task: TaskPushObb(packageName: packageName, localDir: localDir),
+      priority: TaskPriority.normal,
+    ).sendSignalToRust();
+  }
+
+  static void receiveFromPeer(PeerShare share) {
+    TaskRequest(
+      task: TaskReceiveFromPeer(
+        address: share.address,
+        shareId: share.id,
+        name: share.name,
+      ),
+      priority: TaskPriority.normal,
     ).sendSignalToRust();
   }
 
@@ -82,6 +112,32 @@ class SideloadUtils {
     );
   }
 
+  /// Opens the folder of a task artifact, with the file selected for file
+  /// artifacts where the file manager supports it
+  static Future<void> revealArtifact(
+      BuildContext context, TaskArtifact artifact) async {
+    final l10n = AppLocalizations.of(context);
+    final path = artifact.path;
+    final folder = artifact.isDirectory ? path : File(path).parent.path;
+    try {
+      if (Platform.isLinux) {
+        await Process.run('xdg-open', [folder]);
+      } else if (Platform.isMacOS) {
+        await Process.run(
+            'open', artifact.isDirectory ? [path] : ['-R', path]);
+      } else if (Platform.isWindows) {
+        await Process.run(
+            'explorer', artifact.isDirectory ? [path] : ['/select,', path]);
+      } else {
+        copyToClipboard(context, folder,
+            title: l10n.folderPathCopied, description: folder);
+      }
+    } catch (e) {
+      if (!context.mounted) return;
+      showErrorToast(context, l10n.unableToOpenFolder(folder));
+    }
+  }
+
   /// Calculates the total size of a file or directory in bytes.
   static int calculateSize(String path, bool isDirectory) {
     if (isDirectory) {
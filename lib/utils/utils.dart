@@ -107,3 +107,50 @@ String formatSize(int bytes, int decimals) {
     decimals: decimals,
   );
 }
+
+/// Formats a duration in seconds as `42s`, `3m 05s` or `1h 07m`
+String formatSeconds(int seconds) {
+  if (seconds < 60) return '${seconds}s';
+  final minutes = seconds ~/ 60;
+  if (minutes < 60) {
+    return '${minutes}m ${(seconds % 60).toString().padLeft(2, '0')}s';
+  }
+  return '${minutes ~/ 60}h ${(minutes % 60).toString().padLeft(2, '0')}m';
+}
+
+String formatTaskKind(AppLocalizations l10n, TaskKind kind) {
+  switch (kind) {
+    case TaskKind.download:
+      return l10n.taskKindDownload;
+    case TaskKind.downloadInstall:
+      return l10n.taskKindDownloadInstall;
+    case TaskKind.installApk:
+      return l10n.taskKindInstallApk;
+    case TaskKind.installLocalApp:
+      return l10n.taskKindInstallLocalApp;
+    case TaskKind.uninstall:
+      return l10n.taskKindUninstall;
+    case TaskKind.backupApp:
+      return l10n.taskKindBackupApp;
+    case TaskKind.restoreBackup:
+      return l10n.taskKindRestoreBackup;
+    case TaskKind.donateApp:
+      return l10n.taskKindDonateApp;
+    case TaskKind.resumeDownload:
+      return l10n.taskKindResumeDownload;
+    case TaskKind.captureModSet:
+      return l10n.taskKindCaptureModSet;
+    case TaskKind.applyModSet:
+      return l10n.taskKindApplyModSet;
+    case TaskKind.backupAll:
+      return l10n.taskKindBackupAll;
+    case TaskKind.pushObb:
+      return l10n.taskKindPushObb;
+    case TaskKind.reinstallFromTrash:
+      return l10n.taskKindReinstallFromTrash;
+    case TaskKind.receiveFromPeer:
+      return l10n.taskKindReceiveFromPeer;
+    case TaskKind.transferAppData:
+      return l10n.taskKindTransferAppData;
+  }
+}
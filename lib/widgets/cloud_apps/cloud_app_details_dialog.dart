@@ -3,19 +3,18 @@ import 'dart:io';
 
 import 'package:flutter/material.dart';
 import 'package:provider/provider.dart';
-import 'package:cached_network_image/cached_network_image.dart';
 import 'package:video_player/video_player.dart';
-import 'package:flutter_cache_manager/flutter_cache_manager.dart';
-import 'package:http/http.dart' as http;
 import 'package:intl/intl.dart';
 import 'package:url_launcher/url_launcher.dart';
 
 import '../../src/bindings/bindings.dart';
 import 'package:rinf/rinf.dart';
 import '../../src/l10n/app_localizations.dart';
-import '../../providers/device_state.dart';
 import '../../providers/cloud_apps_state.dart';
+import '../../providers/device_state.dart';
+import '../../providers/settings_state.dart';
 import '../../utils/utils.dart';
+import '../dialogs/release_files_dialog.dart';
 import 'cloud_app_list.dart';
 
 class CloudAppDetailsDialog extends StatefulWidget {
@@ -274,6 +273,17 @@ class _CloudAppDetailsDialogState extends State<CloudAppDetailsDialog> {
           },
           child: Text(l10n.commonCopy),
         ),
+        Consumer<CloudAppsState>(builder: (context, cloudAppsState, _) {
+          final package = widget.cachedApp.app.truePackageName;
+          final watched = cloudAppsState.isWatched(package);
+          return TextButton.icon(
+            onPressed: () => cloudAppsState.setWatched(package, !watched),
+            icon: Icon(watched
+                ? Icons.notifications_active
+                : Icons.notifications_none),
+            label: Text(watched ? l10n.stopWatchingApp : l10n.watchApp),
+          );
+        }),
         TextButton(
           onPressed: () => widget.onDownload(widget.cachedApp.app.fullName,
               widget.cachedApp.app.truePackageName),
@@ -286,6 +296,19 @@ class _CloudAppDetailsDialogState extends State<CloudAppDetailsDialog> {
             ],
           ),
         ),
+        Consumer<SettingsState>(builder: (context, settings, _) {
+          if (!settings.downloaderSupportsPartialDownloads) {
+            return const SizedBox.shrink();
+          }
+          return TextButton(
+            onPressed: () => showDialog<void>(
+              context: context,
+              builder: (context) =>
+                  ReleaseFilesDialog(app: widget.cachedApp.app),
+            ),
+            child: Text(l10n.releaseFilesChoose),
+          );
+        }),
         Consumer<DeviceState>(builder: (context, deviceState, _) {
           return FilledButton.icon(
             onPressed: deviceState.isConnected
@@ -763,22 +786,20 @@ class _InlineNetworkImage extends StatelessWidget {
       child: const Center(child: Icon(Icons.broken_image_outlined, size: 40)),
     );
 
-    final img = CachedNetworkImage(
-      imageUrl: url,
-      placeholder: (_, __) => placeholder,
-      errorWidget: (_, __, ___) => error,
-      imageBuilder: (context, provider) => ClipRRect(
-        borderRadius: borderRadius,
-        child: Container(
-          constraints: const BoxConstraints(maxHeight: 360),
-          color: Colors.black12,
-          child: GestureDetector(
-            onTap: onTap,
-            child: Image(
-              image: provider,
-              fit: BoxFit.contain,
-              width: double.infinity,
-            ),
+    final img = ClipRRect(
+      borderRadius: borderRadius,
+      child: Container(
+        constraints: const BoxConstraints(maxHeight: 360),
+        color: Colors.black12,
+        child: GestureDetector(
+          onTap: onTap,
+          child: Image.network(
+            url,
+            fit: BoxFit.contain,
+            width: double.infinity,
+            loadingBuilder: (context, child, progress) =>
+                progress == null ? child : placeholder,
+            errorBuilder: (_, __, ___) => error,
           ),
         ),
       ),
@@ -984,44 +1005,69 @@ class _CloudAppMediaState extends State<_CloudAppMedia> {
   bool _playing = false;
   bool _initializingVideo = false;
   VideoPlayerController? _controller;
-  CacheManager? _cacheManager;
-  String? _cacheDirPath;
+  String? _loadedPackage;
+  String? _thumbnailPath;
+  bool _loadingThumbnail = false;
   bool _checkingTrailer = false;
   bool _trailerAvailable = false;
   bool _muted = true;
-  String? _checkedUrl;
 
-  @override
-  void initState() {
-    super.initState();
-    // Cache manager initialized when Rust provides cache path via provider
+  /// Asks Rust for a cached copy of the media, downloading it if [fetch] is set
+  Future<AppMediaResponse> _requestMedia(
+    MediaKind kind, {
+    required bool fetch,
+  }) {
+    final packageName = widget.truePackageName;
+    final response = AppMediaResponse.rustSignalStream
+        .map((event) => event.message)
+        .firstWhere((m) => m.packageName == packageName && m.kind == kind);
+    GetAppMediaRequest(packageName: packageName, kind: kind, fetch: fetch)
+        .sendSignalToRust();
+    return response;
   }
 
-  void _ensureCacheManager(String path) {
-    if (_cacheDirPath == path && _cacheManager != null) return;
-    final cacheDir = Directory(path);
-    final cacheInfoFile = File('$path${Platform.pathSeparator}cache_info.json');
-    if (!cacheDir.existsSync()) {
-      cacheDir.createSync(recursive: true);
-    }
-    _cacheDirPath = path;
-    _cacheManager = CacheManager(
-      Config(
-        'app_media_cache',
-        stalePeriod: const Duration(days: 30),
-        maxNrOfCacheObjects: 200,
-        repo: JsonCacheInfoRepository.withFile(cacheInfoFile),
-        fileSystem: IOFileSystem(path),
-      ),
-    );
+  void _loadMedia() {
+    _loadedPackage = widget.truePackageName;
+    _thumbnailPath = null;
+    _loadingThumbnail = true;
+    _checkingTrailer = true;
+    _trailerAvailable = false;
+    _requestMedia(MediaKind.thumbnail, fetch: true).then((response) {
+      if (!mounted || _loadedPackage != response.packageName) return;
+      setState(() {
+        _thumbnailPath = response.path;
+        _loadingThumbnail = false;
+      });
+    });
+    _requestMedia(MediaKind.trailer, fetch: false).then((response) {
+      if (!mounted || _loadedPackage != response.packageName) return;
+      setState(() {
+        _trailerAvailable = response.available;
+        _checkingTrailer = false;
+      });
+    });
   }
 
-  Future<void> _startVideo(String url) async {
+  Future<void> _startVideo() async {
     if (_initializingVideo) return;
     setState(() {
       _initializingVideo = true;
     });
-    final controller = VideoPlayerController.networkUrl(Uri.parse(url));
+    // Uncached trailers stream from the server while Rust caches them
+    final response = await _requestMedia(MediaKind.trailer, fetch: true);
+    final path = response.path;
+    final streamUrl = response.streamUrl;
+    if (path == null && streamUrl == null) {
+      if (!mounted) return;
+      setState(() {
+        _trailerAvailable = response.available;
+        _initializingVideo = false;
+      });
+      return;
+    }
+    final controller = path != null
+        ? VideoPlayerController.file(File(path))
+        : VideoPlayerController.networkUrl(Uri.parse(streamUrl!));
     try {
       await controller.initialize();
       await controller.setVolume(_muted ? 0.0 : 1.0);
@@ -1070,19 +1116,10 @@ class _CloudAppMediaState extends State<_CloudAppMedia> {
   @override
   Widget build(BuildContext context) {
     final l10n = AppLocalizations.of(context);
-    final media = context.watch<CloudAppsState>();
-    final thumbUrl = media.thumbnailUrlFor(widget.truePackageName);
-    final trailerUrl = media.trailerUrlFor(widget.truePackageName);
-
     final borderRadius = BorderRadius.circular(8);
 
-    final cachePath = media.mediaCacheDir;
-    final canLoadMedia = cachePath != null;
-    if (canLoadMedia) {
-      _ensureCacheManager(cachePath);
-    }
-    if (_checkedUrl != trailerUrl && !_checkingTrailer) {
-      _checkTrailerAvailability(trailerUrl);
+    if (_loadedPackage != widget.truePackageName) {
+      _loadMedia();
     }
 
     Widget child;
@@ -1168,23 +1205,21 @@ class _CloudAppMediaState extends State<_CloudAppMedia> {
         child: GestureDetector(
           onTap: (!_trailerAvailable || _initializingVideo)
               ? null
-              : () {
-                  _startVideo(trailerUrl);
-                },
+              : _startVideo,
           child: ClipRRect(
             borderRadius: borderRadius,
             child: Stack(
               fit: StackFit.expand,
               children: [
-                CachedNetworkImage(
-                  imageUrl: thumbUrl,
-                  cacheManager: _cacheManager,
-                  fit: BoxFit.cover,
-                  placeholder: (context, url) =>
-                      _mediaPlaceholder(context, loading: true),
-                  errorWidget: (context, url, error) =>
-                      _mediaPlaceholder(context),
-                ),
+                if (_thumbnailPath != null)
+                  Image.file(
+                    File(_thumbnailPath!),
+                    fit: BoxFit.cover,
+                    errorBuilder: (context, error, stackTrace) =>
+                        _mediaPlaceholder(context),
+                  )
+                else
+                  _mediaPlaceholder(context, loading: _loadingThumbnail),
                 // Small availability indicator badge
                 Positioned(
                   left: 8,
@@ -1277,33 +1312,6 @@ class _CloudAppMediaState extends State<_CloudAppMedia> {
     );
   }
 
-  Future<bool> _urlExists(String url) async {
-    try {
-      final uri = Uri.parse(url);
-      final head = await http.head(uri);
-      if (head.statusCode == 200) return true;
-      if (head.statusCode == 405 || head.statusCode == 501) {
-        final get = await http.get(uri, headers: const {'range': 'bytes=0-0'});
-        return get.statusCode == 200 || get.statusCode == 206;
-      }
-      return false;
-    } catch (_) {
-      return false;
-    }
-  }
-
-  Future<void> _checkTrailerAvailability(String trailerUrl) async {
-    if (_checkingTrailer) return;
-    setState(() => _checkingTrailer = true);
-    final available = await _urlExists(trailerUrl);
-    if (!mounted) return;
-    setState(() {
-      _trailerAvailable = available;
-      _checkingTrailer = false;
-      _checkedUrl = trailerUrl;
-    });
-  }
-
   Widget _mediaPlaceholder(BuildContext context, {bool loading = false}) {
     final color = Theme.of(context).colorScheme.surfaceContainerHighest;
     return Container(
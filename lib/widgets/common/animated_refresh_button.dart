@@ -88,7 +88,9 @@ class _AnimatedRefreshButtonState extends State<AnimatedRefreshButton>
     _rotationController.repeat();
     _lastDeviceUpdate = DateTime.now();
 
-    AdbRequest(command: const AdbCommandRefreshDevice(), commandKey: '')
+    AdbRequest(
+            command: const AdbCommandRefreshDevice(value: RefreshScope.full),
+            commandKey: '')
         .sendSignalToRust();
 
     // Fallback: stop spinning after 5 seconds
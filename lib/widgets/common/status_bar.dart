@@ -10,6 +10,7 @@ import '../../src/l10n/app_localizations.dart';
 import '../../providers/task_state.dart';
 import '../../utils/utils.dart';
 import '../dialogs/task_list_dialog.dart';
+import '../dialogs/battery_history_dialog.dart';
 import '../dialogs/connection_diagnostics_dialog.dart';
 import 'animated_refresh_button.dart';
 
@@ -45,6 +46,22 @@ class StatusBar extends StatelessWidget {
                     shape: BoxShape.circle,
                   ),
                 ),
+                if (adbState.hasHostConflicts) ...[
+                  const SizedBox(width: 4),
+                  Tooltip(
+                    message: l10n.diagnosticsHostConflicts,
+                    child: const Icon(Icons.warning_amber_rounded,
+                        size: 16, color: Colors.amber),
+                  ),
+                ],
+                if (adbState.health.health != signals.AdbHealth.healthy) ...[
+                  const SizedBox(width: 4),
+                  Tooltip(
+                    message: l10n.diagnosticsAdbHealth,
+                    child: const Icon(Icons.sync_problem,
+                        size: 16, color: Colors.amber),
+                  ),
+                ],
                 const SizedBox(width: 4),
               ],
             ),
@@ -82,12 +99,19 @@ class StatusBar extends StatelessWidget {
     AppLocalizations l10n,
   ) {
     return Tooltip(
-      message: '${l10n.headset}: ${deviceState.batteryLevel}%\n'
-          '${l10n.leftController}: ${deviceState.controllerBatteryLevel(deviceState.leftController)}%\n'
-          '${l10n.rightController}: ${deviceState.controllerBatteryLevel(deviceState.rightController)}%',
+      message: (deviceState.isVrHeadset
+              ? '${l10n.headset}: ${deviceState.batteryLevel}%\n'
+                  '${l10n.leftController}: ${deviceState.controllerBatteryLevel(deviceState.leftController)}%\n'
+                  '${l10n.rightController}: ${deviceState.controllerBatteryLevel(deviceState.rightController)}%'
+              : '${l10n.deviceTitle}: ${deviceState.batteryLevel}%') +
+          '\n\n${l10n.batteryHistoryHint}',
       child: Material(
         color: Colors.transparent,
         child: InkWell(
+          onLongPress: () => showDialog(
+            context: context,
+            builder: (context) => const BatteryHistoryDialog(),
+          ),
           onTap: () async {
             final key = DateTime.now().millisecondsSinceEpoch.toString();
             signals.AdbRequest(
@@ -386,6 +410,7 @@ class _DeviceSwitcherLabelState extends State<_DeviceSwitcherLabel>
           final isCurrent = serial == current;
           final isWireless = entry.isWireless;
           final isReady = entry.state == signals.AdbBriefState.device;
+          final isOffline = entry.state == signals.AdbBriefState.offline;
           final titleText = (entry.name != null && entry.name!.isNotEmpty)
               ? entry.name!
               : serial;
@@ -423,6 +448,7 @@ class _DeviceSwitcherLabelState extends State<_DeviceSwitcherLabel>
               }
             }();
             if (!isReady && stateLabel.isNotEmpty) subtitle.write(stateLabel);
+            if (isOffline) subtitle.write(' • ${l10n.statusAdbWakeDevice}');
           }
 
           return _AnimatedMenuItem(
@@ -431,18 +457,29 @@ class _DeviceSwitcherLabelState extends State<_DeviceSwitcherLabel>
             child: SizedBox(
                 width: menuWidth,
                 child: MenuItemButton(
-                  onPressed: !isReady
-                      ? null
-                      : () {
-                          if (serial != current) {
-                            signals.AdbRequest(
-                              command:
-                                  signals.AdbCommandConnectTo(value: serial),
-                              commandKey: 'select-device',
-                            ).sendSignalToRust();
-                          }
+                  onPressed: isOffline
+                      ? () {
+                          signals.AdbRequest(
+                            command:
+                                signals.AdbCommandWakeDevice(value: serial),
+                            commandKey: 'wake-device',
+                          ).sendSignalToRust();
                           if (_menuController.isOpen) _menuController.close();
-                        },
+                        }
+                      : !isReady
+                          ? null
+                          : () {
+                              if (serial != current) {
+                                signals.AdbRequest(
+                                  command: signals.AdbCommandConnectTo(
+                                      value: serial),
+                                  commandKey: 'select-device',
+                                ).sendSignalToRust();
+                              }
+                              if (_menuController.isOpen) {
+                                _menuController.close();
+                              }
+                            },
                   leadingIcon: Icon(
                     isWireless ? Icons.wifi_tethering : Icons.usb,
                     size: 18,
@@ -7,8 +7,14 @@ import 'package:toastification/toastification.dart';
 import '../../providers/adb_state.dart';
 import '../../src/bindings/bindings.dart';
 import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
 import '../common/animated_adb_button.dart';
+import '../dialogs/command_macros_dialog.dart';
+import '../dialogs/device_log_dialog.dart';
+import '../dialogs/device_tweaks_dialog.dart';
+import '../dialogs/shell_console_dialog.dart';
 import '../../providers/device_state.dart';
+import '../../providers/settings_state.dart';
 import 'package:provider/provider.dart';
 
 class DeviceActionsCard extends StatelessWidget {
@@ -34,12 +40,14 @@ class DeviceActionsCard extends StatelessWidget {
               const SizedBox(height: 12),
 
               // Proximity sensor toggle
-              const _ProximityToggle(),
-
-              const SizedBox(height: 8),
+              if (context.watch<DeviceState>().supportsProximityOverride) ...[
+                const _ProximityToggle(),
+                const SizedBox(height: 8),
+              ],
 
               // Guardian toggle
-              const _GuardianToggle(),
+              if (context.watch<DeviceState>().supportsGuardianPause)
+                const _GuardianToggle(),
 
               Builder(builder: (context) {
                 final device = context.watch<DeviceState>();
@@ -56,6 +64,7 @@ class DeviceActionsCard extends StatelessWidget {
                   final adb = context.watch<AdbStateProvider>();
                   if (!device.isConnected ||
                       device.isWireless ||
+                      !device.supportsWirelessEnable ||
                       // Check that we don't have an active wireless connection for this device already
                       adb.availableDevices.any((d) =>
                           d.isWireless &&
@@ -84,8 +93,145 @@ class DeviceActionsCard extends StatelessWidget {
                 },
               ),
 
+              // Maintenance log
+              Builder(builder: (context) {
+                final device = context.watch<DeviceState>();
+                if (!device.isConnected) return const SizedBox.shrink();
+                return Row(
+                  children: [
+                    const Icon(Icons.history_edu),
+                    const SizedBox(width: 8),
+                    Expanded(
+                      child: Text(l10n.deviceMaintenanceLog,
+                          style: Theme.of(context).textTheme.titleSmall),
+                    ),
+                    IconButton(
+                      icon: const Icon(Icons.open_in_new),
+                      tooltip: l10n.deviceMaintenanceLog,
+                      onPressed: () => showDialog(
+                        context: context,
+                        builder: (context) => DeviceLogDialog(
+                          serial: device.deviceTrueSerial,
+                          deviceName: device.deviceName,
+                        ),
+                      ),
+                    ),
+                  ],
+                );
+              }),
+
+              // Rendering tweaks
+              if (context.watch<DeviceState>().supportsDeviceTweaks)
+                Row(
+                  children: [
+                    const Icon(Icons.tune),
+                    const SizedBox(width: 8),
+                    Expanded(
+                      child: Text(l10n.deviceTweaks,
+                          style: Theme.of(context).textTheme.titleSmall),
+                    ),
+                    IconButton(
+                      icon: const Icon(Icons.open_in_new),
+                      tooltip: l10n.deviceTweaks,
+                      onPressed: () => showDialog(
+                        context: context,
+                        builder: (context) => const DeviceTweaksDialog(),
+                      ),
+                    ),
+                  ],
+                ),
+
+              // Command macros
+              Row(
+                children: [
+                  const Icon(Icons.playlist_play),
+                  const SizedBox(width: 8),
+                  Expanded(
+                    child: Text(l10n.commandMacros,
+                        style: Theme.of(context).textTheme.titleSmall),
+                  ),
+                  IconButton(
+                    icon: const Icon(Icons.open_in_new),
+                    tooltip: l10n.commandMacros,
+                    onPressed: () => showDialog(
+                      context: context,
+                      builder: (context) => const CommandMacrosDialog(),
+                    ),
+                  ),
+                ],
+              ),
+
+              // Shell console
+              if (context.watch<DeviceState>().isConnected &&
+                  context
+                      .watch<SettingsState>()
+                      .settings
+                      .shellConsoleEnabled)
+                Row(
+                  children: [
+                    const Icon(Icons.terminal),
+                    const SizedBox(width: 8),
+                    Expanded(
+                      child: Text(l10n.shellConsole,
+                          style: Theme.of(context).textTheme.titleSmall),
+                    ),
+                    IconButton(
+                      icon: const Icon(Icons.open_in_new),
+                      tooltip: l10n.shellConsole,
+                      onPressed: () => showDialog(
+                        context: context,
+                        barrierDismissible: false,
+                        builder: (context) => const ShellConsoleDialog(),
+                      ),
+                    ),
+                  ],
+                ),
+
+              // Device helper
+              Builder(builder: (context) {
+                final device = context.watch<DeviceState>();
+                if (!device.isConnected) return const SizedBox.shrink();
+                final agentVersion = device.agentVersion;
+                return Row(
+                  children: [
+                    const Icon(Icons.support_agent),
+                    const SizedBox(width: 8),
+                    Expanded(
+                      child: Text(
+                          agentVersion == null
+                              ? l10n.deviceAgentNotInstalled
+                              : l10n.deviceAgentInstalled(
+                                  agentVersion.toString()),
+                          style: Theme.of(context).textTheme.titleSmall),
+                    ),
+                    AnimatedAdbButton(
+                      icon: agentVersion == null
+                          ? Icons.download
+                          : Icons.system_update_alt,
+                      tooltip: agentVersion == null
+                          ? l10n.deviceAgentInstall
+                          : l10n.deviceAgentUpdate,
+                      commandType: AdbCommandKind.agentInstall,
+                      commandKey: 'install-agent',
+                      onPressed: () => _send(
+                          'install-agent', const AdbCommandInstallAgent()),
+                    ),
+                    if (agentVersion != null)
+                      AnimatedAdbButton(
+                        icon: Icons.delete_outline,
+                        tooltip: l10n.deviceAgentRemove,
+                        commandType: AdbCommandKind.agentRemove,
+                        commandKey: 'remove-agent',
+                        onPressed: () => _send(
+                            'remove-agent', const AdbCommandRemoveAgent()),
+                      ),
+                  ],
+                );
+              }),
+
               // Casting (Windows only)
-              if (Platform.isWindows) ...[
+              if (Platform.isWindows &&
+                  context.watch<DeviceState>().supportsCasting) ...[
                 const SizedBox(height: 8),
                 _CastingRow(onStart: () => _handleCast(context)),
                 const SizedBox(height: 8),
@@ -310,6 +456,22 @@ class _GuardianToggleState extends State<_GuardianToggle> {
     ).sendSignalToRust();
   }
 
+  void _pauseFor(int seconds) {
+    if (_pendingActiveState != null) return;
+    setState(() => _pendingActiveState = false);
+    AdbRequest(
+      command: const AdbCommandSetGuardianPaused(value: true),
+      commandKey: 'guardian',
+    ).sendSignalToRust();
+    AdbRequest(
+      command: AdbCommandScheduleAutoRevert(
+        kind: AutoRevertKind.guardianPause,
+        afterSecs: Uint64.fromBigInt(BigInt.from(seconds)),
+      ),
+      commandKey: 'guardian-revert',
+    ).sendSignalToRust();
+  }
+
   @override
   Widget build(BuildContext context) {
     final l10n = AppLocalizations.of(context);
@@ -329,6 +491,7 @@ class _GuardianToggleState extends State<_GuardianToggle> {
 
     final isUpdating = _pendingActiveState != null;
     final isActive = guardianActive ?? true;
+    final revertAt = device.autoRevertAt(AutoRevertKind.guardianPause);
     final theme = Theme.of(context);
 
     return Row(
@@ -348,9 +511,24 @@ class _GuardianToggleState extends State<_GuardianToggle> {
                   color: theme.colorScheme.outline,
                 ),
               ),
+              if (!isActive && revertAt != null)
+                _AutoRevertCountdown(revertAt: revertAt),
             ],
           ),
         ),
+        if (!isUpdating && isActive && guardianActive != null)
+          PopupMenuButton<int>(
+            icon: const Icon(Icons.timer_outlined),
+            tooltip: l10n.guardianPauseFor,
+            onSelected: _pauseFor,
+            itemBuilder: (context) => [
+              PopupMenuItem(value: 15 * 60, child: Text(l10n.guardianPause15m)),
+              PopupMenuItem(value: 30 * 60, child: Text(l10n.guardianPause30m)),
+              PopupMenuItem(value: 60 * 60, child: Text(l10n.guardianPause1h)),
+              PopupMenuItem(
+                  value: 2 * 60 * 60, child: Text(l10n.guardianPause2h)),
+            ],
+          ),
         if (isUpdating)
           const SizedBox(
             width: 60,
@@ -412,6 +590,17 @@ class _ProximityToggleState extends State<_ProximityToggle> {
       ),
       commandKey: 'proximity',
     ).sendSignalToRust();
+    // The headset restores the sensor on its own, the timer shows the countdown
+    // and catches up if the headset was away
+    if (durationMs > 0) {
+      AdbRequest(
+        command: AdbCommandScheduleAutoRevert(
+          kind: AutoRevertKind.proximitySensor,
+          afterSecs: Uint64.fromBigInt(BigInt.from(durationMs ~/ 1000)),
+        ),
+        commandKey: 'proximity-revert',
+      ).sendSignalToRust();
+    }
   }
 
   @override
@@ -430,6 +619,7 @@ class _ProximityToggleState extends State<_ProximityToggle> {
 
     final isUpdating = _pendingDisabledState != null;
     final sensorDisabled = proximityDisabled == true;
+    final revertAt = device.autoRevertAt(AutoRevertKind.proximitySensor);
     final theme = Theme.of(context);
 
     return Row(
@@ -452,6 +642,8 @@ class _ProximityToggleState extends State<_ProximityToggle> {
                   color: theme.colorScheme.outline,
                 ),
               ),
+              if (sensorDisabled && revertAt != null)
+                _AutoRevertCountdown(revertAt: revertAt),
             ],
           ),
         ),
@@ -518,6 +710,45 @@ class _ProximityToggleState extends State<_ProximityToggle> {
   }
 }
 
+/// Time left until a temporary device state is restored, updated every second
+class _AutoRevertCountdown extends StatefulWidget {
+  final DateTime revertAt;
+  const _AutoRevertCountdown({required this.revertAt});
+
+  @override
+  State<_AutoRevertCountdown> createState() => _AutoRevertCountdownState();
+}
+
+class _AutoRevertCountdownState extends State<_AutoRevertCountdown> {
+  late final Timer _timer;
+
+  @override
+  void initState() {
+    super.initState();
+    _timer = Timer.periodic(
+        const Duration(seconds: 1), (_) => mounted ? setState(() {}) : null);
+  }
+
+  @override
+  void dispose() {
+    _timer.cancel();
+    super.dispose();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final theme = Theme.of(context);
+    final remaining = widget.revertAt.difference(DateTime.now()).inSeconds;
+    return Text(
+      AppLocalizations.of(context)
+          .autoRevertIn(formatSeconds(math.max(remaining, 0))),
+      style: theme.textTheme.bodySmall?.copyWith(
+        color: theme.colorScheme.primary,
+      ),
+    );
+  }
+}
+
 class _CastingRow extends StatelessWidget {
   final VoidCallback onStart;
   const _CastingRow({required this.onStart});
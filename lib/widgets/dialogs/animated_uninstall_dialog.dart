@@ -1,6 +1,8 @@
 import 'dart:async';
 
 import 'package:flutter/material.dart';
+import 'package:provider/provider.dart';
+import '../../providers/settings_state.dart';
 import '../../src/bindings/bindings.dart';
 import '../../src/l10n/app_localizations.dart';
 
@@ -78,6 +80,19 @@ class _AnimatedUninstallDialogState extends State<AnimatedUninstallDialog>
   void _startUninstall() {
     if (_isUninstalling || _showSuccess) return;
 
+    // The backup before uninstalling can take a while, so it runs as a task
+    if (context.read<SettingsState>().settings.trashBeforeUninstall) {
+      TaskRequest(
+        task: TaskUninstall(
+          packageName: widget.app.packageName,
+          displayName: widget.app.label,
+        ),
+        priority: TaskPriority.normal,
+      ).sendSignalToRust();
+      Navigator.of(context).pop();
+      return;
+    }
+
     setState(() {
       _isUninstalling = true;
       _showCloseButton = false;
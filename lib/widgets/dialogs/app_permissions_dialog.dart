@@ -0,0 +1,149 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Permissions an installed app requests, with switches for runtime ones
+class AppPermissionsDialog extends StatefulWidget {
+  final InstalledPackage app;
+
+  const AppPermissionsDialog({super.key, required this.app});
+
+  @override
+  State<AppPermissionsDialog> createState() => _AppPermissionsDialogState();
+}
+
+class _AppPermissionsDialogState extends State<AppPermissionsDialog> {
+  late final String _key = 'permissions-${widget.app.packageName}';
+  StreamSubscription? _responseSubscription;
+  StreamSubscription? _completedSubscription;
+  List<AppPermission>? _permissions;
+  String? _error;
+  final Set<String> _pending = {};
+
+  @override
+  void initState() {
+    super.initState();
+    _responseSubscription =
+        AppPermissionsResponse.rustSignalStream.listen((event) {
+      final response = event.message;
+      if (!mounted || response.commandKey != _key) return;
+      setState(() {
+        _permissions = response.permissions;
+        _error = response.error;
+        _pending.clear();
+      });
+    });
+    _completedSubscription =
+        AdbCommandCompletedEvent.rustSignalStream.listen((event) {
+      final signal = event.message;
+      if (signal.commandType == AdbCommandKind.appPermissionSet &&
+          signal.commandKey.startsWith('$_key:')) {
+        _load();
+      }
+    });
+    _load();
+  }
+
+  @override
+  void dispose() {
+    _responseSubscription?.cancel();
+    _completedSubscription?.cancel();
+    super.dispose();
+  }
+
+  void _load() {
+    AdbRequest(
+      command: AdbCommandGetAppPermissions(value: widget.app.packageName),
+      commandKey: _key,
+    ).sendSignalToRust();
+  }
+
+  void _set(AppPermission permission, bool granted) {
+    setState(() => _pending.add(permission.name));
+    AdbRequest(
+      command: AdbCommandSetAppPermission(
+        packageName: widget.app.packageName,
+        permission: permission.name,
+        granted: granted,
+      ),
+      commandKey: '$_key:${permission.name}',
+    ).sendSignalToRust();
+  }
+
+  Widget _buildTile(AppPermission permission) {
+    final l10n = AppLocalizations.of(context);
+    final name = permission.name;
+    final shortName = name.substring(name.lastIndexOf('.') + 1);
+    if (!permission.runtime) {
+      return ListTile(
+        dense: true,
+        title: Text(shortName),
+        subtitle: Text(name),
+        trailing: Tooltip(
+          message: permission.granted
+              ? l10n.appPermissionsGrantedAtInstall
+              : l10n.appPermissionsNotGranted,
+          child: Icon(permission.granted ? Icons.check : Icons.block),
+        ),
+      );
+    }
+    return SwitchListTile(
+      dense: true,
+      title: Text(shortName),
+      subtitle: Text(name),
+      value: permission.granted,
+      onChanged:
+          _pending.contains(name) ? null : (value) => _set(permission, value),
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final permissions = _permissions;
+    final error = _error;
+    final runtime = permissions?.where((p) => p.runtime).toList() ?? [];
+    final other = permissions?.where((p) => !p.runtime).toList() ?? [];
+
+    Widget content;
+    if (error != null) {
+      content = Text(error);
+    } else if (permissions == null) {
+      content = const Center(child: CircularProgressIndicator());
+    } else if (permissions.isEmpty) {
+      content = Text(l10n.appPermissionsNone);
+    } else {
+      content = ListView(
+        shrinkWrap: true,
+        children: [
+          if (runtime.isNotEmpty) ...[
+            ListTile(dense: true, title: Text(l10n.appPermissionsRuntime)),
+            for (final permission in runtime) _buildTile(permission),
+          ],
+          if (other.isNotEmpty) ...[
+            ListTile(dense: true, title: Text(l10n.appPermissionsOther)),
+            for (final permission in other) _buildTile(permission),
+          ],
+        ],
+      );
+    }
+
+    return AlertDialog(
+      title: Text(l10n.appPermissionsTitle(widget.app.label)),
+      content: SizedBox(width: 480, height: 400, child: content),
+      actions: [
+        TextButton(
+          onPressed: _load,
+          child: Text(l10n.refresh),
+        ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
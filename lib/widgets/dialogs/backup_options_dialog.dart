@@ -1,10 +1,14 @@
+import 'dart:async';
+
 import 'package:flutter/material.dart';
 import '../../src/bindings/bindings.dart';
 import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
 
+/// Backup options for a single app, or for all installed apps when [app] is null
 class BackupOptionsDialog extends StatefulWidget {
-  final InstalledPackage app;
-  const BackupOptionsDialog({super.key, required this.app});
+  final InstalledPackage? app;
+  const BackupOptionsDialog({super.key, this.app});
 
   @override
   State<BackupOptionsDialog> createState() => _BackupOptionsDialogState();
@@ -14,16 +18,67 @@ class _BackupOptionsDialogState extends State<BackupOptionsDialog> {
   bool _backupData = true;
   bool _backupApk = false;
   bool _backupObb = false;
+  bool _compress = false;
   final TextEditingController _suffixController = TextEditingController();
+  StreamSubscription<RustSignalPack<BackupEstimateResponse>>? _estimateSub;
+  BackupEstimateResponse? _estimate;
+
+  @override
+  void initState() {
+    super.initState();
+    final app = widget.app;
+    if (app == null) return;
+    _estimateSub = BackupEstimateResponse.rustSignalStream.listen((event) {
+      if (event.message.packageName != app.packageName || !mounted) return;
+      setState(() => _estimate = event.message);
+    });
+    EstimateBackupRequest(packageName: app.packageName).sendSignalToRust();
+  }
 
   @override
   void dispose() {
+    _estimateSub?.cancel();
     _suffixController.dispose();
     super.dispose();
   }
 
+  /// Size and time of the selected parts according to the estimate
+  Widget _buildEstimate(AppLocalizations l10n) {
+    final estimate = _estimate;
+    final style = Theme.of(context).textTheme.bodySmall;
+    if (estimate == null) return Text(l10n.backupEstimating, style: style);
+    if (estimate.error != null) {
+      return Text(l10n.backupEstimateFailed(estimate.error!), style: style);
+    }
+    final parts = [
+      if (_backupData) ...[estimate.privateData, estimate.sharedData],
+      if (_backupApk) estimate.apk,
+      if (_backupObb) estimate.obb,
+    ].whereType<BackupPartEstimate>();
+    final bytes = parts.fold<int>(0, (sum, p) => sum + p.bytes.toInt());
+    final secs = parts.fold<int>(0, (sum, p) => sum + p.secs.toInt());
+    return Text(
+      l10n.backupEstimate(formatSize(bytes, 1), formatSeconds(secs)),
+      style: style,
+    );
+  }
+
   void _startBackup() {
     final app = widget.app;
+    if (app == null) {
+      TaskRequest(
+        task: TaskBackupAll(
+          backupApk: _backupApk,
+          backupData: _backupData,
+          backupObb: _backupObb,
+          compress: _compress,
+        ),
+        priority: TaskPriority.normal,
+      ).sendSignalToRust();
+      Navigator.of(context).pop();
+      return;
+    }
+
     final suffix = _suffixController.text.trim();
     final name = app.label.isNotEmpty ? app.label : app.packageName;
 
@@ -35,7 +90,9 @@ class _BackupOptionsDialogState extends State<BackupOptionsDialog> {
         backupData: _backupData,
         backupObb: _backupObb,
         backupNameAppend: suffix.isEmpty ? null : suffix,
+        compress: _compress,
       ),
+      priority: TaskPriority.normal,
     ).sendSignalToRust();
 
     Navigator.of(context).pop();
@@ -45,7 +102,9 @@ class _BackupOptionsDialogState extends State<BackupOptionsDialog> {
   Widget build(BuildContext context) {
     final l10n = AppLocalizations.of(context);
     return AlertDialog(
-      title: Text(l10n.backupOptionsTitle),
+      title: Text(widget.app == null
+          ? l10n.backupAllAppsTitle
+          : l10n.backupOptionsTitle),
       content: Column(
         mainAxisSize: MainAxisSize.min,
         crossAxisAlignment: CrossAxisAlignment.start,
@@ -74,13 +133,24 @@ class _BackupOptionsDialogState extends State<BackupOptionsDialog> {
             controlAffinity: ListTileControlAffinity.leading,
           ),
           const SizedBox(height: 8),
-          TextField(
-            controller: _suffixController,
-            decoration: InputDecoration(
-              labelText: l10n.backupNameSuffix,
-              hintText: l10n.backupNameSuffixHint,
-            ),
+          CheckboxListTile(
+            value: _compress,
+            onChanged: (v) => setState(() => _compress = v ?? false),
+            title: Text(l10n.backupCompress),
+            dense: true,
+            controlAffinity: ListTileControlAffinity.leading,
           ),
+          if (widget.app != null) ...[
+            TextField(
+              controller: _suffixController,
+              decoration: InputDecoration(
+                labelText: l10n.backupNameSuffix,
+                hintText: l10n.backupNameSuffixHint,
+              ),
+            ),
+            const SizedBox(height: 12),
+            _buildEstimate(l10n),
+          ],
         ],
       ),
       actions: [
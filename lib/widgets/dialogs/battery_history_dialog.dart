@@ -0,0 +1,189 @@
+import 'dart:async';
+import 'dart:math';
+
+import 'package:flutter/material.dart';
+import 'package:intl/intl.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Battery levels of the current device and its controllers over the session
+class BatteryHistoryDialog extends StatefulWidget {
+  const BatteryHistoryDialog({super.key});
+
+  @override
+  State<BatteryHistoryDialog> createState() => _BatteryHistoryDialogState();
+}
+
+class _BatteryHistoryDialogState extends State<BatteryHistoryDialog> {
+  StreamSubscription<RustSignalPack<BatteryHistory>>? _historySub;
+  BatteryHistory? _history;
+
+  @override
+  void initState() {
+    super.initState();
+    _historySub = BatteryHistory.rustSignalStream.listen((event) {
+      final serial = _history?.serial;
+      if (!mounted || (serial != null && event.message.serial != serial)) {
+        return;
+      }
+      setState(() => _history = event.message);
+    });
+    GetBatteryHistoryRequest(serial: null).sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _historySub?.cancel();
+    super.dispose();
+  }
+
+  Widget _buildLegend(Color color, String label) {
+    return Row(
+      mainAxisSize: MainAxisSize.min,
+      children: [
+        Container(width: 12, height: 3, color: color),
+        const SizedBox(width: 6),
+        Text(label, style: Theme.of(context).textTheme.bodySmall),
+      ],
+    );
+  }
+
+  Widget _buildContent(AppLocalizations l10n) {
+    final history = _history;
+    if (history == null) {
+      return const Center(
+        child: SizedBox(
+          width: 20,
+          height: 20,
+          child: CircularProgressIndicator(strokeWidth: 2),
+        ),
+      );
+    }
+    if (history.samples.length < 2) {
+      return Text(l10n.batteryHistoryEmpty);
+    }
+
+    final colors = Theme.of(context).colorScheme;
+    final List<(Color, String, int? Function(BatterySample))> series = [
+      (colors.primary, l10n.headset, (s) => s.headset),
+      (colors.tertiary, l10n.leftController, (s) => s.leftController),
+      (colors.secondary, l10n.rightController, (s) => s.rightController),
+    ];
+    series.removeWhere(
+      (line) => history.samples.every((s) => line.$3(s) == null),
+    );
+    final time = DateFormat.Hm();
+    DateTime at(BatterySample sample) =>
+        DateTime.fromMillisecondsSinceEpoch(sample.timestamp.toInt());
+
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        Wrap(
+          spacing: 16,
+          children: [
+            for (final (color, label, _) in series)
+              _buildLegend(color, label),
+          ],
+        ),
+        const SizedBox(height: 12),
+        SizedBox(
+          height: 200,
+          width: double.infinity,
+          child: CustomPaint(
+            painter: _BatteryChartPainter(
+              samples: history.samples,
+              lines: [
+                for (final (color, _, level) in series) (color, level),
+              ],
+              gridColor: colors.outlineVariant,
+            ),
+          ),
+        ),
+        const SizedBox(height: 4),
+        Row(
+          mainAxisAlignment: MainAxisAlignment.spaceBetween,
+          children: [
+            Text(time.format(at(history.samples.first))),
+            Text(time.format(at(history.samples.last))),
+          ],
+        ),
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    return AlertDialog(
+      title: Text(l10n.batteryHistoryTitle),
+      content: SizedBox(width: 520, child: _buildContent(l10n)),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
+
+/// Draws battery levels from 0 to 100% against sample time
+class _BatteryChartPainter extends CustomPainter {
+  final List<BatterySample> samples;
+  final List<(Color, int? Function(BatterySample))> lines;
+  final Color gridColor;
+
+  _BatteryChartPainter({
+    required this.samples,
+    required this.lines,
+    required this.gridColor,
+  });
+
+  @override
+  void paint(Canvas canvas, Size size) {
+    final grid = Paint()
+      ..color = gridColor
+      ..strokeWidth = 1;
+    for (var level = 0; level <= 100; level += 25) {
+      final y = size.height * (1 - level / 100);
+      canvas.drawLine(Offset(0, y), Offset(size.width, y), grid);
+    }
+
+    final start = samples.first.timestamp.toInt();
+    final span = max(1, samples.last.timestamp.toInt() - start);
+    for (final (color, level) in lines) {
+      final path = Path();
+      var drawing = false;
+      for (final sample in samples) {
+        final value = level(sample);
+        if (value == null) {
+          drawing = false;
+          continue;
+        }
+        final x = size.width * (sample.timestamp.toInt() - start) / span;
+        final y = size.height * (1 - value / 100);
+        if (drawing) {
+          path.lineTo(x, y);
+        } else {
+          path.moveTo(x, y);
+          drawing = true;
+        }
+      }
+      canvas.drawPath(
+        path,
+        Paint()
+          ..color = color
+          ..strokeWidth = 2
+          ..style = PaintingStyle.stroke,
+      );
+    }
+  }
+
+  @override
+  bool shouldRepaint(_BatteryChartPainter oldDelegate) =>
+      oldDelegate.samples != samples || oldDelegate.lines != lines;
+}
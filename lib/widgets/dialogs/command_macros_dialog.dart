@@ -0,0 +1,439 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:provider/provider.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../providers/device_state.dart';
+import '../../providers/settings_state.dart';
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Human readable description of a macro step
+String describeMacroStep(AppLocalizations l10n, MacroStep step) {
+  if (step is MacroStepDelay) {
+    final seconds = step.value.toInt() / 1000;
+    return l10n.commandMacroStepDelay(
+        seconds == seconds.roundToDouble() ? '${seconds.toInt()}' : '$seconds');
+  }
+  final command = (step as MacroStepCommand).value;
+  return switch (command) {
+    AdbCommandLaunchApp(:final value) => l10n.commandMacroStepLaunch(value),
+    AdbCommandForceStopApp(:final value) =>
+      l10n.commandMacroStepForceStop(value),
+    AdbCommandSetGuardianPaused(:final value) => value
+        ? l10n.commandMacroStepGuardianPause
+        : l10n.commandMacroStepGuardianResume,
+    AdbCommandSetProximitySensor(:final enabled) => enabled
+        ? l10n.commandMacroStepProximityOn
+        : l10n.commandMacroStepProximityOff,
+    AdbCommandRefreshDevice() => l10n.commandMacroStepRefresh,
+    AdbCommandReboot() => l10n.commandMacroStepReboot,
+    _ => l10n.commandMacroStepOther(
+        command.runtimeType.toString().replaceFirst('AdbCommand', '')),
+  };
+}
+
+/// Saved command macros with their last run results
+class CommandMacrosDialog extends StatefulWidget {
+  const CommandMacrosDialog({super.key});
+
+  @override
+  State<CommandMacrosDialog> createState() => _CommandMacrosDialogState();
+}
+
+class _CommandMacrosDialogState extends State<CommandMacrosDialog> {
+  static const _keyPrefix = 'command-macro:';
+
+  StreamSubscription<RustSignalPack<CommandMacroResult>>? _resultSub;
+  final Set<String> _running = {};
+  final Map<String, CommandMacroResult> _results = {};
+
+  @override
+  void initState() {
+    super.initState();
+    _resultSub = CommandMacroResult.rustSignalStream.listen((event) {
+      final result = event.message;
+      if (!result.commandKey.startsWith(_keyPrefix) || !mounted) return;
+      setState(() {
+        _running.remove(result.name);
+        _results[result.name] = result;
+      });
+    });
+  }
+
+  @override
+  void dispose() {
+    _resultSub?.cancel();
+    super.dispose();
+  }
+
+  void _run(CommandMacro commandMacro) {
+    setState(() {
+      _running.add(commandMacro.name);
+      _results.remove(commandMacro.name);
+    });
+    RunCommandMacroRequest(
+      name: commandMacro.name,
+      commandKey: '$_keyPrefix${commandMacro.name}',
+    ).sendSignalToRust();
+  }
+
+  void _stop(CommandMacro commandMacro) {
+    CancelCommandMacroRequest(commandKey: '$_keyPrefix${commandMacro.name}')
+        .sendSignalToRust();
+  }
+
+  Future<void> _edit(CommandMacro? commandMacro) async {
+    final settings = context.read<SettingsState>();
+    final edited = await showDialog<CommandMacro>(
+      context: context,
+      builder: (context) => _CommandMacroEditorDialog(
+        initial: commandMacro,
+        takenNames: settings.commandMacros
+            .map((m) => m.name)
+            .where((name) => name != commandMacro?.name)
+            .toSet(),
+      ),
+    );
+    if (edited == null) return;
+    settings.saveCommandMacro(edited, previousName: commandMacro?.name);
+    setState(() => _results.remove(commandMacro?.name));
+  }
+
+  Widget _buildResult(
+      AppLocalizations l10n, CommandMacro commandMacro, CommandMacroResult r) {
+    final style = Theme.of(context).textTheme.bodySmall;
+    if (r.error != null) {
+      return Text(l10n.commandMacroFailed(r.error!),
+          style: style?.copyWith(color: Theme.of(context).colorScheme.error));
+    }
+    return Column(
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        for (final (index, step) in r.steps.indexed)
+          Row(
+            crossAxisAlignment: CrossAxisAlignment.start,
+            children: [
+              Icon(
+                switch (step.status) {
+                  MacroStepStatus.succeeded => Icons.check_circle_outline,
+                  MacroStepStatus.failed => Icons.error_outline,
+                  MacroStepStatus.cancelled => Icons.cancel_outlined,
+                  MacroStepStatus.skipped => Icons.remove_circle_outline,
+                },
+                size: 16,
+                color: step.status == MacroStepStatus.failed
+                    ? Theme.of(context).colorScheme.error
+                    : null,
+              ),
+              const SizedBox(width: 6),
+              Expanded(
+                child: Text(
+                  [
+                    if (index < commandMacro.steps.length)
+                      describeMacroStep(l10n, commandMacro.steps[index]),
+                    if (step.error != null) step.error!,
+                  ].join(': '),
+                  style: style,
+                ),
+              ),
+            ],
+          ),
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final macros = context.watch<SettingsState>().commandMacros;
+    final connected = context.watch<DeviceState>().isConnected;
+    return AlertDialog(
+      title: Text(l10n.commandMacros),
+      content: SizedBox(
+        width: 480,
+        child: macros.isEmpty
+            ? Text(l10n.commandMacrosEmpty)
+            : ListView(
+                shrinkWrap: true,
+                children: [
+                  for (final commandMacro in macros)
+                    ListTile(
+                      contentPadding: EdgeInsets.zero,
+                      title: Text(commandMacro.name),
+                      subtitle: _results[commandMacro.name] != null
+                          ? _buildResult(l10n, commandMacro,
+                              _results[commandMacro.name]!)
+                          : Text(l10n.commandMacroStepCount(
+                              '${commandMacro.steps.length}')),
+                      trailing: Row(
+                        mainAxisSize: MainAxisSize.min,
+                        children: [
+                          IconButton(
+                            icon: const Icon(Icons.edit_outlined),
+                            tooltip: l10n.commandMacroEdit,
+                            onPressed: () => _edit(commandMacro),
+                          ),
+                          IconButton(
+                            icon: const Icon(Icons.delete_outline),
+                            tooltip: l10n.commandMacroDelete,
+                            onPressed: () => context
+                                .read<SettingsState>()
+                                .removeCommandMacro(commandMacro.name),
+                          ),
+                          _running.contains(commandMacro.name)
+                              ? IconButton(
+                                  icon: const Stack(
+                                    alignment: Alignment.center,
+                                    children: [
+                                      SizedBox(
+                                        width: 24,
+                                        height: 24,
+                                        child: CircularProgressIndicator(
+                                            strokeWidth: 2),
+                                      ),
+                                      Icon(Icons.stop, size: 16),
+                                    ],
+                                  ),
+                                  tooltip: l10n.commandMacroStop,
+                                  onPressed: () => _stop(commandMacro),
+                                )
+                              : IconButton(
+                                  icon: const Icon(Icons.play_arrow),
+                                  tooltip: l10n.commandMacroRun,
+                                  onPressed: connected
+                                      ? () => _run(commandMacro)
+                                      : null,
+                                ),
+                        ],
+                      ),
+                    ),
+                ],
+              ),
+      ),
+      actions: [
+        TextButton.icon(
+          icon: const Icon(Icons.add),
+          label: Text(l10n.commandMacroNew),
+          onPressed: () => _edit(null),
+        ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
+
+enum _StepTemplate {
+  launch,
+  forceStop,
+  delay,
+  guardianPause,
+  guardianResume,
+  proximityOff,
+  proximityOn,
+  refresh,
+  reboot,
+}
+
+class _CommandMacroEditorDialog extends StatefulWidget {
+  final CommandMacro? initial;
+  final Set<String> takenNames;
+
+  const _CommandMacroEditorDialog({this.initial, required this.takenNames});
+
+  @override
+  State<_CommandMacroEditorDialog> createState() =>
+      _CommandMacroEditorDialogState();
+}
+
+class _CommandMacroEditorDialogState extends State<_CommandMacroEditorDialog> {
+  late final TextEditingController _nameController =
+      TextEditingController(text: widget.initial?.name ?? '');
+  late final List<MacroStep> _steps = widget.initial?.steps.toList() ?? [];
+
+  @override
+  void dispose() {
+    _nameController.dispose();
+    super.dispose();
+  }
+
+  Future<String?> _prompt(String label, {TextInputType? keyboardType}) {
+    final controller = TextEditingController();
+    return showDialog<String>(
+      context: context,
+      builder: (context) {
+        final l10n = AppLocalizations.of(context);
+        return AlertDialog(
+          content: TextField(
+            controller: controller,
+            autofocus: true,
+            keyboardType: keyboardType,
+            decoration: InputDecoration(labelText: label),
+            onSubmitted: (value) => Navigator.of(context).pop(value.trim()),
+          ),
+          actions: [
+            TextButton(
+              onPressed: () => Navigator.of(context).pop(),
+              child: Text(l10n.commonCancel),
+            ),
+            FilledButton(
+              onPressed: () =>
+                  Navigator.of(context).pop(controller.text.trim()),
+              child: Text(l10n.commonConfirm),
+            ),
+          ],
+        );
+      },
+    ).whenComplete(controller.dispose);
+  }
+
+  Future<void> _addStep(_StepTemplate template) async {
+    final l10n = AppLocalizations.of(context);
+    MacroStep command(AdbCommand command) => MacroStepCommand(value: command);
+    final MacroStep? step = switch (template) {
+      _StepTemplate.launch || _StepTemplate.forceStop => await _prompt(
+          l10n.commandMacroPackageName,
+        ).then((package) => package == null || package.isEmpty
+            ? null
+            : command(template == _StepTemplate.launch
+                ? AdbCommandLaunchApp(value: package)
+                : AdbCommandForceStopApp(value: package))),
+      _StepTemplate.delay => await _prompt(
+          l10n.commandMacroDelaySeconds,
+          keyboardType: const TextInputType.numberWithOptions(decimal: true),
+        ).then((text) {
+          final seconds = double.tryParse(text ?? '');
+          if (seconds == null || seconds <= 0) return null;
+          final ms = (seconds * 1000).round();
+          return MacroStepDelay(value: Uint64.fromBigInt(BigInt.from(ms)));
+        }),
+      _StepTemplate.guardianPause =>
+        command(const AdbCommandSetGuardianPaused(value: true)),
+      _StepTemplate.guardianResume =>
+        command(const AdbCommandSetGuardianPaused(value: false)),
+      _StepTemplate.proximityOff => command(
+          const AdbCommandSetProximitySensor(enabled: false, durationMs: null)),
+      _StepTemplate.proximityOn => command(
+          const AdbCommandSetProximitySensor(enabled: true, durationMs: null)),
+      _StepTemplate.refresh =>
+        command(const AdbCommandRefreshDevice(value: RefreshScope.full)),
+      _StepTemplate.reboot =>
+        command(const AdbCommandReboot(value: RebootMode.normal)),
+    };
+    if (step == null || !mounted) return;
+    setState(() => _steps.add(step));
+  }
+
+  String _templateLabel(AppLocalizations l10n, _StepTemplate template) {
+    return switch (template) {
+      _StepTemplate.launch => l10n.commandMacroStepLaunch('…'),
+      _StepTemplate.forceStop => l10n.commandMacroStepForceStop('…'),
+      _StepTemplate.delay => l10n.commandMacroStepDelay('…'),
+      _StepTemplate.guardianPause => l10n.commandMacroStepGuardianPause,
+      _StepTemplate.guardianResume => l10n.commandMacroStepGuardianResume,
+      _StepTemplate.proximityOff => l10n.commandMacroStepProximityOff,
+      _StepTemplate.proximityOn => l10n.commandMacroStepProximityOn,
+      _StepTemplate.refresh => l10n.commandMacroStepRefresh,
+      _StepTemplate.reboot => l10n.commandMacroStepReboot,
+    };
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final name = _nameController.text.trim();
+    final nameTaken = widget.takenNames.contains(name);
+    return AlertDialog(
+      title: Text(widget.initial == null
+          ? l10n.commandMacroNew
+          : l10n.commandMacroEdit),
+      content: SizedBox(
+        width: 420,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.start,
+          children: [
+            TextField(
+              controller: _nameController,
+              decoration: InputDecoration(
+                labelText: l10n.commandMacroName,
+                errorText: nameTaken ? l10n.commandMacroNameTaken : null,
+              ),
+              onChanged: (_) => setState(() {}),
+            ),
+            const SizedBox(height: 12),
+            if (_steps.isEmpty)
+              Text(l10n.commandMacroNoSteps,
+                  style: Theme.of(context).textTheme.bodySmall),
+            Flexible(
+              child: ReorderableListView(
+                shrinkWrap: true,
+                buildDefaultDragHandles: false,
+                onReorder: (oldIndex, newIndex) => setState(() {
+                  if (newIndex > oldIndex) newIndex--;
+                  _steps.insert(newIndex, _steps.removeAt(oldIndex));
+                }),
+                children: [
+                  for (final (index, step) in _steps.indexed)
+                    ListTile(
+                      key: ObjectKey(step),
+                      dense: true,
+                      contentPadding: EdgeInsets.zero,
+                      leading: ReorderableDragStartListener(
+                        index: index,
+                        child: const Icon(Icons.drag_handle),
+                      ),
+                      title: Text(describeMacroStep(l10n, step)),
+                      trailing: IconButton(
+                        icon: const Icon(Icons.close),
+                        onPressed: () =>
+                            setState(() => _steps.removeAt(index)),
+                      ),
+                    ),
+                ],
+              ),
+            ),
+            PopupMenuButton<_StepTemplate>(
+              onSelected: _addStep,
+              itemBuilder: (context) => [
+                for (final template in _StepTemplate.values)
+                  PopupMenuItem(
+                    value: template,
+                    child: Text(_templateLabel(l10n, template)),
+                  ),
+              ],
+              child: Padding(
+                padding: const EdgeInsets.symmetric(vertical: 8),
+                child: Row(
+                  mainAxisSize: MainAxisSize.min,
+                  children: [
+                    const Icon(Icons.add),
+                    const SizedBox(width: 8),
+                    Text(l10n.commandMacroAddStep),
+                  ],
+                ),
+              ),
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: name.isEmpty || nameTaken || _steps.isEmpty
+              ? null
+              : () => Navigator.of(context)
+                  .pop(CommandMacro(name: name, steps: List.of(_steps))),
+          child: Text(l10n.commandMacroSave),
+        ),
+      ],
+    );
+  }
+}
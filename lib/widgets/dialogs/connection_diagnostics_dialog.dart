@@ -8,6 +8,15 @@ import '../../src/l10n/app_localizations.dart';
 
 enum _DiagLevel { ok, warn, error, info }
 
+String streamingClientLabel(StreamingClient client) {
+  return switch (client) {
+    StreamingClient.metaLink => 'Meta Quest Link',
+    StreamingClient.virtualDesktop => 'Virtual Desktop',
+    StreamingClient.alvr => 'ALVR',
+    StreamingClient.steamVr => 'SteamVR',
+  };
+}
+
 class ConnectionDiagnosticsDialog extends StatelessWidget {
   const ConnectionDiagnosticsDialog({super.key});
 
@@ -44,6 +53,14 @@ class ConnectionDiagnosticsDialog extends StatelessWidget {
     return _DiagLevel.ok;
   }
 
+  _DiagLevel _healthLevel(AdbHealth health) {
+    return switch (health) {
+      AdbHealth.healthy => _DiagLevel.ok,
+      AdbHealth.unresponsive || AdbHealth.recovering => _DiagLevel.warn,
+      AdbHealth.recoveryFailed => _DiagLevel.error,
+    };
+  }
+
   _DiagLevel _devicesLevel(AdbState state) {
     if (state is AdbStateNoDevices) return _DiagLevel.error;
     if (state is AdbStateDevicesAvailable) return _DiagLevel.warn;
@@ -168,6 +185,7 @@ class ConnectionDiagnosticsDialog extends StatelessWidget {
 
     Widget? activeDeviceDetails() {
       if (!device.isConnected) return null;
+      final firmware = device.firmware;
       // final storageStatus = device.isStorageConnected == null
       //     ? l10n.deviceStorageStatusUnknown
       //     : device.isStorageConnected == true
@@ -177,6 +195,12 @@ class ConnectionDiagnosticsDialog extends StatelessWidget {
         // '${l10n.deviceStorageConnection}: $storageStatus',
         if (device.usbSpeed != null)
           '${l10n.usbSpeedLabel}: ${device.usbSpeed}',
+        if (firmware?.osVersion != null)
+          '${l10n.osVersionLabel}: ${firmware!.osVersion}',
+        if (firmware?.androidVersion != null)
+          '${l10n.androidVersionLabel}: ${firmware!.androidVersion}',
+        if (firmware?.fingerprint != null)
+          '${l10n.buildFingerprintLabel}: ${firmware!.fingerprint}',
       ];
       if (rows.isEmpty) return null;
       return Column(
@@ -199,6 +223,58 @@ class ConnectionDiagnosticsDialog extends StatelessWidget {
       );
     }
 
+    String healthDesc() {
+      final health = adb.health;
+      final retryIn = health.retryInSecs;
+      return switch (health.health) {
+        AdbHealth.healthy => l10n.diagnosticsAdbHealthy,
+        AdbHealth.unresponsive => l10n.diagnosticsAdbUnresponsive,
+        AdbHealth.recovering =>
+          l10n.diagnosticsAdbRecovering(health.recoveryAttempts.toString()),
+        AdbHealth.recoveryFailed => [
+            l10n.diagnosticsAdbRecoveryFailed(health.error ?? ''),
+            if (retryIn != null) l10n.diagnosticsAdbRetryIn(retryIn.toString()),
+          ].join('\n'),
+      };
+    }
+
+    String hostConflictsDesc() {
+      if (!adb.hasHostConflicts) return l10n.diagnosticsHostConflictsNone;
+      return [
+        if (adb.streamingClients.isNotEmpty)
+          l10n.diagnosticsStreamingClientsRunning(
+              adb.streamingClients.map(streamingClientLabel).join(', ')),
+        if (adb.foreignAdbServers.isNotEmpty) l10n.diagnosticsForeignAdbServers,
+      ].join('\n');
+    }
+
+    Widget? foreignAdbServersWidget() {
+      if (adb.foreignAdbServers.isEmpty) return null;
+      return Column(
+        crossAxisAlignment: CrossAxisAlignment.start,
+        mainAxisSize: MainAxisSize.min,
+        children: [
+          for (final server in adb.foreignAdbServers)
+            Padding(
+              padding: const EdgeInsets.symmetric(vertical: 2),
+              child: Text(
+                '• ${server.path} (${server.pid})',
+                style: Theme.of(context)
+                    .textTheme
+                    .bodySmall
+                    ?.copyWith(fontFamily: 'monospace'),
+              ),
+            ),
+          const SizedBox(height: 4),
+          OutlinedButton.icon(
+            icon: const Icon(Icons.stop_circle_outlined),
+            label: Text(l10n.diagnosticsStopForeignAdbServers),
+            onPressed: () => StopForeignAdbServersRequest().sendSignalToRust(),
+          ),
+        ],
+      );
+    }
+
     final adbPath = settings.settings.adbPath;
     final adbPathDesc = (adbPath.isEmpty)
         ? l10n.diagnosticsUsingSystemPath
@@ -225,6 +301,12 @@ class ConnectionDiagnosticsDialog extends StatelessWidget {
               title: l10n.diagnosticsAdbServer,
               description: serverDesc(),
             ),
+            _item(
+              context,
+              level: _healthLevel(adb.health.health),
+              title: l10n.diagnosticsAdbHealth,
+              description: healthDesc(),
+            ),
             _item(
               context,
               level: devicesLevel,
@@ -245,6 +327,13 @@ class ConnectionDiagnosticsDialog extends StatelessWidget {
               description: deviceDesc,
               additionalContent: activeDeviceDetails(),
             ),
+            _item(
+              context,
+              level: adb.hasHostConflicts ? _DiagLevel.warn : _DiagLevel.ok,
+              title: l10n.diagnosticsHostConflicts,
+              description: hostConflictsDesc(),
+              additionalContent: foreignAdbServersWidget(),
+            ),
           ],
         ),
       ),
@@ -0,0 +1,346 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+String customRemoteKindLabel(CustomRemoteKind kind) {
+  return switch (kind) {
+    CustomRemoteKind.http => 'HTTP',
+    CustomRemoteKind.webdav => 'WebDAV',
+    CustomRemoteKind.s3 => 'S3',
+    CustomRemoteKind.ftp => 'FTP',
+    CustomRemoteKind.sftp => 'SFTP',
+  };
+}
+
+/// Rclone remotes added by the user on top of the downloader configuration
+class CustomRemotesDialog extends StatefulWidget {
+  const CustomRemotesDialog({super.key});
+
+  @override
+  State<CustomRemotesDialog> createState() => _CustomRemotesDialogState();
+}
+
+class _CustomRemotesDialogState extends State<CustomRemotesDialog> {
+  StreamSubscription<RustSignalPack<CustomRemotesChanged>>? _remotesSub;
+  List<CustomRemoteInfo>? _remotes;
+  String? _error;
+
+  @override
+  void initState() {
+    super.initState();
+    _remotesSub = CustomRemotesChanged.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      setState(() {
+        _remotes = event.message.remotes;
+        _error = event.message.error;
+      });
+    });
+    ListCustomRemotesRequest().sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _remotesSub?.cancel();
+    super.dispose();
+  }
+
+  Future<void> _edit(CustomRemoteInfo? remote) {
+    return showDialog<void>(
+      context: context,
+      builder: (context) => _CustomRemoteEditorDialog(initial: remote),
+    );
+  }
+
+  Widget _buildContent(AppLocalizations l10n) {
+    final remotes = _remotes;
+    if (remotes == null) {
+      return const Center(child: CircularProgressIndicator());
+    }
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        if (_error != null)
+          Padding(
+            padding: const EdgeInsets.only(bottom: 8),
+            child: Text(_error!,
+                style: TextStyle(color: Theme.of(context).colorScheme.error)),
+          ),
+        if (remotes.isEmpty)
+          Text(l10n.customRemotesEmpty)
+        else
+          Flexible(
+            child: ListView(
+              shrinkWrap: true,
+              children: [
+                for (final remote in remotes)
+                  ListTile(
+                    contentPadding: EdgeInsets.zero,
+                    title: Text(remote.name),
+                    subtitle: Text(
+                        '${customRemoteKindLabel(remote.kind)} • '
+                        '${remote.endpoint}'),
+                    trailing: Row(
+                      mainAxisSize: MainAxisSize.min,
+                      children: [
+                        IconButton(
+                          icon: const Icon(Icons.edit_outlined),
+                          tooltip: l10n.customRemoteEdit,
+                          onPressed: () => _edit(remote),
+                        ),
+                        IconButton(
+                          icon: const Icon(Icons.delete_outline),
+                          tooltip: l10n.customRemoteDelete,
+                          onPressed: () =>
+                              RemoveCustomRemoteRequest(name: remote.name)
+                                  .sendSignalToRust(),
+                        ),
+                      ],
+                    ),
+                  ),
+              ],
+            ),
+          ),
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    return AlertDialog(
+      title: Text(l10n.customRemotes),
+      content: SizedBox(width: 480, child: _buildContent(l10n)),
+      actions: [
+        TextButton.icon(
+          icon: const Icon(Icons.add),
+          label: Text(l10n.customRemoteAdd),
+          onPressed: () => _edit(null),
+        ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
+
+class _CustomRemoteEditorDialog extends StatefulWidget {
+  final CustomRemoteInfo? initial;
+
+  const _CustomRemoteEditorDialog({this.initial});
+
+  @override
+  State<_CustomRemoteEditorDialog> createState() =>
+      _CustomRemoteEditorDialogState();
+}
+
+class _CustomRemoteEditorDialogState extends State<_CustomRemoteEditorDialog> {
+  static const _validateDelay = Duration(milliseconds: 300);
+
+  late final _nameController =
+      TextEditingController(text: widget.initial?.name ?? '');
+  late final _endpointController =
+      TextEditingController(text: widget.initial?.endpoint ?? '');
+  late final _usernameController =
+      TextEditingController(text: widget.initial?.username ?? '');
+  final _passwordController = TextEditingController();
+  late CustomRemoteKind _kind = widget.initial?.kind ?? CustomRemoteKind.http;
+
+  StreamSubscription<RustSignalPack<CustomRemoteValidation>>? _validationSub;
+  StreamSubscription<RustSignalPack<CustomRemoteSaveResult>>? _saveSub;
+  Timer? _validateTimer;
+  List<String>? _errors;
+  String? _saveError;
+  bool _saving = false;
+
+  bool get _keepsPassword => widget.initial?.hasPassword ?? false;
+
+  @override
+  void initState() {
+    super.initState();
+    _validationSub = CustomRemoteValidation.rustSignalStream.listen((event) {
+      final validation = event.message;
+      if (validation.name != _nameController.text.trim() || !mounted) return;
+      setState(() => _errors = validation.errors);
+    });
+    _saveSub = CustomRemoteSaveResult.rustSignalStream.listen((event) {
+      final result = event.message;
+      if (!_saving || result.name != _nameController.text.trim()) return;
+      if (!mounted) return;
+      if (result.error == null) {
+        Navigator.of(context).pop();
+        return;
+      }
+      setState(() {
+        _saving = false;
+        _saveError = result.error;
+      });
+    });
+    if (widget.initial != null) _validate();
+  }
+
+  @override
+  void dispose() {
+    _validateTimer?.cancel();
+    _validationSub?.cancel();
+    _saveSub?.cancel();
+    _nameController.dispose();
+    _endpointController.dispose();
+    _usernameController.dispose();
+    _passwordController.dispose();
+    super.dispose();
+  }
+
+  CustomRemoteDraft _draft() {
+    String? optional(TextEditingController controller) {
+      final text = controller.text.trim();
+      return text.isEmpty ? null : text;
+    }
+
+    final password = _passwordController.text;
+    return CustomRemoteDraft(
+      name: _nameController.text.trim(),
+      kind: _kind,
+      endpoint: _endpointController.text.trim(),
+      username: optional(_usernameController),
+      password: password.isEmpty && _keepsPassword ? null : password,
+    );
+  }
+
+  void _validate() {
+    ValidateCustomRemoteRequest(remote: _draft()).sendSignalToRust();
+  }
+
+  void _onChanged() {
+    setState(() {
+      _errors = null;
+      _saveError = null;
+    });
+    _validateTimer?.cancel();
+    _validateTimer = Timer(_validateDelay, _validate);
+  }
+
+  void _save() {
+    setState(() {
+      _saving = true;
+      _saveError = null;
+    });
+    SaveCustomRemoteRequest(
+      remote: _draft(),
+      previousName: widget.initial?.name,
+    ).sendSignalToRust();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final theme = Theme.of(context);
+    final errors = _errors;
+    final usesUrl = switch (_kind) {
+      CustomRemoteKind.ftp || CustomRemoteKind.sftp => false,
+      _ => true,
+    };
+    return AlertDialog(
+      title: Text(widget.initial == null
+          ? l10n.customRemoteAdd
+          : l10n.customRemoteEdit),
+      content: SizedBox(
+        width: 420,
+        child: SingleChildScrollView(
+          child: Column(
+            mainAxisSize: MainAxisSize.min,
+            crossAxisAlignment: CrossAxisAlignment.start,
+            children: [
+              TextField(
+                controller: _nameController,
+                decoration:
+                    InputDecoration(labelText: l10n.customRemoteName),
+                onChanged: (_) => _onChanged(),
+              ),
+              const SizedBox(height: 12),
+              DropdownButtonFormField<CustomRemoteKind>(
+                initialValue: _kind,
+                decoration:
+                    InputDecoration(labelText: l10n.customRemoteKind),
+                items: [
+                  for (final kind in CustomRemoteKind.values)
+                    DropdownMenuItem(
+                      value: kind,
+                      child: Text(customRemoteKindLabel(kind)),
+                    ),
+                ],
+                onChanged: (kind) {
+                  if (kind == null) return;
+                  _kind = kind;
+                  _onChanged();
+                },
+              ),
+              const SizedBox(height: 12),
+              TextField(
+                controller: _endpointController,
+                decoration: InputDecoration(
+                  labelText: usesUrl
+                      ? l10n.customRemoteEndpointUrl
+                      : l10n.customRemoteEndpointHost,
+                  hintText: usesUrl
+                      ? 'https://example.com/path/'
+                      : 'example.com:21',
+                ),
+                onChanged: (_) => _onChanged(),
+              ),
+              const SizedBox(height: 12),
+              TextField(
+                controller: _usernameController,
+                decoration:
+                    InputDecoration(labelText: l10n.customRemoteUsername),
+                onChanged: (_) => _onChanged(),
+              ),
+              const SizedBox(height: 12),
+              TextField(
+                controller: _passwordController,
+                obscureText: true,
+                decoration: InputDecoration(
+                  labelText: l10n.customRemotePassword,
+                  helperText:
+                      _keepsPassword ? l10n.customRemoteKeepPassword : null,
+                ),
+                onChanged: (_) => _onChanged(),
+              ),
+              if (errors != null && errors.isNotEmpty) ...[
+                const SizedBox(height: 12),
+                for (final error in errors)
+                  Text(error,
+                      style: theme.textTheme.bodySmall
+                          ?.copyWith(color: theme.colorScheme.error)),
+              ],
+              if (_saveError != null) ...[
+                const SizedBox(height: 12),
+                Text(l10n.customRemoteSaveFailed(_saveError!),
+                    style: theme.textTheme.bodySmall
+                        ?.copyWith(color: theme.colorScheme.error)),
+              ],
+            ],
+          ),
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: _saving || errors == null || errors.isNotEmpty
+              ? null
+              : _save,
+          child: Text(l10n.customRemoteSave),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,154 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
+
+/// Maintenance log of a headset with user notes and recorded operations
+class DeviceLogDialog extends StatefulWidget {
+  final String serial;
+  final String deviceName;
+
+  const DeviceLogDialog({
+    super.key,
+    required this.serial,
+    required this.deviceName,
+  });
+
+  @override
+  State<DeviceLogDialog> createState() => _DeviceLogDialogState();
+}
+
+class _DeviceLogDialogState extends State<DeviceLogDialog> {
+  final TextEditingController _noteController = TextEditingController();
+  StreamSubscription<RustSignalPack<DeviceLogChanged>>? _sub;
+  List<DeviceLogEntry>? _entries;
+  String? _error;
+
+  @override
+  void initState() {
+    super.initState();
+    _sub = DeviceLogChanged.rustSignalStream.listen((event) {
+      final message = event.message;
+      if (message.serial != widget.serial || !mounted) return;
+      setState(() {
+        _entries = message.entries;
+        _error = message.error;
+      });
+    });
+    GetDeviceLogRequest(serial: widget.serial).sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _sub?.cancel();
+    _noteController.dispose();
+    super.dispose();
+  }
+
+  void _addNote() {
+    final text = _noteController.text.trim();
+    if (text.isEmpty) return;
+    AddDeviceNoteRequest(serial: widget.serial, text: text).sendSignalToRust();
+    _noteController.clear();
+  }
+
+  IconData _getKindIcon(DeviceLogEntryKind kind) {
+    switch (kind) {
+      case DeviceLogEntryKind.note:
+        return Icons.sticky_note_2_outlined;
+      case DeviceLogEntryKind.restore:
+        return Icons.settings_backup_restore;
+      case DeviceLogEntryKind.reboot:
+        return Icons.restart_alt;
+    }
+  }
+
+  Widget _buildEntry(DeviceLogEntry entry) {
+    final l10n = AppLocalizations.of(context);
+    final time = DateTime.fromMillisecondsSinceEpoch(entry.timestamp.toInt());
+    return ListTile(
+      dense: true,
+      leading: Icon(_getKindIcon(entry.kind)),
+      title: SelectableText(entry.text),
+      subtitle: Text(formatDateTime(context, time) ?? time.toString()),
+      trailing: IconButton(
+        icon: const Icon(Icons.delete_outline),
+        tooltip: l10n.deviceLogDeleteEntry,
+        onPressed: () => DeleteDeviceLogEntryRequest(
+          serial: widget.serial,
+          id: entry.id,
+        ).sendSignalToRust(),
+      ),
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final entries = _entries;
+    final error = _error;
+    return AlertDialog(
+      title: Text(l10n.deviceLogTitle(widget.deviceName)),
+      content: SizedBox(
+        width: 520,
+        height: 420,
+        child: Column(
+          crossAxisAlignment: CrossAxisAlignment.stretch,
+          children: [
+            Row(
+              children: [
+                Expanded(
+                  child: TextField(
+                    controller: _noteController,
+                    decoration: InputDecoration(
+                      labelText: l10n.deviceLogNoteHint,
+                      border: const OutlineInputBorder(),
+                    ),
+                    onSubmitted: (_) => _addNote(),
+                  ),
+                ),
+                const SizedBox(width: 8),
+                FilledButton(
+                  onPressed: _addNote,
+                  child: Text(l10n.deviceLogAddNote),
+                ),
+              ],
+            ),
+            if (error != null) ...[
+              const SizedBox(height: 8),
+              buildCopyableText(
+                context,
+                error,
+                style: Theme.of(context).textTheme.bodySmall?.copyWith(
+                      color: Theme.of(context).colorScheme.error,
+                    ),
+              ),
+            ],
+            const SizedBox(height: 12),
+            Expanded(
+              child: entries == null
+                  ? const Center(child: CircularProgressIndicator())
+                  : entries.isEmpty
+                      ? Center(child: Text(l10n.deviceLogEmpty))
+                      : ListView.builder(
+                          itemCount: entries.length,
+                          itemBuilder: (context, index) =>
+                              _buildEntry(entries[index]),
+                        ),
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
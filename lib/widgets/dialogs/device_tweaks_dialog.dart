@@ -0,0 +1,127 @@
+import 'package:flutter/material.dart';
+import 'package:provider/provider.dart';
+
+import '../../providers/device_state.dart';
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Rendering tweaks of the connected headset, read back on every device refresh
+class DeviceTweaksDialog extends StatelessWidget {
+  const DeviceTweaksDialog({super.key});
+
+  static const _refreshRates = [60, 72, 80, 90, 120];
+  static const _levels = [0, 1, 2, 3, 4, 5];
+  static const _foveationLevels = [0, 1, 2, 3, 4];
+  static const _textureSizes = [1024, 1536, 2048, 2560, 3072];
+
+  void _send(DeviceTweak tweak) {
+    AdbRequest(
+      command: AdbCommandSetDeviceTweak(value: tweak),
+      commandKey: 'device-tweak',
+    ).sendSignalToRust();
+  }
+
+  Widget _buildRow<T>(
+    BuildContext context, {
+    required String label,
+    required T? value,
+    required List<T> options,
+    required String Function(T) format,
+    required ValueChanged<T?> onChanged,
+  }) {
+    final l10n = AppLocalizations.of(context);
+    return Row(
+      children: [
+        Expanded(child: Text(label)),
+        DropdownButton<T?>(
+          value: options.contains(value) ? value : null,
+          onChanged: onChanged,
+          items: [
+            DropdownMenuItem<T?>(
+              value: null,
+              child: Text(value == null || options.contains(value)
+                  ? l10n.deviceTweakDefault
+                  : format(value)),
+            ),
+            for (final option in options)
+              DropdownMenuItem<T?>(value: option, child: Text(format(option))),
+          ],
+        ),
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final tweaks = context.watch<DeviceState>().tweaks;
+    final texture = tweaks?.textureSize;
+    final textureValue = texture != null && texture.width == texture.height
+        ? texture.width
+        : null;
+    return AlertDialog(
+      title: Text(l10n.deviceTweaks),
+      content: SizedBox(
+        width: 360,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.start,
+          children: [
+            Text(
+              l10n.deviceTweaksHint,
+              style: Theme.of(context).textTheme.bodySmall,
+            ),
+            const SizedBox(height: 12),
+            _buildRow<int>(
+              context,
+              label: l10n.deviceTweakRefreshRate,
+              value: tweaks?.refreshRate,
+              options: _refreshRates,
+              format: (v) => '$v Hz',
+              onChanged: (v) => _send(DeviceTweakRefreshRate(value: v)),
+            ),
+            _buildRow<int>(
+              context,
+              label: l10n.deviceTweakCpuLevel,
+              value: tweaks?.cpuLevel,
+              options: _levels,
+              format: (v) => '$v',
+              onChanged: (v) => _send(DeviceTweakCpuLevel(value: v)),
+            ),
+            _buildRow<int>(
+              context,
+              label: l10n.deviceTweakGpuLevel,
+              value: tweaks?.gpuLevel,
+              options: _levels,
+              format: (v) => '$v',
+              onChanged: (v) => _send(DeviceTweakGpuLevel(value: v)),
+            ),
+            _buildRow<int>(
+              context,
+              label: l10n.deviceTweakTextureSize,
+              value: textureValue,
+              options: _textureSizes,
+              format: (v) => '$v × $v',
+              onChanged: (v) => _send(DeviceTweakTextureSize(
+                  value: v == null ? null : TextureSize(width: v, height: v))),
+            ),
+            _buildRow<int>(
+              context,
+              label: l10n.deviceTweakFoveation,
+              value: tweaks?.foveationLevel,
+              options: _foveationLevels,
+              format: (v) => '$v',
+              onChanged: (v) => _send(DeviceTweakFixedFoveation(value: v)),
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,171 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
+
+/// Space taken by YAAS data per category, with buttons to clear each one
+class DiskUsageDialog extends StatefulWidget {
+  const DiskUsageDialog({super.key});
+
+  @override
+  State<DiskUsageDialog> createState() => _DiskUsageDialogState();
+}
+
+class _DiskUsageDialogState extends State<DiskUsageDialog> {
+  StreamSubscription<RustSignalPack<DiskUsageReport>>? _reportSub;
+  DiskUsageReport? _report;
+  DiskUsageCategory? _clearing;
+  bool _loading = true;
+
+  @override
+  void initState() {
+    super.initState();
+    _reportSub = DiskUsageReport.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      setState(() {
+        _report = event.message;
+        _clearing = null;
+        _loading = false;
+      });
+    });
+    _refresh();
+  }
+
+  @override
+  void dispose() {
+    _reportSub?.cancel();
+    super.dispose();
+  }
+
+  void _refresh() {
+    setState(() => _loading = true);
+    GetDiskUsageRequest().sendSignalToRust();
+  }
+
+  String _label(AppLocalizations l10n, DiskUsageCategory category) {
+    return switch (category) {
+      DiskUsageCategory.downloads => l10n.diskUsageDownloads,
+      DiskUsageCategory.mediaCache => l10n.diskUsageMediaCache,
+      DiskUsageCategory.logs => l10n.diskUsageLogs,
+      DiskUsageCategory.backups => l10n.diskUsageBackups,
+      DiskUsageCategory.trash => l10n.diskUsageTrash,
+      DiskUsageCategory.donationUploads => l10n.diskUsageDonationUploads,
+    };
+  }
+
+  Future<void> _clear(CategoryUsage usage) async {
+    final l10n = AppLocalizations.of(context);
+    final label = _label(l10n, usage.category);
+    final confirmed = await showDialog<bool>(
+      context: context,
+      builder: (context) => AlertDialog(
+        title: Text(l10n.diskUsageClearTitle(label)),
+        content: Text(l10n.diskUsageClearPrompt(
+            label, formatSize(usage.size.toInt(), 1), usage.path)),
+        actions: [
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(false),
+            child: Text(l10n.commonCancel),
+          ),
+          FilledButton(
+            onPressed: () => Navigator.of(context).pop(true),
+            child: Text(l10n.diskUsageClear),
+          ),
+        ],
+      ),
+    );
+    if (confirmed != true || !mounted) return;
+    setState(() => _clearing = usage.category);
+    ClearDiskUsageRequest(category: usage.category).sendSignalToRust();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final report = _report;
+    final total = report?.categories
+            .fold<int>(0, (sum, usage) => sum + usage.size.toInt()) ??
+        0;
+
+    return AlertDialog(
+      title: Text(l10n.diskUsage),
+      content: SizedBox(
+        width: 480,
+        child: report == null
+            ? const SizedBox(
+                height: 120,
+                child: Center(child: CircularProgressIndicator()),
+              )
+            : Column(
+                mainAxisSize: MainAxisSize.min,
+                crossAxisAlignment: CrossAxisAlignment.stretch,
+                children: [
+                  if (report.error != null)
+                    Padding(
+                      padding: const EdgeInsets.only(bottom: 8),
+                      child: Text(
+                        report.error!,
+                        style: TextStyle(
+                            color: Theme.of(context).colorScheme.error),
+                      ),
+                    ),
+                  for (final usage in report.categories)
+                    ListTile(
+                      dense: true,
+                      title: Text(_label(l10n, usage.category)),
+                      subtitle: Text(
+                        usage.path,
+                        maxLines: 1,
+                        overflow: TextOverflow.ellipsis,
+                      ),
+                      trailing: Row(
+                        mainAxisSize: MainAxisSize.min,
+                        children: [
+                          Text(formatSize(usage.size.toInt(), 1)),
+                          const SizedBox(width: 8),
+                          _clearing == usage.category
+                              ? const Padding(
+                                  padding: EdgeInsets.all(12),
+                                  child: SizedBox.square(
+                                    dimension: 16,
+                                    child: CircularProgressIndicator(
+                                        strokeWidth: 2),
+                                  ),
+                                )
+                              : IconButton(
+                                  icon: const Icon(Icons.delete_sweep_outlined),
+                                  tooltip: l10n.diskUsageClear,
+                                  onPressed: _clearing != null ||
+                                          usage.size.toInt() == 0
+                                      ? null
+                                      : () => _clear(usage),
+                                ),
+                        ],
+                      ),
+                    ),
+                  const Divider(),
+                  ListTile(
+                    dense: true,
+                    title: Text(l10n.diskUsageTotal),
+                    trailing: Text(formatSize(total, 1)),
+                  ),
+                ],
+              ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: _loading || _clearing != null ? null : _refresh,
+          child: Text(l10n.refresh),
+        ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
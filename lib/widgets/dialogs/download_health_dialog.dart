@@ -0,0 +1,152 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+String describeHealthIssue(AppLocalizations l10n, DownloadHealthIssue issue) {
+  return switch (issue) {
+    DownloadHealthIssueNoApk() => l10n.healthIssueNoApk,
+    DownloadHealthIssueMultipleApks(:final value) =>
+      l10n.healthIssueMultipleApks(value.join(', ')),
+    DownloadHealthIssueApkUnreadable(:final file, :final error) =>
+      l10n.healthIssueApkUnreadable(file, error),
+    DownloadHealthIssueObbFolderMismatch(:final folder, :final packageName) =>
+      l10n.healthIssueObbFolderMismatch(folder, packageName),
+    DownloadHealthIssueObbFileMismatch(:final file, :final packageName) =>
+      l10n.healthIssueObbFileMismatch(file, packageName),
+    DownloadHealthIssueLooseObbFiles(:final value) =>
+      l10n.healthIssueLooseObb(value.join(', ')),
+    DownloadHealthIssueScriptUnreadable(:final value) =>
+      l10n.healthIssueScriptUnreadable(value),
+    DownloadHealthIssueScriptFileMissing(:final line, :final path) =>
+      l10n.healthIssueScriptFileMissing('$line', path),
+    DownloadHealthIssueScriptCommandUnsupported(:final line, :final command) =>
+      l10n.healthIssueScriptUnsupported('$line', command),
+    DownloadHealthIssueArchiveUnreadable(:final file, :final error) =>
+      l10n.healthIssueArchiveUnreadable(file, error),
+  };
+}
+
+/// Checks a local app folder and lists what would keep it from installing
+class DownloadHealthDialog extends StatefulWidget {
+  final String path;
+  final String name;
+
+  const DownloadHealthDialog({
+    super.key,
+    required this.path,
+    required this.name,
+  });
+
+  @override
+  State<DownloadHealthDialog> createState() => _DownloadHealthDialogState();
+}
+
+class _DownloadHealthDialogState extends State<DownloadHealthDialog> {
+  StreamSubscription<RustSignalPack<DownloadHealthReport>>? _reportSub;
+  DownloadHealthReport? _report;
+
+  @override
+  void initState() {
+    super.initState();
+    _reportSub = DownloadHealthReport.rustSignalStream.listen((event) {
+      if (event.message.path != widget.path || !mounted) return;
+      setState(() => _report = event.message);
+    });
+    CheckDownloadHealthRequest(path: widget.path).sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _reportSub?.cancel();
+    super.dispose();
+  }
+
+  Widget _buildContent(AppLocalizations l10n) {
+    final theme = Theme.of(context);
+    final report = _report;
+    if (report == null) {
+      return Row(
+        children: [
+          const SizedBox(
+            width: 20,
+            height: 20,
+            child: CircularProgressIndicator(strokeWidth: 2),
+          ),
+          const SizedBox(width: 12),
+          Text(l10n.downloadHealthChecking),
+        ],
+      );
+    }
+    if (report.error != null) {
+      return Text(l10n.downloadHealthFailed(report.error!));
+    }
+
+    final package = report.packageName;
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        if (package != null)
+          Text(
+            report.versionCode == null
+                ? package
+                : '$package • v${report.versionCode}',
+            style: theme.textTheme.bodySmall,
+          ),
+        if (report.usesInstallScript)
+          Text(l10n.downloadHealthUsesScript,
+              style: theme.textTheme.bodySmall),
+        const SizedBox(height: 12),
+        if (report.issues.isEmpty)
+          Row(
+            children: [
+              Icon(Icons.check_circle, color: theme.colorScheme.primary),
+              const SizedBox(width: 8),
+              Text(l10n.downloadHealthReady),
+            ],
+          )
+        else ...[
+          Text(l10n.downloadHealthFixIssues),
+          const SizedBox(height: 8),
+          for (final issue in report.issues)
+            Padding(
+              padding: const EdgeInsets.only(bottom: 6),
+              child: Row(
+                crossAxisAlignment: CrossAxisAlignment.start,
+                children: [
+                  Icon(Icons.error_outline,
+                      size: 18, color: theme.colorScheme.error),
+                  const SizedBox(width: 8),
+                  Expanded(
+                    child: SelectableText(describeHealthIssue(l10n, issue)),
+                  ),
+                ],
+              ),
+            ),
+        ],
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    return AlertDialog(
+      title: Text(widget.name),
+      content: SizedBox(
+        width: 480,
+        child: SingleChildScrollView(child: _buildContent(l10n)),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,266 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Human readable description of a post-install recipe step
+String describeRecipeStep(AppLocalizations l10n, RecipeStep step) {
+  return switch (step) {
+    RecipeStepGrantPermission(:final value) => l10n.installRecipeStepGrant(
+        value.substring(value.lastIndexOf('.') + 1)),
+    RecipeStepPushFile(:final localPath, :final remotePath) =>
+      l10n.installRecipeStepPush(localPath, remotePath),
+    RecipeStepLaunchOnce() => l10n.installRecipeStepLaunch,
+    RecipeStepSetProperty(:final name, :final value) =>
+      l10n.installRecipeStepProperty(name, value),
+  };
+}
+
+enum _StepTemplate { grantPermission, pushFile, launchOnce, setProperty }
+
+/// Edits the steps run on the device after a package is installed
+class InstallRecipeDialog extends StatefulWidget {
+  final String packageName;
+
+  const InstallRecipeDialog({super.key, required this.packageName});
+
+  @override
+  State<InstallRecipeDialog> createState() => _InstallRecipeDialogState();
+}
+
+class _InstallRecipeDialogState extends State<InstallRecipeDialog> {
+  StreamSubscription<RustSignalPack<InstallRecipesChanged>>? _recipesSub;
+  bool _loaded = false;
+  bool _saving = false;
+  bool _enabled = true;
+  List<RecipeStep> _steps = [];
+  String? _error;
+
+  @override
+  void initState() {
+    super.initState();
+    _recipesSub = InstallRecipesChanged.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      final message = event.message;
+      if (_saving) {
+        if (message.error == null) {
+          Navigator.of(context).pop();
+          return;
+        }
+        setState(() {
+          _saving = false;
+          _error = message.error;
+        });
+        return;
+      }
+      if (_loaded) return;
+      final recipe = message.recipes
+          .where((r) => r.packageName == widget.packageName)
+          .firstOrNull;
+      setState(() {
+        _loaded = true;
+        _enabled = recipe?.enabled ?? true;
+        _steps = recipe?.steps.toList() ?? [];
+      });
+    });
+    GetInstallRecipesRequest().sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _recipesSub?.cancel();
+    super.dispose();
+  }
+
+  void _save() {
+    setState(() {
+      _saving = true;
+      _error = null;
+    });
+    SetInstallRecipeRequest(
+      recipe: InstallRecipe(
+        packageName: widget.packageName,
+        enabled: _enabled,
+        steps: List.of(_steps),
+      ),
+    ).sendSignalToRust();
+  }
+
+  /// Asks for one value per label, returns null if cancelled or left empty
+  Future<List<String>?> _prompt(List<String> labels) async {
+    final controllers = [for (final _ in labels) TextEditingController()];
+    final values = await showDialog<List<String>>(
+      context: context,
+      builder: (context) {
+        final l10n = AppLocalizations.of(context);
+        List<String> read() => [for (final c in controllers) c.text.trim()];
+        return AlertDialog(
+          content: Column(
+            mainAxisSize: MainAxisSize.min,
+            children: [
+              for (final (index, label) in labels.indexed)
+                TextField(
+                  controller: controllers[index],
+                  autofocus: index == 0,
+                  decoration: InputDecoration(labelText: label),
+                ),
+            ],
+          ),
+          actions: [
+            TextButton(
+              onPressed: () => Navigator.of(context).pop(),
+              child: Text(l10n.commonCancel),
+            ),
+            FilledButton(
+              onPressed: () => Navigator.of(context).pop(read()),
+              child: Text(l10n.commonConfirm),
+            ),
+          ],
+        );
+      },
+    );
+    for (final controller in controllers) {
+      controller.dispose();
+    }
+    if (values == null || values.any((v) => v.isEmpty)) return null;
+    return values;
+  }
+
+  Future<void> _addStep(_StepTemplate template) async {
+    final l10n = AppLocalizations.of(context);
+    final RecipeStep? step = switch (template) {
+      _StepTemplate.grantPermission =>
+        await _prompt([l10n.installRecipePermissionName]).then((v) =>
+            v == null ? null : RecipeStepGrantPermission(value: v[0])),
+      _StepTemplate.pushFile => await _prompt([
+          l10n.installRecipeLocalPath,
+          l10n.installRecipeRemotePath,
+        ]).then((v) => v == null
+            ? null
+            : RecipeStepPushFile(localPath: v[0], remotePath: v[1])),
+      _StepTemplate.launchOnce => const RecipeStepLaunchOnce(),
+      _StepTemplate.setProperty => await _prompt([
+          l10n.installRecipePropertyName,
+          l10n.installRecipePropertyValue,
+        ]).then((v) =>
+            v == null ? null : RecipeStepSetProperty(name: v[0], value: v[1])),
+    };
+    if (step == null || !mounted) return;
+    setState(() => _steps.add(step));
+  }
+
+  String _templateLabel(AppLocalizations l10n, _StepTemplate template) {
+    return switch (template) {
+      _StepTemplate.grantPermission => l10n.installRecipeGrantPermission,
+      _StepTemplate.pushFile => l10n.installRecipePushFile,
+      _StepTemplate.launchOnce => l10n.installRecipeStepLaunch,
+      _StepTemplate.setProperty => l10n.installRecipeSetProperty,
+    };
+  }
+
+  Widget _buildContent(AppLocalizations l10n) {
+    if (!_loaded) {
+      return const Center(
+        child: SizedBox(
+          width: 20,
+          height: 20,
+          child: CircularProgressIndicator(strokeWidth: 2),
+        ),
+      );
+    }
+    final theme = Theme.of(context);
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        Text(widget.packageName, style: theme.textTheme.bodySmall),
+        const SizedBox(height: 4),
+        Text(l10n.installRecipeHelp, style: theme.textTheme.bodySmall),
+        SwitchListTile(
+          contentPadding: EdgeInsets.zero,
+          title: Text(l10n.installRecipeEnabled),
+          value: _enabled,
+          onChanged: (v) => setState(() => _enabled = v),
+        ),
+        if (_steps.isEmpty)
+          Text(l10n.installRecipeNoSteps, style: theme.textTheme.bodySmall),
+        Flexible(
+          child: ReorderableListView(
+            shrinkWrap: true,
+            buildDefaultDragHandles: false,
+            onReorder: (oldIndex, newIndex) => setState(() {
+              if (newIndex > oldIndex) newIndex--;
+              _steps.insert(newIndex, _steps.removeAt(oldIndex));
+            }),
+            children: [
+              for (final (index, step) in _steps.indexed)
+                ListTile(
+                  key: ObjectKey(step),
+                  dense: true,
+                  contentPadding: EdgeInsets.zero,
+                  leading: ReorderableDragStartListener(
+                    index: index,
+                    child: const Icon(Icons.drag_handle),
+                  ),
+                  title: Text(describeRecipeStep(l10n, step)),
+                  trailing: IconButton(
+                    icon: const Icon(Icons.close),
+                    onPressed: () => setState(() => _steps.removeAt(index)),
+                  ),
+                ),
+            ],
+          ),
+        ),
+        PopupMenuButton<_StepTemplate>(
+          onSelected: _addStep,
+          itemBuilder: (context) => [
+            for (final template in _StepTemplate.values)
+              PopupMenuItem(
+                value: template,
+                child: Text(_templateLabel(l10n, template)),
+              ),
+          ],
+          child: Padding(
+            padding: const EdgeInsets.symmetric(vertical: 8),
+            child: Row(
+              mainAxisSize: MainAxisSize.min,
+              children: [
+                const Icon(Icons.add),
+                const SizedBox(width: 8),
+                Text(l10n.installRecipeAddStep),
+              ],
+            ),
+          ),
+        ),
+        if (_error != null)
+          Text(
+            _error!,
+            style: theme.textTheme.bodySmall
+                ?.copyWith(color: theme.colorScheme.error),
+          ),
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    return AlertDialog(
+      title: Text(l10n.installRecipe),
+      content: SizedBox(width: 460, child: _buildContent(l10n)),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: !_loaded || _saving ? null : _save,
+          child: Text(l10n.installRecipeSave),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,150 @@
+import 'package:flutter/material.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+String _operationLabel(AppLocalizations l10n, ScriptOperationKind kind) {
+  return switch (kind) {
+    ScriptOperationKind.install => l10n.installScriptOpInstall,
+    ScriptOperationKind.uninstall => l10n.installScriptOpUninstall,
+    ScriptOperationKind.shell => l10n.installScriptOpShell,
+    ScriptOperationKind.push => l10n.installScriptOpPush,
+    ScriptOperationKind.pull => l10n.installScriptOpPull,
+    ScriptOperationKind.pause => l10n.installScriptOpPause,
+  };
+}
+
+IconData _operationIcon(ScriptOperationKind kind) {
+  return switch (kind) {
+    ScriptOperationKind.install => Icons.install_mobile,
+    ScriptOperationKind.uninstall => Icons.delete_outline,
+    ScriptOperationKind.shell => Icons.terminal,
+    ScriptOperationKind.push => Icons.upload,
+    ScriptOperationKind.pull => Icons.download,
+    ScriptOperationKind.pause => Icons.hourglass_empty,
+  };
+}
+
+/// Shows what an install script does on the device and asks whether to run it
+Future<void> showInstallScriptReviewDialog(
+    BuildContext context, InstallScriptReview review) async {
+  final l10n = AppLocalizations.of(context);
+  final theme = Theme.of(context);
+  final hasShell =
+      review.operations.any((op) => op.kind == ScriptOperationKind.shell);
+  final approved = await showDialog<bool>(
+    context: context,
+    barrierDismissible: false,
+    builder: (context) => AlertDialog(
+      title: Text(l10n.installScriptReviewTitle),
+      content: SizedBox(
+        width: 520,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.start,
+          children: [
+            Text(review.scriptPath, style: theme.textTheme.bodySmall),
+            const SizedBox(height: 8),
+            if (hasShell)
+              Text(
+                l10n.installScriptShellWarning,
+                style: theme.textTheme.bodySmall
+                    ?.copyWith(color: theme.colorScheme.error),
+              ),
+            Flexible(
+              child: ListView(
+                shrinkWrap: true,
+                children: [
+                  for (final op in review.operations)
+                    ListTile(
+                      dense: true,
+                      contentPadding: EdgeInsets.zero,
+                      leading: Icon(
+                        _operationIcon(op.kind),
+                        color: op.kind == ScriptOperationKind.shell
+                            ? theme.colorScheme.error
+                            : null,
+                      ),
+                      title: Text(_operationLabel(l10n, op.kind)),
+                      subtitle: SelectableText(op.detail),
+                      trailing: Text(
+                        l10n.installScriptLine(op.line.toString()),
+                        style: theme.textTheme.bodySmall,
+                      ),
+                    ),
+                ],
+              ),
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(false),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: () => Navigator.of(context).pop(true),
+          child: Text(l10n.installScriptRun),
+        ),
+      ],
+    ),
+  );
+  InstallScriptDecision(
+    reviewId: review.reviewId,
+    approved: approved ?? false,
+  ).sendSignalToRust();
+}
+
+/// Lists the lines that kept an install script from running
+Future<void> showInstallScriptUnsupportedDialog(
+    BuildContext context, InstallScriptUnsupported report) {
+  final l10n = AppLocalizations.of(context);
+  final theme = Theme.of(context);
+  return showDialog<void>(
+    context: context,
+    builder: (context) => AlertDialog(
+      title: Text(l10n.installScriptUnsupportedTitle),
+      content: SizedBox(
+        width: 520,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.start,
+          children: [
+            Text(report.scriptPath, style: theme.textTheme.bodySmall),
+            const SizedBox(height: 8),
+            Text(l10n.installScriptUnsupportedDescription),
+            Flexible(
+              child: ListView(
+                shrinkWrap: true,
+                children: [
+                  for (final line in report.lines)
+                    ListTile(
+                      dense: true,
+                      contentPadding: EdgeInsets.zero,
+                      leading: Icon(
+                        Icons.block,
+                        color: theme.colorScheme.error,
+                      ),
+                      title: SelectableText(line.command),
+                      subtitle: Text(line.reason),
+                      trailing: Text(
+                        l10n.installScriptLine(line.line.toString()),
+                        style: theme.textTheme.bodySmall,
+                      ),
+                    ),
+                ],
+              ),
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    ),
+  );
+}
@@ -0,0 +1,152 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+import '../../utils/sideload_utils.dart';
+import '../../utils/utils.dart';
+
+/// Apps shared on the local network, by this instance and by peers.
+///
+/// With [sharePath], that download is shared first.
+class LanShareDialog extends StatefulWidget {
+  const LanShareDialog({super.key, this.sharePath, this.shareName});
+
+  final String? sharePath;
+  final String? shareName;
+
+  @override
+  State<LanShareDialog> createState() => _LanShareDialogState();
+}
+
+class _LanShareDialogState extends State<LanShareDialog> {
+  StreamSubscription<RustSignalPack<LanSharesChanged>>? _sharesSub;
+  LanSharesChanged? _state;
+  final Set<String> _queued = {};
+
+  @override
+  void initState() {
+    super.initState();
+    _sharesSub = LanSharesChanged.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      setState(() => _state = event.message);
+    });
+    final sharePath = widget.sharePath;
+    if (sharePath != null) {
+      ShareOnLanRequest(path: sharePath, name: widget.shareName ?? '')
+          .sendSignalToRust();
+    }
+    GetLanSharesRequest().sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _sharesSub?.cancel();
+    super.dispose();
+  }
+
+  void _receive(PeerShare share) {
+    SideloadUtils.receiveFromPeer(share);
+    setState(() => _queued.add('${share.address}/${share.id}'));
+  }
+
+  Widget _buildSectionTitle(String title) {
+    return Padding(
+      padding: const EdgeInsets.only(top: 8, bottom: 4),
+      child: Text(title, style: Theme.of(context).textTheme.titleSmall),
+    );
+  }
+
+  Widget _buildHint(String text) {
+    return Padding(
+      padding: const EdgeInsets.symmetric(vertical: 8),
+      child: Text(text, style: Theme.of(context).textTheme.bodySmall),
+    );
+  }
+
+  Widget _buildOwnShare(AppLocalizations l10n, LanShare share) {
+    return ListTile(
+      dense: true,
+      leading: const Icon(Icons.upload_outlined),
+      title: Text(share.name),
+      subtitle: Text(
+        '${formatSize(share.size.toInt(), 2)} • ${share.path}',
+        maxLines: 1,
+        overflow: TextOverflow.ellipsis,
+      ),
+      trailing: IconButton(
+        icon: const Icon(Icons.stop_circle_outlined),
+        tooltip: l10n.lanShareStop,
+        onPressed: () => StopLanShareRequest(id: share.id).sendSignalToRust(),
+      ),
+    );
+  }
+
+  Widget _buildPeerShare(AppLocalizations l10n, PeerShare share) {
+    final queued = _queued.contains('${share.address}/${share.id}');
+    return ListTile(
+      dense: true,
+      leading: const Icon(Icons.download_outlined),
+      title: Text(share.name),
+      subtitle: Text('${share.peer} • ${formatSize(share.size.toInt(), 2)}'),
+      trailing: FilledButton.tonal(
+        onPressed: queued ? null : () => _receive(share),
+        child: Text(queued ? l10n.lanShareQueued : l10n.lanShareReceive),
+      ),
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final state = _state;
+    final error = state?.error;
+
+    return AlertDialog(
+      title: Text(l10n.lanShareTitle),
+      content: SizedBox(
+        width: 520,
+        child: state == null
+            ? const SizedBox(
+                height: 80,
+                child: Center(child: CircularProgressIndicator()),
+              )
+            : ListView(
+                shrinkWrap: true,
+                children: [
+                  if (error != null)
+                    Text(
+                      error,
+                      style: TextStyle(
+                          color: Theme.of(context).colorScheme.error),
+                    ),
+                  _buildSectionTitle(l10n.lanShareOwn),
+                  if (state.shares.isEmpty)
+                    _buildHint(l10n.lanShareOwnEmpty)
+                  else
+                    for (final share in state.shares)
+                      _buildOwnShare(l10n, share),
+                  _buildSectionTitle(l10n.lanSharePeers),
+                  if (state.peers.isEmpty)
+                    _buildHint(l10n.lanSharePeersEmpty)
+                  else
+                    for (final share in state.peers)
+                      _buildPeerShare(l10n, share),
+                ],
+              ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => GetLanSharesRequest().sendSignalToRust(),
+          child: Text(l10n.refresh),
+        ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
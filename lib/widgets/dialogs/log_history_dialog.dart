@@ -0,0 +1,258 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:flutter/services.dart';
+import 'package:intl/intl.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../providers/log_state.dart';
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+const _pageSize = 200;
+
+/// Browses past log entries read from the log files, newest first
+class LogHistoryDialog extends StatefulWidget {
+  const LogHistoryDialog({super.key});
+
+  @override
+  State<LogHistoryDialog> createState() => _LogHistoryDialogState();
+}
+
+class _LogHistoryDialogState extends State<LogHistoryDialog> {
+  final _searchController = TextEditingController();
+  final _moduleController = TextEditingController();
+  final _timestampFormat = DateFormat('yyyy-MM-dd HH:mm:ss.SSS');
+  StreamSubscription<RustSignalPack<QueryLogsResponse>>? _querySub;
+  Timer? _debounceTimer;
+
+  LogLevel _minLevel = LogLevel.info;
+  DateTimeRange? _dateRange;
+  List<LogInfo> _entries = const [];
+  int _total = 0;
+  bool _loading = false;
+  String? _error;
+
+  @override
+  void initState() {
+    super.initState();
+    _querySub = QueryLogsResponse.rustSignalStream.listen((event) {
+      final msg = event.message;
+      if (!mounted || msg.offset != _entries.length) return;
+      setState(() {
+        _loading = false;
+        _error = msg.error;
+        _total = msg.total;
+        _entries = [..._entries, ...msg.entries.map(LogInfo.fromEntry)];
+      });
+    });
+    _query();
+  }
+
+  @override
+  void dispose() {
+    _querySub?.cancel();
+    _debounceTimer?.cancel();
+    _searchController.dispose();
+    _moduleController.dispose();
+    super.dispose();
+  }
+
+  static Uint64 _millis(DateTime time) =>
+      Uint64.fromBigInt(BigInt.from(time.millisecondsSinceEpoch));
+
+  /// Requests the next page, or the first one after clearing the list
+  void _query({bool reset = true}) {
+    final range = _dateRange;
+    setState(() {
+      if (reset) {
+        _entries = const [];
+        _total = 0;
+      }
+      _loading = true;
+      _error = null;
+    });
+    QueryLogsRequest(
+      minLevel: _minLevel,
+      module: _moduleController.text.trim(),
+      search: _searchController.text.trim(),
+      since: range == null ? null : _millis(range.start),
+      until: range == null
+          ? null
+          : _millis(range.end.add(const Duration(days: 1))),
+      offset: _entries.length,
+      limit: _pageSize,
+    ).sendSignalToRust();
+  }
+
+  void _onTextChanged(String _) {
+    _debounceTimer?.cancel();
+    _debounceTimer = Timer(const Duration(milliseconds: 400), _query);
+  }
+
+  Future<void> _pickDateRange() async {
+    final range = await showDateRangePicker(
+      context: context,
+      firstDate: DateTime.now().subtract(const Duration(days: 365)),
+      lastDate: DateTime.now(),
+      initialDateRange: _dateRange,
+    );
+    if (range == null || !mounted) return;
+    _dateRange = range;
+    _query();
+  }
+
+  Widget _buildFilters(AppLocalizations l10n) {
+    final dates = MaterialLocalizations.of(context);
+    final range = _dateRange;
+    return Wrap(
+      spacing: 8,
+      runSpacing: 8,
+      crossAxisAlignment: WrapCrossAlignment.center,
+      children: [
+        SizedBox(
+          width: 260,
+          child: TextField(
+            controller: _searchController,
+            decoration: InputDecoration(
+              hintText: l10n.logsSearchHint,
+              prefixIcon: const Icon(Icons.search),
+              border: const OutlineInputBorder(),
+              isDense: true,
+            ),
+            onChanged: _onTextChanged,
+          ),
+        ),
+        SizedBox(
+          width: 200,
+          child: TextField(
+            controller: _moduleController,
+            decoration: InputDecoration(
+              hintText: l10n.logHistoryModuleHint,
+              border: const OutlineInputBorder(),
+              isDense: true,
+            ),
+            onChanged: _onTextChanged,
+          ),
+        ),
+        DropdownButton<LogLevel>(
+          value: _minLevel,
+          hint: Text(l10n.logHistoryMinLevel),
+          items: [
+            for (final level in LogLevel.values)
+              DropdownMenuItem(
+                value: level,
+                child: Text(level.name.toUpperCase()),
+              ),
+          ],
+          onChanged: (level) {
+            if (level == null) return;
+            _minLevel = level;
+            _query();
+          },
+        ),
+        InputChip(
+          avatar: const Icon(Icons.date_range, size: 16),
+          label: Text(range == null
+              ? l10n.logHistoryAnyDate
+              : '${dates.formatShortDate(range.start)} – '
+                  '${dates.formatShortDate(range.end)}'),
+          onPressed: _pickDateRange,
+          onDeleted: range == null
+              ? null
+              : () {
+                  _dateRange = null;
+                  _query();
+                },
+        ),
+      ],
+    );
+  }
+
+  Widget _buildEntry(LogInfo log) {
+    final theme = Theme.of(context);
+    final location = log.fields?['location'];
+    return ListTile(
+      dense: true,
+      leading: Container(width: 4, color: log.levelColor),
+      minLeadingWidth: 4,
+      title: Text(
+        log.message,
+        maxLines: 3,
+        overflow: TextOverflow.ellipsis,
+        style: const TextStyle(fontFamily: 'monospace'),
+      ),
+      subtitle: Text(
+        '${_timestampFormat.format(log.timestamp)} • ${log.levelString} • '
+        '${log.target}${location == null ? '' : ' • $location'}',
+        style: theme.textTheme.bodySmall,
+      ),
+      onTap: () => Clipboard.setData(ClipboardData(
+        text: '${_timestampFormat.format(log.timestamp)} '
+            '${log.levelString} ${log.target}: ${log.message}',
+      )),
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final error = _error;
+    final hasMore = _entries.length < _total;
+
+    return AlertDialog(
+      title: Text(l10n.logHistory),
+      content: SizedBox(
+        width: 900,
+        height: 600,
+        child: Column(
+          crossAxisAlignment: CrossAxisAlignment.stretch,
+          children: [
+            _buildFilters(l10n),
+            const SizedBox(height: 8),
+            if (error != null)
+              Text(
+                error,
+                style: TextStyle(color: Theme.of(context).colorScheme.error),
+              ),
+            Expanded(
+              child: _entries.isEmpty
+                  ? Center(
+                      child: _loading
+                          ? const CircularProgressIndicator()
+                          : Text(l10n.logHistoryEmpty),
+                    )
+                  : ListView.builder(
+                      itemCount: _entries.length + (hasMore ? 1 : 0),
+                      itemBuilder: (context, index) {
+                        if (index < _entries.length) {
+                          return _buildEntry(_entries[index]);
+                        }
+                        return Center(
+                          child: TextButton(
+                            onPressed:
+                                _loading ? null : () => _query(reset: false),
+                            child: Text(l10n.logHistoryLoadMore),
+                          ),
+                        );
+                      },
+                    ),
+            ),
+            const SizedBox(height: 8),
+            Text(
+              l10n.logHistoryCount(
+                  _entries.length.toString(), _total.toString()),
+              style: Theme.of(context).textTheme.bodySmall,
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,237 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:provider/provider.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../providers/device_state.dart';
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+import '../../utils/sideload_utils.dart';
+import '../../utils/utils.dart';
+
+bool _isApk(String path) => path.toLowerCase().endsWith('.apk');
+bool _isObb(String path) => path.toLowerCase().endsWith('.obb');
+
+/// Lists the files of a release with what the local download already has, and
+/// queues a download of the checked ones
+class ReleaseFilesDialog extends StatefulWidget {
+  final CloudApp app;
+
+  const ReleaseFilesDialog({super.key, required this.app});
+
+  @override
+  State<ReleaseFilesDialog> createState() => _ReleaseFilesDialogState();
+}
+
+class _ReleaseFilesDialogState extends State<ReleaseFilesDialog> {
+  StreamSubscription<RustSignalPack<ReleaseFilesResponse>>? _sub;
+  ReleaseFilesResponse? _response;
+  final Set<String> _selected = {};
+
+  @override
+  void initState() {
+    super.initState();
+    _sub = ReleaseFilesResponse.rustSignalStream.listen((event) {
+      final message = event.message;
+      if (message.appFullName != widget.app.fullName || !mounted) return;
+      setState(() {
+        _response = message;
+        _selected
+          ..clear()
+          ..addAll(message.files.map((f) => f.path));
+      });
+    });
+    ListReleaseFilesRequest(appFullName: widget.app.fullName)
+        .sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _sub?.cancel();
+    super.dispose();
+  }
+
+  List<ReleaseFile> get _files => _response?.files ?? const [];
+
+  int get _selectedSize => _files
+      .where((f) => _selected.contains(f.path))
+      .fold<int>(0, (sum, f) => sum + f.size.toInt());
+
+  /// Size of the checked files the local download does not have yet
+  int get _transferSize => _files
+      .where((f) => _selected.contains(f.path) && !f.downloaded)
+      .fold<int>(0, (sum, f) => sum + f.size.toInt());
+
+  String? _fileDetails(AppLocalizations l10n, ReleaseFile file) {
+    final modified = file.modified;
+    final parts = [
+      if (modified != null)
+        formatDateTime(
+          context,
+          DateTime.fromMillisecondsSinceEpoch(modified.toInt()),
+        ),
+      if (file.downloaded) l10n.releaseFilesDownloaded,
+    ].nonNulls;
+    return parts.isEmpty ? null : parts.join(' • ');
+  }
+
+  void _selectWhere(bool Function(String path) test) {
+    setState(() {
+      _selected
+        ..clear()
+        ..addAll(_files.map((f) => f.path).where(test));
+    });
+  }
+
+  /// Checked files in listing order, `null` if the whole release is checked
+  List<String>? _selection() {
+    if (_selected.length == _files.length) return null;
+    return [
+      for (final file in _files)
+        if (_selected.contains(file.path)) file.path,
+    ];
+  }
+
+  Future<void> _submit({required bool install}) async {
+    final selection = _selection();
+    final app = widget.app;
+    if (install) {
+      final proceed =
+          await SideloadUtils.confirmIfLowSpace(context, _selectedSize);
+      if (!proceed) return;
+    }
+    TaskRequest(
+      task: install
+          ? TaskDownloadInstall(
+              field0: app.fullName,
+              field1: app.truePackageName,
+              field2: selection)
+          : TaskDownload(
+              field0: app.fullName,
+              field1: app.truePackageName,
+              field2: selection),
+      priority: TaskPriority.normal,
+    ).sendSignalToRust();
+    if (mounted) Navigator.of(context).pop();
+  }
+
+  Widget _buildContent(AppLocalizations l10n) {
+    final theme = Theme.of(context);
+    final response = _response;
+    if (response == null) {
+      return Row(
+        children: [
+          const SizedBox(
+            width: 20,
+            height: 20,
+            child: CircularProgressIndicator(strokeWidth: 2),
+          ),
+          const SizedBox(width: 12),
+          Text(l10n.releaseFilesLoading),
+        ],
+      );
+    }
+    if (response.error != null) {
+      return Text(l10n.releaseFilesFailed(response.error!));
+    }
+
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        Text(widget.app.fullName, style: theme.textTheme.bodySmall),
+        const SizedBox(height: 8),
+        Wrap(
+          spacing: 8,
+          children: [
+            ActionChip(
+              label: Text(l10n.releaseFilesAll),
+              onPressed: () => _selectWhere((_) => true),
+            ),
+            ActionChip(
+              label: Text(l10n.releaseFilesApkOnly),
+              onPressed: () => _selectWhere(_isApk),
+            ),
+            ActionChip(
+              label: Text(l10n.releaseFilesObbOnly),
+              onPressed: () => _selectWhere(_isObb),
+            ),
+          ],
+        ),
+        const SizedBox(height: 8),
+        Flexible(
+          child: ListView(
+            shrinkWrap: true,
+            children: [
+              for (final file in _files) _buildFileTile(l10n, file),
+            ],
+          ),
+        ),
+        const SizedBox(height: 8),
+        Text(
+          [
+            l10n.releaseFilesSelected(
+              _selected.length.toString(),
+              formatSize(_selectedSize, 1),
+            ),
+            l10n.releaseFilesToTransfer(formatSize(_transferSize, 1)),
+          ].join(' • '),
+          style: theme.textTheme.bodySmall,
+        ),
+      ],
+    );
+  }
+
+  Widget _buildFileTile(AppLocalizations l10n, ReleaseFile file) {
+    final theme = Theme.of(context);
+    final details = _fileDetails(l10n, file);
+    return CheckboxListTile(
+      dense: true,
+      contentPadding: EdgeInsets.zero,
+      controlAffinity: ListTileControlAffinity.leading,
+      value: _selected.contains(file.path),
+      onChanged: (checked) => setState(() {
+        if (checked ?? false) {
+          _selected.add(file.path);
+        } else {
+          _selected.remove(file.path);
+        }
+      }),
+      title: Text(file.path),
+      subtitle: details == null ? null : Text(details),
+      secondary: Text(
+        formatSize(file.size.toInt(), 1),
+        style: theme.textTheme.bodySmall,
+      ),
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final canSubmit = _response?.error == null && _selected.isNotEmpty;
+    final connected = context.watch<DeviceState>().isConnected;
+    return AlertDialog(
+      title: Text(l10n.releaseFilesTitle),
+      content: SizedBox(width: 560, child: _buildContent(l10n)),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        TextButton(
+          onPressed: canSubmit ? () => _submit(install: false) : null,
+          child: Text(l10n.downloadToComputer),
+        ),
+        FilledButton(
+          onPressed:
+              canSubmit && connected ? () => _submit(install: true) : null,
+          child: Text(connected
+              ? l10n.downloadAndInstall
+              : l10n.downloadAndInstallNotConnected),
+        ),
+      ],
+    );
+  }
+}
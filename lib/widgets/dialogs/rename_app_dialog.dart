@@ -0,0 +1,76 @@
+import 'package:flutter/material.dart';
+import 'package:provider/provider.dart';
+
+import '../../providers/cloud_apps_state.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Sets the name shown for a package in the catalog and the installed apps
+class RenameAppDialog extends StatefulWidget {
+  final String packageName;
+  final String currentName;
+
+  const RenameAppDialog({
+    super.key,
+    required this.packageName,
+    required this.currentName,
+  });
+
+  @override
+  State<RenameAppDialog> createState() => _RenameAppDialogState();
+}
+
+class _RenameAppDialogState extends State<RenameAppDialog> {
+  late final TextEditingController _controller;
+
+  @override
+  void initState() {
+    super.initState();
+    _controller = TextEditingController(text: widget.currentName);
+  }
+
+  @override
+  void dispose() {
+    _controller.dispose();
+    super.dispose();
+  }
+
+  void _save(String? name) {
+    context.read<CloudAppsState>().setNameOverride(widget.packageName, name);
+    Navigator.of(context).pop();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final hasOverride =
+        context.watch<CloudAppsState>().nameOverrideFor(widget.packageName) !=
+            null;
+    return AlertDialog(
+      title: Text(l10n.appRenameTitle(widget.packageName)),
+      content: SizedBox(
+        width: 400,
+        child: TextField(
+          controller: _controller,
+          autofocus: true,
+          decoration: InputDecoration(hintText: l10n.appRenameHint),
+          onSubmitted: (value) => _save(value.trim()),
+        ),
+      ),
+      actions: [
+        if (hasOverride)
+          TextButton(
+            onPressed: () => _save(null),
+            child: Text(l10n.appRenameReset),
+          ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: () => _save(_controller.text.trim()),
+          child: Text(l10n.appRenameSave),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,182 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Named settings profiles: saving the current settings and switching
+class SettingsProfilesDialog extends StatefulWidget {
+  const SettingsProfilesDialog({super.key});
+
+  @override
+  State<SettingsProfilesDialog> createState() => _SettingsProfilesDialogState();
+}
+
+class _SettingsProfilesDialogState extends State<SettingsProfilesDialog> {
+  StreamSubscription<RustSignalPack<SettingsProfilesChanged>>? _profilesSub;
+  final _nameController = TextEditingController();
+  SettingsProfilesChanged? _profiles;
+  bool _busy = true;
+
+  @override
+  void initState() {
+    super.initState();
+    _profilesSub = SettingsProfilesChanged.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      setState(() {
+        _profiles = event.message;
+        _busy = false;
+      });
+    });
+    GetSettingsProfilesRequest().sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _profilesSub?.cancel();
+    _nameController.dispose();
+    super.dispose();
+  }
+
+  void _save() {
+    final name = _nameController.text.trim();
+    if (name.isEmpty) return;
+    setState(() => _busy = true);
+    SaveSettingsProfileRequest(name: name).sendSignalToRust();
+    _nameController.clear();
+  }
+
+  void _switch(String name) {
+    setState(() => _busy = true);
+    SwitchSettingsProfileRequest(name: name).sendSignalToRust();
+  }
+
+  Future<void> _delete(String name) async {
+    final l10n = AppLocalizations.of(context);
+    final confirmed = await showDialog<bool>(
+      context: context,
+      builder: (context) => AlertDialog(
+        title: Text(l10n.settingsProfilesDeleteTitle(name)),
+        actions: [
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(false),
+            child: Text(l10n.commonCancel),
+          ),
+          FilledButton(
+            onPressed: () => Navigator.of(context).pop(true),
+            child: Text(l10n.settingsProfilesDelete),
+          ),
+        ],
+      ),
+    );
+    if (confirmed != true || !mounted) return;
+    setState(() => _busy = true);
+    DeleteSettingsProfileRequest(name: name).sendSignalToRust();
+  }
+
+  Widget _buildProfile(AppLocalizations l10n, String name, bool active) {
+    return ListTile(
+      dense: true,
+      leading: Icon(active ? Icons.check_circle : Icons.circle_outlined),
+      title: Text(name),
+      subtitle: active ? Text(l10n.settingsProfilesActive) : null,
+      trailing: Row(
+        mainAxisSize: MainAxisSize.min,
+        children: [
+          IconButton(
+            icon: const Icon(Icons.save_outlined),
+            tooltip: l10n.settingsProfilesOverwrite,
+            onPressed: _busy
+                ? null
+                : () {
+                    setState(() => _busy = true);
+                    SaveSettingsProfileRequest(name: name).sendSignalToRust();
+                  },
+          ),
+          IconButton(
+            icon: const Icon(Icons.delete_outline),
+            tooltip: l10n.settingsProfilesDelete,
+            onPressed: _busy ? null : () => _delete(name),
+          ),
+        ],
+      ),
+      onTap: _busy || active ? null : () => _switch(name),
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final profiles = _profiles;
+    final error = profiles?.error;
+
+    return AlertDialog(
+      title: Text(l10n.settingsProfiles),
+      content: SizedBox(
+        width: 440,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.stretch,
+          children: [
+            if (error != null)
+              Padding(
+                padding: const EdgeInsets.only(bottom: 8),
+                child: Text(
+                  error,
+                  style: TextStyle(color: Theme.of(context).colorScheme.error),
+                ),
+              ),
+            if (profiles == null)
+              const SizedBox(
+                height: 80,
+                child: Center(child: CircularProgressIndicator()),
+              )
+            else if (profiles.names.isEmpty)
+              Padding(
+                padding: const EdgeInsets.symmetric(vertical: 16),
+                child: Text(l10n.settingsProfilesEmpty),
+              )
+            else
+              Flexible(
+                child: ListView(
+                  shrinkWrap: true,
+                  children: [
+                    for (final name in profiles.names)
+                      _buildProfile(l10n, name, name == profiles.active),
+                  ],
+                ),
+              ),
+            const SizedBox(height: 12),
+            Row(
+              children: [
+                Expanded(
+                  child: TextField(
+                    controller: _nameController,
+                    decoration: InputDecoration(
+                      labelText: l10n.settingsProfilesName,
+                      border: const OutlineInputBorder(),
+                    ),
+                    onSubmitted: (_) => _save(),
+                  ),
+                ),
+                const SizedBox(width: 8),
+                FilledButton(
+                  onPressed: _busy ? null : _save,
+                  child: Text(l10n.settingsProfilesSaveCurrent),
+                ),
+              ],
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
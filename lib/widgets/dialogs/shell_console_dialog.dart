@@ -0,0 +1,266 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+enum _LineKind { command, stdout, stderr, status }
+
+/// Interactive shell session on the connected device
+class ShellConsoleDialog extends StatefulWidget {
+  const ShellConsoleDialog({super.key});
+
+  @override
+  State<ShellConsoleDialog> createState() => _ShellConsoleDialogState();
+}
+
+class _ShellConsoleDialogState extends State<ShellConsoleDialog> {
+  static const _maxLines = 5000;
+
+  final List<StreamSubscription> _subscriptions = [];
+  final List<(_LineKind, String)> _lines = [];
+  final _inputController = TextEditingController();
+  final _inputFocus = FocusNode();
+  final _scrollController = ScrollController();
+
+  String _requestKey = '';
+  int? _sessionId;
+  String? _serial;
+  String? _error;
+  ShellSessionEndReason? _endReason;
+  int _nextCommandId = 1;
+  int? _runningCommandId;
+
+  @override
+  void initState() {
+    super.initState();
+    _subscriptions.addAll([
+      ShellSessionOpened.rustSignalStream.listen((event) {
+        final signal = event.message;
+        if (!mounted || signal.requestKey != _requestKey) return;
+        setState(() {
+          _sessionId = signal.sessionId;
+          _serial = signal.deviceSerial;
+          _error = signal.error;
+        });
+        _inputFocus.requestFocus();
+      }),
+      ShellOutputLine.rustSignalStream.listen((event) {
+        final signal = event.message;
+        if (signal.sessionId != _sessionId) return;
+        _append(
+          signal.stream == ShellOutputStream.stderr
+              ? _LineKind.stderr
+              : _LineKind.stdout,
+          signal.line,
+        );
+      }),
+      ShellCommandFinished.rustSignalStream.listen((event) {
+        final signal = event.message;
+        if (!mounted || signal.sessionId != _sessionId) return;
+        if (signal.exitCode != 0) {
+          _append(
+            _LineKind.status,
+            AppLocalizations.of(context)
+                .shellConsoleExitCode('${signal.exitCode}'),
+          );
+        }
+        setState(() {
+          if (_runningCommandId == signal.commandId) _runningCommandId = null;
+        });
+      }),
+      ShellSessionClosed.rustSignalStream.listen((event) {
+        final signal = event.message;
+        if (!mounted || signal.sessionId != _sessionId) return;
+        setState(() {
+          _endReason = signal.reason;
+          _runningCommandId = null;
+        });
+      }),
+    ]);
+    _open();
+  }
+
+  @override
+  void dispose() {
+    for (final subscription in _subscriptions) {
+      subscription.cancel();
+    }
+    final sessionId = _sessionId;
+    if (sessionId != null && _endReason == null) {
+      CloseShellSessionRequest(sessionId: sessionId).sendSignalToRust();
+    }
+    _inputController.dispose();
+    _inputFocus.dispose();
+    _scrollController.dispose();
+    super.dispose();
+  }
+
+  void _open() {
+    _requestKey = 'shell-${DateTime.now().microsecondsSinceEpoch}';
+    _sessionId = null;
+    _error = null;
+    _endReason = null;
+    OpenShellSessionRequest(requestKey: _requestKey).sendSignalToRust();
+  }
+
+  void _append(_LineKind kind, String line) {
+    if (!mounted) return;
+    final atBottom = !_scrollController.hasClients ||
+        _scrollController.position.extentAfter < 24;
+    setState(() {
+      _lines.add((kind, line));
+      if (_lines.length > _maxLines) {
+        _lines.removeRange(0, _lines.length - _maxLines);
+      }
+    });
+    if (atBottom) {
+      WidgetsBinding.instance.addPostFrameCallback((_) {
+        if (_scrollController.hasClients) {
+          _scrollController
+              .jumpTo(_scrollController.position.maxScrollExtent);
+        }
+      });
+    }
+  }
+
+  void _run(String command) {
+    final sessionId = _sessionId;
+    if (sessionId == null || _endReason != null || command.trim().isEmpty) {
+      return;
+    }
+    final commandId = _nextCommandId++;
+    _append(_LineKind.command, '\$ $command');
+    setState(() => _runningCommandId = commandId);
+    ShellCommandRequest(
+      sessionId: sessionId,
+      commandId: commandId,
+      command: command,
+      timeoutSecs: null,
+    ).sendSignalToRust();
+    _inputController.clear();
+    _inputFocus.requestFocus();
+  }
+
+  String _endReasonText(AppLocalizations l10n, ShellSessionEndReason reason) {
+    return switch (reason) {
+      ShellSessionEndReason.closed => l10n.shellConsoleEndClosed,
+      ShellSessionEndReason.exited => l10n.shellConsoleEndExited,
+      ShellSessionEndReason.commandTimedOut => l10n.shellConsoleEndTimedOut,
+      ShellSessionEndReason.idle => l10n.shellConsoleEndIdle,
+      ShellSessionEndReason.disabled => l10n.shellConsoleEndDisabled,
+    };
+  }
+
+  Widget _buildOutput(BuildContext context) {
+    final theme = Theme.of(context);
+    final base = theme.textTheme.bodySmall?.copyWith(fontFamily: 'monospace');
+    return Container(
+      color: theme.colorScheme.surfaceContainerLowest,
+      padding: const EdgeInsets.all(8),
+      child: ListView.builder(
+        controller: _scrollController,
+        itemCount: _lines.length,
+        itemBuilder: (context, index) {
+          final (kind, line) = _lines[index];
+          return SelectableText(
+            line,
+            style: switch (kind) {
+              _LineKind.command => base?.copyWith(fontWeight: FontWeight.bold),
+              _LineKind.stdout => base,
+              _LineKind.stderr =>
+                base?.copyWith(color: theme.colorScheme.error),
+              _LineKind.status =>
+                base?.copyWith(color: theme.colorScheme.outline),
+            },
+          );
+        },
+      ),
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final endReason = _endReason;
+    final error = _error;
+    final serial = _serial;
+    final ready = _sessionId != null && endReason == null;
+
+    final String? status;
+    if (error != null) {
+      status = error;
+    } else if (endReason != null) {
+      status = _endReasonText(l10n, endReason);
+    } else if (_sessionId == null) {
+      status = l10n.shellConsoleOpening;
+    } else {
+      status = null;
+    }
+
+    return AlertDialog(
+      title: Text(serial == null
+          ? l10n.shellConsole
+          : l10n.shellConsoleTitle(serial)),
+      content: SizedBox(
+        width: 720,
+        height: 480,
+        child: Column(
+          crossAxisAlignment: CrossAxisAlignment.stretch,
+          children: [
+            Expanded(child: _buildOutput(context)),
+            if (status != null)
+              Padding(
+                padding: const EdgeInsets.only(top: 8),
+                child: Text(
+                  status,
+                  style: TextStyle(
+                    color: error != null
+                        ? Theme.of(context).colorScheme.error
+                        : null,
+                  ),
+                ),
+              ),
+            const SizedBox(height: 8),
+            TextField(
+              controller: _inputController,
+              focusNode: _inputFocus,
+              enabled: ready,
+              style: const TextStyle(fontFamily: 'monospace'),
+              decoration: InputDecoration(
+                prefixText: '\$ ',
+                hintText: l10n.shellConsoleHint,
+                suffixIcon: _runningCommandId != null
+                    ? const Padding(
+                        padding: EdgeInsets.all(12),
+                        child: SizedBox.square(
+                          dimension: 16,
+                          child: CircularProgressIndicator(strokeWidth: 2),
+                        ),
+                      )
+                    : null,
+              ),
+              onSubmitted: _run,
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        if (endReason != null || error != null)
+          TextButton(
+            onPressed: () => setState(_open),
+            child: Text(l10n.shellConsoleReopen),
+          ),
+        TextButton(
+          onPressed: () => setState(_lines.clear),
+          child: Text(l10n.shellConsoleClear),
+        ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,119 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Moves the content of a storage location to [newPath], showing progress
+class StorageMigrationDialog extends StatefulWidget {
+  const StorageMigrationDialog({
+    super.key,
+    required this.location,
+    required this.newPath,
+  });
+
+  final StorageLocation location;
+  final String newPath;
+
+  @override
+  State<StorageMigrationDialog> createState() => _StorageMigrationDialogState();
+}
+
+class _StorageMigrationDialogState extends State<StorageMigrationDialog> {
+  StreamSubscription<RustSignalPack<StorageMigrationProgress>>? _progressSub;
+  StreamSubscription<RustSignalPack<StorageMigrationFinished>>? _finishedSub;
+  StorageMigrationProgress? _progress;
+  StorageMigrationFinished? _result;
+
+  @override
+  void initState() {
+    super.initState();
+    _progressSub = StorageMigrationProgress.rustSignalStream.listen((event) {
+      if (!mounted || event.message.location != widget.location) return;
+      setState(() => _progress = event.message);
+    });
+    _finishedSub = StorageMigrationFinished.rustSignalStream.listen((event) {
+      if (!mounted || event.message.location != widget.location) return;
+      setState(() => _result = event.message);
+    });
+    MigrateStorageRequest(location: widget.location, newPath: widget.newPath)
+        .sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _progressSub?.cancel();
+    _finishedSub?.cancel();
+    super.dispose();
+  }
+
+  Widget _buildContent(AppLocalizations l10n) {
+    final result = _result;
+    if (result != null) {
+      final error = result.error;
+      if (error == null) {
+        return Text(l10n.storageMigrationDone(result.path));
+      }
+      return Column(
+        mainAxisSize: MainAxisSize.min,
+        crossAxisAlignment: CrossAxisAlignment.start,
+        children: [
+          Text(
+            error,
+            style: TextStyle(color: Theme.of(context).colorScheme.error),
+          ),
+          const SizedBox(height: 8),
+          Text(result.rolledBack
+              ? l10n.storageMigrationRolledBack(result.path)
+              : l10n.storageMigrationUnchanged(result.path)),
+        ],
+      );
+    }
+
+    final progress = _progress;
+    final total = progress?.total ?? 0;
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.stretch,
+      children: [
+        Text(l10n.storageMigrationMoving(widget.newPath)),
+        const SizedBox(height: 12),
+        LinearProgressIndicator(
+          value: total == 0 ? null : progress!.moved / total,
+        ),
+        if (progress?.current != null) ...[
+          const SizedBox(height: 8),
+          Text(
+            l10n.storageMigrationEntry(
+                '${progress!.moved + 1}', '$total', progress.current!),
+            maxLines: 1,
+            overflow: TextOverflow.ellipsis,
+            style: Theme.of(context).textTheme.bodySmall,
+          ),
+        ],
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final finished = _result != null;
+
+    return PopScope(
+      canPop: finished,
+      child: AlertDialog(
+        title: Text(l10n.storageMigrationTitle),
+        content: SizedBox(width: 440, child: _buildContent(l10n)),
+        actions: [
+          TextButton(
+            onPressed: finished ? () => Navigator.of(context).pop() : null,
+            child: Text(l10n.commonClose),
+          ),
+        ],
+      ),
+    );
+  }
+}
@@ -1,9 +1,13 @@
+import 'dart:async';
+
 import 'package:flutter/material.dart';
 import 'package:provider/provider.dart';
 import '../../src/bindings/bindings.dart';
 import '../../providers/task_state.dart';
 import '../../src/l10n/app_localizations.dart';
+import '../../utils/sideload_utils.dart';
 import '../../utils/utils.dart';
+import 'transfer_stats_dialog.dart';
 
 class TaskListDialog extends StatefulWidget {
   final int initialTabIndex;
@@ -27,7 +31,7 @@ class _TaskListDialogState extends State<TaskListDialog>
   void initState() {
     super.initState();
     _tabController = TabController(
-      length: 2,
+      length: 3,
       initialIndex: widget.initialTabIndex,
       vsync: this,
     );
@@ -50,28 +54,6 @@ class _TaskListDialogState extends State<TaskListDialog>
     _previousTaskState = taskState;
   }
 
-  String _getTaskKindString(TaskKind kind) {
-    final l10n = AppLocalizations.of(context);
-    switch (kind) {
-      case TaskKind.download:
-        return l10n.taskKindDownload;
-      case TaskKind.downloadInstall:
-        return l10n.taskKindDownloadInstall;
-      case TaskKind.installApk:
-        return l10n.taskKindInstallApk;
-      case TaskKind.installLocalApp:
-        return l10n.taskKindInstallLocalApp;
-      case TaskKind.uninstall:
-        return l10n.taskKindUninstall;
-      case TaskKind.backupApp:
-        return l10n.taskKindBackupApp;
-      case TaskKind.restoreBackup:
-        return l10n.taskKindRestoreBackup;
-      case TaskKind.donateApp:
-        return l10n.taskKindDonateApp;
-    }
-  }
-
   String _getStatusString(TaskStatus status) {
     final l10n = AppLocalizations.of(context);
     switch (status) {
@@ -103,6 +85,48 @@ class _TaskListDialogState extends State<TaskListDialog>
     }
   }
 
+  String _getPriorityString(TaskPriority priority) {
+    final l10n = AppLocalizations.of(context);
+    switch (priority) {
+      case TaskPriority.high:
+        return l10n.taskPriorityHigh;
+      case TaskPriority.normal:
+        return l10n.taskPriorityNormal;
+      case TaskPriority.low:
+        return l10n.taskPriorityLow;
+    }
+  }
+
+  IconData _getPriorityIcon(TaskPriority priority) {
+    switch (priority) {
+      case TaskPriority.high:
+        return Icons.keyboard_double_arrow_up;
+      case TaskPriority.normal:
+        return Icons.low_priority;
+      case TaskPriority.low:
+        return Icons.keyboard_double_arrow_down;
+    }
+  }
+
+  Widget _buildPriorityMenu(BuildContext context, TaskInfo task) {
+    final l10n = AppLocalizations.of(context);
+    return PopupMenuButton<TaskPriority>(
+      tooltip: '${l10n.taskPriority}: ${_getPriorityString(task.priority)}',
+      icon: Icon(_getPriorityIcon(task.priority)),
+      style: IconButton.styleFrom(visualDensity: VisualDensity.compact),
+      initialValue: task.priority,
+      onSelected: (priority) =>
+          context.read<TaskState>().setTaskPriority(task.taskId, priority),
+      itemBuilder: (context) => [
+        for (final priority in TaskPriority.values.reversed)
+          PopupMenuItem(
+            value: priority,
+            child: Text(_getPriorityString(priority)),
+          ),
+      ],
+    );
+  }
+
   Widget _buildTab(BuildContext context, String label, int count) {
     return Tab(
       child: Row(
@@ -137,9 +161,150 @@ class _TaskListDialogState extends State<TaskListDialog>
     );
   }
 
+  Widget _buildDownloadSummary(BuildContext context, DownloadSummary summary) {
+    final l10n = AppLocalizations.of(context);
+    final duration = formatSeconds((summary.durationMs.toInt() / 1000).round());
+    final details = [
+      if (summary.source != null) l10n.taskDownloadSource(summary.source!),
+      if (summary.retries > 0)
+        l10n.taskDownloadRetries(summary.retries.toString()),
+    ];
+    final text = Text(
+      l10n.taskDownloadSummary(
+        formatSize(summary.totalBytes.toInt(), 1),
+        duration,
+        formatSize(summary.averageSpeed.toInt(), 1),
+        formatSize(summary.peakSpeed.toInt(), 1),
+      ),
+      overflow: TextOverflow.ellipsis,
+      style: TextStyle(
+        fontSize: 12,
+        color: Theme.of(context).colorScheme.onSurface.withValues(alpha: 0.6),
+      ),
+    );
+    if (details.isEmpty) return text;
+    return Tooltip(
+      message: details.join('\n'),
+      waitDuration: const Duration(milliseconds: 500),
+      child: text,
+    );
+  }
+
+  /// Buttons opening the folders of task outputs
+  Widget _buildArtifactButtons(
+      BuildContext context, List<TaskArtifact> artifacts) {
+    return Row(
+      mainAxisSize: MainAxisSize.min,
+      children: [
+        for (final artifact in artifacts)
+          _buildArtifactButton(context, artifact),
+      ],
+    );
+  }
+
+  Widget _buildArtifactButton(BuildContext context, TaskArtifact artifact) {
+    final l10n = AppLocalizations.of(context);
+    final label = artifact.isDirectory
+        ? l10n.openFolderTooltip
+        : l10n.taskArtifactReveal;
+    return IconButton(
+      visualDensity: VisualDensity.compact,
+      icon: Icon(artifact.isDirectory
+          ? Icons.folder_open
+          : Icons.insert_drive_file_outlined),
+      tooltip: '$label\n${artifact.path}',
+      onPressed: () => SideloadUtils.revealArtifact(context, artifact),
+    );
+  }
+
+  String _getHistoryActionString(HistoryAction action) {
+    final l10n = AppLocalizations.of(context);
+    switch (action) {
+      case HistoryAction.install:
+        return l10n.historyActionInstall;
+      case HistoryAction.uninstall:
+        return l10n.historyActionUninstall;
+      case HistoryAction.backup:
+        return l10n.historyActionBackup;
+      case HistoryAction.restore:
+        return l10n.historyActionRestore;
+      case HistoryAction.download:
+        return l10n.historyActionDownload;
+    }
+  }
+
+  Widget _buildHistoryItem(BuildContext context, HistoryEntry entry) {
+    final status = switch (entry.outcome) {
+      HistoryOutcome.succeeded => TaskStatus.completed,
+      HistoryOutcome.failed => TaskStatus.failed,
+      HistoryOutcome.cancelled => TaskStatus.cancelled,
+    };
+    final finishedAt =
+        DateTime.fromMillisecondsSinceEpoch(entry.timestamp.toInt());
+    final details = [
+      _getHistoryActionString(entry.action),
+      if (entry.versionName != null) entry.versionName!,
+      formatDateTime(context, finishedAt) ?? finishedAt.toString(),
+    ].join(' · ');
+    return ListTile(
+      title: Row(
+        children: [
+          Expanded(
+            child: Text(entry.name, overflow: TextOverflow.ellipsis),
+          ),
+          const SizedBox(width: 8),
+          Text(
+            _getStatusString(status),
+            style: TextStyle(color: _getStatusColor(status), fontSize: 12),
+          ),
+        ],
+      ),
+      subtitle: Tooltip(
+        message: entry.error ?? details,
+        waitDuration: const Duration(milliseconds: 500),
+        child: Text(details, overflow: TextOverflow.ellipsis),
+      ),
+      trailing: entry.artifacts.isEmpty
+          ? null
+          : _buildArtifactButtons(context, entry.artifacts),
+    );
+  }
+
+  /// Transferred bytes, speed, time left and files of a running transfer
+  String _formatTransfer(AppLocalizations l10n, TransferProgress transfer) {
+    final bytes = formatSize(transfer.bytes.toInt(), 1);
+    final total = transfer.totalBytes;
+    return [
+      total == null
+          ? bytes
+          : l10n.taskTransferBytes(bytes, formatSize(total.toInt(), 1)),
+      l10n.taskTransferSpeed(formatSize(transfer.speed.toInt(), 1)),
+      if (transfer.etaSecs != null)
+        l10n.taskTimeLeft(formatSeconds(transfer.etaSecs!.toInt())),
+      if (transfer.filesTotal > 1)
+        l10n.taskTransferFiles(
+            transfer.filesDone.toString(), transfer.filesTotal.toString()),
+    ].join(' · ');
+  }
+
+  /// One line per file being transferred, with its progress and speed
+  String? _formatTransferFiles(TransferProgress transfer) {
+    if (transfer.files.isEmpty) return null;
+    return transfer.files.map((file) {
+      final total = file.totalBytes.toInt();
+      final percent =
+          total > 0 ? (file.bytes.toInt() * 100 / total).floor() : 0;
+      return '${file.name}: $percent% '
+          '(${formatSize(file.speed.toInt(), 1)}/s)';
+    }).join('\n');
+  }
+
   Widget _buildTaskItem(BuildContext context, TaskInfo task) {
     final l10n = AppLocalizations.of(context);
     final taskName = task.taskName ?? l10n.taskUnknown;
+    final transfer = task.isFinished ? null : task.transfer;
+    final transferFiles =
+        transfer == null ? null : _formatTransferFiles(transfer);
 
     return ListTile(
       title: Row(
@@ -158,6 +323,20 @@ class _TaskListDialogState extends State<TaskListDialog>
             ),
           ),
           const SizedBox(width: 8),
+          if (task.restored) ...[
+            Tooltip(
+              message: l10n.taskRestored,
+              child: Icon(
+                Icons.history,
+                size: 16,
+                color: Theme.of(context)
+                    .colorScheme
+                    .onSurface
+                    .withValues(alpha: 0.7),
+              ),
+            ),
+            const SizedBox(width: 8),
+          ],
           if (task.isFinished) ...[
             Text(
               '${task.endTime!.hour}:${task.endTime!.minute.toString().padLeft(2, '0')}',
@@ -196,7 +375,7 @@ class _TaskListDialogState extends State<TaskListDialog>
           Row(
             children: [
               Text(
-                _getTaskKindString(task.kind),
+                formatTaskKind(AppLocalizations.of(context), task.kind),
                 style: TextStyle(
                   fontSize: 12,
                   color: Theme.of(context)
@@ -210,7 +389,7 @@ class _TaskListDialogState extends State<TaskListDialog>
                 child: Align(
                   alignment: Alignment.centerLeft,
                   child: Tooltip(
-                    message: task.message,
+                    message: transferFiles ?? task.message,
                     waitDuration: const Duration(milliseconds: 500),
                     child: InkWell(
                       onTap: () => copyToClipboard(
@@ -219,13 +398,30 @@ class _TaskListDialogState extends State<TaskListDialog>
                         description: task.message,
                       ),
                       child: Text(
-                        task.message,
+                        transfer == null
+                            ? task.message
+                            : '${task.message} '
+                                '${_formatTransfer(l10n, transfer)}',
                         overflow: TextOverflow.ellipsis,
                       ),
                     ),
                   ),
                 ),
               ),
+              if (!task.isFinished && task.etaSecs != null) ...[
+                const SizedBox(width: 8),
+                Text(
+                  AppLocalizations.of(context)
+                      .taskTimeLeft(formatSeconds(task.etaSecs!)),
+                  style: TextStyle(
+                    fontSize: 12,
+                    color: Theme.of(context)
+                        .colorScheme
+                        .onSurface
+                        .withValues(alpha: 0.6),
+                  ),
+                ),
+              ],
               if (!task.isFinished) ...[
                 const SizedBox(width: 8),
                 Text(
@@ -246,13 +442,19 @@ class _TaskListDialogState extends State<TaskListDialog>
               value: task.stepProgress, // null -> indeterminate
               backgroundColor: Colors.grey.withValues(alpha: 0.1),
             ),
+          if (task.isFinished && task.downloadSummary != null)
+            _buildDownloadSummary(context, task.downloadSummary!),
         ],
       ),
       trailing: task.isFinished
-          ? null
+          ? (task.artifacts.isEmpty
+              ? null
+              : _buildArtifactButtons(context, task.artifacts))
           : Row(
               mainAxisSize: MainAxisSize.min,
               children: [
+                if (!_cancellingTaskIds.contains(task.taskId))
+                  _buildPriorityMenu(context, task),
                 if (_cancellingTaskIds.contains(task.taskId))
                   const SizedBox(
                     width: 36,
@@ -316,6 +518,14 @@ class _TaskListDialogState extends State<TaskListDialog>
                       );
                     },
                   ),
+                  IconButton(
+                    icon: const Icon(Icons.query_stats),
+                    tooltip: l10n.transferStatsTitle,
+                    onPressed: () => showDialog(
+                      context: context,
+                      builder: (context) => const TransferStatsDialog(),
+                    ),
+                  ),
                   IconButton(
                     icon: const Icon(Icons.close),
                     onPressed: () => Navigator.of(context).pop(),
@@ -337,6 +547,7 @@ class _TaskListDialogState extends State<TaskListDialog>
                                 context, l10n.tasksTabActive, activeCount),
                             _buildTab(
                                 context, l10n.tasksTabRecent, recentCount),
+                            _buildTab(context, l10n.tasksTabHistory, 0),
                           ],
                         ),
                         Expanded(
@@ -353,6 +564,7 @@ class _TaskListDialogState extends State<TaskListDialog>
                                 emptyMessage: l10n.tasksEmptyRecent,
                                 itemBuilder: _buildTaskItem,
                               ),
+                              _HistoryList(itemBuilder: _buildHistoryItem),
                             ],
                           ),
                         ),
@@ -399,3 +611,61 @@ class _TaskList extends StatelessWidget {
     );
   }
 }
+
+/// Install history kept by the backend, newest first
+class _HistoryList extends StatefulWidget {
+  final Widget Function(BuildContext, HistoryEntry) itemBuilder;
+
+  const _HistoryList({required this.itemBuilder});
+
+  @override
+  State<_HistoryList> createState() => _HistoryListState();
+}
+
+class _HistoryListState extends State<_HistoryList> {
+  StreamSubscription<RustSignalPack<InstallHistoryResponse>>? _sub;
+  InstallHistoryResponse? _response;
+
+  @override
+  void initState() {
+    super.initState();
+    _sub = InstallHistoryResponse.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      setState(() => _response = event.message);
+    });
+    GetInstallHistoryRequest(packageName: null).sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _sub?.cancel();
+    super.dispose();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final response = _response;
+    if (response == null) {
+      return const Center(child: CircularProgressIndicator());
+    }
+    final l10n = AppLocalizations.of(context);
+    if (response.error != null || response.entries.isEmpty) {
+      return Center(
+        child: Text(
+          response.error != null
+              ? l10n.historyLoadFailed(response.error!)
+              : l10n.tasksEmptyHistory,
+          style: TextStyle(
+            color:
+                Theme.of(context).colorScheme.onSurface.withValues(alpha: 0.5),
+          ),
+        ),
+      );
+    }
+    return ListView.builder(
+      itemCount: response.entries.length,
+      itemBuilder: (context, index) =>
+          widget.itemBuilder(context, response.entries[index]),
+    );
+  }
+}
@@ -0,0 +1,134 @@
+import 'package:flutter/material.dart';
+import 'package:provider/provider.dart';
+
+import '../../providers/adb_state.dart';
+import '../../providers/device_state.dart';
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Picks another headset to transfer [app] with its data and OBB files to
+class TransferAppDataDialog extends StatefulWidget {
+  const TransferAppDataDialog({super.key, required this.app});
+
+  final InstalledPackage app;
+
+  @override
+  State<TransferAppDataDialog> createState() => _TransferAppDataDialogState();
+}
+
+class _TransferAppDataDialogState extends State<TransferAppDataDialog> {
+  AdbDeviceBrief? _target;
+
+  void _transfer() {
+    final target = _target;
+    if (target == null) return;
+    final app = widget.app;
+    TaskRequest(
+      task: TaskTransferAppData(
+        packageName: app.packageName,
+        displayName: app.label.isEmpty ? null : app.label,
+        targetSerial: target.trueSerial ?? target.serial,
+        targetName: target.name,
+      ),
+      priority: TaskPriority.normal,
+    ).sendSignalToRust();
+    Navigator.of(context).pop();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final current = context.watch<DeviceState>().device;
+    final targets = context
+        .watch<AdbStateProvider>()
+        .availableDevices
+        .where((d) =>
+            d.serial != current?.serial &&
+            (d.trueSerial == null || d.trueSerial != current?.trueSerial))
+        .toList();
+
+    return AlertDialog(
+      title: Text(l10n.transferAppDataTitle(widget.app.label)),
+      content: SizedBox(
+        width: 440,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.stretch,
+          children: [
+            Text(l10n.transferAppDataDescription),
+            const SizedBox(height: 12),
+            if (targets.isEmpty)
+              Text(
+                l10n.transferAppDataNoTargets,
+                style: Theme.of(context).textTheme.bodySmall,
+              )
+            else
+              RadioGroup<String>(
+                groupValue: _target?.serial,
+                onChanged: (serial) => setState(() => _target =
+                    targets.where((d) => d.serial == serial).firstOrNull),
+                child: Column(
+                  children: [
+                    for (final device in targets)
+                      RadioListTile<String>(
+                        dense: true,
+                        value: device.serial,
+                        title: Text(device.name ?? device.serial),
+                        subtitle: Text(device.trueSerial ?? device.serial),
+                      ),
+                  ],
+                ),
+              ),
+            const SizedBox(height: 12),
+            Text(
+              l10n.transferAppDataConflictWarning,
+              style: TextStyle(color: Theme.of(context).colorScheme.error),
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: _target == null ? null : _transfer,
+          child: Text(l10n.transferAppData),
+        ),
+      ],
+    );
+  }
+}
+
+/// Asks whether a transfer may replace the app data already on the target
+Future<void> showTransferOverwriteDialog(
+    BuildContext context, TransferOverwriteReview review) async {
+  final l10n = AppLocalizations.of(context);
+  final prompt = l10n.transferOverwritePrompt(
+    review.appName,
+    review.targetName,
+  );
+  final approved = await showDialog<bool>(
+    context: context,
+    barrierDismissible: false,
+    builder: (context) => AlertDialog(
+      title: Text(l10n.transferOverwriteTitle(review.targetName)),
+      content: Text([prompt, ...review.warnings].join('\n\n')),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(false),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: () => Navigator.of(context).pop(true),
+          child: Text(l10n.transferOverwriteReplace),
+        ),
+      ],
+    ),
+  );
+  TransferOverwriteDecision(
+    taskId: review.taskId,
+    approved: approved ?? false,
+  ).sendSignalToRust();
+}
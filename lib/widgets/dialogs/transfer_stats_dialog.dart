@@ -0,0 +1,136 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
+
+/// Lifetime and session totals of downloads, pushes and installs
+class TransferStatsDialog extends StatefulWidget {
+  const TransferStatsDialog({super.key});
+
+  @override
+  State<TransferStatsDialog> createState() => _TransferStatsDialogState();
+}
+
+class _TransferStatsDialogState extends State<TransferStatsDialog> {
+  StreamSubscription<RustSignalPack<TransferStatsUpdate>>? _statsSub;
+  TransferStatsUpdate? _stats;
+
+  @override
+  void initState() {
+    super.initState();
+    _statsSub = TransferStatsUpdate.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      setState(() => _stats = event.message);
+    });
+    GetTransferStatsRequest().sendSignalToRust();
+  }
+
+  @override
+  void dispose() {
+    _statsSub?.cancel();
+    super.dispose();
+  }
+
+  Widget _buildTotals(
+    AppLocalizations l10n,
+    String title,
+    TransferTotals totals,
+  ) {
+    final theme = Theme.of(context);
+    String transferred(Uint64 bytes, Uint64 speed) =>
+        l10n.transferStatsAverageSpeed(
+          formatSize(bytes.toInt(), 1),
+          formatSize(speed.toInt(), 1),
+        );
+
+    Widget row(String label, String value) {
+      return Padding(
+        padding: const EdgeInsets.only(bottom: 4),
+        child: Row(
+          children: [
+            Expanded(child: Text(label)),
+            Text(value, style: theme.textTheme.bodyMedium),
+          ],
+        ),
+      );
+    }
+
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        Text(title, style: theme.textTheme.titleSmall),
+        const SizedBox(height: 8),
+        row(
+          l10n.transferStatsDownloaded,
+          transferred(totals.bytesDownloaded, totals.averageDownloadSpeed),
+        ),
+        row(l10n.transferStatsDownloads, '${totals.downloadsCompleted}'),
+        row(
+          l10n.transferStatsPushed,
+          transferred(totals.bytesPushed, totals.averagePushSpeed),
+        ),
+        row(l10n.transferStatsInstalls, '${totals.installsCompleted}'),
+      ],
+    );
+  }
+
+  Widget _buildContent(AppLocalizations l10n) {
+    final stats = _stats;
+    if (stats == null) {
+      return const Center(
+        child: SizedBox(
+          width: 20,
+          height: 20,
+          child: CircularProgressIndicator(strokeWidth: 2),
+        ),
+      );
+    }
+
+    final since = DateTime.fromMillisecondsSinceEpoch(
+      stats.lifetimeSince.toInt(),
+    );
+    final sinceText = formatDateTime(context, since) ?? since.toString();
+    return Column(
+      mainAxisSize: MainAxisSize.min,
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        _buildTotals(l10n, l10n.transferStatsSession, stats.session),
+        const Divider(height: 24),
+        _buildTotals(
+          l10n,
+          l10n.transferStatsLifetime(sinceText),
+          stats.lifetime,
+        ),
+      ],
+    );
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    return AlertDialog(
+      title: Text(l10n.transferStatsTitle),
+      content: SizedBox(
+        width: 420,
+        child: SingleChildScrollView(child: _buildContent(l10n)),
+      ),
+      actions: [
+        TextButton(
+          onPressed: _stats == null
+              ? null
+              : () => ResetTransferStatsRequest().sendSignalToRust(),
+          child: Text(l10n.transferStatsReset),
+        ),
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
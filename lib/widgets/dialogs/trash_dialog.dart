@@ -0,0 +1,196 @@
+import 'dart:async';
+
+import 'package:flutter/material.dart';
+import 'package:rinf/rinf.dart';
+
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
+
+/// Apps backed up before they were uninstalled, with buttons to reinstall or
+/// delete each one
+class TrashDialog extends StatefulWidget {
+  const TrashDialog({super.key});
+
+  @override
+  State<TrashDialog> createState() => _TrashDialogState();
+}
+
+class _TrashDialogState extends State<TrashDialog> {
+  StreamSubscription<RustSignalPack<TrashListResponse>>? _listSub;
+  StreamSubscription<RustSignalPack<AppTrashed>>? _trashedSub;
+  TrashListResponse? _response;
+  String? _deleting;
+
+  @override
+  void initState() {
+    super.initState();
+    _listSub = TrashListResponse.rustSignalStream.listen((event) {
+      if (!mounted) return;
+      setState(() {
+        _response = event.message;
+        _deleting = null;
+      });
+    });
+    _trashedSub = AppTrashed.rustSignalStream.listen((_) => _refresh());
+    _refresh();
+  }
+
+  @override
+  void dispose() {
+    _listSub?.cancel();
+    _trashedSub?.cancel();
+    super.dispose();
+  }
+
+  void _refresh() {
+    ListTrashRequest().sendSignalToRust();
+  }
+
+  String _name(TrashedApp app) => app.displayName ?? app.packageName;
+
+  String _details(BuildContext context, TrashedApp app, int retentionDays) {
+    final l10n = AppLocalizations.of(context);
+    final trashedAt =
+        DateTime.fromMillisecondsSinceEpoch(app.trashedAt.toInt());
+    String format(DateTime date) =>
+        formatDateTime(context, date) ?? date.toString();
+    return [
+      l10n.trashTrashedAt(format(trashedAt)),
+      if (retentionDays > 0)
+        l10n.trashExpires(
+            format(trashedAt.add(Duration(days: retentionDays)))),
+    ].join(', ');
+  }
+
+  void _reinstall(TrashedApp app) {
+    TaskRequest(
+      task: TaskReinstallFromTrash(
+        path: app.path,
+        packageName: app.packageName,
+        displayName: app.displayName,
+      ),
+      priority: TaskPriority.normal,
+    ).sendSignalToRust();
+    Navigator.of(context).pop();
+  }
+
+  Future<void> _delete(TrashedApp app) async {
+    final l10n = AppLocalizations.of(context);
+    final confirmed = await showDialog<bool>(
+      context: context,
+      builder: (context) => AlertDialog(
+        title: Text(l10n.trashDeleteTitle(_name(app))),
+        content: Text(l10n.trashDeletePrompt),
+        actions: [
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(false),
+            child: Text(l10n.commonCancel),
+          ),
+          FilledButton(
+            onPressed: () => Navigator.of(context).pop(true),
+            child: Text(l10n.trashDelete),
+          ),
+        ],
+      ),
+    );
+    if (confirmed != true || !mounted) return;
+    setState(() => _deleting = app.path);
+    DeleteTrashedAppRequest(path: app.path).sendSignalToRust();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final response = _response;
+
+    return AlertDialog(
+      title: Text(l10n.trashTitle),
+      content: SizedBox(
+        width: 480,
+        child: response == null
+            ? const SizedBox(
+                height: 120,
+                child: Center(child: CircularProgressIndicator()),
+              )
+            : Column(
+                mainAxisSize: MainAxisSize.min,
+                crossAxisAlignment: CrossAxisAlignment.stretch,
+                children: [
+                  if (response.error != null)
+                    Padding(
+                      padding: const EdgeInsets.only(bottom: 8),
+                      child: Text(
+                        response.error!,
+                        style: TextStyle(
+                            color: Theme.of(context).colorScheme.error),
+                      ),
+                    ),
+                  if (response.apps.isEmpty)
+                    SizedBox(
+                      height: 80,
+                      child: Center(child: Text(l10n.trashEmpty)),
+                    )
+                  else
+                    Flexible(
+                      child: ListView(
+                        shrinkWrap: true,
+                        children: [
+                          for (final app in response.apps)
+                            ListTile(
+                              dense: true,
+                              title: Text(
+                                _name(app),
+                                overflow: TextOverflow.ellipsis,
+                              ),
+                              subtitle: Text(
+                                _details(
+                                    context, app, response.retentionDays),
+                                maxLines: 1,
+                                overflow: TextOverflow.ellipsis,
+                              ),
+                              trailing: _deleting == app.path
+                                  ? const Padding(
+                                      padding: EdgeInsets.all(12),
+                                      child: SizedBox.square(
+                                        dimension: 16,
+                                        child: CircularProgressIndicator(
+                                            strokeWidth: 2),
+                                      ),
+                                    )
+                                  : Row(
+                                      mainAxisSize: MainAxisSize.min,
+                                      children: [
+                                        IconButton(
+                                          icon: const Icon(Icons.restore),
+                                          tooltip: l10n.trashReinstall,
+                                          onPressed: _deleting == null
+                                              ? () => _reinstall(app)
+                                              : null,
+                                        ),
+                                        IconButton(
+                                          icon: const Icon(
+                                              Icons.delete_outline),
+                                          tooltip: l10n.trashDelete,
+                                          onPressed: _deleting == null
+                                              ? () => _delete(app)
+                                              : null,
+                                        ),
+                                      ],
+                                    ),
+                            ),
+                        ],
+                      ),
+                    ),
+                ],
+              ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonClose),
+        ),
+      ],
+    );
+  }
+}
@@ -0,0 +1,101 @@
+import 'package:flutter/material.dart';
+import '../../src/bindings/bindings.dart';
+import '../../src/l10n/app_localizations.dart';
+
+/// Starts updates of all [apps], optionally trying the smallest one first
+class UpdateAllDialog extends StatefulWidget {
+  final List<UpdateAllApp> apps;
+  const UpdateAllDialog({super.key, required this.apps});
+
+  @override
+  State<UpdateAllDialog> createState() => _UpdateAllDialogState();
+}
+
+class _UpdateAllDialogState extends State<UpdateAllDialog> {
+  bool _canary = true;
+
+  void _start() {
+    UpdateAllRequest(apps: widget.apps, canary: _canary).sendSignalToRust();
+    Navigator.of(context).pop();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    return AlertDialog(
+      title: Text(l10n.updateAllTitle(widget.apps.length.toString())),
+      content: SizedBox(
+        width: 420,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.start,
+          children: [
+            Flexible(
+              child: ListView(
+                shrinkWrap: true,
+                children: [
+                  for (final app in widget.apps)
+                    Text(app.fullName, overflow: TextOverflow.ellipsis),
+                ],
+              ),
+            ),
+            const SizedBox(height: 8),
+            CheckboxListTile(
+              value: _canary,
+              onChanged: (v) => setState(() => _canary = v ?? true),
+              title: Text(l10n.updateAllCanary),
+              subtitle: Text(l10n.updateAllCanaryDescription),
+              dense: true,
+              contentPadding: EdgeInsets.zero,
+              controlAffinity: ListTileControlAffinity.leading,
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: _start,
+          child: Text(l10n.updateAll),
+        ),
+      ],
+    );
+  }
+}
+
+/// Asks whether to go on with a batch after its canary app was updated
+Future<void> showUpdateAllCanaryDialog(
+    BuildContext context, UpdateAllCanaryResult result) async {
+  final l10n = AppLocalizations.of(context);
+  final error = result.error;
+  final proceed = await showDialog<bool>(
+    context: context,
+    barrierDismissible: false,
+    builder: (context) => AlertDialog(
+      title: Text(error == null
+          ? l10n.updateAllCanarySucceeded(result.appName)
+          : l10n.updateAllCanaryFailed(result.appName)),
+      content: Text(error == null
+          ? l10n.updateAllContinuePrompt(result.remaining.toString())
+          : '$error\n\n'
+              '${l10n.updateAllContinuePrompt(result.remaining.toString())}'),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(false),
+          child: Text(l10n.updateAllStop),
+        ),
+        FilledButton(
+          onPressed: () => Navigator.of(context).pop(true),
+          child: Text(l10n.updateAllContinue),
+        ),
+      ],
+    ),
+  );
+  ContinueUpdateAllRequest(
+    batchId: result.batchId,
+    proceed: proceed ?? false,
+  ).sendSignalToRust();
+}
@@ -1,8 +1,10 @@
 import 'package:flutter/material.dart';
 import 'package:package_info_plus/package_info_plus.dart';
 import 'package:provider/provider.dart';
+import 'package:url_launcher/url_launcher.dart';
 
 import '../../providers/app_state.dart';
+import '../../providers/update_state.dart';
 import '../../src/l10n/app_localizations.dart';
 import '../../utils/utils.dart';
 
@@ -131,6 +133,8 @@ class _AboutScreenState extends State<AboutScreen> {
           ] else ...[
             const Text('Core: loading…'),
           ],
+          const SizedBox(height: 16),
+          _buildUpdatesSection(context, l10n),
           const SizedBox(height: 8),
           // Text(
           //   'Tip: click the commit to copy the full SHA.',
@@ -142,4 +146,101 @@ class _AboutScreenState extends State<AboutScreen> {
       ),
     );
   }
+
+  Widget _buildUpdatesSection(BuildContext context, AppLocalizations l10n) {
+    final updateState = context.watch<UpdateState>();
+    final update = updateState.availableUpdate;
+    final theme = Theme.of(context);
+
+    return Column(
+      crossAxisAlignment: CrossAxisAlignment.start,
+      children: [
+        Text(l10n.appUpdatesTitle, style: theme.textTheme.titleMedium),
+        const SizedBox(height: 8),
+        Row(
+          children: [
+            OutlinedButton.icon(
+              onPressed:
+                  updateState.checking ? null : updateState.checkForUpdate,
+              icon: updateState.checking
+                  ? const SizedBox(
+                      width: 16,
+                      height: 16,
+                      child: CircularProgressIndicator(strokeWidth: 2),
+                    )
+                  : const Icon(Icons.refresh),
+              label: Text(l10n.checkForAppUpdates),
+            ),
+            const SizedBox(width: 12),
+            if (updateState.checkError != null)
+              Expanded(
+                child: Text(
+                  l10n.appUpdateCheckFailed(updateState.checkError!),
+                  style: TextStyle(color: theme.colorScheme.error),
+                ),
+              )
+            else if (update == null && updateState.checked)
+              Text(l10n.appUpToDate),
+          ],
+        ),
+        if (update != null) ...[
+          const SizedBox(height: 12),
+          Text(
+            l10n.appUpdateNewVersion(update.version),
+            style: theme.textTheme.titleSmall,
+          ),
+          if (update.changelog.isNotEmpty) ...[
+            const SizedBox(height: 4),
+            ConstrainedBox(
+              constraints: const BoxConstraints(maxHeight: 200),
+              child: SingleChildScrollView(
+                child: SelectableText(update.changelog),
+              ),
+            ),
+          ],
+          const SizedBox(height: 8),
+          Row(
+            children: [
+              FilledButton.icon(
+                onPressed: update.downloadUrl == null || updateState.downloading
+                    ? null
+                    : updateState.downloadUpdate,
+                icon: const Icon(Icons.download),
+                label: Text(l10n.appUpdateDownload),
+              ),
+              const SizedBox(width: 8),
+              TextButton.icon(
+                onPressed: () => launchUrl(
+                  Uri.parse(update.releaseUrl),
+                  mode: LaunchMode.externalApplication,
+                ),
+                icon: const Icon(Icons.open_in_new),
+                label: Text(l10n.appUpdateReleasePage),
+              ),
+            ],
+          ),
+          if (update.downloadUrl == null) ...[
+            const SizedBox(height: 4),
+            Text(l10n.appUpdateNoBuild),
+          ],
+          if (updateState.downloading) ...[
+            const SizedBox(height: 8),
+            LinearProgressIndicator(value: updateState.downloadProgress),
+          ],
+          if (updateState.downloadedPath != null) ...[
+            const SizedBox(height: 8),
+            SelectableText(
+                l10n.appUpdateDownloaded(updateState.downloadedPath!)),
+          ],
+          if (updateState.downloadError != null) ...[
+            const SizedBox(height: 8),
+            Text(
+              l10n.appUpdateDownloadFailed(updateState.downloadError!),
+              style: TextStyle(color: theme.colorScheme.error),
+            ),
+          ],
+        ],
+      ],
+    );
+  }
 }
@@ -1,3 +1,4 @@
+import 'dart:async';
 import 'dart:io';
 
 import 'package:flutter/material.dart';
@@ -5,6 +6,7 @@ import 'package:flutter/material.dart';
 import '../../src/bindings/bindings.dart';
 import 'package:proper_filesize/proper_filesize.dart' as filesize;
 import 'package:provider/provider.dart';
+import 'package:rinf/rinf.dart';
 import '../../utils/sideload_utils.dart';
 import '../../src/l10n/app_localizations.dart';
 import '../../providers/device_state.dart';
@@ -23,8 +25,20 @@ class BackupsScreen extends StatefulWidget {
 
 class _BackupsScreenState extends State<BackupsScreen> {
   List<BackupEntry> _entries = const [];
+  int _totalCount = 0;
+  int _totalSize = 0;
   bool _loading = false;
   String? _error;
+  final Set<String> _verifying = {};
+  String _search = '';
+  Timer? _searchDebounceTimer;
+  final _searchController = TextEditingController();
+  BackupsSortOrder _sort = BackupsSortOrder.date;
+  bool _sortAscending = false;
+  bool _requireApk = false;
+  bool _requireData = false;
+  bool _requireObb = false;
+  DateTimeRange? _dateRange;
 
   @override
   void initState() {
@@ -35,12 +49,171 @@ class _BackupsScreenState extends State<BackupsScreen> {
     });
   }
 
+  @override
+  void dispose() {
+    _searchDebounceTimer?.cancel();
+    _searchController.dispose();
+    super.dispose();
+  }
+
   Future<void> _refresh() async {
     await _loadBackups();
   }
 
+  bool get _hasFilters =>
+      _requireApk || _requireData || _requireObb || _dateRange != null;
+
+  void _onSearchChanged(String value) {
+    _searchDebounceTimer?.cancel();
+    _searchDebounceTimer = Timer(const Duration(milliseconds: 300), () {
+      if (!mounted || value == _search) return;
+      _search = value;
+      _loadBackups();
+    });
+  }
+
+  Widget _buildSearchField(AppLocalizations l10n) {
+    return ConstrainedBox(
+      constraints: const BoxConstraints(maxWidth: 300),
+      child: SizedBox(
+        height: 40,
+        child: TextField(
+          controller: _searchController,
+          decoration: InputDecoration(
+            hintText: l10n.searchBackupsHint,
+            contentPadding:
+                const EdgeInsets.symmetric(horizontal: 8, vertical: 8),
+            border: const OutlineInputBorder(),
+            suffixIcon: _searchController.text.isNotEmpty
+                ? IconButton(
+                    icon: const Icon(Icons.close),
+                    tooltip: l10n.clearSearch,
+                    onPressed: () {
+                      _searchController.clear();
+                      _onSearchChanged('');
+                    },
+                  )
+                : null,
+          ),
+          onChanged: (value) {
+            setState(() {});
+            _onSearchChanged(value);
+          },
+        ),
+      ),
+    );
+  }
+
+  Widget _buildFilterButton(AppLocalizations l10n) {
+    return PopupMenuButton<int>(
+      tooltip: l10n.backupsFilter,
+      icon: Icon(_hasFilters ? Icons.filter_alt : Icons.filter_alt_outlined),
+      itemBuilder: (context) => [
+        CheckedPopupMenuItem(
+          value: 0,
+          checked: _requireApk,
+          child: Text(l10n.backupsFilterApk),
+        ),
+        CheckedPopupMenuItem(
+          value: 1,
+          checked: _requireData,
+          child: Text(l10n.backupsFilterData),
+        ),
+        CheckedPopupMenuItem(
+          value: 2,
+          checked: _requireObb,
+          child: Text(l10n.backupsFilterObb),
+        ),
+        const PopupMenuDivider(),
+        PopupMenuItem(
+          value: 3,
+          child: Text(_dateRange == null
+              ? l10n.backupsDateRange
+              : _formatDateRange(context, _dateRange!)),
+        ),
+        if (_dateRange != null)
+          PopupMenuItem(value: 4, child: Text(l10n.backupsClearDateRange)),
+      ],
+      onSelected: (value) async {
+        switch (value) {
+          case 0:
+            setState(() => _requireApk = !_requireApk);
+          case 1:
+            setState(() => _requireData = !_requireData);
+          case 2:
+            setState(() => _requireObb = !_requireObb);
+          case 3:
+            final range = await showDateRangePicker(
+              context: context,
+              firstDate: DateTime(2020),
+              lastDate: DateTime.now(),
+              initialDateRange: _dateRange,
+            );
+            if (range == null || !mounted) return;
+            setState(() => _dateRange = range);
+          case 4:
+            setState(() => _dateRange = null);
+        }
+        _loadBackups();
+      },
+    );
+  }
+
+  static String _formatDateRange(BuildContext context, DateTimeRange range) {
+    final dates = MaterialLocalizations.of(context);
+    return '${dates.formatShortDate(range.start)} – '
+        '${dates.formatShortDate(range.end)}';
+  }
+
+  Widget _buildSortButton(AppLocalizations l10n) {
+    PopupMenuItem<(BackupsSortOrder, bool)> buildItem(
+      BackupsSortOrder sort,
+      bool ascending,
+      String label,
+    ) {
+      final selected = _sort == sort && _sortAscending == ascending;
+      return PopupMenuItem(
+        value: (sort, ascending),
+        child: Row(
+          children: [
+            Icon(selected
+                ? Icons.radio_button_checked
+                : Icons.radio_button_unchecked),
+            const SizedBox(width: 8),
+            Text(label),
+          ],
+        ),
+      );
+    }
+
+    return PopupMenuButton<(BackupsSortOrder, bool)>(
+      tooltip: l10n.sortBy,
+      icon: const Icon(Icons.sort),
+      initialValue: (_sort, _sortAscending),
+      itemBuilder: (context) => [
+        PopupMenuItem(
+          enabled: false,
+          child: Text(l10n.sortBy),
+        ),
+        buildItem(BackupsSortOrder.date, true, l10n.sortDateOldest),
+        buildItem(BackupsSortOrder.date, false, l10n.sortDateNewest),
+        buildItem(BackupsSortOrder.size, true, l10n.sortSizeSmallest),
+        buildItem(BackupsSortOrder.size, false, l10n.sortSizeLargest),
+      ],
+      onSelected: (value) {
+        final (sort, ascending) = value;
+        setState(() {
+          _sort = sort;
+          _sortAscending = ascending;
+        });
+        _loadBackups();
+      },
+    );
+  }
+
   @override
   Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
     return Scaffold(
       body: SafeArea(
         child: Column(
@@ -54,6 +227,15 @@ class _BackupsScreenState extends State<BackupsScreen> {
                     style: Theme.of(context).textTheme.titleLarge,
                   ),
                   const Spacer(),
+                  _buildSearchField(l10n),
+                  const SizedBox(width: 8),
+                  _buildFilterButton(l10n),
+                  _buildSortButton(l10n),
+                  IconButton(
+                    tooltip: AppLocalizations.of(context).backupsCleanUp,
+                    onPressed: _previewPrune,
+                    icon: const Icon(Icons.cleaning_services_outlined),
+                  ),
                   IconButton(
                     tooltip: AppLocalizations.of(context).openBackupsFolder,
                     onPressed: _openBackupsRoot,
@@ -67,6 +249,21 @@ class _BackupsScreenState extends State<BackupsScreen> {
                 ],
               ),
             ),
+            if (!_loading && _error == null && _totalCount > 0)
+              Padding(
+                padding: const EdgeInsets.fromLTRB(16, 0, 16, 8),
+                child: Align(
+                  alignment: Alignment.centerLeft,
+                  child: Text(
+                    l10n.backupsSummary(
+                      _entries.length.toString(),
+                      _totalCount.toString(),
+                      formatSize(_totalSize, 1),
+                    ),
+                    style: Theme.of(context).textTheme.bodySmall,
+                  ),
+                ),
+              ),
             Expanded(
               child: _loading
                   ? const Center(child: CircularProgressIndicator())
@@ -74,17 +271,25 @@ class _BackupsScreenState extends State<BackupsScreen> {
                       ? Center(child: Text(_error!))
                       : _entries.isEmpty
                           ? Center(
-                              child: Text(
-                                  AppLocalizations.of(context).noBackupsFound))
+                              child: Text(_totalCount == 0
+                                  ? l10n.noBackupsFound
+                                  : l10n.noMatchingBackups))
                           : ListView.builder(
                               padding: _listPadding,
                               itemCount: _entries.length,
                               itemBuilder: (context, index) => _BackupTile(
                                 entry: _entries[index],
+                                verifying:
+                                    _verifying.contains(_entries[index].path),
+                                onVerify: () => _verify(_entries[index]),
                                 onRestore: () => SideloadUtils.restoreBackup(
                                     _entries[index].path),
-                                onOpenFolder: () =>
-                                    _openFolder(_entries[index].path),
+                                onRestoreTo: () =>
+                                    _restoreToPackage(_entries[index]),
+                                onOpenFolder: () => _openFolder(
+                                    _entries[index].compressed
+                                        ? File(_entries[index].path).parent.path
+                                        : _entries[index].path),
                                 onDelete: () =>
                                     _confirmAndDelete(_entries[index]),
                               ),
@@ -101,16 +306,52 @@ class _BackupsScreenState extends State<BackupsScreen> {
       _loading = true;
       _error = null;
     });
-    GetBackupsResponse.rustSignalStream.take(1).listen((event) {
+    final search = _search;
+    GetBackupsResponse.rustSignalStream
+        .where((event) => event.message.search == search)
+        .take(1)
+        .listen((event) {
       final msg = event.message;
-      if (!mounted) return;
+      if (!mounted || search != _search) return;
       setState(() {
         _loading = false;
         _error = msg.error;
         _entries = msg.entries;
+        _totalCount = msg.totalCount;
+        _totalSize = msg.totalSize.toInt();
       });
     });
-    GetBackupsRequest().sendSignalToRust();
+    final range = _dateRange;
+    GetBackupsRequest(
+      query: BackupsQuery(
+        search: search,
+        since: range == null ? null : _millis(range.start),
+        until: range == null
+            ? null
+            : _millis(range.end.add(const Duration(days: 1))),
+        requireApk: _requireApk,
+        requireData: _requireData,
+        requireObb: _requireObb,
+        sort: _sort,
+        ascending: _sortAscending,
+      ),
+    ).sendSignalToRust();
+  }
+
+  static Uint64 _millis(DateTime time) =>
+      Uint64.fromBigInt(BigInt.from(time.millisecondsSinceEpoch));
+
+  void _verify(BackupEntry entry) {
+    setState(() => _verifying.add(entry.path));
+    BackupVerification.rustSignalStream
+        .where((event) => event.message.path == entry.path)
+        .take(1)
+        .listen((event) {
+      if (!mounted) return;
+      setState(() => _verifying.remove(entry.path));
+      _showVerification(entry, event.message);
+    });
+    VerifyBackupRequest(path: entry.path).sendSignalToRust();
   }
 
   Future<void> _openFolder(String folderPath) async {
@@ -144,13 +385,19 @@ class _BackupsScreenState extends State<BackupsScreen> {
 
 class _BackupTile extends StatelessWidget {
   final BackupEntry entry;
+  final bool verifying;
+  final VoidCallback onVerify;
   final VoidCallback onRestore;
+  final VoidCallback onRestoreTo;
   final VoidCallback onOpenFolder;
   final VoidCallback onDelete;
 
   const _BackupTile({
     required this.entry,
+    required this.verifying,
+    required this.onVerify,
     required this.onRestore,
+    required this.onRestoreTo,
     required this.onOpenFolder,
     required this.onDelete,
   });
@@ -178,6 +425,20 @@ class _BackupTile extends StatelessWidget {
                 onPressed: onDelete,
               ),
               const SizedBox(width: 8),
+              verifying
+                  ? const Padding(
+                      padding: EdgeInsets.all(12),
+                      child: SizedBox.square(
+                        dimension: 16,
+                        child: CircularProgressIndicator(strokeWidth: 2),
+                      ),
+                    )
+                  : IconButton(
+                      tooltip: l10n.backupVerify,
+                      icon: const Icon(Icons.verified_outlined),
+                      onPressed: onVerify,
+                    ),
+              const SizedBox(width: 8),
               IconButton(
                 tooltip: l10n.openFolderTooltip,
                 icon: const Icon(Icons.folder_open),
@@ -196,10 +457,21 @@ class _BackupTile extends StatelessWidget {
                       ),
                     );
                   }
-                  return FilledButton.icon(
-                    onPressed: onRestore,
-                    icon: const Icon(Icons.restore),
-                    label: Text(l10n.restore),
+                  return Row(
+                    mainAxisSize: MainAxisSize.min,
+                    children: [
+                      IconButton(
+                        tooltip: l10n.backupRestoreToPackage,
+                        icon: const Icon(Icons.drive_file_move_outline),
+                        onPressed: onRestoreTo,
+                      ),
+                      const SizedBox(width: 8),
+                      FilledButton.icon(
+                        onPressed: onRestore,
+                        icon: const Icon(Icons.restore),
+                        label: Text(l10n.restore),
+                      ),
+                    ],
                   );
                 },
               ),
@@ -210,13 +482,19 @@ class _BackupTile extends StatelessWidget {
     );
   }
 
-  String _buildSubtitle(
-      BuildContext context, BackupEntry entry, AppLocalizations l10n) {
+  static String _formatTimestamp(BuildContext context, BackupEntry entry) {
     final tsMillis = entry.timestamp.toInt();
     final dt = tsMillis == 0
         ? null
         : DateTime.fromMillisecondsSinceEpoch(tsMillis, isUtc: true).toLocal();
-    final tsStr = dt == null ? l10n.unknownTime : formatDateTime(context, dt);
+    return dt == null
+        ? AppLocalizations.of(context).unknownTime
+        : formatDateTime(context, dt);
+  }
+
+  String _buildSubtitle(
+      BuildContext context, BackupEntry entry, AppLocalizations l10n) {
+    final tsStr = _formatTimestamp(context, entry);
 
     final sizeStr =
         filesize.FileSize.fromBytes(entry.totalSize.toInt()).toString(
@@ -227,14 +505,32 @@ class _BackupTile extends StatelessWidget {
       decimals: 2,
     );
 
+    String part(String label, Uint64 size) =>
+        size.toInt() == 0 ? label : '$label (${formatSize(size.toInt(), 1)})';
+
+    final sizes = entry.sizes;
     final parts = <String>[];
-    if (entry.hasApk) parts.add(l10n.partAPK);
-    if (entry.hasPrivateData) parts.add(l10n.partPrivate);
-    if (entry.hasSharedData) parts.add(l10n.partShared);
-    if (entry.hasObb) parts.add(l10n.partOBB);
+    if (entry.hasApk) parts.add(part(l10n.partAPK, sizes.apk));
+    if (entry.hasPrivateData) {
+      parts.add(part(l10n.partPrivate, sizes.privateData));
+    }
+    if (entry.hasSharedData) {
+      parts.add(part(l10n.partShared, sizes.sharedData));
+    }
+    if (entry.hasObb) parts.add(part(l10n.partOBB, sizes.obb));
     final partsStr = parts.isEmpty ? l10n.noPartsDetected : parts.join(', ');
+    final sizeLabel =
+        entry.compressed ? '$sizeStr (${l10n.backupCompressed})' : sizeStr;
 
-    return '$tsStr • $partsStr • $sizeStr';
+    final versionName = entry.versionName;
+    final deviceSerial = entry.deviceSerial;
+    return [
+      tsStr,
+      if (versionName != null) 'v$versionName',
+      partsStr,
+      sizeLabel,
+      if (deviceSerial != null) l10n.backupFromDevice(deviceSerial),
+    ].join(' • ');
   }
 }
 
@@ -287,3 +583,244 @@ extension _BackupsRoot on _BackupsScreenState {
     GetBackupsDirectoryRequest().sendSignalToRust();
   }
 }
+
+extension _BackupsPrune on _BackupsScreenState {
+  void _previewPrune() {
+    BackupPrunePreview.rustSignalStream.take(1).listen((event) async {
+      final preview = event.message;
+      if (!mounted) return;
+      final l10n = AppLocalizations.of(context);
+      if (preview.error != null) {
+        SideloadUtils.showErrorToast(context, preview.error!);
+        return;
+      }
+      if (preview.entries.isEmpty) {
+        SideloadUtils.showInfoToast(
+            context, l10n.backupsPruneTitle, l10n.backupsPruneNothing);
+        return;
+      }
+
+      final confirmed = await showDialog<bool>(
+        context: context,
+        builder: (context) => AlertDialog(
+          title: Text(l10n.backupsPruneTitle),
+          content: SizedBox(
+            width: 480,
+            child: Column(
+              mainAxisSize: MainAxisSize.min,
+              crossAxisAlignment: CrossAxisAlignment.start,
+              children: [
+                Text(l10n.backupsPruneConfirm(
+                  preview.entries.length.toString(),
+                  formatSize(preview.freedBytes.toInt(), 1),
+                )),
+                const SizedBox(height: 8),
+                Flexible(
+                  child: ListView(
+                    shrinkWrap: true,
+                    children: [
+                      for (final entry in preview.entries)
+                        ListTile(
+                          dense: true,
+                          title: Text(entry.name),
+                          subtitle: Text(
+                              _BackupTile._formatTimestamp(context, entry)),
+                          trailing:
+                              Text(formatSize(entry.totalSize.toInt(), 1)),
+                        ),
+                    ],
+                  ),
+                ),
+              ],
+            ),
+          ),
+          actions: [
+            TextButton(
+              onPressed: () => Navigator.of(context).pop(false),
+              child: Text(l10n.commonCancel),
+            ),
+            FilledButton(
+              onPressed: () => Navigator.of(context).pop(true),
+              child: Text(l10n.delete),
+            ),
+          ],
+        ),
+      );
+
+      if (confirmed == true) PruneBackupsRequest().sendSignalToRust();
+    });
+    PreviewBackupPruneRequest().sendSignalToRust();
+  }
+}
+
+extension _BackupsVerify on _BackupsScreenState {
+  void _showVerification(BackupEntry entry, BackupVerification result) {
+    final l10n = AppLocalizations.of(context);
+    final error = result.error;
+    if (error != null) {
+      SideloadUtils.showErrorToast(context, error);
+      return;
+    }
+    if (result.problems.isEmpty) {
+      SideloadUtils.showInfoToast(
+        context,
+        l10n.backupVerifyOk,
+        result.hasFileList
+            ? l10n.backupVerifyOkFiles(result.checkedFiles.toString())
+            : l10n.backupVerifyNoFileList,
+      );
+      return;
+    }
+
+    showDialog<void>(
+      context: context,
+      builder: (context) => AlertDialog(
+        title: Text(l10n.backupVerifyProblemsTitle(entry.name)),
+        content: SizedBox(
+          width: 520,
+          child: Column(
+            mainAxisSize: MainAxisSize.min,
+            crossAxisAlignment: CrossAxisAlignment.start,
+            children: [
+              Text(
+                l10n.backupVerifyProblemsWarning,
+                style: TextStyle(color: Theme.of(context).colorScheme.error),
+              ),
+              if (!result.hasFileList) ...[
+                const SizedBox(height: 8),
+                Text(l10n.backupVerifyNoFileList),
+              ],
+              const SizedBox(height: 8),
+              Flexible(
+                child: ListView(
+                  shrinkWrap: true,
+                  children: [
+                    for (final problem in result.problems)
+                      ListTile(
+                        dense: true,
+                        leading: const Icon(Icons.error_outline),
+                        title: Text(problem),
+                      ),
+                  ],
+                ),
+              ),
+            ],
+          ),
+        ),
+        actions: [
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(),
+            child: Text(l10n.commonClose),
+          ),
+        ],
+      ),
+    );
+  }
+}
+
+extension _BackupsRestoreTo on _BackupsScreenState {
+  Future<void> _restoreToPackage(BackupEntry entry) async {
+    final installed =
+        context.read<DeviceState>().installedByPackage.keys.toList()..sort();
+    final target = await showDialog<String>(
+      context: context,
+      builder: (context) => _RestoreToPackageDialog(
+        entry: entry,
+        installedPackages: installed,
+      ),
+    );
+    if (target == null) return;
+    SideloadUtils.restoreBackup(entry.path, targetPackage: target);
+  }
+}
+
+class _RestoreToPackageDialog extends StatefulWidget {
+  final BackupEntry entry;
+  final List<String> installedPackages;
+
+  const _RestoreToPackageDialog({
+    required this.entry,
+    required this.installedPackages,
+  });
+
+  @override
+  State<_RestoreToPackageDialog> createState() =>
+      _RestoreToPackageDialogState();
+}
+
+class _RestoreToPackageDialogState extends State<_RestoreToPackageDialog> {
+  static final _packageNameRegex =
+      RegExp(r'^[A-Za-z][A-Za-z0-9_]*(\.[A-Za-z0-9_]+)+$');
+
+  String _target = '';
+
+  String? _validate(AppLocalizations l10n) {
+    final target = _target.trim();
+    if (target.isEmpty) return null;
+    if (!_packageNameRegex.hasMatch(target)) {
+      return l10n.backupRestoreInvalidPackage;
+    }
+    if (!widget.installedPackages.contains(target)) {
+      return l10n.backupRestorePackageNotInstalled;
+    }
+    if (target == widget.entry.packageName) {
+      return l10n.backupRestoreSamePackage;
+    }
+    return null;
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final error = _validate(l10n);
+    final canRestore = _target.trim().isNotEmpty && error == null;
+    return AlertDialog(
+      title: Text(l10n.backupRestoreToPackage),
+      content: SizedBox(
+        width: 480,
+        child: Column(
+          mainAxisSize: MainAxisSize.min,
+          crossAxisAlignment: CrossAxisAlignment.start,
+          children: [
+            Text(l10n.backupRestoreToPackageDescription(widget.entry.name)),
+            const SizedBox(height: 16),
+            Autocomplete<String>(
+              optionsBuilder: (value) {
+                final query = value.text.trim().toLowerCase();
+                if (query.isEmpty) return const Iterable<String>.empty();
+                return widget.installedPackages
+                    .where((p) => p.toLowerCase().contains(query));
+              },
+              onSelected: (value) => setState(() => _target = value),
+              fieldViewBuilder: (context, controller, focusNode, onSubmit) =>
+                  TextField(
+                controller: controller,
+                focusNode: focusNode,
+                autofocus: true,
+                decoration: InputDecoration(
+                  labelText: l10n.backupRestoreTargetPackage,
+                  hintText: widget.entry.packageName,
+                  errorText: error,
+                ),
+                onChanged: (value) => setState(() => _target = value),
+                onSubmitted: (_) => onSubmit(),
+              ),
+            ),
+          ],
+        ),
+      ),
+      actions: [
+        TextButton(
+          onPressed: () => Navigator.of(context).pop(),
+          child: Text(l10n.commonCancel),
+        ),
+        FilledButton(
+          onPressed: canRestore
+              ? () => Navigator.of(context).pop(_target.trim())
+              : null,
+          child: Text(l10n.restore),
+        ),
+      ],
+    );
+  }
+}
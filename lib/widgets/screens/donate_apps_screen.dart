@@ -138,6 +138,7 @@ class _DonateAppsScreenState extends State<DonateAppsScreen> {
         packageName: app.packageName,
         displayName: displayName,
       ),
+      priority: TaskPriority.normal,
     ).sendSignalToRust();
   }
 
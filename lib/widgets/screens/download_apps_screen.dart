@@ -371,13 +371,17 @@ class _DownloadAppsScreenState extends State<DownloadAppsScreen> {
     final proceed = await SideloadUtils.confirmIfLowSpace(context, size);
     if (!proceed) return;
     TaskRequest(
-      task: TaskDownloadInstall(field0: appFullName, field1: truePackageName),
+      task: TaskDownloadInstall(
+          field0: appFullName, field1: truePackageName, field2: null),
+      priority: TaskPriority.normal,
     ).sendSignalToRust();
   }
 
   void _download(String appFullName, String truePackageName) {
     TaskRequest(
-      task: TaskDownload(field0: appFullName, field1: truePackageName),
+      task: TaskDownload(
+          field0: appFullName, field1: truePackageName, field2: null),
+      priority: TaskPriority.normal,
     ).sendSignalToRust();
   }
 
@@ -806,7 +810,9 @@ class _DownloadAppsScreenState extends State<DownloadAppsScreen> {
                           TaskRequest(
                             task: TaskDownloadInstall(
                                 field0: app.app.fullName,
-                                field1: app.app.truePackageName),
+                                field1: app.app.truePackageName,
+                                field2: null),
+                            priority: TaskPriority.normal,
                           ).sendSignalToRust();
                         }
                         _clearSelection();
@@ -1,8 +1,10 @@
+import 'dart:async';
 import 'dart:io';
 
 import 'package:flutter/material.dart';
 import 'package:proper_filesize/proper_filesize.dart' as filesize;
 import 'package:provider/provider.dart';
+import 'package:rinf/rinf.dart';
 import '../../utils/sideload_utils.dart';
 import '../../src/bindings/bindings.dart';
 import '../../src/l10n/app_localizations.dart';
@@ -10,10 +12,13 @@ import '../../providers/device_state.dart';
 import '../../providers/cloud_apps_state.dart';
 import '../../providers/app_state.dart';
 import '../../utils/utils.dart';
+import '../dialogs/download_health_dialog.dart';
+import '../dialogs/lan_share_dialog.dart';
 
 const _listPadding = EdgeInsets.only(bottom: 24);
 const _cardMargin = EdgeInsets.symmetric(horizontal: 16, vertical: 2);
 const _cardPadding = EdgeInsets.symmetric(horizontal: 16, vertical: 4);
+const _pageSize = 100;
 
 enum DownloadsSortOption {
   name,
@@ -30,31 +35,46 @@ class DownloadsScreen extends StatefulWidget {
 
 class _DownloadsScreenState extends State<DownloadsScreen> {
   List<DownloadEntry> _entries = const [];
+  int _total = 0;
   bool _loading = false;
+  bool _loadingMore = false;
   String? _error;
   Map<String, int> _latestDownloadedByPackage = const {};
   DownloadsSortOption _sortOption = DownloadsSortOption.date;
   bool _sortAscending = false;
   bool _initialized = false;
+  String _search = '';
+  Timer? _searchDebounceTimer;
+  final _searchController = TextEditingController();
   final _scrollController = ScrollController();
   double _lastScrollOffset = 0.0;
+  StreamSubscription<RustSignalPack<QueryDownloadsResponse>>? _querySub;
+  StreamSubscription<RustSignalPack<DownloadsChanged>>? _changedSub;
 
   @override
   void initState() {
     super.initState();
     _scrollController.addListener(() {
-      if (_scrollController.hasClients) {
-        _lastScrollOffset = _scrollController.position.pixels;
-      }
+      if (!_scrollController.hasClients) return;
+      final position = _scrollController.position;
+      _lastScrollOffset = position.pixels;
+      if (position.pixels >= position.maxScrollExtent - 400) _loadMore();
+    });
+    _querySub = QueryDownloadsResponse.rustSignalStream.listen((event) {
+      if (mounted) _onQueryResponse(event.message);
     });
-    _loadDownloads();
-    DownloadsChanged.rustSignalStream.listen((_) {
-      if (mounted) _loadDownloads();
+    _loadDownloads(refresh: true);
+    _changedSub = DownloadsChanged.rustSignalStream.listen((_) {
+      if (mounted) _loadDownloads(refresh: true);
     });
   }
 
   @override
   void dispose() {
+    _searchDebounceTimer?.cancel();
+    _querySub?.cancel();
+    _changedSub?.cancel();
+    _searchController.dispose();
     _scrollController.dispose();
     super.dispose();
   }
@@ -73,42 +93,76 @@ class _DownloadsScreenState extends State<DownloadsScreen> {
     _initialized = true;
   }
 
-  Future<void> _loadDownloads() async {
+  DownloadsSortOrder get _sortOrder => switch (_sortOption) {
+        DownloadsSortOption.name => DownloadsSortOrder.name,
+        DownloadsSortOption.date => DownloadsSortOrder.date,
+        DownloadsSortOption.size => DownloadsSortOrder.size,
+      };
+
+  void _sendQuery(int offset, {bool refresh = false}) {
+    QueryDownloadsRequest(
+      search: _search,
+      sort: _sortOrder,
+      ascending: _sortAscending,
+      offset: offset,
+      limit: _pageSize,
+      refresh: refresh,
+    ).sendSignalToRust();
+  }
+
+  /// Loads the first page again, keeping the scroll position
+  Future<void> _loadDownloads({bool refresh = false}) async {
     setState(() {
-      _loading = true;
+      _loading = _entries.isEmpty;
+      _loadingMore = false;
       _error = null;
     });
-    GetDownloadsResponse.rustSignalStream.take(1).listen((event) {
-      final msg = event.message;
-      if (!mounted) return;
-      setState(() {
-        _loading = false;
-        _error = msg.error;
-        _entries = msg.entries;
-        // Precompute newest downloaded version per package (for update checking)
-        final latest = <String, int>{};
-        for (final e in _entries) {
-          final pkg = e.packageName;
-          final code = e.versionCode;
-          if (pkg == null || pkg.isEmpty || code == null) continue;
-          final prev = latest[pkg];
-          if (prev == null || code > prev) {
-            latest[pkg] = code;
-          }
-        }
-        _latestDownloadedByPackage = latest;
-      });
-      WidgetsBinding.instance.addPostFrameCallback((_) {
-        if (!_scrollController.hasClients) return;
-        final max = _scrollController.position.maxScrollExtent;
-        _scrollController.jumpTo(_lastScrollOffset.clamp(0.0, max));
-      });
+    _sendQuery(0, refresh: refresh);
+  }
+
+  void _loadMore() {
+    if (_loading || _loadingMore || _entries.length >= _total) return;
+    setState(() => _loadingMore = true);
+    _sendQuery(_entries.length);
+  }
+
+  void _onQueryResponse(QueryDownloadsResponse msg) {
+    if (msg.search != _search) return;
+    final offset = msg.offset;
+    // Pages answering an outdated request are dropped
+    if (offset != 0 && (!_loadingMore || offset != _entries.length)) return;
+    final firstPage = offset == 0;
+    setState(() {
+      _loading = false;
+      _loadingMore = false;
+      _error = msg.error;
+      _total = msg.total;
+      _entries = firstPage ? msg.entries : [..._entries, ...msg.entries];
+      _latestDownloadedByPackage = {
+        if (!firstPage) ..._latestDownloadedByPackage,
+        ...msg.newestVersionCodes,
+      };
+    });
+    if (!firstPage) return;
+    WidgetsBinding.instance.addPostFrameCallback((_) {
+      if (!_scrollController.hasClients) return;
+      final max = _scrollController.position.maxScrollExtent;
+      _scrollController.jumpTo(_lastScrollOffset.clamp(0.0, max));
     });
-    GetDownloadsRequest().sendSignalToRust();
   }
 
   Future<void> _refresh() async {
-    await _loadDownloads();
+    await _loadDownloads(refresh: true);
+  }
+
+  void _onSearchChanged(String value) {
+    _searchDebounceTimer?.cancel();
+    _searchDebounceTimer = Timer(const Duration(milliseconds: 300), () {
+      if (!mounted || value == _search) return;
+      _search = value;
+      _lastScrollOffset = 0;
+      _loadDownloads();
+    });
   }
 
   Future<void> _installEntry(DownloadEntry entry) async {
@@ -118,34 +172,37 @@ class _DownloadsScreenState extends State<DownloadsScreen> {
     SideloadUtils.installApp(entry.path, true);
   }
 
-  List<DownloadEntry> _sortedEntries() {
-    final entries = [..._entries];
-    entries.sort((a, b) {
-      final nameA = a.name.toLowerCase();
-      final nameB = b.name.toLowerCase();
-      int result;
-
-      switch (_sortOption) {
-        case DownloadsSortOption.name:
-          result = nameA.compareTo(nameB);
-          break;
-        case DownloadsSortOption.date:
-          result = a.timestamp.toInt().compareTo(b.timestamp.toInt());
-          if (result == 0) {
-            result = nameA.compareTo(nameB);
-          }
-          break;
-        case DownloadsSortOption.size:
-          result = a.totalSize.toInt().compareTo(b.totalSize.toInt());
-          if (result == 0) {
-            result = nameA.compareTo(nameB);
-          }
-          break;
-      }
-
-      return _sortAscending ? result : -result;
-    });
-    return entries;
+  Widget _buildSearchField() {
+    final l10n = AppLocalizations.of(context);
+    return ConstrainedBox(
+      constraints: const BoxConstraints(maxWidth: 300),
+      child: SizedBox(
+        height: 40,
+        child: TextField(
+          controller: _searchController,
+          decoration: InputDecoration(
+            hintText: l10n.searchDownloadsHint,
+            contentPadding:
+                const EdgeInsets.symmetric(horizontal: 8, vertical: 8),
+            border: const OutlineInputBorder(),
+            suffixIcon: _searchController.text.isNotEmpty
+                ? IconButton(
+                    icon: const Icon(Icons.close),
+                    tooltip: l10n.clearSearch,
+                    onPressed: () {
+                      _searchController.clear();
+                      _onSearchChanged('');
+                    },
+                  )
+                : null,
+          ),
+          onChanged: (value) {
+            setState(() {});
+            _onSearchChanged(value);
+          },
+        ),
+      ),
+    );
   }
 
   Widget _buildSortButton() {
@@ -197,6 +254,8 @@ class _DownloadsScreenState extends State<DownloadsScreen> {
           _sortAscending = ascending;
         });
         context.read<AppState>().setDownloadsSort(key, ascending);
+        _lastScrollOffset = 0;
+        _loadDownloads();
       },
     );
   }
@@ -204,7 +263,7 @@ class _DownloadsScreenState extends State<DownloadsScreen> {
   @override
   Widget build(BuildContext context) {
     final l10n = AppLocalizations.of(context);
-    final entries = _sortedEntries();
+    final entries = _entries;
     return Scaffold(
       body: SafeArea(
         child: Column(
@@ -218,11 +277,26 @@ class _DownloadsScreenState extends State<DownloadsScreen> {
                     style: Theme.of(context).textTheme.titleLarge,
                   ),
                   const Spacer(),
+                  _buildSearchField(),
+                  const SizedBox(width: 8),
                   IconButton(
                     tooltip: l10n.deleteAllDownloads,
                     onPressed: _confirmDeleteAllDownloads,
                     icon: const Icon(Icons.delete_sweep),
                   ),
+                  IconButton(
+                    tooltip: l10n.dedupeDownloads,
+                    onPressed: _dedupeDownloads,
+                    icon: const Icon(Icons.join_inner),
+                  ),
+                  IconButton(
+                    tooltip: l10n.lanShareTitle,
+                    onPressed: () => showDialog(
+                      context: context,
+                      builder: (context) => const LanShareDialog(),
+                    ),
+                    icon: const Icon(Icons.lan_outlined),
+                  ),
                   IconButton(
                     tooltip: l10n.openDownloadsFolder,
                     onPressed: _openDownloadsRoot,
@@ -243,7 +317,10 @@ class _DownloadsScreenState extends State<DownloadsScreen> {
                   : _error != null
                       ? Center(child: Text(_error!))
                       : _entries.isEmpty
-                          ? Center(child: Text(l10n.noDownloadsFound))
+                          ? Center(
+                              child: Text(_search.isEmpty
+                                  ? l10n.noDownloadsFound
+                                  : l10n.noMatchingDownloads))
                           : ListView.builder(
                               controller: _scrollController,
                               padding: _listPadding,
@@ -322,6 +399,30 @@ class _DownloadsScreenState extends State<DownloadsScreen> {
     DeleteAllDownloadsRequest().sendSignalToRust();
   }
 
+  void _dedupeDownloads() {
+    final l10n = AppLocalizations.of(context);
+    SideloadUtils.showInfoToast(
+        context, l10n.dedupeDownloads, l10n.dedupeDownloadsRunning);
+    DedupeReport.rustSignalStream.take(1).listen((event) {
+      final report = event.message;
+      if (!mounted) return;
+      if (report.error != null) {
+        SideloadUtils.showErrorToast(context, report.error!);
+        return;
+      }
+      SideloadUtils.showInfoToast(
+        context,
+        l10n.dedupeDownloads,
+        l10n.dedupeDownloadsResult(
+          report.newlyLinked.toString(),
+          formatSize(report.savedBytes.toInt(), 1),
+        ),
+      );
+      _loadDownloads();
+    });
+    DedupeDownloadsRequest(apply: true).sendSignalToRust();
+  }
+
   Future<void> _confirmDeleteAllDownloads() async {
     final confirmed = await showDialog<bool>(
       context: context,
@@ -426,6 +527,30 @@ class _DownloadTile extends StatelessWidget {
                 onPressed: onDelete,
               ),
               const SizedBox(width: 8),
+              IconButton(
+                tooltip: l10n.downloadHealthCheck,
+                icon: const Icon(Icons.fact_check_outlined),
+                onPressed: () => showDialog(
+                  context: context,
+                  builder: (context) => DownloadHealthDialog(
+                    path: entry.path,
+                    name: entry.name,
+                  ),
+                ),
+              ),
+              const SizedBox(width: 8),
+              IconButton(
+                tooltip: l10n.lanShareApp,
+                icon: const Icon(Icons.share_outlined),
+                onPressed: () => showDialog(
+                  context: context,
+                  builder: (context) => LanShareDialog(
+                    sharePath: entry.path,
+                    shareName: entry.name,
+                  ),
+                ),
+              ),
+              const SizedBox(width: 8),
               IconButton(
                 tooltip: l10n.openFolderTooltip,
                 icon: const Icon(Icons.folder_open),
@@ -4,6 +4,7 @@ import 'package:provider/provider.dart';
 import '../../providers/device_state.dart';
 import '../../src/bindings/bindings.dart';
 import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
 import '../device/device_actions.dart';
 import '../common/no_device_connected_indicator.dart';
 
@@ -70,6 +71,39 @@ class HomeScreen extends StatelessWidget {
     );
   }
 
+  Widget _buildControllerStatus(
+    BuildContext context,
+    DeviceState deviceState,
+    AppLocalizations l10n, {
+    required bool left,
+  }) {
+    final controller =
+        left ? deviceState.leftController : deviceState.rightController;
+    final firmware = controller?.firmwareVersion;
+    final lastSeen = controller?.lastSeen;
+    final lastSeenTime = lastSeen == null
+        ? null
+        : DateTime.fromMillisecondsSinceEpoch(lastSeen.toInt());
+    return _buildDeviceStatus(
+      context,
+      title: left ? l10n.leftController : l10n.rightController,
+      status: deviceState.controllerStatusString(context, controller),
+      batteryLevel: deviceState.controllerBatteryLevel(controller),
+      tooltipDetails: [
+        if (firmware != null) '${l10n.firmwareLabel}: $firmware',
+        if (lastSeenTime != null)
+          '${l10n.lastConnectedLabel}: '
+              '${formatDateTime(context, lastSeenTime) ?? lastSeenTime}',
+      ],
+      icon: SvgPicture.asset(
+        left ? 'assets/svg/controller_l.svg' : 'assets/svg/controller_r.svg',
+        colorFilter: _monoIconFilter(context),
+      ),
+      isDimmed: controller?.status is! ControllerStatusActive,
+      l10n: l10n,
+    );
+  }
+
   @override
   Widget build(BuildContext context) {
     return Consumer<DeviceState>(
@@ -132,30 +166,23 @@ class HomeScreen extends StatelessWidget {
                             Row(
                               mainAxisAlignment: MainAxisAlignment.center,
                               children: [
+                                if (deviceState.isVrHeadset) ...[
+                                  _buildControllerStatus(
+                                      context, deviceState, l10n,
+                                      left: true),
+                                  const SizedBox(width: 12),
+                                ],
                                 _buildDeviceStatus(
                                   context,
-                                  title: l10n.leftController,
-                                  status: deviceState.controllerStatusString(
-                                      context, deviceState.leftController),
-                                  batteryLevel:
-                                      deviceState.controllerBatteryLevel(
-                                          deviceState.leftController),
-                                  icon: SvgPicture.asset(
-                                    'assets/svg/controller_l.svg',
-                                    colorFilter: _monoIconFilter(context),
-                                  ),
-                                  isDimmed: deviceState.leftController?.status
-                                      is! ControllerStatusActive,
-                                  l10n: l10n,
-                                ),
-                                const SizedBox(width: 12),
-                                _buildDeviceStatus(
-                                  context,
-                                  title: l10n.headset,
+                                  title: deviceState.isVrHeadset
+                                      ? l10n.headset
+                                      : l10n.deviceTitle,
                                   batteryLevel: deviceState.batteryLevel,
                                   tooltipDetails: [
                                     if (deviceState.usbSpeed != null)
                                       '${l10n.usbSpeedLabel}: ${deviceState.usbSpeed}',
+                                    if (deviceState.firmware?.osVersion != null)
+                                      '${l10n.osVersionLabel}: ${deviceState.firmware!.osVersion}',
                                   ],
                                   icon: SvgPicture.asset(
                                     'assets/svg/headset.svg',
@@ -163,23 +190,12 @@ class HomeScreen extends StatelessWidget {
                                   ),
                                   l10n: l10n,
                                 ),
-                                const SizedBox(width: 12),
-                                _buildDeviceStatus(
-                                  context,
-                                  title: l10n.rightController,
-                                  status: deviceState.controllerStatusString(
-                                      context, deviceState.rightController),
-                                  batteryLevel:
-                                      deviceState.controllerBatteryLevel(
-                                          deviceState.rightController),
-                                  icon: SvgPicture.asset(
-                                    'assets/svg/controller_r.svg',
-                                    colorFilter: _monoIconFilter(context),
-                                  ),
-                                  isDimmed: deviceState.rightController?.status
-                                      is! ControllerStatusActive,
-                                  l10n: l10n,
-                                ),
+                                if (deviceState.isVrHeadset) ...[
+                                  const SizedBox(width: 12),
+                                  _buildControllerStatus(
+                                      context, deviceState, l10n,
+                                      left: false),
+                                ],
                               ],
                             )
                           ],
@@ -218,11 +234,24 @@ class _DeviceMenuButton extends StatelessWidget {
           case 'reboot':
             _showRebootOptions(context);
             break;
+          case 'pairControllers':
+            _confirmAndSend(
+              context,
+              title: l10n.pairControllersTitle,
+              message: l10n.pairControllersConfirm,
+              command: const AdbCommandPairControllers(),
+            );
+            break;
         }
       },
       itemBuilder: (context) => [
         PopupMenuItem(value: 'powerOff', child: Text(l10n.powerOffMenu)),
         PopupMenuItem(value: 'reboot', child: Text(l10n.rebootMenu)),
+        if (context.read<DeviceState>().supportsControllerPairing)
+          PopupMenuItem(
+            value: 'pairControllers',
+            child: Text(l10n.pairControllersMenu),
+          ),
       ],
     );
   }
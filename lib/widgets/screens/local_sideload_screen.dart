@@ -1,4 +1,5 @@
 import 'dart:async';
+import 'dart:io';
 
 import 'package:flutter/material.dart';
 import 'package:file_picker/file_picker.dart';
@@ -7,6 +8,7 @@ import 'package:provider/provider.dart';
 import '../../providers/device_state.dart';
 import '../../utils/sideload_utils.dart';
 import '../../providers/app_state.dart';
+import '../../src/bindings/bindings.dart';
 import '../../src/l10n/app_localizations.dart';
 import '../common/no_device_connected_indicator.dart';
 
@@ -22,13 +24,22 @@ class _LocalSideloadScreenState extends State<LocalSideloadScreen> {
   bool _isDirectory = false;
   AppState? _appState;
   bool _initialized = false;
+  ApkInfoResponse? _apkInfo;
+  String? _apkInfoPath;
+  Timer? _apkInfoDebounce;
+  StreamSubscription<RustSignalPack<ApkInfoResponse>>? _apkInfoSubscription;
 
   @override
   void initState() {
     super.initState();
     ServicesBinding.instance.keyboard.addHandler(_onKey);
+    _apkInfoSubscription = ApkInfoResponse.rustSignalStream.listen((event) {
+      if (!mounted || event.message.path != _apkInfoPath) return;
+      setState(() => _apkInfo = event.message);
+    });
     _pathController.addListener(() {
       setState(() {});
+      _requestApkInfo();
       final appState = _appState;
       if (appState != null) {
         appState.setSideloadLastPath(_pathController.text);
@@ -47,8 +58,28 @@ class _LocalSideloadScreenState extends State<LocalSideloadScreen> {
     }
   }
 
+  /// Asks for details of the selected APK once the path stops changing
+  void _requestApkInfo() {
+    final path = _pathController.text;
+    if (path == _apkInfoPath) return;
+    _apkInfoDebounce?.cancel();
+    _apkInfoPath = null;
+    _apkInfo = null;
+    if (_isDirectory ||
+        SideloadUtils.isApkBundle(path) ||
+        !SideloadUtils.isValidApkFile(path)) {
+      return;
+    }
+    _apkInfoDebounce = Timer(const Duration(milliseconds: 300), () {
+      _apkInfoPath = path;
+      GetApkInfoRequest(path: path).sendSignalToRust();
+    });
+  }
+
   @override
   void dispose() {
+    _apkInfoDebounce?.cancel();
+    _apkInfoSubscription?.cancel();
     _pathController.dispose();
     ServicesBinding.instance.keyboard.removeHandler(_onKey);
     super.dispose();
@@ -66,7 +97,7 @@ class _LocalSideloadScreenState extends State<LocalSideloadScreen> {
       final result = await FilePicker.pickFiles(
         dialogTitle: l10n.selectApkFileTitle,
         type: FileType.custom,
-        allowedExtensions: ['apk'],
+        allowedExtensions: ['apk', ...SideloadUtils.apkBundleExtensions],
         allowMultiple: false,
       );
       path = result?.files.single.path;
@@ -191,6 +222,10 @@ class _LocalSideloadScreenState extends State<LocalSideloadScreen> {
                             ),
                           ],
                         ),
+                        if (!_isDirectory && _apkInfo != null) ...[
+                          const SizedBox(height: 16),
+                          _ApkPreview(info: _apkInfo!),
+                        ],
                         if (_isDirectory) ...[
                           const SizedBox(height: 16),
                           Text(
@@ -233,6 +268,77 @@ class _LocalSideloadScreenState extends State<LocalSideloadScreen> {
   }
 }
 
+class _ApkPreview extends StatelessWidget {
+  final ApkInfoResponse info;
+
+  const _ApkPreview({required this.info});
+
+  @override
+  Widget build(BuildContext context) {
+    final l10n = AppLocalizations.of(context);
+    final theme = Theme.of(context);
+    if (info.error != null) {
+      return Text(
+        l10n.apkPreviewFailed(info.error!),
+        style: theme.textTheme.bodySmall
+            ?.copyWith(color: theme.colorScheme.error),
+        textAlign: TextAlign.center,
+      );
+    }
+
+    final iconPath = info.iconPath;
+    final version = [
+      if (info.versionName != null) info.versionName!,
+      if (info.versionCode != null) '(${info.versionCode})',
+    ].join(' ');
+    final details = [
+      if (version.isNotEmpty) l10n.apkPreviewVersion(version),
+      if (info.minSdkVersion != null || info.targetSdkVersion != null)
+        l10n.apkPreviewSdk(
+          info.minSdkVersion?.toString() ?? '?',
+          info.targetSdkVersion?.toString() ?? '?',
+        ),
+      info.nativeAbis.isEmpty
+          ? l10n.apkPreviewNoNativeLibs
+          : l10n.apkPreviewAbis(info.nativeAbis.join(', ')),
+    ];
+
+    return Card.outlined(
+      margin: EdgeInsets.zero,
+      child: ExpansionTile(
+        shape: const Border(),
+        leading: SizedBox.square(
+          dimension: 40,
+          child: iconPath != null
+              ? Image.file(
+                  File(iconPath),
+                  errorBuilder: (context, error, stackTrace) =>
+                      const Icon(Icons.android),
+                )
+              : const Icon(Icons.android),
+        ),
+        title: Text(info.label ?? info.packageName ?? ''),
+        subtitle: Text(
+          [if (info.label != null) info.packageName ?? '', ...details]
+              .where((line) => line.isNotEmpty)
+              .join('\n'),
+        ),
+        childrenPadding: const EdgeInsets.fromLTRB(16, 0, 16, 12),
+        expandedCrossAxisAlignment: CrossAxisAlignment.start,
+        children: [
+          Text(
+            l10n.apkPreviewPermissions(info.permissions.length.toString()),
+            style: theme.textTheme.titleSmall,
+          ),
+          const SizedBox(height: 4),
+          for (final permission in info.permissions)
+            SelectableText(permission, style: theme.textTheme.bodySmall),
+        ],
+      ),
+    );
+  }
+}
+
 class _AnimatedSideloadButton extends StatefulWidget {
   final bool isEnabled;
   final bool isDirectory;
@@ -1,3 +1,4 @@
+import 'dart:async';
 import 'dart:io';
 import 'package:flutter/material.dart';
 import 'package:flutter/services.dart';
@@ -6,6 +7,8 @@ import 'package:provider/provider.dart';
 import '../../providers/log_state.dart';
 import '../../src/bindings/bindings.dart';
 import '../../src/l10n/app_localizations.dart';
+import '../../utils/utils.dart';
+import '../dialogs/log_history_dialog.dart';
 
 class LogsScreen extends StatefulWidget {
   const LogsScreen({super.key});
@@ -187,6 +190,31 @@ class _LogsScreenState extends State<LogsScreen> {
                   ),
                   onTap: () => _exportLogs(logState),
                 ),
+                // Browse past logs from the log files
+                PopupMenuItem(
+                  child: Row(
+                    children: [
+                      const Icon(Icons.history, size: 16),
+                      const SizedBox(width: 8),
+                      Text(l10n.logHistory),
+                    ],
+                  ),
+                  onTap: () => showDialog(
+                    context: context,
+                    builder: (context) => const LogHistoryDialog(),
+                  ),
+                ),
+                // Bundle logs and environment details for bug reports
+                PopupMenuItem(
+                  child: Row(
+                    children: [
+                      const Icon(Icons.medical_information_outlined, size: 16),
+                      const SizedBox(width: 8),
+                      Text(l10n.createDiagnosticsBundle),
+                    ],
+                  ),
+                  onTap: _createDiagnosticsBundle,
+                ),
                 // Open logs directory
                 PopupMenuItem(
                   child: Row(
@@ -1030,6 +1058,38 @@ class _LogsScreenState extends State<LogsScreen> {
     );
   }
 
+  Future<void> _createDiagnosticsBundle() async {
+    final l10n = AppLocalizations.of(context);
+    final messenger = ScaffoldMessenger.of(context);
+    messenger.showSnackBar(
+      SnackBar(content: Text(l10n.diagnosticsBundleCreating)),
+    );
+    final response = DiagnosticsBundleResponse.rustSignalStream.first
+        .timeout(const Duration(minutes: 2));
+    CreateDiagnosticsBundleRequest().sendSignalToRust();
+
+    String? error;
+    try {
+      final message = (await response).message;
+      final path = message.bundlePath;
+      if (path != null) {
+        if (!mounted) return;
+        messenger.hideCurrentSnackBar();
+        copyToClipboard(context, path,
+            title: l10n.diagnosticsBundleSaved, description: path);
+        return;
+      }
+      error = message.error;
+    } on TimeoutException {
+      // Reported with the generic error below
+    }
+    if (!mounted) return;
+    messenger.hideCurrentSnackBar();
+    messenger.showSnackBar(SnackBar(
+      content: Text(l10n.diagnosticsBundleFailed(error ?? l10n.commonError)),
+    ));
+  }
+
   void _openLogsDirectory() async {
     // Listen for logs directory path from Rust
     GetLogsDirectoryResponse.rustSignalStream.take(1).listen((response) async {
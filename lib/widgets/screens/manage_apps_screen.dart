@@ -1,6 +1,9 @@
+import 'dart:async';
+
 import 'package:flutter/material.dart';
 import '../../src/l10n/app_localizations.dart';
 import 'package:flutter/services.dart';
+import 'package:file_picker/file_picker.dart';
 import 'package:provider/provider.dart';
 import '../../providers/device_state.dart';
 import '../../providers/cloud_apps_state.dart';
@@ -11,7 +14,12 @@ import '../common/animated_adb_button.dart';
 import '../common/animated_refresh_button.dart';
 import '../common/no_device_connected_indicator.dart';
 import '../dialogs/animated_uninstall_dialog.dart';
+import '../dialogs/app_permissions_dialog.dart';
+import '../dialogs/install_recipe_dialog.dart';
+import '../dialogs/rename_app_dialog.dart';
 import '../dialogs/backup_options_dialog.dart';
+import '../dialogs/transfer_app_data_dialog.dart';
+import '../dialogs/update_all_dialog.dart';
 import '../../utils/sideload_utils.dart';
 
 const _animationDuration = Duration(milliseconds: 200);
@@ -45,23 +53,6 @@ const hiddenPrefixes = [
   'com.facebook.',
 ];
 
-class _CloudUpdateInfo {
-  final List<CloudApp> matchingApps;
-  final CloudApp? newestApp;
-  final int installedVersionCode;
-
-  const _CloudUpdateInfo({
-    required this.matchingApps,
-    required this.newestApp,
-    required this.installedVersionCode,
-  });
-
-  bool get hasMatch => matchingApps.isNotEmpty;
-  bool get hasMultipleMatches => matchingApps.length > 1;
-  bool get hasNewerVersion =>
-      newestApp != null && newestApp!.versionCode > installedVersionCode;
-}
-
 class _ManageAppsScreenState extends State<ManageAppsScreen> {
   AppCategory _selectedCategory = AppCategory.vr;
   ManageSortOption _sortOption = ManageSortOption.name;
@@ -168,19 +159,7 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
 
   bool _hasUpdateAvailable(
       InstalledPackage app, CloudAppsState cloudAppsState) {
-    return _getCloudUpdateInfo(app, cloudAppsState).hasNewerVersion;
-  }
-
-  _CloudUpdateInfo _getCloudUpdateInfo(
-    InstalledPackage app,
-    CloudAppsState cloudAppsState,
-  ) {
-    final matchingApps = cloudAppsState.matchingAppsForPackage(app.packageName);
-    return _CloudUpdateInfo(
-      matchingApps: matchingApps,
-      newestApp: cloudAppsState.newestAppForPackage(app.packageName),
-      installedVersionCode: app.versionCode.toInt(),
-    );
+    return cloudAppsState.updatableApp(app.packageName) != null;
   }
 
   List<InstalledPackage> _sortApps(
@@ -297,6 +276,77 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
     );
   }
 
+  Widget _buildExportButton() {
+    final l10n = AppLocalizations.of(context);
+    return PopupMenuButton<ExportFormat>(
+      tooltip: l10n.exportInstalledList,
+      icon: const Icon(Icons.file_download_outlined),
+      itemBuilder: (context) => [
+        PopupMenuItem(
+          value: ExportFormat.csv,
+          child: Text(l10n.exportInstalledListCsv),
+        ),
+        PopupMenuItem(
+          value: ExportFormat.json,
+          child: Text(l10n.exportInstalledListJson),
+        ),
+      ],
+      onSelected: _exportInstalledList,
+    );
+  }
+
+  Future<void> _exportInstalledList(ExportFormat format) async {
+    final l10n = AppLocalizations.of(context);
+    final extension = format == ExportFormat.csv ? 'csv' : 'json';
+    final path = await FilePicker.saveFile(
+      dialogTitle: l10n.exportInstalledList,
+      fileName: 'installed_apps.$extension',
+      type: FileType.custom,
+      allowedExtensions: [extension],
+    );
+    if (path == null) return;
+    ExportInstalledListRequest(
+      path: path,
+      format: format,
+      includeSystem: _selectedCategory == AppCategory.system,
+    ).sendSignalToRust();
+  }
+
+  Widget _buildUpdateAllButton(CloudAppsState cloudAppsState) {
+    final l10n = AppLocalizations.of(context);
+    final updates = cloudAppsState.updatableApps.toList();
+    return IconButton(
+      icon: Badge.count(
+        count: updates.length,
+        isLabelVisible: updates.isNotEmpty,
+        child: const Icon(Icons.system_update_alt),
+      ),
+      tooltip: l10n.updateAll,
+      onPressed: updates.isEmpty
+          ? null
+          : () => _showUpdateAllDialog(cloudAppsState, updates),
+    );
+  }
+
+  Future<void> _showUpdateAllDialog(
+      CloudAppsState cloudAppsState, List<UpdatableApp> updates) async {
+    var totalSize = 0;
+    final apps = updates.map((update) {
+      final cloudApp = cloudAppsState.appByFullName(update.fullName);
+      totalSize += cloudApp?.size.toInt() ?? 0;
+      return UpdateAllApp(
+        fullName: update.fullName,
+        packageName: cloudApp?.truePackageName ?? update.packageName,
+      );
+    }).toList();
+    final proceed = await SideloadUtils.confirmIfLowSpace(context, totalSize);
+    if (!proceed || !mounted) return;
+    await showDialog<void>(
+      context: context,
+      builder: (context) => UpdateAllDialog(apps: apps),
+    );
+  }
+
   Widget _buildUpdatesFirstChip() {
     final l10n = AppLocalizations.of(context);
     return FilterChip(
@@ -358,6 +408,11 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
           children: [
             ...() {
               final l10n = AppLocalizations.of(context);
+              final pin = context
+                  .watch<CloudAppsState>()
+                  .versionPinFor(app.packageName);
+              final pinned = pin?.pinnedVersionCode;
+              final ignored = pin?.ignoredVersionCodes ?? const [];
               return [
                 _buildDetailsRow(
                     l10n.detailsPackageName, app.packageName, true),
@@ -370,6 +425,12 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
                     app.launchable ? l10n.commonYes : l10n.commonNo, false),
                 _buildDetailsRow(l10n.detailsIsSystem,
                     app.system ? l10n.commonYes : l10n.commonNo, false),
+                if (pinned != null)
+                  _buildDetailsRow(l10n.detailsPinnedVersion,
+                      pinned.toInt().toString(), false),
+                if (ignored.isNotEmpty)
+                  _buildDetailsRow(l10n.detailsIgnoredVersions,
+                      ignored.map((code) => code.toInt()).join(', '), false),
               ];
             }(),
             const SizedBox(height: 16),
@@ -393,6 +454,67 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
           ],
         ),
         actions: [
+          TextButton(
+            onPressed: () => showDialog(
+              context: context,
+              builder: (context) => AppPermissionsDialog(app: app),
+            ),
+            child: Text(AppLocalizations.of(context).appPermissions),
+          ),
+          TextButton(
+            onPressed: () => showDialog(
+              context: context,
+              builder: (context) =>
+                  InstallRecipeDialog(packageName: app.packageName),
+            ),
+            child: Text(AppLocalizations.of(context).installRecipe),
+          ),
+          Consumer<CloudAppsState>(
+            builder: (context, cloudAppsState, _) {
+              final l10n = AppLocalizations.of(context);
+              final pinned = cloudAppsState
+                      .versionPinFor(app.packageName)
+                      ?.pinnedVersionCode !=
+                  null;
+              return TextButton(
+                onPressed: () => cloudAppsState.setVersionPin(app.packageName,
+                    pinned ? null : app.versionCode.toInt()),
+                child: Text(pinned ? l10n.versionUnpin : l10n.versionPin),
+              );
+            },
+          ),
+          Consumer<CloudAppsState>(
+            builder: (context, cloudAppsState, _) {
+              final ignored = cloudAppsState
+                      .versionPinFor(app.packageName)
+                      ?.ignoredVersionCodes ??
+                  const [];
+              if (ignored.isEmpty) return const SizedBox.shrink();
+              return TextButton(
+                onPressed: () {
+                  for (final code in ignored) {
+                    cloudAppsState.setIgnoredVersion(
+                        app.packageName, code.toInt(), false);
+                  }
+                },
+                child: Text(AppLocalizations.of(context).updateStopIgnoring),
+              );
+            },
+          ),
+          TextButton(
+            onPressed: () => showDialog(
+              context: context,
+              builder: (context) => RenameAppDialog(
+                packageName: app.packageName,
+                currentName: app.label,
+              ),
+            ),
+            child: Text(AppLocalizations.of(context).appRename),
+          ),
+          TextButton(
+            onPressed: () => _collectCrashReport(context, app),
+            child: Text(AppLocalizations.of(context).crashReportCollect),
+          ),
           TextButton(
             onPressed: () => {
               copyToClipboard(context, _formatAppDetails(app)),
@@ -408,6 +530,39 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
     );
   }
 
+  Future<void> _collectCrashReport(
+      BuildContext context, InstalledPackage app) async {
+    final l10n = AppLocalizations.of(context);
+    final key = 'crash-${app.packageName}-'
+        '${DateTime.now().millisecondsSinceEpoch}';
+    final response = CrashReportResponse.rustSignalStream
+        .firstWhere((e) => e.message.commandKey == key)
+        .timeout(const Duration(minutes: 1));
+    AdbRequest(
+      command: AdbCommandCollectCrashReport(value: app.packageName),
+      commandKey: key,
+    ).sendSignalToRust();
+
+    String? error;
+    try {
+      final message = (await response).message;
+      final bundle = message.bundle;
+      if (bundle != null) {
+        if (!context.mounted) return;
+        copyToClipboard(context, bundle.path,
+            title: l10n.crashReportSaved, description: bundle.path);
+        await SideloadUtils.revealArtifact(context, bundle);
+        return;
+      }
+      error = message.error;
+    } on TimeoutException {
+      // Reported with the generic error below
+    }
+    if (!context.mounted) return;
+    SideloadUtils.showErrorToast(
+        context, l10n.crashReportFailed(error ?? l10n.commonError));
+  }
+
   void _showUninstallDialog(BuildContext context, InstalledPackage app) async {
     showDialog(
       context: context,
@@ -542,7 +697,9 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
     final proceed = await SideloadUtils.confirmIfLowSpace(context, size);
     if (!proceed) return;
     TaskRequest(
-      task: TaskDownloadInstall(field0: appFullName, field1: truePackageName),
+      task: TaskDownloadInstall(
+          field0: appFullName, field1: truePackageName, field2: null),
+      priority: TaskPriority.normal,
     ).sendSignalToRust();
   }
 
@@ -558,10 +715,17 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
           );
         }
 
-        final updateInfo = _getCloudUpdateInfo(app, cloudAppsState);
-        final matchingCloudApps = updateInfo.matchingApps;
+        final matchingCloudApps =
+            cloudAppsState.matchingAppsForPackage(app.packageName);
+        // Updates come from the backend, which applies version pins
+        final update = cloudAppsState.updatableApp(app.packageName);
+        final updateCloudApp = update == null
+            ? null
+            : cloudAppsState.appByFullName(update.fullName);
+        final newestCloudApp = updateCloudApp ??
+            cloudAppsState.newestAppForPackage(app.packageName);
 
-        if (!updateInfo.hasMatch) {
+        if (newestCloudApp == null) {
           return IconButton(
             icon: const Icon(Icons.update),
             tooltip: l10n.noMatchingCloudApp,
@@ -569,15 +733,14 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
           );
         }
 
-        final hasNewerVersion = updateInfo.hasNewerVersion;
-        final newestCloudApp = updateInfo.newestApp!;
-        final installedVersionCode = updateInfo.installedVersionCode;
+        final hasNewerVersion = updateCloudApp != null;
+        final installedVersionCode = app.versionCode.toInt();
 
-        return ValueListenableBuilder<bool>(
+        final updateButton = ValueListenableBuilder<bool>(
             valueListenable: _isShiftPressedNotifier,
             builder: (context, isShiftPressed, _) {
               final l10n = AppLocalizations.of(context);
-              if (!updateInfo.hasMultipleMatches) {
+              if (matchingCloudApps.length <= 1) {
                 // Single match
                 final isSameVersion =
                     newestCloudApp.versionCode == installedVersionCode;
@@ -649,6 +812,20 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
                 );
               }
             });
+        if (update == null) return updateButton;
+        final updateVersion = update.versionCode.toInt();
+        return Row(
+          mainAxisSize: MainAxisSize.min,
+          children: [
+            IconButton(
+              icon: const Icon(Icons.notifications_off_outlined),
+              tooltip: l10n.updateIgnoreVersion(updateVersion.toString()),
+              onPressed: () => cloudAppsState.setIgnoredVersion(
+                  app.packageName, updateVersion, true),
+            ),
+            updateButton,
+          ],
+        );
       },
     );
   }
@@ -769,6 +946,19 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
                       },
                     ),
                   ),
+                  IconButton(
+                    icon: const Icon(Icons.move_up_outlined),
+                    tooltip: l10n.transferAppData,
+                    onPressed: () => showDialog(
+                      context: context,
+                      builder: (context) => TransferAppDataDialog(app: app),
+                    ),
+                  ),
+                  IconButton(
+                    icon: const Icon(Icons.drive_folder_upload_outlined),
+                    tooltip: l10n.pushObb,
+                    onPressed: () => _pushObb(context, app),
+                  ),
                   IconButton(
                     icon: const Icon(Icons.delete_outline),
                     tooltip: l10n.uninstall,
@@ -783,6 +973,15 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
     );
   }
 
+  Future<void> _pushObb(BuildContext context, InstalledPackage app) async {
+    final l10n = AppLocalizations.of(context);
+    final dir = await FilePicker.getDirectoryPath(
+      dialogTitle: l10n.pushObbSelectFolder(app.label),
+    );
+    if (dir == null) return;
+    SideloadUtils.pushObb(app.packageName, dir);
+  }
+
   @override
   Widget build(BuildContext context) {
     return Consumer2<DeviceState, CloudAppsState>(
@@ -870,7 +1069,17 @@ class _ManageAppsScreenState extends State<ManageAppsScreen> {
                         iconSize: 24,
                       ),
                       _buildUpdatesFirstChip(),
+                      _buildUpdateAllButton(cloudAppsState),
                       _buildSortButton(),
+                      _buildExportButton(),
+                      IconButton(
+                        icon: const Icon(Icons.inventory_2_outlined),
+                        tooltip: l10n.backupAllApps,
+                        onPressed: () => showDialog(
+                          context: context,
+                          builder: (context) => const BackupOptionsDialog(),
+                        ),
+                      ),
                     ],
                   ),
                 ),
@@ -1,3 +1,4 @@
+import 'dart:async';
 import 'dart:io';
 import 'dart:math' as math;
 
@@ -14,14 +15,26 @@ import '../../src/l10n/app_localizations.dart';
 import '../../utils/utils.dart';
 import '../../utils/sideload_utils.dart';
 import '../common/selectable_link_text.dart';
+import '../dialogs/custom_remotes_dialog.dart';
+import '../dialogs/disk_usage_dialog.dart';
 import '../dialogs/downloader_setup_dialog.dart';
+import '../dialogs/settings_profiles_dialog.dart';
+import '../dialogs/storage_migration_dialog.dart';
+import '../dialogs/trash_dialog.dart';
 
 enum SettingTextField {
   rcloneRemoteName,
   adbPath,
   downloadsLocation,
+  downloadLayout,
   backupsLocation,
+  installedDownloadsLocation,
   bandwidthLimit,
+  castingBandwidthLimit,
+  downloadMirrors,
+  catalogBaseUrl,
+  mediaBaseUrl,
+  pinnedSpki,
 }
 
 class SettingsConstants {
@@ -30,6 +43,7 @@ class SettingsConstants {
   static const double verticalSpacing = 8.0;
   static const double iconButtonSize = 32.0;
   static const double iconSize = 16.0;
+  static const int maxConcurrency = 4;
 }
 
 // TODO: validate paths on input change
@@ -140,10 +154,32 @@ class _SettingsScreenState extends State<SettingsScreen> {
           _currentFormSettings.copyWith(adbPath: value),
         SettingTextField.downloadsLocation =>
           _currentFormSettings.copyWith(downloadsLocation: value),
+        SettingTextField.downloadLayout =>
+          _currentFormSettings.copyWith(downloadLayout: value),
         SettingTextField.backupsLocation =>
           _currentFormSettings.copyWith(backupsLocation: value),
+        SettingTextField.installedDownloadsLocation =>
+          _currentFormSettings.copyWith(installedDownloadsLocation: value),
         SettingTextField.bandwidthLimit =>
           _currentFormSettings.copyWith(bandwidthLimit: value),
+        SettingTextField.castingBandwidthLimit =>
+          _currentFormSettings.copyWith(castingBandwidthLimit: value),
+        SettingTextField.downloadMirrors => _currentFormSettings.copyWith(
+            downloadMirrors: value
+                .split(',')
+                .map((mirror) => mirror.trim())
+                .where((mirror) => mirror.isNotEmpty)
+                .toList()),
+        SettingTextField.catalogBaseUrl =>
+          _currentFormSettings.copyWith(catalogBaseUrl: value.trim()),
+        SettingTextField.mediaBaseUrl =>
+          _currentFormSettings.copyWith(mediaBaseUrl: value.trim()),
+        SettingTextField.pinnedSpki => _currentFormSettings.copyWith(
+            pinnedSpki: value
+                .split(',')
+                .map((pin) => pin.trim())
+                .where((pin) => pin.isNotEmpty)
+                .toList()),
       };
 
       _checkForChanges();
@@ -158,9 +194,20 @@ class _SettingsScreenState extends State<SettingsScreen> {
         SettingTextField.adbPath => _currentFormSettings.adbPath,
         SettingTextField.downloadsLocation =>
           _currentFormSettings.downloadsLocation,
+        SettingTextField.downloadLayout => _currentFormSettings.downloadLayout,
         SettingTextField.backupsLocation =>
           _currentFormSettings.backupsLocation,
+        SettingTextField.installedDownloadsLocation =>
+          _currentFormSettings.installedDownloadsLocation,
         SettingTextField.bandwidthLimit => _currentFormSettings.bandwidthLimit,
+        SettingTextField.castingBandwidthLimit =>
+          _currentFormSettings.castingBandwidthLimit,
+        SettingTextField.downloadMirrors =>
+          _currentFormSettings.downloadMirrors.join(', '),
+        SettingTextField.catalogBaseUrl => _currentFormSettings.catalogBaseUrl,
+        SettingTextField.mediaBaseUrl => _currentFormSettings.mediaBaseUrl,
+        SettingTextField.pinnedSpki =>
+          _currentFormSettings.pinnedSpki.join(', '),
       };
     }
   }
@@ -236,6 +283,22 @@ class _SettingsScreenState extends State<SettingsScreen> {
     }
   }
 
+  /// Moves the content of a storage location to a newly picked directory.
+  ///
+  /// Only offered without unsaved edits, which would otherwise overwrite the
+  /// location the migration switches to.
+  Future<void> _migrateStorage(
+      StorageLocation location, String label, String currentValue) async {
+    final path = await _pickDirectory(currentValue, label);
+    if (path == null || path == currentValue || !mounted) return;
+    await showDialog<void>(
+      context: context,
+      barrierDismissible: false,
+      builder: (context) =>
+          StorageMigrationDialog(location: location, newPath: path),
+    );
+  }
+
   Future<String?> _pickFile(String currentValue, String label) async {
     final l10n = AppLocalizations.of(context);
     String? initialDirectory;
@@ -295,6 +358,17 @@ class _SettingsScreenState extends State<SettingsScreen> {
           l10n.settingsCleanupKeepTwoVersions,
         DownloadCleanupPolicy.keepAllVersions =>
           l10n.settingsCleanupKeepAllVersions,
+        DownloadCleanupPolicy.moveAfterInstall =>
+          l10n.settingsCleanupMoveAfterInstall,
+      };
+
+  String _formatCastingDownloadPolicy(
+          AppLocalizations l10n, CastingDownloadPolicy policy) =>
+      switch (policy) {
+        CastingDownloadPolicy.ignore => l10n.settingsCastingDownloadsIgnore,
+        CastingDownloadPolicy.throttle =>
+          l10n.settingsCastingDownloadsThrottle,
+        CastingDownloadPolicy.pause => l10n.settingsCastingDownloadsPause,
       };
 
   String _formatDownloadMode(AppLocalizations l10n, DownloadMode mode) =>
@@ -384,6 +458,23 @@ class _SettingsScreenState extends State<SettingsScreen> {
         ),
         Row(
           children: [
+            SizedBox(
+              height: SettingsConstants.iconButtonSize,
+              width: SettingsConstants.iconButtonSize,
+              child: IconButton.filledTonal(
+                // Unsaved edits would hide the settings of a switched profile
+                onPressed: _hasChanges
+                    ? null
+                    : () => showDialog<void>(
+                          context: context,
+                          builder: (context) => const SettingsProfilesDialog(),
+                        ),
+                iconSize: SettingsConstants.iconSize,
+                icon: const Icon(Icons.switch_account_outlined),
+                tooltip: l10n.settingsProfiles,
+              ),
+            ),
+            const SizedBox(width: SettingsConstants.verticalSpacing),
             ValueListenableBuilder<bool>(
               valueListenable: _isShiftPressedNotifier,
               builder: (context, isShiftPressed, _) {
@@ -527,6 +618,39 @@ class _SettingsScreenState extends State<SettingsScreen> {
               }
             },
           ),
+          SwitchListTile(
+            title: Text(l10n.settingsCheckForUpdates),
+            value: _currentFormSettings.checkForUpdates,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(checkForUpdates: v);
+                _checkForChanges();
+              });
+            },
+          ),
+          _buildDropdownSetting<LogFileFormat>(
+            label: l10n.settingsLogFileFormat,
+            helperText: l10n.settingsLogFileFormatHelp,
+            value: _currentFormSettings.logFileFormat,
+            items: LogFileFormat.values.map((format) {
+              return DropdownMenuItem(
+                value: format,
+                child: Text(switch (format) {
+                  LogFileFormat.pretty => l10n.settingsLogFileFormatPretty,
+                  LogFileFormat.json => l10n.settingsLogFileFormatJson,
+                  LogFileFormat.both => l10n.settingsLogFileFormatBoth,
+                }),
+              );
+            }).toList(),
+            onChanged: (value) {
+              if (value != null) {
+                setState(() => _currentFormSettings =
+                    _currentFormSettings.copyWith(logFileFormat: value));
+                _checkForChanges();
+              }
+            },
+          ),
           const Divider(height: 24),
           Consumer<SettingsState>(builder: (context, settings, _) {
             final hasFavorites = settings.favoritePackages.isNotEmpty;
@@ -557,12 +681,135 @@ class _SettingsScreenState extends State<SettingsScreen> {
             isDirectory: true,
             currentValue: _currentFormSettings.downloadsLocation,
           ),
+          _buildTextSetting(
+            field: SettingTextField.downloadLayout,
+            label: l10n.settingsDownloadLayout,
+            helper: Text(l10n
+                .settingsDownloadLayoutHelp('{letter}, {name}, {package}')),
+            trailing: PopupMenuButton<String>(
+              tooltip: l10n.settingsDownloadLayoutPresets,
+              icon: const Icon(Icons.account_tree_outlined),
+              onSelected: (template) => _updateSetting(
+                  SettingTextField.downloadLayout, template,
+                  updateController: true),
+              itemBuilder: (context) => [
+                ('', l10n.settingsDownloadLayoutFlat),
+                ('{letter}', l10n.settingsDownloadLayoutByLetter),
+                ('{name}', l10n.settingsDownloadLayoutByName),
+                ('{package}', l10n.settingsDownloadLayoutByPackage),
+              ]
+                  .map((preset) => PopupMenuItem(
+                        value: preset.$1,
+                        child: Text(preset.$2),
+                      ))
+                  .toList(),
+            ),
+          ),
           _buildPathSetting(
             field: SettingTextField.backupsLocation,
             label: l10n.settingsBackupsLocation,
             isDirectory: true,
             currentValue: _currentFormSettings.backupsLocation,
           ),
+          Padding(
+            padding: const EdgeInsets.only(
+                top: SettingsConstants.verticalSpacing),
+            child: Text(
+              l10n.settingsBackupRetentionHelp,
+              style: Theme.of(context).textTheme.bodySmall,
+            ),
+          ),
+          _buildRetentionSetting(
+            label: l10n.settingsBackupRetentionPerApp,
+            value: _currentFormSettings.backupRetentionMaxPerPackage,
+            presets: const [1, 2, 3, 5, 10],
+            format: (count) => '$count',
+            onChanged: (value) => _currentFormSettings = _currentFormSettings
+                .copyWith(backupRetentionMaxPerPackage: value),
+          ),
+          _buildRetentionSetting(
+            label: l10n.settingsBackupRetentionTotalSize,
+            value: _currentFormSettings.backupRetentionMaxTotalGb,
+            presets: const [10, 25, 50, 100, 250, 500],
+            format: (gb) => formatSize(gb * 1000000000, 0),
+            onChanged: (value) => _currentFormSettings = _currentFormSettings
+                .copyWith(backupRetentionMaxTotalGb: value),
+          ),
+          _buildRetentionSetting(
+            label: l10n.settingsBackupRetentionMaxAge,
+            value: _currentFormSettings.backupRetentionMaxAgeDays,
+            presets: const [7, 30, 90, 180, 365],
+            format: (days) => l10n.settingsBackupRetentionDays('$days'),
+            onChanged: (value) => _currentFormSettings = _currentFormSettings
+                .copyWith(backupRetentionMaxAgeDays: value),
+          ),
+          const SizedBox(height: SettingsConstants.verticalSpacing),
+          SwitchListTile(
+            title: Text(l10n.settingsTrashBeforeUninstall),
+            subtitle: Text(l10n.settingsTrashBeforeUninstallHelp),
+            value: _currentFormSettings.trashBeforeUninstall,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(trashBeforeUninstall: v);
+                _checkForChanges();
+              });
+            },
+          ),
+          if (_currentFormSettings.trashBeforeUninstall)
+            _buildRetentionSetting(
+              label: l10n.settingsTrashRetention,
+              value: _currentFormSettings.trashRetentionDays,
+              presets: const [1, 3, 7, 14, 30],
+              format: (days) => l10n.settingsBackupRetentionDays('$days'),
+              onChanged: (value) => _currentFormSettings =
+                  _currentFormSettings.copyWith(trashRetentionDays: value),
+            ),
+          const SizedBox(height: SettingsConstants.verticalSpacing),
+          Wrap(
+            spacing: 8,
+            runSpacing: 8,
+            children: [
+              OutlinedButton.icon(
+                onPressed: () => showDialog<void>(
+                  context: context,
+                  builder: (context) => const DiskUsageDialog(),
+                ),
+                icon: const Icon(Icons.pie_chart_outline),
+                label: Text(l10n.diskUsage),
+              ),
+              OutlinedButton.icon(
+                onPressed: () => showDialog<void>(
+                  context: context,
+                  builder: (context) => const TrashDialog(),
+                ),
+                icon: const Icon(Icons.restore_from_trash_outlined),
+                label: Text(l10n.trashTitle),
+              ),
+              OutlinedButton.icon(
+                onPressed: _hasChanges
+                    ? null
+                    : () => _migrateStorage(
+                          StorageLocation.downloads,
+                          l10n.settingsDownloadsLocation,
+                          _currentFormSettings.downloadsLocation,
+                        ),
+                icon: const Icon(Icons.drive_file_move_outline),
+                label: Text(l10n.storageMigrationMoveDownloads),
+              ),
+              OutlinedButton.icon(
+                onPressed: _hasChanges
+                    ? null
+                    : () => _migrateStorage(
+                          StorageLocation.backups,
+                          l10n.settingsBackupsLocation,
+                          _currentFormSettings.backupsLocation,
+                        ),
+                icon: const Icon(Icons.drive_file_move_outline),
+                label: Text(l10n.storageMigrationMoveBackups),
+              ),
+            ],
+          ),
         ],
       ),
       const SizedBox(height: SettingsConstants.sectionSpacing),
@@ -588,6 +835,43 @@ class _SettingsScreenState extends State<SettingsScreen> {
               });
             },
           ),
+          SwitchListTile(
+            title: Text(l10n.settingsReconnectWirelessDevices),
+            subtitle: Text(l10n.settingsReconnectWirelessDevicesHelp),
+            value: _currentFormSettings.reconnectWirelessDevices,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings = _currentFormSettings.copyWith(
+                  reconnectWirelessDevices: v,
+                );
+                _checkForChanges();
+              });
+            },
+          ),
+          SwitchListTile(
+            title: Text(l10n.settingsStopForeignAdbServers),
+            subtitle: Text(l10n.settingsStopForeignAdbServersHelp),
+            value: _currentFormSettings.stopForeignAdbServers,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(stopForeignAdbServers: v);
+                _checkForChanges();
+              });
+            },
+          ),
+          SwitchListTile(
+            title: Text(l10n.settingsShellConsole),
+            subtitle: Text(l10n.settingsShellConsoleHelp),
+            value: _currentFormSettings.shellConsoleEnabled,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(shellConsoleEnabled: v);
+                _checkForChanges();
+              });
+            },
+          ),
           SwitchListTile(
             title: Text(l10n.settingsAutoReinstallOnConflict),
             subtitle: Text(l10n.settingsAutoReinstallOnConflictHelp),
@@ -600,6 +884,89 @@ class _SettingsScreenState extends State<SettingsScreen> {
               });
             },
           ),
+          SwitchListTile(
+            title: Text(l10n.settingsAutoApproveInstallScripts),
+            subtitle: Text(l10n.settingsAutoApproveInstallScriptsHelp),
+            value: _currentFormSettings.autoApproveInstallScripts,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(autoApproveInstallScripts: v);
+                _checkForChanges();
+              });
+            },
+          ),
+          SwitchListTile(
+            title: Text(l10n.settingsWakeOfflineDevices),
+            subtitle: Text(l10n.settingsWakeOfflineDevicesHelp),
+            value: _currentFormSettings.wakeOfflineDevices,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(wakeOfflineDevices: v);
+                _checkForChanges();
+              });
+            },
+          ),
+          _buildDropdownSetting<OfflineWakeAction>(
+            label: l10n.settingsOfflineWakeAction,
+            value: _currentFormSettings.offlineWakeAction,
+            items: OfflineWakeAction.values.map((action) {
+              return DropdownMenuItem(
+                value: action,
+                child: Text(switch (action) {
+                  OfflineWakeAction.keyevent =>
+                    l10n.settingsOfflineWakeActionKeyevent,
+                  OfflineWakeAction.proximityClose =>
+                    l10n.settingsOfflineWakeActionProximity,
+                }),
+              );
+            }).toList(),
+            onChanged: (value) {
+              if (value != null) {
+                setState(() => _currentFormSettings =
+                    _currentFormSettings.copyWith(offlineWakeAction: value));
+                _checkForChanges();
+              }
+            },
+          ),
+          _buildReconnectGraceSetting(l10n),
+          SwitchListTile(
+            title: Text(l10n.settingsDeviceAgent),
+            subtitle: Text(l10n.settingsDeviceAgentHelp),
+            value: _currentFormSettings.deviceAgentEnabled,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(deviceAgentEnabled: v);
+                _checkForChanges();
+              });
+            },
+          ),
+          SwitchListTile(
+            title: Text(l10n.settingsWatchPackageChanges),
+            subtitle: Text(l10n.settingsWatchPackageChangesHelp),
+            value: _currentFormSettings.watchPackageChanges,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(watchPackageChanges: v);
+                _checkForChanges();
+              });
+            },
+          ),
+          SwitchListTile(
+            title: Text(l10n.settingsProbeWirelessLink),
+            subtitle: Text(l10n.settingsProbeWirelessLinkHelp),
+            value: _currentFormSettings.probeWirelessLink,
+            onChanged: (v) {
+              setState(() {
+                _currentFormSettings =
+                    _currentFormSettings.copyWith(probeWirelessLink: v);
+                _checkForChanges();
+              });
+            },
+          ),
           _buildDropdownSetting<ConnectionKind>(
             label: l10n.settingsPreferredConnection,
             value: _currentFormSettings.preferredConnectionType,
@@ -626,6 +993,39 @@ class _SettingsScreenState extends State<SettingsScreen> {
         ],
       ),
       const SizedBox(height: SettingsConstants.sectionSpacing),
+      _buildSection(
+        title: l10n.settingsSectionTasks,
+        children: [
+          _buildConcurrencySetting(
+            label: l10n.settingsMaxConcurrentDownloads,
+            value: _currentFormSettings.maxConcurrentDownloads,
+            onChanged: (value) {
+              setState(() => _currentFormSettings = _currentFormSettings
+                  .copyWith(maxConcurrentDownloads: value));
+              _checkForChanges();
+            },
+          ),
+          _buildConcurrencySetting(
+            label: l10n.settingsMaxConcurrentAdbOperations,
+            help: l10n.settingsMaxConcurrentAdbOperationsHelp,
+            value: _currentFormSettings.maxConcurrentAdbOperations,
+            onChanged: (value) {
+              setState(() => _currentFormSettings = _currentFormSettings
+                  .copyWith(maxConcurrentAdbOperations: value));
+              _checkForChanges();
+            },
+          ),
+          _buildExtractionThreadsSetting(l10n),
+          ExpansionTile(
+            title: Text(l10n.settingsTaskKindLimits),
+            subtitle: Text(l10n.settingsTaskKindLimitsHelp),
+            children: TaskKind.values
+                .map((kind) => _buildTaskKindLimitSetting(l10n, kind))
+                .toList(),
+          ),
+        ],
+      ),
+      const SizedBox(height: SettingsConstants.sectionSpacing),
     ];
 
     // Downloader section
@@ -672,6 +1072,30 @@ class _SettingsScreenState extends State<SettingsScreen> {
                   ),
                 ),
               ),
+            _buildMirrorSettings(l10n, settingsState),
+            if (!settingsState.downloaderSupportsRemoteSelection)
+              _buildTextSetting(
+                field: SettingTextField.catalogBaseUrl,
+                label: l10n.settingsCatalogBaseUrl,
+                helperText: l10n.settingsCatalogBaseUrlHelp,
+                trailing: _buildSourceTestButton(
+                  l10n,
+                  SourceUrlKind.catalog,
+                  SettingTextField.catalogBaseUrl,
+                ),
+              ),
+            _buildTextSetting(
+              field: SettingTextField.mediaBaseUrl,
+              label: l10n.settingsMediaBaseUrl,
+              helperText: l10n.settingsMediaBaseUrlHelp,
+              trailing: _buildSourceTestButton(
+                  l10n, SourceUrlKind.media, SettingTextField.mediaBaseUrl),
+            ),
+            _buildTextSetting(
+              field: SettingTextField.pinnedSpki,
+              label: l10n.settingsPinnedSpki,
+              helperText: l10n.settingsPinnedSpkiHelp,
+            ),
             _buildDropdownSetting<DownloadCleanupPolicy>(
               label: l10n.settingsDownloadsCleanup,
               value: _currentFormSettings.cleanupPolicy,
@@ -689,6 +1113,41 @@ class _SettingsScreenState extends State<SettingsScreen> {
                 }
               },
             ),
+            if (_currentFormSettings.cleanupPolicy ==
+                DownloadCleanupPolicy.moveAfterInstall)
+              _buildPathSetting(
+                field: SettingTextField.installedDownloadsLocation,
+                label: l10n.settingsInstalledDownloadsLocation,
+                isDirectory: true,
+                currentValue: _currentFormSettings.installedDownloadsLocation,
+              ),
+            // Casting is only available on Windows
+            if (Platform.isWindows) ...[
+              _buildDropdownSetting<CastingDownloadPolicy>(
+                label: l10n.settingsCastingDownloads,
+                value: _currentFormSettings.castingDownloadPolicy,
+                items: CastingDownloadPolicy.values.map((policy) {
+                  return DropdownMenuItem(
+                    value: policy,
+                    child: Text(_formatCastingDownloadPolicy(l10n, policy)),
+                  );
+                }).toList(),
+                onChanged: (value) {
+                  if (value != null) {
+                    setState(() => _currentFormSettings = _currentFormSettings
+                        .copyWith(castingDownloadPolicy: value));
+                    _checkForChanges();
+                  }
+                },
+              ),
+              if (_currentFormSettings.castingDownloadPolicy ==
+                      CastingDownloadPolicy.throttle &&
+                  settingsState.downloaderSupportsBandwidthLimit)
+                _buildTextSetting(
+                  field: SettingTextField.castingBandwidthLimit,
+                  label: l10n.settingsCastingBandwidthLimit,
+                ),
+            ],
             if (settingsState.downloaderSupportsDownloadModeSelection)
               _buildDownloadModeSetting(l10n),
             const SizedBox(height: SettingsConstants.verticalSpacing),
@@ -704,6 +1163,18 @@ class _SettingsScreenState extends State<SettingsScreen> {
                 });
               },
             ),
+            SwitchListTile(
+              title: Text(l10n.settingsDedupeDownloads),
+              subtitle: Text(l10n.settingsDedupeDownloadsHelp),
+              value: _currentFormSettings.dedupeDownloads,
+              onChanged: (v) {
+                setState(() {
+                  _currentFormSettings =
+                      _currentFormSettings.copyWith(dedupeDownloads: v);
+                  _checkForChanges();
+                });
+              },
+            ),
           ],
           Align(
             alignment: Alignment.centerLeft,
@@ -953,6 +1424,142 @@ class _SettingsScreenState extends State<SettingsScreen> {
     );
   }
 
+  Widget _buildSourceTestButton(
+      AppLocalizations l10n, SourceUrlKind kind, SettingTextField field) {
+    return IconButton.filledTonal(
+      icon: const Icon(Icons.network_check),
+      tooltip: l10n.settingsTestConnection,
+      onPressed: () =>
+          _testSourceUrl(kind, _textControllers[field]?.text.trim() ?? ''),
+    );
+  }
+
+  Future<void> _testSourceUrl(SourceUrlKind kind, String url) async {
+    final l10n = AppLocalizations.of(context);
+    final pending = SourceUrlTestResult.rustSignalStream
+        .map((event) => event.message)
+        .firstWhere((result) => result.kind == kind && result.url == url)
+        .timeout(const Duration(seconds: 20));
+    final pins = (_textControllers[SettingTextField.pinnedSpki]?.text ?? '')
+        .split(',')
+        .map((pin) => pin.trim())
+        .where((pin) => pin.isNotEmpty)
+        .toList();
+    TestSourceUrlRequest(kind: kind, url: url, pinnedSpki: pins)
+        .sendSignalToRust();
+    try {
+      final result = await pending;
+      if (!mounted) return;
+      final error = result.error;
+      if (error != null) {
+        SideloadUtils.showErrorToast(context, error);
+      } else {
+        SideloadUtils.showInfoToast(
+          context,
+          l10n.settingsTestConnectionOk,
+          l10n.settingsTestConnectionDetails(
+              '${result.status}', '${result.latencyMs}'),
+        );
+      }
+    } on TimeoutException {
+      if (!mounted) return;
+      SideloadUtils.showErrorToast(context, l10n.settingsTestConnectionTimeout);
+    }
+  }
+
+  Widget _buildReconnectGraceSetting(AppLocalizations l10n) {
+    const durations = [0, 3, 5, 10, 20, 30];
+    final graceSecs = _currentFormSettings.deviceReconnectGraceSecs;
+    return _buildDropdownSetting<int>(
+      label: l10n.settingsDeviceReconnectGrace,
+      helperText: l10n.settingsDeviceReconnectGraceHelp,
+      value: graceSecs,
+      items: [
+        for (final seconds in {...durations, graceSecs})
+          DropdownMenuItem(
+            value: seconds,
+            child: Text(seconds == 0
+                ? l10n.settingsDeviceReconnectGraceOff
+                : l10n.settingsMirrorSlowSeconds('$seconds')),
+          ),
+      ],
+      onChanged: (value) {
+        if (value == null) return;
+        setState(() => _currentFormSettings =
+            _currentFormSettings.copyWith(deviceReconnectGraceSecs: value));
+        _checkForChanges();
+      },
+    );
+  }
+
+  Widget _buildMirrorSettings(
+      AppLocalizations l10n, SettingsState settingsState) {
+    const speeds = [0, 100, 250, 500, 1000, 2000];
+    const durations = [15, 30, 60, 120];
+    final minSpeed = _currentFormSettings.mirrorMinSpeedKbps;
+    final slowSecs = _currentFormSettings.mirrorSlowSecs;
+    return Column(
+      children: [
+        _buildTextSetting(
+          field: SettingTextField.downloadMirrors,
+          label: l10n.settingsDownloadMirrors,
+          helperText: settingsState.downloaderSupportsRemoteSelection
+              ? l10n.settingsDownloadMirrorsRemotesHelp
+              : l10n.settingsDownloadMirrorsUrlsHelp,
+        ),
+        if (_currentFormSettings.downloadMirrors.isNotEmpty)
+          Row(
+            children: [
+              Expanded(
+                child: _buildDropdownSetting<int>(
+                  label: l10n.settingsMirrorMinSpeed,
+                  value: minSpeed,
+                  items: [
+                    for (final speed in {...speeds, minSpeed})
+                      DropdownMenuItem(
+                        value: speed,
+                        child: Text(speed == 0
+                            ? l10n.settingsMirrorMinSpeedOff
+                            : l10n.settingsMirrorSpeedKbps('$speed')),
+                      ),
+                  ],
+                  onChanged: (value) {
+                    if (value == null) return;
+                    setState(() => _currentFormSettings = _currentFormSettings
+                        .copyWith(mirrorMinSpeedKbps: value));
+                    _checkForChanges();
+                  },
+                ),
+              ),
+              if (minSpeed > 0) ...[
+                const SizedBox(width: SettingsConstants.verticalSpacing),
+                Expanded(
+                  child: _buildDropdownSetting<int>(
+                    label: l10n.settingsMirrorSlowFor,
+                    value: slowSecs,
+                    items: [
+                      for (final seconds in {...durations, slowSecs})
+                        DropdownMenuItem(
+                          value: seconds,
+                          child: Text(
+                              l10n.settingsMirrorSlowSeconds('$seconds')),
+                        ),
+                    ],
+                    onChanged: (value) {
+                      if (value == null) return;
+                      setState(() => _currentFormSettings = _currentFormSettings
+                          .copyWith(mirrorSlowSecs: value));
+                      _checkForChanges();
+                    },
+                  ),
+                ),
+              ],
+            ],
+          ),
+      ],
+    );
+  }
+
   Widget _buildPathSetting({
     required SettingTextField field,
     required String label,
@@ -1014,6 +1621,7 @@ class _SettingsScreenState extends State<SettingsScreen> {
     required List<DropdownMenuItem<T>> items,
     required ValueChanged<T?>? onChanged,
     Widget? disabledHint,
+    String? helperText,
   }) {
     return Padding(
       padding: const EdgeInsets.symmetric(
@@ -1025,12 +1633,138 @@ class _SettingsScreenState extends State<SettingsScreen> {
         onChanged: onChanged,
         decoration: InputDecoration(
           labelText: label,
+          helperText: helperText,
+          border: const OutlineInputBorder(),
+        ),
+      ),
+    );
+  }
+
+  Widget _buildConcurrencySetting({
+    required String label,
+    String? help,
+    required int value,
+    required ValueChanged<int> onChanged,
+  }) {
+    return Padding(
+      padding: const EdgeInsets.symmetric(
+          vertical: SettingsConstants.verticalSpacing),
+      child: DropdownButtonFormField<int>(
+        initialValue: value.clamp(1, SettingsConstants.maxConcurrency),
+        items: [
+          for (var i = 1; i <= SettingsConstants.maxConcurrency; i++)
+            DropdownMenuItem(value: i, child: Text('$i')),
+        ],
+        onChanged: (value) {
+          if (value != null) onChanged(value);
+        },
+        decoration: InputDecoration(
+          labelText: label,
+          border: const OutlineInputBorder(),
+          suffixIcon: help == null
+              ? null
+              : Tooltip(
+                  message: help,
+                  child: const Icon(Icons.info_outline),
+                ),
+        ),
+      ),
+    );
+  }
+
+  /// Dropdown for the extraction thread count where 0 means automatic
+  Widget _buildExtractionThreadsSetting(AppLocalizations l10n) {
+    final value = _currentFormSettings.extractionThreads;
+    final values = {1, 2, 4, 8, 16, if (value != 0) value}.toList()..sort();
+    return Padding(
+      padding: const EdgeInsets.symmetric(
+          vertical: SettingsConstants.verticalSpacing),
+      child: DropdownButtonFormField<int>(
+        initialValue: value,
+        items: [
+          DropdownMenuItem(
+            value: 0,
+            child: Text(l10n.settingsExtractionThreadsAuto),
+          ),
+          for (final v in values)
+            DropdownMenuItem(value: v, child: Text('$v')),
+        ],
+        onChanged: (value) {
+          if (value == null) return;
+          setState(() => _currentFormSettings =
+              _currentFormSettings.copyWith(extractionThreads: value));
+          _checkForChanges();
+        },
+        decoration: InputDecoration(
+          labelText: l10n.settingsExtractionThreads,
           border: const OutlineInputBorder(),
+          suffixIcon: Tooltip(
+            message: l10n.settingsExtractionThreadsHelp,
+            child: const Icon(Icons.info_outline),
+          ),
         ),
       ),
     );
   }
 
+  /// Dropdown for a retention limit where 0 means no limit
+  Widget _buildRetentionSetting({
+    required String label,
+    required int value,
+    required List<int> presets,
+    required String Function(int) format,
+    required void Function(int) onChanged,
+  }) {
+    final l10n = AppLocalizations.of(context);
+    final values = {...presets, if (value != 0) value}.toList()..sort();
+    return _buildDropdownSetting<int>(
+      label: label,
+      value: value,
+      items: [
+        DropdownMenuItem(
+          value: 0,
+          child: Text(l10n.settingsBackupRetentionUnlimited),
+        ),
+        for (final v in values)
+          DropdownMenuItem(value: v, child: Text(format(v))),
+      ],
+      onChanged: (value) {
+        if (value == null) return;
+        setState(() => onChanged(value));
+        _checkForChanges();
+      },
+    );
+  }
+
+  Widget _buildTaskKindLimitSetting(AppLocalizations l10n, TaskKind kind) {
+    final limit = _currentFormSettings.taskKindLimits
+        .where((limit) => limit.kind == kind)
+        .firstOrNull;
+    return _buildDropdownSetting<int?>(
+      label: formatTaskKind(l10n, kind),
+      value: limit?.maxActive.clamp(1, SettingsConstants.maxConcurrency),
+      items: [
+        DropdownMenuItem(
+          value: null,
+          child: Text(l10n.settingsTaskKindUnlimited),
+        ),
+        for (var i = 1; i <= SettingsConstants.maxConcurrency; i++)
+          DropdownMenuItem(value: i, child: Text('$i')),
+      ],
+      onChanged: (value) {
+        final limits = _currentFormSettings.taskKindLimits
+            .where((limit) => limit.kind != kind)
+            .toList();
+        if (value != null) {
+          limits.add(TaskKindLimit(kind: kind, maxActive: value));
+        }
+        setState(() => _currentFormSettings =
+            _currentFormSettings.copyWith(taskKindLimits: limits));
+        _checkForChanges();
+      },
+    );
+  }
+
   Widget _buildDownloadModeSetting(AppLocalizations l10n) {
     return Padding(
       padding: const EdgeInsets.symmetric(
@@ -1138,6 +1872,19 @@ class _SettingsScreenState extends State<SettingsScreen> {
                         icon: const Icon(Icons.refresh),
                       ),
               ),
+              const SizedBox(width: SettingsConstants.verticalSpacing),
+              IconButton.filledTonal(
+                onPressed: () async {
+                  await showDialog<void>(
+                    context: context,
+                    builder: (context) => const CustomRemotesDialog(),
+                  );
+                  settingsState.refreshRcloneRemotes();
+                },
+                iconSize: SettingsConstants.iconSize,
+                tooltip: l10n.customRemotes,
+                icon: const Icon(Icons.dns_outlined),
+              ),
             ],
           ),
         ],
@@ -0,0 +1,110 @@
+//! Timers restoring temporary device state, such as a paused guardian.
+//!
+//! Reverts are kept per device serial outside of `AdbDevice`, so they outlive refreshes and
+//! reconnects. A revert that comes due while its device is away runs once it is back.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::models::signals::adb::auto_revert::{AutoRevertCountdown, AutoRevertKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScheduledRevert {
+    serial: String,
+    kind: AutoRevertKind,
+    deadline: Instant,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct RevertSchedule {
+    entries: Vec<ScheduledRevert>,
+}
+
+impl RevertSchedule {
+    /// Schedules restoring `kind` on the device after `after`, replacing an earlier schedule
+    pub(super) fn schedule(
+        &mut self,
+        serial: &str,
+        kind: AutoRevertKind,
+        after: Duration,
+        now: Instant,
+    ) {
+        self.cancel(serial, kind);
+        self.entries.push(ScheduledRevert {
+            serial: serial.to_string(),
+            kind,
+            deadline: now + after,
+        });
+    }
+
+    /// Drops a scheduled revert, returns whether there was one
+    pub(super) fn cancel(&mut self, serial: &str, kind: AutoRevertKind) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|e| e.serial != serial || e.kind != kind);
+        self.entries.len() != len
+    }
+
+    /// Removes and returns the reverts of the device that are due
+    pub(super) fn take_due(&mut self, serial: &str, now: Instant) -> Vec<AutoRevertKind> {
+        let (due, pending) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition::<Vec<_>, _>(|e| e.serial == serial && e.deadline <= now);
+        self.entries = pending;
+        due.into_iter().map(|e| e.kind).collect()
+    }
+
+    /// Earliest deadline still in the future. Past ones wait for their device instead.
+    pub(super) fn next_deadline(&self, now: Instant) -> Option<Instant> {
+        self.entries.iter().map(|e| e.deadline).filter(|d| *d > now).min()
+    }
+
+    /// Pending reverts of the device, soonest first
+    pub(super) fn countdown(&self, serial: &str, now: Instant) -> Vec<AutoRevertCountdown> {
+        let mut entries = self.entries.iter().filter(|e| e.serial == serial).collect::<Vec<_>>();
+        entries.sort_by_key(|e| e.deadline);
+        entries
+            .into_iter()
+            .map(|e| AutoRevertCountdown {
+                kind: e.kind,
+                remaining_secs: e.deadline.saturating_duration_since(now).as_secs_f64().ceil()
+                    as u64,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_and_takes_due_reverts_per_device() {
+        let now = Instant::now();
+        let mut schedule = RevertSchedule::default();
+        schedule.schedule("A", AutoRevertKind::GuardianPause, Duration::from_secs(600), now);
+        schedule.schedule("A", AutoRevertKind::ProximitySensor, Duration::from_secs(60), now);
+        schedule.schedule("B", AutoRevertKind::GuardianPause, Duration::from_secs(30), now);
+        // Rescheduling replaces the earlier timer
+        schedule.schedule("A", AutoRevertKind::GuardianPause, Duration::from_secs(300), now);
+
+        assert_eq!(schedule.next_deadline(now), Some(now + Duration::from_secs(30)));
+        assert_eq!(
+            schedule.countdown("A", now + Duration::from_millis(500)),
+            [
+                AutoRevertCountdown { kind: AutoRevertKind::ProximitySensor, remaining_secs: 60 },
+                AutoRevertCountdown { kind: AutoRevertKind::GuardianPause, remaining_secs: 300 },
+            ]
+        );
+
+        let later = now + Duration::from_secs(120);
+        assert_eq!(schedule.take_due("A", later), [AutoRevertKind::ProximitySensor]);
+        // B is due but not connected, it no longer drives the timer
+        assert_eq!(schedule.next_deadline(later), Some(now + Duration::from_secs(300)));
+        assert_eq!(schedule.take_due("B", later), [AutoRevertKind::GuardianPause]);
+
+        assert!(schedule.cancel("A", AutoRevertKind::GuardianPause));
+        assert!(!schedule.cancel("A", AutoRevertKind::GuardianPause));
+        assert_eq!(schedule.next_deadline(later), None);
+    }
+}
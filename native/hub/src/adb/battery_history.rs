@@ -0,0 +1,65 @@
+//! Battery levels of the headset and its controllers sampled while a device is connected.
+//!
+//! Samples are kept in memory per device serial so the UI can draw discharge curves over the
+//! session. Each device keeps at most `MAX_SAMPLES`, dropping the oldest first.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use crate::models::signals::adb::battery_history::BatterySample;
+
+/// How often the current device is sampled
+pub(super) const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+/// Samples kept per device, a day at `SAMPLE_INTERVAL`
+const MAX_SAMPLES: usize = 24 * 60;
+
+#[derive(Debug, Default)]
+pub(super) struct BatteryHistoryBuffer {
+    samples: HashMap<String, VecDeque<BatterySample>>,
+}
+
+impl BatteryHistoryBuffer {
+    /// Adds a sample of the device with `serial`, dropping its oldest one when full
+    pub(super) fn record(&mut self, serial: &str, sample: BatterySample) {
+        let samples = self.samples.entry(serial.to_string()).or_default();
+        if samples.len() == MAX_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// Samples of the device with `serial`, oldest first
+    pub(super) fn samples(&self, serial: &str) -> Vec<BatterySample> {
+        self.samples
+            .get(serial)
+            .map(|samples| samples.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: u64, headset: u8) -> BatterySample {
+        BatterySample { timestamp, headset, left_controller: Some(80), right_controller: None }
+    }
+
+    #[test]
+    fn keeps_samples_per_device_and_drops_the_oldest() {
+        let mut history = BatteryHistoryBuffer::default();
+        for i in 0..MAX_SAMPLES as u64 + 2 {
+            history.record("quest", sample(i, 100 - (i % 100) as u8));
+        }
+        history.record("other", sample(7, 50));
+
+        let samples = history.samples("quest");
+        assert_eq!(samples.len(), MAX_SAMPLES);
+        assert_eq!(samples[0].timestamp, 2);
+        assert_eq!(samples.last().unwrap().timestamp, MAX_SAMPLES as u64 + 1);
+        assert_eq!(history.samples("other"), [sample(7, 50)]);
+        assert!(history.samples("missing").is_empty());
+    }
+}
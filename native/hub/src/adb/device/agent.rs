@@ -0,0 +1,189 @@
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result, ensure};
+use const_format::concatcp;
+use serde::{Deserialize, de::DeserializeOwned};
+use tokio::time::sleep;
+use tracing::{debug, info, instrument, warn};
+
+use super::{AdbDevice, PackageUsage};
+use crate::{
+    models::{AppSize, apk_info::get_apk_info},
+    utils::resolve_binary_path,
+};
+
+/// Package name of the on-device helper
+pub(crate) const AGENT_PACKAGE: &str = "io.github.skrimix.yaas.agent";
+/// File name of the helper APK shipped next to the app
+const AGENT_APK_NAME: &str = "yaas-agent.apk";
+/// Port the helper listens on, bound to the device loopback interface only
+const AGENT_PORT: u16 = 47625;
+const AGENT_START_COMMAND: &str =
+    concatcp!("am start-foreground-service -n ", AGENT_PACKAGE, "/.AgentService");
+/// Time given to a freshly started helper before it is asked again
+const AGENT_START_DELAY: Duration = Duration::from_millis(500);
+
+/// Helper APK available for installation on devices
+#[derive(Debug, Clone)]
+pub(crate) struct AgentApk {
+    pub path: PathBuf,
+    pub version_code: u64,
+}
+
+impl AgentApk {
+    /// Finds the helper APK shipped with the app
+    #[instrument(level = "debug", ret, err)]
+    pub(crate) fn bundled() -> Result<Self> {
+        let path = resolve_binary_path(None, AGENT_APK_NAME)
+            .context("The device helper is not shipped with this build")?;
+        let info = get_apk_info(&path)?;
+        ensure!(
+            info.package_name == AGENT_PACKAGE,
+            "{} contains {} instead of the device helper",
+            path.display(),
+            info.package_name
+        );
+        Ok(Self { path, version_code: info.version_code.unwrap_or_default().into() })
+    }
+}
+
+/// Per-package data returned by the helper
+#[derive(Debug, Deserialize)]
+struct AgentResponse<T> {
+    packages: HashMap<String, T>,
+}
+
+/// Package change counter of the helper
+#[derive(Debug, Deserialize)]
+struct AgentChanges {
+    seq: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AgentUsage {
+    last_used: Option<String>,
+    total_time_used_secs: Option<u64>,
+}
+
+impl AdbDevice {
+    /// Version of the helper installed on the device, if any
+    pub(crate) fn agent_version(&self) -> Option<u64> {
+        self.installed_packages
+            .iter()
+            .find(|p| p.package_name() == AGENT_PACKAGE)
+            .map(|p| p.version_code())
+    }
+
+    /// Installs the helper, or upgrades it if an older version is installed, and starts it.
+    ///
+    /// Returns whether anything was installed.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn install_agent(&self, apk: &AgentApk) -> Result<bool> {
+        if let Some(installed) = self.agent_version()
+            && installed >= apk.version_code
+        {
+            debug!(installed, "Device helper is up to date");
+            return Ok(false);
+        }
+        info!(version = apk.version_code, "Installing device helper");
+        self.inner
+            .install_package(&apk.path, true, true, true)
+            .await
+            .context("Failed to install device helper")?;
+        if let Err(e) = self.shell_checked(AGENT_START_COMMAND).await {
+            warn!(error = %format!("{e:#}"), "Failed to start device helper after install");
+        }
+        Ok(true)
+    }
+
+    /// Removes the helper from the device
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn remove_agent(&self) -> Result<()> {
+        self.inner.uninstall_package(AGENT_PACKAGE).await.context("Failed to remove device helper")
+    }
+
+    /// Sends a request to the helper and returns its response, starting the helper if it does not
+    /// answer
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    async fn agent_request(&self, request: &str) -> Result<String> {
+        let command = format!("echo {request} | nc -w 5 127.0.0.1 {AGENT_PORT}");
+        match self.shell_checked(&command).await {
+            Ok(output) if !output.trim().is_empty() => return Ok(output),
+            Ok(_) => debug!("Device helper returned nothing, starting it"),
+            Err(e) => debug!(error = %format!("{e:#}"), "Device helper unreachable, starting it"),
+        }
+        self.shell_checked(AGENT_START_COMMAND).await.context("Failed to start device helper")?;
+        sleep(AGENT_START_DELAY).await;
+        let output = self.shell_checked(&command).await.context("Device helper did not respond")?;
+        ensure!(!output.trim().is_empty(), "Device helper returned an empty response");
+        Ok(output)
+    }
+
+    /// Queries app, data and cache sizes of all packages from the helper
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn agent_package_sizes(&self) -> Result<HashMap<String, AppSize>> {
+        parse_agent_response(&self.agent_request("sizes").await?)
+    }
+
+    /// Queries the helper's package change counter.
+    ///
+    /// The helper listens for package added, replaced and removed broadcasts and bumps the counter
+    /// on each, so a changed value means the package list is stale. The counter starts over when
+    /// the helper restarts.
+    #[instrument(level = "trace", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn agent_package_changes(&self) -> Result<u64> {
+        parse_agent_changes(&self.agent_request("changes").await?)
+    }
+
+    /// Queries per-package usage statistics from the helper
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn agent_usage_stats(&self) -> Result<HashMap<String, PackageUsage>> {
+        let usage: HashMap<String, AgentUsage> =
+            parse_agent_response(&self.agent_request("usage").await?)?;
+        Ok(usage
+            .into_iter()
+            .map(|(package, usage)| {
+                let AgentUsage { last_used, total_time_used_secs } = usage;
+                (package, PackageUsage { last_used, total_time_used_secs })
+            })
+            .collect())
+    }
+}
+
+fn parse_agent_response<T: DeserializeOwned>(output: &str) -> Result<HashMap<String, T>> {
+    let response: AgentResponse<T> =
+        serde_json::from_str(output.trim()).context("Failed to parse device helper response")?;
+    Ok(response.packages)
+}
+
+fn parse_agent_changes(output: &str) -> Result<u64> {
+    let changes: AgentChanges =
+        serde_json::from_str(output.trim()).context("Failed to parse device helper response")?;
+    Ok(changes.seq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_agent_responses() {
+        let sizes: HashMap<String, AppSize> = parse_agent_response(
+            r#"{"packages": {"com.example.game": {"app": 10, "data": 20, "cache": 30}}}"#,
+        )
+        .unwrap();
+        let size = &sizes["com.example.game"];
+        assert_eq!((size.app(), size.data(), size.cache()), (10, 20, 30));
+
+        let usage: HashMap<String, AgentUsage> = parse_agent_response(
+            "{\"packages\": {\"com.example.game\": {\"last_used\": \"2024-05-01 12:00:00\", \
+             \"total_time_used_secs\": 42}}}\n",
+        )
+        .unwrap();
+        assert_eq!(usage["com.example.game"].total_time_used_secs, Some(42));
+
+        assert_eq!(parse_agent_changes("{\"seq\": 7}\n").unwrap(), 7);
+
+        assert!(parse_agent_response::<AppSize>("nc: connect: Connection refused").is_err());
+    }
+}
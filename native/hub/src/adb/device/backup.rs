@@ -7,11 +7,18 @@ use tokio::fs::{self, File};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, instrument, warn};
 
-use super::AdbDevice;
+use super::{AdbDevice, local_source::is_dir_writable};
 use crate::{
     adb::{PACKAGE_NAME_REGEX, PackageName},
+    archive::{create_zip_from_dir, decompress_archive},
+    downloader::http_cache::compute_md5_file,
+    models::{
+        BACKUP_ARCHIVE_EXTENSION, BACKUP_MANIFEST_FILE, BackupContents, BackupFile, BackupManifest,
+        BackupSizes, apk_info::get_apk_info,
+    },
     utils::{
-        dir_has_any_files, first_subdirectory, remove_child_dir_if_exists, single_subdirectory,
+        dir_has_any_files, dir_size, first_subdirectory, list_files_relative,
+        remove_child_dir_if_exists, single_subdirectory,
     },
 };
 
@@ -28,12 +35,23 @@ pub(crate) struct BackupOptions {
     pub require_private_data: bool,
     /// Should backup OBB files
     pub backup_obb: bool,
+    /// Should pack the backup into a single `.yaasbackup` archive
+    pub compress: bool,
+}
+
+/// Result of a backup restore
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RestoreOutcome {
+    /// Package restored into, None if it could not be read from the backup APK
+    pub package: Option<String>,
+    /// The backup has private data that was not restored because `run-as` is unavailable
+    pub skipped_private_data: bool,
 }
 
 impl AdbDevice {
     /// Creates a backup of the given package.
     /// Returns `Ok(Some(path))` if backup was created, `Ok(None)` if nothing to back up.
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(crate) async fn backup_app(
         &self,
         package: &PackageName,
@@ -95,58 +113,64 @@ impl AdbDevice {
                 self.shell("rm -rf /sdcard/backup_tmp/").await?;
             }
 
-            // Private data via run-as
-            // Pipe through tar because run-as has weird permissions
-            debug!("Trying to backup private data");
-            fs::create_dir_all(&private_data_backup_path).await?;
-            let tmp_pkg = tmp_root.join(package_str);
-            let cmd = format!(
-                "mkdir -p '{tmp}'; run-as {pkg} tar -cf - -C '{priv_path}' . | tar -xvf - -C \
-                 '{tmp}'",
-                tmp = tmp_pkg.display(),
-                pkg = package_str,
-                priv_path = private_data_path.display(),
-            );
-            let cmd_output = await_or_cancel_backup(
-                &token,
-                &backup_path,
-                "run-as private data tar",
-                self.shell(&cmd),
-                async {
-                    let _ = self.shell("rm -rf /sdcard/backup_tmp/").await;
-                },
-            )
-            .await?;
-            if !cmd_output.is_empty() {
-                debug!("Command output: {}", cmd_output);
-            }
-            if options.require_private_data && cmd_output.contains("run-as:") {
-                bail!("Private data backup failed: run-as failed: {}", cmd_output);
-            }
-            await_or_cancel_backup(
-                &token,
-                &backup_path,
-                "pull private data",
-                self.pull_dir(&tmp_pkg, &private_data_backup_path),
-                async {
-                    let _ = self.shell("rm -rf /sdcard/backup_tmp/").await;
-                },
-            )
-            .await?;
-            let _ = self.shell("rm -rf /sdcard/backup_tmp/").await;
+            if self.capabilities.run_as {
+                // Private data via run-as
+                // Pipe through tar because run-as has weird permissions
+                debug!("Trying to backup private data");
+                fs::create_dir_all(&private_data_backup_path).await?;
+                let tmp_pkg = tmp_root.join(package_str);
+                let cmd = format!(
+                    "mkdir -p '{tmp}'; run-as {pkg} tar -cf - -C '{priv_path}' . | tar -xvf - -C \
+                     '{tmp}'",
+                    tmp = tmp_pkg.display(),
+                    pkg = package_str,
+                    priv_path = private_data_path.display(),
+                );
+                let cmd_output = await_or_cancel_backup(
+                    &token,
+                    &backup_path,
+                    "run-as private data tar",
+                    self.shell(&cmd),
+                    async {
+                        let _ = self.shell("rm -rf /sdcard/backup_tmp/").await;
+                    },
+                )
+                .await?;
+                if !cmd_output.is_empty() {
+                    debug!("Command output: {}", cmd_output);
+                }
+                if options.require_private_data && cmd_output.contains("run-as:") {
+                    bail!("Private data backup failed: run-as failed: {}", cmd_output);
+                }
+                await_or_cancel_backup(
+                    &token,
+                    &backup_path,
+                    "pull private data",
+                    self.pull_dir(&tmp_pkg, &private_data_backup_path),
+                    async {
+                        let _ = self.shell("rm -rf /sdcard/backup_tmp/").await;
+                    },
+                )
+                .await?;
+                let _ = self.shell("rm -rf /sdcard/backup_tmp/").await;
 
-            let private_pkg_dir = private_data_backup_path.join(package_str);
-            if private_pkg_dir.is_dir() {
-                let _ = remove_child_dir_if_exists(&private_pkg_dir, "cache").await;
-                let _ = remove_child_dir_if_exists(&private_pkg_dir, "code_cache").await;
-            }
+                let private_pkg_dir = private_data_backup_path.join(package_str);
+                if private_pkg_dir.is_dir() {
+                    let _ = remove_child_dir_if_exists(&private_pkg_dir, "cache").await;
+                    let _ = remove_child_dir_if_exists(&private_pkg_dir, "code_cache").await;
+                }
 
-            let has_private_files = dir_has_any_files(&private_data_backup_path).await?;
-            if !has_private_files {
-                debug!("No files in pulled private data, deleting");
-                let _ = fs::remove_dir_all(&private_data_backup_path).await;
+                let has_private_files = dir_has_any_files(&private_data_backup_path).await?;
+                if !has_private_files {
+                    debug!("No files in pulled private data, deleting");
+                    let _ = fs::remove_dir_all(&private_data_backup_path).await;
+                }
+                backup_empty &= !has_private_files;
+            } else if options.require_private_data {
+                bail!("Private data backup failed: run-as is not available on this device");
+            } else {
+                warn!("run-as is not available on this device, skipping private data backup");
             }
-            backup_empty &= !has_private_files;
 
             // Shared data
             if self.dir_exists(&shared_data_path).await? {
@@ -223,15 +247,76 @@ impl AdbDevice {
             return Ok(None);
         }
 
+        let files = record_backup_files(&backup_path).await?;
         // Marker file
         let _ = File::create(backup_path.join(".backup")).await?;
+        let installed = self.installed_packages.iter().find(|p| p.package_name() == package_str);
+        let manifest = BackupManifest {
+            package_name: package_str.to_string(),
+            display_name: display_name.map(str::to_string),
+            version_code: installed.map(|p| p.version_code()),
+            version_name: installed.map(|p| p.version_name().to_string()),
+            created_at: (now.unix_timestamp_nanos() / 1_000_000) as u64,
+            device_serial: Some(self.true_serial.clone()),
+            contents: BackupContents {
+                apk: find_backup_apk(&backup_path).await?.is_some(),
+                private_data: private_data_backup_path.is_dir(),
+                shared_data: shared_data_backup_path.is_dir(),
+                obb: obb_backup_path.is_dir(),
+            },
+            sizes: BackupSizes::from_files(files.iter().map(|f| (f.path.as_str(), f.size))),
+            files,
+        };
+        fs::write(backup_path.join(BACKUP_MANIFEST_FILE), manifest.to_json()?)
+            .await
+            .context("Failed to write backup manifest")?;
         info!(path = %backup_path.display(), "Backup created successfully");
+
+        if options.compress {
+            return compress_backup(&backup_path, &token).await.map(Some);
+        }
         Ok(Some(backup_path))
     }
 
-    /// Restores a backup from the given path
-    #[instrument(level = "debug", skip(self), err)]
-    pub(crate) async fn restore_backup(&self, backup_path: &Path) -> Result<()> {
+    /// Restores a backup from the given directory or `.yaasbackup` archive.
+    ///
+    /// With `target_package` the data is restored into that package instead of the one it was
+    /// backed up from. The target must already be installed, the APK from the backup is not.
+    ///
+    /// Returns the package restored into and whether private data had to be skipped.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn restore_backup(
+        &self,
+        backup_path: &Path,
+        target_package: Option<&PackageName>,
+    ) -> Result<RestoreOutcome> {
+        if backup_path.is_file() {
+            ensure!(is_backup_archive(backup_path), "Backup file is not a .yaasbackup archive");
+            // Extract next to the archive to stay on the same drive, unless it is read-only
+            let archive_dir = backup_path.parent().context("Failed to get backup directory")?;
+            let temp_dir_builder = tempfile::Builder::new().prefix(".yaas-restore-").clone();
+            let temp_dir = if is_dir_writable(archive_dir) {
+                temp_dir_builder.tempdir_in(archive_dir)
+            } else {
+                temp_dir_builder.tempdir()
+            }
+            .context("Failed to create temporary directory")?;
+
+            debug!(temp_dir = %temp_dir.path().display(), "Extracting backup archive");
+            decompress_archive(backup_path, temp_dir.path(), None, None, None)
+                .await
+                .context("Failed to extract backup archive")?;
+            let extracted = if temp_dir.path().join(".backup").exists() {
+                temp_dir.path().to_path_buf()
+            } else {
+                single_subdirectory(temp_dir.path())
+                    .await?
+                    .filter(|dir| dir.join(".backup").exists())
+                    .context("Backup archive does not contain a backup")?
+            };
+            return Box::pin(self.restore_backup(&extracted, target_package)).await;
+        }
+
         ensure!(backup_path.is_dir(), "Backup path is not a directory");
         ensure!(backup_path.join(".backup").exists(), "Backup marker not found (.backup)");
 
@@ -239,61 +324,64 @@ impl AdbDevice {
         let private_data_backup_path = backup_path.join("data_private");
         let obb_backup_path = backup_path.join("obb");
 
+        // The APK is staged on the device before installation and private data is pushed to a
+        // temporary directory before extraction, so both briefly take up space twice
+        let apk_size = match find_backup_apk(backup_path).await? {
+            Some(apk) => fs::metadata(&apk).await.context("Failed to read APK size")?.len(),
+            None => 0,
+        };
+        let required = dir_size(backup_path)
+            .await?
+            .saturating_add(apk_size)
+            .saturating_add(dir_size(&private_data_backup_path).await?);
+        self.ensure_free_space("backup restore", required).await?;
+
         // Restore APK
-        {
-            let mut apk_candidate: Option<PathBuf> = None;
-            if backup_path.is_dir() {
-                let mut rd = fs::read_dir(backup_path).await?;
-                while let Some(entry) = rd.next_entry().await? {
-                    if entry.file_type().await.map(|t| t.is_file()).unwrap_or(false)
-                        && entry
-                            .path()
-                            .extension()
-                            .and_then(|e| e.to_str())
-                            .is_some_and(|e| e.eq_ignore_ascii_case("apk"))
-                    {
-                        apk_candidate = Some(entry.path());
-                        break;
-                    }
+        let restored_package = if let Some(target) = target_package {
+            info!(target = %target, "Restoring into another package, skipping APK");
+            let _ = self.get_apk_path(target).await.with_context(|| {
+                format!("Package '{target}' must be installed to restore a backup into it")
+            })?;
+            Some(target.to_string())
+        } else if let Some(apk) = find_backup_apk(backup_path).await? {
+            info!(apk = %apk.display(), "Restoring APK");
+            // Use direct install without any special handling
+            self.inner
+                .install_package(&apk, true, true, true)
+                .await
+                .context("Failed to install APK during restore")?;
+            get_apk_info(&apk)
+                .inspect_err(|e| debug!(error = %format!("{e:#}"), "Not reading restored package"))
+                .ok()
+                .map(|info| info.package_name)
+        } else {
+            // If there is no APK in the backup, ensure the app is already installed
+            // Try to infer the package name from any backup subfolder (private/shared/obb)
+            let mut candidate_pkg: Option<String> = None;
+            for dir in [&private_data_backup_path, &shared_data_backup_path, &obb_backup_path] {
+                if dir.is_dir()
+                    && let Some(sub) = first_subdirectory(dir).await?
+                    && let Some(name) = sub.file_name().and_then(|n| n.to_str())
+                    && PACKAGE_NAME_REGEX.is_match(name)
+                {
+                    candidate_pkg = Some(name.to_string());
+                    break;
                 }
             }
-            if let Some(apk) = apk_candidate {
-                info!(apk = %apk.display(), "Restoring APK");
-                // Use direct install without any special handling
-                self.inner
-                    .install_package(&apk, true, true, true)
-                    .await
-                    .context("Failed to install APK during restore")?;
+            if let Some(pkg) = candidate_pkg {
+                let pkg = PackageName::parse(&pkg)
+                    .context("Inferred invalid package name from backup directory")?;
+                let _ = self.get_apk_path(&pkg).await.with_context(|| {
+                    format!("Backup does not contain an APK and package '{pkg}' is not installed")
+                })?;
+                Some(pkg.to_string())
             } else {
-                // If there is no APK in the backup, ensure the app is already installed
-                // Try to infer the package name from any backup subfolder (private/shared/obb)
-                let mut candidate_pkg: Option<String> = None;
-                for dir in [&private_data_backup_path, &shared_data_backup_path, &obb_backup_path] {
-                    if dir.is_dir()
-                        && let Some(sub) = first_subdirectory(dir).await?
-                        && let Some(name) = sub.file_name().and_then(|n| n.to_str())
-                        && PACKAGE_NAME_REGEX.is_match(name)
-                    {
-                        candidate_pkg = Some(name.to_string());
-                        break;
-                    }
-                }
-                if let Some(pkg) = candidate_pkg {
-                    let pkg = PackageName::parse(&pkg)
-                        .context("Inferred invalid package name from backup directory")?;
-                    let _ = self.get_apk_path(&pkg).await.with_context(|| {
-                        format!(
-                            "Backup does not contain an APK and package '{pkg}' is not installed"
-                        )
-                    })?;
-                } else {
-                    bail!(
-                        "Backup does not contain an APK and no package folder was found to infer \
-                         the package name"
-                    );
-                }
+                bail!(
+                    "Backup does not contain an APK and no package folder was found to infer the \
+                     package name"
+                );
             }
-        }
+        };
 
         // Restore OBB
         if obb_backup_path.is_dir()
@@ -301,7 +389,14 @@ impl AdbDevice {
         {
             debug!("Restoring OBB");
             let remote_parent = UnixPath::new("/sdcard/Android/obb");
-            self.push_dir(&pkg_dir, remote_parent, true).await?;
+            match target_package {
+                Some(target) => {
+                    let remote_dir = remote_parent.join(target.as_str());
+                    self.push_dir_to_path(&pkg_dir, &remote_dir, true).await?;
+                    self.retarget_obb_files(&pkg_dir, &remote_dir, target).await?;
+                }
+                None => self.push_dir(&pkg_dir, remote_parent, true).await?,
+            }
         }
 
         // Restore shared data
@@ -310,23 +405,36 @@ impl AdbDevice {
         {
             debug!("Restoring shared data");
             let remote_parent = UnixPath::new("/sdcard/Android/data");
-            self.push_dir(&pkg_dir, remote_parent, true).await?;
+            match target_package {
+                Some(target) => {
+                    let remote_dir = remote_parent.join(target.as_str());
+                    self.push_dir_to_path(&pkg_dir, &remote_dir, true).await?;
+                }
+                None => self.push_dir(&pkg_dir, remote_parent, true).await?,
+            }
         }
 
         // Restore private data
-        if private_data_backup_path.is_dir()
+        let skipped_private_data = private_data_backup_path.is_dir() && !self.capabilities.run_as;
+        if skipped_private_data {
+            warn!("run-as is not available on this device, skipping private data restore");
+        } else if private_data_backup_path.is_dir()
             && let Some(pkg_dir) = single_subdirectory(&private_data_backup_path).await?
         {
-            let package_name = pkg_dir
-                .file_name()
-                .and_then(|n| n.to_str())
-                .context("Failed to get private data package name")?;
+            let package_name = match target_package {
+                Some(target) => target.as_str(),
+                None => pkg_dir
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .context("Failed to get private data package name")?,
+            };
 
             debug!("Restoring private data");
             // Push to temporary dir
             let _ = self.shell("rm -rf /sdcard/restore_tmp/").await;
             self.shell("mkdir -p /sdcard/restore_tmp/").await?;
-            self.push_dir(&pkg_dir, UnixPath::new("/sdcard/restore_tmp/"), false).await?;
+            let remote_dir = UnixPath::new("/sdcard/restore_tmp/").join(package_name);
+            self.push_dir_to_path(&pkg_dir, &remote_dir, false).await?;
 
             // Pipe through tar because run-as has weird permissions
             let cmd = format!(
@@ -337,11 +445,121 @@ impl AdbDevice {
             self.shell(&cmd).await?;
         }
 
-        info!("Backup restored successfully");
+        info!(skipped_private_data, "Backup restored");
+        Ok(RestoreOutcome { package: restored_package, skipped_private_data })
+    }
+
+    /// Renames restored OBB files that carry the original package name to `target`
+    async fn retarget_obb_files(
+        &self,
+        local_dir: &Path,
+        remote_dir: &UnixPath,
+        target: &PackageName,
+    ) -> Result<()> {
+        let Some(source_package) = local_dir.file_name().and_then(|n| n.to_str()) else {
+            return Ok(());
+        };
+        let mut rd = fs::read_dir(local_dir).await?;
+        while let Some(entry) = rd.next_entry().await? {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(renamed) = retarget_obb_name(&file_name, source_package, target.as_str()) {
+                debug!(from = %file_name, to = %renamed, "Renaming restored OBB file");
+                self.shell_checked(&format!(
+                    "mv '{dir}/{file_name}' '{dir}/{renamed}'",
+                    dir = remote_dir.display()
+                ))
+                .await
+                .with_context(|| format!("Failed to rename OBB file {file_name}"))?;
+            }
+        }
         Ok(())
     }
 }
 
+/// Renames an OBB file named after `from` (`main.<version>.<package>.obb`) to package `to`
+fn retarget_obb_name(file_name: &str, from: &str, to: &str) -> Option<String> {
+    let stem = file_name.strip_suffix(".obb")?;
+    let prefix = stem.strip_suffix(from)?.strip_suffix('.')?;
+    (from != to && !prefix.is_empty()).then(|| format!("{prefix}.{to}.obb"))
+}
+
+/// Returns whether the path has the single-file backup extension
+pub(crate) fn is_backup_archive(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(BACKUP_ARCHIVE_EXTENSION))
+}
+
+/// Packs a backup directory into a `.yaasbackup` archive next to it and removes the directory.
+///
+/// If packing fails for any reason other than cancellation, the directory is kept as is.
+#[instrument(level = "debug", skip(token), err)]
+async fn compress_backup(backup_path: &Path, token: &CancellationToken) -> Result<PathBuf> {
+    let parent = backup_path.parent().context("Backup path has no parent")?;
+    let name = backup_path
+        .file_name()
+        .context("Backup path has no file name")?
+        .to_string_lossy()
+        .into_owned();
+    let archive_path = parent.join(format!("{name}.{BACKUP_ARCHIVE_EXTENSION}"));
+    let partial_name = format!(".{name}.{BACKUP_ARCHIVE_EXTENSION}.partial");
+
+    debug!(archive = %archive_path.display(), "Compressing backup");
+    match create_zip_from_dir(backup_path, parent, &partial_name, Some(token.clone())).await {
+        Ok(partial_path) => {
+            fs::rename(&partial_path, &archive_path)
+                .await
+                .context("Failed to move compressed backup into place")?;
+            fs::remove_dir_all(backup_path)
+                .await
+                .context("Failed to remove uncompressed backup directory")?;
+            info!(path = %archive_path.display(), "Backup compressed");
+            Ok(archive_path)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(parent.join(&partial_name)).await;
+            if token.is_cancelled() {
+                let _ = fs::remove_dir_all(backup_path).await;
+                return Err(anyhow!("Backup cancelled"));
+            }
+            warn!(
+                error = e.as_ref() as &dyn std::error::Error,
+                "Failed to compress backup, keeping it as a directory"
+            );
+            Ok(backup_path.to_path_buf())
+        }
+    }
+}
+
+/// Lists the files of a new backup with their sizes and hashes for the manifest
+#[instrument(level = "debug", err)]
+async fn record_backup_files(backup_path: &Path) -> Result<Vec<BackupFile>> {
+    let mut files = Vec::new();
+    for (path, size) in list_files_relative(backup_path).await? {
+        let md5 = compute_md5_file(&backup_path.join(&path)).await?;
+        files.push(BackupFile { path, size, md5: Some(md5) });
+    }
+    debug!(count = files.len(), "Recorded backup files");
+    Ok(files)
+}
+
+/// Finds the APK file at the top level of a backup directory
+async fn find_backup_apk(backup_path: &Path) -> Result<Option<PathBuf>> {
+    let mut rd = fs::read_dir(backup_path).await?;
+    while let Some(entry) = rd.next_entry().await? {
+        if entry.file_type().await.map(|t| t.is_file()).unwrap_or(false)
+            && entry
+                .path()
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("apk"))
+        {
+            return Ok(Some(entry.path()));
+        }
+    }
+    Ok(None)
+}
+
 /// Awaits a future or, if cancellation is requested, deletes the incomplete backup directory and
 /// runs cleanup, then returns a cancellation error.
 #[instrument(level = "debug", skip(token, fut, backup_path, cleanup), fields(op = op_name), err)]
@@ -366,3 +584,26 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retargets_obb_file_names() {
+        let from = "com.example.game";
+        let to = "com.example.game.beta";
+        assert_eq!(
+            retarget_obb_name("main.42.com.example.game.obb", from, to).as_deref(),
+            Some("main.42.com.example.game.beta.obb")
+        );
+        assert_eq!(
+            retarget_obb_name("patch.7.com.example.game.obb", from, to).as_deref(),
+            Some("patch.7.com.example.game.beta.obb")
+        );
+        assert_eq!(retarget_obb_name("data.obb", from, to), None);
+        assert_eq!(retarget_obb_name("com.example.game.obb", from, to), None);
+        assert_eq!(retarget_obb_name("main.1.com.other.obb", from, to), None);
+        assert_eq!(retarget_obb_name("main.1.com.example.game.obb", from, from), None);
+    }
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tracing::{debug, instrument};
+
+use super::AdbDevice;
+use crate::{
+    adb::PackageName,
+    models::signals::adb::backup_estimate::{BackupEstimateResponse, BackupPartEstimate},
+};
+
+/// Sustained `adb pull` throughput over USB, in bytes per second
+const USB_PULL_RATE: f64 = 25.0 * 1024.0 * 1024.0;
+/// Sustained `adb pull` throughput over wireless ADB, in bytes per second
+const WIRELESS_PULL_RATE: f64 = 5.0 * 1024.0 * 1024.0;
+/// Fixed cost of pulling one file over USB, a round trip per file dominates for small files
+const USB_FILE_OVERHEAD: Duration = Duration::from_millis(3);
+const WIRELESS_FILE_OVERHEAD: Duration = Duration::from_millis(15);
+/// Rate private data is staged to shared storage through `run-as tar` before it is pulled
+const STAGING_RATE: f64 = 100.0 * 1024.0 * 1024.0;
+
+/// Prints `<part> <KiB> <files>` for a path if it exists
+const SIZE_OF_FUNCTION: &str = "size_of() { [ -e \"$2\" ] && echo \"$1 $(du -sk \"$2\" | cut -f1) \
+                                $(find \"$2\" -type f | wc -l)\"; }";
+
+/// Sizes of a backup part measured on the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PartSize {
+    bytes: u64,
+    files: u64,
+}
+
+impl AdbDevice {
+    /// Measures what a backup of `package` would pull and how long that would take.
+    ///
+    /// Sizes come from `du`, private data is measured through `run-as` where available. Times are
+    /// based on typical pull rates of the current connection, so they are rough.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn estimate_backup(
+        &self,
+        package: &PackageName,
+    ) -> Result<BackupEstimateResponse> {
+        let mut script = format!(
+            "{SIZE_OF_FUNCTION}; size_of apk \"$(pm path {package} | head -n 1 | cut -d: -f2)\"; \
+             size_of shared /sdcard/Android/data/{package}; size_of obb \
+             /sdcard/Android/obb/{package}"
+        );
+        if self.capabilities.run_as {
+            script.push_str(&format!(
+                "; run-as {package} sh -c '{SIZE_OF_FUNCTION}; size_of private \
+                 /data/data/{package}'"
+            ));
+        }
+        let output = self.shell(&script).await.context("Failed to measure backup size")?;
+        debug!(output, "Measured backup parts");
+
+        let estimate = |part: &str, staged: bool| {
+            parse_part_size(&output, part).map(|size| BackupPartEstimate {
+                bytes: size.bytes,
+                files: size.files,
+                secs: pull_secs(size, self.is_wireless, staged),
+            })
+        };
+        Ok(BackupEstimateResponse {
+            package_name: package.to_string(),
+            apk: estimate("apk", false),
+            private_data: estimate("private", true),
+            shared_data: estimate("shared", false),
+            obb: estimate("obb", false),
+            error: None,
+        })
+    }
+}
+
+/// Finds the `<part> <KiB> <files>` line of `part` in the measurement output
+fn parse_part_size(output: &str, part: &str) -> Option<PartSize> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.next()? != part {
+            return None;
+        }
+        let kib: u64 = fields.next()?.parse().ok()?;
+        let files = fields.next()?.parse().ok()?;
+        Some(PartSize { bytes: kib * 1024, files })
+    })
+}
+
+/// Expected seconds to pull a part, rounded up
+fn pull_secs(size: PartSize, wireless: bool, staged: bool) -> u64 {
+    let (rate, per_file) = if wireless {
+        (WIRELESS_PULL_RATE, WIRELESS_FILE_OVERHEAD)
+    } else {
+        (USB_PULL_RATE, USB_FILE_OVERHEAD)
+    };
+    let mut secs = size.bytes as f64 / rate + per_file.as_secs_f64() * size.files as f64;
+    if staged {
+        secs += size.bytes as f64 / STAGING_RATE;
+    }
+    secs.ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_part_sizes() {
+        let output = "apk 102400 1\nshared 2048 3\nrun-as: package not debuggable: com.example\n";
+        assert_eq!(parse_part_size(output, "apk"), Some(PartSize { bytes: 100 << 20, files: 1 }));
+        assert_eq!(parse_part_size(output, "shared"), Some(PartSize { bytes: 2 << 20, files: 3 }));
+        assert_eq!(parse_part_size(output, "private"), None);
+        assert_eq!(parse_part_size("obb  0\n", "obb"), None);
+    }
+
+    #[test]
+    fn estimates_pull_time_by_connection() {
+        let size = PartSize { bytes: 250 << 20, files: 1000 };
+        assert_eq!(pull_secs(size, false, false), 13);
+        assert_eq!(pull_secs(size, false, true), 16);
+        assert_eq!(pull_secs(size, true, false), 65);
+        assert_eq!(pull_secs(PartSize { bytes: 0, files: 0 }, true, true), 0);
+    }
+}
@@ -0,0 +1,162 @@
+use std::{collections::HashSet, fs::File, io::Write, path::Path};
+
+use anyhow::{Context, Result};
+use time::{OffsetDateTime, macros::format_description};
+use tracing::{debug, instrument};
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+use super::AdbDevice;
+use crate::adb::PackageName;
+
+/// Dropbox tags of app crashes, ANRs and native crash tombstones
+const CRASH_DROPBOX_TAGS: &str =
+    "data_app_crash data_app_anr data_app_native_crash SYSTEM_TOMBSTONE";
+/// Line `dumpsys dropbox --print` prints before every entry
+const DROPBOX_ENTRY_SEPARATOR: &str = "========================================";
+
+/// File name for a new crash report bundle of `package`
+pub(crate) fn crash_report_file_name(package: &PackageName) -> String {
+    let fmt = format_description!("[year]-[month]-[day]_[hour]-[minute]-[second]");
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    let timestamp = now.format(&fmt).unwrap_or_else(|_| now.unix_timestamp().to_string());
+    format!("crash_{package}_{timestamp}.zip")
+}
+
+impl AdbDevice {
+    /// Collects crash information about `package` into a zip bundle at `bundle_path`.
+    ///
+    /// The bundle holds the package's lines from the crash log buffer and its crash, ANR and
+    /// tombstone entries from the dropbox service. Unlike `/data/tombstones` and `/data/anr`, both
+    /// are readable without root.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn collect_crash_report(
+        &self,
+        package: &PackageName,
+        bundle_path: &Path,
+    ) -> Result<()> {
+        let device_info = self
+            .shell(&format!(
+                "getprop ro.build.fingerprint; dumpsys package {package} | grep -E \
+                 'versionCode|versionName|firstInstallTime|lastUpdateTime'"
+            ))
+            .await
+            .context("Failed to read device and package info")?;
+        let crash_log = self
+            .shell_checked("logcat -d -b crash -v threadtime")
+            .await
+            .context("Failed to read crash log")?;
+        let dropbox = self
+            .shell_checked(&format!("dumpsys dropbox --print {CRASH_DROPBOX_TAGS}"))
+            .await
+            .context("Failed to read crash entries from dropbox")?;
+
+        let crash_log = package_crash_log(&crash_log, package.as_str());
+        let dropbox = package_dropbox_entries(&dropbox, package.as_str());
+        debug!(
+            crash_log_lines = crash_log.lines().count(),
+            dropbox_entries = dropbox.len(),
+            "Collected crash information"
+        );
+
+        let files = [
+            ("device.txt", format!("{self}\n{}", device_info.trim())),
+            ("crash_log.txt", crash_log),
+            ("dropbox.txt", dropbox.join("\n")),
+        ];
+        let bundle_path = bundle_path.to_path_buf();
+        tokio::task::spawn_blocking(move || write_bundle(&bundle_path, &files)).await?
+    }
+}
+
+fn write_bundle(path: &Path, files: &[(&str, String)]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = ZipWriter::new(file);
+    for (name, content) in files {
+        zip.start_file(*name, SimpleFileOptions::default())?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish().context("Failed to write crash report bundle")?;
+    Ok(())
+}
+
+/// Keeps the lines of `threadtime` formatted crash log output written by processes of `package`.
+///
+/// Crashes name the package in their header (`Process: <package>, PID: <pid>` for Java crashes,
+/// `>>> <package> <<<` for native ones), every other line is matched by the process ID.
+fn package_crash_log(output: &str, package: &str) -> String {
+    let java_header = format!("Process: {package}, PID: ");
+    let native_header = format!(">>> {package} <<<");
+    let pids = output
+        .lines()
+        .filter(|line| line.contains(&java_header) || line.contains(&native_header))
+        .filter_map(line_pid)
+        .collect::<HashSet<_>>();
+    output
+        .lines()
+        .filter(|line| line_pid(line).is_some_and(|pid| pids.contains(pid)))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+/// Returns the PID column of a `threadtime` formatted log line
+fn line_pid(line: &str) -> Option<&str> {
+    line.split_whitespace().nth(2).filter(|pid| pid.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Returns the dropbox entries mentioning `package`
+fn package_dropbox_entries(output: &str, package: &str) -> Vec<String> {
+    output
+        .split(DROPBOX_ENTRY_SEPARATOR)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty() && entry.contains(package))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_crash_lines_of_package_processes() {
+        let output = "\
+--------- beginning of crash
+05-01 12:00:00.100  1234  1234 E AndroidRuntime: FATAL EXCEPTION: main
+05-01 12:00:00.100  1234  1234 E AndroidRuntime: Process: com.example.game, PID: 1234
+05-01 12:00:00.101  1234  1234 E AndroidRuntime: java.lang.IllegalStateException: boom
+05-01 12:01:00.000  2222  2222 E AndroidRuntime: Process: com.example.other, PID: 2222
+05-01 12:02:00.000  3333  3333 F DEBUG   : pid: 3333, tid: 3340, name: UnityMain  >>> \
+                      com.example.game <<<
+05-01 12:02:00.001  3333  3333 F DEBUG   : signal 11 (SIGSEGV)
+";
+        let log = package_crash_log(output, "com.example.game");
+        assert_eq!(log.lines().count(), 5);
+        assert!(log.contains("IllegalStateException"));
+        assert!(log.contains("SIGSEGV"));
+        assert!(!log.contains("com.example.other"));
+        assert!(package_crash_log(output, "com.example.missing").is_empty());
+    }
+
+    #[test]
+    fn filters_dropbox_entries_by_package() {
+        let output = "\
+Drop box contents: 2 entries
+Max entries: 1000
+========================================
+2024-05-01 12:00:00 data_app_crash (text, 812 bytes)
+Process: com.example.game
+java.lang.IllegalStateException: boom
+========================================
+2024-05-01 12:05:00 data_app_anr (text, 420 bytes)
+Process: com.example.other
+";
+        let entries = package_dropbox_entries(output, "com.example.game");
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].starts_with("2024-05-01 12:00:00 data_app_crash"));
+    }
+}
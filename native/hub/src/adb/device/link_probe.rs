@@ -0,0 +1,101 @@
+//! Quick latency and throughput measurement of a wireless ADB connection.
+//!
+//! Wi‑Fi links to a headset vary from faster than USB 2 to a few hundred KB/s, so large
+//! wireless sideloads are preceded by a few shell round trips and a small timed push to tell
+//! the user what to expect.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use forensic_adb::UnixPath;
+use tracing::{debug, instrument};
+
+use super::AdbDevice;
+
+/// Shell round trips timed for the latency
+const ROUND_TRIPS: usize = 5;
+/// Size of the timed push
+const PROBE_BYTES: usize = 2 * 1024 * 1024;
+const PROBE_PATH: &str = "/data/local/tmp/yaas_link_probe";
+/// Links below this push speed are reported as slow
+const SLOW_THROUGHPUT: u64 = 2_000_000;
+/// Links above this round trip time are reported as slow
+const SLOW_LATENCY: Duration = Duration::from_millis(150);
+
+/// Measured quality of the connection to a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LinkQuality {
+    /// Median shell round trip time
+    pub latency: Duration,
+    /// Push speed in bytes per second
+    pub throughput: u64,
+}
+
+impl LinkQuality {
+    fn from_measurements(
+        mut round_trips: Vec<Duration>,
+        pushed: usize,
+        push_time: Duration,
+    ) -> Self {
+        round_trips.sort_unstable();
+        let latency = round_trips.get(round_trips.len() / 2).copied().unwrap_or_default();
+        let push_millis = push_time.as_millis().max(1);
+        Self { latency, throughput: (pushed as u128 * 1000 / push_millis) as u64 }
+    }
+
+    /// Whether large transfers over this link are likely to be painfully slow
+    pub(crate) fn is_slow(&self) -> bool {
+        self.throughput < SLOW_THROUGHPUT || self.latency > SLOW_LATENCY
+    }
+
+    /// Expected time to push `bytes` over this link
+    pub(crate) fn transfer_time(&self, bytes: u64) -> Duration {
+        Duration::from_secs(bytes.div_ceil(self.throughput.max(1)))
+    }
+}
+
+impl AdbDevice {
+    /// Times a few shell round trips and a small push to estimate the link quality
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err, ret)]
+    pub(crate) async fn probe_link(&self) -> Result<LinkQuality> {
+        let mut round_trips = Vec::with_capacity(ROUND_TRIPS);
+        for _ in 0..ROUND_TRIPS {
+            let start = Instant::now();
+            self.shell_checked("echo").await.context("Link probe round trip failed")?;
+            round_trips.push(start.elapsed());
+        }
+
+        let data = vec![0x5a; PROBE_BYTES];
+        let start = Instant::now();
+        self.push_bytes(&data, UnixPath::new(PROBE_PATH))
+            .await
+            .context("Link probe push failed")?;
+        let push_time = start.elapsed();
+        if let Err(e) = self.shell(&format!("rm -f {PROBE_PATH}")).await {
+            debug!(error = %format!("{e:#}"), "Failed to remove link probe file");
+        }
+
+        Ok(LinkQuality::from_measurements(round_trips, PROBE_BYTES, push_time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_median_latency_and_push_speed() {
+        let millis = Duration::from_millis;
+        let quality = LinkQuality::from_measurements(
+            vec![millis(40), millis(12), millis(300), millis(10), millis(15)],
+            2_000_000,
+            millis(500),
+        );
+        assert_eq!(quality, LinkQuality { latency: millis(15), throughput: 4_000_000 });
+        assert!(!quality.is_slow());
+        assert_eq!(quality.transfer_time(1_000_000_000), Duration::from_secs(250));
+
+        assert!(LinkQuality { throughput: 1_000_000, ..quality }.is_slow());
+        assert!(LinkQuality { latency: millis(200), ..quality }.is_slow());
+    }
+}
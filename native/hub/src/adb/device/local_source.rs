@@ -0,0 +1,153 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use tracing::{debug, instrument};
+
+/// Entry of a local directory that is reached through a symbolic link or junction.
+///
+/// Directory pushes do not follow links, so these entries have to be pushed separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct LinkedEntry {
+    /// Path of the link relative to the scanned directory
+    pub relative: PathBuf,
+    /// Canonical path the link resolves to
+    pub target: PathBuf,
+    pub is_dir: bool,
+}
+
+/// Local directory prepared for pushing
+#[derive(Debug)]
+pub(super) struct LocalSourceTree {
+    /// Canonical path of the directory
+    pub root: PathBuf,
+    /// Linked entries, parents before the entries nested in them
+    pub links: Vec<LinkedEntry>,
+}
+
+/// Resolves a local directory and checks that everything in it can be pushed.
+///
+/// Links are resolved and followed, failing on broken links, links that point back to one of
+/// their parent directories and entries that are neither files nor directories (pipes, sockets,
+/// devices), instead of failing halfway through the push.
+#[instrument(level = "debug", err)]
+pub(super) async fn scan_source_tree(path: &Path) -> Result<LocalSourceTree> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let root = fs::canonicalize(&path).with_context(|| {
+            format!("Failed to resolve '{}' (broken link or inaccessible location)", path.display())
+        })?;
+        let mut links = Vec::new();
+        let mut ancestors = vec![root.clone()];
+        scan_dir(&root, Path::new(""), &mut ancestors, &mut links)?;
+        links.sort_by_key(|link| link.relative.components().count());
+        debug!(root = %root.display(), links = links.len(), "Scanned local source");
+        Ok(LocalSourceTree { root, links })
+    })
+    .await
+    .context("Source scan task failed")?
+}
+
+fn scan_dir(
+    dir: &Path,
+    relative: &Path,
+    ancestors: &mut Vec<PathBuf>,
+    links: &mut Vec<LinkedEntry>,
+) -> Result<()> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read '{}'", dir.display()))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read '{}'", dir.display()))?;
+        let path = entry.path();
+        let entry_relative = relative.join(entry.file_name());
+        let file_type = entry
+            .file_type()
+            .with_context(|| format!("Failed to read file type of '{}'", path.display()))?;
+
+        if file_type.is_symlink() {
+            let target = fs::canonicalize(&path)
+                .with_context(|| format!("Broken link '{}'", path.display()))?;
+            let metadata = fs::metadata(&target)
+                .with_context(|| format!("Failed to read '{}'", target.display()))?;
+            if metadata.is_dir() {
+                if ancestors.iter().any(|ancestor| ancestor.starts_with(&target)) {
+                    bail!(
+                        "Link '{}' points to its own parent folder '{}'",
+                        path.display(),
+                        target.display()
+                    );
+                }
+                links.push(LinkedEntry {
+                    relative: entry_relative.clone(),
+                    target: target.clone(),
+                    is_dir: true,
+                });
+                ancestors.push(target.clone());
+                // Links inside the linked directory are relative to the link itself
+                let mut nested = Vec::new();
+                scan_dir(&target, &entry_relative, ancestors, &mut nested)?;
+                ancestors.pop();
+                links.extend(nested);
+            } else if metadata.is_file() {
+                links.push(LinkedEntry { relative: entry_relative, target, is_dir: false });
+            } else {
+                bail!("Link '{}' points to an unsupported file type", path.display());
+            }
+        } else if file_type.is_dir() {
+            ancestors.push(path.clone());
+            scan_dir(&path, &entry_relative, ancestors, links)?;
+            ancestors.pop();
+        } else if !file_type.is_file() {
+            bail!("Unsupported file type (not a regular file or folder): '{}'", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether new files can be created in a local directory
+pub(super) fn is_dir_writable(dir: &Path) -> bool {
+    tempfile::tempfile_in(dir).is_ok()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::symlink;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn scan_resolves_nested_links_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        fs::create_dir_all(shared.join("inner")).unwrap();
+        fs::write(shared.join("inner/data.obb"), b"obb").unwrap();
+        fs::write(dir.path().join("main.obb"), b"main").unwrap();
+        symlink(dir.path().join("main.obb"), shared.join("main-link.obb")).unwrap();
+
+        let app = dir.path().join("app");
+        fs::create_dir_all(&app).unwrap();
+        symlink(&shared, app.join("linked")).unwrap();
+
+        let tree = scan_source_tree(&app).await.unwrap();
+        let relative = tree.links.iter().map(|l| l.relative.clone()).collect::<Vec<_>>();
+        assert_eq!(relative, vec![PathBuf::from("linked"), PathBuf::from("linked/main-link.obb")]);
+        assert!(tree.links[0].is_dir);
+        assert!(!tree.links[1].is_dir);
+    }
+
+    #[tokio::test]
+    async fn scan_rejects_broken_and_looping_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app");
+        fs::create_dir_all(app.join("sub")).unwrap();
+        symlink(&app, app.join("sub/loop")).unwrap();
+        assert!(scan_source_tree(&app).await.is_err());
+
+        fs::remove_file(app.join("sub/loop")).unwrap();
+        symlink(dir.path().join("missing"), app.join("sub/broken")).unwrap();
+        let err = scan_source_tree(&app).await.unwrap_err();
+        assert!(format!("{err:#}").contains("Broken link"));
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use tracing::instrument;
+
+use super::AdbDevice;
+
+/// Log tags of the services involved in installing and removing packages
+const INSTALL_LOG_TAGS: &[&str] = &[
+    "PackageManager",
+    "PackageInstaller",
+    "PackageInstallerSession",
+    "InstallPackageHelper",
+    "PackageParsing",
+    "installd",
+    "pm",
+];
+
+/// Position in the device log, used to collect lines written after it
+#[derive(Debug, Clone)]
+pub(crate) struct LogcatMark {
+    /// Device time in seconds since the epoch
+    since: u64,
+}
+
+impl AdbDevice {
+    /// Marks the current position in the device log
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn logcat_mark(&self) -> Result<LogcatMark> {
+        let output = self.shell_checked("date +%s").await?;
+        let since = output.trim().parse().context("Failed to parse device time")?;
+        Ok(LogcatMark { since })
+    }
+
+    /// Returns package manager and installer log lines written since `mark`
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn install_logcat_since(&self, mark: &LogcatMark) -> Result<Vec<String>> {
+        let output = self
+            .shell_checked(&install_logcat_command(mark))
+            .await
+            .context("Failed to read device log")?;
+        Ok(output
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with("--------- beginning of"))
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+fn install_logcat_command(mark: &LogcatMark) -> String {
+    // `-t` with a time prints everything from that time on and exits
+    format!("logcat -v threadtime -t '{}.000' -s {}", mark.since, INSTALL_LOG_TAGS.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_filtered_logcat_command() {
+        assert_eq!(
+            install_logcat_command(&LogcatMark { since: 1_700_000_000 }),
+            "logcat -v threadtime -t '1700000000.000' -s PackageManager PackageInstaller \
+             PackageInstallerSession InstallPackageHelper PackageParsing installd pm"
+        );
+    }
+}
@@ -1,36 +1,64 @@
+mod agent;
 mod backup;
+mod backup_estimate;
+mod crash_report;
+mod link_probe;
+mod local_source;
+mod logcat;
+mod mods;
+mod package_watch;
+mod parsers;
+mod permissions;
+mod recipes;
 mod sideload;
+mod splits;
 mod transfer;
+mod tweaks;
 
 use std::{
+    collections::HashMap,
     error::Error,
     fmt::Display,
     net::{Ipv4Addr, SocketAddrV4},
     path::{Path, PathBuf},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use anyhow::{Context, Result, anyhow, bail};
-pub(crate) use backup::BackupOptions;
+pub(crate) use agent::AgentApk;
+use anyhow::{Context, Result, anyhow, bail, ensure};
+pub(crate) use backup::{BackupOptions, RestoreOutcome, is_backup_archive};
 use const_format::concatcp;
+pub(crate) use crash_report::crash_report_file_name;
 use derive_more::Debug;
 use forensic_adb::{Device, UnixPath};
 use futures::FutureExt;
 use lazy_regex::regex;
+pub(crate) use logcat::LogcatMark;
+pub(crate) use package_watch::{PackageVersions, package_changes};
+pub(crate) use parsers::PackageUsage;
+use rinf::RustSignal;
 use sha2_const_stable::Sha256;
-pub(crate) use sideload::SideloadProgress;
+pub(crate) use sideload::{
+    ScriptAction, ScriptCommand, SideloadProgress, parse_install_script, preview_install_script,
+};
+pub(crate) use splits::read_split_set;
 use tokio::{fs, time::sleep};
 use tracing::{Span, debug, error, info, instrument, trace, warn};
 pub(crate) mod battery_dump;
 
+use self::parsers::{SectionOutput, ShellBatch, UptimeClock};
 use crate::{
     adb::PackageName,
     models::{
-        InstalledPackage, SPACE_INFO_COMMAND, SpaceInfo, parse_list_apps_dex,
-        signals::{adb::command::RebootMode, system::Toast},
-        vendor::quest_controller::{
-            CONTROLLER_INFO_COMMAND_DUMPSYS, CONTROLLER_INFO_COMMAND_JSON, HeadsetControllersInfo,
+        AppSize, DeviceCapabilities, DeviceTweaks, FirmwareInfo, InstalledPackage, SpaceInfo,
+        signals::{
+            adb::{
+                command::{RebootMode, RefreshScope},
+                device::InsufficientDeviceSpace,
+            },
+            system::Toast,
         },
+        vendor::{ControllerQuery, Vendor, quest_controller::HeadsetControllersInfo},
     },
 };
 
@@ -38,6 +66,15 @@ use crate::{
 static LIST_APPS_DEX_BYTES: &[u8] = include_bytes!("../../../assets/list_apps.dex");
 const LIST_APPS_DEX_SHA256: const_hex::Buffer<32> =
     const_hex::const_encode(&Sha256::new().update(LIST_APPS_DEX_BYTES).finalize());
+/// Share of the install size kept free on top of it for staging during installation (1/10)
+const INSTALL_OVERHEAD_DIVISOR: u64 = 10;
+
+/// Package change made on the device, see `AdbDevice::apply_package_changes`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PackageChange<'a> {
+    Installed(&'a str),
+    Uninstalled(&'a str),
+}
 
 /// Represents a connected Android device with ADB capabilities
 #[derive(Debug, Clone)]
@@ -56,6 +93,16 @@ pub(crate) struct AdbDevice {
     pub transport_id: String,
     /// True if connected over TCP/IP (adb over network)
     pub is_wireless: bool,
+    /// Android API level (`ro.build.version.sdk`), used to pick shell commands and parsers
+    pub sdk_version: Option<u32>,
+    /// Headset vendor, selects vendor-specific commands
+    pub vendor: Vendor,
+    /// Optional features supported by the device
+    pub capabilities: DeviceCapabilities,
+    /// OS build of the device
+    pub firmware: FirmwareInfo,
+    /// Current rendering tweaks, all unset on devices without tweak support
+    pub tweaks: DeviceTweaks,
     /// Device battery level (0-100)
     pub battery_level: u8,
     /// Information about connected controllers
@@ -88,8 +135,13 @@ impl AdbDevice {
     ///
     /// # Arguments
     /// * `inner` - The underlying forensic_adb Device instance
+    /// * `known` - Vendor and capabilities detected on an earlier connection, kept if the probe
+    ///   fails
     #[instrument(level = "debug", skip(inner), ret, err)]
-    pub(super) async fn new(inner: Device) -> Result<Self> {
+    pub(super) async fn new(
+        inner: Device,
+        known: Option<(Vendor, DeviceCapabilities)>,
+    ) -> Result<Self> {
         let serial = inner.serial.clone();
         // Heuristic: wireless adb usually uses host:port as serial
         let is_wireless = serial.contains(':');
@@ -112,6 +164,11 @@ impl AdbDevice {
             true_serial,
             transport_id,
             is_wireless,
+            sdk_version: None,
+            vendor: Vendor::default(),
+            capabilities: DeviceCapabilities::default(),
+            firmware: FirmwareInfo::default(),
+            tweaks: DeviceTweaks::default(),
             battery_level: 0,
             controllers: HeadsetControllersInfo::default(),
             space_info: SpaceInfo::default(),
@@ -122,6 +179,35 @@ impl AdbDevice {
             usb_speed: None,
         };
 
+        device.sdk_version = device
+            .shell_checked(parsers::SDK_VERSION_COMMAND)
+            .await
+            .ok()
+            .and_then(|output| parsers::parse_sdk_version(&output));
+        debug!(sdk_version = device.sdk_version, "Detected Android API level");
+        match device.shell(parsers::CAPABILITIES_PROBE_COMMAND).await {
+            Ok(output) => {
+                (device.vendor, device.capabilities) = parsers::parse_capabilities(&output)
+            }
+            Err(e) => {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to detect device capabilities");
+                let fallback = if known.is_some() {
+                    "using the ones detected before"
+                } else {
+                    "assuming every feature is supported"
+                };
+                (device.vendor, device.capabilities) =
+                    known.unwrap_or((Vendor::default(), DeviceCapabilities::optimistic()));
+                Toast::send(
+                    "Device capabilities".to_string(),
+                    format!("Failed to detect device capabilities, {fallback}: {e:#}"),
+                    true,
+                    None,
+                );
+            }
+        }
+        debug!(vendor = ?device.vendor, capabilities = ?device.capabilities, "Detected device capabilities");
+
         // Read identity first to use manufacturer + model if available
         match Self::query_identity(&device.inner).await {
             Ok(identity) => device.name = Some(identity),
@@ -177,20 +263,154 @@ impl AdbDevice {
             .to_string())
     }
 
-    /// Refreshes device information (packages, battery, space, guardian, USB) in parallel
-    #[instrument(level = "debug", skip(self), err)]
+    /// Refreshes device information (packages, battery, space, guardian, USB, firmware, tweaks).
+    ///
+    /// Round trips dominate refresh time over wireless connections, so everything except the
+    /// package list is queried through a single batched shell command that runs in parallel
+    /// with it. Components that fail keep their previous value where it is still meaningful.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn refresh(&mut self) -> Result<()> {
-        // Run all queries in parallel
-        let (packages_res, battery_res, space_res, guardian_res, proximity_res, usb_res) = tokio::join!(
-            self.query_package_list(),
-            self.query_battery_info(),
-            self.query_space_info(),
-            self.query_guardian_state(),
-            self.query_proximity_state(),
-            self.query_usb_state(),
-        );
+        const BATTERY: &str = "battery";
+        const SPACE: &str = "space";
+        const CONTROLLERS: &str = "controllers";
+        const UPTIME: &str = "uptime";
+        const GUARDIAN: &str = "guardian";
+        const PROXIMITY: &str = "proximity";
+        const USB_FUNCTIONS: &str = "usb_functions";
+        const USB_SPEED: &str = "usb_speed";
+        const DISKSTATS: &str = "diskstats";
+        const FIRMWARE: &str = "firmware";
+        const TWEAKS: &str = "tweaks";
+
+        let adapter = self.vendor.adapter();
+        let controller_queries = if self.capabilities.vr_headset {
+            adapter.controller_queries(&self.capabilities)
+        } else {
+            Vec::new()
+        };
+        let space_commands = parsers::space_info_commands(self.sdk_version);
+        let guardian_control = adapter.boundary_pause();
+        let tweak_properties = adapter.tweak_properties();
+
+        let mut batch = ShellBatch::default();
+        batch.push(BATTERY, "dumpsys battery");
+        batch.push(SPACE, space_commands[0]);
+        if let Some(query) = controller_queries.first() {
+            batch.push(CONTROLLERS, query.command);
+            batch.push(UPTIME, parsers::UPTIME_COMMAND);
+        }
+        if let Some(control) = &guardian_control {
+            batch.push(GUARDIAN, format!("getprop {}", control.property));
+        }
+        if self.capabilities.proximity_override {
+            batch.push(PROXIMITY, parsers::PROXIMITY_STATE_COMMAND);
+        }
+        if parsers::sdk_at_least(self.sdk_version, parsers::USB_FUNCTIONS_MIN_SDK) {
+            batch.push(USB_FUNCTIONS, "svc usb getFunctions");
+        }
+        if !self.is_wireless && parsers::sdk_at_least(self.sdk_version, parsers::USB_SPEED_MIN_SDK)
+        {
+            batch.push(USB_SPEED, "svc usb getUsbSpeed");
+        }
+        batch.push(DISKSTATS, parsers::DISKSTATS_COMMAND);
+        let os_version_properties = adapter.os_version_properties();
+        batch.push(FIRMWARE, parsers::firmware_command(os_version_properties));
+        if let Some(properties) = &tweak_properties {
+            batch.push(TWEAKS, parsers::tweaks_command(properties));
+        }
+
+        let script = batch.script();
+        let (packages_res, batch_res) =
+            tokio::join!(self.query_package_list(), self.shell(&script));
 
         let mut errors = Vec::new();
+        let mut sections = match batch_res {
+            Ok(output) => parsers::split_batch_output(&output),
+            Err(e) => {
+                errors.push(("batch", e));
+                HashMap::new()
+            }
+        };
+        let mut section = |name: &str| {
+            sections.remove(name).with_context(|| format!("No {name} section in batched output"))
+        };
+
+        let battery_res = section(BATTERY).and_then(SectionOutput::checked).and_then(|dump| {
+            parsers::parse_battery_level(&dump)
+                .context("Failed to parse device battery level from dumpsys output")
+        });
+        let space_res = match section(SPACE)
+            .and_then(SectionOutput::checked)
+            .and_then(|output| parsers::parse_space_info(&output))
+        {
+            Ok(space_info) => Ok(space_info),
+            Err(e) => {
+                debug!(error = e.as_ref() as &dyn Error, "Batched space query failed, retrying");
+                self.get_space_info().await
+            }
+        };
+        let uptime_clock =
+            section(UPTIME).and_then(SectionOutput::checked).ok().and_then(|output| {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+                UptimeClock::parse(&output, now.as_millis() as u64)
+            });
+        let controllers_res = match controller_queries.split_first() {
+            None => Ok(HeadsetControllersInfo::default()),
+            Some((query, fallbacks)) => match section(CONTROLLERS).and_then(|section| {
+                parsers::parse_controllers(query.format, &section.output, uptime_clock)
+            }) {
+                Ok(controllers) => Ok(controllers),
+                Err(e) if fallbacks.is_empty() => Err(e.context("Failed to get controller info")),
+                Err(e) => {
+                    warn!(
+                        error = e.as_ref() as &dyn Error,
+                        command = query.command,
+                        "Failed to get controller info, trying next source"
+                    );
+                    self.query_controllers(fallbacks, uptime_clock).await
+                }
+            },
+        };
+        // Property value is "1" for paused, "0" or empty for not paused
+        let guardian_res = guardian_control
+            .map(|_| section(GUARDIAN).map(|section| section.output.trim() == "1"))
+            .transpose();
+        let proximity_res = if self.capabilities.proximity_override {
+            section(PROXIMITY).map(|section| {
+                let state = parsers::parse_proximity_state(&section.output);
+                if state.is_none() {
+                    trace!(output = section.output, "No virtual proximity state found");
+                }
+                state
+            })
+        } else {
+            Ok(None)
+        };
+        let storage_connected = section(USB_FUNCTIONS)
+            .and_then(SectionOutput::checked)
+            .inspect_err(|e| trace!(error = e.as_ref() as &dyn Error, "No USB functions"))
+            .ok()
+            .map(|output| parsers::is_mtp_enabled(&output));
+        let usb_speed = section(USB_SPEED)
+            .and_then(SectionOutput::checked)
+            .inspect_err(|e| trace!(error = e.as_ref() as &dyn Error, "No USB speed"))
+            .ok()
+            .and_then(|output| parsers::format_usb_speed(&output));
+        let diskstats_sizes = section(DISKSTATS)
+            .and_then(SectionOutput::checked)
+            .inspect_err(|e| trace!(error = e.as_ref() as &dyn Error, "No disk stats"))
+            .map(|output| parsers::parse_diskstats_sizes(&output))
+            .unwrap_or_default();
+        let tweaks_res = tweak_properties
+            .map(|properties| {
+                section(TWEAKS)
+                    .and_then(SectionOutput::checked)
+                    .map(|output| parsers::parse_tweaks(&output, &properties))
+            })
+            .transpose();
+        let firmware_res = section(FIRMWARE)
+            .and_then(SectionOutput::checked)
+            .map(|output| parsers::parse_firmware(&output, os_version_properties));
 
         // Apply results
         match packages_res {
@@ -200,14 +420,15 @@ impl AdbDevice {
                 self.installed_packages = Vec::new();
             }
         }
+        self.fill_package_sizes(diskstats_sizes).await;
         match battery_res {
-            Ok((level, controllers)) => {
-                self.battery_level = level;
-                self.controllers = controllers;
-            }
+            Ok(level) => self.battery_level = level,
+            Err(e) => errors.push(("battery", e)),
+        }
+        match controllers_res {
+            Ok(controllers) => self.controllers = controllers,
             Err(e) => {
-                errors.push(("battery", e));
-                self.battery_level = 0;
+                errors.push(("controllers", e));
                 self.controllers = HeadsetControllersInfo::default();
             }
         }
@@ -232,16 +453,21 @@ impl AdbDevice {
                 self.proximity_disabled = None;
             }
         }
-        match usb_res {
-            Ok((storage_connected, usb_speed)) => {
-                self.storage_connected = storage_connected;
-                self.usb_speed = usb_speed;
-            }
-            Err(e) => {
-                errors.push(("usb", e));
-                self.storage_connected = None;
-                self.usb_speed = None;
+        self.storage_connected = storage_connected;
+        self.usb_speed = usb_speed;
+        // The firmware only changes across reboots, so a failed read keeps the known build
+        match firmware_res {
+            Ok(firmware) => {
+                if firmware != self.firmware {
+                    info!(?firmware, "Detected device firmware");
+                }
+                self.firmware = firmware;
             }
+            Err(e) => errors.push(("firmware", e)),
+        }
+        match tweaks_res {
+            Ok(tweaks) => self.tweaks = tweaks.unwrap_or_default(),
+            Err(e) => errors.push(("tweaks", e)),
         }
 
         if !errors.is_empty() {
@@ -256,8 +482,106 @@ impl AdbDevice {
         Ok(())
     }
 
+    /// Refreshes only the parts of the device info in `scope`
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn refresh_scope(&mut self, scope: RefreshScope) -> Result<()> {
+        match scope {
+            RefreshScope::Full => self.refresh().await,
+            RefreshScope::Battery => {
+                let dump = self.shell("dumpsys battery").await?;
+                self.battery_level = parsers::parse_battery_level(&dump)
+                    .context("Failed to parse device battery level from dumpsys output")?;
+                Ok(())
+            }
+            RefreshScope::Space => {
+                self.space_info = self.get_space_info().await?;
+                Ok(())
+            }
+            RefreshScope::Packages => {
+                let (packages, diskstats) = tokio::join!(
+                    self.query_package_list(),
+                    self.shell_checked(parsers::DISKSTATS_COMMAND)
+                );
+                self.installed_packages = packages?;
+                let diskstats_sizes = diskstats
+                    .inspect_err(|e| trace!(error = e.as_ref() as &dyn Error, "No disk stats"))
+                    .map(|output| parsers::parse_diskstats_sizes(&output))
+                    .unwrap_or_default();
+                self.fill_package_sizes(diskstats_sizes).await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Updates the package list and free space after packages were installed or uninstalled.
+    ///
+    /// The list is patched in place where possible instead of listing every package again. A
+    /// newly installed package needs its label and flags, so only then is the list fetched, once
+    /// for all changes.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn apply_package_changes(
+        &mut self,
+        changes: &[PackageChange<'_>],
+    ) -> Result<()> {
+        let mut list_stale = false;
+        for change in changes {
+            match *change {
+                PackageChange::Uninstalled(package) => {
+                    self.installed_packages.retain(|p| p.package_name() != package);
+                }
+                PackageChange::Installed(_) if list_stale => {}
+                PackageChange::Installed(package) => {
+                    let dump = self.shell(&parsers::package_dump_command(package)).await?;
+                    let version = parsers::parse_package_version(&dump);
+                    match (version, self.installed_package_mut(package)) {
+                        (Some((code, name)), Some(installed)) => {
+                            debug!(package, code, name, "Patching version of updated package");
+                            installed.set_version(code, name);
+                        }
+                        _ => list_stale = true,
+                    }
+                }
+            }
+        }
+        if list_stale {
+            self.refresh_scope(RefreshScope::Packages).boxed().await?;
+        }
+        self.refresh_scope(RefreshScope::Space).await
+    }
+
+    fn installed_package_mut(&mut self, package: &str) -> Option<&mut InstalledPackage> {
+        self.installed_packages.iter_mut().find(|p| p.package_name() == package)
+    }
+
+    /// Fills in package sizes from the device helper, then from disk stats
+    async fn fill_package_sizes(&mut self, mut diskstats_sizes: HashMap<String, AppSize>) {
+        if self.agent_version().is_some() {
+            match self.agent_package_sizes().await {
+                Ok(mut sizes) => {
+                    for package in &mut self.installed_packages {
+                        if let Some(size) = sizes.remove(package.package_name()) {
+                            package.set_size(size);
+                        }
+                    }
+                }
+                Err(e) => debug!(
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to get sizes from device helper, keeping list_apps.dex sizes"
+                ),
+            }
+        }
+        // Disk stats can be a day old, so they only fill in sizes nothing else reported
+        for package in &mut self.installed_packages {
+            if package.size().is_empty()
+                && let Some(size) = diskstats_sizes.remove(package.package_name())
+            {
+                package.set_size(size);
+            }
+        }
+    }
+
     /// Returns humanized `dumpsys battery` output from the device
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn battery_dump(&self) -> Result<String> {
         Ok(battery_dump::humanize_dump(
             &self
@@ -267,8 +591,39 @@ impl AdbDevice {
         ))
     }
 
+    /// Puts the controllers into pairing mode so they can be paired again
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn start_controller_pairing(&self) -> Result<()> {
+        let command = self
+            .vendor
+            .adapter()
+            .controller_pairing()
+            .context("Controller pairing is not supported on this device")?;
+        self.shell_checked(command).await.context("Failed to start controller pairing")?;
+        info!("Started controller pairing");
+        Ok(())
+    }
+
+    /// Reads the battery levels of the device and its controllers without touching the cached
+    /// device info
+    #[instrument(level = "trace", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn battery_levels(&self) -> Result<(u8, HeadsetControllersInfo)> {
+        let controller_queries = if self.capabilities.vr_headset {
+            self.vendor.adapter().controller_queries(&self.capabilities)
+        } else {
+            Vec::new()
+        };
+        let (dump, controllers) = tokio::join!(
+            self.shell_checked("dumpsys battery"),
+            self.query_controllers(&controller_queries, None)
+        );
+        let level = parsers::parse_battery_level(&dump?)
+            .context("Failed to parse device battery level from dumpsys output")?;
+        Ok((level, controllers?))
+    }
+
     /// Executes a shell command on the device
-    #[instrument(level = "debug", skip(self), err, ret)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err, ret)]
     pub(super) async fn shell(&self, command: &str) -> Result<String> {
         self.inner
             .execute_host_shell_command(command)
@@ -279,7 +634,7 @@ impl AdbDevice {
 
     /// Executes a shell command and fails if exit code is non-zero.
     /// Appends `; printf '\n%s' $?` and parses the final line as the exit status.
-    #[instrument(level = "debug", skip(self), err, ret)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err, ret)]
     pub(super) async fn shell_checked(&self, command: &str) -> Result<String> {
         let shell_output = self
             .shell(&format!("{} ; printf '\\n%s' $?", command))
@@ -307,7 +662,7 @@ impl AdbDevice {
     ///
     /// # Arguments
     /// * `mode` - The mode to reboot the device in (normal, bootloader, recovery, fastboot, power off)
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn reboot_with_mode(&self, mode: RebootMode) -> Result<()> {
         let cmd = match mode {
             RebootMode::Normal => "reboot",
@@ -325,12 +680,16 @@ impl AdbDevice {
     /// # Arguments
     /// * `enabled` - Whether to enable the real proximity sensor (true) or fake it as close (false)
     /// * `duration_ms` - Optional duration in milliseconds for disabling (only used when enabled=false)
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn set_proximity_sensor(
         &self,
         enabled: bool,
         duration_ms: Option<u64>,
     ) -> Result<()> {
+        ensure!(
+            self.capabilities.proximity_override,
+            "Proximity sensor override is not supported on this device"
+        );
         let cmd = if enabled {
             // Enable real sensor by disabling automation
             "am broadcast -a com.oculus.vrpowermanager.automation_disable".to_string()
@@ -355,83 +714,30 @@ impl AdbDevice {
     ///
     /// # Arguments
     /// * `paused` - Whether to pause or resume the guardian
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn set_guardian_paused(&self, paused: bool) -> Result<()> {
+        let control = self
+            .vendor
+            .adapter()
+            .boundary_pause()
+            .context("Guardian pause is not supported on this device")?;
         let value = if paused { 1 } else { 0 };
-        self.shell_checked(&format!("setprop debug.oculus.guardian_pause {value}"))
+        self.shell_checked(&format!("setprop {} {value}", control.property))
             .await
             .context(format!("Failed to set guardian paused: {paused}"))?;
         Ok(())
     }
 
     /// Sets USB storage connection state.
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn set_storage_connection(&self, connected: bool) -> Result<()> {
         let command = if connected { "svc usb setFunctions mtp" } else { "svc usb setFunctions" };
         self.shell(command).await.context("Failed to set USB storage connection")?;
         Ok(())
     }
 
-    /// Queries the guardian paused state from the device
-    #[instrument(level = "debug", skip(self), err)]
-    async fn query_guardian_state(&self) -> Result<Option<bool>> {
-        let output = self.shell("getprop debug.oculus.guardian_pause").await?;
-        let trimmed = output.trim();
-        // Property value is "1" for paused, "0" or empty for not paused
-        Ok(Some(trimmed == "1"))
-    }
-
-    /// Queries the proximity sensor disabled state from the device.
-    /// Parses `dumpsys oculus.internal.power.IVrPowerManager/default` output.
-    /// - `Virtual proximity state: CLOSE` => proximity disabled (faked)
-    /// - `Virtual proximity state: DISABLED` => proximity enabled (real sensor)
-    #[instrument(level = "debug", skip(self), err)]
-    async fn query_proximity_state(&self) -> Result<Option<bool>> {
-        let output = self.shell("dumpsys oculus.internal.power.IVrPowerManager/default").await?;
-
-        if let Some(captures) = regex!(r"^Virtual proximity state: (\w+)").captures(&output)
-            && let Some(state) = captures.get(1)
-        {
-            // CLOSE means proximity is faked (sensor disabled)
-            // DISABLED means proximity override is off (sensor enabled/real)
-            return Ok(match state.as_str() {
-                "CLOSE" => Some(true),
-                "DISABLED" => Some(false),
-                _ => None,
-            });
-        }
-        trace!(output, "No virtual proximity state found");
-
-        Ok(None)
-    }
-
-    /// Queries current USB functions and speed.
-    #[instrument(level = "debug", skip(self), err)]
-    async fn query_usb_state(&self) -> Result<(Option<bool>, Option<String>)> {
-        let storage_connected = match self.shell_checked("svc usb getFunctions").await {
-            Ok(output) => Some(output.split(',').any(|function| function.trim() == "mtp")),
-            Err(e) => {
-                trace!(error = e.as_ref() as &dyn Error, "Failed to query USB functions");
-                None
-            }
-        };
-        let speed = if !self.is_wireless {
-            match self.shell_checked("svc usb getUsbSpeed").await {
-                Ok(output) => format_usb_speed(&output),
-                Err(e) => {
-                    trace!(error = e.as_ref() as &dyn Error, "Failed to query USB speed");
-                    None
-                }
-            }
-        } else {
-            None
-        };
-
-        Ok((storage_connected, speed))
-    }
-
     /// Queries the list of installed packages on the device
-    #[instrument(level = "debug", skip(self), fields(count), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial, count), err)]
     async fn query_package_list(&self) -> Result<Vec<InstalledPackage>> {
         const LIST_APPS_DEX_PATH: &str = "/data/local/tmp/list_apps.dex";
         if !self
@@ -451,95 +757,150 @@ impl AdbDevice {
             .await
             .context("Failed to execute app_process for list_apps.dex")?;
 
-        let packages =
-            parse_list_apps_dex(&list_output).context("Failed to parse list_apps.dex output")?;
+        let packages = parsers::parse_package_list(&list_output)?;
 
         Span::current().record("count", packages.len());
         Ok(packages)
     }
 
-    /// Queries battery information for the device and controllers
-    #[instrument(level = "debug", skip(self), err)]
-    async fn query_battery_info(&self) -> Result<(u8, HeadsetControllersInfo)> {
-        // Get device battery level
-        let battery_dump = self.battery_dump().await.context("Failed to get battery dump")?;
-
-        let device_level: u8 = battery_dump
-            .lines()
-            .find_map(|line| {
-                // Look for lines like "  level: 85"
-                if line.trim().starts_with("level:") {
-                    line.split(':').nth(1)?.trim().parse().ok()
-                } else {
-                    None
+    /// Queries controller state from the vendor's sources, in order of preference
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    async fn query_controllers(
+        &self,
+        queries: &[ControllerQuery],
+        clock: Option<UptimeClock>,
+    ) -> Result<HeadsetControllersInfo> {
+        let mut last_error = None;
+        for query in queries {
+            match self
+                .shell(query.command)
+                .await
+                .and_then(|output| parsers::parse_controllers(query.format, &output, clock))
+            {
+                Ok(controllers) => {
+                    trace!(?controllers, "Parsed controller info");
+                    return Ok(controllers);
                 }
-            })
-            .context("Failed to parse device battery level from dumpsys output")?;
-        trace!(level = device_level, "Parsed device battery level");
-
-        // Get controller battery levels using rstest first, then fall back to dumpsys
-        let controllers = match self.shell_checked(CONTROLLER_INFO_COMMAND_JSON).await {
-            Ok(json) => match HeadsetControllersInfo::from_rstest_json(&json) {
-                Ok(info) => info,
                 Err(e) => {
                     warn!(
                         error = e.as_ref() as &dyn Error,
-                        "Failed to parse rstest json, falling back to dumpsys"
+                        command = query.command,
+                        "Failed to get controller info, trying next source"
                     );
-                    let dump = self
-                        .shell(CONTROLLER_INFO_COMMAND_DUMPSYS)
-                        .await
-                        .context("Failed to get controller info via dumpsys")?;
-                    HeadsetControllersInfo::from_dumpsys(&dump)
+                    last_error = Some(e);
                 }
-            },
-            Err(e) => {
-                warn!(
-                    error = e.as_ref() as &dyn Error,
-                    "rstest command failed, falling back to dumpsys"
-                );
-                let dump = self
-                    .shell(CONTROLLER_INFO_COMMAND_DUMPSYS)
-                    .await
-                    .context("Failed to get controller info via dumpsys")?;
-                HeadsetControllersInfo::from_dumpsys(&dump)
             }
-        };
-        trace!(?controllers, "Parsed controller info");
+        }
+        match last_error {
+            Some(e) => Err(e.context("Failed to get controller info")),
+            None => Ok(HeadsetControllersInfo::default()),
+        }
+    }
 
-        Ok((device_level, controllers))
+    /// Gets storage space information from the device
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    async fn get_space_info(&self) -> Result<SpaceInfo> {
+        let mut failures = Vec::new();
+        for command in parsers::space_info_commands(self.sdk_version) {
+            match self
+                .shell_checked(command)
+                .await
+                .and_then(|output| parsers::parse_space_info(&output))
+            {
+                Ok(space_info) => return Ok(space_info),
+                Err(e) => failures.push(format!("`{command}`: {e:#}")),
+            }
+        }
+        bail!("Failed to get space info ({})", failures.join("; "))
     }
 
-    /// Queries storage space information
-    #[instrument(level = "debug", skip(self), err)]
-    async fn query_space_info(&self) -> Result<SpaceInfo> {
-        self.get_space_info().await
+    /// Returns the disk usage of a path on the device in bytes, 0 if it does not exist
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    async fn remote_disk_usage(&self, path: &UnixPath) -> Result<u64> {
+        let output = self.shell(&format!("du -sk '{}' 2>/dev/null", path.display())).await?;
+        let kib = output.split_whitespace().next().and_then(|kib| kib.parse::<u64>().ok());
+        Ok(kib.unwrap_or(0).saturating_mul(1024))
     }
 
-    /// Gets storage space information from the device
-    #[instrument(level = "debug", skip(self), err)]
-    async fn get_space_info(&self) -> Result<SpaceInfo> {
-        let output =
-            self.shell_checked(SPACE_INFO_COMMAND).await.context("Space info command failed")?;
-        SpaceInfo::from_stat_output(&output)
+    /// Queries per-package usage statistics from the device, through the helper if installed
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn usage_stats(&self) -> Result<HashMap<String, PackageUsage>> {
+        if self.agent_version().is_some() {
+            match self.agent_usage_stats().await {
+                Ok(usage) => return Ok(usage),
+                Err(e) => warn!(
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to get usage stats from device helper, falling back to dumpsys"
+                ),
+            }
+        }
+        let output = self.shell(parsers::USAGE_STATS_COMMAND).await?;
+        Ok(parsers::parse_usage_stats(&output))
+    }
+
+    /// Checks that `required` bytes fit on the device before starting `operation`.
+    ///
+    /// Queries fresh space information and, if there is not enough room, notifies Dart with
+    /// the amount that has to be freed and fails.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    async fn ensure_free_space(&self, operation: &str, required: u64) -> Result<()> {
+        let space_info = self.get_space_info().await?;
+        let available = space_info.usable();
+        let Some(missing) = space_info.shortfall(required) else {
+            debug!(required, available, "Enough free space on device");
+            return Ok(());
+        };
+
+        warn!(required, available, missing, "Not enough free space on device");
+        InsufficientDeviceSpace { required, available, missing }.send_signal_to_dart();
+        let format = |bytes| humansize::format_size(bytes, humansize::DECIMAL);
+        bail!(
+            "Not enough free space on device for {operation}: needs {}, only {} available (free \
+             up at least {} more)",
+            format(required),
+            format(available),
+            format(missing)
+        )
+    }
+
+    /// Checks ahead of a download that an app of `install_size` bytes will fit once installed,
+    /// `package_name` being the package the app keeps its OBB files under.
+    ///
+    /// OBB data already on the device gets replaced, so its size is discounted, while some
+    /// headroom is required for staging the APK and pushing files.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn ensure_space_for_install(
+        &self,
+        package_name: &str,
+        install_size: u64,
+    ) -> Result<()> {
+        let existing_obb = self
+            .remote_disk_usage(&UnixPath::new("/sdcard/Android/obb").join(package_name))
+            .await?;
+        let required = install_size
+            .saturating_sub(existing_obb)
+            .saturating_add(install_size / INSTALL_OVERHEAD_DIVISOR);
+        self.ensure_free_space("install", required).await
     }
 
     /// Launches an application on the device
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn launch(&self, package: &PackageName) -> Result<()> {
-        // First try launching with VR category
-        let output = self
-            .shell(&format!("monkey -p {package} -c com.oculus.intent.category.VR 1"))
-            .await
-            .context("Failed to execute monkey command")?;
+        // On headsets, first try launching with VR category
+        if self.capabilities.vr_headset {
+            let output = self
+                .shell(&format!("monkey -p {package} -c com.oculus.intent.category.VR 1"))
+                .await
+                .context("Failed to execute monkey command")?;
 
-        if !output.contains("monkey aborted") {
-            info!("Launched with VR category");
-            return Ok(());
+            if !output.contains("monkey aborted") {
+                info!("Launched with VR category");
+                return Ok(());
+            }
+            info!(output, "Monkey command with VR category failed");
+            debug!("Retrying with default launch category");
         }
-        info!(output, "Monkey command with VR category failed");
 
-        debug!("Retrying with default launch category");
         let output = self
             .shell(&format!("monkey -p {package} 1"))
             .await
@@ -555,7 +916,7 @@ impl AdbDevice {
     }
 
     /// Force stops an application on the device
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn force_stop(&self, package: &PackageName) -> Result<()> {
         self.inner
             .force_stop(package.as_str())
@@ -563,8 +924,15 @@ impl AdbDevice {
             .with_context(|| format!("Failed to force stop {package}"))
     }
 
+    /// Checks whether a process of the package is running on the device
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn is_running(&self, package: &PackageName) -> Result<bool> {
+        let output = self.shell(&format!("pidof {package}")).await?;
+        Ok(output.split_whitespace().any(|pid| pid.parse::<u32>().is_ok()))
+    }
+
     /// Uninstalls a package from the device
-    #[instrument(level = "debug", skip(self))]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial))]
     pub(super) async fn uninstall_package(&self, package: &PackageName) -> Result<()> {
         match self.inner.uninstall_package(package.as_str()).await {
             Ok(_) => Ok(()),
@@ -604,7 +972,7 @@ impl AdbDevice {
     }
 
     /// Gets APK path reported by `pm path <package>`
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn get_apk_path(&self, package: &PackageName) -> Result<String> {
         let output = self
             .shell_checked(&format!("pm path {package}"))
@@ -626,7 +994,7 @@ impl AdbDevice {
     /// Layout:
     /// - `<dest_root>/<package_name>/<package_name>.apk`
     /// - `<dest_root>/<package_name>/` + OBB contents (when present)
-    #[instrument(level = "debug", skip(self, dest_root), err)]
+    #[instrument(level = "debug", skip(self, dest_root), fields(serial = %self.serial), err)]
     pub(super) async fn pull_app_for_donation(
         &self,
         package: &PackageName,
@@ -683,14 +1051,25 @@ impl AdbDevice {
         Ok(app_dir)
     }
 
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn clean_temp_apks(&self) -> Result<()> {
         debug!("Cleaning up temporary APKs");
         self.shell("rm -rf /data/local/tmp/*.apk").await?;
         Ok(())
     }
 
-    #[instrument(level = "debug", skip(self), ret, err)]
+    /// Removes the copy of `apk_path` that an install staged in `/data/local/tmp`, leaving the
+    /// APKs of other installs alone
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(super) async fn clean_temp_apk(&self, apk_path: &Path) -> Result<()> {
+        let name = apk_path.file_name().context("APK path has no file name")?.to_string_lossy();
+        let remote = format!("/data/local/tmp/{name}").replace('\'', "'\\''");
+        debug!(remote, "Cleaning up staged APK");
+        self.shell(&format!("rm -f '{remote}'")).await?;
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), ret, err)]
     async fn ip_from_route(&self) -> Result<Option<Ipv4Addr>> {
         let output = self
             .shell_checked("ip route | grep wlan0")
@@ -707,15 +1086,19 @@ impl AdbDevice {
         Ok(Some(ip))
     }
 
-    #[instrument(level = "debug", skip(self), ret, err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), ret, err)]
     async fn enable_tcpip(&self, ip: Ipv4Addr) -> Result<SocketAddrV4> {
         self.inner.tcpip(Self::WIRELESS_ADB_PORT).await.context("Failed to enable tcpip mode")?;
 
         Ok(SocketAddrV4::new(ip, Self::WIRELESS_ADB_PORT))
     }
 
-    #[instrument(level = "debug", skip(self), ret, err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), ret, err)]
     pub(super) async fn enable_wireless_adb(&self) -> Result<SocketAddrV4> {
+        ensure!(
+            self.capabilities.wireless_enable,
+            "Enabling wireless ADB is not supported on this device"
+        );
         if let Some(ip) = self.ip_from_route().await? {
             return self.enable_tcpip(ip).await;
         }
@@ -744,48 +1127,3 @@ impl AdbDevice {
         }
     }
 }
-
-pub(crate) fn format_usb_speed(output: &str) -> Option<String> {
-    let value = output.trim();
-    if value.is_empty() {
-        return None;
-    }
-    if let Ok(mbps) = value.parse::<u64>() {
-        if mbps >= 1024 {
-            let gbps = mbps as f64 / 1024.0;
-            let formatted = if gbps.fract() == 0.0 {
-                format!("{gbps:.0}")
-            } else {
-                format!("{gbps:.2}").trim_end_matches('0').trim_end_matches('.').to_string()
-            };
-            return Some(format!("{formatted} Gbps"));
-        }
-        return Some(format!("{mbps} Mbps"));
-    }
-    Some(value.to_string())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::format_usb_speed;
-
-    #[test]
-    fn formats_numeric_usb_speed() {
-        assert_eq!(format_usb_speed("480\n").as_deref(), Some("480 Mbps"));
-    }
-
-    #[test]
-    fn formats_gigabit_usb_speed() {
-        assert_eq!(format_usb_speed("5120\n").as_deref(), Some("5 Gbps"));
-    }
-
-    #[test]
-    fn keeps_text_usb_speed() {
-        assert_eq!(format_usb_speed("high speed\n").as_deref(), Some("high speed"));
-    }
-
-    #[test]
-    fn ignores_empty_usb_values() {
-        assert_eq!(format_usb_speed(" \n"), None);
-    }
-}
@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use forensic_adb::UnixPath;
+use tokio::fs;
+use tracing::{debug, info, instrument};
+
+use super::AdbDevice;
+use crate::mods_library::local_path_in_set;
+
+/// Suffix of the temporary directory a mod path is pushed to before it is swapped in
+const MOD_STAGING_SUFFIX: &str = ".yaas_staging";
+
+impl AdbDevice {
+    /// Pulls the current contents of mod paths into a mod set directory, replacing it.
+    ///
+    /// Device paths that do not exist are left out of the set, so applying the set later
+    /// removes them again.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn capture_mod_paths(&self, paths: &[String], set_dir: &Path) -> Result<()> {
+        let set_name = set_dir.file_name().context("Mod set path has no file name")?;
+        let staging_dir =
+            set_dir.with_file_name(format!(".{}.partial", set_name.to_string_lossy()));
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir).await.with_context(|| {
+                format!("Failed to remove stale directory {}", staging_dir.display())
+            })?;
+        }
+
+        for path in paths {
+            let remote_path = UnixPath::new(path);
+            if self.remote_entry_kind(remote_path).await?.is_none() {
+                debug!(path, "Mod path does not exist on device, skipping");
+                continue;
+            }
+            let local_path = local_path_in_set(&staging_dir, path);
+            if let Some(parent) = local_path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            debug!(path, local = %local_path.display(), "Pulling mod path");
+            self.pull_any(remote_path, &local_path).await?;
+        }
+        fs::create_dir_all(&staging_dir)
+            .await
+            .with_context(|| format!("Failed to create {}", staging_dir.display()))?;
+
+        if set_dir.exists() {
+            fs::remove_dir_all(set_dir)
+                .await
+                .with_context(|| format!("Failed to remove {}", set_dir.display()))?;
+        }
+        fs::rename(&staging_dir, set_dir).await.with_context(|| {
+            format!("Failed to move {} to {}", staging_dir.display(), set_dir.display())
+        })?;
+        info!(set = %set_dir.display(), "Captured mod set");
+        Ok(())
+    }
+
+    /// Replaces the contents of mod paths on the device with a mod set.
+    ///
+    /// Each path is pushed to a staging location next to it first and swapped in only after
+    /// the push succeeded. Paths missing from the set are removed from the device.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn apply_mod_paths(&self, paths: &[String], set_dir: &Path) -> Result<()> {
+        for path in paths {
+            let local_path = local_path_in_set(set_dir, path);
+            if !local_path.exists() {
+                debug!(path, "Mod path is not part of the set, removing from device");
+                self.shell_checked(&format!("rm -rf '{path}'")).await?;
+                continue;
+            }
+
+            let staging_path = format!("{path}{MOD_STAGING_SUFFIX}");
+            let staging = UnixPath::new(&staging_path);
+            self.shell_checked(&format!("rm -rf '{staging_path}'")).await?;
+            debug!(path, local = %local_path.display(), "Pushing mod path");
+            if local_path.is_dir() {
+                self.push_dir_to_path(&local_path, staging, false).await?;
+            } else {
+                self.push(&local_path, staging).await?;
+            }
+            self.shell_checked(&format!("rm -rf '{path}' && mv '{staging_path}' '{path}'"))
+                .await
+                .with_context(|| format!("Failed to swap in mod path {path}"))?;
+        }
+        info!(set = %set_dir.display(), "Applied mod set");
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! Device-side watch for installed, updated and removed packages.
+//!
+//! A shell loop on the device compares the package list with version codes until it changes, so
+//! the host only hears back once something happened instead of listing every package with
+//! list_apps.dex on each refresh.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, Result};
+use lazy_regex::regex_captures;
+use tracing::instrument;
+
+use super::{AdbDevice, PackageChange};
+
+/// How often the device compares the package list
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// Checks before the watch returns without a change, bounding how long the shell stays open
+const MAX_CHECKS: u32 = 30;
+
+/// Version codes of the installed packages by package name
+pub(crate) type PackageVersions = HashMap<String, u64>;
+
+impl AdbDevice {
+    /// Waits on the device until the package list changes or the watch times out, then returns
+    /// the package versions at that point
+    #[instrument(level = "trace", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn wait_for_package_changes(&self) -> Result<PackageVersions> {
+        let output = self
+            .shell_checked(&package_watch_command(CHECK_INTERVAL, MAX_CHECKS))
+            .await
+            .context("Package watch failed")?;
+        Ok(parse_package_versions(&output))
+    }
+}
+
+fn package_watch_command(interval: Duration, max_checks: u32) -> String {
+    const LIST: &str = "pm list packages --show-versioncode";
+    format!(
+        "old=$({LIST}); new=$old; i=0; while [ \"$new\" = \"$old\" ] && [ $i -lt {max_checks} ]; \
+         do sleep {}; new=$({LIST}); i=$((i+1)); done; echo \"$new\"",
+        interval.as_secs()
+    )
+}
+
+/// Parses `pm list packages --show-versioncode` output, e.g. `package:com.example versionCode:12`
+fn parse_package_versions(output: &str) -> PackageVersions {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (_, package, code) =
+                regex_captures!(r"^package:(\S+) versionCode:(\d+)$", line.trim())?;
+            Some((package.to_string(), code.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Packages installed, updated or removed between two snapshots, sorted by package name
+pub(crate) fn package_changes<'a>(
+    old: &'a PackageVersions,
+    new: &'a PackageVersions,
+) -> Vec<PackageChange<'a>> {
+    let mut changes = new
+        .iter()
+        .filter(|(package, code)| old.get(*package) != Some(code))
+        .map(|(package, _)| PackageChange::Installed(package))
+        .chain(
+            old.keys()
+                .filter(|package| !new.contains_key(*package))
+                .map(|package| PackageChange::Uninstalled(package)),
+        )
+        .collect::<Vec<_>>();
+    changes.sort_unstable_by_key(|change| match change {
+        PackageChange::Installed(package) | PackageChange::Uninstalled(package) => *package,
+    });
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(entries: &[(&str, u64)]) -> PackageVersions {
+        entries.iter().map(|(package, code)| (package.to_string(), *code)).collect()
+    }
+
+    #[test]
+    fn parses_package_versions() {
+        let output = "package:com.oculus.shellenv versionCode:1234\npackage:com.example.game \
+                      versionCode:7\r\nWARNING: linker: unsupported \
+                      flags\npackage:com.example.broken versionCode:\n";
+        assert_eq!(
+            parse_package_versions(output),
+            versions(&[("com.oculus.shellenv", 1234), ("com.example.game", 7)])
+        );
+    }
+
+    #[test]
+    fn watch_command_is_bounded() {
+        assert_eq!(
+            package_watch_command(Duration::from_secs(2), 30),
+            "old=$(pm list packages --show-versioncode); new=$old; i=0; while [ \"$new\" = \
+             \"$old\" ] && [ $i -lt 30 ]; do sleep 2; new=$(pm list packages --show-versioncode); \
+             i=$((i+1)); done; echo \"$new\""
+        );
+    }
+
+    #[test]
+    fn finds_installed_updated_and_removed_packages() {
+        let old = versions(&[("a.kept", 1), ("b.updated", 1), ("c.removed", 3)]);
+        let new = versions(&[("a.kept", 1), ("b.updated", 2), ("d.installed", 1)]);
+        assert_eq!(
+            package_changes(&old, &new),
+            [
+                PackageChange::Installed("b.updated"),
+                PackageChange::Uninstalled("c.removed"),
+                PackageChange::Installed("d.installed"),
+            ]
+        );
+        assert!(package_changes(&new, &new).is_empty());
+    }
+}
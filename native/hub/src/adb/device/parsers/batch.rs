@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+
+/// Printed before the output of every section, followed by the section name
+const SECTION_START: &str = "--yaas-section-start-- ";
+/// Printed after the output of every section, followed by the section name and exit code
+const SECTION_END: &str = "--yaas-section-end-- ";
+
+/// Several shell commands run in a single `adb shell` round trip.
+///
+/// Every command becomes a named section whose output and exit code are recovered host-side
+/// with `split_batch_output`, so one failing command does not affect the others.
+#[derive(Debug, Default)]
+pub(crate) struct ShellBatch {
+    sections: Vec<(&'static str, String)>,
+}
+
+impl ShellBatch {
+    /// Adds `command` as section `name`
+    pub(crate) fn push(&mut self, name: &'static str, command: impl Into<String>) {
+        self.sections.push((name, command.into()));
+    }
+
+    /// Builds the compound shell command running all sections in order
+    pub(crate) fn script(&self) -> String {
+        self.sections
+            .iter()
+            .map(|(name, command)| {
+                format!(
+                    "echo '{SECTION_START}{name}'; {command}; printf '\\n{SECTION_END}{name} \
+                     %s\\n' $?"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Output of one section of a `ShellBatch`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SectionOutput {
+    pub output: String,
+    /// `None` if the section did not run to completion, e.g. because the connection dropped
+    pub exit_code: Option<i32>,
+}
+
+impl SectionOutput {
+    /// Returns the output if the command exited successfully
+    pub(crate) fn checked(self) -> Result<String> {
+        match self.exit_code {
+            Some(0) => Ok(self.output),
+            Some(code) => bail!("Command failed with exit code {code}. Output: {}", self.output),
+            None => bail!("Command did not finish. Output: {}", self.output),
+        }
+    }
+}
+
+/// Splits the output of `ShellBatch::script` into sections by name
+pub(crate) fn split_batch_output(output: &str) -> HashMap<String, SectionOutput> {
+    let mut sections = HashMap::new();
+    for chunk in output.split(SECTION_START).skip(1) {
+        let (name, rest) = chunk.split_once('\n').unwrap_or((chunk, ""));
+        let name = name.trim_end_matches('\r');
+        let end_marker = format!("{SECTION_END}{name} ");
+        let (body, exit_code) = match rest.rfind(&end_marker) {
+            Some(end) => (
+                &rest[..end],
+                rest[end + end_marker.len()..]
+                    .lines()
+                    .next()
+                    .and_then(|code| code.trim().parse().ok()),
+            ),
+            None => (rest, None),
+        };
+        // Drop the newline printed in front of the end marker
+        let body = body.strip_suffix("\r\n").or_else(|| body.strip_suffix('\n')).unwrap_or(body);
+        sections.insert(name.to_string(), SectionOutput { output: body.to_string(), exit_code });
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_sections_with_exit_codes() {
+        let mut batch = ShellBatch::default();
+        batch.push("battery", "dumpsys battery");
+        batch.push("guardian", "getprop debug.oculus.guardian_pause");
+        assert!(batch.script().contains("dumpsys battery; printf"));
+
+        let output = concat!(
+            "--yaas-section-start-- battery\r\n",
+            "  level: 87\r\n",
+            "\r\n",
+            "--yaas-section-end-- battery 0\r\n",
+            "--yaas-section-start-- guardian\n",
+            "\n",
+            "--yaas-section-end-- guardian 1\n",
+        );
+        let sections = split_batch_output(output);
+        assert_eq!(sections["battery"].clone().checked().unwrap(), "  level: 87\r\n");
+        assert_eq!(sections["guardian"].exit_code, Some(1));
+        assert!(sections["guardian"].clone().checked().is_err());
+    }
+
+    #[test]
+    fn truncated_section_has_no_exit_code() {
+        let sections = split_batch_output("noise\n--yaas-section-start-- space\n4096:10:5");
+        assert_eq!(
+            sections["space"],
+            SectionOutput { output: "4096:10:5".to_string(), exit_code: None }
+        );
+        assert_eq!(sections.len(), 1);
+    }
+}
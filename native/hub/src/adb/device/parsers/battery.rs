@@ -0,0 +1,48 @@
+use super::{find_value, leading_number};
+
+/// Extracts the device battery level in percent from `dumpsys battery` output.
+///
+/// The raw `level` is relative to `scale`, which is 100 on most builds but not all of them.
+pub(crate) fn parse_battery_level(dump: &str) -> Option<u8> {
+    let level: u32 = find_value(dump, &["level", "mBatteryLevel"]).and_then(leading_number)?;
+    let scale = find_value(dump, &["scale", "mBatteryScale"])
+        .and_then(leading_number::<u32>)
+        .filter(|&scale| scale > 0)
+        .unwrap_or(100);
+    let percent = if scale == 100 { level } else { level.saturating_mul(100) / scale };
+    u8::try_from(percent.min(100)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_recorded_dumps() {
+        assert_eq!(parse_battery_level(include_str!("corpus/battery_android10.txt")), Some(87));
+        assert_eq!(
+            parse_battery_level(include_str!("corpus/battery_android12_updates_stopped.txt")),
+            Some(64)
+        );
+        assert_eq!(parse_battery_level(include_str!("corpus/battery_android14.txt")), Some(100));
+        assert_eq!(parse_battery_level(include_str!("corpus/battery_scale_255.txt")), Some(50));
+    }
+
+    #[test]
+    fn ignores_unrelated_level_keys() {
+        let dump = "  Capacity level: 3\n  Battery level: 12\n  level: 55\n";
+        assert_eq!(parse_battery_level(dump), Some(55));
+    }
+
+    #[test]
+    fn missing_or_invalid_level_yields_none() {
+        assert_eq!(parse_battery_level("Can't find service: battery"), None);
+        assert_eq!(parse_battery_level("  level: -1\n"), None);
+        assert_eq!(parse_battery_level(""), None);
+    }
+
+    #[test]
+    fn clamps_levels_above_scale() {
+        assert_eq!(parse_battery_level("level: 120\nscale: 100\n"), Some(100));
+    }
+}
@@ -0,0 +1,74 @@
+use super::find_value;
+use crate::models::{DeviceCapabilities, vendor::Vendor};
+
+/// Prints the manufacturer, features, tools and services needed to detect device capabilities
+pub(crate) const CAPABILITIES_PROBE_COMMAND: &str =
+    "echo manufacturer=$(getprop ro.product.manufacturer); pm list features 2>/dev/null | grep -q \
+     android.hardware.vr.headtracking && echo feature=vr_headtracking; for tool in rstest run-as \
+     ip svc; do command -v $tool >/dev/null 2>&1 && echo tool=$tool; done; dumpsys -l 2>/dev/null \
+     | grep -q IVrPowerManager && echo service=vr_power_manager";
+
+/// Parses the output of `CAPABILITIES_PROBE_COMMAND` into the device vendor and capabilities
+pub(crate) fn parse_capabilities(output: &str) -> (Vendor, DeviceCapabilities) {
+    let vendor =
+        Vendor::from_manufacturer(find_value(output, &["manufacturer"]).unwrap_or_default());
+    let adapter = vendor.adapter();
+    let has = |kind: &str, name: &str| {
+        output.lines().any(|line| line.trim().split_once('=') == Some((kind, name)))
+    };
+
+    let capabilities = DeviceCapabilities {
+        vr_headset: vendor != Vendor::Generic || has("feature", "vr_headtracking"),
+        guardian_pause: adapter.boundary_pause().is_some(),
+        proximity_override: has("service", "vr_power_manager"),
+        rstest_json: has("tool", "rstest"),
+        casting: adapter.supports_casting(),
+        wireless_enable: has("tool", "ip") && has("tool", "svc"),
+        run_as: has("tool", "run-as"),
+        device_tweaks: adapter.tweak_properties().is_some(),
+        controller_pairing: adapter.controller_pairing().is_some(),
+    };
+    (vendor, capabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_quest_capabilities() {
+        let (vendor, caps) = parse_capabilities(include_str!("corpus/capabilities_quest3.txt"));
+        assert_eq!(vendor, Vendor::Meta);
+        assert_eq!(
+            caps,
+            DeviceCapabilities {
+                vr_headset: true,
+                guardian_pause: true,
+                proximity_override: true,
+                rstest_json: true,
+                casting: true,
+                wireless_enable: true,
+                run_as: true,
+                device_tweaks: true,
+                controller_pairing: true,
+            }
+        );
+    }
+
+    #[test]
+    fn detects_reduced_phone_capabilities() {
+        let (vendor, caps) = parse_capabilities(include_str!("corpus/capabilities_phone.txt"));
+        assert_eq!(vendor, Vendor::Generic);
+        assert_eq!(
+            caps,
+            DeviceCapabilities { wireless_enable: true, run_as: true, ..Default::default() }
+        );
+        assert_eq!(parse_capabilities(""), (Vendor::Generic, DeviceCapabilities::default()));
+
+        let (vendor, pico) = parse_capabilities("manufacturer=Pico\nfeature=vr_headtracking\n");
+        assert_eq!(vendor, Vendor::Pico);
+        assert!(pico.vr_headset);
+        assert!(!pico.guardian_pause);
+        assert!(!pico.casting);
+    }
+}
@@ -0,0 +1,484 @@
+use anyhow::{Context, Result};
+use lazy_regex::regex_captures;
+use serde_json::Value;
+use tracing::{debug, trace, warn};
+
+use super::{json_array, leading_number};
+use crate::models::vendor::{
+    ControllerFormat,
+    quest_controller::{ControllerInfo, ControllerStatus, HeadsetControllersInfo},
+};
+
+/// Command printing the device uptime, see `UptimeClock`
+pub(crate) const UPTIME_COMMAND: &str = "cat /proc/uptime";
+
+/// Device uptime read at a known host time, placing uptime-based timestamps in host time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct UptimeClock {
+    pub uptime_millis: u64,
+    /// Milliseconds since Unix epoch on the host when the uptime was read
+    pub epoch_millis: u64,
+}
+
+impl UptimeClock {
+    /// Parses the output of `UPTIME_COMMAND` read at `epoch_millis`
+    pub(crate) fn parse(output: &str, epoch_millis: u64) -> Option<Self> {
+        let uptime_secs = output.split_whitespace().next()?.parse::<f64>().ok()?;
+        let uptime_millis = secs_to_millis(uptime_secs)?;
+        Some(Self { uptime_millis, epoch_millis })
+    }
+
+    /// Host time when the device uptime was `uptime_secs`, None if that is in the future
+    fn epoch_millis_at(self, uptime_secs: f64) -> Option<u64> {
+        let ago = self.uptime_millis.checked_sub(secs_to_millis(uptime_secs)?)?;
+        self.epoch_millis.checked_sub(ago)
+    }
+}
+
+fn secs_to_millis(secs: f64) -> Option<u64> {
+    (secs.is_finite() && secs >= 0.0).then_some((secs * 1000.0) as u64)
+}
+
+/// Parses the output of a controller state command in the given format.
+///
+/// Last-seen times are only filled in when `clock` is given.
+pub(crate) fn parse_controllers(
+    format: ControllerFormat,
+    output: &str,
+    clock: Option<UptimeClock>,
+) -> Result<HeadsetControllersInfo> {
+    match format {
+        ControllerFormat::RstestJson => parse_controllers_json(output, clock),
+        ControllerFormat::OvrRemoteDumpsys => Ok(parse_controllers_dumpsys(output)),
+        ControllerFormat::Properties => Ok(parse_controllers_properties(output)),
+    }
+}
+
+/// Parses the JSON output of `rstest info --json`.
+///
+/// Entries without a controller type are skipped, and missing fields are left empty instead of
+/// discarding the controller. `lastConnectedTimestamp` is in seconds of device uptime.
+pub(crate) fn parse_controllers_json(
+    output: &str,
+    clock: Option<UptimeClock>,
+) -> Result<HeadsetControllersInfo> {
+    let json = json_array(output).context("No JSON array in rstest info --json output")?;
+    let items: Vec<Value> =
+        serde_json::from_str(json).context("Failed to deserialize rstest info --json output")?;
+
+    let mut result = HeadsetControllersInfo::default();
+    for item in items {
+        let Some(controller_type) = item.get("type").and_then(Value::as_str) else {
+            debug!(?item, "Skipping rstest entry without controller type");
+            continue;
+        };
+        let battery_level = item.get("batteryLevel").and_then(|level| match level {
+            Value::Number(number) => number.as_u64().and_then(|level| u8::try_from(level).ok()),
+            Value::String(text) => leading_number(text.trim()),
+            _ => None,
+        });
+        let status = item
+            .get("status")
+            .and_then(Value::as_str)
+            .map(ControllerStatus::from)
+            .unwrap_or_default();
+        let firmware_version = item
+            .get("firmwareVersion")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|version| !version.is_empty())
+            .map(str::to_string);
+        let last_seen = clock
+            .zip(item.get("lastConnectedTimestamp").and_then(Value::as_f64))
+            .and_then(|(clock, uptime_secs)| clock.epoch_millis_at(uptime_secs));
+        let info = ControllerInfo { battery_level, status, firmware_version, last_seen };
+        set_controller(&mut result, controller_type, info);
+    }
+
+    if result.left.is_none() {
+        warn!("left controller info not found in rstest json");
+    }
+    if result.right.is_none() {
+        warn!("right controller info not found in rstest json");
+    }
+
+    Ok(result)
+}
+
+/// Parses `Paired device: ...` lines of `dumpsys OVRRemoteService`.
+///
+/// Fields are extracted one by one, so lines from OS versions that add, drop or reorder
+/// fields still yield whatever they contain.
+pub(crate) fn parse_controllers_dumpsys(output: &str) -> HeadsetControllersInfo {
+    let mut result = HeadsetControllersInfo::default();
+
+    for line in output.lines() {
+        let Some((_, controller_type)) = regex_captures!(r"\bType:\s*(\w+)", line) else {
+            continue;
+        };
+        let battery = regex_captures!(r"\bBattery:\s*(-?\d+)", line).map(|(_, level)| level);
+        let status = regex_captures!(r"\bStatus:\s*(\w+)", line).map(|(_, status)| status);
+        let firmware =
+            regex_captures!(r"\bFirmware:\s*([^,\s]+)", line).map(|(_, version)| version);
+        if battery.is_none() && status.is_none() {
+            continue;
+        }
+
+        let battery_level = battery.and_then(|level| level.parse::<u8>().ok());
+        if let Some(level) = battery
+            && battery_level.is_none()
+        {
+            debug!(
+                "Invalid battery level for {} controller: {}",
+                controller_type.to_lowercase(),
+                level
+            );
+        }
+        let info = ControllerInfo {
+            battery_level,
+            status: status.map(ControllerStatus::from).unwrap_or_default(),
+            firmware_version: firmware.map(str::to_string),
+            last_seen: None,
+        };
+        set_controller(&mut result, controller_type, info);
+    }
+
+    trace!("parsed controller levels: {:?}", result);
+
+    if result.left.is_none() {
+        warn!("left controller info not found");
+    }
+    if result.right.is_none() {
+        warn!("right controller info not found");
+    }
+
+    result
+}
+
+/// Parses `getprop` lines (`[name]: [value]`) with per-hand controller properties.
+///
+/// Property names differ between firmware versions, so any property mentioning a hand and a
+/// battery level, connection state or firmware version is used.
+pub(crate) fn parse_controllers_properties(output: &str) -> HeadsetControllersInfo {
+    let mut result = HeadsetControllersInfo::default();
+
+    for line in output.lines() {
+        let Some((_, name, value)) = regex_captures!(r"^\s*\[([^\]]+)\]:\s*\[([^\]]*)\]", line)
+        else {
+            continue;
+        };
+        let name = name.to_ascii_lowercase();
+        let value = value.trim();
+        let slot = if name.contains("left") {
+            &mut result.left
+        } else if name.contains("right") {
+            &mut result.right
+        } else {
+            continue;
+        };
+
+        if name.contains("battery") || name.contains("power") {
+            let Some(level) = leading_number::<u8>(value).filter(|&level| level <= 100) else {
+                debug!(name, value, "Invalid controller battery property");
+                continue;
+            };
+            slot.get_or_insert_with(ControllerInfo::default).battery_level = Some(level);
+        } else if name.contains("connect") {
+            let status = match value.to_ascii_lowercase().as_str() {
+                "1" | "true" | "connected" => ControllerStatus::Active,
+                "0" | "false" | "disconnected" => ControllerStatus::Inactive,
+                other => ControllerStatus::Unknown(other.to_string()),
+            };
+            slot.get_or_insert_with(ControllerInfo::default).status = status;
+        } else if name.contains("firmware") && !value.is_empty() {
+            slot.get_or_insert_with(ControllerInfo::default).firmware_version =
+                Some(value.to_string());
+        }
+    }
+
+    trace!("parsed controller properties: {:?}", result);
+    result
+}
+
+fn set_controller(
+    result: &mut HeadsetControllersInfo,
+    controller_type: &str,
+    info: ControllerInfo,
+) {
+    match controller_type {
+        "Left" | "LeftHand" => result.left = Some(info),
+        "Right" | "RightHand" => result.right = Some(info),
+        other => warn!("unexpected controller type '{}'", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    fn controller(
+        battery_level: Option<u8>,
+        status: ControllerStatus,
+        firmware_version: Option<&str>,
+    ) -> ControllerInfo {
+        ControllerInfo {
+            battery_level,
+            status,
+            firmware_version: firmware_version.map(str::to_string),
+            last_seen: None,
+        }
+    }
+
+    const SAMPLE_JSON_1: &str = r#"
+[
+        {
+                "batteryLevel" : 90,
+                "brightnessLevel" : "GOOD",
+                "errors" : "",
+                "firmwareVersion" : "1.9.2",
+                "hardwareRevision" : "0x08",
+                "id" : "56c5083b9f13da12",
+                "imuModel" : "ICM42686",
+                "lastConnectedTimestamp" : 15.827976419000001,
+                "model" : "JEDI",
+                "modelId" : 1,
+                "serial" : "1WMHCLE0MS1204",
+                "status" : "DISABLED",
+                "trackingStatus" : "NONE",
+                "type" : "LeftHand"
+        },
+        {
+                "batteryLevel" : 40,
+                "brightnessLevel" : "GOOD",
+                "errors" : "",
+                "firmwareVersion" : "1.9.2",
+                "hardwareRevision" : "0x08",
+                "id" : "c93fff8c9460a480",
+                "imuModel" : "ICM42686",
+                "lastConnectedTimestamp" : 773005.87873876607,
+                "model" : "JEDI",
+                "modelId" : 1,
+                "serial" : "1WMHCR30LQ1205",
+                "status" : "CONNECTED_ACTIVE",
+                "trackingStatus" : "NONE",
+                "type" : "RightHand"
+        }
+]
+"#;
+
+    const SAMPLE_JSON_2: &str = r#"
+[
+        {
+                "batteryLevel" : 90,
+                "brightnessLevel" : "GOOD",
+                "errors" : "",
+                "firmwareVersion" : "1.9.2",
+                "hardwareRevision" : "0x08",
+                "id" : "56c5083b9f13da12",
+                "imuModel" : "ICM42686",
+                "lastConnectedTimestamp" : 15.827976419000001,
+                "model" : "JEDI",
+                "modelId" : 1,
+                "serial" : "1WMHCLE0MS1204",
+                "status" : "SEARCHING",
+                "trackingStatus" : "NONE",
+                "type" : "LeftHand"
+        },
+        {
+                "batteryLevel" : 50,
+                "brightnessLevel" : "GOOD",
+                "errors" : "",
+                "firmwareVersion" : "1.9.2",
+                "hardwareRevision" : "0x08",
+                "id" : "c93fff8c9460a480",
+                "imuModel" : "ICM42686",
+                "lastConnectedTimestamp" : 797709.79250015109,
+                "model" : "JEDI",
+                "modelId" : 1,
+                "serial" : "1WMHCR30LQ1205",
+                "status" : "CONNECTED_ACTIVE",
+                "trackingStatus" : "NONE",
+                "type" : "RightHand"
+        }
+]
+"#;
+
+    #[test]
+    fn test_parse_rstest_json_sample1() {
+        let parsed =
+            parse_controllers_json(SAMPLE_JSON_1, None).expect("json parse should succeed");
+        assert_eq!(
+            parsed.left,
+            Some(ControllerInfo {
+                battery_level: Some(90),
+                status: ControllerStatus::Disabled,
+                firmware_version: Some("1.9.2".into()),
+                last_seen: None,
+            })
+        );
+        assert_eq!(
+            parsed.right,
+            Some(ControllerInfo {
+                battery_level: Some(40),
+                status: ControllerStatus::Active,
+                firmware_version: Some("1.9.2".into()),
+                last_seen: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rstest_json_sample2() {
+        let parsed =
+            parse_controllers_json(SAMPLE_JSON_2, None).expect("json parse should succeed");
+        assert_eq!(
+            parsed.left.map(|left| (left.battery_level, left.status)),
+            Some((Some(90), ControllerStatus::Searching))
+        );
+        assert_eq!(
+            parsed.right.map(|right| (right.battery_level, right.status)),
+            Some((Some(50), ControllerStatus::Active))
+        );
+    }
+
+    #[test]
+    fn places_last_connected_uptime_in_host_time() {
+        let clock = UptimeClock::parse("797800.50 1500000.00\n", 1_700_000_000_000).unwrap();
+        assert_eq!(clock.uptime_millis, 797_800_500);
+        let parsed = parse_controllers_json(SAMPLE_JSON_2, Some(clock)).unwrap();
+        assert_eq!(parsed.left.unwrap().last_seen, Some(1_700_000_000_000 - 797_784_673));
+        assert_eq!(parsed.right.unwrap().last_seen, Some(1_700_000_000_000 - 90_708));
+
+        let clock = UptimeClock { uptime_millis: 10_000, epoch_millis: 1_000_000 };
+        assert_eq!(clock.epoch_millis_at(20.0), None);
+        assert_eq!(clock.epoch_millis_at(f64::NAN), None);
+        assert_eq!(UptimeClock::parse("", 0), None);
+    }
+
+    #[test]
+    fn test_quest_parse_dumpsys_controller_both() {
+        let lines = "  Paired device: c93fff8c9460a480, Type:  Right, Model: JEDI, Firmware: \
+                     1.9.2, ImuModel: ICM42686, Battery: 100%, Status: Active, ExternalStatus: \
+                     DISABLED, TrackingStatus: ORIENTATION, BrightnessLevel: GOOD
+  Paired device: 56c5083b9f13da12, Type:   Left, Model: JEDI, Firmware: 1.9.2, ImuModel: ICM42686, \
+                     Battery:  50%, Status: Disabled, ExternalStatus: DISABLED, TrackingStatus: \
+                     POSITION, BrightnessLevel: GOOD
+  ";
+        let parsed = parse_controllers_dumpsys(lines);
+        assert_eq!(
+            parsed.right,
+            Some(controller(Some(100), ControllerStatus::Active, Some("1.9.2")))
+        );
+        assert_eq!(
+            parsed.left,
+            Some(controller(Some(50), ControllerStatus::Disabled, Some("1.9.2")))
+        );
+    }
+
+    #[test]
+    fn test_quest_parse_dumpsys_controller_left() {
+        let lines = "  Paired device: 56c5083b9f13da12, Type:   Left, Model: JEDI, Firmware: \
+                     1.9.2, ImuModel: ICM42686, Battery:  50%, Status: Active, ExternalStatus: \
+                     DISABLED, TrackingStatus: POSITION, BrightnessLevel: GOOD
+  ";
+        let parsed = parse_controllers_dumpsys(lines);
+        assert_eq!(
+            parsed.left,
+            Some(controller(Some(50), ControllerStatus::Active, Some("1.9.2")))
+        );
+        assert_eq!(parsed.right, None);
+    }
+
+    #[test]
+    fn test_quest_parse_dumpsys_controller_right() {
+        let lines = "  Paired device: c93fff8c9460a480, Type:  Right, Model: JEDI, Firmware: \
+                     1.9.2, ImuModel: ICM42686, Battery: 100%, Status: Disabled, ExternalStatus: \
+                     DISABLED, TrackingStatus: ORIENTATION, BrightnessLevel: GOOD
+  ";
+        let parsed = parse_controllers_dumpsys(lines);
+        assert_eq!(
+            parsed.right,
+            Some(controller(Some(100), ControllerStatus::Disabled, Some("1.9.2")))
+        );
+        assert_eq!(parsed.left, None);
+    }
+
+    #[test]
+    fn test_quest_parse_dumpsys_controller_unknown_battery() {
+        let lines = "  Paired device: c93fff8c9460a480, Type:  Right, Model: JEDI, Firmware: \
+                     1.9.2, ImuModel: ICM42686, Battery: -1%, Status: Disabled, ExternalStatus: \
+                     DISABLED, TrackingStatus: ORIENTATION, BrightnessLevel: GOOD
+  ";
+        let parsed = parse_controllers_dumpsys(lines);
+        assert_eq!(parsed.right, Some(controller(None, ControllerStatus::Disabled, Some("1.9.2"))));
+    }
+
+    #[test]
+    fn test_quest_parse_dumpsys_controller_empty() {
+        let lines = "\n";
+        let parsed = parse_controllers_dumpsys(lines);
+        assert!(parsed.left.is_none());
+        assert!(parsed.right.is_none());
+    }
+
+    #[test]
+    fn test_quest_parse_dumpsys_controller_noservice() {
+        let lines = "Can't find service: battery";
+        let parsed = parse_controllers_dumpsys(lines);
+        assert!(parsed.left.is_none());
+        assert!(parsed.right.is_none());
+    }
+
+    #[test]
+    fn test_parse_recorded_outputs() {
+        let v47 = parse_controllers_dumpsys(include_str!("corpus/controllers_dumpsys_v47.txt"));
+        assert_eq!(v47.left, Some(controller(Some(70), ControllerStatus::Active, Some("1.7.0"))));
+        assert_eq!(v47.right, Some(controller(None, ControllerStatus::Searching, Some("1.7.0"))));
+
+        let v69 = parse_controllers_dumpsys(include_str!("corpus/controllers_dumpsys_v69.txt"));
+        assert_eq!(v69.right.and_then(|c| c.battery_level), Some(100));
+        assert_eq!(v69.left.map(|c| c.status), Some(ControllerStatus::Disabled));
+
+        let v72 = parse_controllers_json(include_str!("corpus/controllers_rstest_v72.json"), None)
+            .expect("json parse should succeed");
+        assert_eq!(v72.left.and_then(|c| c.battery_level), Some(90));
+    }
+
+    #[test]
+    fn test_parse_rstest_json_partial_entries() {
+        let parsed =
+            parse_controllers_json(include_str!("corpus/controllers_rstest_partial.txt"), None)
+                .expect("json parse should succeed");
+        assert_eq!(parsed.left, Some(controller(Some(75), ControllerStatus::Inactive, None)));
+        assert_eq!(parsed.right, Some(controller(None, ControllerStatus::Searching, None)));
+    }
+
+    #[test]
+    fn test_parse_controller_properties() {
+        let parsed =
+            parse_controllers_properties(include_str!("corpus/controllers_properties.txt"));
+        assert_eq!(parsed.left, Some(controller(Some(80), ControllerStatus::Active, None)));
+        assert_eq!(parsed.right, Some(controller(None, ControllerStatus::Inactive, None)));
+        assert_eq!(parse_controllers_properties(""), HeadsetControllersInfo::default());
+        let firmware =
+            parse_controllers_properties("[persist.pxr.controller.left.firmware]: [2.1.4]");
+        assert_eq!(firmware.left.and_then(|c| c.firmware_version).as_deref(), Some("2.1.4"));
+    }
+
+    #[test]
+    fn test_parse_rstest_json_not_json() {
+        assert!(
+            parse_controllers_json("/system/bin/sh: rstest: inaccessible or not found", None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_quest_parse_dumpsys_controller_missing_status() {
+        let lines = "  Paired device: 56c5083b9f13da12, Type: Left, Battery: 30%\n";
+        let parsed = parse_controllers_dumpsys(lines);
+        assert_eq!(parsed.left, Some(controller(Some(30), ControllerStatus::default(), None)));
+    }
+}
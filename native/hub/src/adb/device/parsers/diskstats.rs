@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+
+use crate::models::AppSize;
+
+/// Dumps storage usage, including per-package sizes recorded by the daily disk stats job
+pub(crate) const DISKSTATS_COMMAND: &str = "dumpsys diskstats";
+
+/// Extracts per-package sizes from `dumpsys diskstats` output.
+///
+/// The sizes come from the last run of the disk stats job, so they can be up to a day old and
+/// recently installed packages may be missing. Packages without any recorded size are skipped.
+pub(crate) fn parse_diskstats_sizes(output: &str) -> HashMap<String, AppSize> {
+    let (Some(names), Some(app), Some(data), Some(cache)) = (
+        list::<String>(output, "Package Names"),
+        list::<u64>(output, "App Sizes"),
+        list::<u64>(output, "App Data Sizes"),
+        list::<u64>(output, "Cache Sizes"),
+    ) else {
+        return HashMap::new();
+    };
+    if [app.len(), data.len(), cache.len()].iter().any(|&len| len != names.len()) {
+        return HashMap::new();
+    }
+
+    names
+        .into_iter()
+        .zip(app.into_iter().zip(data).zip(cache))
+        .map(|(name, ((app, data), cache))| (name, AppSize::new(app, data, cache)))
+        .filter(|(_, size)| !size.is_empty())
+        .collect()
+}
+
+/// Reads the JSON list on the `key: [...]` line
+fn list<T: DeserializeOwned>(output: &str, key: &str) -> Option<Vec<T>> {
+    let value = output.lines().find_map(|line| line.trim().strip_prefix(key)?.strip_prefix(':'))?;
+    serde_json::from_str(value.trim()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_package_sizes() {
+        let sizes = parse_diskstats_sizes(include_str!("corpus/diskstats_quest3.txt"));
+        assert_eq!(sizes.len(), 2);
+        let size = &sizes["com.beatgames.beatsaber"];
+        assert_eq!((size.app(), size.data(), size.cache()), (1845493760, 268435456, 1048576));
+        assert!(!sizes.contains_key("com.example.pending"));
+    }
+
+    #[test]
+    fn ignores_mismatched_lists() {
+        let output = "Package Names: [\"a\",\"b\"]\nApp Sizes: [1]\nApp Data Sizes: [1,2]\nCache \
+                      Sizes: [1,2]\n";
+        assert!(parse_diskstats_sizes(output).is_empty());
+        assert!(parse_diskstats_sizes("Can't find service: diskstats").is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+use super::{properties_command, property_value};
+use crate::models::FirmwareInfo;
+
+const FINGERPRINT_PROPERTY: &str = "ro.build.fingerprint";
+const ANDROID_VERSION_PROPERTY: &str = "ro.build.version.release";
+const SECURITY_PATCH_PROPERTY: &str = "ro.build.version.security_patch";
+const BUILD_ID_PROPERTY: &str = "ro.build.display.id";
+
+/// Builds a command printing `<property>=<value>` for the build properties and the vendor OS
+/// version properties
+pub(crate) fn firmware_command(os_version_properties: &[&str]) -> String {
+    properties_command(
+        [
+            FINGERPRINT_PROPERTY,
+            ANDROID_VERSION_PROPERTY,
+            SECURITY_PATCH_PROPERTY,
+            BUILD_ID_PROPERTY,
+        ]
+        .into_iter()
+        .chain(os_version_properties.iter().copied()),
+    )
+}
+
+/// Parses the output of `firmware_command`
+pub(crate) fn parse_firmware(output: &str, os_version_properties: &[&str]) -> FirmwareInfo {
+    let value = |property: &str| property_value(output, property).map(str::to_string);
+    FirmwareInfo {
+        fingerprint: value(FINGERPRINT_PROPERTY),
+        android_version: value(ANDROID_VERSION_PROPERTY),
+        security_patch: value(SECURITY_PATCH_PROPERTY),
+        build_id: value(BUILD_ID_PROPERTY),
+        os_version: os_version_properties.iter().find_map(|property| value(property)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vendor::Vendor;
+
+    #[test]
+    fn parses_quest_firmware() {
+        let properties = Vendor::Meta.adapter().os_version_properties();
+        let firmware = parse_firmware(include_str!("corpus/firmware_quest3.txt"), properties);
+        assert_eq!(
+            firmware,
+            FirmwareInfo {
+                fingerprint: Some(
+                    "oculus/eureka/eureka:12/SQ3A.220605.009.A1/51154110129000520:user/\
+                     release-keys"
+                        .to_string()
+                ),
+                android_version: Some("12".to_string()),
+                security_patch: Some("2024-11-01".to_string()),
+                build_id: Some("SQ3A.220605.009.A1".to_string()),
+                os_version: Some("72".to_string()),
+            }
+        );
+        assert!(firmware_command(properties).contains("getprop ro.ovr.os.api.version"));
+    }
+
+    #[test]
+    fn missing_properties_are_none() {
+        let firmware = parse_firmware("ro.build.fingerprint=\nro.build.version.release=14\n", &[]);
+        assert_eq!(
+            firmware,
+            FirmwareInfo { android_version: Some("14".to_string()), ..Default::default() }
+        );
+    }
+}
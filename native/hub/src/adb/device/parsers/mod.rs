@@ -0,0 +1,207 @@
+//! Parsers for the shell output used to refresh device state.
+//!
+//! `dumpsys` and friends print slightly different formats across Android and Quest OS versions,
+//! so every parser extracts what it can and returns partial data instead of failing the whole
+//! component.
+
+mod batch;
+mod battery;
+mod capabilities;
+mod controllers;
+mod diskstats;
+mod firmware;
+mod packages;
+mod permissions;
+mod proximity;
+mod space;
+mod tweaks;
+mod usage;
+mod usb;
+
+pub(super) use batch::{SectionOutput, ShellBatch, split_batch_output};
+pub(super) use battery::parse_battery_level;
+pub(super) use capabilities::{CAPABILITIES_PROBE_COMMAND, parse_capabilities};
+pub(super) use controllers::{UPTIME_COMMAND, UptimeClock, parse_controllers};
+pub(super) use diskstats::{DISKSTATS_COMMAND, parse_diskstats_sizes};
+pub(super) use firmware::{firmware_command, parse_firmware};
+pub(super) use packages::{package_dump_command, parse_package_list, parse_package_version};
+pub(super) use permissions::{parse_permissions, permissions_command};
+pub(super) use proximity::{PROXIMITY_STATE_COMMAND, parse_proximity_state};
+pub(super) use space::{parse_space_info, space_info_commands};
+pub(super) use tweaks::{parse_tweaks, tweaks_command};
+pub(crate) use usage::PackageUsage;
+pub(super) use usage::{USAGE_STATS_COMMAND, parse_usage_stats};
+pub(super) use usb::{USB_FUNCTIONS_MIN_SDK, USB_SPEED_MIN_SDK, format_usb_speed, is_mtp_enabled};
+
+/// Command printing the Android API level of the device
+pub(super) const SDK_VERSION_COMMAND: &str = "getprop ro.build.version.sdk";
+
+/// Parses the output of `SDK_VERSION_COMMAND`
+pub(super) fn parse_sdk_version(output: &str) -> Option<u32> {
+    leading_number(output.trim())
+}
+
+/// Returns whether a device with API level `sdk` is at least on `min`.
+///
+/// Devices with an unknown API level are assumed to run a recent OS.
+pub(super) fn sdk_at_least(sdk: Option<u32>, min: u32) -> bool {
+    sdk.is_none_or(|sdk| sdk >= min)
+}
+
+/// Finds the value of the first `key: value` or `key=value` line whose key matches one of
+/// `keys`, ignoring case and indentation.
+fn find_value<'a>(output: &'a str, keys: &[&str]) -> Option<&'a str> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once([':', '='])?;
+        let key = key.trim();
+        keys.iter().any(|candidate| key.eq_ignore_ascii_case(candidate)).then(|| value.trim())
+    })
+}
+
+/// Builds a command printing `<property>=<value>` for each system property
+fn properties_command<'a>(properties: impl IntoIterator<Item = &'a str>) -> String {
+    properties
+        .into_iter()
+        .map(|property| format!("echo \"{property}=$(getprop {property})\""))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Returns the value of `property` from `properties_command` output, None if unset
+fn property_value<'a>(output: &'a str, property: &str) -> Option<&'a str> {
+    find_value(output, &[property]).filter(|value| !value.is_empty())
+}
+
+/// Parses the digits at the start of `value`, ignoring units such as `%` that follow them
+fn leading_number<T: std::str::FromStr>(value: &str) -> Option<T> {
+    let end = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+/// Returns the JSON array in `output`, skipping stray lines printed around it
+fn json_array(output: &str) -> Option<&str> {
+    let start = output.find('[')?;
+    let end = output.rfind(']')?;
+    (start < end).then(|| &output[start..=end])
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::models::vendor::{ControllerFormat, Vendor};
+
+    /// Recorded outputs of the commands used during refresh
+    const CORPUS: &[&str] = &[
+        include_str!("corpus/battery_android10.txt"),
+        include_str!("corpus/battery_android12_updates_stopped.txt"),
+        include_str!("corpus/battery_android14.txt"),
+        include_str!("corpus/battery_scale_255.txt"),
+        include_str!("corpus/capabilities_phone.txt"),
+        include_str!("corpus/capabilities_quest3.txt"),
+        include_str!("corpus/controllers_dumpsys_v47.txt"),
+        include_str!("corpus/controllers_dumpsys_v69.txt"),
+        include_str!("corpus/controllers_properties.txt"),
+        include_str!("corpus/controllers_rstest_v72.json"),
+        include_str!("corpus/controllers_rstest_partial.txt"),
+        include_str!("corpus/diskstats_quest3.txt"),
+        include_str!("corpus/firmware_quest3.txt"),
+        include_str!("corpus/list_apps_linker_warning.txt"),
+        include_str!("corpus/permissions_quest3.txt"),
+        include_str!("corpus/proximity_v62.txt"),
+        include_str!("corpus/proximity_v74.txt"),
+        include_str!("corpus/space_df_toolbox.txt"),
+        include_str!("corpus/space_df_toybox.txt"),
+        include_str!("corpus/space_stat_warning.txt"),
+    ];
+
+    fn parse_everything(output: &str) {
+        let _ = parse_battery_level(output);
+        let _ = parse_capabilities(output);
+        for format in [
+            ControllerFormat::RstestJson,
+            ControllerFormat::OvrRemoteDumpsys,
+            ControllerFormat::Properties,
+        ] {
+            let _ = parse_controllers(format, output, None);
+        }
+        let _ = parse_diskstats_sizes(output);
+        let _ = parse_firmware(output, &["ro.ovr.os.api.version"]);
+        if let Some(properties) = Vendor::Meta.adapter().tweak_properties() {
+            let _ = parse_tweaks(output, &properties);
+        }
+        let _ = parse_package_list(output);
+        let _ = parse_permissions(output);
+        let _ = parse_proximity_state(output);
+        let _ = parse_space_info(output);
+        let _ = format_usb_speed(output);
+        let _ = is_mtp_enabled(output);
+        let _ = parse_sdk_version(output);
+        let _ = split_batch_output(output);
+    }
+
+    #[test]
+    fn find_value_ignores_case_indent_and_separator() {
+        let output = "Current state:\n  LEVEL: 42\n  mScale=100\n";
+        assert_eq!(find_value(output, &["level"]), Some("42"));
+        assert_eq!(find_value(output, &["mscale"]), Some("100"));
+        assert_eq!(find_value(output, &["voltage"]), None);
+    }
+
+    #[test]
+    fn sdk_version_strategies() {
+        assert_eq!(parse_sdk_version("32\r\n"), Some(32));
+        assert_eq!(parse_sdk_version(""), None);
+        assert!(sdk_at_least(None, 31));
+        assert!(sdk_at_least(Some(32), 31));
+        assert!(!sdk_at_least(Some(29), 31));
+    }
+
+    #[test]
+    fn corpus_truncations_do_not_panic() {
+        for output in CORPUS {
+            for (end, _) in output.char_indices() {
+                parse_everything(&output[..end]);
+                parse_everything(&output[end..]);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_output_does_not_panic(output in any::<String>()) {
+            parse_everything(&output);
+        }
+
+        #[test]
+        fn corpus_with_inserted_noise_does_not_panic(
+            index in 0..CORPUS.len(),
+            position in any::<prop::sample::Index>(),
+            noise in "[ -~\\n\\r\\t]{0,32}",
+        ) {
+            let output = CORPUS[index];
+            let boundaries = output
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([output.len()])
+                .collect::<Vec<_>>();
+            let at = *position.get(&boundaries);
+            parse_everything(&format!("{}{noise}{}", &output[..at], &output[at..]));
+        }
+
+        #[test]
+        fn battery_level_round_trips(level in 0u8..=100, indent in "[ \\t]{0,4}") {
+            let dump = format!("Current Battery Service state:\n{indent}level: {level}\n{indent}scale: 100\n");
+            prop_assert_eq!(parse_battery_level(&dump), Some(level));
+        }
+
+        #[test]
+        fn stat_output_round_trips(block_size in 1u64..=65536, total in 0u64..=1 << 32, used in 0u64..=1 << 32) {
+            let available = total.saturating_sub(used);
+            let info = parse_space_info(&format!("{block_size}:{total}:{available}\n")).unwrap();
+            prop_assert_eq!(info.total, block_size * total);
+            prop_assert_eq!(info.available, block_size * available);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use lazy_regex::regex_captures;
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::json_array;
+use crate::models::{InstalledPackage, parse_list_apps_dex};
+
+/// Parses list_apps.dex output.
+///
+/// Stray lines printed around the JSON (such as linker warnings) are ignored and entries that
+/// cannot be read are skipped, so one unexpected package does not hide the whole list.
+pub(crate) fn parse_package_list(output: &str) -> Result<Vec<InstalledPackage>> {
+    if let Ok(packages) = parse_list_apps_dex(output) {
+        return Ok(packages);
+    }
+
+    let json = json_array(output).context("No package list in list_apps.dex output")?;
+    let entries: Vec<Value> =
+        serde_json::from_str(json).context("Failed to parse list_apps.dex output")?;
+    let total = entries.len();
+    let packages = entries
+        .into_iter()
+        .filter_map(|entry| {
+            InstalledPackage::from_list_apps_entry(entry)
+                .inspect_err(|e| {
+                    debug!(error = e as &dyn std::error::Error, "Skipping package entry")
+                })
+                .ok()
+        })
+        .collect::<Vec<_>>();
+    if packages.len() < total {
+        warn!(skipped = total - packages.len(), total, "Skipped unreadable package entries");
+    }
+    Ok(packages)
+}
+
+/// Command printing the package manager state of one package
+pub(crate) fn package_dump_command(package: &str) -> String {
+    format!("dumpsys package {package}")
+}
+
+/// Version code and name of the package in `dumpsys package` output, if it is installed
+pub(crate) fn parse_package_version(dump: &str) -> Option<(u64, String)> {
+    let (_, code) = regex_captures!(r"(?m)^\s*versionCode=(\d+)", dump)?;
+    let name = regex_captures!(r"(?m)^\s*versionName=(.*)$", dump)
+        .map(|(_, name)| name.trim().to_string())
+        .unwrap_or_default();
+    Some((code.parse().ok()?, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_noise_and_unreadable_entries() {
+        let packages = parse_package_list(include_str!("corpus/list_apps_linker_warning.txt"))
+            .expect("package list should parse");
+        let names = packages.iter().map(InstalledPackage::package_name).collect::<Vec<_>>();
+        assert_eq!(names, ["com.oculus.shellenv", "com.example.partial"]);
+        assert_eq!(packages[1].version_code(), 12);
+        assert_eq!(packages[1].version_name(), "");
+    }
+
+    #[test]
+    fn fails_without_package_list() {
+        assert!(parse_package_list("Error: Could not find or load main class Main").is_err());
+        assert!(parse_package_list("[{\"uid\": 1,}]").is_err());
+    }
+
+    #[test]
+    fn parses_package_version_from_dump() {
+        let dump = include_str!("corpus/permissions_quest3.txt");
+        assert_eq!(parse_package_version(dump), Some((1042, "1.4.2".to_string())));
+
+        let dump = "Packages:\n  Package [com.example.app] (1a2b3c):\n    userId=10123\n    \
+                    versionCode=7 minSdk=29 targetSdk=32\n    versionName=1.2.0\n";
+        assert_eq!(parse_package_version(dump), Some((7, "1.2.0".to_string())));
+        assert_eq!(parse_package_version("Unable to find package: com.example.app"), None);
+    }
+}
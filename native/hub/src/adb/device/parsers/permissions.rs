@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use crate::models::signals::adb::permissions::AppPermission;
+
+/// Builds the command dumping the package manager state of `package`
+pub(crate) fn permissions_command(package: &str) -> String {
+    format!("dumpsys package {package}")
+}
+
+/// Extracts the requested permissions of a package and whether they are granted from
+/// `dumpsys package` output.
+///
+/// Install permissions are listed for the package and runtime ones per user, only the first
+/// user's grants are read. Runtime permissions missing from the requested list are appended.
+pub(crate) fn parse_permissions(output: &str) -> Vec<AppPermission> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Section {
+        Requested,
+        Install,
+        Runtime,
+    }
+
+    let mut requested: Vec<String> = Vec::new();
+    let mut install: HashMap<String, bool> = HashMap::new();
+    let mut runtime: Vec<(String, bool)> = Vec::new();
+    let mut runtime_done = false;
+    let mut section: Option<(Section, usize)> = None;
+
+    for line in output.lines() {
+        let line = line.trim_end_matches('\r');
+        let content = line.trim_start();
+        if content.is_empty() {
+            continue;
+        }
+        let indent = line.len() - content.len();
+        if let Some((_, header_indent)) = section
+            && indent <= header_indent
+        {
+            if section.is_some_and(|(kind, _)| kind == Section::Runtime) {
+                runtime_done = true;
+            }
+            section = None;
+        }
+
+        let header = match content {
+            "requested permissions:" => Some(Section::Requested),
+            "install permissions:" => Some(Section::Install),
+            "runtime permissions:" => Some(Section::Runtime),
+            _ => None,
+        };
+        if let Some(kind) = header {
+            section = Some((kind, indent));
+            continue;
+        }
+
+        let Some((kind, _)) = section else { continue };
+        let (name, attributes) = content.split_once(':').unwrap_or((content, ""));
+        let name = name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            continue;
+        }
+        let granted = attributes.contains("granted=true");
+        match kind {
+            Section::Requested => {
+                if !requested.iter().any(|existing| existing == name) {
+                    requested.push(name.to_string());
+                }
+            }
+            Section::Install => {
+                install.entry(name.to_string()).or_insert(granted);
+            }
+            Section::Runtime if !runtime_done => runtime.push((name.to_string(), granted)),
+            Section::Runtime => {}
+        }
+    }
+
+    let runtime_granted = |name: &str| {
+        runtime.iter().find(|(runtime_name, _)| runtime_name == name).map(|(_, granted)| *granted)
+    };
+    let mut permissions: Vec<AppPermission> = requested
+        .iter()
+        .map(|name| {
+            let runtime = runtime_granted(name);
+            AppPermission {
+                name: name.clone(),
+                granted: runtime.or_else(|| install.get(name).copied()).unwrap_or(false),
+                runtime: runtime.is_some(),
+            }
+        })
+        .collect();
+    for (name, granted) in &runtime {
+        if !requested.contains(name) {
+            permissions.push(AppPermission {
+                name: name.clone(),
+                granted: *granted,
+                runtime: true,
+            });
+        }
+    }
+    permissions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn permission(name: &str, granted: bool, runtime: bool) -> AppPermission {
+        AppPermission { name: name.to_string(), granted, runtime }
+    }
+
+    #[test]
+    fn parses_recorded_output() {
+        let permissions = parse_permissions(include_str!("corpus/permissions_quest3.txt"));
+        assert_eq!(
+            permissions,
+            [
+                permission("android.permission.RECORD_AUDIO", false, true),
+                permission("android.permission.INTERNET", true, false),
+                permission("android.permission.READ_EXTERNAL_STORAGE", true, true),
+                permission("android.permission.WRITE_EXTERNAL_STORAGE", false, true),
+                permission("com.oculus.permission.HAND_TRACKING", true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_package_yields_no_permissions() {
+        assert!(parse_permissions("Unable to find package: com.example.missing\n").is_empty());
+    }
+}
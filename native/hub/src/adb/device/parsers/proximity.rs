@@ -0,0 +1,40 @@
+use super::find_value;
+
+/// Dumps the VR power manager state, including the proximity sensor override
+pub(crate) const PROXIMITY_STATE_COMMAND: &str =
+    "dumpsys oculus.internal.power.IVrPowerManager/default";
+
+/// Extracts the proximity sensor override from the VR power manager `dumpsys` output.
+/// - `Virtual proximity state: CLOSE` => proximity disabled (faked)
+/// - `Virtual proximity state: DISABLED` => proximity enabled (real sensor)
+///
+/// Returns `None` when the state is missing or unknown.
+pub(crate) fn parse_proximity_state(output: &str) -> Option<bool> {
+    let value = find_value(output, &["Virtual proximity state", "mVirtualProximityState"])?;
+    let state = value.split_whitespace().next()?;
+    if state.eq_ignore_ascii_case("CLOSE") {
+        Some(true)
+    } else if state.eq_ignore_ascii_case("DISABLED") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_recorded_outputs() {
+        assert_eq!(parse_proximity_state(include_str!("corpus/proximity_v62.txt")), Some(true));
+        assert_eq!(parse_proximity_state(include_str!("corpus/proximity_v74.txt")), Some(false));
+    }
+
+    #[test]
+    fn unknown_or_missing_state_yields_none() {
+        assert_eq!(parse_proximity_state("Virtual proximity state: FAR\n"), None);
+        assert_eq!(parse_proximity_state("Can't find service: oculus.internal.power"), None);
+        assert_eq!(parse_proximity_state("mVirtualProximityState=close\n"), Some(true));
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+
+use super::sdk_at_least;
+use crate::models::SpaceInfo;
+
+/// Prints block size, total and available blocks of `/data`
+const STAT_COMMAND: &str = "stat -fc %S:%b:%a /data";
+/// Fallback for builds whose `stat` cannot report filesystem info
+const DF_COMMAND: &str = "df -k /data";
+/// First API level that ships toybox `stat -f` (Android 6.0)
+const STAT_MIN_SDK: u32 = 23;
+
+/// Returns the commands that report `/data` space, preferred first
+pub(crate) fn space_info_commands(sdk: Option<u32>) -> [&'static str; 2] {
+    if sdk_at_least(sdk, STAT_MIN_SDK) {
+        [STAT_COMMAND, DF_COMMAND]
+    } else {
+        [DF_COMMAND, STAT_COMMAND]
+    }
+}
+
+/// Parses the output of any of the `space_info_commands`
+pub(crate) fn parse_space_info(output: &str) -> Result<SpaceInfo> {
+    if let Some(info) = output.lines().find_map(|line| SpaceInfo::from_stat_output(line).ok()) {
+        return Ok(info);
+    }
+    parse_df(output).with_context(|| format!("Unrecognized space info output: {}", output.trim()))
+}
+
+/// Parses `df` output of either toybox (`1K-blocks`/`Available` columns) or the legacy toolbox
+/// (`Size`/`Free` columns with unit suffixes).
+fn parse_df(output: &str) -> Option<SpaceInfo> {
+    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next()?.split_whitespace().collect::<Vec<_>>();
+    let column = |names: &[&str]| {
+        header.iter().position(|column| names.iter().any(|name| column.eq_ignore_ascii_case(name)))
+    };
+    let total_column = column(&["1K-blocks", "Size"])?;
+    let available_column = column(&["Available", "Avail", "Free"])?;
+
+    // Long filesystem names push the values onto the next line
+    let mut values = Vec::new();
+    for line in lines {
+        values.extend(line.split_whitespace());
+        if values.len() > total_column.max(available_column) {
+            break;
+        }
+    }
+    let total = parse_df_size(values.get(total_column)?)?;
+    let available = parse_df_size(values.get(available_column)?)?;
+    (total > 0).then(|| SpaceInfo { total, available: available.min(total) })
+}
+
+/// Parses a `df` size in KiB, with an optional `K`/`M`/`G`/`T` suffix
+fn parse_df_size(value: &str) -> Option<u64> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit() && c != '.') {
+        Some(index) => value.split_at(index),
+        None => (value, "K"),
+    };
+    let multiplier: u64 = match unit.to_ascii_uppercase().as_str() {
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        "T" => 1 << 40,
+        _ => return None,
+    };
+    if number.contains('.') {
+        let size = number.parse::<f64>().ok()?;
+        (size.is_finite() && size >= 0.0).then_some((size * multiplier as f64) as u64)
+    } else {
+        number.parse::<u64>().ok()?.checked_mul(multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1 << 30;
+
+    #[test]
+    fn parses_recorded_outputs() {
+        let stat = parse_space_info(include_str!("corpus/space_stat_warning.txt")).unwrap();
+        assert_eq!(stat.total, 4096 * 28751314);
+        assert_eq!(stat.available, 4096 * 18511314);
+
+        let toybox = parse_space_info(include_str!("corpus/space_df_toybox.txt")).unwrap();
+        assert_eq!(toybox.total, 115005256 * 1024);
+        assert_eq!(toybox.available, 74045256 * 1024);
+
+        let toolbox = parse_space_info(include_str!("corpus/space_df_toolbox.txt")).unwrap();
+        assert_eq!(toolbox.total / GIB, 52);
+        assert_eq!(toolbox.available / GIB, 31);
+    }
+
+    #[test]
+    fn parses_wrapped_df_lines() {
+        let output = "Filesystem 1K-blocks Used Available Use% Mounted \
+                      on\n/dev/block/platform/soc/1d84000.ufshc/by-name/userdata\n\x20 1000 400 \
+                      600 40% /data\n";
+        let info = parse_space_info(output).unwrap();
+        assert_eq!(info.total, 1000 * 1024);
+        assert_eq!(info.available, 600 * 1024);
+    }
+
+    #[test]
+    fn rejects_unknown_output() {
+        assert!(parse_space_info("stat: '/data': Permission denied").is_err());
+        assert!(parse_space_info("").is_err());
+        assert!(parse_df_size("12Q").is_none());
+    }
+
+    #[test]
+    fn prefers_stat_on_recent_devices() {
+        assert_eq!(space_info_commands(Some(32))[0], STAT_COMMAND);
+        assert_eq!(space_info_commands(None)[0], STAT_COMMAND);
+        assert_eq!(space_info_commands(Some(22))[0], DF_COMMAND);
+    }
+}
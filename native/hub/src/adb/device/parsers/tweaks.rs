@@ -0,0 +1,57 @@
+use super::{properties_command, property_value};
+use crate::models::{DeviceTweaks, TextureSize, vendor::TweakProperties};
+
+/// Builds a command printing the current values of the tweak properties
+pub(crate) fn tweaks_command(properties: &TweakProperties) -> String {
+    properties_command(properties.all())
+}
+
+/// Parses the output of `tweaks_command`, unset or malformed values are None
+pub(crate) fn parse_tweaks(output: &str, properties: &TweakProperties) -> DeviceTweaks {
+    fn value<T: std::str::FromStr>(output: &str, property: &str) -> Option<T> {
+        property_value(output, property)?.parse().ok()
+    }
+    let width = value(output, properties.texture_width);
+    let height = value(output, properties.texture_height);
+    DeviceTweaks {
+        refresh_rate: value(output, properties.refresh_rate),
+        cpu_level: value(output, properties.cpu_level),
+        gpu_level: value(output, properties.gpu_level),
+        texture_size: width.zip(height).map(|(width, height)| TextureSize { width, height }),
+        foveation_level: value(output, properties.foveation_level),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vendor::Vendor;
+
+    #[test]
+    fn parses_quest_tweaks() {
+        let properties = Vendor::Meta.adapter().tweak_properties().unwrap();
+        let output = [
+            "debug.oculus.refreshRate=120",
+            "debug.oculus.cpuLevel=",
+            "debug.oculus.gpuLevel=4",
+            "debug.oculus.textureWidth=2048",
+            "debug.oculus.textureHeight=2048",
+            "debug.oculus.foveation.level=high",
+        ]
+        .join("\n");
+        assert_eq!(
+            parse_tweaks(&output, &properties),
+            DeviceTweaks {
+                refresh_rate: Some(120),
+                cpu_level: None,
+                gpu_level: Some(4),
+                texture_size: Some(TextureSize { width: 2048, height: 2048 }),
+                foveation_level: None,
+            }
+        );
+
+        let half_set = "debug.oculus.textureWidth=2048\ndebug.oculus.textureHeight=\n";
+        assert_eq!(parse_tweaks(half_set, &properties), DeviceTweaks::default());
+        assert_eq!(tweaks_command(&properties).matches("getprop").count(), 6);
+    }
+}
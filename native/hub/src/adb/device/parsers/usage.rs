@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+/// Dumps app usage statistics for all packages
+pub(crate) const USAGE_STATS_COMMAND: &str = "dumpsys usagestats";
+
+/// Usage of one package as recorded by the usage stats service
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PackageUsage {
+    /// Local time of last use as printed by the device (`YYYY-MM-DD HH:MM:SS`)
+    pub(crate) last_used: Option<String>,
+    /// Foreground time in seconds over the longest interval the device keeps
+    pub(crate) total_time_used_secs: Option<u64>,
+}
+
+/// Extracts per-package usage from `dumpsys usagestats` output.
+///
+/// The dump lists the same package once per interval (daily, weekly, monthly, yearly), so the
+/// latest last use and the largest total time are kept.
+pub(crate) fn parse_usage_stats(output: &str) -> HashMap<String, PackageUsage> {
+    let mut usage: HashMap<String, PackageUsage> = HashMap::new();
+    for line in output.lines() {
+        let line = line.trim();
+        let Some(package) = attribute(line, "package") else {
+            continue;
+        };
+        let last_used = attribute(line, "lastTimeUsed");
+        let total_time_used = attribute(line, "totalTimeUsed").and_then(parse_elapsed_time);
+        if last_used.is_none() && total_time_used.is_none() {
+            continue;
+        }
+
+        let entry = usage.entry(package.to_string()).or_default();
+        // Timestamps are zero padded, so they compare correctly as strings
+        if let Some(last_used) = last_used.filter(|time| !time.starts_with("1970-"))
+            && entry.last_used.as_deref().is_none_or(|current| current < last_used)
+        {
+            entry.last_used = Some(last_used.to_string());
+        }
+        if let Some(total) = total_time_used {
+            entry.total_time_used_secs = entry.total_time_used_secs.max(Some(total));
+        }
+    }
+    usage
+}
+
+/// Finds `key=value` or `key="value"` on a line
+fn attribute<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.match_indices(key).map(|(index, _)| index).find(|&index| {
+        (index == 0 || line.as_bytes()[index - 1] == b' ')
+            && line[index + key.len()..].starts_with('=')
+    })? + key.len()
+        + 1;
+    let rest = &line[start..];
+    match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next(),
+        None => rest.split_whitespace().next(),
+    }
+}
+
+/// Parses Android elapsed time like `1:02:03` or `02:03` into seconds
+fn parse_elapsed_time(value: &str) -> Option<u64> {
+    value
+        .split(':')
+        .try_fold(0u64, |total, part| Some(total * 60 + part.trim().parse::<u64>().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_latest_use_across_intervals() {
+        let output = concat!(
+            "In-memory daily stats\n",
+            "  packages\n",
+            "    package=com.example.game totalTimeUsed=\"12:30\" lastTimeUsed=\"2024-05-02 \
+             20:15:00\" totalTimeVisible=\"12:31\"\n",
+            "    package=com.example.idle totalTimeUsed=\"00:00\" lastTimeUsed=\"1970-01-01 \
+             00:00:00\"\n",
+            "In-memory yearly stats\n",
+            "  packages\n",
+            "    package=com.example.game totalTimeUsed=\"3:05:10\" lastTimeUsed=\"2024-04-30 \
+             10:00:00\"\n",
+            "    package=com.example.other totalTimeUsed=\"01:00\" lastTimeUsed=\"2024-01-01 \
+             08:00:00\"\n",
+            "  events\n",
+            "    time=\"2024-05-02 20:15:00\" type=ACTIVITY_RESUMED package=com.example.game\n",
+        );
+        let usage = parse_usage_stats(output);
+
+        assert_eq!(usage.len(), 3);
+        assert_eq!(
+            usage["com.example.game"],
+            PackageUsage {
+                last_used: Some("2024-05-02 20:15:00".into()),
+                total_time_used_secs: Some(3 * 3600 + 5 * 60 + 10),
+            }
+        );
+        assert_eq!(usage["com.example.idle"].last_used, None);
+        assert_eq!(usage["com.example.other"].total_time_used_secs, Some(60));
+    }
+}
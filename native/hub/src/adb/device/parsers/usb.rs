@@ -0,0 +1,62 @@
+/// First API level with `svc usb getFunctions` (Android 9)
+pub(crate) const USB_FUNCTIONS_MIN_SDK: u32 = 28;
+/// First API level with `svc usb getUsbSpeed` (Android 12)
+pub(crate) const USB_SPEED_MIN_SDK: u32 = 31;
+
+/// Returns whether MTP is among the functions printed by `svc usb getFunctions`
+pub(crate) fn is_mtp_enabled(output: &str) -> bool {
+    output.split([',', '\n']).any(|function| function.trim() == "mtp")
+}
+
+/// Formats the output of `svc usb getUsbSpeed` (Mbps) for display
+pub(crate) fn format_usb_speed(output: &str) -> Option<String> {
+    let value = output.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(mbps) = value.parse::<u64>() {
+        if mbps >= 1024 {
+            let gbps = mbps as f64 / 1024.0;
+            let formatted = if gbps.fract() == 0.0 {
+                format!("{gbps:.0}")
+            } else {
+                format!("{gbps:.2}").trim_end_matches('0').trim_end_matches('.').to_string()
+            };
+            return Some(format!("{formatted} Gbps"));
+        }
+        return Some(format!("{mbps} Mbps"));
+    }
+    Some(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numeric_usb_speed() {
+        assert_eq!(format_usb_speed("480\n").as_deref(), Some("480 Mbps"));
+    }
+
+    #[test]
+    fn formats_gigabit_usb_speed() {
+        assert_eq!(format_usb_speed("5120\n").as_deref(), Some("5 Gbps"));
+    }
+
+    #[test]
+    fn keeps_text_usb_speed() {
+        assert_eq!(format_usb_speed("high speed\n").as_deref(), Some("high speed"));
+    }
+
+    #[test]
+    fn ignores_empty_usb_values() {
+        assert_eq!(format_usb_speed(" \n"), None);
+    }
+
+    #[test]
+    fn detects_mtp_function() {
+        assert!(is_mtp_enabled("mtp,adb\n"));
+        assert!(!is_mtp_enabled("adb\n"));
+        assert!(!is_mtp_enabled("none"));
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result, ensure};
+use tracing::{info, instrument};
+
+use super::{
+    AdbDevice,
+    parsers::{parse_permissions, permissions_command},
+};
+use crate::{
+    adb::PackageName,
+    models::signals::adb::permissions::{AppPermission, is_valid_permission_name},
+};
+
+impl AdbDevice {
+    /// Lists the permissions `package` requests and whether they are granted
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn app_permissions(
+        &self,
+        package: &PackageName,
+    ) -> Result<Vec<AppPermission>> {
+        let output = self
+            .shell_checked(&permissions_command(package.as_str()))
+            .await
+            .context("Failed to read package permissions")?;
+        ensure!(
+            output.contains(&format!("Package [{package}]")),
+            "Package {package} is not installed"
+        );
+        Ok(parse_permissions(&output))
+    }
+
+    /// Grants or revokes a runtime permission of `package`
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn set_app_permission(
+        &self,
+        package: &PackageName,
+        permission: &str,
+        granted: bool,
+    ) -> Result<()> {
+        ensure!(is_valid_permission_name(permission), "Invalid permission name: {permission}");
+        let action = if granted { "grant" } else { "revoke" };
+        self.shell_checked(&format!("pm {action} {package} {permission}"))
+            .await
+            .with_context(|| format!("Failed to {action} {permission}"))?;
+        info!(%package, permission, granted, "App permission changed");
+        Ok(())
+    }
+}
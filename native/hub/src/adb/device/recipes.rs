@@ -0,0 +1,65 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::{Context, Result, anyhow};
+use forensic_adb::UnixPath;
+use tokio::time::sleep;
+use tracing::{info, instrument, warn};
+
+use super::AdbDevice;
+use crate::{
+    adb::PackageName,
+    models::signals::recipes::{InstallRecipe, RecipeStep},
+};
+
+/// How long an app started by a recipe is left running before it is stopped
+const LAUNCH_ONCE_RUN_TIME: Duration = Duration::from_secs(5);
+
+impl AdbDevice {
+    /// Runs the steps of a post-install recipe in order.
+    ///
+    /// A failed step does not stop the following ones, the returned error lists every failure.
+    #[instrument(level = "debug", skip(self, recipe), fields(serial = %self.serial, package = %recipe.package_name), err)]
+    pub(crate) async fn run_install_recipe(&self, recipe: &InstallRecipe) -> Result<()> {
+        let package = PackageName::parse(&recipe.package_name)?;
+        let mut failures = Vec::new();
+        for (index, step) in recipe.steps.iter().enumerate() {
+            if let Err(e) = self.run_recipe_step(&package, step).await {
+                warn!(error = %format!("{e:#}"), step = index + 1, "Install recipe step failed");
+                failures.push(format!("step {}: {e:#}", index + 1));
+            }
+        }
+        if !failures.is_empty() {
+            return Err(anyhow!("{}", failures.join("; ")));
+        }
+        info!(steps = recipe.steps.len(), "Install recipe completed");
+        Ok(())
+    }
+
+    async fn run_recipe_step(&self, package: &PackageName, step: &RecipeStep) -> Result<()> {
+        match step {
+            RecipeStep::GrantPermission(permission) => {
+                self.set_app_permission(package, permission, true).await
+            }
+            RecipeStep::PushFile { local_path, remote_path } => {
+                let source = Path::new(local_path);
+                let dest = UnixPath::new(remote_path);
+                if source.is_dir() {
+                    self.push_dir_to_path(source, dest, false).await
+                } else {
+                    self.push(source, dest).await
+                }
+                .with_context(|| format!("Failed to push {local_path}"))
+            }
+            RecipeStep::LaunchOnce => {
+                self.launch(package).await?;
+                sleep(LAUNCH_ONCE_RUN_TIME).await;
+                self.force_stop(package).await
+            }
+            RecipeStep::SetProperty { name, value } => self
+                .shell_checked(&format!("setprop {name} '{value}'"))
+                .await
+                .map(|_| ())
+                .with_context(|| format!("Failed to set {name}")),
+        }
+    }
+}
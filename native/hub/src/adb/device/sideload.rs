@@ -1,23 +1,468 @@
 use std::{
     error::Error,
-    path::Path,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result, bail, ensure};
 use forensic_adb::{DeviceError, DirectoryTransferProgress, UnixPath};
+use futures::future::BoxFuture;
 use lazy_regex::{Lazy, Regex, lazy_regex};
-use tokio::sync::mpsc::{self, UnboundedSender};
+use rinf::RustSignal;
+use tempfile::TempDir;
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, BufReader},
+    sync::mpsc::{self, UnboundedSender},
+};
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Span, debug, info, instrument, trace, warn};
 
-use super::{AdbDevice, backup::BackupOptions};
-use crate::{adb::PackageName, archive::decompress_all_7z_in_dir, models::apk_info::get_apk_info};
+use super::{
+    AdbDevice, backup::BackupOptions, local_source::is_dir_writable, splits::read_split_set,
+};
+use crate::{
+    adb::PackageName,
+    archive::{
+        ArchiveEntryReader, ArchiveFormat, ExtractOptions, ExtractProgress,
+        decompress_all_7z_in_dir, extract_apk_bundle, extract_archive, extract_single_from_archive,
+        is_apk_bundle, list_archive_entries,
+    },
+    models::{
+        apk_info::get_apk_info,
+        signals::adb::install_script::{
+            InstallScriptUnsupported, ScriptOperation, ScriptOperationKind, UnsupportedScriptLine,
+        },
+    },
+    shutdown::CleanupGuard,
+    utils::{canonicalize, dir_has_any_files, dir_size},
+};
 
 /// Regex to split command arguments - handles quoted arguments with spaces
 /// Note: This is a simplified parser for install scripts and may not handle all edge cases
 static COMMAND_ARGS_REGEX: Lazy<Regex> = lazy_regex!(r#""[^"]*"|'[^']*'|[^\s]+"#);
 
+/// Command line of an install script, with comments and redirections removed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScriptCommand {
+    /// 1-based line number in the script
+    pub(crate) line_num: usize,
+    pub(crate) command: String,
+    /// Arguments of the command with surrounding quotes removed, the first one is the program
+    pub(crate) tokens: Vec<String>,
+}
+
+/// Splits an install script into commands, skipping empty and comment lines
+pub(crate) fn parse_install_script(content: &str) -> Result<Vec<ScriptCommand>> {
+    let mut commands = Vec::new();
+    for (line_index, line) in content.lines().enumerate() {
+        let line_num = line_index + 1;
+        // Remove comments and redirections
+        let line = line.split('#').next().unwrap_or("").split("REM").next().unwrap_or("").trim();
+        if line.is_empty() {
+            trace!(line_num, "Skipping empty or comment line");
+            continue;
+        }
+
+        let command = line.split('>').next().unwrap_or("").trim();
+        ensure!(!command.is_empty(), "Line {line_num}: Line is empty after removing redirections");
+
+        let tokens = COMMAND_ARGS_REGEX
+            .find_iter(command)
+            .map(|m| {
+                let token = m.as_str();
+                // Remove surrounding quotes but preserve the content
+                if (token.starts_with('"') && token.ends_with('"'))
+                    || (token.starts_with('\'') && token.ends_with('\''))
+                {
+                    token[1..token.len() - 1].to_string()
+                } else {
+                    token.to_string()
+                }
+            })
+            .collect();
+        commands.push(ScriptCommand { line_num, command: command.to_string(), tokens });
+    }
+    Ok(commands)
+}
+
+/// Longest pause an install script may request
+const MAX_SCRIPT_PAUSE: Duration = Duration::from_secs(300);
+
+/// What a supported install script line does. Local paths have `%~dp0` expanded already and
+/// stay inside the install folder.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ScriptAction {
+    /// `7z` line, archives are unpacked before the script runs
+    Unpack,
+    Install(String),
+    /// Split APKs of one app, installed in one session
+    InstallMultiple(Vec<String>),
+    Uninstall(String),
+    /// Command run in the device shell as is
+    Shell(Vec<String>),
+    Push {
+        source: String,
+        dest: String,
+    },
+    Pull {
+        source: String,
+        dest: String,
+    },
+    Pause(Duration),
+    /// `adb wait-for-device`, the device is already connected when a script runs
+    WaitForDevice,
+}
+
+/// Asks whether the operations of an install script at the given path may run. Called once
+/// the script was checked, before anything is done on the device.
+pub(crate) type ScriptApproval<'a> =
+    dyn Fn(String, Vec<ScriptOperation>) -> BoxFuture<'a, bool> + Send + Sync + 'a;
+
+impl ScriptAction {
+    /// Operation shown to the user when the script is reviewed, `None` for lines that do
+    /// nothing on the device
+    fn operation(self, line_num: usize) -> Option<ScriptOperation> {
+        let (kind, detail) = match self {
+            Self::Unpack | Self::WaitForDevice => return None,
+            Self::Install(apk) => (ScriptOperationKind::Install, apk),
+            Self::InstallMultiple(apks) => (ScriptOperationKind::Install, apks.join(", ")),
+            Self::Uninstall(package) => (ScriptOperationKind::Uninstall, package),
+            Self::Shell(args) => (ScriptOperationKind::Shell, args.join(" ")),
+            Self::Push { source, dest } => {
+                (ScriptOperationKind::Push, format!("{source} → {dest}"))
+            }
+            Self::Pull { source, dest } => {
+                (ScriptOperationKind::Pull, format!("{source} → {dest}"))
+            }
+            Self::Pause(duration) => {
+                (ScriptOperationKind::Pause, format!("{}s", duration.as_secs_f32()))
+            }
+        };
+        Some(ScriptOperation { line: line_num as u32, kind, detail })
+    }
+}
+
+impl ScriptCommand {
+    /// Reads what the line does, failing for commands that cannot be run
+    pub(crate) fn action(&self) -> Result<ScriptAction> {
+        let Self { command, tokens, .. } = self;
+        let expand = |path: &str| local_script_path(path);
+        match tokens[0].to_lowercase().as_str() {
+            "7z" => return Ok(ScriptAction::Unpack),
+            "sleep" | "wait" | "timeout" => return parse_script_pause(&tokens[1..]),
+            "adb" => {}
+            _ => bail!("Unsupported command '{command}'"),
+        }
+
+        ensure!(tokens.len() >= 2, "ADB command missing operation");
+        let adb_args_raw = &tokens[2..];
+        let adb_args = adb_args_raw.iter().filter(|arg| !arg.starts_with('-')).collect::<Vec<_>>();
+        let expect_args = |count: usize| {
+            ensure!(
+                adb_args.len() == count,
+                "adb {}: wrong number of arguments: expected {count}, got {}",
+                tokens[1],
+                adb_args.len()
+            );
+            Ok(())
+        };
+        Ok(match tokens[1].as_str() {
+            "install" => {
+                // We only care about the APK path
+                let apk = adb_args
+                    .iter()
+                    .find(|arg| arg.ends_with(".apk"))
+                    .context("adb install: missing APK path")?;
+                ScriptAction::Install(expand(apk)?)
+            }
+            "install-multiple" => {
+                let apks = adb_args
+                    .iter()
+                    .filter(|arg| arg.ends_with(".apk"))
+                    .map(|apk| expand(apk))
+                    .collect::<Result<Vec<_>>>()?;
+                ensure!(!apks.is_empty(), "adb install-multiple: missing APK paths");
+                ScriptAction::InstallMultiple(apks)
+            }
+            "uninstall" => {
+                expect_args(1)?;
+                ScriptAction::Uninstall(adb_args[0].clone())
+            }
+            "shell" => {
+                ensure!(!adb_args_raw.is_empty(), "adb shell: missing command");
+                match adb_args_raw {
+                    [pm, uninstall, package] if pm == "pm" && uninstall == "uninstall" => {
+                        ScriptAction::Uninstall(package.clone())
+                    }
+                    _ => ScriptAction::Shell(adb_args_raw.to_vec()),
+                }
+            }
+            "push" => {
+                expect_args(2)?;
+                ScriptAction::Push { source: expand(adb_args[0])?, dest: adb_args[1].clone() }
+            }
+            "pull" => {
+                expect_args(2)?;
+                ScriptAction::Pull { source: adb_args[0].clone(), dest: expand(adb_args[1])? }
+            }
+            "wait-for-device" => ScriptAction::WaitForDevice,
+            _ => bail!("Unsupported ADB command '{command}'"),
+        })
+    }
+}
+
+/// Reads a `sleep <seconds>`, `wait <seconds>` or `timeout [/t] <seconds> [/nobreak]` line
+fn parse_script_pause(args: &[String]) -> Result<ScriptAction> {
+    let seconds = match args
+        .iter()
+        .filter(|arg| !arg.eq_ignore_ascii_case("/t") && !arg.eq_ignore_ascii_case("/nobreak"))
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [seconds] => seconds.trim_end_matches('s').parse::<f64>().ok(),
+        _ => None,
+    };
+    let duration = seconds
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .context("Pause needs a number of seconds")?;
+    ensure!(
+        duration <= MAX_SCRIPT_PAUSE,
+        "Pause of {}s is longer than the allowed {}s",
+        duration.as_secs(),
+        MAX_SCRIPT_PAUSE.as_secs()
+    );
+    Ok(ScriptAction::Pause(duration))
+}
+
+/// Reads a local path from an install script, relative to the install folder.
+///
+/// `%~dp0` is the script folder, which relative paths are resolved against anyway. Other
+/// variables are kept as literal text, scripts must not reach outside the install folder.
+fn local_script_path(path: &str) -> Result<String> {
+    static SCRIPT_DIR_REGEX: Lazy<Regex> = lazy_regex!(r"%~dp0[\\/]?");
+    let path = SCRIPT_DIR_REGEX.replace_all(path, "").into_owned();
+    ensure!(is_inside_folder(&path), "Local path '{path}' leaves the install folder");
+    Ok(path)
+}
+
+/// Whether a relative path with `/` or `\` separators stays inside the folder it is joined to
+fn is_inside_folder(path: &str) -> bool {
+    if path.starts_with(['/', '\\']) {
+        return false;
+    }
+    let mut depth = 0usize;
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => match depth.checked_sub(1) {
+                Some(parent) => depth = parent,
+                None => return false,
+            },
+            // Drive letters and alternate data streams
+            _ if part.contains(':') => return false,
+            _ => depth += 1,
+        }
+    }
+    true
+}
+
+/// Resolves `relative` against `dir`, failing if the result leaves all `roots` once symlinks
+/// are resolved. The path does not have to exist yet.
+async fn contained_path(dir: &Path, relative: &str, roots: &[PathBuf]) -> Result<PathBuf> {
+    let path = dir.join(relative.replace('\\', std::path::MAIN_SEPARATOR_STR));
+    let mut existing = path.as_path();
+    let mut missing = Vec::new();
+    let canonical = loop {
+        match canonicalize(existing).await {
+            Ok(canonical) => break canonical,
+            Err(_) => {
+                missing.push(existing.file_name().context("Path has no existing parent")?);
+                existing = existing.parent().context("Path has no existing parent")?;
+            }
+        }
+    };
+    let resolved = missing.iter().rev().fold(canonical, |path, part| path.join(part));
+    ensure!(
+        roots.iter().any(|root| resolved.starts_with(root)),
+        "Local path '{relative}' leaves the install folder"
+    );
+    Ok(path)
+}
+
+/// Dry run of an install script, returns each line that cannot be run with the reason
+fn validate_install_script(commands: &[ScriptCommand]) -> Vec<UnsupportedScriptLine> {
+    commands
+        .iter()
+        .filter_map(|command| {
+            command.action().err().map(|e| UnsupportedScriptLine {
+                line: command.line_num as u32,
+                command: command.command.clone(),
+                reason: format!("{e:#}"),
+            })
+        })
+        .collect()
+}
+
+/// Operations of a script as shown to the user when it is reviewed
+fn script_operations(commands: &[ScriptCommand]) -> Vec<ScriptOperation> {
+    commands
+        .iter()
+        .filter_map(|command| command.action().ok()?.operation(command.line_num))
+        .collect()
+}
+
+/// Reads the install script an app folder or archive would run, with its operations for review.
+///
+/// Returns `None` if there is no script, if it has lines that cannot be run, or if it is only
+/// found after unpacking (nested archives, single-stream archives). Those scripts are reviewed
+/// or reported when they run.
+pub(crate) async fn preview_install_script(
+    app_path: &Path,
+) -> Result<Option<(String, Vec<ScriptOperation>)>> {
+    let is_script = |name: &str| name.eq_ignore_ascii_case("install.txt");
+    let (script_path, content) = if app_path.is_dir() {
+        let mut dir = tokio::fs::read_dir(app_path).await?;
+        let mut script = None;
+        while let Some(entry) = dir.next_entry().await? {
+            if entry.file_name().to_str().is_some_and(is_script) {
+                script = Some(entry.path());
+                break;
+            }
+        }
+        let Some(script) = script else {
+            return Ok(None);
+        };
+        let content =
+            tokio::fs::read_to_string(&script).await.context("Failed to read install script")?;
+        (script.display().to_string(), content)
+    } else if is_sideload_archive(app_path)
+        && !is_apk_bundle(app_path)
+        && !ArchiveFormat::detect(app_path).is_some_and(ArchiveFormat::is_single_stream)
+    {
+        let entries = list_archive_entries(app_path).await.context("Failed to list archive")?;
+        let Some(entry) = entries.iter().find(|e| is_script(archive_entry_file_name(&e.path)))
+        else {
+            return Ok(None);
+        };
+        let mut reader = ArchiveEntryReader::open(app_path, &entry.path)?;
+        let mut content = String::new();
+        reader
+            .reader()
+            .read_to_string(&mut content)
+            .await
+            .context("Failed to read install script from archive")?;
+        reader.finish().await?;
+        (app_path.join(&entry.path).display().to_string(), content)
+    } else {
+        return Ok(None);
+    };
+
+    let commands = parse_install_script(&content)?;
+    if !validate_install_script(&commands).is_empty() {
+        return Ok(None);
+    }
+    Ok(Some((script_path, script_operations(&commands))))
+}
+
+/// Returns whether the path looks like an archive that can be sideloaded directly
+fn is_sideload_archive(path: &Path) -> bool {
+    ArchiveFormat::detect(path).is_some()
+}
+
+/// Whether a package manager error is solved by uninstalling the installed app first
+fn is_reinstall_conflict(msg: &str) -> bool {
+    msg.contains("INSTALL_FAILED_VERSION_DOWNGRADE")
+        || msg.contains("INSTALL_FAILED_UPDATE_INCOMPATIBLE")
+}
+
+/// Creates a temporary directory to extract `archive` into.
+///
+/// It is created next to the archive to stay on the same drive, unless that directory is
+/// read-only.
+fn extraction_temp_dir(archive: &Path) -> Result<TempDir> {
+    let archive_dir = archive.parent().context("Failed to get archive directory")?;
+    let temp_dir_builder = tempfile::Builder::new().prefix(".yaas-sideload-").clone();
+    if is_dir_writable(archive_dir) {
+        temp_dir_builder.tempdir_in(archive_dir)
+    } else {
+        info!("Archive directory is read-only, extracting to the system temporary directory");
+        temp_dir_builder.tempdir()
+    }
+    .context("Failed to create temporary directory")
+}
+
+pub(super) fn send_progress(
+    progress_sender: &UnboundedSender<SideloadProgress>,
+    status: &str,
+    progress: Option<f32>,
+) {
+    let _ = progress_sender.send(SideloadProgress { status: status.to_string(), progress });
+}
+
+/// Archive extraction options reporting progress through `on_progress` and stopping on `token`
+fn extract_options<'a>(
+    token: &CancellationToken,
+    on_progress: &'a (dyn Fn(&ExtractProgress) + Sync),
+) -> ExtractOptions<'a> {
+    ExtractOptions {
+        cancel: Some(token.clone()),
+        on_progress: Some(on_progress),
+        ..Default::default()
+    }
+}
+
+/// Forwards archive extraction progress as sideload progress
+fn send_extract_progress(
+    progress_sender: &UnboundedSender<SideloadProgress>,
+    progress: &ExtractProgress,
+) {
+    let status = match progress.total_files {
+        0 => "Extracting archive".to_string(),
+        total => format!("Extracting {}/{}", (progress.files_done + 1).min(total), total),
+    };
+    send_progress(progress_sender, &status, progress.fraction());
+}
+
+/// File name of an archive entry path
+fn archive_entry_file_name(entry_path: &str) -> &str {
+    entry_path.rsplit('/').next().unwrap_or(entry_path)
+}
+
+/// Directory of an archive entry path, `None` for entries at the archive root
+fn archive_entry_dir(entry_path: &str) -> Option<&str> {
+    entry_path.rsplit_once('/').map(|(dir, _)| dir)
+}
+
+/// The only entry of `dir`, if it is a directory
+async fn single_subdir(dir: &Path) -> Result<Option<PathBuf>> {
+    let mut rd = tokio::fs::read_dir(dir).await?;
+    let Some(first) = rd.next_entry().await? else {
+        return Ok(None);
+    };
+    if rd.next_entry().await?.is_some() || !first.file_type().await?.is_dir() {
+        return Ok(None);
+    }
+    Ok(Some(first.path()))
+}
+
+/// Names of subdirectories among `entries` that directly contain an APK file
+async fn find_nested_apk_dirs(entries: &[tokio::fs::DirEntry]) -> Vec<String> {
+    let mut nested = Vec::new();
+    for entry in entries.iter().filter(|e| e.path().is_dir()) {
+        let Ok(mut rd) = tokio::fs::read_dir(entry.path()).await else {
+            continue;
+        };
+        while let Ok(Some(child)) = rd.next_entry().await {
+            if child.path().extension().and_then(|s| s.to_str()) == Some("apk") {
+                nested.push(format!("'{}'", entry.file_name().to_string_lossy()));
+                break;
+            }
+        }
+    }
+    nested
+}
+
 /// Progress information for sideload operations
 #[derive(Debug)]
 pub(crate) struct SideloadProgress {
@@ -27,12 +472,14 @@ pub(crate) struct SideloadProgress {
 
 impl AdbDevice {
     /// Executes an install script from the given path
-    #[instrument(level = "debug", skip(self, token))]
+    #[instrument(level = "debug", skip(self, progress_sender, token, approve_script), fields(serial = %self.serial))]
     async fn execute_install_script(
         &self,
         script_path: &Path,
         backups_location: &Path,
+        progress_sender: &UnboundedSender<SideloadProgress>,
         token: CancellationToken,
+        approve_script: &ScriptApproval<'_>,
         auto_reinstall_on_conflict: bool,
     ) -> Result<()> {
         let script_content = tokio::fs::read_to_string(script_path)
@@ -40,63 +487,76 @@ impl AdbDevice {
             .context("Failed to read install script")?;
         let script_dir = script_path.parent().context("Failed to get script directory")?;
 
-        // Unpack all 7z archives if present
-        decompress_all_7z_in_dir(script_dir, Some(token.clone()))
-            .await
-            .context("Failed to decompress .7z archives in install folder")?;
+        // Files the script creates (unpacked archives, pulled files) go next to it, unless the
+        // folder is read-only. Then they go to a staging directory, which is checked first when
+        // resolving paths used by the script.
+        let staging_dir = if is_dir_writable(script_dir) {
+            None
+        } else {
+            info!("Install folder is read-only, using a staging directory");
+            Some(
+                tempfile::Builder::new()
+                    .prefix(".yaas-install-")
+                    .tempdir()
+                    .context("Failed to create staging directory")?,
+            )
+        };
+        let work_dir = staging_dir.as_ref().map_or(script_dir, |dir| dir.path());
+        let roots = [
+            canonicalize(script_dir).await.context("Failed to resolve script folder")?,
+            canonicalize(work_dir).await.context("Failed to resolve staging folder")?,
+        ];
+        let resolve_path = async |relative: &str| {
+            let staged = contained_path(work_dir, relative, &roots).await?;
+            if staged.exists() {
+                Ok(staged)
+            } else {
+                contained_path(script_dir, relative, &roots).await
+            }
+        };
 
-        for (line_index, line) in script_content.lines().enumerate() {
-            let line_num = line_index + 1;
-            // Remove comments and redirections
-            let line =
-                line.split('#').next().unwrap_or("").split("REM").next().unwrap_or("").trim();
-            if line.is_empty() {
-                trace!(line_num, "Skipping empty or comment line");
-                continue;
+        // Check every line before running any, so a script is not left half done
+        let commands = parse_install_script(&script_content)?;
+        let unsupported = validate_install_script(&commands);
+        if !unsupported.is_empty() {
+            let lines = unsupported
+                .iter()
+                .map(|line| format!("line {}: {}", line.line, line.reason))
+                .collect::<Vec<_>>();
+            warn!(?lines, "Install script has lines that cannot be run");
+            InstallScriptUnsupported {
+                script_path: script_path.display().to_string(),
+                lines: unsupported,
             }
+            .send_signal_to_dart();
+            bail!("Install script has lines that cannot be run: {}", lines.join("; "));
+        }
 
-            let command = line.split('>').next().unwrap_or("").trim();
-            ensure!(
-                !command.is_empty(),
-                "Line {line_num}: Line is empty after removing redirections"
-            );
-            debug!(line_num, command, "Parsed command");
+        let operations = script_operations(&commands);
+        send_progress(progress_sender, "Waiting for install script approval", None);
+        let approved = tokio::select! {
+            approved = approve_script(script_path.display().to_string(), operations) => approved,
+            _ = token.cancelled() => bail!("Install script cancelled"),
+        };
+        ensure!(approved, "Install script was not approved");
+        send_progress(progress_sender, "Executing install script", None);
 
-            let tokens: Vec<String> = COMMAND_ARGS_REGEX
-                .find_iter(command)
-                .map(|m| {
-                    let token = m.as_str();
-                    // Remove surrounding quotes but preserve the content
-                    if (token.starts_with('"') && token.ends_with('"'))
-                        || (token.starts_with('\'') && token.ends_with('\''))
-                    {
-                        token[1..token.len() - 1].to_string()
-                    } else {
-                        token.to_string()
-                    }
-                })
-                .collect();
+        // Unpack all 7z archives if present
+        let on_progress =
+            |progress: &ExtractProgress| send_extract_progress(progress_sender, progress);
+        decompress_all_7z_in_dir(script_dir, work_dir, &extract_options(&token, &on_progress))
+            .await
+            .context("Failed to decompress .7z archives in install folder")?;
 
-            if tokens[0] == "7z" {
-                debug!(line_num, command, "Skipping 7z command");
-                continue;
-            }
-            ensure!(tokens[0] == "adb", "Line {line_num}: Unsupported command '{command}'");
-
-            ensure!(tokens.len() >= 2, "Line {line_num}: ADB command missing operation");
-            let adb_command = &tokens[1];
-            let adb_args_raw = &tokens[2..];
-            let adb_args =
-                adb_args_raw.iter().filter(|arg| !arg.starts_with('-')).collect::<Vec<_>>();
-
-            match adb_command.as_str() {
-                "install" => {
-                    // We only care about the APK path
-                    let apk_path = script_dir.join(
-                        adb_args.iter().find(|arg| arg.ends_with(".apk")).with_context(|| {
-                            format!("Line {line_num}: adb install: missing APK path")
-                        })?,
-                    );
+        for script_command in &commands {
+            let ScriptCommand { line_num, command, .. } = script_command;
+            debug!(line_num, command, "Parsed command");
+            match script_command.action()? {
+                ScriptAction::Unpack => {
+                    debug!(line_num, command, "Skipping 7z command");
+                }
+                ScriptAction::Install(apk) => {
+                    let apk_path = resolve_path(&apk).await?;
                     debug!(apk_path = %apk_path.display(), "Line {line_num}: adb install: installing APK");
                     self.install_apk(&apk_path, backups_location, auto_reinstall_on_conflict)
                         .await
@@ -107,21 +567,38 @@ impl AdbDevice {
                             )
                         })?;
                 }
-                "uninstall" => {
-                    ensure!(
-                        adb_args.len() == 1,
-                        "Line {line_num}: adb uninstall: wrong number of arguments: expected 1, \
-                         got {}",
-                        adb_args.len()
-                    );
-                    let package = &adb_args[0];
+                ScriptAction::InstallMultiple(apks) => {
+                    let mut apk_paths = Vec::with_capacity(apks.len());
+                    for apk in &apks {
+                        apk_paths.push(resolve_path(apk).await?);
+                    }
+                    debug!(?apk_paths, "Line {line_num}: adb install-multiple: installing APKs");
+                    let apk_info = read_split_set(&apk_paths).with_context(|| {
+                        format!(
+                            "Line {line_num}: adb install-multiple: APKs are not splits of one app"
+                        )
+                    })?;
+                    self.install_apks_with_scaled_progress(
+                        &apk_paths,
+                        &apk_info.package_name,
+                        backups_location,
+                        progress_sender,
+                        1.0,
+                        auto_reinstall_on_conflict,
+                    )
+                    .await
+                    .with_context(|| {
+                        format!("Line {line_num}: adb install-multiple: failed to install APKs")
+                    })?;
+                }
+                ScriptAction::Uninstall(package) => {
                     debug!(package, "Line {line_num}: uninstalling package");
-                    let package = match PackageName::parse(package) {
+                    let package = match PackageName::parse(&package) {
                         Ok(p) => p,
                         Err(e) => {
                             warn!(
                                 error = e.as_ref() as &dyn Error,
-                                "Line {line_num}: adb uninstall: invalid package '{package}'"
+                                "Line {line_num}: uninstall: invalid package '{package}'"
                             );
                             return Ok(());
                         }
@@ -129,59 +606,28 @@ impl AdbDevice {
                     if let Err(e) = self.uninstall_package(&package).await {
                         warn!(
                             error = e.as_ref() as &dyn Error,
-                            "Line {line_num}: adb uninstall: failed to uninstall package \
-                             '{package}'"
+                            "Line {line_num}: failed to uninstall package '{package}'"
                         );
                     }
                 }
-                "shell" => {
-                    let adb_args = adb_args_raw;
-                    ensure!(!adb_args.is_empty(), "Line {line_num}: adb shell: missing command");
-                    // Handle special case for 'pm uninstall'
-                    if adb_args.len() == 3 && adb_args[0] == "pm" && adb_args[1] == "uninstall" {
-                        let package = &adb_args[2];
-                        debug!(package, "Line {line_num}: uninstalling package");
-                        let package = match PackageName::parse(package) {
-                            Ok(p) => p,
-                            Err(e) => {
-                                warn!(
-                                    error = e.as_ref() as &dyn Error,
-                                    "Line {line_num}: adb shell uninstall: invalid package \
-                                     '{package}'"
-                                );
-                                return Ok(());
-                            }
-                        };
-                        if let Err(e) = self.uninstall_package(&package).await {
-                            warn!(
-                                error = e.as_ref() as &dyn Error,
-                                "Line {line_num}: failed to uninstall package '{package}'"
-                            );
-                        }
-                    } else {
-                        let shell_cmd = adb_args
-                            .iter()
-                            .map(|arg| match arg.contains(' ') && !arg.starts_with(['"', '\'']) {
-                                true => format!("\"{arg}\""),
-                                false => arg.to_string(),
-                            })
-                            .collect::<Vec<_>>()
-                            .join(" ");
-                        debug!(shell_cmd, "Line {line_num}: executing shell command");
-                        let output = self.shell(&shell_cmd).await.with_context(|| {
-                            format!("Line {line_num}: failed to execute command '{shell_cmd}'")
-                        })?;
-                        debug!(output, "Line {line_num}: shell command output");
-                    }
+                ScriptAction::Shell(adb_args) => {
+                    let shell_cmd = adb_args
+                        .iter()
+                        .map(|arg| match arg.contains(' ') && !arg.starts_with(['"', '\'']) {
+                            true => format!("\"{arg}\""),
+                            false => arg.to_string(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    debug!(shell_cmd, "Line {line_num}: executing shell command");
+                    let output = self.shell(&shell_cmd).await.with_context(|| {
+                        format!("Line {line_num}: failed to execute command '{shell_cmd}'")
+                    })?;
+                    debug!(output, "Line {line_num}: shell command output");
                 }
-                "push" => {
-                    ensure!(
-                        adb_args.len() == 2,
-                        "Line {line_num}: adb push: wrong number of arguments: expected 2, got {}",
-                        adb_args.len()
-                    );
-                    let source = script_dir.join(adb_args[0]);
-                    let dest = UnixPath::new(&adb_args[1]);
+                ScriptAction::Push { source, dest } => {
+                    let source = resolve_path(&source).await?;
+                    let dest = UnixPath::new(&dest);
                     debug!(source = %source.display(), dest = %dest.display(), "Line {line_num}: pushing directory");
                     let push_result = if source.is_dir()
                         && dest.display().to_string().starts_with("/sdcard/Android/obb/")
@@ -202,25 +648,31 @@ impl AdbDevice {
                         )
                     }
                 }
-                "pull" => {
-                    ensure!(
-                        adb_args.len() == 2,
-                        "Line {line_num}: adb pull: wrong number of arguments: expected 2, got {}",
-                        adb_args.len()
-                    );
-                    let source = UnixPath::new(&adb_args[0]);
-                    let dest = script_dir.join(adb_args[1]);
-                    debug!(source = %source.display(), dest = %dest.display(), "Line {line_num}: pulling directory");
-                    if let Err(e) = self.pull_any(source, &dest).await {
+                ScriptAction::Pull { source, dest } => {
+                    let dest_path = contained_path(work_dir, &dest, &roots).await?;
+                    debug!(source, dest = %dest_path.display(), "Line {line_num}: pulling directory");
+                    if let Err(e) = self.pull_any(UnixPath::new(&source), &dest_path).await {
                         warn!(
                             error = e.as_ref() as &dyn Error,
-                            "Line {line_num}: adb pull: failed to pull '{}' to '{}'",
-                            adb_args[0],
-                            adb_args[1]
+                            "Line {line_num}: adb pull: failed to pull '{source}' to '{dest}'"
                         )
                     }
                 }
-                _ => bail!("Line {line_num}: Unsupported ADB command '{command}'"),
+                ScriptAction::Pause(duration) => {
+                    debug!(?duration, "Line {line_num}: pausing");
+                    send_progress(
+                        progress_sender,
+                        &format!("Waiting {}s", duration.as_secs_f32().ceil()),
+                        None,
+                    );
+                    tokio::select! {
+                        _ = tokio::time::sleep(duration) => {}
+                        _ = token.cancelled() => bail!("Install script cancelled"),
+                    }
+                }
+                ScriptAction::WaitForDevice => {
+                    debug!(line_num, "Device is connected, skipping wait-for-device");
+                }
             }
         }
 
@@ -230,26 +682,51 @@ impl AdbDevice {
     /// Sideloads an app by installing its APK and pushing OBB data if present
     ///
     /// # Arguments
-    /// * `app_dir` - Path to directory containing the app files
+    /// * `app_dir` - Path to directory containing the app files, to an archive of it or to
+    ///   an XAPK/APKS/APKM bundle
     /// * `progress_sender` - Sender for progress updates
-    #[instrument(level = "debug", skip(self, progress_sender, token))]
+    ///
+    /// Returns the package of the installed app, None for apps installed by an install script.
+    #[instrument(level = "debug", skip(self, progress_sender, token, approve_script), fields(serial = %self.serial))]
     pub(crate) async fn sideload_app(
         &self,
         app_dir: &Path,
         backups_location: &Path,
         progress_sender: UnboundedSender<SideloadProgress>,
         token: CancellationToken,
+        approve_script: &ScriptApproval<'_>,
         auto_reinstall_on_conflict: bool,
-    ) -> Result<()> {
-        fn send_progress(
-            progress_sender: &UnboundedSender<SideloadProgress>,
-            status: &str,
-            progress: Option<f32>,
-        ) {
-            let _ = progress_sender.send(SideloadProgress { status: status.to_string(), progress });
+    ) -> Result<Option<String>> {
+        let app_dir = &canonicalize(app_dir).await.with_context(|| {
+            format!(
+                "Failed to resolve app path '{}' (broken link or inaccessible location)",
+                app_dir.display()
+            )
+        })?;
+        if app_dir.is_file() && is_apk_bundle(app_dir) {
+            return self
+                .sideload_apk_bundle(
+                    app_dir,
+                    backups_location,
+                    progress_sender,
+                    token,
+                    auto_reinstall_on_conflict,
+                )
+                .await
+                .map(Some);
         }
-
-        ensure!(app_dir.is_dir(), "App path must be a directory");
+        if app_dir.is_file() && is_sideload_archive(app_dir) {
+            return Box::pin(self.sideload_app_from_archive(
+                app_dir,
+                backups_location,
+                progress_sender,
+                token,
+                approve_script,
+                auto_reinstall_on_conflict,
+            ))
+            .await;
+        }
+        ensure!(app_dir.is_dir(), "App path must be a directory or an archive");
 
         send_progress(&progress_sender, "Enumerating files", None);
         let mut entries = Vec::new();
@@ -262,30 +739,44 @@ impl AdbDevice {
             .iter()
             .find(|e| e.file_name().to_str().is_some_and(|n| n.to_lowercase() == "install.txt"))
         {
-            send_progress(&progress_sender, "Executing install script", None);
+            send_progress(&progress_sender, "Checking install script", None);
             return self
                 .execute_install_script(
                     &entry.path(),
                     backups_location,
+                    &progress_sender,
                     token.clone(),
+                    approve_script,
                     auto_reinstall_on_conflict,
                 )
                 .await
-                .context("Failed to execute install script");
+                .context("Failed to execute install script")
+                .map(|()| None);
         }
 
-        let apk_paths = entries
+        let mut apk_paths = entries
             .iter()
             .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("apk"))
             .map(|e| e.path())
             .collect::<Vec<_>>();
-        let apk_path = match apk_paths.len() {
-            0 => bail!("No APK file found in app directory"),
-            1 => &apk_paths[0],
-            _ => bail!("Multiple APK files found in app directory"),
+        apk_paths.sort();
+        let apk_info = match apk_paths.as_slice() {
+            [] => {
+                let nested = find_nested_apk_dirs(&entries).await;
+                if nested.is_empty() {
+                    bail!("No APK file found in app directory");
+                }
+                bail!(
+                    "No APK file found in app directory, but found APK files in subfolders: {}. \
+                     Select the folder that contains the APK file",
+                    nested.join(", ")
+                );
+            }
+            [apk_path] => get_apk_info(apk_path).context("Failed to read APK info")?,
+            _ => read_split_set(&apk_paths).context(
+                "Multiple APK files found in app directory that are not splits of one app",
+            )?,
         };
-
-        let apk_info = get_apk_info(apk_path).context("Failed to read APK info")?;
         let package_name = &apk_info.package_name;
 
         let obb_dir = entries.iter().find_map(|e| {
@@ -298,22 +789,474 @@ impl AdbDevice {
             }
         });
 
+        send_progress(&progress_sender, "Checking free space", None);
+        let mut apk_size = 0u64;
+        for apk_path in &apk_paths {
+            apk_size +=
+                tokio::fs::metadata(apk_path).await.context("Failed to read APK size")?.len();
+        }
+        // OBB files already on the device get replaced, so only the difference is needed
+        let obb_size = match &obb_dir {
+            Some(obb_dir) => dir_size(obb_dir).await?.saturating_sub(
+                self.remote_disk_usage(&UnixPath::new("/sdcard/Android/obb").join(package_name))
+                    .await?,
+            ),
+            None => 0,
+        };
+        // The APK is staged on the device before installation, so it briefly takes up space twice
+        self.ensure_free_space("sideload", apk_size.saturating_mul(2).saturating_add(obb_size))
+            .await?;
+
         send_progress(&progress_sender, "Installing APK", Some(0.0));
         let install_progress_scale = if obb_dir.is_some() { 0.5 } else { 1.0 };
+        self.install_apks_with_scaled_progress(
+            &apk_paths,
+            package_name,
+            backups_location,
+            &progress_sender,
+            install_progress_scale,
+            auto_reinstall_on_conflict,
+        )
+        .await?;
+
+        if let Some(obb_dir) = obb_dir {
+            self.push_obb_dir_with_progress(&obb_dir, package_name, &progress_sender, 0.5).await?;
+        }
+
+        Ok(Some(apk_info.package_name))
+    }
 
+    /// Pushes only the OBB files of `package` from `local_dir`, leaving the installed APK alone.
+    ///
+    /// `local_dir` either holds the OBB files or a folder named after the package, as in app
+    /// release directories.
+    #[instrument(level = "debug", skip(self, progress_sender), fields(serial = %self.serial), err)]
+    pub(crate) async fn push_obb(
+        &self,
+        package: &PackageName,
+        local_dir: &Path,
+        progress_sender: UnboundedSender<SideloadProgress>,
+    ) -> Result<()> {
+        let nested = local_dir.join(package.as_str());
+        let obb_dir = if nested.is_dir() { nested } else { local_dir.to_path_buf() };
+        ensure!(dir_has_any_files(&obb_dir).await?, "No OBB files found in {}", obb_dir.display());
+
+        send_progress(&progress_sender, "Checking free space", None);
+        // OBB files already on the device get replaced, so only the difference is needed
+        let obb_size = dir_size(&obb_dir).await?.saturating_sub(
+            self.remote_disk_usage(&UnixPath::new("/sdcard/Android/obb").join(package.as_str()))
+                .await?,
+        );
+        self.ensure_free_space("OBB push", obb_size).await?;
+
+        send_progress(&progress_sender, "Pushing OBB", Some(0.0));
+        self.push_obb_dir_with_progress(&obb_dir, package.as_str(), &progress_sender, 1.0).await?;
+        info!(package = %package, "OBB pushed");
+        Ok(())
+    }
+
+    /// Pushes a local OBB directory to `/sdcard/Android/obb/<package>`, replacing what is there.
+    ///
+    /// Push progress is reported scaled to `progress_scale`.
+    #[instrument(level = "debug", skip(self, progress_sender), fields(serial = %self.serial), err)]
+    async fn push_obb_dir_with_progress(
+        &self,
+        obb_dir: &Path,
+        package_name: &str,
+        progress_sender: &UnboundedSender<SideloadProgress>,
+        progress_scale: f32,
+    ) -> Result<()> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<DirectoryTransferProgress>();
+        tokio::spawn(
+            {
+                let progress_sender = progress_sender.clone();
+                async move {
+                    let mut last_update = Instant::now();
+                    let mut last_file_index: Option<u64> = None;
+                    while let Some(progress) = rx.recv().await {
+                        let now = Instant::now();
+                        if now.duration_since(last_update) < Duration::from_millis(300)
+                            && (last_file_index == Some(progress.transferred_files as u64))
+                        {
+                            continue;
+                        }
+                        last_update = now;
+                        last_file_index = Some(progress.transferred_files as u64);
+
+                        let push_progress =
+                            progress.transferred_bytes as f32 / progress.total_bytes as f32;
+                        let file_progress = progress.current_file_progress.transferred_bytes as f32
+                            / progress.current_file_progress.total_bytes as f32;
+                        // Show currently transferred file, but don't overflow on final progress when transferred_files==total_files
+                        let current_count =
+                            progress.total_files.min(progress.transferred_files + 1);
+                        let status = format!(
+                            "Pushing OBB {}/{} ({:.0}%)",
+                            current_count,
+                            progress.total_files,
+                            file_progress * 100.0
+                        );
+                        send_progress(
+                            &progress_sender,
+                            &status,
+                            Some(push_progress * progress_scale),
+                        );
+                    }
+                }
+            }
+            .instrument(Span::current()),
+        );
+
+        let remote_obb_path = UnixPath::new("/sdcard/Android/obb").join(package_name);
+        self.push_dir_to_path_with_progress(obb_dir, &remote_obb_path, true, tx).await
+    }
+
+    /// Sideloads an app directly from an archive without extracting it first.
+    ///
+    /// Only the APK entry is extracted (to a temporary directory next to the archive), OBB
+    /// files are streamed to the device entry by entry. Archives with an install script or split
+    /// APKs, and single-stream archives such as tar.gz, are extracted in full.
+    #[instrument(level = "debug", skip(self, progress_sender, token, approve_script), fields(serial = %self.serial))]
+    async fn sideload_app_from_archive(
+        &self,
+        archive: &Path,
+        backups_location: &Path,
+        progress_sender: UnboundedSender<SideloadProgress>,
+        token: CancellationToken,
+        approve_script: &ScriptApproval<'_>,
+        auto_reinstall_on_conflict: bool,
+    ) -> Result<Option<String>> {
+        let temp_dir = extraction_temp_dir(archive)?;
+        let on_progress =
+            |progress: &ExtractProgress| send_extract_progress(&progress_sender, progress);
+
+        // Entries of single-stream archives can only be read by unpacking all of them
+        if ArchiveFormat::detect(archive).is_some_and(ArchiveFormat::is_single_stream) {
+            send_progress(&progress_sender, "Extracting archive", None);
+            extract_archive(archive, temp_dir.path(), &extract_options(&token, &on_progress))
+                .await
+                .context("Failed to extract archive")?;
+            let app_dir = match single_subdir(temp_dir.path()).await? {
+                Some(dir) => dir,
+                None => temp_dir.path().to_path_buf(),
+            };
+            return Box::pin(self.sideload_app(
+                &app_dir,
+                backups_location,
+                progress_sender,
+                token,
+                approve_script,
+                auto_reinstall_on_conflict,
+            ))
+            .await;
+        }
+
+        send_progress(&progress_sender, "Reading archive", None);
+        let entries = list_archive_entries(archive).await.context("Failed to list archive")?;
+
+        let apk_entries = entries
+            .iter()
+            .filter(|e| {
+                Path::new(&e.path)
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("apk"))
+            })
+            .collect::<Vec<_>>();
+        let script_entry = entries
+            .iter()
+            .find(|e| archive_entry_file_name(&e.path).eq_ignore_ascii_case("install.txt"));
+        // Install scripts may reference any file and split APKs are installed together
+        let extract_all_entry = match (script_entry, apk_entries.as_slice()) {
+            (Some(script_entry), _) => Some(script_entry),
+            (None, [first, rest @ ..]) if !rest.is_empty() => {
+                ensure!(
+                    rest.iter()
+                        .all(|e| archive_entry_dir(&e.path) == archive_entry_dir(&first.path)),
+                    "Multiple APK files found in archive"
+                );
+                Some(*first)
+            }
+            _ => None,
+        };
+
+        if let Some(entry) = extract_all_entry {
+            info!(entry = %entry.path, "Extracting the whole archive");
+            send_progress(&progress_sender, "Extracting archive", None);
+            extract_archive(archive, temp_dir.path(), &extract_options(&token, &on_progress))
+                .await
+                .context("Failed to extract archive")?;
+            let app_dir = temp_dir.path().join(archive_entry_dir(&entry.path).unwrap_or_default());
+            return Box::pin(self.sideload_app(
+                &app_dir,
+                backups_location,
+                progress_sender,
+                token,
+                approve_script,
+                auto_reinstall_on_conflict,
+            ))
+            .await;
+        }
+
+        let [apk_entry] = apk_entries.as_slice() else {
+            bail!("No APK file found in archive");
+        };
+
+        send_progress(&progress_sender, "Extracting APK", None);
+        extract_single_from_archive(archive, temp_dir.path(), &apk_entry.path)
+            .await
+            .context("Failed to extract APK from archive")?;
+        let apk_path = temp_dir.path().join(archive_entry_file_name(&apk_entry.path));
+        let apk_info = get_apk_info(&apk_path).context("Failed to read APK info")?;
+        let package_name = &apk_info.package_name;
+
+        // OBB files live in a directory named after the package, next to the APK
+        let obb_prefix = match archive_entry_dir(&apk_entry.path) {
+            Some(dir) => format!("{dir}/{package_name}/"),
+            None => format!("{package_name}/"),
+        };
+        let obb_entries =
+            entries.iter().filter(|e| e.path.starts_with(&obb_prefix)).collect::<Vec<_>>();
+
+        send_progress(&progress_sender, "Checking free space", None);
+        // The existing OBB directory is removed before pushing, so only the difference is needed
+        let obb_size = obb_entries.iter().map(|e| e.size).sum::<u64>().saturating_sub(
+            self.remote_disk_usage(&UnixPath::new("/sdcard/Android/obb").join(package_name))
+                .await?,
+        );
+        // The APK is staged on the device before installation, so it briefly takes up space twice
+        self.ensure_free_space(
+            "sideload",
+            apk_entry.size.saturating_mul(2).saturating_add(obb_size),
+        )
+        .await?;
+
+        send_progress(&progress_sender, "Installing APK", Some(0.0));
+        let install_progress_scale = if obb_entries.is_empty() { 1.0 } else { 0.5 };
+        self.install_apk_with_scaled_progress(
+            &apk_path,
+            backups_location,
+            &progress_sender,
+            install_progress_scale,
+            auto_reinstall_on_conflict,
+        )
+        .await?;
+        drop(temp_dir);
+
+        if obb_entries.is_empty() {
+            return Ok(Some(apk_info.package_name));
+        }
+
+        let remote_obb_path = UnixPath::new("/sdcard/Android/obb").join(package_name);
+        debug!(path = %remote_obb_path.display(), "Cleaning up OBB directory");
+        self.shell(&format!("rm -rf '{}'", remote_obb_path.display())).await?;
+
+        let total_bytes = obb_entries.iter().map(|e| e.size).sum::<u64>().max(1);
+        let mut pushed_bytes = 0;
+        for (index, entry) in obb_entries.iter().enumerate() {
+            ensure!(!token.is_cancelled(), "Sideload cancelled");
+
+            let dest = remote_obb_path.join(&entry.path[obb_prefix.len()..]);
+            let status = format!("Pushing OBB {}/{}", index + 1, obb_entries.len());
+            let progress = 0.5 + 0.5 * (pushed_bytes as f32 / total_bytes as f32);
+            send_progress(&progress_sender, &status, Some(progress));
+
+            debug!(entry = %entry.path, dest = %dest.display(), "Streaming OBB file from archive");
+            let mut entry_reader = ArchiveEntryReader::open(archive, &entry.path)?;
+            self.inner
+                .push(entry_reader.reader(), &dest, 0o777)
+                .await
+                .with_context(|| format!("Failed to push OBB file '{}'", entry.path))?;
+            entry_reader
+                .finish()
+                .await
+                .with_context(|| format!("Failed to extract OBB file '{}'", entry.path))?;
+            pushed_bytes += entry.size;
+        }
+
+        Ok(Some(apk_info.package_name))
+    }
+
+    /// Sideloads an XAPK/APKS/APKM bundle.
+    ///
+    /// The bundle is extracted to a temporary directory next to it, its APKs are installed in one
+    /// session and the expansion files it lists are pushed to their install paths. Returns the
+    /// package of the bundle.
+    #[instrument(level = "debug", skip(self, progress_sender, token), fields(serial = %self.serial))]
+    async fn sideload_apk_bundle(
+        &self,
+        bundle: &Path,
+        backups_location: &Path,
+        progress_sender: UnboundedSender<SideloadProgress>,
+        token: CancellationToken,
+        auto_reinstall_on_conflict: bool,
+    ) -> Result<String> {
+        let temp_dir = extraction_temp_dir(bundle)?;
+        send_progress(&progress_sender, "Extracting bundle", None);
+        let on_progress =
+            |progress: &ExtractProgress| send_extract_progress(&progress_sender, progress);
+        let contents =
+            extract_apk_bundle(bundle, temp_dir.path(), &extract_options(&token, &on_progress))
+                .await
+                .context("Failed to extract APK bundle")?;
+        let apk_info = read_split_set(&contents.apks)?;
+        let package_name = &apk_info.package_name;
+        debug!(
+            package_name,
+            apks = contents.apks.len(),
+            obb_files = contents.obb_files.len(),
+            "Read APK bundle"
+        );
+
+        send_progress(&progress_sender, "Checking free space", None);
+        let mut apk_size = 0u64;
+        for apk in &contents.apks {
+            apk_size += tokio::fs::metadata(apk).await.context("Failed to read APK size")?.len();
+        }
+        let mut obb_size = 0u64;
+        for (obb, _) in &contents.obb_files {
+            obb_size += tokio::fs::metadata(obb).await.context("Failed to read OBB size")?.len();
+        }
+        // APKs are staged on the device before installation, so they briefly take up space twice
+        self.ensure_free_space("sideload", apk_size.saturating_mul(2).saturating_add(obb_size))
+            .await?;
+
+        send_progress(&progress_sender, "Installing APK", Some(0.0));
+        let install_progress_scale = if contents.obb_files.is_empty() { 1.0 } else { 0.5 };
+        self.install_apks_with_scaled_progress(
+            &contents.apks,
+            package_name,
+            backups_location,
+            &progress_sender,
+            install_progress_scale,
+            auto_reinstall_on_conflict,
+        )
+        .await?;
+
+        let total_bytes = obb_size.max(1);
+        let mut pushed_bytes = 0;
+        for (index, (obb, install_path)) in contents.obb_files.iter().enumerate() {
+            ensure!(!token.is_cancelled(), "Sideload cancelled");
+
+            let status = format!("Pushing OBB {}/{}", index + 1, contents.obb_files.len());
+            let progress = 0.5 + 0.5 * (pushed_bytes as f32 / total_bytes as f32);
+            send_progress(&progress_sender, &status, Some(progress));
+
+            let dest = UnixPath::new("/sdcard").join(install_path);
+            debug!(obb = %obb.display(), dest = %dest.display(), "Pushing bundle expansion file");
+            let mut file = BufReader::new(File::open(obb).await?);
+            self.inner
+                .push(&mut file, &dest, 0o777)
+                .await
+                .with_context(|| format!("Failed to push OBB file '{install_path}'"))?;
+            pushed_bytes += tokio::fs::metadata(obb).await?.len();
+        }
+
+        info!(package_name, "Sideloaded APK bundle");
+        Ok(apk_info.package_name)
+    }
+
+    /// Installs the APK of an app, or all of its split APKs in one session.
+    ///
+    /// Install progress is forwarded scaled by `progress_scale`.
+    async fn install_apks_with_scaled_progress(
+        &self,
+        apk_paths: &[PathBuf],
+        package_name: &str,
+        backups_location: &Path,
+        progress_sender: &UnboundedSender<SideloadProgress>,
+        progress_scale: f32,
+        auto_reinstall_on_conflict: bool,
+    ) -> Result<()> {
+        if let [apk_path] = apk_paths {
+            return self
+                .install_apk_with_scaled_progress(
+                    apk_path,
+                    backups_location,
+                    progress_sender,
+                    progress_scale,
+                    auto_reinstall_on_conflict,
+                )
+                .await;
+        }
+
+        let result = self.install_split_apks(apk_paths, progress_sender, progress_scale).await;
+        let conflict = result.as_ref().err().and_then(|e| e.downcast_ref::<DeviceError>()).is_some_and(
+            |e| matches!(e, DeviceError::PackageManagerError(msg) if is_reinstall_conflict(msg)),
+        );
+        if !conflict || !auto_reinstall_on_conflict {
+            return result;
+        }
+
+        info!(package_name, "Incompatible update of split APKs, reinstalling");
+        send_progress(progress_sender, "Incompatible update, reinstalling", None);
+        let package_name =
+            PackageName::parse(package_name).context("Invalid package name in APK info")?;
+        let backup_path = self.backup_and_uninstall(&package_name, backups_location).await?;
+        self.install_split_apks(apk_paths, progress_sender, progress_scale)
+            .await
+            .context("Failed to reinstall split APKs")?;
+        if let Some(backup_path) = backup_path {
+            self.restore_backup(&backup_path, None)
+                .await
+                .context("Failed to restore backup after reinstall")?;
+        }
+        Ok(())
+    }
+
+    /// Backs up the data of an app and uninstalls it, so that an incompatible update can be
+    /// installed in its place
+    async fn backup_and_uninstall(
+        &self,
+        package_name: &PackageName,
+        backups_location: &Path,
+    ) -> Result<Option<PathBuf>> {
+        let backup_path = self
+            .backup_app(
+                package_name,
+                None,
+                backups_location,
+                &BackupOptions {
+                    name_append: Some("reinstall".to_string()),
+                    backup_apk: false,
+                    backup_data: true,
+                    backup_obb: false,
+                    // Don't lose private data on reinstall, e.g. when the app is not debuggable
+                    require_private_data: true,
+                    compress: false,
+                },
+                CancellationToken::new(),
+            )
+            .await
+            .context("Failed to backup app for reinstall")?;
+        self.uninstall_package(package_name)
+            .await
+            .context("Failed to uninstall package for reinstall")?;
+        Ok(backup_path)
+    }
+
+    /// Installs an APK, forwarding install progress scaled by `progress_scale`
+    async fn install_apk_with_scaled_progress(
+        &self,
+        apk_path: &Path,
+        backups_location: &Path,
+        progress_sender: &UnboundedSender<SideloadProgress>,
+        progress_scale: f32,
+        auto_reinstall_on_conflict: bool,
+    ) -> Result<()> {
         let (tx, mut rx) = mpsc::unbounded_channel::<SideloadProgress>();
         tokio::spawn(
             {
                 let progress_sender = progress_sender.clone();
                 async move {
                     while let Some(p) = rx.recv().await {
-                        let scaled = p.progress.map(|v| v * install_progress_scale);
+                        let scaled = p.progress.map(|v| v * progress_scale);
                         let status = if let Some(pr) = p.progress {
                             format!("Installing APK ({:.0}%)", pr * 100.0)
                         } else {
                             p.status
                         };
-                        send_progress(&progress_sender, &status, scaled);
+                        let _ = progress_sender.send(SideloadProgress { status, progress: scaled });
                     }
                 }
             }
@@ -326,58 +1269,11 @@ impl AdbDevice {
             false,
             auto_reinstall_on_conflict,
         )
-        .await?;
-
-        if let Some(obb_dir) = obb_dir {
-            let remote_obb_parent = UnixPath::new("/sdcard/Android/obb");
-
-            let (tx, mut rx) = mpsc::unbounded_channel::<DirectoryTransferProgress>();
-            tokio::spawn(
-                {
-                    let progress_sender = progress_sender.clone();
-                    async move {
-                        let mut last_update = Instant::now();
-                        let mut last_file_index: Option<u64> = None;
-                        while let Some(progress) = rx.recv().await {
-                            let now = Instant::now();
-                            if now.duration_since(last_update) < Duration::from_millis(300)
-                                && (last_file_index == Some(progress.transferred_files as u64))
-                            {
-                                continue;
-                            }
-                            last_update = now;
-                            last_file_index = Some(progress.transferred_files as u64);
-
-                            let push_progress =
-                                progress.transferred_bytes as f32 / progress.total_bytes as f32;
-                            let file_progress = progress.current_file_progress.transferred_bytes
-                                as f32
-                                / progress.current_file_progress.total_bytes as f32;
-                            // Show currently transferred file, but don't overflow on final progress when transferred_files==total_files
-                            let current_count =
-                                progress.total_files.min(progress.transferred_files + 1);
-                            let status = format!(
-                                "Pushing OBB {}/{} ({:.0}%)",
-                                current_count,
-                                progress.total_files,
-                                file_progress * 100.0
-                            );
-                            send_progress(&progress_sender, &status, Some(push_progress * 0.5));
-                        }
-                    }
-                }
-                .instrument(Span::current()),
-            );
-
-            let remote_obb_path = remote_obb_parent.join(package_name);
-            self.push_dir_to_path_with_progress(&obb_dir, &remote_obb_path, true, tx).await?;
-        }
-
-        Ok(())
+        .await
     }
 
     /// Installs an APK on the device
-    #[instrument(level = "debug", skip(self, apk_path, backups_location), err)]
+    #[instrument(level = "debug", skip(self, apk_path, backups_location), fields(serial = %self.serial), err)]
     pub(super) async fn install_apk(
         &self,
         apk_path: &Path,
@@ -399,7 +1295,7 @@ impl AdbDevice {
     }
 
     /// Installs an APK on the device (with progress)
-    #[instrument(level = "debug", skip(self, apk_path, progress_sender), err)]
+    #[instrument(level = "debug", skip(self, apk_path, progress_sender), fields(serial = %self.serial), err)]
     pub(crate) async fn install_apk_with_progress(
         &self,
         apk_path: &Path,
@@ -429,7 +1325,15 @@ impl AdbDevice {
             .instrument(Span::current()),
         );
 
-        match self.inner.install_package_with_progress(apk_path, true, true, true, tx).await {
+        // The APK is staged in /data/local/tmp, which is left behind if the install is aborted
+        let cleanup = CleanupGuard::new("staged APK", {
+            let device = self.clone();
+            let apk_path = apk_path.to_path_buf();
+            async move { device.clean_temp_apk(&apk_path).await }
+        });
+        let result = self.inner.install_package_with_progress(apk_path, true, true, true, tx).await;
+        cleanup.disarm();
+        match result {
             Ok(_) => Ok(()),
             Err(DeviceError::PackageManagerError(msg)) => {
                 info!(
@@ -437,11 +1341,7 @@ impl AdbDevice {
                     "Package manager returned error, checking if reinstall is needed"
                 );
 
-                if (msg.contains("INSTALL_FAILED_VERSION_DOWNGRADE")
-                    || msg.contains("INSTALL_FAILED_UPDATE_INCOMPATIBLE"))
-                    && !did_reinstall
-                    && auto_reinstall_on_conflict
-                {
+                if is_reinstall_conflict(&msg) && !did_reinstall && auto_reinstall_on_conflict {
                     info!("Incompatible update, reinstalling. Reason: {}", msg);
                     let _ = progress_sender.send(SideloadProgress {
                         status: "Incompatible update, reinstalling".to_string(),
@@ -451,26 +1351,8 @@ impl AdbDevice {
                         get_apk_info(apk_path).context("Failed to get APK info for backup")?;
                     let package_name = PackageName::parse(&apk_info.package_name)
                         .context("Invalid package name in APK info")?;
-                    let backup_path = self
-                        .backup_app(
-                            &package_name,
-                            None,
-                            backups_location,
-                            &BackupOptions {
-                                name_append: Some("reinstall".to_string()),
-                                backup_apk: false,
-                                backup_data: true,
-                                backup_obb: false,
-                                // Don't lose private data on reinstall, e.g. when the app is not debuggable
-                                require_private_data: true,
-                            },
-                            CancellationToken::new(),
-                        )
-                        .await
-                        .context("Failed to backup app for reinstall")?;
-                    self.uninstall_package(&package_name)
-                        .await
-                        .context("Failed to uninstall package for reinstall")?;
+                    let backup_path =
+                        self.backup_and_uninstall(&package_name, backups_location).await?;
                     Box::pin(self.install_apk_with_progress(
                         apk_path,
                         backups_location,
@@ -481,7 +1363,7 @@ impl AdbDevice {
                     .await
                     .context("Failed to reinstall APK")?;
                     if let Some(backup_path) = backup_path {
-                        self.restore_backup(&backup_path)
+                        self.restore_backup(&backup_path, None)
                             .await
                             .context("Failed to restore backup after reinstall")?;
                     }
@@ -494,3 +1376,127 @@ impl AdbDevice {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(line: &str) -> Result<ScriptAction> {
+        let commands = parse_install_script(line).unwrap();
+        commands[0].action()
+    }
+
+    #[test]
+    fn reads_script_actions() {
+        assert_eq!(
+            action("adb install-multiple -r base.apk \"split config.apk\"").unwrap(),
+            ScriptAction::InstallMultiple(vec![
+                "base.apk".to_string(),
+                "split config.apk".to_string()
+            ])
+        );
+        assert_eq!(
+            action("adb shell pm uninstall com.example.game").unwrap(),
+            ScriptAction::Uninstall("com.example.game".to_string())
+        );
+        assert_eq!(action("sleep 3").unwrap(), ScriptAction::Pause(Duration::from_secs(3)));
+        assert_eq!(
+            action("timeout /t 5 /nobreak").unwrap(),
+            ScriptAction::Pause(Duration::from_secs(5))
+        );
+        assert_eq!(action("adb wait-for-device").unwrap(), ScriptAction::WaitForDevice);
+
+        for unsupported in ["adb reboot", "copy a b", "sleep forever", "wait 3600", "adb push a"] {
+            assert!(action(unsupported).is_err(), "{unsupported}");
+        }
+    }
+
+    #[test]
+    fn summarizes_operations_for_review() {
+        let script = "7z x data.7z\nadb install game.apk\nadb shell settings put global x 1\nadb \
+                      push obb /sdcard/Android/obb/\nadb wait-for-device";
+        let operations = parse_install_script(script)
+            .unwrap()
+            .iter()
+            .filter_map(|command| command.action().ok()?.operation(command.line_num))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            operations,
+            [
+                ScriptOperation {
+                    line: 2,
+                    kind: ScriptOperationKind::Install,
+                    detail: "game.apk".to_string()
+                },
+                ScriptOperation {
+                    line: 3,
+                    kind: ScriptOperationKind::Shell,
+                    detail: "settings put global x 1".to_string()
+                },
+                ScriptOperation {
+                    line: 4,
+                    kind: ScriptOperationKind::Push,
+                    detail: "obb → /sdcard/Android/obb/".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_local_paths_inside_install_folder() {
+        assert_eq!(
+            action("adb install %~dp0\\game.apk").unwrap(),
+            ScriptAction::Install("game.apk".to_string())
+        );
+        // Variables other than the script folder are file name text
+        assert_eq!(
+            action("adb push ${GAME_DIR}/obb /sdcard/Android/obb/").unwrap(),
+            ScriptAction::Push {
+                source: "${GAME_DIR}/obb".to_string(),
+                dest: "/sdcard/Android/obb/".to_string()
+            }
+        );
+        assert_eq!(
+            action("adb install game$1.apk").unwrap(),
+            ScriptAction::Install("game$1.apk".to_string())
+        );
+        assert_eq!(
+            action("adb pull /sdcard/save.dat saves/../save.dat").unwrap(),
+            ScriptAction::Pull {
+                source: "/sdcard/save.dat".to_string(),
+                dest: "saves/../save.dat".to_string()
+            }
+        );
+        for outside in [
+            "adb push %USERPROFILE%\\..\\..\\id_rsa /sdcard/",
+            "adb pull /sdcard/x C:\\Users\\x.bat",
+            "adb pull /sdcard/x /etc/x",
+            "adb install ../game.apk",
+        ] {
+            assert!(action(outside).is_err(), "{outside}");
+        }
+        // Device paths are left for the device shell
+        assert_eq!(
+            action("adb shell echo $HOME").unwrap(),
+            ScriptAction::Shell(vec!["echo".to_string(), "$HOME".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn resolved_paths_stay_inside_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let install_dir = dir.path().join("install");
+        std::fs::create_dir(&install_dir).unwrap();
+        std::fs::write(install_dir.join("game.apk"), b"apk").unwrap();
+        let roots = [canonicalize(&install_dir).await.unwrap()];
+
+        let apk = contained_path(&install_dir, "game.apk", &roots).await.unwrap();
+        assert_eq!(apk, install_dir.join("game.apk"));
+        assert!(contained_path(&install_dir, "saves/new.dat", &roots).await.is_ok());
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(dir.path(), install_dir.join("up")).unwrap();
+            assert!(contained_path(&install_dir, "up/x.bat", &roots).await.is_err());
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Split APK installs through package manager install sessions
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail, ensure};
+use forensic_adb::{DeviceError, UnixPath};
+use lazy_regex::regex_captures;
+use tokio::{
+    fs::{self, File},
+    io::BufReader,
+    sync::mpsc::UnboundedSender,
+};
+use tracing::{debug, info, instrument, warn};
+
+use super::{AdbDevice, SideloadProgress, sideload::send_progress};
+use crate::{
+    models::apk_info::{ApkInfo, get_apk_info},
+    shutdown::CleanupGuard,
+};
+
+/// Where splits are staged before they are written into an install session, in a directory
+/// per session
+const SPLITS_STAGING_DIR: &str = "/data/local/tmp/yaas-splits";
+
+/// Reads the info of a set of split APKs, failing unless they all belong to one app.
+///
+/// Only the base APK has the application label, so its info is preferred.
+pub(crate) fn read_split_set(apks: &[PathBuf]) -> Result<ApkInfo> {
+    let mut infos = apks
+        .iter()
+        .map(|apk| {
+            get_apk_info(apk)
+                .with_context(|| format!("Failed to read APK info of {}", apk.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let first = infos.first().context("No APK files given")?;
+    if let Some(other) = infos.iter().find(|info| info.package_name != first.package_name) {
+        bail!(
+            "APK files belong to different apps: {} and {}",
+            first.package_name,
+            other.package_name
+        );
+    }
+    let base = infos.iter().position(|info| info.application_label.is_some()).unwrap_or(0);
+    Ok(infos.swap_remove(base))
+}
+
+/// Session ID from `pm install-create` output, e.g. `Success: created install session [1234]`
+fn parse_session_id(output: &str) -> Option<u32> {
+    let (_, id) = regex_captures!(r"^Success: created install session \[(\d+)\]", output.trim())?;
+    id.parse().ok()
+}
+
+impl AdbDevice {
+    /// Installs the split APKs of one app in a single package manager session.
+    ///
+    /// Splits are staged on the device and written into the session one by one, then the session
+    /// is committed so that either all splits get installed or none. Write progress is reported
+    /// scaled to `progress_scale`.
+    #[instrument(level = "debug", skip(self, progress_sender), fields(serial = %self.serial), err)]
+    pub(super) async fn install_split_apks(
+        &self,
+        apks: &[PathBuf],
+        progress_sender: &UnboundedSender<SideloadProgress>,
+        progress_scale: f32,
+    ) -> Result<()> {
+        let mut sizes = Vec::with_capacity(apks.len());
+        for apk in apks {
+            let size = fs::metadata(apk)
+                .await
+                .with_context(|| format!("Failed to read size of {}", apk.display()))?
+                .len();
+            sizes.push(size);
+        }
+        let total_size = sizes.iter().sum::<u64>();
+
+        let output = self.shell(&format!("pm install-create -r -d -g -S {total_size}")).await?;
+        let session = parse_session_id(&output)
+            .with_context(|| format!("Failed to create install session: {}", output.trim()))?;
+        debug!(session, splits = apks.len(), total_size, "Created install session");
+
+        let staging_dir = format!("{SPLITS_STAGING_DIR}/{session}");
+        let cleanup = CleanupGuard::new("split install session", {
+            let device = self.clone();
+            let staging_dir = staging_dir.clone();
+            async move {
+                device.shell(&format!("pm install-abandon {session}")).await?;
+                device.shell(&format!("rm -rf {staging_dir}")).await?;
+                Ok(())
+            }
+        });
+        let result = self
+            .write_and_commit_splits(
+                session,
+                &staging_dir,
+                apks,
+                &sizes,
+                progress_sender,
+                progress_scale,
+            )
+            .await;
+        cleanup.disarm();
+        if result.is_err()
+            && let Err(e) = self.shell(&format!("pm install-abandon {session}")).await
+        {
+            warn!(error = format!("{e:#}"), session, "Failed to abandon install session");
+        }
+        if let Err(e) = self.shell(&format!("rm -rf {staging_dir}")).await {
+            warn!(error = format!("{e:#}"), "Failed to clean up staged splits");
+        }
+        result
+    }
+
+    async fn write_and_commit_splits(
+        &self,
+        session: u32,
+        staging_dir: &str,
+        apks: &[PathBuf],
+        sizes: &[u64],
+        progress_sender: &UnboundedSender<SideloadProgress>,
+        progress_scale: f32,
+    ) -> Result<()> {
+        self.shell_checked(&format!("mkdir -p {staging_dir}")).await?;
+        let total_size = sizes.iter().sum::<u64>().max(1);
+        let mut written = 0;
+        for (index, (apk, size)) in apks.iter().zip(sizes).enumerate() {
+            let status = format!("Installing split {}/{}", index + 1, apks.len());
+            let progress = progress_scale * written as f32 / total_size as f32;
+            send_progress(progress_sender, &status, Some(progress));
+
+            let remote = UnixPath::new(staging_dir).join(format!("{index}.apk"));
+            let mut file = BufReader::new(File::open(apk).await?);
+            self.inner
+                .push(&mut file, &remote, 0o644)
+                .await
+                .with_context(|| format!("Failed to push {}", apk.display()))?;
+            let output = self
+                .shell(&format!(
+                    "pm install-write -S {size} {session} split{index}.apk {}",
+                    remote.display()
+                ))
+                .await?;
+            ensure!(
+                output.contains("Success"),
+                "Failed to write {} into install session: {}",
+                apk.display(),
+                output.trim()
+            );
+            written += size;
+        }
+
+        send_progress(progress_sender, "Committing install", Some(progress_scale));
+        let output = self.shell(&format!("pm install-commit {session}")).await?;
+        if !output.contains("Success") {
+            return Err(DeviceError::PackageManagerError(output.trim().to_string()).into());
+        }
+        info!(session, splits = apks.len(), "Installed split APKs");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_install_session_id() {
+        assert_eq!(parse_session_id("Success: created install session [1234567]\n"), Some(1234567));
+        assert_eq!(parse_session_id("Error: java.lang.SecurityException"), None);
+        assert_eq!(parse_session_id(""), None);
+    }
+}
@@ -9,10 +9,13 @@ use tokio::{
 };
 use tracing::{debug, instrument, trace};
 
-use super::AdbDevice;
+use super::{
+    AdbDevice,
+    local_source::{LinkedEntry, scan_source_tree},
+};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum TransferKind {
+pub(super) enum TransferKind {
     File,
     Directory,
 }
@@ -49,7 +52,7 @@ enum DirectoryPushDestination {
 
 impl AdbDevice {
     #[instrument(level = "debug", ret, err)]
-    async fn remote_entry_kind(&self, path: &UnixPath) -> Result<Option<TransferKind>> {
+    pub(super) async fn remote_entry_kind(&self, path: &UnixPath) -> Result<Option<TransferKind>> {
         match self.inner.stat(path).await {
             Ok(stat) => Ok(TransferKind::from_remote_status(stat.file_mode)),
             Err(DeviceError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
@@ -156,7 +159,7 @@ impl AdbDevice {
     /// # Arguments
     /// * `source_file` - Local path of the file to push
     /// * `dest_file` - Destination path on the device
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn push(&self, source_file: &Path, dest_file: &UnixPath) -> Result<()> {
         ensure!(
             source_file.is_file(),
@@ -183,7 +186,7 @@ impl AdbDevice {
     /// * `source` - Local path of the directory to push
     /// * `dest` - Destination path on the device
     /// * `overwrite` - Whether to remove existing destination before pushing
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn push_dir(
         &self,
         source: &Path,
@@ -198,7 +201,7 @@ impl AdbDevice {
     ///
     /// If `dest` already exists as a directory, the directory contents are pushed into that
     /// directory instead of appending the local source directory name again.
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn push_dir_to_path(
         &self,
         source: &Path,
@@ -221,14 +224,19 @@ impl AdbDevice {
             source.display()
         );
 
+        let tree = scan_source_tree(source).await?;
         let dest_path =
             self.resolve_push_dest_path(source, TransferKind::Directory, dest, dir_dest).await?;
         if overwrite {
             debug!(path = %dest_path.display(), "Cleaning up destination directory");
             self.shell(&format!("rm -rf '{}'", dest_path.display())).await?;
         }
-        debug!(source = %source.display(), dest = %dest_path.display(), "Pushing directory");
-        self.inner.push_dir(source, &dest_path, 0o777).await.context("Failed to push directory")
+        debug!(source = %tree.root.display(), dest = %dest_path.display(), "Pushing directory");
+        self.inner
+            .push_dir(&tree.root, &dest_path, 0o777)
+            .await
+            .context("Failed to push directory")?;
+        self.push_linked_entries(&tree.links, &dest_path).await
     }
 
     /// Pushes a directory to an exact path on the device (with progress).
@@ -238,7 +246,7 @@ impl AdbDevice {
     /// * `dest` - Exact destination directory path on device
     /// * `overwrite` - Whether to clean up destination directory before pushing
     /// * `progress_sender` - Sender for progress updates
-    #[instrument(level = "debug", skip(self, progress_sender), err)]
+    #[instrument(level = "debug", skip(self, progress_sender), fields(serial = %self.serial), err)]
     pub(super) async fn push_dir_to_path_with_progress(
         &self,
         source: &Path,
@@ -270,6 +278,7 @@ impl AdbDevice {
             source.display()
         );
 
+        let tree = scan_source_tree(source).await?;
         let dest_path =
             self.resolve_push_dest_path(source, TransferKind::Directory, dest, dir_dest).await?;
         if overwrite {
@@ -277,13 +286,45 @@ impl AdbDevice {
             self.shell(&format!("rm -rf '{}'", dest_path.display())).await?;
         }
         self.inner
-            .push_dir_with_progress(source, &dest_path, 0o777, progress_sender)
+            .push_dir_with_progress(&tree.root, &dest_path, 0o777, progress_sender)
             .await
-            .context("Failed to push directory")
+            .context("Failed to push directory")?;
+        self.push_linked_entries(&tree.links, &dest_path).await
+    }
+
+    /// Pushes entries reached through links, which directory pushes do not follow
+    async fn push_linked_entries(&self, links: &[LinkedEntry], dest: &UnixPath) -> Result<()> {
+        for link in links {
+            let relative = link
+                .relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let remote_path = dest.join(relative);
+            debug!(
+                source = %link.target.display(),
+                dest = %remote_path.display(),
+                "Pushing linked entry"
+            );
+            if link.is_dir {
+                self.inner
+                    .push_dir(&link.target, &remote_path, 0o777)
+                    .await
+                    .with_context(|| format!("Failed to push '{}'", link.target.display()))?;
+            } else {
+                let mut file = BufReader::new(File::open(&link.target).await?);
+                self.inner
+                    .push(&mut file, &remote_path, 0o777)
+                    .await
+                    .with_context(|| format!("Failed to push '{}'", link.target.display()))?;
+            }
+        }
+        Ok(())
     }
 
     /// Pushes raw bytes to a file on the device
-    #[instrument(level = "debug", skip(self, bytes), fields(len = bytes.len()), err)]
+    #[instrument(level = "debug", skip(self, bytes), fields(serial = %self.serial, len = bytes.len()), err)]
     pub(super) async fn push_bytes(&self, mut bytes: &[u8], remote_path: &UnixPath) -> Result<()> {
         self.inner.push(&mut bytes, remote_path, 0o777).await.context("Failed to push bytes")
     }
@@ -293,7 +334,7 @@ impl AdbDevice {
     /// # Arguments
     /// * `source_file` - Source path on the device
     /// * `dest_file` - Local path to save the file
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn pull(&self, source_file: &UnixPath, dest_file: &Path) -> Result<PathBuf> {
         let source_stat =
             self.inner.stat(source_file).await.context("Failed to stat source file")?;
@@ -323,7 +364,7 @@ impl AdbDevice {
     /// # Arguments
     /// * `source` - Source path on the device
     /// * `dest` - Local path to save the directory
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn pull_dir(&self, source: &UnixPath, dest: &Path) -> Result<PathBuf> {
         let source_stat =
             self.inner.stat(source).await.context("Failed to stat source directory")?;
@@ -354,7 +395,7 @@ impl AdbDevice {
     }
 
     /// Pulls an item from the device.
-    #[instrument(level = "debug", skip(self, remote_path, local_path))]
+    #[instrument(level = "debug", skip(self, remote_path, local_path), fields(serial = %self.serial))]
     pub(super) async fn pull_any(&self, remote_path: &UnixPath, local_path: &Path) -> Result<()> {
         let stat = self.inner.stat(remote_path).await.context("Stat command failed")?;
         let source_kind = TransferKind::from_remote_status(stat.file_mode)
@@ -391,7 +432,7 @@ impl AdbDevice {
     }
 
     /// Pushes an item to the device
-    #[instrument(level = "debug", skip(self, source, dest), err)]
+    #[instrument(level = "debug", skip(self, source, dest), fields(serial = %self.serial), err)]
     pub(super) async fn push_any(&self, source: &Path, dest: &UnixPath) -> Result<()> {
         ensure!(source.exists(), "Source path does not exist: {}", source.display());
         if source.is_dir() {
@@ -405,7 +446,7 @@ impl AdbDevice {
     }
 
     /// Returns true if a directory exists on the device
-    #[instrument(level = "debug", skip(self), err)]
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
     pub(super) async fn dir_exists(&self, path: &UnixPath) -> Result<bool> {
         match self.inner.stat(path).await {
             Ok(stat) => Ok(stat.file_mode == UnixFileStatus::Directory),
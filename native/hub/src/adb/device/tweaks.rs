@@ -0,0 +1,110 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{Context, Result, ensure};
+use tracing::{info, instrument};
+
+use super::AdbDevice;
+use crate::models::{signals::adb::command::DeviceTweak, vendor::TweakProperties};
+
+const REFRESH_RATES: RangeInclusive<u32> = 60..=144;
+const PERFORMANCE_LEVELS: RangeInclusive<u8> = 0..=5;
+const FOVEATION_LEVELS: RangeInclusive<u8> = 0..=4;
+const TEXTURE_DIMENSIONS: RangeInclusive<u32> = 512..=4096;
+
+impl AdbDevice {
+    /// Sets a rendering tweak, or clears it to restore the system default.
+    ///
+    /// Apps read the properties when they start, so running apps keep their settings.
+    #[instrument(level = "debug", skip(self), fields(serial = %self.serial), err)]
+    pub(crate) async fn set_device_tweak(&self, tweak: &DeviceTweak) -> Result<()> {
+        let properties = self
+            .vendor
+            .adapter()
+            .tweak_properties()
+            .context("Rendering tweaks are not supported on this device")?;
+        let command = tweak_assignments(tweak, &properties)?
+            .into_iter()
+            .map(|(property, value)| format!("setprop {property} '{value}'"))
+            .collect::<Vec<_>>()
+            .join(" && ");
+        self.shell_checked(&command).await.context("Failed to set device tweak")?;
+        info!(?tweak, "Device tweak set");
+        Ok(())
+    }
+}
+
+/// Returns the properties to set for `tweak` and their values, empty to clear
+fn tweak_assignments(
+    tweak: &DeviceTweak,
+    properties: &TweakProperties,
+) -> Result<Vec<(&'static str, String)>> {
+    fn checked<T: PartialOrd + ToString + std::fmt::Debug>(
+        value: Option<T>,
+        range: &RangeInclusive<T>,
+        name: &str,
+    ) -> Result<String> {
+        let Some(value) = value else {
+            return Ok(String::new());
+        };
+        ensure!(range.contains(&value), "{name} must be within {range:?}, got {value:?}");
+        Ok(value.to_string())
+    }
+
+    Ok(match tweak {
+        DeviceTweak::RefreshRate(rate) => {
+            vec![(properties.refresh_rate, checked(*rate, &REFRESH_RATES, "Refresh rate")?)]
+        }
+        DeviceTweak::CpuLevel(level) => {
+            vec![(properties.cpu_level, checked(*level, &PERFORMANCE_LEVELS, "CPU level")?)]
+        }
+        DeviceTweak::GpuLevel(level) => {
+            vec![(properties.gpu_level, checked(*level, &PERFORMANCE_LEVELS, "GPU level")?)]
+        }
+        DeviceTweak::TextureSize(size) => vec![
+            (
+                properties.texture_width,
+                checked(size.map(|s| s.width), &TEXTURE_DIMENSIONS, "Texture width")?,
+            ),
+            (
+                properties.texture_height,
+                checked(size.map(|s| s.height), &TEXTURE_DIMENSIONS, "Texture height")?,
+            ),
+        ],
+        DeviceTweak::FixedFoveation(level) => vec![(
+            properties.foveation_level,
+            checked(*level, &FOVEATION_LEVELS, "Foveation level")?,
+        )],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TextureSize, vendor::Vendor};
+
+    #[test]
+    fn validates_and_maps_tweaks() {
+        let properties = Vendor::Meta.adapter().tweak_properties().unwrap();
+        assert_eq!(
+            tweak_assignments(&DeviceTweak::RefreshRate(Some(120)), &properties).unwrap(),
+            [("debug.oculus.refreshRate", "120".to_string())]
+        );
+        assert_eq!(
+            tweak_assignments(
+                &DeviceTweak::TextureSize(Some(TextureSize { width: 2048, height: 1536 })),
+                &properties
+            )
+            .unwrap(),
+            [
+                ("debug.oculus.textureWidth", "2048".to_string()),
+                ("debug.oculus.textureHeight", "1536".to_string())
+            ]
+        );
+        assert_eq!(
+            tweak_assignments(&DeviceTweak::FixedFoveation(None), &properties).unwrap(),
+            [("debug.oculus.foveation.level", String::new())]
+        );
+        assert!(tweak_assignments(&DeviceTweak::CpuLevel(Some(9)), &properties).is_err());
+        assert!(tweak_assignments(&DeviceTweak::RefreshRate(Some(30)), &properties).is_err());
+    }
+}
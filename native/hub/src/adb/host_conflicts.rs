@@ -0,0 +1,306 @@
+//! Detection of VR streaming apps and ADB servers of other apps running on this computer.
+//!
+//! Streaming over Link or ALVR shares the USB connection with YAAS, and apps bundling their own
+//! ADB binary keep restarting the server when its version differs from ours, dropping devices.
+
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use rinf::{DartSignal, RustSignal};
+use tokio::{process::Command, sync::watch};
+use tracing::{Instrument, debug, info, info_span, warn};
+
+use crate::{
+    adb::AdbService,
+    models::{
+        Settings,
+        signals::adb::host_conflicts::{
+            ForeignAdbServer, HostConflictsChanged, StopForeignAdbServersRequest, StreamingClient,
+        },
+    },
+};
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Process names of each streaming app, lowercase and without `.exe`
+const STREAMING_PROCESSES: &[(StreamingClient, &[&str])] = &[
+    (StreamingClient::MetaLink, &["ovrserver_x64", "oculusclient"]),
+    (StreamingClient::VirtualDesktop, &["virtualdesktop.streamer", "virtualdesktop.service"]),
+    (StreamingClient::Alvr, &["alvr dashboard", "alvr_dashboard", "alvr_launcher"]),
+    (StreamingClient::SteamVr, &["vrserver", "vrmonitor"]),
+];
+
+/// Last report, kept to resend it to a reconnecting UI
+static LAST_REPORT: LazyLock<watch::Sender<HostConflictsChanged>> =
+    LazyLock::new(|| watch::Sender::new(HostConflictsChanged::default()));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HostProcess {
+    pid: u32,
+    name: String,
+    /// Executable path, if the OS lets us read it
+    path: Option<PathBuf>,
+}
+
+/// Scans running processes periodically and reports conflicts to the UI
+pub(crate) struct HostConflictMonitor {
+    adb_service: Arc<AdbService>,
+    settings: watch::Receiver<Settings>,
+}
+
+impl HostConflictMonitor {
+    pub(crate) fn start(adb_service: Arc<AdbService>, settings: watch::Receiver<Settings>) {
+        let monitor = Arc::new(Self { adb_service, settings });
+        tokio::spawn(monitor.clone().run().instrument(info_span!("task_host_conflicts")));
+        tokio::spawn(
+            monitor.receive_stop_requests().instrument(info_span!("task_stop_foreign_adb")),
+        );
+    }
+
+    /// Sends the last report again
+    pub(crate) fn resend() {
+        LAST_REPORT.borrow().clone().send_signal_to_dart();
+    }
+
+    async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(SCAN_INTERVAL);
+        loop {
+            interval.tick().await;
+            let report = self.scan().await;
+            if !report.foreign_adb_servers.is_empty()
+                && self.settings.borrow().stop_foreign_adb_servers
+            {
+                info!(servers = ?report.foreign_adb_servers, "Stopping ADB servers of other apps");
+                self.stop_servers(&report.foreign_adb_servers).await;
+            }
+        }
+    }
+
+    async fn receive_stop_requests(self: Arc<Self>) {
+        let receiver = StopForeignAdbServersRequest::get_dart_signal_receiver();
+        while receiver.recv().await.is_some() {
+            let servers = LAST_REPORT.borrow().foreign_adb_servers.clone();
+            info!(?servers, "Stopping ADB servers of other apps on request");
+            self.stop_servers(&servers).await;
+        }
+        panic!("StopForeignAdbServersRequest receiver closed");
+    }
+
+    /// Lists processes and sends a report if anything changed since the last one
+    async fn scan(&self) -> HostConflictsChanged {
+        let processes = match list_processes().await {
+            Ok(processes) => processes,
+            Err(e) => {
+                debug!(error = e.as_ref() as &dyn Error, "Failed to list host processes");
+                return LAST_REPORT.borrow().clone();
+            }
+        };
+        let own_adb = self.adb_service.adb_binary_path().await;
+        let report = detect_conflicts(&processes, own_adb.as_deref());
+        LAST_REPORT.send_if_modified(|last| {
+            if *last == report {
+                return false;
+            }
+            if !report.streaming_clients.is_empty() || !report.foreign_adb_servers.is_empty() {
+                warn!(
+                    clients = ?report.streaming_clients,
+                    adb_servers = ?report.foreign_adb_servers,
+                    "Found apps that may conflict with the device connection"
+                );
+            }
+            *last = report.clone();
+            report.clone().send_signal_to_dart();
+            true
+        });
+        report
+    }
+
+    async fn stop_servers(&self, servers: &[ForeignAdbServer]) {
+        if servers.is_empty() {
+            return;
+        }
+        for server in servers {
+            if let Err(e) = kill_process(server.pid).await {
+                warn!(
+                    pid = server.pid,
+                    path = server.path,
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to stop ADB server"
+                );
+            }
+        }
+        if let Err(e) = self.adb_service.clone().restart_adb().await {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to restart ADB after stopping others");
+        }
+        self.scan().await;
+    }
+}
+
+/// Finds streaming apps and ADB servers not started from `own_adb`.
+///
+/// ADB processes are only reported when both paths are known, so nothing is stopped on a guess.
+fn detect_conflicts(processes: &[HostProcess], own_adb: Option<&Path>) -> HostConflictsChanged {
+    let own_adb = own_adb.map(normalize_path);
+    let mut report = HostConflictsChanged::default();
+    for process in processes {
+        let name = process.name.to_lowercase();
+        let name = name.strip_suffix(".exe").unwrap_or(&name);
+        if let Some((client, _)) =
+            STREAMING_PROCESSES.iter().find(|(_, names)| names.contains(&name))
+            && !report.streaming_clients.contains(client)
+        {
+            report.streaming_clients.push(*client);
+        }
+        if name == "adb"
+            && let (Some(own), Some(path)) = (&own_adb, &process.path)
+            && normalize_path(path) != *own
+        {
+            report
+                .foreign_adb_servers
+                .push(ForeignAdbServer { pid: process.pid, path: path.display().to_string() });
+        }
+    }
+    report
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if cfg!(target_os = "windows") {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    } else {
+        path
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn list_processes() -> Result<Vec<HostProcess>> {
+    let mut processes = Vec::new();
+    let mut entries = tokio::fs::read_dir("/proc").await.context("Failed to read /proc")?;
+    while let Some(entry) = entries.next_entry().await? {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(name) = tokio::fs::read_to_string(entry.path().join("comm")).await else {
+            continue;
+        };
+        let path = tokio::fs::read_link(entry.path().join("exe")).await.ok();
+        processes.push(HostProcess { pid, name: name.trim().to_string(), path });
+    }
+    Ok(processes)
+}
+
+#[cfg(target_os = "macos")]
+async fn list_processes() -> Result<Vec<HostProcess>> {
+    let output = Command::new("ps")
+        .args(["-axo", "pid=,comm="])
+        .output()
+        .await
+        .context("Failed to run ps")?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pid, command) = line.trim().split_once(char::is_whitespace)?;
+            let path = PathBuf::from(command.trim());
+            let name = path.file_name()?.to_string_lossy().to_string();
+            Some(HostProcess { pid: pid.parse().ok()?, name, path: Some(path) })
+        })
+        .collect())
+}
+
+#[cfg(target_os = "windows")]
+async fn list_processes() -> Result<Vec<HostProcess>> {
+    let mut command = Command::new("tasklist");
+    command.args(["/FO", "CSV", "/NH"]);
+    command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    let output = command.output().await.context("Failed to run tasklist")?;
+    let mut processes: Vec<HostProcess> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().trim_matches('"').split("\",\"");
+            let name = fields.next()?.to_string();
+            let pid = fields.next()?.parse().ok()?;
+            Some(HostProcess { pid, name, path: None })
+        })
+        .collect();
+
+    // tasklist has no paths, only ADB servers need them
+    if processes.iter().any(|p| p.name.eq_ignore_ascii_case("adb.exe")) {
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Process -Filter \"Name='adb.exe'\" | ForEach-Object { \
+             \"$($_.ProcessId)|$($_.ExecutablePath)\" }",
+        ]);
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        let output = command.output().await.context("Failed to query ADB process paths")?;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let Some((pid, path)) = line.trim().split_once('|') else { continue };
+            let (Ok(pid), false) = (pid.parse::<u32>(), path.is_empty()) else { continue };
+            if let Some(process) = processes.iter_mut().find(|p| p.pid == pid) {
+                process.path = Some(PathBuf::from(path));
+            }
+        }
+    }
+    Ok(processes)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+async fn list_processes() -> Result<Vec<HostProcess>> {
+    Ok(Vec::new())
+}
+
+async fn kill_process(pid: u32) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("taskkill");
+        command.args(["/PID", &pid.to_string(), "/F"]);
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        command
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut command = {
+        let mut command = Command::new("kill");
+        command.arg(pid.to_string());
+        command
+    };
+    let status = command.status().await.context("Failed to run kill command")?;
+    anyhow::ensure!(status.success(), "Kill command exited with {status}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, path: Option<&str>) -> HostProcess {
+        HostProcess { pid, name: name.to_string(), path: path.map(PathBuf::from) }
+    }
+
+    #[test]
+    fn detects_streaming_apps_and_other_adb_servers() {
+        let processes = [
+            process(1, "OVRServer_x64.exe", None),
+            process(2, "vrserver", None),
+            process(3, "vrmonitor", None),
+            process(4, "adb", Some("/opt/yaas/platform-tools/adb")),
+            process(5, "adb", Some("/opt/alvr/platform-tools/adb")),
+            process(6, "adb", None),
+            process(7, "firefox", Some("/usr/bin/firefox")),
+        ];
+        let report = detect_conflicts(&processes, Some(Path::new("/opt/yaas/platform-tools/adb")));
+        assert_eq!(report.streaming_clients, [StreamingClient::MetaLink, StreamingClient::SteamVr]);
+        assert_eq!(
+            report.foreign_adb_servers,
+            [ForeignAdbServer { pid: 5, path: "/opt/alvr/platform-tools/adb".to_string() }]
+        );
+
+        // Without knowing our own binary, no server is reported
+        assert!(detect_conflicts(&processes, None).foreign_adb_servers.is_empty());
+    }
+}
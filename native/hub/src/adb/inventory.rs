@@ -0,0 +1,183 @@
+//! Installed app list exports for keeping inventory of headsets in spreadsheets.
+
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::fs;
+
+use super::device::{AdbDevice, PackageUsage};
+use crate::models::signals::adb::export::ExportFormat;
+
+const CSV_HEADER: [&str; 12] = [
+    "device_serial",
+    "device_name",
+    "package_name",
+    "label",
+    "version_code",
+    "version_name",
+    "system",
+    "app_size",
+    "data_size",
+    "cache_size",
+    "last_used",
+    "total_time_used_secs",
+];
+
+/// One exported package, sizes are in bytes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct InventoryRow {
+    pub(crate) device_serial: String,
+    pub(crate) device_name: Option<String>,
+    pub(crate) package_name: String,
+    pub(crate) label: String,
+    pub(crate) version_code: u64,
+    pub(crate) version_name: String,
+    pub(crate) system: bool,
+    pub(crate) app_size: u64,
+    pub(crate) data_size: u64,
+    pub(crate) cache_size: u64,
+    pub(crate) last_used: Option<String>,
+    pub(crate) total_time_used_secs: Option<u64>,
+}
+
+/// Builds export rows for the installed packages of `device`, sorted by package name
+pub(crate) fn inventory_rows(
+    device: &AdbDevice,
+    usage: &HashMap<String, PackageUsage>,
+    include_system: bool,
+) -> Vec<InventoryRow> {
+    let mut rows: Vec<InventoryRow> = device
+        .installed_packages
+        .iter()
+        .filter(|package| include_system || !package.is_system())
+        .map(|package| {
+            let usage = usage.get(package.package_name()).cloned().unwrap_or_default();
+            InventoryRow {
+                device_serial: device.true_serial.clone(),
+                device_name: device.name.clone(),
+                package_name: package.package_name().to_string(),
+                label: package.label().to_string(),
+                version_code: package.version_code(),
+                version_name: package.version_name().to_string(),
+                system: package.is_system(),
+                app_size: package.size().app(),
+                data_size: package.size().data(),
+                cache_size: package.size().cache(),
+                last_used: usage.last_used,
+                total_time_used_secs: usage.total_time_used_secs,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| a.package_name.cmp(&b.package_name));
+    rows
+}
+
+/// Serializes rows in the given format
+fn render_inventory(rows: &[InventoryRow], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(render_csv(rows)),
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(rows).context("Failed to serialize installed app list")
+        }
+    }
+}
+
+/// Writes rows to `path`, replacing the file only once the export is complete
+pub(crate) async fn write_inventory(
+    path: &Path,
+    rows: &[InventoryRow],
+    format: ExportFormat,
+) -> Result<()> {
+    let contents = render_inventory(rows, format)?;
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, contents)
+        .await
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    if let Err(e) = fs::rename(&tmp_path, path).await {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(e).with_context(|| format!("Failed to replace {}", path.display()));
+    }
+    Ok(())
+}
+
+fn render_csv(rows: &[InventoryRow]) -> String {
+    let mut out = CSV_HEADER.join(",");
+    out.push_str("\r\n");
+    for row in rows {
+        let fields = [
+            csv_field(&row.device_serial),
+            csv_field(row.device_name.as_deref().unwrap_or_default()),
+            csv_field(&row.package_name),
+            csv_field(&row.label),
+            row.version_code.to_string(),
+            csv_field(&row.version_name),
+            row.system.to_string(),
+            row.app_size.to_string(),
+            row.data_size.to_string(),
+            row.cache_size.to_string(),
+            csv_field(row.last_used.as_deref().unwrap_or_default()),
+            row.total_time_used_secs.map(|secs| secs.to_string()).unwrap_or_default(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Quotes a CSV field if it contains separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(package_name: &str, label: &str) -> InventoryRow {
+        InventoryRow {
+            device_serial: "1WMHH000M12345".to_string(),
+            device_name: Some("Quest 3".to_string()),
+            package_name: package_name.to_string(),
+            label: label.to_string(),
+            version_code: 42,
+            version_name: "1.2.3".to_string(),
+            system: false,
+            app_size: 1024,
+            data_size: 2048,
+            cache_size: 0,
+            last_used: None,
+            total_time_used_secs: Some(3600),
+        }
+    }
+
+    #[test]
+    fn csv_escapes_special_characters() {
+        let csv = render_csv(&[row("com.example.game", "Game, \"Deluxe\" Edition")]);
+        let mut lines = csv.split("\r\n");
+        assert_eq!(lines.next(), Some(CSV_HEADER.join(",").as_str()));
+        assert_eq!(
+            lines.next(),
+            Some(
+                "1WMHH000M12345,Quest 3,com.example.game,\"Game, \"\"Deluxe\"\" \
+                 Edition\",42,1.2.3,false,1024,2048,0,,3600"
+            )
+        );
+        assert_eq!(lines.next(), Some(""));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn json_keeps_missing_usage_as_null() {
+        let json =
+            render_inventory(&[row("com.example.game", "Game")], ExportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["package_name"], "com.example.game");
+        assert_eq!(value[0]["last_used"], serde_json::Value::Null);
+        assert_eq!(value[0]["total_time_used_secs"], 3600);
+    }
+}
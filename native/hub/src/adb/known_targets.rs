@@ -0,0 +1,73 @@
+//! Wireless ADB addresses of headsets connected before, kept in settings.
+//!
+//! mDNS announcements are not reliable on every network, so the last address of each headset is
+//! remembered and the most recently connected one is dialed directly when no device is connected.
+
+use std::{net::SocketAddr, time::Duration};
+
+use crate::models::KnownWirelessTarget;
+
+/// How often the wireless connection is pinged and reconnecting is retried
+pub(super) const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(20);
+/// Headsets remembered, the least recently connected one is forgotten first
+const MAX_KNOWN_TARGETS: usize = 8;
+
+/// Moves the headset to the front with its current address.
+///
+/// Returns whether the list changed.
+pub(super) fn remember(
+    targets: &mut Vec<KnownWirelessTarget>,
+    true_serial: &str,
+    address: &str,
+) -> bool {
+    if targets.first().is_some_and(|t| t.true_serial == true_serial && t.address == address) {
+        return false;
+    }
+    targets.retain(|t| t.true_serial != true_serial && t.address != address);
+    targets.insert(
+        0,
+        KnownWirelessTarget { true_serial: true_serial.to_string(), address: address.to_string() },
+    );
+    targets.truncate(MAX_KNOWN_TARGETS);
+    true
+}
+
+/// Address of the most recently connected headset to reconnect to
+pub(super) fn preferred(targets: &[KnownWirelessTarget]) -> Option<SocketAddr> {
+    targets.iter().find_map(|t| t.address.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(true_serial: &str, address: &str) -> KnownWirelessTarget {
+        KnownWirelessTarget { true_serial: true_serial.into(), address: address.into() }
+    }
+
+    #[test]
+    fn keeps_most_recent_address_per_headset_first() {
+        let mut targets = vec![target("A", "10.0.0.2:5555"), target("B", "10.0.0.3:5555")];
+
+        assert!(!remember(&mut targets, "A", "10.0.0.2:5555"));
+        assert!(remember(&mut targets, "B", "10.0.0.7:41234"));
+        assert_eq!(targets, [target("B", "10.0.0.7:41234"), target("A", "10.0.0.2:5555")]);
+
+        // An address taken over by another headset belongs to that one now
+        assert!(remember(&mut targets, "C", "10.0.0.2:5555"));
+        assert_eq!(targets, [target("C", "10.0.0.2:5555"), target("B", "10.0.0.7:41234")]);
+
+        for i in 0..MAX_KNOWN_TARGETS {
+            remember(&mut targets, &format!("S{i}"), &format!("10.0.1.{i}:5555"));
+        }
+        assert_eq!(targets.len(), MAX_KNOWN_TARGETS);
+        assert_eq!(targets[0].true_serial, format!("S{}", MAX_KNOWN_TARGETS - 1));
+    }
+
+    #[test]
+    fn prefers_first_parseable_address() {
+        assert_eq!(preferred(&[]), None);
+        let targets = [target("A", "adb-123._adb-tls-connect._tcp"), target("B", "10.0.0.3:5555")];
+        assert_eq!(preferred(&targets), Some("10.0.0.3:5555".parse().unwrap()));
+    }
+}
@@ -1,3 +1,11 @@
+mod auto_revert;
+mod battery_history;
 pub(crate) mod device;
+pub(crate) mod host_conflicts;
+pub(crate) mod inventory;
+mod known_targets;
+mod reconnect_grace;
 pub(crate) mod service;
+pub(crate) mod shell_console;
+pub(crate) mod watchdog;
 pub(crate) use service::*;
@@ -0,0 +1,130 @@
+//! Grace period keeping the current device while it is briefly missing from the device list.
+//!
+//! A headset going to sleep and waking up drops off USB for a moment. Disconnecting right away
+//! would reconnect and refresh everything once it is back, so the device is kept for a while
+//! and silently re-attached if the same serial comes back online.
+
+use std::time::Duration;
+
+use forensic_adb::{DeviceBrief, DeviceState};
+use tokio::time::Instant;
+
+/// What to do with the current device after a device list update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CurrentDeviceAction {
+    /// Still online, nothing to do
+    Keep,
+    /// Came back online within the grace period
+    Reattach,
+    /// Missing, kept until the grace period ends
+    Wait,
+    /// Missing for longer than the grace period
+    Disconnect,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ReconnectGrace {
+    /// Serial of the missing device and when it is given up on
+    missing: Option<(String, Instant)>,
+}
+
+impl ReconnectGrace {
+    /// Decides what happens to the current device given the latest device list
+    pub(super) fn check(
+        &mut self,
+        serial: &str,
+        devices: &[DeviceBrief],
+        grace_period: Duration,
+        now: Instant,
+    ) -> CurrentDeviceAction {
+        let online = devices.iter().any(|d| d.serial == serial && d.state == DeviceState::Device);
+        let waiting = self.missing.take_if(|(missing, _)| missing == serial);
+        match (online, waiting) {
+            (true, None) => CurrentDeviceAction::Keep,
+            (true, Some(_)) => CurrentDeviceAction::Reattach,
+            (false, None) if grace_period.is_zero() => CurrentDeviceAction::Disconnect,
+            (false, None) => {
+                self.missing = Some((serial.to_string(), now + grace_period));
+                CurrentDeviceAction::Wait
+            }
+            (false, Some((_, deadline))) if deadline <= now => CurrentDeviceAction::Disconnect,
+            (false, Some(waiting)) => {
+                self.missing = Some(waiting);
+                CurrentDeviceAction::Wait
+            }
+        }
+    }
+
+    /// When the missing device is given up on, if one is being waited for
+    pub(super) fn deadline(&self) -> Option<Instant> {
+        self.missing.as_ref().map(|(_, deadline)| *deadline)
+    }
+
+    /// Stops waiting, e.g. after the device was disconnected another way
+    pub(super) fn clear(&mut self) {
+        self.missing = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRACE: Duration = Duration::from_secs(5);
+
+    fn device(serial: &str, state: DeviceState) -> DeviceBrief {
+        DeviceBrief { serial: serial.to_string(), state }
+    }
+
+    #[test]
+    fn missing_device_is_kept_until_the_grace_period_ends() {
+        let mut grace = ReconnectGrace::default();
+        let now = Instant::now();
+
+        assert_eq!(grace.check("A", &[], GRACE, now), CurrentDeviceAction::Wait);
+        assert_eq!(grace.deadline(), Some(now + GRACE));
+        let offline = [device("A", DeviceState::Offline)];
+        assert_eq!(
+            grace.check("A", &offline, GRACE, now + Duration::from_secs(2)),
+            CurrentDeviceAction::Wait
+        );
+        assert_eq!(grace.deadline(), Some(now + GRACE));
+        assert_eq!(grace.check("A", &offline, GRACE, now + GRACE), CurrentDeviceAction::Disconnect);
+        assert_eq!(grace.deadline(), None);
+    }
+
+    #[test]
+    fn returning_device_is_reattached() {
+        let mut grace = ReconnectGrace::default();
+        let now = Instant::now();
+        let online = [device("A", DeviceState::Device)];
+
+        assert_eq!(grace.check("A", &online, GRACE, now), CurrentDeviceAction::Keep);
+        assert_eq!(grace.check("A", &[], GRACE, now), CurrentDeviceAction::Wait);
+        assert_eq!(
+            grace.check("A", &online, GRACE, now + Duration::from_secs(1)),
+            CurrentDeviceAction::Reattach
+        );
+        assert_eq!(grace.deadline(), None);
+    }
+
+    #[test]
+    fn zero_grace_period_disconnects_at_once() {
+        let mut grace = ReconnectGrace::default();
+        assert_eq!(
+            grace.check("A", &[], Duration::ZERO, Instant::now()),
+            CurrentDeviceAction::Disconnect
+        );
+    }
+
+    #[test]
+    fn waiting_for_another_device_starts_over() {
+        let mut grace = ReconnectGrace::default();
+        let now = Instant::now();
+        grace.check("A", &[], GRACE, now);
+
+        let later = now + Duration::from_secs(3);
+        assert_eq!(grace.check("B", &[], GRACE, later), CurrentDeviceAction::Wait);
+        assert_eq!(grace.deadline(), Some(later + GRACE));
+    }
+}
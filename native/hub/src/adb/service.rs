@@ -1,48 +1,81 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     error::Error,
     fmt,
     net::SocketAddr,
     path::{Path, PathBuf},
-    sync::Arc,
-    time::Duration,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{Context, Result, anyhow, bail, ensure};
 use derive_more::Debug;
 use forensic_adb::{DeviceBrief, DeviceInfo, DeviceState};
-use futures::FutureExt;
+use futures::{FutureExt, future};
 use lazy_regex::{Lazy, Regex, lazy_regex};
 use mdns_sd::{ServiceDaemon, ServiceEvent};
 use rinf::{DartSignal, RustSignal};
 use tokio::{
     process::Command,
-    sync::{Mutex, RwLock, mpsc::UnboundedSender},
-    time::{self, timeout},
+    sync::{Mutex, Notify, RwLock, mpsc::UnboundedSender, oneshot, watch},
+    time::{self, Instant, timeout},
 };
 use tokio_stream::{StreamExt, wrappers::WatchStream};
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Span, debug, error, info, info_span, instrument, trace, warn};
 
-use super::device::AdbDevice;
+use super::{
+    auto_revert::RevertSchedule,
+    battery_history::{BatteryHistoryBuffer, SAMPLE_INTERVAL},
+    device::AdbDevice,
+    inventory, known_targets,
+    reconnect_grace::{CurrentDeviceAction, ReconnectGrace},
+};
 use crate::{
-    adb::device::{BackupOptions, SideloadProgress},
+    adb::device::{
+        AgentApk, BackupOptions, PackageChange, PackageVersions, RestoreOutcome, SideloadProgress,
+        crash_report_file_name, package_changes,
+    },
+    device_log::DeviceLog,
+    metadata_store::MetadataStore,
     models::{
-        ConnectionKind, Settings,
+        ConnectionKind, DeviceCapabilities, OfflineWakeAction, Settings,
+        apk_info::get_apk_info,
         signals::{
             adb::{
+                auto_revert::{AutoRevertKind, AutoRevertStatus},
+                backup_estimate::{BackupEstimateResponse, EstimateBackupRequest},
+                battery_history::{BatteryHistory, BatterySample, GetBatteryHistoryRequest},
                 command::*,
-                device::DeviceChangedEvent,
+                device::{AdbDevice as SignalAdbDevice, DeviceChangedEvent},
                 devices_list::{AdbDeviceBrief, AdbDevicesList},
-                dump::BatteryDumpResponse,
+                dump::{BatteryDumpResponse, CrashReportResponse},
+                export::{ExportInstalledListRequest, ExportInstalledListResponse},
+                install_script::{InstallScriptDecision, InstallScriptReview, ScriptOperation},
+                macros::{
+                    CancelCommandMacroRequest, CommandMacro, CommandMacroResult, MacroStep,
+                    MacroStepResult, MacroStepStatus, RunCommandMacroRequest,
+                },
+                permissions::AppPermissionsResponse,
                 state::AdbState,
+                wake::{DeviceWakeReport, WakeStep, WakeStepKind},
             },
+            device_log::DeviceLogEntryKind,
             system::Toast,
+            task::{TaskArtifact, TaskArtifactKind},
         },
+        vendor::Vendor,
     },
+    settings::SettingsHandler,
     utils::resolve_binary_path,
 };
 
+/// Longest time a temporary device state may be kept before it is restored
+const MAX_AUTO_REVERT_SECS: u64 = 12 * 60 * 60;
+
 pub(crate) static PACKAGE_NAME_REGEX: Lazy<Regex> =
     lazy_regex!(r"^(?:[A-Za-z]{1}[\w]*\.)+[A-Za-z][\w]*$");
 
@@ -75,6 +108,13 @@ struct CachedDeviceData {
     pub true_serial: String,
 }
 
+/// How long a woken device is given to come online
+const WAKE_ONLINE_TIMEOUT: Duration = Duration::from_secs(8);
+/// How long the watchdog health check waits for the ADB server, which can be slow while busy
+const HEALTH_PING_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long an install script review waits for an answer before it is declined
+const SCRIPT_REVIEW_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
 /// Handles ADB state, device connections and commands
 #[derive(Debug)]
 pub(crate) struct AdbService {
@@ -88,16 +128,65 @@ pub(crate) struct AdbService {
     adb_state: RwLock<AdbState>,
     /// Currently connected device (if any)
     device: RwLock<Option<Arc<AdbDevice>>>,
+    /// Notified whenever the current device is replaced or refreshed
+    device_changed_tx: watch::Sender<()>,
     /// Serializes connect/disconnect operations to avoid races
     device_op_mutex: Mutex<()>,
     /// Cancellation token for running tasks
     cancel_token: RwLock<CancellationToken>,
+    /// Number of device tracker runs started, used to tell them apart
+    tracker_runs: AtomicU64,
+    /// Run of the device tracker currently receiving updates, 0 if none
+    tracker_receiving: AtomicU64,
     /// Cache of adb transport_id -> device data
     device_data_cache: RwLock<HashMap<String, CachedDeviceData>>,
+    /// Vendor and capabilities last detected per adb serial, kept when a later probe fails
+    known_capabilities: RwLock<HashMap<String, (Vendor, DeviceCapabilities)>>,
     /// Whether mDNS auto-connect is enabled
     mdns_auto_connect: bool,
+    /// Whether wireless connections are kept alive and the last wireless device is reconnected
+    reconnect_wireless_devices: RwLock<bool>,
+    /// Wakes the wireless reconnect task when the last device goes away
+    wireless_reconnect: Notify,
     /// Preferred connection type (USB or Wireless) for auto-connect
     preferred_connection_type: RwLock<ConnectionKind>,
+    /// Whether devices that stay offline are woken automatically
+    wake_offline_devices: RwLock<bool>,
+    /// What is sent to wake an offline device
+    offline_wake_action: RwLock<OfflineWakeAction>,
+    /// Seconds the current device is kept while missing from the device list
+    device_reconnect_grace_secs: RwLock<u32>,
+    /// Offline devices already woken automatically, cleared once they recover or go away
+    wake_attempted: Mutex<HashSet<String>>,
+    /// Whether the device helper is installed and upgraded on connected devices
+    device_agent_enabled: RwLock<bool>,
+    /// Whether the package list of devices without the helper is watched on the device
+    watch_package_changes: RwLock<bool>,
+    /// Command macros saved in settings
+    command_macros: RwLock<Vec<CommandMacro>>,
+    /// Cancellation tokens of running macros by their command key
+    running_macros: Mutex<HashMap<String, CancellationToken>>,
+    /// Temporary device states to restore, per device serial
+    auto_reverts: Mutex<RevertSchedule>,
+    /// Wakes the auto-revert timer when the schedule changes
+    auto_reverts_changed: Notify,
+    /// Battery levels sampled this session, per device serial
+    battery_history: Mutex<BatteryHistoryBuffer>,
+    /// User-defined app metadata applied to device signals
+    #[debug(skip)]
+    metadata_store: Arc<MetadataStore>,
+    /// Per-headset maintenance logs receiving entries for major operations
+    #[debug(skip)]
+    device_log: Arc<DeviceLog>,
+    /// Install scripts waiting for the user to approve them, by review ID
+    script_reviews: std::sync::Mutex<HashMap<u64, oneshot::Sender<bool>>>,
+    /// Last install script review ID handed out
+    last_script_review: AtomicU64,
+    /// Answer given to install script reviews when there is no UI to ask, `None` with the UI
+    headless_script_approval: std::sync::Mutex<Option<bool>>,
+    /// Saves the addresses of connected wireless devices
+    #[debug(skip)]
+    settings_handler: Arc<SettingsHandler>,
     /// App data directory used by auxiliary tools.
     #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
     app_dir: PathBuf,
@@ -109,11 +198,14 @@ impl AdbService {
     ///
     /// # Returns
     /// Arc-wrapped AdbService that manages ADB device connections
-    #[instrument(level = "debug", skip(settings_stream))]
+    #[instrument(level = "debug", skip(settings_handler, metadata_store, device_log))]
     pub(crate) async fn new(
-        mut settings_stream: WatchStream<Settings>,
+        settings_handler: Arc<SettingsHandler>,
+        metadata_store: Arc<MetadataStore>,
+        device_log: Arc<DeviceLog>,
         app_dir: PathBuf,
     ) -> Arc<Self> {
+        let mut settings_stream = WatchStream::new(settings_handler.subscribe());
         let first_settings =
             settings_stream.next().await.expect("Settings stream closed on adb init");
         let adb_path = first_settings.adb_path;
@@ -130,11 +222,34 @@ impl AdbService {
             adb_path: RwLock::new(adb_path),
             adb_state: RwLock::new(AdbState::default()),
             device: None.into(),
+            device_changed_tx: watch::Sender::new(()),
             device_op_mutex: Mutex::new(()),
             cancel_token: RwLock::new(CancellationToken::new()),
+            tracker_runs: AtomicU64::new(0),
+            tracker_receiving: AtomicU64::new(0),
             device_data_cache: RwLock::new(HashMap::new()),
+            known_capabilities: RwLock::new(HashMap::new()),
             mdns_auto_connect: first_settings.mdns_auto_connect,
+            reconnect_wireless_devices: RwLock::new(first_settings.reconnect_wireless_devices),
+            wireless_reconnect: Notify::new(),
             preferred_connection_type: RwLock::new(first_settings.preferred_connection_type),
+            wake_offline_devices: RwLock::new(first_settings.wake_offline_devices),
+            offline_wake_action: RwLock::new(first_settings.offline_wake_action),
+            device_reconnect_grace_secs: RwLock::new(first_settings.device_reconnect_grace_secs),
+            wake_attempted: Mutex::new(HashSet::new()),
+            device_agent_enabled: RwLock::new(first_settings.device_agent_enabled),
+            watch_package_changes: RwLock::new(first_settings.watch_package_changes),
+            command_macros: RwLock::new(first_settings.command_macros),
+            running_macros: Mutex::new(HashMap::new()),
+            auto_reverts: Mutex::new(RevertSchedule::default()),
+            auto_reverts_changed: Notify::new(),
+            battery_history: Mutex::new(BatteryHistoryBuffer::default()),
+            metadata_store,
+            device_log,
+            script_reviews: std::sync::Mutex::new(HashMap::new()),
+            last_script_review: AtomicU64::new(0),
+            headless_script_approval: std::sync::Mutex::new(None),
+            settings_handler,
             app_dir,
         });
         tokio::spawn(
@@ -196,6 +311,29 @@ impl AdbService {
                             info!(?new_connection_type, "Preferred connection type changed");
                             *handle.preferred_connection_type.write().await = new_connection_type;
                         }
+
+                        *handle.reconnect_wireless_devices.write().await =
+                            settings.reconnect_wireless_devices;
+                        *handle.wake_offline_devices.write().await = settings.wake_offline_devices;
+                        *handle.offline_wake_action.write().await = settings.offline_wake_action;
+                        *handle.device_reconnect_grace_secs.write().await =
+                            settings.device_reconnect_grace_secs;
+                        *handle.watch_package_changes.write().await =
+                            settings.watch_package_changes;
+                        *handle.command_macros.write().await = settings.command_macros.clone();
+
+                        let agent_enabled = settings.device_agent_enabled;
+                        let was_enabled = std::mem::replace(
+                            &mut *handle.device_agent_enabled.write().await,
+                            agent_enabled,
+                        );
+                        if agent_enabled && !was_enabled {
+                            info!("Device helper enabled");
+                            tokio::spawn({
+                                let handle = handle.clone();
+                                async move { handle.sync_device_agent().await }
+                            });
+                        }
                     }
 
                     panic!("Settings stream closed for AdbService");
@@ -204,6 +342,44 @@ impl AdbService {
             .instrument(info_span!("task_handle_settings_updates")),
         );
 
+        // Install or upgrade the device helper on newly connected devices
+        tokio::spawn(
+            {
+                let handle = self.clone();
+                let mut device_changes = handle.subscribe_device_changes();
+                async move {
+                    let mut last_serial = None;
+                    while device_changes.changed().await.is_ok() {
+                        let serial = handle.try_current_device().await.map(|d| d.serial.clone());
+                        if serial.is_some() && serial != last_serial {
+                            handle.sync_device_agent().await;
+                        }
+                        last_serial = serial;
+                    }
+                }
+            }
+            .instrument(info_span!("task_sync_device_agent")),
+        );
+
+        // Resend the current device when display name overrides change
+        tokio::spawn(
+            {
+                let handle = self.clone();
+                let mut metadata_stream =
+                    WatchStream::from_changes(handle.metadata_store.subscribe());
+                async move {
+                    while metadata_stream.next().await.is_some() {
+                        let device = handle.device.read().await.as_deref().cloned();
+                        if device.is_some() {
+                            debug!("App metadata changed, resending device");
+                            handle.send_device_changed(device);
+                        }
+                    }
+                }
+            }
+            .instrument(info_span!("task_handle_metadata_updates")),
+        );
+
         self.start_adb_tasks().await;
     }
 
@@ -248,6 +424,101 @@ impl AdbService {
             }
         });
 
+        // Listen for installed app list exports
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result =
+                    cancel_token.run_until_cancelled(handle.receive_export_requests()).await;
+                debug!(result = ?result, "Export request receiver task finished");
+                result
+            }
+        });
+
+        // Listen for install script decisions
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result =
+                    cancel_token.run_until_cancelled(handle.receive_script_decisions()).await;
+                debug!(result = ?result, "Install script decision receiver task finished");
+                result
+            }
+        });
+
+        // Listen for backup estimate requests
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result =
+                    cancel_token.run_until_cancelled(handle.receive_backup_estimates()).await;
+                debug!(result = ?result, "Backup estimate receiver task finished");
+                result
+            }
+        });
+
+        // Listen for command macro runs
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result =
+                    cancel_token.run_until_cancelled(handle.receive_macro_requests()).await;
+                debug!(result = ?result, "Macro request receiver task finished");
+                result
+            }
+        });
+
+        // Restore temporary device states when their time is up
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result = cancel_token.run_until_cancelled(handle.run_auto_reverts()).await;
+                debug!(result = ?result, "Auto-revert task finished");
+                result
+            }
+        });
+
+        // Sample battery levels for the battery history
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result = cancel_token.run_until_cancelled(handle.run_battery_sampling()).await;
+                debug!(result = ?result, "Battery sampling task finished");
+                result
+            }
+        });
+
+        // Keep wireless connections alive and reconnect to the last wireless device
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result =
+                    cancel_token.run_until_cancelled(handle.run_wireless_keepalive()).await;
+                debug!(result = ?result, "Wireless keepalive task finished");
+                result
+            }
+        });
+
+        // Listen for battery history requests
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result = cancel_token
+                    .run_until_cancelled(handle.receive_battery_history_requests())
+                    .await;
+                debug!(result = ?result, "Battery history request receiver task finished");
+                result
+            }
+        });
+
         // Refresh device info periodically
         tokio::spawn({
             let handle = self.clone();
@@ -259,6 +530,30 @@ impl AdbService {
             }
         });
 
+        // Refresh as soon as the device helper reports package changes
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result =
+                    cancel_token.run_until_cancelled(handle.run_agent_package_watch()).await;
+                debug!(result = ?result, "Device helper package watch task finished");
+                result
+            }
+        });
+
+        // Patch the package list as soon as the on-device watch sees changes
+        tokio::spawn({
+            let handle = self.clone();
+            let cancel_token = self.cancel_token.read().await.clone();
+            async move {
+                let result =
+                    cancel_token.run_until_cancelled(handle.run_device_package_watch()).await;
+                debug!(result = ?result, "Device package watch task finished");
+                result
+            }
+        });
+
         // mDNS auto-connect for ADB-over-Wi‑Fi targets (applies on startup)
         if self.mdns_auto_connect {
             tokio::spawn({
@@ -277,7 +572,7 @@ impl AdbService {
     /// Restarts the ADB handling
     // TODO: make sure this cannot race with `ensure_server_running`
     #[instrument(skip(self), err)]
-    async fn restart_adb(self: Arc<AdbService>) -> Result<()> {
+    pub(crate) async fn restart_adb(self: Arc<AdbService>) -> Result<()> {
         info!("Restarting ADB server and tasks");
         // Cancel all tasks
         self.cancel_token.read().await.cancel();
@@ -296,6 +591,31 @@ impl AdbService {
         Ok(())
     }
 
+    /// ADB binary the server is started from, if it can be found
+    pub(crate) async fn adb_binary_path(&self) -> Option<PathBuf> {
+        resolve_binary_path(self.adb_path.read().await.as_deref(), "adb").ok()
+    }
+
+    /// Output of `adb version` for the ADB binary in use
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn adb_version(&self) -> Result<String> {
+        let adb_path = self.adb_binary_path().await.context("ADB binary not found")?;
+        let mut command = Command::new(&adb_path);
+        command.arg("version");
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        let output = timeout(Duration::from_secs(10), command.output())
+            .await
+            .context("Timed out while reading the ADB version")?
+            .context("Failed to run adb version")?;
+        ensure!(
+            output.status.success(),
+            "adb version failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
     /// Kills the ADB server
     #[instrument(level = "debug", skip(self), err)]
     async fn kill_adb_server(&self) -> Result<()> {
@@ -317,6 +637,8 @@ impl AdbService {
         self: Arc<AdbService>,
         sender: tokio::sync::mpsc::UnboundedSender<Vec<DeviceBrief>>,
     ) -> Result<()> {
+        let run = self.tracker_runs.fetch_add(1, Ordering::Relaxed) + 1;
+        let _receiving = TrackerRun { receiving: &self.tracker_receiving, run };
         loop {
             debug!("Starting track_devices loop");
             self.ensure_server_running().await?;
@@ -328,6 +650,7 @@ impl AdbService {
                 match device_result {
                     Ok(device_list) => {
                         got_update = true;
+                        self.tracker_receiving.store(run, Ordering::Relaxed);
                         if sender.send(device_list).is_err() {
                             bail!("Device update receiver dropped");
                         }
@@ -340,6 +663,12 @@ impl AdbService {
                                 "track_devices stream returned an unexpected error, restarting"
                             );
                             // Server might have died
+                            let _ = self.tracker_receiving.compare_exchange(
+                                run,
+                                0,
+                                Ordering::Relaxed,
+                                Ordering::Relaxed,
+                            );
                             self.refresh_adb_state().await;
                             break;
                         } else {
@@ -362,22 +691,51 @@ impl AdbService {
         self: Arc<AdbService>,
         mut receiver: tokio::sync::mpsc::UnboundedReceiver<Vec<DeviceBrief>>,
     ) -> Result<()> {
-        while let Some(devices) = receiver.recv().await {
-            debug!(update = ?devices, "Received device list update");
+        let mut devices: Vec<DeviceBrief> = Vec::new();
+        let mut grace = ReconnectGrace::default();
+        loop {
+            let had_devices = devices.iter().any(|d| d.state == DeviceState::Device);
+            let deadline = grace.deadline();
+            tokio::select! {
+                update = receiver.recv() => match update {
+                    Some(update) => {
+                        debug!(update = ?update, "Received device list update");
+                        devices = update;
+                    }
+                    None => break,
+                },
+                _ = time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    debug!("Reconnect grace period ended");
+                }
+            }
 
             if let Some(current) = self.try_current_device().await {
-                let still_present = devices
-                    .iter()
-                    .any(|d| d.serial == current.serial && d.state == DeviceState::Device);
-                if !still_present {
-                    info!(
-                        serial = %current.serial,
-                        "Current device missing from device list or is not in \"device\" state, disconnecting"
-                    );
-                    if let Err(e) = self.disconnect_device(Some(&current.serial)).await {
-                        error!(error = e.as_ref() as &dyn Error, "Auto-disconnect failed");
+                let grace_period =
+                    Duration::from_secs((*self.device_reconnect_grace_secs.read().await).into());
+                match grace.check(&current.serial, &devices, grace_period, Instant::now()) {
+                    CurrentDeviceAction::Keep => {}
+                    CurrentDeviceAction::Reattach => {
+                        info!(serial = %current.serial, "Current device is back, keeping it");
+                    }
+                    CurrentDeviceAction::Wait => {
+                        debug!(
+                            serial = %current.serial,
+                            grace_secs = grace_period.as_secs(),
+                            "Current device is missing, waiting for it to come back"
+                        );
+                    }
+                    CurrentDeviceAction::Disconnect => {
+                        info!(
+                            serial = %current.serial,
+                            "Current device missing from device list or is not in \"device\" state, disconnecting"
+                        );
+                        if let Err(e) = self.disconnect_device(Some(&current.serial)).await {
+                            error!(error = e.as_ref() as &dyn Error, "Auto-disconnect failed");
+                        }
                     }
                 }
+            } else {
+                grace.clear();
             }
 
             if self.try_current_device().await.is_none()
@@ -390,6 +748,12 @@ impl AdbService {
                 }
             }
 
+            if had_devices && !devices.iter().any(|d| d.state == DeviceState::Device) {
+                debug!("Last device went away");
+                self.wireless_reconnect.notify_one();
+            }
+
+            self.wake_offline_devices(&devices).await;
             self.refresh_adb_state().await;
         }
 
@@ -412,6 +776,288 @@ impl AdbService {
         panic!("AdbRequest receiver closed");
     }
 
+    /// Listens for installed app list export requests from Dart
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_export_requests(&self) {
+        let receiver = ExportInstalledListRequest::get_dart_signal_receiver();
+        while let Some(request) = receiver.recv().await {
+            let request = request.message;
+            debug!(?request, "Received installed app list export request");
+            let path = request.path.clone();
+            let (count, error) = match self.export_installed_list(request).await {
+                Ok(count) => (count, None),
+                Err(e) => {
+                    error!(error = e.as_ref() as &dyn Error, "Installed app list export failed");
+                    (0, Some(format!("{e:#}")))
+                }
+            };
+            ExportInstalledListResponse { path, count, error }.send_signal_to_dart();
+        }
+        panic!("ExportInstalledListRequest receiver closed");
+    }
+
+    /// Listens for backup size estimate requests from Dart
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_backup_estimates(&self) {
+        let receiver = EstimateBackupRequest::get_dart_signal_receiver();
+        while let Some(request) = receiver.recv().await {
+            let package_name = request.message.package_name;
+            debug!(package_name, "Received backup estimate request");
+            let result = async {
+                let package = PackageName::parse(&package_name)?;
+                self.current_device().await?.estimate_backup(&package).await
+            }
+            .await;
+            match result {
+                Ok(estimate) => estimate.send_signal_to_dart(),
+                Err(e) => {
+                    error!(error = e.as_ref() as &dyn Error, "Backup estimate failed");
+                    BackupEstimateResponse {
+                        package_name,
+                        error: Some(format!("{e:#}")),
+                        ..Default::default()
+                    }
+                    .send_signal_to_dart();
+                }
+            }
+        }
+        panic!("EstimateBackupRequest receiver closed");
+    }
+
+    /// Listens for the user's answers to install script reviews
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_script_decisions(&self) {
+        let receiver = InstallScriptDecision::get_dart_signal_receiver();
+        while let Some(request) = receiver.recv().await {
+            let InstallScriptDecision { review_id, approved } = request.message;
+            debug!(review_id, approved, "Received install script decision");
+            match self.script_reviews().remove(&review_id) {
+                Some(sender) => {
+                    let _ = sender.send(approved);
+                }
+                None => warn!(review_id, "Install script review not found, it may have ended"),
+            }
+        }
+        panic!("InstallScriptDecision receiver closed");
+    }
+
+    fn script_reviews(&self) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<bool>>> {
+        self.script_reviews.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Answers install script reviews without asking, for running without the UI
+    pub(crate) fn set_headless_script_approval(&self, approve: bool) {
+        *self.headless_script_approval.lock().unwrap_or_else(|e| e.into_inner()) = Some(approve);
+    }
+
+    /// Asks the user to approve the operations of an install script, unless scripts are
+    /// approved automatically. Reviews left unanswered for [`SCRIPT_REVIEW_TIMEOUT`] are
+    /// declined.
+    #[instrument(level = "debug", skip(self, operations), fields(operations = operations.len()))]
+    pub(crate) async fn review_install_script(
+        &self,
+        script_path: String,
+        operations: Vec<ScriptOperation>,
+    ) -> bool {
+        if self.settings_handler.subscribe().borrow().auto_approve_install_scripts {
+            info!("Install script approved automatically");
+            return true;
+        }
+        let headless_approval =
+            *self.headless_script_approval.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(approved) = headless_approval {
+            info!(approved, "Install script reviewed without UI");
+            return approved;
+        }
+
+        /// Forgets the review if the install is cancelled before it is answered
+        struct PendingReview<'a>(&'a AdbService, u64);
+        impl Drop for PendingReview<'_> {
+            fn drop(&mut self) {
+                self.0.script_reviews().remove(&self.1);
+            }
+        }
+
+        let review_id = self.last_script_review.fetch_add(1, Ordering::Relaxed) + 1;
+        let (approved_tx, approved_rx) = oneshot::channel();
+        self.script_reviews().insert(review_id, approved_tx);
+        let _pending = PendingReview(self, review_id);
+        InstallScriptReview { review_id, script_path, operations }.send_signal_to_dart();
+
+        let approved = match timeout(SCRIPT_REVIEW_TIMEOUT, approved_rx).await {
+            Ok(answer) => answer.unwrap_or(false),
+            Err(_) => {
+                warn!(review_id, "Install script review timed out, declining");
+                false
+            }
+        };
+        info!(review_id, approved, "Install script reviewed");
+        approved
+    }
+
+    /// Listens for battery history requests from Dart
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_battery_history_requests(&self) {
+        let receiver = GetBatteryHistoryRequest::get_dart_signal_receiver();
+        while let Some(request) = receiver.recv().await {
+            debug!(serial = ?request.message.serial, "Received battery history request");
+            let serial = match request.message.serial {
+                Some(serial) => Some(serial),
+                None => self.try_current_device().await.map(|device| device.serial.clone()),
+            };
+            let samples = match &serial {
+                Some(serial) => self.battery_history.lock().await.samples(serial),
+                None => Vec::new(),
+            };
+            BatteryHistory { serial, samples }.send_signal_to_dart();
+        }
+        panic!("GetBatteryHistoryRequest receiver closed");
+    }
+
+    /// Listens for command macro runs and cancellations requested from Dart.
+    ///
+    /// Each macro runs in its own task, so a long macro does not hold up other requests.
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_macro_requests(self: Arc<Self>) {
+        let run_receiver = RunCommandMacroRequest::get_dart_signal_receiver();
+        let cancel_receiver = CancelCommandMacroRequest::get_dart_signal_receiver();
+        loop {
+            tokio::select! {
+                request = run_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("RunCommandMacroRequest receiver closed");
+                    };
+                    let RunCommandMacroRequest { name, command_key } = request.message;
+                    debug!(name, command_key, "Received command macro request");
+                    self.clone().start_command_macro(name, command_key).await;
+                }
+                request = cancel_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("CancelCommandMacroRequest receiver closed");
+                    };
+                    let command_key = request.message.command_key;
+                    debug!(command_key, "Received command macro cancel request");
+                    if let Some(token) = self.running_macros.lock().await.get(&command_key) {
+                        token.cancel();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Validates the named macro and runs it in a new task, sending its result when done
+    async fn start_command_macro(self: Arc<Self>, name: String, command_key: String) {
+        let token = self.cancel_token.read().await.child_token();
+        let checked = async {
+            let command_macro = self
+                .command_macros
+                .read()
+                .await
+                .iter()
+                .find(|m| m.name == name)
+                .cloned()
+                .context("Macro not found")?;
+            command_macro.validate()?;
+            let mut running = self.running_macros.lock().await;
+            ensure!(!running.contains_key(&command_key), "Macro is already running");
+            running.insert(command_key.clone(), token.clone());
+            anyhow::Ok(command_macro)
+        }
+        .await;
+        let command_macro = match checked {
+            Ok(command_macro) => command_macro,
+            Err(e) => {
+                error!(error = e.as_ref() as &dyn Error, name, "Cannot run command macro");
+                CommandMacroResult {
+                    name,
+                    command_key,
+                    steps: Vec::new(),
+                    error: Some(format!("{e:#}")),
+                }
+                .send_signal_to_dart();
+                return;
+            }
+        };
+        tokio::spawn(async move {
+            let steps = self.run_command_macro(&command_key, &command_macro, &token).await;
+            self.running_macros.lock().await.remove(&command_key);
+            CommandMacroResult { name, command_key, steps, error: None }.send_signal_to_dart();
+        });
+    }
+
+    /// Runs the steps of a validated macro in order, stopping at the first failure or when
+    /// `token` is cancelled.
+    ///
+    /// Steps are executed like individual commands, with `<key>/<step>` as their command key.
+    #[instrument(level = "debug", skip(self, command_macro, token), fields(name = %command_macro.name))]
+    async fn run_command_macro(
+        &self,
+        key: &str,
+        command_macro: &CommandMacro,
+        token: &CancellationToken,
+    ) -> Vec<MacroStepResult> {
+        info!(steps = command_macro.steps.len(), "Running command macro");
+        let mut results = Vec::with_capacity(command_macro.steps.len());
+        let mut failed = false;
+        for (index, step) in command_macro.steps.iter().enumerate() {
+            if failed {
+                results.push(MacroStepResult { status: MacroStepStatus::Skipped, error: None });
+                continue;
+            }
+            let step_run = async {
+                match step {
+                    MacroStep::Command(command) => {
+                        self.execute_command(format!("{key}/{index}"), command.clone()).await
+                    }
+                    MacroStep::Delay(ms) => {
+                        tokio::time::sleep(Duration::from_millis(*ms)).await;
+                        Ok(())
+                    }
+                }
+            };
+            let Some(result) = token.run_until_cancelled(step_run).await else {
+                info!(step = index + 1, "Command macro cancelled");
+                failed = true;
+                results.push(MacroStepResult { status: MacroStepStatus::Cancelled, error: None });
+                continue;
+            };
+            results.push(match result {
+                Ok(()) => MacroStepResult { status: MacroStepStatus::Succeeded, error: None },
+                Err(e) => {
+                    warn!(error = e.as_ref() as &dyn Error, step = index + 1, "Macro step failed");
+                    failed = true;
+                    MacroStepResult {
+                        status: MacroStepStatus::Failed,
+                        error: Some(format!("{e:#}")),
+                    }
+                }
+            });
+        }
+        results
+    }
+
+    /// Writes the installed packages of the current device to the requested file.
+    ///
+    /// Usage statistics are best effort, the export goes on without them if they cannot be read.
+    #[instrument(level = "debug", skip(self), err)]
+    async fn export_installed_list(&self, request: ExportInstalledListRequest) -> Result<u32> {
+        let device = self.current_device().await?;
+        let usage = match device.usage_stats().await {
+            Ok(usage) => usage,
+            Err(e) => {
+                warn!(
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to read usage stats, exporting without them"
+                );
+                HashMap::new()
+            }
+        };
+        let rows = inventory::inventory_rows(&device, &usage, request.include_system);
+        inventory::write_inventory(Path::new(&request.path), &rows, request.format).await?;
+        info!(path = %request.path, count = rows.len(), "Exported installed app list");
+        Ok(rows.len() as u32)
+    }
+
     /// Executes a received ADB command with the given parameters
     #[instrument(level = "debug", skip(self))]
     async fn execute_command(&self, key: String, command: AdbCommand) -> Result<()> {
@@ -486,6 +1132,22 @@ impl AdbService {
                     };
 
                     let device = self.current_device().await?;
+                    if !device.capabilities.casting {
+                        send_toast(
+                            "Casting not supported".to_string(),
+                            "The Meta Quest Casting tool only works with Meta Quest headsets."
+                                .to_string(),
+                            true,
+                            None,
+                        );
+                        AdbCommandCompletedEvent {
+                            command_type: AdbCommandKind::StartCasting,
+                            command_key: key.clone(),
+                            success: false,
+                        }
+                        .send_signal_to_dart();
+                        return Ok(());
+                    }
                     let wireless = device.is_wireless;
                     let device_serial = &device.true_serial;
 
@@ -561,7 +1223,7 @@ impl AdbService {
                 }
             }
 
-            AdbCommand::RefreshDevice => match self.refresh_device().await {
+            AdbCommand::RefreshDevice(scope) => match self.refresh_device_scope(scope).await {
                 Ok(_) => Ok(()),
                 Err(e) => {
                     let error_msg = format!("Failed to refresh device: {e:#}");
@@ -573,7 +1235,14 @@ impl AdbService {
             // Power and device actions (parameterized)
             AdbCommand::Reboot(mode) => {
                 let device = self.current_device().await?;
-                let result = device.reboot_with_mode(mode).await;
+                let result = device.reboot_with_mode(mode.clone()).await;
+                if result.is_ok() {
+                    self.device_log.record_event(
+                        &device.true_serial,
+                        DeviceLogEntryKind::Reboot,
+                        format!("Rebooted ({mode:?})"),
+                    );
+                }
                 AdbCommandCompletedEvent {
                     command_type: AdbCommandKind::Reboot,
                     command_key: key.clone(),
@@ -595,6 +1264,7 @@ impl AdbService {
                 .send_signal_to_dart();
                 // Refresh device state to update proximity_disabled field
                 if success {
+                    self.cancel_auto_revert(&device, AutoRevertKind::ProximitySensor).await;
                     let _ = self.refresh_device().await;
                 }
                 result.map(|_| ()).context("Failed to set proximity sensor")
@@ -612,21 +1282,76 @@ impl AdbService {
                 .send_signal_to_dart();
                 // Refresh guardian state
                 if success {
+                    self.cancel_auto_revert(&device, AutoRevertKind::GuardianPause).await;
                     let _ = self.refresh_device().await;
                 }
                 result.map(|_| ()).context("Failed to set guardian paused state")
             }
 
-            AdbCommand::SetStorageConnection(connected) => {
-                let device = self.current_device().await?;
-                if device.is_wireless {
-                    AdbCommandCompletedEvent {
-                        command_type: AdbCommandKind::StorageConnectionSet,
-                        command_key: key.clone(),
-                        success: false,
-                    }
-                    .send_signal_to_dart();
-                    bail!("USB storage connection is only available over USB")
+            AdbCommand::ScheduleAutoRevert { kind, after_secs } => {
+                let result = async {
+                    let device = self.current_device().await?;
+                    let mut schedule = self.auto_reverts.lock().await;
+                    match after_secs {
+                        Some(secs) => {
+                            ensure!(
+                                (1..=MAX_AUTO_REVERT_SECS).contains(&secs),
+                                "Auto-revert delay must be between 1 and {MAX_AUTO_REVERT_SECS} \
+                                 seconds"
+                            );
+                            schedule.schedule(
+                                &device.true_serial,
+                                kind,
+                                Duration::from_secs(secs),
+                                Instant::now(),
+                            );
+                            info!(?kind, secs, "Auto-revert scheduled");
+                        }
+                        None => {
+                            schedule.cancel(&device.true_serial, kind);
+                            info!(?kind, "Auto-revert cancelled");
+                        }
+                    }
+                    self.auto_reverts_changed.notify_one();
+                    Ok(())
+                }
+                .await;
+                AdbCommandCompletedEvent {
+                    command_type: AdbCommandKind::AutoRevertSchedule,
+                    command_key: key.clone(),
+                    success: result.is_ok(),
+                }
+                .send_signal_to_dart();
+                result
+            }
+
+            AdbCommand::SetDeviceTweak(tweak) => {
+                let device = self.current_device().await?;
+                let result = device.set_device_tweak(&tweak).await;
+                let success = result.is_ok();
+                AdbCommandCompletedEvent {
+                    command_type: AdbCommandKind::DeviceTweakSet,
+                    command_key: key.clone(),
+                    success,
+                }
+                .send_signal_to_dart();
+                // Read back the applied value
+                if success {
+                    let _ = self.refresh_device().await;
+                }
+                result
+            }
+
+            AdbCommand::SetStorageConnection(connected) => {
+                let device = self.current_device().await?;
+                if device.is_wireless {
+                    AdbCommandCompletedEvent {
+                        command_type: AdbCommandKind::StorageConnectionSet,
+                        command_key: key.clone(),
+                        success: false,
+                    }
+                    .send_signal_to_dart();
+                    bail!("USB storage connection is only available over USB")
                 }
 
                 let result = device.set_storage_connection(connected).await;
@@ -638,9 +1363,156 @@ impl AdbService {
                 }
                 .send_signal_to_dart();
 
+                result
+            }
+
+            AdbCommand::WakeDevice(serial) => {
+                let report = self.wake_device(&serial, false).await;
+                AdbCommandCompletedEvent {
+                    command_type: AdbCommandKind::DeviceWake,
+                    command_key: key.clone(),
+                    success: report.recovered,
+                }
+                .send_signal_to_dart();
+                report.send_signal_to_dart();
                 Ok(())
             }
 
+            AdbCommand::InstallAgent => {
+                let result = async {
+                    let device = self.current_device().await?;
+                    let apk = AgentApk::bundled()?;
+                    if device.install_agent(&apk).await? {
+                        self.refresh_device().await?;
+                    }
+                    Ok::<_, anyhow::Error>(())
+                }
+                .await;
+                AdbCommandCompletedEvent {
+                    command_type: AdbCommandKind::AgentInstall,
+                    command_key: key.clone(),
+                    success: result.is_ok(),
+                }
+                .send_signal_to_dart();
+                result.inspect_err(|e| {
+                    send_toast(
+                        "Device helper install failed".to_string(),
+                        format!("{e:#}"),
+                        true,
+                        None,
+                    );
+                })
+            }
+
+            AdbCommand::RemoveAgent => {
+                let result = async {
+                    let device = self.current_device().await?;
+                    device.remove_agent().await?;
+                    self.refresh_device().await
+                }
+                .await;
+                AdbCommandCompletedEvent {
+                    command_type: AdbCommandKind::AgentRemove,
+                    command_key: key.clone(),
+                    success: result.is_ok(),
+                }
+                .send_signal_to_dart();
+                result.inspect_err(|e| {
+                    send_toast(
+                        "Device helper removal failed".to_string(),
+                        format!("{e:#}"),
+                        true,
+                        None,
+                    );
+                })
+            }
+
+            AdbCommand::CollectCrashReport(package_name) => {
+                let result = async {
+                    let package = PackageName::parse(&package_name)?;
+                    let device = self.current_device().await?;
+                    let bundle_path =
+                        self.app_dir.join("diagnostics").join(crash_report_file_name(&package));
+                    device.collect_crash_report(&package, &bundle_path).await?;
+                    info!(path = %bundle_path.display(), "Crash report collected");
+                    Ok::<_, anyhow::Error>(bundle_path)
+                }
+                .await;
+                CrashReportResponse {
+                    command_key: key.clone(),
+                    package_name,
+                    bundle: result
+                        .as_ref()
+                        .ok()
+                        .map(|path| TaskArtifact::new(TaskArtifactKind::ExportedZip, path)),
+                    error: result.as_ref().err().map(|e| format!("{e:#}")),
+                }
+                .send_signal_to_dart();
+                result.map(|_| ()).context("Failed to collect crash report")
+            }
+
+            AdbCommand::GetAppPermissions(package_name) => {
+                let result = async {
+                    let package = PackageName::parse(&package_name)?;
+                    self.current_device().await?.app_permissions(&package).await
+                }
+                .await;
+                let error = result.as_ref().err().map(|e| format!("{e:#}"));
+                AppPermissionsResponse {
+                    command_key: key.clone(),
+                    package_name,
+                    permissions: result.as_ref().cloned().unwrap_or_default(),
+                    error,
+                }
+                .send_signal_to_dart();
+                result.map(|_| ()).context("Failed to list app permissions")
+            }
+
+            AdbCommand::SetAppPermission { package_name, permission, granted } => {
+                let result = async {
+                    let package = PackageName::parse(&package_name)?;
+                    let device = self.current_device().await?;
+                    device.set_app_permission(&package, &permission, granted).await
+                }
+                .await;
+                AdbCommandCompletedEvent {
+                    command_type: AdbCommandKind::AppPermissionSet,
+                    command_key: key.clone(),
+                    success: result.is_ok(),
+                }
+                .send_signal_to_dart();
+                if let Err(e) = &result {
+                    let action = if granted { "Grant" } else { "Revoke" };
+                    Toast::send(
+                        format!("{action} Permission Failed"),
+                        format!("{e:#}"),
+                        true,
+                        None,
+                    );
+                }
+                result
+            }
+
+            AdbCommand::PairControllers => {
+                let result =
+                    async { self.current_device().await?.start_controller_pairing().await }.await;
+                AdbCommandCompletedEvent {
+                    command_type: AdbCommandKind::ControllerPairingStart,
+                    command_key: key.clone(),
+                    success: result.is_ok(),
+                }
+                .send_signal_to_dart();
+                if let Err(e) = &result {
+                    Toast::send(
+                        "Controller Pairing Failed".to_string(),
+                        format!("{e:#}"),
+                        true,
+                        None,
+                    );
+                }
+                result
+            }
+
             AdbCommand::GetBatteryDump => {
                 let device = self.current_device().await?;
                 match device.battery_dump().await {
@@ -834,15 +1706,33 @@ impl AdbService {
         debug!(device = ?device.as_ref().map(|d| &d.serial), "Setting new device data");
         *current_device = device.map(Arc::new);
 
-        DeviceChangedEvent { device: device_clone.map(|d| d.into()) }.send_signal_to_dart();
+        self.send_device_changed(device_clone);
+        self.device_changed_tx.send_replace(());
         Ok(true)
     }
 
+    /// Sends the device to Dart with user-defined name overrides applied.
+    fn send_device_changed(&self, device: Option<AdbDevice>) {
+        let device = device.map(|d| {
+            let mut device: SignalAdbDevice = d.into();
+            self.metadata_store
+                .current()
+                .apply_to_installed_packages(&mut device.installed_packages);
+            device
+        });
+        DeviceChangedEvent { device }.send_signal_to_dart();
+    }
+
+    /// Create a receiver notified when the current device changes
+    pub(crate) fn subscribe_device_changes(&self) -> watch::Receiver<()> {
+        self.device_changed_tx.subscribe()
+    }
+
     /// Attempts to get the currently connected device    ///
     /// # Returns
     /// Option containing the current device if one is connected
     #[instrument(level = "debug", skip(self))]
-    async fn try_current_device(&self) -> Option<Arc<AdbDevice>> {
+    pub(crate) async fn try_current_device(&self) -> Option<Arc<AdbDevice>> {
         self.device.read().await.as_ref().map(Arc::clone)
     }
 
@@ -923,7 +1813,12 @@ impl AdbService {
         .await
         .context("Failed to connect to device")?;
 
-        let device = AdbDevice::new(inner_device).await?;
+        let known = self.known_capabilities.read().await.get(&target_device.serial).copied();
+        let device = AdbDevice::new(inner_device, known).await?;
+        self.known_capabilities
+            .write()
+            .await
+            .insert(device.serial.clone(), (device.vendor, device.capabilities));
         let prev = self.try_current_device().await;
 
         // Clean up old APKs (might be leftovers from interrupted installs)
@@ -966,10 +1861,89 @@ impl AdbService {
             _ => {}
         }
 
+        if device.is_wireless {
+            self.remember_wireless_target(&device);
+        }
         self.refresh_adb_state().await;
         Ok(device)
     }
 
+    /// Saves the address of a connected wireless device to reconnect to it later
+    fn remember_wireless_target(&self, device: &AdbDevice) {
+        let mut settings = self.settings_handler.subscribe().borrow().clone();
+        if !known_targets::remember(
+            &mut settings.known_wireless_targets,
+            &device.true_serial,
+            &device.serial,
+        ) {
+            return;
+        }
+        debug!(serial = %device.serial, "Remembering wireless device address");
+        if let Err(e) = self.settings_handler.save_settings(&settings) {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to save wireless device address");
+        }
+    }
+
+    /// Switches to a USB connection of the same headset after its wireless connection dropped.
+    ///
+    /// Returns the USB device when the wireless device no longer responds and the headset with
+    /// the same true serial is connected over USB, `None` if there is nothing to switch to.
+    #[instrument(level = "debug", skip(self, wireless), fields(serial = %wireless.serial))]
+    pub(crate) async fn fall_back_to_usb(&self, wireless: &AdbDevice) -> Option<Arc<AdbDevice>> {
+        // The device monitor may already have switched to USB on its own
+        if let Some(current) = self.try_current_device().await
+            && !current.is_wireless
+            && current.true_serial == wireless.true_serial
+        {
+            return Some(current);
+        }
+
+        if let Ok(Ok(_)) = timeout(Duration::from_secs(3), wireless.shell_checked("echo")).await {
+            debug!("Wireless device still responds, not falling back to USB");
+            return None;
+        }
+
+        let devices = match self.adb_host.devices::<Vec<_>>().await {
+            Ok(devices) => devices,
+            Err(e) => {
+                warn!(error = &e as &dyn Error, "Failed to list devices for USB fallback");
+                return None;
+            }
+        };
+        for entry in devices
+            .into_iter()
+            .filter(|d| d.state == DeviceState::Device && !d.serial.contains(':'))
+        {
+            let Ok(device) =
+                forensic_adb::Device::new(self.adb_host.clone(), entry.serial.clone(), entry.info)
+                    .await
+            else {
+                continue;
+            };
+            if AdbDevice::query_true_serial(&device).await.ok().as_ref()
+                != Some(&wireless.true_serial)
+            {
+                continue;
+            }
+
+            info!(usb_serial = %entry.serial, "Falling back to USB connection");
+            if let Err(e) = self.connect_device(Some(&entry.serial), ConnectionKind::Usb).await {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to connect over USB");
+                return None;
+            }
+            Toast::send(
+                "Switched to USB".to_string(),
+                "Wireless connection was lost, continuing over USB".to_string(),
+                false,
+                None,
+            );
+            return self.try_current_device().await;
+        }
+
+        debug!("No USB connection of the device available");
+        None
+    }
+
     /// Disconnects the current ADB device
     ///
     /// # Arguments
@@ -1033,6 +2007,396 @@ impl AdbService {
         }
     }
 
+    /// Pings the current wireless device so idle connections are not dropped, and dials the most
+    /// recently connected wireless device when no device is connected.
+    ///
+    /// Runs every `KEEPALIVE_INTERVAL` and right after the last device goes away.
+    #[instrument(level = "debug", skip(self))]
+    async fn run_wireless_keepalive(&self) {
+        let mut interval = time::interval(known_targets::KEEPALIVE_INTERVAL);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = self.wireless_reconnect.notified() => {}
+            }
+            if !*self.reconnect_wireless_devices.read().await {
+                continue;
+            }
+
+            if let Some(device) = self.try_current_device().await {
+                if device.is_wireless
+                    && let Err(e) = device.shell_checked("echo").await
+                {
+                    debug!(error = e.as_ref() as &dyn Error, "Wireless keepalive ping failed");
+                }
+                continue;
+            }
+
+            let Ok(devices) = self.adb_host.devices::<Vec<_>>().await else {
+                continue;
+            };
+            if devices.iter().any(|d| d.state == DeviceState::Device) {
+                continue;
+            }
+            let targets = self.settings_handler.subscribe().borrow().known_wireless_targets.clone();
+            let Some(addr) = known_targets::preferred(&targets) else {
+                continue;
+            };
+            debug!(target = %display_target(addr), "No device connected, reconnecting");
+            if let Err(e) = self.try_connect_wireless_adb(addr).await {
+                debug!(
+                    error = e.as_ref() as &dyn Error,
+                    target = %display_target(addr),
+                    "Wireless reconnect failed"
+                );
+            }
+        }
+    }
+
+    /// Samples the battery levels of the current device and its controllers into the battery
+    /// history, sending the updated history of the device after each sample
+    #[instrument(level = "debug", skip(self))]
+    async fn run_battery_sampling(&self) {
+        let mut interval = time::interval(SAMPLE_INTERVAL);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let Some(device) = self.try_current_device().await else {
+                continue;
+            };
+            let (headset, controllers) = match device.battery_levels().await {
+                Ok(levels) => levels,
+                Err(e) => {
+                    debug!(error = e.as_ref() as &dyn Error, "Failed to sample battery levels");
+                    continue;
+                }
+            };
+            let sample = BatterySample {
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0),
+                headset,
+                left_controller: controllers.left.and_then(|c| c.battery_level),
+                right_controller: controllers.right.and_then(|c| c.battery_level),
+            };
+            trace!(serial = %device.serial, ?sample, "Sampled battery levels");
+            let samples = {
+                let mut history = self.battery_history.lock().await;
+                history.record(&device.serial, sample);
+                history.samples(&device.serial)
+            };
+            BatteryHistory { serial: Some(device.serial.clone()), samples }.send_signal_to_dart();
+        }
+    }
+
+    /// Restores temporary device states once their scheduled time is up.
+    ///
+    /// Wakes at the next deadline, when the schedule changes and when the device changes, so
+    /// reverts that came due while their device was away run as soon as it is back.
+    #[instrument(level = "debug", skip(self))]
+    async fn run_auto_reverts(&self) {
+        let mut device_changed = self.subscribe_device_changes();
+        loop {
+            let next = self.auto_reverts.lock().await.next_deadline(Instant::now());
+            tokio::select! {
+                _ = async {
+                    match next {
+                        Some(deadline) => time::sleep_until(deadline).await,
+                        None => std::future::pending().await,
+                    }
+                } => {}
+                _ = self.auto_reverts_changed.notified() => {}
+                _ = device_changed.changed() => {}
+            }
+            self.apply_due_reverts().await;
+        }
+    }
+
+    /// Applies the due reverts of the current device and reports the remaining ones
+    async fn apply_due_reverts(&self) {
+        let Some(device) = self.try_current_device().await else {
+            AutoRevertStatus { pending: Vec::new() }.send_signal_to_dart();
+            return;
+        };
+        let due = self.auto_reverts.lock().await.take_due(&device.true_serial, Instant::now());
+        for kind in &due {
+            let (result, title) = match kind {
+                AutoRevertKind::GuardianPause => {
+                    (device.set_guardian_paused(false).await, "Guardian resumed")
+                }
+                AutoRevertKind::ProximitySensor => {
+                    (device.set_proximity_sensor(true, None).await, "Proximity sensor restored")
+                }
+            };
+            match result {
+                Ok(()) => {
+                    info!(?kind, "Device state reverted automatically");
+                    Toast::send(
+                        title.to_string(),
+                        "The scheduled time is up".to_string(),
+                        false,
+                        None,
+                    );
+                }
+                Err(e) => {
+                    error!(error = e.as_ref() as &dyn Error, ?kind, "Automatic revert failed");
+                    Toast::send(
+                        "Automatic revert failed".to_string(),
+                        format!("{e:#}"),
+                        true,
+                        None,
+                    );
+                }
+            }
+        }
+        let pending = self.auto_reverts.lock().await.countdown(&device.true_serial, Instant::now());
+        AutoRevertStatus { pending }.send_signal_to_dart();
+        if !due.is_empty()
+            && let Err(e) = self.refresh_device().await
+        {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to refresh device after revert");
+        }
+    }
+
+    /// Drops a scheduled revert after the state was set by hand
+    async fn cancel_auto_revert(&self, device: &AdbDevice, kind: AutoRevertKind) {
+        if self.auto_reverts.lock().await.cancel(&device.true_serial, kind) {
+            debug!(?kind, "Scheduled auto-revert superseded");
+            self.auto_reverts_changed.notify_one();
+        }
+    }
+
+    /// Refreshes the device shortly after the device helper reports installed, updated or removed
+    /// packages.
+    ///
+    /// Does nothing while the current device has no helper, the periodic refresh covers it.
+    #[instrument(level = "debug", skip(self))]
+    async fn run_agent_package_watch(&self) {
+        const POLL_INTERVAL: Duration = Duration::from_secs(3);
+        const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+        // Serial and change counter seen last
+        let mut last_seen: Option<(String, u64)> = None;
+        loop {
+            time::sleep(POLL_INTERVAL).await;
+            let Some(device) = self.try_current_device().await else {
+                last_seen = None;
+                continue;
+            };
+            if device.agent_version().is_none() {
+                last_seen = None;
+                continue;
+            }
+
+            match device.agent_package_changes().await {
+                Ok(seq) => {
+                    let changed = last_seen
+                        .as_ref()
+                        .is_some_and(|(serial, last)| *serial == device.serial && *last != seq);
+                    last_seen = Some((device.serial.clone(), seq));
+                    if changed {
+                        debug!(seq, "Device helper reported package changes, refreshing");
+                        if let Err(e) = self.refresh_device().await {
+                            error!(
+                                error = e.as_ref() as &dyn Error,
+                                "Device refresh after package change failed"
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    debug!(
+                        error = e.as_ref() as &dyn Error,
+                        "Device helper did not report package changes, retrying later"
+                    );
+                    last_seen = None;
+                    time::sleep(RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// Patches the package list whenever the on-device watch reports installed, updated or
+    /// removed packages.
+    ///
+    /// Only runs while enabled in the settings and for devices without the helper, which reports
+    /// changes itself. The first watch after connecting only records the current versions.
+    #[instrument(level = "debug", skip(self))]
+    async fn run_device_package_watch(&self) {
+        const IDLE_INTERVAL: Duration = Duration::from_secs(5);
+        const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+        // Serial and package versions seen last
+        let mut last_seen: Option<(String, PackageVersions)> = None;
+        loop {
+            let device = match self.try_current_device().await {
+                Some(device)
+                    if *self.watch_package_changes.read().await
+                        && device.agent_version().is_none() =>
+                {
+                    device
+                }
+                _ => {
+                    last_seen = None;
+                    time::sleep(IDLE_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            match device.wait_for_package_changes().await {
+                Ok(versions) => {
+                    if let Some((serial, previous)) = &last_seen
+                        && *serial == device.serial
+                    {
+                        let changes = package_changes(previous, &versions);
+                        if !changes.is_empty() {
+                            debug!(
+                                ?changes,
+                                "Package watch reported changes, patching package list"
+                            );
+                            if let Err(e) = self.refresh_after_package_change(&changes).await {
+                                error!(
+                                    error = e.as_ref() as &dyn Error,
+                                    "Package list update after package change failed"
+                                );
+                            }
+                        }
+                    }
+                    last_seen = Some((device.serial.clone(), versions));
+                }
+                Err(e) => {
+                    debug!(
+                        error = e.as_ref() as &dyn Error,
+                        "Package watch failed, retrying later"
+                    );
+                    last_seen = None;
+                    time::sleep(RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// Starts waking devices that are offline while nothing is connected, once per offline period
+    async fn wake_offline_devices(self: &Arc<AdbService>, devices: &[DeviceBrief]) {
+        let mut attempted = self.wake_attempted.lock().await;
+        attempted.retain(|serial| {
+            devices.iter().any(|d| &d.serial == serial && d.state == DeviceState::Offline)
+        });
+        if !*self.wake_offline_devices.read().await || self.try_current_device().await.is_some() {
+            return;
+        }
+
+        for device in devices.iter().filter(|d| d.state == DeviceState::Offline) {
+            if !attempted.insert(device.serial.clone()) {
+                continue;
+            }
+            let handle = self.clone();
+            let serial = device.serial.clone();
+            tokio::spawn(
+                async move { handle.wake_device(&serial, true).await.send_signal_to_dart() }
+                    .instrument(info_span!("task_wake_offline_device")),
+            );
+        }
+    }
+
+    /// Tries to bring back a device that is present but offline.
+    ///
+    /// Reconnects offline transports, sends the configured wake action and connects to the device
+    /// once it is online. Every step is attempted and reported, even if an earlier one failed.
+    #[instrument(skip(self))]
+    async fn wake_device(&self, serial: &str, automatic: bool) -> DeviceWakeReport {
+        let action = *self.offline_wake_action.read().await;
+        info!(?action, "Trying to wake offline device");
+        let mut steps = Vec::new();
+
+        steps.push(wake_step(WakeStepKind::Reconnect, self.reconnect_offline_devices().await));
+        let mut online = self.wait_until_online(serial, WAKE_ONLINE_TIMEOUT / 2).await;
+
+        let (kind, command) = match action {
+            OfflineWakeAction::Keyevent => {
+                (WakeStepKind::Keyevent, "input keyevent KEYCODE_WAKEUP")
+            }
+            OfflineWakeAction::ProximityClose => (
+                WakeStepKind::ProximityClose,
+                "am broadcast -a com.oculus.vrpowermanager.prox_close",
+            ),
+        };
+        let wake_result = async {
+            let info = self
+                .get_adb_devices()
+                .await?
+                .into_iter()
+                .find(|d| d.serial == serial)
+                .context("Device is no longer listed")?;
+            let device =
+                forensic_adb::Device::new(self.adb_host.clone(), serial.to_string(), info.info)
+                    .await?;
+            device.execute_host_shell_command(command).await?;
+            Ok(())
+        }
+        .await;
+        steps.push(wake_step(kind, wake_result));
+        if !online {
+            online = self.wait_until_online(serial, WAKE_ONLINE_TIMEOUT).await;
+        }
+
+        let connect_result = if online {
+            let preferred = *self.preferred_connection_type.read().await;
+            self.connect_device(Some(serial), preferred).await.map(|_| ())
+        } else {
+            Err(anyhow!("Device is still offline"))
+        };
+        let recovered = connect_result.is_ok();
+        steps.push(wake_step(WakeStepKind::Connect, connect_result));
+
+        if recovered {
+            info!("Offline device recovered");
+        } else {
+            warn!(?steps, "Failed to wake offline device");
+        }
+        self.refresh_adb_state().await;
+        DeviceWakeReport { serial: serial.to_string(), automatic, steps, recovered }
+    }
+
+    /// Runs `adb reconnect offline`, resetting the transports of all offline devices
+    #[instrument(level = "debug", skip(self), err)]
+    async fn reconnect_offline_devices(&self) -> Result<()> {
+        let adb_path = resolve_binary_path(self.adb_path.read().await.as_deref(), "adb")
+            .context("ADB binary not found")?;
+        let mut command = Command::new(&adb_path);
+        command.args(["reconnect", "offline"]);
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        let output = timeout(Duration::from_secs(10), command.output())
+            .await
+            .context("Timed out while reconnecting offline devices")?
+            .context("Failed to run adb reconnect")?;
+        ensure!(
+            output.status.success(),
+            "adb reconnect failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        Ok(())
+    }
+
+    /// Polls the device list until `serial` is online, returning whether it came online in time
+    async fn wait_until_online(&self, serial: &str, max_wait: Duration) -> bool {
+        let deadline = time::Instant::now() + max_wait;
+        loop {
+            if let Ok(devices) = self.get_adb_devices().await
+                && devices.iter().any(|d| d.serial == serial && d.state == DeviceState::Device)
+            {
+                return true;
+            }
+            if time::Instant::now() >= deadline {
+                return false;
+            }
+            time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
     /// Browses for ADB-over-Wi‑Fi services via mDNS and attempts ADB `connect`.
     #[instrument(level = "debug", skip(self), err)]
     async fn run_mdns_auto_connect(self: Arc<AdbService>) -> Result<()> {
@@ -1177,21 +2541,71 @@ impl AdbService {
         }
     }
 
+    /// Installs or upgrades the device helper on the current device if it is enabled.
+    ///
+    /// Failures are only logged, everything keeps working through shell commands without it.
+    #[instrument(level = "debug", skip(self))]
+    async fn sync_device_agent(&self) {
+        if !*self.device_agent_enabled.read().await {
+            return;
+        }
+        let result = async {
+            let Some(device) = self.try_current_device().await else {
+                return Ok(());
+            };
+            let apk = tokio::task::spawn_blocking(AgentApk::bundled).await??;
+            if device.install_agent(&apk).await? {
+                info!(version = apk.version_code, "Device helper installed");
+                self.refresh_device().await?;
+            }
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+        if let Err(e) = result {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to set up device helper");
+        }
+    }
+
     /// Refreshes the currently connected device
-    #[instrument(level = "debug", skip(self), fields(serial), err)]
     pub(crate) async fn refresh_device(&self) -> Result<()> {
+        self.refresh_device_scope(RefreshScope::Full).await
+    }
+
+    /// Refreshes the parts of the current device info in `scope`
+    #[instrument(level = "debug", skip(self), fields(serial), err)]
+    pub(crate) async fn refresh_device_scope(&self, scope: RefreshScope) -> Result<()> {
         let device = self.current_device().await?;
         Span::current().record("serial", &device.serial);
         debug!("Refreshing device data");
         let mut device_clone = (*device).clone();
-        device_clone.refresh().boxed().await?;
+        device_clone.refresh_scope(scope).boxed().await?;
 
         let _ = self.set_device(Some(device_clone), Some(&device.serial)).await?;
         debug!("Device data refreshed successfully");
         Ok(())
     }
 
-    /// Installs an APK on the currently connected device
+    /// Updates the current device after packages were installed, changed or removed.
+    ///
+    /// Known changes patch the package list, with no `changes` the list is fetched again. Free
+    /// space is refreshed either way.
+    #[instrument(level = "debug", skip(self), fields(serial), err)]
+    async fn refresh_after_package_change(&self, changes: &[PackageChange<'_>]) -> Result<()> {
+        let device = self.current_device().await?;
+        Span::current().record("serial", &device.serial);
+        let mut device_clone = (*device).clone();
+        if changes.is_empty() {
+            device_clone.refresh_scope(RefreshScope::Packages).boxed().await?;
+            device_clone.refresh_scope(RefreshScope::Space).await?;
+        } else {
+            device_clone.apply_package_changes(changes).boxed().await?;
+        }
+
+        let _ = self.set_device(Some(device_clone), Some(&device.serial)).await?;
+        Ok(())
+    }
+
+    /// Installs an APK on the currently connected device, returning its package if it can be read
     #[instrument(level = "debug", skip(self, progress_sender))]
     pub(crate) async fn install_apk(
         &self,
@@ -1200,7 +2614,7 @@ impl AdbService {
         backups_location: std::path::PathBuf,
         progress_sender: UnboundedSender<SideloadProgress>,
         auto_reinstall_on_conflict: bool,
-    ) -> Result<()> {
+    ) -> Result<Option<String>> {
         let result = device
             .install_apk_with_progress(
                 apk_path,
@@ -1210,8 +2624,27 @@ impl AdbService {
                 auto_reinstall_on_conflict,
             )
             .await;
-        self.refresh_device().await?;
-        result
+        let package = match &result {
+            Ok(()) => get_apk_info(apk_path).ok().map(|info| info.package_name),
+            Err(_) => None,
+        };
+        let change = package.as_deref().map(PackageChange::Installed);
+        self.refresh_after_package_change(change.as_slice()).await?;
+        result.map(|()| package)
+    }
+
+    /// Launches a package and checks that it is still running after `settle`, then stops it
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn check_launch(&self, package: &PackageName, settle: Duration) -> Result<()> {
+        let device = self.current_device().await?;
+        device.launch(package).await?;
+        tokio::time::sleep(settle).await;
+        let running = device.is_running(package).await;
+        if let Err(e) = device.force_stop(package).await {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to stop app after launch check");
+        }
+        ensure!(running?, "{package} exited right after launch");
+        Ok(())
     }
 
     /// Uninstalls a package from the currently connected device
@@ -1222,12 +2655,18 @@ impl AdbService {
         package: &PackageName,
     ) -> Result<()> {
         let result = device.uninstall_package(package).await;
-        self.refresh_device().await?;
+        let change = result.is_ok().then(|| PackageChange::Uninstalled(package.as_str()));
+        self.refresh_after_package_change(change.as_slice()).await?;
         result
     }
 
-    /// Sideloads an app by installing its APK and pushing OBB data if present
-    #[instrument(level = "debug", skip(self, progress_sender))]
+    /// Sideloads an app by installing its APK and pushing OBB data if present.
+    ///
+    /// An install script with exactly the `approved_script` operations runs without asking
+    /// again, other scripts are reviewed when they are reached. Returns the installed package,
+    /// None for apps installed by an install script.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "debug", skip(self, progress_sender, approved_script))]
     pub(crate) async fn sideload_app(
         &self,
         device: &AdbDevice,
@@ -1235,18 +2674,40 @@ impl AdbService {
         backups_location: std::path::PathBuf,
         progress_sender: UnboundedSender<SideloadProgress>,
         token: CancellationToken,
+        approved_script: Option<Vec<ScriptOperation>>,
         auto_reinstall_on_conflict: bool,
-    ) -> Result<()> {
+    ) -> Result<Option<String>> {
+        let approve_script = |script_path, operations: Vec<ScriptOperation>| {
+            if approved_script.as_ref() == Some(&operations) {
+                return future::ready(true).boxed();
+            }
+            self.review_install_script(script_path, operations).boxed()
+        };
         let result = device
             .sideload_app(
                 app_path,
                 &backups_location,
                 progress_sender,
                 token,
+                &approve_script,
                 auto_reinstall_on_conflict,
             )
             .await;
-        self.refresh_device().await?;
+        self.refresh_after_package_change(&[]).await?;
+        result
+    }
+
+    /// Pushes only the OBB files of an app to the currently connected device
+    #[instrument(level = "debug", skip(self, progress_sender))]
+    pub(crate) async fn push_obb(
+        &self,
+        device: &AdbDevice,
+        package: &PackageName,
+        local_dir: &Path,
+        progress_sender: UnboundedSender<SideloadProgress>,
+    ) -> Result<()> {
+        let result = device.push_obb(package, local_dir, progress_sender).await;
+        self.refresh_device_scope(RefreshScope::Space).await?;
         result
     }
 
@@ -1264,18 +2725,62 @@ impl AdbService {
         device.backup_app(package, display_name, backups_location, options, token).await
     }
 
-    /// Restores a backup to the currently connected device
+    /// Restores a backup to the currently connected device.
+    ///
+    /// A restore that had to skip private data is reported to the user as partial.
     #[instrument(level = "debug", skip(self))]
     pub(crate) async fn restore_backup(
         &self,
         device: &AdbDevice,
         backup_path: &Path,
-    ) -> Result<()> {
-        let result = device.restore_backup(backup_path).await;
-        self.refresh_device().await?;
+        target_package: Option<&PackageName>,
+    ) -> Result<RestoreOutcome> {
+        let result = device.restore_backup(backup_path, target_package).await;
+        if let Ok(outcome) = &result {
+            let name = backup_path.file_name().unwrap_or(backup_path.as_os_str());
+            let mut text = match target_package {
+                Some(target) => format!("Restored backup {} into {target}", name.to_string_lossy()),
+                None => format!("Restored backup {}", name.to_string_lossy()),
+            };
+            if outcome.skipped_private_data {
+                text.push_str(" without private data");
+                Toast::send(
+                    format!("Partially restored {}", name.to_string_lossy()),
+                    "Private app data was not restored because run-as is not available on this \
+                     device"
+                        .to_string(),
+                    true,
+                    None,
+                );
+            }
+            self.device_log.record_event(&device.true_serial, DeviceLogEntryKind::Restore, text);
+        }
+        self.refresh_after_package_change(&[]).await?;
         result
     }
 
+    /// Pulls the current contents of mod paths on the device into a mod set directory
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn capture_mod_set(
+        &self,
+        device: &AdbDevice,
+        paths: &[String],
+        set_dir: &Path,
+    ) -> Result<()> {
+        device.capture_mod_paths(paths, set_dir).await
+    }
+
+    /// Replaces the contents of mod paths on the device with a mod set
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn apply_mod_set(
+        &self,
+        device: &AdbDevice,
+        paths: &[String],
+        set_dir: &Path,
+    ) -> Result<()> {
+        device.apply_mod_paths(paths, set_dir).await
+    }
+
     /// Pulls an application's APK and OBB (if present) into a local directory suitable for donation.
     ///
     /// Layout:
@@ -1378,6 +2883,32 @@ impl AdbService {
         Ok(())
     }
 
+    /// Whether the device tracker is receiving updates and the ADB server still responds.
+    ///
+    /// A server start in progress counts as healthy, it is not a stall. So does a server that
+    /// is slow to answer while the tracker still runs, it is busy with transfers rather than
+    /// stalled.
+    #[instrument(skip(self), level = "debug", ret)]
+    pub(crate) async fn is_healthy(&self) -> bool {
+        if *self.adb_state.read().await == AdbState::ServerStarting {
+            return true;
+        }
+        if self.tracker_receiving.load(Ordering::Relaxed) == 0 {
+            return false;
+        }
+        match timeout(HEALTH_PING_TIMEOUT, self.adb_host.check_host_running()).await {
+            Ok(Ok(_)) => true,
+            Ok(Err(e)) => {
+                warn!(error = &e as &dyn Error, "ADB server health check failed");
+                false
+            }
+            Err(_) => {
+                debug!("ADB server is slow to answer the health check, treating it as busy");
+                true
+            }
+        }
+    }
+
     /// Checks if the ADB server is running
     #[instrument(skip(self), level = "debug", ret)]
     async fn is_server_running(&self) -> bool {
@@ -1484,8 +3015,35 @@ impl AdbService {
         }
     }
 
+    /// Sends the ADB state, the devices list and the current device to Dart again
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) async fn resend_state(&self) {
+        self.adb_state.read().await.clone().send_signal_to_dart();
+        let devices = if self.is_server_running().await {
+            self.get_adb_devices().await.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        self.emit_devices_list(&devices).await;
+        let device = self.try_current_device().await.map(|device| (*device).clone());
+        self.send_device_changed(device);
+    }
+
+    /// Lists the devices known to the ADB server, starting it if needed
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn list_devices(&self) -> Result<Vec<AdbDeviceBrief>> {
+        self.ensure_server_running().await?;
+        let devices = self.get_adb_devices().await?;
+        Ok(self.device_briefs(&devices).await)
+    }
+
     /// Emits the AdbDevicesList signal using the provided devices and cached data
     async fn emit_devices_list(&self, devices: &[DeviceInfo]) {
+        AdbDevicesList { value: self.device_briefs(devices).await }.send_signal_to_dart();
+    }
+
+    /// Describes the devices using cached names of devices seen before
+    async fn device_briefs(&self, devices: &[DeviceInfo]) -> Vec<AdbDeviceBrief> {
         let current = self.try_current_device().await;
         if let Some(dev) = &current
             && let Some(dev_name) = dev.name.as_ref()
@@ -1497,7 +3055,7 @@ impl AdbService {
         }
 
         let cache = self.device_data_cache.read().await;
-        let list = devices
+        devices
             .iter()
             .map(|d| {
                 let cached = d.info.get("transport_id").and_then(|s| cache.get(s));
@@ -1509,8 +3067,7 @@ impl AdbService {
                     true_serial: cached.map(|d| d.true_serial.clone()),
                 }
             })
-            .collect();
-        AdbDevicesList { value: list }.send_signal_to_dart();
+            .collect()
     }
 
     /// Resolves and caches device data for ready devices missing entries, then re-emits list
@@ -1562,6 +3119,22 @@ impl AdbService {
 }
 
 /// Formats wireless ADB target address for logging
+/// Clears the receiving device tracker run when that run ends
+struct TrackerRun<'a> {
+    receiving: &'a AtomicU64,
+    run: u64,
+}
+
+impl Drop for TrackerRun<'_> {
+    fn drop(&mut self) {
+        let _ = self.receiving.compare_exchange(self.run, 0, Ordering::Relaxed, Ordering::Relaxed);
+    }
+}
+
+fn wake_step(kind: WakeStepKind, result: Result<()>) -> WakeStep {
+    WakeStep { kind, success: result.is_ok(), error: result.err().map(|e| format!("{e:#}")) }
+}
+
 fn display_target(addr: SocketAddr) -> String {
     match addr {
         SocketAddr::V4(_) => format!("{}:{}", addr.ip(), addr.port()),
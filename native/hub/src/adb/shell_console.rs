@@ -0,0 +1,381 @@
+//! Interactive shell sessions on the connected device, covering device actions that have no
+//! dedicated command.
+//!
+//! Each session is an `adb shell` process. Commands are written to its input one at a time, each
+//! followed by an `echo` of a marker with the command's exit code, which tells when it finished.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    process::Stdio,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::{Context, Result, ensure};
+use rinf::{DartSignal, RustSignal};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Split},
+    process::{Child, ChildStderr, ChildStdin, ChildStdout, Command},
+    sync::{mpsc, watch},
+    time::{self, Instant},
+};
+use tracing::{Instrument, debug, info, info_span, warn};
+
+use crate::{
+    adb::AdbService,
+    models::{
+        Settings,
+        signals::adb::shell::{
+            CloseShellSessionRequest, OpenShellSessionRequest, ShellCommandFinished,
+            ShellCommandRequest, ShellOutputLine, ShellOutputStream, ShellSessionClosed,
+            ShellSessionEndReason, ShellSessionOpened,
+        },
+    },
+};
+
+const MAX_SESSIONS: usize = 4;
+const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_COMMAND_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+/// Sessions without a running command are closed after this long
+const IDLE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+/// Printed after each command as `<marker><command id>:<exit code>`
+const DONE_MARKER: &str = "__YAAS_SHELL_DONE__";
+
+enum SessionInput {
+    Command(ShellCommandRequest),
+    Close,
+}
+
+/// Handles shell session requests from Dart
+pub(crate) struct ShellConsole {
+    adb_service: Arc<AdbService>,
+    settings: watch::Receiver<Settings>,
+    /// Input channels of open sessions by session ID
+    sessions: std::sync::Mutex<HashMap<u32, mpsc::UnboundedSender<SessionInput>>>,
+    next_session_id: AtomicU32,
+}
+
+impl ShellConsole {
+    pub(crate) fn start(adb_service: Arc<AdbService>, settings: watch::Receiver<Settings>) {
+        let console = Arc::new(Self {
+            adb_service,
+            settings,
+            sessions: Default::default(),
+            next_session_id: AtomicU32::new(1),
+        });
+        tokio::spawn(console.receive_signals().instrument(info_span!("task_shell_console")));
+    }
+
+    async fn receive_signals(self: Arc<Self>) {
+        let open_receiver = OpenShellSessionRequest::get_dart_signal_receiver();
+        let command_receiver = ShellCommandRequest::get_dart_signal_receiver();
+        let close_receiver = CloseShellSessionRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = open_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("OpenShellSessionRequest receiver closed");
+                    };
+                    let request_key = request.message.request_key;
+                    let response = match self.clone().open_session().await {
+                        Ok((session_id, serial)) => ShellSessionOpened {
+                            request_key,
+                            session_id: Some(session_id),
+                            device_serial: Some(serial),
+                            error: None,
+                        },
+                        Err(e) => {
+                            warn!(error = e.as_ref() as &dyn Error, "Failed to open shell session");
+                            ShellSessionOpened {
+                                request_key,
+                                session_id: None,
+                                device_serial: None,
+                                error: Some(format!("{e:#}")),
+                            }
+                        }
+                    };
+                    response.send_signal_to_dart();
+                }
+                request = command_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("ShellCommandRequest receiver closed");
+                    };
+                    let session_id = request.message.session_id;
+                    self.send_input(session_id, SessionInput::Command(request.message));
+                }
+                request = close_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("CloseShellSessionRequest receiver closed");
+                    };
+                    self.send_input(request.message.session_id, SessionInput::Close);
+                }
+            }
+        }
+    }
+
+    fn sessions(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<u32, mpsc::UnboundedSender<SessionInput>>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Passes input to a session, telling the UI the session is gone if it does not exist
+    fn send_input(&self, session_id: u32, input: SessionInput) {
+        let sent = self.sessions().get(&session_id).is_some_and(|tx| tx.send(input).is_ok());
+        if !sent {
+            debug!(session_id, "Shell session not found");
+            ShellSessionClosed {
+                session_id,
+                reason: ShellSessionEndReason::Closed,
+                exit_code: None,
+            }
+            .send_signal_to_dart();
+        }
+    }
+
+    /// Starts `adb shell` on the current device, returning the session ID and device serial
+    async fn open_session(self: Arc<Self>) -> Result<(u32, String)> {
+        ensure!(self.settings.borrow().shell_console_enabled, "Shell console is disabled");
+        ensure!(self.sessions().len() < MAX_SESSIONS, "Too many open shell sessions");
+        let device = self.adb_service.current_device().await?;
+        let adb_path = self.adb_service.adb_binary_path().await.context("ADB binary not found")?;
+
+        let mut command = Command::new(&adb_path);
+        command
+            .args(["-s", &device.serial, "shell"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        let mut child = command.spawn().context("Failed to start adb shell")?;
+        let stdin = child.stdin.take().context("Shell input not available")?;
+        let stdout = child.stdout.take().context("Shell output not available")?;
+        let stderr = child.stderr.take().context("Shell error output not available")?;
+
+        let session_id = self.next_session_id.fetch_add(1, Ordering::Relaxed);
+        let (input_tx, input_rx) = mpsc::unbounded_channel();
+        self.sessions().insert(session_id, input_tx);
+        info!(session_id, serial = device.serial, "Shell session opened");
+
+        let session =
+            Session { id: session_id, child, stdin, queue: VecDeque::new(), running: None };
+        let settings = self.settings.clone();
+        tokio::spawn(
+            async move {
+                let (reason, exit_code) = session
+                    .run(
+                        BufReader::new(stdout).split(b'\n'),
+                        BufReader::new(stderr).split(b'\n'),
+                        input_rx,
+                        settings,
+                    )
+                    .await;
+                self.sessions().remove(&session_id);
+                info!(?reason, exit_code, "Shell session closed");
+                ShellSessionClosed { session_id, reason, exit_code }.send_signal_to_dart();
+            }
+            .instrument(info_span!("shell_session", session_id)),
+        );
+        Ok((session_id, device.serial.clone()))
+    }
+}
+
+struct Session {
+    id: u32,
+    child: Child,
+    stdin: ChildStdin,
+    /// Commands waiting for the running one to finish
+    queue: VecDeque<ShellCommandRequest>,
+    /// Running command and when it times out
+    running: Option<(u32, Instant)>,
+}
+
+impl Session {
+    /// Runs commands and forwards output until the session ends, returning why it ended
+    async fn run(
+        mut self,
+        mut stdout: Split<BufReader<ChildStdout>>,
+        mut stderr: Split<BufReader<ChildStderr>>,
+        mut input_rx: mpsc::UnboundedReceiver<SessionInput>,
+        mut settings: watch::Receiver<Settings>,
+    ) -> (ShellSessionEndReason, Option<i32>) {
+        let mut stderr_open = true;
+        let mut idle_deadline = Instant::now() + IDLE_TIMEOUT;
+        loop {
+            let deadline = self.running.map_or(idle_deadline, |(_, deadline)| deadline);
+            let reason = tokio::select! {
+                input = input_rx.recv() => match input {
+                    Some(SessionInput::Command(request)) => {
+                        self.queue.push_back(request);
+                        match self.start_next().await {
+                            Ok(()) => continue,
+                            Err(e) => {
+                                warn!(error = e.as_ref() as &dyn Error, "Failed to write to shell");
+                                ShellSessionEndReason::Exited
+                            }
+                        }
+                    }
+                    Some(SessionInput::Close) | None => ShellSessionEndReason::Closed,
+                },
+                segment = stdout.next_segment() => match segment {
+                    Ok(Some(bytes)) => {
+                        if !self.handle_stdout(&bytes) {
+                            continue;
+                        }
+                        idle_deadline = Instant::now() + IDLE_TIMEOUT;
+                        match self.start_next().await {
+                            Ok(()) => continue,
+                            Err(e) => {
+                                warn!(error = e.as_ref() as &dyn Error, "Failed to write to shell");
+                                ShellSessionEndReason::Exited
+                            }
+                        }
+                    }
+                    Ok(None) | Err(_) => ShellSessionEndReason::Exited,
+                },
+                segment = stderr.next_segment(), if stderr_open => {
+                    match segment {
+                        Ok(Some(bytes)) => self.send_line(ShellOutputStream::Stderr, &bytes),
+                        Ok(None) | Err(_) => stderr_open = false,
+                    }
+                    continue;
+                }
+                _ = time::sleep_until(deadline) => {
+                    if self.running.is_some() {
+                        ShellSessionEndReason::CommandTimedOut
+                    } else {
+                        ShellSessionEndReason::Idle
+                    }
+                }
+                changed = settings.changed() => {
+                    if changed.is_ok() && settings.borrow_and_update().shell_console_enabled {
+                        continue;
+                    }
+                    ShellSessionEndReason::Disabled
+                }
+            };
+            return self.end(reason).await;
+        }
+    }
+
+    /// Writes the next queued command to the shell if none is running
+    async fn start_next(&mut self) -> Result<()> {
+        if self.running.is_some() {
+            return Ok(());
+        }
+        let Some(request) = self.queue.pop_front() else {
+            return Ok(());
+        };
+        let timeout = request
+            .timeout_secs
+            .map_or(DEFAULT_COMMAND_TIMEOUT, |secs| Duration::from_secs(u64::from(secs.max(1))))
+            .min(MAX_COMMAND_TIMEOUT);
+        debug!(command_id = request.command_id, command = request.command, "Running shell command");
+        self.stdin
+            .write_all(shell_input(request.command_id, &request.command).as_bytes())
+            .await
+            .context("Failed to send command to shell")?;
+        self.stdin.flush().await.context("Failed to send command to shell")?;
+        self.running = Some((request.command_id, Instant::now() + timeout));
+        Ok(())
+    }
+
+    /// Forwards a line of standard output, returning whether it finished the running command
+    fn handle_stdout(&mut self, bytes: &[u8]) -> bool {
+        let line = String::from_utf8_lossy(bytes);
+        let (output, done) = split_done_marker(line.trim_end_matches(['\r', '\n']));
+        if let (Some((command_id, exit_code)), Some((running_id, _))) = (done, self.running)
+            && command_id == running_id
+        {
+            if let Some(output) = output {
+                self.send_line(ShellOutputStream::Stdout, output.as_bytes());
+            }
+            debug!(command_id, exit_code, "Shell command finished");
+            ShellCommandFinished { session_id: self.id, command_id, exit_code }
+                .send_signal_to_dart();
+            self.running = None;
+            return true;
+        }
+        self.send_line(ShellOutputStream::Stdout, bytes);
+        false
+    }
+
+    fn send_line(&self, stream: ShellOutputStream, bytes: &[u8]) {
+        ShellOutputLine {
+            session_id: self.id,
+            command_id: self.running.map(|(command_id, _)| command_id),
+            stream,
+            line: String::from_utf8_lossy(bytes).trim_end_matches(['\r', '\n']).to_string(),
+        }
+        .send_signal_to_dart();
+    }
+
+    /// Stops the shell, returning the end reason with the shell's exit code if it exited
+    async fn end(mut self, reason: ShellSessionEndReason) -> (ShellSessionEndReason, Option<i32>) {
+        if reason != ShellSessionEndReason::Exited {
+            let _ = self.child.start_kill();
+        }
+        let status = time::timeout(Duration::from_secs(2), self.child.wait()).await;
+        let exit_code = match (reason, status) {
+            (ShellSessionEndReason::Exited, Ok(Ok(status))) => status.code(),
+            _ => None,
+        };
+        (reason, exit_code)
+    }
+}
+
+/// Shell input running `command` and printing the done marker after it
+fn shell_input(command_id: u32, command: &str) -> String {
+    format!("{}\necho \"{DONE_MARKER}{command_id}:$?\"\n", command.trim_end())
+}
+
+/// Splits a line of shell output into output text and a done marker's command ID and exit code.
+///
+/// The marker ends up on the same line as output of commands that do not end it with a newline.
+fn split_done_marker(line: &str) -> (Option<&str>, Option<(u32, i32)>) {
+    let Some(start) = line.find(DONE_MARKER) else {
+        return (Some(line), None);
+    };
+    let (output, marker) = line.split_at(start);
+    let done = marker[DONE_MARKER.len()..]
+        .split_once(':')
+        .and_then(|(id, code)| Some((id.parse().ok()?, code.parse().ok()?)));
+    match done {
+        Some(done) => ((!output.is_empty()).then_some(output), Some(done)),
+        None => (Some(line), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_followed_by_the_done_marker() {
+        assert_eq!(
+            shell_input(3, "pm list packages \n"),
+            "pm list packages\necho \"__YAAS_SHELL_DONE__3:$?\"\n"
+        );
+    }
+
+    #[test]
+    fn done_markers_are_split_from_output() {
+        assert_eq!(split_done_marker("package:com.example"), (Some("package:com.example"), None));
+        assert_eq!(split_done_marker("__YAAS_SHELL_DONE__3:0"), (None, Some((3, 0))));
+        assert_eq!(
+            split_done_marker("no newline__YAAS_SHELL_DONE__4:127"),
+            (Some("no newline"), Some((4, 127)))
+        );
+        assert_eq!(
+            split_done_marker("__YAAS_SHELL_DONE__x:0"),
+            (Some("__YAAS_SHELL_DONE__x:0"), None)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Watchdog restarting the ADB server and device tracker when they stop responding.
+//!
+//! The device tracker gives up when its stream fails before the first update, and a dead server
+//! leaves it waiting forever, so devices would silently stop updating without this.
+
+use std::{
+    error::Error,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use rinf::RustSignal;
+use tokio::{
+    sync::watch,
+    time::{self, Instant},
+};
+use tracing::{Instrument, info, info_span, warn};
+
+use crate::{
+    adb::AdbService,
+    models::signals::adb::health::{AdbHealth, AdbHealthChanged},
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// Consecutive failed checks after which the ADB server is restarted
+const FAILED_CHECKS_BEFORE_RECOVERY: u32 = 2;
+const BASE_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Last status, kept to resend it to a reconnecting UI
+static LAST_STATUS: LazyLock<watch::Sender<AdbHealthChanged>> =
+    LazyLock::new(|| watch::Sender::new(AdbHealthChanged::default()));
+
+/// Checks the ADB server and device tracker periodically and restarts them when stalled
+pub(crate) struct AdbWatchdog {
+    adb_service: Arc<AdbService>,
+}
+
+impl AdbWatchdog {
+    pub(crate) fn start(adb_service: Arc<AdbService>) {
+        let watchdog = Self { adb_service };
+        tokio::spawn(watchdog.run().instrument(info_span!("task_adb_watchdog")));
+    }
+
+    /// Sends the last status again
+    pub(crate) fn resend() {
+        LAST_STATUS.borrow().clone().send_signal_to_dart();
+    }
+
+    async fn run(self) {
+        let mut failed_checks = 0;
+        let mut attempts = 0;
+        let mut next_attempt = Instant::now();
+        loop {
+            time::sleep(CHECK_INTERVAL).await;
+            if self.adb_service.is_healthy().await {
+                if attempts > 0 {
+                    info!(attempts, "ADB server recovered");
+                }
+                failed_checks = 0;
+                attempts = 0;
+                report(AdbHealthChanged::default());
+                continue;
+            }
+
+            failed_checks += 1;
+            if failed_checks < FAILED_CHECKS_BEFORE_RECOVERY {
+                report(AdbHealthChanged {
+                    health: AdbHealth::Unresponsive,
+                    recovery_attempts: attempts,
+                    ..Default::default()
+                });
+                continue;
+            }
+            if Instant::now() < next_attempt {
+                continue;
+            }
+            // Restarting would only repeat the same error toast
+            if self.adb_service.adb_binary_path().await.is_none() {
+                report(AdbHealthChanged {
+                    health: AdbHealth::RecoveryFailed,
+                    recovery_attempts: attempts,
+                    retry_in_secs: None,
+                    error: Some("ADB binary not found".to_string()),
+                });
+                continue;
+            }
+
+            attempts += 1;
+            let delay = backoff(attempts);
+            next_attempt = Instant::now() + delay;
+            warn!(attempts, "ADB server or device tracker stopped responding, restarting");
+            report(AdbHealthChanged {
+                health: AdbHealth::Recovering,
+                recovery_attempts: attempts,
+                ..Default::default()
+            });
+            if let Err(e) = self.adb_service.clone().restart_adb().await {
+                warn!(
+                    error = e.as_ref() as &dyn Error,
+                    retry_in = ?delay,
+                    "Failed to recover ADB server"
+                );
+                report(AdbHealthChanged {
+                    health: AdbHealth::RecoveryFailed,
+                    recovery_attempts: attempts,
+                    retry_in_secs: Some(delay.as_secs() as u32),
+                    error: Some(format!("{e:#}")),
+                });
+            }
+        }
+    }
+}
+
+/// Sends `status` to the UI if it differs from the last one
+fn report(status: AdbHealthChanged) {
+    LAST_STATUS.send_if_modified(|last| {
+        if *last == status {
+            return false;
+        }
+        *last = status.clone();
+        status.send_signal_to_dart();
+        true
+    });
+}
+
+/// Time to wait after the `attempt`-th restart before trying again, doubling up to `MAX_BACKOFF`
+fn backoff(attempt: u32) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(16);
+    BASE_BACKOFF.saturating_mul(factor).min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_limit() {
+        assert_eq!(backoff(1), Duration::from_secs(5));
+        assert_eq!(backoff(2), Duration::from_secs(10));
+        assert_eq!(backoff(4), Duration::from_secs(40));
+        assert_eq!(backoff(7), MAX_BACKOFF);
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+}
@@ -0,0 +1,90 @@
+use rinf::{DartSignal, RustSignal};
+use tracing::{debug, info, instrument, warn};
+
+use crate::models::signals::{
+    system::{ApiHandshakeRequest, BackendCapabilities},
+    task::TaskKind,
+};
+
+/// Signal API version of the backend.
+///
+/// Bumped whenever existing signals change in a way older UIs cannot decode. New signals and
+/// task kinds do not need a bump.
+pub(crate) const API_VERSION: u32 = 3;
+
+/// Oldest UI signal API version the backend can talk to.
+///
+/// Kept in sync with `uiApiVersion` and `minCoreApiVersion` in `lib/providers/app_state.dart`.
+pub(crate) const MIN_UI_API_VERSION: u32 = 3;
+
+/// Builds the capabilities message, checking the UI version if the UI sent one.
+pub(crate) fn capabilities(ui_api_version: Option<u32>) -> BackendCapabilities {
+    BackendCapabilities {
+        api_version: API_VERSION,
+        min_ui_api_version: MIN_UI_API_VERSION,
+        ui_supported: ui_api_version.map(|version| version >= MIN_UI_API_VERSION),
+        task_kinds: TaskKind::ALL.iter().map(|kind| format!("{kind:?}")).collect(),
+    }
+}
+
+/// Announces backend capabilities and starts answering handshake requests from Dart.
+pub(crate) fn start() {
+    capabilities(None).send_signal_to_dart();
+    tokio::spawn(receive_handshakes());
+}
+
+#[instrument(level = "debug")]
+async fn receive_handshakes() {
+    let receiver = ApiHandshakeRequest::get_dart_signal_receiver();
+    loop {
+        let Some(request) = receiver.recv().await else {
+            panic!("ApiHandshakeRequest receiver closed");
+        };
+        let ui_api_version = request.message.ui_api_version;
+        debug!(ui_api_version, "Received ApiHandshakeRequest");
+        let response = capabilities(Some(ui_api_version));
+        if response.ui_supported == Some(false) {
+            warn!(
+                ui_api_version,
+                min_ui_api_version = MIN_UI_API_VERSION,
+                "UI signal API version is too old for this backend"
+            );
+        } else {
+            info!(ui_api_version, api_version = API_VERSION, "Completed API handshake");
+        }
+        response.send_signal_to_dart();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capabilities_report_ui_support() {
+        assert_eq!(capabilities(None).ui_supported, None);
+        assert_eq!(capabilities(Some(MIN_UI_API_VERSION)).ui_supported, Some(true));
+        assert_eq!(capabilities(Some(MIN_UI_API_VERSION - 1)).ui_supported, Some(false));
+
+        let caps = capabilities(None);
+        assert_eq!(caps.task_kinds.len(), TaskKind::ALL.len());
+        assert!(caps.task_kinds.iter().any(|kind| kind == "ApplyModSet"));
+    }
+
+    #[test]
+    fn api_versions_match_the_ui() {
+        let app_state = include_str!("../../../lib/providers/app_state.dart");
+        let dart_const = |name: &str| -> u32 {
+            let prefix = format!("static const int {name} = ");
+            app_state
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(prefix.as_str()))
+                .and_then(|value| value.trim_end_matches(';').parse().ok())
+                .unwrap_or_else(|| panic!("{name} not found in app_state.dart"))
+        };
+        let ui_api_version = dart_const("uiApiVersion");
+        assert_eq!(ui_api_version, API_VERSION);
+        assert!(ui_api_version >= MIN_UI_API_VERSION);
+        assert!(dart_const("minCoreApiVersion") <= API_VERSION);
+    }
+}
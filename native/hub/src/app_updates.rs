@@ -0,0 +1,334 @@
+use std::{collections::HashMap, error::Error, sync::Arc, time::Duration};
+
+use rinf::{DartSignal, RustSignal};
+use tokio::sync::watch;
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    adb::AdbService,
+    downloader::manager::DownloaderManager,
+    metadata_store::MetadataStore,
+    models::{
+        AppMetadata, CloudApp, InstalledPackage, normalize_package_name,
+        signals::{
+            cloud_apps::updates::{GetUpdatableAppsRequest, UpdatableApp, UpdatableApps},
+            system::Toast,
+        },
+    },
+};
+
+/// Key used to match installed packages with catalog entries.
+///
+/// Catalog releases are sometimes published under renamed or differently cased package names,
+/// so both sides are reduced to the original lowercase name.
+fn match_key(package_name: &str) -> String {
+    normalize_package_name(package_name).to_lowercase()
+}
+
+/// Finds installed packages with newer catalog versions that are not blocked by version pins.
+///
+/// If several catalog releases match a package, the newest allowed one is picked, preferring the
+/// release with the exact installed package name between equal versions.
+pub(crate) fn find_updatable_apps(
+    installed: &[InstalledPackage],
+    catalog: &[CloudApp],
+    metadata: &AppMetadata,
+) -> Vec<UpdatableApp> {
+    let mut by_package: HashMap<String, Vec<&CloudApp>> = HashMap::new();
+    for app in catalog {
+        by_package.entry(match_key(&app.true_package_name)).or_default().push(app);
+    }
+
+    let mut updatable: Vec<UpdatableApp> = installed
+        .iter()
+        .filter_map(|package| {
+            let package_name = package.package_name();
+            let installed_version_code = package.version_code();
+            let newest = by_package
+                .get(&match_key(package_name))?
+                .iter()
+                .filter(|app| {
+                    let version_code = u64::from(app.version_code);
+                    version_code > installed_version_code
+                        && metadata.is_update_allowed(package_name, version_code)
+                })
+                .max_by_key(|app| (app.version_code, app.package_name == package_name))?;
+            Some(UpdatableApp {
+                package_name: package_name.to_string(),
+                installed_version_code,
+                full_name: newest.full_name.clone(),
+                app_name: newest.app_name.clone(),
+                version_code: newest.version_code.into(),
+            })
+        })
+        .collect();
+    updatable.sort_by(|a, b| a.package_name.cmp(&b.package_name));
+    updatable
+}
+
+/// Newest catalog release of a watched app that was not reported yet
+#[derive(Debug, PartialEq, Eq)]
+struct WatchedRelease<'a> {
+    package_name: &'a str,
+    app: &'a CloudApp,
+    /// Version reported before, `None` on the first check after the app was watched
+    seen_version_code: Option<u64>,
+}
+
+/// Finds watched apps whose newest catalog version is newer than the one reported last
+fn find_new_watched_releases<'a>(
+    catalog: &'a [CloudApp],
+    metadata: &'a AppMetadata,
+) -> Vec<WatchedRelease<'a>> {
+    metadata
+        .watched_apps
+        .iter()
+        .filter_map(|(package_name, watched)| {
+            let key = match_key(package_name);
+            let newest = catalog
+                .iter()
+                .filter(|app| match_key(&app.true_package_name) == key)
+                .max_by_key(|app| app.version_code)?;
+            let seen = watched.seen_version_code;
+            seen.is_none_or(|seen| u64::from(newest.version_code) > seen)
+                .then_some(WatchedRelease { package_name, app: newest, seen_version_code: seen })
+        })
+        .collect()
+}
+
+/// Keeps Dart informed about installed apps that have catalog updates
+pub(crate) struct UpdatesTracker {
+    adb_service: Arc<AdbService>,
+    downloader_manager: Arc<DownloaderManager>,
+    metadata_store: Arc<MetadataStore>,
+}
+
+impl UpdatesTracker {
+    pub(crate) fn new(
+        adb_service: Arc<AdbService>,
+        downloader_manager: Arc<DownloaderManager>,
+        metadata_store: Arc<MetadataStore>,
+    ) -> Self {
+        Self { adb_service, downloader_manager, metadata_store }
+    }
+
+    /// Starts recomputing updatable apps on device, catalog and metadata changes
+    pub(crate) fn start(self) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        handle
+    }
+
+    async fn receive_signals(&self) {
+        let request_receiver = GetUpdatableAppsRequest::get_dart_signal_receiver();
+        let mut device_rx = self.adb_service.subscribe_device_changes();
+        let mut metadata_rx = self.metadata_store.subscribe();
+        let mut available_rx = self.downloader_manager.subscribe_available();
+        let mut catalog_rx = self.catalog_receiver().await;
+        let mut last_sent: Option<Vec<UpdatableApp>> = None;
+
+        loop {
+            let force = tokio::select! {
+                request = request_receiver.recv() => {
+                    if request.is_none() {
+                        panic!("GetUpdatableAppsRequest receiver closed");
+                    }
+                    debug!("Received GetUpdatableAppsRequest");
+                    true
+                }
+                result = device_rx.changed() => {
+                    result.expect("AdbService device watch closed");
+                    false
+                }
+                result = metadata_rx.changed() => {
+                    result.expect("Metadata store watch closed");
+                    false
+                }
+                result = available_rx.changed() => {
+                    result.expect("Downloader availability watch closed");
+                    catalog_rx = self.catalog_receiver().await;
+                    false
+                }
+                changed = catalog_changed(&mut catalog_rx) => {
+                    if !changed {
+                        catalog_rx = None;
+                        continue;
+                    }
+                    false
+                }
+            };
+
+            self.check_watched_apps().await;
+            let apps = self.compute().await;
+            if force || last_sent.as_ref() != Some(&apps) {
+                debug!(count = apps.len(), "Sending updatable apps");
+                UpdatableApps { apps: apps.clone() }.send_signal_to_dart();
+                last_sent = Some(apps);
+            }
+        }
+    }
+
+    async fn catalog_receiver(&self) -> Option<watch::Receiver<()>> {
+        let downloader = self.downloader_manager.get().await?;
+        Some(downloader.subscribe_catalog_changes())
+    }
+
+    /// Announces new catalog versions of watched apps. The first check after an app is watched
+    /// only records the current version.
+    async fn check_watched_apps(&self) {
+        let metadata = self.metadata_store.current();
+        if metadata.watched_apps.is_empty() {
+            return;
+        }
+        let Some(downloader) = self.downloader_manager.get().await else {
+            return;
+        };
+        let catalog = downloader.cloud_apps().await;
+        let releases = find_new_watched_releases(&catalog, &metadata);
+        if releases.is_empty() {
+            return;
+        }
+
+        for release in releases.iter().filter(|release| release.seen_version_code.is_some()) {
+            info!(
+                package = release.package_name,
+                version_code = release.app.version_code,
+                "New version of watched app in catalog"
+            );
+            Toast::send(
+                "New version available".to_string(),
+                format!("{} is now in the catalog", release.app.full_name),
+                false,
+                Some(Duration::from_secs(8)),
+            );
+        }
+        let versions: Vec<(String, u64)> = releases
+            .iter()
+            .map(|release| (release.package_name.to_string(), release.app.version_code.into()))
+            .collect();
+        if let Err(e) = self.metadata_store.set_watched_seen(&versions) {
+            warn!(
+                error = e.as_ref() as &dyn Error,
+                "Failed to record seen versions of watched apps"
+            );
+        }
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn compute(&self) -> Vec<UpdatableApp> {
+        let Some(device) = self.adb_service.try_current_device().await else {
+            return Vec::new();
+        };
+        let Some(downloader) = self.downloader_manager.get().await else {
+            return Vec::new();
+        };
+        let catalog = downloader.cloud_apps().await;
+        find_updatable_apps(&device.installed_packages, &catalog, &self.metadata_store.current())
+    }
+}
+
+/// Waits for the next catalog change, returning `false` once the downloader is gone
+async fn catalog_changed(rx: &mut Option<watch::Receiver<()>>) -> bool {
+    match rx {
+        Some(rx) => rx.changed().await.is_ok(),
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(package_name: &str, version_code: u64) -> InstalledPackage {
+        InstalledPackage::from_list_apps_entry(serde_json::json!({
+            "package_name": package_name,
+            "version_code": version_code,
+        }))
+        .unwrap()
+    }
+
+    fn cloud(full_name: &str, package_name: &str, version_code: u32) -> CloudApp {
+        CloudApp::new(
+            full_name.to_string(),
+            full_name.to_string(),
+            package_name.to_string(),
+            version_code,
+            String::new(),
+            0,
+        )
+    }
+
+    #[test]
+    fn finds_newest_release_including_renamed_packages() {
+        let installed = [installed("com.example.game", 10), installed("com.example.other", 5)];
+        let catalog = [
+            cloud("Game v11", "com.example.game", 11),
+            cloud("Game v12 (renamed)", "mr.com.example.game", 12),
+            cloud("Game v9", "com.example.game", 9),
+            cloud("Other v5", "com.example.other", 5),
+        ];
+
+        let updatable = find_updatable_apps(&installed, &catalog, &AppMetadata::default());
+
+        assert_eq!(updatable.len(), 1);
+        assert_eq!(updatable[0].package_name, "com.example.game");
+        assert_eq!(updatable[0].installed_version_code, 10);
+        assert_eq!(updatable[0].full_name, "Game v12 (renamed)");
+        assert_eq!(updatable[0].version_code, 12);
+    }
+
+    #[test]
+    fn version_pins_limit_offered_updates() {
+        let installed = [installed("com.example.game", 10)];
+        let catalog = [
+            cloud("Game v11", "com.example.game", 11),
+            cloud("Game v12", "com.example.game", 12),
+            cloud("Game v13", "com.example.game", 13),
+        ];
+        let mut metadata = AppMetadata::default();
+        metadata.set_version_pin("com.example.game", Some(12));
+        metadata.set_version_ignored("com.example.game", 12, true);
+
+        let updatable = find_updatable_apps(&installed, &catalog, &metadata);
+        assert_eq!(updatable.len(), 1);
+        assert_eq!(updatable[0].version_code, 11);
+
+        metadata.set_version_ignored("com.example.game", 11, true);
+        assert!(find_updatable_apps(&installed, &catalog, &metadata).is_empty());
+    }
+
+    #[test]
+    fn watched_apps_report_versions_newer_than_seen() {
+        let catalog = [
+            cloud("Game v11", "com.example.game", 11),
+            cloud("Game v12 (renamed)", "mr.com.example.game", 12),
+            cloud("Other v5", "com.example.other", 5),
+        ];
+        let mut metadata = AppMetadata::default();
+        metadata.set_watched("com.example.game", true);
+        metadata.set_watched("com.example.other", true);
+        metadata.set_watched("com.example.missing", true);
+        metadata.set_watched_seen("com.example.other", 5);
+
+        let releases = find_new_watched_releases(&catalog, &metadata);
+        assert_eq!(
+            releases,
+            [WatchedRelease {
+                package_name: "com.example.game",
+                app: &catalog[1],
+                seen_version_code: None,
+            }]
+        );
+
+        metadata.set_watched_seen("com.example.game", 11);
+        let releases = find_new_watched_releases(&catalog, &metadata);
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].seen_version_code, Some(11));
+
+        metadata.set_watched_seen("com.example.game", 12);
+        assert!(find_new_watched_releases(&catalog, &metadata).is_empty());
+    }
+}
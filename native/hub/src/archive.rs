@@ -1,17 +1,28 @@
 use std::{
     ffi::{OsStr, OsString},
     io,
-    path::{Path, PathBuf},
+    num::NonZeroUsize,
+    path::{Component, Path, PathBuf},
     process::Stdio,
-    sync::Mutex,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
 
 use anyhow::{Context, Result, anyhow, ensure};
-use tokio::{fs, io::AsyncReadExt, process::Command as TokioCommand};
+use lazy_regex::regex_captures;
+use serde::Deserialize;
+use tokio::{
+    fs,
+    io::AsyncReadExt,
+    process::{Child, ChildStdout, Command as TokioCommand},
+    sync::watch,
+};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, instrument};
 
-use crate::utils::resolve_binary_path;
+use crate::{models::Settings, utils::resolve_binary_path};
 
 /// Cached 7-Zip binary path. Re-resolved if missing or if the cached path no longer exists.
 static SEVENZ_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
@@ -52,6 +63,22 @@ fn get_7z_path() -> Result<PathBuf> {
 }
 
 async fn run_7z<I, S>(args: I, cancel: Option<&CancellationToken>) -> Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_7z_with_output(args, cancel, None).await
+}
+
+/// Runs 7-Zip, passing each status segment it prints to stdout to `on_output`.
+///
+/// 7-Zip redraws progress in place with backspaces and carriage returns, so those separate
+/// segments as well as newlines.
+async fn run_7z_with_output<I, S>(
+    args: I,
+    cancel: Option<&CancellationToken>,
+    mut on_output: Option<&mut (dyn FnMut(&str) + Send)>,
+) -> Result<()>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
@@ -59,24 +86,52 @@ where
     let bin = get_7z_path()?;
 
     let mut cmd = TokioCommand::new(&bin);
-    cmd.args(args).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped());
+    let stdout = if on_output.is_some() { Stdio::piped() } else { Stdio::null() };
+    cmd.args(args).stdin(Stdio::null()).stdout(stdout).stderr(Stdio::piped());
 
     #[cfg(target_os = "windows")]
     cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
 
     let mut child = cmd.spawn().context("Failed to spawn 7-Zip process")?;
     let mut stderr = child.stderr.take();
+    let stdout = child.stdout.take();
+
+    let read_output = async {
+        let (Some(mut stdout), Some(on_output)) = (stdout, on_output.as_mut()) else {
+            return;
+        };
+        let mut buf = vec![0u8; 4096];
+        let mut pending = Vec::new();
+        while let Ok(read) = stdout.read(&mut buf).await
+            && read > 0
+        {
+            for &byte in &buf[..read] {
+                if matches!(byte, b'\n' | b'\r' | 0x08) {
+                    if !pending.is_empty() {
+                        on_output(&String::from_utf8_lossy(&pending));
+                        pending.clear();
+                    }
+                } else {
+                    pending.push(byte);
+                }
+            }
+        }
+        if !pending.is_empty() {
+            on_output(&String::from_utf8_lossy(&pending));
+        }
+    };
+    let wait = async { tokio::join!(child.wait(), read_output).0 };
 
     let status = if let Some(tok) = cancel {
         tokio::select! {
-            status = child.wait() => status.context("Failed to wait for 7-Zip process")?,
+            status = wait => status.context("Failed to wait for 7-Zip process")?,
             _ = tok.cancelled() => {
                 let _ = child.kill().await;
                 return Err(anyhow!(io::Error::new(io::ErrorKind::Interrupted, "extraction cancelled")));
             }
         }
     } else {
-        child.wait().await.context("Failed to wait for 7-Zip process")?
+        wait.await.context("Failed to wait for 7-Zip process")?
     };
 
     if !status.success() {
@@ -169,11 +224,320 @@ pub(crate) async fn decompress_archive(
     run_7z(args, cancel.as_ref()).await
 }
 
-/// Decompresses all `.7z` archives found directly under `dir` into `dir`.
-#[instrument(level = "debug", skip(dir, cancel))]
+/// Archive formats [`extract_archive`] supports, detected from the file name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArchiveFormat {
+    Zip,
+    SevenZip,
+    Tar,
+    TarGz,
+    TarZstd,
+    /// A single zstd-compressed file
+    Zstd,
+}
+
+impl ArchiveFormat {
+    pub(crate) fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        let format = if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Self::TarGz
+        } else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            Self::TarZstd
+        } else if name.ends_with(".zst") {
+            Self::Zstd
+        } else if name.ends_with(".tar") {
+            Self::Tar
+        } else if name.ends_with(".7z") || name.ends_with(".7z.001") {
+            Self::SevenZip
+        } else if name.ends_with(".zip") {
+            Self::Zip
+        } else {
+            return None;
+        };
+        Some(format)
+    }
+
+    /// Whether the archive is a compressed tarball, which 7-Zip unpacks in two passes
+    fn is_compressed_tar(self) -> bool {
+        matches!(self, Self::TarGz | Self::TarZstd)
+    }
+
+    /// Whether the archive is compressed as one stream, so its entries can't be read one by one
+    pub(crate) fn is_single_stream(self) -> bool {
+        matches!(self, Self::TarGz | Self::TarZstd | Self::Zstd)
+    }
+}
+
+/// Progress of an archive extraction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ExtractProgress {
+    /// Entry being extracted, with `/` separators
+    pub current_file: Option<String>,
+    pub files_done: u64,
+    pub total_files: u64,
+    pub bytes_done: u64,
+    /// Uncompressed size of the archive, 0 if unknown
+    pub total_bytes: u64,
+}
+
+impl ExtractProgress {
+    /// Extracted fraction of the archive, if its size is known
+    pub(crate) fn fraction(&self) -> Option<f32> {
+        (self.total_bytes > 0).then(|| (self.bytes_done as f32 / self.total_bytes as f32).min(1.0))
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ExtractOptions<'a> {
+    /// Passed to 7-Zip as `-p<password>`
+    pub password: Option<&'a str>,
+    pub cancel: Option<CancellationToken>,
+    /// Called whenever the extracted file or the extracted byte count changes
+    pub on_progress: Option<&'a (dyn Fn(&ExtractProgress) + Sync)>,
+}
+
+/// Extracts a zip, 7z, tar, tar.gz, tar.zst or zst archive into `dest_dir`, reporting progress.
+///
+/// Archives of unknown formats are handed to 7-Zip as is. Compressed tarballs are decompressed
+/// into a temporary directory inside `dest_dir` first, so progress runs twice for them: once for
+/// the tarball and once for its contents.
+#[instrument(level = "debug", skip(dest_dir, options), err)]
+pub(crate) async fn extract_archive(
+    archive: &Path,
+    dest_dir: &Path,
+    options: &ExtractOptions<'_>,
+) -> Result<()> {
+    if !ArchiveFormat::detect(archive).is_some_and(ArchiveFormat::is_compressed_tar) {
+        return extract_with_progress(archive, dest_dir, options).await;
+    }
+
+    fs::create_dir_all(dest_dir).await?;
+    let tar_dir = tempfile::Builder::new()
+        .prefix(".yaas-extract-")
+        .tempdir_in(dest_dir)
+        .context("Failed to create temporary directory")?;
+    extract_with_progress(archive, tar_dir.path(), options).await?;
+
+    let mut rd = fs::read_dir(tar_dir.path()).await?;
+    let tarball = rd.next_entry().await?.context("Compressed tarball is empty")?.path();
+    debug!(tarball = %tarball.display(), "Unpacking decompressed tarball");
+    let tar_options = ExtractOptions {
+        password: None,
+        cancel: options.cancel.clone(),
+        on_progress: options.on_progress,
+    };
+    extract_with_progress(&tarball, dest_dir, &tar_options).await
+}
+
+/// Runs a single 7-Zip extraction pass, tracking progress against the archive listing.
+///
+/// Zip entries are compressed independently, so with more than one extraction thread they are
+/// split between several 7-Zip processes. Other formats leave multithreading to 7-Zip's decoder.
+async fn extract_with_progress(
+    archive: &Path,
+    dest_dir: &Path,
+    options: &ExtractOptions<'_>,
+) -> Result<()> {
+    let threads = extraction_threads();
+    let format = ArchiveFormat::detect(archive);
+    let parallel_zip = threads > 1 && format == Some(ArchiveFormat::Zip);
+
+    let mut args: Vec<OsString> = vec!["x".into(), "-y".into(), "-bsp1".into(), "-bb1".into()];
+    if format == Some(ArchiveFormat::SevenZip) {
+        args.push(format!("-mmt{threads}").into());
+    }
+    if let Some(password) = options.password {
+        args.push(format!("-p{password}").into());
+    }
+    let mut out_arg = OsString::from("-o");
+    out_arg.push(dest_dir.as_os_str());
+    args.push(out_arg);
+    args.push(archive.as_os_str().to_os_string());
+
+    if options.on_progress.is_none() && !parallel_zip {
+        return run_7z(args, options.cancel.as_ref()).await;
+    }
+
+    // Without a listing (e.g. encrypted headers) progress is reported by file name only
+    let entries = match list_archive_entries(archive).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!(error = format!("{e:#}"), "Failed to list archive for extraction");
+            Vec::new()
+        }
+    };
+    let groups = if parallel_zip { split_entries(&entries, threads) } else { Vec::new() };
+    if groups.len() < 2 {
+        let tracker = ProgressTracker::new(&entries, &[], options.on_progress);
+        let mut on_output = |segment: &str| tracker.update(0, segment);
+        return run_7z_with_output(args, options.cancel.as_ref(), Some(&mut on_output)).await;
+    }
+
+    debug!(workers = groups.len(), entries = entries.len(), "Extracting zip in parallel");
+    let tracker = ProgressTracker::new(&entries, &groups, options.on_progress);
+    let list_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let mut workers = Vec::with_capacity(groups.len());
+    for (index, group) in groups.iter().enumerate() {
+        let list_file = list_dir.path().join(format!("{index}.txt"));
+        let list = group.iter().map(|entry| entry.path.as_str()).collect::<Vec<_>>().join("\n");
+        fs::write(&list_file, list).await.context("Failed to write extraction list")?;
+
+        let mut worker_args = args.clone();
+        worker_args.push("-scsUTF-8".into());
+        let mut list_arg = OsString::from("@");
+        list_arg.push(list_file.as_os_str());
+        worker_args.push(list_arg);
+        let tracker = &tracker;
+        workers.push(async move {
+            let mut on_output = |segment: &str| tracker.update(index, segment);
+            run_7z_with_output(worker_args, options.cancel.as_ref(), Some(&mut on_output)).await
+        });
+    }
+    futures::future::try_join_all(workers).await?;
+    Ok(())
+}
+
+/// Threads extraction may use, 0 for all CPU cores. Follows `Settings::extraction_threads`.
+static EXTRACTION_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps the extraction thread count in sync with the settings
+pub(crate) fn follow_settings(mut settings: watch::Receiver<Settings>) {
+    tokio::spawn(async move {
+        loop {
+            let threads = settings.borrow_and_update().extraction_threads;
+            EXTRACTION_THREADS.store(threads as usize, Ordering::Relaxed);
+            if settings.changed().await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn extraction_threads() -> usize {
+    match EXTRACTION_THREADS.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+}
+
+/// Splits entries into at most `workers` groups of similar total size, largest entries first
+fn split_entries(entries: &[ArchiveEntry], workers: usize) -> Vec<Vec<&ArchiveEntry>> {
+    let mut sorted = entries.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|entry| std::cmp::Reverse(entry.size));
+    let mut groups: Vec<(u64, Vec<&ArchiveEntry>)> = vec![(0, Vec::new()); workers.max(1)];
+    for entry in sorted {
+        let (size, group) =
+            groups.iter_mut().min_by_key(|(size, _)| *size).expect("at least one group");
+        *size += entry.size;
+        group.push(entry);
+    }
+    groups.into_iter().map(|(_, group)| group).filter(|group| !group.is_empty()).collect()
+}
+
+/// Combines the progress of the 7-Zip processes of one extraction
+struct ProgressTracker<'a> {
+    /// Uncompressed size each process extracts
+    worker_bytes: Vec<u64>,
+    on_progress: Option<&'a (dyn Fn(&ExtractProgress) + Sync)>,
+    state: Mutex<TrackerState>,
+}
+
+struct TrackerState {
+    /// Bytes and files done per process
+    workers: Vec<(u64, u64)>,
+    progress: ExtractProgress,
+}
+
+impl<'a> ProgressTracker<'a> {
+    /// Without `groups`, all entries are extracted by a single process
+    fn new(
+        entries: &[ArchiveEntry],
+        groups: &[Vec<&ArchiveEntry>],
+        on_progress: Option<&'a (dyn Fn(&ExtractProgress) + Sync)>,
+    ) -> Self {
+        let total_bytes = entries.iter().map(|e| e.size).sum();
+        let worker_bytes = if groups.is_empty() {
+            vec![total_bytes]
+        } else {
+            groups.iter().map(|group| group.iter().map(|e| e.size).sum()).collect()
+        };
+        let progress = ExtractProgress {
+            total_files: entries.len() as u64,
+            total_bytes,
+            ..Default::default()
+        };
+        if let Some(on_progress) = on_progress {
+            on_progress(&progress);
+        }
+        Self {
+            state: Mutex::new(TrackerState { workers: vec![(0, 0); worker_bytes.len()], progress }),
+            worker_bytes,
+            on_progress,
+        }
+    }
+
+    /// Applies a status segment printed by the process extracting group `worker`
+    fn update(&self, worker: usize, segment: &str) {
+        let Some(on_progress) = self.on_progress else {
+            return;
+        };
+        let Some(output) = parse_7z_progress(segment) else {
+            return;
+        };
+        let mut state = self.state.lock().expect("extraction progress lock poisoned");
+        let previous = state.progress.clone();
+        match output {
+            SevenZipOutput::Percent { percent, files, file } => {
+                let (bytes_done, files_done) = &mut state.workers[worker];
+                *bytes_done = self.worker_bytes[worker] * u64::from(percent) / 100;
+                if let Some(files) = files {
+                    *files_done = files;
+                }
+                if let Some(file) = file {
+                    state.progress.current_file = Some(file.replace('\\', "/"));
+                }
+            }
+            SevenZipOutput::File(file) => {
+                state.progress.current_file = Some(file.replace('\\', "/"));
+            }
+        }
+        state.progress.bytes_done = state.workers.iter().map(|(bytes, _)| bytes).sum();
+        state.progress.files_done = state.workers.iter().map(|(_, files)| files).sum();
+        if state.progress != previous {
+            on_progress(&state.progress);
+        }
+    }
+}
+
+/// A status segment 7-Zip prints with `-bsp1 -bb1`
+#[derive(Debug, PartialEq, Eq)]
+enum SevenZipOutput<'a> {
+    /// Overall progress, e.g. ` 42% 3 - dir/file.bin`
+    Percent { percent: u8, files: Option<u64>, file: Option<&'a str> },
+    /// A file being extracted, e.g. `- dir/file.bin`
+    File(&'a str),
+}
+
+fn parse_7z_progress(segment: &str) -> Option<SevenZipOutput<'_>> {
+    let segment = segment.trim();
+    if let Some(file) = segment.strip_prefix("- ") {
+        return Some(SevenZipOutput::File(file));
+    }
+    let (_, percent, files, file) =
+        regex_captures!(r"^(\d{1,3})%(?:\s+(\d+))?(?:\s+-\s+(.+))?$", segment)?;
+    Some(SevenZipOutput::Percent {
+        percent: percent.parse::<u8>().ok()?.min(100),
+        files: files.parse().ok(),
+        file: (!file.is_empty()).then_some(file),
+    })
+}
+
+/// Decompresses all `.7z` archives found directly under `dir` into `dest_dir`.
+#[instrument(level = "debug", skip(dir, dest_dir, options))]
 pub(crate) async fn decompress_all_7z_in_dir(
     dir: &Path,
-    cancel: Option<CancellationToken>,
+    dest_dir: &Path,
+    options: &ExtractOptions<'_>,
 ) -> Result<()> {
     if !dir.is_dir() {
         return Ok(());
@@ -187,7 +551,7 @@ pub(crate) async fn decompress_all_7z_in_dir(
                 .and_then(|e| e.to_str())
                 .is_some_and(|e| e.eq_ignore_ascii_case("7z"))
         {
-            if cancel.as_ref().is_some_and(|t| t.is_cancelled()) {
+            if options.cancel.as_ref().is_some_and(|t| t.is_cancelled()) {
                 debug!("Cancellation requested before starting 7z extraction");
                 return Err(anyhow::Error::from(io::Error::new(
                     io::ErrorKind::Interrupted,
@@ -196,12 +560,132 @@ pub(crate) async fn decompress_all_7z_in_dir(
             }
             let path = entry.path();
             debug!(path = %path.display(), "Decompressing 7z archive");
-            decompress_archive(&path, dir, None, None, cancel.clone()).await?;
+            extract_archive(&path, dest_dir, options).await?;
         }
     }
     Ok(())
 }
 
+/// Returns whether the path is a split APK bundle (`.xapk`, `.apks` or `.apkm`)
+pub(crate) fn is_apk_bundle(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ["xapk", "apks", "apkm"].iter().any(|b| ext.eq_ignore_ascii_case(b)))
+}
+
+/// What to install from an extracted APK bundle
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ApkBundle {
+    /// APK files to install in one session, sorted
+    pub apks: Vec<PathBuf>,
+    /// Expansion files with their destination relative to `/sdcard`
+    pub obb_files: Vec<(PathBuf, String)>,
+}
+
+/// `manifest.json` of an XAPK bundle
+#[derive(Debug, Deserialize)]
+struct XapkManifest {
+    #[serde(default)]
+    split_apks: Vec<XapkSplit>,
+    #[serde(default)]
+    expansions: Vec<XapkExpansion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct XapkSplit {
+    file: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct XapkExpansion {
+    file: String,
+    install_path: String,
+}
+
+/// Extracts an APK bundle into `dest_dir` and lists what to install from it
+#[instrument(level = "debug", skip(dest_dir, options), err)]
+pub(crate) async fn extract_apk_bundle(
+    bundle: &Path,
+    dest_dir: &Path,
+    options: &ExtractOptions<'_>,
+) -> Result<ApkBundle> {
+    extract_archive(bundle, dest_dir, options).await?;
+    read_apk_bundle(dest_dir).await
+}
+
+/// Lists the APK and expansion files of an extracted bundle.
+///
+/// XAPK bundles describe their contents in `manifest.json`. Other bundles install every APK,
+/// except the `standalones` variants bundletool ships next to the splits.
+async fn read_apk_bundle(dir: &Path) -> Result<ApkBundle> {
+    let manifest_path = dir.join("manifest.json");
+    let (mut apks, obb_files) = if manifest_path.is_file() {
+        let manifest: XapkManifest = serde_json::from_slice(&fs::read(&manifest_path).await?)
+            .context("Failed to parse XAPK manifest")?;
+        let apks = manifest
+            .split_apks
+            .iter()
+            .map(|split| bundle_file(dir, &split.file))
+            .collect::<Result<Vec<_>>>()?;
+        let obb_files = manifest
+            .expansions
+            .into_iter()
+            .map(|expansion| {
+                ensure!(
+                    is_relative_normal(&expansion.install_path),
+                    "Invalid expansion install path: {}",
+                    expansion.install_path
+                );
+                Ok((bundle_file(dir, &expansion.file)?, expansion.install_path))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Bundles of apps without splits only list expansions
+        let apks = if apks.is_empty() { find_bundle_apks(dir).await? } else { apks };
+        (apks, obb_files)
+    } else {
+        (find_bundle_apks(dir).await?, Vec::new())
+    };
+    ensure!(!apks.is_empty(), "No APK files found in bundle");
+    apks.sort();
+    Ok(ApkBundle { apks, obb_files })
+}
+
+fn is_relative_normal(path: &str) -> bool {
+    !path.is_empty() && Path::new(path).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Resolves a file listed in a bundle manifest, rejecting paths outside the bundle
+fn bundle_file(dir: &Path, file: &str) -> Result<PathBuf> {
+    ensure!(is_relative_normal(file), "Invalid file path in bundle manifest: {file}");
+    let path = dir.join(file);
+    ensure!(path.is_file(), "File listed in bundle manifest is missing: {file}");
+    Ok(path)
+}
+
+/// Finds APK files anywhere in an extracted bundle, skipping bundletool's standalone APKs
+async fn find_bundle_apks(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut apks = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut rd = fs::read_dir(&current).await?;
+        while let Some(entry) = rd.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                if entry.file_name() != "standalones" {
+                    pending.push(path);
+                }
+            } else if path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("apk"))
+            {
+                apks.push(path);
+            }
+        }
+    }
+    Ok(apks)
+}
+
 /// Run 7-Zip and capture stdout.
 async fn run_7z_to_string<I, S>(args: I) -> Result<String>
 where
@@ -228,9 +712,24 @@ where
     Ok(stdout)
 }
 
+/// File entry contained in an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ArchiveEntry {
+    /// Path inside the archive, with `/` separators
+    pub path: String,
+    /// Uncompressed size in bytes (0 if unknown)
+    pub size: u64,
+}
+
 /// List file paths contained in an archive using 7-Zip.
 /// Returns only file entries (directories are filtered out).
 pub(crate) async fn list_archive_file_paths(archive: &Path) -> Result<Vec<String>> {
+    Ok(list_archive_entries(archive).await?.into_iter().map(|e| e.path).collect())
+}
+
+/// List file entries contained in an archive using 7-Zip.
+/// Directories are filtered out.
+pub(crate) async fn list_archive_entries(archive: &Path) -> Result<Vec<ArchiveEntry>> {
     // Use technical list for easier parsing
     let out = run_7z_to_string([
         OsString::from("l"),
@@ -238,13 +737,20 @@ pub(crate) async fn list_archive_file_paths(archive: &Path) -> Result<Vec<String
         archive.as_os_str().to_os_string(),
     ])
     .await?;
-    Ok(parse_7z_slt_listing(&out))
+    Ok(parse_7z_slt_entries(&out))
 }
 
 /// Parse stdout of `7z l -slt` and return file paths (directories filtered out).
+#[cfg(test)]
 fn parse_7z_slt_listing(out: &str) -> Vec<String> {
+    parse_7z_slt_entries(out).into_iter().map(|e| e.path).collect()
+}
+
+/// Parse stdout of `7z l -slt` and return file entries (directories filtered out).
+fn parse_7z_slt_entries(out: &str) -> Vec<ArchiveEntry> {
     let mut result = Vec::new();
     let mut cur_path: Option<String> = None;
+    let mut cur_size: u64 = 0;
     let mut cur_is_folder: Option<bool> = None;
 
     for line in out.lines() {
@@ -253,13 +759,18 @@ fn parse_7z_slt_listing(out: &str) -> Vec<String> {
             if let (Some(path), Some(is_folder)) = (&cur_path, cur_is_folder)
                 && !is_folder
             {
-                result.push(path.clone());
+                result.push(ArchiveEntry { path: path.clone(), size: cur_size });
             }
             let normalized = p.trim().replace('\\', "/");
             cur_path = Some(normalized);
+            cur_size = 0;
             cur_is_folder = None;
             continue;
         }
+        if let Some(size) = line.strip_prefix("Size = ") {
+            cur_size = size.trim().parse().unwrap_or(0);
+            continue;
+        }
         if let Some(f) = line.strip_prefix("Folder = ") {
             let v = f.trim();
             // 7-Zip -slt prints Folder as one of: "+" (dir), "-" (file), or sometimes "Yes"/"No".
@@ -278,7 +789,7 @@ fn parse_7z_slt_listing(out: &str) -> Vec<String> {
     if let (Some(path), Some(is_folder)) = (cur_path, cur_is_folder)
         && !is_folder
     {
-        result.push(path);
+        result.push(ArchiveEntry { path, size: cur_size });
     }
 
     result
@@ -305,6 +816,69 @@ pub(crate) async fn extract_single_from_archive(
     .await
 }
 
+/// Streams a single archive entry through 7-Zip's stdout, without writing it to disk.
+///
+/// The 7-Zip process is killed if the reader is dropped before [`ArchiveEntryReader::finish`].
+#[derive(Debug)]
+pub(crate) struct ArchiveEntryReader {
+    child: Child,
+    stdout: ChildStdout,
+    /// Drains stderr while the entry is read, so 7-Zip never blocks on a full pipe
+    stderr: tokio::task::JoinHandle<Vec<u8>>,
+}
+
+impl ArchiveEntryReader {
+    #[instrument(level = "debug", err)]
+    pub(crate) fn open(archive: &Path, entry: &str) -> Result<Self> {
+        let bin = get_7z_path()?;
+
+        let mut cmd = TokioCommand::new(&bin);
+        cmd.args([
+            OsString::from("x"),
+            OsString::from("-so"),
+            OsString::from("-bsp0"),
+            OsString::from("-y"),
+            archive.as_os_str().to_os_string(),
+            OsString::from(entry),
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+        #[cfg(target_os = "windows")]
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+
+        let mut child = cmd.spawn().context("Failed to spawn 7-Zip process")?;
+        let stdout = child.stdout.take().context("Failed to capture 7-Zip stdout")?;
+        let mut stderr = child.stderr.take().context("Failed to capture 7-Zip stderr")?;
+        let stderr = tokio::spawn(async move {
+            let mut buf = Vec::new();
+            stderr.read_to_end(&mut buf).await.ok();
+            buf
+        });
+        Ok(Self { child, stdout, stderr })
+    }
+
+    /// Entry contents.
+    pub(crate) fn reader(&mut self) -> &mut ChildStdout {
+        &mut self.stdout
+    }
+
+    /// Waits for 7-Zip to exit and checks that the entry was extracted successfully.
+    pub(crate) async fn finish(mut self) -> Result<()> {
+        let status = self.child.wait().await.context("Failed to wait for 7-Zip process")?;
+        let stderr = self.stderr.await.unwrap_or_default();
+        ensure!(
+            status.success(),
+            "7-Zip exited with status: {}, stderr:\n{}",
+            status,
+            String::from_utf8_lossy(&stderr)
+        );
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{ffi::OsString, path::Path};
@@ -591,6 +1165,189 @@ Offset = 17198364
         assert_eq!(files.len(), 5);
     }
 
+    #[test]
+    fn parse_7z_entries_with_sizes() {
+        let sample = r#"Path = game.zip
+Type = zip
+Physical Size = 300
+
+----------
+Path = Game v1+1\com.example.game
+Folder = +
+Size = 0
+
+Path = Game v1+1\com.example.game.apk
+Folder = -
+Size = 100
+
+Path = Game v1+1\com.example.game\main.1.com.example.game.obb
+Folder = -
+Size = 200
+"#;
+        let entries = parse_7z_slt_entries(sample);
+        assert_eq!(
+            entries,
+            vec![
+                ArchiveEntry { path: "Game v1+1/com.example.game.apk".into(), size: 100 },
+                ArchiveEntry {
+                    path: "Game v1+1/com.example.game/main.1.com.example.game.obb".into(),
+                    size: 200
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_archive_formats() {
+        let detect = |name: &str| ArchiveFormat::detect(Path::new(name));
+        assert_eq!(detect("Game v1.ZIP"), Some(ArchiveFormat::Zip));
+        assert_eq!(detect("Game v1.7z.001"), Some(ArchiveFormat::SevenZip));
+        assert_eq!(detect("game.tar"), Some(ArchiveFormat::Tar));
+        assert_eq!(detect("game.tar.gz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(detect("game.tgz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(detect("game.tar.zst"), Some(ArchiveFormat::TarZstd));
+        assert_eq!(detect("game.apk.zst"), Some(ArchiveFormat::Zstd));
+        assert_eq!(detect("game.apk"), None);
+        assert!(ArchiveFormat::Zstd.is_single_stream());
+        assert!(!ArchiveFormat::Zip.is_single_stream());
+    }
+
+    #[test]
+    fn parse_7z_progress_segments() {
+        assert_eq!(
+            parse_7z_progress(" 42% 3 - Game/main.obb"),
+            Some(SevenZipOutput::Percent {
+                percent: 42,
+                files: Some(3),
+                file: Some("Game/main.obb")
+            })
+        );
+        assert_eq!(
+            parse_7z_progress("  0%"),
+            Some(SevenZipOutput::Percent { percent: 0, files: None, file: None })
+        );
+        assert_eq!(
+            parse_7z_progress("- Game/game.apk"),
+            Some(SevenZipOutput::File("Game/game.apk"))
+        );
+        assert_eq!(parse_7z_progress("Everything is Ok"), None);
+        assert_eq!(parse_7z_progress(""), None);
+    }
+
+    #[test]
+    fn split_entries_balances_sizes() {
+        let entries = [100, 10, 60, 50, 30]
+            .iter()
+            .enumerate()
+            .map(|(i, size)| ArchiveEntry { path: format!("f{i}"), size: *size })
+            .collect::<Vec<_>>();
+        let groups = split_entries(&entries, 2);
+        let sizes =
+            groups.iter().map(|g| g.iter().map(|e| e.size).sum::<u64>()).collect::<Vec<_>>();
+        assert_eq!(sizes, [130, 120]);
+        assert_eq!(groups.iter().map(Vec::len).sum::<usize>(), entries.len());
+
+        assert_eq!(split_entries(&entries[..1], 4).len(), 1);
+        assert!(split_entries(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn progress_tracker_combines_workers() {
+        let entries = [
+            ArchiveEntry { path: "a.bin".into(), size: 300 },
+            ArchiveEntry { path: "b.bin".into(), size: 100 },
+        ];
+        let groups = split_entries(&entries, 2);
+        let updates = Mutex::new(Vec::new());
+        let on_progress =
+            |progress: &ExtractProgress| updates.lock().unwrap().push(progress.clone());
+        let tracker = ProgressTracker::new(&entries, &groups, Some(&on_progress));
+
+        tracker.update(0, " 50% - a.bin");
+        tracker.update(1, "100% 1 - b.bin");
+        tracker.update(1, "Everything is Ok");
+        let updates = updates.into_inner().unwrap();
+        assert_eq!(updates.len(), 3);
+        assert_eq!(
+            updates[2],
+            ExtractProgress {
+                current_file: Some("b.bin".into()),
+                files_done: 1,
+                total_files: 2,
+                bytes_done: 250,
+                total_bytes: 400,
+            }
+        );
+    }
+
+    #[test]
+    fn extract_progress_fraction() {
+        let progress = ExtractProgress { bytes_done: 50, total_bytes: 200, ..Default::default() };
+        assert_eq!(progress.fraction(), Some(0.25));
+        assert_eq!(ExtractProgress::default().fraction(), None);
+    }
+
+    #[tokio::test]
+    async fn read_xapk_and_split_bundles() {
+        let xapk = tempdir().unwrap();
+        let root = xapk.path();
+        std::fs::create_dir_all(root.join("Android/obb/com.example.game")).unwrap();
+        for file in ["com.example.game.apk", "config.arm64_v8a.apk", "icon.png"] {
+            std::fs::write(root.join(file), b"").unwrap();
+        }
+        std::fs::write(root.join("Android/obb/com.example.game/main.1.com.example.game.obb"), b"")
+            .unwrap();
+        std::fs::write(
+            root.join("manifest.json"),
+            r#"{
+                "package_name": "com.example.game",
+                "split_apks": [
+                    {"file": "com.example.game.apk", "id": "base"},
+                    {"file": "config.arm64_v8a.apk", "id": "config.arm64_v8a"}
+                ],
+                "expansions": [{
+                    "file": "Android/obb/com.example.game/main.1.com.example.game.obb",
+                    "install_location": "EXTERNAL_STORAGE",
+                    "install_path": "Android/obb/com.example.game/main.1.com.example.game.obb"
+                }]
+            }"#,
+        )
+        .unwrap();
+        let bundle = read_apk_bundle(root).await.unwrap();
+        assert_eq!(
+            bundle.apks,
+            [root.join("com.example.game.apk"), root.join("config.arm64_v8a.apk")]
+        );
+        assert_eq!(
+            bundle.obb_files,
+            [(
+                root.join("Android/obb/com.example.game/main.1.com.example.game.obb"),
+                "Android/obb/com.example.game/main.1.com.example.game.obb".to_string()
+            )]
+        );
+
+        std::fs::write(
+            root.join("manifest.json"),
+            r#"{"split_apks": [{"file": "../outside.apk"}]}"#,
+        )
+        .unwrap();
+        assert!(read_apk_bundle(root).await.is_err());
+
+        let apks = tempdir().unwrap();
+        let root = apks.path();
+        std::fs::create_dir_all(root.join("splits")).unwrap();
+        std::fs::create_dir_all(root.join("standalones")).unwrap();
+        for file in ["splits/base-master.apk", "splits/base-arm64_v8a.apk", "standalones/all.apk"] {
+            std::fs::write(root.join(file), b"").unwrap();
+        }
+        let bundle = read_apk_bundle(root).await.unwrap();
+        assert_eq!(
+            bundle.apks,
+            [root.join("splits/base-arm64_v8a.apk"), root.join("splits/base-master.apk")]
+        );
+        assert!(bundle.obb_files.is_empty());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[ignore]
     async fn create_zip_and_decompress_roundtrip() {
@@ -651,6 +1408,53 @@ Offset = 17198364
         assert_eq!(content, "FIRST");
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    async fn extract_tar_gz_with_progress() {
+        let src_dir = tempdir().unwrap();
+        let payload = src_dir.path().join("payload");
+        std::fs::create_dir(&payload).unwrap();
+        std::fs::write(payload.join("a.bin"), vec![1u8; 64 * 1024]).unwrap();
+        std::fs::write(payload.join("b.bin"), vec![2u8; 64 * 1024]).unwrap();
+
+        let tar = src_dir.path().join("payload.tar");
+        let tar_gz = src_dir.path().join("payload.tar.gz");
+        for (archive, input, kind) in [(&tar, &payload, "-ttar"), (&tar_gz, &tar, "-tgzip")] {
+            run_7z(
+                [
+                    OsString::from("a"),
+                    OsString::from(kind),
+                    archive.as_os_str().to_os_string(),
+                    input.as_os_str().to_os_string(),
+                ],
+                None,
+            )
+            .await
+            .expect("archive creation should succeed");
+        }
+
+        let updates = std::sync::Mutex::new(Vec::new());
+        let on_progress =
+            |progress: &ExtractProgress| updates.lock().unwrap().push(progress.clone());
+        let dest_dir = tempdir().unwrap();
+        extract_archive(
+            &tar_gz,
+            dest_dir.path(),
+            &ExtractOptions { on_progress: Some(&on_progress), ..Default::default() },
+        )
+        .await
+        .expect("extraction should succeed");
+
+        assert_eq!(std::fs::read(dest_dir.path().join("payload/b.bin")).unwrap().len(), 64 * 1024);
+        let updates = updates.into_inner().unwrap();
+        let last = updates.last().expect("progress should be reported");
+        assert_eq!(last.total_files, 2);
+        assert!(updates.iter().any(|p| p.current_file.is_some()));
+        let mut leftovers = std::fs::read_dir(dest_dir.path()).unwrap();
+        assert_eq!(leftovers.next().unwrap().unwrap().file_name(), "payload");
+        assert!(leftovers.next().is_none(), "the decompressed tarball should be removed");
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[ignore]
     async fn decompress_all_7z_archives_in_dir() {
@@ -678,7 +1482,7 @@ Offset = 17198364
         std::fs::remove_dir_all(&payload_dir).unwrap();
         assert!(!payload_dir.exists());
 
-        decompress_all_7z_in_dir(root_path, None)
+        decompress_all_7z_in_dir(root_path, root_path, &ExtractOptions::default())
             .await
             .expect("decompress_all_7z_in_dir should succeed");
 
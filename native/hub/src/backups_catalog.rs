@@ -1,21 +1,66 @@
 use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
     path::{Path, PathBuf},
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{Context, Result, ensure};
 use rinf::{DartSignal, RustSignal};
-use tokio::fs;
+use tokio::{
+    fs,
+    io::AsyncReadExt,
+    sync::{Mutex, Notify, RwLock},
+};
 use tokio_stream::{StreamExt, wrappers::WatchStream};
-use tracing::{Span, debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
+
+use crate::{
+    adb::device::is_backup_archive,
+    archive::{ArchiveEntryReader, list_archive_entries},
+    downloader::http_cache::compute_md5_file,
+    models::{
+        BACKUP_MANIFEST_FILE, BackupContents, BackupFile, BackupManifest, BackupSizes, Settings,
+        apk_info::get_apk_info, signals::backups::*,
+    },
+    utils::{first_subdirectory, list_files_relative},
+};
 
-use crate::models::{Settings, signals::backups::*};
+/// How often the retention settings are enforced in the background
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
-/// Handles backup list-related requests (list, delete)
+/// Limits on the backups that are kept, 0 disables a limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct BackupRetention {
+    max_per_package: u32,
+    max_total_bytes: u64,
+    max_age_millis: u64,
+}
+
+impl BackupRetention {
+    fn from_settings(settings: &Settings) -> Self {
+        Self {
+            max_per_package: settings.backup_retention_max_per_package,
+            max_total_bytes: u64::from(settings.backup_retention_max_total_gb) * 1_000_000_000,
+            max_age_millis: u64::from(settings.backup_retention_max_age_days) * 24 * 60 * 60 * 1000,
+        }
+    }
+
+    fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Handles backup list-related requests (list, delete, prune)
 #[derive(Debug, Clone)]
 pub(crate) struct BackupsCatalog {
-    backups_dir: Arc<tokio::sync::RwLock<PathBuf>>,
+    backups_dir: Arc<RwLock<PathBuf>>,
+    retention: Arc<RwLock<BackupRetention>>,
+    /// Postpones the next background prune, so settings being edited are not enforced midway
+    retention_changed: Arc<Notify>,
+    /// Serializes prunes so background and requested ones do not race
+    prune_lock: Arc<Mutex<()>>,
 }
 
 impl BackupsCatalog {
@@ -24,7 +69,10 @@ impl BackupsCatalog {
             .expect("Settings stream closed on backups catalog init");
 
         let handler = Arc::new(Self {
-            backups_dir: Arc::new(tokio::sync::RwLock::new(initial_settings.backups_location())),
+            backups_dir: Arc::new(RwLock::new(initial_settings.backups_location())),
+            retention: Arc::new(RwLock::new(BackupRetention::from_settings(&initial_settings))),
+            retention_changed: Arc::new(Notify::new()),
+            prune_lock: Arc::new(Mutex::new(())),
         });
 
         // Watch settings updates
@@ -34,11 +82,24 @@ impl BackupsCatalog {
                 while let Some(settings) = settings_stream.next().await {
                     debug!(dir = %settings.backups_location().display(), "Backups location updated");
                     *handler.backups_dir.write().await = settings.backups_location();
+                    let retention = BackupRetention::from_settings(&settings);
+                    let mut current = handler.retention.write().await;
+                    if *current != retention {
+                        debug!(?retention, "Backup retention updated");
+                        *current = retention;
+                        handler.retention_changed.notify_one();
+                    }
                 }
                 panic!("Settings stream closed");
             });
         }
 
+        // Enforce retention in the background
+        {
+            let handler = handler.clone();
+            tokio::spawn(async move { handler.run_background_pruner().await });
+        }
+
         // Start signal receivers
         {
             let handler = handler.clone();
@@ -53,23 +114,29 @@ impl BackupsCatalog {
         let list_receiver = GetBackupsRequest::get_dart_signal_receiver();
         let delete_receiver = DeleteBackupRequest::get_dart_signal_receiver();
         let get_dir_receiver = GetBackupsDirectoryRequest::get_dart_signal_receiver();
+        let preview_prune_receiver = PreviewBackupPruneRequest::get_dart_signal_receiver();
+        let prune_receiver = PruneBackupsRequest::get_dart_signal_receiver();
+        let verify_receiver = VerifyBackupRequest::get_dart_signal_receiver();
 
         loop {
             tokio::select! {
                 // Handle list backup requests
-                signal = list_receiver.recv() => {
-                    if signal.is_some() {
-                        debug!("Received GetBackupsRequest");
+                request = list_receiver.recv() => {
+                    if let Some(request) = request {
+                        let query = request.message.query;
+                        debug!(?query, "Received GetBackupsRequest");
                         match self.list_backups().await {
-                            Ok(mut entries) => {
-                                // Newest first
-                                entries.sort_by_key(|b| std::cmp::Reverse(b.timestamp));
-                                GetBackupsResponse { entries, error: None }.send_signal_to_dart();
-                            }
+                            Ok(entries) => query_backups(entries, &query).send_signal_to_dart(),
                             Err(e) => {
                                 error!(error = %format!("{e:#}"), "Failed to list backups");
-                                GetBackupsResponse { entries: vec![], error: Some(format!("{e:#}")) }
-                                    .send_signal_to_dart();
+                                GetBackupsResponse {
+                                    search: query.search,
+                                    entries: vec![],
+                                    total_count: 0,
+                                    total_size: 0,
+                                    error: Some(format!("{e:#}")),
+                                }
+                                .send_signal_to_dart();
                             }
                         }
                     } else {
@@ -111,10 +178,176 @@ impl BackupsCatalog {
                         panic!("GetBackupsDirectoryRequest receiver closed");
                     }
                 }
+
+                // Handle prune preview requests
+                request = preview_prune_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received PreviewBackupPruneRequest");
+                        match self.plan_prune().await {
+                            Ok(entries) => {
+                                let freed_bytes = entries.iter().map(|e| e.total_size).sum();
+                                BackupPrunePreview { entries, freed_bytes, error: None }
+                                    .send_signal_to_dart();
+                            }
+                            Err(e) => {
+                                error!(error = %format!("{e:#}"), "Failed to plan backup prune");
+                                BackupPrunePreview {
+                                    entries: vec![],
+                                    freed_bytes: 0,
+                                    error: Some(format!("{e:#}")),
+                                }
+                                .send_signal_to_dart();
+                            }
+                        }
+                    } else {
+                        panic!("PreviewBackupPruneRequest receiver closed");
+                    }
+                }
+
+                // Handle prune requests
+                request = prune_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received PruneBackupsRequest");
+                        let handler = self.clone();
+                        tokio::spawn(async move { handler.prune().await.send_signal_to_dart() });
+                    } else {
+                        panic!("PruneBackupsRequest receiver closed");
+                    }
+                }
+
+                // Handle verify requests
+                request = verify_receiver.recv() => {
+                    if let Some(request) = request {
+                        let path = request.message.path;
+                        debug!(%path, "Received VerifyBackupRequest");
+                        tokio::spawn(async move {
+                            let signal = match verify_backup(Path::new(&path)).await {
+                                Ok(report) => {
+                                    info!(%path, problems = report.problems.len(), "Verified backup");
+                                    BackupVerification {
+                                        path,
+                                        has_file_list: report.has_file_list,
+                                        checked_files: report.checked_files,
+                                        problems: report.problems,
+                                        error: None,
+                                    }
+                                }
+                                Err(e) => {
+                                    error!(%path, error = %format!("{e:#}"), "Failed to verify backup");
+                                    BackupVerification {
+                                        path,
+                                        has_file_list: false,
+                                        checked_files: 0,
+                                        problems: vec![],
+                                        error: Some(format!("{e:#}")),
+                                    }
+                                }
+                            };
+                            signal.send_signal_to_dart();
+                        });
+                    } else {
+                        panic!("VerifyBackupRequest receiver closed");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Prunes backups on an interval. A retention change restarts the interval instead of
+    /// pruning, changed settings are applied right away only through a confirmed
+    /// `PruneBackupsRequest`.
+    async fn run_background_pruner(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = self.retention_changed.notified() => {
+                    debug!("Backup retention changed, postponing background prune");
+                    interval.reset();
+                    continue;
+                }
+            }
+            if self.retention.read().await.is_unlimited() {
+                continue;
+            }
+            let result = self.prune().await;
+            // Failures are only logged here, a missing backups directory is not worth a toast
+            if result.deleted > 0 {
+                result.send_signal_to_dart();
             }
         }
     }
 
+    /// Lists the backups the current retention settings would delete, oldest first
+    #[instrument(level = "debug", skip(self), err)]
+    async fn plan_prune(&self) -> Result<Vec<BackupEntry>> {
+        let retention = *self.retention.read().await;
+        if retention.is_unlimited() {
+            return Ok(vec![]);
+        }
+        let entries = self.list_backups().await?;
+        Ok(plan_prune(&entries, &retention, system_time_to_millis(SystemTime::now())))
+    }
+
+    /// Deletes the backups the retention settings prune, continuing past failed deletions
+    #[instrument(level = "debug", skip(self))]
+    async fn prune(&self) -> BackupsPruned {
+        let _guard = self.prune_lock.lock().await;
+        let entries = match self.plan_prune().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!(error = %format!("{e:#}"), "Failed to plan backup prune");
+                return BackupsPruned { deleted: 0, freed_bytes: 0, error: Some(format!("{e:#}")) };
+            }
+        };
+
+        let mut deleted = 0;
+        let mut freed_bytes = 0;
+        let mut failures = Vec::new();
+        for entry in &entries {
+            match self.delete_backup(Path::new(&entry.path)).await {
+                Ok(()) => {
+                    deleted += 1;
+                    freed_bytes += entry.total_size;
+                }
+                Err(e) => {
+                    error!(path = %entry.path, error = %format!("{e:#}"), "Failed to prune backup");
+                    failures.push(format!("{}: {e:#}", entry.name));
+                }
+            }
+        }
+        info!(deleted, freed_bytes, failed = failures.len(), "Pruned backups");
+        if deleted > 0 {
+            BackupsChanged {}.send_signal_to_dart();
+        }
+        BackupsPruned {
+            deleted,
+            freed_bytes,
+            error: (!failures.is_empty()).then(|| failures.join("\n")),
+        }
+    }
+
+    /// Deletes every backup in the backups directory, leaving other files alone. Returns how
+    /// many backups were deleted.
+    #[instrument(level = "debug", skip(self), err, ret)]
+    pub(crate) async fn delete_all_backups(&self) -> Result<u32> {
+        let _guard = self.prune_lock.lock().await;
+        let dir = self.backups_dir.read().await.clone();
+        if !fs::try_exists(&dir).await.unwrap_or(false) {
+            return Ok(0);
+        }
+        let mut deleted = 0;
+        for entry in self.list_backups().await? {
+            self.delete_backup(Path::new(&entry.path)).await?;
+            deleted += 1;
+        }
+        if deleted > 0 {
+            BackupsChanged {}.send_signal_to_dart();
+        }
+        Ok(deleted)
+    }
+
     #[instrument(level = "debug", skip(self), err)]
     async fn list_backups(&self) -> Result<Vec<BackupEntry>> {
         let dir = self.backups_dir.read().await.clone();
@@ -131,12 +364,25 @@ impl BackupsCatalog {
             .await
             .with_context(|| format!("Failed to read backups directory: {}", dir_path.display()))?;
         while let Some(entry) = rd.next_entry().await? {
-            if entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false) {
-                let candidate = entry.path();
-                if candidate.join(".backup").exists() {
-                    trace!(path = %candidate.display(), "Found backup candidate");
-                    if let Some(entry) = self.build_entry(&candidate).await? {
-                        entries.push(entry);
+            let Ok(file_type) = entry.file_type().await else {
+                continue;
+            };
+            let candidate = entry.path();
+            // Staging folders, such as archives being extracted for a restore
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            if file_type.is_dir() && candidate.join(".backup").exists() {
+                trace!(path = %candidate.display(), "Found backup candidate");
+                if let Some(entry) = self.build_entry(&candidate).await? {
+                    entries.push(entry);
+                }
+            } else if file_type.is_file() && is_backup_archive(&candidate) {
+                trace!(path = %candidate.display(), "Found backup archive candidate");
+                match build_archive_entry(&candidate).await {
+                    Ok(entry) => entries.push(entry),
+                    Err(e) => {
+                        warn!(path = %candidate.display(), error = %format!("{e:#}"), "Skipping unreadable backup archive")
                     }
                 }
             }
@@ -155,33 +401,7 @@ impl BackupsCatalog {
             .and_then(|n| n.to_str())
             .map(|s| s.to_string())
             .unwrap_or_else(|| dir.to_string_lossy().into_owned());
-
-        let mut timestamp = 0u64;
-        let mut display_name = name.clone();
-
-        // Parse prefix: YYYY-MM-DD_HH-MM-SS_...
-        if name.len() > 20 && name.as_bytes()[19] == b'_' {
-            let ts_str = &name[0..19];
-            display_name = name[20..].to_string();
-            let parts: Vec<&str> = ts_str.split(|c: char| !c.is_ascii_digit()).collect();
-            if parts.len() >= 6
-                && let (Ok(y), Ok(m), Ok(d), Ok(h), Ok(min), Ok(s)) = (
-                    parts[0].parse::<i32>(),
-                    parts[1].parse::<u32>(),
-                    parts[2].parse::<u32>(),
-                    parts[3].parse::<u32>(),
-                    parts[4].parse::<u32>(),
-                    parts[5].parse::<u32>(),
-                )
-            {
-                // Convert to unix millis using chrono-less approach
-                // Use time crate would be nicer, but avoid extra deps here
-                // Fallback to file mtime if conversion fails
-                // TODO: use time crate
-                // TODO: log errors
-                timestamp = datetime_to_unix_millis(y, m, d, h, min, s).unwrap_or(0);
-            }
-        }
+        let (display_name, mut timestamp) = parse_backup_name(&name);
 
         if timestamp == 0
             && let Ok(meta) = fs::metadata(dir).await
@@ -191,11 +411,30 @@ impl BackupsCatalog {
         }
 
         // Part flags (check existence quickly)
-        let has_apk = has_any_apk_immediate(dir).await?;
+        let has_apk = find_immediate_apk(dir).await?.is_some();
         let has_private_data = dir.join("data_private").exists();
         let has_shared_data = dir.join("data").exists();
         let has_obb = dir.join("obb").exists();
-        let total_size = dir_size(dir).await.unwrap_or(0);
+        let files = list_files_relative(dir).await.unwrap_or_default();
+        let total_size = files.iter().map(|(_, size)| size).sum();
+        let sizes =
+            BackupSizes::from_files(files.iter().map(|(path, size)| (path.as_str(), *size)));
+        let manifest = match fs::read(dir.join(BACKUP_MANIFEST_FILE)).await {
+            Ok(content) => BackupManifest::from_json(&content)
+                .inspect_err(
+                    |e| warn!(error = %format!("{e:#}"), "Ignoring unreadable backup manifest"),
+                )
+                .ok(),
+            Err(_) => backfill_manifest(dir, &display_name, timestamp, sizes)
+                .await
+                .inspect_err(|e| debug!(error = %format!("{e:#}"), "No manifest for legacy backup"))
+                .ok(),
+        };
+        if let Some(manifest) = &manifest
+            && manifest.created_at != 0
+        {
+            timestamp = manifest.created_at;
+        }
 
         trace!(
             name = %display_name,
@@ -208,15 +447,23 @@ impl BackupsCatalog {
             "Built backup entry"
         );
 
+        let manifest = manifest.as_ref();
         Ok(Some(BackupEntry {
             path: dir.to_string_lossy().to_string(),
             name: display_name,
+            package_name: manifest.map(|m| m.package_name.clone()),
+            display_name: manifest.and_then(|m| m.display_name.clone()),
+            version_code: manifest.and_then(|m| m.version_code),
+            version_name: manifest.and_then(|m| m.version_name.clone()),
+            device_serial: manifest.and_then(|m| m.device_serial.clone()),
             timestamp,
             total_size,
+            sizes,
             has_apk,
             has_private_data,
             has_shared_data,
             has_obb,
+            compressed: false,
         }))
     }
 
@@ -230,6 +477,12 @@ impl BackupsCatalog {
         debug!(root = %canon_root.display(), target = %canon_req.display(), "Canonicalized paths for deletion");
 
         ensure!(canon_req.starts_with(&canon_root), "Requested path is outside backups directory");
+        if canon_req.is_file() {
+            ensure!(is_backup_archive(&canon_req), "Backup file is not a .yaasbackup archive");
+            info!(path = %canon_req.display(), "Deleting backup archive");
+            fs::remove_file(&canon_req).await.context("Failed to delete backup archive")?;
+            return Ok(());
+        }
         ensure!(canon_req.is_dir(), "Backup path is not a directory");
         ensure!(canon_req.join(".backup").exists(), "Backup marker not found (.backup)");
 
@@ -239,6 +492,423 @@ impl BackupsCatalog {
     }
 }
 
+/// Builds a list entry for a `.yaasbackup` archive from the manifest stored in it
+#[instrument(level = "debug", err)]
+async fn build_archive_entry(path: &Path) -> Result<BackupEntry> {
+    let entries = list_archive_entries(path).await?;
+    let manifest_entry = entries
+        .iter()
+        .find(|e| {
+            e.path == BACKUP_MANIFEST_FILE
+                || e.path.split_once('/').is_some_and(|(_, rest)| rest == BACKUP_MANIFEST_FILE)
+        })
+        .context("Backup archive has no manifest")?;
+
+    let mut reader = ArchiveEntryReader::open(path, &manifest_entry.path)?;
+    let mut content = Vec::new();
+    reader.reader().read_to_end(&mut content).await.context("Failed to read backup manifest")?;
+    reader.finish().await?;
+    let manifest = BackupManifest::from_json(&content)?;
+
+    let stem = path.file_stem().and_then(|n| n.to_str()).unwrap_or_default();
+    let (display_name, _) = parse_backup_name(stem);
+    let total_size = fs::metadata(path).await.map(|m| m.len()).unwrap_or(0);
+    let sizes = if manifest.sizes == BackupSizes::default() {
+        let root = manifest_entry.path.strip_suffix(BACKUP_MANIFEST_FILE).unwrap_or_default();
+        BackupSizes::from_files(
+            entries.iter().filter_map(|e| Some((e.path.strip_prefix(root)?, e.size))),
+        )
+    } else {
+        manifest.sizes
+    };
+    Ok(BackupEntry {
+        path: path.to_string_lossy().to_string(),
+        name: display_name,
+        package_name: Some(manifest.package_name),
+        display_name: manifest.display_name,
+        version_code: manifest.version_code,
+        version_name: manifest.version_name,
+        device_serial: manifest.device_serial,
+        timestamp: manifest.created_at,
+        total_size,
+        sizes,
+        has_apk: manifest.contents.apk,
+        has_private_data: manifest.contents.private_data,
+        has_shared_data: manifest.contents.shared_data,
+        has_obb: manifest.contents.obb,
+        compressed: true,
+    })
+}
+
+/// Writes a manifest into a legacy backup directory that has none, detecting its package from the
+/// data directories or the APK.
+///
+/// The file list is left empty since the files can no longer be vouched for.
+#[instrument(level = "debug", skip(sizes), err)]
+async fn backfill_manifest(
+    dir: &Path,
+    name: &str,
+    timestamp: u64,
+    sizes: BackupSizes,
+) -> Result<BackupManifest> {
+    ensure!(dir.join(".backup").exists(), "Backup marker not found (.backup)");
+    let mut package_name = None;
+    for part in ["data_private", "data", "obb"] {
+        if let Some(subdir) = first_subdirectory(&dir.join(part)).await?
+            && let Some(name) = subdir.file_name().and_then(|n| n.to_str())
+        {
+            package_name = Some(name.to_string());
+            break;
+        }
+    }
+    let (mut version_code, mut version_name) = (None, None);
+    if let Some(apk) = find_immediate_apk(dir).await? {
+        let info = tokio::task::spawn_blocking(move || get_apk_info(apk)).await??;
+        version_code = info.version_code.map(u64::from);
+        version_name = info.version_name;
+        package_name.get_or_insert(info.package_name);
+    }
+    let package_name = package_name.context("Could not determine the package of the backup")?;
+
+    let manifest = BackupManifest {
+        package_name,
+        display_name: Some(name.to_string()),
+        version_code,
+        version_name,
+        created_at: timestamp,
+        device_serial: None,
+        contents: BackupContents {
+            apk: sizes.apk > 0,
+            private_data: sizes.private_data > 0,
+            shared_data: sizes.shared_data > 0,
+            obb: sizes.obb > 0,
+        },
+        sizes,
+        files: Vec::new(),
+    };
+    fs::write(dir.join(BACKUP_MANIFEST_FILE), manifest.to_json()?)
+        .await
+        .context("Failed to write backup manifest")?;
+    info!(dir = %dir.display(), package = manifest.package_name, "Wrote manifest for legacy backup");
+    Ok(manifest)
+}
+
+/// Outcome of checking a backup against its manifest
+#[derive(Debug, Default, PartialEq, Eq)]
+struct VerifyReport {
+    has_file_list: bool,
+    checked_files: u32,
+    problems: Vec<String>,
+}
+
+/// Where the files of a backup are read from
+enum BackupSource<'a> {
+    Dir(&'a Path),
+    /// `.yaasbackup` archive, with the prefix of the backup root inside it
+    Archive(&'a Path, String),
+}
+
+/// Files found in a backup, keyed by their path relative to the backup root
+#[derive(Debug, Default)]
+struct BackupListing {
+    has_marker: bool,
+    has_manifest: bool,
+    /// Sizes of all files except the marker and the manifest
+    files: BTreeMap<String, u64>,
+}
+
+impl BackupListing {
+    fn from_files(files: impl IntoIterator<Item = (String, u64)>) -> Self {
+        let mut listing = Self::default();
+        for (path, size) in files {
+            match path.as_str() {
+                ".backup" => listing.has_marker = true,
+                BACKUP_MANIFEST_FILE => listing.has_manifest = true,
+                _ => {
+                    listing.files.insert(path, size);
+                }
+            }
+        }
+        listing
+    }
+}
+
+/// Checks that a backup directory or `.yaasbackup` archive has its marker, contains the files its
+/// manifest lists with the recorded sizes and hashes, and nothing else
+#[instrument(level = "debug", err)]
+async fn verify_backup(path: &Path) -> Result<VerifyReport> {
+    let (source, listing) = if path.is_file() {
+        ensure!(is_backup_archive(path), "Backup file is not a .yaasbackup archive");
+        let entries = list_archive_entries(path).await?;
+        // The backup root may be a directory inside the archive
+        let prefix = entries
+            .iter()
+            .find_map(|e| {
+                let (prefix, name) = match e.path.split_once('/') {
+                    Some((dir, rest)) => (format!("{dir}/"), rest),
+                    None => (String::new(), e.path.as_str()),
+                };
+                (name == BACKUP_MANIFEST_FILE || name == ".backup").then_some(prefix)
+            })
+            .unwrap_or_default();
+        let files = entries
+            .into_iter()
+            .filter_map(|e| Some((e.path.strip_prefix(prefix.as_str())?.to_string(), e.size)));
+        let listing = BackupListing::from_files(files);
+        (BackupSource::Archive(path, prefix), listing)
+    } else {
+        ensure!(path.is_dir(), "Backup not found: {}", path.display());
+        let listing = BackupListing::from_files(list_files_relative(path).await?);
+        (BackupSource::Dir(path), listing)
+    };
+    let manifest = if listing.has_manifest {
+        let content = read_backup_file(&source, BACKUP_MANIFEST_FILE).await;
+        Some(content.and_then(|content| BackupManifest::from_json(&content)))
+    } else {
+        None
+    };
+
+    let (mut report, to_hash) = compare_with_manifest(&listing, manifest.as_ref());
+    for (file, expected) in to_hash {
+        match read_backup_file_md5(&source, &file.path).await {
+            Ok(actual) if actual.eq_ignore_ascii_case(expected) => {}
+            Ok(_) => {
+                report.problems.push(format!("{} does not match its recorded hash", file.path))
+            }
+            Err(e) => report.problems.push(format!("Failed to read {}: {e:#}", file.path)),
+        }
+    }
+    Ok(report)
+}
+
+/// Compares the files of a backup with its manifest, returning the report so far and the files
+/// whose recorded hashes still need to be checked
+fn compare_with_manifest<'a>(
+    listing: &BackupListing,
+    manifest: Option<&'a Result<BackupManifest>>,
+) -> (VerifyReport, Vec<(&'a BackupFile, &'a str)>) {
+    let mut report = VerifyReport::default();
+    if !listing.has_marker {
+        report.problems.push("Backup marker (.backup) is missing".to_string());
+    }
+    let manifest = match manifest {
+        Some(Ok(manifest)) => manifest,
+        Some(Err(e)) => {
+            report.problems.push(format!("Manifest is unreadable: {e:#}"));
+            return (report, Vec::new());
+        }
+        None => {
+            if listing.files.is_empty() {
+                report.problems.push("Backup contains no files".to_string());
+            }
+            return (report, Vec::new());
+        }
+    };
+
+    let has_files_under = |prefix: &str| listing.files.keys().any(|path| path.starts_with(prefix));
+    let has_apk = listing.files.keys().any(|path| {
+        !path.contains('/')
+            && Path::new(path).extension().is_some_and(|e| e.eq_ignore_ascii_case("apk"))
+    });
+    let contents = &manifest.contents;
+    for (listed, present, part) in [
+        (contents.apk, has_apk, "APK"),
+        (contents.private_data, has_files_under("data_private/"), "private data"),
+        (contents.shared_data, has_files_under("data/"), "shared data"),
+        (contents.obb, has_files_under("obb/"), "OBB files"),
+    ] {
+        if listed && !present {
+            report.problems.push(format!("Manifest lists {part} but the backup has none"));
+        }
+    }
+
+    report.has_file_list = !manifest.files.is_empty();
+    report.checked_files = manifest.files.len() as u32;
+    let mut to_hash = Vec::new();
+    for file in &manifest.files {
+        match listing.files.get(&file.path) {
+            None => report.problems.push(format!("Missing file: {}", file.path)),
+            Some(&size) if size != file.size => report
+                .problems
+                .push(format!("{} is {size} bytes, {} bytes were backed up", file.path, file.size)),
+            Some(_) => {
+                if let Some(md5) = &file.md5 {
+                    to_hash.push((file, md5.as_str()));
+                }
+            }
+        }
+    }
+    if report.has_file_list {
+        let listed: HashSet<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        for path in listing.files.keys().filter(|path| !listed.contains(path.as_str())) {
+            report.problems.push(format!("Unexpected file: {path}"));
+        }
+    }
+    (report, to_hash)
+}
+
+/// Reads a whole file of a backup
+async fn read_backup_file(source: &BackupSource<'_>, path: &str) -> Result<Vec<u8>> {
+    match source {
+        BackupSource::Dir(dir) => fs::read(dir.join(path))
+            .await
+            .with_context(|| format!("Failed to read {path} from backup")),
+        BackupSource::Archive(archive, prefix) => {
+            let mut reader = ArchiveEntryReader::open(archive, &format!("{prefix}{path}"))?;
+            let mut content = Vec::new();
+            reader
+                .reader()
+                .read_to_end(&mut content)
+                .await
+                .with_context(|| format!("Failed to read {path} from backup"))?;
+            reader.finish().await?;
+            Ok(content)
+        }
+    }
+}
+
+/// Computes the MD5 hash of a file of a backup, as lowercase hex
+async fn read_backup_file_md5(source: &BackupSource<'_>, path: &str) -> Result<String> {
+    match source {
+        BackupSource::Dir(dir) => compute_md5_file(&dir.join(path)).await,
+        BackupSource::Archive(archive, prefix) => {
+            let mut reader = ArchiveEntryReader::open(archive, &format!("{prefix}{path}"))?;
+            let mut buf = vec![0u8; 1024 * 64];
+            let mut ctx = md5::Context::new();
+            loop {
+                let n = reader.reader().read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+                ctx.consume(&buf[..n]);
+            }
+            reader.finish().await?;
+            Ok(format!("{:x}", ctx.finalize()))
+        }
+    }
+}
+/// Filters and sorts listed backups by a query from the UI
+fn query_backups(entries: Vec<BackupEntry>, query: &BackupsQuery) -> GetBackupsResponse {
+    let total_count = entries.len() as u32;
+    let terms: Vec<String> =
+        query.search.split_whitespace().map(|term| term.to_lowercase()).collect();
+    let mut matches: Vec<BackupEntry> = entries
+        .into_iter()
+        .filter(|entry| {
+            let haystack =
+                [Some(&entry.name), entry.display_name.as_ref(), entry.package_name.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .map(|s| s.to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            terms.iter().all(|term| haystack.contains(term.as_str()))
+        })
+        .filter(|entry| query.since.is_none_or(|since| entry.timestamp >= since))
+        .filter(|entry| query.until.is_none_or(|until| entry.timestamp < until))
+        .filter(|entry| !query.require_apk || entry.has_apk)
+        .filter(|entry| !query.require_data || entry.has_private_data || entry.has_shared_data)
+        .filter(|entry| !query.require_obb || entry.has_obb)
+        .collect();
+
+    matches.sort_by(|a, b| {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let ordering = match query.sort {
+            BackupsSortOrder::Date => a.timestamp.cmp(&b.timestamp).then_with(by_name),
+            BackupsSortOrder::Size => a.total_size.cmp(&b.total_size).then_with(by_name),
+        };
+        if query.ascending { ordering } else { ordering.reverse() }
+    });
+
+    GetBackupsResponse {
+        search: query.search.clone(),
+        total_size: matches.iter().map(|entry| entry.total_size).sum(),
+        entries: matches,
+        total_count,
+        error: None,
+    }
+}
+
+/// Picks the backups to prune under `retention`, oldest first.
+///
+/// The newest backup of each app is always kept. Older ones are pruned past the per-app count
+/// and the age limit, then oldest first until the rest fits into the total size limit.
+fn plan_prune(
+    entries: &[BackupEntry],
+    retention: &BackupRetention,
+    now_millis: u64,
+) -> Vec<BackupEntry> {
+    let mut newest_first: Vec<&BackupEntry> = entries.iter().collect();
+    newest_first.sort_by_key(|e| Reverse(e.timestamp));
+
+    let mut seen_per_package: HashMap<&str, u32> = HashMap::new();
+    let mut kept_size = 0u64;
+    // Kept backups that may still go for the size limit, newest first
+    let mut prunable = Vec::new();
+    let mut pruned = Vec::new();
+    for entry in newest_first {
+        let key = entry.package_name.as_deref().unwrap_or(&entry.name);
+        let seen = seen_per_package.entry(key).or_default();
+        *seen += 1;
+        if *seen == 1 {
+            kept_size += entry.total_size;
+            continue;
+        }
+
+        let over_count = retention.max_per_package > 0 && *seen > retention.max_per_package;
+        let too_old = retention.max_age_millis > 0
+            && entry.timestamp != 0
+            && now_millis.saturating_sub(entry.timestamp) > retention.max_age_millis;
+        if over_count || too_old {
+            pruned.push(entry);
+        } else {
+            kept_size += entry.total_size;
+            prunable.push(entry);
+        }
+    }
+
+    if retention.max_total_bytes > 0 {
+        while kept_size > retention.max_total_bytes
+            && let Some(entry) = prunable.pop()
+        {
+            kept_size -= entry.total_size;
+            pruned.push(entry);
+        }
+    }
+
+    pruned.sort_by_key(|e| e.timestamp);
+    pruned.into_iter().cloned().collect()
+}
+
+/// Splits a backup name into the display name and the timestamp of its
+/// `YYYY-MM-DD_HH-MM-SS_` prefix (0 if there is none)
+fn parse_backup_name(name: &str) -> (String, u64) {
+    if name.len() > 20 && name.as_bytes()[19] == b'_' {
+        let ts_str = &name[0..19];
+        let parts: Vec<&str> = ts_str.split(|c: char| !c.is_ascii_digit()).collect();
+        let timestamp = if parts.len() >= 6
+            && let (Ok(y), Ok(m), Ok(d), Ok(h), Ok(min), Ok(s)) = (
+                parts[0].parse::<i32>(),
+                parts[1].parse::<u32>(),
+                parts[2].parse::<u32>(),
+                parts[3].parse::<u32>(),
+                parts[4].parse::<u32>(),
+                parts[5].parse::<u32>(),
+            ) {
+            // Convert to unix millis using chrono-less approach
+            // Use time crate would be nicer, but avoid extra deps here
+            // Fallback to file mtime if conversion fails
+            // TODO: use time crate
+            // TODO: log errors
+            datetime_to_unix_millis(y, m, d, h, min, s).unwrap_or(0)
+        } else {
+            0
+        };
+        return (name[20..].to_string(), timestamp);
+    }
+    (name.to_string(), 0)
+}
+
 fn system_time_to_millis(time: SystemTime) -> u64 {
     time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
 }
@@ -255,43 +925,195 @@ fn datetime_to_unix_millis(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) ->
 }
 
 #[instrument(level = "debug", err)]
-async fn has_any_apk_immediate(dir: &Path) -> Result<bool> {
+async fn find_immediate_apk(dir: &Path) -> Result<Option<PathBuf>> {
     let mut rd = fs::read_dir(dir).await?;
     while let Some(entry) = rd.next_entry().await? {
         let p = entry.path();
         if entry.file_type().await?.is_file()
             && p.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("apk"))
         {
-            return Ok(true);
+            return Ok(Some(p));
         }
     }
-    Ok(false)
+    Ok(None)
 }
 
-#[instrument(level = "debug", fields(dir = %dir.display(), size), err)]
-async fn dir_size(dir: &Path) -> Result<u64> {
-    if !dir.is_dir() {
-        return Ok(0);
-    }
-    let mut total: u64 = 0;
-    let mut stack: Vec<PathBuf> = vec![dir.to_path_buf()];
-    while let Some(path) = stack.pop() {
-        let mut rd = match fs::read_dir(&path).await {
-            Ok(r) => r,
-            Err(_) => continue,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * 60 * 60 * 1000;
+
+    fn entry(name: &str, package: Option<&str>, timestamp: u64, total_size: u64) -> BackupEntry {
+        BackupEntry {
+            path: format!("/backups/{timestamp}_{name}"),
+            name: name.to_string(),
+            package_name: package.map(str::to_string),
+            display_name: None,
+            version_code: None,
+            version_name: None,
+            device_serial: None,
+            timestamp,
+            total_size,
+            sizes: BackupSizes::default(),
+            has_apk: true,
+            has_private_data: false,
+            has_shared_data: false,
+            has_obb: false,
+            compressed: false,
+        }
+    }
+
+    fn pruned_paths(entries: &[BackupEntry], retention: &BackupRetention, now: u64) -> Vec<String> {
+        plan_prune(entries, retention, now).into_iter().map(|e| e.path).collect()
+    }
+
+    #[test]
+    fn prunes_by_count_age_and_size_keeping_newest_per_app() {
+        let now = 100 * DAY;
+        let entries = vec![
+            entry("Beat Saber", Some("com.beatgames.beatsaber"), 99 * DAY, 10),
+            entry("Beat Saber_modded", Some("com.beatgames.beatsaber"), 98 * DAY, 10),
+            entry("Beat Saber", Some("com.beatgames.beatsaber"), 90 * DAY, 10),
+            entry("Old Game", None, 10 * DAY, 50),
+            entry("Old Game", None, 5 * DAY, 50),
+        ];
+
+        assert!(pruned_paths(&entries, &BackupRetention::default(), now).is_empty());
+
+        let by_count = BackupRetention { max_per_package: 2, ..Default::default() };
+        assert_eq!(pruned_paths(&entries, &by_count, now), vec![entries[2].path.clone()]);
+
+        // The only other backup of "Old Game" is too old, its newest one stays
+        let by_age = BackupRetention { max_age_millis: 30 * DAY, ..Default::default() };
+        assert_eq!(pruned_paths(&entries, &by_age, now), vec![entries[4].path.clone()]);
+
+        let by_size = BackupRetention { max_total_bytes: 75, ..Default::default() };
+        assert_eq!(
+            pruned_paths(&entries, &by_size, now),
+            vec![entries[4].path.clone(), entries[2].path.clone()]
+        );
+    }
+
+    #[test]
+    fn query_filters_sorts_and_sums_backups() {
+        let mut entries = vec![
+            entry("Beat Saber", Some("com.beatgames.beatsaber"), 3 * DAY, 30),
+            entry("Beat Saber", Some("com.beatgames.beatsaber"), DAY, 10),
+            entry("Old Game", None, 2 * DAY, 50),
+        ];
+        entries[0].has_obb = true;
+        entries[2].has_apk = false;
+        entries[2].has_shared_data = true;
+        let days = |response: &GetBackupsResponse| {
+            response.entries.iter().map(|e| e.timestamp / DAY).collect::<Vec<_>>()
         };
-        while let Some(entry) = rd.next_entry().await? {
-            let meta = match entry.metadata().await {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-            if meta.is_file() {
-                total = total.saturating_add(meta.len());
-            } else if meta.is_dir() {
-                stack.push(entry.path());
-            }
+
+        let all = query_backups(entries.clone(), &BackupsQuery::default());
+        assert_eq!(days(&all), vec![3, 2, 1]);
+        assert_eq!((all.total_count, all.total_size), (3, 90));
+
+        let by_package = BackupsQuery { search: "BEATGAMES".into(), ..Default::default() };
+        let response = query_backups(entries.clone(), &by_package);
+        assert_eq!(days(&response), vec![3, 1]);
+        assert_eq!((response.total_count, response.total_size), (3, 40));
+
+        let by_size =
+            BackupsQuery { sort: BackupsSortOrder::Size, ascending: true, ..Default::default() };
+        assert_eq!(days(&query_backups(entries.clone(), &by_size)), vec![1, 3, 2]);
+
+        let dated =
+            BackupsQuery { since: Some(DAY + 1), until: Some(3 * DAY), ..Default::default() };
+        assert_eq!(days(&query_backups(entries.clone(), &dated)), vec![2]);
+
+        let with_apk = BackupsQuery { require_apk: true, ..Default::default() };
+        assert_eq!(days(&query_backups(entries.clone(), &with_apk)), vec![3, 1]);
+        let with_data = BackupsQuery { require_data: true, ..Default::default() };
+        assert_eq!(days(&query_backups(entries.clone(), &with_data)), vec![2]);
+        let with_obb = BackupsQuery { require_obb: true, ..Default::default() };
+        assert_eq!(days(&query_backups(entries, &with_obb)), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn verify_backup_reports_mismatches() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup = dir.path();
+        fs::write(backup.join("com.example.game.apk"), b"apk").await.unwrap();
+        fs::create_dir_all(backup.join("data_private/files")).await.unwrap();
+        fs::write(backup.join("data_private/files/save.dat"), b"save").await.unwrap();
+        fs::write(backup.join("data_private/files/config.ini"), b"config").await.unwrap();
+        fs::write(backup.join(".backup"), b"").await.unwrap();
+
+        let files = list_files_relative(backup).await.unwrap();
+        let mut recorded = Vec::new();
+        for (path, size) in files.into_iter().filter(|(path, _)| path != ".backup") {
+            let md5 = compute_md5_file(&backup.join(&path)).await.unwrap();
+            recorded.push(BackupFile { path, size, md5: Some(md5) });
         }
+        let manifest = BackupManifest {
+            package_name: "com.example.game".into(),
+            display_name: None,
+            version_code: None,
+            version_name: None,
+            created_at: 1,
+            device_serial: None,
+            contents: BackupContents {
+                apk: true,
+                private_data: true,
+                obb: true,
+                ..Default::default()
+            },
+            sizes: BackupSizes::default(),
+            files: recorded,
+        };
+        fs::write(backup.join(BACKUP_MANIFEST_FILE), manifest.to_json().unwrap()).await.unwrap();
+
+        let report = verify_backup(backup).await.unwrap();
+        assert!(report.has_file_list);
+        assert_eq!(report.checked_files, 3);
+        assert_eq!(report.problems, vec!["Manifest lists OBB files but the backup has none"]);
+
+        fs::write(backup.join("data_private/files/save.dat"), b"SAVE").await.unwrap();
+        fs::write(backup.join("data_private/files/config.ini"), b"changed").await.unwrap();
+        fs::remove_file(backup.join("com.example.game.apk")).await.unwrap();
+        fs::write(backup.join("extra.txt"), b"extra").await.unwrap();
+        fs::remove_file(backup.join(".backup")).await.unwrap();
+        let report = verify_backup(backup).await.unwrap();
+        assert_eq!(
+            report.problems,
+            vec![
+                "Backup marker (.backup) is missing",
+                "Manifest lists APK but the backup has none",
+                "Manifest lists OBB files but the backup has none",
+                "Missing file: com.example.game.apk",
+                "data_private/files/config.ini is 7 bytes, 6 bytes were backed up",
+                "Unexpected file: extra.txt",
+                "data_private/files/save.dat does not match its recorded hash",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn backfills_manifest_of_legacy_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup = dir.path();
+        fs::create_dir_all(backup.join("data_private/com.example.game/files")).await.unwrap();
+        fs::write(backup.join("data_private/com.example.game/files/save.dat"), b"save")
+            .await
+            .unwrap();
+        let sizes = BackupSizes { private_data: 4, ..Default::default() };
+
+        assert!(backfill_manifest(backup, "Game", 5, sizes).await.is_err());
+
+        fs::write(backup.join(".backup"), b"").await.unwrap();
+        let manifest = backfill_manifest(backup, "Game", 5, sizes).await.unwrap();
+        assert_eq!(manifest.package_name, "com.example.game");
+        assert_eq!(manifest.display_name.as_deref(), Some("Game"));
+        assert_eq!(manifest.created_at, 5);
+        assert_eq!(manifest.contents, BackupContents { private_data: true, ..Default::default() });
+        assert!(manifest.files.is_empty());
+
+        let written = fs::read(backup.join(BACKUP_MANIFEST_FILE)).await.unwrap();
+        assert_eq!(BackupManifest::from_json(&written).unwrap(), manifest);
     }
-    Span::current().record("size", total);
-    Ok(total)
 }
@@ -0,0 +1,230 @@
+//! Runs YAAS tasks from the command line, without the Flutter UI.
+//!
+//! Uses the same app data, settings and downloader configuration as the desktop app.
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::{Context, Result, bail};
+use hub::headless::{Headless, HeadlessTask, TaskUpdate};
+
+const USAGE: &str = "\
+Usage: yaas-cli [--app-dir <dir>] [--portable] [--approve-scripts] <command> [args]
+
+Install scripts of apps are declined unless --approve-scripts is given.
+
+Commands:
+  list-devices                 List devices known to the ADB server
+  download <app> [--install]   Download a catalog app by full name or package name
+  install <path>               Install an APK or APK bundle, an app folder or an archive of one
+  backup <package> [--no-apk] [--no-data] [--no-obb] [--compress] [--name <suffix>]
+                               Back up an installed app";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Cli {
+    app_dir: Option<PathBuf>,
+    portable: bool,
+    approve_scripts: bool,
+    command: Command,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Help,
+    ListDevices,
+    Task(HeadlessTask),
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Cli> {
+    let mut args = args.into_iter();
+    let mut app_dir = None;
+    let mut portable = false;
+    let mut approve_scripts = false;
+
+    let command = loop {
+        match args.next().as_deref() {
+            Some("--app-dir") => {
+                app_dir = Some(PathBuf::from(args.next().context("--app-dir needs a value")?));
+            }
+            Some("--portable") => portable = true,
+            Some("--approve-scripts") => approve_scripts = true,
+            Some("-h" | "--help") | None => {
+                return Ok(Cli { app_dir, portable, approve_scripts, command: Command::Help });
+            }
+            Some(command) => break command.to_string(),
+        }
+    };
+
+    let mut positional = Vec::new();
+    let mut flags = Vec::new();
+    let mut name_append = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" if command == "backup" => {
+                name_append = Some(args.next().context("--name needs a value")?);
+            }
+            "-h" | "--help" => {
+                return Ok(Cli { app_dir, portable, approve_scripts, command: Command::Help });
+            }
+            flag if flag.starts_with("--") => flags.push(arg),
+            _ => positional.push(arg),
+        }
+    }
+    let allowed_flags: &[&str] = match command.as_str() {
+        "download" => &["--install"],
+        "backup" => &["--no-apk", "--no-data", "--no-obb", "--compress"],
+        _ => &[],
+    };
+    if let Some(flag) = flags.iter().find(|flag| !allowed_flags.contains(&flag.as_str())) {
+        bail!("Unknown option for {command}: {flag}");
+    }
+    let has_flag = |name: &str| flags.iter().any(|flag| flag == name);
+
+    let command = match (command.as_str(), positional.as_slice()) {
+        ("list-devices", []) => Command::ListDevices,
+        ("download", [app]) => Command::Task(HeadlessTask::Download {
+            app: app.clone(),
+            install: has_flag("--install"),
+        }),
+        ("install", [path]) => Command::Task(HeadlessTask::Install { path: PathBuf::from(path) }),
+        ("backup", [package_name]) => Command::Task(HeadlessTask::Backup {
+            package_name: package_name.clone(),
+            backup_apk: !has_flag("--no-apk"),
+            backup_data: !has_flag("--no-data"),
+            backup_obb: !has_flag("--no-obb"),
+            name_append,
+            compress: has_flag("--compress"),
+        }),
+        ("list-devices" | "download" | "install" | "backup", _) => {
+            bail!("Wrong number of arguments for {command}")
+        }
+        _ => bail!("Unknown command: {command}"),
+    };
+    Ok(Cli { app_dir, portable, approve_scripts, command })
+}
+
+fn main() -> ExitCode {
+    let cli = match parse_args(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{e:#}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    if cli.command == Command::Help {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build tokio runtime");
+    match runtime.block_on(run(cli)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let headless = Headless::start(cli.app_dir, cli.portable).await?;
+    if cli.approve_scripts {
+        headless.approve_install_scripts();
+    }
+    let result = match cli.command {
+        Command::Help => Ok(()),
+        Command::ListDevices => list_devices(&headless).await,
+        Command::Task(task) => run_task(&headless, task).await,
+    };
+    headless.shutdown().await;
+    result
+}
+
+async fn list_devices(headless: &Headless) -> Result<()> {
+    let devices = headless.list_devices().await?;
+    if devices.is_empty() {
+        eprintln!("No devices found");
+    }
+    for device in devices {
+        let connection = if device.is_wireless { "wireless" } else { "usb" };
+        let name = device.name.as_deref().unwrap_or("-");
+        println!("{}\t{}\t{connection}\t{name}", device.serial, device.state);
+    }
+    Ok(())
+}
+
+async fn run_task(headless: &Headless, task: HeadlessTask) -> Result<()> {
+    let mut last_line = String::new();
+    let outcome = headless
+        .run(task, |update: &TaskUpdate| {
+            let mut line = format!(
+                "[{}/{}] {:>3}% {}",
+                update.step,
+                update.total_steps,
+                (update.progress * 100.0).round() as u32,
+                update.message
+            );
+            if let Some(speed) = update.speed {
+                line += &format!(" - {}/s", humansize::format_size(speed, humansize::DECIMAL));
+            }
+            // Transfers report many times per second, only print what changed
+            if line != last_line {
+                eprintln!("{line}");
+                last_line = line;
+            }
+        })
+        .await?;
+
+    eprintln!("Done: {}", outcome.name.as_deref().unwrap_or("task"));
+    for artifact in outcome.artifacts {
+        println!("{}", artifact.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_commands_and_options() {
+        assert_eq!(
+            parse(&["--app-dir", "/tmp/yaas", "download", "com.example.game", "--install"])
+                .unwrap(),
+            Cli {
+                app_dir: Some(PathBuf::from("/tmp/yaas")),
+                portable: false,
+                approve_scripts: false,
+                command: Command::Task(HeadlessTask::Download {
+                    app: "com.example.game".to_string(),
+                    install: true
+                }),
+            }
+        );
+        assert_eq!(
+            parse(&["backup", "com.example.game", "--no-obb", "--name", "before update"])
+                .unwrap()
+                .command,
+            Command::Task(HeadlessTask::Backup {
+                package_name: "com.example.game".to_string(),
+                backup_apk: true,
+                backup_data: true,
+                backup_obb: false,
+                name_append: Some("before update".to_string()),
+                compress: false,
+            })
+        );
+        assert_eq!(parse(&["--portable"]).unwrap().command, Command::Help);
+        assert!(parse(&["--approve-scripts", "install", "game"]).unwrap().approve_scripts);
+
+        assert!(parse(&["install"]).is_err());
+        assert!(parse(&["install", "app.apk", "--install"]).is_err());
+        assert!(parse(&["uninstall", "com.example.game"]).is_err());
+    }
+}
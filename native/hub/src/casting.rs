@@ -2,16 +2,18 @@ use std::{
     fs::File,
     io,
     path::{Path, PathBuf},
+    sync::{LazyLock, atomic::AtomicUsize},
 };
 
 #[allow(unused)]
 use anyhow::{Context, Result, anyhow, bail};
 use futures::StreamExt;
 use rinf::{DartSignal, RustSignal};
-use tokio::{fs, io::AsyncWriteExt};
+use tokio::{fs, io::AsyncWriteExt, sync::watch};
 use tracing::{info, instrument};
 
 use crate::{
+    downloader::tls,
     models::signals::casting::{
         CastingDownloadProgress, CastingStatusChanged, DownloadCastingBundleRequest,
         GetCastingStatusRequest,
@@ -19,6 +21,12 @@ use crate::{
     utils::remove_child_dir_if_exists,
 };
 
+/// Number of running casting sessions launched by the app
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+static SESSION_COUNT: AtomicUsize = AtomicUsize::new(0);
+/// Whether any casting session launched by the app is running
+static SESSION_ACTIVE: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::Sender::new(false));
+
 const CASTING_URL: &str =
     "https://github.com/skrimix/yaas/releases/download/files/casting-bundle.zip";
 
@@ -50,7 +58,7 @@ async fn download_casting_bundle(app_dir: &Path) -> Result<()> {
     info!(url, path = %target_zip.display(), "Downloading casting bundle");
 
     let client = {
-        let mut builder = reqwest::Client::builder().use_rustls_tls().user_agent(crate::USER_AGENT);
+        let mut builder = tls::http_client_builder(tls::ServerPins::default())?.use_rustls_tls();
         if let Some(proxy) = crate::utils::get_sys_proxy() {
             builder = builder.proxy(reqwest::Proxy::all(&proxy)?);
         }
@@ -111,6 +119,11 @@ fn unzip_to_dir(zip_path: &Path, output_dir: &Path) -> Result<()> {
 pub(crate) struct CastingManager;
 
 impl CastingManager {
+    /// Create a receiver for casting session start and stop
+    pub(crate) fn subscribe_session() -> watch::Receiver<bool> {
+        SESSION_ACTIVE.subscribe()
+    }
+
     pub(crate) fn start(app_dir: PathBuf) {
         // Status requests
         let status_app_dir = app_dir.clone();
@@ -148,7 +161,7 @@ impl CastingManager {
         device_serial: &str,
         wireless: bool,
     ) -> Result<()> {
-        use std::path::PathBuf;
+        use std::{path::PathBuf, sync::atomic::Ordering};
 
         use tokio::process::Command as TokioCommand;
 
@@ -204,7 +217,19 @@ impl CastingManager {
         }
 
         match cmd.spawn() {
-            Ok(_child) => Ok(()),
+            Ok(mut child) => {
+                SESSION_COUNT.fetch_add(1, Ordering::SeqCst);
+                SESSION_ACTIVE.send_replace(true);
+                info!("Casting session started");
+                tokio::spawn(async move {
+                    let status = child.wait().await;
+                    info!(?status, "Casting session ended");
+                    if SESSION_COUNT.fetch_sub(1, Ordering::SeqCst) == 1 {
+                        SESSION_ACTIVE.send_replace(false);
+                    }
+                });
+                Ok(())
+            }
             Err(e) => {
                 Toast::send("Failed to launch Casting".to_string(), format!("{:#}", e), true, None);
                 bail!("Failed to launch Casting");
@@ -0,0 +1,204 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, ensure};
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, instrument, warn};
+
+use crate::models::signals::device_log::*;
+
+/// Oldest entries of a device are dropped once its log grows past this
+const MAX_ENTRIES_PER_DEVICE: usize = 500;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedLogs {
+    /// Entries keyed by true device serial, oldest first
+    #[serde(default)]
+    devices: BTreeMap<String, Vec<DeviceLogEntry>>,
+}
+
+/// Persists per-headset maintenance logs with user notes and major operations
+#[derive(Debug)]
+pub(crate) struct DeviceLog {
+    file_path: PathBuf,
+    logs: Mutex<PersistedLogs>,
+}
+
+impl DeviceLog {
+    /// Loads the device logs file from the app directory, falling back to empty logs.
+    #[instrument(level = "debug")]
+    pub(crate) fn load(app_dir: PathBuf) -> Self {
+        let file_path = app_dir.join("device_logs.json");
+        let logs = if file_path.exists() {
+            Self::read_logs(&file_path).unwrap_or_else(|e| {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to load device logs, using empty");
+                PersistedLogs::default()
+            })
+        } else {
+            PersistedLogs::default()
+        };
+        debug!(devices = logs.devices.len(), "Loaded device logs");
+
+        Self { file_path, logs: Mutex::new(logs) }
+    }
+
+    /// Starts handling device log requests from Dart.
+    pub(crate) fn start(self) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        handle
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let get_receiver = GetDeviceLogRequest::get_dart_signal_receiver();
+        let add_note_receiver = AddDeviceNoteRequest::get_dart_signal_receiver();
+        let delete_receiver = DeleteDeviceLogEntryRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = get_receiver.recv() => {
+                    if let Some(request) = request {
+                        let serial = request.message.serial;
+                        debug!(%serial, "Received GetDeviceLogRequest");
+                        self.send_log(serial, None);
+                    } else {
+                        panic!("GetDeviceLogRequest receiver closed");
+                    }
+                }
+                request = add_note_receiver.recv() => {
+                    if let Some(request) = request {
+                        let AddDeviceNoteRequest { serial, text } = request.message;
+                        debug!(%serial, "Received AddDeviceNoteRequest");
+                        let result = self.add_note(&serial, &text);
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to add device note");
+                        }
+                        self.send_log(serial, result.err().map(|e| format!("{e:#}")));
+                    } else {
+                        panic!("AddDeviceNoteRequest receiver closed");
+                    }
+                }
+                request = delete_receiver.recv() => {
+                    if let Some(request) = request {
+                        let DeleteDeviceLogEntryRequest { serial, id } = request.message;
+                        debug!(%serial, id, "Received DeleteDeviceLogEntryRequest");
+                        let result = self.delete_entry(&serial, id);
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to delete device log entry");
+                        }
+                        self.send_log(serial, result.err().map(|e| format!("{e:#}")));
+                    } else {
+                        panic!("DeleteDeviceLogEntryRequest receiver closed");
+                    }
+                }
+            }
+        }
+    }
+
+    fn send_log(&self, serial: String, error: Option<String>) {
+        DeviceLogChanged { entries: self.entries(&serial), serial, error }.send_signal_to_dart();
+    }
+
+    /// Records an automatic entry for a major operation and notifies Dart.
+    ///
+    /// Failures are only logged, the operation itself already happened.
+    pub(crate) fn record_event(&self, serial: &str, kind: DeviceLogEntryKind, text: String) {
+        if let Err(e) = self.append(serial, kind, text) {
+            error!(error = e.as_ref() as &dyn Error, "Failed to record device log entry");
+        }
+        self.send_log(serial.to_string(), None);
+    }
+
+    /// Adds a user note to the log of a device.
+    #[instrument(level = "debug", skip(self, text), err)]
+    pub(crate) fn add_note(&self, serial: &str, text: &str) -> Result<()> {
+        let text = text.trim();
+        ensure!(!text.is_empty(), "Note is empty");
+        self.append(serial, DeviceLogEntryKind::Note, text.to_string())
+    }
+
+    fn append(&self, serial: &str, kind: DeviceLogEntryKind, text: String) -> Result<()> {
+        ensure!(!serial.is_empty(), "Device serial is empty");
+        let mut logs = self.logs.lock().expect("device log lock poisoned");
+        let entries = logs.devices.entry(serial.to_string()).or_default();
+        let id = entries.last().map_or(1, |entry| entry.id + 1);
+        let timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+        entries.push(DeviceLogEntry { id, timestamp, kind, text });
+        let excess = entries.len().saturating_sub(MAX_ENTRIES_PER_DEVICE);
+        entries.drain(..excess);
+        self.save(&logs)
+    }
+
+    /// Removes an entry from the log of a device.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn delete_entry(&self, serial: &str, id: u64) -> Result<()> {
+        let mut logs = self.logs.lock().expect("device log lock poisoned");
+        let entries = logs.devices.get_mut(serial).context("Device has no log entries")?;
+        let index =
+            entries.iter().position(|entry| entry.id == id).context("Log entry not found")?;
+        entries.remove(index);
+        if entries.is_empty() {
+            logs.devices.remove(serial);
+        }
+        self.save(&logs)
+    }
+
+    /// Returns the log entries of a device, newest first.
+    pub(crate) fn entries(&self, serial: &str) -> Vec<DeviceLogEntry> {
+        let logs = self.logs.lock().expect("device log lock poisoned");
+        logs.devices
+            .get(serial)
+            .map(|entries| entries.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn read_logs(path: &Path) -> Result<PersistedLogs> {
+        let content = fs::read_to_string(path).context("Failed to read device logs file")?;
+        serde_json::from_str(&content).context("Failed to parse device logs file")
+    }
+
+    fn save(&self, logs: &PersistedLogs) -> Result<()> {
+        let json = serde_json::to_string_pretty(logs).context("Failed to serialize device logs")?;
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).context("Failed to write device logs file")?;
+        fs::rename(&tmp_path, &self.file_path).context("Failed to replace device logs file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_persist_per_device_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = DeviceLog::load(dir.path().to_path_buf());
+        log.add_note("1WMHH000A", "  Replaced facial interface  ").unwrap();
+        log.append("1WMHH000B", DeviceLogEntryKind::Reboot, "Rebooted".into()).unwrap();
+        log.append("1WMHH000A", DeviceLogEntryKind::Restore, "Restored backup".into()).unwrap();
+        assert!(log.add_note("1WMHH000A", " ").is_err());
+
+        let reloaded = DeviceLog::load(dir.path().to_path_buf());
+        let entries = reloaded.entries("1WMHH000A");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, DeviceLogEntryKind::Restore);
+        assert_eq!(entries[1].text, "Replaced facial interface");
+        assert_eq!(reloaded.entries("1WMHH000B").len(), 1);
+
+        reloaded.delete_entry("1WMHH000A", entries[1].id).unwrap();
+        assert_eq!(reloaded.entries("1WMHH000A").len(), 1);
+        assert!(reloaded.delete_entry("1WMHH000A", entries[1].id).is_err());
+    }
+}
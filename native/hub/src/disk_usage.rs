@@ -0,0 +1,318 @@
+//! Disk space taken by YAAS data, by category, with requests to reclaim it.
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result, ensure};
+use rinf::{DartSignal, RustSignal};
+use tokio::{fs, sync::watch};
+use tracing::{Instrument, debug, info, info_span, instrument, warn};
+
+use crate::{
+    backups_catalog::BackupsCatalog,
+    downloader::{dedupe::DEDUPE_STORE_DIR, downloads_catalog::DownloadsCatalog},
+    models::{
+        Settings,
+        signals::disk_usage::{
+            CategoryUsage, ClearDiskUsageRequest, DiskUsageCategory, DiskUsageReport,
+            GetDiskUsageRequest,
+        },
+    },
+    task::{DONATE_TMP_DIR, TaskManager},
+    trash::{self, TRASH_DIR},
+    utils::dir_size,
+};
+
+impl DiskUsageCategory {
+    const ALL: [DiskUsageCategory; 6] = [
+        DiskUsageCategory::Downloads,
+        DiskUsageCategory::MediaCache,
+        DiskUsageCategory::Logs,
+        DiskUsageCategory::Backups,
+        DiskUsageCategory::Trash,
+        DiskUsageCategory::DonationUploads,
+    ];
+
+    /// Whether tasks read or write this data while they run
+    fn used_by_tasks(self) -> bool {
+        !matches!(self, DiskUsageCategory::MediaCache | DiskUsageCategory::Logs)
+    }
+}
+
+/// What clearing data goes through: the catalogs delete only the entries they recognise, and
+/// data used by tasks is only cleared while none are running
+struct Clearing {
+    task_manager: Arc<TaskManager>,
+    downloads_catalog: Arc<DownloadsCatalog>,
+    backups_catalog: Arc<BackupsCatalog>,
+}
+
+/// Answers disk usage requests from Dart
+pub(crate) struct DiskUsage {
+    app_dir: PathBuf,
+    settings: watch::Receiver<Settings>,
+}
+
+impl DiskUsage {
+    pub(crate) fn new(app_dir: PathBuf, settings: watch::Receiver<Settings>) -> Self {
+        Self { app_dir, settings }
+    }
+
+    pub(crate) fn start(
+        app_dir: PathBuf,
+        settings: watch::Receiver<Settings>,
+        task_manager: Arc<TaskManager>,
+        downloads_catalog: Arc<DownloadsCatalog>,
+        backups_catalog: Arc<BackupsCatalog>,
+    ) {
+        let handler = Self::new(app_dir, settings);
+        let clearing = Clearing { task_manager, downloads_catalog, backups_catalog };
+        tokio::spawn(handler.receive_signals(clearing).instrument(info_span!("task_disk_usage")));
+    }
+
+    async fn receive_signals(self, clearing: Clearing) {
+        let get_receiver = GetDiskUsageRequest::get_dart_signal_receiver();
+        let clear_receiver = ClearDiskUsageRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = get_receiver.recv() => {
+                    if request.is_none() {
+                        panic!("GetDiskUsageRequest receiver closed");
+                    }
+                    self.send_report(None).await;
+                }
+                request = clear_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("ClearDiskUsageRequest receiver closed");
+                    };
+                    let category = request.message.category;
+                    let error = match self.clear(category, &clearing).await {
+                        Ok(()) => None,
+                        Err(e) => {
+                            warn!(error = e.as_ref() as &dyn Error, ?category, "Failed to clear data");
+                            Some(format!("{e:#}"))
+                        }
+                    };
+                    self.send_report(error).await;
+                }
+            }
+        }
+    }
+
+    async fn send_report(&self, error: Option<String>) {
+        DiskUsageReport { categories: self.measure().await, error }.send_signal_to_dart();
+    }
+
+    /// Measures every category, counting the ones that could not be measured as empty
+    pub(crate) async fn measure(&self) -> Vec<CategoryUsage> {
+        let mut categories = Vec::with_capacity(DiskUsageCategory::ALL.len());
+        for category in DiskUsageCategory::ALL {
+            let (dir, excluded) = self.location(category);
+            let size = match usage(&dir, excluded).await {
+                Ok(size) => size,
+                Err(e) => {
+                    warn!(error = e.as_ref() as &dyn Error, ?category, "Failed to measure data");
+                    0
+                }
+            };
+            categories.push(CategoryUsage {
+                category,
+                path: dir.to_string_lossy().into_owned(),
+                size,
+            });
+        }
+        categories
+    }
+
+    /// Directory of a category and names of its entries that belong to other categories or are
+    /// counted elsewhere, like deduplicated files that are also linked from their releases
+    fn location(&self, category: DiskUsageCategory) -> (PathBuf, &'static [&'static str]) {
+        let settings = self.settings.borrow();
+        match category {
+            DiskUsageCategory::Downloads => {
+                (settings.downloads_location(), &[DONATE_TMP_DIR, DEDUPE_STORE_DIR])
+            }
+            DiskUsageCategory::MediaCache => (self.app_dir.join("media_cache"), &[]),
+            DiskUsageCategory::Logs => (self.app_dir.join("logs"), &[]),
+            DiskUsageCategory::Backups => (settings.backups_location(), &[TRASH_DIR]),
+            DiskUsageCategory::Trash => (trash::trash_dir(&settings.backups_location()), &[]),
+            DiskUsageCategory::DonationUploads => {
+                (settings.downloads_location().join(DONATE_TMP_DIR), &[])
+            }
+        }
+    }
+
+    #[instrument(level = "debug", skip(self, clearing))]
+    async fn clear(&self, category: DiskUsageCategory, clearing: &Clearing) -> Result<()> {
+        if category.used_by_tasks() {
+            ensure!(
+                clearing.task_manager.active_task_count().await == 0,
+                "Wait for running tasks to finish before clearing this data"
+            );
+        }
+        let (dir, excluded) = self.location(category);
+        match category {
+            DiskUsageCategory::Logs => clear_logs(&dir).await?,
+            DiskUsageCategory::Trash => trash::empty(&dir).await?,
+            DiskUsageCategory::Downloads => {
+                clearing.downloads_catalog.delete_all_downloads().await?;
+            }
+            DiskUsageCategory::Backups => {
+                clearing.backups_catalog.delete_all_backups().await?;
+            }
+            DiskUsageCategory::MediaCache | DiskUsageCategory::DonationUploads => {
+                clear_dir(&dir, excluded).await?
+            }
+        }
+        info!(?category, dir = %dir.display(), "Cleared data");
+        Ok(())
+    }
+}
+
+/// Size of a directory without its `excluded` entries, 0 if it does not exist
+async fn usage(dir: &Path, excluded: &[&str]) -> Result<u64> {
+    if excluded.is_empty() {
+        return dir_size(dir).await;
+    }
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut total = 0u64;
+    while let Some(entry) = entries.next_entry().await? {
+        if is_excluded(&entry.path(), excluded) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata().await else {
+            continue;
+        };
+        let size = if meta.is_dir() { dir_size(&entry.path()).await? } else { meta.len() };
+        total = total.saturating_add(size);
+    }
+    Ok(total)
+}
+
+/// Deletes everything in a directory except its `excluded` entries, keeping the directory
+async fn clear_dir(dir: &Path, excluded: &[&str]) -> Result<()> {
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if is_excluded(&path, excluded) {
+            continue;
+        }
+        let result = if entry.file_type().await?.is_dir() {
+            fs::remove_dir_all(&path).await
+        } else {
+            fs::remove_file(&path).await
+        };
+        result.with_context(|| format!("Failed to delete {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Deletes log files except the newest one of each kind in each folder, which the running session
+/// writes to
+async fn clear_logs(dir: &Path) -> Result<()> {
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+        };
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let meta = entry.metadata().await?;
+            if meta.is_dir() {
+                stack.push(entry.path());
+            } else {
+                files.push((meta.modified().unwrap_or(SystemTime::UNIX_EPOCH), entry.path()));
+            }
+        }
+        for path in older_than_newest(files) {
+            if let Err(e) = fs::remove_file(&path).await {
+                debug!(error = &e as &dyn Error, path = %path.display(), "Failed to delete log");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Paths of all files but the most recently modified one of each extension
+fn older_than_newest(mut files: Vec<(SystemTime, PathBuf)>) -> Vec<PathBuf> {
+    files.sort();
+    let mut newest = HashSet::new();
+    let mut older = Vec::new();
+    for (_, path) in files.into_iter().rev() {
+        if !newest.insert(path.extension().map(OsStr::to_os_string)) {
+            older.push(path);
+        }
+    }
+    older.reverse();
+    older
+}
+
+fn is_excluded(path: &Path, excluded: &[&str]) -> bool {
+    path.file_name().and_then(|name| name.to_str()).is_some_and(|name| excluded.contains(&name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn excluded_entries_are_neither_measured_nor_cleared() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("app/sub")).await.unwrap();
+        fs::create_dir_all(dir.path().join(DONATE_TMP_DIR)).await.unwrap();
+        fs::write(dir.path().join("app/sub/a.apk"), [0u8; 10]).await.unwrap();
+        fs::write(dir.path().join("b.txt"), [0u8; 5]).await.unwrap();
+        fs::write(dir.path().join(DONATE_TMP_DIR).join("c.zip"), [0u8; 100]).await.unwrap();
+
+        assert_eq!(usage(dir.path(), &[DONATE_TMP_DIR]).await.unwrap(), 15);
+        assert_eq!(usage(dir.path(), &[]).await.unwrap(), 115);
+
+        clear_dir(dir.path(), &[DONATE_TMP_DIR]).await.unwrap();
+        assert_eq!(usage(dir.path(), &[]).await.unwrap(), 100);
+        assert!(dir.path().exists());
+
+        let missing = dir.path().join("missing");
+        assert_eq!(usage(&missing, &[DONATE_TMP_DIR]).await.unwrap(), 0);
+        clear_dir(&missing, &[]).await.unwrap();
+    }
+
+    #[test]
+    fn only_the_newest_log_is_kept() {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let files = vec![
+            (at(20), PathBuf::from("yaas.2.log")),
+            (at(30), PathBuf::from("yaas.3.log")),
+            (at(10), PathBuf::from("yaas.1.log")),
+            (at(25), PathBuf::from("yaas.2.jsonl")),
+            (at(15), PathBuf::from("yaas.1.jsonl")),
+        ];
+        assert_eq!(
+            older_than_newest(files),
+            [
+                PathBuf::from("yaas.1.log"),
+                PathBuf::from("yaas.1.jsonl"),
+                PathBuf::from("yaas.2.log")
+            ]
+        );
+        assert!(older_than_newest(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::watch;
+use tracing::debug;
+
+use crate::models::{CastingDownloadPolicy, Settings};
+
+/// Rate running transfers are slowed to while downloads are paused for casting.
+///
+/// rclone cannot suspend a running transfer, so it is kept alive at a trickle instead.
+const PAUSED_LIMIT: &str = "1K";
+
+/// Download speed cap shared by all transfers of a downloader.
+///
+/// The value uses rclone `--bwlimit` syntax and can be changed while transfers are running, they
+/// subscribe to it and adjust their rate on the fly.
+#[derive(Debug, Clone)]
+pub(crate) struct BandwidthLimit {
+    tx: Arc<watch::Sender<String>>,
+}
+
+impl BandwidthLimit {
+    pub(crate) fn new(limit: String) -> Self {
+        Self { tx: Arc::new(watch::Sender::new(limit)) }
+    }
+
+    /// Replaces the limit, notifying running transfers if it changed
+    pub(crate) fn set(&self, limit: &str) {
+        let changed = self.tx.send_if_modified(|current| {
+            if current == limit {
+                return false;
+            }
+            *current = limit.to_string();
+            true
+        });
+        if changed {
+            debug!(limit, "Bandwidth limit changed");
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<String> {
+        self.tx.subscribe()
+    }
+
+    /// Creates a throttle pacing a single download to the current limit
+    pub(crate) fn throttle(&self) -> Throttle {
+        let rx = self.subscribe();
+        let rate = parse_download_rate(&rx.borrow());
+        Throttle { rx, pacer: Arc::new(Mutex::new(Pacer::new(rate, Instant::now()))) }
+    }
+}
+
+/// Limits compare equal while they hold the same value
+impl PartialEq for BandwidthLimit {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.tx, &other.tx) || *self.tx.borrow() == *other.tx.borrow()
+    }
+}
+
+impl Eq for BandwidthLimit {}
+
+/// Paces byte streams to the download rate of a `BandwidthLimit`.
+///
+/// Clones share their budget, so parallel streams of one download are limited together.
+#[derive(Debug, Clone)]
+pub(crate) struct Throttle {
+    rx: watch::Receiver<String>,
+    pacer: Arc<Mutex<Pacer>>,
+}
+
+impl Throttle {
+    /// Accounts for `bytes` received, sleeping if they arrived faster than allowed
+    pub(crate) async fn consume(&mut self, bytes: u64) {
+        let delay = {
+            let mut pacer = self.pacer.lock().expect("throttle lock poisoned");
+            let now = Instant::now();
+            if self.rx.has_changed().unwrap_or(false) {
+                let rate = parse_download_rate(&self.rx.borrow_and_update());
+                if rate != pacer.rate {
+                    *pacer = Pacer::new(rate, now);
+                }
+            }
+            pacer.reserve(bytes, now)
+        };
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Pacer {
+    /// Bytes per second, `None` if unlimited
+    rate: Option<u64>,
+    started_at: Instant,
+    bytes: u64,
+}
+
+impl Pacer {
+    fn new(rate: Option<u64>, now: Instant) -> Self {
+        Self { rate, started_at: now, bytes: 0 }
+    }
+
+    /// Records `bytes` and returns how long to wait before reading more
+    fn reserve(&mut self, bytes: u64, now: Instant) -> Duration {
+        let Some(rate) = self.rate else {
+            return Duration::ZERO;
+        };
+        // Do not let idle time accumulate into a burst
+        let elapsed = now.duration_since(self.started_at);
+        let budget_start = Duration::from_secs_f64(self.bytes as f64 / rate as f64);
+        if elapsed > budget_start + Duration::from_secs(1) {
+            self.started_at = now - Duration::from_secs(1);
+            self.bytes = 0;
+        }
+        self.bytes += bytes;
+        let due = Duration::from_secs_f64(self.bytes as f64 / rate as f64);
+        due.saturating_sub(now.duration_since(self.started_at))
+    }
+}
+
+/// Returns the download limit to apply, taking a running casting session into account
+pub(crate) fn effective_limit(settings: &Settings, casting_active: bool) -> String {
+    match settings.casting_download_policy {
+        CastingDownloadPolicy::Throttle if casting_active => {
+            settings.casting_bandwidth_limit.clone()
+        }
+        CastingDownloadPolicy::Pause if casting_active => PAUSED_LIMIT.to_string(),
+        _ => settings.bandwidth_limit.clone(),
+    }
+}
+
+/// Extracts the download rate in bytes per second from a `--bwlimit` value.
+///
+/// Plain numbers are KiB/s, `B|K|M|G|T|P` suffixes are binary multiples and an `UP:DOWN` pair
+/// limits downloads to `DOWN`. Returns `None` for no limit, including timetables which only rclone
+/// understands.
+pub(crate) fn parse_download_rate(limit: &str) -> Option<u64> {
+    let limit = limit.trim();
+    if limit.contains([' ', ',']) {
+        return None;
+    }
+    let down = limit.rsplit_once(':').map_or(limit, |(_, down)| down);
+    if down.is_empty() || down.eq_ignore_ascii_case("off") {
+        return None;
+    }
+
+    let (number, multiplier) = match down.char_indices().last() {
+        Some((index, suffix)) if suffix.is_ascii_alphabetic() => {
+            let exponent = match suffix.to_ascii_uppercase() {
+                'B' => 0,
+                'K' => 1,
+                'M' => 2,
+                'G' => 3,
+                'T' => 4,
+                'P' => 5,
+                _ => return None,
+            };
+            (&down[..index], 1024_f64.powi(exponent))
+        }
+        _ => (down, 1024.0),
+    };
+    let rate = (number.parse::<f64>().ok()? * multiplier) as u64;
+    (rate > 0).then_some(rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rclone_rates() {
+        assert_eq!(parse_download_rate(""), None);
+        assert_eq!(parse_download_rate("off"), None);
+        assert_eq!(parse_download_rate("0"), None);
+        assert_eq!(parse_download_rate("512"), Some(512 * 1024));
+        assert_eq!(parse_download_rate("1.5M"), Some(1536 * 1024));
+        assert_eq!(parse_download_rate("100b"), Some(100));
+        assert_eq!(parse_download_rate("10M:100K"), Some(100 * 1024));
+        assert_eq!(parse_download_rate("08:00,512k 19:00,off"), None);
+        assert_eq!(parse_download_rate("fast"), None);
+    }
+
+    #[test]
+    fn casting_policy_overrides_limit_only_while_casting() {
+        let mut settings = Settings::default();
+        settings.bandwidth_limit = "10M".to_string();
+        settings.casting_bandwidth_limit = "512K".to_string();
+        assert_eq!(effective_limit(&settings, true), "10M");
+
+        settings.casting_download_policy = CastingDownloadPolicy::Throttle;
+        assert_eq!(effective_limit(&settings, false), "10M");
+        assert_eq!(effective_limit(&settings, true), "512K");
+
+        settings.casting_download_policy = CastingDownloadPolicy::Pause;
+        assert_eq!(effective_limit(&settings, true), PAUSED_LIMIT);
+    }
+
+    #[test]
+    fn pacer_delays_reads_beyond_rate() {
+        let start = Instant::now();
+        let mut pacer = Pacer::new(Some(1000), start);
+
+        assert_eq!(pacer.reserve(500, start), Duration::from_millis(500));
+        assert_eq!(
+            pacer.reserve(500, start + Duration::from_millis(500)),
+            Duration::from_millis(500)
+        );
+        assert_eq!(pacer.reserve(1000, start + Duration::from_secs(2)), Duration::ZERO);
+        assert_eq!(Pacer::new(None, start).reserve(u64::MAX, start), Duration::ZERO);
+    }
+
+    #[test]
+    fn pacer_limits_burst_after_idle() {
+        let start = Instant::now();
+        let mut pacer = Pacer::new(Some(1000), start);
+        pacer.reserve(1000, start);
+
+        // After a long pause only one second worth of bytes may go through without waiting
+        let later = start + Duration::from_secs(60);
+        assert_eq!(pacer.reserve(1000, later), Duration::ZERO);
+        assert!(pacer.reserve(1000, later) >= Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+
+use rinf::RustSignal;
+use tracing::debug;
+
+use crate::models::{
+    CloudApp,
+    signals::cloud_apps::list::{CatalogDelta, CloudAppsChangedEvent},
+};
+
+/// Remembers the app list last sent to Dart so refreshes only send what changed
+#[derive(Debug, Default)]
+pub(super) struct CatalogPublisher {
+    /// Apps by full name, empty when the UI has no list to apply changes to
+    sent: std::sync::Mutex<HashMap<String, CloudApp>>,
+}
+
+impl CatalogPublisher {
+    /// Sends the whole list and uses it as the base of later deltas
+    pub(super) fn send_full(&self, apps: Vec<CloudApp>, donation_blacklist: Option<Vec<String>>) {
+        debug!(count = apps.len(), "Sending app list to UI");
+        *self.sent() = index_by_full_name(&apps).unwrap_or_default();
+        CloudAppsChangedEvent {
+            is_loading: false,
+            apps: Some(apps),
+            donation_blacklist,
+            error: None,
+        }
+        .send_signal_to_dart();
+    }
+
+    /// Sends only the changes since the last sent list, or the whole list if there is no base
+    pub(super) fn send_changes(
+        &self,
+        apps: Vec<CloudApp>,
+        donation_blacklist: Option<Vec<String>>,
+    ) {
+        let mut sent = self.sent();
+        let Some(index) = index_by_full_name(&apps).filter(|_| !sent.is_empty()) else {
+            drop(sent);
+            self.send_full(apps, donation_blacklist);
+            return;
+        };
+        let delta = diff(&sent, &index);
+        *sent = index;
+        drop(sent);
+
+        if delta.added.is_empty() && delta.changed.is_empty() && delta.removed.is_empty() {
+            debug!("App list unchanged");
+        } else {
+            debug!(
+                added = delta.added.len(),
+                changed = delta.changed.len(),
+                removed = delta.removed.len(),
+                "Sending app list changes to UI"
+            );
+            delta.send_signal_to_dart();
+        }
+        CloudAppsChangedEvent { is_loading: false, apps: None, donation_blacklist, error: None }
+            .send_signal_to_dart();
+    }
+
+    /// Forgets the sent list after the UI dropped it, e.g. because loading failed
+    pub(super) fn reset(&self) {
+        self.sent().clear();
+    }
+
+    fn sent(&self) -> std::sync::MutexGuard<'_, HashMap<String, CloudApp>> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Apps by full name, `None` if names repeat and changes could not be told apart
+fn index_by_full_name(apps: &[CloudApp]) -> Option<HashMap<String, CloudApp>> {
+    let index: HashMap<_, _> =
+        apps.iter().map(|app| (app.full_name.clone(), app.clone())).collect();
+    (index.len() == apps.len()).then_some(index)
+}
+
+fn diff(old: &HashMap<String, CloudApp>, new: &HashMap<String, CloudApp>) -> CatalogDelta {
+    let mut delta = CatalogDelta {
+        base_count: old.len() as u32,
+        added: Vec::new(),
+        changed: Vec::new(),
+        removed: Vec::new(),
+    };
+    for (full_name, app) in new {
+        match old.get(full_name) {
+            None => delta.added.push(app.clone()),
+            Some(previous) if previous != app => delta.changed.push(app.clone()),
+            Some(_) => {}
+        }
+    }
+    let kept: HashSet<&String> = new.keys().collect();
+    delta.removed = old.keys().filter(|name| !kept.contains(name)).cloned().collect();
+
+    delta.added.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+    delta.changed.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+    delta.removed.sort();
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(full_name: &str, size: u64) -> CloudApp {
+        CloudApp::new(
+            "App".to_string(),
+            full_name.to_string(),
+            "com.example.app".to_string(),
+            1,
+            "2024-01-01 00:00 UTC".to_string(),
+            size,
+        )
+    }
+
+    #[test]
+    fn diff_reports_added_changed_and_removed_apps() {
+        let old = index_by_full_name(&[app("A v1", 1), app("B v1", 1), app("C v1", 1)]).unwrap();
+        let new = index_by_full_name(&[app("A v1", 1), app("B v1", 2), app("D v1", 1)]).unwrap();
+
+        let delta = diff(&old, &new);
+        assert_eq!(delta.base_count, 3);
+        assert_eq!(delta.added, [app("D v1", 1)]);
+        assert_eq!(delta.changed, [app("B v1", 2)]);
+        assert_eq!(delta.removed, ["C v1"]);
+
+        let unchanged = diff(&new, &new);
+        assert!(unchanged.added.is_empty() && unchanged.changed.is_empty());
+        assert!(unchanged.removed.is_empty());
+    }
+
+    #[test]
+    fn repeated_full_names_are_not_indexed() {
+        assert!(index_by_full_name(&[app("A v1", 1), app("A v1", 2)]).is_none());
+    }
+}
@@ -5,7 +5,10 @@ use const_format::concatcp;
 use serde::Deserialize;
 use tracing::error;
 
-use crate::downloader::SensitiveUrl;
+use crate::downloader::{
+    SensitiveUrl,
+    tls::{parse_pinned_certificates, parse_spki_pins},
+};
 
 #[derive(Debug, Clone, Deserialize)]
 pub(crate) struct DownloaderConfig {
@@ -31,6 +34,10 @@ pub(crate) struct DownloaderConfig {
     /// Optional path within the donation remote where uploaded archives are placed.
     #[serde(default)]
     pub donation_remote_path: Option<String>,
+    /// Optional HTTP(S) URL donation archives are uploaded to with PUT, with the archive file
+    /// name appended. Takes precedence over the donation remote and works with any layout.
+    #[serde(default)]
+    pub donation_upload_url: Option<String>,
     /// Optional path to a newline-separated donation blacklist.
     ///
     /// For FFA layout this is a path on the configured rclone remote.
@@ -47,6 +54,16 @@ pub(crate) struct DownloaderConfig {
     /// Optional URL used to update this downloader configuration.
     #[serde(default)]
     pub config_update_url: Option<String>,
+    /// Optional PEM certificates the source's servers must chain to.
+    ///
+    /// When set, the system trust store is not used for the repository and rclone, so private
+    /// mirrors fail hard on an intercepted connection instead of trusting any public CA.
+    #[serde(default)]
+    pub pinned_certificates: Option<String>,
+    /// Optional `sha256/<base64>` SPKI pins, one of which a certificate in the verified chain of
+    /// the source's servers must carry
+    #[serde(default)]
+    pub pinned_spki: Vec<String>,
 }
 
 fn default_root_dir() -> String {
@@ -93,6 +110,11 @@ impl DownloaderConfig {
                         .unwrap_or(false),
                     "rclone_config_path is required for the ffa repository layout"
                 );
+                ensure!(
+                    self.pinned_spki.is_empty(),
+                    "pinned_spki is not supported for the ffa repository layout, rclone can only \
+                     be restricted to pinned_certificates"
+                );
             }
             RepoLayoutKind::NewRepo => {
                 let base_url = self.base_url.as_deref().map(str::trim).unwrap_or_default();
@@ -106,12 +128,41 @@ impl DownloaderConfig {
                     parsed.scheme() == "http" || parsed.scheme() == "https",
                     "new-repo base_url must use http or https"
                 );
+                ensure!(
+                    (self.pinned_certificates.is_none() && self.pinned_spki.is_empty())
+                        || parsed.scheme() == "https",
+                    "new-repo base_url must use https with pinned certificates or keys"
+                );
             }
         }
+        if let Some(pem) = &self.pinned_certificates {
+            parse_pinned_certificates(pem)?;
+        }
+        parse_spki_pins(&self.pinned_spki)?;
+        if let Some(url) = self.donation_upload_url() {
+            let parsed = reqwest::Url::parse(url).context("Invalid donation_upload_url")?;
+            ensure!(
+                parsed.scheme() == "http" || parsed.scheme() == "https",
+                "donation_upload_url must use http or https"
+            );
+        }
 
         Ok(())
     }
 
+    /// HTTP URL for donation uploads, if one is set
+    pub(crate) fn donation_upload_url(&self) -> Option<&str> {
+        self.donation_upload_url.as_deref().map(str::trim).filter(|url| !url.is_empty())
+    }
+
+    /// Whether donated apps can be uploaded somewhere, given what the repository layout supports
+    pub(crate) fn is_donation_configured(&self, supports_remote_upload: bool) -> bool {
+        self.donation_upload_url().is_some()
+            || (supports_remote_upload
+                && self.donation_remote_name.as_deref().is_some_and(|s| !s.is_empty())
+                && self.donation_remote_path.as_deref().is_some_and(|s| !s.is_empty()))
+    }
+
     pub(crate) fn validate_managed_remote(
         &self,
         source_url: Option<SensitiveUrl<'_>>,
@@ -201,12 +252,15 @@ impl Default for DownloaderConfig {
             disable_randomize_remote: false,
             donation_remote_name: None,
             donation_remote_path: None,
+            donation_upload_url: None,
             donation_blacklist_path: None,
             layout: RepoLayoutKind::Ffa,
             base_url: None,
             root_dir: default_root_dir(),
             list_path: default_list_path(),
             config_update_url: None,
+            pinned_certificates: None,
+            pinned_spki: Vec::new(),
         }
     }
 }
@@ -362,6 +416,52 @@ mod tests {
         assert!(format!("{err:#}").contains("base_url is required"));
     }
 
+    #[test]
+    fn pinned_keys_require_https() {
+        let mut cfg = DownloaderConfig {
+            id: "mirror".to_string(),
+            layout: RepoLayoutKind::NewRepo,
+            base_url: Some("http://mirror.example/".to_string()),
+            pinned_spki: vec!["sha256/lLYhCKBO3yUgPMDC0E8cCy41d1UWmBxbU4d2cITUlu4=".to_string()],
+            ..Default::default()
+        };
+        let err = cfg.validate().expect_err("plain http with pinned keys");
+        assert!(format!("{err:#}").contains("must use https"));
+
+        cfg.base_url = Some("https://mirror.example/".to_string());
+        cfg.validate().expect("https with pinned keys");
+        cfg.pinned_spki = vec!["sha256/AAAA".to_string()];
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn ffa_rejects_pinned_keys() {
+        let cfg = DownloaderConfig {
+            rclone_config_path: Some("/tmp/rclone.conf".to_string()),
+            pinned_spki: vec!["sha256/lLYhCKBO3yUgPMDC0E8cCy41d1UWmBxbU4d2cITUlu4=".to_string()],
+            ..Default::default()
+        };
+        let err = cfg.validate().expect_err("pinned keys with rclone");
+        assert!(format!("{err:#}").contains("pinned_spki is not supported"));
+    }
+
+    #[test]
+    fn donation_upload_url_enables_donations_for_any_layout() {
+        let mut cfg = DownloaderConfig {
+            rclone_config_path: Some("/tmp/rclone.conf".to_string()),
+            ..Default::default()
+        };
+        assert!(!cfg.is_donation_configured(false));
+
+        cfg.donation_upload_url = Some("ftp://example.com/donations".to_string());
+        let err = cfg.validate().expect_err("non-http upload url");
+        assert!(format!("{err:#}").contains("must use http or https"));
+
+        cfg.donation_upload_url = Some("https://example.com/donations/".to_string());
+        cfg.validate().expect("valid upload url");
+        assert!(cfg.is_donation_configured(false));
+    }
+
     #[test]
     fn newrepo_loads_without_rclone_fields() {
         let dir = tempdir().unwrap();
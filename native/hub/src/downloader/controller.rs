@@ -1,6 +1,7 @@
 use std::{error::Error, path::Path, sync::Arc};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
+use lazy_regex::Regex;
 use rinf::{DartSignal, RustSignal};
 use tokio::sync::Mutex;
 use tokio_stream::wrappers::WatchStream;
@@ -11,9 +12,11 @@ use crate::{
         Downloader, SensitiveUrl,
         config::{DownloaderConfig, RepoLayoutKind},
         manager::DownloaderManager,
+        rclone::{CustomRemotes, validate_draft, write_merged_config},
         repo,
         sources::{DownloaderSources, LoadedSources, RefreshReport, runtime_cache_dir},
     },
+    metadata_store::MetadataStore,
     models::signals::{
         downloader::{
             availability::{DownloaderAvailabilityChanged, RepoCapabilities},
@@ -24,6 +27,11 @@ use crate::{
                 RetryDownloaderInitRequest, SelectDownloaderSourceRequest,
             },
         },
+        storage::remotes::{
+            CustomRemoteDraft, CustomRemoteSaveResult, CustomRemoteValidation,
+            CustomRemotesChanged, ListCustomRemotesRequest, RemoveCustomRemoteRequest,
+            SaveCustomRemoteRequest, ValidateCustomRemoteRequest,
+        },
         system::Toast,
     },
     settings::SettingsHandler,
@@ -33,7 +41,9 @@ use crate::{
 pub(crate) struct DownloaderController {
     manager: Arc<DownloaderManager>,
     sources: DownloaderSources,
+    custom_remotes: CustomRemotes,
     settings_handler: Arc<SettingsHandler>,
+    metadata_store: Arc<MetadataStore>,
     reload_guard: Arc<Mutex<()>>,
 }
 
@@ -45,9 +55,11 @@ enum ReloadReason {
     Remove,
     Select,
     ManualRefresh,
+    CustomRemotes,
 }
 
 struct DownloaderAvailabilityReporter {
+    manager: Arc<DownloaderManager>,
     config_id: String,
     is_donation_configured: bool,
     capabilities: RepoCapabilities,
@@ -58,11 +70,14 @@ impl DownloaderController {
         manager: Arc<DownloaderManager>,
         app_dir: std::path::PathBuf,
         settings_handler: Arc<SettingsHandler>,
+        metadata_store: Arc<MetadataStore>,
     ) -> Arc<Self> {
         Arc::new(Self {
             manager,
+            custom_remotes: CustomRemotes::new(&app_dir),
             sources: DownloaderSources::new(app_dir, settings_handler.clone()),
             settings_handler,
+            metadata_store,
             reload_guard: Arc::new(Mutex::new(())),
         })
     }
@@ -130,8 +145,10 @@ impl DownloaderController {
 
         if sources.is_empty() {
             self.manager.clear().await;
-            DownloaderAvailabilityChanged { needs_setup: true, ..Default::default() }
-                .send_signal_to_dart();
+            self.manager.report_availability(DownloaderAvailabilityChanged {
+                needs_setup: true,
+                ..Default::default()
+            });
             return Ok(sources);
         }
 
@@ -145,7 +162,8 @@ impl DownloaderController {
 
     async fn start_downloader(&self, cfg: DownloaderConfig) -> Result<()> {
         let repo = repo::make_repo_from_config(&cfg);
-        let availability = DownloaderAvailabilityReporter::new(&cfg, repo.capabilities());
+        let availability =
+            DownloaderAvailabilityReporter::new(&cfg, repo.capabilities(), self.manager.clone());
 
         availability.send_initializing();
         self.manager.clear().await;
@@ -153,9 +171,10 @@ impl DownloaderController {
         let cache_dir = runtime_cache_dir(self.sources.app_dir(), &cfg.id);
         let _ = tokio::fs::create_dir_all(&cache_dir).await;
 
-        let (rclone_path, rclone_config_path) = prepare_downloader_runtime(&cache_dir, &cfg)
-            .await
-            .inspect_err(|e| availability.send_error("prepare downloader", e))?;
+        let (rclone_path, rclone_config_path) =
+            prepare_downloader_runtime(&cache_dir, &cfg, &self.custom_remotes)
+                .await
+                .inspect_err(|e| availability.send_error("prepare downloader", e))?;
 
         let downloader = Downloader::new(
             Arc::new(cfg),
@@ -164,6 +183,7 @@ impl DownloaderController {
             rclone_config_path,
             self.settings_handler.clone(),
             WatchStream::new(self.settings_handler.subscribe()),
+            self.metadata_store.clone(),
         )
         .await
         .inspect_err(|e| availability.send_error("initialize downloader", e))?;
@@ -318,6 +338,107 @@ impl DownloaderController {
         }
     }
 
+    /// Checks a custom remote, including whether the active source would pick it up
+    fn custom_remote_errors(&self, draft: &CustomRemoteDraft) -> Vec<String> {
+        let mut errors = validate_draft(draft);
+        let filter = self
+            .sources
+            .load(Vec::new())
+            .ok()
+            .and_then(|sources| sources.active_config())
+            .filter(|cfg| cfg.layout == RepoLayoutKind::Ffa)
+            .and_then(|cfg| cfg.remote_name_filter_regex);
+        if let Some(pattern) = filter
+            && let Ok(re) = Regex::new(&pattern)
+            && !re.is_match(&draft.name)
+        {
+            errors.push(format!("Name must match {pattern} to be used by the current source"));
+        }
+        errors
+    }
+
+    fn send_custom_remotes(&self, error: Option<String>) {
+        match self.custom_remotes.list() {
+            Ok(remotes) => CustomRemotesChanged { remotes, error }.send_signal_to_dart(),
+            Err(e) => {
+                error!(error = e.as_ref() as &dyn Error, "Failed to list custom remotes");
+                CustomRemotesChanged { remotes: Vec::new(), error: Some(format!("{e:#}")) }
+                    .send_signal_to_dart();
+            }
+        }
+    }
+
+    async fn save_custom_remote(&self, draft: CustomRemoteDraft, previous_name: Option<String>) {
+        let errors = self.custom_remote_errors(&draft);
+        let result = if errors.is_empty() {
+            let rclone_path = self.active_rclone_path().await;
+            self.custom_remotes.save(&draft, previous_name.as_deref(), rclone_path.as_deref()).await
+        } else {
+            Err(anyhow!(errors.join("\n")))
+        };
+
+        match result {
+            Ok(()) => {
+                CustomRemoteSaveResult { name: draft.name, error: None }.send_signal_to_dart();
+                self.send_custom_remotes(None);
+                self.reload_for_custom_remotes().await;
+            }
+            Err(e) => {
+                warn!(error = e.as_ref() as &dyn Error, name = %draft.name, "Failed to save custom remote");
+                CustomRemoteSaveResult { name: draft.name, error: Some(format!("{e:#}")) }
+                    .send_signal_to_dart();
+            }
+        }
+    }
+
+    /// Rclone binary of the active source, `None` if it does not use rclone
+    async fn active_rclone_path(&self) -> Option<std::path::PathBuf> {
+        let cfg = self
+            .sources
+            .load(Vec::new())
+            .ok()
+            .and_then(|sources| sources.active_config())
+            .filter(|cfg| cfg.layout == RepoLayoutKind::Ffa)?;
+        let cache_dir = runtime_cache_dir(self.sources.app_dir(), &cfg.id);
+        match crate::downloader::rclone::prepare_rclone_files(&cache_dir, &cfg).await {
+            Ok((rclone_path, _)) => Some(rclone_path),
+            Err(e) => {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to locate the rclone binary");
+                None
+            }
+        }
+    }
+
+    async fn remove_custom_remote(&self, name: &str) {
+        match self.custom_remotes.remove(name) {
+            Ok(removed) => {
+                self.send_custom_remotes(None);
+                if removed {
+                    self.reload_for_custom_remotes().await;
+                }
+            }
+            Err(e) => {
+                error!(error = e.as_ref() as &dyn Error, name, "Failed to remove custom remote");
+                self.send_custom_remotes(Some(format!("Failed to remove {name}: {e:#}")));
+            }
+        }
+    }
+
+    /// Restarts an rclone-based downloader so it sees the changed remotes
+    async fn reload_for_custom_remotes(&self) {
+        let uses_rclone = self
+            .sources
+            .load(Vec::new())
+            .ok()
+            .and_then(|sources| sources.active_config())
+            .is_some_and(|cfg| cfg.layout == RepoLayoutKind::Ffa);
+        if uses_rclone
+            && let Err(e) = self.reload_and_apply(ReloadReason::CustomRemotes, Vec::new()).await
+        {
+            send_error_toast("Failed to apply custom remotes", &e);
+        }
+    }
+
     fn spawn_background_refresh(self: Arc<Self>, configs: Vec<DownloaderConfig>) {
         tokio::spawn(async move {
             let report = self.sources.refresh_all(&configs).await;
@@ -408,16 +529,75 @@ impl DownloaderController {
                 panic!("RetryDownloaderInitRequest receiver closed")
             }
         });
+        tokio::spawn({
+            let controller = self.clone();
+            async move {
+                let receiver = ListCustomRemotesRequest::get_dart_signal_receiver();
+                while receiver.recv().await.is_some() {
+                    controller.send_custom_remotes(None);
+                }
+
+                panic!("ListCustomRemotesRequest receiver closed")
+            }
+        });
+
+        tokio::spawn({
+            let controller = self.clone();
+            async move {
+                let receiver = ValidateCustomRemoteRequest::get_dart_signal_receiver();
+                while let Some(req) = receiver.recv().await {
+                    let remote = req.message.remote;
+                    CustomRemoteValidation {
+                        errors: controller.custom_remote_errors(&remote),
+                        name: remote.name,
+                    }
+                    .send_signal_to_dart();
+                }
+
+                panic!("ValidateCustomRemoteRequest receiver closed")
+            }
+        });
+
+        tokio::spawn({
+            let controller = self.clone();
+            async move {
+                let receiver = SaveCustomRemoteRequest::get_dart_signal_receiver();
+                while let Some(req) = receiver.recv().await {
+                    let SaveCustomRemoteRequest { remote, previous_name } = req.message;
+                    debug!(name = %remote.name, ?previous_name, "Received SaveCustomRemoteRequest");
+                    controller.save_custom_remote(remote, previous_name).await;
+                }
+
+                panic!("SaveCustomRemoteRequest receiver closed")
+            }
+        });
+
+        tokio::spawn({
+            let controller = self.clone();
+            async move {
+                let receiver = RemoveCustomRemoteRequest::get_dart_signal_receiver();
+                while let Some(req) = receiver.recv().await {
+                    debug!(name = %req.message.name, "Received RemoveCustomRemoteRequest");
+                    controller.remove_custom_remote(&req.message.name).await;
+                }
+
+                panic!("RemoveCustomRemoteRequest receiver closed")
+            }
+        });
     }
 }
 
 impl DownloaderAvailabilityReporter {
-    fn new(cfg: &DownloaderConfig, capabilities: RepoCapabilities) -> Self {
+    fn new(
+        cfg: &DownloaderConfig,
+        capabilities: RepoCapabilities,
+        manager: Arc<DownloaderManager>,
+    ) -> Self {
         Self {
+            manager,
             config_id: cfg.id.clone(),
-            is_donation_configured: capabilities.supports_donation_upload
-                && cfg.donation_remote_name.is_some()
-                && cfg.donation_remote_path.is_some(),
+            is_donation_configured: cfg
+                .is_donation_configured(capabilities.supports_donation_upload),
             capabilities,
         }
     }
@@ -434,30 +614,39 @@ impl DownloaderAvailabilityReporter {
     fn send_initializing(&self) {
         let mut signal = self.signal();
         signal.initializing = true;
-        signal.send_signal_to_dart();
+        self.manager.report_availability(signal);
     }
 
     fn send_available(&self) {
         let mut signal = self.signal();
         signal.available = true;
-        signal.send_signal_to_dart();
+        self.manager.report_availability(signal);
     }
 
     fn send_error(&self, context: &str, error: &anyhow::Error) {
         let mut signal = self.signal();
         signal.error = Some(format!("Failed to {context}: {error:#}"));
-        signal.send_signal_to_dart();
+        self.manager.report_availability(signal);
     }
 }
 
 async fn prepare_downloader_runtime(
     cache_dir: &Path,
     cfg: &DownloaderConfig,
+    custom_remotes: &CustomRemotes,
 ) -> Result<(Option<std::path::PathBuf>, Option<std::path::PathBuf>)> {
     match cfg.layout {
-        RepoLayoutKind::Ffa => crate::downloader::rclone::prepare_rclone_files(cache_dir, cfg)
-            .await
-            .map(|(rclone_path, rclone_config_path)| (Some(rclone_path), Some(rclone_config_path))),
+        RepoLayoutKind::Ffa => {
+            let (rclone_path, mut rclone_config_path) =
+                crate::downloader::rclone::prepare_rclone_files(cache_dir, cfg).await?;
+            let custom = custom_remotes.load()?;
+            if !custom.is_empty() {
+                debug!(count = custom.len(), "Adding custom remotes to rclone config");
+                rclone_config_path =
+                    write_merged_config(cache_dir, &rclone_config_path, &custom).await?;
+            }
+            Ok((Some(rclone_path), Some(rclone_config_path)))
+        }
         RepoLayoutKind::NewRepo => Ok((None, None)),
     }
 }
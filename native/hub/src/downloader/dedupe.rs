@@ -0,0 +1,497 @@
+//! Content-addressable store deduplicating files shared by downloaded releases.
+//!
+//! Large files of finished downloads are hashed and hard-linked to a single object in a store
+//! under the downloads location, keyed by checksum. The store index keeps the release files
+//! linked to each object, so an object is removed once no release refers to it anymore.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs::Metadata,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs,
+    io::{AsyncRead, AsyncReadExt},
+    sync::Mutex,
+};
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    downloader::http_cache::compute_md5_file, models::signals::downloads_local::DedupeReport,
+};
+
+/// Directory under the downloads location holding deduplicated file objects
+pub(crate) const DEDUPE_STORE_DIR: &str = ".yaas_store";
+const INDEX_FILE: &str = "index.json";
+/// Files smaller than this are not worth deduplicating
+const MIN_DEDUPE_SIZE: u64 = 1024 * 1024;
+
+/// Serializes changes to the store between finished downloads and manual runs
+static STORE_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreIndex {
+    /// Objects keyed by the MD5 checksum of their content
+    #[serde(default)]
+    objects: BTreeMap<String, StoreObject>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreObject {
+    size: u64,
+    /// Release files linked to the object, relative to the downloads location
+    refs: BTreeSet<String>,
+}
+
+/// Outcome of deduplicating releases
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct DedupeSummary {
+    /// Files replaced by a link to an object that another release already had
+    pub(crate) linked: u32,
+    /// Bytes freed by the replaced files
+    pub(crate) freed_bytes: u64,
+}
+
+impl StoreIndex {
+    async fn load(root: &Path) -> Result<Self> {
+        let path = root.join(DEDUPE_STORE_DIR).join(INDEX_FILE);
+        match fs::read_to_string(&path).await {
+            Ok(content) => serde_json::from_str(&content).context("Failed to parse store index"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).context("Failed to read store index"),
+        }
+    }
+
+    async fn save(&self, root: &Path) -> Result<()> {
+        let dir = root.join(DEDUPE_STORE_DIR);
+        fs::create_dir_all(&dir).await.context("Failed to create store directory")?;
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize store index")?;
+        let path = dir.join(INDEX_FILE);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).await.context("Failed to write store index")?;
+        fs::rename(&tmp_path, &path).await.context("Failed to replace store index")
+    }
+
+    /// Whether the release file `relative` is still a link to the object it is recorded for,
+    /// rather than a file written in its place since
+    async fn is_linked(&self, root: &Path, relative: &str, meta: &Metadata) -> bool {
+        let Some((hash, _)) = self
+            .objects
+            .iter()
+            .find(|(_, object)| object.size == meta.len() && object.refs.contains(relative))
+        else {
+            return false;
+        };
+        same_file(&root.join(relative), &object_path(root, hash)).await
+    }
+
+    fn remove_ref(&mut self, relative: &str) {
+        for object in self.objects.values_mut() {
+            object.refs.remove(relative);
+        }
+    }
+
+    fn report(&self) -> DedupeReport {
+        let referenced = self.objects.values().filter(|object| !object.refs.is_empty());
+        DedupeReport {
+            objects: referenced.clone().count() as u32,
+            linked_files: referenced.clone().map(|object| object.refs.len() as u32).sum(),
+            saved_bytes: referenced
+                .map(|object| object.size * (object.refs.len() as u64).saturating_sub(1))
+                .sum(),
+            newly_linked: 0,
+            error: None,
+        }
+    }
+}
+
+/// Whether two paths are the same file, i.e. one is a hard link of the other. Compares device
+/// and inode on Unix, volume serial number and file index on Windows.
+async fn same_file(a: &Path, b: &Path) -> bool {
+    let (a, b) = (a.to_path_buf(), b.to_path_buf());
+    tokio::task::spawn_blocking(move || ::same_file::is_same_file(a, b))
+        .await
+        .is_ok_and(|result| result.unwrap_or(false))
+}
+
+/// Whether two files hold the same bytes
+async fn same_content(a: &Path, b: &Path) -> Result<bool> {
+    let open = |path: &Path| {
+        let path = path.to_path_buf();
+        async move {
+            fs::File::open(&path)
+                .await
+                .with_context(|| format!("Failed to open {}", path.display()))
+        }
+    };
+    let (mut file_a, mut file_b) = (open(a).await?, open(b).await?);
+    if file_a.metadata().await?.len() != file_b.metadata().await?.len() {
+        return Ok(false);
+    }
+    let (mut buf_a, mut buf_b) = (vec![0u8; 64 * 1024], vec![0u8; 64 * 1024]);
+    loop {
+        let read_a = read_chunk(&mut file_a, &mut buf_a).await?;
+        let read_b = read_chunk(&mut file_b, &mut buf_b).await?;
+        if buf_a[..read_a] != buf_b[..read_b] {
+            return Ok(false);
+        }
+        if read_a == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Fills `buf` from `reader` unless it ends first, returning how much was read
+async fn read_chunk(reader: &mut (impl AsyncRead + Unpin), buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let read = reader.read(&mut buf[filled..]).await.context("Failed to read file")?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    Ok(filled)
+}
+
+/// Whether the release file at `path` still holds the content of `object`, checking the bytes
+/// unless it is a link to it
+async fn matches_object(object: &Path, path: &Path) -> Result<bool> {
+    let (object_meta, meta) = (fs::metadata(object).await?, fs::metadata(path).await?);
+    if object_meta.len() != meta.len() {
+        return Ok(false);
+    }
+    if same_file(object, path).await {
+        return Ok(true);
+    }
+    same_content(object, path).await
+}
+
+fn object_path(root: &Path, hash: &str) -> PathBuf {
+    root.join(DEDUPE_STORE_DIR).join(&hash[..2]).join(hash)
+}
+
+/// Path of a release file relative to the downloads location, with `/` separators
+fn relative_key(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Files below `dir` large enough to be deduplicated, with their metadata
+async fn large_files(dir: &Path) -> Result<Vec<(PathBuf, Metadata)>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut rd = fs::read_dir(&dir)
+            .await
+            .with_context(|| format!("Failed to read {}", dir.display()))?;
+        while let Some(entry) = rd.next_entry().await? {
+            let meta = entry.metadata().await?;
+            if meta.is_dir() {
+                pending.push(entry.path());
+            } else if meta.is_file() && meta.len() >= MIN_DEDUPE_SIZE {
+                files.push((entry.path(), meta));
+            }
+        }
+    }
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(files)
+}
+
+/// Hard-links the large files of the given releases to store objects, replacing files whose
+/// content another release already has.
+///
+/// Files that cannot be hard-linked, for example on file systems without link support, are left
+/// as they are.
+#[instrument(level = "debug", skip(release_dirs), fields(releases = release_dirs.len()), err)]
+pub(crate) async fn dedupe_releases(
+    root: &Path,
+    release_dirs: &[PathBuf],
+) -> Result<DedupeSummary> {
+    let _guard = STORE_LOCK.lock().await;
+    let mut index = StoreIndex::load(root).await?;
+    let mut summary = DedupeSummary::default();
+    for release_dir in release_dirs {
+        for (path, meta) in large_files(release_dir).await? {
+            let Some(relative) = relative_key(root, &path) else {
+                continue;
+            };
+            if index.is_linked(root, &relative, &meta).await {
+                continue;
+            }
+            let size = meta.len();
+            index.remove_ref(&relative);
+            let hash = compute_md5_file(&path).await?;
+            let object = object_path(root, &hash);
+            let existing = fs::metadata(&object).await.ok().filter(|meta| meta.len() == size);
+            let result = match existing {
+                // Equal checksums are not trusted to mean equal content
+                Some(_) => match same_content(&object, &path).await {
+                    Ok(true) => replace_with_link(&object, &path).await.map(|()| {
+                        summary.linked += 1;
+                        summary.freed_bytes += size;
+                    }),
+                    Ok(false) => {
+                        warn!(path = %path.display(), hash, "File differs from the store object with its checksum, leaving it as is");
+                        continue;
+                    }
+                    Err(e) => Err(e),
+                },
+                None => add_object(&path, &object).await,
+            };
+            match result {
+                Ok(()) => {
+                    let entry = index.objects.entry(hash).or_default();
+                    entry.size = size;
+                    entry.refs.insert(relative);
+                }
+                Err(e) => {
+                    warn!(error = e.as_ref() as &dyn Error, path = %path.display(), "Failed to deduplicate file");
+                }
+            }
+        }
+    }
+    index.save(root).await?;
+    if summary.linked > 0 {
+        info!(?summary, "Deduplicated downloaded files");
+    }
+    Ok(summary)
+}
+
+async fn add_object(path: &Path, object: &Path) -> Result<()> {
+    if let Some(parent) = object.parent() {
+        fs::create_dir_all(parent).await.context("Failed to create store directory")?;
+    }
+    let _ = fs::remove_file(object).await;
+    fs::hard_link(path, object).await.context("Failed to link file into store")
+}
+
+/// Replaces `path` with a hard link to `object`, leaving `path` untouched on failure
+async fn replace_with_link(object: &Path, path: &Path) -> Result<()> {
+    let mut tmp_name = path.file_name().context("File has no name")?.to_os_string();
+    tmp_name.push(".yaas-link");
+    let tmp_path = path.with_file_name(tmp_name);
+    let _ = fs::remove_file(&tmp_path).await;
+    fs::hard_link(object, &tmp_path).await.context("Failed to link store object")?;
+    if let Err(e) = fs::rename(&tmp_path, path).await {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(e).context("Failed to replace file with link");
+    }
+    // Renaming onto a link to the same object leaves both names in place
+    let _ = fs::remove_file(&tmp_path).await;
+    Ok(())
+}
+
+/// Links release files to their store objects again, after a move that copied them as separate
+/// files, e.g. to another drive. Files whose content no longer matches their object are left as
+/// they are. Returns how many files were linked.
+#[instrument(level = "debug", err, ret)]
+pub(crate) async fn relink(root: &Path) -> Result<u32> {
+    let _guard = STORE_LOCK.lock().await;
+    let index = StoreIndex::load(root).await?;
+    let mut linked = 0;
+    for (hash, object) in &index.objects {
+        let object_file = object_path(root, hash);
+        if !fs::metadata(&object_file).await.is_ok_and(|meta| meta.len() == object.size) {
+            continue;
+        }
+        for relative in &object.refs {
+            let path = root.join(relative);
+            match fs::metadata(&path).await {
+                Ok(meta) if meta.len() == object.size => {}
+                _ => continue,
+            }
+            match same_content(&object_file, &path).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    debug!(path = %path.display(), "File changed since it was deduplicated, not linking it");
+                    continue;
+                }
+                Err(e) => {
+                    warn!(error = e.as_ref() as &dyn Error, path = %path.display(), "Failed to compare file with store object");
+                    continue;
+                }
+            }
+            match replace_with_link(&object_file, &path).await {
+                Ok(()) => linked += 1,
+                Err(e) => {
+                    warn!(
+                        error = e.as_ref() as &dyn Error,
+                        path = %path.display(),
+                        "Failed to link file to store again"
+                    );
+                }
+            }
+        }
+    }
+    Ok(linked)
+}
+
+/// Drops references to release files that are gone or no longer hold the content of their
+/// object, removing objects no release refers to anymore, and reports the space saved by the
+/// store.
+#[instrument(level = "debug", err)]
+pub(crate) async fn prune(root: &Path) -> Result<DedupeReport> {
+    let _guard = STORE_LOCK.lock().await;
+    let mut index = StoreIndex::load(root).await?;
+    let mut removed = 0;
+    for (hash, object) in &mut index.objects {
+        let object_file = object_path(root, hash);
+        let mut live = BTreeSet::new();
+        for relative in &object.refs {
+            if matches_object(&object_file, &root.join(relative)).await.unwrap_or(false) {
+                live.insert(relative.clone());
+            }
+        }
+        object.refs = live;
+        if object.refs.is_empty() {
+            if let Err(e) = fs::remove_file(&object_file).await
+                && e.kind() != std::io::ErrorKind::NotFound
+            {
+                warn!(error = &e as &dyn Error, hash, "Failed to remove store object");
+                continue;
+            }
+            removed += 1;
+        }
+    }
+    index.objects.retain(|_, object| !object.refs.is_empty());
+    if removed > 0 || root.join(DEDUPE_STORE_DIR).exists() {
+        index.save(root).await?;
+    }
+    debug!(removed, objects = index.objects.len(), "Pruned download store");
+    Ok(index.report())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn write_release(root: &Path, name: &str, content: &[u8]) -> PathBuf {
+        let dir = root.join(name);
+        fs::create_dir_all(dir.join("com.example.game")).await.unwrap();
+        fs::write(dir.join("com.example.game").join("main.1.com.example.game.obb"), content)
+            .await
+            .unwrap();
+        fs::write(dir.join("metadata.json"), b"{}").await.unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn links_shared_files_and_prunes_unreferenced_objects() {
+        let root = tempfile::tempdir().unwrap();
+        let content = vec![7u8; MIN_DEDUPE_SIZE as usize];
+        let v1 = write_release(root.path(), "Game v1+1", &content).await;
+        let v2 = write_release(root.path(), "Game v2+2", &content).await;
+
+        let summary = dedupe_releases(root.path(), &[v1.clone(), v2.clone()]).await.unwrap();
+        assert_eq!(summary, DedupeSummary { linked: 1, freed_bytes: MIN_DEDUPE_SIZE });
+        // Already linked files are not linked again
+        let summary = dedupe_releases(root.path(), &[v1.clone(), v2.clone()]).await.unwrap();
+        assert_eq!(summary, DedupeSummary::default());
+
+        let report = prune(root.path()).await.unwrap();
+        assert_eq!((report.objects, report.linked_files), (1, 2));
+        assert_eq!(report.saved_bytes, MIN_DEDUPE_SIZE);
+
+        fs::remove_dir_all(&v1).await.unwrap();
+        let report = prune(root.path()).await.unwrap();
+        assert_eq!((report.objects, report.linked_files, report.saved_bytes), (1, 1, 0));
+
+        fs::remove_dir_all(&v2).await.unwrap();
+        let report = prune(root.path()).await.unwrap();
+        assert_eq!(report.objects, 0);
+        let index = StoreIndex::load(root.path()).await.unwrap();
+        assert!(index.objects.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rehashes_files_replaced_with_the_same_size() {
+        let root = tempfile::tempdir().unwrap();
+        let content = vec![7u8; MIN_DEDUPE_SIZE as usize];
+        let v1 = write_release(root.path(), "Game v1+1", &content).await;
+        dedupe_releases(root.path(), std::slice::from_ref(&v1)).await.unwrap();
+
+        let file = v1.join("com.example.game").join("main.1.com.example.game.obb");
+        fs::remove_file(&file).await.unwrap();
+        fs::write(&file, vec![8u8; MIN_DEDUPE_SIZE as usize]).await.unwrap();
+        dedupe_releases(root.path(), std::slice::from_ref(&v1)).await.unwrap();
+
+        let index = StoreIndex::load(root.path()).await.unwrap();
+        let relative = relative_key(root.path(), &file).unwrap();
+        let linked = index.objects.values().filter(|object| object.refs.contains(&relative));
+        assert_eq!(linked.count(), 1);
+        assert_eq!(index.objects.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn keeps_files_changed_since_deduplication() {
+        let root = tempfile::tempdir().unwrap();
+        let content = vec![7u8; MIN_DEDUPE_SIZE as usize];
+        let v1 = write_release(root.path(), "Game v1+1", &content).await;
+        let v2 = write_release(root.path(), "Game v2+2", &content).await;
+        dedupe_releases(root.path(), &[v1.clone(), v2.clone()]).await.unwrap();
+
+        // A copy left by a move, then changed without changing its size
+        let file = v2.join("com.example.game").join("main.1.com.example.game.obb");
+        fs::remove_file(&file).await.unwrap();
+        let mut changed = content.clone();
+        changed[0] = 8;
+        fs::write(&file, &changed).await.unwrap();
+
+        assert_eq!(relink(root.path()).await.unwrap(), 1);
+        assert_eq!(fs::read(&file).await.unwrap(), changed);
+        let report = prune(root.path()).await.unwrap();
+        assert_eq!((report.objects, report.linked_files), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn compares_file_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, c) = (dir.path().join("a"), dir.path().join("b"), dir.path().join("c"));
+        let content = vec![7u8; 200 * 1024];
+        fs::write(&a, &content).await.unwrap();
+        fs::write(&b, &content).await.unwrap();
+        let mut changed = content.clone();
+        *changed.last_mut().unwrap() = 8;
+        fs::write(&c, &changed).await.unwrap();
+
+        assert!(same_content(&a, &b).await.unwrap());
+        assert!(!same_content(&a, &c).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn relinks_files_copied_by_a_move() {
+        let root = tempfile::tempdir().unwrap();
+        let content = vec![7u8; MIN_DEDUPE_SIZE as usize];
+        let v1 = write_release(root.path(), "Game v1+1", &content).await;
+        let v2 = write_release(root.path(), "Game v2+2", &content).await;
+        dedupe_releases(root.path(), &[v1.clone(), v2.clone()]).await.unwrap();
+
+        // A move to another drive leaves separate copies
+        for dir in [&v1, &v2] {
+            let file = dir.join("com.example.game").join("main.1.com.example.game.obb");
+            fs::remove_file(&file).await.unwrap();
+            fs::write(&file, &content).await.unwrap();
+        }
+
+        assert_eq!(relink(root.path()).await.unwrap(), 2);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let index = StoreIndex::load(root.path()).await.unwrap();
+            let hash = index.objects.keys().next().unwrap();
+            let meta = fs::metadata(object_path(root.path(), hash)).await.unwrap();
+            assert_eq!(meta.nlink(), 3);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Uploads of donated app archives to the HTTP endpoint set as `donation_upload_url`.
+
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use futures::{StreamExt, stream};
+use tokio::{fs::File, io::AsyncReadExt, sync::mpsc::UnboundedSender};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, instrument};
+
+use super::{SensitiveUrl, TransferSpeedTracker, TransferStats};
+
+const CHUNK_SIZE: usize = 256 * 1024;
+const SPEED_SAMPLE_WINDOW: Duration = Duration::from_secs(8);
+
+/// URL an archive named `file_name` is uploaded to, below `base_url`
+pub(super) fn upload_url(base_url: &str, file_name: &str) -> Result<reqwest::Url> {
+    let mut url = reqwest::Url::parse(base_url).context("Invalid donation upload URL")?;
+    url.path_segments_mut()
+        .map_err(|()| anyhow!("Donation upload URL cannot have a path"))?
+        .pop_if_empty()
+        .push(file_name);
+    Ok(url)
+}
+
+/// Uploads a file with a single PUT request, sending its MD5 for the server to check
+#[instrument(skip_all, fields(url = %SensitiveUrl::new(url.as_str())))]
+pub(super) async fn put_file(
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+    path: &Path,
+    md5_hex: &str,
+    stats_tx: Option<UnboundedSender<TransferStats>>,
+    cancellation_token: CancellationToken,
+) -> Result<()> {
+    let file = File::open(path)
+        .await
+        .with_context(|| format!("Failed to open {} for upload", path.display()))?;
+    let total = file.metadata().await?.len();
+    let content_md5 = base64::engine::general_purpose::STANDARD
+        .encode(const_hex::decode(md5_hex).context("Invalid MD5 checksum")?);
+
+    let started_at = Instant::now();
+    let mut speed_tracker = TransferSpeedTracker::new(SPEED_SAMPLE_WINDOW);
+    let mut uploaded = 0u64;
+    let body = stream::try_unfold(file, |mut file| async move {
+        let mut chunk = vec![0u8; CHUNK_SIZE];
+        let n = file.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok::<_, std::io::Error>(None);
+        }
+        chunk.truncate(n);
+        Ok(Some((chunk, file)))
+    })
+    .inspect(move |chunk| {
+        if let (Ok(chunk), Some(stats_tx)) = (chunk, &stats_tx) {
+            uploaded += chunk.len() as u64;
+            let speed = speed_tracker.record(uploaded, started_at.elapsed().as_millis());
+            let _ = stats_tx.send(TransferStats {
+                bytes: uploaded,
+                total_bytes: Some(total),
+                speed,
+                ..Default::default()
+            });
+        }
+    });
+
+    let request = client
+        .put(url.clone())
+        .header(reqwest::header::CONTENT_LENGTH, total)
+        .header("Content-MD5", content_md5)
+        .body(reqwest::Body::wrap_stream(body))
+        .send();
+    let response = tokio::select! {
+        _ = cancellation_token.cancelled() => bail!("Upload cancelled"),
+        response = request => response,
+    };
+    let status = response
+        .map_err(reqwest::Error::without_url)
+        .context("Failed to send donation archive")?
+        .status();
+    if !status.is_success() {
+        bail!("Donation server responded with {status}");
+    }
+    debug!(bytes = total, "Donation archive uploaded");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upload_url_appends_the_file_name() {
+        let url = upload_url("https://example.com/donations/", "App v1 com.app.zip").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/donations/App%20v1%20com.app.zip");
+
+        let url = upload_url("https://example.com/donations?token=abc", "a.zip").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/donations/a.zip?token=abc");
+    }
+}
@@ -0,0 +1,286 @@
+//! Offline checks of local app folders, reporting what would make an install fail.
+
+use std::{collections::HashSet, path::Path};
+
+use anyhow::{Context, Result, ensure};
+use lazy_regex::regex_captures;
+use tokio::fs;
+use tracing::{debug, instrument};
+
+use crate::{
+    adb::device::{ScriptAction, ScriptCommand, parse_install_script, read_split_set},
+    archive::list_archive_file_paths,
+    models::{
+        apk_info::get_apk_info,
+        signals::downloads_local::{DownloadHealthIssue, DownloadHealthReport},
+    },
+};
+
+/// Checks a local app folder the way sideloading would use it.
+///
+/// Folders with an install script are checked for files the script needs, others for a single
+/// readable APK, or split APKs of one app, and OBB files in a folder named after its package.
+#[instrument(level = "debug", fields(dir = %dir.display()), err)]
+pub(crate) async fn check_download_health(dir: &Path) -> Result<DownloadHealthReport> {
+    ensure!(dir.is_dir(), "App folder does not exist: {}", dir.display());
+
+    let mut files = Vec::new();
+    let mut subdirs = Vec::new();
+    let mut read_dir = fs::read_dir(dir).await.context("Failed to read app folder")?;
+    while let Some(entry) = read_dir.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type().await?.is_dir() {
+            subdirs.push(name);
+        } else {
+            files.push(name);
+        }
+    }
+    files.sort();
+    subdirs.sort();
+
+    let mut issues = Vec::new();
+    let script = files.iter().find(|name| name.eq_ignore_ascii_case("install.txt"));
+    let apks = files.iter().filter(|name| name.ends_with(".apk")).collect::<Vec<_>>();
+
+    // A script may install any number of APKs, the package is only known for a single one or
+    // for split APKs of one app
+    let apk_info = match apks.as_slice() {
+        [] => None,
+        [apk] => match get_apk_info(dir.join(apk)) {
+            Ok(info) => Some(info),
+            Err(e) => {
+                issues.push(DownloadHealthIssue::ApkUnreadable {
+                    file: apk.to_string(),
+                    error: format!("{e:#}"),
+                });
+                None
+            }
+        },
+        _ => read_split_set(&apks.iter().map(|apk| dir.join(apk)).collect::<Vec<_>>()).ok(),
+    };
+
+    if let Some(script) = script {
+        issues.extend(check_script(dir, &files, &dir.join(script)).await);
+    } else {
+        match apks.len() {
+            0 => issues.push(DownloadHealthIssue::NoApk),
+            1 => {}
+            _ if apk_info.is_some() => {}
+            _ => issues.push(DownloadHealthIssue::MultipleApks(
+                apks.iter().map(|name| name.to_string()).collect(),
+            )),
+        }
+        if let Some(info) = &apk_info {
+            issues.extend(check_obb_layout(dir, &files, &subdirs, &info.package_name).await?);
+        }
+    }
+
+    debug!(issues = issues.len(), "Checked app folder");
+    Ok(DownloadHealthReport {
+        path: dir.display().to_string(),
+        package_name: apk_info.as_ref().map(|info| info.package_name.clone()),
+        version_code: apk_info.and_then(|info| info.version_code),
+        uses_install_script: script.is_some(),
+        issues,
+        error: None,
+    })
+}
+
+/// Finds OBB files that sideloading would not push to `/sdcard/Android/obb/<package>`
+async fn check_obb_layout(
+    dir: &Path,
+    files: &[String],
+    subdirs: &[String],
+    package_name: &str,
+) -> Result<Vec<DownloadHealthIssue>> {
+    let mut issues = Vec::new();
+    let loose = files.iter().filter(|name| is_obb(name)).cloned().collect::<Vec<_>>();
+    if !loose.is_empty() {
+        issues.push(DownloadHealthIssue::LooseObbFiles(loose));
+    }
+
+    for subdir in subdirs {
+        let mut obb_files = Vec::new();
+        let mut read_dir = fs::read_dir(dir.join(subdir)).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_obb(&name) {
+                obb_files.push(name);
+            }
+        }
+        obb_files.sort();
+        if obb_files.is_empty() {
+            continue;
+        }
+
+        if subdir != package_name {
+            issues.push(DownloadHealthIssue::ObbFolderMismatch {
+                folder: subdir.clone(),
+                package_name: package_name.to_string(),
+            });
+            continue;
+        }
+        for file in obb_files {
+            if obb_file_package(&file).is_some_and(|package| package != package_name) {
+                issues.push(DownloadHealthIssue::ObbFileMismatch {
+                    file,
+                    package_name: package_name.to_string(),
+                });
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Checks that the files an install script installs or pushes exist, either in the folder or
+/// in a 7z archive the script runner unpacks first
+async fn check_script(dir: &Path, files: &[String], script: &Path) -> Vec<DownloadHealthIssue> {
+    let commands = match fs::read_to_string(script)
+        .await
+        .context("Failed to read install script")
+        .and_then(|content| parse_install_script(&content))
+    {
+        Ok(commands) => commands,
+        Err(e) => return vec![DownloadHealthIssue::ScriptUnreadable(format!("{e:#}"))],
+    };
+
+    let mut issues = Vec::new();
+    let mut archived = HashSet::new();
+    for archive in files.iter().filter(|name| name.to_lowercase().ends_with(".7z")) {
+        match list_archive_file_paths(&dir.join(archive)).await {
+            Ok(paths) => archived.extend(paths),
+            Err(e) => issues.push(DownloadHealthIssue::ArchiveUnreadable {
+                file: archive.clone(),
+                error: format!("{e:#}"),
+            }),
+        }
+    }
+
+    issues.extend(script_issues(&commands, |path| {
+        dir.join(path).exists()
+            || archived.contains(path)
+            || archived
+                .iter()
+                .any(|entry| entry.strip_prefix(path).is_some_and(|rest| rest.starts_with('/')))
+    }));
+    issues
+}
+
+/// Checks script commands, `exists` tells whether a path relative to the folder will exist when
+/// the command runs
+fn script_issues(
+    commands: &[ScriptCommand],
+    exists: impl Fn(&str) -> bool,
+) -> Vec<DownloadHealthIssue> {
+    let mut issues = Vec::new();
+    // Files pulled from the device by earlier lines
+    let mut pulled = HashSet::new();
+    for script_command in commands {
+        let line = script_command.line_num as u32;
+        let local_paths = match script_command.action() {
+            Ok(ScriptAction::Install(apk)) => vec![apk],
+            Ok(ScriptAction::InstallMultiple(apks)) => apks,
+            Ok(ScriptAction::Push { source, .. }) => vec![source],
+            Ok(ScriptAction::Pull { dest, .. }) => {
+                pulled.insert(normalize_script_path(&dest));
+                continue;
+            }
+            Ok(_) => continue,
+            Err(_) => {
+                issues.push(DownloadHealthIssue::ScriptCommandUnsupported {
+                    line,
+                    command: script_command.command.clone(),
+                });
+                continue;
+            }
+        };
+        for path in local_paths.iter().map(|path| normalize_script_path(path)) {
+            if !exists(&path) && !pulled.contains(&path) {
+                issues.push(DownloadHealthIssue::ScriptFileMissing { line, path });
+            }
+        }
+    }
+    issues
+}
+
+/// Makes a path from an install script comparable to archive entries
+fn normalize_script_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").trim_end_matches('/').to_string()
+}
+
+fn is_obb(name: &str) -> bool {
+    name.to_lowercase().ends_with(".obb")
+}
+
+/// Package an OBB file belongs to according to the `main.<version>.<package>.obb` convention
+fn obb_file_package(name: &str) -> Option<&str> {
+    regex_captures!(r"^(?:main|patch)\.\d+\.(.+)\.obb$", name).map(|(_, package)| package)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_install_script_commands() {
+        let script = [
+            "7z x data.7z",
+            "adb uninstall com.example.game",
+            "adb install -r -g \"Game v1.apk\"",
+            "adb push .\\com.example.game /sdcard/Android/obb/",
+            "adb pull /sdcard/save.dat save.dat",
+            "adb push save.dat /sdcard/Download/",
+            "adb push missing.bin /sdcard/",
+            "adb reboot",
+            "copy a b",
+            "adb install-multiple base.apk config.arm64_v8a.apk",
+            "sleep 2",
+            "timeout /t 600 /nobreak",
+        ]
+        .join("\n");
+        let commands = parse_install_script(&script).unwrap();
+        let present = ["Game v1.apk", "com.example.game", "base.apk"];
+
+        assert_eq!(
+            script_issues(&commands, |path| present.contains(&path)),
+            [
+                DownloadHealthIssue::ScriptFileMissing { line: 7, path: "missing.bin".to_string() },
+                DownloadHealthIssue::ScriptCommandUnsupported {
+                    line: 8,
+                    command: "adb reboot".to_string()
+                },
+                DownloadHealthIssue::ScriptCommandUnsupported {
+                    line: 9,
+                    command: "copy a b".to_string()
+                },
+                DownloadHealthIssue::ScriptFileMissing {
+                    line: 10,
+                    path: "config.arm64_v8a.apk".to_string()
+                },
+                DownloadHealthIssue::ScriptCommandUnsupported {
+                    line: 12,
+                    command: "timeout /t 600 /nobreak".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_obb_file_packages() {
+        assert_eq!(obb_file_package("main.42.com.example.game.obb"), Some("com.example.game"));
+        assert_eq!(obb_file_package("patch.1.com.example.obb"), Some("com.example"));
+        assert_eq!(obb_file_package("data.obb"), None);
+    }
+
+    #[tokio::test]
+    async fn reports_folders_without_apk() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("readme.txt"), "").unwrap();
+
+        let report = check_download_health(dir.path()).await.unwrap();
+        assert_eq!(report.issues, [DownloadHealthIssue::NoApk]);
+        assert!(!report.uses_install_script);
+        assert_eq!(report.package_name, None);
+    }
+}
@@ -6,22 +6,31 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use anyhow::{Context, Result, ensure};
+use anyhow::{Context, Result, bail, ensure};
 use lazy_regex::regex;
 use rinf::{DartSignal, RustSignal};
 use tokio::fs;
 use tokio_stream::{StreamExt, wrappers::WatchStream};
-use tracing::{Span, debug, error, info, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::{
-    downloader::download_metadata::read_metadata,
+    downloader::{
+        dedupe, download_health::check_download_health, download_metadata::read_metadata,
+        downloads_index::DownloadsIndex, layout::find_release_dirs, resume,
+    },
     models::{DownloadCleanupPolicy, Settings, signals::downloads_local::*},
-    task::DONATE_TMP_DIR,
+    utils::{dir_size, move_path},
 };
 
+/// Marks a download moved to the folder for installed downloads, so a later move of the same
+/// release may replace it
+const MOVED_MARKER_FILE: &str = ".yaas-moved";
+
 #[derive(Debug, Clone)]
 pub(crate) struct DownloadsCatalog {
     root: Arc<tokio::sync::RwLock<PathBuf>>,
+    /// Index of finished downloads for `QueryDownloadsRequest`, built on first use
+    index: Arc<tokio::sync::Mutex<Option<Arc<DownloadsIndex>>>>,
 }
 
 impl DownloadsCatalog {
@@ -31,6 +40,7 @@ impl DownloadsCatalog {
 
         let handler = Arc::new(Self {
             root: Arc::new(tokio::sync::RwLock::new(initial_settings.downloads_location())),
+            index: Arc::new(tokio::sync::Mutex::new(None)),
         });
 
         // Watch settings updates
@@ -40,6 +50,7 @@ impl DownloadsCatalog {
                 while let Some(settings) = settings_stream.next().await {
                     debug!(dir = %settings.downloads_location().display(), "Downloads location updated");
                     *handler.root.write().await = settings.downloads_location();
+                    handler.invalidate_index().await;
                 }
                 panic!("Settings stream closed");
             });
@@ -56,28 +67,39 @@ impl DownloadsCatalog {
 
     #[instrument(level = "debug", skip(self))]
     async fn receive_signals(self: Arc<Self>) {
-        let list_receiver = GetDownloadsRequest::get_dart_signal_receiver();
+        let query_receiver = QueryDownloadsRequest::get_dart_signal_receiver();
         let get_dir_receiver = GetDownloadsDirectoryRequest::get_dart_signal_receiver();
         let delete_receiver = DeleteDownloadRequest::get_dart_signal_receiver();
         let delete_all_receiver = DeleteAllDownloadsRequest::get_dart_signal_receiver();
+        let resumable_receiver = GetResumableDownloadsRequest::get_dart_signal_receiver();
+        let discard_resumable_receiver =
+            DiscardResumableDownloadRequest::get_dart_signal_receiver();
+        let health_receiver = CheckDownloadHealthRequest::get_dart_signal_receiver();
+        let dedupe_receiver = DedupeDownloadsRequest::get_dart_signal_receiver();
 
         loop {
             tokio::select! {
-                signal = list_receiver.recv() => {
-                    if signal.is_some() {
-                        debug!("Received GetDownloadsRequest");
-                        match self.list_downloads().await {
-                            Ok(mut entries) => {
-                                entries.sort_by(|a, b| a.name.cmp(&b.name));
-                                GetDownloadsResponse { entries, error: None }.send_signal_to_dart();
-                            }
+                request = query_receiver.recv() => {
+                    if let Some(request) = request {
+                        let request = request.message;
+                        debug!(search = %request.search, offset = request.offset, "Received QueryDownloadsRequest");
+                        match self.query_downloads(&request).await {
+                            Ok(response) => response.send_signal_to_dart(),
                             Err(e) => {
                                 error!(error = %format!("{e:#}"), "Failed to list downloads");
-                                GetDownloadsResponse { entries: vec![], error: Some(format!("{e:#}")) }.send_signal_to_dart();
+                                QueryDownloadsResponse {
+                                    search: request.search,
+                                    offset: request.offset,
+                                    entries: Vec::new(),
+                                    total: 0,
+                                    newest_version_codes: Default::default(),
+                                    error: Some(format!("{e:#}")),
+                                }
+                                .send_signal_to_dart();
                             }
                         }
                     } else {
-                        panic!("GetDownloadsRequest receiver closed");
+                        panic!("QueryDownloadsRequest receiver closed");
                     }
                 }
                 request = get_dir_receiver.recv() => {
@@ -126,28 +148,147 @@ impl DownloadsCatalog {
                         panic!("DeleteAllDownloadsRequest receiver closed");
                     }
                 }
+                request = resumable_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received GetResumableDownloadsRequest");
+                        match self.list_resumable_downloads().await {
+                            Ok(entries) => {
+                                GetResumableDownloadsResponse { entries, error: None }.send_signal_to_dart();
+                            }
+                            Err(e) => {
+                                error!(error = %format!("{e:#}"), "Failed to list resumable downloads");
+                                GetResumableDownloadsResponse { entries: vec![], error: Some(format!("{e:#}")) }.send_signal_to_dart();
+                            }
+                        }
+                    } else {
+                        panic!("GetResumableDownloadsRequest receiver closed");
+                    }
+                }
+                request = discard_resumable_receiver.recv() => {
+                    if let Some(request) = request {
+                        let app_full_name = request.message.app_full_name;
+                        debug!(%app_full_name, "Received DiscardResumableDownloadRequest");
+                        match self.discard_resumable_download(&app_full_name).await {
+                            Ok(()) => {
+                                DiscardResumableDownloadResponse { app_full_name, error: None }.send_signal_to_dart();
+                            }
+                            Err(e) => {
+                                error!(%app_full_name, error = %format!("{e:#}"), "Failed to discard resumable download");
+                                DiscardResumableDownloadResponse { app_full_name, error: Some(format!("{e:#}")) }.send_signal_to_dart();
+                            }
+                        }
+                    } else {
+                        panic!("DiscardResumableDownloadRequest receiver closed");
+                    }
+                }
+                request = health_receiver.recv() => {
+                    if let Some(request) = request {
+                        let path = request.message.path;
+                        debug!(%path, "Received CheckDownloadHealthRequest");
+                        match check_download_health(Path::new(&path)).await {
+                            Ok(report) => report.send_signal_to_dart(),
+                            Err(e) => {
+                                error!(%path, error = %format!("{e:#}"), "Failed to check download health");
+                                DownloadHealthReport {
+                                    path,
+                                    package_name: None,
+                                    version_code: None,
+                                    uses_install_script: false,
+                                    issues: Vec::new(),
+                                    error: Some(format!("{e:#}")),
+                                }
+                                .send_signal_to_dart();
+                            }
+                        }
+                    } else {
+                        panic!("CheckDownloadHealthRequest receiver closed");
+                    }
+                }
+                request = dedupe_receiver.recv() => {
+                    if let Some(request) = request {
+                        let apply = request.message.apply;
+                        debug!(apply, "Received DedupeDownloadsRequest");
+                        // Hashing large downloads takes a while, keep answering other requests
+                        let handler = self.clone();
+                        tokio::spawn(async move {
+                            match handler.dedupe_downloads(apply).await {
+                                Ok(report) => report.send_signal_to_dart(),
+                                Err(e) => {
+                                    error!(error = %format!("{e:#}"), "Failed to deduplicate downloads");
+                                    DedupeReport {
+                                        error: Some(format!("{e:#}")),
+                                        ..Default::default()
+                                    }
+                                    .send_signal_to_dart();
+                                }
+                            }
+                        });
+                    } else {
+                        panic!("DedupeDownloadsRequest receiver closed");
+                    }
+                }
             }
         }
     }
 
+    /// Answers a query from the index, rebuilding it first if asked to or if there is none
+    async fn query_downloads(
+        &self,
+        request: &QueryDownloadsRequest,
+    ) -> Result<QueryDownloadsResponse> {
+        let index = {
+            let mut index = self.index.lock().await;
+            match &*index {
+                Some(current) if !request.refresh => current.clone(),
+                _ => {
+                    let built = Arc::new(DownloadsIndex::new(self.list_downloads().await?));
+                    *index = Some(built.clone());
+                    built
+                }
+            }
+        };
+        Ok(index.query(request))
+    }
+
+    /// Links files shared by finished downloads when `apply` is set, then reports the savings
+    #[instrument(level = "debug", skip(self), err)]
+    async fn dedupe_downloads(&self, apply: bool) -> Result<DedupeReport> {
+        let root = self.root.read().await.clone();
+        let mut newly_linked = 0;
+        if apply {
+            let releases = self
+                .list_downloads()
+                .await?
+                .into_iter()
+                .map(|entry| PathBuf::from(entry.path))
+                .collect::<Vec<_>>();
+            newly_linked = dedupe::dedupe_releases(&root, &releases).await?.linked;
+        }
+        let report = dedupe::prune(&root).await?;
+        Ok(DedupeReport { newly_linked, ..report })
+    }
+
+    /// Drops store objects of deleted downloads, failures only leave unused objects behind
+    async fn prune_store(&self, root: &Path) {
+        if let Err(e) = dedupe::prune(root).await {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to prune download store");
+        }
+    }
+
+    async fn invalidate_index(&self) {
+        *self.index.lock().await = None;
+    }
+
     #[instrument(level = "debug", skip(self), err)]
     async fn list_downloads(&self) -> Result<Vec<DownloadEntry>> {
         let root = self.root.read().await.clone();
+        // Unfinished downloads are listed separately as resumable
+        let unfinished: HashSet<String> =
+            resume::list_checkpoints(&root).await?.into_iter().map(|c| c.app_full_name).collect();
         let mut entries: Vec<DownloadEntry> = Vec::new();
-        let mut rd = fs::read_dir(&root)
-            .await
-            .with_context(|| format!("Failed to read {}", root.display()))?;
-        while let Some(entry) = rd.next_entry().await? {
-            let p = entry.path();
-            if p.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase() == DONATE_TMP_DIR
-            {
-                continue;
-            }
-            let meta = match entry.metadata().await {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-            if !meta.is_dir() {
+        for p in find_release_dirs(&root).await? {
+            let file_name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if unfinished.contains(file_name) {
                 continue;
             }
             if let Some(e) = self.try_build_download_entry(&p).await? {
@@ -157,6 +298,37 @@ impl DownloadsCatalog {
         Ok(entries)
     }
 
+    #[instrument(level = "debug", skip(self), err)]
+    async fn list_resumable_downloads(&self) -> Result<Vec<ResumableDownloadEntry>> {
+        let root = self.root.read().await.clone();
+        let mut entries = Vec::new();
+        for checkpoint in resume::list_checkpoints(&root).await? {
+            let downloaded_bytes = dir_size(&checkpoint.download_dir(&root)).await.unwrap_or(0);
+            entries.push(ResumableDownloadEntry {
+                app_full_name: checkpoint.app_full_name,
+                true_package_name: checkpoint.true_package_name,
+                started_at: checkpoint.started_at,
+                updated_at: checkpoint.updated_at,
+                attempts: checkpoint.attempts,
+                downloaded_bytes,
+            });
+        }
+        Ok(entries)
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    async fn discard_resumable_download(&self, app_full_name: &str) -> Result<()> {
+        let root = self.root.read().await.clone();
+        let checkpoint = resume::load_checkpoint(&root, app_full_name)
+            .await?
+            .with_context(|| format!("No interrupted download found for {app_full_name}"))?;
+        let partial_dir = checkpoint.download_dir(&root);
+        if partial_dir.is_dir() {
+            self.delete_download(&partial_dir).await?;
+        }
+        resume::remove_checkpoint(&root, app_full_name).await
+    }
+
     #[instrument(level = "debug", skip(self), fields(dir = %dir.display()), err)]
     async fn try_build_download_entry(&self, dir: &Path) -> Result<Option<DownloadEntry>> {
         if !dir.is_dir() {
@@ -194,35 +366,22 @@ impl DownloadsCatalog {
     }
 }
 
-fn system_time_to_millis(time: SystemTime) -> u64 {
-    time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
-}
-#[instrument(level = "debug", fields(dir = %dir.display(), size), err)]
-async fn dir_size(dir: &Path) -> Result<u64> {
-    if !dir.is_dir() {
-        return Ok(0);
-    }
-    let mut total: u64 = 0;
-    let mut stack: Vec<PathBuf> = vec![dir.to_path_buf()];
-    while let Some(path) = stack.pop() {
-        let mut rd = match fs::read_dir(&path).await {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-        while let Some(entry) = rd.next_entry().await? {
-            let meta = match entry.metadata().await {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-            if meta.is_file() {
-                total = total.saturating_add(meta.len());
-            } else if meta.is_dir() {
-                stack.push(entry.path());
-            }
+/// Removes the layout folders above a deleted release that are now empty
+async fn remove_empty_layout_dirs(release_dir: &Path, root: &Path) {
+    for dir in release_dir.ancestors().skip(1) {
+        if dir == root || !dir.starts_with(root) {
+            break;
+        }
+        // Fails for non-empty directories, which ends the walk
+        if fs::remove_dir(dir).await.is_err() {
+            break;
         }
+        debug!(path = %dir.display(), "Removed empty layout folder");
     }
-    Span::current().record("size", total);
-    Ok(total)
+}
+
+fn system_time_to_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
 }
 
 impl DownloadsCatalog {
@@ -237,6 +396,7 @@ impl DownloadsCatalog {
         policy: DownloadCleanupPolicy,
         installed_full_name: &str,
         installed_path: &str,
+        installed_downloads_dir: &Path,
     ) -> Result<()> {
         use DownloadCleanupPolicy as Policy;
         // Match versioned download directory names: `{name} v{version}+{build}`
@@ -262,6 +422,20 @@ impl DownloadsCatalog {
                 info!(removed = %path.display(), "Removed downloaded directory after install");
                 return Ok(());
             }
+            Policy::MoveAfterInstall => {
+                let path = Path::new(installed_path);
+                if !path.exists() {
+                    debug!(missing = %path.display(), "Downloaded directory no longer exists");
+                    return Ok(());
+                }
+                ensure!(
+                    !installed_downloads_dir.as_os_str().is_empty(),
+                    "No folder set to move installed downloads to"
+                );
+                let moved_to = self.move_download(path, installed_downloads_dir).await?;
+                info!(moved_to = %moved_to.display(), "Moved downloaded directory after install");
+                return Ok(());
+            }
             Policy::KeepOneVersion | Policy::KeepTwoVersions => {
                 let keep_total = match policy {
                     Policy::KeepOneVersion => 1,
@@ -350,6 +524,52 @@ impl DownloadsCatalog {
         }
     }
 
+    /// Moves a download into `dest_root`, returning its new path. A download of the same name
+    /// moved there earlier is replaced, any other folder of that name fails the move.
+    #[instrument(skip(self), err)]
+    async fn move_download(&self, path: &Path, dest_root: &Path) -> Result<PathBuf> {
+        let root = self.root.read().await.clone();
+        let canon_root = fs::canonicalize(root).await?;
+        let canon_req = fs::canonicalize(path).await?;
+        ensure!(
+            canon_req.starts_with(&canon_root),
+            "Requested path is outside downloads directory"
+        );
+        ensure!(canon_req.is_dir(), "Download path is not a directory");
+        ensure!(dest_root.is_absolute(), "Folder for installed downloads must be an absolute path");
+        fs::create_dir_all(dest_root)
+            .await
+            .context("Failed to create folder for installed downloads")?;
+        let dest_root = fs::canonicalize(dest_root).await?;
+        ensure!(
+            !dest_root.starts_with(&canon_root) && !canon_root.starts_with(&dest_root),
+            "Folder for installed downloads must be outside the downloads directory"
+        );
+        let name = canon_req.file_name().context("Download path has no name")?;
+        let dest = dest_root.join(name);
+        ensure!(
+            !canon_req.starts_with(&dest),
+            "Moving the download would replace the download itself"
+        );
+        if fs::try_exists(&dest).await.unwrap_or(false) {
+            if !fs::try_exists(dest.join(MOVED_MARKER_FILE)).await.unwrap_or(false) {
+                bail!(
+                    "{} already exists and was not moved there by YAAS, not replacing it",
+                    dest.display()
+                );
+            }
+            debug!(path = %dest.display(), "Replacing previously moved download");
+            fs::remove_dir_all(&dest).await.context("Failed to replace moved download")?;
+        }
+        move_path(&canon_req, &dest).await.context("Failed to move download directory")?;
+        if let Err(e) = fs::write(dest.join(MOVED_MARKER_FILE), b"").await {
+            warn!(error = &e as &dyn Error, path = %dest.display(), "Failed to mark moved download");
+        }
+        remove_empty_layout_dirs(&canon_req, &canon_root).await;
+        self.invalidate_index().await;
+        Ok(dest)
+    }
+
     #[instrument(skip(self), err)]
     async fn delete_download(&self, path: &Path) -> Result<()> {
         let root = self.root.read().await.clone();
@@ -362,25 +582,24 @@ impl DownloadsCatalog {
         ensure!(canon_req.is_dir(), "Download path is not a directory");
         info!(path = %canon_req.display(), "Deleting download directory");
         fs::remove_dir_all(&canon_req).await.context("Failed to delete download directory")?;
+        remove_empty_layout_dirs(&canon_req, &canon_root).await;
+        self.prune_store(&canon_root).await;
+        self.invalidate_index().await;
         Ok(())
     }
 
+    /// Deletes every release that has a metadata file, leaving unrelated folders, resume state
+    /// and donation uploads alone. Returns how many releases were deleted and how many failed.
     #[instrument(skip(self), err, ret)]
-    async fn delete_all_downloads(&self) -> Result<(u32, u32)> {
+    pub(crate) async fn delete_all_downloads(&self) -> Result<(u32, u32)> {
         info!("Deleting all downloads");
         let root = self.root.read().await.clone();
+        if !fs::try_exists(&root).await.unwrap_or(false) {
+            return Ok((0, 0));
+        }
         let mut removed: u32 = 0;
         let mut skipped: u32 = 0;
-        let mut rd = fs::read_dir(&root).await?;
-        while let Some(entry) = rd.next_entry().await? {
-            let dir = entry.path();
-            let meta = match entry.metadata().await {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-            if !meta.is_dir() {
-                continue;
-            }
+        for dir in find_release_dirs(&root).await? {
             // Only delete directories that contain metadata.json or release.json
             let meta_path = dir.join("metadata.json");
             let meta_path_alt = dir.join("release.json");
@@ -392,6 +611,7 @@ impl DownloadsCatalog {
                 match fs::remove_dir_all(&dir).await {
                     Ok(()) => {
                         removed += 1;
+                        remove_empty_layout_dirs(&dir, &root).await;
                     }
                     Err(_) => {
                         skipped += 1;
@@ -399,6 +619,8 @@ impl DownloadsCatalog {
                 }
             }
         }
+        self.prune_store(&root).await;
+        self.invalidate_index().await;
         Ok((removed, skipped))
     }
 }
@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::signals::downloads_local::{
+    DownloadEntry, DownloadsSortOrder, QueryDownloadsRequest, QueryDownloadsResponse,
+};
+
+/// In-memory index of the downloads directory answering searches from the UI
+#[derive(Debug, Default)]
+pub(super) struct DownloadsIndex {
+    entries: Vec<IndexedEntry>,
+    newest_version_codes: HashMap<String, u32>,
+}
+
+#[derive(Debug)]
+struct IndexedEntry {
+    entry: DownloadEntry,
+    /// Lowercased name and package name searches are matched against
+    haystack: String,
+    sort_name: String,
+}
+
+impl DownloadsIndex {
+    pub(super) fn new(entries: Vec<DownloadEntry>) -> Self {
+        let mut newest_version_codes: HashMap<String, u32> = HashMap::new();
+        for entry in &entries {
+            if let (Some(package), Some(code)) = (&entry.package_name, entry.version_code)
+                && !package.is_empty()
+            {
+                let newest = newest_version_codes.entry(package.clone()).or_insert(code);
+                *newest = (*newest).max(code);
+            }
+        }
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let sort_name = entry.name.to_lowercase();
+                let haystack = match &entry.package_name {
+                    Some(package) => format!("{sort_name}\n{}", package.to_lowercase()),
+                    None => sort_name.clone(),
+                };
+                IndexedEntry { entry, haystack, sort_name }
+            })
+            .collect();
+        Self { entries, newest_version_codes }
+    }
+
+    pub(super) fn query(&self, request: &QueryDownloadsRequest) -> QueryDownloadsResponse {
+        let terms: Vec<String> =
+            request.search.split_whitespace().map(|term| term.to_lowercase()).collect();
+        let mut matches: Vec<&IndexedEntry> = self
+            .entries
+            .iter()
+            .filter(|indexed| terms.iter().all(|term| indexed.haystack.contains(term.as_str())))
+            .collect();
+
+        matches.sort_by(|a, b| {
+            let by_name = || a.sort_name.cmp(&b.sort_name);
+            let ordering = match request.sort {
+                DownloadsSortOrder::Name => by_name(),
+                DownloadsSortOrder::Date => {
+                    a.entry.timestamp.cmp(&b.entry.timestamp).then_with(by_name)
+                }
+                DownloadsSortOrder::Size => {
+                    a.entry.total_size.cmp(&b.entry.total_size).then_with(by_name)
+                }
+            };
+            if request.ascending { ordering } else { ordering.reverse() }
+        });
+
+        let total = matches.len();
+        let limit = if request.limit == 0 { usize::MAX } else { request.limit as usize };
+        let entries: Vec<DownloadEntry> = matches
+            .into_iter()
+            .skip(request.offset as usize)
+            .take(limit)
+            .map(|indexed| indexed.entry.clone())
+            .collect();
+        let newest_version_codes: BTreeMap<String, u32> = entries
+            .iter()
+            .filter_map(|entry| entry.package_name.as_ref())
+            .filter_map(|package| {
+                self.newest_version_codes.get(package).map(|code| (package.clone(), *code))
+            })
+            .collect();
+
+        QueryDownloadsResponse {
+            search: request.search.clone(),
+            offset: request.offset,
+            entries,
+            total: total as u32,
+            newest_version_codes,
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, package: Option<&str>, version: u32, timestamp: u64) -> DownloadEntry {
+        DownloadEntry {
+            path: format!("/downloads/{name}"),
+            name: name.to_string(),
+            timestamp,
+            total_size: u64::from(version) * 1000,
+            package_name: package.map(str::to_string),
+            version_code: package.map(|_| version),
+        }
+    }
+
+    fn request(
+        search: &str,
+        sort: DownloadsSortOrder,
+        offset: u32,
+        limit: u32,
+    ) -> QueryDownloadsRequest {
+        QueryDownloadsRequest {
+            search: search.to_string(),
+            sort,
+            ascending: true,
+            offset,
+            limit,
+            refresh: false,
+        }
+    }
+
+    fn index() -> DownloadsIndex {
+        DownloadsIndex::new(vec![
+            entry("Beat Saber v1+1", Some("com.beatgames.beatsaber"), 1, 30),
+            entry("Beat Saber v2+2", Some("com.beatgames.beatsaber"), 2, 10),
+            entry("Superhot v5+1", Some("com.superhot.vr"), 5, 20),
+            entry("Sideloaded", None, 0, 40),
+        ])
+    }
+
+    fn names(response: &QueryDownloadsResponse) -> Vec<&str> {
+        response.entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn search_matches_all_words_in_name_or_package() {
+        let index = index();
+
+        let response = index.query(&request("saber V2", DownloadsSortOrder::Name, 0, 0));
+        assert_eq!(names(&response), ["Beat Saber v2+2"]);
+        assert_eq!(response.total, 1);
+
+        let response = index.query(&request("superhot.vr", DownloadsSortOrder::Name, 0, 0));
+        assert_eq!(names(&response), ["Superhot v5+1"]);
+
+        assert_eq!(index.query(&request("", DownloadsSortOrder::Name, 0, 0)).total, 4);
+    }
+
+    #[test]
+    fn pages_are_sorted_and_report_newest_versions() {
+        let index = index();
+
+        let mut by_date = request("", DownloadsSortOrder::Date, 1, 2);
+        by_date.ascending = false;
+        let response = index.query(&by_date);
+        assert_eq!(names(&response), ["Beat Saber v1+1", "Superhot v5+1"]);
+        assert_eq!(response.total, 4);
+        assert_eq!(response.offset, 1);
+        assert_eq!(
+            response.newest_version_codes,
+            BTreeMap::from([
+                ("com.beatgames.beatsaber".to_string(), 2),
+                ("com.superhot.vr".to_string(), 5),
+            ])
+        );
+
+        let response = index.query(&request("", DownloadsSortOrder::Size, 3, 10));
+        assert_eq!(names(&response), ["Superhot v5+1"]);
+        assert!(index.query(&request("", DownloadsSortOrder::Size, 10, 10)).entries.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, error::Error, path::Path};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail, ensure};
 use fs_err::tokio::{self as fs, File, OpenOptions};
 use fs4::fs_err3_tokio::AsyncFileExt as _;
 use reqwest::StatusCode;
@@ -105,17 +105,40 @@ pub(crate) enum DownloadResult {
 }
 
 /// Download a file or use cached copy if available.
+pub(crate) async fn update_file_cached(
+    client: &reqwest::Client,
+    url: &str,
+    dst: &Path,
+    cache_dir: &Path,
+    progress: Option<fn(u64, Option<u64>)>,
+) -> Result<DownloadResult> {
+    fetch_cached(client, url, dst, cache_dir, progress, None).await
+}
+
+/// Like [`update_file_cached`], but fails without touching `dst` if the remote file is larger
+/// than `max_size` bytes.
+pub(crate) async fn update_file_cached_limited(
+    client: &reqwest::Client,
+    url: &str,
+    dst: &Path,
+    cache_dir: &Path,
+    max_size: u64,
+) -> Result<DownloadResult> {
+    fetch_cached(client, url, dst, cache_dir, None, Some(max_size)).await
+}
+
 #[instrument(
     level = "debug",
     skip(client, progress),
     fields(url = %SensitiveUrl::new(url), dst = %dst.display(), cache_dir = %cache_dir.display())
 )]
-pub(crate) async fn update_file_cached(
+async fn fetch_cached(
     client: &reqwest::Client,
     url: &str,
     dst: &Path,
     cache_dir: &Path,
     progress: Option<fn(u64, Option<u64>)>,
+    max_size: Option<u64>,
 ) -> Result<DownloadResult> {
     fs::create_dir_all(cache_dir)
         .await
@@ -180,6 +203,12 @@ pub(crate) async fn update_file_cached(
         .map_err(reqwest::Error::without_url)
         .with_context(|| format!("Request failed for {sanitized_url}"))?;
     let header_meta = extract_header_meta(resp.headers());
+    if let (Some(max_size), Some(length)) = (max_size, resp.content_length()) {
+        ensure!(
+            length <= max_size,
+            "{sanitized_url} is too large ({length} bytes, limit is {max_size} bytes)"
+        );
+    }
 
     debug!(
         has_previous_meta = had_prev,
@@ -209,9 +238,16 @@ pub(crate) async fn update_file_cached(
         let chunk = item
             .map_err(reqwest::Error::without_url)
             .with_context(|| format!("Failed to read response body from {sanitized_url}"))?;
+        downloaded += chunk.len() as u64;
+        if let Some(max_size) = max_size
+            && downloaded > max_size
+        {
+            drop(tmp_file);
+            let _ = fs::remove_file(&tmp).await;
+            bail!("{sanitized_url} is too large (limit is {max_size} bytes)");
+        }
         tmp_file.write_all(&chunk).await?;
         md5_ctx.consume(&chunk);
-        downloaded += chunk.len() as u64;
         if let Some(cb) = progress {
             let now = Instant::now();
             if now.duration_since(last_emit) >= min_interval && downloaded != last_reported {
@@ -309,7 +345,7 @@ async fn swap_and_persist(
     Ok(())
 }
 
-pub(super) async fn compute_md5_file(path: &Path) -> Result<String> {
+pub(crate) async fn compute_md5_file(path: &Path) -> Result<String> {
     let mut file = File::open(path)
         .await
         .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
@@ -679,6 +715,28 @@ mod tests {
         assert!(CALLS.load(Ordering::Relaxed) >= 1);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn limited_download_rejects_large_files() {
+        let dir = tempdir().unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/large.bin"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"1234567890"))
+            .mount(&server)
+            .await;
+
+        let client = client();
+        let url = format!("{}/large.bin", server.uri());
+        let dst = dir.path().join("large.bin");
+        assert!(update_file_cached_limited(&client, &url, &dst, dir.path(), 4).await.is_err());
+        assert!(!dst.exists());
+        assert!(!dst.with_extension("tmp").exists());
+
+        let r = update_file_cached_limited(&client, &url, &dst, dir.path(), 10).await.unwrap();
+        assert!(matches!(r, DownloadResult::Downloaded(_)));
+        assert_eq!(fs::read_to_string(&dst).await.unwrap(), "1234567890");
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn local_consistency_checks() {
         let dir = tempdir().unwrap();
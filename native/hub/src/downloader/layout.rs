@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tokio::fs;
+use tracing::{instrument, trace};
+
+use super::{dedupe::DEDUPE_STORE_DIR, resume::RESUME_STATE_DIR};
+use crate::task::DONATE_TMP_DIR;
+
+/// Deepest level below the downloads location that is searched for release directories
+const MAX_LAYOUT_DEPTH: usize = 4;
+
+/// Folder layout of downloaded apps below the downloads location.
+///
+/// The template names the folders a release directory is placed in, separated by `/`.
+/// `{letter}`, `{name}` and `{package}` are replaced with the first letter of the app name, the
+/// app name and the package name. An empty template keeps releases directly in the downloads
+/// location.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DownloadLayout {
+    template: String,
+}
+
+impl DownloadLayout {
+    pub(crate) fn new(template: &str) -> Self {
+        Self { template: template.trim().to_string() }
+    }
+
+    /// Returns the directory of a release relative to the downloads location
+    pub(crate) fn relative_dir(
+        &self,
+        full_name: &str,
+        app_name: Option<&str>,
+        package_name: &str,
+    ) -> PathBuf {
+        let name = app_name.filter(|n| !n.trim().is_empty()).unwrap_or(full_name).trim();
+        let letter = match name.chars().next() {
+            Some(c) if c.is_alphabetic() => c.to_uppercase().collect(),
+            _ => "#".to_string(),
+        };
+
+        let mut path = PathBuf::new();
+        for component in self.template.split(['/', '\\']) {
+            let component = component
+                .replace("{letter}", &letter)
+                .replace("{name}", name)
+                .replace("{package}", package_name);
+            let component = sanitize_filename::sanitize(component.trim());
+            if !component.is_empty() && component != "." && component != ".." {
+                path.push(component);
+            }
+        }
+        path.push(full_name);
+        path
+    }
+}
+
+/// Finds release directories below `root`, following any layout template.
+///
+/// A directory holding files is a release, one holding only directories is a layout folder and is
+/// searched further.
+#[instrument(level = "debug", err)]
+pub(crate) async fn find_release_dirs(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut releases = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let mut rd = fs::read_dir(&dir)
+            .await
+            .with_context(|| format!("Failed to read {}", dir.display()))?;
+        while let Some(entry) = rd.next_entry().await? {
+            let path = entry.path();
+            let Ok(meta) = entry.metadata().await else {
+                continue;
+            };
+            if !meta.is_dir() {
+                continue;
+            }
+            if depth == 0 {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                if name.eq_ignore_ascii_case(DONATE_TMP_DIR)
+                    || name == RESUME_STATE_DIR
+                    || name == DEDUPE_STORE_DIR
+                {
+                    continue;
+                }
+            }
+            if has_files(&path).await || depth + 1 >= MAX_LAYOUT_DEPTH {
+                trace!(path = %path.display(), "Found release directory");
+                releases.push(path);
+            } else {
+                pending.push((path, depth + 1));
+            }
+        }
+    }
+    Ok(releases)
+}
+
+async fn has_files(dir: &Path) -> bool {
+    let Ok(mut rd) = fs::read_dir(dir).await else {
+        return false;
+    };
+    while let Ok(Some(entry)) = rd.next_entry().await {
+        if entry.file_type().await.is_ok_and(|t| t.is_file()) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_layout_templates() {
+        let full_name = "Beat Saber v1+1.0";
+        let package = "com.beatgames.beatsaber";
+        let dir = |template: &str, app_name: Option<&str>| {
+            DownloadLayout::new(template).relative_dir(full_name, app_name, package)
+        };
+
+        assert_eq!(dir("", Some("Beat Saber")), PathBuf::from(full_name));
+        assert_eq!(dir("{letter}", Some("beat Saber")), Path::new("B").join(full_name));
+        assert_eq!(dir("{letter}", Some("2048")), Path::new("#").join(full_name));
+        assert_eq!(dir("{package}/", None), Path::new(package).join(full_name));
+        assert_eq!(
+            dir("Games/{name}", Some("Beat: Saber")),
+            Path::new("Games").join("Beat Saber").join(full_name)
+        );
+        assert_eq!(dir("../{letter}", Some("Beat Saber")), Path::new("B").join(full_name));
+    }
+
+    #[tokio::test]
+    async fn finds_releases_in_layout_folders() {
+        let root = tempfile::tempdir().unwrap();
+        let flat = root.path().join("Flat App v1+1");
+        let nested = root.path().join("B").join("Beat Saber v1+1");
+        for dir in [&flat, &nested] {
+            fs::create_dir_all(dir.join("com.example")).await.unwrap();
+            fs::write(dir.join("app.apk"), b"apk").await.unwrap();
+        }
+        fs::create_dir_all(root.path().join(RESUME_STATE_DIR).join("x")).await.unwrap();
+        let store_dir = root.path().join(DEDUPE_STORE_DIR).join("ab");
+        fs::create_dir_all(&store_dir).await.unwrap();
+        fs::write(store_dir.join("abcdef"), b"apk").await.unwrap();
+        fs::create_dir_all(root.path().join("Empty")).await.unwrap();
+
+        let mut found = find_release_dirs(root.path()).await.unwrap();
+        found.sort();
+        assert_eq!(found, vec![nested, flat]);
+    }
+}
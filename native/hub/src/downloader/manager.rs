@@ -1,19 +1,59 @@
 use std::sync::Arc;
 
 use anyhow::{Result, anyhow};
-use tokio::sync::RwLock;
+use rinf::RustSignal;
+use tokio::sync::{RwLock, watch};
 use tracing::{debug, instrument};
 
-use crate::downloader::Downloader;
+use crate::{
+    downloader::Downloader,
+    models::signals::downloader::availability::DownloaderAvailabilityChanged,
+};
 
 #[derive(Clone, Default)]
 pub(crate) struct DownloaderManager {
     current: Arc<RwLock<Option<Arc<Downloader>>>>,
+    available_tx: watch::Sender<bool>,
+    /// Availability last sent to Dart
+    availability: Arc<std::sync::Mutex<Option<DownloaderAvailabilityChanged>>>,
 }
 
 impl DownloaderManager {
     pub(crate) fn new() -> Arc<Self> {
-        Arc::new(Self { current: Arc::new(RwLock::new(None)) })
+        Arc::new(Self {
+            current: Arc::new(RwLock::new(None)),
+            available_tx: watch::Sender::new(false),
+            availability: Arc::default(),
+        })
+    }
+
+    /// Waits until a downloader instance is set.
+    pub(crate) async fn wait_available(&self) {
+        let mut rx = self.available_tx.subscribe();
+        // The sender is owned by `self`, so it cannot be dropped while we wait
+        let _ = rx.wait_for(|available| *available).await;
+    }
+
+    /// Create a receiver for downloader availability changes
+    pub(crate) fn subscribe_available(&self) -> watch::Receiver<bool> {
+        self.available_tx.subscribe()
+    }
+
+    /// Sends the availability to Dart and keeps it for `resend_state`
+    pub(crate) fn report_availability(&self, signal: DownloaderAvailabilityChanged) {
+        *self.availability.lock().expect("availability lock poisoned") = Some(signal.clone());
+        signal.send_signal_to_dart();
+    }
+
+    /// Sends the last availability and the cached app list to Dart again
+    pub(crate) async fn resend_state(&self) {
+        let availability = self.availability.lock().expect("availability lock poisoned").clone();
+        if let Some(availability) = availability {
+            availability.send_signal_to_dart();
+        }
+        if let Some(downloader) = self.get().await {
+            downloader.resend_app_list().await;
+        }
     }
 
     pub(crate) async fn get(&self) -> Option<Arc<Downloader>> {
@@ -41,8 +81,10 @@ impl DownloaderManager {
     async fn set(&self, downloader: Option<Arc<Downloader>>) {
         let mut guard = self.current.write().await;
         let old = guard.take();
+        let available = downloader.is_some();
         *guard = downloader;
         drop(guard);
+        self.available_tx.send_replace(available);
 
         if let Some(downloader) = old {
             downloader.stop().await;
@@ -0,0 +1,133 @@
+use std::time::{Duration, Instant};
+
+use super::repo::RepoStorage;
+use crate::models::Settings;
+
+/// Mirrors a download falls back to, in order, when the selected remote fails or stays slow
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct MirrorPolicy {
+    mirrors: Vec<String>,
+    /// Bytes per second below which a transfer counts as slow, 0 to never switch because of speed
+    min_speed: u64,
+    slow_for: Duration,
+}
+
+impl MirrorPolicy {
+    pub(super) fn from_settings(settings: &Settings) -> Self {
+        Self {
+            mirrors: settings
+                .download_mirrors
+                .iter()
+                .map(|mirror| mirror.trim().to_string())
+                .filter(|mirror| !mirror.is_empty())
+                .collect(),
+            min_speed: u64::from(settings.mirror_min_speed_kbps) * 1000,
+            slow_for: Duration::from_secs(settings.mirror_slow_secs.max(1).into()),
+        }
+    }
+
+    /// Storages to download from in order, starting with `primary`. Mirrors the storage cannot
+    /// use and duplicates are left out.
+    pub(super) fn candidates(&self, primary: RepoStorage) -> Vec<RepoStorage> {
+        let mut candidates = vec![primary];
+        for mirror in &self.mirrors {
+            if let Some(storage) = candidates[0].with_mirror(mirror)
+                && !candidates.contains(&storage)
+            {
+                candidates.push(storage);
+            }
+        }
+        candidates
+    }
+
+    /// Detector for transfers that should move on to the next mirror, if speed is checked at all
+    pub(super) fn slow_detector(&self, now: Instant) -> Option<SlowTransferDetector> {
+        (self.min_speed > 0).then_some(SlowTransferDetector {
+            min_speed: self.min_speed,
+            slow_for: self.slow_for,
+            slow_since: None,
+            paused: false,
+            started_at: now,
+        })
+    }
+}
+
+/// Tells when a transfer stayed below the minimum speed for too long.
+///
+/// Only transfer phases are judged, a status update pauses the check until the next transfer
+/// stats so local work like extraction does not count as a slow mirror.
+#[derive(Debug)]
+pub(super) struct SlowTransferDetector {
+    min_speed: u64,
+    slow_for: Duration,
+    slow_since: Option<Instant>,
+    paused: bool,
+    /// Transfers get `slow_for` to pick up speed before they are judged
+    started_at: Instant,
+}
+
+impl SlowTransferDetector {
+    pub(super) fn observe_speed(&mut self, speed: u64, now: Instant) {
+        self.paused = false;
+        if speed >= self.min_speed {
+            self.slow_since = None;
+        } else if self.slow_since.is_none() {
+            self.slow_since = Some(now.max(self.started_at));
+        }
+    }
+
+    pub(super) fn pause(&mut self) {
+        self.paused = true;
+        self.slow_since = None;
+    }
+
+    pub(super) fn is_slow(&self, now: Instant) -> bool {
+        !self.paused
+            && now.saturating_duration_since(self.started_at) >= self.slow_for
+            && self
+                .slow_since
+                .is_some_and(|since| now.saturating_duration_since(since) >= self.slow_for)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector(start: Instant) -> SlowTransferDetector {
+        let mut settings = Settings::default();
+        settings.mirror_min_speed_kbps = 100;
+        settings.mirror_slow_secs = 10;
+        MirrorPolicy::from_settings(&settings).slow_detector(start).unwrap()
+    }
+
+    #[test]
+    fn slow_transfers_are_reported_after_the_grace_period() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut detector = detector(start);
+
+        detector.observe_speed(50_000, at(1));
+        assert!(!detector.is_slow(at(5)));
+        assert!(detector.is_slow(at(11)));
+
+        // Speeding up resets the clock
+        detector.observe_speed(200_000, at(12));
+        detector.observe_speed(10_000, at(13));
+        assert!(!detector.is_slow(at(20)));
+        assert!(detector.is_slow(at(23)));
+
+        // Local work between transfers is not judged
+        detector.pause();
+        assert!(!detector.is_slow(at(60)));
+    }
+
+    #[test]
+    fn speed_checks_are_off_without_a_minimum_speed() {
+        let mut settings = Settings::default();
+        settings.download_mirrors = vec![" FFA-2 ".to_string(), String::new()];
+        let policy = MirrorPolicy::from_settings(&settings);
+        assert!(policy.slow_detector(Instant::now()).is_none());
+        assert_eq!(policy.mirrors, ["FFA-2"]);
+    }
+}
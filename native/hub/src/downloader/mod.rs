@@ -1,17 +1,31 @@
 mod progress;
-pub(crate) use progress::{TransferSpeedTracker, TransferStats};
+pub(crate) use progress::{
+    DownloadMetrics, SpeedAverage, TransferSpeedTracker, TransferStats, remaining_secs,
+};
+mod bandwidth;
+pub(crate) use bandwidth::{BandwidthLimit, Throttle};
+mod catalog_delta;
 mod cloud_api;
 pub(crate) mod config;
 pub(crate) mod controller;
+pub(crate) mod dedupe;
+mod donation;
+mod download_health;
 pub(crate) mod download_metadata;
-mod http_cache;
+pub(crate) mod http_cache;
+pub(crate) mod layout;
 pub(crate) mod manager;
+mod mirrors;
 mod rclone;
 mod repo;
+pub(crate) mod resume;
 mod service;
 pub(crate) use service::Downloader;
 pub(crate) mod downloads_catalog;
+mod downloads_index;
 pub(crate) mod sources;
+pub(crate) mod sweeper;
+pub(crate) mod tls;
 
 #[derive(Clone, Copy)]
 pub(crate) struct SensitiveUrl<'a>(&'a str);
@@ -76,7 +90,24 @@ fn sanitize_raw_url_for_log(url: &str) -> String {
 #[derive(Debug, Clone)]
 pub(crate) enum AppDownloadProgress {
     Status(String),
+    /// Files are about to be fetched from `source`. `attempt` counts interrupted earlier attempts
+    /// of the same download.
+    Started {
+        source: String,
+        attempt: u32,
+    },
     Transfer(TransferStats),
+    /// Local files already match the remote, nothing was transferred
+    Skipped,
+}
+
+/// Outcome of checking a finished download against the remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DownloadVerification {
+    /// Every remote file exists locally with the same content
+    Verified { files: usize },
+    /// The repository has no way to compare downloads with the remote
+    Unsupported,
 }
 
 #[cfg(test)]
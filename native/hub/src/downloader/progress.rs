@@ -1,10 +1,30 @@
-use std::{collections::VecDeque, time::Duration};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use crate::models::signals::task::{DownloadSummary, FileTransferProgress, TransferProgress};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct TransferStats {
     pub bytes: u64,
     pub total_bytes: Option<u64>,
     pub speed: u64,
+    /// Seconds left at the current speed, if the source estimates it
+    pub eta_secs: Option<u64>,
+    /// Finished and total files of a multi-file transfer, 0 when unknown
+    pub files_done: u32,
+    pub files_total: u32,
+    /// Files being transferred right now
+    pub files: Vec<FileTransferProgress>,
+}
+
+impl From<TransferStats> for TransferProgress {
+    fn from(stats: TransferStats) -> Self {
+        let TransferStats { bytes, total_bytes, speed, eta_secs, files_done, files_total, files } =
+            stats;
+        Self { bytes, total_bytes, speed, eta_secs, files_done, files_total, files }
+    }
 }
 
 #[derive(Debug)]
@@ -38,6 +58,87 @@ impl TransferSpeedTracker {
     }
 }
 
+/// Exponential moving average of a transfer speed.
+///
+/// Samples are weighted by the time since the previous one, so irregular update intervals do not
+/// skew the average. Older samples fade out over `time_constant`.
+#[derive(Debug)]
+pub(crate) struct SpeedAverage {
+    time_constant_millis: f64,
+    /// Average and the time of the sample it was last updated with
+    last: Option<(f64, u128)>,
+}
+
+impl SpeedAverage {
+    pub(crate) fn new(time_constant: Duration) -> Self {
+        Self { time_constant_millis: time_constant.as_millis().max(1) as f64, last: None }
+    }
+
+    /// Adds a speed sample taken `elapsed_millis` into the transfer and returns the average
+    pub(crate) fn record(&mut self, speed: u64, elapsed_millis: u128) -> u64 {
+        let sample = speed as f64;
+        let average = match self.last {
+            Some((average, at)) => {
+                let span = elapsed_millis.saturating_sub(at) as f64;
+                let weight = 1.0 - (-span / self.time_constant_millis).exp();
+                average + weight * (sample - average)
+            }
+            None => sample,
+        };
+        self.last = Some((average, elapsed_millis));
+        average.round() as u64
+    }
+
+    /// Forgets the average, so the next sample starts over, e.g. after a stall
+    pub(crate) fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Seconds until `total_bytes` are transferred at `speed`
+pub(crate) fn remaining_secs(bytes: u64, total_bytes: Option<u64>, speed: u64) -> Option<u64> {
+    let remaining = total_bytes?.checked_sub(bytes)?;
+    (speed > 0).then(|| remaining.div_ceil(speed))
+}
+
+/// Aggregates the progress updates of a single download into a `DownloadSummary`
+#[derive(Debug)]
+pub(crate) struct DownloadMetrics {
+    started_at: Instant,
+    bytes: u64,
+    peak_speed: u64,
+    retries: u32,
+    source: Option<String>,
+}
+
+impl DownloadMetrics {
+    pub(crate) fn new(started_at: Instant) -> Self {
+        Self { started_at, bytes: 0, peak_speed: 0, retries: 0, source: None }
+    }
+
+    pub(crate) fn record_start(&mut self, source: String, attempt: u32) {
+        self.source = Some(source);
+        self.retries = attempt.saturating_sub(1);
+    }
+
+    pub(crate) fn record_transfer(&mut self, stats: &TransferStats) {
+        self.bytes = self.bytes.max(stats.bytes);
+        self.peak_speed = self.peak_speed.max(stats.speed);
+    }
+
+    pub(crate) fn summary(&self, finished_at: Instant) -> DownloadSummary {
+        let duration_millis = finished_at.saturating_duration_since(self.started_at).as_millis();
+        DownloadSummary {
+            total_bytes: self.bytes,
+            average_speed: speed_bytes_per_sec(self.bytes, duration_millis),
+            peak_speed: self.peak_speed,
+            retries: self.retries,
+            source: self.source.clone(),
+            duration_ms: duration_millis as u64,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct TransferSpeedSample {
     bytes: u64,
@@ -65,6 +166,50 @@ mod tests {
         assert_eq!(tracker.record(13_000, 4_000), 5_500);
     }
 
+    #[test]
+    fn download_metrics_summarize_transfer() {
+        let start = Instant::now();
+        let mut metrics = DownloadMetrics::new(start);
+        metrics.record_start("VRP-mirror01".into(), 3);
+        for (bytes, speed) in [(1_000, 2_000), (6_000, 9_000), (8_000, 4_000)] {
+            metrics.record_transfer(&TransferStats {
+                bytes,
+                total_bytes: Some(8_000),
+                speed,
+                ..Default::default()
+            });
+        }
+
+        let summary = metrics.summary(start + Duration::from_secs(4));
+        assert_eq!(summary.total_bytes, 8_000);
+        assert_eq!(summary.average_speed, 2_000);
+        assert_eq!(summary.peak_speed, 9_000);
+        assert_eq!(summary.retries, 2);
+        assert_eq!(summary.source.as_deref(), Some("VRP-mirror01"));
+        assert_eq!(summary.duration_ms, 4_000);
+    }
+
+    #[test]
+    fn speed_average_follows_changes_gradually() {
+        let mut average = SpeedAverage::new(Duration::from_secs(2));
+
+        assert_eq!(average.record(1_000, 0), 1_000);
+        let after_drop = average.record(0, 1_000);
+        assert!((550..650).contains(&after_drop), "{after_drop}");
+        assert!(average.record(0, 11_000) < 10);
+        average.reset();
+        assert_eq!(average.record(4_000, 12_000), 4_000);
+    }
+
+    #[test]
+    fn remaining_secs_needs_total_and_speed() {
+        assert_eq!(remaining_secs(1_000, Some(10_000), 1_000), Some(9));
+        assert_eq!(remaining_secs(1_000, Some(10_500), 1_000), Some(10));
+        assert_eq!(remaining_secs(1_000, None, 1_000), None);
+        assert_eq!(remaining_secs(1_000, Some(10_000), 0), None);
+        assert_eq!(remaining_secs(12_000, Some(10_000), 1_000), None);
+    }
+
     #[test]
     fn transfer_speed_tracker_handles_non_advancing_bytes() {
         let mut tracker = TransferSpeedTracker::new(Duration::from_secs(2));
@@ -9,16 +9,19 @@ use anyhow::{Context, Result, anyhow, bail, ensure};
 use lazy_regex::Regex;
 use serde::Deserialize;
 use tokio::{
-    io::{AsyncBufReadExt, BufReader},
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
     process::Command,
-    sync::mpsc::UnboundedSender,
+    sync::{mpsc::UnboundedSender, watch},
     time::{self, Instant, MissedTickBehavior},
 };
 use tokio_util::sync::CancellationToken;
-use tracing::{Span, error, instrument, trace, warn};
+use tracing::{Span, debug, error, instrument, trace, warn};
 
 use crate::{
-    downloader::{TransferSpeedTracker, TransferStats},
+    downloader::{
+        BandwidthLimit, SpeedAverage, TransferSpeedTracker, TransferStats, remaining_secs, tls,
+    },
+    models::signals::task::FileTransferProgress,
     utils::{get_sys_proxy, resolve_binary_path},
 };
 
@@ -26,7 +29,9 @@ static CONNECTION_TIMEOUT: &str = "5s";
 static IO_IDLE_TIMEOUT: &str = "30s";
 const RCLONE_STATS_INTERVAL: Duration = Duration::from_millis(500);
 const RCLONE_STALE_SPEED_TIMEOUT: Duration = Duration::from_millis(1500);
-const RCLONE_SPEED_SAMPLE_WINDOW: Duration = Duration::from_secs(8);
+const RCLONE_SPEED_SAMPLE_WINDOW: Duration = Duration::from_secs(2);
+/// How long a speed change takes to mostly show in the smoothed speed
+const RCLONE_SPEED_SMOOTHING: Duration = Duration::from_secs(3);
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -47,13 +52,94 @@ pub(super) struct RcloneSizeOutput {
     pub bytes: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Result of comparing a remote directory against its local copy with `rclone check`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RcloneCheckReport {
+    /// Number of files identical on both sides
+    pub matched: usize,
+    /// Files whose size or hash differs from the remote
+    pub differing: Vec<String>,
+    /// Remote files missing locally
+    pub missing: Vec<String>,
+    /// Files that could not be compared
+    pub errors: Vec<String>,
+}
+
+impl RcloneCheckReport {
+    pub(crate) fn is_ok(&self) -> bool {
+        self.differing.is_empty() && self.missing.is_empty() && self.errors.is_empty()
+    }
+
+    /// Parses the `--combined` report, one `<marker> <path>` line per file
+    fn parse_combined(output: &str) -> Self {
+        let mut report = Self::default();
+        for line in output.lines() {
+            let Some((marker, path)) = line.split_once(' ') else {
+                continue;
+            };
+            let path = path.to_string();
+            match marker {
+                "=" => report.matched += 1,
+                "*" => report.differing.push(path),
+                "-" => report.missing.push(path),
+                "!" => report.errors.push(path),
+                // "+" marks files only present locally, which `--one-way` does not care about
+                _ => {}
+            }
+        }
+        report
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RcloneTransferStats {
     bytes: u64,
     // total_bytes: u64,
     // #[serde(deserialize_with = "deserialize_speed")]
     // speed: u64,
+    /// Files finished so far
+    #[serde(default)]
+    transfers: u32,
+    /// Files to transfer in total, growing while rclone is still listing
+    #[serde(default)]
+    total_transfers: u32,
+    /// Files being transferred right now
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    transferring: Vec<RcloneFileTransfer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RcloneFileTransfer {
+    name: String,
+    #[serde(default)]
+    bytes: u64,
+    #[serde(default)]
+    size: u64,
+    /// Bytes per second averaged by rclone
+    #[serde(default)]
+    speed_avg: f64,
+}
+
+impl From<RcloneFileTransfer> for FileTransferProgress {
+    fn from(file: RcloneFileTransfer) -> Self {
+        Self {
+            name: file.name,
+            bytes: file.bytes,
+            total_bytes: file.size,
+            speed: file.speed_avg.max(0.0).round() as u64,
+        }
+    }
+}
+
+/// Reads `null` as the default value, rclone prints empty lists as `null`
+fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
 }
 
 // fn deserialize_speed<'de, D>(deserializer: D) -> Result<u64, D::Error>
@@ -78,6 +164,7 @@ struct RcloneJsonLogLine {
 #[derive(Debug)]
 struct RcloneProgressTracker {
     speed_tracker: TransferSpeedTracker,
+    speed_average: SpeedAverage,
     started_at: Instant,
     expected_total_bytes: u64,
     last_stats: Option<TransferStats>,
@@ -88,6 +175,7 @@ impl RcloneProgressTracker {
     fn new(expected_total_bytes: u64) -> Self {
         Self {
             speed_tracker: TransferSpeedTracker::new(RCLONE_SPEED_SAMPLE_WINDOW),
+            speed_average: SpeedAverage::new(RCLONE_SPEED_SMOOTHING),
             started_at: Instant::now(),
             expected_total_bytes,
             last_stats: None,
@@ -96,12 +184,19 @@ impl RcloneProgressTracker {
     }
 
     fn record_stats(&mut self, stats: RcloneTransferStats) -> TransferStats {
-        let speed = self.speed_tracker.record(stats.bytes, self.started_at.elapsed().as_millis());
+        let elapsed_millis = self.started_at.elapsed().as_millis();
+        let speed = self.speed_tracker.record(stats.bytes, elapsed_millis);
+        let speed = self.speed_average.record(speed, elapsed_millis);
+        let total_bytes =
+            (stats.bytes <= self.expected_total_bytes).then_some(self.expected_total_bytes);
         let normalized = TransferStats {
             bytes: stats.bytes,
-            total_bytes: (stats.bytes <= self.expected_total_bytes)
-                .then_some(self.expected_total_bytes),
+            total_bytes,
             speed,
+            eta_secs: remaining_secs(stats.bytes, total_bytes, speed),
+            files_done: stats.transfers,
+            files_total: stats.total_transfers,
+            files: stats.transferring.into_iter().map(Into::into).collect(),
         };
         self.last_update_at = Some(Instant::now());
         self.last_stats = Some(normalized.clone());
@@ -120,12 +215,26 @@ impl RcloneProgressTracker {
         }
 
         last_stats.speed = 0;
+        last_stats.eta_secs = None;
+        for file in &mut last_stats.files {
+            file.speed = 0;
+        }
+        self.speed_average.reset();
         self.last_update_at = Some(now);
         Some(last_stats.clone())
     }
 }
 
 impl RcloneJsonLogLine {
+    /// Path of the file a line reports as copied, relative to the destination
+    fn copied_file(&self) -> Option<&str> {
+        if self.level == "info" && self.msg.contains("Copied (") {
+            self.object.as_deref()
+        } else {
+            None
+        }
+    }
+
     /// Converts to human-readable format like "2025/12/03 16:25:39 ERROR : object: message"
     fn to_human_readable(&self) -> String {
         let formatted_time = self.format_time();
@@ -183,12 +292,18 @@ pub(super) struct RcloneCli {
     rclone_path: PathBuf,
     config_path: PathBuf,
     sys_proxy: Option<String>,
-    bandwidth_limit: String,
+    bandwidth_limit: BandwidthLimit,
+    /// Certificates passed as `--ca-cert`, replacing the system trust store
+    ca_cert: Option<PathBuf>,
 }
 
 impl RcloneCli {
     #[instrument(level = "debug", fields(sys_proxy), ret)]
-    pub(super) fn new(rclone_path: PathBuf, config_path: PathBuf, bandwidth_limit: String) -> Self {
+    pub(super) fn new(
+        rclone_path: PathBuf,
+        config_path: PathBuf,
+        bandwidth_limit: BandwidthLimit,
+    ) -> Self {
         let sys_proxy = get_sys_proxy();
         let resolved_path =
             match resolve_binary_path(Some(&rclone_path.to_string_lossy()), "rclone") {
@@ -203,7 +318,12 @@ impl RcloneCli {
                 }
             };
         Span::current().record("sys_proxy", sys_proxy.as_deref());
-        Self { rclone_path: resolved_path, config_path, sys_proxy, bandwidth_limit }
+        Self { rclone_path: resolved_path, config_path, sys_proxy, bandwidth_limit, ca_cert: None }
+    }
+
+    pub(super) fn with_ca_cert(mut self, ca_cert: Option<PathBuf>) -> Self {
+        self.ca_cert = ca_cert;
+        self
     }
 
     #[instrument(skip(self), level = "debug")]
@@ -224,6 +344,9 @@ impl RcloneCli {
         }
 
         command.arg("--config").arg(&self.config_path);
+        if let Some(ca_cert) = &self.ca_cert {
+            command.arg("--ca-cert").arg(ca_cert);
+        }
         if use_json_log {
             command.arg("--use-json-log");
         }
@@ -252,12 +375,53 @@ impl RcloneCli {
         }
     }
 
+    /// Output of `rclone version`
+    #[instrument(skip(self), level = "debug")]
+    pub(super) async fn version(&self) -> Result<String> {
+        Ok(self.run_to_string(&["version"]).await?.trim().to_string())
+    }
+
+    /// Obscures a password with `rclone obscure`, the form rclone expects in its config. The
+    /// password is passed on stdin to keep it out of the process list.
+    #[instrument(skip_all, level = "debug", err)]
+    pub(super) async fn obscure(&self, password: &str) -> Result<String> {
+        let mut command = self.command(&["obscure", "-"], false);
+        command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+        let mut child = command.spawn().context("Failed to start rclone")?;
+        let mut stdin = child.stdin.take().context("Rclone stdin is not available")?;
+        stdin
+            .write_all(format!("{password}\n").as_bytes())
+            .await
+            .context("Failed to pass the password to rclone")?;
+        drop(stdin);
+
+        let output = child.wait_with_output().await.context("Rclone obscure failed")?;
+        ensure!(
+            output.status.success(),
+            "Rclone obscure returned exit code {}, stderr:\n{}",
+            output.status.code().map_or("unknown".to_string(), |c| c.to_string()),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let obscured = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        ensure!(!obscured.is_empty(), "Rclone obscure printed nothing");
+        Ok(obscured)
+    }
+
     #[instrument(skip(self), level = "debug")]
     pub(super) async fn remotes(&self) -> Result<Vec<String>> {
         let output = self.run_to_string(&["listremotes"]).await?;
         Ok(output.lines().map(|line| line.trim().trim_end_matches(':').to_string()).collect())
     }
 
+    /// Lists all files under `path` recursively.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(super) async fn lsjson_files(&self, path: &str) -> Result<Vec<RcloneLsJsonEntry>> {
+        let output = self
+            .run_to_string(&["lsjson", "--recursive", "--files-only", "--fast-list", path])
+            .await?;
+        serde_json::from_str(&output).context("Failed to parse rclone lsjson output")
+    }
+
     #[instrument(level = "debug", skip(self), ret, err)]
     pub(super) async fn size(&self, path: &str) -> Result<RcloneSizeOutput> {
         // TODO: can `--check-first` be used to make `total_bytes` reliable instead?
@@ -267,6 +431,70 @@ impl RcloneCli {
         Ok(size_output)
     }
 
+    /// Compares the files under `source` with `dest` by hash, or by size if the remote does not
+    /// support any hash that can be computed locally.
+    ///
+    /// Files only present in `dest` and paths matching `excludes` are ignored. `files_from` limits
+    /// the comparison to the paths listed in that file.
+    #[instrument(level = "debug", skip(self, cancellation_token), err)]
+    pub(super) async fn check(
+        &self,
+        source: &str,
+        dest: &str,
+        excludes: &[&str],
+        files_from: Option<&Path>,
+        cancellation_token: CancellationToken,
+    ) -> Result<RcloneCheckReport> {
+        let files_from = files_from.map(|path| path.display().to_string());
+        let mut args = vec!["check", "--one-way", "--fast-list", "--combined", "-"];
+        for exclude in excludes {
+            args.extend_from_slice(&["--exclude", exclude]);
+        }
+        if let Some(files_from) = &files_from {
+            args.extend_from_slice(&["--files-from-raw", files_from]);
+        }
+        args.extend_from_slice(&[source, dest]);
+
+        let output = tokio::select! {
+            output = self.command(&args, false).output() => {
+                output.context("Failed to run rclone check")?
+            }
+            _ = cancellation_token.cancelled() => bail!("Verification cancelled"),
+        };
+        let report = RcloneCheckReport::parse_combined(&String::from_utf8_lossy(&output.stdout));
+        // Differences make rclone exit with an error too, only fail if nothing was reported
+        if !output.status.success() && report.is_ok() {
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            error!(code = output.status.code().unwrap_or(-1), stderr, "Rclone check failed");
+            bail!(
+                "Rclone check returned exit code {}, stderr:\n{}",
+                output.status.code().map_or("unknown".to_string(), |c| c.to_string()),
+                stderr
+            );
+        }
+        trace!(?report, "Rclone check finished");
+        Ok(report)
+    }
+
+    /// Changes the limit of a transfer started with remote control on `rc_addr`
+    #[instrument(level = "debug", skip(auth), err)]
+    async fn set_live_bandwidth_limit(rc_addr: &str, auth: &RcAuth, limit: &str) -> Result<()> {
+        let rate = if limit.is_empty() { "off" } else { limit };
+        tls::http_client_builder(tls::ServerPins::default())?
+            .no_proxy()
+            .build()?
+            .post(format!("http://{rc_addr}/core/bwlimit"))
+            .basic_auth(&auth.user, Some(&auth.pass))
+            .json(&serde_json::json!({ "rate": rate }))
+            .send()
+            .await
+            .context("Failed to reach rclone remote control")?
+            .error_for_status()
+            .context("Rclone remote control rejected the request")?;
+        debug!(limit, "Changed bandwidth limit of running transfer");
+        Ok(())
+    }
+
     #[instrument(level = "debug", skip(self, cancellation_token))]
     pub(super) async fn transfer(
         &self,
@@ -275,17 +503,24 @@ impl RcloneCli {
         operation: RcloneTransferOperation,
         cancellation_token: Option<CancellationToken>,
     ) -> Result<()> {
-        self.transfer_internal(source, dest, operation, None, None, cancellation_token).await
+        self.transfer_internal(source, dest, operation, None, None, None, None, cancellation_token)
+            .await
     }
 
-    #[instrument(level = "debug", skip(self, stats_tx, cancellation_token))]
+    /// Transfers `source` to `dest`, reporting stats against `total_bytes`. `files_from` limits
+    /// the transfer to the paths listed in that file. Paths of copied files, relative to `dest`,
+    /// are sent to `completed_tx`.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "debug", skip(self, stats_tx, completed_tx, cancellation_token))]
     pub(super) async fn transfer_with_stats(
         &self,
         source: String,
         dest: String,
         operation: RcloneTransferOperation,
         total_bytes: u64,
+        files_from: Option<&Path>,
         stats_tx: Option<UnboundedSender<TransferStats>>,
+        completed_tx: Option<UnboundedSender<String>>,
         cancellation_token: Option<CancellationToken>,
     ) -> Result<()> {
         self.transfer_internal(
@@ -293,20 +528,25 @@ impl RcloneCli {
             dest,
             operation,
             Some(total_bytes),
+            files_from,
             stats_tx,
+            completed_tx,
             cancellation_token,
         )
         .await
     }
 
-    #[instrument(level = "debug", skip(self, stats_tx, cancellation_token))]
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "debug", skip(self, stats_tx, completed_tx, cancellation_token))]
     async fn transfer_internal(
         &self,
         source: String,
         dest: String,
         operation: RcloneTransferOperation,
         total_bytes: Option<u64>,
+        files_from: Option<&Path>,
         stats_tx: Option<UnboundedSender<TransferStats>>,
+        completed_tx: Option<UnboundedSender<String>>,
         cancellation_token: Option<CancellationToken>,
     ) -> Result<()> {
         ensure!(
@@ -327,14 +567,38 @@ impl RcloneCli {
             "8",
         ];
 
-        if !self.bandwidth_limit.is_empty() {
-            args.extend_from_slice(&["--bwlimit", &self.bandwidth_limit]);
+        // Subscribe before reading the limit so no change is missed
+        let mut limit_rx = self.bandwidth_limit.subscribe();
+        let bandwidth_limit = limit_rx.borrow_and_update().clone();
+        if !bandwidth_limit.is_empty() {
+            args.extend_from_slice(&["--bwlimit", &bandwidth_limit]);
+        }
+
+        // Long transfers expose the remote control API so the limit can be changed while they
+        // run. Rclone picks a free port and logs it, the credentials are passed in environment.
+        let rc_auth = stats_tx.is_some().then(RcAuth::random);
+        if rc_auth.is_some() {
+            args.extend_from_slice(&["--rc", "--rc-addr", "127.0.0.1:0"]);
+        }
+        let (rc_addr_tx, mut rc_addr_rx) = watch::channel(None::<String>);
+
+        let files_from = files_from.map(|path| path.display().to_string());
+        if let Some(files_from) = &files_from {
+            args.extend_from_slice(&["--files-from-raw", files_from]);
+        }
+        // Copied files are only logged at the info level
+        if completed_tx.is_some() {
+            args.extend_from_slice(&["--log-level", "INFO"]);
         }
 
         args.extend_from_slice(&[&source, &dest]);
 
         let use_json_log = stats_tx.is_some();
-        let mut child = self.command(&args, use_json_log).stderr(Stdio::piped()).spawn()?;
+        let mut command = self.command(&args, use_json_log);
+        if let Some(auth) = &rc_auth {
+            command.env("RCLONE_RC_USER", &auth.user).env("RCLONE_RC_PASS", &auth.pass);
+        }
+        let mut child = command.stderr(Stdio::piped()).spawn()?;
         let stderr = child.stderr.take().context("Failed to get stderr")?;
         let mut lines = BufReader::new(stderr).lines();
 
@@ -365,6 +629,14 @@ impl RcloneCli {
                                             warn!("Stats receiver dropped, stopping stats processing.");
                                             break;
                                         }
+                                    } else if let Some(addr) = rc_served_addr(&log_line.msg) {
+                                        trace!(addr, "Rclone remote control started");
+                                        let _ = rc_addr_tx.send(Some(addr));
+                                    } else if let Some(file) = log_line.copied_file() {
+                                        trace!(file, "Rclone copied file");
+                                        if let Some(completed_tx) = &completed_tx {
+                                            let _ = completed_tx.send(file.to_string());
+                                        }
                                     } else {
                                         stderr_lines.push(log_line.to_human_readable());
                                     }
@@ -409,6 +681,33 @@ impl RcloneCli {
             }
         };
 
+        let follow_limit = async {
+            let Some(rc_auth) = &rc_auth else {
+                return std::future::pending::<Result<()>>().await;
+            };
+            // Changes made before the address is known are applied once it is
+            let addr = rc_addr_rx.wait_for(Option::is_some).await.map(|addr| addr.clone());
+            let Ok(Some(rc_addr)) = addr else {
+                return std::future::pending::<Result<()>>().await;
+            };
+            while limit_rx.changed().await.is_ok() {
+                let limit = limit_rx.borrow_and_update().clone();
+                if let Err(e) = Self::set_live_bandwidth_limit(&rc_addr, rc_auth, &limit).await {
+                    warn!(
+                        error = e.as_ref() as &dyn Error,
+                        limit, "Failed to change bandwidth limit of running transfer"
+                    );
+                }
+            }
+            std::future::pending::<Result<()>>().await
+        };
+        let transfer_future = async {
+            tokio::select! {
+                res = transfer_future => res,
+                never = follow_limit => never,
+            }
+        };
+
         if let Some(token) = cancellation_token {
             tokio::select! {
                 res = transfer_future => res,
@@ -424,6 +723,27 @@ impl RcloneCli {
     }
 }
 
+/// Credentials of the remote control API of a transfer, random for each transfer
+#[derive(Debug)]
+struct RcAuth {
+    user: String,
+    pass: String,
+}
+
+impl RcAuth {
+    fn random() -> Self {
+        Self { user: "yaas".to_string(), pass: uuid::Uuid::new_v4().simple().to_string() }
+    }
+}
+
+/// Address of the remote control API from rclone's "Serving remote control on" log line
+fn rc_served_addr(msg: &str) -> Option<String> {
+    let url = msg.trim().strip_prefix("Serving remote control on ")?;
+    let url = url.trim_matches(|c| c == '[' || c == ']').split_whitespace().next()?;
+    let addr = url.strip_prefix("http://")?.trim_end_matches('/');
+    (!addr.is_empty()).then(|| addr.to_string())
+}
+
 fn filter_remotes_with_regex(remotes: Vec<String>, pattern: Option<&str>) -> Vec<String> {
     if let Some(pat) = pattern {
         match Regex::new(pat) {
@@ -448,7 +768,11 @@ pub(crate) async fn list_remotes(
     config_path: &Path,
     remote_filter_regex: Option<&str>,
 ) -> Result<Vec<String>> {
-    let cli = RcloneCli::new(rclone_path.to_path_buf(), config_path.to_path_buf(), String::new());
+    let cli = RcloneCli::new(
+        rclone_path.to_path_buf(),
+        config_path.to_path_buf(),
+        BandwidthLimit::new(String::new()),
+    );
     let remotes = cli.remotes().await?;
     Ok(filter_remotes_with_regex(remotes, remote_filter_regex))
 }
@@ -457,13 +781,26 @@ pub(crate) async fn list_remotes(
 mod tests {
     use super::*;
 
+    #[test]
+    fn passes_pinned_certificates_to_rclone() {
+        let cli = RcloneCli::new(
+            PathBuf::from("rclone"),
+            PathBuf::from("rclone.conf"),
+            BandwidthLimit::new(String::new()),
+        )
+        .with_ca_cert(Some(PathBuf::from("pinned_ca.pem")));
+        let command = cli.command(&["lsf", "remote:"], false);
+        let args = command.as_std().get_args().collect::<Vec<_>>();
+        assert!(args.windows(2).any(|pair| pair == ["--ca-cert", "pinned_ca.pem"]), "{args:?}");
+    }
+
     #[test]
     fn progress_tracker_derives_speed_from_bytes() {
         let mut tracker = RcloneProgressTracker::new(100);
 
-        let first = tracker.record_stats(RcloneTransferStats { bytes: 25 });
+        let first = tracker.record_stats(RcloneTransferStats { bytes: 25, ..Default::default() });
         std::thread::sleep(Duration::from_millis(20));
-        let second = tracker.record_stats(RcloneTransferStats { bytes: 25 });
+        let second = tracker.record_stats(RcloneTransferStats { bytes: 25, ..Default::default() });
 
         assert_eq!(first.total_bytes, Some(100));
         assert!(second.speed <= first.speed);
@@ -473,7 +810,7 @@ mod tests {
     fn progress_tracker_marks_progress_unknown_when_bytes_exceed_expected_total() {
         let mut tracker = RcloneProgressTracker::new(100);
 
-        let stats = tracker.record_stats(RcloneTransferStats { bytes: 120 });
+        let stats = tracker.record_stats(RcloneTransferStats { bytes: 120, ..Default::default() });
 
         assert_eq!(stats.total_bytes, None);
     }
@@ -482,7 +819,8 @@ mod tests {
     fn progress_tracker_emits_zero_speed_after_stall() {
         let mut tracker = RcloneProgressTracker::new(100);
         std::thread::sleep(Duration::from_millis(20));
-        let recorded = tracker.record_stats(RcloneTransferStats { bytes: 50 });
+        let recorded =
+            tracker.record_stats(RcloneTransferStats { bytes: 50, ..Default::default() });
         assert!(recorded.speed > 0);
         tracker.last_update_at = Some(Instant::now() - RCLONE_STALE_SPEED_TIMEOUT);
 
@@ -492,6 +830,71 @@ mod tests {
         assert_eq!(stats.bytes, 50);
     }
 
+    #[test]
+    fn progress_tracker_reports_files_and_eta() {
+        let line = r#"{"level":"info","msg":"stats","time":"2025-12-03T16:18:24.677508041+03:00",
+            "stats":{"bytes":400,"speed":120.5,"totalBytes":1000,"transfers":1,"totalTransfers":3,
+            "transferring":[{"name":"Game/main.obb","bytes":300,"size":800,"speed":150.2,
+            "speedAvg":99.6,"percentage":37,"eta":5}]}}"#;
+        let stats = serde_json::from_str::<RcloneJsonLogLine>(line)
+            .expect("stats line should parse")
+            .stats
+            .expect("stats");
+        let mut tracker = RcloneProgressTracker::new(1_000);
+        std::thread::sleep(Duration::from_millis(20));
+
+        let recorded = tracker.record_stats(stats);
+
+        assert_eq!((recorded.files_done, recorded.files_total), (1, 3));
+        assert_eq!(
+            recorded.files,
+            [FileTransferProgress {
+                name: "Game/main.obb".into(),
+                bytes: 300,
+                total_bytes: 800,
+                speed: 100,
+            }]
+        );
+        assert_eq!(recorded.eta_secs, remaining_secs(400, Some(1_000), recorded.speed));
+        assert!(recorded.eta_secs.is_some());
+
+        tracker.last_update_at = Some(Instant::now() - RCLONE_STALE_SPEED_TIMEOUT);
+        let stale = tracker.maybe_stale_stats(Instant::now()).expect("stale stats");
+        assert_eq!((stale.speed, stale.eta_secs, stale.files[0].speed), (0, None, 0));
+    }
+
+    #[test]
+    fn stats_without_transfers_parse() {
+        let line =
+            r#"{"level":"info","msg":"stats","time":"t","stats":{"bytes":0,"transferring":null}}"#;
+        let stats = serde_json::from_str::<RcloneJsonLogLine>(line)
+            .expect("stats line should parse")
+            .stats
+            .expect("stats");
+        assert!(stats.transferring.is_empty());
+        assert_eq!(stats.total_transfers, 0);
+    }
+
+    #[test]
+    fn check_report_parses_combined_output() {
+        let output = concat!(
+            "= Game/com.example.game.apk\n",
+            "* Game/com.example.game/main.1.com.example.game.obb\n",
+            "- Game/com.example.game/patch.1.com.example.game.obb\n",
+            "+ Game/notes.txt\n",
+            "! Game/broken file.bin\n",
+        );
+
+        let report = RcloneCheckReport::parse_combined(output);
+
+        assert_eq!(report.matched, 1);
+        assert_eq!(report.differing, ["Game/com.example.game/main.1.com.example.game.obb"]);
+        assert_eq!(report.missing, ["Game/com.example.game/patch.1.com.example.game.obb"]);
+        assert_eq!(report.errors, ["Game/broken file.bin"]);
+        assert!(!report.is_ok());
+        assert!(RcloneCheckReport::parse_combined("= a.apk\n+ b.txt\n").is_ok());
+    }
+
     #[test]
     fn parse_json_log_line_with_object() {
         let json = r#"{"time":"2025-12-03T16:18:24.49104384+03:00","level":"error","msg":"error reading source root directory: directory not found","object":"webdav root 'Quest Games/A Fishermans Tale v16+1.064 -QU'","objectType":"*webdav.Fs","source":"slog/logger.go:256"}"#;
@@ -529,6 +932,34 @@ mod tests {
         assert_eq!(stats.bytes, 39841792);
     }
 
+    #[test]
+    fn reads_copied_files_from_log() {
+        let json = r#"{"time":"2025-12-03T16:36:52.1+03:00","level":"info","msg":"Copied (new)","object":"com.example/main.obb","objectType":"*local.Object","source":"slog/logger.go:256"}"#;
+        let parsed: RcloneJsonLogLine = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.copied_file(), Some("com.example/main.obb"));
+
+        let json = r#"{"time":"2025-12-03T16:36:52.1+03:00","level":"info","msg":"Multi-thread Copied (replaced existing)","object":"game.apk","source":"slog/logger.go:256"}"#;
+        let parsed: RcloneJsonLogLine = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.copied_file(), Some("game.apk"));
+
+        let json = r#"{"time":"2025-12-03T16:36:52.1+03:00","level":"info","msg":"Deleted","object":"old.apk","source":"slog/logger.go:256"}"#;
+        let parsed: RcloneJsonLogLine = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.copied_file(), None);
+    }
+
+    #[test]
+    fn reads_remote_control_address_from_log() {
+        assert_eq!(
+            rc_served_addr("Serving remote control on http://127.0.0.1:41235/").as_deref(),
+            Some("127.0.0.1:41235")
+        );
+        assert_eq!(
+            rc_served_addr("Serving remote control on [http://127.0.0.1:5572/]").as_deref(),
+            Some("127.0.0.1:5572")
+        );
+        assert_eq!(rc_served_addr("Transferred: 1 / 2"), None);
+    }
+
     #[test]
     fn to_human_readable_with_object() {
         let log_line = RcloneJsonLogLine {
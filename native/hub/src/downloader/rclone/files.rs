@@ -12,7 +12,7 @@ use tracing::{debug, info, instrument, warn};
 use super::super::http_cache::{self, DownloadResult};
 use crate::{
     archive::{extract_single_from_archive, list_archive_file_paths},
-    downloader::{config::DownloaderConfig, http_cache::compute_md5_file, repo},
+    downloader::{config::DownloaderConfig, http_cache::compute_md5_file, repo, tls},
     models::signals::downloader::progress::DownloaderInitProgress,
 };
 
@@ -114,8 +114,7 @@ fn init_progress(bytes: u64, total: Option<u64>) {
 }
 
 fn build_http_client() -> Result<reqwest::Client> {
-    Ok(reqwest::Client::builder()
-        .user_agent(crate::USER_AGENT)
+    Ok(tls::http_client_builder(tls::ServerPins::default())?
         .connect_timeout(Duration::from_secs(10))
         .timeout(Duration::from_secs(300))
         .build()?)
@@ -1,7 +1,9 @@
 mod cli;
 mod files;
+mod remotes;
 mod storage;
 
-pub(super) use cli::list_remotes;
+pub(super) use cli::{RcloneCheckReport, list_remotes};
 pub(crate) use files::prepare_rclone_files;
-pub(super) use storage::RcloneStorage;
+pub(crate) use remotes::{CustomRemotes, validate_draft, write_merged_config};
+pub(super) use storage::{RcloneStorage, RemoteFile};
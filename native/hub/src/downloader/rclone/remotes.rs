@@ -0,0 +1,507 @@
+//! Remotes added by the user on top of the rclone config of a source.
+//!
+//! They are kept in rclone's own config format in the app directory and merged into the config of
+//! rclone-based sources when the downloader starts, so mirrors survive source config refreshes.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, ensure};
+use lazy_regex::regex_is_match;
+use tracing::warn;
+
+use super::cli::RcloneCli;
+use crate::{
+    downloader::BandwidthLimit,
+    models::signals::storage::remotes::{CustomRemoteDraft, CustomRemoteInfo, CustomRemoteKind},
+};
+
+const CUSTOM_REMOTES_FILENAME: &str = "rclone_remotes.conf";
+const MERGED_CONFIG_FILENAME: &str = "rclone.merged.conf";
+
+/// A `[name]` section of an rclone config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RcloneSection {
+    pub name: String,
+    pub options: Vec<(String, String)>,
+}
+
+impl RcloneSection {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.options.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parses an rclone config, ignoring comments, options outside of sections and lines rclone
+/// would not read either
+fn parse_rclone_config(content: &str) -> Vec<RcloneSection> {
+    let mut sections: Vec<RcloneSection> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            sections.push(RcloneSection { name: name.trim().to_string(), options: Vec::new() });
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            warn!(line = index + 1, "Skipping rclone config line without '='");
+            continue;
+        };
+        if let Some(section) = sections.last_mut() {
+            section.options.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    sections
+}
+
+/// Renders sections as an rclone config, failing for text that would spill into other lines
+fn render_rclone_config(sections: &[RcloneSection]) -> Result<String> {
+    let mut out = String::new();
+    for section in sections {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        ensure!(
+            !has_control_chars(&section.name),
+            "Remote name contains control characters: {:?}",
+            section.name
+        );
+        out.push_str(&format!("[{}]\n", section.name));
+        for (key, value) in &section.options {
+            ensure!(
+                !has_control_chars(key) && !has_control_chars(value),
+                "Option {key:?} of remote {} contains control characters",
+                section.name
+            );
+            out.push_str(&format!("{key} = {value}\n"));
+        }
+    }
+    Ok(out)
+}
+
+fn has_control_chars(text: &str) -> bool {
+    text.chars().any(char::is_control)
+}
+
+/// Appends custom remotes to a source's config, replacing source remotes of the same name
+fn merge_rclone_config(base: &str, custom: &[RcloneSection]) -> Result<String> {
+    let mut sections = parse_rclone_config(base);
+    sections.retain(|section| custom.iter().all(|c| c.name != section.name));
+    sections.extend(custom.iter().cloned());
+    render_rclone_config(&sections)
+}
+
+/// Writes the config of a source with the custom remotes added into its cache directory
+pub(crate) async fn write_merged_config(
+    cache_dir: &Path,
+    base_config: &Path,
+    custom: &[RcloneSection],
+) -> Result<PathBuf> {
+    let base = tokio::fs::read_to_string(base_config)
+        .await
+        .with_context(|| format!("Failed to read {}", base_config.display()))?;
+    let merged_path = cache_dir.join(MERGED_CONFIG_FILENAME);
+    tokio::fs::write(&merged_path, merge_rclone_config(&base, custom)?)
+        .await
+        .context("Failed to write merged rclone config")?;
+    Ok(merged_path)
+}
+
+/// Lists what keeps a draft from being saved
+pub(crate) fn validate_draft(draft: &CustomRemoteDraft) -> Vec<String> {
+    let mut errors = Vec::new();
+    let fields = [
+        ("Name", Some(draft.name.as_str())),
+        ("Endpoint", Some(draft.endpoint.as_str())),
+        ("User name", draft.username.as_deref()),
+        ("Password", draft.password.as_deref()),
+    ];
+    for (field, value) in fields {
+        if value.is_some_and(has_control_chars) {
+            errors.push(format!("{field} must not contain control characters"));
+        }
+    }
+
+    let name = draft.name.as_str();
+    if name.is_empty() {
+        errors.push("Name must not be empty".to_string());
+    } else if !regex_is_match!(r"^[\w.+@][\w.+@ -]*$", name) || name.ends_with(' ') {
+        errors.push(
+            "Name may only contain letters, digits, spaces and _ - . + @, and must not start with \
+             - or a space"
+                .to_string(),
+        );
+    }
+
+    let endpoint = draft.endpoint.trim();
+    match draft.kind {
+        CustomRemoteKind::Http | CustomRemoteKind::Webdav | CustomRemoteKind::S3 => {
+            match reqwest::Url::parse(endpoint) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                Ok(_) => errors.push("Endpoint must be an http or https URL".to_string()),
+                Err(e) => errors.push(format!("Invalid endpoint URL: {e}")),
+            }
+        }
+        CustomRemoteKind::Ftp | CustomRemoteKind::Sftp => {
+            if let Err(e) = split_host_port(endpoint) {
+                errors.push(format!("{e:#}"));
+            }
+        }
+    }
+
+    let username = draft.username.as_deref().filter(|u| !u.is_empty());
+    let password = draft.password.as_deref().filter(|p| !p.is_empty());
+    match draft.kind {
+        CustomRemoteKind::Http if username.is_some() || password.is_some() => {
+            errors.push("HTTP remotes do not support credentials".to_string());
+        }
+        CustomRemoteKind::S3 if username.is_some() != password.is_some() => {
+            errors.push("S3 needs both an access key ID and a secret access key".to_string());
+        }
+        CustomRemoteKind::Webdav | CustomRemoteKind::Ftp | CustomRemoteKind::Sftp
+            if password.is_some() && username.is_none() =>
+        {
+            errors.push("A password needs a user name".to_string());
+        }
+        _ => {}
+    }
+    errors
+}
+
+/// Splits `host[:port]`, also accepting an `ftp://` or `sftp://` URL
+fn split_host_port(endpoint: &str) -> Result<(String, Option<u16>)> {
+    let endpoint = endpoint.split_once("://").map_or(endpoint, |(_, rest)| rest);
+    let endpoint = endpoint.trim_end_matches('/');
+    ensure!(!endpoint.is_empty(), "Host must not be empty");
+    ensure!(!endpoint.contains(['/', ' ', '@']), "Endpoint must be a host with an optional port");
+    match endpoint.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => {
+            let port = port.parse().with_context(|| format!("Invalid port: {port}"))?;
+            Ok((host.to_string(), Some(port)))
+        }
+        _ => Ok((endpoint.to_string(), None)),
+    }
+}
+
+/// Builds the rclone section of a valid draft. `password` is the value written for the password
+/// option, already obscured where rclone expects it.
+fn section_from_draft(draft: &CustomRemoteDraft, password: Option<String>) -> RcloneSection {
+    let endpoint = draft.endpoint.trim();
+    // URLs are written as parsed, normalized and percent-encoded
+    let endpoint = match draft.kind {
+        CustomRemoteKind::Http | CustomRemoteKind::Webdav | CustomRemoteKind::S3 => {
+            reqwest::Url::parse(endpoint)
+                .map_or(endpoint.to_string(), |url| url.as_str().to_string())
+        }
+        CustomRemoteKind::Ftp | CustomRemoteKind::Sftp => endpoint.to_string(),
+    };
+    let username = draft.username.clone().filter(|u| !u.is_empty());
+
+    let mut options = vec![("type", section_type(draft.kind).to_string())];
+    match draft.kind {
+        CustomRemoteKind::Http => {
+            options.push(("url", endpoint));
+        }
+        CustomRemoteKind::Webdav => {
+            options.extend([("url", endpoint), ("vendor", "other".to_string())]);
+        }
+        CustomRemoteKind::S3 => {
+            options.extend([("provider", "Other".to_string()), ("endpoint", endpoint)]);
+        }
+        CustomRemoteKind::Ftp | CustomRemoteKind::Sftp => {
+            let (host, port) = split_host_port(&endpoint).unwrap_or((endpoint, None));
+            options.push(("host", host));
+            if let Some(port) = port {
+                options.push(("port", port.to_string()));
+            }
+        }
+    }
+    if let Some(username) = username {
+        options.push((user_key(draft.kind), username));
+    }
+    if let Some(password) = password {
+        options.push((password_key(draft.kind), password));
+    }
+
+    let options = options.into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+    RcloneSection { name: draft.name.clone(), options }
+}
+
+fn section_type(kind: CustomRemoteKind) -> &'static str {
+    match kind {
+        CustomRemoteKind::Http => "http",
+        CustomRemoteKind::Webdav => "webdav",
+        CustomRemoteKind::S3 => "s3",
+        CustomRemoteKind::Ftp => "ftp",
+        CustomRemoteKind::Sftp => "sftp",
+    }
+}
+
+fn user_key(kind: CustomRemoteKind) -> &'static str {
+    if kind == CustomRemoteKind::S3 { "access_key_id" } else { "user" }
+}
+
+fn password_key(kind: CustomRemoteKind) -> &'static str {
+    if kind == CustomRemoteKind::S3 { "secret_access_key" } else { "pass" }
+}
+
+/// Describes a saved section, `None` for backends the editor does not manage
+fn info_from_section(section: &RcloneSection) -> Option<CustomRemoteInfo> {
+    let kind = match section.get("type")? {
+        "http" => CustomRemoteKind::Http,
+        "webdav" => CustomRemoteKind::Webdav,
+        "s3" => CustomRemoteKind::S3,
+        "ftp" => CustomRemoteKind::Ftp,
+        "sftp" => CustomRemoteKind::Sftp,
+        _ => return None,
+    };
+    let endpoint = match kind {
+        CustomRemoteKind::Http | CustomRemoteKind::Webdav => section.get("url")?.to_string(),
+        CustomRemoteKind::S3 => section.get("endpoint")?.to_string(),
+        CustomRemoteKind::Ftp | CustomRemoteKind::Sftp => {
+            let host = section.get("host")?;
+            match section.get("port") {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_string(),
+            }
+        }
+    };
+    let username = section.get(user_key(kind));
+    Some(CustomRemoteInfo {
+        name: section.name.clone(),
+        kind,
+        endpoint,
+        username: username.map(str::to_string),
+        has_password: section.get(password_key(kind)).is_some(),
+    })
+}
+
+/// Custom remotes file in the app directory
+#[derive(Debug, Clone)]
+pub(crate) struct CustomRemotes {
+    path: PathBuf,
+}
+
+impl CustomRemotes {
+    pub(crate) fn new(app_dir: &Path) -> Self {
+        Self { path: app_dir.join(CUSTOM_REMOTES_FILENAME) }
+    }
+
+    /// Saved sections, none if the file does not exist
+    pub(crate) fn load(&self) -> Result<Vec<RcloneSection>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path).context("Failed to read custom remotes")?;
+        Ok(parse_rclone_config(&content))
+    }
+
+    pub(crate) fn list(&self) -> Result<Vec<CustomRemoteInfo>> {
+        Ok(self.load()?.iter().filter_map(info_from_section).collect())
+    }
+
+    /// Adds a remote, or replaces `previous_name` when editing one. New passwords are obscured
+    /// with the rclone binary at `rclone_path`.
+    pub(crate) async fn save(
+        &self,
+        draft: &CustomRemoteDraft,
+        previous_name: Option<&str>,
+        rclone_path: Option<&Path>,
+    ) -> Result<()> {
+        let errors = validate_draft(draft);
+        ensure!(errors.is_empty(), "{}", errors.join("\n"));
+
+        let mut sections = self.load()?;
+        ensure!(
+            previous_name == Some(draft.name.as_str())
+                || sections.iter().all(|s| s.name != draft.name),
+            "A remote named {} already exists",
+            draft.name
+        );
+        let replaced = previous_name.unwrap_or(&draft.name);
+        let saved_password = sections
+            .iter()
+            .find(|s| s.name == replaced)
+            .filter(|s| s.get("type") == Some(section_type(draft.kind)))
+            .and_then(|s| s.get(password_key(draft.kind)))
+            .map(str::to_string);
+
+        let password = match draft.password.as_deref() {
+            Some("") => None,
+            // rclone reads S3 keys as they are
+            Some(password) if draft.kind == CustomRemoteKind::S3 => Some(password.to_string()),
+            Some(password) => {
+                let rclone_path = rclone_path.context(
+                    "Passwords are obscured with rclone, select an rclone-based source first",
+                )?;
+                let rclone = RcloneCli::new(
+                    rclone_path.to_path_buf(),
+                    self.path.clone(),
+                    BandwidthLimit::new(String::new()),
+                );
+                Some(rclone.obscure(password).await.context("Failed to obscure password")?)
+            }
+            None => saved_password,
+        };
+
+        let section = section_from_draft(draft, password);
+        match sections.iter().position(|s| s.name == replaced) {
+            Some(index) => sections[index] = section,
+            None => sections.push(section),
+        }
+        self.write(&sections)
+    }
+
+    /// Removes a remote, returns whether it existed
+    pub(crate) fn remove(&self, name: &str) -> Result<bool> {
+        let mut sections = self.load()?;
+        let len = sections.len();
+        sections.retain(|s| s.name != name);
+        if sections.len() == len {
+            return Ok(false);
+        }
+        self.write(&sections)?;
+        Ok(true)
+    }
+
+    fn write(&self, sections: &[RcloneSection]) -> Result<()> {
+        let tmp_path = self.path.with_extension("conf.tmp");
+        fs::write(&tmp_path, render_rclone_config(sections)?)
+            .context("Failed to write custom remotes")?;
+        fs::rename(&tmp_path, &self.path).context("Failed to replace custom remotes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(name: &str, kind: CustomRemoteKind, endpoint: &str) -> CustomRemoteDraft {
+        CustomRemoteDraft {
+            name: name.to_string(),
+            kind,
+            endpoint: endpoint.to_string(),
+            username: None,
+            password: None,
+        }
+    }
+
+    #[test]
+    fn validates_drafts() {
+        assert!(
+            validate_draft(&draft("mirror-1", CustomRemoteKind::Http, "https://m.example/"))
+                .is_empty()
+        );
+        assert_eq!(validate_draft(&draft("-bad", CustomRemoteKind::Ftp, "host:21")).len(), 1);
+        assert_eq!(validate_draft(&draft("m", CustomRemoteKind::Webdav, "ftp://host")).len(), 1);
+        assert_eq!(validate_draft(&draft("m", CustomRemoteKind::Sftp, "host:port")).len(), 1);
+
+        let mut s3 = draft("s3", CustomRemoteKind::S3, "https://s3.example");
+        s3.username = Some("key".to_string());
+        assert_eq!(validate_draft(&s3), ["S3 needs both an access key ID and a secret access key"]);
+
+        // Text that would add lines to the rclone config
+        let mut injected = draft("m", CustomRemoteKind::Ftp, "host\n[evil]");
+        injected.username = Some("quest\ntype = local".to_string());
+        assert_eq!(
+            validate_draft(&injected),
+            [
+                "Endpoint must not contain control characters",
+                "User name must not contain control characters"
+            ]
+        );
+        let http = draft("m", CustomRemoteKind::Http, "https://m.example/a\nb");
+        assert_eq!(validate_draft(&http), ["Endpoint must not contain control characters"]);
+    }
+
+    #[test]
+    fn skips_invalid_config_lines() {
+        let sections = parse_rclone_config("[main]\ntype = http\nnot an option\nurl = https://m\n");
+        assert_eq!(
+            sections,
+            [RcloneSection {
+                name: "main".to_string(),
+                options: vec![
+                    ("type".to_string(), "http".to_string()),
+                    ("url".to_string(), "https://m".to_string())
+                ],
+            }]
+        );
+        let section = RcloneSection {
+            name: "m".to_string(),
+            options: vec![("url".to_string(), "https://m\n[evil]".to_string())],
+        };
+        assert!(render_rclone_config(&[section]).is_err());
+    }
+
+    /// Writes a stand-in for rclone whose `obscure` prefixes the password read from stdin
+    #[cfg(unix)]
+    fn fake_rclone(dir: &Path) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("rclone");
+        fs::write(&path, "#!/bin/sh\nread -r password\necho \"obscured-$password\"\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn saves_edits_and_merges_remotes() {
+        let dir = tempfile::tempdir().unwrap();
+        let remotes = CustomRemotes::new(dir.path());
+        let rclone = fake_rclone(dir.path());
+
+        let mut sftp = draft("mirror", CustomRemoteKind::Sftp, "sftp://files.example:2222");
+        sftp.username = Some("quest".to_string());
+        sftp.password = Some("secret".to_string());
+        // Passwords other than S3 keys need rclone to obscure them
+        assert!(remotes.save(&sftp, None, None).await.is_err());
+        remotes.save(&sftp, None, Some(&rclone)).await.unwrap();
+        let http = draft("mirror", CustomRemoteKind::Http, "https://x");
+        assert!(remotes.save(&http, None, None).await.is_err());
+
+        // Editing without a password keeps the saved one
+        sftp.name = "mirror-2".to_string();
+        sftp.password = None;
+        remotes.save(&sftp, Some("mirror"), None).await.unwrap();
+        assert_eq!(
+            remotes.list().unwrap(),
+            [CustomRemoteInfo {
+                name: "mirror-2".to_string(),
+                kind: CustomRemoteKind::Sftp,
+                endpoint: "files.example:2222".to_string(),
+                username: Some("quest".to_string()),
+                has_password: true,
+            }]
+        );
+
+        let base = [
+            "# source",
+            "[mirror-2]",
+            "type = http",
+            "url = https://old",
+            "",
+            "[main]",
+            "type = http",
+            "url = https://main",
+        ]
+        .join("\n");
+        let merged =
+            parse_rclone_config(&merge_rclone_config(&base, &remotes.load().unwrap()).unwrap());
+        assert_eq!(
+            merged.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            ["main", "mirror-2"]
+        );
+        assert_eq!(merged[1].get("type"), Some("sftp"));
+        assert_eq!(merged[1].get("pass"), Some("obscured-secret"));
+
+        assert!(remotes.remove("mirror-2").unwrap());
+        assert!(!remotes.remove("mirror-2").unwrap());
+        assert!(remotes.list().unwrap().is_empty());
+    }
+}
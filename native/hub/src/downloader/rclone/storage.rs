@@ -5,12 +5,23 @@ use std::{
 
 use anyhow::{Context, Result, anyhow, ensure};
 use lazy_regex::Regex;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 use tokio::{fs, sync::mpsc::UnboundedSender};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, instrument, warn};
 
-use super::cli::{RcloneCli, RcloneTransferOperation};
-use crate::downloader::TransferStats;
+use super::cli::{RcloneCheckReport, RcloneCli, RcloneTransferOperation};
+use crate::downloader::{BandwidthLimit, TransferStats};
+
+/// File of a remote directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RemoteFile {
+    /// Path relative to the listed directory, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// Last modification time in milliseconds since Unix epoch, if the remote reports it
+    pub modified: Option<u64>,
+}
 
 #[derive(Debug, Clone)]
 pub(crate) struct RcloneStorage {
@@ -29,7 +40,7 @@ impl RcloneStorage {
         config_path: PathBuf,
         root_dir: String,
         remote: String,
-        bandwidth_limit: String,
+        bandwidth_limit: BandwidthLimit,
         remote_filter_regex: Option<String>,
     ) -> Self {
         let compiled = match &remote_filter_regex {
@@ -51,6 +62,21 @@ impl RcloneStorage {
         }
     }
 
+    /// Makes rclone trust only the certificates in `ca_cert`
+    pub(crate) fn with_ca_cert(mut self, ca_cert: Option<PathBuf>) -> Self {
+        self.client = self.client.with_ca_cert(ca_cert);
+        self
+    }
+
+    pub(crate) fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    /// Same storage reading from another remote of the same config
+    pub(crate) fn with_remote(&self, remote: String) -> Self {
+        Self { remote, ..self.clone() }
+    }
+
     fn format_remote_path(&self, path: &str) -> String {
         format!(
             "{}:{}",
@@ -97,18 +123,22 @@ impl RcloneStorage {
                 dest,
                 RcloneTransferOperation::Copy,
                 total_bytes,
+                None,
                 stats_tx,
+                None,
                 cancellation_token,
             )
             .await
     }
 
-    #[instrument(level = "debug", skip(self, stats_tx, cancellation_token), ret)]
+    /// Syncs a remote directory to `dest`, sending the paths of copied files to `completed_tx`
+    #[instrument(level = "debug", skip(self, stats_tx, completed_tx, cancellation_token), ret)]
     pub(crate) async fn download_dir_with_stats(
         &self,
         source: String,
         dest: PathBuf,
         stats_tx: UnboundedSender<TransferStats>,
+        completed_tx: UnboundedSender<String>,
         cancellation_token: CancellationToken,
     ) -> Result<PathBuf> {
         ensure!(dest.parent().is_some(), "Destination must have a parent directory");
@@ -121,13 +151,97 @@ impl RcloneStorage {
                 dest.display().to_string(),
                 RcloneTransferOperation::Sync,
                 total_bytes,
+                None,
+                Some(stats_tx),
+                Some(completed_tx),
+                Some(cancellation_token),
+            )
+            .await
+            .map(|_| dest)
+    }
+
+    /// Downloads only `files` of a remote directory, given as `(relative path, size)` pairs.
+    /// Other files already in `dest` are kept. Paths of copied files are sent to `completed_tx`.
+    #[instrument(level = "debug", skip(self, files, stats_tx, completed_tx, cancellation_token), fields(files = files.len()), ret)]
+    pub(crate) async fn download_files_with_stats(
+        &self,
+        source: String,
+        dest: PathBuf,
+        files: &[(String, u64)],
+        stats_tx: UnboundedSender<TransferStats>,
+        completed_tx: UnboundedSender<String>,
+        cancellation_token: CancellationToken,
+    ) -> Result<PathBuf> {
+        ensure!(dest.parent().is_some(), "Destination must have a parent directory");
+        ensure!(!files.is_empty(), "No files selected for download");
+        let source = self.format_remote_path(&source);
+        let total_bytes = files.iter().map(|(_, size)| size).sum();
+        let files_list = write_files_list(files.iter().map(|(path, _)| path.as_str())).await?;
+        self.client
+            .transfer_with_stats(
+                source,
+                dest.display().to_string(),
+                RcloneTransferOperation::Copy,
+                total_bytes,
+                Some(files_list.path()),
                 Some(stats_tx),
+                Some(completed_tx),
                 Some(cancellation_token),
             )
             .await
             .map(|_| dest)
     }
 
+    /// Compares a downloaded directory with its remote source, ignoring `excludes`. If `files`
+    /// is given, only those paths are compared.
+    #[instrument(level = "debug", skip(self, cancellation_token), err)]
+    pub(crate) async fn verify_dir(
+        &self,
+        source: &str,
+        dest: &Path,
+        excludes: &[&str],
+        files: Option<&[String]>,
+        cancellation_token: CancellationToken,
+    ) -> Result<RcloneCheckReport> {
+        let source = self.format_remote_path(source);
+        let files_list = match files {
+            Some(files) => Some(write_files_list(files.iter().map(String::as_str)).await?),
+            None => None,
+        };
+        self.client
+            .check(
+                &source,
+                &dest.display().to_string(),
+                excludes,
+                files_list.as_ref().map(|file| file.path()),
+                cancellation_token,
+            )
+            .await
+    }
+
+    /// Lists files of a remote directory as `(relative path, size)` pairs.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn list_dir_files(&self, source: &str) -> Result<Vec<(String, u64)>> {
+        let source = self.format_remote_path(source);
+        let entries = self.client.lsjson_files(&source).await?;
+        Ok(entries.into_iter().map(|e| (e.path, e.size)).collect())
+    }
+
+    /// Lists files of a remote directory with their sizes and modification times.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn list_dir_details(&self, source: &str) -> Result<Vec<RemoteFile>> {
+        let source = self.format_remote_path(source);
+        let entries = self.client.lsjson_files(&source).await?;
+        Ok(entries
+            .into_iter()
+            .map(|e| RemoteFile {
+                modified: e.mod_time.as_deref().and_then(parse_mod_time),
+                path: e.path,
+                size: e.size,
+            })
+            .collect())
+    }
+
     #[instrument(level = "debug", skip(self, cancellation_token), ret)]
     pub(crate) async fn download_file(
         &self,
@@ -160,6 +274,11 @@ impl RcloneStorage {
         let remotes = self.client.remotes().await?;
         Ok(filter_remotes_with_regex(remotes, self.remote_filter_regex.as_ref()))
     }
+
+    /// Version details of the rclone binary in use
+    pub(crate) async fn rclone_version(&self) -> Result<String> {
+        self.client.version().await
+    }
 }
 
 fn format_upload_destination(remote: &str, remote_dir: &str) -> String {
@@ -190,10 +309,31 @@ impl PartialEq for RcloneStorage {
 
 impl Eq for RcloneStorage {}
 
+/// Parses an RFC 3339 modification time from `rclone lsjson` into milliseconds since Unix epoch
+fn parse_mod_time(value: &str) -> Option<u64> {
+    let time = OffsetDateTime::parse(value, &Rfc3339).ok()?;
+    u64::try_from(time.unix_timestamp_nanos() / 1_000_000).ok()
+}
+
+/// Writes `paths` one per line into a temporary file for rclone's `--files-from-raw`
+async fn write_files_list(paths: impl Iterator<Item = &str>) -> Result<tempfile::NamedTempFile> {
+    let file = tempfile::NamedTempFile::new().context("Failed to create files list")?;
+    let content = paths.map(|path| format!("{path}\n")).collect::<String>();
+    fs::write(file.path(), content).await.context("Failed to write files list")?;
+    Ok(file)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_lsjson_mod_times() {
+        assert_eq!(parse_mod_time("2024-05-01T12:00:00.5Z"), Some(1_714_564_800_500));
+        assert_eq!(parse_mod_time("2024-05-01T14:00:00+02:00"), Some(1_714_564_800_000));
+        assert_eq!(parse_mod_time("yesterday"), None);
+    }
+
     #[test]
     fn storage_equality_reflects_bandwidth_limit() {
         let base = RcloneStorage::new(
@@ -201,7 +341,7 @@ mod tests {
             PathBuf::from("config"),
             "root".to_string(),
             "remote".to_string(),
-            "".to_string(),
+            BandwidthLimit::new(String::new()),
             None,
         );
         let same = RcloneStorage::new(
@@ -209,7 +349,7 @@ mod tests {
             PathBuf::from("config"),
             "root".to_string(),
             "remote".to_string(),
-            "".to_string(),
+            BandwidthLimit::new(String::new()),
             None,
         );
         let with_limit = RcloneStorage::new(
@@ -217,7 +357,7 @@ mod tests {
             PathBuf::from("config"),
             "root".to_string(),
             "remote".to_string(),
-            "2M".to_string(),
+            BandwidthLimit::new("2M".to_string()),
             None,
         );
 
@@ -1,4 +1,8 @@
-use std::{collections::HashSet, error::Error, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    path::Path,
+};
 
 use anyhow::{Context, Result, anyhow, bail, ensure};
 use async_trait::async_trait;
@@ -10,17 +14,18 @@ use tokio::{
     sync::mpsc::UnboundedSender,
 };
 use tokio_util::sync::CancellationToken;
-use tracing::{Instrument, Span, debug, instrument, warn};
+use tracing::{Instrument, Span, debug, info, instrument, warn};
 
 use super::{
-    BuildStorageArgs, BuildStorageResult, Repo, RepoAppList, RepoCapabilities, RepoDownloadResult,
-    RepoStorage,
+    BuildStorageArgs, BuildStorageResult, LOCAL_DOWNLOAD_METADATA_PATHS, Repo, RepoAppList,
+    RepoCapabilities, RepoDownloadResult, RepoStorage,
 };
 use crate::{
     downloader::{
-        AppDownloadProgress, TransferStats,
+        AppDownloadProgress, DownloadVerification, TransferStats,
         config::DownloaderConfig,
-        rclone::{self, RcloneStorage},
+        rclone::{self, RcloneCheckReport, RcloneStorage, RemoteFile},
+        resume::CheckpointHandle,
     },
     models::{CloudApp, DownloadMode},
 };
@@ -49,6 +54,7 @@ impl Repo for FFARepo {
             supports_bandwidth_limit: true,
             supports_download_mode_selection: false,
             supports_donation_upload: true,
+            supports_partial_downloads: true,
         }
     }
 
@@ -78,9 +84,10 @@ impl Repo for FFARepo {
             rclone_config_path.to_path_buf(),
             args.root_dir.to_string(),
             remote_name,
-            args.bandwidth_limit.to_string(),
+            args.bandwidth_limit.clone(),
             args.remote_name_filter_regex.clone(),
-        );
+        )
+        .with_ca_cert(args.ca_cert_path.map(Path::to_path_buf));
         Ok(BuildStorageResult { storage: RepoStorage::Ffa(storage), persist_remote })
     }
 
@@ -174,6 +181,8 @@ impl Repo for FFARepo {
         storage: RepoStorage,
         app_full_name: &str,
         destination_dir: &Path,
+        files: Option<&[String]>,
+        checkpoint: &CheckpointHandle,
         _cache_dir: &Path,
         _http_client: &reqwest::Client,
         _download_mode: DownloadMode,
@@ -183,6 +192,73 @@ impl Repo for FFARepo {
         let RepoStorage::Ffa(storage) = storage else {
             unreachable!("new-repo storage passed to ffa repo");
         };
+
+        let selected = match files {
+            Some(files) => {
+                let remote_files = storage.list_dir_files(app_full_name).await?;
+                Some(select_release_files(remote_files, files)?)
+            }
+            None => None,
+        };
+
+        if destination_dir.exists() {
+            let _ = progress_tx
+                .send(AppDownloadProgress::Status("Checking existing files...".to_string()));
+            let matches = async {
+                match &selected {
+                    Some(selected) => local_files_present(destination_dir, selected).await,
+                    None => {
+                        let remote_files = storage.list_dir_files(app_full_name).await?;
+                        local_files_match(destination_dir, &remote_files).await
+                    }
+                }
+            }
+            .await;
+            match matches {
+                Ok(true) => {
+                    info!(
+                        path = %destination_dir.display(),
+                        "Skipping download because local files already match the remote"
+                    );
+                    return Ok(RepoDownloadResult { skipped: true });
+                }
+                Ok(false) => {
+                    debug!(
+                        path = %destination_dir.display(),
+                        "Existing download does not match the remote, continuing with download"
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        path = %destination_dir.display(),
+                        error = e.as_ref() as &dyn Error,
+                        "Failed to compare existing download, continuing with download"
+                    );
+                }
+            }
+        }
+
+        // Files an earlier attempt completed are not transferred again
+        let completed = checkpoint.snapshot().await.completed_files;
+        let selected = if completed.is_empty() {
+            selected
+        } else {
+            let files = match selected {
+                Some(selected) => selected,
+                None => storage.list_dir_files(app_full_name).await?,
+            };
+            let remaining = remaining_files(destination_dir, files, &completed).await;
+            info!(
+                completed = completed.len(),
+                remaining = remaining.len(),
+                "Resuming download with the files left"
+            );
+            if remaining.is_empty() {
+                return Ok(RepoDownloadResult { skipped: false });
+            }
+            Some(remaining)
+        };
+
         let _ = progress_tx.send(AppDownloadProgress::Status("Downloading files...".to_string()));
         let (stats_tx, mut stats_rx) = tokio::sync::mpsc::unbounded_channel::<TransferStats>();
         let forward_progress = tokio::spawn(async move {
@@ -190,18 +266,91 @@ impl Repo for FFARepo {
                 let _ = progress_tx.send(AppDownloadProgress::Transfer(stats));
             }
         });
-        storage
-            .download_dir_with_stats(
-                app_full_name.to_string(),
-                destination_dir.to_path_buf(),
-                stats_tx,
-                cancellation_token,
-            )
-            .await?;
+        let (completed_tx, mut completed_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let record_completed = {
+            let checkpoint = checkpoint.clone();
+            tokio::spawn(async move {
+                while let Some(file) = completed_rx.recv().await {
+                    if let Err(e) = checkpoint.complete_file(file).await {
+                        warn!(
+                            error = e.as_ref() as &dyn Error,
+                            "Failed to save download checkpoint"
+                        );
+                    }
+                }
+            })
+        };
+        let result = match &selected {
+            Some(selected) => {
+                info!(files = selected.len(), "Downloading selected files of the release");
+                storage
+                    .download_files_with_stats(
+                        app_full_name.to_string(),
+                        destination_dir.to_path_buf(),
+                        selected,
+                        stats_tx,
+                        completed_tx,
+                        cancellation_token,
+                    )
+                    .await
+            }
+            None => {
+                storage
+                    .download_dir_with_stats(
+                        app_full_name.to_string(),
+                        destination_dir.to_path_buf(),
+                        stats_tx,
+                        completed_tx,
+                        cancellation_token,
+                    )
+                    .await
+            }
+        };
         let _ = forward_progress.await;
+        let _ = record_completed.await;
+        result?;
         Ok(RepoDownloadResult { skipped: false })
     }
 
+    #[instrument(level = "debug", name = "repo.list_release_files", skip(storage), fields(layout = %self.id()), err)]
+    async fn list_release_files(
+        &self,
+        storage: RepoStorage,
+        app_full_name: &str,
+    ) -> Result<Vec<RemoteFile>> {
+        let RepoStorage::Ffa(storage) = storage else {
+            unreachable!("new-repo storage passed to ffa repo");
+        };
+        let mut files = storage.list_dir_details(app_full_name).await?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    #[instrument(level = "debug", name = "repo.verify_download", skip(storage, cancellation_token), fields(layout = %self.id()), err)]
+    async fn verify_download(
+        &self,
+        storage: RepoStorage,
+        app_full_name: &str,
+        destination_dir: &Path,
+        files: Option<&[String]>,
+        cancellation_token: CancellationToken,
+    ) -> Result<DownloadVerification> {
+        let RepoStorage::Ffa(storage) = storage else {
+            unreachable!("new-repo storage passed to ffa repo");
+        };
+        // Anchor the patterns to the download root
+        let excludes =
+            LOCAL_DOWNLOAD_METADATA_PATHS.iter().map(|path| format!("/{path}")).collect::<Vec<_>>();
+        let excludes = excludes.iter().map(String::as_str).collect::<Vec<_>>();
+        let report = storage
+            .verify_dir(app_full_name, destination_dir, &excludes, files, cancellation_token)
+            .await
+            .context("Failed to compare download with the remote")?;
+        ensure!(report.is_ok(), "{}", describe_check_failures(&report));
+        info!(files = report.matched, "Download matches the remote");
+        Ok(DownloadVerification::Verified { files: report.matched })
+    }
+
     async fn upload_donation_archive(
         &self,
         storage: RepoStorage,
@@ -209,7 +358,7 @@ impl Repo for FFARepo {
         archive_path: &Path,
         stats_tx: Option<UnboundedSender<TransferStats>>,
         cancellation_token: CancellationToken,
-    ) -> Result<()> {
+    ) -> Result<String> {
         let RepoStorage::Ffa(storage) = storage else {
             unreachable!("new-repo storage passed to ffa repo");
         };
@@ -237,7 +386,8 @@ impl Repo for FFARepo {
                 Some(cancellation_token),
             )
             .await
-            .context("Failed to upload donation archive")
+            .context("Failed to upload donation archive")?;
+        Ok(format!("{remote}:{remote_path}"))
     }
 }
 
@@ -324,3 +474,181 @@ fn parse_blacklist(text: &str) -> HashSet<String> {
         .map(|line| line.to_string())
         .collect()
 }
+
+/// Returns true if `dir` contains exactly the remote files (by relative path and size),
+/// ignoring our own metadata files.
+#[instrument(level = "debug", skip(remote_files), fields(dir = %dir.display()), ret, err)]
+async fn local_files_match(dir: &Path, remote_files: &[(String, u64)]) -> Result<bool> {
+    if remote_files.is_empty() {
+        return Ok(false);
+    }
+
+    let mut local_files = HashMap::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(path) = stack.pop() {
+        let mut rd = fs::read_dir(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        while let Some(entry) = rd.next_entry().await? {
+            let meta = entry.metadata().await?;
+            if meta.is_dir() {
+                stack.push(entry.path());
+                continue;
+            }
+            let relative = entry.path().strip_prefix(dir)?.to_string_lossy().replace('\\', "/");
+            if !LOCAL_DOWNLOAD_METADATA_PATHS.contains(&relative.as_str()) {
+                local_files.insert(relative, meta.len());
+            }
+        }
+    }
+
+    Ok(local_files.len() == remote_files.len()
+        && remote_files.iter().all(|(path, size)| local_files.get(path) == Some(size)))
+}
+
+/// Picks `files` out of the remote files of a release, failing if any of them is not part of it
+fn select_release_files(
+    remote_files: Vec<(String, u64)>,
+    files: &[String],
+) -> Result<Vec<(String, u64)>> {
+    let sizes = remote_files.into_iter().collect::<HashMap<_, _>>();
+    files
+        .iter()
+        .map(|path| match sizes.get(path) {
+            Some(size) => Ok((path.clone(), *size)),
+            None => Err(anyhow!("File \"{path}\" is not part of the release")),
+        })
+        .collect()
+}
+
+/// Drops the `completed` files that are still in `dir` with their remote size from `files`
+async fn remaining_files(
+    dir: &Path,
+    files: Vec<(String, u64)>,
+    completed: &[String],
+) -> Vec<(String, u64)> {
+    let mut remaining = Vec::with_capacity(files.len());
+    for (path, size) in files {
+        let done = completed.contains(&path)
+            && fs::metadata(dir.join(&path))
+                .await
+                .is_ok_and(|meta| meta.is_file() && meta.len() == size);
+        if !done {
+            remaining.push((path, size));
+        }
+    }
+    remaining
+}
+
+/// Whether all `files` are already in `dir` with the same sizes
+async fn local_files_present(dir: &Path, files: &[(String, u64)]) -> Result<bool> {
+    for (path, size) in files {
+        match fs::metadata(dir.join(path)).await {
+            Ok(meta) if meta.is_file() && meta.len() == *size => {}
+            Ok(_) => return Ok(false),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {path}")),
+        }
+    }
+    Ok(!files.is_empty())
+}
+
+/// Summarizes the files that failed verification, listing at most a few paths per category
+fn describe_check_failures(report: &RcloneCheckReport) -> String {
+    const MAX_LISTED: usize = 3;
+    let list = |paths: &[String]| {
+        let mut listed = paths.iter().take(MAX_LISTED).cloned().collect::<Vec<_>>().join(", ");
+        if paths.len() > MAX_LISTED {
+            listed.push_str(&format!(" and {} more", paths.len() - MAX_LISTED));
+        }
+        listed
+    };
+    [
+        ("differ from the remote", &report.differing),
+        ("are missing", &report.missing),
+        ("could not be checked", &report.errors),
+    ]
+    .into_iter()
+    .filter(|(_, paths)| !paths.is_empty())
+    .map(|(problem, paths)| format!("{} file(s) {problem}: {}", paths.len(), list(paths)))
+    .collect::<Vec<_>>()
+    .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_files_match_compares_paths_and_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("com.example")).unwrap();
+        std::fs::write(dir.path().join("game.apk"), b"apk").unwrap();
+        std::fs::write(dir.path().join("com.example/main.obb"), b"obb!").unwrap();
+        std::fs::write(dir.path().join("metadata.json"), b"{}").unwrap();
+
+        let remote = vec![("game.apk".to_string(), 3), ("com.example/main.obb".to_string(), 4)];
+        assert!(local_files_match(dir.path(), &remote).await.unwrap());
+
+        let different_size =
+            vec![("game.apk".to_string(), 3), ("com.example/main.obb".to_string(), 5)];
+        assert!(!local_files_match(dir.path(), &different_size).await.unwrap());
+
+        let missing_local = vec![("game.apk".to_string(), 3)];
+        assert!(!local_files_match(dir.path(), &missing_local).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn selected_files_are_checked_individually() {
+        let remote = vec![
+            ("game.apk".to_string(), 3),
+            ("com.example/main.obb".to_string(), 4),
+            ("trailer.mp4".to_string(), 5),
+        ];
+        let selected = select_release_files(remote.clone(), &["game.apk".to_string()]).unwrap();
+        assert_eq!(selected, vec![("game.apk".to_string(), 3)]);
+        assert!(select_release_files(remote, &["other.apk".to_string()]).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!local_files_present(dir.path(), &selected).await.unwrap());
+        std::fs::write(dir.path().join("game.apk"), b"apk").unwrap();
+        assert!(local_files_present(dir.path(), &selected).await.unwrap());
+        std::fs::write(dir.path().join("game.apk"), b"apk!").unwrap();
+        assert!(!local_files_present(dir.path(), &selected).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn resumed_downloads_skip_completed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("game.apk"), b"apk").unwrap();
+        std::fs::write(dir.path().join("main.obb"), b"ob").unwrap();
+        let files = vec![
+            ("game.apk".to_string(), 3),
+            ("main.obb".to_string(), 4),
+            ("patch.obb".to_string(), 5),
+        ];
+        let completed = ["game.apk".to_string(), "main.obb".to_string()];
+
+        // The obb was changed since it was recorded as completed
+        assert_eq!(
+            remaining_files(dir.path(), files, &completed).await,
+            vec![("main.obb".to_string(), 4), ("patch.obb".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn check_failures_are_summarized_per_category() {
+        let report = RcloneCheckReport {
+            matched: 2,
+            differing: (1..=5).map(|i| format!("part{i}.obb")).collect(),
+            missing: vec!["game.apk".to_string()],
+            errors: Vec::new(),
+        };
+
+        assert_eq!(
+            describe_check_failures(&report),
+            "5 file(s) differ from the remote: part1.obb, part2.obb, part3.obb and 2 more; 1 \
+             file(s) are missing: game.apk"
+        );
+    }
+}
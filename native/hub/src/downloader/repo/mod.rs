@@ -1,13 +1,17 @@
 use std::{path::Path, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use async_trait::async_trait;
 use derive_more::Debug;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::sync::CancellationToken;
 
 use self::{ffa::FFARepo, newrepo::NewRepo};
-use super::{AppDownloadProgress, TransferStats, rclone::RcloneStorage};
+use super::{
+    AppDownloadProgress, BandwidthLimit, DownloadVerification, SensitiveUrl, TransferStats,
+    rclone::{RcloneStorage, RemoteFile},
+    resume::CheckpointHandle,
+};
 use crate::{
     downloader::config::{DownloaderConfig, RepoLayoutKind},
     models::{CloudApp, DownloadMode, signals::downloader::availability::RepoCapabilities},
@@ -16,6 +20,9 @@ use crate::{
 mod ffa;
 mod newrepo;
 
+/// Files written by us into download directories, ignored when comparing against the remote
+const LOCAL_DOWNLOAD_METADATA_PATHS: [&str; 2] = ["metadata.json", "release.json"];
+
 #[derive(Debug)]
 pub(super) struct BuildStorageResult {
     pub storage: RepoStorage,
@@ -41,6 +48,32 @@ pub(super) enum RepoStorage {
     NewRepo(newrepo::NewRepoStorage),
 }
 
+impl RepoStorage {
+    /// Remote or mirror files are downloaded from, for display
+    pub(super) fn source_name(&self) -> String {
+        match self {
+            RepoStorage::Ffa(storage) => storage.remote().to_string(),
+            RepoStorage::NewRepo(storage) => SensitiveUrl::new(storage.base_url()).to_string(),
+        }
+    }
+
+    /// Storage reading from `mirror` instead, a remote name for rclone sources or a base URL
+    /// for the others. `None` if the mirror does not fit this kind of storage.
+    pub(super) fn with_mirror(&self, mirror: &str) -> Option<RepoStorage> {
+        let is_url = mirror.starts_with("http://") || mirror.starts_with("https://");
+        match self {
+            RepoStorage::Ffa(storage) => (!mirror.contains("://")).then(|| {
+                RepoStorage::Ffa(storage.with_remote(mirror.trim_end_matches(':').to_string()))
+            }),
+            RepoStorage::NewRepo(storage) => is_url.then(|| {
+                RepoStorage::NewRepo(
+                    storage.with_base_url(mirror.trim_end_matches('/').to_string()),
+                )
+            }),
+        }
+    }
+}
+
 /// High-level operations a repository must implement.
 #[async_trait]
 pub(super) trait Repo: Send + Sync {
@@ -61,12 +94,16 @@ pub(super) trait Repo: Send + Sync {
         cancellation_token: CancellationToken,
     ) -> Result<RepoAppList>;
 
+    /// Downloads a release into `destination_dir`, recording its progress in `checkpoint` and
+    /// continuing from what an interrupted earlier attempt recorded there.
     #[allow(clippy::too_many_arguments)]
     async fn download_app(
         &self,
         storage: RepoStorage,
         app_full_name: &str,
         destination_dir: &Path,
+        files: Option<&[String]>,
+        checkpoint: &CheckpointHandle,
         cache_dir: &Path,
         http_client: &reqwest::Client,
         download_mode: DownloadMode,
@@ -74,6 +111,17 @@ pub(super) trait Repo: Send + Sync {
         cancellation_token: CancellationToken,
     ) -> Result<RepoDownloadResult>;
 
+    /// Lists the files of a release with paths relative to it, to show what a download fetches
+    /// and limit it to some of them.
+    async fn list_release_files(
+        &self,
+        _storage: RepoStorage,
+        _app_full_name: &str,
+    ) -> Result<Vec<RemoteFile>> {
+        bail!("This source does not support downloading selected files")
+    }
+
+    /// Uploads a donated app archive, returning where it went
     async fn upload_donation_archive(
         &self,
         storage: RepoStorage,
@@ -81,7 +129,20 @@ pub(super) trait Repo: Send + Sync {
         archive_path: &Path,
         stats_tx: Option<UnboundedSender<TransferStats>>,
         cancellation_token: CancellationToken,
-    ) -> Result<()>;
+    ) -> Result<String>;
+
+    /// Checks a finished download in `destination_dir` against the remote, failing if any file
+    /// is missing or differs. Only `files` are checked if the download was limited to them.
+    async fn verify_download(
+        &self,
+        _storage: RepoStorage,
+        _app_full_name: &str,
+        _destination_dir: &Path,
+        _files: Option<&[String]>,
+        _cancellation_token: CancellationToken,
+    ) -> Result<DownloadVerification> {
+        Ok(DownloadVerification::Unsupported)
+    }
 
     /// If the repo generates its own rclone config at runtime, return the
     /// suggested filename to be used. Otherwise None.
@@ -106,8 +167,10 @@ pub(super) struct BuildStorageArgs<'a> {
     pub root_dir: &'a str,
     /// Remote selected by Downloader. Repo may keep or replace it.
     pub remote_name: &'a str,
-    pub bandwidth_limit: &'a str,
+    pub bandwidth_limit: &'a BandwidthLimit,
     pub remote_name_filter_regex: Option<String>,
     /// Whether repo is allowed to pick a different remote automatically.
     pub allow_randomize_remote: bool,
+    /// Pinned certificates for rclone, if the source pins any
+    pub ca_cert_path: Option<&'a Path>,
 }
@@ -30,13 +30,15 @@ use yarc::{
 };
 
 use super::{
-    BuildStorageArgs, BuildStorageResult, Repo, RepoAppList, RepoCapabilities, RepoDownloadResult,
-    RepoStorage,
+    BuildStorageArgs, BuildStorageResult, LOCAL_DOWNLOAD_METADATA_PATHS, Repo, RepoAppList,
+    RepoCapabilities, RepoDownloadResult, RepoStorage,
 };
 use crate::{
     downloader::{
-        AppDownloadProgress, TransferSpeedTracker, TransferStats, config::DownloaderConfig,
+        AppDownloadProgress, BandwidthLimit, Throttle, TransferSpeedTracker, TransferStats,
+        config::DownloaderConfig,
         http_cache,
+        resume::{CheckpointHandle, PackageProgress},
     },
     models::{CloudApp, DownloadMode},
 };
@@ -45,9 +47,10 @@ const YAAS_KEY_HEADER: &str = "x-yaas-key";
 const STREAM_PROGRESS_INTERVAL_MILLIS: u128 = 500;
 const SPEED_SAMPLE_WINDOW: Duration = Duration::from_secs(4);
 const SLOW_NETWORK_WARNING_THRESHOLD: Duration = Duration::from_secs(8);
-const LOCAL_DOWNLOAD_METADATA_PATHS: [&str; 2] = ["metadata.json", "release.json"];
 const STAGED_DOWNLOAD_WORKERS: usize = 4;
 const STAGED_MIN_PART_SIZE: u64 = 32 * 1024 * 1024;
+/// How often the written part of a staged package is saved to the download checkpoint
+const STAGED_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Debug, Default)]
 struct NewRepoRuntime {
@@ -58,12 +61,22 @@ struct NewRepoRuntime {
 #[derive(Debug, Clone)]
 pub(in crate::downloader) struct NewRepoStorage {
     base_url: String,
+    bandwidth_limit: BandwidthLimit,
     runtime: Arc<Mutex<NewRepoRuntime>>,
 }
 
 impl NewRepoStorage {
-    fn new(base_url: String) -> Self {
-        Self { base_url, runtime: Arc::new(Mutex::new(NewRepoRuntime::default())) }
+    fn new(base_url: String, bandwidth_limit: BandwidthLimit) -> Self {
+        Self { base_url, bandwidth_limit, runtime: Arc::new(Mutex::new(NewRepoRuntime::default())) }
+    }
+
+    pub(in crate::downloader) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Same storage served from another base URL, sharing the loaded catalog state
+    pub(in crate::downloader) fn with_base_url(&self, base_url: String) -> Self {
+        Self { base_url, ..self.clone() }
     }
 
     fn list_url(&self) -> String {
@@ -102,7 +115,7 @@ impl NewRepoStorage {
 
 impl PartialEq for NewRepoStorage {
     fn eq(&self, other: &Self) -> bool {
-        self.base_url == other.base_url
+        self.base_url == other.base_url && self.bandwidth_limit == other.bandwidth_limit
     }
 }
 
@@ -134,15 +147,19 @@ impl Repo for NewRepo {
     fn capabilities(&self) -> RepoCapabilities {
         RepoCapabilities {
             supports_remote_selection: false,
-            supports_bandwidth_limit: false,
+            supports_bandwidth_limit: true,
             supports_download_mode_selection: true,
             supports_donation_upload: false,
+            supports_partial_downloads: false,
         }
     }
 
-    async fn build_storage(&self, _args: BuildStorageArgs<'_>) -> Result<BuildStorageResult> {
+    async fn build_storage(&self, args: BuildStorageArgs<'_>) -> Result<BuildStorageResult> {
         Ok(BuildStorageResult {
-            storage: RepoStorage::NewRepo(NewRepoStorage::new(self.base_url.clone())),
+            storage: RepoStorage::NewRepo(NewRepoStorage::new(
+                self.base_url.clone(),
+                args.bandwidth_limit.clone(),
+            )),
             persist_remote: None,
         })
     }
@@ -237,6 +254,8 @@ impl Repo for NewRepo {
         storage: RepoStorage,
         app_full_name: &str,
         destination_dir: &Path,
+        files: Option<&[String]>,
+        checkpoint: &CheckpointHandle,
         _cache_dir: &Path,
         http_client: &reqwest::Client,
         download_mode: DownloadMode,
@@ -246,6 +265,7 @@ impl Repo for NewRepo {
         let RepoStorage::NewRepo(storage) = storage else {
             unreachable!("ffa storage passed to new-repo backend");
         };
+        ensure!(files.is_none(), "This source does not support downloading selected files");
 
         ensure_not_cancelled(&cancellation_token)?;
         info!(
@@ -361,7 +381,8 @@ impl Repo for NewRepo {
             let blob_url = storage.blob_url(&manifest.yarc_id);
             match download_mode {
                 DownloadMode::Staged => {
-                    let package_path = temp_dir_path.join("package.yarc");
+                    // Kept with the checkpoint so an interrupted download continues from it
+                    let package_path = checkpoint.package_path().await;
                     debug!(
                         blob_id = %manifest.yarc_id,
                         total_bytes = manifest.yarc_size,
@@ -373,7 +394,10 @@ impl Repo for NewRepo {
                         http_client,
                         &blob_url,
                         &package_path,
+                        &manifest.yarc_id.to_string(),
                         manifest.yarc_size,
+                        checkpoint,
+                        storage.bandwidth_limit.throttle(),
                         progress_tx.clone(),
                         cancellation_token.clone(),
                     )
@@ -386,13 +410,15 @@ impl Repo for NewRepo {
                     let package_file = fs::File::open(&package_path)
                         .await
                         .with_context(|| format!("Failed to open {}", package_path.display()))?;
-                    YarcReader::new(yarc_key)
+                    let extracted = YarcReader::new(yarc_key)
                         .extract_to_directory(package_file, &temp_dir_path)
                         .await
-                        .context("Failed to extract YARC package")?;
-                    fs::remove_file(&package_path)
-                        .await
-                        .with_context(|| format!("Failed to remove {}", package_path.display()))?;
+                        .context("Failed to extract YARC package");
+                    // A package that failed to extract is downloaded again
+                    if extracted.is_ok() || !cancellation_token.is_cancelled() {
+                        discard_package(&package_path, checkpoint).await?;
+                    }
+                    extracted?;
                 }
                 DownloadMode::Streamed => {
                     download_package_streamed(
@@ -401,6 +427,7 @@ impl Repo for NewRepo {
                         temp_dir_path,
                         yarc_key,
                         manifest.yarc_size,
+                        storage.bandwidth_limit.throttle(),
                         progress_tx.clone(),
                         cancellation_token.clone(),
                     )
@@ -468,8 +495,11 @@ impl Repo for NewRepo {
         _archive_path: &Path,
         _stats_tx: Option<UnboundedSender<TransferStats>>,
         _cancellation_token: CancellationToken,
-    ) -> Result<()> {
-        bail!("App donations are not supported for the new-repo repository layout")
+    ) -> Result<String> {
+        bail!(
+            "The new-repo repository layout has no donation remote, set donation_upload_url in \
+             downloader.json instead"
+        )
     }
 }
 
@@ -567,12 +597,14 @@ async fn cache_remote_file(
     Ok(destination)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn download_package_streamed(
     client: &reqwest::Client,
     url: &str,
     destination: &Path,
     yarc_key: [u8; 32],
     expected_bytes: u64,
+    throttle: Throttle,
     progress_tx: UnboundedSender<AppDownloadProgress>,
     cancellation_token: CancellationToken,
 ) -> Result<()> {
@@ -591,6 +623,7 @@ async fn download_package_streamed(
     let stream_task = tokio::spawn(stream_package_to_pipe(
         response,
         writer,
+        throttle,
         progress_tx,
         total_bytes,
         cancellation_token,
@@ -610,11 +643,17 @@ async fn download_package_streamed(
     }
 }
 
+/// Downloads a package into `destination` in ranges, continuing the ranges `checkpoint` has
+/// partly written for the same blob
+#[allow(clippy::too_many_arguments)]
 async fn download_package_staged(
     client: &reqwest::Client,
     url: &str,
     destination: &Path,
+    blob_id: &str,
     total_bytes: u64,
+    checkpoint: &CheckpointHandle,
+    throttle: Throttle,
     progress_tx: UnboundedSender<AppDownloadProgress>,
     cancellation_token: CancellationToken,
 ) -> Result<()> {
@@ -625,19 +664,45 @@ async fn download_package_staged(
             .with_context(|| format!("Failed to create {}", parent.display()))?;
     }
 
-    let file = fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(destination)
-        .await
-        .with_context(|| format!("Failed to create {}", destination.display()))?;
-    file.set_len(total_bytes)
-        .await
-        .with_context(|| format!("Failed to preallocate {}", destination.display()))?;
-    drop(file);
-
     let ranges = staged_download_ranges(total_bytes);
+    let saved = checkpoint.snapshot().await.package.filter(|package| {
+        package.blob_id == blob_id
+            && package.total_bytes == total_bytes
+            && package.written.len() == ranges.len()
+    });
+    let file_len = fs::metadata(destination).await.ok().map(|meta| meta.len());
+    let written = match saved {
+        Some(package) if file_len == Some(total_bytes) => {
+            info!(
+                path = %destination.display(),
+                bytes = package.written.iter().sum::<u64>(),
+                "Continuing staged package download"
+            );
+            package.written
+        }
+        _ => {
+            let file = fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(destination)
+                .await
+                .with_context(|| format!("Failed to create {}", destination.display()))?;
+            file.set_len(total_bytes)
+                .await
+                .with_context(|| format!("Failed to preallocate {}", destination.display()))?;
+            drop(file);
+            vec![0; ranges.len()]
+        }
+    };
+    let written: Arc<[AtomicU64]> = ranges
+        .iter()
+        .zip(written)
+        .map(|(range, written)| AtomicU64::new(written.min(range.len())))
+        .collect();
+    let package = PackageWriter { blob_id: blob_id.to_string(), total_bytes, written };
+    package.save(checkpoint).await;
+
     debug!(
         url,
         destination = %destination.display(),
@@ -646,25 +711,33 @@ async fn download_package_staged(
         "Downloading package with ranged staged transfer"
     );
 
-    let downloaded_bytes = Arc::new(AtomicU64::new(0));
+    let downloaded_bytes = Arc::new(AtomicU64::new(package.bytes()));
     let transfer_token = cancellation_token.child_token();
     let progress_token = CancellationToken::new();
     let progress_task = tokio::spawn(staged_progress_loop(
         downloaded_bytes.clone(),
         total_bytes,
+        package.clone(),
+        checkpoint.clone(),
         progress_tx,
         progress_token.clone(),
     ));
 
     let mut tasks = Vec::with_capacity(ranges.len());
     for range in ranges {
+        let Some(remaining) = range.remaining(package.written[range.index].load(Ordering::Relaxed))
+        else {
+            continue;
+        };
         tasks.push(tokio::spawn(download_staged_range(
             client.clone(),
             url.to_string(),
             destination.to_path_buf(),
-            range,
+            remaining,
             total_bytes,
             downloaded_bytes.clone(),
+            package.written.clone(),
+            throttle.clone(),
             transfer_token.clone(),
         )));
     }
@@ -684,6 +757,7 @@ async fn download_package_staged(
 
     progress_token.cancel();
     let _ = join_transfer_task(progress_task).await;
+    package.save(checkpoint).await;
     result?;
 
     let actual_len = fs::metadata(destination)
@@ -697,6 +771,44 @@ async fn download_package_staged(
     Ok(())
 }
 
+/// Bytes of a staged package written so far, per range
+#[derive(Clone, Debug)]
+struct PackageWriter {
+    blob_id: String,
+    total_bytes: u64,
+    written: Arc<[AtomicU64]>,
+}
+
+impl PackageWriter {
+    fn bytes(&self) -> u64 {
+        self.written.iter().map(|written| written.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Records the written bytes in the checkpoint, a failure only costs them on resume
+    async fn save(&self, checkpoint: &CheckpointHandle) {
+        let progress = PackageProgress {
+            blob_id: self.blob_id.clone(),
+            total_bytes: self.total_bytes,
+            written: self.written.iter().map(|written| written.load(Ordering::Relaxed)).collect(),
+        };
+        if let Err(e) = checkpoint.set_package(Some(progress)).await {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to save staged package progress");
+        }
+    }
+}
+
+/// Removes an extracted or unusable package so the next attempt downloads it again
+async fn discard_package(path: &Path, checkpoint: &CheckpointHandle) -> Result<()> {
+    if let Err(e) = checkpoint.set_package(None).await {
+        warn!(error = e.as_ref() as &dyn Error, "Failed to save download checkpoint");
+    }
+    match fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {}", path.display())),
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct StagedRange {
     index: usize,
@@ -712,6 +824,11 @@ impl StagedRange {
     fn header_value(self) -> String {
         format!("bytes={}-{}", self.start, self.end)
     }
+
+    /// Part of the range left after `written` bytes, `None` once it is complete
+    fn remaining(self, written: u64) -> Option<Self> {
+        (written < self.len()).then_some(Self { start: self.start + written, ..self })
+    }
 }
 
 fn staged_download_ranges(total_bytes: u64) -> Vec<StagedRange> {
@@ -728,6 +845,7 @@ fn staged_download_ranges(total_bytes: u64) -> Vec<StagedRange> {
         .collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn download_staged_range(
     client: reqwest::Client,
     url: String,
@@ -735,6 +853,8 @@ async fn download_staged_range(
     range: StagedRange,
     total_bytes: u64,
     downloaded_bytes: Arc<AtomicU64>,
+    range_written: Arc<[AtomicU64]>,
+    mut throttle: Throttle,
     cancellation_token: CancellationToken,
 ) -> Result<()> {
     ensure_not_cancelled(&cancellation_token)?;
@@ -795,6 +915,14 @@ async fn download_staged_range(
         }
         written += chunk.len() as u64;
         downloaded_bytes.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        range_written[range.index].fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        tokio::select! {
+            _ = cancellation_token.cancelled() => {
+                info!(range_index = range.index, "Cancelled while throttling staged package range");
+                bail!("Operation cancelled")
+            },
+            _ = throttle.consume(chunk.len() as u64) => {}
+        }
     }
 
     file.shutdown()
@@ -820,21 +948,32 @@ async fn download_staged_range(
 async fn staged_progress_loop(
     downloaded_bytes: Arc<AtomicU64>,
     total_bytes: u64,
+    package: PackageWriter,
+    checkpoint: CheckpointHandle,
     progress_tx: UnboundedSender<AppDownloadProgress>,
     cancellation_token: CancellationToken,
 ) -> Result<()> {
     let started_at = Instant::now();
+    // Bytes written by an earlier attempt do not count towards the speed
+    let resumed_bytes = downloaded_bytes.load(Ordering::Relaxed);
     let mut speed_tracker = TransferSpeedTracker::new(SPEED_SAMPLE_WINDOW);
+    let mut saved_at = Instant::now();
     loop {
         tokio::select! {
             _ = cancellation_token.cancelled() => break,
             _ = tokio_time::sleep(Duration::from_millis(STREAM_PROGRESS_INTERVAL_MILLIS as u64)) => {
                 let bytes = downloaded_bytes.load(Ordering::Relaxed);
-                let speed = speed_tracker.record(bytes, started_at.elapsed().as_millis());
+                let speed = speed_tracker
+                    .record(bytes.saturating_sub(resumed_bytes), started_at.elapsed().as_millis());
+                if saved_at.elapsed() >= STAGED_CHECKPOINT_INTERVAL {
+                    package.save(&checkpoint).await;
+                    saved_at = Instant::now();
+                }
                 let _ = progress_tx.send(AppDownloadProgress::Transfer(TransferStats {
                     bytes,
                     total_bytes: Some(total_bytes),
                     speed,
+                    ..Default::default()
                 }));
                 if bytes >= total_bytes {
                     break;
@@ -844,11 +983,13 @@ async fn staged_progress_loop(
     }
 
     let bytes = downloaded_bytes.load(Ordering::Relaxed);
-    let speed = speed_tracker.record(bytes, started_at.elapsed().as_millis());
+    let speed =
+        speed_tracker.record(bytes.saturating_sub(resumed_bytes), started_at.elapsed().as_millis());
     let _ = progress_tx.send(AppDownloadProgress::Transfer(TransferStats {
         bytes,
         total_bytes: Some(total_bytes),
         speed,
+        ..Default::default()
     }));
     Ok(())
 }
@@ -870,6 +1011,7 @@ fn validate_content_range(
 async fn stream_package_to_pipe(
     response: reqwest::Response,
     mut writer: DuplexStream,
+    mut throttle: Throttle,
     progress_tx: UnboundedSender<AppDownloadProgress>,
     total_bytes: u64,
     cancellation_token: CancellationToken,
@@ -924,6 +1066,13 @@ async fn stream_package_to_pipe(
             }
         }
         downloaded_bytes += chunk.len() as u64;
+        tokio::select! {
+            _ = cancellation_token.cancelled() => {
+                info!(downloaded_bytes, total_bytes, "Cancelled while throttling YARC stream");
+                bail!("Operation cancelled")
+            },
+            _ = throttle.consume(chunk.len() as u64) => {}
+        }
 
         let elapsed_millis = started_at.elapsed().as_millis();
         if elapsed_millis.saturating_sub(last_emit) >= STREAM_PROGRESS_INTERVAL_MILLIS {
@@ -932,6 +1081,7 @@ async fn stream_package_to_pipe(
                 bytes: downloaded_bytes,
                 total_bytes: Some(total_bytes),
                 speed,
+                ..Default::default()
             }));
             last_emit = elapsed_millis;
         }
@@ -942,6 +1092,7 @@ async fn stream_package_to_pipe(
         bytes: downloaded_bytes,
         total_bytes: Some(total_bytes),
         speed: final_speed,
+        ..Default::default()
     }));
     debug!(downloaded_bytes, total_bytes, "Finished streaming YARC package");
     writer.shutdown().await.context("Failed to finalize YARC package stream")?;
@@ -1037,7 +1188,13 @@ async fn remove_existing_path(path: &Path) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path},
+    };
+
     use super::*;
+    use crate::downloader::resume::DownloadCheckpoint;
 
     fn sample_release() -> AppRelease {
         AppRelease {
@@ -1086,4 +1243,60 @@ mod tests {
             assert_eq!(pair[0].end + 1, pair[1].start);
         }
     }
+
+    #[test]
+    fn staged_ranges_continue_after_written_bytes() {
+        let range = StagedRange { index: 1, start: 10, end: 19 };
+        let remaining = range.remaining(4).unwrap();
+
+        assert_eq!((remaining.index, remaining.start, remaining.end), (1, 14, 19));
+        assert_eq!(remaining.header_value(), "bytes=14-19");
+        assert!(range.remaining(10).is_none());
+    }
+
+    #[tokio::test]
+    async fn staged_download_resumes_written_package() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/blob"))
+            .and(header("range", "bytes=4-9"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 4-9/10")
+                    .set_body_bytes(b"456789".to_vec()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut checkpoint = DownloadCheckpoint::next_attempt(None, "Game", "com.example");
+        checkpoint.package = Some(PackageProgress {
+            blob_id: "blob".to_string(),
+            total_bytes: 10,
+            written: vec![4],
+        });
+        let checkpoint = CheckpointHandle::new(dir.path().to_path_buf(), checkpoint);
+        let package_path = checkpoint.package_path().await;
+        fs::create_dir_all(package_path.parent().unwrap()).await.unwrap();
+        fs::write(&package_path, b"0123\0\0\0\0\0\0").await.unwrap();
+
+        let (progress_tx, _progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        download_package_staged(
+            &reqwest::Client::new(),
+            &format!("{}/blob", server.uri()),
+            &package_path,
+            "blob",
+            10,
+            &checkpoint,
+            BandwidthLimit::new(String::new()).throttle(),
+            progress_tx,
+            CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fs::read(&package_path).await.unwrap(), b"0123456789");
+        assert_eq!(checkpoint.snapshot().await.package.unwrap().written, [10]);
+    }
 }
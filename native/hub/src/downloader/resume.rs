@@ -0,0 +1,283 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, sync::Mutex};
+use tracing::{debug, instrument, warn};
+
+/// Directory under the downloads location holding checkpoints of unfinished downloads
+pub(crate) const RESUME_STATE_DIR: &str = ".yaas_resume";
+
+/// Checkpoint of an unfinished download.
+///
+/// Written before the transfer starts and removed once it completes, so a checkpoint left
+/// behind means the download was interrupted (failure, cancellation or app exit). It is updated
+/// while the download runs: completed files are not transferred again, and a package of a staged
+/// download continues from the bytes already written. Streamed downloads extract while they
+/// download and start their package over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct DownloadCheckpoint {
+    pub app_full_name: String,
+    pub true_package_name: String,
+    /// Milliseconds since Unix epoch
+    pub started_at: u64,
+    /// Milliseconds since Unix epoch
+    pub updated_at: u64,
+    /// Number of times the download was started
+    pub attempts: u32,
+    /// Destination relative to the downloads location, `None` for `app_full_name` directly in it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_dir: Option<PathBuf>,
+    /// Files of the release the download is limited to, `None` for all of them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<String>>,
+    /// Files of the release transferred completely, relative to the download directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub completed_files: Vec<String>,
+    /// Package of a staged download written so far
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<PackageProgress>,
+}
+
+/// Part of a package written by a staged download, kept next to the checkpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PackageProgress {
+    /// Blob the package is downloaded from, a different one starts over
+    pub blob_id: String,
+    pub total_bytes: u64,
+    /// Bytes written from the start of each range the package is downloaded in
+    pub written: Vec<u64>,
+}
+
+impl DownloadCheckpoint {
+    /// Creates a checkpoint for a new download attempt, carrying over the previous one if any.
+    pub(crate) fn next_attempt(
+        previous: Option<Self>,
+        app_full_name: &str,
+        true_package_name: &str,
+    ) -> Self {
+        let now = now_millis();
+        match previous {
+            Some(previous) => Self {
+                true_package_name: true_package_name.to_string(),
+                updated_at: now,
+                attempts: previous.attempts.saturating_add(1),
+                ..previous
+            },
+            None => Self {
+                app_full_name: app_full_name.to_string(),
+                true_package_name: true_package_name.to_string(),
+                started_at: now,
+                updated_at: now,
+                attempts: 1,
+                relative_dir: None,
+                files: None,
+                completed_files: Vec::new(),
+                package: None,
+            },
+        }
+    }
+
+    /// Returns the directory the download is written to
+    pub(crate) fn download_dir(&self, downloads_dir: &Path) -> PathBuf {
+        downloads_dir.join(self.relative_dir.as_deref().unwrap_or(Path::new(&self.app_full_name)))
+    }
+}
+
+/// Checkpoint of a running download, saved whenever it makes progress
+#[derive(Debug, Clone)]
+pub(crate) struct CheckpointHandle {
+    downloads_dir: PathBuf,
+    checkpoint: Arc<Mutex<DownloadCheckpoint>>,
+}
+
+impl CheckpointHandle {
+    pub(crate) fn new(downloads_dir: PathBuf, checkpoint: DownloadCheckpoint) -> Self {
+        Self { downloads_dir, checkpoint: Arc::new(Mutex::new(checkpoint)) }
+    }
+
+    pub(crate) async fn snapshot(&self) -> DownloadCheckpoint {
+        self.checkpoint.lock().await.clone()
+    }
+
+    /// Where the package of a staged download is written, kept until the download completes
+    pub(crate) async fn package_path(&self) -> PathBuf {
+        package_path(&self.downloads_dir, &self.checkpoint.lock().await.app_full_name)
+    }
+
+    /// Records a file that was transferred completely
+    pub(crate) async fn complete_file(&self, path: String) -> Result<()> {
+        let mut checkpoint = self.checkpoint.lock().await;
+        if checkpoint.completed_files.contains(&path) {
+            return Ok(());
+        }
+        checkpoint.completed_files.push(path);
+        self.save(&mut checkpoint).await
+    }
+
+    /// Records how much of the package is written, `None` once it is no longer needed
+    pub(crate) async fn set_package(&self, package: Option<PackageProgress>) -> Result<()> {
+        let mut checkpoint = self.checkpoint.lock().await;
+        if checkpoint.package == package {
+            return Ok(());
+        }
+        checkpoint.package = package;
+        self.save(&mut checkpoint).await
+    }
+
+    async fn save(&self, checkpoint: &mut DownloadCheckpoint) -> Result<()> {
+        checkpoint.updated_at = now_millis();
+        save_checkpoint(&self.downloads_dir, checkpoint).await
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+fn checkpoint_path(downloads_dir: &Path, app_full_name: &str) -> PathBuf {
+    downloads_dir
+        .join(RESUME_STATE_DIR)
+        .join(format!("{}.json", sanitize_filename::sanitize(app_full_name)))
+}
+
+fn package_path(downloads_dir: &Path, app_full_name: &str) -> PathBuf {
+    checkpoint_path(downloads_dir, app_full_name).with_extension("yarc")
+}
+
+#[instrument(level = "debug", skip(checkpoint), fields(app = %checkpoint.app_full_name), err)]
+pub(crate) async fn save_checkpoint(
+    downloads_dir: &Path,
+    checkpoint: &DownloadCheckpoint,
+) -> Result<()> {
+    let path = checkpoint_path(downloads_dir, &checkpoint.app_full_name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let json =
+        serde_json::to_string_pretty(checkpoint).context("Failed to serialize checkpoint")?;
+    fs::write(&path, json).await.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[instrument(level = "debug", err)]
+pub(crate) async fn load_checkpoint(
+    downloads_dir: &Path,
+    app_full_name: &str,
+) -> Result<Option<DownloadCheckpoint>> {
+    let path = checkpoint_path(downloads_dir, app_full_name);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let checkpoint = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(checkpoint))
+}
+
+/// Removes a checkpoint along with the package it kept
+#[instrument(level = "debug", err)]
+pub(crate) async fn remove_checkpoint(downloads_dir: &Path, app_full_name: &str) -> Result<()> {
+    for path in
+        [package_path(downloads_dir, app_full_name), checkpoint_path(downloads_dir, app_full_name)]
+    {
+        if path.exists() {
+            fs::remove_file(&path)
+                .await
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            debug!(path = %path.display(), "Removed download checkpoint file");
+        }
+    }
+    Ok(())
+}
+
+/// Lists checkpoints of all unfinished downloads. Unreadable checkpoints are skipped.
+#[instrument(level = "debug", err)]
+pub(crate) async fn list_checkpoints(downloads_dir: &Path) -> Result<Vec<DownloadCheckpoint>> {
+    let dir = downloads_dir.join(RESUME_STATE_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut checkpoints = Vec::new();
+    let mut rd =
+        fs::read_dir(&dir).await.with_context(|| format!("Failed to read {}", dir.display()))?;
+    while let Some(entry) = rd.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let parsed = fs::read_to_string(&path)
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|content| Ok(serde_json::from_str::<DownloadCheckpoint>(&content)?));
+        match parsed {
+            Ok(checkpoint) => checkpoints.push(checkpoint),
+            Err(e) => {
+                warn!(path = %path.display(), error = %format!("{e:#}"), "Skipping unreadable checkpoint")
+            }
+        }
+    }
+    checkpoints.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+    Ok(checkpoints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn checkpoint_round_trip_and_removal() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint =
+            DownloadCheckpoint::next_attempt(None, "Game v1+1 (com.example)", "com.example");
+        save_checkpoint(dir.path(), &checkpoint).await.unwrap();
+
+        let loaded = load_checkpoint(dir.path(), "Game v1+1 (com.example)").await.unwrap();
+        assert_eq!(loaded.as_ref(), Some(&checkpoint));
+        assert_eq!(list_checkpoints(dir.path()).await.unwrap(), vec![checkpoint]);
+
+        remove_checkpoint(dir.path(), "Game v1+1 (com.example)").await.unwrap();
+        assert!(load_checkpoint(dir.path(), "Game v1+1 (com.example)").await.unwrap().is_none());
+        assert!(list_checkpoints(dir.path()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn handle_saves_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint = DownloadCheckpoint::next_attempt(None, "Game", "com.example");
+        let handle = CheckpointHandle::new(dir.path().to_path_buf(), checkpoint);
+
+        handle.complete_file("game.apk".to_string()).await.unwrap();
+        handle.complete_file("game.apk".to_string()).await.unwrap();
+        let package =
+            PackageProgress { blob_id: "blob".to_string(), total_bytes: 10, written: vec![4, 0] };
+        handle.set_package(Some(package.clone())).await.unwrap();
+
+        let saved = load_checkpoint(dir.path(), "Game").await.unwrap().unwrap();
+        assert_eq!(saved.completed_files, ["game.apk"]);
+        assert_eq!(saved.package, Some(package));
+
+        let package_path = handle.package_path().await;
+        fs::write(&package_path, [0u8; 10]).await.unwrap();
+        remove_checkpoint(dir.path(), "Game").await.unwrap();
+        assert!(!package_path.exists());
+        assert!(load_checkpoint(dir.path(), "Game").await.unwrap().is_none());
+    }
+
+    #[test]
+    fn next_attempt_keeps_start_time() {
+        let first = DownloadCheckpoint::next_attempt(None, "Game", "com.example");
+        let second = DownloadCheckpoint::next_attempt(Some(first.clone()), "Game", "com.example");
+
+        assert_eq!(second.started_at, first.started_at);
+        assert_eq!(second.attempts, 2);
+    }
+}
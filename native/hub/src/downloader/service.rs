@@ -1,28 +1,45 @@
 use std::{
+    collections::HashMap,
     error::Error,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result, ensure};
 use rinf::{DartSignal, RustSignal};
-use tokio::sync::{Mutex, RwLock, mpsc::UnboundedSender};
+use tokio::sync::{
+    Mutex, RwLock,
+    mpsc::{self, UnboundedSender},
+    watch,
+};
 use tokio_stream::{StreamExt, wrappers::WatchStream};
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, debug, error, info, info_span, instrument, warn};
 
 use crate::{
     adb::PackageName,
+    casting::CastingManager,
     downloader::{
-        AppDownloadProgress, TransferStats, cloud_api, config::DownloaderConfig, download_metadata,
+        AppDownloadProgress, BandwidthLimit, DownloadVerification, SensitiveUrl, TransferStats,
+        bandwidth::effective_limit,
+        catalog_delta::CatalogPublisher,
+        cloud_api,
+        config::DownloaderConfig,
+        dedupe, donation, download_metadata,
+        layout::DownloadLayout,
+        mirrors::{MirrorPolicy, SlowTransferDetector},
         repo,
+        resume::{self, CheckpointHandle, DownloadCheckpoint},
+        tls,
     },
+    metadata_store::MetadataStore,
     models::{
         CloudApp, DownloadMode, Settings,
         signals::{
             cloud_apps::{
                 details::{AppDetailsResponse, GetAppDetailsRequest},
+                files::{ListReleaseFilesRequest, ReleaseFile, ReleaseFilesResponse},
                 list::{CloudAppsChangedEvent, LoadCloudAppsRequest},
                 reviews::{AppReviewsResponse, GetAppReviewsRequest},
             },
@@ -42,20 +59,33 @@ pub(crate) struct Downloader {
     root_dir: String,
     list_path: String,
     cloud_apps: Arc<Mutex<Vec<CloudApp>>>,
+    /// Notified whenever a new app list is loaded
+    catalog_changed_tx: watch::Sender<()>,
     donation_blacklist: Arc<Mutex<Vec<String>>>,
     storage: RwLock<repo::RepoStorage>,
     download_dir: RwLock<PathBuf>,
+    download_layout: RwLock<DownloadLayout>,
     current_load_token: RwLock<CancellationToken>,
     write_legacy_release_json: RwLock<bool>,
+    /// Whether finished downloads are deduplicated against earlier ones
+    dedupe_downloads: RwLock<bool>,
     download_mode: RwLock<DownloadMode>,
+    mirror_policy: RwLock<MirrorPolicy>,
+    bandwidth_limit: BandwidthLimit,
     cancel_token: CancellationToken,
     http_client: reqwest::Client,
+    /// Client for requests to the source itself, see `source_http_client`
+    source_http_client: RwLock<reqwest::Client>,
+    /// Pinned certificates written out for rclone
+    ca_cert_path: Option<PathBuf>,
     repo: Arc<dyn repo::Repo>,
     installation_id: String,
+    metadata_store: Arc<MetadataStore>,
+    catalog_publisher: Arc<CatalogPublisher>,
 }
 
 impl Downloader {
-    #[instrument(level = "debug", skip(settings_stream))]
+    #[instrument(level = "debug", skip(settings_stream, metadata_store))]
     pub(crate) async fn new(
         config: Arc<DownloaderConfig>,
         cache_dir: PathBuf,
@@ -63,16 +93,21 @@ impl Downloader {
         rclone_config_path: Option<PathBuf>,
         settings_handler: Arc<SettingsHandler>,
         mut settings_stream: WatchStream<Settings>,
+        metadata_store: Arc<MetadataStore>,
     ) -> Result<Arc<Self>> {
         let settings =
             settings_stream.next().await.expect("Settings stream closed on downloader init");
 
         let repo = repo::make_repo_from_config(&config);
 
-        let http_client = reqwest::Client::builder()
-            .user_agent(crate::USER_AGENT)
+        let http_client = tls::http_client_builder(tls::ServerPins::default())?
             .build()
-            .unwrap_or_else(|_| reqwest::Client::new());
+            .context("Failed to build HTTP client")?;
+        let source_client = source_http_client(&config, &settings)?;
+        let ca_cert_path = match config.pinned_certificates.as_deref() {
+            Some(pem) => Some(tls::write_rclone_ca_file(&cache_dir, pem).await?),
+            None => None,
+        };
 
         let repo_capabilities = repo.capabilities();
         let donation_remote_configured = repo_capabilities.supports_donation_upload
@@ -87,15 +122,18 @@ impl Downloader {
             );
         }
 
+        let casting_active = *CastingManager::subscribe_session().borrow();
+        let bandwidth_limit = BandwidthLimit::new(effective_limit(&settings, casting_active));
         let built = repo
             .build_storage(repo::BuildStorageArgs {
                 rclone_path: rclone_path.as_deref(),
                 rclone_config_path: rclone_config_path.as_deref(),
                 root_dir: &config.root_dir,
                 remote_name: &settings.rclone_remote_name,
-                bandwidth_limit: &settings.bandwidth_limit,
+                bandwidth_limit: &bandwidth_limit,
                 remote_name_filter_regex: config.remote_name_filter_regex.clone(),
                 allow_randomize_remote: !config.disable_randomize_remote,
+                ca_cert_path: ca_cert_path.as_deref(),
             })
             .await?;
         // If the repo asked us to persist a remote, update settings
@@ -111,7 +149,7 @@ impl Downloader {
             updated.rclone_remote_name = remote.clone();
             let _ = settings_handler.save_settings(&updated);
         }
-        let storage = built.storage;
+        let storage = with_catalog_url(built.storage, &settings);
 
         let root_dir = config.root_dir.clone();
         let list_path = config.list_path.clone();
@@ -126,16 +164,25 @@ impl Downloader {
             root_dir,
             list_path,
             cloud_apps: Arc::new(Mutex::new(Vec::new())),
+            catalog_publisher: Arc::new(CatalogPublisher::default()),
+            catalog_changed_tx: watch::Sender::new(()),
             donation_blacklist: Arc::new(Mutex::new(Vec::new())),
             storage: RwLock::new(storage),
             download_dir: RwLock::new(settings.downloads_location()),
+            download_layout: RwLock::new(DownloadLayout::new(&settings.download_layout)),
             current_load_token: RwLock::new(cancel_token.child_token()),
             write_legacy_release_json: RwLock::new(settings.write_legacy_release_json),
+            dedupe_downloads: RwLock::new(settings.dedupe_downloads),
             download_mode: RwLock::new(settings.download_mode),
+            mirror_policy: RwLock::new(MirrorPolicy::from_settings(&settings)),
+            bandwidth_limit,
             cancel_token,
             http_client,
+            source_http_client: RwLock::new(source_client),
+            ca_cert_path,
             repo,
             installation_id: settings.installation_id.clone(),
+            metadata_store,
         });
 
         tokio::spawn({
@@ -145,6 +192,30 @@ impl Downloader {
             }
         });
 
+        // Yield bandwidth to casting sessions as configured
+        tokio::spawn({
+            let handle = handle.clone();
+            let settings_rx = settings_handler.subscribe();
+            async move {
+                let mut casting_rx = CastingManager::subscribe_session();
+                loop {
+                    tokio::select! {
+                        _ = handle.cancel_token.cancelled() => return,
+                        result = casting_rx.changed() => {
+                            if result.is_err() {
+                                return;
+                            }
+                            let casting_active = *casting_rx.borrow_and_update();
+                            let limit = effective_limit(&settings_rx.borrow(), casting_active);
+                            debug!(casting_active, limit, "Casting session changed, updating download limit");
+                            handle.bandwidth_limit.set(&limit);
+                        }
+                    }
+                }
+            }
+            .instrument(info_span!("task_follow_casting_session"))
+        });
+
         tokio::spawn({
             let handle = handle.clone();
             async move {
@@ -162,6 +233,10 @@ impl Downloader {
                             debug!("Downloader received settings update");
                             debug!(?settings, "New settings");
 
+                            // Running transfers pick up the new limit without recreating storage
+                            let casting_active = *CastingManager::subscribe_session().borrow();
+                            handle.bandwidth_limit.set(&effective_limit(&settings, casting_active));
+
                             // Rebuild storage on settings changes, do not randomize the remote
                             let built = handle
                                 .repo
@@ -170,12 +245,20 @@ impl Downloader {
                                     rclone_config_path: handle.rclone_config_path.as_deref(),
                                     root_dir: &handle.root_dir,
                                     remote_name: &settings.rclone_remote_name,
-                                    bandwidth_limit: &settings.bandwidth_limit,
+                                    bandwidth_limit: &handle.bandwidth_limit,
                                     remote_name_filter_regex: handle.config.remote_name_filter_regex.clone(),
                                     allow_randomize_remote: false,
+                                    ca_cert_path: handle.ca_cert_path.as_deref(),
                                 })
                                 .await;
 
+                            match source_http_client(&handle.config, &settings) {
+                                Ok(client) => *handle.source_http_client.write().await = client,
+                                Err(e) => {
+                                    error!(error = e.as_ref() as &dyn Error, "Failed to rebuild source HTTP client on settings change");
+                                }
+                            }
+
                             let new_storage = match built {
                                 Ok(res) => {
                                     if let Some(remote) = res.persist_remote
@@ -184,7 +267,7 @@ impl Downloader {
                                             updated.rclone_remote_name = remote.clone();
                                             let _ = settings_handler.save_settings(&updated);
                                         }
-                                    res.storage
+                                    with_catalog_url(res.storage, &settings)
                                 }
                                 Err(e) => {
                                     error!(error = e.as_ref() as &dyn Error, "Failed to rebuild storage on settings change");
@@ -228,13 +311,19 @@ impl Downloader {
                                 debug!(new_dir = %new_download_dir.display(), "Download directory changed");
                                 *download_dir = new_download_dir;
                             }
+                            *handle.download_layout.write().await =
+                                DownloadLayout::new(&settings.download_layout);
 
                             // Update legacy release.json toggle
                             let mut legacy_flag = handle.write_legacy_release_json.write().await;
                             *legacy_flag = settings.write_legacy_release_json;
+                            *handle.dedupe_downloads.write().await = settings.dedupe_downloads;
 
                             let mut download_mode = handle.download_mode.write().await;
                             *download_mode = settings.download_mode;
+
+                            *handle.mirror_policy.write().await =
+                                MirrorPolicy::from_settings(&settings);
                         }
                     }
                 }
@@ -242,6 +331,35 @@ impl Downloader {
         }.instrument(info_span!("task_handle_settings_updates")),
         );
 
+        // Resend the cached app list when display name overrides change
+        tokio::spawn(
+            {
+                let handle = handle.clone();
+                let mut metadata_stream =
+                    WatchStream::from_changes(handle.metadata_store.subscribe());
+                async move {
+                    loop {
+                        tokio::select! {
+                            _ = handle.cancel_token.cancelled() => return,
+                            maybe_metadata = metadata_stream.next() => {
+                                if maybe_metadata.is_none() {
+                                    return;
+                                }
+                                let mut apps = handle.cloud_apps.lock().await.clone();
+                                if apps.is_empty() {
+                                    continue;
+                                }
+                                debug!("App metadata changed, sending renamed apps");
+                                handle.metadata_store.current().apply_to_cloud_apps(&mut apps);
+                                handle.catalog_publisher.send_changes(apps, None);
+                            }
+                        }
+                    }
+                }
+            }
+            .instrument(info_span!("task_handle_metadata_updates")),
+        );
+
         // On init, send rclone remotes list
         tokio::spawn({
             let handle = handle.clone();
@@ -278,24 +396,74 @@ impl Downloader {
         Ok(handle)
     }
 
+    /// Returns a snapshot of the loaded app list
+    pub(crate) async fn cloud_apps(&self) -> Vec<CloudApp> {
+        self.cloud_apps.lock().await.clone()
+    }
+
+    /// Loads the app list unless it is cached already, for callers that do not go through Dart
+    pub(crate) async fn ensure_app_list(&self) -> Result<Vec<CloudApp>> {
+        self.load_app_list(false, self.cancel_token.child_token()).await;
+        let apps = self.cloud_apps().await;
+        ensure!(!apps.is_empty(), "Failed to load the app list, see logs for details");
+        Ok(apps)
+    }
+
+    /// Config ID and the remote or mirror files are currently downloaded from, for display
+    pub(crate) async fn source_summary(&self) -> (String, String) {
+        (self.config.id.clone(), self.storage.read().await.source_name())
+    }
+
+    /// Version details of the rclone binary, None for sources not using rclone
+    pub(crate) async fn rclone_version(&self) -> Option<Result<String>> {
+        let storage = self.storage.read().await.clone();
+        match storage {
+            repo::RepoStorage::Ffa(storage) => Some(storage.rclone_version().await),
+            repo::RepoStorage::NewRepo(_) => None,
+        }
+    }
+
+    /// Create a receiver notified when a new app list is loaded
+    pub(crate) fn subscribe_catalog_changes(&self) -> watch::Receiver<()> {
+        self.catalog_changed_tx.subscribe()
+    }
+
     /// Returns the cached CloudApp (if any) that matches the given full name
     #[instrument(level = "debug", skip(self))]
-    async fn get_app_by_full_name(&self, full_name: &str) -> Option<CloudApp> {
+    pub(crate) async fn get_app_by_full_name(&self, full_name: &str) -> Option<CloudApp> {
         let cache = self.cloud_apps.lock().await;
         cache.iter().find(|a| a.full_name == full_name).cloned()
     }
 
-    /// Upload a prepared archive used for app donation.
+    /// Upload a prepared archive used for app donation, returning where it went.
     ///
-    /// This uses optional `donation_remote_name` and `donation_remote_path` from DownloaderConfig.
-    /// If either is missing or empty, the call fails with a configuration error.
+    /// Archives go to `donation_upload_url` from DownloaderConfig with an HTTP PUT if it is set,
+    /// otherwise to the repository's donation remote. `md5_hex` is the archive's checksum.
     #[instrument(skip(self, stats_tx, cancellation_token))]
     pub(crate) async fn upload_donation_archive(
         &self,
         archive_path: &Path,
+        md5_hex: &str,
         stats_tx: Option<UnboundedSender<TransferStats>>,
         cancellation_token: CancellationToken,
-    ) -> Result<()> {
+    ) -> Result<String> {
+        if let Some(base_url) = self.config.donation_upload_url() {
+            let file_name = archive_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .context("Donation archive has no file name")?;
+            let url = donation::upload_url(base_url, file_name)?;
+            donation::put_file(
+                &self.http_client,
+                &url,
+                archive_path,
+                md5_hex,
+                stats_tx,
+                cancellation_token,
+            )
+            .await?;
+            return Ok(SensitiveUrl::new(url.as_str()).to_string());
+        }
         let storage = self.storage.read().await.clone();
         self.repo
             .upload_donation_archive(
@@ -314,6 +482,7 @@ impl Downloader {
         let get_rclone_remotes_receiver = GetRcloneRemotesRequest::get_dart_signal_receiver();
         let get_app_details_receiver = GetAppDetailsRequest::get_dart_signal_receiver();
         let get_app_reviews_receiver = GetAppReviewsRequest::get_dart_signal_receiver();
+        let list_release_files_receiver = ListReleaseFilesRequest::get_dart_signal_receiver();
         loop {
             tokio::select! {
                 _ = self.cancel_token.cancelled() => {
@@ -429,6 +598,23 @@ impl Downloader {
                         return;
                     }
                 }
+                request = list_release_files_receiver.recv() => {
+                    if let Some(request) = request {
+                        let app_full_name = request.message.app_full_name;
+                        debug!(%app_full_name, "Received ListReleaseFilesRequest");
+                        match self.list_release_files(&app_full_name).await {
+                            Ok(files) => {
+                                ReleaseFilesResponse { app_full_name, files, error: None }.send_signal_to_dart();
+                            }
+                            Err(e) => {
+                                ReleaseFilesResponse { app_full_name, files: Vec::new(), error: Some(format!("Failed to list release files: {:#}", e)) }.send_signal_to_dart();
+                            }
+                        }
+                    } else {
+                        info!("ListReleaseFilesRequest receiver closed, shutting down downloader command loop");
+                        return;
+                    }
+                }
             }
         }
     }
@@ -440,19 +626,28 @@ impl Downloader {
         self.current_load_token.read().await.cancel();
     }
 
+    /// Sends the cached app list to Dart again, nothing is loaded when it is empty
+    pub(crate) async fn resend_app_list(&self) {
+        let mut apps = self.cloud_apps.lock().await.clone();
+        if apps.is_empty() {
+            return;
+        }
+        let donation_blacklist = self.donation_blacklist.lock().await.clone();
+        self.metadata_store.current().apply_to_cloud_apps(&mut apps);
+        self.catalog_publisher.send_full(apps, Some(donation_blacklist));
+    }
+
     #[instrument(level = "debug", skip(self, cancellation_token))]
     async fn load_app_list(&self, force_refresh: bool, cancellation_token: CancellationToken) {
-        fn send_event(
-            is_loading: bool,
-            apps: Option<Vec<CloudApp>>,
-            donation_blacklist: Option<Vec<String>>,
-            error: Option<String>,
-        ) {
-            if let Some(ref a) = apps {
-                debug!(count = a.len(), ?error, "Sending app list to UI");
+        fn send_error(publisher: &CatalogPublisher, error: String) {
+            publisher.reset();
+            CloudAppsChangedEvent {
+                is_loading: false,
+                apps: None,
+                donation_blacklist: None,
+                error: Some(error),
             }
-            CloudAppsChangedEvent { is_loading, apps, donation_blacklist, error }
-                .send_signal_to_dart();
+            .send_signal_to_dart();
         }
 
         // Short lock to decide refresh vs cached send
@@ -470,7 +665,9 @@ impl Downloader {
                 count = cached_apps.as_ref().map(|v| v.len()).unwrap_or(0),
                 "Using cached app list"
             );
-            send_event(false, cached_apps, cached_blacklist, None);
+            let mut apps = cached_apps.unwrap_or_default();
+            self.metadata_store.current().apply_to_cloud_apps(&mut apps);
+            self.catalog_publisher.send_full(apps, cached_blacklist);
             return;
         }
 
@@ -480,12 +677,18 @@ impl Downloader {
         }
 
         info!("Loading app list from remote");
-        send_event(true, None, None, None);
+        CloudAppsChangedEvent {
+            is_loading: true,
+            apps: None,
+            donation_blacklist: None,
+            error: None,
+        }
+        .send_signal_to_dart();
 
         let storage = self.storage.read().await.clone();
         let list_path = self.list_path.clone();
         let cache_dir = self.cache_dir.clone();
-        let client = self.http_client.clone();
+        let client = self.source_http_client.read().await.clone();
 
         let timeout = Duration::from_secs(30);
         let repo = self.repo.clone();
@@ -500,18 +703,29 @@ impl Downloader {
         match tokio::time::timeout(timeout, fut).await {
             Ok(Ok(result)) => {
                 debug!(len = result.apps.len(), "Loaded app list successfully");
+                let mut result = result;
 
-                // Cache and send without popularity
+                // Cache and send with the popularity known so far, so a refresh only sends changes
                 {
                     // TODO: Should we hold the lock for the whole duration of the load?
                     let mut cache = self.cloud_apps.lock().await;
+                    let previous: HashMap<&str, &CloudApp> =
+                        cache.iter().map(|app| (app.full_name.as_str(), app)).collect();
+                    for app in &mut result.apps {
+                        if let Some(previous) = previous.get(app.full_name.as_str()) {
+                            app.popularity.clone_from(&previous.popularity);
+                        }
+                    }
                     *cache = result.apps.clone();
                 }
+                self.catalog_changed_tx.send_replace(());
                 {
                     let mut blacklist_cache = self.donation_blacklist.lock().await;
                     *blacklist_cache = result.donation_blacklist.clone();
                 }
-                send_event(false, Some(result.apps.clone()), Some(result.donation_blacklist), None);
+                let mut apps = result.apps.clone();
+                self.metadata_store.current().apply_to_cloud_apps(&mut apps);
+                self.catalog_publisher.send_changes(apps, Some(result.donation_blacklist));
 
                 // Load popularity data in background and send updated list if successful
                 if !result.apps.is_empty() {
@@ -519,6 +733,8 @@ impl Downloader {
                     let donation_blacklist_cache = Arc::clone(&self.donation_blacklist);
                     let client = client.clone();
                     let cancel = cancellation_token.clone();
+                    let metadata_store = self.metadata_store.clone();
+                    let catalog_publisher = self.catalog_publisher.clone();
                     tokio::spawn(
                         async move {
                             // BUG: If this is cancelled by another non-force_refresh load, we get left with no popularity data, since the second load will use the cache.
@@ -545,7 +761,8 @@ impl Downloader {
                                                 let cache = donation_blacklist_cache.lock().await;
                                                 cache.clone()
                                             };
-                                            send_event(false, Some(apps), Some(blacklist), None);
+                                            metadata_store.current().apply_to_cloud_apps(&mut apps);
+                                            catalog_publisher.send_changes(apps, Some(blacklist));
                                         }
                                         Err(e) => {
                                             warn!(
@@ -573,11 +790,11 @@ impl Downloader {
                     return;
                 }
                 error!(error = e.as_ref() as &dyn Error, storage = ?storage, "Failed to load app list");
-                send_event(false, None, None, Some(format!("Failed to load app list: {e:#}")));
+                send_error(&self.catalog_publisher, format!("Failed to load app list: {e:#}"));
             }
             Err(_) => {
                 error!(storage = ?storage, "App list load timed out");
-                send_event(false, None, None, Some("Timed out while loading app list".into()));
+                send_error(&self.catalog_publisher, "Timed out while loading app list".into());
             }
         }
     }
@@ -587,26 +804,58 @@ impl Downloader {
         &self,
         app_full_name: String,
         true_package: PackageName,
+        files: Option<Vec<String>>,
         progress_tx: UnboundedSender<AppDownloadProgress>,
         cancellation_token: CancellationToken,
     ) -> Result<String> {
-        let dst_dir = self.download_dir.read().await.join(&app_full_name);
-        info!(app = %app_full_name, dest = %dst_dir.display(), "Starting app download");
+        let downloads_dir = self.download_dir.read().await.clone();
+        let cached = self.get_app_by_full_name(&app_full_name).await;
         let _ = progress_tx.send(AppDownloadProgress::Status("Preparing download...".to_string()));
 
-        let storage = self.storage.read().await.clone();
-        let download_mode = *self.download_mode.read().await;
+        let previous_checkpoint =
+            resume::load_checkpoint(&downloads_dir, &app_full_name).await.unwrap_or_else(|e| {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to read download checkpoint");
+                None
+            });
+        // Interrupted downloads continue where they were started, even if the layout changed since
+        let relative_dir = match &previous_checkpoint {
+            Some(previous) => previous.download_dir(Path::new("")),
+            None => self.download_layout.read().await.relative_dir(
+                &app_full_name,
+                cached.as_ref().map(|app| app.app_name.as_str()),
+                true_package.as_str(),
+            ),
+        };
+        let dst_dir = downloads_dir.join(&relative_dir);
+        info!(app = %app_full_name, dest = %dst_dir.display(), "Starting app download");
+        if let Some(previous) = &previous_checkpoint {
+            info!(
+                app = %app_full_name,
+                attempts = previous.attempts,
+                "Resuming interrupted download"
+            );
+            let _ =
+                progress_tx.send(AppDownloadProgress::Status("Resuming download...".to_string()));
+        }
+        let mut checkpoint = DownloadCheckpoint::next_attempt(
+            previous_checkpoint,
+            &app_full_name,
+            true_package.as_str(),
+        );
+        checkpoint.relative_dir = Some(relative_dir);
+        checkpoint.files = files;
+        if let Err(e) = resume::save_checkpoint(&downloads_dir, &checkpoint).await {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to save download checkpoint");
+        }
+        let checkpoint = CheckpointHandle::new(downloads_dir.clone(), checkpoint);
+
         let download_result = match self
-            .repo
-            .download_app(
-                storage,
+            .download_from_mirrors(
                 &app_full_name,
                 &dst_dir,
-                &self.cache_dir,
-                &self.http_client,
-                download_mode,
-                progress_tx.clone(),
-                cancellation_token.clone(),
+                &checkpoint,
+                &progress_tx,
+                &cancellation_token,
             )
             .await
         {
@@ -629,7 +878,9 @@ impl Downloader {
             }
         };
 
-        if !download_result.skipped {
+        if download_result.skipped {
+            let _ = progress_tx.send(AppDownloadProgress::Skipped);
+        } else {
             let installation_id = self.installation_id.clone();
             tokio::spawn({
                 let http_client = self.http_client.clone();
@@ -649,7 +900,6 @@ impl Downloader {
         }
 
         // Prepare metadata inputs without holding long locks
-        let cached = self.get_app_by_full_name(&app_full_name).await;
         let write_legacy = *self.write_legacy_release_json.read().await;
         let _ = progress_tx.send(AppDownloadProgress::Status("Writing metadata...".to_string()));
 
@@ -668,9 +918,216 @@ impl Downloader {
             );
         }
 
+        if let Err(e) = resume::remove_checkpoint(&downloads_dir, &app_full_name).await {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to remove download checkpoint");
+        }
+
+        if *self.dedupe_downloads.read().await {
+            let _ =
+                progress_tx.send(AppDownloadProgress::Status("Deduplicating files...".to_string()));
+            if let Err(e) =
+                dedupe::dedupe_releases(&downloads_dir, std::slice::from_ref(&dst_dir)).await
+            {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to deduplicate download");
+            }
+        }
+
         // Notify UI that downloads may have changed
         DownloadsChanged {}.send_signal_to_dart();
 
         Ok(dst_dir.display().to_string())
     }
+
+    /// Downloads the files `checkpoint` is limited to from the selected storage, moving on to the
+    /// configured mirrors in order when a download fails or stays below the minimum speed.
+    /// Returns the error of the last mirror if all of them fail.
+    async fn download_from_mirrors(
+        &self,
+        app_full_name: &str,
+        dst_dir: &Path,
+        checkpoint: &CheckpointHandle,
+        progress_tx: &UnboundedSender<AppDownloadProgress>,
+        cancellation_token: &CancellationToken,
+    ) -> Result<repo::RepoDownloadResult> {
+        let DownloadCheckpoint { files, attempts: attempt, .. } = checkpoint.snapshot().await;
+        let policy = self.mirror_policy.read().await.clone();
+        let candidates = policy.candidates(self.storage.read().await.clone());
+        let download_mode = *self.download_mode.read().await;
+        let last_index = candidates.len() - 1;
+
+        let mut last_error = None;
+        for (index, storage) in candidates.into_iter().enumerate() {
+            let source = storage.source_name();
+            if index > 0 {
+                info!(app = %app_full_name, mirror = %source, "Switching to next mirror");
+                let _ = progress_tx
+                    .send(AppDownloadProgress::Status(format!("Switching to mirror {source}...")));
+            }
+            let _ =
+                progress_tx.send(AppDownloadProgress::Started { source: source.clone(), attempt });
+
+            // The last mirror is kept however slow it is, there is nothing left to switch to
+            let detector =
+                if index < last_index { policy.slow_detector(Instant::now()) } else { None };
+            let attempt_token = cancellation_token.child_token();
+            let (attempt_tx, attempt_rx) = mpsc::unbounded_channel();
+            let client = self.source_http_client.read().await.clone();
+            let watcher = tokio::spawn(forward_progress(
+                attempt_rx,
+                progress_tx.clone(),
+                detector,
+                attempt_token.clone(),
+            ));
+
+            let result = self
+                .repo
+                .download_app(
+                    storage,
+                    app_full_name,
+                    dst_dir,
+                    files.as_deref(),
+                    checkpoint,
+                    &self.cache_dir,
+                    &client,
+                    download_mode,
+                    attempt_tx,
+                    attempt_token,
+                )
+                .await;
+            let too_slow = watcher.await.unwrap_or(false);
+            match result {
+                Ok(result) => return Ok(result),
+                Err(error) if cancellation_token.is_cancelled() => return Err(error),
+                Err(error) => {
+                    if too_slow {
+                        warn!(app = %app_full_name, mirror = %source, "Download too slow on mirror");
+                    } else if index < last_index {
+                        warn!(
+                            app = %app_full_name,
+                            mirror = %source,
+                            error = error.as_ref() as &dyn Error,
+                            "Download failed on mirror"
+                        );
+                    }
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.expect("at least the selected storage is tried"))
+    }
+
+    /// Returns the directory `app_full_name` is downloaded to under the current layout, or its
+    /// directory directly in the downloads location if it was downloaded there before
+    async fn release_dir(&self, app_full_name: &str) -> PathBuf {
+        let downloads_dir = self.download_dir.read().await.clone();
+        let app = self.get_app_by_full_name(app_full_name).await;
+        let dir = downloads_dir.join(self.download_layout.read().await.relative_dir(
+            app_full_name,
+            app.as_ref().map(|app| app.app_name.as_str()),
+            app.as_ref().map_or("", |app| app.true_package_name.as_str()),
+        ));
+        let flat_dir = downloads_dir.join(app_full_name);
+        if !dir.is_dir() && flat_dir.is_dir() { flat_dir } else { dir }
+    }
+
+    /// Checks the downloaded files of `app_full_name` against the remote, only `files` if the
+    /// download was limited to them.
+    #[instrument(skip(self, cancellation_token), ret, err)]
+    pub(crate) async fn verify_download(
+        &self,
+        app_full_name: &str,
+        files: Option<&[String]>,
+        cancellation_token: CancellationToken,
+    ) -> Result<DownloadVerification> {
+        let dst_dir = self.release_dir(app_full_name).await;
+        let storage = self.storage.read().await.clone();
+        self.repo.verify_download(storage, app_full_name, &dst_dir, files, cancellation_token).await
+    }
+
+    /// Lists the files of a release in the selected storage, marking the ones its local download
+    /// already has
+    #[instrument(skip(self), err)]
+    async fn list_release_files(&self, app_full_name: &str) -> Result<Vec<ReleaseFile>> {
+        let storage = self.storage.read().await.clone();
+        let files = self.repo.list_release_files(storage, app_full_name).await?;
+        let dst_dir = self.release_dir(app_full_name).await;
+        let mut release_files = Vec::with_capacity(files.len());
+        for file in files {
+            let downloaded = tokio::fs::metadata(dst_dir.join(&file.path))
+                .await
+                .is_ok_and(|meta| meta.is_file() && meta.len() == file.size);
+            release_files.push(ReleaseFile {
+                path: file.path,
+                size: file.size,
+                modified: file.modified,
+                downloaded,
+            });
+        }
+        Ok(release_files)
+    }
+}
+
+/// `storage` reading from the catalog URL of the settings, if one is set and fits the storage
+/// Client for the source, pinned like the self-hosted catalog of the settings if one replaces it
+fn source_http_client(config: &DownloaderConfig, settings: &Settings) -> Result<reqwest::Client> {
+    let pins = if settings.catalog_base_url.trim().is_empty() {
+        tls::ServerPins {
+            certificates: config.pinned_certificates.as_deref(),
+            spki: &config.pinned_spki,
+        }
+    } else {
+        settings.catalog_server_pins()
+    };
+    tls::source_http_client(pins)
+}
+
+fn with_catalog_url(storage: repo::RepoStorage, settings: &Settings) -> repo::RepoStorage {
+    let url = settings.catalog_base_url.trim();
+    if url.is_empty() {
+        return storage;
+    }
+    storage.with_mirror(url).unwrap_or_else(|| {
+        warn!(url = %SensitiveUrl::new(url), "Catalog URL does not apply to this source, ignoring");
+        storage
+    })
+}
+
+/// Passes download progress on, cancelling `attempt_token` if `detector` finds the transfer too
+/// slow. Returns whether it did.
+async fn forward_progress(
+    mut attempt_rx: mpsc::UnboundedReceiver<AppDownloadProgress>,
+    progress_tx: UnboundedSender<AppDownloadProgress>,
+    mut detector: Option<SlowTransferDetector>,
+    attempt_token: CancellationToken,
+) -> bool {
+    let mut check = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        let progress = tokio::select! {
+            progress = attempt_rx.recv() => match progress {
+                Some(progress) => progress,
+                None => return false,
+            },
+            _ = check.tick() => {
+                if detector.as_ref().is_some_and(|d| d.is_slow(Instant::now()))
+                    && !attempt_token.is_cancelled()
+                {
+                    attempt_token.cancel();
+                    // Keep draining so the download can wind down
+                    while attempt_rx.recv().await.is_some() {}
+                    return true;
+                }
+                continue;
+            }
+        };
+        if let Some(detector) = &mut detector {
+            match &progress {
+                AppDownloadProgress::Transfer(stats) => {
+                    detector.observe_speed(stats.speed, Instant::now())
+                }
+                AppDownloadProgress::Status(_) => detector.pause(),
+                _ => {}
+            }
+        }
+        let _ = progress_tx.send(progress);
+    }
 }
@@ -9,7 +9,7 @@ use anyhow::{Context, Result, anyhow, ensure};
 use tracing::{debug, info, warn};
 
 use crate::{
-    downloader::{SensitiveUrl, config::DownloaderConfig, http_cache},
+    downloader::{SensitiveUrl, config::DownloaderConfig, http_cache, tls},
     models::{InstalledDownloaderConfig, Settings},
     settings::SettingsHandler,
 };
@@ -296,8 +296,7 @@ async fn cache_config_from_url(
 
     let (cache_dir, cached_cfg_path) = config_download_cache_path(app_dir, cache_key);
 
-    let client = reqwest::Client::builder()
-        .user_agent(crate::USER_AGENT)
+    let client = tls::http_client_builder(tls::ServerPins::default())?
         .build()
         .context("Failed to build HTTP client for downloader config update")?;
 
@@ -621,12 +620,15 @@ mod tests {
                 disable_randomize_remote: false,
                 donation_remote_name: None,
                 donation_remote_path: None,
+                donation_upload_url: None,
                 donation_blacklist_path: None,
                 layout: RepoLayoutKind::Ffa,
                 base_url: None,
                 root_dir: "Quest Games".into(),
                 list_path: "FFA.txt".into(),
                 config_update_url: Some("https://example.com/b.json".into()),
+                pinned_certificates: None,
+                pinned_spki: Vec::new(),
             },
             DownloaderConfig {
                 id: "a".into(),
@@ -640,12 +642,15 @@ mod tests {
                 disable_randomize_remote: false,
                 donation_remote_name: None,
                 donation_remote_path: None,
+                donation_upload_url: None,
                 donation_blacklist_path: None,
                 layout: RepoLayoutKind::Ffa,
                 base_url: None,
                 root_dir: "Quest Games".into(),
                 list_path: "FFA.txt".into(),
                 config_update_url: Some("https://example.com/a.json".into()),
+                pinned_certificates: None,
+                pinned_spki: Vec::new(),
             },
         ];
 
@@ -0,0 +1,151 @@
+//! Startup cleanup of files left in the downloads location by work that was interrupted: apps
+//! pulled from the device for donation and partial files of unfinished rclone transfers.
+
+use std::{
+    error::Error,
+    fs::Metadata,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use tokio::fs;
+use tracing::{Instrument, debug, info, info_span, instrument, warn};
+
+use crate::{
+    downloader::resume::RESUME_STATE_DIR, models::signals::system::Toast, task::DONATE_TMP_DIR,
+    utils::dir_size,
+};
+
+/// Files modified more recently than this may belong to work that is still running
+const MIN_AGE: Duration = Duration::from_secs(60 * 60);
+/// Suffix rclone gives files while they are being transferred
+const PARTIAL_SUFFIX: &str = ".partial";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct SweepSummary {
+    removed: u32,
+    bytes: u64,
+}
+
+/// Sweeps the downloads location once in the background, reporting reclaimed space in a toast
+pub(crate) fn start(downloads_dir: PathBuf) {
+    tokio::spawn(
+        async move {
+            match sweep(&downloads_dir, MIN_AGE, SystemTime::now()).await {
+                Ok(summary) if summary.removed > 0 => {
+                    info!(?summary, "Removed leftover files");
+                    Toast::send(
+                        "Cleaned up leftover files".to_string(),
+                        format!(
+                            "Reclaimed {} from interrupted downloads and donations",
+                            humansize::format_size(summary.bytes, humansize::DECIMAL)
+                        ),
+                        false,
+                        None,
+                    );
+                }
+                Ok(_) => debug!("No leftover files found"),
+                Err(e) => warn!(error = e.as_ref() as &dyn Error, "Failed to sweep leftover files"),
+            }
+        }
+        .instrument(info_span!("task_sweep_leftovers")),
+    );
+}
+
+/// Removes donation leftovers and partial transfer files not modified within `min_age`
+#[instrument(level = "debug", skip(now), err)]
+async fn sweep(downloads_dir: &Path, min_age: Duration, now: SystemTime) -> Result<SweepSummary> {
+    let mut summary = SweepSummary::default();
+
+    let upload_dir = downloads_dir.join(DONATE_TMP_DIR);
+    if let Ok(mut entries) = fs::read_dir(&upload_dir).await {
+        while let Some(entry) = entries.next_entry().await? {
+            let meta = entry.metadata().await?;
+            if is_stale(&meta, min_age, now) {
+                remove(&entry.path(), &meta, &mut summary).await;
+            }
+        }
+    }
+
+    let mut stack = vec![downloads_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let meta = entry.metadata().await?;
+            let name = entry.file_name();
+            if meta.is_dir() {
+                let skip =
+                    dir == downloads_dir && (name == RESUME_STATE_DIR || name == DONATE_TMP_DIR);
+                if !skip {
+                    stack.push(entry.path());
+                }
+            } else if name.to_string_lossy().ends_with(PARTIAL_SUFFIX)
+                && is_stale(&meta, min_age, now)
+            {
+                remove(&entry.path(), &meta, &mut summary).await;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+fn is_stale(meta: &Metadata, min_age: Duration, now: SystemTime) -> bool {
+    meta.modified()
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age >= min_age)
+}
+
+async fn remove(path: &Path, meta: &Metadata, summary: &mut SweepSummary) {
+    let (size, result) = if meta.is_dir() {
+        (dir_size(path).await.unwrap_or(0), fs::remove_dir_all(path).await)
+    } else {
+        (meta.len(), fs::remove_file(path).await)
+    };
+    match result {
+        Ok(()) => {
+            debug!(path = %path.display(), size, "Removed leftover");
+            summary.removed += 1;
+            summary.bytes += size;
+        }
+        Err(e) => {
+            warn!(error = &e as &dyn Error, path = %path.display(), "Failed to remove leftover")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn removes_stale_leftovers_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(DONATE_TMP_DIR).join("com.example.app")).await.unwrap();
+        fs::write(root.join(DONATE_TMP_DIR).join("com.example.app/base.apk"), [0u8; 10])
+            .await
+            .unwrap();
+        fs::create_dir_all(root.join("App v1+1/com.example.app")).await.unwrap();
+        fs::write(root.join("App v1+1/com.example.app/base.apk"), [0u8; 20]).await.unwrap();
+        fs::write(root.join("App v1+1/main.obb.1a2b3c.partial"), [0u8; 5]).await.unwrap();
+        fs::create_dir_all(root.join(RESUME_STATE_DIR)).await.unwrap();
+        fs::write(root.join(RESUME_STATE_DIR).join("x.partial"), [0u8; 1]).await.unwrap();
+
+        let fresh = sweep(root, MIN_AGE, SystemTime::now()).await.unwrap();
+        assert_eq!(fresh, SweepSummary::default());
+
+        let later = SystemTime::now() + MIN_AGE;
+        let summary = sweep(root, MIN_AGE, later).await.unwrap();
+        assert_eq!(summary, SweepSummary { removed: 2, bytes: 15 });
+        assert!(!root.join(DONATE_TMP_DIR).join("com.example.app").exists());
+        assert!(!root.join("App v1+1/main.obb.1a2b3c.partial").exists());
+        assert!(root.join("App v1+1/com.example.app/base.apk").exists());
+        assert!(root.join(RESUME_STATE_DIR).join("x.partial").exists());
+    }
+}
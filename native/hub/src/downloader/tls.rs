@@ -0,0 +1,366 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result, anyhow, ensure};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use rustls::{
+    CertificateError, DigitallySignedStruct, RootCertStore, SignatureScheme,
+    client::{
+        WebPkiServerVerifier,
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    },
+    crypto::{CryptoProvider, WebPkiSupportedAlgorithms, ring},
+    pki_types::{CertificateDer, ServerName, TrustAnchor, UnixTime, pem::PemObject},
+    server::ParsedCertificate,
+};
+use sha2_const_stable::Sha256;
+use tokio::fs;
+use tracing::{debug, instrument};
+
+/// File the pinned certificates are written to for rclone's `--ca-cert`
+const RCLONE_CA_FILE_NAME: &str = "pinned_ca.pem";
+/// Prefix of SPKI pins, followed by the base64 SHA-256 digest of the key
+const SPKI_PIN_PREFIX: &str = "sha256/";
+
+/// Server identity a client trusts instead of any certificate from the system store
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ServerPins<'a> {
+    /// PEM certificates servers must chain to
+    pub certificates: Option<&'a str>,
+    /// `sha256/<base64>` digests of public keys, one of which a certificate in the verified
+    /// chain from the server to a trusted root must carry
+    pub spki: &'a [String],
+}
+
+impl ServerPins<'_> {
+    fn is_empty(&self) -> bool {
+        self.certificates.is_none() && self.spki.is_empty()
+    }
+}
+
+/// Parses the pinned certificates of a source from a PEM bundle
+pub(crate) fn parse_pinned_certificates(pem: &str) -> Result<Vec<reqwest::Certificate>> {
+    let certificates = reqwest::Certificate::from_pem_bundle(pem.as_bytes())
+        .context("Failed to parse pinned certificates")?;
+    ensure!(!certificates.is_empty(), "pinned_certificates contains no certificates");
+    Ok(certificates)
+}
+
+/// Parses `sha256/<base64>` SPKI pins into the digests they stand for
+pub(crate) fn parse_spki_pins(pins: &[String]) -> Result<Vec<[u8; 32]>> {
+    pins.iter()
+        .map(|pin| {
+            let encoded = pin
+                .trim()
+                .strip_prefix(SPKI_PIN_PREFIX)
+                .with_context(|| format!("SPKI pin must start with {SPKI_PIN_PREFIX}: {pin}"))?;
+            let digest = STANDARD
+                .decode(encoded)
+                .with_context(|| format!("SPKI pin is not valid base64: {pin}"))?;
+            digest.try_into().map_err(|_| anyhow!("SPKI pin is not a SHA-256 digest: {pin}"))
+        })
+        .collect()
+}
+
+/// Starts the builder every HTTP client is made from.
+///
+/// With pinned certificates the built-in roots are not trusted, so a server that does not chain
+/// to one of them fails the request. With SPKI pins a certificate of the chain verified from the
+/// server to a trusted root must also have one of the pinned keys. Either pin refuses plain HTTP.
+pub(crate) fn http_client_builder(pins: ServerPins<'_>) -> Result<reqwest::ClientBuilder> {
+    let builder = reqwest::Client::builder().user_agent(crate::USER_AGENT);
+    if pins.is_empty() {
+        return Ok(builder);
+    }
+    let roots = match pins.certificates {
+        Some(pem) => {
+            let mut roots = RootCertStore::empty();
+            for certificate in CertificateDer::pem_slice_iter(pem.as_bytes()) {
+                roots
+                    .add(certificate.context("Failed to parse pinned certificates")?)
+                    .context("Pinned certificate cannot be used as a root")?;
+            }
+            ensure!(!roots.is_empty(), "pinned_certificates contains no certificates");
+            roots
+        }
+        None => RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() },
+    };
+    let provider = Arc::new(ring::default_provider());
+    let verifier = SpkiPinVerifier::new(roots, provider.clone(), parse_spki_pins(pins.spki)?)?;
+    let tls = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("Failed to set up TLS")?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+    Ok(builder.use_preconfigured_tls(tls).https_only(true))
+}
+
+/// Builds the client used for requests to a source
+pub(crate) fn source_http_client(pins: ServerPins<'_>) -> Result<reqwest::Client> {
+    http_client_builder(pins)?.build().context("Failed to build HTTP client")
+}
+
+/// Verifies server certificates against the trusted roots, then checks the SPKI pins if any
+#[derive(Debug)]
+struct SpkiPinVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    roots: Arc<RootCertStore>,
+    signature_algorithms: WebPkiSupportedAlgorithms,
+    /// SHA-256 digests of the pinned public keys
+    pins: Vec<[u8; 32]>,
+}
+
+impl SpkiPinVerifier {
+    fn new(
+        roots: RootCertStore,
+        provider: Arc<CryptoProvider>,
+        pins: Vec<[u8; 32]>,
+    ) -> Result<Self> {
+        let roots = Arc::new(roots);
+        let signature_algorithms = provider.signature_verification_algorithms;
+        let inner = WebPkiServerVerifier::builder_with_provider(roots.clone(), provider)
+            .build()
+            .context("Failed to build certificate verifier")?;
+        Ok(Self { inner, roots, signature_algorithms, pins })
+    }
+
+    /// Whether a chain from `end_entity` to a trusted root has a certificate with one of the
+    /// pinned public keys. Certificates the server sent that are not part of such a chain do
+    /// not count, any trusted certificate could be sent along with a copy of the pinned one.
+    fn verified_path_matches_pins(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> bool {
+        let Ok(certificate) = webpki::EndEntityCert::try_from(end_entity) else {
+            return false;
+        };
+        // Rejecting a path makes webpki go on with the other paths it can build
+        let matches_pins = |path: &webpki::VerifiedPath<'_>| {
+            if path_matches_pins(end_entity, path, &self.pins) {
+                Ok(())
+            } else {
+                Err(webpki::Error::UnknownIssuer)
+            }
+        };
+        certificate
+            .verify_for_usage(
+                self.signature_algorithms.all,
+                &self.roots.roots,
+                intermediates,
+                now,
+                webpki::KeyUsage::server_auth(),
+                None,
+                Some(&matches_pins),
+            )
+            .is_ok()
+    }
+}
+
+impl ServerCertVerifier for SpkiPinVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        if self.pins.is_empty() || self.verified_path_matches_pins(end_entity, intermediates, now) {
+            Ok(verified)
+        } else {
+            Err(rustls::Error::InvalidCertificate(CertificateError::ApplicationVerificationFailure))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Whether a certificate of a verified path, or its trust anchor, has one of the pinned public
+/// keys
+fn path_matches_pins(
+    end_entity: &CertificateDer<'_>,
+    path: &webpki::VerifiedPath<'_>,
+    pins: &[[u8; 32]],
+) -> bool {
+    let intermediates = path.intermediate_certificates().map(|certificate| certificate.der());
+    std::iter::once(end_entity.clone())
+        .chain(intermediates)
+        .filter_map(|certificate| spki_digest(&certificate))
+        .chain(std::iter::once(anchor_spki_digest(path.anchor())))
+        .any(|digest| pins.contains(&digest))
+}
+
+/// SHA-256 digest of the DER SubjectPublicKeyInfo of a certificate
+fn spki_digest(certificate: &CertificateDer<'_>) -> Option<[u8; 32]> {
+    let parsed = ParsedCertificate::try_from(certificate).ok()?;
+    Some(Sha256::new().update(parsed.subject_public_key_info().as_ref()).finalize())
+}
+
+/// SHA-256 digest of the DER SubjectPublicKeyInfo of a trust anchor, which only keeps the
+/// contents of the SPKI sequence
+fn anchor_spki_digest(anchor: &TrustAnchor<'_>) -> [u8; 32] {
+    let contents = anchor.subject_public_key_info.as_ref();
+    let mut header = vec![0x30];
+    match u8::try_from(contents.len()) {
+        Ok(len) if len < 0x80 => header.push(len),
+        _ => {
+            let len = contents.len().to_be_bytes();
+            let len = &len[len.iter().take_while(|byte| **byte == 0).count()..];
+            header.push(0x80 | len.len() as u8);
+            header.extend_from_slice(len);
+        }
+    }
+    Sha256::new().update(&header).update(contents).finalize()
+}
+
+/// Writes the pinned certificates to `dir` for rclone, which replaces its trusted roots with
+/// the `--ca-cert` file
+#[instrument(level = "debug", skip(pem), err)]
+pub(crate) async fn write_rclone_ca_file(dir: &Path, pem: &str) -> Result<PathBuf> {
+    fs::create_dir_all(dir).await.with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(RCLONE_CA_FILE_NAME);
+    fs::write(&path, pem).await.with_context(|| format!("Failed to write {}", path.display()))?;
+    debug!(path = %path.display(), "Wrote pinned certificates for rclone");
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIRROR_CERTIFICATE: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBiTCCAS+gAwIBAgIUa6mbGob4jtUs7O+M909KSlzh58AwCgYIKoZIzj0EAwIw
+GTEXMBUGA1UEAwwObWlycm9yLmV4YW1wbGUwIBcNMjYxMDE2MTE0NjI0WhgPMjEy
+NjA5MjIxMTQ2MjRaMBkxFzAVBgNVBAMMDm1pcnJvci5leGFtcGxlMFkwEwYHKoZI
+zj0CAQYIKoZIzj0DAQcDQgAElfHonrkehPvW2bw/y0c904qS94KhJ1irGHbVsWzY
+f6FeBpSL4qrMSwciHEJF5TN8UkPrCBuBcOdIwNoR+q/fo6NTMFEwHQYDVR0OBBYE
+FMF+uCOr2/CDLDrHUbau/911jcwFMB8GA1UdIwQYMBaAFMF+uCOr2/CDLDrHUbau
+/911jcwFMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIgY1cxlcgi
+meOMXdsVP/8yWLTBU4jgXDKyVJH1zq7PgRUCIQDDbotYoYRm8/kL9goErYcb0vHb
+zBLpUwmQGhWWpmpO2g==
+-----END CERTIFICATE-----
+";
+    const MIRROR_SPKI_PIN: &str = "sha256/lLYhCKBO3yUgPMDC0E8cCy41d1UWmBxbU4d2cITUlu4=";
+    /// Root the test leaf certificate for `mirror.test` is issued by
+    const TEST_ROOT_CERTIFICATE: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBnDCCAUOgAwIBAgIUOWcHQCy+HhlNgBo/nYrPNTYoEHcwCgYIKoZIzj0EAwIw
+GzEZMBcGA1UEAwwQUGlubmVkIFRlc3QgUm9vdDAgFw0yNjEwMTYyMjAxMTBaGA8y
+MTI2MDkyMjIyMDExMFowGzEZMBcGA1UEAwwQUGlubmVkIFRlc3QgUm9vdDBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABDlBpO1M3ZszXkWQ9+5fHX3VV9i8ujQR9lsI
+HX0xyuCLrMU5dVM2z8O9wKDYKlcHWdQiWZAbDM/0ahuI5B2122+jYzBhMB0GA1Ud
+DgQWBBS1kuKpySvkX5ujNmTT3uU+OaRGbjAfBgNVHSMEGDAWgBS1kuKpySvkX5uj
+NmTT3uU+OaRGbjAPBgNVHRMBAf8EBTADAQH/MA4GA1UdDwEB/wQEAwIBBjAKBggq
+hkjOPQQDAgNHADBEAiAxkN2Jv/Iax5E+zGZ1znHlxcVyY46xwFYiXL6lKdsSKgIg
+IpPL3WN7wcOP70GFACTtyh9qb0ndH7++F//bXf0IHLY=
+-----END CERTIFICATE-----
+";
+    const TEST_ROOT_SPKI_PIN: &str = "sha256/GQFTYVeQABQNoZW18iBKIY3kpZl4yiIj9gSQkXGh7o8=";
+    const TEST_LEAF_CERTIFICATE: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBwzCCAWqgAwIBAgIUR3w2REfGYRdWmJNyINjxy30tzz4wCgYIKoZIzj0EAwIw
+GzEZMBcGA1UEAwwQUGlubmVkIFRlc3QgUm9vdDAgFw0yNjEwMTYyMjAxMTBaGA8y
+MTI2MDkyMjIyMDExMFowFjEUMBIGA1UEAwwLbWlycm9yLnRlc3QwWTATBgcqhkjO
+PQIBBggqhkjOPQMBBwNCAASi0UklTY0513yA0ZSkeWgcN1/G11LpE/yV2MAZHsEa
+7kxfxcI6DEWiiKbUQz+YKc7He42O75EYh1ANZCPJQTSro4GOMIGLMBYGA1UdEQQP
+MA2CC21pcnJvci50ZXN0MBMGA1UdJQQMMAoGCCsGAQUFBwMBMAwGA1UdEwEB/wQC
+MAAwDgYDVR0PAQH/BAQDAgeAMB0GA1UdDgQWBBTdll2tXCQ/MElBFLQCSFI7sr/O
+STAfBgNVHSMEGDAWgBS1kuKpySvkX5ujNmTT3uU+OaRGbjAKBggqhkjOPQQDAgNH
+ADBEAiBDTRvILXVhKHGT7pfcOfM2slVHAUlycB4imRbdhdGvhwIgc72wSlhDEqOX
+tgyECEbTg3SnQGwUpeWeJDoTMgqeh+Y=
+-----END CERTIFICATE-----
+";
+    const TEST_LEAF_SPKI_PIN: &str = "sha256/ZejHSHd/uTpntOtzlchZPGDxEB5WOy+q6+qXZqZkvJ4=";
+
+    #[test]
+    fn parses_pinned_certificates() {
+        assert_eq!(parse_pinned_certificates(MIRROR_CERTIFICATE).unwrap().len(), 1);
+        let bundle = format!("{MIRROR_CERTIFICATE}{MIRROR_CERTIFICATE}");
+        assert_eq!(parse_pinned_certificates(&bundle).unwrap().len(), 2);
+        assert!(parse_pinned_certificates("").is_err());
+        assert!(parse_pinned_certificates("-----BEGIN CERTIFICATE-----\nnope\n").is_err());
+    }
+
+    #[test]
+    fn parses_spki_pins() {
+        assert_eq!(parse_spki_pins(&[MIRROR_SPKI_PIN.to_string()]).unwrap().len(), 1);
+        assert!(parse_spki_pins(&["sha1/AAAA".to_string()]).is_err());
+        assert!(parse_spki_pins(&["sha256/not base64".to_string()]).is_err());
+        assert!(parse_spki_pins(&["sha256/AAAA".to_string()]).is_err());
+    }
+
+    #[test]
+    fn pins_only_match_the_verified_path() {
+        let root = CertificateDer::from_pem_slice(TEST_ROOT_CERTIFICATE.as_bytes()).unwrap();
+        let leaf = CertificateDer::from_pem_slice(TEST_LEAF_CERTIFICATE.as_bytes()).unwrap();
+        let extra = CertificateDer::from_pem_slice(MIRROR_CERTIFICATE.as_bytes()).unwrap();
+        let server_name = ServerName::try_from("mirror.test").unwrap();
+        let verify = |pin: &str, intermediates: &[CertificateDer<'_>]| {
+            let mut roots = RootCertStore::empty();
+            roots.add(root.clone()).unwrap();
+            let pins = parse_spki_pins(&[pin.to_string()]).unwrap();
+            SpkiPinVerifier::new(roots, Arc::new(ring::default_provider()), pins)
+                .unwrap()
+                .verify_server_cert(&leaf, intermediates, &server_name, &[], UnixTime::now())
+        };
+
+        assert!(verify(TEST_LEAF_SPKI_PIN, &[]).is_ok());
+        assert!(verify(TEST_ROOT_SPKI_PIN, &[]).is_ok());
+        // A certificate with the pinned key that does not take part in the chain is ignored
+        assert!(matches!(
+            verify(MIRROR_SPKI_PIN, std::slice::from_ref(&extra)),
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn pinned_client_refuses_plain_http() {
+        let pins = [MIRROR_SPKI_PIN.to_string()];
+        for pins in [
+            ServerPins { certificates: Some(MIRROR_CERTIFICATE), spki: &[] },
+            ServerPins { certificates: None, spki: &pins },
+        ] {
+            let client = source_http_client(pins).unwrap();
+            let error = client.get("http://127.0.0.1:9/list").send().await.unwrap_err();
+            assert!(error.is_builder(), "{error:?}");
+        }
+        assert!(source_http_client(ServerPins::default()).is_ok());
+        let invalid = ["sha256/AAAA".to_string()];
+        assert!(source_http_client(ServerPins { certificates: None, spki: &invalid }).is_err());
+    }
+}
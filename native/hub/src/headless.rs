@@ -0,0 +1,276 @@
+//! Library facade running the task pipeline without Flutter, used by the `yaas-cli` binary.
+//!
+//! Signals meant for Dart are still sent and simply go nowhere. Task progress is read back from
+//! the task manager instead.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
+use tokio::{sync::broadcast::error::RecvError, time::timeout};
+
+use crate::{
+    Core, TASK_SHUTDOWN_TIMEOUT,
+    adb::device::AdbDevice,
+    models::signals::{
+        adb::devices_list::AdbBriefState,
+        task::{Task, TaskPriority, TaskStatus},
+    },
+    resolve_app_dir, setup_logging, start_core, utils,
+};
+
+/// How long to wait for a saved downloader configuration to load
+const DOWNLOADER_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long to wait for ADB to pick up a device
+const DEVICE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Device seen by the ADB server
+#[derive(Debug, Clone)]
+pub struct DeviceEntry {
+    pub serial: String,
+    /// Connection state as `adb devices` names it
+    pub state: &'static str,
+    /// Friendly name, only known for devices that were ready at some point
+    pub name: Option<String>,
+    pub is_wireless: bool,
+}
+
+/// Work the CLI can queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadlessTask {
+    /// Download a catalog app by full name or package name, the newest release for the latter
+    Download { app: String, install: bool },
+    /// Install an APK file or XAPK/APKS/APKM bundle, an app folder or an archive of one
+    Install { path: PathBuf },
+    /// Back up an installed app into the configured backups directory
+    Backup {
+        package_name: String,
+        backup_apk: bool,
+        backup_data: bool,
+        backup_obb: bool,
+        name_append: Option<String>,
+        compress: bool,
+    },
+}
+
+/// Progress of a running task
+#[derive(Debug, Clone)]
+pub struct TaskUpdate {
+    pub step: u32,
+    pub total_steps: u32,
+    /// Overall progress in range [0.0, 1.0]
+    pub progress: f32,
+    pub message: String,
+    /// Bytes per second of the transfer the step is making, if any
+    pub speed: Option<u64>,
+}
+
+/// Result of a task that completed
+#[derive(Debug, Clone)]
+pub struct TaskOutcome {
+    pub name: Option<String>,
+    /// Files and folders the task produced on this machine
+    pub artifacts: Vec<PathBuf>,
+}
+
+/// Core services started without the Flutter UI
+pub struct Headless {
+    core: Core,
+}
+
+impl Headless {
+    /// Starts the core services using the app data of the desktop app, or `app_dir` if given.
+    ///
+    /// Tasks are not persisted, so an interrupted CLI run is never resumed by the app. Install
+    /// scripts are declined unless the settings approve them automatically or
+    /// [`Headless::approve_install_scripts`] is called.
+    pub async fn start(app_dir: Option<PathBuf>, portable_mode: bool) -> Result<Self> {
+        let app_dir = app_dir.unwrap_or_else(|| resolve_app_dir(portable_mode));
+        std::fs::create_dir_all(&app_dir).context("Failed to create app directory")?;
+        let app_dir = std::fs::canonicalize(&app_dir).context("Failed to resolve app directory")?;
+        setup_logging(&app_dir)?;
+
+        let core = start_core(&app_dir, portable_mode, false).await;
+        core.adb_service.set_headless_script_approval(false);
+        Ok(Self { core })
+    }
+
+    /// Runs install scripts without asking, there is no UI to review them
+    pub fn approve_install_scripts(&self) {
+        self.core.adb_service.set_headless_script_approval(true);
+    }
+
+    /// Lists devices known to the ADB server, starting it if needed
+    pub async fn list_devices(&self) -> Result<Vec<DeviceEntry>> {
+        let devices = self.core.adb_service.list_devices().await?;
+        Ok(devices
+            .into_iter()
+            .map(|device| DeviceEntry {
+                serial: device.serial,
+                state: state_label(&device.state),
+                name: device.name,
+                is_wireless: device.is_wireless,
+            })
+            .collect())
+    }
+
+    /// Queues a task and waits for it to finish, reporting progress on the way.
+    ///
+    /// Tasks that need a device use the one ADB connects to automatically.
+    pub async fn run(
+        &self,
+        task: HeadlessTask,
+        mut on_progress: impl FnMut(&TaskUpdate),
+    ) -> Result<TaskOutcome> {
+        let task = self.resolve_task(task).await?;
+        let task_manager = &self.core.task_manager;
+
+        // Subscribe first to not miss the progress of a task that fails right away
+        let mut progress_rx = task_manager.subscribe_progress();
+        let id = task_manager
+            .clone()
+            .enqueue_task(task, TaskPriority::default())
+            .await
+            .context("Task manager is shutting down")?;
+
+        loop {
+            let progress = match progress_rx.recv().await {
+                Ok(progress) if progress.task_id == id => progress,
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => bail!("Task manager stopped"),
+            };
+            match progress.status {
+                TaskStatus::Waiting | TaskStatus::Running => on_progress(&TaskUpdate {
+                    step: progress.current_step,
+                    total_steps: progress.total_steps,
+                    progress: progress.total_progress,
+                    message: progress.message,
+                    speed: progress.transfer.map(|transfer| transfer.speed),
+                }),
+                TaskStatus::Completed => {
+                    return Ok(TaskOutcome {
+                        name: progress.task_name,
+                        artifacts: progress
+                            .artifacts
+                            .into_iter()
+                            .map(|artifact| PathBuf::from(artifact.path))
+                            .collect(),
+                    });
+                }
+                TaskStatus::Failed | TaskStatus::Cancelled => bail!(progress.message),
+            }
+        }
+    }
+
+    /// Cancels running tasks and waits for them to stop
+    pub async fn shutdown(&self) {
+        self.core.task_manager.shutdown(TASK_SHUTDOWN_TIMEOUT).await;
+    }
+
+    async fn resolve_task(&self, task: HeadlessTask) -> Result<Task> {
+        Ok(match task {
+            HeadlessTask::Download { app, install } => {
+                let (full_name, package_name) = self.find_catalog_app(&app).await?;
+                if install {
+                    self.wait_for_device().await?;
+                    Task::DownloadInstall(full_name, package_name, None)
+                } else {
+                    Task::Download(full_name, package_name, None)
+                }
+            }
+            HeadlessTask::Install { path } => {
+                let path = utils::canonicalize(&path)
+                    .await
+                    .with_context(|| format!("Path does not exist: {}", path.display()))?;
+                self.wait_for_device().await?;
+                let path_str = path.display().to_string();
+                if path.is_file() && is_apk(&path) {
+                    Task::InstallApk(path_str)
+                } else {
+                    Task::InstallLocalApp(path_str)
+                }
+            }
+            HeadlessTask::Backup {
+                package_name,
+                backup_apk,
+                backup_data,
+                backup_obb,
+                name_append,
+                compress,
+            } => {
+                ensure!(
+                    backup_apk || backup_data || backup_obb,
+                    "Nothing to back up, all parts are excluded"
+                );
+                let device = self.wait_for_device().await?;
+                ensure!(
+                    device.installed_packages.iter().any(|p| p.package_name() == package_name),
+                    "{package_name} is not installed on {device}"
+                );
+                Task::BackupApp {
+                    package_name,
+                    display_name: None,
+                    backup_apk,
+                    backup_data,
+                    backup_obb,
+                    backup_name_append: name_append,
+                    compress,
+                }
+            }
+        })
+    }
+
+    /// Finds the full name and true package name of a catalog app
+    async fn find_catalog_app(&self, app: &str) -> Result<(String, String)> {
+        let downloader_manager = &self.core.downloader_manager;
+        // A missing configuration is reported by `require` below
+        let _ = timeout(DOWNLOADER_TIMEOUT, downloader_manager.wait_available()).await;
+        let apps = downloader_manager.require().await?.ensure_app_list().await?;
+
+        let found = apps.iter().find(|candidate| candidate.full_name == app).or_else(|| {
+            apps.iter()
+                .filter(|candidate| {
+                    candidate.package_name == app || candidate.true_package_name == app
+                })
+                .max_by_key(|candidate| candidate.version_code)
+        });
+        let found = found.ok_or_else(|| anyhow!("App not found in the catalog: {app}"))?;
+        Ok((found.full_name.clone(), found.true_package_name.clone()))
+    }
+
+    async fn wait_for_device(&self) -> Result<Arc<AdbDevice>> {
+        let adb_service = &self.core.adb_service;
+        let mut device_changes = adb_service.subscribe_device_changes();
+        let wait = async {
+            loop {
+                if let Some(device) = adb_service.try_current_device().await {
+                    return Some(device);
+                }
+                device_changes.changed().await.ok()?;
+            }
+        };
+        timeout(DEVICE_TIMEOUT, wait).await.ok().flatten().context("No device connected")
+    }
+}
+
+fn is_apk(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("apk"))
+}
+
+fn state_label(state: &AdbBriefState) -> &'static str {
+    match state {
+        AdbBriefState::Offline => "offline",
+        AdbBriefState::Bootloader => "bootloader",
+        AdbBriefState::Device => "device",
+        AdbBriefState::Host => "host",
+        AdbBriefState::Recovery => "recovery",
+        AdbBriefState::NoPermissions => "no permissions",
+        AdbBriefState::Sideload => "sideload",
+        AdbBriefState::Unauthorized => "unauthorized",
+        AdbBriefState::Authorizing => "authorizing",
+        AdbBriefState::Unknown => "unknown",
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::models::signals::history::*;
+
+/// Oldest entries are dropped once the history grows past this
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedHistory {
+    #[serde(default)]
+    entries: Vec<HistoryEntry>,
+}
+
+/// Persists a ledger of finished installs, uninstalls, backups, restores and downloads
+#[derive(Debug)]
+pub(crate) struct InstallHistory {
+    file_path: PathBuf,
+    /// Oldest first
+    entries: Mutex<Vec<HistoryEntry>>,
+}
+
+impl InstallHistory {
+    /// Loads the history file from the app directory, falling back to an empty history.
+    #[instrument(level = "debug")]
+    pub(crate) fn load(app_dir: PathBuf) -> Self {
+        let file_path = app_dir.join("install_history.json");
+        let entries = if file_path.exists() {
+            Self::read_history(&file_path).map(|history| history.entries).unwrap_or_else(|e| {
+                warn!(
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to load install history, using empty"
+                );
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        debug!(count = entries.len(), "Loaded install history");
+
+        Self { file_path, entries: Mutex::new(entries) }
+    }
+
+    /// Starts handling history requests from Dart.
+    pub(crate) fn start(self) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        handle
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let get_receiver = GetInstallHistoryRequest::get_dart_signal_receiver();
+        let clear_receiver = ClearInstallHistoryRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = get_receiver.recv() => {
+                    if let Some(request) = request {
+                        let package_name = request.message.package_name;
+                        debug!(?package_name, "Received GetInstallHistoryRequest");
+                        InstallHistoryResponse {
+                            entries: self.entries(package_name.as_deref()),
+                            error: None,
+                        }
+                        .send_signal_to_dart();
+                    } else {
+                        panic!("GetInstallHistoryRequest receiver closed");
+                    }
+                }
+                request = clear_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received ClearInstallHistoryRequest");
+                        let result = self.clear();
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to clear install history");
+                        }
+                        InstallHistoryResponse {
+                            entries: self.entries(None),
+                            error: result.err().map(|e| format!("{e:#}")),
+                        }
+                        .send_signal_to_dart();
+                    } else {
+                        panic!("ClearInstallHistoryRequest receiver closed");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Appends an entry and persists the history.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn record(&self, entry: HistoryEntry) -> Result<()> {
+        let mut entries = self.entries.lock().expect("install history lock poisoned");
+        entries.push(entry);
+        let excess = entries.len().saturating_sub(MAX_ENTRIES);
+        entries.drain(..excess);
+        self.save(&entries)
+    }
+
+    /// Returns entries newest first, optionally only those of `package_name`.
+    pub(crate) fn entries(&self, package_name: Option<&str>) -> Vec<HistoryEntry> {
+        let entries = self.entries.lock().expect("install history lock poisoned");
+        entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                package_name.is_none_or(|package| entry.package_name.as_deref() == Some(package))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Removes all entries.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn clear(&self) -> Result<()> {
+        let mut entries = self.entries.lock().expect("install history lock poisoned");
+        if self.file_path.exists() {
+            fs::remove_file(&self.file_path).context("Failed to remove install history file")?;
+        }
+        entries.clear();
+        info!("Cleared install history");
+        Ok(())
+    }
+
+    fn read_history(path: &Path) -> Result<PersistedHistory> {
+        let content = fs::read_to_string(path).context("Failed to read install history file")?;
+        serde_json::from_str(&content).context("Failed to parse install history file")
+    }
+
+    fn save(&self, entries: &[HistoryEntry]) -> Result<()> {
+        let json = serde_json::to_string_pretty(&PersistedHistory { entries: entries.to_vec() })
+            .context("Failed to serialize install history")?;
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).context("Failed to write install history file")?;
+        fs::rename(&tmp_path, &self.file_path).context("Failed to replace install history file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(package_name: &str, timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            action: HistoryAction::Install,
+            name: package_name.to_string(),
+            package_name: Some(package_name.to_string()),
+            version_code: Some(1),
+            version_name: Some("1.0".into()),
+            timestamp,
+            duration_ms: 1500,
+            outcome: HistoryOutcome::Succeeded,
+            error: None,
+            download_summary: None,
+            timeline: Vec::new(),
+            artifacts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn record_persists_and_filters_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = InstallHistory::load(dir.path().to_path_buf());
+        history.record(entry("com.example.first", 1)).unwrap();
+        history.record(entry("com.example.second", 2)).unwrap();
+        history.record(entry("com.example.first", 3)).unwrap();
+
+        let reloaded = InstallHistory::load(dir.path().to_path_buf());
+        let timestamps = |entries: Vec<HistoryEntry>| {
+            entries.into_iter().map(|entry| entry.timestamp).collect::<Vec<_>>()
+        };
+        assert_eq!(timestamps(reloaded.entries(None)), [3, 2, 1]);
+        assert_eq!(timestamps(reloaded.entries(Some("com.example.first"))), [3, 1]);
+
+        reloaded.clear().unwrap();
+        assert!(reloaded.entries(None).is_empty());
+        assert!(InstallHistory::load(dir.path().to_path_buf()).entries(None).is_empty());
+    }
+
+    #[test]
+    fn record_drops_oldest_entries_past_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = InstallHistory::load(dir.path().to_path_buf());
+        for timestamp in 0..MAX_ENTRIES as u64 + 5 {
+            history.entries.lock().unwrap().push(entry("com.example.app", timestamp));
+        }
+        history.record(entry("com.example.app", u64::MAX)).unwrap();
+
+        let entries = history.entries(None);
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0].timestamp, u64::MAX);
+        assert_eq!(entries.last().unwrap().timestamp, 6);
+    }
+}
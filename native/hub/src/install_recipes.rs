@@ -0,0 +1,176 @@
+//! Per-app post-install recipes, kept in a file in the app directory.
+//!
+//! A recipe lists device actions (granting permissions, pushing config files, launching the app
+//! once, setting properties) run automatically after the app was installed successfully.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::models::signals::recipes::*;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedRecipes {
+    /// Recipes keyed by package name
+    #[serde(default)]
+    recipes: BTreeMap<String, InstallRecipe>,
+}
+
+/// Persists post-install recipes and answers recipe requests from Dart
+#[derive(Debug)]
+pub(crate) struct InstallRecipes {
+    file_path: PathBuf,
+    recipes: Mutex<BTreeMap<String, InstallRecipe>>,
+}
+
+impl InstallRecipes {
+    /// Loads the recipes file from the app directory, falling back to no recipes.
+    #[instrument(level = "debug")]
+    pub(crate) fn load(app_dir: PathBuf) -> Self {
+        let file_path = app_dir.join("install_recipes.json");
+        let recipes = if file_path.exists() {
+            Self::read_recipes(&file_path).map(|persisted| persisted.recipes).unwrap_or_else(|e| {
+                warn!(
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to load install recipes, using none"
+                );
+                BTreeMap::new()
+            })
+        } else {
+            BTreeMap::new()
+        };
+        debug!(count = recipes.len(), "Loaded install recipes");
+
+        Self { file_path, recipes: Mutex::new(recipes) }
+    }
+
+    /// Starts handling recipe requests from Dart.
+    pub(crate) fn start(self) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        handle
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let get_receiver = GetInstallRecipesRequest::get_dart_signal_receiver();
+        let set_receiver = SetInstallRecipeRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = get_receiver.recv() => {
+                    if request.is_none() {
+                        panic!("GetInstallRecipesRequest receiver closed");
+                    }
+                    debug!("Received GetInstallRecipesRequest");
+                    self.send_recipes(None);
+                }
+                request = set_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("SetInstallRecipeRequest receiver closed");
+                    };
+                    let recipe = request.message.recipe;
+                    debug!(package_name = %recipe.package_name, steps = recipe.steps.len(), "Received SetInstallRecipeRequest");
+                    let result = self.set(recipe);
+                    if let Err(e) = &result {
+                        error!(error = e.as_ref() as &dyn Error, "Failed to save install recipe");
+                    }
+                    self.send_recipes(result.err().map(|e| format!("{e:#}")));
+                }
+            }
+        }
+    }
+
+    fn send_recipes(&self, error: Option<String>) {
+        let recipes = self.recipes.lock().expect("install recipes lock poisoned");
+        InstallRecipesChanged { recipes: recipes.values().cloned().collect(), error }
+            .send_signal_to_dart();
+    }
+
+    /// Enabled recipe of `package_name` with at least one step
+    pub(crate) fn recipe(&self, package_name: &str) -> Option<InstallRecipe> {
+        let recipes = self.recipes.lock().expect("install recipes lock poisoned");
+        recipes.get(package_name).filter(|r| r.enabled && !r.steps.is_empty()).cloned()
+    }
+
+    /// Saves a recipe, or removes it if it has no steps
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn set(&self, recipe: InstallRecipe) -> Result<()> {
+        recipe.validate()?;
+        let mut recipes = self.recipes.lock().expect("install recipes lock poisoned");
+        let mut updated = recipes.clone();
+        if recipe.steps.is_empty() {
+            updated.remove(&recipe.package_name);
+        } else {
+            updated.insert(recipe.package_name.clone(), recipe);
+        }
+        self.save(&updated)?;
+        *recipes = updated;
+        info!(path = %self.file_path.display(), "Saved install recipes");
+        Ok(())
+    }
+
+    fn read_recipes(path: &Path) -> Result<PersistedRecipes> {
+        let content = fs::read_to_string(path).context("Failed to read install recipes file")?;
+        serde_json::from_str(&content).context("Failed to parse install recipes file")
+    }
+
+    fn save(&self, recipes: &BTreeMap<String, InstallRecipe>) -> Result<()> {
+        let json = serde_json::to_string_pretty(&PersistedRecipes { recipes: recipes.clone() })
+            .context("Failed to serialize install recipes")?;
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).context("Failed to write install recipes file")?;
+        fs::rename(&tmp_path, &self.file_path).context("Failed to replace install recipes file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(enabled: bool, steps: Vec<RecipeStep>) -> InstallRecipe {
+        InstallRecipe { package_name: "com.example.game".to_string(), enabled, steps }
+    }
+
+    #[test]
+    fn saved_recipes_persist_and_empty_ones_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let recipes = InstallRecipes::load(dir.path().to_path_buf());
+        recipes.set(recipe(true, vec![RecipeStep::LaunchOnce])).unwrap();
+
+        let reloaded = InstallRecipes::load(dir.path().to_path_buf());
+        assert_eq!(
+            reloaded.recipe("com.example.game"),
+            Some(recipe(true, vec![RecipeStep::LaunchOnce]))
+        );
+
+        reloaded.set(recipe(false, vec![RecipeStep::LaunchOnce])).unwrap();
+        assert_eq!(reloaded.recipe("com.example.game"), None);
+
+        reloaded.set(recipe(true, vec![])).unwrap();
+        let reloaded = InstallRecipes::load(dir.path().to_path_buf());
+        assert!(reloaded.recipes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_recipes_are_not_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let recipes = InstallRecipes::load(dir.path().to_path_buf());
+
+        let step = RecipeStep::SetProperty { name: "a b".to_string(), value: "1".to_string() };
+        assert!(recipes.set(recipe(true, vec![step])).is_err());
+        assert!(!dir.path().join("install_recipes.json").exists());
+    }
+}
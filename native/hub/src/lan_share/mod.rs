@@ -0,0 +1,421 @@
+//! Sharing downloaded apps with other instances on the local network.
+//!
+//! Shared apps are served over plain HTTP and announced with mDNS, so peers can list them and
+//! copy one with a receive task instead of downloading it from the remote again.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    net::{Ipv4Addr, SocketAddr},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result, anyhow, ensure};
+use futures::future::join_all;
+use mdns_sd::{ResolvedService, ServiceDaemon, ServiceEvent, ServiceInfo};
+use rinf::{DartSignal, RustSignal};
+use tokio::{fs, net::TcpListener};
+use tracing::{Instrument, debug, info, info_span, instrument, trace, warn};
+use uuid::Uuid;
+
+use crate::models::signals::lan_share::*;
+
+mod protocol;
+mod server;
+
+use protocol::{ShareManifest, SharedFile};
+pub(crate) use protocol::{fetch_shares, http_client, receive_share};
+use server::{SharedDir, Shares};
+
+const SERVICE_TYPE: &str = "_yaas-share._tcp.local.";
+
+#[derive(Debug, Clone)]
+struct Peer {
+    name: String,
+    /// `host:port` of the peer's share server
+    address: String,
+}
+
+enum Incoming {
+    Share(ShareOnLanRequest),
+    Stop(String),
+    Get,
+    Mdns(Option<ServiceEvent>),
+}
+
+/// Serves shared apps, announces them and keeps track of peers doing the same
+pub(crate) struct LanShareService {
+    /// Name announced to peers, unique per installation
+    instance_name: String,
+    host_name: String,
+    shares: Shares,
+    port: Option<u16>,
+    mdns: Option<ServiceDaemon>,
+    /// Full mDNS name of our announcement while anything is shared
+    announced: Option<String>,
+    browse: Option<mdns_sd::Receiver<ServiceEvent>>,
+    /// Peers by full mDNS name
+    peers: HashMap<String, Peer>,
+    client: reqwest::Client,
+}
+
+impl LanShareService {
+    pub(crate) fn start(installation_id: &str) {
+        let short_id: String =
+            installation_id.chars().filter(char::is_ascii_alphanumeric).take(8).collect();
+        let computer = std::env::var("COMPUTERNAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| "YAAS".to_string());
+        let service = Self {
+            instance_name: format!("{} ({short_id})", computer.trim()),
+            host_name: format!("yaas-{short_id}.local."),
+            shares: Arc::new(Mutex::new(BTreeMap::new())),
+            port: None,
+            mdns: None,
+            announced: None,
+            browse: None,
+            peers: HashMap::new(),
+            client: http_client(),
+        };
+        tokio::spawn(service.receive_signals().instrument(info_span!("task_lan_share")));
+    }
+
+    async fn receive_signals(mut self) {
+        let share_receiver = ShareOnLanRequest::get_dart_signal_receiver();
+        let stop_receiver = StopLanShareRequest::get_dart_signal_receiver();
+        let get_receiver = GetLanSharesRequest::get_dart_signal_receiver();
+
+        loop {
+            let incoming = tokio::select! {
+                request = share_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("ShareOnLanRequest receiver closed");
+                    };
+                    Incoming::Share(request.message)
+                }
+                request = stop_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("StopLanShareRequest receiver closed");
+                    };
+                    Incoming::Stop(request.message.id)
+                }
+                request = get_receiver.recv() => {
+                    if request.is_none() {
+                        panic!("GetLanSharesRequest receiver closed");
+                    }
+                    Incoming::Get
+                }
+                event = next_event(self.browse.as_ref()) => Incoming::Mdns(event),
+            };
+
+            let result = match incoming {
+                Incoming::Share(ShareOnLanRequest { path, name }) => {
+                    self.share(Path::new(&path), &name).await
+                }
+                Incoming::Stop(id) => self.stop(&id),
+                Incoming::Get => self.ensure_browsing(),
+                Incoming::Mdns(event) => {
+                    if !self.handle_event(event) {
+                        continue;
+                    }
+                    Ok(())
+                }
+            };
+            if let Err(e) = &result {
+                warn!(error = e.as_ref() as &dyn Error, "LAN share request failed");
+            }
+            self.send_state(result.err().map(|e| format!("{e:#}"))).await;
+        }
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    async fn share(&mut self, path: &Path, name: &str) -> Result<()> {
+        let root = fs::canonicalize(path)
+            .await
+            .with_context(|| format!("Failed to resolve {}", path.display()))?;
+        ensure!(root.is_dir(), "{} is not a directory", root.display());
+        let name = match name.trim() {
+            "" => root.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+            name => name.to_string(),
+        };
+        let files = list_files(&root).await?;
+        ensure!(!files.is_empty(), "{} has no files to share", root.display());
+
+        self.ensure_serving().await?;
+        {
+            let mut shares = self.shares.lock().expect("lan shares lock poisoned");
+            shares.retain(|_, share| share.root != root);
+            let id = Uuid::new_v4().simple().to_string();
+            info!(%id, name, files = files.len(), "Sharing app on the local network");
+            let manifest = ShareManifest { id: id.clone(), name, files };
+            shares.insert(id, SharedDir { root, manifest });
+        }
+        self.announce()
+    }
+
+    fn stop(&mut self, id: &str) -> Result<()> {
+        let now_empty = {
+            let mut shares = self.shares.lock().expect("lan shares lock poisoned");
+            ensure!(shares.remove(id).is_some(), "Share {id} not found");
+            shares.is_empty()
+        };
+        info!(id, "Stopped sharing app");
+        if now_empty && let Some(fullname) = self.announced.take() {
+            debug!(fullname, "Withdrawing mDNS announcement");
+            self.daemon()?.unregister(&fullname)?;
+        }
+        Ok(())
+    }
+
+    /// Starts the share server on a free port the first time something is shared
+    async fn ensure_serving(&mut self) -> Result<()> {
+        if self.port.is_none() {
+            let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0))
+                .await
+                .context("Failed to start the share server")?;
+            let port = listener.local_addr()?.port();
+            info!(port, "Share server listening");
+            tokio::spawn(
+                server::serve(listener, self.shares.clone())
+                    .instrument(info_span!("task_lan_share_server")),
+            );
+            self.port = Some(port);
+        }
+        Ok(())
+    }
+
+    fn daemon(&mut self) -> Result<&ServiceDaemon> {
+        if self.mdns.is_none() {
+            self.mdns = Some(ServiceDaemon::new().context("Failed to start mDNS")?);
+        }
+        Ok(self.mdns.as_ref().expect("mDNS daemon just created"))
+    }
+
+    fn announce(&mut self) -> Result<()> {
+        if self.announced.is_some() {
+            return Ok(());
+        }
+        let port = self.port.context("Share server is not running")?;
+        let info = ServiceInfo::new(
+            SERVICE_TYPE,
+            &self.instance_name,
+            &self.host_name,
+            "",
+            port,
+            None::<HashMap<String, String>>,
+        )
+        .context("Failed to build the mDNS announcement")?
+        .enable_addr_auto();
+        let fullname = info.get_fullname().to_string();
+        self.daemon()?.register(info).context("Failed to announce shares")?;
+        debug!(fullname, port, "Announced shares over mDNS");
+        self.announced = Some(fullname);
+        Ok(())
+    }
+
+    fn ensure_browsing(&mut self) -> Result<()> {
+        if self.browse.is_none() {
+            let receiver =
+                self.daemon()?.browse(SERVICE_TYPE).context("Failed to look for peers")?;
+            debug!("Browsing for peers");
+            self.browse = Some(receiver);
+        }
+        Ok(())
+    }
+
+    /// Updates the peers from an mDNS event, returning whether they changed
+    fn handle_event(&mut self, event: Option<ServiceEvent>) -> bool {
+        match event {
+            Some(ServiceEvent::ServiceResolved(resolved)) => {
+                let Some(peer) = peer_from(&resolved) else {
+                    return false;
+                };
+                debug!(fullname = resolved.get_fullname(), ?peer, "Found peer");
+                self.peers.insert(resolved.get_fullname().to_string(), peer);
+                true
+            }
+            Some(ServiceEvent::ServiceRemoved(_, fullname)) => {
+                debug!(fullname, "Peer gone");
+                self.peers.remove(&fullname).is_some()
+            }
+            Some(_) => false,
+            None => {
+                warn!("mDNS browse channel closed");
+                self.browse = None;
+                false
+            }
+        }
+    }
+
+    async fn send_state(&self, error: Option<String>) {
+        let shares = {
+            let shares = self.shares.lock().expect("lan shares lock poisoned");
+            shares
+                .values()
+                .map(|share| LanShare {
+                    id: share.manifest.id.clone(),
+                    name: share.manifest.name.clone(),
+                    path: share.root.to_string_lossy().into_owned(),
+                    size: share.manifest.size(),
+                    file_count: share.manifest.files.len() as u32,
+                })
+                .collect()
+        };
+
+        let peers = self
+            .peers
+            .iter()
+            .filter(|(fullname, _)| self.announced.as_ref() != Some(*fullname))
+            .map(|(_, peer)| async move {
+                match fetch_shares(&self.client, &peer.address).await {
+                    Ok(manifests) => manifests
+                        .into_iter()
+                        .map(|manifest| PeerShare {
+                            peer: peer.name.clone(),
+                            address: peer.address.clone(),
+                            size: manifest.size(),
+                            id: manifest.id,
+                            name: manifest.name,
+                        })
+                        .collect(),
+                    Err(e) => {
+                        debug!(
+                            error = e.as_ref() as &dyn Error,
+                            ?peer,
+                            "Failed to list peer shares"
+                        );
+                        Vec::new()
+                    }
+                }
+            });
+        let mut peers: Vec<PeerShare> = join_all(peers).await.into_iter().flatten().collect();
+        peers.sort_by(|a, b| (&a.peer, &a.name).cmp(&(&b.peer, &b.name)));
+
+        LanSharesChanged { shares, peers, error }.send_signal_to_dart();
+    }
+}
+
+async fn next_event(browse: Option<&mdns_sd::Receiver<ServiceEvent>>) -> Option<ServiceEvent> {
+    match browse {
+        Some(receiver) => receiver.recv_async().await.ok(),
+        None => std::future::pending().await,
+    }
+}
+
+/// Name and address of a resolved peer, preferring an IPv4 address
+fn peer_from(resolved: &ResolvedService) -> Option<Peer> {
+    let fullname = resolved.get_fullname();
+    let name = fullname.strip_suffix(&format!(".{SERVICE_TYPE}")).unwrap_or(fullname);
+    let ip = resolved
+        .get_addresses()
+        .iter()
+        .filter(|address| !address.is_loopback())
+        .map(|address| address.to_ip_addr())
+        .min_by_key(|ip| !ip.is_ipv4())?;
+    let address = SocketAddr::new(ip, resolved.get_port()).to_string();
+    Some(Peer { name: name.to_string(), address })
+}
+
+/// Lists the files under `root` with their paths relative to it, sorted by path
+async fn list_files(root: &Path) -> Result<Vec<SharedFile>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = fs::read_dir(&dir)
+            .await
+            .with_context(|| format!("Failed to read {}", dir.display()))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let meta = entry.metadata().await?;
+            let path = entry.path();
+            if meta.is_dir() {
+                pending.push(path);
+            } else if meta.is_file() {
+                let relative = path
+                    .strip_prefix(root)?
+                    .components()
+                    .map(|component| {
+                        component.as_os_str().to_str().ok_or_else(|| {
+                            anyhow!("{} has a name that cannot be shared", path.display())
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join("/");
+                trace!(path = relative, size = meta.len(), "Found file to share");
+                files.push(SharedFile { path: relative, size: meta.len() });
+            }
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+
+    async fn serve_dir(root: &Path) -> (String, ShareManifest) {
+        let root = fs::canonicalize(root).await.unwrap();
+        let manifest = ShareManifest {
+            id: "abc".into(),
+            name: "App v1+1".into(),
+            files: list_files(&root).await.unwrap(),
+        };
+        let shares: Shares = Arc::new(Mutex::new(BTreeMap::from([(
+            manifest.id.clone(),
+            SharedDir { root, manifest: manifest.clone() },
+        )])));
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(server::serve(listener, shares));
+        (address, manifest)
+    }
+
+    #[tokio::test]
+    async fn shares_round_trip_over_http() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("com.example.app")).await.unwrap();
+        fs::write(src.join("com.example.app/main.obb"), vec![7u8; 300_000]).await.unwrap();
+        fs::write(src.join("base.apk"), b"apk").await.unwrap();
+        let (address, manifest) = serve_dir(&src).await;
+        assert_eq!(
+            manifest.files,
+            [
+                SharedFile { path: "base.apk".into(), size: 3 },
+                SharedFile { path: "com.example.app/main.obb".into(), size: 300_000 },
+            ]
+        );
+
+        let client = http_client();
+        assert_eq!(fetch_shares(&client, &address).await.unwrap(), std::slice::from_ref(&manifest));
+
+        let dest = dir.path().join("received");
+        let received = Mutex::new(0);
+        let token = CancellationToken::new();
+        receive_share(
+            &client,
+            &address,
+            &manifest,
+            &dest,
+            |n| *received.lock().unwrap() = n,
+            &token,
+        )
+        .await
+        .unwrap();
+        assert_eq!(*received.lock().unwrap(), 300_003);
+        assert_eq!(fs::read(dest.join("base.apk")).await.unwrap(), b"apk");
+        assert_eq!(fs::read(dest.join("com.example.app/main.obb")).await.unwrap().len(), 300_000);
+
+        // A file that changed since it was shared is refused and nothing is left behind
+        fs::write(src.join("base.apk"), b"changed").await.unwrap();
+        let dest = dir.path().join("again");
+        assert!(receive_share(&client, &address, &manifest, &dest, |_| {}, &token).await.is_err());
+        assert!(!dest.exists());
+
+        let unknown = ShareManifest { id: "missing".into(), ..manifest };
+        assert!(receive_share(&client, &address, &unknown, &dest, |_| {}, &token).await.is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! What share servers and receivers exchange: a JSON list of shares at `/shares` and the content
+//! of each shared file at `/shares/{id}/{index}`.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail, ensure};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, io::AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, instrument};
+
+use crate::downloader::tls;
+
+/// How long a peer gets to answer a request before it is considered gone
+pub(super) const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ShareManifest {
+    pub id: String,
+    pub name: String,
+    pub files: Vec<SharedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SharedFile {
+    /// Path relative to the shared directory, `/`-separated
+    pub path: String,
+    pub size: u64,
+}
+
+impl ShareManifest {
+    pub(crate) fn size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+pub(super) fn list_url(address: &str) -> String {
+    format!("http://{address}/shares")
+}
+
+fn file_url(address: &str, id: &str, index: usize) -> String {
+    format!("http://{address}/shares/{id}/{index}")
+}
+
+pub(crate) fn http_client() -> reqwest::Client {
+    tls::http_client_builder(tls::ServerPins::default())
+        .and_then(|builder| {
+            Ok(builder.connect_timeout(REQUEST_TIMEOUT).read_timeout(REQUEST_TIMEOUT).build()?)
+        })
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Lists the shares of the peer at `address` (`host:port`)
+pub(crate) async fn fetch_shares(
+    client: &reqwest::Client,
+    address: &str,
+) -> Result<Vec<ShareManifest>> {
+    client
+        .get(list_url(address))
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Peer {address} did not answer"))?
+        .json()
+        .await
+        .with_context(|| format!("Peer {address} sent an invalid share list"))
+}
+
+/// Resolves a path from a peer's manifest inside `root`, refusing anything that could leave it
+pub(super) fn local_path(root: &Path, relative: &str) -> Result<PathBuf> {
+    let mut path = root.to_path_buf();
+    for component in relative.split('/') {
+        ensure!(
+            !component.is_empty()
+                && component != "."
+                && component != ".."
+                && !component.contains(['\\', ':']),
+            "Invalid path in share: {relative}"
+        );
+        path.push(component);
+    }
+    Ok(path)
+}
+
+/// Receives every file of `manifest` from the peer at `address` into `dest`.
+///
+/// `on_progress` is called with the bytes received so far. `dest` must not exist yet and is
+/// removed again if receiving fails or is cancelled.
+#[instrument(level = "debug", skip(client, manifest, on_progress, token), fields(id = %manifest.id), err)]
+pub(crate) async fn receive_share(
+    client: &reqwest::Client,
+    address: &str,
+    manifest: &ShareManifest,
+    dest: &Path,
+    on_progress: impl Fn(u64),
+    token: &CancellationToken,
+) -> Result<()> {
+    ensure!(!fs::try_exists(dest).await.unwrap_or(true), "{} already exists", dest.display());
+    let targets = manifest
+        .files
+        .iter()
+        .map(|file| local_path(dest, &file.path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let result = async {
+        let mut received = 0;
+        for (index, (file, target)) in manifest.files.iter().zip(&targets).enumerate() {
+            debug!(path = %file.path, size = file.size, "Receiving file");
+            let url = file_url(address, &manifest.id, index);
+            received += receive_file(client, &url, file, target, received, &on_progress, token)
+                .await
+                .with_context(|| format!("Failed to receive {}", file.path))?;
+        }
+        Ok(())
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = fs::remove_dir_all(dest).await;
+    }
+    result
+}
+
+async fn receive_file(
+    client: &reqwest::Client,
+    url: &str,
+    file: &SharedFile,
+    target: &Path,
+    received_before: u64,
+    on_progress: &impl Fn(u64),
+    token: &CancellationToken,
+) -> Result<u64> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut response =
+        client.get(url).send().await.and_then(|response| response.error_for_status())?;
+    let mut out = fs::File::create(target).await?;
+    let mut received = 0;
+    loop {
+        let chunk = tokio::select! {
+            _ = token.cancelled() => bail!("Cancelled"),
+            chunk = response.chunk() => chunk?,
+        };
+        let Some(chunk) = chunk else { break };
+        received += chunk.len() as u64;
+        ensure!(received <= file.size, "Peer sent more than the expected {} bytes", file.size);
+        out.write_all(&chunk).await?;
+        on_progress(received_before + received);
+    }
+    out.flush().await?;
+    ensure!(received == file.size, "Expected {} bytes, got {received}", file.size);
+    Ok(received)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_paths_stay_inside_the_root() {
+        let root = Path::new("/downloads/App");
+        assert_eq!(
+            local_path(root, "com.example.app/main.obb").unwrap(),
+            root.join("com.example.app").join("main.obb")
+        );
+        for bad in ["../x", "a/../../x", "/etc/passwd", "a//b", "C:/x", "a\\..\\x", ""] {
+            assert!(local_path(root, bad).is_err(), "{bad}");
+        }
+    }
+}
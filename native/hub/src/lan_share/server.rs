@@ -0,0 +1,167 @@
+//! Minimal HTTP/1.1 server for shared apps. Only the files listed in a share's manifest can be
+//! requested, by their index, so nothing outside the shared directories is reachable.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use tokio::{
+    fs,
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+use tracing::{Instrument, debug, info_span, trace, warn};
+
+use super::protocol::ShareManifest;
+
+/// Longest request head accepted
+const MAX_HEAD_LEN: u64 = 8 * 1024;
+/// How long a client gets to send its request head
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A shared directory and the files in it that can be requested
+#[derive(Debug, Clone)]
+pub(super) struct SharedDir {
+    pub root: PathBuf,
+    pub manifest: ShareManifest,
+}
+
+pub(super) type Shares = Arc<Mutex<BTreeMap<String, SharedDir>>>;
+
+/// Accepts connections until the listener fails, serving each on its own task
+pub(super) async fn serve(listener: TcpListener, shares: Shares) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                warn!(error = &e as &dyn Error, "Failed to accept share connection");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        let shares = shares.clone();
+        tokio::spawn(
+            async move {
+                if let Err(e) = handle_connection(stream, &shares).await {
+                    debug!(error = e.as_ref() as &dyn Error, "Share request failed");
+                }
+            }
+            .instrument(info_span!("lan_share_connection", %peer)),
+        );
+    }
+}
+
+enum Route<'a> {
+    List,
+    File { id: &'a str, index: usize },
+}
+
+fn route(target: &str) -> Option<Route<'_>> {
+    let rest = target.strip_prefix("/shares")?;
+    if rest.is_empty() || rest == "/" {
+        return Some(Route::List);
+    }
+    let (id, index) = rest.strip_prefix('/')?.split_once('/')?;
+    Some(Route::File { id, index: index.parse().ok()? })
+}
+
+async fn handle_connection(mut stream: TcpStream, shares: &Shares) -> Result<()> {
+    let (reader, mut writer) = stream.split();
+    let mut reader = BufReader::new(reader.take(MAX_HEAD_LEN));
+
+    let head = tokio::time::timeout(HEAD_TIMEOUT, async {
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).await?;
+        // Headers are not needed, only read past them
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                break;
+            }
+        }
+        anyhow::Ok(request_line)
+    })
+    .await
+    .context("Timed out reading request")??;
+    trace!(request = head.trim(), "Share request");
+
+    let mut parts = head.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return respond(&mut writer, "400 Bad Request", "text/plain", b"Bad request").await;
+    };
+    if method != "GET" {
+        return respond(&mut writer, "405 Method Not Allowed", "text/plain", b"Only GET").await;
+    }
+
+    match route(target) {
+        Some(Route::List) => {
+            let manifests = {
+                let shares = shares.lock().expect("lan shares lock poisoned");
+                shares.values().map(|share| share.manifest.clone()).collect::<Vec<_>>()
+            };
+            let body = serde_json::to_vec(&manifests)?;
+            respond(&mut writer, "200 OK", "application/json", &body).await
+        }
+        Some(Route::File { id, index }) => {
+            let file = {
+                let shares = shares.lock().expect("lan shares lock poisoned");
+                shares.get(id).and_then(|share| {
+                    let file = share.manifest.files.get(index)?;
+                    Some((share.root.join(&file.path), file.size))
+                })
+            };
+            match file {
+                Some((path, size)) => send_file(&mut writer, path, size).await,
+                None => respond(&mut writer, "404 Not Found", "text/plain", b"Not found").await,
+            }
+        }
+        None => respond(&mut writer, "404 Not Found", "text/plain", b"Not found").await,
+    }
+}
+
+async fn respond(
+    writer: &mut (impl AsyncWrite + Unpin),
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> Result<()> {
+    write_head(writer, status, content_type, body.len() as u64).await?;
+    writer.write_all(body).await?;
+    writer.shutdown().await?;
+    Ok(())
+}
+
+async fn write_head(
+    writer: &mut (impl AsyncWrite + Unpin),
+    status: &str,
+    content_type: &str,
+    length: u64,
+) -> Result<()> {
+    let head = format!("HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\n");
+    writer.write_all(head.as_bytes()).await?;
+    let head = format!("Content-Length: {length}\r\nConnection: close\r\n\r\n");
+    writer.write_all(head.as_bytes()).await?;
+    Ok(())
+}
+
+/// Streams a shared file, as long as it still has the size listed in the manifest
+async fn send_file(writer: &mut (impl AsyncWrite + Unpin), path: PathBuf, size: u64) -> Result<()> {
+    let file = match fs::File::open(&path).await {
+        Ok(file) if file.metadata().await.is_ok_and(|meta| meta.len() == size) => file,
+        _ => {
+            respond(writer, "410 Gone", "text/plain", b"File changed").await?;
+            bail!("Shared file {} is gone or changed", path.display());
+        }
+    };
+    write_head(writer, "200 OK", "application/octet-stream", size).await?;
+    let sent = tokio::io::copy(&mut file.take(size), writer).await?;
+    writer.shutdown().await?;
+    debug!(path = %path.display(), sent, "Sent shared file");
+    Ok(())
+}
@@ -8,15 +8,23 @@ use std::{
     time::{Duration, Instant},
 };
 
-use adb::AdbService;
+use adb::{
+    AdbService, host_conflicts::HostConflictMonitor, shell_console::ShellConsole,
+    watchdog::AdbWatchdog,
+};
 use anyhow::{Context, Result};
 use logging::SignalLayer;
 use mimalloc::MiMalloc;
-use models::signals::system::{
-    AppShutdownReady, AppShutdownRequest, AppVersionInfo, MediaConfigChanged, RustPanic,
+use models::{
+    LogFileFormat,
+    signals::system::{
+        AppShutdownReady, AppShutdownRequest, AppVersionInfo, ResyncStateRequest, RustPanic,
+    },
 };
 use rinf::{DartSignal, RustSignal};
 use settings::SettingsHandler;
+use settings_profiles::SettingsProfiles;
+use storage_migration::StorageMigration;
 use task::TaskManager;
 use tokio::{sync::Notify, time::timeout};
 use tokio_stream::wrappers::WatchStream;
@@ -25,34 +33,65 @@ use tracing_appender::{
     non_blocking::WorkerGuard,
     rolling::{RollingFileAppender, Rotation},
 };
-use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt};
+use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt};
 
 use crate::{
+    app_updates::UpdatesTracker,
     backups_catalog::BackupsCatalog,
     casting::CastingManager,
+    device_log::DeviceLog,
+    diagnostics::Diagnostics,
+    disk_usage::DiskUsage,
     downloader::{
         controller::DownloaderController, downloads_catalog::DownloadsCatalog,
         manager::DownloaderManager,
     },
+    install_history::InstallHistory,
+    install_recipes::InstallRecipes,
+    lan_share::LanShareService,
+    media_cache::MediaCache,
+    metadata_store::MetadataStore,
+    mods_library::ModsLibrary,
+    transfer_stats::TransferStatistics,
+    updater::Updater,
 };
 
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 
-// Keep logging guard alive for the whole process lifetime
-static LOG_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+// Keep logging guards alive for the whole process lifetime
+static LOG_GUARDS: OnceLock<[WorkerGuard; 2]> = OnceLock::new();
 
 rinf::write_interface!();
 
 pub(crate) mod adb;
+pub(crate) mod api;
+pub(crate) mod app_updates;
 pub(crate) mod archive;
 pub(crate) mod backups_catalog;
 pub(crate) mod casting;
+pub(crate) mod device_log;
+pub(crate) mod diagnostics;
+pub(crate) mod disk_usage;
 pub(crate) mod downloader;
+pub mod headless;
+pub(crate) mod install_history;
+pub(crate) mod install_recipes;
+pub(crate) mod lan_share;
 pub(crate) mod logging;
+pub(crate) mod media_cache;
+pub(crate) mod metadata_store;
 pub(crate) mod models;
+pub(crate) mod mods_library;
 pub(crate) mod settings;
+pub(crate) mod settings_profiles;
+pub(crate) mod shutdown;
+pub(crate) mod source_check;
+pub(crate) mod storage_migration;
 pub(crate) mod task;
+pub(crate) mod transfer_stats;
+pub(crate) mod trash;
+pub(crate) mod updater;
 pub(crate) mod utils;
 
 pub(crate) mod built_info {
@@ -61,6 +100,8 @@ pub(crate) mod built_info {
 
 pub(crate) const USER_AGENT: &str = concat!("YAAS/", env!("CARGO_PKG_VERSION"));
 const TASK_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+/// Shorter wait after a panic, as the app may be in a broken state
+const PANIC_TASK_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
 
 fn main() {
     let portable_mode = std::env::args().any(|arg| arg == "--portable");
@@ -116,16 +157,20 @@ fn main() {
             };
 
             match source {
-                ShutdownSource::Panic => {}
+                ShutdownSource::Panic => {
+                    shutdown::shutdown(&task_manager, PANIC_TASK_SHUTDOWN_TIMEOUT).await;
+                }
                 ShutdownSource::Dart => {
                     tokio::select! {
-                        _ = task_manager.shutdown(TASK_SHUTDOWN_TIMEOUT) => {},
+                        _ = shutdown::shutdown(&task_manager, TASK_SHUTDOWN_TIMEOUT) => {},
                         _ = panic_notify.notified() => {},
                     }
                 }
                 ShutdownSource::Request => {
                     let shutdown_result = tokio::select! {
-                        result = task_manager.shutdown(TASK_SHUTDOWN_TIMEOUT) => Some(result),
+                        result = shutdown::shutdown(&task_manager, TASK_SHUTDOWN_TIMEOUT) => {
+                            Some(result)
+                        }
                         _ = panic_notify.notified() => None,
                     };
                     if let Some(shutdown_result) = shutdown_result {
@@ -182,25 +227,150 @@ async fn init_in_dir(app_dir: PathBuf, portable_mode: bool) -> Arc<TaskManager>
         git_dirty: built_info::GIT_DIRTY,
     }
     .send_signal_to_dart();
-
-    debug!("Creating settings handler");
-    let settings_handler = SettingsHandler::new(app_dir.clone(), portable_mode)
-        .expect("Failed to create settings handler");
+    api::start();
+
+    let core = start_core(&app_dir, portable_mode, true).await;
+    core.start_resync_handler();
+    let Core {
+        settings_handler,
+        metadata_store,
+        adb_service,
+        downloader_manager,
+        task_manager,
+        downloads_catalog,
+        ..
+    } = core;
 
     // Prepare media cache directory and send media configuration to Flutter
     let media_cache_dir = app_dir.join("media_cache");
     if let Err(e) = std::fs::create_dir_all(&media_cache_dir) {
         rinf::debug_print!("Failed to create media cache directory: {:#}", e);
     }
-    let media_base_url = "https://webdav.5698452.xyz/media/".to_string();
-    MediaConfigChanged { media_base_url, cache_dir: media_cache_dir.display().to_string() }
-        .send_signal_to_dart();
+    let media_cache =
+        MediaCache::new(&settings_handler.subscribe().borrow(), media_cache_dir).start();
+    media_cache.send_config();
+    media_cache.follow_settings(settings_handler.subscribe());
+
+    debug!("Starting host conflict monitor");
+    HostConflictMonitor::start(adb_service.clone(), settings_handler.subscribe());
+    AdbWatchdog::start(adb_service.clone());
+    ShellConsole::start(adb_service.clone(), settings_handler.subscribe());
+    trash::start_pruner(settings_handler.subscribe());
+    trash::start_request_handler(settings_handler.subscribe());
+    debug!("Creating backups catalog");
+    let backups_catalog = BackupsCatalog::start(WatchStream::new(settings_handler.subscribe()));
+    DiskUsage::start(
+        app_dir.clone(),
+        settings_handler.subscribe(),
+        task_manager.clone(),
+        downloads_catalog,
+        backups_catalog,
+    );
+    Diagnostics::start(
+        app_dir.clone(),
+        settings_handler.subscribe(),
+        adb_service.clone(),
+        downloader_manager.clone(),
+    );
+    StorageMigration::start(settings_handler.clone(), task_manager.clone());
+    SettingsProfiles::load(&app_dir, settings_handler.clone()).start();
+    source_check::start();
+    LanShareService::start(&settings_handler.subscribe().borrow().installation_id);
+    downloader::sweeper::start(settings_handler.subscribe().borrow().downloads_location());
+
+    debug!("Starting updates tracker");
+    UpdatesTracker::new(adb_service, downloader_manager, metadata_store).start();
+
+    // Casting-related requests (Windows-only)
+    debug!("Creating casting manager");
+    CastingManager::start(app_dir.clone());
+
+    debug!("Starting updater");
+    let check_for_updates = settings_handler.subscribe().borrow().check_for_updates;
+    match Updater::new() {
+        Ok(updater) => {
+            updater.start(check_for_updates);
+        }
+        Err(e) => error!(error = e.as_ref() as &dyn std::error::Error, "Failed to create updater"),
+    }
+
+    // Log-related requests from Flutter
+    debug!("Starting signal layer request handler");
+    SignalLayer::start_request_handler(app_dir.join("logs"));
+
+    task_manager
+}
+
+/// Services behind the task pipeline, shared by the app and the headless CLI
+struct Core {
+    settings_handler: Arc<SettingsHandler>,
+    metadata_store: Arc<MetadataStore>,
+    adb_service: Arc<AdbService>,
+    downloader_manager: Arc<DownloaderManager>,
+    task_manager: Arc<TaskManager>,
+    downloads_catalog: Arc<DownloadsCatalog>,
+    transfer_stats: Arc<TransferStatistics>,
+}
+
+impl Core {
+    /// Answers `ResyncStateRequest` by sending the current state of every service again
+    fn start_resync_handler(&self) {
+        let settings_handler = self.settings_handler.clone();
+        let adb_service = self.adb_service.clone();
+        let downloader_manager = self.downloader_manager.clone();
+        let task_manager = self.task_manager.clone();
+        let transfer_stats = self.transfer_stats.clone();
+        tokio::spawn(async move {
+            let receiver = ResyncStateRequest::get_dart_signal_receiver();
+            while receiver.recv().await.is_some() {
+                info!("UI requested state resync");
+                settings_handler.resend();
+                adb_service.resend_state().await;
+                HostConflictMonitor::resend();
+                AdbWatchdog::resend();
+                downloader_manager.resend_state().await;
+                task_manager.resync().await;
+                transfer_stats.resend();
+            }
+            panic!("ResyncStateRequest receiver closed");
+        });
+    }
+}
+
+/// Starts settings, ADB, the downloader and the task manager.
+///
+/// `persist_queue` keeps unfinished tasks across restarts, only the app itself should do that.
+async fn start_core(app_dir: &Path, portable_mode: bool, persist_queue: bool) -> Core {
+    debug!("Creating settings handler");
+    let settings_handler = SettingsHandler::new(app_dir.to_path_buf(), portable_mode)
+        .expect("Failed to create settings handler");
+    archive::follow_settings(settings_handler.subscribe());
+    logging::follow_settings(settings_handler.subscribe());
+
+    debug!("Creating metadata store");
+    let metadata_store = MetadataStore::load(app_dir.to_path_buf()).start();
+
+    debug!("Creating device log");
+    let device_log = DeviceLog::load(app_dir.to_path_buf()).start();
 
     debug!("Creating adb service");
-    let adb_service =
-        AdbService::new(WatchStream::new(settings_handler.subscribe()), app_dir.clone()).await;
+    let adb_service = AdbService::new(
+        settings_handler.clone(),
+        metadata_store.clone(),
+        device_log,
+        app_dir.to_path_buf(),
+    )
+    .await;
     debug!("Creating downloads catalog");
     let downloads_catalog = DownloadsCatalog::new(WatchStream::new(settings_handler.subscribe()));
+    debug!("Creating mods library");
+    let mods_library = ModsLibrary::start(app_dir.join("mods"));
+    debug!("Creating install history");
+    let install_history = InstallHistory::load(app_dir.to_path_buf()).start();
+    debug!("Creating install recipes");
+    let install_recipes = InstallRecipes::load(app_dir.to_path_buf()).start();
+    debug!("Creating transfer statistics");
+    let transfer_stats = TransferStatistics::load(app_dir.to_path_buf()).start();
     debug!("Creating downloader manager");
     let downloader_manager = DownloaderManager::new();
     debug!("Creating task manager");
@@ -208,45 +378,53 @@ async fn init_in_dir(app_dir: PathBuf, portable_mode: bool) -> Arc<TaskManager>
         adb_service.clone(),
         downloader_manager.clone(),
         downloads_catalog.clone(),
+        mods_library,
+        install_history,
+        install_recipes,
+        transfer_stats.clone(),
+        app_dir.to_path_buf(),
+        persist_queue,
         WatchStream::new(settings_handler.subscribe()),
     );
     debug!("Starting downloader manager");
     DownloaderController::new(
         downloader_manager.clone(),
-        app_dir.clone(),
+        app_dir.to_path_buf(),
         settings_handler.clone(),
+        metadata_store.clone(),
     )
     .start();
 
-    // Backups-related requests
-    debug!("Creating backups catalog");
-    let _backups_handler = BackupsCatalog::start(WatchStream::new(settings_handler.subscribe()));
-
-    // Casting-related requests (Windows-only)
-    debug!("Creating casting manager");
-    CastingManager::start(app_dir.clone());
-
-    // Log-related requests from Flutter
-    debug!("Starting signal layer request handler");
-    SignalLayer::start_request_handler(app_dir.join("logs"));
-
-    task_manager
+    Core {
+        settings_handler,
+        metadata_store,
+        adb_service,
+        downloader_manager,
+        task_manager,
+        downloads_catalog,
+        transfer_stats,
+    }
 }
 
 fn setup_logging(app_dir: &Path) -> Result<()> {
     let logs_dir = app_dir.join("logs");
-    let log_prefix = logs_dir.join("yaas_native");
+    let log_prefix = logs_dir.join(logging::NATIVE_LOGS_SUBDIR);
 
-    // Log to file
+    // Log to files, in the formats selected in settings
     std::fs::create_dir_all(&logs_dir).context("Failed to create logs directory")?;
-    let file_appender = RollingFileAppender::builder()
-        .rotation(Rotation::DAILY)
-        .max_log_files(10)
-        .filename_prefix("yaas")
-        .filename_suffix("log")
-        .build(&log_prefix)
-        .context("Failed to initialize file appender")?;
-    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+    let file_appender = |suffix| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .max_log_files(10)
+            .filename_prefix("yaas")
+            .filename_suffix(suffix)
+            .build(&log_prefix)
+            .context("Failed to initialize file appender")
+    };
+    let (pretty_writer, pretty_guard) =
+        tracing_appender::non_blocking(file_appender(logging::PRETTY_SUFFIX)?);
+    let (json_writer, json_guard) =
+        tracing_appender::non_blocking(file_appender(logging::JSON_SUFFIX)?);
 
     // Real-time logging to Flutter
     let (signal_layer, log_receiver) = SignalLayer::new();
@@ -257,16 +435,28 @@ fn setup_logging(app_dir: &Path) -> Result<()> {
         .with(
             tracing_subscriber::fmt::layer()
                 .with_ansi(false)
-                .with_writer(non_blocking)
+                .with_writer(pretty_writer)
                 // .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
-                .event_format(fmt::format().pretty()),
+                .event_format(fmt::format().pretty())
+                .with_filter(logging::format_filter(LogFileFormat::Pretty)),
+        )
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_ansi(false)
+                .with_writer(json_writer)
+                .with_file(true)
+                .with_line_number(true)
+                .with_current_span(false)
+                .with_span_list(true)
+                .with_filter(logging::format_filter(LogFileFormat::Json)),
         )
         .with(EnvFilter::new("debug,hyper_util=info"));
 
     tracing::subscriber::set_global_default(subscriber)
         .context("Failed to set global subscriber")?;
 
-    let _ = LOG_GUARD.set(guard);
+    let _ = LOG_GUARDS.set([pretty_guard, json_guard]);
     Ok(())
 }
 
@@ -0,0 +1,64 @@
+//! Format of the rolling log files, following the settings
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use tokio::sync::watch;
+use tracing::Metadata;
+use tracing_subscriber::filter::{FilterFn, filter_fn};
+
+use crate::models::{LogFileFormat, Settings};
+
+/// Suffix of the pretty log files
+pub(crate) const PRETTY_SUFFIX: &str = "log";
+/// Suffix of the JSON lines log files
+pub(crate) const JSON_SUFFIX: &str = "jsonl";
+
+/// Current `Settings::log_file_format`, pretty until settings are loaded
+static FILE_FORMAT: AtomicU8 = AtomicU8::new(LogFileFormat::Pretty as u8);
+
+/// Keeps the log file format in sync with the settings
+pub(crate) fn follow_settings(mut settings: watch::Receiver<Settings>) {
+    tokio::spawn(async move {
+        loop {
+            let format = settings.borrow_and_update().log_file_format;
+            FILE_FORMAT.store(format as u8, Ordering::Relaxed);
+            if settings.changed().await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn current_format() -> LogFileFormat {
+    match FILE_FORMAT.load(Ordering::Relaxed) {
+        value if value == LogFileFormat::Json as u8 => LogFileFormat::Json,
+        value if value == LogFileFormat::Both as u8 => LogFileFormat::Both,
+        _ => LogFileFormat::Pretty,
+    }
+}
+
+/// Whether files in `format` are written when the settings ask for `selected`
+fn writes(selected: LogFileFormat, format: LogFileFormat) -> bool {
+    selected == LogFileFormat::Both || selected == format
+}
+
+/// Per-layer filter passing events only while files in `format` are being written
+pub(crate) fn format_filter(format: LogFileFormat) -> FilterFn<impl Fn(&Metadata<'_>) -> bool> {
+    filter_fn(move |_| writes(current_format(), format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_formats_write_every_file() {
+        use crate::models::LogFileFormat::*;
+        assert!(writes(Pretty, Pretty));
+        assert!(!writes(Pretty, Json));
+        assert!(writes(Json, Json));
+        assert!(!writes(Json, Pretty));
+        assert!(writes(Both, Pretty));
+        assert!(writes(Both, Json));
+    }
+}
@@ -0,0 +1,471 @@
+//! Past log entries read back from the rolling log files
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use lazy_regex::{regex, regex_captures};
+use serde_json::{Map, Value};
+use time::{
+    Date, OffsetDateTime, format_description::well_known::Rfc3339, macros::format_description,
+};
+use tokio::fs;
+use tracing::{debug, instrument, warn};
+
+use super::files::{JSON_SUFFIX, PRETTY_SUFFIX};
+use crate::models::signals::logging::{
+    LogEntry, LogKind, LogLevel, QueryLogsRequest, QueryLogsResponse, SpanInfo, SpanTrace,
+};
+
+/// Subdirectory of the logs directory holding the rolling native log files
+pub(crate) const NATIVE_LOGS_SUBDIR: &str = "yaas_native";
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// Looks up past log entries matching a query, newest first
+#[instrument(level = "debug", skip(request), err)]
+pub(super) async fn query_logs(
+    logs_dir: &Path,
+    request: &QueryLogsRequest,
+) -> Result<QueryLogsResponse> {
+    let terms: Vec<String> =
+        request.search.split_whitespace().map(|term| term.to_lowercase()).collect();
+    let limit = if request.limit == 0 { usize::MAX } else { request.limit as usize };
+    let offset = request.offset as usize;
+
+    let mut total = 0usize;
+    let mut entries = Vec::new();
+    for day in list_log_files(&logs_dir.join(NATIVE_LOGS_SUBDIR)).await? {
+        // Files cover one UTC day, skip the ones entirely outside the requested range
+        if let Some(day_start) = day.day_start {
+            let outside = request.since.is_some_and(|since| day_start + DAY_MILLIS <= since as i64)
+                || request.until.is_some_and(|until| day_start >= until as i64);
+            if outside {
+                continue;
+            }
+        }
+        let parsed = read_day(&day).await;
+        for entry in parsed.into_iter().rev().filter(|entry| matches(entry, request, &terms)) {
+            if total >= offset && entries.len() < limit {
+                entries.push(entry);
+            }
+            total += 1;
+        }
+    }
+    debug!(total, returned = entries.len(), "Queried log files");
+
+    Ok(QueryLogsResponse { offset: request.offset, entries, total: total as u32, error: None })
+}
+
+fn matches(entry: &LogEntry, request: &QueryLogsRequest, terms: &[String]) -> bool {
+    if entry.level < request.min_level {
+        return false;
+    }
+    if !request.module.is_empty() && !entry.target.starts_with(&request.module) {
+        return false;
+    }
+    if request.since.is_some_and(|since| entry.timestamp < since)
+        || request.until.is_some_and(|until| entry.timestamp >= until)
+    {
+        return false;
+    }
+    if terms.is_empty() {
+        return true;
+    }
+    let message = entry.message.to_lowercase();
+    terms.iter().all(|term| message.contains(term.as_str()))
+}
+
+/// Log files written for the same period, in the pretty and/or JSON lines formats
+#[derive(Debug)]
+struct LogDay {
+    /// Start of the covered day in milliseconds since Unix epoch, if the file was rolled
+    day_start: Option<i64>,
+    pretty: Option<PathBuf>,
+    json: Option<PathBuf>,
+}
+
+/// Lists the log files grouped by the day they cover, newest first
+async fn list_log_files(dir: &Path) -> Result<Vec<LogDay>> {
+    let mut days = BTreeMap::new();
+    let mut rd = match fs::read_dir(dir).await {
+        Ok(rd) => rd,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", dir.display()));
+        }
+    };
+    while let Some(entry) = rd.next_entry().await? {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+        let Some((stem, suffix)) = name.rsplit_once('.') else { continue };
+        if !entry.file_type().await?.is_file() || ![PRETTY_SUFFIX, JSON_SUFFIX].contains(&suffix) {
+            continue;
+        }
+        let day = days.entry(stem.to_string()).or_insert_with(|| LogDay {
+            day_start: log_file_day(stem),
+            pretty: None,
+            json: None,
+        });
+        if suffix == JSON_SUFFIX {
+            day.json = Some(path);
+        } else {
+            day.pretty = Some(path);
+        }
+    }
+    // Rolled file names sort by date
+    Ok(days.into_values().rev().collect())
+}
+
+/// Start of the day a rolled log file like `yaas.2024-05-01.log` covers, given its name
+/// without the suffix
+fn log_file_day(stem: &str) -> Option<i64> {
+    let (_, date) = regex_captures!(r"\.(\d{4}-\d{2}-\d{2})$", stem)?;
+    let date = Date::parse(date, format_description!("[year]-[month]-[day]")).ok()?;
+    Some((date.midnight().assume_utc().unix_timestamp_nanos() / 1_000_000) as i64)
+}
+
+async fn read_log_file(path: &Path) -> Option<String> {
+    match fs::read(path).await {
+        Ok(content) => Some(String::from_utf8_lossy(&content).into_owned()),
+        Err(e) => {
+            warn!(path = %path.display(), error = %e, "Failed to read log file");
+            None
+        }
+    }
+}
+
+/// Reads the entries of one day in time order.
+///
+/// When both formats were written, JSON entries are preferred and pretty ones are only kept for
+/// the times the JSON file was not being written.
+async fn read_day(day: &LogDay) -> Vec<LogEntry> {
+    let mut entries = match &day.json {
+        Some(path) => {
+            read_log_file(path).await.map(|c| parse_json_log_file(&c)).unwrap_or_default()
+        }
+        None => Vec::new(),
+    };
+    let Some(path) = &day.pretty else { return entries };
+    let Some(content) = read_log_file(path).await else { return entries };
+    let pretty = parse_log_file(&content);
+    if entries.is_empty() {
+        return pretty;
+    }
+    let written: HashSet<(u64, String)> =
+        entries.iter().map(|entry| (entry.timestamp, entry.target.clone())).collect();
+    entries.extend(
+        pretty
+            .into_iter()
+            .filter(|entry| !written.contains(&(entry.timestamp, entry.target.clone()))),
+    );
+    entries.sort_by_key(|entry| entry.timestamp);
+    entries
+}
+
+/// Parses the contents of a log file written by the pretty formatter, in file order.
+///
+/// Each event starts with a `timestamp LEVEL target: message` line, followed by indented
+/// `at file:line` and `in target::span with fields` lines.
+fn parse_log_file(content: &str) -> Vec<LogEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<LogEntry> = None;
+    for line in content.lines() {
+        if let Some(entry) = parse_header(line) {
+            entries.extend(current.replace(entry));
+            continue;
+        }
+        let Some(entry) = current.as_mut() else { continue };
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(location) = line.strip_prefix("    at ") {
+            entry
+                .fields
+                .get_or_insert_with(BTreeMap::new)
+                .insert("location".to_string(), location.trim().to_string());
+        } else if let Some(span) = line.strip_prefix("    in ") {
+            // Spans are listed from the innermost one, traces start from the root
+            let trace = entry.span_trace.get_or_insert_with(|| SpanTrace { spans: Vec::new() });
+            trace.spans.insert(0, parse_span(span));
+        } else {
+            entry.message.push('\n');
+            entry.message.push_str(line);
+        }
+    }
+    entries.extend(current);
+    entries
+}
+
+fn parse_header(line: &str) -> Option<LogEntry> {
+    let (_, timestamp, level, target, message) =
+        regex_captures!(r"^  (\S+) +(TRACE|DEBUG|INFO|WARN|ERROR) (\S+?):(?: |$)(.*)$", line)?;
+    let timestamp = OffsetDateTime::parse(timestamp, &Rfc3339).ok()?;
+    Some(LogEntry {
+        timestamp: (timestamp.unix_timestamp_nanos() / 1_000_000) as u64,
+        level: parse_level(level)?,
+        target: target.to_string(),
+        message: message.to_string(),
+        kind: LogKind::Event,
+        fields: None,
+        span_trace: None,
+    })
+}
+
+fn parse_level(level: &str) -> Option<LogLevel> {
+    Some(match level {
+        "TRACE" => LogLevel::Trace,
+        "DEBUG" => LogLevel::Debug,
+        "INFO" => LogLevel::Info,
+        "WARN" => LogLevel::Warn,
+        "ERROR" => LogLevel::Error,
+        _ => return None,
+    })
+}
+
+/// Parses the contents of a log file written by the JSON formatter, one event per line.
+///
+/// Event fields other than the message, the source location and the fields of the spans the
+/// event was in are kept as structured data.
+fn parse_json_log_file(content: &str) -> Vec<LogEntry> {
+    content.lines().filter(|line| !line.trim().is_empty()).filter_map(parse_json_line).collect()
+}
+
+fn parse_json_line(line: &str) -> Option<LogEntry> {
+    let Value::Object(mut event) = serde_json::from_str(line).ok()? else { return None };
+    let timestamp = OffsetDateTime::parse(event.get("timestamp")?.as_str()?, &Rfc3339).ok()?;
+    let level = parse_level(event.get("level")?.as_str()?)?;
+    let target = event.get("target")?.as_str()?.to_string();
+
+    let mut fields = match event.remove("fields") {
+        Some(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    let message = fields.remove("message").map(json_to_string).unwrap_or_default();
+    let mut fields: BTreeMap<String, String> =
+        fields.into_iter().map(|(key, value)| (key, json_to_string(value))).collect();
+    if let (Some(file), Some(line)) = (
+        event.get("filename").and_then(Value::as_str),
+        event.get("line_number").and_then(Value::as_u64),
+    ) {
+        fields.insert("location".to_string(), format!("{file}:{line}"));
+    }
+
+    let spans: Vec<SpanInfo> = match event.remove("spans") {
+        Some(Value::Array(spans)) => spans.into_iter().filter_map(parse_json_span).collect(),
+        _ => Vec::new(),
+    };
+    Some(LogEntry {
+        timestamp: (timestamp.unix_timestamp_nanos() / 1_000_000) as u64,
+        level,
+        target,
+        message,
+        kind: LogKind::Event,
+        fields: (!fields.is_empty()).then_some(fields),
+        span_trace: (!spans.is_empty()).then_some(SpanTrace { spans }),
+    })
+}
+
+/// Parses a span from the `spans` list, which holds its name along with its fields
+fn parse_json_span(span: Value) -> Option<SpanInfo> {
+    let Value::Object(mut span) = span else { return None };
+    let name = span.remove("name").map(json_to_string)?;
+    let parameters: BTreeMap<String, String> =
+        span.into_iter().map(|(key, value)| (key, json_to_string(value))).collect();
+    Some(SpanInfo {
+        id: String::new(),
+        name,
+        target: String::new(),
+        parameters: (!parameters.is_empty()).then_some(parameters),
+    })
+}
+
+fn json_to_string(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Parses `target::name with key=value key2="quoted value"` into a span without an ID
+fn parse_span(span: &str) -> SpanInfo {
+    let (path, fields) = span.split_once(" with ").unwrap_or((span, ""));
+    let (target, name) = path.rsplit_once("::").unwrap_or(("", path));
+    let parameters: BTreeMap<String, String> = regex!(r#"(\w+)=("(?:[^"\\]|\\.)*"|\S+)"#)
+        .captures_iter(fields)
+        .map(|caps| (caps[1].to_string(), caps[2].trim_matches('"').to_string()))
+        .collect();
+    SpanInfo {
+        id: String::new(),
+        name: name.to_string(),
+        target: target.to_string(),
+        parameters: (!parameters.is_empty()).then_some(parameters),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = r#"  2024-05-01T10:00:00.000000Z  INFO hub::adb: Device connected, serial: "1WMHH"
+    at native/hub/src/adb/mod.rs:10
+    in hub::adb::refresh with serial="1WMHH" reason=hotplug
+    in hub::adb::service
+
+  2024-05-01T10:00:01.500000Z ERROR hub::task::install: Install failed
+Caused by:
+    at native/hub/src/task/install.rs:20
+
+  2024-05-01T10:00:02.000000Z DEBUG hub::downloader: Checking mirrors
+    at native/hub/src/downloader/mod.rs:30
+
+"#;
+
+    const JSON_LOG: &str = r#"{"timestamp":"2024-05-01T10:00:00.000000Z","level":"INFO","fields":{"message":"Device connected","serial":"1WMHH"},"target":"hub::adb","filename":"native/hub/src/adb/mod.rs","line_number":10,"spans":[{"name":"process_task","task_id":7},{"name":"refresh","serial":"1WMHH"}]}
+not json
+{"timestamp":"2024-05-01T12:00:00.250000Z","level":"WARN","fields":{"message":"Slow mirror"},"target":"hub::downloader"}
+"#;
+
+    fn request() -> QueryLogsRequest {
+        QueryLogsRequest {
+            min_level: LogLevel::Trace,
+            module: String::new(),
+            search: String::new(),
+            since: None,
+            until: None,
+            offset: 0,
+            limit: 0,
+        }
+    }
+
+    #[test]
+    fn parses_pretty_log_entries() {
+        let entries = parse_log_file(LOG);
+        assert_eq!(entries.len(), 3);
+
+        let first = &entries[0];
+        assert_eq!(first.timestamp, 1_714_557_600_000);
+        assert_eq!(first.target, "hub::adb");
+        assert_eq!(first.message, "Device connected, serial: \"1WMHH\"");
+        assert_eq!(first.fields.as_ref().unwrap()["location"], "native/hub/src/adb/mod.rs:10");
+        let spans = &first.span_trace.as_ref().unwrap().spans;
+        assert_eq!(
+            spans.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            ["service", "refresh"]
+        );
+        let parameters = spans[1].parameters.as_ref().unwrap();
+        assert_eq!(parameters["serial"], "1WMHH");
+        assert_eq!(parameters["reason"], "hotplug");
+
+        assert_eq!(entries[1].level, LogLevel::Error);
+        assert_eq!(entries[1].message, "Install failed\nCaused by:");
+        assert_eq!(entries[1].timestamp, 1_714_557_601_500);
+    }
+
+    #[tokio::test]
+    async fn queries_filter_and_page_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = dir.path().join(NATIVE_LOGS_SUBDIR);
+        fs::create_dir_all(&files).await.unwrap();
+        fs::write(files.join("yaas.2024-05-01.log"), LOG).await.unwrap();
+        fs::write(
+            files.join("yaas.2024-04-30.log"),
+            "  2024-04-30T23:00:00.000000Z  WARN hub::adb: Old warning\n",
+        )
+        .await
+        .unwrap();
+
+        let all = query_logs(dir.path(), &request()).await.unwrap();
+        assert_eq!(all.total, 4);
+        let messages = |response: &QueryLogsResponse| {
+            response
+                .entries
+                .iter()
+                .map(|e| e.message.lines().next().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(&all),
+            [
+                "Checking mirrors",
+                "Install failed",
+                "Device connected, serial: \"1WMHH\"",
+                "Old warning"
+            ]
+        );
+
+        let page = QueryLogsRequest { offset: 1, limit: 2, ..request() };
+        let response = query_logs(dir.path(), &page).await.unwrap();
+        assert_eq!((response.total, response.offset), (4, 1));
+        assert_eq!(messages(&response), ["Install failed", "Device connected, serial: \"1WMHH\""]);
+
+        let warnings = QueryLogsRequest { min_level: LogLevel::Warn, ..request() };
+        assert_eq!(
+            messages(&query_logs(dir.path(), &warnings).await.unwrap()),
+            ["Install failed", "Old warning"]
+        );
+
+        let adb =
+            QueryLogsRequest { module: "hub::adb".into(), search: "CONNECTED".into(), ..request() };
+        assert_eq!(
+            messages(&query_logs(dir.path(), &adb).await.unwrap()),
+            ["Device connected, serial: \"1WMHH\""]
+        );
+
+        let day = QueryLogsRequest {
+            since: Some(1_714_521_600_000),
+            until: Some(1_714_557_601_000),
+            ..request()
+        };
+        assert_eq!(
+            messages(&query_logs(dir.path(), &day).await.unwrap()),
+            ["Device connected, serial: \"1WMHH\""]
+        );
+    }
+
+    #[test]
+    fn parses_json_log_entries() {
+        let entries = parse_json_log_file(JSON_LOG);
+        assert_eq!(entries.len(), 2);
+
+        let first = &entries[0];
+        assert_eq!(first.timestamp, 1_714_557_600_000);
+        assert_eq!((first.level, first.target.as_str()), (LogLevel::Info, "hub::adb"));
+        assert_eq!(first.message, "Device connected");
+        let fields = first.fields.as_ref().unwrap();
+        assert_eq!(fields["serial"], "1WMHH");
+        assert_eq!(fields["location"], "native/hub/src/adb/mod.rs:10");
+        let spans = &first.span_trace.as_ref().unwrap().spans;
+        assert_eq!(
+            spans.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            ["process_task", "refresh"]
+        );
+        assert_eq!(spans[0].parameters.as_ref().unwrap()["task_id"], "7");
+
+        assert_eq!(entries[1].level, LogLevel::Warn);
+        assert_eq!(entries[1].timestamp, 1_714_564_800_250);
+        assert!(entries[1].fields.is_none() && entries[1].span_trace.is_none());
+    }
+
+    #[tokio::test]
+    async fn merges_days_written_in_both_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = dir.path().join(NATIVE_LOGS_SUBDIR);
+        fs::create_dir_all(&files).await.unwrap();
+        fs::write(files.join("yaas.2024-05-01.log"), LOG).await.unwrap();
+        fs::write(files.join("yaas.2024-05-01.jsonl"), JSON_LOG).await.unwrap();
+
+        let response = query_logs(dir.path(), &request()).await.unwrap();
+        let messages: Vec<_> = response
+            .entries
+            .iter()
+            .map(|e| e.message.lines().next().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            ["Slow mirror", "Checking mirrors", "Install failed", "Device connected"]
+        );
+    }
+}
@@ -16,9 +16,15 @@ use tracing_subscriber::layer::{Context, Layer};
 
 use crate::models::signals::logging::{
     GetLogsDirectoryRequest, GetLogsDirectoryResponse, LogBatch, LogEntry, LogKind, LogLevel,
-    SpanInfo, SpanTrace,
+    QueryLogsRequest, QueryLogsResponse, SpanInfo, SpanTrace,
 };
 
+mod files;
+mod history;
+
+pub(crate) use files::{JSON_SUFFIX, PRETTY_SUFFIX, follow_settings, format_filter};
+pub(crate) use history::NATIVE_LOGS_SUBDIR;
+
 /// Cached span field information stored in span extensions
 #[derive(Clone, Debug)]
 struct CachedSpanFields {
@@ -86,12 +92,38 @@ impl SignalLayer {
     pub(crate) fn start_request_handler(logs_dir: PathBuf) {
         tokio::spawn(async move {
             let directory_receiver = GetLogsDirectoryRequest::get_dart_signal_receiver();
+            let query_receiver = QueryLogsRequest::get_dart_signal_receiver();
 
-            while directory_receiver.recv().await.is_some() {
-                let logs_path = logs_dir.to_string_lossy().to_string();
-                GetLogsDirectoryResponse { path: logs_path }.send_signal_to_dart();
+            loop {
+                tokio::select! {
+                    request = directory_receiver.recv() => {
+                        if request.is_none() {
+                            panic!("GetLogsDirectoryRequest receiver closed");
+                        }
+                        let logs_path = logs_dir.to_string_lossy().to_string();
+                        GetLogsDirectoryResponse { path: logs_path }.send_signal_to_dart();
+                    }
+                    request = query_receiver.recv() => {
+                        let Some(request) = request else {
+                            panic!("QueryLogsRequest receiver closed");
+                        };
+                        let logs_dir = logs_dir.clone();
+                        tokio::spawn(async move {
+                            let request = request.message;
+                            match history::query_logs(&logs_dir, &request).await {
+                                Ok(response) => response.send_signal_to_dart(),
+                                Err(e) => QueryLogsResponse {
+                                    offset: request.offset,
+                                    entries: Vec::new(),
+                                    total: 0,
+                                    error: Some(format!("{e:#}")),
+                                }
+                                .send_signal_to_dart(),
+                            }
+                        });
+                    }
+                }
             }
-            panic!("GetLogsDirectoryRequest receiver closed");
         });
     }
 
@@ -0,0 +1,673 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result, bail};
+use reqwest::{StatusCode, header::RANGE};
+use rinf::{DartSignal, RustSignal};
+use tokio::{fs, sync::watch};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::{
+    adb::PackageName,
+    downloader::{SensitiveUrl, http_cache, tls},
+    models::{
+        Settings,
+        apk_info::{extract_launcher_icon, get_apk_info},
+        signals::{media::*, system::MediaConfigChanged},
+    },
+};
+
+/// Largest thumbnail that is downloaded
+const MAX_THUMBNAIL_SIZE: u64 = 10 << 20;
+/// Largest trailer that is downloaded
+const MAX_TRAILER_SIZE: u64 = 256 << 20;
+/// Least recently validated files are evicted once the cache grows past this
+const MAX_CACHE_SIZE: u64 = 2 << 30;
+/// Cached files validated more recently than this are served without asking the server
+const REVALIDATE_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+/// Cache directory of launcher icons extracted from local APKs
+const ICONS_DIR: &str = "icons";
+/// Validators of the cached files, kept by `http_cache` in the cache directory
+const HTTP_META_FILE: &str = "meta.json";
+
+impl MediaKind {
+    /// Directory of this kind of media, both on the server and in the cache
+    fn dir(self) -> &'static str {
+        match self {
+            MediaKind::Thumbnail => "thumbnails",
+            MediaKind::Trailer => "videos",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            MediaKind::Thumbnail => "jpg",
+            MediaKind::Trailer => "mp4",
+        }
+    }
+
+    fn max_size(self) -> u64 {
+        match self {
+            MediaKind::Thumbnail => MAX_THUMBNAIL_SIZE,
+            MediaKind::Trailer => MAX_TRAILER_SIZE,
+        }
+    }
+
+    const ALL: [MediaKind; 2] = [MediaKind::Thumbnail, MediaKind::Trailer];
+}
+
+/// Downloads app thumbnails and trailers for the UI and keeps them on disk.
+///
+/// Files are revalidated with the server at most once per `REVALIDATE_AFTER` and served from
+/// disk when the server cannot be reached.
+#[derive(Debug)]
+pub(crate) struct MediaCache {
+    server: RwLock<MediaServer>,
+    cache_dir: PathBuf,
+    /// Serializes fetches of the same file, entries are dropped once no fetch uses them
+    file_locks: Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+/// Media server of the settings and the client for it
+#[derive(Debug, Clone)]
+struct MediaServer {
+    /// Ends with a slash
+    base_url: String,
+    /// SPKI pins the server must match
+    pinned_spki: Vec<String>,
+    /// `None` if no client could be built for the pins, nothing is fetched then
+    client: Option<reqwest::Client>,
+}
+
+impl MediaServer {
+    fn from_settings(settings: &Settings) -> Self {
+        let pins = settings.media_server_pins();
+        let client = tls::http_client_builder(pins)
+            .and_then(|builder| {
+                builder
+                    .connect_timeout(Duration::from_secs(10))
+                    .build()
+                    .context("Failed to build HTTP client")
+            })
+            .inspect_err(|e| {
+                error!(error = e.as_ref() as &dyn Error, "Failed to set up media server client")
+            })
+            .ok();
+        Self { base_url: settings.media_base_url(), pinned_spki: pins.spki.to_vec(), client }
+    }
+}
+
+impl MediaCache {
+    pub(crate) fn new(settings: &Settings, cache_dir: PathBuf) -> Self {
+        Self {
+            server: RwLock::new(MediaServer::from_settings(settings)),
+            cache_dir,
+            file_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Tells Dart where media is fetched from and cached
+    pub(crate) fn send_config(&self) {
+        MediaConfigChanged {
+            media_base_url: self.base_url(),
+            cache_dir: self.cache_dir.display().to_string(),
+        }
+        .send_signal_to_dart();
+    }
+
+    /// Switches to the media server of the settings whenever it changes
+    pub(crate) fn follow_settings(self: &Arc<Self>, mut settings: watch::Receiver<Settings>) {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            while settings.changed().await.is_ok() {
+                let server = MediaServer::from_settings(&settings.borrow_and_update());
+                let current = handle.server();
+                if server.base_url != current.base_url || server.pinned_spki != current.pinned_spki
+                {
+                    info!(base_url = %SensitiveUrl::new(&server.base_url), "Media server changed");
+                    *handle.server.write().expect("media server lock poisoned") = server;
+                    handle.send_config();
+                }
+            }
+        });
+    }
+
+    fn server(&self) -> MediaServer {
+        self.server.read().expect("media server lock poisoned").clone()
+    }
+
+    fn base_url(&self) -> String {
+        self.server().base_url
+    }
+
+    fn client(&self) -> Result<reqwest::Client> {
+        self.server().client.context("Media server client is not available, check the pinned keys")
+    }
+
+    /// Starts handling media requests from Dart.
+    pub(crate) fn start(self) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move {
+                if let Err(e) = remove_loose_files(&handle.cache_dir).await {
+                    warn!(error = e.as_ref() as &dyn Error, "Failed to clean up media cache");
+                }
+                handle.receive_signals().await
+            }
+        });
+        handle
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let media_receiver = GetAppMediaRequest::get_dart_signal_receiver();
+        let apk_info_receiver = GetApkInfoRequest::get_dart_signal_receiver();
+        loop {
+            tokio::select! {
+                request = media_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("GetAppMediaRequest receiver closed");
+                    };
+                    let GetAppMediaRequest { package_name, kind, fetch } = request.message;
+                    debug!(%package_name, ?kind, fetch, "Received GetAppMediaRequest");
+                    // Trailers can take a while, so requests are handled concurrently
+                    tokio::spawn({
+                        let handle = self.clone();
+                        async move { handle.handle_request(package_name, kind, fetch).await }
+                    });
+                }
+                request = apk_info_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("GetApkInfoRequest receiver closed");
+                    };
+                    let path = request.message.path;
+                    debug!(path, "Received GetApkInfoRequest");
+                    tokio::spawn({
+                        let handle = self.clone();
+                        async move { handle.handle_apk_info_request(path).await }
+                    });
+                }
+            }
+        }
+    }
+
+    async fn handle_request(self: Arc<Self>, package_name: String, kind: MediaKind, fetch: bool) {
+        let result = async {
+            let package = PackageName::parse(&package_name)?;
+            // Trailers are large, so they play from the server while being cached in the
+            // background, unless a copy is cached already. The player does not check pinned
+            // keys, so trailers of a pinned server are cached before they play.
+            let pinned = !self.server().pinned_spki.is_empty();
+            if fetch && kind == MediaKind::Trailer && !pinned {
+                self.fetch_in_background(package.clone(), kind);
+            } else if fetch {
+                let path = self.fetch(&package, kind).await?;
+                return Ok((path.is_some(), path, None));
+            }
+            let path = self.local_path(&package, kind);
+            if path.exists() {
+                return Ok((true, Some(path), None));
+            }
+            let available = self.exists_remote(&package, kind).await?;
+            let stream_url = (fetch && available).then(|| self.url(&package, kind));
+            Ok::<_, anyhow::Error>((available, None, stream_url))
+        }
+        .await;
+
+        let response = match result {
+            Ok((available, path, stream_url)) => AppMediaResponse {
+                package_name,
+                kind,
+                available,
+                path: path.map(|path| path.to_string_lossy().into_owned()),
+                stream_url,
+                error: None,
+            },
+            Err(e) => {
+                error!(error = e.as_ref() as &dyn Error, %package_name, ?kind, "Failed to get app media");
+                AppMediaResponse {
+                    package_name,
+                    kind,
+                    available: false,
+                    path: None,
+                    stream_url: None,
+                    error: Some(format!("{e:#}")),
+                }
+            }
+        };
+        response.send_signal_to_dart();
+    }
+
+    async fn handle_apk_info_request(&self, path: String) {
+        let icons_dir = self.cache_dir.join(ICONS_DIR);
+        let apk_path = PathBuf::from(&path);
+        let result = tokio::task::spawn_blocking(move || {
+            let info = get_apk_info(&apk_path)?;
+            let icon_stem = icons_dir.join(format!(
+                "{}-{}",
+                info.package_name,
+                info.version_code.unwrap_or_default()
+            ));
+            let icon_path = extract_launcher_icon(&apk_path, &icon_stem)
+                .inspect_err(|e| {
+                    warn!(error = e.as_ref() as &dyn Error, "Failed to extract launcher icon");
+                })
+                .ok()
+                .flatten();
+            Ok::<_, anyhow::Error>((info, icon_path))
+        })
+        .await
+        .context("APK info task failed")
+        .and_then(|r| r);
+
+        let response = match result {
+            Ok((info, icon_path)) => {
+                if let Some(icon_path) = &icon_path
+                    && let Err(e) = self.evict(MAX_CACHE_SIZE, icon_path).await
+                {
+                    warn!(error = e.as_ref() as &dyn Error, "Failed to evict old media");
+                }
+                ApkInfoResponse {
+                    path,
+                    package_name: Some(info.package_name),
+                    label: info.application_label,
+                    version_code: info.version_code,
+                    version_name: info.version_name,
+                    min_sdk_version: info.min_sdk_version,
+                    target_sdk_version: info.target_sdk_version,
+                    permissions: info.permissions,
+                    native_abis: info.native_abis,
+                    icon_path: icon_path.map(|p| p.to_string_lossy().into_owned()),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                error!(error = e.as_ref() as &dyn Error, path, "Failed to read APK info");
+                ApkInfoResponse {
+                    path,
+                    package_name: None,
+                    label: None,
+                    version_code: None,
+                    version_name: None,
+                    min_sdk_version: None,
+                    target_sdk_version: None,
+                    permissions: Vec::new(),
+                    native_abis: Vec::new(),
+                    icon_path: None,
+                    error: Some(format!("{e:#}")),
+                }
+            }
+        };
+        response.send_signal_to_dart();
+    }
+
+    fn url(&self, package: &PackageName, kind: MediaKind) -> String {
+        format!("{}{}/{package}.{}", self.base_url(), kind.dir(), kind.extension())
+    }
+
+    fn local_path(&self, package: &PackageName, kind: MediaKind) -> PathBuf {
+        self.cache_dir.join(kind.dir()).join(format!("{package}.{}", kind.extension()))
+    }
+
+    fn fetch_in_background(self: &Arc<Self>, package: PackageName, kind: MediaKind) {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle.fetch(&package, kind).await {
+                warn!(error = e.as_ref() as &dyn Error, %package, ?kind, "Failed to cache media");
+            }
+        });
+    }
+
+    /// Returns the cached media file, downloading or revalidating it when needed.
+    ///
+    /// Returns `None` if the server does not have this media. A stale copy is returned when the
+    /// server cannot be reached.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn fetch(
+        &self,
+        package: &PackageName,
+        kind: MediaKind,
+    ) -> Result<Option<PathBuf>> {
+        let path = self.local_path(package, kind);
+        let lock = self
+            .file_locks
+            .lock()
+            .expect("media file locks poisoned")
+            .entry(path.clone())
+            .or_default()
+            .clone();
+        let guard = lock.lock().await;
+        let result = self.fetch_locked(package, kind, path.clone()).await;
+        drop(guard);
+
+        let mut locks = self.file_locks.lock().expect("media file locks poisoned");
+        // Held by the map and this fetch only, other fetches take it while holding the map
+        if Arc::strong_count(&lock) == 2 {
+            locks.remove(&path);
+        }
+        result
+    }
+
+    async fn fetch_locked(
+        &self,
+        package: &PackageName,
+        kind: MediaKind,
+        path: PathBuf,
+    ) -> Result<Option<PathBuf>> {
+        if validated_within(&path, REVALIDATE_AFTER).await {
+            debug!(path = %path.display(), "Serving fresh cached media");
+            return Ok(Some(path));
+        }
+
+        let dir = self.cache_dir.join(kind.dir());
+        fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let url = self.url(package, kind);
+        let result = match self.client() {
+            Ok(client) => {
+                http_cache::update_file_cached_limited(
+                    &client,
+                    &url,
+                    &path,
+                    &self.cache_dir,
+                    kind.max_size(),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(result) => {
+                debug!(?result, path = %path.display(), "Media is up to date");
+                if let Err(e) = mark_validated(&path) {
+                    warn!(error = &e as &dyn Error, path = %path.display(), "Failed to mark media as validated");
+                }
+                if let Err(e) = self.evict(MAX_CACHE_SIZE, &path).await {
+                    warn!(error = e.as_ref() as &dyn Error, "Failed to evict old media");
+                }
+                Ok(Some(path))
+            }
+            Err(e) if is_not_found(&e) => {
+                debug!(url = %SensitiveUrl::new(&url), "Media not found on server");
+                if path.exists() {
+                    fs::remove_file(&path)
+                        .await
+                        .with_context(|| format!("Failed to remove {}", path.display()))?;
+                }
+                Ok(None)
+            }
+            Err(e) if path.exists() => {
+                warn!(
+                    error = e.as_ref() as &dyn Error,
+                    path = %path.display(),
+                    "Failed to revalidate media, serving cached copy"
+                );
+                Ok(Some(path))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Checks whether the server has the media without downloading it
+    #[instrument(level = "debug", skip(self), err)]
+    async fn exists_remote(&self, package: &PackageName, kind: MediaKind) -> Result<bool> {
+        let url = self.url(package, kind);
+        let sanitized_url = SensitiveUrl::new(&url);
+        let client = self.client()?;
+        let mut status = client
+            .head(&url)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Failed to request {sanitized_url}"))?
+            .status();
+        if matches!(status, StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED) {
+            status = client
+                .get(&url)
+                .header(RANGE, "bytes=0-0")
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url)
+                .with_context(|| format!("Failed to request {sanitized_url}"))?
+                .status();
+        }
+        match status {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => bail!("Unexpected status {status} for {sanitized_url}"),
+        }
+    }
+
+    /// Removes the least recently validated files until the cache fits in `max_size`,
+    /// never removing `keep`.
+    async fn evict(&self, max_size: u64, keep: &Path) -> Result<()> {
+        let mut files = Vec::new();
+        let dirs = MediaKind::ALL.map(MediaKind::dir).into_iter().chain([ICONS_DIR]);
+        for dir in dirs {
+            let dir = self.cache_dir.join(dir);
+            let Ok(mut entries) = fs::read_dir(&dir).await else {
+                continue;
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_file() {
+                    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    files.push((modified, metadata.len(), entry.path()));
+                }
+            }
+        }
+
+        let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+        if total <= max_size {
+            return Ok(());
+        }
+        files.sort();
+        for (_, size, path) in files {
+            if total <= max_size {
+                break;
+            }
+            if path == keep {
+                continue;
+            }
+            fs::remove_file(&path)
+                .await
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            total -= size;
+            debug!(path = %path.display(), size, "Evicted cached media");
+        }
+        info!(total, max_size, "Evicted old media from cache");
+        Ok(())
+    }
+}
+
+/// Removes files directly in the cache directory other than the HTTP cache metadata.
+///
+/// Earlier versions cached media in the UI, leaving its files and index there.
+async fn remove_loose_files(cache_dir: &Path) -> Result<()> {
+    let mut entries = match fs::read_dir(cache_dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).context("Failed to read media cache directory"),
+    };
+    let mut removed = 0;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_file() && entry.file_name() != HTTP_META_FILE {
+            fs::remove_file(entry.path())
+                .await
+                .with_context(|| format!("Failed to remove {}", entry.path().display()))?;
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        info!(removed, "Removed old files from media cache");
+    }
+    Ok(())
+}
+
+/// Whether `path` exists and was validated with the server within `max_age`
+async fn validated_within(path: &Path, max_age: Duration) -> bool {
+    fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age < max_age))
+}
+
+/// Records a successful validation in the file's modification time
+fn mark_validated(path: &Path) -> std::io::Result<()> {
+    std::fs::File::options().write(true).open(path)?.set_modified(SystemTime::now())
+}
+
+fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|cause| cause.status() == Some(StatusCode::NOT_FOUND))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::*;
+
+    fn package(name: &str) -> PackageName {
+        PackageName::parse(name).unwrap()
+    }
+
+    fn media_settings(base_url: &str) -> Settings {
+        let mut settings = Settings::default();
+        settings.media_base_url = base_url.to_string();
+        settings
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fetch_caches_and_serves_offline() {
+        let dir = tempdir().unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/thumbnails/com.example.app.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"JPEG"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let cache = MediaCache::new(&media_settings(&server.uri()), dir.path().to_path_buf());
+        let app = package("com.example.app");
+
+        let path = cache.fetch(&app, MediaKind::Thumbnail).await.unwrap().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"JPEG");
+        // Fresh copies are served without another request
+        assert_eq!(cache.fetch(&app, MediaKind::Thumbnail).await.unwrap(), Some(path.clone()));
+        server.verify().await;
+
+        // Stale copies are still served when the server fails
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() - REVALIDATE_AFTER * 2)
+            .unwrap();
+        server.reset().await;
+        Mock::given(method("GET")).respond_with(ResponseTemplate::new(503)).mount(&server).await;
+        assert_eq!(cache.fetch(&app, MediaKind::Thumbnail).await.unwrap(), Some(path));
+        assert!(cache.file_locks.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pinned_server_is_only_reached_over_https() {
+        let dir = tempdir().unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"JPEG"))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let mut settings = media_settings(&server.uri());
+        settings.pinned_spki =
+            vec!["sha256/lLYhCKBO3yUgPMDC0E8cCy41d1UWmBxbU4d2cITUlu4=".to_string()];
+        let cache = MediaCache::new(&settings, dir.path().to_path_buf());
+
+        assert!(cache.fetch(&package("com.example.app"), MediaKind::Thumbnail).await.is_err());
+        server.verify().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn missing_and_oversized_media() {
+        let dir = tempdir().unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/videos/com.example.huge.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![
+                0;
+                MAX_TRAILER_SIZE as usize
+                    + 1
+            ]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET")).respond_with(ResponseTemplate::new(404)).mount(&server).await;
+        let cache = MediaCache::new(&media_settings(&server.uri()), dir.path().to_path_buf());
+
+        assert_eq!(
+            cache.fetch(&package("com.example.none"), MediaKind::Trailer).await.unwrap(),
+            None
+        );
+        let huge = package("com.example.huge");
+        assert!(cache.fetch(&huge, MediaKind::Trailer).await.is_err());
+        assert!(!cache.local_path(&huge, MediaKind::Trailer).exists());
+    }
+
+    #[tokio::test]
+    async fn removes_loose_files_only() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("cache_info.json"), b"{}").unwrap();
+        std::fs::write(dir.path().join(HTTP_META_FILE), b"{}").unwrap();
+        std::fs::write(dir.path().join("8c1b.jpg"), b"JPEG").unwrap();
+        std::fs::create_dir_all(dir.path().join("thumbnails")).unwrap();
+        std::fs::write(dir.path().join("thumbnails").join("com.example.app.jpg"), b"JPEG").unwrap();
+
+        remove_loose_files(dir.path()).await.unwrap();
+        let mut names: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, [HTTP_META_FILE, "thumbnails"]);
+        assert!(dir.path().join("thumbnails").join("com.example.app.jpg").exists());
+    }
+
+    #[tokio::test]
+    async fn evict_removes_least_recently_validated_files() {
+        let dir = tempdir().unwrap();
+        let cache = MediaCache::new(&media_settings("http://localhost/"), dir.path().to_path_buf());
+        let now = SystemTime::now();
+        let mut paths = Vec::new();
+        for (age, name) in [(3, "com.example.old"), (2, "com.example.mid"), (1, "com.example.new")]
+        {
+            let path = cache.local_path(&package(name), MediaKind::Thumbnail);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, [0; 10]).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs(age * 60))
+                .unwrap();
+            paths.push(path);
+        }
+
+        cache.evict(15, &paths[0]).await.unwrap();
+        assert!(paths[0].exists());
+        assert!(!paths[1].exists());
+        assert!(!paths[2].exists());
+    }
+}
@@ -0,0 +1,308 @@
+use std::{error::Error, path::PathBuf, sync::Arc};
+
+use anyhow::{Context, Result};
+use rinf::{DartSignal, RustSignal};
+use tokio::sync::watch;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::{
+    adb::PackageName,
+    models::{AppMetadata, signals::metadata::*},
+};
+
+/// Persists user-defined per-app metadata and broadcasts changes to subscribers
+#[derive(Debug)]
+pub(crate) struct MetadataStore {
+    file_path: PathBuf,
+    watch_tx: watch::Sender<AppMetadata>,
+}
+
+impl MetadataStore {
+    /// Loads the metadata file from the app directory, falling back to empty metadata.
+    #[instrument(level = "debug")]
+    pub(crate) fn load(app_dir: PathBuf) -> Self {
+        let file_path = app_dir.join("app_metadata.json");
+        let metadata = if file_path.exists() {
+            AppMetadata::load_from_file(&file_path).unwrap_or_else(|e| {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to load app metadata, using empty");
+                AppMetadata::default()
+            })
+        } else {
+            AppMetadata::default()
+        };
+        debug!(
+            name_overrides = metadata.name_overrides.len(),
+            version_pins = metadata.version_pins.len(),
+            watched_apps = metadata.watched_apps.len(),
+            "Loaded app metadata"
+        );
+
+        Self { file_path, watch_tx: watch::Sender::new(metadata) }
+    }
+
+    /// Starts handling metadata requests from Dart.
+    pub(crate) fn start(self) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        handle
+    }
+
+    /// Create a receiver for metadata changes
+    pub(crate) fn subscribe(&self) -> watch::Receiver<AppMetadata> {
+        self.watch_tx.subscribe()
+    }
+
+    /// Returns a snapshot of the current metadata.
+    pub(crate) fn current(&self) -> AppMetadata {
+        self.watch_tx.borrow().clone()
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let get_overrides_receiver = GetAppNameOverridesRequest::get_dart_signal_receiver();
+        let set_override_receiver = SetAppNameOverrideRequest::get_dart_signal_receiver();
+        let get_pins_receiver = GetAppVersionPinsRequest::get_dart_signal_receiver();
+        let set_pin_receiver = SetAppVersionPinRequest::get_dart_signal_receiver();
+        let set_ignored_receiver = SetIgnoredAppVersionRequest::get_dart_signal_receiver();
+        let get_watched_receiver = GetWatchedAppsRequest::get_dart_signal_receiver();
+        let set_watched_receiver = SetAppWatchedRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = get_overrides_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received GetAppNameOverridesRequest");
+                        self.send_name_overrides(None);
+                    } else {
+                        panic!("GetAppNameOverridesRequest receiver closed");
+                    }
+                }
+                request = set_override_receiver.recv() => {
+                    if let Some(request) = request {
+                        let SetAppNameOverrideRequest { package_name, name } = request.message;
+                        debug!(%package_name, ?name, "Received SetAppNameOverrideRequest");
+                        let result = self.set_name_override(&package_name, name.as_deref());
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to set app name override");
+                        }
+                        self.send_name_overrides(result.err().map(|e| format!("{e:#}")));
+                    } else {
+                        panic!("SetAppNameOverrideRequest receiver closed");
+                    }
+                }
+                request = get_pins_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received GetAppVersionPinsRequest");
+                        self.send_version_pins(None);
+                    } else {
+                        panic!("GetAppVersionPinsRequest receiver closed");
+                    }
+                }
+                request = set_pin_receiver.recv() => {
+                    if let Some(request) = request {
+                        let SetAppVersionPinRequest { package_name, version_code } = request.message;
+                        debug!(%package_name, ?version_code, "Received SetAppVersionPinRequest");
+                        let result = self.set_version_pin(&package_name, version_code);
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to set app version pin");
+                        }
+                        self.send_version_pins(result.err().map(|e| format!("{e:#}")));
+                    } else {
+                        panic!("SetAppVersionPinRequest receiver closed");
+                    }
+                }
+                request = set_ignored_receiver.recv() => {
+                    if let Some(request) = request {
+                        let SetIgnoredAppVersionRequest { package_name, version_code, ignored } =
+                            request.message;
+                        debug!(%package_name, version_code, ignored, "Received SetIgnoredAppVersionRequest");
+                        let result = self.set_version_ignored(&package_name, version_code, ignored);
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to update ignored app versions");
+                        }
+                        self.send_version_pins(result.err().map(|e| format!("{e:#}")));
+                    } else {
+                        panic!("SetIgnoredAppVersionRequest receiver closed");
+                    }
+                }
+                request = get_watched_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received GetWatchedAppsRequest");
+                        self.send_watched_apps(None);
+                    } else {
+                        panic!("GetWatchedAppsRequest receiver closed");
+                    }
+                }
+                request = set_watched_receiver.recv() => {
+                    if let Some(request) = request {
+                        let SetAppWatchedRequest { package_name, watched } = request.message;
+                        debug!(%package_name, watched, "Received SetAppWatchedRequest");
+                        let result = self.set_watched(&package_name, watched);
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to update watched apps");
+                        }
+                        self.send_watched_apps(result.err().map(|e| format!("{e:#}")));
+                    } else {
+                        panic!("SetAppWatchedRequest receiver closed");
+                    }
+                }
+            }
+        }
+    }
+
+    fn send_name_overrides(&self, error: Option<String>) {
+        let overrides = self
+            .watch_tx
+            .borrow()
+            .name_overrides
+            .iter()
+            .map(|(package_name, name)| AppNameOverride {
+                package_name: package_name.clone(),
+                name: name.clone(),
+            })
+            .collect();
+        AppNameOverridesChanged { overrides, error }.send_signal_to_dart();
+    }
+
+    fn send_version_pins(&self, error: Option<String>) {
+        let pins = self
+            .watch_tx
+            .borrow()
+            .version_pins
+            .iter()
+            .map(|(package_name, pin)| AppVersionPin {
+                package_name: package_name.clone(),
+                pinned_version_code: pin.pinned_version_code,
+                ignored_version_codes: pin.ignored_version_codes.iter().copied().collect(),
+            })
+            .collect();
+        AppVersionPinsChanged { pins, error }.send_signal_to_dart();
+    }
+
+    fn send_watched_apps(&self, error: Option<String>) {
+        let apps = self
+            .watch_tx
+            .borrow()
+            .watched_apps
+            .iter()
+            .map(|(package_name, app)| WatchedAppEntry {
+                package_name: package_name.clone(),
+                seen_version_code: app.seen_version_code,
+            })
+            .collect();
+        WatchedAppsChanged { apps, error }.send_signal_to_dart();
+    }
+
+    /// Sets or clears the display name override for a package.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn set_name_override(&self, package_name: &str, name: Option<&str>) -> Result<()> {
+        let package_name = PackageName::parse(package_name)?;
+        self.update(|metadata| metadata.set_name_override(package_name.as_str(), name))
+    }
+
+    /// Pins a package to a version code, or unpins it with `None`.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn set_version_pin(
+        &self,
+        package_name: &str,
+        version_code: Option<u64>,
+    ) -> Result<()> {
+        let package_name = PackageName::parse(package_name)?;
+        self.update(|metadata| metadata.set_version_pin(package_name.as_str(), version_code))
+    }
+
+    /// Ignores or stops ignoring a catalog version of a package for updates.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn set_version_ignored(
+        &self,
+        package_name: &str,
+        version_code: u64,
+        ignored: bool,
+    ) -> Result<()> {
+        let package_name = PackageName::parse(package_name)?;
+        self.update(|metadata| {
+            metadata.set_version_ignored(package_name.as_str(), version_code, ignored)
+        })
+    }
+
+    /// Adds a catalog app to the watched apps or removes it.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn set_watched(&self, package_name: &str, watched: bool) -> Result<()> {
+        let package_name = PackageName::parse(package_name)?;
+        self.update(|metadata| metadata.set_watched(package_name.as_str(), watched))
+    }
+
+    /// Records the newest reported catalog versions of watched apps.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn set_watched_seen(&self, versions: &[(String, u64)]) -> Result<()> {
+        let result = self.update(|metadata| {
+            versions.iter().fold(false, |changed, (package_name, version_code)| {
+                metadata.set_watched_seen(package_name, *version_code) | changed
+            })
+        });
+        if result.is_ok() {
+            self.send_watched_apps(None);
+        }
+        result
+    }
+
+    /// Applies `f` to a copy of the current metadata and, if it reports a change,
+    /// persists it before notifying subscribers.
+    fn update(&self, f: impl FnOnce(&mut AppMetadata) -> bool) -> Result<()> {
+        let mut result = Ok(());
+        let changed = self.watch_tx.send_if_modified(|current| {
+            let mut updated = current.clone();
+            if !f(&mut updated) {
+                return false;
+            }
+            match updated.save_to_file(&self.file_path) {
+                Ok(()) => {
+                    *current = updated;
+                    true
+                }
+                Err(e) => {
+                    result = Err(e);
+                    false
+                }
+            }
+        });
+        if changed {
+            info!(path = %self.file_path.display(), "Saved app metadata");
+        }
+        result.context("Failed to update app metadata")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_name_override_persists_and_notifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MetadataStore::load(dir.path().to_path_buf());
+        let mut rx = store.subscribe();
+
+        store.set_name_override("com.example.app", Some("Example")).unwrap();
+
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(
+            rx.borrow_and_update().name_overrides.get("com.example.app").unwrap(),
+            "Example"
+        );
+        let reloaded = MetadataStore::load(dir.path().to_path_buf());
+        assert_eq!(reloaded.current(), store.current());
+    }
+
+    #[test]
+    fn set_name_override_rejects_invalid_package_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MetadataStore::load(dir.path().to_path_buf());
+
+        assert!(store.set_name_override("not a package", Some("Example")).is_err());
+        assert!(store.current().name_overrides.is_empty());
+    }
+}
@@ -1,8 +1,13 @@
-use std::path::Path;
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result, anyhow};
 use apk_info::Apk;
-use tracing::instrument;
+use tracing::{debug, instrument};
 
 #[derive(Debug, Clone)]
 #[allow(unused)]
@@ -11,9 +16,15 @@ pub(crate) struct ApkInfo {
     pub package_name: String,
     pub version_code: Option<u32>,
     pub version_name: Option<String>,
+    pub min_sdk_version: Option<u32>,
+    pub target_sdk_version: Option<u32>,
+    /// Requested permissions, sorted
+    pub permissions: Vec<String>,
+    /// ABIs the APK ships native libraries for, empty if it has none
+    pub native_abis: Vec<String>,
 }
 
-/// Parse minimal info from an APK using `apk_info` crate.
+/// Parse info from an APK using `apk_info` crate.
 #[instrument(ret, level = "debug", fields(apk_path = %apk_path.as_ref().display()))]
 pub(crate) fn get_apk_info(apk_path: impl AsRef<Path>) -> Result<ApkInfo> {
     let apk_path = apk_path.as_ref();
@@ -30,6 +41,146 @@ pub(crate) fn get_apk_info(apk_path: impl AsRef<Path>) -> Result<ApkInfo> {
         .transpose()?;
     let version_name = apk.get_version_name();
     let application_label = apk.get_application_label();
+    let min_sdk_version = apk.get_min_sdk_version().and_then(|v| v.trim().parse().ok());
+    let target_sdk_version = apk.get_target_sdk_version().and_then(|v| v.trim().parse().ok());
+    let mut permissions: Vec<String> = apk.get_permissions().into_iter().collect();
+    permissions.sort();
 
-    Ok(ApkInfo { application_label, package_name, version_code, version_name })
+    let native_abis = match open_apk_zip(apk_path) {
+        Ok(zip) => native_abis(zip.file_names()),
+        Err(e) => {
+            debug!(error = %format!("{e:#}"), "Failed to list APK entries for native libraries");
+            Vec::new()
+        }
+    };
+
+    Ok(ApkInfo {
+        application_label,
+        package_name,
+        version_code,
+        version_name,
+        min_sdk_version,
+        target_sdk_version,
+        permissions,
+        native_abis,
+    })
+}
+
+/// Extracts the launcher icon of an APK to `dest_stem` with the icon's extension added.
+///
+/// The icon is not resolved through the resource table. The largest PNG or WebP bitmap named like a
+/// launcher icon is taken instead, which matches what most apps ship. Returns `None` if the APK has
+/// no such bitmap.
+#[instrument(level = "debug", err)]
+pub(crate) fn extract_launcher_icon(apk_path: &Path, dest_stem: &Path) -> Result<Option<PathBuf>> {
+    let mut zip = open_apk_zip(apk_path)?;
+    let mut candidates = Vec::new();
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)?;
+        candidates.push((entry.name().to_string(), entry.size()));
+    }
+    let Some(name) = launcher_icon_entry(&candidates) else {
+        debug!("No launcher icon found in APK");
+        return Ok(None);
+    };
+    let extension = Path::new(&name).extension().and_then(|e| e.to_str()).unwrap_or("png");
+    let dest = dest_stem.with_extension(extension.to_ascii_lowercase());
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut entry = zip.by_name(&name)?;
+    let tmp = dest.with_extension("part");
+    let mut out =
+        File::create(&tmp).with_context(|| format!("Failed to create {}", tmp.display()))?;
+    io::copy(&mut entry, &mut out).with_context(|| format!("Failed to extract {name}"))?;
+    drop(out);
+    std::fs::rename(&tmp, &dest).with_context(|| format!("Failed to write {}", dest.display()))?;
+    debug!(entry = name, dest = %dest.display(), "Extracted launcher icon");
+    Ok(Some(dest))
+}
+
+fn open_apk_zip(apk_path: &Path) -> Result<zip::ZipArchive<File>> {
+    let file = File::open(apk_path)
+        .with_context(|| format!("Failed to open APK: {}", apk_path.display()))?;
+    zip::ZipArchive::new(file).context("APK is not a valid ZIP archive")
+}
+
+/// Collects the ABIs of native libraries from APK entry names (`lib/<abi>/<name>.so`)
+fn native_abis<'a>(entry_names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    entry_names
+        .filter_map(|name| {
+            let mut parts = name.strip_prefix("lib/")?.splitn(2, '/');
+            let abi = parts.next()?;
+            parts.next().filter(|file| file.ends_with(".so"))?;
+            Some(abi.to_string())
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Picks the most likely launcher icon from APK entries given as `(name, size)`
+fn launcher_icon_entry(entries: &[(String, u64)]) -> Option<String> {
+    entries
+        .iter()
+        .filter(|(name, _)| {
+            let Some(rest) = name.strip_prefix("res/") else {
+                return false;
+            };
+            let Some((dir, file)) = rest.split_once('/') else {
+                return false;
+            };
+            let file = file.to_ascii_lowercase();
+            (dir.starts_with("mipmap") || dir.starts_with("drawable"))
+                && (file.ends_with(".png") || file.ends_with(".webp"))
+                && !file.ends_with(".9.png")
+                && (file.contains("launcher") || file.contains("icon"))
+                && !file.contains("foreground")
+                && !file.contains("background")
+        })
+        .max_by_key(|(name, size)| (name.starts_with("res/mipmap"), *size))
+        .map(|(name, _)| name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_native_abis() {
+        let names = [
+            "lib/arm64-v8a/libgame.so",
+            "lib/arm64-v8a/libunity.so",
+            "lib/armeabi-v7a/libgame.so",
+            "lib/arm64-v8a/",
+            "lib/readme.txt",
+            "assets/lib/x86/libfake.so",
+        ];
+        assert_eq!(native_abis(names.into_iter()), vec!["arm64-v8a", "armeabi-v7a"]);
+        assert!(native_abis(["classes.dex"].into_iter()).is_empty());
+    }
+
+    #[test]
+    fn picks_largest_launcher_bitmap() {
+        let entries = [
+            ("res/drawable-hdpi/icon.png", 4_000),
+            ("res/mipmap-mdpi/ic_launcher.png", 2_000),
+            ("res/mipmap-xxxhdpi/ic_launcher.webp", 9_000),
+            ("res/mipmap-xxxhdpi/ic_launcher_foreground.png", 20_000),
+            ("res/mipmap-anydpi-v26/ic_launcher.xml", 500),
+            ("assets/icon.png", 50_000),
+        ]
+        .map(|(name, size)| (name.to_string(), size));
+        assert_eq!(
+            launcher_icon_entry(&entries).as_deref(),
+            Some("res/mipmap-xxxhdpi/ic_launcher.webp")
+        );
+        assert_eq!(
+            launcher_icon_entry(&entries[..1]).as_deref(),
+            Some("res/drawable-hdpi/icon.png")
+        );
+        assert_eq!(launcher_icon_entry(&entries[4..]), None);
+    }
 }
@@ -0,0 +1,276 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{CloudApp, InstalledPackage};
+
+/// User-defined per-app metadata persisted by the app
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AppMetadata {
+    /// Display name overrides keyed by package name
+    #[serde(default)]
+    pub name_overrides: BTreeMap<String, String>,
+    /// Update restrictions keyed by package name
+    #[serde(default)]
+    pub version_pins: BTreeMap<String, VersionPin>,
+    /// Catalog apps to notify about when new versions appear, keyed by original package name
+    #[serde(default)]
+    pub watched_apps: BTreeMap<String, WatchedApp>,
+}
+
+/// App on the install-later list
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct WatchedApp {
+    /// Newest catalog version already reported, `None` until the catalog was checked once
+    #[serde(default)]
+    pub seen_version_code: Option<u64>,
+}
+
+/// Restricts which catalog versions of an app are offered as updates
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct VersionPin {
+    /// Version code the app is pinned to. Newer versions are not offered as updates.
+    #[serde(default)]
+    pub pinned_version_code: Option<u64>,
+    /// Specific catalog versions that are never offered as updates
+    #[serde(default)]
+    pub ignored_version_codes: BTreeSet<u64>,
+}
+
+impl VersionPin {
+    fn is_empty(&self) -> bool {
+        self.pinned_version_code.is_none() && self.ignored_version_codes.is_empty()
+    }
+}
+
+impl AppMetadata {
+    pub(crate) fn load_from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).context("Failed to read metadata file")?;
+        serde_json::from_str(&content).context("Failed to parse metadata file")
+    }
+
+    pub(crate) fn save_to_file(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize metadata")?;
+        fs::write(path, json).context("Failed to write metadata file")?;
+        Ok(())
+    }
+
+    /// Sets or clears (`None` or blank name) the display name override for a package.
+    ///
+    /// Returns `true` if the metadata changed.
+    pub(crate) fn set_name_override(&mut self, package_name: &str, name: Option<&str>) -> bool {
+        match name.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(name) => {
+                self.name_overrides.insert(package_name.to_string(), name.to_string())
+                    != Some(name.to_string())
+            }
+            None => self.name_overrides.remove(package_name).is_some(),
+        }
+    }
+
+    /// Pins a package to a version code, or unpins it with `None`.
+    ///
+    /// Returns `true` if the metadata changed.
+    pub(crate) fn set_version_pin(
+        &mut self,
+        package_name: &str,
+        version_code: Option<u64>,
+    ) -> bool {
+        self.update_version_pin(package_name, |pin| {
+            std::mem::replace(&mut pin.pinned_version_code, version_code) != version_code
+        })
+    }
+
+    /// Marks a catalog version of a package as ignored for updates, or clears the mark.
+    ///
+    /// Returns `true` if the metadata changed.
+    pub(crate) fn set_version_ignored(
+        &mut self,
+        package_name: &str,
+        version_code: u64,
+        ignored: bool,
+    ) -> bool {
+        self.update_version_pin(package_name, |pin| {
+            if ignored {
+                pin.ignored_version_codes.insert(version_code)
+            } else {
+                pin.ignored_version_codes.remove(&version_code)
+            }
+        })
+    }
+
+    fn update_version_pin(
+        &mut self,
+        package_name: &str,
+        f: impl FnOnce(&mut VersionPin) -> bool,
+    ) -> bool {
+        let pin = self.version_pins.entry(package_name.to_string()).or_default();
+        let changed = f(pin);
+        if pin.is_empty() {
+            self.version_pins.remove(package_name);
+        }
+        changed
+    }
+
+    /// Adds a package to the watched apps or removes it.
+    ///
+    /// Returns `true` if the metadata changed.
+    pub(crate) fn set_watched(&mut self, package_name: &str, watched: bool) -> bool {
+        if watched {
+            if self.watched_apps.contains_key(package_name) {
+                return false;
+            }
+            self.watched_apps.insert(package_name.to_string(), WatchedApp::default());
+            true
+        } else {
+            self.watched_apps.remove(package_name).is_some()
+        }
+    }
+
+    /// Records the newest catalog version reported for a watched package.
+    ///
+    /// Returns `true` if the metadata changed.
+    pub(crate) fn set_watched_seen(&mut self, package_name: &str, version_code: u64) -> bool {
+        match self.watched_apps.get_mut(package_name) {
+            Some(app) => app.seen_version_code.replace(version_code) != Some(version_code),
+            None => false,
+        }
+    }
+
+    /// Whether a catalog version of a package may be offered as an update
+    pub(crate) fn is_update_allowed(&self, package_name: &str, version_code: u64) -> bool {
+        let Some(pin) = self.version_pins.get(package_name) else {
+            return true;
+        };
+        if pin.pinned_version_code.is_some_and(|pinned| version_code > pinned) {
+            return false;
+        }
+        !pin.ignored_version_codes.contains(&version_code)
+    }
+
+    /// Applies name overrides to catalog entries, matching either the listed or the original package name.
+    pub(crate) fn apply_to_cloud_apps(&self, apps: &mut [CloudApp]) {
+        if self.name_overrides.is_empty() {
+            return;
+        }
+        for app in apps {
+            if let Some(name) = self
+                .name_overrides
+                .get(&app.package_name)
+                .or_else(|| self.name_overrides.get(&app.true_package_name))
+            {
+                app.app_name = name.clone();
+            }
+        }
+    }
+
+    /// Applies name overrides to installed package labels.
+    pub(crate) fn apply_to_installed_packages(&self, packages: &mut [InstalledPackage]) {
+        if self.name_overrides.is_empty() {
+            return;
+        }
+        for package in packages {
+            if let Some(name) = self.name_overrides.get(package.package_name()) {
+                package.set_label(name.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_name_override_trims_and_clears() {
+        let mut metadata = AppMetadata::default();
+
+        assert!(metadata.set_name_override("com.example.app", Some("  Example  ")));
+        assert_eq!(metadata.name_overrides.get("com.example.app").unwrap(), "Example");
+        assert!(!metadata.set_name_override("com.example.app", Some("Example")));
+
+        assert!(metadata.set_name_override("com.example.app", Some("   ")));
+        assert!(metadata.name_overrides.is_empty());
+        assert!(!metadata.set_name_override("com.example.app", None));
+    }
+
+    #[test]
+    fn apply_to_cloud_apps_matches_original_package_name() {
+        let mut metadata = AppMetadata::default();
+        metadata.set_name_override("com.example.app", Some("Example"));
+
+        let mut apps = vec![
+            CloudApp::new(
+                "Example v1+1 (garbled)".into(),
+                "Example v1+1 (garbled)".into(),
+                "mr.com.example.app".into(),
+                1,
+                String::new(),
+                0,
+            ),
+            CloudApp::new(
+                "Other".into(),
+                "Other v1+1".into(),
+                "com.example.other".into(),
+                1,
+                String::new(),
+                0,
+            ),
+        ];
+        metadata.apply_to_cloud_apps(&mut apps);
+
+        assert_eq!(apps[0].app_name, "Example");
+        assert_eq!(apps[1].app_name, "Other");
+    }
+
+    #[test]
+    fn version_pins_drop_empty_entries() {
+        let mut metadata = AppMetadata::default();
+
+        assert!(metadata.set_version_pin("com.example.app", Some(3)));
+        assert!(!metadata.set_version_pin("com.example.app", Some(3)));
+        assert!(metadata.set_version_ignored("com.example.app", 4, true));
+        assert!(!metadata.set_version_ignored("com.example.app", 4, true));
+        let pin = &metadata.version_pins["com.example.app"];
+        assert_eq!(pin.pinned_version_code, Some(3));
+        assert!(pin.ignored_version_codes.contains(&4));
+
+        assert!(metadata.set_version_pin("com.example.app", None));
+        assert!(metadata.set_version_ignored("com.example.app", 4, false));
+        assert!(metadata.version_pins.is_empty());
+    }
+
+    #[test]
+    fn watched_apps_track_seen_versions() {
+        let mut metadata = AppMetadata::default();
+
+        assert!(metadata.set_watched("com.example.app", true));
+        assert!(!metadata.set_watched("com.example.app", true));
+        assert!(metadata.set_watched_seen("com.example.app", 3));
+        assert!(!metadata.set_watched_seen("com.example.app", 3));
+        assert!(!metadata.set_watched_seen("com.example.other", 3));
+        assert_eq!(metadata.watched_apps["com.example.app"].seen_version_code, Some(3));
+
+        assert!(metadata.set_watched("com.example.app", false));
+        assert!(metadata.watched_apps.is_empty());
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app_metadata.json");
+        let mut metadata = AppMetadata::default();
+        metadata.set_name_override("com.example.app", Some("Example"));
+        metadata.set_version_pin("com.example.app", Some(3));
+        metadata.set_watched("com.example.app", true);
+
+        metadata.save_to_file(&path).unwrap();
+
+        assert_eq!(AppMetadata::load_from_file(&path).unwrap(), metadata);
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+
+/// File name of the manifest stored in the root of a backup
+pub(crate) const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+
+/// Extension of single-file (compressed) backups
+pub(crate) const BACKUP_ARCHIVE_EXTENSION: &str = "yaasbackup";
+
+/// Describes a backup created by the app.
+///
+/// Written into every new backup. Legacy backup directories only have the `.backup` marker,
+/// so their contents are detected from the directory layout instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BackupManifest {
+    pub package_name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub version_code: Option<u64>,
+    #[serde(default)]
+    pub version_name: Option<String>,
+    /// Milliseconds since Unix epoch
+    pub created_at: u64,
+    /// Serial of the device the backup was made on
+    #[serde(default)]
+    pub device_serial: Option<String>,
+    #[serde(default)]
+    pub contents: BackupContents,
+    #[serde(default)]
+    pub sizes: BackupSizes,
+    /// Files stored in the backup, empty in backups made before they were recorded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<BackupFile>,
+}
+
+/// Sizes of the parts of a backup in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct BackupSizes {
+    #[serde(default)]
+    pub apk: u64,
+    #[serde(default)]
+    pub private_data: u64,
+    #[serde(default)]
+    pub shared_data: u64,
+    #[serde(default)]
+    pub obb: u64,
+}
+
+impl BackupSizes {
+    /// Sums the sizes of backup files by part, given their paths relative to the backup root
+    pub(crate) fn from_files<'a>(files: impl IntoIterator<Item = (&'a str, u64)>) -> Self {
+        let mut sizes = Self::default();
+        for (path, size) in files {
+            let part = match path.split_once('/') {
+                Some(("data_private", _)) => &mut sizes.private_data,
+                Some(("data", _)) => &mut sizes.shared_data,
+                Some(("obb", _)) => &mut sizes.obb,
+                None if Path::new(path)
+                    .extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("apk")) =>
+                {
+                    &mut sizes.apk
+                }
+                _ => continue,
+            };
+            *part += size;
+        }
+        sizes
+    }
+}
+
+/// File stored in a backup, recorded so the backup can be verified later
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BackupFile {
+    /// Path relative to the backup root, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// MD5 hash of the contents, as lowercase hex
+    #[serde(default)]
+    pub md5: Option<String>,
+}
+
+/// Parts of an app contained in a backup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BackupContents {
+    #[serde(default)]
+    pub apk: bool,
+    #[serde(default)]
+    pub private_data: bool,
+    #[serde(default)]
+    pub shared_data: bool,
+    #[serde(default)]
+    pub obb: bool,
+}
+
+impl BackupManifest {
+    pub(crate) fn from_json(content: &[u8]) -> Result<Self> {
+        serde_json::from_slice(content).context("Failed to parse backup manifest")
+    }
+
+    pub(crate) fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize backup manifest")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_round_trip_and_defaults() {
+        let manifest = BackupManifest {
+            package_name: "com.example.game".into(),
+            display_name: Some("Game".into()),
+            version_code: Some(42),
+            version_name: Some("1.2.3".into()),
+            created_at: 1_700_000_000_000,
+            device_serial: Some("1WMHH000000000".into()),
+            contents: BackupContents { apk: true, private_data: true, ..Default::default() },
+            sizes: BackupSizes { apk: 3, private_data: 12, ..Default::default() },
+            files: vec![BackupFile {
+                path: "data_private/files/save.dat".into(),
+                size: 12,
+                md5: Some("d41d8cd98f00b204e9800998ecf8427e".into()),
+            }],
+        };
+        let json = manifest.to_json().unwrap();
+        assert_eq!(BackupManifest::from_json(json.as_bytes()).unwrap(), manifest);
+
+        let minimal = br#"{"package_name":"com.example.game","created_at":1}"#;
+        let parsed = BackupManifest::from_json(minimal).unwrap();
+        assert_eq!(parsed.contents, BackupContents::default());
+        assert_eq!(parsed.version_code, None);
+        assert!(parsed.files.is_empty());
+        assert_eq!(parsed.sizes, BackupSizes::default());
+        assert_eq!(parsed.device_serial, None);
+    }
+
+    #[test]
+    fn sizes_from_files_by_part() {
+        let sizes = BackupSizes::from_files([
+            ("com.example.game.apk", 100),
+            ("data_private/com.example.game/files/save.dat", 10),
+            ("data_private/com.example.game/shared_prefs/prefs.xml", 5),
+            ("data/com.example.game/files/log.txt", 7),
+            ("obb/com.example.game/main.1.com.example.game.obb", 1000),
+            ("notes.txt", 1),
+        ]);
+        assert_eq!(sizes, BackupSizes { apk: 100, private_data: 15, shared_data: 7, obb: 1000 });
+    }
+}
@@ -6,7 +6,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use super::RENAME_PATTERN;
 
 /// Popularity percentage for different time windows.
-#[derive(Serialize, Deserialize, Debug, Clone, SignalPiece)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, SignalPiece)]
 pub(crate) struct Popularity {
     pub day_1: Option<u8>,
     pub day_7: Option<u8>,
@@ -31,6 +31,8 @@ struct CloudAppCsvHelper {
     last_updated: String,
     #[serde(alias = "Size (MB)")]
     size_mb: String,
+    #[serde(alias = "Install Size (MB)", default)]
+    install_size_mb: Option<String>,
 }
 
 fn parse_size_mb_to_bytes(size_mb_str: &str) -> Result<u64, String> {
@@ -40,7 +42,7 @@ fn parse_size_mb_to_bytes(size_mb_str: &str) -> Result<u64, String> {
 }
 
 /// Strips known rename markers from a package name to derive the original.
-fn normalize_package_name(name: &str) -> String {
+pub(crate) fn normalize_package_name(name: &str) -> String {
     // Do some manual handling where regex can't help us
     let name = name.replace(".mrf.", ".");
     RENAME_PATTERN.replace_all(&name, "").into_owned()
@@ -61,10 +63,31 @@ mod tests {
         assert_eq!(normalize_package_name("mr.com.example.app"), "com.example.app");
         assert_eq!(normalize_package_name("mrf.com.example.app"), "com.example.app");
     }
+
+    #[test]
+    fn install_size_column_is_optional() {
+        let parse = |extra: &str| {
+            serde_json::from_str::<CloudApp>(&format!(
+                r#"{{"Game Name": "App", "Release Name": "App v1+1", "Package Name": "com.example.app",
+                "Version Code": 1, "Last Updated": "2024-01-01 00:00 UTC", "Size (MB)": "100"{extra}}}"#
+            ))
+            .unwrap()
+        };
+
+        let app = parse("");
+        assert_eq!(app.install_size, None);
+        assert_eq!(app.expected_install_size(), 100_000_000);
+
+        let app = parse(r#", "Install Size (MB)": "250.5""#);
+        assert_eq!(app.install_size, Some(250_500_000));
+        assert_eq!(app.expected_install_size(), 250_500_000);
+
+        assert_eq!(parse(r#", "Install Size (MB)": " ""#).install_size, None);
+    }
 }
 
 /// A cloud app from the remote repository.
-#[derive(Serialize, Debug, Clone, SignalPiece)]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, SignalPiece)]
 pub(crate) struct CloudApp {
     pub app_name: String,
     pub full_name: String,
@@ -75,6 +98,9 @@ pub(crate) struct CloudApp {
     pub last_updated: String,
     /// Size in bytes
     pub size: u64,
+    /// Size on the device after installation (APK plus expanded OBB) in bytes, if the catalog
+    /// lists it
+    pub install_size: Option<u64>,
     pub popularity: Option<Popularity>,
 }
 
@@ -96,9 +122,15 @@ impl CloudApp {
             version_code,
             last_updated,
             size,
+            install_size: None,
             popularity: None,
         }
     }
+
+    /// Bytes the app is expected to take up on the device, falling back to the download size
+    pub(crate) fn expected_install_size(&self) -> u64 {
+        self.install_size.unwrap_or(self.size)
+    }
 }
 
 impl<'de> Deserialize<'de> for CloudApp {
@@ -109,14 +141,24 @@ impl<'de> Deserialize<'de> for CloudApp {
         // Delegate to helper with serde field attributes, then convert
         let helper = CloudAppCsvHelper::deserialize(deserializer)?;
         let size = parse_size_mb_to_bytes(&helper.size_mb).map_err(serde::de::Error::custom)?;
-        Ok(CloudApp::new(
+        let install_size = helper
+            .install_size_mb
+            .as_deref()
+            .map(str::trim)
+            .filter(|size_mb| !size_mb.is_empty())
+            .map(parse_size_mb_to_bytes)
+            .transpose()
+            .map_err(serde::de::Error::custom)?;
+        let mut app = CloudApp::new(
             helper.app_name,
             helper.full_name,
             helper.package_name,
             helper.version_code,
             helper.last_updated,
             size,
-        ))
+        );
+        app.install_size = install_size;
+        Ok(app)
     }
 }
 
@@ -0,0 +1,46 @@
+use rinf::SignalPiece;
+use serde::Serialize;
+
+/// Optional device features, detected once when the device connects.
+///
+/// Commands that rely on one of these are only offered and attempted when it is supported, so
+/// devices other than Meta Quest headsets get a reduced but working feature set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, SignalPiece)]
+pub(crate) struct DeviceCapabilities {
+    /// The device is a standalone VR headset rather than a phone or tablet
+    pub vr_headset: bool,
+    /// Guardian can be paused through `debug.oculus.guardian_pause`
+    pub guardian_pause: bool,
+    /// The proximity sensor can be overridden through the VR power manager
+    pub proximity_override: bool,
+    /// `rstest info --json` reports controller state
+    pub rstest_json: bool,
+    /// The device can be cast with the Meta Quest Casting tool
+    pub casting: bool,
+    /// Wireless ADB can be enabled from a USB connection
+    pub wireless_enable: bool,
+    /// `run-as` is available for private app data backups
+    pub run_as: bool,
+    /// Rendering tweaks can be set through debug properties
+    pub device_tweaks: bool,
+    /// Controllers can be put into pairing mode over ADB
+    pub controller_pairing: bool,
+}
+
+impl DeviceCapabilities {
+    /// Every feature enabled, used when the probe fails and nothing was detected before so no
+    /// command is hidden because of a transient error
+    pub(crate) fn optimistic() -> Self {
+        Self {
+            vr_headset: true,
+            guardian_pause: true,
+            proximity_override: true,
+            rstest_json: true,
+            casting: true,
+            wireless_enable: true,
+            run_as: true,
+            device_tweaks: true,
+            controller_pairing: true,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use rinf::SignalPiece;
+use serde::Serialize;
+
+/// OS build of a device, read from system properties.
+///
+/// Sideload compatibility often depends on the firmware, so it is shown with the device. Every
+/// field is None if its property is missing or empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, SignalPiece)]
+pub(crate) struct FirmwareInfo {
+    /// `ro.build.fingerprint`
+    pub fingerprint: Option<String>,
+    /// Android release the OS is based on (`ro.build.version.release`)
+    pub android_version: Option<String>,
+    /// `ro.build.version.security_patch`
+    pub security_patch: Option<String>,
+    /// `ro.build.display.id`
+    pub build_id: Option<String>,
+    /// Version of the vendor OS, e.g. the Horizon OS or Pico OS release
+    pub os_version: Option<String>,
+}
@@ -2,7 +2,8 @@ use anyhow::{Context, Result, ensure};
 use rinf::SignalPiece;
 use serde::Serialize;
 
-pub(crate) static SPACE_INFO_COMMAND: &str = "stat -fc %S:%b:%a /data";
+/// Upper bound of the space Android keeps free on `/data` (10% of the partition otherwise)
+const RESERVED_SPACE_CAP: u64 = 500 * 1024 * 1024;
 
 /// Represents storage space information for a device
 ///
@@ -17,7 +18,7 @@ pub(crate) struct SpaceInfo {
 }
 
 impl SpaceInfo {
-    /// Creates a new SpaceInfo instance from `SPACE_INFO_COMMAND`
+    /// Creates a new SpaceInfo instance from `stat -fc %S:%b:%a` output
     pub(crate) fn from_stat_output(output: &str) -> Result<Self> {
         // block_size:total_blocks:available_blocks
         let parts = output.trim().split(':').collect::<Vec<&str>>();
@@ -38,6 +39,17 @@ impl SpaceInfo {
                 .context("Available space overflow")?,
         })
     }
+
+    /// Returns the available space minus what Android keeps in reserve for itself
+    pub(crate) fn usable(&self) -> u64 {
+        let reserved = (self.total / 10).min(RESERVED_SPACE_CAP);
+        self.available.saturating_sub(reserved)
+    }
+
+    /// Returns how many more bytes have to be freed to fit `required` bytes, if any
+    pub(crate) fn shortfall(&self, required: u64) -> Option<u64> {
+        required.checked_sub(self.usable()).filter(|&missing| missing > 0)
+    }
 }
 
 #[cfg(test)]
@@ -70,6 +82,20 @@ mod tests {
         assert!(SpaceInfo::from_stat_output(output).is_err());
     }
 
+    #[test]
+    fn test_shortfall_accounts_for_reserved_space() {
+        let gib = 1024 * 1024 * 1024;
+        let info = SpaceInfo { total: 128 * gib, available: 2 * gib };
+        assert_eq!(info.usable(), 2 * gib - RESERVED_SPACE_CAP);
+        assert_eq!(info.shortfall(gib), None);
+        assert_eq!(info.shortfall(2 * gib), Some(RESERVED_SPACE_CAP));
+
+        let small = SpaceInfo { total: 1000, available: 500 };
+        assert_eq!(small.usable(), 400);
+        assert_eq!(small.shortfall(400), None);
+        assert_eq!(small.shortfall(450), Some(50));
+    }
+
     #[test]
     fn test_overflow() {
         let output = format!("{}:{}:{}", u64::MAX, u64::MAX, u64::MAX);
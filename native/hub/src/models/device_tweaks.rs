@@ -0,0 +1,25 @@
+use rinf::SignalPiece;
+use serde::{Deserialize, Serialize};
+
+/// Current values of the rendering tweaks on a device.
+///
+/// Apps read the tweaks when they start, so a change only affects apps launched afterwards. A
+/// None value means the property is unset and the system default applies.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, SignalPiece)]
+pub(crate) struct DeviceTweaks {
+    /// Display refresh rate in Hz
+    pub refresh_rate: Option<u32>,
+    pub cpu_level: Option<u8>,
+    pub gpu_level: Option<u8>,
+    /// Default eye texture size in pixels, only reported when both dimensions are set
+    pub texture_size: Option<TextureSize>,
+    /// Fixed foveated rendering level
+    pub foveation_level: Option<u8>,
+}
+
+/// Eye texture dimensions in pixels
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct TextureSize {
+    pub width: u32,
+    pub height: u32,
+}
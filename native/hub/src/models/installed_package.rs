@@ -21,23 +21,101 @@ pub(crate) struct AppSize {
     cache: u64,
 }
 
+impl AppSize {
+    pub(crate) fn new(app: u64, data: u64, cache: u64) -> Self {
+        Self { app, data, cache }
+    }
+
+    pub(crate) fn app(&self) -> u64 {
+        self.app
+    }
+
+    pub(crate) fn data(&self) -> u64 {
+        self.data
+    }
+
+    pub(crate) fn cache(&self) -> u64 {
+        self.cache
+    }
+
+    /// Whether no size is known
+    pub(crate) fn is_empty(&self) -> bool {
+        self.app == 0 && self.data == 0 && self.cache == 0
+    }
+}
+
 /// Represents an installed package on the device with its metadata
 #[derive(Debug, Clone, Default, Deserialize, Serialize, SignalPiece)]
 pub(crate) struct InstalledPackage {
+    #[serde(default)]
     uid: u64,
+    #[serde(default)]
     system: bool,
     package_name: String,
     version_code: u64,
+    #[serde(default)]
     version_name: String,
+    #[serde(default)]
     label: String,
+    #[serde(default)]
     launchable: bool,
+    #[serde(default)]
     vr: bool,
+    #[serde(default)]
     size: AppSize,
     /// Whether the package name contains known rename markers
     #[serde(default)]
     is_package_renamed: bool,
 }
 
+impl InstalledPackage {
+    pub(crate) fn package_name(&self) -> &str {
+        &self.package_name
+    }
+
+    pub(crate) fn version_code(&self) -> u64 {
+        self.version_code
+    }
+
+    pub(crate) fn version_name(&self) -> &str {
+        &self.version_name
+    }
+
+    pub(crate) fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub(crate) fn is_system(&self) -> bool {
+        self.system
+    }
+
+    pub(crate) fn size(&self) -> &AppSize {
+        &self.size
+    }
+
+    pub(crate) fn set_label(&mut self, label: String) {
+        self.label = label;
+    }
+
+    pub(crate) fn set_size(&mut self, size: AppSize) {
+        self.size = size;
+    }
+
+    pub(crate) fn set_version(&mut self, version_code: u64, version_name: String) {
+        self.version_code = version_code;
+        self.version_name = version_name;
+    }
+
+    /// Reads a single entry of list_apps.dex output
+    pub(crate) fn from_list_apps_entry(
+        entry: serde_json::Value,
+    ) -> Result<Self, serde_json::Error> {
+        let mut package: Self = serde_json::from_value(entry)?;
+        package.is_package_renamed = is_package_renamed(&package.package_name);
+        Ok(package)
+    }
+}
+
 /// Parses the output of list_apps.dex command
 pub(crate) fn parse_list_apps_dex(
     dex_output: &str,
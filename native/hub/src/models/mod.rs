@@ -1,8 +1,18 @@
 pub(crate) mod apk_info;
+mod app_metadata;
+pub(crate) use app_metadata::*;
+mod backup_manifest;
+pub(crate) use backup_manifest::*;
 mod cloud_app;
 pub(crate) use cloud_app::*;
+mod device_capabilities;
+pub(crate) use device_capabilities::*;
+mod device_firmware;
+pub(crate) use device_firmware::*;
 mod device_space;
 pub(crate) use device_space::*;
+mod device_tweaks;
+pub(crate) use device_tweaks::*;
 mod installed_downloader_config;
 pub(crate) use installed_downloader_config::*;
 mod installed_package;
@@ -14,4 +24,11 @@ pub(crate) mod signals;
 pub(crate) mod vendor {
     /// Quest-specific models.
     pub(crate) mod quest_controller;
+
+    /// Vendor-specific commands for standalone headsets.
+    mod adapter;
+    mod generic;
+    mod meta;
+    mod pico;
+    pub(crate) use adapter::*;
 }
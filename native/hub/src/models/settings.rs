@@ -3,12 +3,23 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, ensure};
 use rinf::SignalPiece;
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 use uuid::Uuid;
 
+use crate::{
+    downloader::tls::{ServerPins, parse_spki_pins},
+    models::signals::{
+        adb::macros::{CommandMacro, upgrade_saved_macros},
+        task::TaskKind,
+    },
+};
+
+/// Media server used unless `Settings::media_base_url` is set
+pub(crate) const DEFAULT_MEDIA_BASE_URL: &str = "https://webdav.5698452.xyz/media/";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece, Default)]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum ThemePreference {
@@ -49,6 +60,8 @@ pub(crate) enum DownloadCleanupPolicy {
     KeepOneVersion,
     KeepTwoVersions,
     KeepAllVersions,
+    /// Moves the installed download into `installed_downloads_location`
+    MoveAfterInstall,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece, Default)]
@@ -58,6 +71,55 @@ pub(crate) enum DownloadMode {
     Staged,
 }
 
+/// What happens to downloads while a casting session is running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece, Default)]
+pub(crate) enum CastingDownloadPolicy {
+    /// Downloads are not affected
+    #[default]
+    Ignore,
+    /// Downloads are limited to `casting_bandwidth_limit`
+    Throttle,
+    /// New downloads wait for casting to stop and running ones are slowed to a trickle
+    Pause,
+}
+
+/// How a device that is present but offline is nudged awake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece, Default)]
+pub(crate) enum OfflineWakeAction {
+    /// Sends the wakeup key event
+    #[default]
+    Keyevent,
+    /// Reports the proximity sensor as covered, as if the headset was put on
+    ProximityClose,
+}
+
+/// Format the rolling log files are written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece, Default)]
+pub(crate) enum LogFileFormat {
+    /// Human-readable multi-line events in `.log` files
+    #[default]
+    Pretty,
+    /// One JSON object per event in `.jsonl` files
+    Json,
+    /// Both, in separate files
+    Both,
+}
+
+/// Maximum number of tasks of one kind that may be active at the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct TaskKindLimit {
+    pub kind: TaskKind,
+    pub max_active: u32,
+}
+
+/// Wireless ADB address a headset was last connected at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct KnownWirelessTarget {
+    pub true_serial: String,
+    /// `ip:port` the device was last connected at
+    pub address: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, SignalPiece)]
 #[serde(default)]
 pub(crate) struct Settings {
@@ -70,9 +132,13 @@ pub(crate) struct Settings {
     backups_location: String,
     pub bandwidth_limit: String,
     pub cleanup_policy: DownloadCleanupPolicy,
+    /// Where installed downloads are moved with `DownloadCleanupPolicy::MoveAfterInstall`
+    pub installed_downloads_location: String,
     pub download_mode: DownloadMode,
     /// Also write legacy release.json metadata alongside download.json
     pub write_legacy_release_json: bool,
+    /// Hard-link files shared by finished downloads to a single copy
+    pub dedupe_downloads: bool,
     /// Locale code (language) for the UI
     locale_code: String,
     navigation_rail_label_visibility: NavigationRailLabelVisibility,
@@ -87,10 +153,79 @@ pub(crate) struct Settings {
     favorite_packages: Vec<String>,
     /// Discover and auto-connect ADB over Wi‑Fi devices via mDNS
     pub mdns_auto_connect: bool,
+    /// Keep wireless connections alive and reconnect to the last wireless device when no
+    /// device is connected
+    pub reconnect_wireless_devices: bool,
+    /// Addresses wireless devices were last connected at, most recently connected first
+    pub known_wireless_targets: Vec<KnownWirelessTarget>,
     /// Popularity display range
     popularity_range: PopularityRange,
     /// Auto reinstall app on incompatible update or downgrade (requires debuggable app for data backup)
     pub auto_reinstall_on_conflict: bool,
+    /// Run install scripts without asking the user to approve their operations first
+    pub auto_approve_install_scripts: bool,
+    /// Downloads that may run at the same time
+    pub max_concurrent_downloads: u32,
+    /// Device operations (installs, backups, restores...) that may run at the same time
+    pub max_concurrent_adb_operations: u32,
+    /// Threads used to extract archives, 0 to use all CPU cores
+    pub extraction_threads: u32,
+    /// Per task kind limits, kinds without an entry are only bound by the limits above
+    pub task_kind_limits: Vec<TaskKindLimit>,
+    /// Look for a newer YAAS release on startup
+    pub check_for_updates: bool,
+    /// How downloads yield bandwidth to a running casting session
+    pub casting_download_policy: CastingDownloadPolicy,
+    /// Download limit while casting with the throttle policy, in rclone `--bwlimit` syntax
+    pub casting_bandwidth_limit: String,
+    /// Backups kept per app, older ones are pruned (0 keeps all)
+    pub backup_retention_max_per_package: u32,
+    /// Total size backups may take up in GB, oldest ones are pruned first (0 for no limit)
+    pub backup_retention_max_total_gb: u32,
+    /// Age in days after which backups are pruned (0 keeps them forever)
+    pub backup_retention_max_age_days: u32,
+    /// Back up an app's APK and data to the trash before uninstalling it, so it can be reinstalled
+    pub trash_before_uninstall: bool,
+    /// Days uninstalled apps stay in the trash (0 keeps them until reinstalled)
+    pub trash_retention_days: u32,
+    /// Try to wake devices that are present but stay offline, then connect to them
+    pub wake_offline_devices: bool,
+    /// What is sent to an offline device to wake it
+    pub offline_wake_action: OfflineWakeAction,
+    /// Seconds the current device is kept when it drops off the device list, so a brief
+    /// disconnect (e.g. sleep and wake) re-attaches it without a reconnect (0 disconnects at once)
+    pub device_reconnect_grace_secs: u32,
+    /// Folders downloaded apps are sorted into, see `DownloadLayout`
+    pub download_layout: String,
+    /// Install the device helper on connected devices and keep it up to date
+    pub device_agent_enabled: bool,
+    /// Watch the package list on the device and update installed apps as soon as they change,
+    /// instead of only on refresh (devices with the device helper report changes on their own)
+    pub watch_package_changes: bool,
+    /// Measure the Wi‑Fi link before large wireless sideloads and warn when it is slow
+    pub probe_wireless_link: bool,
+    /// User-defined device command sequences
+    pub command_macros: Vec<CommandMacro>,
+    /// Remotes, or base URLs for sources without remotes, tried in order when a download fails
+    /// or stays slow
+    pub download_mirrors: Vec<String>,
+    /// Speed in KB/s below which a download moves on to the next mirror (0 disables the check)
+    pub mirror_min_speed_kbps: u32,
+    /// How long a download may stay below `mirror_min_speed_kbps` before switching mirrors
+    pub mirror_slow_secs: u32,
+    /// Stop ADB servers started by other apps, such as streaming apps bundling their own ADB
+    pub stop_foreign_adb_servers: bool,
+    /// Allow opening interactive shell sessions on the connected device
+    pub shell_console_enabled: bool,
+    /// Base URL of a self-hosted catalog for URL-based sources, empty to use the configured one
+    pub catalog_base_url: String,
+    /// Base URL of a self-hosted media server, empty to use `DEFAULT_MEDIA_BASE_URL`
+    pub media_base_url: String,
+    /// `sha256/<base64>` SPKI pins the self-hosted catalog and media servers must present a key
+    /// of, applied only while their URLs are set
+    pub pinned_spki: Vec<String>,
+    /// Format of the log files, JSON lines can be ingested by external tooling
+    pub log_file_format: LogFileFormat,
 }
 
 impl Default for Settings {
@@ -114,8 +249,10 @@ impl Default for Settings {
                 .to_string(),
             bandwidth_limit: String::new(),
             cleanup_policy: DownloadCleanupPolicy::default(),
+            installed_downloads_location: String::new(),
             download_mode: DownloadMode::default(),
             write_legacy_release_json: false,
+            dedupe_downloads: false,
             locale_code: "system".to_string(),
             navigation_rail_label_visibility: NavigationRailLabelVisibility::default(),
             startup_page_key: "home".to_string(),
@@ -124,8 +261,40 @@ impl Default for Settings {
             theme_preference: ThemePreference::Dark,
             favorite_packages: Vec::new(),
             mdns_auto_connect: true,
+            reconnect_wireless_devices: true,
+            known_wireless_targets: Vec::new(),
             popularity_range: PopularityRange::default(),
             auto_reinstall_on_conflict: true,
+            auto_approve_install_scripts: false,
+            max_concurrent_downloads: 1,
+            max_concurrent_adb_operations: 1,
+            extraction_threads: 0,
+            task_kind_limits: Vec::new(),
+            check_for_updates: true,
+            casting_download_policy: CastingDownloadPolicy::default(),
+            casting_bandwidth_limit: "1M".to_string(),
+            backup_retention_max_per_package: 0,
+            backup_retention_max_total_gb: 0,
+            backup_retention_max_age_days: 0,
+            trash_before_uninstall: false,
+            trash_retention_days: 7,
+            wake_offline_devices: false,
+            offline_wake_action: OfflineWakeAction::default(),
+            device_reconnect_grace_secs: 5,
+            download_layout: String::new(),
+            device_agent_enabled: false,
+            watch_package_changes: false,
+            probe_wireless_link: true,
+            command_macros: Vec::new(),
+            download_mirrors: Vec::new(),
+            mirror_min_speed_kbps: 0,
+            mirror_slow_secs: 30,
+            stop_foreign_adb_servers: false,
+            shell_console_enabled: false,
+            catalog_base_url: String::new(),
+            media_base_url: String::new(),
+            pinned_spki: Vec::new(),
+            log_file_format: LogFileFormat::default(),
         }
     }
 }
@@ -146,8 +315,13 @@ impl Settings {
         let file_content =
             fs::read_to_string(settings_file).context("Failed to read settings file")?;
 
-        let mut settings: Settings =
+        let mut value: serde_json::Value =
             serde_json::from_str(&file_content).context("Failed to parse settings file")?;
+        if let Some(command_macros) = value.get_mut("command_macros") {
+            upgrade_saved_macros(command_macros);
+        }
+        let mut settings: Settings =
+            serde_json::from_value(value).context("Failed to parse settings file")?;
 
         // TODO: Validate settings
         let defaults = Settings::new(portable_mode);
@@ -185,7 +359,7 @@ impl Settings {
     }
 
     pub(crate) fn save_to_file(&self, settings_file: &Path) -> Result<()> {
-        // TODO: Validate settings
+        self.validate()?;
 
         let settings_json =
             serde_json::to_string_pretty(self).context("Failed to serialize settings")?;
@@ -200,4 +374,83 @@ impl Settings {
     pub(crate) fn backups_location(&self) -> PathBuf {
         PathBuf::from(&self.backups_location)
     }
+
+    /// Media server thumbnails and trailers are fetched from, ending with a slash
+    pub(crate) fn media_base_url(&self) -> String {
+        let url = self.media_base_url.trim();
+        let url = if url.is_empty() { DEFAULT_MEDIA_BASE_URL } else { url };
+        if url.ends_with('/') { url.to_string() } else { format!("{url}/") }
+    }
+
+    /// Pins of the self-hosted catalog, none unless its URL is set
+    pub(crate) fn catalog_server_pins(&self) -> ServerPins<'_> {
+        self.self_hosted_pins(&self.catalog_base_url)
+    }
+
+    /// Pins of the self-hosted media server, none unless its URL is set
+    pub(crate) fn media_server_pins(&self) -> ServerPins<'_> {
+        self.self_hosted_pins(&self.media_base_url)
+    }
+
+    fn self_hosted_pins(&self, url: &str) -> ServerPins<'_> {
+        let spki = if url.trim().is_empty() { &[] } else { self.pinned_spki.as_slice() };
+        ServerPins { certificates: None, spki }
+    }
+
+    fn validate(&self) -> Result<()> {
+        parse_spki_pins(&self.pinned_spki).context("Pinned server keys are invalid")?;
+        for (url, what) in
+            [(&self.catalog_base_url, "Catalog URL"), (&self.media_base_url, "Media URL")]
+        {
+            let url = url.trim();
+            if !url.is_empty() {
+                let parsed = check_http_url(url).with_context(|| format!("{what} is invalid"))?;
+                ensure!(
+                    self.pinned_spki.is_empty() || parsed.scheme() == "https",
+                    "{what} must use https with pinned server keys"
+                );
+            }
+        }
+        self.check_installed_downloads_location()
+    }
+
+    /// Installed downloads are moved out of the downloads directory, so the two must not overlap
+    fn check_installed_downloads_location(&self) -> Result<()> {
+        let location = self.installed_downloads_location.trim();
+        if location.is_empty() {
+            return Ok(());
+        }
+        let location = Path::new(location);
+        ensure!(
+            location.is_absolute(),
+            "Folder for installed downloads must be an absolute path: {}",
+            location.display()
+        );
+        let resolve = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let (location, downloads) = (resolve(location), resolve(&self.downloads_location()));
+        ensure!(
+            !location.starts_with(&downloads) && !downloads.starts_with(&location),
+            "Folder for installed downloads must be outside the downloads directory: {}",
+            location.display()
+        );
+        Ok(())
+    }
+
+    pub(crate) fn set_downloads_location(&mut self, path: &Path) {
+        self.downloads_location = path.to_string_lossy().into_owned();
+    }
+
+    pub(crate) fn set_backups_location(&mut self, path: &Path) {
+        self.backups_location = path.to_string_lossy().into_owned();
+    }
+}
+
+/// Fails unless `url` is an absolute http(s) URL with a host
+pub(crate) fn check_http_url(url: &str) -> Result<reqwest::Url> {
+    let parsed = reqwest::Url::parse(url).with_context(|| format!("Not a valid URL: {url}"))?;
+    ensure!(
+        matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some(),
+        "Only http and https URLs are supported: {url}"
+    );
+    Ok(parsed)
 }
@@ -0,0 +1,24 @@
+use rinf::{RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Device state that can be restored automatically after a while
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum AutoRevertKind {
+    /// Resumes the guardian
+    GuardianPause,
+    /// Gives control back to the real proximity sensor
+    ProximitySensor,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct AutoRevertCountdown {
+    pub kind: AutoRevertKind,
+    /// Seconds until the state is restored, 0 if it is due and waits for the device
+    pub remaining_secs: u64,
+}
+
+/// Pending automatic reverts of the current device, sent whenever they or the device change
+#[derive(Debug, Serialize, Deserialize, RustSignal)]
+pub(crate) struct AutoRevertStatus {
+    pub pending: Vec<AutoRevertCountdown>,
+}
@@ -0,0 +1,29 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Size of one part of a backup and the time pulling it is expected to take
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct BackupPartEstimate {
+    pub bytes: u64,
+    pub files: u64,
+    pub secs: u64,
+}
+
+/// Asks how large a backup of a package would be and how long it would take, without pulling
+/// anything
+#[derive(Debug, Serialize, Deserialize, DartSignal)]
+pub(crate) struct EstimateBackupRequest {
+    pub package_name: String,
+}
+
+/// Estimate of every part of a backup, the UI adds up the selected ones
+#[derive(Debug, Default, Serialize, Deserialize, RustSignal)]
+pub(crate) struct BackupEstimateResponse {
+    pub package_name: String,
+    /// Parts are None if they do not exist on the device or cannot be read
+    pub apk: Option<BackupPartEstimate>,
+    pub private_data: Option<BackupPartEstimate>,
+    pub shared_data: Option<BackupPartEstimate>,
+    pub obb: Option<BackupPartEstimate>,
+    pub error: Option<String>,
+}
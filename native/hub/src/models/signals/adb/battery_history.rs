@@ -0,0 +1,28 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Battery levels of a device and its controllers at one point in time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct BatterySample {
+    /// Milliseconds since Unix epoch
+    pub timestamp: u64,
+    pub headset: u8,
+    /// None if the controller is not paired or reports no level
+    pub left_controller: Option<u8>,
+    pub right_controller: Option<u8>,
+}
+
+/// Asks for the battery history of a device, the current one if `serial` is None
+#[derive(Debug, Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetBatteryHistoryRequest {
+    pub serial: Option<String>,
+}
+
+/// Battery samples of a device taken this session, oldest first. Also sent after every new
+/// sample of the current device.
+#[derive(Debug, Default, Serialize, Deserialize, RustSignal)]
+pub(crate) struct BatteryHistory {
+    /// None if no device was asked for and none is connected
+    pub serial: Option<String>,
+    pub samples: Vec<BatterySample>,
+}
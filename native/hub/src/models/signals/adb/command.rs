@@ -1,12 +1,15 @@
 use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, SignalPiece)]
+use super::auto_revert::AutoRevertKind;
+use crate::models::TextureSize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
 pub(crate) enum AdbCommand {
     LaunchApp(String),
     ForceStopApp(String),
     UninstallPackage(String),
-    RefreshDevice,
+    RefreshDevice(RefreshScope),
     Reboot(RebootMode),
     /// Set proximity sensor state.
     /// - `enabled`: true to enable sensor, false to disable
@@ -16,6 +19,12 @@ pub(crate) enum AdbCommand {
         duration_ms: Option<u64>,
     },
     SetGuardianPaused(bool),
+    /// Restore `kind` on the current device after `after_secs`, None cancels a scheduled revert.
+    /// Setting the state by hand also cancels it.
+    ScheduleAutoRevert {
+        kind: AutoRevertKind,
+        after_secs: Option<u64>,
+    },
     GetBatteryDump,
     /// Windows-only: Start Meta Quest Casting tool against the current device
     StartCasting,
@@ -25,6 +34,81 @@ pub(crate) enum AdbCommand {
     EnableWirelessAdb,
     /// Connect or reset USB storage functions.
     SetStorageConnection(bool),
+    /// Try to wake a device that is present but offline and connect to it
+    WakeDevice(String),
+    /// Install or upgrade the device helper on the current device
+    InstallAgent,
+    /// Remove the device helper from the current device
+    RemoveAgent,
+    /// Collect crash logs of a package into a local diagnostics bundle
+    CollectCrashReport(String),
+    /// Change a rendering tweak, applied to apps started afterwards
+    SetDeviceTweak(DeviceTweak),
+    /// List the permissions a package requests and which of them are granted
+    GetAppPermissions(String),
+    /// Grant or revoke a runtime permission of a package
+    SetAppPermission {
+        package_name: String,
+        permission: String,
+        granted: bool,
+    },
+    /// Put the controllers of the current device into pairing mode
+    PairControllers,
+}
+
+impl AdbCommand {
+    /// Whether the command may be a step of a command macro.
+    ///
+    /// Only commands that act on the current device are allowed. Commands that switch the current
+    /// device or only produce output for a dialog are not, and neither are new commands until
+    /// they are added here.
+    pub(crate) fn macro_supported(&self) -> bool {
+        matches!(
+            self,
+            Self::LaunchApp(_)
+                | Self::ForceStopApp(_)
+                | Self::UninstallPackage(_)
+                | Self::RefreshDevice(_)
+                | Self::Reboot(_)
+                | Self::SetProximitySensor { .. }
+                | Self::SetGuardianPaused(_)
+                | Self::ScheduleAutoRevert { .. }
+                | Self::SetStorageConnection(_)
+                | Self::InstallAgent
+                | Self::RemoveAgent
+                | Self::SetDeviceTweak(_)
+                | Self::SetAppPermission { .. }
+                | Self::PairControllers
+        )
+    }
+}
+
+/// Parts of the device info a refresh fetches again
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum RefreshScope {
+    /// Everything, including the package list
+    #[default]
+    Full,
+    Battery,
+    /// Free and total storage space
+    Space,
+    /// Installed packages and their sizes
+    Packages,
+}
+
+/// Rendering tweak to set. None restores the system default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum DeviceTweak {
+    /// Display refresh rate in Hz
+    RefreshRate(Option<u32>),
+    /// CPU performance level, 0 to 5
+    CpuLevel(Option<u8>),
+    /// GPU performance level, 0 to 5
+    GpuLevel(Option<u8>),
+    /// Default eye texture size
+    TextureSize(Option<TextureSize>),
+    /// Fixed foveated rendering level, 0 (off) to 4
+    FixedFoveation(Option<u8>),
 }
 
 #[derive(Serialize, Deserialize, DartSignal)]
@@ -42,13 +126,20 @@ pub(crate) enum AdbCommandKind {
     Reboot,
     ProximitySensorSet,
     GuardianPausedSet,
+    AutoRevertSchedule,
     StartCasting,
     ConnectTo,
     WirelessAdbEnable,
     StorageConnectionSet,
+    DeviceWake,
+    AgentInstall,
+    AgentRemove,
+    DeviceTweakSet,
+    AppPermissionSet,
+    ControllerPairingStart,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, SignalPiece)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
 pub(crate) enum RebootMode {
     Normal,
     Bootloader,
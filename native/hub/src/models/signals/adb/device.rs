@@ -3,7 +3,10 @@ use serde::Serialize;
 
 use crate::{
     adb,
-    models::{InstalledPackage, SpaceInfo, vendor::quest_controller::HeadsetControllersInfo},
+    models::{
+        DeviceCapabilities, DeviceTweaks, FirmwareInfo, InstalledPackage, SpaceInfo,
+        vendor::{Vendor, quest_controller::HeadsetControllersInfo},
+    },
 };
 
 #[derive(Serialize, SignalPiece)]
@@ -24,6 +27,16 @@ pub(crate) struct AdbDevice {
     pub proximity_disabled: Option<bool>,
     pub storage_connected: Option<bool>,
     pub usb_speed: Option<String>,
+    /// Headset vendor
+    pub vendor: Vendor,
+    /// Optional features supported by the device
+    pub capabilities: DeviceCapabilities,
+    /// OS build of the device
+    pub firmware: FirmwareInfo,
+    /// Current rendering tweaks
+    pub tweaks: DeviceTweaks,
+    /// Version of the installed device helper
+    pub agent_version: Option<u64>,
 }
 
 #[derive(Serialize, RustSignal)]
@@ -31,9 +44,21 @@ pub(crate) struct DeviceChangedEvent {
     pub device: Option<AdbDevice>,
 }
 
+/// Sent when an operation is refused because the device does not have enough free space
+#[derive(Serialize, RustSignal)]
+pub(crate) struct InsufficientDeviceSpace {
+    /// Estimated space the operation needs, in bytes
+    pub required: u64,
+    /// Space currently usable on the device, in bytes
+    pub available: u64,
+    /// Space that has to be freed before retrying, in bytes
+    pub missing: u64,
+}
+
 impl From<adb::device::AdbDevice> for AdbDevice {
     fn from(device: adb::device::AdbDevice) -> Self {
         AdbDevice {
+            agent_version: device.agent_version(),
             name: device.name,
             product: device.product,
             serial: device.serial,
@@ -48,6 +73,10 @@ impl From<adb::device::AdbDevice> for AdbDevice {
             proximity_disabled: device.proximity_disabled,
             storage_connected: device.storage_connected,
             usb_speed: device.usb_speed,
+            vendor: device.vendor,
+            capabilities: device.capabilities,
+            firmware: device.firmware,
+            tweaks: device.tweaks,
         }
     }
 }
@@ -1,9 +1,21 @@
 use rinf::RustSignal;
 use serde::{Deserialize, Serialize};
 
+use crate::models::signals::task::TaskArtifact;
+
 /// Response signal carrying raw battery dump output
 #[derive(Serialize, Deserialize, RustSignal)]
 pub(crate) struct BatteryDumpResponse {
     pub command_key: String,
     pub dump: String,
 }
+
+/// Response signal carrying the crash report bundle collected for a package
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct CrashReportResponse {
+    pub command_key: String,
+    pub package_name: String,
+    /// Zip bundle on the host, None if collection failed
+    pub bundle: Option<TaskArtifact>,
+    pub error: Option<String>,
+}
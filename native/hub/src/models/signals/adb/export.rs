@@ -0,0 +1,29 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// File format of an installed app list export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Request to write the installed packages of the current device to a file
+#[derive(Debug, Serialize, Deserialize, DartSignal)]
+pub(crate) struct ExportInstalledListRequest {
+    /// Destination file, overwritten if it exists
+    pub path: String,
+    pub format: ExportFormat,
+    /// Whether to include system packages
+    pub include_system: bool,
+}
+
+/// Result of an installed app list export
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ExportInstalledListResponse {
+    pub path: String,
+    /// Number of exported packages
+    pub count: u32,
+    pub error: Option<String>,
+}
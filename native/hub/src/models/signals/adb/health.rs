@@ -0,0 +1,25 @@
+use rinf::{RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// State of the ADB server and device tracker as seen by the watchdog
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum AdbHealth {
+    #[default]
+    Healthy,
+    /// Health checks are failing, recovery starts if they keep failing
+    Unresponsive,
+    /// The ADB server and device tracker were restarted
+    Recovering,
+    /// The last restart failed, `retry_in_secs` tells when the next one is tried
+    RecoveryFailed,
+}
+
+/// Sent whenever the ADB watchdog status changes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, RustSignal)]
+pub(crate) struct AdbHealthChanged {
+    pub health: AdbHealth,
+    /// Restarts tried since the ADB server was last healthy
+    pub recovery_attempts: u32,
+    pub retry_in_secs: Option<u32>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,31 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// VR streaming app that may compete with YAAS for the USB link or the ADB server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum StreamingClient {
+    /// Meta Quest Link, formerly Oculus Link
+    MetaLink,
+    VirtualDesktop,
+    /// ALVR, which runs its own ADB server for wired streaming
+    Alvr,
+    SteamVr,
+}
+
+/// ADB server process started from another binary than the one YAAS uses
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct ForeignAdbServer {
+    pub pid: u32,
+    pub path: String,
+}
+
+/// Streaming apps and ADB servers of other apps running on this computer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, RustSignal)]
+pub(crate) struct HostConflictsChanged {
+    pub streaming_clients: Vec<StreamingClient>,
+    pub foreign_adb_servers: Vec<ForeignAdbServer>,
+}
+
+/// Stops the ADB servers in the last `HostConflictsChanged` and restarts the one YAAS uses
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct StopForeignAdbServersRequest {}
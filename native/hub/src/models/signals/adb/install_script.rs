@@ -0,0 +1,53 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Kind of device operation an install script runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum ScriptOperationKind {
+    Install,
+    Uninstall,
+    Shell,
+    Push,
+    Pull,
+    Pause,
+}
+
+/// Line of an install script, as shown to the user before the script runs
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct ScriptOperation {
+    pub line: u32,
+    pub kind: ScriptOperationKind,
+    /// Paths, package or command the line works on
+    pub detail: String,
+}
+
+/// Asks the user to approve an install script before any of it runs. Answered with
+/// `InstallScriptDecision`.
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct InstallScriptReview {
+    pub review_id: u64,
+    pub script_path: String,
+    pub operations: Vec<ScriptOperation>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct InstallScriptDecision {
+    pub review_id: u64,
+    pub approved: bool,
+}
+
+/// Line of an install script that cannot be run
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct UnsupportedScriptLine {
+    pub line: u32,
+    pub command: String,
+    pub reason: String,
+}
+
+/// Sent when an install script is refused before any of it runs, listing every line that cannot
+/// be run
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct InstallScriptUnsupported {
+    pub script_path: String,
+    pub lines: Vec<UnsupportedScriptLine>,
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use anyhow::{Result, ensure};
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+use super::command::AdbCommand;
+
+/// Longest pause a single macro step may take
+pub(crate) const MAX_MACRO_DELAY: Duration = Duration::from_secs(600);
+/// Most steps a macro may have
+pub(crate) const MAX_MACRO_STEPS: usize = 50;
+
+/// Named sequence of device commands saved in settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct CommandMacro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum MacroStep {
+    /// Runs a command on the current device
+    Command(AdbCommand),
+    /// Waits before the next step, in milliseconds
+    Delay(u64),
+}
+
+impl CommandMacro {
+    /// Checks that the macro can run, before any of its steps is executed
+    pub(crate) fn validate(&self) -> Result<()> {
+        ensure!(!self.name.trim().is_empty(), "Macro name must not be empty");
+        ensure!(!self.steps.is_empty(), "Macro has no steps");
+        ensure!(
+            self.steps.len() <= MAX_MACRO_STEPS,
+            "Macro has {} steps, at most {MAX_MACRO_STEPS} are allowed",
+            self.steps.len()
+        );
+        for (index, step) in self.steps.iter().enumerate() {
+            match step {
+                MacroStep::Command(command) => ensure!(
+                    command.macro_supported(),
+                    "Step {} ({command:?}) cannot be used in macros",
+                    index + 1
+                ),
+                MacroStep::Delay(ms) => ensure!(
+                    Duration::from_millis(*ms) <= MAX_MACRO_DELAY,
+                    "Step {} waits longer than {} seconds",
+                    index + 1,
+                    MAX_MACRO_DELAY.as_secs()
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rewrites steps saved by older versions in `command_macros` settings JSON to the current form.
+///
+/// `RefreshDevice` used to have no scope and was saved as a plain string.
+pub(crate) fn upgrade_saved_macros(command_macros: &mut serde_json::Value) {
+    let Some(macros) = command_macros.as_array_mut() else {
+        return;
+    };
+    let steps = macros
+        .iter_mut()
+        .filter_map(|command_macro| command_macro.get_mut("steps")?.as_array_mut())
+        .flatten();
+    for step in steps {
+        if let Some(command) = step.get_mut("Command")
+            && command.as_str() == Some("RefreshDevice")
+        {
+            *command = serde_json::json!({ "RefreshDevice": "Full" });
+        }
+    }
+}
+
+/// Runs the saved macro with the given name on the current device
+#[derive(Debug, Serialize, Deserialize, DartSignal)]
+pub(crate) struct RunCommandMacroRequest {
+    pub name: String,
+    /// Arbitrary identifier to correlate the result with UI elements
+    pub command_key: String,
+}
+
+/// Stops a running macro, the step in progress is interrupted and the rest are skipped
+#[derive(Debug, Serialize, Deserialize, DartSignal)]
+pub(crate) struct CancelCommandMacroRequest {
+    /// Key the macro was started with
+    pub command_key: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum MacroStepStatus {
+    Succeeded,
+    Failed,
+    /// Interrupted by a cancel request
+    Cancelled,
+    /// Not run because an earlier step failed or the macro was cancelled
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct MacroStepResult {
+    pub status: MacroStepStatus,
+    pub error: Option<String>,
+}
+
+/// Outcome of a macro run, with one result per step
+#[derive(Debug, Serialize, Deserialize, RustSignal)]
+pub(crate) struct CommandMacroResult {
+    pub name: String,
+    pub command_key: String,
+    pub steps: Vec<MacroStepResult>,
+    /// Set if the macro could not be started at all
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::signals::adb::command::RefreshScope;
+
+    fn command_macro(steps: Vec<MacroStep>) -> CommandMacro {
+        CommandMacro { name: "Morning".to_string(), steps }
+    }
+
+    #[test]
+    fn validates_macros() {
+        let valid = command_macro(vec![
+            MacroStep::Command(AdbCommand::SetGuardianPaused(true)),
+            MacroStep::Delay(2000),
+            MacroStep::Command(AdbCommand::LaunchApp("com.example.game".to_string())),
+        ]);
+        assert!(valid.validate().is_ok());
+
+        assert!(command_macro(Vec::new()).validate().is_err());
+        assert!(CommandMacro { name: " ".to_string(), ..valid.clone() }.validate().is_err());
+        assert!(
+            command_macro(vec![MacroStep::Command(AdbCommand::GetBatteryDump)]).validate().is_err()
+        );
+        assert!(command_macro(vec![MacroStep::Delay(601_000)]).validate().is_err());
+    }
+
+    #[test]
+    fn upgrades_refresh_steps_without_scope() {
+        let mut saved = serde_json::json!([{
+            "name": "Morning",
+            "steps": [{ "Command": "RefreshDevice" }, { "Delay": 500 }],
+        }]);
+        upgrade_saved_macros(&mut saved);
+
+        let macros: Vec<CommandMacro> = serde_json::from_value(saved).unwrap();
+        assert_eq!(
+            macros[0].steps,
+            [
+                MacroStep::Command(AdbCommand::RefreshDevice(RefreshScope::Full)),
+                MacroStep::Delay(500)
+            ]
+        );
+    }
+}
@@ -1,5 +1,16 @@
+pub(crate) mod auto_revert;
+pub(crate) mod backup_estimate;
+pub(crate) mod battery_history;
 pub(crate) mod command;
 pub(crate) mod device;
 pub(crate) mod devices_list;
 pub(crate) mod dump;
+pub(crate) mod export;
+pub(crate) mod health;
+pub(crate) mod host_conflicts;
+pub(crate) mod install_script;
+pub(crate) mod macros;
+pub(crate) mod permissions;
+pub(crate) mod shell;
 pub(crate) mod state;
+pub(crate) mod wake;
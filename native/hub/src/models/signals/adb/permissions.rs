@@ -0,0 +1,40 @@
+use rinf::{RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Whether `name` can be passed to `pm grant` and `pm revoke` as a permission name
+pub(crate) fn is_valid_permission_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+}
+
+/// Permission requested by an installed app
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct AppPermission {
+    /// Full name, e.g. `android.permission.RECORD_AUDIO`
+    pub name: String,
+    pub granted: bool,
+    /// Whether the permission is granted at runtime and can be granted or revoked with adb.
+    /// Other permissions are granted at install time.
+    pub runtime: bool,
+}
+
+/// Response signal carrying the permissions of a package, sent for `GetAppPermissions`
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct AppPermissionsResponse {
+    pub command_key: String,
+    pub package_name: String,
+    /// Requested permissions in the order the app declares them
+    pub permissions: Vec<AppPermission>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_permission_names() {
+        assert!(is_valid_permission_name("android.permission.RECORD_AUDIO"));
+        assert!(!is_valid_permission_name(""));
+        assert!(!is_valid_permission_name("android.permission.CAMERA; reboot"));
+    }
+}
@@ -0,0 +1,83 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Opens a shell session on the connected device, answered with `ShellSessionOpened`.
+///
+/// Only allowed while the shell console is enabled in settings.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct OpenShellSessionRequest {
+    pub request_key: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ShellSessionOpened {
+    pub request_key: String,
+    pub session_id: Option<u32>,
+    /// Serial of the device the session runs on
+    pub device_serial: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Runs a command in a shell session.
+///
+/// Commands run one at a time in the order received. Each is answered with
+/// `ShellCommandFinished` unless the session closes first.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ShellCommandRequest {
+    pub session_id: u32,
+    /// Chosen by the sender, echoed in output lines and the finish signal
+    pub command_id: u32,
+    pub command: String,
+    /// Seconds the command may run before the session is closed, default if not set
+    pub timeout_secs: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct CloseShellSessionRequest {
+    pub session_id: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum ShellOutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Line of output of a shell session
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ShellOutputLine {
+    pub session_id: u32,
+    /// Command running when the line was read
+    pub command_id: Option<u32>,
+    pub stream: ShellOutputStream,
+    pub line: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ShellCommandFinished {
+    pub session_id: u32,
+    pub command_id: u32,
+    pub exit_code: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum ShellSessionEndReason {
+    /// Closed on request
+    Closed,
+    /// The shell exited, e.g. after `exit` or because the device went away
+    Exited,
+    /// A command ran past its timeout
+    CommandTimedOut,
+    /// No command was run for too long
+    Idle,
+    /// The shell console was disabled in settings
+    Disabled,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ShellSessionClosed {
+    pub session_id: u32,
+    pub reason: ShellSessionEndReason,
+    /// Exit code of the shell, if it exited
+    pub exit_code: Option<i32>,
+}
@@ -0,0 +1,32 @@
+use rinf::{RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum WakeStepKind {
+    /// Asked the ADB server to reconnect offline transports
+    Reconnect,
+    /// Sent the wakeup key event
+    Keyevent,
+    /// Sent the proximity sensor broadcast
+    ProximityClose,
+    /// Connected to the device once it came online
+    Connect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct WakeStep {
+    pub kind: WakeStepKind,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// What was attempted to bring an offline device back and whether it worked
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct DeviceWakeReport {
+    pub serial: String,
+    /// Whether the attempt was started automatically rather than by the user
+    pub automatic: bool,
+    pub steps: Vec<WakeStep>,
+    /// Whether the device is connected now
+    pub recovered: bool,
+}
@@ -1,26 +1,73 @@
 use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 
+use crate::models::BackupSizes;
+
 #[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
 pub(crate) struct BackupEntry {
     pub path: String,
     pub name: String,
+    /// Package the backup was made of, if recorded in its manifest
+    pub package_name: Option<String>,
+    /// App name recorded in the manifest
+    pub display_name: Option<String>,
+    pub version_code: Option<u64>,
+    pub version_name: Option<String>,
+    /// Serial of the device the backup was made on
+    pub device_serial: Option<String>,
     /// Milliseconds since Unix epoch
     pub timestamp: u64,
     /// Total size of this backup directory in bytes
     pub total_size: u64,
+    /// Sizes of the parts, all 0 for backups made before they were recorded
+    pub sizes: BackupSizes,
     pub has_apk: bool,
     pub has_private_data: bool,
     pub has_shared_data: bool,
     pub has_obb: bool,
+    /// Whether this is a single-file `.yaasbackup` archive rather than a directory
+    pub compressed: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum BackupsSortOrder {
+    #[default]
+    Date,
+    Size,
+}
+
+/// Filters and ordering of the backups list
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct BackupsQuery {
+    /// Words that must all appear in the name or package name, case-insensitive
+    pub search: String,
+    /// Only backups made at or after this time, in milliseconds since Unix epoch
+    pub since: Option<u64>,
+    /// Only backups made before this time, in milliseconds since Unix epoch
+    pub until: Option<u64>,
+    pub require_apk: bool,
+    /// Require private or shared data
+    pub require_data: bool,
+    pub require_obb: bool,
+    pub sort: BackupsSortOrder,
+    pub ascending: bool,
 }
 
 #[derive(Serialize, Deserialize, DartSignal)]
-pub(crate) struct GetBackupsRequest {}
+pub(crate) struct GetBackupsRequest {
+    pub query: BackupsQuery,
+}
 
 #[derive(Serialize, Deserialize, RustSignal)]
 pub(crate) struct GetBackupsResponse {
+    /// Search of the request, to match the response to it
+    pub search: String,
+    /// Backups matching the query, in its order
     pub entries: Vec<BackupEntry>,
+    /// Number of backups before filtering
+    pub total_count: u32,
+    /// Combined size of the matching backups in bytes
+    pub total_size: u64,
     pub error: Option<String>,
 }
 
@@ -45,3 +92,49 @@ pub(crate) struct GetBackupsDirectoryRequest {}
 pub(crate) struct GetBackupsDirectoryResponse {
     pub path: String,
 }
+
+/// Asks which backups the retention settings would prune, without deleting anything
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct PreviewBackupPruneRequest {}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct BackupPrunePreview {
+    /// Backups that would be deleted, oldest first
+    pub entries: Vec<BackupEntry>,
+    /// Space deleting them would free, in bytes
+    pub freed_bytes: u64,
+    pub error: Option<String>,
+}
+
+/// Deletes the backups the retention settings prune
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct PruneBackupsRequest {}
+
+/// Sent after a requested or background prune
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct BackupsPruned {
+    pub deleted: u32,
+    /// Space freed, in bytes
+    pub freed_bytes: u64,
+    /// Failed deletions, the other backups are pruned regardless
+    pub error: Option<String>,
+}
+
+/// Checks a backup against its manifest without modifying it
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct VerifyBackupRequest {
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct BackupVerification {
+    pub path: String,
+    /// Whether the manifest lists the backup files, older backups only have their layout checked
+    pub has_file_list: bool,
+    /// Files compared with the manifest
+    pub checked_files: u32,
+    /// Problems found, empty if the backup is intact
+    pub problems: Vec<String>,
+    /// Set if the backup could not be read at all
+    pub error: Option<String>,
+}
@@ -0,0 +1,27 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Lists the files of a release, to show what a download fetches and pick the ones it is
+/// limited to
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ListReleaseFilesRequest {
+    pub app_full_name: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ReleaseFilesResponse {
+    pub app_full_name: String,
+    pub files: Vec<ReleaseFile>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, SignalPiece)]
+pub(crate) struct ReleaseFile {
+    /// Path relative to the release directory, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// Last modification time on the remote in milliseconds since Unix epoch
+    pub modified: Option<u64>,
+    /// Already in the local download with the same size, so it is not transferred again
+    pub downloaded: bool,
+}
@@ -18,3 +18,17 @@ pub(crate) struct CloudAppsChangedEvent {
     pub donation_blacklist: Option<Vec<String>>,
     pub error: Option<String>,
 }
+
+/// Changes to the app list since the last list or delta that was sent, sent for refreshes
+/// instead of a full `CloudAppsChangedEvent` list
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct CatalogDelta {
+    /// Number of apps the changes apply to. A UI holding a different number of apps should
+    /// request the full list again.
+    pub base_count: u32,
+    pub added: Vec<CloudApp>,
+    /// New versions of apps with the same full name
+    pub changed: Vec<CloudApp>,
+    /// Full names of apps no longer in the list
+    pub removed: Vec<String>,
+}
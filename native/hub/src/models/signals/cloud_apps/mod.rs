@@ -1,3 +1,5 @@
 pub(crate) mod details;
+pub(crate) mod files;
 pub(crate) mod list;
 pub(crate) mod reviews;
+pub(crate) mod updates;
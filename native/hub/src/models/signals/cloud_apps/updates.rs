@@ -0,0 +1,26 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Installed app with a newer version in the catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct UpdatableApp {
+    /// Package name as installed on the device
+    pub package_name: String,
+    pub installed_version_code: u64,
+    /// Full name of the catalog release to update to
+    pub full_name: String,
+    pub app_name: String,
+    pub version_code: u64,
+}
+
+/// Requests a fresh `UpdatableApps` signal for the current device and catalog
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetUpdatableAppsRequest {}
+
+/// Apps on the current device that have newer catalog versions.
+///
+/// Sent whenever the device, the catalog or version pins change.
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct UpdatableApps {
+    pub apps: Vec<UpdatableApp>,
+}
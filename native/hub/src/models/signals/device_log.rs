@@ -0,0 +1,53 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Source of a device maintenance log entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum DeviceLogEntryKind {
+    /// Free-form note written by the user
+    Note,
+    /// Backup restored to the device
+    Restore,
+    /// Reboot requested from the app
+    Reboot,
+}
+
+/// Entry of a per-headset maintenance log
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct DeviceLogEntry {
+    /// Unique within the device log
+    pub id: u64,
+    /// Milliseconds since Unix epoch when the entry was added
+    pub timestamp: u64,
+    pub kind: DeviceLogEntryKind,
+    pub text: String,
+}
+
+/// Requests the maintenance log of a device by its true serial
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetDeviceLogRequest {
+    pub serial: String,
+}
+
+/// Adds a note to the maintenance log of a device
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct AddDeviceNoteRequest {
+    pub serial: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct DeleteDeviceLogEntryRequest {
+    pub serial: String,
+    pub id: u64,
+}
+
+/// Maintenance log of a device, newest first.
+///
+/// Sent in response to requests and whenever an entry is recorded automatically.
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct DeviceLogChanged {
+    pub serial: String,
+    pub entries: Vec<DeviceLogEntry>,
+    pub error: Option<String>,
+}
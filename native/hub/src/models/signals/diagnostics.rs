@@ -0,0 +1,14 @@
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+
+/// Assembles a support bundle with recent logs, redacted settings, device and tool details,
+/// answered with `DiagnosticsBundleResponse`
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct CreateDiagnosticsBundleRequest {}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct DiagnosticsBundleResponse {
+    /// Path of the zip bundle on the host, None if creating it failed
+    pub bundle_path: Option<String>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,46 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Kind of data YAAS keeps on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum DiskUsageCategory {
+    /// Downloaded apps in the downloads location
+    Downloads,
+    /// Cached thumbnails, trailers and icons
+    MediaCache,
+    /// Log files of earlier sessions
+    Logs,
+    /// App backups in the backups location
+    Backups,
+    /// Backups taken before uninstalling apps
+    Trash,
+    /// Apps pulled from the device for donation and their archives
+    DonationUploads,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct CategoryUsage {
+    pub category: DiskUsageCategory,
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// Requests a `DiskUsageReport`
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetDiskUsageRequest {}
+
+/// Deletes the data of a category, answered with a new `DiskUsageReport`.
+///
+/// The log file of the running session is kept.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ClearDiskUsageRequest {
+    pub category: DiskUsageCategory,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct DiskUsageReport {
+    pub categories: Vec<CategoryUsage>,
+    /// Set if clearing a category failed
+    pub error: Option<String>,
+}
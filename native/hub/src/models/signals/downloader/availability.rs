@@ -6,9 +6,11 @@ pub(crate) struct RepoCapabilities {
     pub supports_bandwidth_limit: bool,
     pub supports_download_mode_selection: bool,
     pub supports_donation_upload: bool,
+    /// Releases can be listed and downloaded with only some of their files
+    pub supports_partial_downloads: bool,
 }
 
-#[derive(Default, serde::Serialize, serde::Deserialize, RustSignal)]
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize, RustSignal)]
 pub(crate) struct DownloaderAvailabilityChanged {
     pub available: bool,
     pub initializing: bool,
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 
@@ -14,12 +16,39 @@ pub(crate) struct DownloadEntry {
     pub version_code: Option<u32>,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum DownloadsSortOrder {
+    Name,
+    /// When the download finished
+    #[default]
+    Date,
+    Size,
+}
+
+/// Looks up downloads in the index kept by the backend, one page at a time
 #[derive(Serialize, Deserialize, DartSignal)]
-pub(crate) struct GetDownloadsRequest {}
+pub(crate) struct QueryDownloadsRequest {
+    /// Words that must all appear in the name or package name, case-insensitive
+    pub search: String,
+    pub sort: DownloadsSortOrder,
+    pub ascending: bool,
+    pub offset: u32,
+    /// Maximum number of entries to return, 0 for no limit
+    pub limit: u32,
+    /// Rescan the downloads directory before the lookup
+    pub refresh: bool,
+}
 
 #[derive(Serialize, Deserialize, RustSignal)]
-pub(crate) struct GetDownloadsResponse {
+pub(crate) struct QueryDownloadsResponse {
+    /// Search and offset of the request, to match the response to it
+    pub search: String,
+    pub offset: u32,
     pub entries: Vec<DownloadEntry>,
+    /// Number of downloads matching the search across all pages
+    pub total: u32,
+    /// Newest version code downloaded for each package on this page, counting all downloads
+    pub newest_version_codes: BTreeMap<String, u32>,
     pub error: Option<String>,
 }
 
@@ -54,3 +83,103 @@ pub(crate) struct DeleteAllDownloadsResponse {
     pub skipped: u32,
     pub error: Option<String>,
 }
+
+/// Reports the space saved by linking files shared by downloads. With `apply`, every finished
+/// download is deduplicated first.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct DedupeDownloadsRequest {
+    pub apply: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, RustSignal)]
+pub(crate) struct DedupeReport {
+    /// Distinct file contents kept in the store
+    pub objects: u32,
+    /// Download files linked to a stored content
+    pub linked_files: u32,
+    /// Bytes the linked files would take as separate copies
+    pub saved_bytes: u64,
+    /// Files linked by this run
+    pub newly_linked: u32,
+    pub error: Option<String>,
+}
+
+/// Download that was interrupted and can be continued with a `ResumeDownload` task
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct ResumableDownloadEntry {
+    pub app_full_name: String,
+    pub true_package_name: String,
+    /// Milliseconds since Unix epoch
+    pub started_at: u64,
+    /// Milliseconds since Unix epoch
+    pub updated_at: u64,
+    pub attempts: u32,
+    /// Size of the files already present in the destination directory
+    pub downloaded_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetResumableDownloadsRequest {}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct GetResumableDownloadsResponse {
+    pub entries: Vec<ResumableDownloadEntry>,
+    pub error: Option<String>,
+}
+
+/// Discards an interrupted download, removing its checkpoint and partially downloaded files.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct DiscardResumableDownloadRequest {
+    pub app_full_name: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct DiscardResumableDownloadResponse {
+    pub app_full_name: String,
+    pub error: Option<String>,
+}
+
+/// Checks whether a local app folder can be installed as is, without touching the device
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct CheckDownloadHealthRequest {
+    pub path: String,
+}
+
+/// Problem that keeps a local app folder from installing correctly
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum DownloadHealthIssue {
+    /// Neither an APK nor an install script was found
+    NoApk,
+    /// More than one APK that are not splits of one app, and no install script to pick one
+    MultipleApks(Vec<String>),
+    /// The APK could not be parsed
+    ApkUnreadable { file: String, error: String },
+    /// OBB files are in a folder not named after the package
+    ObbFolderMismatch { folder: String, package_name: String },
+    /// An OBB file name refers to another package
+    ObbFileMismatch { file: String, package_name: String },
+    /// OBB files next to the APK instead of in a folder named after the package
+    LooseObbFiles(Vec<String>),
+    /// The install script could not be read or parsed
+    ScriptUnreadable(String),
+    /// A file used by the install script is missing
+    ScriptFileMissing { line: u32, path: String },
+    /// The install script uses a command that cannot be run
+    ScriptCommandUnsupported { line: u32, command: String },
+    /// An archive in the folder could not be listed
+    ArchiveUnreadable { file: String, error: String },
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct DownloadHealthReport {
+    pub path: String,
+    /// Package of the APK, if one could be parsed
+    pub package_name: Option<String>,
+    pub version_code: Option<u32>,
+    /// Whether the folder is installed through its install script
+    pub uses_install_script: bool,
+    /// Empty if the folder is ready to install
+    pub issues: Vec<DownloadHealthIssue>,
+    /// Set if the folder could not be checked at all
+    pub error: Option<String>,
+}
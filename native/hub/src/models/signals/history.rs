@@ -0,0 +1,73 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+use super::task::{DownloadSummary, TaskArtifact, TaskStatus};
+
+/// Operation recorded in the install history
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum HistoryAction {
+    Install,
+    Uninstall,
+    Backup,
+    Restore,
+    Download,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum HistoryOutcome {
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// Finished operation in the install history
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct HistoryEntry {
+    pub action: HistoryAction,
+    /// Task name shown in the UI (app full name, file name or display name)
+    pub name: String,
+    pub package_name: Option<String>,
+    /// Version installed after the operation, or before it for uninstalls and backups
+    pub version_code: Option<u64>,
+    pub version_name: Option<String>,
+    /// Milliseconds since Unix epoch when the operation finished
+    pub timestamp: u64,
+    pub duration_ms: u64,
+    pub outcome: HistoryOutcome,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub download_summary: Option<DownloadSummary>,
+    /// Progress over the run of the task, oldest first
+    #[serde(default)]
+    pub timeline: Vec<ProgressSnapshot>,
+    /// Outputs of the task that still existed when it finished
+    #[serde(default)]
+    pub artifacts: Vec<TaskArtifact>,
+}
+
+/// Progress of a task at one point of its run
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct ProgressSnapshot {
+    /// Milliseconds since Unix epoch
+    pub timestamp: u64,
+    pub status: TaskStatus,
+    pub step: u32,
+    /// Overall progress across all steps
+    pub percent: u8,
+    pub message: String,
+}
+
+/// Requests history entries, newest first. `package_name` limits them to one package.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetInstallHistoryRequest {
+    pub package_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ClearInstallHistoryRequest {}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct InstallHistoryResponse {
+    pub entries: Vec<HistoryEntry>,
+    pub error: Option<String>,
+}
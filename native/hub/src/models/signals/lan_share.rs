@@ -0,0 +1,53 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// App shared by this instance with peers on the local network
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct LanShare {
+    pub id: String,
+    pub name: String,
+    /// Local directory being shared
+    pub path: String,
+    /// Total size of the shared files in bytes
+    pub size: u64,
+    pub file_count: u32,
+}
+
+/// App shared by another instance found on the local network
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct PeerShare {
+    /// Name the peer announces itself with
+    pub peer: String,
+    /// `host:port` of the peer's share server
+    pub address: String,
+    pub id: String,
+    pub name: String,
+    /// Total size of the shared files in bytes
+    pub size: u64,
+}
+
+/// Starts sharing a downloaded app directory on the local network
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ShareOnLanRequest {
+    pub path: String,
+    /// Name shown to peers, the directory name if empty
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct StopLanShareRequest {
+    pub id: String,
+}
+
+/// Asks for own shares and the shares of peers found so far
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetLanSharesRequest {}
+
+/// Own shares and peer shares, sent after every change and on request
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct LanSharesChanged {
+    pub shares: Vec<LanShare>,
+    pub peers: Vec<PeerShare>,
+    /// Error of the last request, if it failed
+    pub error: Option<String>,
+}
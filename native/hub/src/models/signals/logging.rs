@@ -4,7 +4,9 @@ use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 use tracing::Level;
 
-#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, SignalPiece,
+)]
 pub(crate) enum LogLevel {
     Trace,
     Debug,
@@ -81,3 +83,31 @@ pub(crate) struct GetLogsDirectoryRequest {}
 pub(crate) struct GetLogsDirectoryResponse {
     pub path: String,
 }
+
+/// Looks up past entries in the log files, newest first, one page at a time
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct QueryLogsRequest {
+    /// Least severe level to include
+    pub min_level: LogLevel,
+    /// Target prefix the entries must have, e.g. `hub::adb`, all targets if empty
+    pub module: String,
+    /// Words that must all appear in the message, case-insensitive
+    pub search: String,
+    /// Only entries logged at or after this time, in milliseconds since Unix epoch
+    pub since: Option<u64>,
+    /// Only entries logged before this time, in milliseconds since Unix epoch
+    pub until: Option<u64>,
+    pub offset: u32,
+    /// Maximum number of entries to return, 0 for no limit
+    pub limit: u32,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct QueryLogsResponse {
+    /// Offset of the request, to match the response to it
+    pub offset: u32,
+    pub entries: Vec<LogEntry>,
+    /// Number of entries matching the query across all pages
+    pub total: u32,
+    pub error: Option<String>,
+}
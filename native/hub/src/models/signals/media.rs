@@ -0,0 +1,54 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum MediaKind {
+    Thumbnail,
+    Trailer,
+}
+
+/// Requests a local copy of an app's media, downloading or revalidating it as needed.
+/// Without `fetch`, only reports whether the media exists and returns it if already cached.
+/// Fetched trailers are cached in the background and streamed until then.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetAppMediaRequest {
+    pub package_name: String,
+    pub kind: MediaKind,
+    pub fetch: bool,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct AppMediaResponse {
+    pub package_name: String,
+    pub kind: MediaKind,
+    pub available: bool,
+    /// Path of the cached file on the host
+    pub path: Option<String>,
+    /// URL to stream the media from while it is cached in the background, for fetched trailers
+    /// that are not cached yet
+    pub stream_url: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Requests details of a local APK file, for previewing it before installing
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetApkInfoRequest {
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ApkInfoResponse {
+    pub path: String,
+    pub package_name: Option<String>,
+    pub label: Option<String>,
+    pub version_code: Option<u32>,
+    pub version_name: Option<String>,
+    pub min_sdk_version: Option<u32>,
+    pub target_sdk_version: Option<u32>,
+    pub permissions: Vec<String>,
+    /// ABIs the APK has native libraries for
+    pub native_abis: Vec<String>,
+    /// Path of the extracted launcher icon on the host
+    pub icon_path: Option<String>,
+    pub error: Option<String>,
+}
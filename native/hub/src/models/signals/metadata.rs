@@ -0,0 +1,82 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct AppNameOverride {
+    pub package_name: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetAppNameOverridesRequest {}
+
+/// Sets the display name override for a package. `None` or a blank name clears it.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SetAppNameOverrideRequest {
+    pub package_name: String,
+    pub name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct AppNameOverridesChanged {
+    pub overrides: Vec<AppNameOverride>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct AppVersionPin {
+    pub package_name: String,
+    /// Newer catalog versions are not offered as updates
+    pub pinned_version_code: Option<u64>,
+    /// Catalog versions that are never offered as updates
+    pub ignored_version_codes: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetAppVersionPinsRequest {}
+
+/// Pins a package to a version code. `None` unpins it.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SetAppVersionPinRequest {
+    pub package_name: String,
+    pub version_code: Option<u64>,
+}
+
+/// Ignores (or stops ignoring) a specific catalog version of a package for updates.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SetIgnoredAppVersionRequest {
+    pub package_name: String,
+    pub version_code: u64,
+    pub ignored: bool,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct AppVersionPinsChanged {
+    pub pins: Vec<AppVersionPin>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct WatchedAppEntry {
+    /// Original package name of the catalog app
+    pub package_name: String,
+    /// Newest catalog version already reported
+    pub seen_version_code: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetWatchedAppsRequest {}
+
+/// Adds a catalog app to the install-later list, or removes it.
+/// New catalog versions of listed apps are announced with a toast.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SetAppWatchedRequest {
+    pub package_name: String,
+    pub watched: bool,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct WatchedAppsChanged {
+    pub apps: Vec<WatchedAppEntry>,
+    pub error: Option<String>,
+}
@@ -2,10 +2,22 @@ pub(crate) mod adb;
 pub(crate) mod backups;
 pub(crate) mod casting;
 pub(crate) mod cloud_apps;
+pub(crate) mod device_log;
+pub(crate) mod diagnostics;
+pub(crate) mod disk_usage;
 pub(crate) mod downloader;
 pub(crate) mod downloads_local;
+pub(crate) mod history;
+pub(crate) mod lan_share;
 pub(crate) mod logging;
+pub(crate) mod media;
+pub(crate) mod metadata;
+pub(crate) mod mods;
+pub(crate) mod recipes;
 pub(crate) mod settings;
+pub(crate) mod stats;
 pub(crate) mod storage;
 pub(crate) mod system;
 pub(crate) mod task;
+pub(crate) mod trash;
+pub(crate) mod update;
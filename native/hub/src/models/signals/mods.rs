@@ -0,0 +1,36 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct ModProfile {
+    pub package_name: String,
+    /// Absolute device paths holding mod files
+    pub paths: Vec<String>,
+    /// Captured mod sets, including the vanilla snapshot
+    pub sets: Vec<String>,
+    /// Mod set currently applied on the device, `None` if vanilla
+    pub active_set: Option<String>,
+    pub has_vanilla_snapshot: bool,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetModProfilesRequest {}
+
+/// Replaces the device paths managed for a game.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SaveModProfileRequest {
+    pub package_name: String,
+    pub paths: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct DeleteModSetRequest {
+    pub package_name: String,
+    pub set_name: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct ModProfilesChanged {
+    pub profiles: Vec<ModProfile>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,131 @@
+use anyhow::{Result, ensure};
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+use crate::{adb::PackageName, models::signals::adb::permissions::is_valid_permission_name};
+
+/// Most steps a recipe may have
+pub(crate) const MAX_RECIPE_STEPS: usize = 50;
+
+/// Action run on the device after an app was installed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum RecipeStep {
+    /// Grants a runtime permission to the app
+    GrantPermission(String),
+    /// Pushes a local file or folder to a path on the device
+    PushFile { local_path: String, remote_path: String },
+    /// Starts the app once and stops it again, so it can set up its data
+    LaunchOnce,
+    /// Sets a system property
+    SetProperty { name: String, value: String },
+}
+
+/// Steps run automatically after a package is installed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct InstallRecipe {
+    pub package_name: String,
+    /// Disabled recipes are kept but not run
+    pub enabled: bool,
+    pub steps: Vec<RecipeStep>,
+}
+
+impl InstallRecipe {
+    /// Checks that the recipe can be saved, before any of its steps is run
+    pub(crate) fn validate(&self) -> Result<()> {
+        PackageName::parse(&self.package_name)?;
+        ensure!(
+            self.steps.len() <= MAX_RECIPE_STEPS,
+            "Recipe has {} steps, at most {MAX_RECIPE_STEPS} are allowed",
+            self.steps.len()
+        );
+        for (index, step) in self.steps.iter().enumerate() {
+            let number = index + 1;
+            match step {
+                RecipeStep::GrantPermission(permission) => ensure!(
+                    is_valid_permission_name(permission),
+                    "Step {number} has an invalid permission name: {permission}"
+                ),
+                RecipeStep::PushFile { local_path, remote_path } => {
+                    ensure!(!local_path.trim().is_empty(), "Step {number} has no local path");
+                    ensure!(
+                        remote_path.starts_with('/'),
+                        "Step {number} needs an absolute device path"
+                    );
+                }
+                RecipeStep::LaunchOnce => {}
+                RecipeStep::SetProperty { name, value } => {
+                    ensure!(
+                        !name.is_empty()
+                            && name.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c)),
+                        "Step {number} has an invalid property name: {name}"
+                    );
+                    ensure!(
+                        !value.contains('\'') && !value.contains('\n'),
+                        "Step {number} has a property value with quotes or line breaks"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetInstallRecipesRequest {}
+
+/// Saves the recipe of a package. A recipe without steps is removed.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SetInstallRecipeRequest {
+    pub recipe: InstallRecipe,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct InstallRecipesChanged {
+    pub recipes: Vec<InstallRecipe>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe(steps: Vec<RecipeStep>) -> InstallRecipe {
+        InstallRecipe { package_name: "com.example.game".to_string(), enabled: true, steps }
+    }
+
+    #[test]
+    fn validates_recipes() {
+        recipe(vec![
+            RecipeStep::GrantPermission("android.permission.RECORD_AUDIO".to_string()),
+            RecipeStep::PushFile {
+                local_path: "/home/me/config.json".to_string(),
+                remote_path: "/sdcard/Android/data/com.example.game/files/config.json".to_string(),
+            },
+            RecipeStep::LaunchOnce,
+            RecipeStep::SetProperty {
+                name: "debug.oculus.refreshRate".to_string(),
+                value: "120".to_string(),
+            },
+        ])
+        .validate()
+        .unwrap();
+
+        let invalid = [
+            RecipeStep::GrantPermission("android.permission.X; reboot".to_string()),
+            RecipeStep::PushFile {
+                local_path: "config.json".to_string(),
+                remote_path: "sdcard/config.json".to_string(),
+            },
+            RecipeStep::SetProperty { name: "a b".to_string(), value: "1".to_string() },
+            RecipeStep::SetProperty { name: "debug.x".to_string(), value: "'; reboot".to_string() },
+        ];
+        for step in invalid {
+            assert!(recipe(vec![step.clone()]).validate().is_err(), "{step:?}");
+        }
+        assert!(
+            InstallRecipe { package_name: "not a package".to_string(), ..recipe(vec![]) }
+                .validate()
+                .is_err()
+        );
+    }
+}
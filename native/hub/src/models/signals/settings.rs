@@ -1,4 +1,4 @@
-use rinf::{DartSignal, RustSignal};
+use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 
 use crate::models::Settings;
@@ -24,3 +24,98 @@ pub(crate) struct SettingsChangedEvent {
 pub(crate) struct SettingsSavedEvent {
     pub error: Option<String>,
 }
+
+/// Root location whose content can be moved elsewhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum StorageLocation {
+    Downloads,
+    Backups,
+}
+
+/// Moves everything in a storage location to `new_path` and switches the setting to it.
+///
+/// Refused while tasks are queued. Progress is reported with `StorageMigrationProgress`, the
+/// outcome with `StorageMigrationFinished`.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct MigrateStorageRequest {
+    pub location: StorageLocation,
+    pub new_path: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct StorageMigrationProgress {
+    pub location: StorageLocation,
+    /// Top-level entries moved so far
+    pub moved: u32,
+    pub total: u32,
+    /// Name of the entry being moved
+    pub current: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct StorageMigrationFinished {
+    pub location: StorageLocation,
+    /// Location in use after the migration
+    pub path: String,
+    pub error: Option<String>,
+    /// Whether entries already moved were put back after a failure
+    pub rolled_back: bool,
+}
+
+/// Requests a `SettingsProfilesChanged`
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetSettingsProfilesRequest {}
+
+/// Stores the active settings as a named profile, replacing a profile of the same name
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SaveSettingsProfileRequest {
+    pub name: String,
+}
+
+/// Replaces the active settings with those of a profile
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SwitchSettingsProfileRequest {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct DeleteSettingsProfileRequest {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct SettingsProfilesChanged {
+    /// Profile names, sorted
+    pub names: Vec<String>,
+    /// Profile that was last saved or switched to
+    pub active: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Self-hosted server a URL setting points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum SourceUrlKind {
+    Catalog,
+    Media,
+}
+
+/// Checks that a catalog or media URL answers, replied to with `SourceUrlTestResult`.
+///
+/// An empty media URL tests the default media server.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct TestSourceUrlRequest {
+    pub kind: SourceUrlKind,
+    pub url: String,
+    /// SPKI pins the server must match, see `Settings::pinned_spki`
+    pub pinned_spki: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct SourceUrlTestResult {
+    pub kind: SourceUrlKind,
+    pub url: String,
+    /// HTTP status of the answer, if the server answered
+    pub status: Option<u16>,
+    pub latency_ms: Option<u32>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,35 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Transfer and install totals over a period
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct TransferTotals {
+    /// Bytes downloaded, excluding files that were already downloaded
+    pub bytes_downloaded: u64,
+    pub downloads_completed: u32,
+    /// Bytes per second over the time spent downloading
+    pub average_download_speed: u64,
+    /// Bytes of apps, APKs and OBB files pushed to devices
+    pub bytes_pushed: u64,
+    pub installs_completed: u32,
+    /// Bytes per second over the time spent pushing to devices
+    pub average_push_speed: u64,
+}
+
+/// Lifetime and current session totals, sent every few seconds while they change
+#[derive(Debug, Clone, Serialize, Deserialize, RustSignal)]
+pub(crate) struct TransferStatsUpdate {
+    pub lifetime: TransferTotals,
+    /// Since the app was started
+    pub session: TransferTotals,
+    /// Milliseconds since Unix epoch when the lifetime totals were started or last reset
+    pub lifetime_since: u64,
+}
+
+/// Requests a `TransferStatsUpdate` right away
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct GetTransferStatsRequest {}
+
+/// Resets the lifetime totals, answered with a `TransferStatsUpdate`
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ResetTransferStatsRequest {}
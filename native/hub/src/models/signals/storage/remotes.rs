@@ -1,4 +1,4 @@
-use rinf::{DartSignal, RustSignal};
+use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, DartSignal)]
@@ -9,3 +9,76 @@ pub(crate) struct RcloneRemotesChanged {
     pub remotes: Vec<String>,
     pub error: Option<String>,
 }
+
+/// Backend of a remote added by the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) enum CustomRemoteKind {
+    Http,
+    Webdav,
+    S3,
+    Ftp,
+    Sftp,
+}
+
+/// Remote as entered by the user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct CustomRemoteDraft {
+    pub name: String,
+    pub kind: CustomRemoteKind,
+    /// URL for HTTP, WebDAV and S3, `host[:port]` for FTP and SFTP
+    pub endpoint: String,
+    /// User name, or the access key ID for S3
+    pub username: Option<String>,
+    /// Password, or the secret access key for S3. `None` keeps the saved one when editing.
+    pub password: Option<String>,
+}
+
+/// Saved remote, without its password
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct CustomRemoteInfo {
+    pub name: String,
+    pub kind: CustomRemoteKind,
+    pub endpoint: String,
+    pub username: Option<String>,
+    pub has_password: bool,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ListCustomRemotesRequest {}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ValidateCustomRemoteRequest {
+    pub remote: CustomRemoteDraft,
+}
+
+/// Adds a remote, or replaces `previous_name` when editing one
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct SaveCustomRemoteRequest {
+    pub remote: CustomRemoteDraft,
+    pub previous_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct RemoveCustomRemoteRequest {
+    pub name: String,
+}
+
+/// Remotes added by the user, sent on request and after every change
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct CustomRemotesChanged {
+    pub remotes: Vec<CustomRemoteInfo>,
+    pub error: Option<String>,
+}
+
+/// Problems with a draft, empty when it can be saved
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct CustomRemoteValidation {
+    pub name: String,
+    pub errors: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct CustomRemoteSaveResult {
+    pub name: String,
+    pub error: Option<String>,
+}
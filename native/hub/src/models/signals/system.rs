@@ -56,6 +56,35 @@ pub(crate) struct AppVersionInfo {
     pub git_dirty: Option<bool>,
 }
 
+/// Sent by the UI on startup to exchange signal API versions with the backend.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ApiHandshakeRequest {
+    /// Signal API version the UI was built against
+    pub ui_api_version: u32,
+}
+
+/// Sent by a UI that started while the backend kept running, e.g. after a hot restart.
+///
+/// The backend answers with the signals describing its current tasks, device, catalog and
+/// settings.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ResyncStateRequest {}
+
+/// Signal API version and task kinds of the backend.
+///
+/// Sent on startup and in response to `ApiHandshakeRequest`.
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct BackendCapabilities {
+    /// Signal API version of the backend
+    pub api_version: u32,
+    /// Oldest UI signal API version the backend works with
+    pub min_ui_api_version: u32,
+    /// Whether the UI that sent the handshake is supported, if it sent one
+    pub ui_supported: Option<bool>,
+    /// Task kinds the backend can run
+    pub task_kinds: Vec<String>,
+}
+
 impl Toast {
     pub(crate) fn send(
         title: String,
@@ -5,7 +5,9 @@ use anyhow::Result;
 use rinf::{DartSignal, RustSignal, SignalPiece};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, SignalPiece)]
+use super::history::HistoryAction;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, SignalPiece)]
 pub(crate) enum TaskKind {
     Download,
     DownloadInstall,
@@ -16,9 +18,61 @@ pub(crate) enum TaskKind {
     RestoreBackup,
     /// Pull an installed app from device and upload it for donation
     DonateApp,
+    ResumeDownload,
+    CaptureModSet,
+    ApplyModSet,
+    BackupAll,
+    PushObb,
+    ReinstallFromTrash,
+    ReceiveFromPeer,
+    TransferAppData,
+}
+
+impl TaskKind {
+    /// All task kinds, in declaration order
+    pub(crate) const ALL: [TaskKind; 16] = [
+        TaskKind::Download,
+        TaskKind::DownloadInstall,
+        TaskKind::InstallApk,
+        TaskKind::InstallLocalApp,
+        TaskKind::Uninstall,
+        TaskKind::BackupApp,
+        TaskKind::RestoreBackup,
+        TaskKind::DonateApp,
+        TaskKind::ResumeDownload,
+        TaskKind::CaptureModSet,
+        TaskKind::ApplyModSet,
+        TaskKind::BackupAll,
+        TaskKind::PushObb,
+        TaskKind::ReinstallFromTrash,
+        TaskKind::ReceiveFromPeer,
+        TaskKind::TransferAppData,
+    ];
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, SignalPiece)]
+/// How soon a task gets its turn when it waits for a slot, higher goes first.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    SignalPiece,
+)]
+pub(crate) enum TaskPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
 pub(crate) enum TaskStatus {
     Waiting,
     Running,
@@ -27,16 +81,88 @@ pub(crate) enum TaskStatus {
     Cancelled,
 }
 
+/// Kind of output produced by a task.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, SignalPiece, PartialEq, Eq)]
+pub(crate) enum TaskArtifactKind {
+    /// Directory with a downloaded app release
+    DownloadedApp,
+    /// Backup directory created on the host
+    Backup,
+    /// Zip archive exported on the host, like a donation archive or a crash report bundle
+    ExportedZip,
+}
+
+/// Output artifact registered by a task, used by the UI for "open folder"/"reveal file" actions.
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece, PartialEq, Eq)]
+pub(crate) struct TaskArtifact {
+    pub kind: TaskArtifactKind,
+    /// Absolute path on the host
+    pub path: String,
+    pub is_directory: bool,
+}
+
+impl TaskArtifact {
+    pub(crate) fn new(kind: TaskArtifactKind, path: &Path) -> Self {
+        Self { kind, path: path.to_string_lossy().into_owned(), is_directory: path.is_dir() }
+    }
+}
+
+/// Live stats of the transfer a running task step is making
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SignalPiece, PartialEq, Eq)]
+pub(crate) struct TransferProgress {
+    pub bytes: u64,
+    /// None while the size of the whole transfer is unknown
+    pub total_bytes: Option<u64>,
+    /// Bytes per second, smoothed over the last few seconds
+    pub speed: u64,
+    /// Estimated seconds until the transfer finishes at the current speed
+    pub eta_secs: Option<u64>,
+    /// Files finished so far
+    pub files_done: u32,
+    /// Files in the whole transfer, 0 when unknown
+    pub files_total: u32,
+    /// Files being transferred right now
+    pub files: Vec<FileTransferProgress>,
+}
+
+/// Progress of one file of a multi-file transfer
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece, PartialEq, Eq)]
+pub(crate) struct FileTransferProgress {
+    /// Path relative to the transfer root
+    pub name: String,
+    pub bytes: u64,
+    pub total_bytes: u64,
+    /// Bytes per second
+    pub speed: u64,
+}
+
+/// Aggregate transfer stats of a finished download
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece, PartialEq, Eq)]
+pub(crate) struct DownloadSummary {
+    /// Bytes transferred, excluding files that were already downloaded
+    pub total_bytes: u64,
+    /// Bytes per second over the whole download step
+    pub average_speed: u64,
+    pub peak_speed: u64,
+    /// Interrupted earlier attempts the download was resumed from
+    pub retries: u32,
+    /// Remote or mirror the files came from
+    pub source: Option<String>,
+    pub duration_ms: u64,
+}
+
 /// Task with parameters.
 #[derive(Debug, Clone, Serialize, Deserialize, SignalPiece)]
 pub(crate) enum Task {
-    /// Download an app by full name (catalog entry identifier) and original (not renamed) package name
-    Download(String, String),
-    /// Download and then install an app by full name and true package name
-    DownloadInstall(String, String),
+    /// Download an app by full name (catalog entry identifier) and original (not renamed) package
+    /// name, optionally limited to the listed files of the release
+    Download(String, String, #[serde(default)] Option<Vec<String>>),
+    /// Download and then install an app by full name and true package name, optionally limited to
+    /// the listed files of the release
+    DownloadInstall(String, String, #[serde(default)] Option<Vec<String>>),
     /// Install an APK from a single-file path
     InstallApk(String),
-    /// Install a local app (a directory containing APK/manifest)
+    /// Install a local app (a directory containing APK/manifest, or an archive of it)
     InstallLocalApp(String),
     /// Uninstall a package. Optional display name is used only for UI.
     Uninstall { package_name: String, display_name: Option<String> },
@@ -48,11 +174,40 @@ pub(crate) enum Task {
         backup_data: bool,
         backup_obb: bool,
         backup_name_append: Option<String>,
+        /// Pack the backup into a single `.yaasbackup` archive
+        #[serde(default)]
+        compress: bool,
     },
-    /// Restore from a backup directory path (contains a `.backup` marker)
-    RestoreBackup(String),
+    /// Restore from a backup directory path (contains a `.backup` marker) or a `.yaasbackup` file.
+    /// `target_package` restores the data into another installed package instead, e.g. a renamed
+    /// or beta build of the same app.
+    RestoreBackup { path: String, target_package: Option<String> },
     /// Donate (upload) installed app files from the device.
     DonateApp { package_name: String, display_name: Option<String> },
+    /// Continue an interrupted download by full name from its checkpoint in the downloads directory
+    ResumeDownload(String),
+    /// Capture the current contents of a game's mod paths into a named mod set.
+    CaptureModSet { package_name: String, display_name: Option<String>, set_name: String },
+    /// Apply a mod set to a game's mod paths. `None` restores the vanilla snapshot.
+    ApplyModSet { package_name: String, display_name: Option<String>, set_name: Option<String> },
+    /// Back up every installed non-system app one after another with the selected parts.
+    BackupAll { backup_apk: bool, backup_data: bool, backup_obb: bool, compress: bool },
+    /// Push only the OBB files of a package from a local directory, without installing the APK.
+    /// `local_dir` holds the OBB files directly or a folder named after the package.
+    PushObb { package_name: String, local_dir: String },
+    /// Reinstall an app with its data from its safety backup in the trash, then delete the backup.
+    ReinstallFromTrash { path: String, package_name: String, display_name: Option<String> },
+    /// Copy an app shared by a peer on the local network into the downloads directory.
+    /// `address` is the `host:port` of the peer's share server.
+    ReceiveFromPeer { address: String, share_id: String, name: String },
+    /// Back up an app with its data and OBB files from the current device and restore it to the
+    /// device with `target_serial` (true or ADB serial) once that one is connected.
+    TransferAppData {
+        package_name: String,
+        display_name: Option<String>,
+        target_serial: String,
+        target_name: Option<String>,
+    },
 }
 
 impl Task {
@@ -66,12 +221,22 @@ impl Task {
             Task::BackupApp { .. } => "Backup App",
             Task::RestoreBackup { .. } => "Restore Backup",
             Task::DonateApp { .. } => "Donate App",
+            Task::ResumeDownload { .. } => "Resume Download",
+            Task::CaptureModSet { .. } => "Capture Mod Set",
+            Task::ApplyModSet { .. } => "Apply Mod Set",
+            Task::BackupAll { .. } => "Backup All Apps",
+            Task::PushObb { .. } => "Push OBB",
+            Task::ReinstallFromTrash { .. } => "Reinstall From Trash",
+            Task::ReceiveFromPeer { .. } => "Receive From Peer",
+            Task::TransferAppData { .. } => "Transfer App Data",
         }
     }
 
     pub(crate) fn task_name(&self) -> Result<String> {
         Ok(match self {
-            Task::Download(name, _) | Task::DownloadInstall(name, _) => name.clone(),
+            Task::Download(name, ..)
+            | Task::DownloadInstall(name, ..)
+            | Task::ResumeDownload(name) => name.clone(),
             Task::InstallApk(apk_path) => {
                 Path::new(apk_path).file_name().unwrap_or_default().to_string_lossy().to_string()
             }
@@ -84,25 +249,90 @@ impl Task {
             Task::BackupApp { package_name, display_name, .. } => {
                 display_name.clone().unwrap_or_else(|| package_name.clone())
             }
-            Task::RestoreBackup(path) => {
+            Task::RestoreBackup { path, .. } => {
                 Path::new(path).file_name().unwrap_or_default().to_string_lossy().to_string()
             }
-            Task::DonateApp { package_name, display_name } => {
+            Task::DonateApp { package_name, display_name }
+            | Task::ReinstallFromTrash { package_name, display_name, .. }
+            | Task::CaptureModSet { package_name, display_name, .. }
+            | Task::ApplyModSet { package_name, display_name, .. }
+            | Task::TransferAppData { package_name, display_name, .. } => {
                 display_name.clone().unwrap_or_else(|| package_name.clone())
             }
+            Task::BackupAll { .. } => "All apps".to_string(),
+            Task::PushObb { package_name, .. } => package_name.clone(),
+            Task::ReceiveFromPeer { name, .. } => name.clone(),
         })
     }
 
+    /// Install history action recorded when the task finishes, if any.
+    pub(crate) fn history_action(&self) -> Option<HistoryAction> {
+        match self {
+            Task::Download(..) | Task::ResumeDownload(..) | Task::ReceiveFromPeer { .. } => {
+                Some(HistoryAction::Download)
+            }
+            Task::DownloadInstall(..) | Task::InstallApk(..) | Task::InstallLocalApp(..) => {
+                Some(HistoryAction::Install)
+            }
+            Task::Uninstall { .. } => Some(HistoryAction::Uninstall),
+            Task::BackupApp { .. } => Some(HistoryAction::Backup),
+            Task::RestoreBackup { .. }
+            | Task::ReinstallFromTrash { .. }
+            | Task::TransferAppData { .. } => Some(HistoryAction::Restore),
+            Task::DonateApp { .. }
+            | Task::CaptureModSet { .. }
+            | Task::ApplyModSet { .. }
+            | Task::BackupAll { .. }
+            | Task::PushObb { .. } => None,
+        }
+    }
+
+    /// Package the task operates on, if it is known before the task runs.
+    pub(crate) fn package_name(&self) -> Option<&str> {
+        match self {
+            Task::Download(_, package_name, _) | Task::DownloadInstall(_, package_name, _) => {
+                Some(package_name)
+            }
+            Task::Uninstall { package_name, .. }
+            | Task::BackupApp { package_name, .. }
+            | Task::DonateApp { package_name, .. }
+            | Task::CaptureModSet { package_name, .. }
+            | Task::ApplyModSet { package_name, .. }
+            | Task::PushObb { package_name, .. }
+            | Task::ReinstallFromTrash { package_name, .. }
+            | Task::TransferAppData { package_name, .. } => Some(package_name),
+            Task::RestoreBackup { target_package, .. } => target_package.as_deref(),
+            Task::InstallApk(..)
+            | Task::InstallLocalApp(..)
+            | Task::ResumeDownload(..)
+            | Task::BackupAll { .. }
+            | Task::ReceiveFromPeer { .. } => None,
+        }
+    }
+
+    /// Whether an unfinished task is persisted and re-queued after an app restart.
+    pub(crate) fn is_restorable(&self) -> bool {
+        matches!(self, Task::Download(..) | Task::DownloadInstall(..))
+    }
+
     pub(crate) fn total_steps(&self) -> u8 {
         match self {
-            Task::Download { .. } => 1,
-            Task::DownloadInstall { .. } => 2,
+            Task::Download { .. } => 2,
+            Task::DownloadInstall { .. } => 3,
             Task::InstallApk { .. } => 1,
             Task::InstallLocalApp { .. } => 1,
             Task::Uninstall { .. } => 1,
             Task::BackupApp { .. } => 1,
             Task::RestoreBackup { .. } => 1,
             Task::DonateApp { .. } => 3,
+            Task::ResumeDownload { .. } => 2,
+            Task::CaptureModSet { .. } => 1,
+            Task::ApplyModSet { .. } => 2,
+            Task::BackupAll { .. } => 1,
+            Task::PushObb { .. } => 1,
+            Task::ReinstallFromTrash { .. } => 1,
+            Task::ReceiveFromPeer { .. } => 1,
+            Task::TransferAppData { .. } => 4,
         }
     }
 }
@@ -124,6 +354,14 @@ impl From<&Task> for TaskKind {
             Task::BackupApp { .. } => TaskKind::BackupApp,
             Task::RestoreBackup { .. } => TaskKind::RestoreBackup,
             Task::DonateApp { .. } => TaskKind::DonateApp,
+            Task::ResumeDownload { .. } => TaskKind::ResumeDownload,
+            Task::CaptureModSet { .. } => TaskKind::CaptureModSet,
+            Task::ApplyModSet { .. } => TaskKind::ApplyModSet,
+            Task::BackupAll { .. } => TaskKind::BackupAll,
+            Task::PushObb { .. } => TaskKind::PushObb,
+            Task::ReinstallFromTrash { .. } => TaskKind::ReinstallFromTrash,
+            Task::ReceiveFromPeer { .. } => TaskKind::ReceiveFromPeer,
+            Task::TransferAppData { .. } => TaskKind::TransferAppData,
         }
     }
 }
@@ -131,6 +369,14 @@ impl From<&Task> for TaskKind {
 #[derive(Serialize, Deserialize, DartSignal)]
 pub(crate) struct TaskRequest {
     pub task: Task,
+    pub priority: TaskPriority,
+}
+
+/// Changes the priority of a task, moving it ahead of or behind other waiting tasks.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct TaskReorderRequest {
+    pub task_id: u64,
+    pub priority: TaskPriority,
 }
 
 #[derive(Serialize, Deserialize, DartSignal)]
@@ -138,12 +384,90 @@ pub(crate) struct TaskCancelRequest {
     pub task_id: u64,
 }
 
+/// App of an Update All batch
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece, PartialEq, Eq)]
+pub(crate) struct UpdateAllApp {
+    pub full_name: String,
+    pub package_name: String,
+}
+
+/// Queues updates for several apps at once.
+///
+/// With `canary`, the smallest app is updated and launched first, and the rest of the batch waits
+/// for a `ContinueUpdateAllRequest` after `UpdateAllCanaryResult` is sent.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct UpdateAllRequest {
+    pub apps: Vec<UpdateAllApp>,
+    pub canary: bool,
+}
+
+/// Outcome of the canary app of an Update All batch waiting for confirmation
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct UpdateAllCanaryResult {
+    pub batch_id: u64,
+    pub app_name: String,
+    /// Why the update or launch check failed, `None` when the app updated and launched
+    pub error: Option<String>,
+    /// Apps left in the batch
+    pub remaining: u32,
+}
+
+/// Queues the rest of a paused Update All batch, or drops it when `proceed` is false
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ContinueUpdateAllRequest {
+    pub batch_id: u64,
+    pub proceed: bool,
+}
+
+/// Asks the user to confirm a transfer that replaces the data an app already has on the target
+/// headset. Answered with `TransferOverwriteDecision`.
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct TransferOverwriteReview {
+    pub task_id: u64,
+    pub app_name: String,
+    pub target_name: String,
+    /// Why the transferred data may not work on the target, e.g. a newer installed version
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct TransferOverwriteDecision {
+    pub task_id: u64,
+    pub approved: bool,
+}
+
+/// Sent when a donated app archive has been uploaded
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct DonationUploaded {
+    pub package_name: String,
+    pub archive_name: String,
+    pub size: u64,
+    /// MD5 checksum of the archive as lowercase hex
+    pub md5: String,
+    /// Where the archive was uploaded, without credentials
+    pub destination: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct RestoredTask {
+    pub task_id: u64,
+    pub task_kind: TaskKind,
+    pub task_name: Option<String>,
+}
+
+/// Sent when unfinished tasks from a previous session were re-queued on startup.
 #[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct TasksRestored {
+    pub tasks: Vec<RestoredTask>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, RustSignal)]
 pub(crate) struct TaskProgress {
     pub task_id: u64,
     pub task_kind: TaskKind,
     pub task_name: Option<String>,
     pub status: TaskStatus,
+    pub priority: TaskPriority,
     /// Overall progress across all steps in range [0.0, 1.0]
     pub total_progress: f32,
     /// Human-readable status for the current step
@@ -153,4 +477,51 @@ pub(crate) struct TaskProgress {
     /// Progress for the current step in range [0.0, 1.0].
     /// None means this step does not report progress.
     pub step_progress: Option<f32>,
+    /// Artifacts registered by the task. Only populated once the task has finished.
+    pub artifacts: Vec<TaskArtifact>,
+    /// Transfer stats of the download step. Only populated once a downloading task has finished.
+    pub download_summary: Option<DownloadSummary>,
+    /// Estimated seconds until the whole task finishes.
+    /// None while waiting or without timings of earlier tasks of this kind.
+    pub eta_secs: Option<u64>,
+    /// Stats of the transfer the current step is making, if it transfers data
+    pub transfer: Option<TransferProgress>,
+}
+
+/// State of a live task in `TaskListSnapshot`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct TaskSnapshot {
+    pub task_id: u64,
+    pub task_kind: TaskKind,
+    pub task_name: Option<String>,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+    /// Overall progress across all steps in range [0.0, 1.0]
+    pub total_progress: f32,
+    pub current_step: u32,
+    pub total_steps: u32,
+}
+
+impl From<&TaskProgress> for TaskSnapshot {
+    fn from(progress: &TaskProgress) -> Self {
+        Self {
+            task_id: progress.task_id,
+            task_kind: progress.task_kind,
+            task_name: progress.task_name.clone(),
+            status: progress.status,
+            priority: progress.priority,
+            total_progress: progress.total_progress,
+            current_step: progress.current_step,
+            total_steps: progress.total_steps,
+        }
+    }
+}
+
+/// Every unfinished task, oldest first.
+///
+/// Sent whenever a task is added or removed and on resync, so a UI that missed progress signals
+/// can rebuild its task list. Tasks missing from it have finished.
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct TaskListSnapshot {
+    pub tasks: Vec<TaskSnapshot>,
 }
@@ -0,0 +1,42 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// Safety backup of an uninstalled app, kept in the trash until it expires
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct TrashedApp {
+    /// Backup directory inside the trash
+    pub path: String,
+    pub package_name: String,
+    pub display_name: Option<String>,
+    /// Milliseconds since Unix epoch
+    pub trashed_at: u64,
+}
+
+/// Sent after an app was backed up to the trash and uninstalled
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct AppTrashed {
+    pub app: TrashedApp,
+}
+
+/// Requests a `TrashListResponse`
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct ListTrashRequest {}
+
+/// Deletes an app from the trash, answered with a new `TrashListResponse`.
+///
+/// Only backups listed in the trash are deleted.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct DeleteTrashedAppRequest {
+    /// Backup directory of the app, as listed
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct TrashListResponse {
+    /// Most recently trashed first
+    pub apps: Vec<TrashedApp>,
+    /// Days apps are kept before they are deleted, 0 keeps them until they are deleted by hand
+    pub retention_days: u32,
+    /// Set if listing the trash or deleting an app failed
+    pub error: Option<String>,
+}
@@ -0,0 +1,53 @@
+use rinf::{DartSignal, RustSignal, SignalPiece};
+use serde::{Deserialize, Serialize};
+
+/// A YAAS release newer than the running one
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SignalPiece)]
+pub(crate) struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    /// Release notes in Markdown
+    pub changelog: String,
+    /// Release page on GitHub
+    pub release_url: String,
+    /// Build for the current platform, `None` if the release has none
+    pub download_url: Option<String>,
+    pub asset_name: Option<String>,
+    pub asset_size: Option<u64>,
+    /// RFC 3339 publication time
+    pub published_at: Option<String>,
+}
+
+/// Sent whenever a check finds a newer release, including the check on startup.
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct UpdateAvailable {
+    pub update: UpdateInfo,
+}
+
+/// Checks GitHub for a newer release, answered with `UpdateCheckResponse`.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct CheckForUpdateRequest {}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct UpdateCheckResponse {
+    /// `None` if the running version is the latest
+    pub update: Option<UpdateInfo>,
+    pub error: Option<String>,
+}
+
+/// Downloads the build of the last found update to a temporary directory.
+#[derive(Serialize, Deserialize, DartSignal)]
+pub(crate) struct DownloadUpdateRequest {}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct UpdateDownloadProgress {
+    pub received: u64,
+    pub total: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, RustSignal)]
+pub(crate) struct UpdateDownloadResponse {
+    /// Downloaded file on the host
+    pub path: Option<String>,
+    pub error: Option<String>,
+}
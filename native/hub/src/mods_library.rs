@@ -0,0 +1,340 @@
+use std::{
+    error::Error,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result, bail, ensure};
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::{adb::PackageName, models::signals::mods::*};
+
+/// Name of the mod set holding the unmodified game files
+pub(crate) const VANILLA_SET: &str = "vanilla";
+
+const PROFILE_FILE: &str = "profile.json";
+const SETS_DIR: &str = "sets";
+
+/// Persisted mod profile of a single game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ModProfileFile {
+    /// Absolute device paths holding mod files (e.g. `/sdcard/ModData/com.example.game/Mods`)
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Mod set currently applied on the device, `None` if unknown or vanilla
+    #[serde(default)]
+    pub active_set: Option<String>,
+}
+
+/// Stores mod profiles and captured mod sets on the host.
+///
+/// Layout:
+/// - `<root>/<package_name>/profile.json`
+/// - `<root>/<package_name>/sets/<set_name>/` + device paths mirrored relative to `/`
+#[derive(Debug)]
+pub(crate) struct ModsLibrary {
+    root: PathBuf,
+}
+
+impl ModsLibrary {
+    pub(crate) fn start(root: PathBuf) -> Arc<Self> {
+        let handle = Arc::new(Self { root });
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        handle
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let get_profiles_receiver = GetModProfilesRequest::get_dart_signal_receiver();
+        let save_profile_receiver = SaveModProfileRequest::get_dart_signal_receiver();
+        let delete_set_receiver = DeleteModSetRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = get_profiles_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received GetModProfilesRequest");
+                        self.send_profiles(None).await;
+                    } else {
+                        panic!("GetModProfilesRequest receiver closed");
+                    }
+                }
+                request = save_profile_receiver.recv() => {
+                    if let Some(request) = request {
+                        let SaveModProfileRequest { package_name, paths } = request.message;
+                        debug!(%package_name, ?paths, "Received SaveModProfileRequest");
+                        let result = self.save_profile_paths(&package_name, paths).await;
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to save mod profile");
+                        }
+                        self.send_profiles(result.err().map(|e| format!("{e:#}"))).await;
+                    } else {
+                        panic!("SaveModProfileRequest receiver closed");
+                    }
+                }
+                request = delete_set_receiver.recv() => {
+                    if let Some(request) = request {
+                        let DeleteModSetRequest { package_name, set_name } = request.message;
+                        debug!(%package_name, %set_name, "Received DeleteModSetRequest");
+                        let result = self.delete_set(&package_name, &set_name).await;
+                        if let Err(e) = &result {
+                            error!(error = e.as_ref() as &dyn Error, "Failed to delete mod set");
+                        }
+                        self.send_profiles(result.err().map(|e| format!("{e:#}"))).await;
+                    } else {
+                        panic!("DeleteModSetRequest receiver closed");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends all mod profiles to Dart.
+    pub(crate) async fn send_profiles(&self, error: Option<String>) {
+        let (profiles, error) = match self.list_profiles().await {
+            Ok(profiles) => (profiles, error),
+            Err(e) => {
+                error!(error = e.as_ref() as &dyn Error, "Failed to list mod profiles");
+                (Vec::new(), Some(error.unwrap_or_else(|| format!("{e:#}"))))
+            }
+        };
+        ModProfilesChanged { profiles, error }.send_signal_to_dart();
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    async fn list_profiles(&self) -> Result<Vec<ModProfile>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut profiles = Vec::new();
+        let mut rd = fs::read_dir(&self.root)
+            .await
+            .with_context(|| format!("Failed to read {}", self.root.display()))?;
+        while let Some(entry) = rd.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            let Some(package_name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let Ok(package) = PackageName::parse(&package_name) else {
+                warn!(%package_name, "Skipping unexpected directory in mods library");
+                continue;
+            };
+            let profile = self.load_profile(&package).await?;
+            let sets = self.list_sets(&package).await?;
+            profiles.push(ModProfile {
+                has_vanilla_snapshot: sets.iter().any(|s| s == VANILLA_SET),
+                package_name,
+                paths: profile.paths,
+                sets,
+                active_set: profile.active_set,
+            });
+        }
+        profiles.sort_by(|a, b| a.package_name.cmp(&b.package_name));
+        Ok(profiles)
+    }
+
+    fn package_dir(&self, package: &PackageName) -> PathBuf {
+        self.root.join(package.as_str())
+    }
+
+    /// Returns the host directory of a mod set. The set does not have to exist yet.
+    pub(crate) fn set_dir(&self, package: &PackageName, set_name: &str) -> Result<PathBuf> {
+        validate_set_name(set_name)?;
+        Ok(self.package_dir(package).join(SETS_DIR).join(set_name))
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn load_profile(&self, package: &PackageName) -> Result<ModProfileFile> {
+        let path = self.package_dir(package).join(PROFILE_FILE);
+        if !path.is_file() {
+            return Ok(ModProfileFile::default());
+        }
+        let content = fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    async fn save_profile(&self, package: &PackageName, profile: &ModProfileFile) -> Result<()> {
+        let dir = self.package_dir(package);
+        fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let json = serde_json::to_string_pretty(profile).context("Failed to serialize profile")?;
+        let path = dir.join(PROFILE_FILE);
+        fs::write(&path, json).await.with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Replaces the device paths managed for a game.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn save_profile_paths(
+        &self,
+        package_name: &str,
+        paths: Vec<String>,
+    ) -> Result<()> {
+        let package = PackageName::parse(package_name)?;
+        let mut normalized = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = normalize_device_path(&path)?;
+            if !normalized.contains(&path) {
+                normalized.push(path);
+            }
+        }
+
+        let mut profile = self.load_profile(&package).await?;
+        profile.paths = normalized;
+        self.save_profile(&package, &profile).await?;
+        info!(package = %package, paths = ?profile.paths, "Saved mod profile");
+        Ok(())
+    }
+
+    /// Records the mod set currently applied on the device.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn set_active_set(
+        &self,
+        package: &PackageName,
+        set_name: Option<&str>,
+    ) -> Result<()> {
+        let mut profile = self.load_profile(package).await?;
+        profile.active_set = set_name.filter(|s| *s != VANILLA_SET).map(str::to_string);
+        self.save_profile(package, &profile).await
+    }
+
+    /// Lists captured mod sets of a game.
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn list_sets(&self, package: &PackageName) -> Result<Vec<String>> {
+        let dir = self.package_dir(package).join(SETS_DIR);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut sets = Vec::new();
+        let mut rd = fs::read_dir(&dir)
+            .await
+            .with_context(|| format!("Failed to read {}", dir.display()))?;
+        while let Some(entry) = rd.next_entry().await? {
+            if entry.file_type().await?.is_dir()
+                && let Some(name) = entry.file_name().to_str()
+                && validate_set_name(name).is_ok()
+            {
+                sets.push(name.to_string());
+            }
+        }
+        sets.sort();
+        Ok(sets)
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    async fn delete_set(&self, package_name: &str, set_name: &str) -> Result<()> {
+        let package = PackageName::parse(package_name)?;
+        let profile = self.load_profile(&package).await?;
+        ensure!(
+            set_name != VANILLA_SET || profile.active_set.is_none(),
+            "Cannot delete the vanilla snapshot while a mod set is applied"
+        );
+        ensure!(
+            profile.active_set.as_deref() != Some(set_name),
+            "Cannot delete the mod set that is currently applied"
+        );
+        let dir = self.set_dir(&package, set_name)?;
+        ensure!(dir.is_dir(), "Mod set not found: {set_name}");
+        fs::remove_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to delete {}", dir.display()))?;
+        info!(package = %package, set_name, "Deleted mod set");
+        Ok(())
+    }
+}
+
+/// Validates a mod set name, which is used as a directory name.
+pub(crate) fn validate_set_name(set_name: &str) -> Result<()> {
+    ensure!(!set_name.trim().is_empty(), "Mod set name is empty");
+    ensure!(
+        !set_name.starts_with('.') && sanitize_filename::sanitize(set_name) == set_name,
+        "Invalid mod set name: {set_name}"
+    );
+    Ok(())
+}
+
+/// Validates an absolute device path and strips trailing slashes.
+fn normalize_device_path(path: &str) -> Result<String> {
+    let trimmed = path.trim().trim_end_matches('/');
+    ensure!(trimmed.starts_with('/'), "Device path must be absolute: {path}");
+    ensure!(!trimmed.contains('\''), "Device path must not contain quotes: {path}");
+    for component in Path::new(trimmed).components() {
+        if let Component::ParentDir | Component::CurDir = component {
+            bail!("Device path must not contain relative components: {path}");
+        }
+    }
+    ensure!(trimmed.matches('/').count() >= 2, "Device path is too broad: {path}");
+    Ok(trimmed.to_string())
+}
+
+/// Returns where a device path is mirrored inside a mod set directory.
+pub(crate) fn local_path_in_set(set_dir: &Path, device_path: &str) -> PathBuf {
+    set_dir.join(device_path.trim_start_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_device_path_rejects_unsafe_paths() {
+        assert_eq!(
+            normalize_device_path(" /sdcard/ModData/com.example/Mods/ ").unwrap(),
+            "/sdcard/ModData/com.example/Mods"
+        );
+        assert!(normalize_device_path("sdcard/Mods").is_err());
+        assert!(normalize_device_path("/sdcard/../data").is_err());
+        assert!(normalize_device_path("/sdcard").is_err());
+        assert!(normalize_device_path("/sdcard/it's").is_err());
+    }
+
+    #[test]
+    fn validate_set_name_rejects_path_like_names() {
+        assert!(validate_set_name("Quality mods").is_ok());
+        assert!(validate_set_name("").is_err());
+        assert!(validate_set_name("../x").is_err());
+        assert!(validate_set_name(".hidden").is_err());
+    }
+
+    #[tokio::test]
+    async fn profile_and_sets_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = ModsLibrary { root: dir.path().to_path_buf() };
+        let package = PackageName::parse("com.example.game").unwrap();
+
+        library
+            .save_profile_paths(
+                "com.example.game",
+                vec!["/sdcard/Mods/".into(), "/sdcard/Mods".into()],
+            )
+            .await
+            .unwrap();
+        library.set_active_set(&package, Some("Fun")).await.unwrap();
+        fs::create_dir_all(library.set_dir(&package, "Fun").unwrap()).await.unwrap();
+        fs::create_dir_all(library.set_dir(&package, VANILLA_SET).unwrap()).await.unwrap();
+
+        let profiles = library.list_profiles().await.unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].paths, vec!["/sdcard/Mods".to_string()]);
+        assert_eq!(profiles[0].active_set.as_deref(), Some("Fun"));
+        assert_eq!(profiles[0].sets, vec!["Fun".to_string(), VANILLA_SET.to_string()]);
+        assert!(profiles[0].has_vanilla_snapshot);
+
+        assert!(library.delete_set("com.example.game", "Fun").await.is_err());
+        library.set_active_set(&package, Some(VANILLA_SET)).await.unwrap();
+        library.delete_set("com.example.game", "Fun").await.unwrap();
+        assert_eq!(library.list_sets(&package).await.unwrap(), vec![VANILLA_SET.to_string()]);
+    }
+}
@@ -160,6 +160,12 @@ impl SettingsHandler {
         }
     }
 
+    /// Sends the active settings to Dart again, for a UI that started after they were loaded
+    pub(crate) fn resend(&self) {
+        let settings = self.watch_tx.borrow().clone();
+        SettingsChangedEvent { settings, error: None }.send_signal_to_dart();
+    }
+
     /// Create a receiver for settings changes
     pub(crate) fn subscribe(&self) -> watch::Receiver<Settings> {
         self.watch_tx.subscribe()
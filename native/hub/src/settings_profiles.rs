@@ -0,0 +1,240 @@
+//! Named snapshots of the settings that can be switched between at runtime.
+//!
+//! Switching saves the profile as the active settings, so every service follows it through the
+//! settings watch channel like after any other settings change.
+
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result, ensure};
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+use tracing::{Instrument, debug, info, info_span, instrument, warn};
+
+use crate::{
+    models::{
+        Settings,
+        signals::{adb::macros::upgrade_saved_macros, settings::*},
+    },
+    settings::SettingsHandler,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SettingsProfile {
+    name: String,
+    settings: Settings,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedProfiles {
+    #[serde(default)]
+    active: Option<String>,
+    /// Sorted by name
+    #[serde(default)]
+    profiles: Vec<SettingsProfile>,
+}
+
+impl PersistedProfiles {
+    fn names(&self) -> Vec<String> {
+        self.profiles.iter().map(|profile| profile.name.clone()).collect()
+    }
+
+    fn upsert(&mut self, name: &str, settings: Settings) -> Result<()> {
+        let name = name.trim();
+        ensure!(!name.is_empty(), "Profile name cannot be empty");
+        match self.profiles.binary_search_by(|profile| profile.name.as_str().cmp(name)) {
+            Ok(index) => self.profiles[index].settings = settings,
+            Err(index) => {
+                self.profiles.insert(index, SettingsProfile { name: name.to_string(), settings })
+            }
+        }
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, name: &str) -> Result<()> {
+        let before = self.profiles.len();
+        self.profiles.retain(|profile| profile.name != name);
+        ensure!(self.profiles.len() < before, "Profile \"{name}\" not found");
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        Ok(())
+    }
+
+    /// Settings of a profile, keeping the installation ID of `current`
+    fn settings_for(&self, name: &str, current: &Settings) -> Result<Settings> {
+        let profile = self
+            .profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .with_context(|| format!("Profile \"{name}\" not found"))?;
+        let mut settings = profile.settings.clone();
+        settings.installation_id = current.installation_id.clone();
+        Ok(settings)
+    }
+}
+
+/// Persists settings profiles and answers profile requests from Dart
+pub(crate) struct SettingsProfiles {
+    file_path: PathBuf,
+    profiles: Mutex<PersistedProfiles>,
+    settings_handler: Arc<SettingsHandler>,
+}
+
+impl SettingsProfiles {
+    /// Loads the profiles file from the app directory, falling back to no profiles.
+    #[instrument(level = "debug", skip(settings_handler))]
+    pub(crate) fn load(app_dir: &Path, settings_handler: Arc<SettingsHandler>) -> Self {
+        let file_path = app_dir.join("settings_profiles.json");
+        let profiles = if file_path.exists() {
+            Self::read_profiles(&file_path).unwrap_or_else(|e| {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to load settings profiles");
+                PersistedProfiles::default()
+            })
+        } else {
+            PersistedProfiles::default()
+        };
+        debug!(count = profiles.profiles.len(), "Loaded settings profiles");
+
+        Self { file_path, profiles: Mutex::new(profiles), settings_handler }
+    }
+
+    pub(crate) fn start(self) {
+        tokio::spawn(self.receive_signals().instrument(info_span!("task_settings_profiles")));
+    }
+
+    async fn receive_signals(self) {
+        let get_receiver = GetSettingsProfilesRequest::get_dart_signal_receiver();
+        let save_receiver = SaveSettingsProfileRequest::get_dart_signal_receiver();
+        let switch_receiver = SwitchSettingsProfileRequest::get_dart_signal_receiver();
+        let delete_receiver = DeleteSettingsProfileRequest::get_dart_signal_receiver();
+
+        loop {
+            let result = tokio::select! {
+                request = get_receiver.recv() => {
+                    if request.is_none() {
+                        panic!("GetSettingsProfilesRequest receiver closed");
+                    }
+                    Ok(())
+                }
+                request = save_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("SaveSettingsProfileRequest receiver closed");
+                    };
+                    self.save_profile(&request.message.name)
+                }
+                request = switch_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("SwitchSettingsProfileRequest receiver closed");
+                    };
+                    self.switch_profile(&request.message.name)
+                }
+                request = delete_receiver.recv() => {
+                    let Some(request) = request else {
+                        panic!("DeleteSettingsProfileRequest receiver closed");
+                    };
+                    self.delete_profile(&request.message.name)
+                }
+            };
+            if let Err(e) = &result {
+                warn!(error = e.as_ref() as &dyn Error, "Settings profile request failed");
+            }
+            self.send_profiles(result.err().map(|e| format!("{e:#}")));
+        }
+    }
+
+    fn send_profiles(&self, error: Option<String>) {
+        let profiles = self.profiles.lock().expect("settings profiles lock poisoned");
+        SettingsProfilesChanged { names: profiles.names(), active: profiles.active.clone(), error }
+            .send_signal_to_dart();
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    fn save_profile(&self, name: &str) -> Result<()> {
+        let settings = self.settings_handler.subscribe().borrow().clone();
+        let mut profiles = self.profiles.lock().expect("settings profiles lock poisoned");
+        profiles.upsert(name, settings)?;
+        self.write_profiles(&profiles)?;
+        info!(name, "Saved settings profile");
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    fn switch_profile(&self, name: &str) -> Result<()> {
+        let current = self.settings_handler.subscribe().borrow().clone();
+        let mut profiles = self.profiles.lock().expect("settings profiles lock poisoned");
+        let settings = profiles.settings_for(name, &current)?;
+        self.settings_handler.save_settings(&settings).context("Failed to apply profile")?;
+        profiles.active = Some(name.to_string());
+        self.write_profiles(&profiles)?;
+        info!(name, "Switched settings profile");
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self), err)]
+    fn delete_profile(&self, name: &str) -> Result<()> {
+        let mut profiles = self.profiles.lock().expect("settings profiles lock poisoned");
+        profiles.remove(name)?;
+        self.write_profiles(&profiles)?;
+        info!(name, "Deleted settings profile");
+        Ok(())
+    }
+
+    fn read_profiles(path: &Path) -> Result<PersistedProfiles> {
+        let content = fs::read_to_string(path).context("Failed to read settings profiles")?;
+        let mut value: serde_json::Value =
+            serde_json::from_str(&content).context("Failed to parse settings profiles")?;
+        let saved_macros = value
+            .get_mut("profiles")
+            .and_then(serde_json::Value::as_array_mut)
+            .into_iter()
+            .flatten()
+            .filter_map(|profile| profile.pointer_mut("/settings/command_macros"));
+        for command_macros in saved_macros {
+            upgrade_saved_macros(command_macros);
+        }
+        serde_json::from_value(value).context("Failed to parse settings profiles")
+    }
+
+    fn write_profiles(&self, profiles: &PersistedProfiles) -> Result<()> {
+        let content = serde_json::to_string_pretty(profiles)
+            .context("Failed to serialize settings profiles")?;
+        fs::write(&self.file_path, content).context("Failed to write settings profiles")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_stay_sorted_and_keep_the_installation_id() {
+        let mut profiles = PersistedProfiles::default();
+        let mut home = Settings::default();
+        home.rclone_remote_name = "home".into();
+        let mut work = Settings::default();
+        work.rclone_remote_name = "work".into();
+        profiles.upsert(" Work proxy ", work).unwrap();
+        profiles.upsert("Home LAN mirror", home.clone()).unwrap();
+        assert_eq!(profiles.names(), ["Home LAN mirror", "Work proxy"]);
+        assert_eq!(profiles.active.as_deref(), Some("Home LAN mirror"));
+        assert!(profiles.upsert("  ", home).is_err());
+
+        let mut current = Settings::default();
+        current.installation_id = "current".into();
+        let applied = profiles.settings_for("Work proxy", &current).unwrap();
+        assert_eq!(applied.rclone_remote_name, "work");
+        assert_eq!(applied.installation_id, "current");
+        assert!(profiles.settings_for("Missing", &current).is_err());
+
+        profiles.remove("Home LAN mirror").unwrap();
+        assert_eq!(profiles.active, None);
+        assert!(profiles.remove("Home LAN mirror").is_err());
+        assert_eq!(profiles.names(), ["Work proxy"]);
+    }
+}
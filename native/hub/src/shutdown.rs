@@ -0,0 +1,166 @@
+//! Application shutdown: cancels running tasks and waits for the cleanup of interrupted work.
+//!
+//! Work that leaves temporary state behind when interrupted (APKs staged on the device, open
+//! install sessions) holds a `CleanupGuard`. Dropping an armed guard runs its cleanup in the
+//! background, and shutdown waits a bounded time for those cleanups before the runtime stops.
+
+use std::{error::Error, future::Future, sync::LazyLock, time::Duration};
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use tokio::{runtime::Handle, time::timeout};
+use tokio_util::task::TaskTracker;
+use tracing::{Instrument, debug, info, info_span, warn};
+
+use crate::task::{TaskManager, TaskShutdownResult};
+
+/// How long cleanups of interrupted work may take once tasks have stopped
+const CLEANUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Cleanups started by dropped guards
+static CLEANUPS: LazyLock<TaskTracker> = LazyLock::new(TaskTracker::new);
+
+/// Cleanup of temporary state, run in the background if dropped before being disarmed
+#[must_use = "the cleanup runs as soon as the guard is dropped"]
+pub(crate) struct CleanupGuard {
+    name: &'static str,
+    cleanup: Option<BoxFuture<'static, Result<()>>>,
+    tracker: TaskTracker,
+}
+
+impl CleanupGuard {
+    pub(crate) fn new(
+        name: &'static str,
+        cleanup: impl Future<Output = Result<()>> + Send + 'static,
+    ) -> Self {
+        Self::with_tracker(name, cleanup, CLEANUPS.clone())
+    }
+
+    fn with_tracker(
+        name: &'static str,
+        cleanup: impl Future<Output = Result<()>> + Send + 'static,
+        tracker: TaskTracker,
+    ) -> Self {
+        Self { name, cleanup: Some(Box::pin(cleanup)), tracker }
+    }
+
+    /// Discards the cleanup, the work finished and cleaned up after itself
+    pub(crate) fn disarm(mut self) {
+        self.cleanup = None;
+    }
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        let Some(cleanup) = self.cleanup.take() else {
+            return;
+        };
+        let Ok(handle) = Handle::try_current() else {
+            warn!(name = self.name, "No runtime to clean up interrupted work");
+            return;
+        };
+        let name = self.name;
+        self.tracker.spawn_on(
+            async move {
+                match cleanup.await {
+                    Ok(()) => debug!("Cleaned up interrupted work"),
+                    Err(e) => {
+                        warn!(
+                            error = e.as_ref() as &dyn Error,
+                            "Failed to clean up interrupted work"
+                        )
+                    }
+                }
+            }
+            .instrument(info_span!("cleanup", name)),
+            &handle,
+        );
+    }
+}
+
+/// Cancels all tasks, then waits for the cleanups of the work they left unfinished.
+///
+/// Restorable tasks stay in the persisted queue and interrupted downloads keep their
+/// checkpoints, so both continue on the next start.
+pub(crate) async fn shutdown(
+    task_manager: &TaskManager,
+    task_timeout: Duration,
+) -> TaskShutdownResult {
+    let mut result = task_manager.shutdown(task_timeout).await;
+    if !wait_for_cleanups(&CLEANUPS, CLEANUP_TIMEOUT).await {
+        result.timed_out = true;
+    }
+    result
+}
+
+/// Waits for the cleanups in `tracker`, returning whether they all finished in time
+async fn wait_for_cleanups(tracker: &TaskTracker, wait_timeout: Duration) -> bool {
+    tracker.close();
+    let pending = tracker.len();
+    if pending > 0 {
+        info!(pending, "Waiting for cleanup of interrupted work");
+    }
+    let finished = timeout(wait_timeout, tracker.wait()).await.is_ok();
+    if !finished {
+        warn!(
+            remaining = tracker.len(),
+            timeout_secs = wait_timeout.as_secs(),
+            "Timed out waiting for cleanup of interrupted work"
+        );
+    }
+    finished
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn dropped_guards_clean_up_and_disarmed_guards_do_not() {
+        let tracker = TaskTracker::new();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let disarmed = Arc::new(AtomicBool::new(false));
+
+        let guard = CleanupGuard::with_tracker(
+            "dropped",
+            {
+                let dropped = dropped.clone();
+                async move {
+                    dropped.store(true, Ordering::Relaxed);
+                    Ok(())
+                }
+            },
+            tracker.clone(),
+        );
+        drop(guard);
+        CleanupGuard::with_tracker(
+            "disarmed",
+            {
+                let disarmed = disarmed.clone();
+                async move {
+                    disarmed.store(true, Ordering::Relaxed);
+                    Ok(())
+                }
+            },
+            tracker.clone(),
+        )
+        .disarm();
+
+        assert!(wait_for_cleanups(&tracker, Duration::from_secs(1)).await);
+        assert!(dropped.load(Ordering::Relaxed));
+        assert!(!disarmed.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn waiting_for_cleanups_is_bounded() {
+        let tracker = TaskTracker::new();
+        drop(CleanupGuard::with_tracker("stuck", std::future::pending(), tracker.clone()));
+
+        assert!(!wait_for_cleanups(&tracker, Duration::from_millis(10)).await);
+    }
+}
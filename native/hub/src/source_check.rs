@@ -0,0 +1,119 @@
+//! Connection tests for the self-hosted catalog and media URLs in the settings.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use rinf::{DartSignal, RustSignal};
+use tracing::{Instrument, debug, info_span, instrument};
+
+use crate::{
+    downloader::tls::{self, ServerPins},
+    models::{
+        DEFAULT_MEDIA_BASE_URL, check_http_url,
+        signals::settings::{SourceUrlKind, SourceUrlTestResult, TestSourceUrlRequest},
+    },
+};
+
+/// How long a server gets to answer a test request
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(crate) fn start() {
+    tokio::spawn(receive_requests().instrument(info_span!("task_source_check")));
+}
+
+async fn receive_requests() {
+    let receiver = TestSourceUrlRequest::get_dart_signal_receiver();
+    while let Some(request) = receiver.recv().await {
+        let TestSourceUrlRequest { kind, url, pinned_spki } = request.message;
+        tokio::spawn(async move { check(kind, url, &pinned_spki).await.send_signal_to_dart() });
+    }
+    panic!("TestSourceUrlRequest receiver closed");
+}
+
+/// Asks the catalog for its app list, or the media server for its root
+#[instrument(level = "debug")]
+async fn check(kind: SourceUrlKind, url: String, pinned_spki: &[String]) -> SourceUrlTestResult {
+    let started = Instant::now();
+    let response = async {
+        let probe = probe_url(kind, &url)?;
+        // Pins belong to the self-hosted servers, not to the default media server
+        let spki = if url.trim().is_empty() { &[] } else { pinned_spki };
+        let client = tls::http_client_builder(ServerPins { certificates: None, spki })?
+            .timeout(TIMEOUT)
+            .build()
+            .context("Failed to build HTTP client")?;
+        client.head(probe).send().await.context("The server did not answer")
+    }
+    .await;
+    let latency_ms = Some(started.elapsed().as_millis().min(u32::MAX as u128) as u32);
+
+    let (status, error) = match response {
+        Ok(response) => {
+            let status = response.status();
+            (Some(status.as_u16()), status_error(kind, status))
+        }
+        Err(e) => (None, Some(format!("{e:#}"))),
+    };
+    debug!(?status, ?error, "Source URL checked");
+    SourceUrlTestResult { kind, url, status, latency_ms: status.and(latency_ms), error }
+}
+
+fn probe_url(kind: SourceUrlKind, url: &str) -> Result<reqwest::Url> {
+    let url = url.trim();
+    match kind {
+        SourceUrlKind::Catalog => check_http_url(&format!("{}/list", url.trim_end_matches('/'))),
+        SourceUrlKind::Media => {
+            check_http_url(if url.is_empty() { DEFAULT_MEDIA_BASE_URL } else { url })
+        }
+    }
+}
+
+/// Why an answer does not look like a working server of `kind`
+fn status_error(kind: SourceUrlKind, status: StatusCode) -> Option<String> {
+    let ok = match kind {
+        SourceUrlKind::Catalog => status.is_success() || status == StatusCode::METHOD_NOT_ALLOWED,
+        // Media servers often refuse to list their root
+        SourceUrlKind::Media => !status.is_server_error(),
+    };
+    (!ok).then(|| format!("The server answered with {status}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn catalog_is_checked_through_its_app_list() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/repo/list"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let ok = check(SourceUrlKind::Catalog, format!("{}/repo/", server.uri()), &[]).await;
+        assert_eq!((ok.status, ok.error), (Some(200), None));
+
+        let missing = check(SourceUrlKind::Catalog, format!("{}/other", server.uri()), &[]).await;
+        assert_eq!(missing.status, Some(404));
+        assert!(missing.error.is_some());
+
+        let media = check(SourceUrlKind::Media, format!("{}/media", server.uri()), &[]).await;
+        assert_eq!((media.status, media.error), (Some(404), None));
+
+        let invalid = check(SourceUrlKind::Catalog, "ftp://example.com".into(), &[]).await;
+        assert_eq!(invalid.status, None);
+        assert!(invalid.error.is_some());
+
+        let pins = ["sha256/lLYhCKBO3yUgPMDC0E8cCy41d1UWmBxbU4d2cITUlu4=".to_string()];
+        let pinned = check(SourceUrlKind::Catalog, format!("{}/repo/", server.uri()), &pins).await;
+        assert_eq!(pinned.status, None);
+        assert!(pinned.error.is_some());
+    }
+}
@@ -0,0 +1,276 @@
+//! Moving the content of the downloads or backups location to a new place, so the setting can be
+//! changed without leaving existing files behind.
+
+use std::{
+    error::Error,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result, anyhow, ensure};
+use rinf::{DartSignal, RustSignal};
+use tokio::fs;
+use tracing::{Instrument, info, info_span, instrument, warn};
+
+use crate::{
+    downloader::dedupe,
+    models::signals::settings::{
+        MigrateStorageRequest, StorageLocation, StorageMigrationFinished, StorageMigrationProgress,
+    },
+    settings::SettingsHandler,
+    task::TaskManager,
+    utils::move_path,
+};
+
+/// A failed migration and whether the entries moved before the failure were put back
+struct MigrationError {
+    error: anyhow::Error,
+    rolled_back: bool,
+}
+
+impl From<anyhow::Error> for MigrationError {
+    fn from(error: anyhow::Error) -> Self {
+        Self { error, rolled_back: false }
+    }
+}
+
+/// Answers storage migration requests from Dart, one at a time
+pub(crate) struct StorageMigration {
+    settings_handler: Arc<SettingsHandler>,
+    task_manager: Arc<TaskManager>,
+}
+
+impl StorageMigration {
+    pub(crate) fn start(settings_handler: Arc<SettingsHandler>, task_manager: Arc<TaskManager>) {
+        let handler = Self { settings_handler, task_manager };
+        tokio::spawn(handler.receive_requests().instrument(info_span!("task_storage_migration")));
+    }
+
+    async fn receive_requests(self) {
+        let receiver = MigrateStorageRequest::get_dart_signal_receiver();
+        while let Some(request) = receiver.recv().await {
+            let MigrateStorageRequest { location, new_path } = request.message;
+            self.migrate(location, PathBuf::from(new_path)).await;
+        }
+        panic!("MigrateStorageRequest receiver closed");
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn migrate(&self, location: StorageLocation, new_path: PathBuf) {
+        let current = {
+            let settings = self.settings_handler.subscribe();
+            let settings = settings.borrow();
+            match location {
+                StorageLocation::Downloads => settings.downloads_location(),
+                StorageLocation::Backups => settings.backups_location(),
+            }
+        };
+
+        let (path, error, rolled_back) = match self.try_migrate(location, &current, &new_path).await
+        {
+            Ok(()) => {
+                info!(from = %current.display(), to = %new_path.display(), "Storage migrated");
+                (new_path, None, false)
+            }
+            Err(MigrationError { error, rolled_back }) => {
+                warn!(
+                    error = error.as_ref() as &dyn Error,
+                    rolled_back, "Storage migration failed"
+                );
+                (current, Some(format!("{error:#}")), rolled_back)
+            }
+        };
+        StorageMigrationFinished {
+            location,
+            path: path.to_string_lossy().into_owned(),
+            error,
+            rolled_back,
+        }
+        .send_signal_to_dart();
+    }
+
+    async fn try_migrate(
+        &self,
+        location: StorageLocation,
+        current: &Path,
+        new_path: &Path,
+    ) -> Result<(), MigrationError> {
+        if !new_path.is_absolute() {
+            return Err(anyhow!("The new location must be an absolute path").into());
+        }
+        // Tasks queued during the move wait for it instead of using a half-moved location
+        let _paused = self.task_manager.pause_tasks().await?;
+        let moved = move_contents(current, new_path, |moved, total, entry| {
+            StorageMigrationProgress { location, moved, total, current: entry.map(str::to_string) }
+                .send_signal_to_dart();
+        })
+        .await?;
+        if location == StorageLocation::Downloads {
+            // Moves across drives copy the files of the dedupe store as separate files
+            if let Err(e) = dedupe::relink(new_path).await {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to relink deduplicated files");
+            }
+        }
+
+        let mut settings = self.settings_handler.subscribe().borrow().clone();
+        match location {
+            StorageLocation::Downloads => settings.set_downloads_location(new_path),
+            StorageLocation::Backups => settings.set_backups_location(new_path),
+        }
+        if let Err(e) = self.settings_handler.save_settings(&settings) {
+            return Err(put_back(current, new_path, &moved, e).await);
+        }
+        Ok(())
+    }
+}
+
+/// Moves every entry of `src` into `dst`, returning the names of the moved entries.
+///
+/// Nothing is moved if `dst` is or overlaps `src`, or already has an entry of the same name. If
+/// moving an entry fails, the entries moved before it are put back into `src`.
+async fn move_contents(
+    src: &Path,
+    dst: &Path,
+    mut on_progress: impl FnMut(u32, u32, Option<&str>),
+) -> Result<Vec<OsString>, MigrationError> {
+    let entries = plan(src, dst).await?;
+    let total = entries.len() as u32;
+    for (index, name) in entries.iter().enumerate() {
+        on_progress(index as u32, total, Some(&name.to_string_lossy()));
+        if let Err(e) = move_path(&src.join(name), &dst.join(name)).await {
+            return Err(put_back(src, dst, &entries[..index], e).await);
+        }
+    }
+    on_progress(total, total, None);
+    Ok(entries)
+}
+
+/// Checks that `src` can be moved into `dst`, creates `dst` and lists the entries of `src`
+async fn plan(src: &Path, dst: &Path) -> Result<Vec<OsString>> {
+    let resolved_dst = resolve(dst).await?;
+    let src = match fs::canonicalize(src).await {
+        Ok(src) => Some(src),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e).with_context(|| format!("Failed to resolve {}", src.display())),
+    };
+    if let Some(src) = &src {
+        ensure!(*src != resolved_dst, "The new location is the current one");
+        ensure!(!resolved_dst.starts_with(src), "The new location is inside the current one");
+        ensure!(!src.starts_with(&resolved_dst), "The current location is inside the new one");
+    }
+    fs::create_dir_all(dst).await.with_context(|| format!("Failed to create {}", dst.display()))?;
+    let Some(src) = src else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = Vec::new();
+    let mut dir = fs::read_dir(&src).await?;
+    while let Some(entry) = dir.next_entry().await? {
+        let name = entry.file_name();
+        ensure!(
+            !fs::try_exists(dst.join(&name)).await.unwrap_or(true),
+            "{} already exists in the new location",
+            name.to_string_lossy()
+        );
+        entries.push(name);
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+/// Resolves a path that may not exist yet through its closest existing ancestor
+async fn resolve(path: &Path) -> Result<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        match fs::canonicalize(existing).await {
+            Ok(resolved) => {
+                return Ok(missing.iter().rev().fold(resolved, |path, name| path.join(name)));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                missing.push(existing.file_name().context("Location has no existing parent")?);
+                existing = existing.parent().context("Location has no existing parent")?;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to resolve {}", existing.display()));
+            }
+        }
+    }
+}
+
+/// Moves `moved` entries back from `dst` into `src` after `error`
+async fn put_back(
+    src: &Path,
+    dst: &Path,
+    moved: &[OsString],
+    error: anyhow::Error,
+) -> MigrationError {
+    for name in moved.iter().rev() {
+        if let Err(e) = move_path(&dst.join(name), &src.join(name)).await {
+            warn!(error = e.as_ref() as &dyn Error, entry = ?name, "Failed to put entry back");
+            let error = anyhow!(
+                "{error:#}. Moving {} back to {} also failed: {e:#}",
+                name.to_string_lossy(),
+                src.display()
+            );
+            return MigrationError { error, rolled_back: false };
+        }
+    }
+    MigrationError { error, rolled_back: !moved.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn moves_everything_and_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("old");
+        let dst = dir.path().join("new");
+        fs::create_dir_all(src.join("App v1+1/com.example.app")).await.unwrap();
+        fs::write(src.join("App v1+1/com.example.app/base.apk"), b"apk").await.unwrap();
+        fs::write(src.join("download.json"), b"{}").await.unwrap();
+
+        let mut progress = Vec::new();
+        let moved = move_contents(&src, &dst, |moved, total, entry| {
+            progress.push((moved, total, entry.map(str::to_string)))
+        })
+        .await
+        .ok()
+        .unwrap();
+
+        assert_eq!(moved.len(), 2);
+        assert!(dst.join("App v1+1/com.example.app/base.apk").is_file());
+        assert!(dst.join("download.json").is_file());
+        assert!(!src.join("download.json").exists());
+        assert_eq!(progress.first(), Some(&(0, 2, Some("App v1+1".to_string()))));
+        assert_eq!(progress.last(), Some(&(2, 2, None)));
+    }
+
+    #[tokio::test]
+    async fn refuses_overlaps_and_conflicts_without_moving() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("old");
+        fs::create_dir_all(&src).await.unwrap();
+        fs::write(src.join("a.txt"), b"a").await.unwrap();
+        fs::write(src.join("b.txt"), b"b").await.unwrap();
+
+        for dst in [src.clone(), src.join("nested"), dir.path().to_path_buf()] {
+            assert!(move_contents(&src, &dst, |_, _, _| {}).await.is_err());
+        }
+        assert!(!src.join("nested").exists());
+
+        let dst = dir.path().join("new");
+        fs::create_dir_all(&dst).await.unwrap();
+        fs::write(dst.join("b.txt"), b"other").await.unwrap();
+        let error = move_contents(&src, &dst, |_, _, _| {}).await.err().unwrap();
+        assert!(!error.rolled_back);
+        assert!(src.join("a.txt").exists());
+        assert!(!dst.join("a.txt").exists());
+
+        let missing = dir.path().join("missing");
+        assert!(move_contents(&missing, &dst, |_, _, _| {}).await.ok().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use std::{path::Path, sync::Mutex};
+
+use tracing::debug;
+
+use crate::models::signals::task::{DownloadSummary, TaskArtifact, TaskArtifactKind};
+
+/// Collects output artifacts and the download summary registered by a single task.
+///
+/// Both are reported to Dart with the final task progress update.
+#[derive(Debug, Default)]
+pub(super) struct TaskArtifacts {
+    artifacts: Mutex<Vec<TaskArtifact>>,
+    download_summary: Mutex<Option<DownloadSummary>>,
+    package_name: Mutex<Option<String>>,
+}
+
+impl TaskArtifacts {
+    /// Registers an artifact. Registering the same path twice replaces the previous entry.
+    pub(super) fn register(&self, kind: TaskArtifactKind, path: impl AsRef<Path>) {
+        let artifact = TaskArtifact::new(kind, path.as_ref());
+        debug!(?artifact, "Registering task artifact");
+
+        let mut artifacts = self.artifacts.lock().expect("task artifacts lock poisoned");
+        artifacts.retain(|a| a.path != artifact.path);
+        artifacts.push(artifact);
+    }
+
+    /// Records the transfer stats of the task's download, replacing earlier ones
+    pub(super) fn set_download_summary(&self, summary: DownloadSummary) {
+        debug!(?summary, "Recording download summary");
+        *self.download_summary.lock().expect("task artifacts lock poisoned") = Some(summary);
+    }
+
+    pub(super) fn download_summary(&self) -> Option<DownloadSummary> {
+        self.download_summary.lock().expect("task artifacts lock poisoned").clone()
+    }
+
+    /// Records the package the task acted on, for tasks that only learn it while running
+    pub(super) fn set_package_name(&self, package_name: String) {
+        debug!(package_name, "Recording task package");
+        *self.package_name.lock().expect("task artifacts lock poisoned") = Some(package_name);
+    }
+
+    pub(super) fn package_name(&self) -> Option<String> {
+        self.package_name.lock().expect("task artifacts lock poisoned").clone()
+    }
+
+    /// Returns registered artifacts that still exist on disk.
+    pub(super) fn snapshot(&self) -> Vec<TaskArtifact> {
+        let artifacts = self.artifacts.lock().expect("task artifacts lock poisoned");
+        artifacts.iter().filter(|a| Path::new(&a.path).exists()).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn register_replaces_duplicate_paths() {
+        let dir = tempdir().unwrap();
+        let artifacts = TaskArtifacts::default();
+
+        artifacts.register(TaskArtifactKind::DownloadedApp, dir.path());
+        artifacts.register(TaskArtifactKind::Backup, dir.path());
+
+        let snapshot = artifacts.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].kind, TaskArtifactKind::Backup);
+        assert!(snapshot[0].is_directory);
+    }
+
+    #[test]
+    fn snapshot_skips_removed_paths() {
+        let dir = tempdir().unwrap();
+        let kept = dir.path().join("kept");
+        let removed = dir.path().join("removed");
+        std::fs::create_dir(&kept).unwrap();
+        std::fs::create_dir(&removed).unwrap();
+
+        let artifacts = TaskArtifacts::default();
+        artifacts.register(TaskArtifactKind::DownloadedApp, &kept);
+        artifacts.register(TaskArtifactKind::DownloadedApp, &removed);
+        std::fs::remove_dir(&removed).unwrap();
+
+        let snapshot = artifacts.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].path, kept.to_string_lossy());
+    }
+}
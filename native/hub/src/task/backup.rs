@@ -1,29 +1,37 @@
-use std::path::Path;
+use std::{error::Error, path::Path};
 
-use anyhow::{Result, ensure};
+use anyhow::{Result, bail, ensure};
 use rinf::RustSignal;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, instrument};
+use tracing::{debug, info, instrument, warn};
 
-use super::{AdbStepConfig, BackupStepConfig, ProgressUpdate, TaskManager};
+use super::{
+    AdbStepConfig, BackupStepConfig, ProgressUpdate, TaskManager, acquire_permit_or_cancel,
+    artifacts::TaskArtifacts, scheduler::SlotClass,
+};
 use crate::{
     adb::{PackageName, device::BackupOptions},
-    models::signals::backups::BackupsChanged,
+    models::signals::{
+        backups::BackupsChanged,
+        task::{TaskArtifactKind, TaskStatus},
+    },
+    trash,
 };
 
 impl TaskManager {
-    #[instrument(skip(self, update_progress, token))]
+    #[instrument(skip(self, update_progress, artifacts, token))]
     pub(super) async fn handle_backup(
         &self,
         cfg: BackupStepConfig,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
     ) -> Result<()> {
         ensure!(cfg.backup_apk || cfg.backup_data || cfg.backup_obb, "No parts selected to backup");
 
         debug!(
             package_name = %cfg.package_name,
-            adb_permits_available = self.adb_semaphore.available_permits(),
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
             "Starting backup task"
         );
 
@@ -48,6 +56,7 @@ impl TaskManager {
             backup_data: cfg.backup_data,
             backup_obb: cfg.backup_obb,
             require_private_data: false,
+            compress: cfg.compress,
         };
 
         let pkg = PackageName::parse(&cfg.package_name)?;
@@ -87,27 +96,128 @@ impl TaskManager {
             )
             .await?;
 
+        let Some(backup_path) = maybe_created else {
+            bail!("Nothing to back up for this app (selected parts: {})", parts);
+        };
+        artifacts.register(TaskArtifactKind::Backup, &backup_path);
+
+        BackupsChanged {}.send_signal_to_dart();
+
+        Ok(())
+    }
+
+    /// Backs up all installed non-system apps one at a time.
+    ///
+    /// A failed app does not stop the run, the task fails at the end listing the apps that could
+    /// not be backed up. Cancellation is honored between and during backups.
+    #[instrument(skip(self, update_progress, artifacts, token))]
+    pub(super) async fn handle_backup_all(
+        &self,
+        options: BackupOptions,
+        update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
+        token: CancellationToken,
+    ) -> Result<()> {
         ensure!(
-            maybe_created.is_some(),
-            "Nothing to back up for this app (selected parts: {})",
-            parts
+            options.backup_apk || options.backup_data || options.backup_obb,
+            "No parts selected to backup"
         );
 
-        BackupsChanged {}.send_signal_to_dart();
+        let adb_service = self.adb_service.clone();
+        let device = adb_service.current_device().await?;
+        let mut packages = device
+            .installed_packages
+            .iter()
+            .filter(|package| !package.is_system())
+            .map(|package| {
+                let label = package.label();
+                let name = if label.is_empty() { package.package_name() } else { label };
+                (package.package_name().to_string(), name.to_string())
+            })
+            .collect::<Vec<_>>();
+        ensure!(!packages.is_empty(), "No installed apps to back up");
+        packages.sort_by_key(|(_, name)| name.to_lowercase());
+
+        let backups_path = self.settings.read().await.backups_location();
+        let total = packages.len();
+        info!(total, path = %backups_path.display(), "Starting backup of all apps");
+
+        let mut created = 0;
+        let mut failed = Vec::new();
+        for (index, (package_name, name)) in packages.iter().enumerate() {
+            if token.is_cancelled() {
+                bail!("Task cancelled after backing up {created} of {total} apps");
+            }
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Waiting,
+                step_number: 1,
+                step_progress: Some(index as f32 / total as f32),
+                message: format!("Waiting to back up {name} ({}/{total})...", index + 1),
+                transfer: None,
+            });
+            let _permit = acquire_permit_or_cancel!(self.scheduler, SlotClass::Adb, token, "ADB");
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Running,
+                step_number: 1,
+                step_progress: Some(index as f32 / total as f32),
+                message: format!("Backing up {name} ({}/{total})...", index + 1),
+                transfer: None,
+            });
+
+            let result = async {
+                let package = PackageName::parse(package_name)?;
+                adb_service
+                    .backup_app(
+                        &device,
+                        &package,
+                        Some(name),
+                        &backups_path,
+                        &options,
+                        token.clone(),
+                    )
+                    .await
+            }
+            .await;
+            match result {
+                Ok(Some(backup_path)) => {
+                    artifacts.register(TaskArtifactKind::Backup, &backup_path);
+                    created += 1;
+                }
+                Ok(None) => debug!(package_name, "Nothing to back up"),
+                Err(e) if token.is_cancelled() => return Err(e),
+                Err(e) => {
+                    warn!(package_name, error = e.as_ref() as &dyn Error, "App backup failed");
+                    failed.push(name.as_str());
+                }
+            }
+        }
 
+        if created > 0 {
+            BackupsChanged {}.send_signal_to_dart();
+        }
+        ensure!(
+            failed.is_empty(),
+            "Failed to back up {} of {total} apps: {}",
+            failed.len(),
+            failed.join(", ")
+        );
+        info!(created, total, "Backed up all apps");
         Ok(())
     }
 
-    #[instrument(skip(self, update_progress, token))]
+    #[instrument(skip(self, update_progress, artifacts, token))]
     pub(super) async fn handle_restore(
         &self,
         backup_path: String,
+        target_package: Option<PackageName>,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
     ) -> Result<()> {
         debug!(
             backup_path = %backup_path,
-            adb_permits_available = self.adb_semaphore.available_permits(),
+            target_package = ?target_package.as_ref().map(PackageName::as_str),
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
             "Starting restore task"
         );
 
@@ -115,21 +225,51 @@ impl TaskManager {
         let device = adb_service.current_device().await?;
 
         let backup_path_cloned = backup_path.clone();
-        self.run_adb_one_step(
-            AdbStepConfig {
-                step_number: 1,
-                waiting_msg: "Waiting to start restore...",
-                running_msg: "Restoring backup...".to_string(),
-                log_context: "restore",
-            },
-            update_progress,
-            token,
-            move || {
-                let path = backup_path_cloned.clone();
-                async move { adb_service.restore_backup(&device, Path::new(&path)).await }
-            },
-        )
-        .await
-        .map(|_| ())
+        let restored = self
+            .run_adb_one_step(
+                AdbStepConfig {
+                    step_number: 1,
+                    waiting_msg: "Waiting to start restore...",
+                    running_msg: "Restoring backup...".to_string(),
+                    log_context: "restore",
+                },
+                update_progress,
+                token,
+                move || {
+                    let path = backup_path_cloned.clone();
+                    let target_package = target_package.clone();
+                    async move {
+                        adb_service
+                            .restore_backup(&device, Path::new(&path), target_package.as_ref())
+                            .await
+                    }
+                },
+            )
+            .await?;
+        if let Some(package) = restored.package {
+            artifacts.set_package_name(package);
+        }
+        Ok(())
+    }
+
+    /// Reinstalls an app from its trash backup, deleting the backup once it is restored
+    #[instrument(skip(self, update_progress, artifacts, token))]
+    pub(super) async fn handle_reinstall_from_trash(
+        &self,
+        path: String,
+        update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
+        token: CancellationToken,
+    ) -> Result<()> {
+        let trash_dir = trash::trash_dir(&self.settings.read().await.backups_location());
+        ensure!(
+            trash::find(&trash_dir, &path).await?.is_some(),
+            "App is no longer in the trash, it may have expired"
+        );
+        self.handle_restore(path.clone(), None, update_progress, artifacts, token).await?;
+        if let Err(e) = trash::delete(&trash_dir, &path).await {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to delete restored trash backup");
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,179 @@
+use std::time::{Duration, Instant};
+
+use super::ProgressUpdate;
+use crate::models::signals::task::{TaskStatus, TransferProgress};
+
+/// Minimum time between progress updates within the same step (at most 10 per second)
+const MIN_INTERVAL: Duration = Duration::from_millis(100);
+/// Minimum step progress change worth an update (0.5%)
+const MIN_PROGRESS_DELTA: f32 = 0.005;
+
+/// Rate-limits the progress updates of one task so fast transfers do not flood Dart.
+///
+/// Status and step changes always pass, and so do new messages without progress since those
+/// announce a phase. Otherwise an update passes once `MIN_INTERVAL` has elapsed since the last
+/// one and it moved the progress by `MIN_PROGRESS_DELTA` or changed the message or transfer
+/// stats. The last dropped update is sent ahead of the next status, step or phase change, so a
+/// step never ends on stale progress.
+#[derive(Default)]
+pub(super) struct ProgressCoalescer {
+    last: Option<Sent>,
+    /// Newest update dropped since the last sent one
+    pending: Option<ProgressUpdate>,
+}
+
+#[derive(Debug)]
+struct Sent {
+    at: Instant,
+    status: TaskStatus,
+    step_number: u8,
+    step_progress: Option<f32>,
+    message: String,
+    transfer: Option<TransferProgress>,
+}
+
+impl ProgressCoalescer {
+    /// Returns the updates to send for `update`, oldest first: the last dropped update if
+    /// `update` changes the status, step or phase, then `update` itself unless it is dropped.
+    pub(super) fn admit(
+        &mut self,
+        now: Instant,
+        update: ProgressUpdate,
+    ) -> impl Iterator<Item = ProgressUpdate> + use<> {
+        let (send, changed) = match &self.last {
+            None => (true, false),
+            Some(last)
+                if last.status != update.status
+                    || last.step_number != update.step_number
+                    || last.step_progress.is_some() != update.step_progress.is_some() =>
+            {
+                (true, true)
+            }
+            Some(last) if update.step_progress.is_none() && last.message != update.message => {
+                (true, true)
+            }
+            Some(last) if now.duration_since(last.at) < MIN_INTERVAL => (false, false),
+            Some(last) => {
+                let moved = match (last.step_progress, update.step_progress) {
+                    (Some(last), Some(current)) => (current - last).abs() >= MIN_PROGRESS_DELTA,
+                    _ => false,
+                };
+                (moved || last.message != update.message || last.transfer != update.transfer, false)
+            }
+        };
+        let (flushed, update) = if send {
+            let flushed = self.pending.take().filter(|_| changed);
+            self.last = Some(Sent {
+                at: now,
+                status: update.status,
+                step_number: update.step_number,
+                step_progress: update.step_progress,
+                message: update.message.clone(),
+                transfer: update.transfer.clone(),
+            });
+            (flushed, Some(update))
+        } else {
+            self.pending = Some(update);
+            (None, None)
+        };
+        flushed.into_iter().chain(update)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(
+        status: TaskStatus,
+        step_number: u8,
+        progress: Option<f32>,
+        message: &str,
+    ) -> ProgressUpdate {
+        ProgressUpdate {
+            status,
+            step_number,
+            step_progress: progress,
+            message: message.into(),
+            transfer: None,
+        }
+    }
+
+    /// Messages and progress of the updates sent for `u`
+    fn admit(
+        coalescer: &mut ProgressCoalescer,
+        at: Instant,
+        u: ProgressUpdate,
+    ) -> Vec<(String, Option<f32>)> {
+        coalescer.admit(at, u).map(|u| (u.message, u.step_progress)).collect()
+    }
+
+    #[test]
+    fn rate_limits_updates_within_a_step() {
+        let mut coalescer = ProgressCoalescer::default();
+        let start = Instant::now();
+        let running = |progress| update(TaskStatus::Running, 1, Some(progress), "Downloading");
+        let at = |millis| start + Duration::from_millis(millis);
+
+        assert_eq!(admit(&mut coalescer, start, running(0.0)).len(), 1);
+        assert!(admit(&mut coalescer, at(50), running(0.2)).is_empty());
+        assert!(admit(&mut coalescer, at(150), running(0.003)).is_empty());
+        assert_eq!(admit(&mut coalescer, at(150), running(0.2)).len(), 1);
+        let message = update(TaskStatus::Running, 1, Some(0.2), "Downloading (2 MB/s)");
+        assert_eq!(admit(&mut coalescer, at(300), message).len(), 1);
+    }
+
+    #[test]
+    fn status_and_step_changes_always_pass() {
+        let mut coalescer = ProgressCoalescer::default();
+        let start = Instant::now();
+        let mut passes = |u| !admit(&mut coalescer, start, u).is_empty();
+
+        assert!(passes(update(TaskStatus::Running, 1, Some(0.5), "Downloading")));
+        assert!(passes(update(TaskStatus::Running, 2, None, "Installing")));
+        assert!(passes(update(TaskStatus::Running, 2, Some(0.0), "Installing")));
+        assert!(passes(update(TaskStatus::Running, 2, None, "Cleaning up")));
+        assert!(passes(update(TaskStatus::Running, 2, None, "Verifying")));
+        assert!(!passes(update(TaskStatus::Running, 2, None, "Verifying")));
+        assert!(passes(update(TaskStatus::Completed, 2, Some(1.0), "Done")));
+    }
+
+    #[test]
+    fn dropped_progress_is_sent_before_the_step_changes() {
+        let mut coalescer = ProgressCoalescer::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        admit(&mut coalescer, start, update(TaskStatus::Running, 1, Some(0.9), "Downloading"));
+        let last = update(TaskStatus::Running, 1, Some(1.0), "Downloading");
+        assert!(admit(&mut coalescer, at(10), last).is_empty());
+        assert_eq!(
+            admit(&mut coalescer, at(20), update(TaskStatus::Running, 2, None, "Installing")),
+            [("Downloading".to_string(), Some(1.0)), ("Installing".to_string(), None)]
+        );
+
+        admit(&mut coalescer, at(200), update(TaskStatus::Running, 2, Some(0.1), "Installing"));
+        let last = update(TaskStatus::Running, 2, Some(0.6), "Installing");
+        assert!(admit(&mut coalescer, at(210), last).is_empty());
+        let sent: Vec<_> =
+            admit(&mut coalescer, at(220), update(TaskStatus::Completed, 2, None, "Done"))
+                .into_iter()
+                .map(|(message, _)| message)
+                .collect();
+        assert_eq!(sent, ["Installing", "Done"]);
+    }
+
+    #[test]
+    fn superseded_progress_is_not_sent_again() {
+        let mut coalescer = ProgressCoalescer::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let running = |progress| update(TaskStatus::Running, 1, Some(progress), "Downloading");
+
+        admit(&mut coalescer, start, running(0.1));
+        assert!(admit(&mut coalescer, at(10), running(0.2)).is_empty());
+        assert_eq!(admit(&mut coalescer, at(150), running(0.3)).len(), 1);
+        let next_step = update(TaskStatus::Running, 2, None, "Installing");
+        assert_eq!(admit(&mut coalescer, at(160), next_step).len(), 1);
+    }
+}
@@ -1,19 +1,34 @@
-use std::{error::Error, path::PathBuf, time::Duration};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::{Context, Result, anyhow, ensure};
+use rinf::RustSignal;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Span, debug, info, instrument, warn};
 
-use super::{AdbStepConfig, ProgressUpdate, TaskManager};
+use super::{
+    AdbStepConfig, ProgressUpdate, TaskManager, artifacts::TaskArtifacts, scheduler::SlotClass,
+};
 use crate::{
     adb::PackageName,
     archive::create_zip_from_dir,
-    downloader::TransferStats,
-    models::{apk_info::get_apk_info, signals::task::TaskStatus},
+    downloader::{Downloader, TransferStats, http_cache::compute_md5_file},
+    models::{
+        apk_info::get_apk_info,
+        signals::task::{DonationUploaded, TaskArtifactKind, TaskStatus},
+    },
 };
 
 pub(crate) const DONATE_TMP_DIR: &str = "_upload";
+/// Upload attempts before the donation fails
+const UPLOAD_ATTEMPTS: u32 = 3;
+/// Wait before the first retry, multiplied by the number of failed attempts
+const UPLOAD_RETRY_DELAY: Duration = Duration::from_secs(10);
 
 /// Guard that cleans up temporary files/directories when dropped.
 /// Paths are removed in reverse order of addition.
@@ -55,19 +70,23 @@ impl Drop for CleanupGuard {
 }
 
 impl TaskManager {
-    #[instrument(skip(self, update_progress, token))]
+    /// Pulls an app from the device, archives it and uploads the archive. If the upload fails,
+    /// the archive is moved to the downloads directory and registered in `artifacts`, so it can
+    /// be shared another way.
+    #[instrument(skip(self, update_progress, artifacts, token))]
     pub(super) async fn handle_donate_app(
         &self,
         package: PackageName,
         display_name: Option<String>,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
     ) -> Result<()> {
         let downloader = self.downloader_manager.require().await?;
 
         debug!(
             package_name = %package,
-            adb_permits_available = self.adb_semaphore.available_permits(),
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
             "Starting app donation task"
         );
 
@@ -123,6 +142,7 @@ impl TaskManager {
             step_number: 2,
             step_progress: None,
             message: "Preparing archive for upload...".into(),
+            transfer: None,
         });
 
         let apk_path = pulled_dir.join(format!("{package}.apk"));
@@ -166,25 +186,120 @@ impl TaskManager {
             return Err(anyhow!("Task cancelled after preparing archive"));
         }
 
-        // Step 3: upload archive via rclone.
+        let md5_hex = compute_md5_file(&archive_path)
+            .await
+            .context("Failed to compute donation archive checksum")?;
+        let size = tokio::fs::metadata(&archive_path).await?.len();
+        info!(archive = archive_file_name, size, md5 = md5_hex, "Donation archive prepared");
+
+        // Step 3: upload archive, retrying failed attempts.
+        let mut attempt = 1;
+        let destination = loop {
+            let result = self
+                .upload_donation_archive(
+                    &downloader,
+                    &archive_path,
+                    &md5_hex,
+                    update_progress,
+                    token.clone(),
+                )
+                .await;
+            match result {
+                Ok(destination) => break destination,
+                Err(e) if attempt < UPLOAD_ATTEMPTS && !token.is_cancelled() => {
+                    let delay = UPLOAD_RETRY_DELAY * attempt;
+                    warn!(
+                        attempt,
+                        error = e.as_ref() as &dyn Error,
+                        "Donation upload failed, retrying in {}s",
+                        delay.as_secs()
+                    );
+                    update_progress(ProgressUpdate {
+                        status: TaskStatus::Running,
+                        step_number: 3,
+                        step_progress: None,
+                        message: format!(
+                            "Upload failed, retrying in {}s ({}/{UPLOAD_ATTEMPTS})...",
+                            delay.as_secs(),
+                            attempt + 1
+                        ),
+                        transfer: None,
+                    });
+                    tokio::select! {
+                        _ = token.cancelled() => {
+                            return Err(anyhow!("Task cancelled while waiting to retry upload"));
+                        }
+                        _ = tokio::time::sleep(delay) => {}
+                    }
+                    attempt += 1;
+                }
+                Err(e) => {
+                    if !token.is_cancelled() {
+                        // The upload directory is swept, keep the archive out of it
+                        let kept_path = downloads_root.join(&archive_file_name);
+                        match tokio::fs::rename(&archive_path, &kept_path).await {
+                            Ok(()) => {
+                                info!(
+                                    path = %kept_path.display(),
+                                    "Kept donation archive after failed upload"
+                                );
+                                artifacts.register(TaskArtifactKind::ExportedZip, &kept_path);
+                            }
+                            Err(err) => warn!(
+                                error = &err as &dyn Error,
+                                "Failed to keep donation archive after failed upload"
+                            ),
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        };
+
+        info!(destination, "Donation archive uploaded");
+        DonationUploaded {
+            package_name: package.to_string(),
+            archive_name: archive_file_name,
+            size,
+            md5: md5_hex,
+            destination,
+        }
+        .send_signal_to_dart();
+
+        Ok(())
+    }
+
+    /// Uploads the archive once, reporting transfer progress as step 3
+    async fn upload_donation_archive(
+        &self,
+        downloader: &Arc<Downloader>,
+        archive_path: &Path,
+        md5_hex: &str,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: CancellationToken,
+    ) -> Result<String> {
         update_progress(ProgressUpdate {
             status: TaskStatus::Running,
             step_number: 3,
             step_progress: None,
             message: "Uploading archive...".into(),
+            transfer: None,
         });
 
         let (tx, mut rx) = mpsc::unbounded_channel::<TransferStats>();
 
         let mut upload_task = {
-            let archive_path = archive_path.clone();
-            let token = token.clone();
+            let downloader = downloader.clone();
+            let archive_path = archive_path.to_path_buf();
+            let md5_hex = md5_hex.to_string();
             tokio::spawn(
-                    async move {
-                        downloader.upload_donation_archive(&archive_path, Some(tx), token).await
-                    }
-                    .instrument(Span::current()),
-                )
+                async move {
+                    downloader
+                        .upload_donation_archive(&archive_path, &md5_hex, Some(tx), token)
+                        .await
+                }
+                .instrument(Span::current()),
+            )
         };
 
         debug!("Starting upload monitoring");
@@ -196,11 +311,11 @@ impl TaskManager {
         while upload_result.is_none() {
             tokio::select! {
                 result = &mut upload_task => {
-                    result
+                    let destination = result
                         .context("Upload task failed")?
                         .context("Failed to upload donation app archive")?;
                     info!("Upload task completed");
-                    upload_result = Some(());
+                    upload_result = Some(destination);
                 }
                 Some(progress) = rx.recv() => {
                     let now = std::time::Instant::now();
@@ -255,15 +370,12 @@ impl TaskManager {
                         step_number,
                         step_progress,
                         message,
+                        transfer: None,
                     });
                 }
             }
         }
 
-        upload_result.expect("upload_result should be Some after loop exit");
-
-        // drop(cleanup_guard);
-
-        Ok(())
+        Ok(upload_result.expect("upload_result should be Some after loop exit"))
     }
 }
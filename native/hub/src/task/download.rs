@@ -1,39 +1,100 @@
-use std::{error::Error, path::Path, time::Duration};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Span, debug, error, info, instrument, warn};
 
-use super::{InstallStepConfig, ProgressUpdate, TaskManager};
+use super::{InstallStepConfig, ProgressUpdate, TaskManager, artifacts::TaskArtifacts};
 use crate::{
-    adb::PackageName, downloader::AppDownloadProgress, models::signals::task::TaskStatus,
-    task::acquire_permit_or_cancel,
+    adb::PackageName,
+    casting::CastingManager,
+    downloader::{
+        AppDownloadProgress, DownloadMetrics, DownloadVerification, TransferStats, remaining_secs,
+        resume,
+    },
+    models::{
+        CastingDownloadPolicy,
+        signals::task::{TaskArtifactKind, TaskStatus},
+    },
+    task::{acquire_permit_or_cancel, scheduler::SlotClass},
 };
 
 const DOWNLOAD_CANCEL_ABORT_TIMEOUT: Duration = Duration::from_secs(5);
+const CASTING_POLICY_RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Local copy of an app left by the download step
+#[derive(Debug)]
+struct DownloadedApp {
+    path: String,
+    /// Local files already matched the remote, nothing was transferred
+    skipped: bool,
+}
 
 impl TaskManager {
-    #[instrument(level = "debug", skip(self, update_progress, token))]
+    /// Holds a download back while a casting session runs and the pause policy is selected
+    async fn wait_for_casting_to_end(
+        &self,
+        step_number: u8,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: &CancellationToken,
+    ) -> Result<()> {
+        let mut casting_rx = CastingManager::subscribe_session();
+        loop {
+            let pause =
+                self.settings.read().await.casting_download_policy == CastingDownloadPolicy::Pause;
+            if !pause || !*casting_rx.borrow_and_update() {
+                return Ok(());
+            }
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Waiting,
+                step_number,
+                step_progress: None,
+                message: "Waiting for casting to end...".into(),
+                transfer: None,
+            });
+            tokio::select! {
+                _ = token.cancelled() => bail!("Task cancelled while waiting for casting to end"),
+                result = casting_rx.changed() => result.context("Casting session watch closed")?,
+                // Pick up policy changes made while waiting
+                _ = tokio::time::sleep(CASTING_POLICY_RECHECK_INTERVAL) => {}
+            }
+        }
+    }
+
+    /// Downloads an app, or only `files` of it, recording its transfer stats in `artifacts` unless
+    /// nothing was transferred
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "debug", skip(self, update_progress, artifacts, token))]
     async fn run_download_step(
         &self,
         app_full_name: &str,
         true_package: PackageName,
+        files: Option<Vec<String>>,
         step_number: u8,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
-    ) -> Result<String> {
+    ) -> Result<DownloadedApp> {
         let downloader = self.downloader_manager.require().await?;
         update_progress(ProgressUpdate {
             status: TaskStatus::Waiting,
             step_number,
             step_progress: None,
             message: "Waiting to start download...".into(),
+            transfer: None,
         });
 
-        let _permit = acquire_permit_or_cancel!(self.download_semaphore, token, "download");
+        self.wait_for_casting_to_end(step_number, update_progress, &token).await?;
+
+        let _permit =
+            acquire_permit_or_cancel!(self.scheduler, SlotClass::Download, token, "download");
         debug!(
-            download_permits_remaining = self.download_semaphore.available_permits(),
+            download_permits_remaining = self.scheduler.available(SlotClass::Download),
             "Acquired download semaphore"
         );
 
@@ -42,16 +103,18 @@ impl TaskManager {
             step_number,
             step_progress: None,
             message: "Starting download...".into(),
+            transfer: None,
         });
 
         let (tx, mut rx) = mpsc::unbounded_channel::<AppDownloadProgress>();
+        let mut metrics = DownloadMetrics::new(std::time::Instant::now());
 
         let mut download_task = {
             let app_full_name = app_full_name.to_string();
             let token = token.clone();
             tokio::spawn(
                 async move {
-                    downloader.download_app(app_full_name, true_package, tx, token).await
+                    downloader.download_app(app_full_name, true_package, files, tx, token).await
                 }
                 .instrument(Span::current()),
             )
@@ -63,6 +126,7 @@ impl TaskManager {
         let mut last_log_progress = 0.0;
         let mut cancel_requested = false;
         let mut cancel_deadline = None;
+        let mut skipped = false;
 
         while download_result.is_none() {
             let abort_deadline = cancel_deadline;
@@ -92,6 +156,7 @@ impl TaskManager {
                         step_number,
                         step_progress: None,
                         message: "Cancelling download...".into(),
+                        transfer: None,
                     });
                 }
                 _ = &mut abort_timeout => {
@@ -114,36 +179,26 @@ impl TaskManager {
                                 step_number,
                                 step_progress: None,
                                 message,
+                                transfer: None,
                             });
                             continue;
                         }
+                        AppDownloadProgress::Started { source, attempt } => {
+                            metrics.record_start(source, attempt);
+                            continue;
+                        }
+                        AppDownloadProgress::Skipped => {
+                            skipped = true;
+                            continue;
+                        }
                         AppDownloadProgress::Transfer(progress) => progress,
                     };
+                    metrics.record_transfer(&progress);
                     let now = std::time::Instant::now();
-                    let (step_progress, message, progress_percent) = match progress.total_bytes {
-                        Some(total_bytes) => {
-                            let step_progress = progress.bytes as f32 / total_bytes as f32;
-                            let progress_percent = step_progress * 100.0;
-                            (
-                                Some(step_progress),
-                                format!(
-                                    "Downloading ({:.1}%) - {}/s",
-                                    progress_percent,
-                                    humansize::format_size(progress.speed, humansize::DECIMAL)
-                                ),
-                                Some(progress_percent),
-                            )
-                        }
-                        None => (
-                            None,
-                            format!(
-                                "Downloading (Unknown%) - {}/s",
-                                humansize::format_size(progress.speed, humansize::DECIMAL)
-                            ),
-                            None,
-                        ),
-                    };
-
+                    let step_progress = progress
+                        .total_bytes
+                        .map(|total_bytes| progress.bytes as f32 / total_bytes.max(1) as f32);
+                    let progress_percent = step_progress.map(|step_progress| step_progress * 100.0);
                     let should_log = now.duration_since(last_log_time) > Duration::from_secs(10)
                         || step_progress.is_some_and(|step_progress| {
                             ((0.25..0.26).contains(&step_progress)
@@ -166,11 +221,15 @@ impl TaskManager {
                         }
                     }
 
+                    let eta_secs = progress.eta_secs.or_else(|| {
+                        remaining_secs(progress.bytes, progress.total_bytes, progress.speed)
+                    });
                     update_progress(ProgressUpdate {
                         status: TaskStatus::Running,
                         step_number,
                         step_progress,
-                        message,
+                        message: "Downloading...".into(),
+                        transfer: Some(TransferStats { eta_secs, ..progress }.into()),
                     });
                 }
             }
@@ -182,40 +241,165 @@ impl TaskManager {
         }
         info!(
             app_path = %app_path,
-            download_permits = self.download_semaphore.available_permits() + 1,
+            skipped,
+            download_permits = self.scheduler.available(SlotClass::Download) + 1,
             "Download completed, releasing download semaphore"
         );
         drop(_permit);
 
-        Ok(app_path)
+        if skipped {
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Running,
+                step_number,
+                step_progress: Some(1.0),
+                message: "Already downloaded, skipped download".into(),
+                transfer: None,
+            });
+        } else {
+            artifacts.set_download_summary(metrics.summary(std::time::Instant::now()));
+        }
+
+        Ok(DownloadedApp { path: app_path, skipped })
     }
 
-    #[instrument(skip(self, update_progress, token))]
+    /// Compares the finished download, or its `files` only, with the remote to catch files
+    /// truncated in transit.
+    ///
+    /// Nothing is compared when the download was skipped, it already matched the remote then.
+    #[instrument(level = "debug", skip(self, download, update_progress, token), err)]
+    async fn run_verify_step(
+        &self,
+        app_full_name: &str,
+        files: Option<&[String]>,
+        download: &DownloadedApp,
+        step_number: u8,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: CancellationToken,
+    ) -> Result<()> {
+        if download.skipped {
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Running,
+                step_number,
+                step_progress: Some(1.0),
+                message: "Already matches the remote, not verified again".into(),
+                transfer: None,
+            });
+            return Ok(());
+        }
+        let downloader = self.downloader_manager.require().await?;
+        update_progress(ProgressUpdate {
+            status: TaskStatus::Running,
+            step_number,
+            step_progress: None,
+            message: "Verifying download...".into(),
+            transfer: None,
+        });
+
+        let verification = downloader.verify_download(app_full_name, files, token.clone()).await;
+        if token.is_cancelled() {
+            return Err(anyhow!("Task cancelled during verification"));
+        }
+        let message = match verification.context("Download verification failed")? {
+            DownloadVerification::Verified { files } => format!("Verified {files} file(s)"),
+            DownloadVerification::Unsupported => {
+                "Verification is not supported by this repository".into()
+            }
+        };
+        update_progress(ProgressUpdate {
+            status: TaskStatus::Running,
+            step_number,
+            step_progress: Some(1.0),
+            message,
+            transfer: None,
+        });
+        Ok(())
+    }
+
+    /// Fails early if the connected device cannot fit the app once installed.
+    ///
+    /// Skipped when no device is connected or the app is not in the catalog, the sideload step
+    /// checks the actual files again either way.
+    #[instrument(level = "debug", skip(self, update_progress))]
+    async fn run_space_precheck(
+        &self,
+        app_full_name: &str,
+        true_package: &PackageName,
+        step_number: u8,
+        update_progress: &impl Fn(ProgressUpdate),
+    ) -> Result<()> {
+        let downloader = self.downloader_manager.require().await?;
+        let Some(app) = downloader.get_app_by_full_name(app_full_name).await else {
+            debug!("App not found in catalog, skipping space pre-check");
+            return Ok(());
+        };
+        let Ok(device) = self.adb_service.current_device().await else {
+            debug!("No device connected, skipping space pre-check");
+            return Ok(());
+        };
+
+        // Only queries the device, so does not wait for the ADB slot held by running installs
+        update_progress(ProgressUpdate {
+            status: TaskStatus::Running,
+            step_number,
+            step_progress: None,
+            message: "Checking free space on device...".into(),
+            transfer: None,
+        });
+        device
+            .ensure_space_for_install(true_package.as_str(), app.expected_install_size())
+            .await
+            .context("Device space pre-check failed")
+    }
+
+    #[instrument(skip(self, update_progress, artifacts, token))]
     pub(super) async fn handle_download_install(
         &self,
         app_full_name: String,
         true_package: PackageName,
+        files: Option<Vec<String>>,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
     ) -> Result<()> {
         debug!(
             app_name = %app_full_name,
-            download_permits_available = self.download_semaphore.available_permits(),
-            adb_permits_available = self.adb_semaphore.available_permits(),
+            download_permits_available = self.scheduler.available(SlotClass::Download),
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
             "Starting download and install task"
         );
 
-        let app_path = self
-            .run_download_step(&app_full_name, true_package, 1, update_progress, token.clone())
+        self.run_space_precheck(&app_full_name, &true_package, 1, update_progress).await?;
+        let download = self
+            .run_download_step(
+                &app_full_name,
+                true_package.clone(),
+                files.clone(),
+                1,
+                update_progress,
+                artifacts,
+                token.clone(),
+            )
             .await?;
+        artifacts.register(TaskArtifactKind::DownloadedApp, &download.path);
+        self.run_verify_step(
+            &app_full_name,
+            files.as_deref(),
+            &download,
+            2,
+            update_progress,
+            token.clone(),
+        )
+        .await?;
+        let app_path = download.path;
 
         if token.is_cancelled() {
             warn!("Task was cancelled after download completion");
             return Err(anyhow!("Task cancelled after download"));
         }
 
+        let approved_script =
+            self.review_install_script(Path::new(&app_path), 3, update_progress, &token).await?;
         let adb_service = self.adb_service.clone();
-        let device = adb_service.current_device().await?;
 
         let settings = self.settings.read().await;
         let backups_location = settings.backups_location();
@@ -224,12 +408,18 @@ impl TaskManager {
 
         let app_path_cloned = app_path.clone();
         self.run_install_step(
-            InstallStepConfig { step_number: 2, log_context: "sideload" },
+            InstallStepConfig {
+                step_number: 3,
+                log_context: "sideload",
+                pushed_from: Path::new(&app_path),
+            },
             update_progress,
             token.clone(),
-            move |tx, token| {
+            move |device, tx, token| {
+                let adb_service = adb_service.clone();
                 let app_path = app_path_cloned.clone();
                 let backups_location = backups_location.clone();
+                let approved_script = approved_script.clone();
                 tokio::spawn(
                     async move {
                         adb_service
@@ -239,6 +429,7 @@ impl TaskManager {
                                 backups_location,
                                 tx,
                                 token,
+                                approved_script,
                                 auto_reinstall_on_conflict,
                             )
                             .await
@@ -248,6 +439,7 @@ impl TaskManager {
             },
         )
         .await?;
+        self.run_install_recipe(true_package.as_str(), 3, update_progress, token).await;
 
         // Apply downloads cleanup policy
         if let Err(e) = self.cleanup_downloads_after_install(&app_full_name, &app_path).await {
@@ -261,21 +453,89 @@ impl TaskManager {
         Ok(())
     }
 
-    #[instrument(skip(self, update_progress, token))]
+    #[instrument(skip(self, update_progress, artifacts, token))]
     pub(super) async fn handle_download(
         &self,
         app_full_name: String,
         true_package: PackageName,
+        files: Option<Vec<String>>,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
     ) -> Result<()> {
         debug!(
             app_name = %app_full_name,
-            download_permits_available = self.download_semaphore.available_permits(),
+            download_permits_available = self.scheduler.available(SlotClass::Download),
             "Starting download task"
         );
 
-        self.run_download_step(&app_full_name, true_package, 1, update_progress, token).await?;
+        let download = self
+            .run_download_step(
+                &app_full_name,
+                true_package,
+                files.clone(),
+                1,
+                update_progress,
+                artifacts,
+                token.clone(),
+            )
+            .await?;
+        artifacts.register(TaskArtifactKind::DownloadedApp, &download.path);
+        self.run_verify_step(
+            &app_full_name,
+            files.as_deref(),
+            &download,
+            2,
+            update_progress,
+            token,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, update_progress, artifacts, token))]
+    pub(super) async fn handle_resume_download(
+        &self,
+        app_full_name: String,
+        update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
+        token: CancellationToken,
+    ) -> Result<()> {
+        let downloads_dir = self.settings.read().await.downloads_location();
+        let checkpoint = resume::load_checkpoint(&downloads_dir, &app_full_name)
+            .await?
+            .with_context(|| format!("No interrupted download found for \"{app_full_name}\""))?;
+        debug!(
+            app_name = %app_full_name,
+            attempts = checkpoint.attempts,
+            started_at = checkpoint.started_at,
+            "Resuming download from checkpoint"
+        );
+
+        let true_package = PackageName::parse(&checkpoint.true_package_name)?;
+        artifacts.set_package_name(true_package.to_string());
+        let download = self
+            .run_download_step(
+                &app_full_name,
+                true_package,
+                checkpoint.files.clone(),
+                1,
+                update_progress,
+                artifacts,
+                token.clone(),
+            )
+            .await?;
+        artifacts.register(TaskArtifactKind::DownloadedApp, &download.path);
+        self.run_verify_step(
+            &app_full_name,
+            checkpoint.files.as_deref(),
+            &download,
+            2,
+            update_progress,
+            token,
+        )
+        .await?;
 
         Ok(())
     }
@@ -286,7 +546,12 @@ impl TaskManager {
         app_full_name: &str,
         app_path: &str,
     ) -> Result<()> {
-        let cleanup_policy = self.settings.read().await.cleanup_policy;
-        self.downloads_catalog.apply_cleanup_policy(cleanup_policy, app_full_name, app_path).await
+        let settings = self.settings.read().await;
+        let cleanup_policy = settings.cleanup_policy;
+        let installed_downloads_dir = PathBuf::from(&settings.installed_downloads_location);
+        drop(settings);
+        self.downloads_catalog
+            .apply_cleanup_policy(cleanup_policy, app_full_name, app_path, &installed_downloads_dir)
+            .await
     }
 }
@@ -0,0 +1,309 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace, warn};
+
+use crate::models::signals::task::{TaskKind, TaskStatus};
+
+/// Weight of the newest sample in the running averages
+const SMOOTHING: f64 = 0.3;
+/// Step progress below this is too noisy to extrapolate from
+const MIN_EXTRAPOLATION_PROGRESS: f32 = 0.02;
+/// Time a step must have run before its progress is extrapolated
+const MIN_EXTRAPOLATION_ELAPSED: Duration = Duration::from_secs(2);
+
+/// Running averages of one step of one task kind
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct StepStats {
+    /// Seconds the step took
+    secs: f64,
+    /// Seconds per byte of app size, only known once the step ran for an app of known size
+    #[serde(default)]
+    secs_per_byte: Option<f64>,
+    samples: u32,
+}
+
+impl StepStats {
+    fn record(&mut self, secs: f64, size: Option<u64>) {
+        let per_byte = size.filter(|&size| size > 0).map(|size| secs / size as f64);
+        if self.samples == 0 {
+            self.secs = secs;
+            self.secs_per_byte = per_byte;
+        } else {
+            self.secs += SMOOTHING * (secs - self.secs);
+            self.secs_per_byte = match (self.secs_per_byte, per_byte) {
+                (Some(average), Some(sample)) => Some(average + SMOOTHING * (sample - average)),
+                (average, sample) => sample.or(average),
+            };
+        }
+        self.samples = self.samples.saturating_add(1);
+    }
+
+    fn estimate(&self, size: Option<u64>) -> Duration {
+        let secs = match (self.secs_per_byte, size) {
+            (Some(per_byte), Some(size)) if size > 0 => per_byte * size as f64,
+            _ => self.secs,
+        };
+        Duration::from_secs_f64(secs.max(0.0))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedStep {
+    kind: TaskKind,
+    step: u8,
+    #[serde(flatten)]
+    stats: StepStats,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedTimings {
+    #[serde(default)]
+    steps: Vec<PersistedStep>,
+}
+
+/// State of the running step of a task
+#[derive(Debug, Clone, Copy)]
+pub(super) struct StepProgress {
+    pub step: u8,
+    pub total_steps: u8,
+    /// Time the step has been running, excluding time spent waiting for a slot
+    pub elapsed: Duration,
+    pub progress: Option<f32>,
+}
+
+/// Tracks how long the current step of a task has been running
+#[derive(Debug, Default)]
+pub(super) struct StepClock {
+    step: u8,
+    running_since: Option<Instant>,
+}
+
+impl StepClock {
+    /// Follows a progress update, returning the step it finished and how long that step ran.
+    ///
+    /// Steps only count once they report running, so time spent waiting for a slot before that is
+    /// left out. Steps interrupted by a failure or cancellation are not counted.
+    pub(super) fn advance(
+        &mut self,
+        step: u8,
+        status: TaskStatus,
+        now: Instant,
+    ) -> Option<(u8, Duration)> {
+        let mut finished = None;
+        if step != self.step {
+            let since = self.running_since.take();
+            let failed = matches!(status, TaskStatus::Failed | TaskStatus::Cancelled);
+            if step > self.step && !failed {
+                finished = since.map(|since| (self.step, now - since));
+            }
+            self.step = step;
+        }
+        match status {
+            TaskStatus::Running => {
+                self.running_since.get_or_insert(now);
+            }
+            TaskStatus::Completed => {
+                let since = self.running_since.take();
+                if finished.is_none() {
+                    finished = since.map(|since| (self.step, now - since));
+                }
+            }
+            TaskStatus::Failed | TaskStatus::Cancelled => self.running_since = None,
+            TaskStatus::Waiting => {}
+        }
+        finished
+    }
+
+    /// Time the current step has been running
+    pub(super) fn elapsed(&self, now: Instant) -> Option<Duration> {
+        self.running_since.map(|since| now - since)
+    }
+}
+
+/// Learns how long each step of each task kind takes to estimate the time left for running tasks.
+///
+/// Durations are kept per byte of app size where the size is known, so estimates scale with the
+/// app being processed.
+#[derive(Debug)]
+pub(super) struct StepTimings {
+    path: PathBuf,
+    stats: Mutex<HashMap<(TaskKind, u8), StepStats>>,
+}
+
+impl StepTimings {
+    /// Loads timings from `path`. A missing or unreadable file starts without any timings.
+    pub(super) fn load(path: PathBuf) -> Self {
+        let stats = if path.exists() {
+            match Self::read_timings(&path) {
+                Ok(timings) => timings
+                    .steps
+                    .into_iter()
+                    .map(|step| ((step.kind, step.step), step.stats))
+                    .collect(),
+                Err(e) => {
+                    warn!(error = e.as_ref() as &dyn Error, "Failed to load task step timings");
+                    HashMap::new()
+                }
+            }
+        } else {
+            HashMap::new()
+        };
+        debug!(steps = stats.len(), "Loaded task step timings");
+        Self { path, stats: Mutex::new(stats) }
+    }
+
+    fn read_timings(path: &Path) -> Result<PersistedTimings> {
+        let content = fs::read_to_string(path).context("Failed to read step timings file")?;
+        serde_json::from_str(&content).context("Failed to parse step timings file")
+    }
+
+    /// Records how long a finished step took
+    pub(super) fn record(&self, kind: TaskKind, step: u8, duration: Duration, size: Option<u64>) {
+        trace!(?kind, step, ?duration, size, "Recording step duration");
+        let mut stats = self.stats.lock().expect("step timings lock poisoned");
+        stats.entry((kind, step)).or_default().record(duration.as_secs_f64(), size);
+    }
+
+    pub(super) fn save(&self) -> Result<()> {
+        let timings = {
+            let stats = self.stats.lock().expect("step timings lock poisoned");
+            let mut steps = stats
+                .iter()
+                .map(|(&(kind, step), stats)| PersistedStep { kind, step, stats: stats.clone() })
+                .collect::<Vec<_>>();
+            steps.sort_by_key(|step| {
+                (TaskKind::ALL.iter().position(|k| *k == step.kind), step.step)
+            });
+            PersistedTimings { steps }
+        };
+        let json =
+            serde_json::to_string_pretty(&timings).context("Failed to serialize step timings")?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).context("Failed to write step timings file")?;
+        fs::rename(&tmp_path, &self.path).context("Failed to replace step timings file")
+    }
+
+    /// Estimates the time until a task of `kind` finishes, or `None` without enough data.
+    ///
+    /// The running step is extrapolated from its progress where it reports one, the steps after
+    /// it are taken from the recorded averages.
+    pub(super) fn estimate_remaining(
+        &self,
+        kind: TaskKind,
+        current: StepProgress,
+        size: Option<u64>,
+    ) -> Option<Duration> {
+        let stats = self.stats.lock().expect("step timings lock poisoned");
+        let estimate = |step: u8| stats.get(&(kind, step)).map(|stats| stats.estimate(size));
+
+        let current_left = match current.progress {
+            Some(progress)
+                if progress >= MIN_EXTRAPOLATION_PROGRESS
+                    && current.elapsed >= MIN_EXTRAPOLATION_ELAPSED =>
+            {
+                let progress = progress.min(1.0) as f64;
+                current.elapsed.mul_f64((1.0 - progress) / progress)
+            }
+            _ => estimate(current.step)?.saturating_sub(current.elapsed),
+        };
+        (current.step + 1..=current.total_steps)
+            .try_fold(current_left, |total, step| Some(total + estimate(step)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(step: u8, elapsed_secs: u64, progress: Option<f32>) -> StepProgress {
+        StepProgress { step, total_steps: 3, elapsed: Duration::from_secs(elapsed_secs), progress }
+    }
+
+    #[test]
+    fn estimates_need_every_remaining_step() {
+        let dir = tempfile::tempdir().unwrap();
+        let timings = StepTimings::load(dir.path().join("step_timings.json"));
+        let kind = TaskKind::DownloadInstall;
+        timings.record(kind, 2, Duration::from_secs(10), None);
+        assert_eq!(timings.estimate_remaining(kind, step(1, 30, Some(0.5)), None), None);
+
+        timings.record(kind, 3, Duration::from_secs(20), None);
+        assert_eq!(
+            timings.estimate_remaining(kind, step(1, 30, Some(0.5)), None),
+            Some(Duration::from_secs(60))
+        );
+        // Without usable progress the running step falls back to its average
+        assert_eq!(
+            timings.estimate_remaining(kind, step(2, 4, None), None),
+            Some(Duration::from_secs(26))
+        );
+        assert_eq!(
+            timings.estimate_remaining(kind, step(3, 25, Some(0.01)), None),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn estimates_scale_with_app_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("step_timings.json");
+        let timings = StepTimings::load(path.clone());
+        let kind = TaskKind::Download;
+        timings.record(kind, 1, Duration::from_secs(100), Some(1_000_000_000));
+        timings.record(kind, 2, Duration::from_secs(4), None);
+        timings.save().unwrap();
+
+        let timings = StepTimings::load(path);
+        let current = StepProgress { total_steps: 2, ..step(1, 0, None) };
+        assert_eq!(
+            timings.estimate_remaining(kind, current, Some(2_000_000_000)),
+            Some(Duration::from_secs(204))
+        );
+        assert_eq!(timings.estimate_remaining(kind, current, None), Some(Duration::from_secs(104)));
+    }
+
+    #[test]
+    fn clock_times_running_steps() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut clock = StepClock::default();
+
+        assert_eq!(clock.advance(1, TaskStatus::Waiting, at(0)), None);
+        assert_eq!(clock.elapsed(at(1)), None);
+        assert_eq!(clock.advance(1, TaskStatus::Running, at(2)), None);
+        assert_eq!(clock.elapsed(at(5)), Some(Duration::from_secs(3)));
+        assert_eq!(
+            clock.advance(2, TaskStatus::Waiting, at(10)),
+            Some((1, Duration::from_secs(8)))
+        );
+        assert_eq!(clock.advance(2, TaskStatus::Running, at(12)), None);
+        assert_eq!(
+            clock.advance(2, TaskStatus::Completed, at(15)),
+            Some((2, Duration::from_secs(3)))
+        );
+        assert_eq!(clock.elapsed(at(16)), None);
+
+        let mut clock = StepClock::default();
+        clock.advance(1, TaskStatus::Running, at(0));
+        assert_eq!(clock.advance(3, TaskStatus::Failed, at(5)), None);
+    }
+
+    #[test]
+    fn averages_favor_recent_samples() {
+        let mut stats = StepStats::default();
+        stats.record(10.0, None);
+        stats.record(20.0, Some(10));
+        assert_eq!(stats.secs, 13.0);
+        assert_eq!(stats.secs_per_byte, Some(2.0));
+        assert_eq!(stats.samples, 2);
+    }
+}
@@ -1,101 +1,161 @@
-use std::{path::Path, time::Duration};
+use std::{error::Error, path::Path, sync::Arc, time::Duration};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail, ensure};
+use rinf::RustSignal;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Span, debug, info, instrument, warn};
 
 use super::{AdbStepConfig, InstallStepConfig, ProgressUpdate, TaskManager};
 use crate::{
-    adb::{PackageName, device::SideloadProgress},
-    task::acquire_permit_or_cancel,
+    adb::{
+        PackageName,
+        device::{AdbDevice, LogcatMark, SideloadProgress, preview_install_script},
+    },
+    archive::is_apk_bundle,
+    models::signals::{adb::install_script::ScriptOperation, system::Toast, trash::AppTrashed},
+    task::{acquire_permit_or_cancel, artifacts::TaskArtifacts, scheduler::SlotClass},
+    trash,
+    utils::dir_size,
 };
 
+/// Apps at least this large get a Wi-Fi link probe before a wireless sideload
+const LINK_PROBE_MIN_BYTES: u64 = 200_000_000;
+
 impl TaskManager {
+    /// Runs an install step on the current device.
+    ///
+    /// If a wireless device stops responding during the step and the same headset is plugged in
+    /// over USB, the step switches to the USB connection and is retried once. Returns what the
+    /// install task returned.
     #[instrument(level = "debug", skip(self, update_progress, token, spawn_install))]
-    pub(super) async fn run_install_step<'a>(
+    pub(super) async fn run_install_step<'a, T>(
         &self,
         cfg: InstallStepConfig<'a>,
         update_progress: &impl Fn(ProgressUpdate),
         token: CancellationToken,
-        spawn_install: impl FnOnce(
+        spawn_install: impl Fn(
+            Arc<AdbDevice>,
             mpsc::UnboundedSender<SideloadProgress>,
             CancellationToken,
-        ) -> tokio::task::JoinHandle<anyhow::Result<()>>,
-    ) -> Result<()> {
+        ) -> tokio::task::JoinHandle<anyhow::Result<T>>,
+    ) -> Result<T> {
         update_progress(ProgressUpdate {
             status: crate::models::signals::task::TaskStatus::Waiting,
             step_number: cfg.step_number,
             step_progress: None,
             message: "Waiting to start installation...".into(),
+            transfer: None,
         });
 
-        let _permit = acquire_permit_or_cancel!(self.adb_semaphore, token, "ADB");
+        let _permit = acquire_permit_or_cancel!(self.scheduler, SlotClass::Adb, token, "ADB");
         debug!(
-            adb_permits_remaining = self.adb_semaphore.available_permits(),
+            adb_permits_remaining = self.scheduler.available(SlotClass::Adb),
             "Acquired ADB semaphore for installation"
         );
 
+        let mut device = self.adb_service.current_device().await?;
+        // Installer log lines from the device are added to the task log once the step ends
+        let logcat_mark = device
+            .logcat_mark()
+            .await
+            .inspect_err(|e| debug!(error = %format!("{e:#}"), "Not capturing device log"))
+            .ok();
+
+        let mut message = "Installing APK...".to_string();
+        if device.is_wireless
+            && self.settings.read().await.probe_wireless_link
+            && let Some(estimate) = self.probe_wireless_link(&device, cfg.pushed_from).await
+        {
+            message = format!("Installing APK... ({estimate})");
+        }
         update_progress(ProgressUpdate {
             status: crate::models::signals::task::TaskStatus::Running,
             step_number: cfg.step_number,
             step_progress: None,
-            message: "Installing APK...".into(),
+            message,
+            transfer: None,
         });
 
-        let (tx, mut rx) = mpsc::unbounded_channel::<SideloadProgress>();
-        let mut install_task = spawn_install(tx, token.clone());
-
-        debug!("Starting {} monitoring", cfg.log_context);
-        let mut install_result = None;
-        let mut last_log_time = std::time::Instant::now();
-        let mut cancel_requested = false;
-
-        while install_result.is_none() {
-            tokio::select! {
-                result = &mut install_task => {
-                    install_result = Some(result.context("Install task failed")?);
-                    info!("{} task completed", cfg.log_context);
-                }
-                _ = token.cancelled(), if !cancel_requested => {
-                    warn!("Cancellation requested for install step, requesting task abort");
-                    cancel_requested = true;
-                    install_task.abort();
-                }
-                Some(progress) = rx.recv() => {
-                    let step_progress_num = progress.progress.unwrap_or(0.0);
-
-                    // Log progress every 5 seconds
-                    let now = std::time::Instant::now();
-                    if now.duration_since(last_log_time) > Duration::from_secs(5) {
-                        debug!(
-                            install_progress = step_progress_num,
-                            status = %progress.status,
-                            context = cfg.log_context,
-                            "Installation progress"
-                        );
-                        last_log_time = now;
-                    }
-
+        let started_at = std::time::Instant::now();
+        let mut fell_back_to_usb = false;
+        let install_result = loop {
+            let (tx, rx) = mpsc::unbounded_channel::<SideloadProgress>();
+            let install_task = spawn_install(device.clone(), tx, token.clone());
+            match monitor_install(&cfg, update_progress, &token, install_task, rx).await? {
+                Err(e) if device.is_wireless && !fell_back_to_usb && !token.is_cancelled() => {
+                    let Some(usb_device) = self.adb_service.fall_back_to_usb(&device).await else {
+                        break Err(e);
+                    };
+                    warn!(
+                        error = e.as_ref() as &dyn Error,
+                        usb_serial = %usb_device.serial,
+                        "Wireless connection lost during {}, retrying over USB",
+                        cfg.log_context
+                    );
                     update_progress(ProgressUpdate {
                         status: crate::models::signals::task::TaskStatus::Running,
                         step_number: cfg.step_number,
-                        step_progress: progress.progress,
-                        message: progress.status,
+                        step_progress: None,
+                        message: "Wireless connection lost, retrying over USB...".into(),
+                        transfer: None,
                     });
+                    device = usb_device;
+                    fell_back_to_usb = true;
                 }
+                result => break result,
             }
-        }
+        };
 
-        install_result.expect("install_result should be Some after loop exit")?;
+        if let Some(mark) = &logcat_mark {
+            log_install_logcat(&device, mark, install_result.is_err()).await;
+        }
+        let output = install_result?;
+        self.record_push(cfg.pushed_from, started_at.elapsed()).await;
 
         info!(
-            adb_permits = self.adb_semaphore.available_permits() + 1,
+            adb_permits = self.scheduler.available(SlotClass::Adb) + 1,
             context = cfg.log_context,
             "Installation completed, releasing ADB semaphore"
         );
 
-        Ok(())
+        Ok(output)
+    }
+
+    async fn record_push(&self, path: &Path, elapsed: Duration) {
+        match path_size(path).await {
+            Ok(size) => self.transfer_stats.record_push(size, elapsed),
+            Err(e) => debug!(error = %format!("{e:#}"), "Not counting pushed size"),
+        }
+    }
+
+    /// Measures the Wi‑Fi link before pushing a large app, warning when it is slow.
+    ///
+    /// Returns the measured speed and expected push time for the step message, None for small
+    /// apps or when the probe fails.
+    async fn probe_wireless_link(&self, device: &AdbDevice, path: &Path) -> Option<String> {
+        let size = path_size(path).await.ok().filter(|&size| size >= LINK_PROBE_MIN_BYTES)?;
+        let quality = device
+            .probe_link()
+            .await
+            .inspect_err(|e| warn!(error = e.as_ref() as &dyn Error, "Wi-Fi link probe failed"))
+            .ok()?;
+        let speed = humansize::format_size(quality.throughput, humansize::DECIMAL);
+        let eta = format_duration(quality.transfer_time(size));
+        if quality.is_slow() {
+            Toast::send(
+                "Slow Wi-Fi Connection".to_string(),
+                format!(
+                    "Measured {speed}/s with {} ms latency, pushing {} will take about {eta}. A \
+                     USB cable is usually much faster.",
+                    quality.latency.as_millis(),
+                    humansize::format_size(size, humansize::DECIMAL),
+                ),
+                false,
+                None,
+            );
+        }
+        Some(format!("Wi-Fi {speed}/s, about {eta}"))
     }
 
     #[instrument(level = "debug", skip(self, update_progress, token, fut))]
@@ -115,11 +175,12 @@ impl TaskManager {
             step_number: cfg.step_number,
             step_progress: None,
             message: cfg.waiting_msg.into(),
+            transfer: None,
         });
 
-        let _permit = acquire_permit_or_cancel!(self.adb_semaphore, token, "ADB");
+        let _permit = acquire_permit_or_cancel!(self.scheduler, SlotClass::Adb, token, "ADB");
         debug!(
-            adb_permits_remaining = self.adb_semaphore.available_permits(),
+            adb_permits_remaining = self.scheduler.available(SlotClass::Adb),
             "Acquired ADB semaphore for {}", cfg.log_context
         );
 
@@ -128,6 +189,7 @@ impl TaskManager {
             step_number: cfg.step_number,
             step_progress: None,
             message: cfg.running_msg,
+            transfer: None,
         });
 
         debug!("Starting {} operation", cfg.log_context);
@@ -135,143 +197,432 @@ impl TaskManager {
         debug!("{} operation completed", cfg.log_context);
 
         info!(
-            adb_permits = self.adb_semaphore.available_permits() + 1,
+            adb_permits = self.scheduler.available(SlotClass::Adb) + 1,
             "{} completed, releasing ADB semaphore", cfg.log_context
         );
 
         Ok(result)
     }
 
-    #[instrument(skip(self, update_progress, token))]
+    #[instrument(skip(self, update_progress, artifacts, token))]
     pub(super) async fn handle_install_apk(
         &self,
         apk_path: String,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
     ) -> Result<()> {
         debug!(
             apk_path = %apk_path,
-            adb_permits_available = self.adb_semaphore.available_permits(),
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
             "Starting APK install task"
         );
 
+        // Bundles are extracted and installed the way local app folders are
+        if is_apk_bundle(Path::new(&apk_path)) {
+            return self
+                .handle_install_local_app(apk_path, update_progress, artifacts, token)
+                .await;
+        }
+
         let adb_service = self.adb_service.clone();
-        let device = adb_service.current_device().await?;
 
         let settings = self.settings.read().await;
         let backups_location = settings.backups_location();
         let auto_reinstall_on_conflict = settings.auto_reinstall_on_conflict;
         drop(settings);
 
-        self.run_install_step(
-            InstallStepConfig { step_number: 1, log_context: "apk_install" },
-            update_progress,
-            token,
-            move |tx, _token| {
-                let backups_location = backups_location.clone();
-                tokio::spawn(
-                    async move {
-                        adb_service
-                            .install_apk(
-                                &device,
-                                Path::new(&apk_path),
-                                backups_location,
-                                tx,
-                                auto_reinstall_on_conflict,
-                            )
-                            .await
-                    }
-                    .instrument(Span::current()),
-                )
-            },
-        )
-        .await
-        .map(|_| ())
-        .context("APK installation failed")
+        let apk_path_cloned = apk_path.clone();
+        let installed = self
+            .run_install_step(
+                InstallStepConfig {
+                    step_number: 1,
+                    log_context: "apk_install",
+                    pushed_from: Path::new(&apk_path),
+                },
+                update_progress,
+                token.clone(),
+                move |device, tx, _token| {
+                    let adb_service = adb_service.clone();
+                    let apk_path = apk_path_cloned.clone();
+                    let backups_location = backups_location.clone();
+                    tokio::spawn(
+                        async move {
+                            adb_service
+                                .install_apk(
+                                    &device,
+                                    Path::new(&apk_path),
+                                    backups_location,
+                                    tx,
+                                    auto_reinstall_on_conflict,
+                                )
+                                .await
+                        }
+                        .instrument(Span::current()),
+                    )
+                },
+            )
+            .await
+            .context("APK installation failed")?;
+        if let Some(package) = installed {
+            self.run_install_recipe(&package, 1, update_progress, token).await;
+            artifacts.set_package_name(package);
+        }
+        Ok(())
     }
 
-    #[instrument(skip(self, update_progress, token))]
+    #[instrument(skip(self, update_progress, artifacts, token))]
     pub(super) async fn handle_install_local_app(
         &self,
         app_path: String,
         update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
         token: CancellationToken,
     ) -> Result<()> {
         debug!(
             app_path = %app_path,
-            adb_permits_available = self.adb_semaphore.available_permits(),
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
             "Starting local app install task"
         );
 
         let adb_service = self.adb_service.clone();
-        let device = adb_service.current_device().await?;
 
         let settings = self.settings.read().await;
         let backups_location = settings.backups_location();
         let auto_reinstall_on_conflict = settings.auto_reinstall_on_conflict;
         drop(settings);
 
+        let approved_script =
+            self.review_install_script(Path::new(&app_path), 1, update_progress, &token).await?;
         let app_path_cloned = app_path.clone();
+        let installed = self
+            .run_install_step(
+                InstallStepConfig {
+                    step_number: 1,
+                    log_context: "sideload_local",
+                    pushed_from: Path::new(&app_path),
+                },
+                update_progress,
+                token.clone(),
+                move |device, tx, token| {
+                    let adb_service = adb_service.clone();
+                    let app_path = app_path_cloned.clone();
+                    let backups_location = backups_location.clone();
+                    let approved_script = approved_script.clone();
+                    tokio::spawn(
+                        async move {
+                            adb_service
+                                .sideload_app(
+                                    &device,
+                                    Path::new(&app_path),
+                                    backups_location,
+                                    tx,
+                                    token,
+                                    approved_script,
+                                    auto_reinstall_on_conflict,
+                                )
+                                .await
+                        }
+                        .instrument(Span::current()),
+                    )
+                },
+            )
+            .await
+            .context("Local app installation failed")?;
+        if let Some(package) = installed {
+            self.run_install_recipe(&package, 1, update_progress, token).await;
+            artifacts.set_package_name(package);
+        }
+        Ok(())
+    }
+
+    /// Asks for approval of the install script of an app, if it has one that can be read up
+    /// front. Done before an ADB slot is taken, so a pending review does not hold up other
+    /// device work. Returns the approved operations.
+    pub(super) async fn review_install_script(
+        &self,
+        app_path: &Path,
+        step_number: u8,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: &CancellationToken,
+    ) -> Result<Option<Vec<ScriptOperation>>> {
+        let (script_path, operations) = match preview_install_script(app_path).await {
+            Ok(Some(script)) => script,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to read install script up front");
+                return Ok(None);
+            }
+        };
+        update_progress(ProgressUpdate {
+            status: crate::models::signals::task::TaskStatus::Waiting,
+            step_number,
+            step_progress: None,
+            message: "Waiting for install script approval...".into(),
+            transfer: None,
+        });
+        let approved = tokio::select! {
+            approved = self.adb_service.review_install_script(script_path, operations.clone()) => approved,
+            _ = token.cancelled() => bail!("Install script cancelled"),
+        };
+        ensure!(approved, "Install script was not approved");
+        Ok(Some(operations))
+    }
+
+    /// Runs the post-install recipe of `package` on the current device, if it has one.
+    ///
+    /// Failures are reported without failing the task, the app is installed at this point.
+    pub(super) async fn run_install_recipe(
+        &self,
+        package: &str,
+        step_number: u8,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: CancellationToken,
+    ) {
+        let Some(recipe) = self.install_recipes.recipe(package) else {
+            return;
+        };
+        let adb_service = self.adb_service.clone();
+        let result = self
+            .run_adb_one_step(
+                AdbStepConfig {
+                    step_number,
+                    waiting_msg: "Waiting to run post-install recipe...",
+                    running_msg: format!(
+                        "Running post-install recipe ({} steps)...",
+                        recipe.steps.len()
+                    ),
+                    log_context: "install_recipe",
+                },
+                update_progress,
+                token.clone(),
+                move || async move {
+                    adb_service.current_device().await?.run_install_recipe(&recipe).await
+                },
+            )
+            .await;
+        if let Err(e) = result
+            && !token.is_cancelled()
+        {
+            warn!(error = e.as_ref() as &dyn Error, %package, "Post-install recipe failed");
+            Toast::send(
+                "Post-install recipe failed".to_string(),
+                format!("{package}: {e:#}"),
+                true,
+                None,
+            );
+        }
+    }
+
+    #[instrument(skip(self, update_progress, token))]
+    pub(super) async fn handle_push_obb(
+        &self,
+        package: PackageName,
+        local_dir: String,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: CancellationToken,
+    ) -> Result<()> {
+        debug!(
+            package_name = %package,
+            local_dir = %local_dir,
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
+            "Starting OBB push task"
+        );
+
+        let adb_service = self.adb_service.clone();
+
+        let local_dir_cloned = local_dir.clone();
         self.run_install_step(
-            InstallStepConfig { step_number: 1, log_context: "sideload_local" },
+            InstallStepConfig {
+                step_number: 1,
+                log_context: "obb_push",
+                pushed_from: Path::new(&local_dir),
+            },
             update_progress,
             token,
-            move |tx, token| {
-                let app_path = app_path_cloned.clone();
-                let backups_location = backups_location.clone();
+            move |device, tx, _token| {
+                let adb_service = adb_service.clone();
+                let package = package.clone();
+                let local_dir = local_dir_cloned.clone();
                 tokio::spawn(
                     async move {
-                        adb_service
-                            .sideload_app(
-                                &device,
-                                Path::new(&app_path),
-                                backups_location,
-                                tx,
-                                token,
-                                auto_reinstall_on_conflict,
-                            )
-                            .await
+                        adb_service.push_obb(&device, &package, Path::new(&local_dir), tx).await
                     }
                     .instrument(Span::current()),
                 )
             },
         )
         .await
-        .map(|_| ())
-        .context("Local app installation failed")
+        .context("OBB push failed")
     }
 
+    /// Uninstalls a package, first backing it up to the trash if that is enabled
     #[instrument(skip(self, update_progress, token))]
     pub(super) async fn handle_uninstall(
         &self,
         package: PackageName,
+        display_name: Option<String>,
         update_progress: &impl Fn(ProgressUpdate),
         token: CancellationToken,
     ) -> Result<()> {
         debug!(
             package_name = %package,
-            adb_permits_available = self.adb_semaphore.available_permits(),
+            adb_permits_available = self.scheduler.available(SlotClass::Adb),
             "Starting uninstall task"
         );
 
         let adb_service = self.adb_service.clone();
         let device = adb_service.current_device().await?;
+        let settings = self.settings.read().await;
+        let trash_dir =
+            settings.trash_before_uninstall.then(|| trash::trash_dir(&settings.backups_location()));
+        let trash_retention_days = settings.trash_retention_days;
+        drop(settings);
 
-        self.run_adb_one_step(
-            AdbStepConfig {
-                step_number: 1,
-                waiting_msg: "Waiting to start uninstallation...",
-                running_msg: "Uninstalling app...".to_string(),
-                log_context: "uninstall",
-            },
-            update_progress,
-            token,
-            move || {
-                let package_name = package.clone();
-                async move { adb_service.uninstall_package(&device, &package_name).await }
-            },
-        )
-        .await
-        .map(|_| ())
+        let trashed = self
+            .run_adb_one_step(
+                AdbStepConfig {
+                    step_number: 1,
+                    waiting_msg: "Waiting to start uninstallation...",
+                    running_msg: if trash_dir.is_some() {
+                        "Backing up app to trash and uninstalling...".to_string()
+                    } else {
+                        "Uninstalling app...".to_string()
+                    },
+                    log_context: "uninstall",
+                },
+                update_progress,
+                token.clone(),
+                move || async move {
+                    let trashed = match &trash_dir {
+                        Some(dir) => trash::backup_to_trash(
+                            &adb_service,
+                            &device,
+                            &package,
+                            display_name.as_deref(),
+                            dir,
+                            token,
+                        )
+                        .await
+                        .context("Failed to back up app to trash, it was not uninstalled")?
+                        .map(|app| (dir.clone(), app)),
+                        None => None,
+                    };
+                    if let Err(e) = adb_service.uninstall_package(&device, &package).await {
+                        if let Some((dir, app)) = &trashed
+                            && let Err(e) = trash::delete(dir, &app.path).await
+                        {
+                            warn!(
+                                error = e.as_ref() as &dyn Error,
+                                "Failed to discard trash backup"
+                            );
+                        }
+                        return Err(e);
+                    }
+                    Ok(trashed)
+                },
+            )
+            .await?;
+
+        if let Some((dir, app)) = trashed {
+            AppTrashed { app }.send_signal_to_dart();
+            if let Err(e) = trash::prune(&dir, trash_retention_days).await {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to prune trash");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adds the device's installer log lines written since `mark` to the task log.
+///
+/// Lines are logged as a warning when the step failed, since they usually hold the reason.
+async fn log_install_logcat(device: &AdbDevice, mark: &LogcatMark, failed: bool) {
+    let lines = match device.install_logcat_since(mark).await {
+        Ok(lines) => lines,
+        Err(e) => {
+            debug!(error = %format!("{e:#}"), "Failed to read installer log from device");
+            return;
+        }
+    };
+    if lines.is_empty() {
+        return;
+    }
+    let device_log = lines.join("\n");
+    if failed {
+        warn!(lines = lines.len(), device_log, "Device installer log");
+    } else {
+        debug!(lines = lines.len(), device_log, "Device installer log");
+    }
+}
+
+/// Forwards the progress of a running install task until it ends or the step is cancelled.
+///
+/// The outer error means the task itself failed to run, the inner one is the install result.
+async fn monitor_install<T>(
+    cfg: &InstallStepConfig<'_>,
+    update_progress: &impl Fn(ProgressUpdate),
+    token: &CancellationToken,
+    mut install_task: tokio::task::JoinHandle<Result<T>>,
+    mut rx: mpsc::UnboundedReceiver<SideloadProgress>,
+) -> Result<Result<T>> {
+    debug!("Starting {} monitoring", cfg.log_context);
+    let mut install_result = None;
+    let mut last_log_time = std::time::Instant::now();
+    let mut cancel_requested = false;
+
+    while install_result.is_none() {
+        tokio::select! {
+            result = &mut install_task => {
+                install_result = Some(result.context("Install task failed")?);
+                info!("{} task completed", cfg.log_context);
+            }
+            _ = token.cancelled(), if !cancel_requested => {
+                warn!("Cancellation requested for install step, requesting task abort");
+                cancel_requested = true;
+                install_task.abort();
+            }
+            Some(progress) = rx.recv() => {
+                let step_progress_num = progress.progress.unwrap_or(0.0);
+
+                // Log progress every 5 seconds
+                let now = std::time::Instant::now();
+                if now.duration_since(last_log_time) > Duration::from_secs(5) {
+                    debug!(
+                        install_progress = step_progress_num,
+                        status = %progress.status,
+                        context = cfg.log_context,
+                        "Installation progress"
+                    );
+                    last_log_time = now;
+                }
+
+                update_progress(ProgressUpdate {
+                    status: crate::models::signals::task::TaskStatus::Running,
+                    step_number: cfg.step_number,
+                    step_progress: progress.progress,
+                    message: progress.status,
+                    transfer: None,
+                });
+            }
+        }
+    }
+
+    Ok(install_result.expect("install_result should be Some after loop exit"))
+}
+
+/// Size of a file, or of everything in a directory
+async fn path_size(path: &Path) -> Result<u64> {
+    let meta = tokio::fs::metadata(path).await?;
+    if meta.is_dir() { dir_size(path).await } else { Ok(meta.len()) }
+}
+
+/// Rough human-readable duration, e.g. `45 s`, `12 min` or `1 h 5 min`
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs} s"),
+        60..3600 => format!("{} min", secs.div_ceil(60)),
+        _ => format!("{} h {} min", secs / 3600, secs % 3600 / 60),
     }
 }
@@ -0,0 +1,98 @@
+use std::{sync::Mutex, time::Instant};
+
+use anyhow::{Context, Result};
+use rinf::RustSignal;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument};
+
+use super::{ProgressUpdate, TaskManager, artifacts::TaskArtifacts};
+use crate::{
+    lan_share::{fetch_shares, http_client, receive_share},
+    models::signals::{
+        downloads_local::DownloadsChanged,
+        task::{TaskArtifactKind, TaskStatus},
+    },
+    task::{acquire_permit_or_cancel, scheduler::SlotClass},
+};
+
+/// Minimum time between two progress updates while receiving
+const PROGRESS_INTERVAL_MS: u128 = 200;
+
+impl TaskManager {
+    /// Copies an app shared by a peer into the downloads directory
+    #[instrument(skip(self, update_progress, artifacts, token))]
+    pub(super) async fn handle_receive_from_peer(
+        &self,
+        address: &str,
+        share_id: &str,
+        name: &str,
+        update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
+        token: CancellationToken,
+    ) -> Result<()> {
+        update_progress(ProgressUpdate {
+            status: TaskStatus::Waiting,
+            step_number: 1,
+            step_progress: None,
+            message: "Waiting to start transfer...".into(),
+            transfer: None,
+        });
+        let _permit =
+            acquire_permit_or_cancel!(self.scheduler, SlotClass::Download, token, "download");
+
+        update_progress(ProgressUpdate {
+            status: TaskStatus::Running,
+            step_number: 1,
+            step_progress: None,
+            message: "Connecting to peer...".into(),
+            transfer: None,
+        });
+        let client = http_client();
+        let manifest = fetch_shares(&client, address)
+            .await?
+            .into_iter()
+            .find(|share| share.id == share_id)
+            .with_context(|| format!("The peer no longer shares \"{name}\""))?;
+        let dest = self
+            .settings
+            .read()
+            .await
+            .downloads_location()
+            .join(sanitize_filename::sanitize(&manifest.name));
+        let total = manifest.size();
+        debug!(dest = %dest.display(), total, files = manifest.files.len(), "Receiving share");
+
+        let total_label = humansize::format_size(total, humansize::DECIMAL);
+        let last_update = Mutex::new(Instant::now());
+        receive_share(
+            &client,
+            address,
+            &manifest,
+            &dest,
+            |received| {
+                let mut last_update = last_update.lock().expect("progress lock poisoned");
+                if last_update.elapsed().as_millis() < PROGRESS_INTERVAL_MS && received < total {
+                    return;
+                }
+                *last_update = Instant::now();
+                update_progress(ProgressUpdate {
+                    status: TaskStatus::Running,
+                    step_number: 1,
+                    step_progress: Some(received as f32 / total.max(1) as f32),
+                    message: format!(
+                        "Receiving... {} / {total_label}",
+                        humansize::format_size(received, humansize::DECIMAL)
+                    ),
+                    transfer: None,
+                });
+            },
+            &token,
+        )
+        .await?;
+
+        info!(dest = %dest.display(), "Received app from peer");
+        artifacts.register(TaskArtifactKind::DownloadedApp, &dest);
+        DownloadsChanged {}.send_signal_to_dart();
+        Ok(())
+    }
+}
@@ -1,16 +1,18 @@
 use std::{
     collections::HashMap,
     error::Error,
+    path::PathBuf,
     sync::{
         Arc,
         atomic::{AtomicU64, Ordering},
     },
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use anyhow::{Result, anyhow, ensure};
 use rinf::{DartSignal, RustSignal};
 use tokio::{
-    sync::{Mutex, Notify, RwLock, Semaphore},
+    sync::{Mutex, Notify, RwLock, RwLockWriteGuard, broadcast, oneshot},
     time::timeout,
 };
 use tokio_stream::{StreamExt, wrappers::WatchStream};
@@ -18,39 +20,82 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::{
-    adb::{AdbService, PackageName},
+    adb::{AdbService, PackageName, device::BackupOptions},
     downloader::{downloads_catalog::DownloadsCatalog, manager::DownloaderManager},
+    install_history::InstallHistory,
+    install_recipes::InstallRecipes,
     models::{
         Settings,
         signals::{
+            history::{HistoryAction, HistoryEntry, HistoryOutcome},
             system::Toast,
-            task::{Task, TaskCancelRequest, TaskKind, TaskProgress, TaskRequest, TaskStatus},
+            task::{
+                ContinueUpdateAllRequest, RestoredTask, Task, TaskCancelRequest, TaskKind,
+                TaskListSnapshot, TaskPriority, TaskProgress, TaskReorderRequest, TaskRequest,
+                TaskSnapshot, TaskStatus, TasksRestored, TransferOverwriteDecision,
+                UpdateAllRequest,
+            },
         },
     },
-    task::{BackupStepConfig, ProgressUpdate},
+    mods_library::ModsLibrary,
+    task::{
+        BackupStepConfig, ProgressUpdate, acquire_permit_or_cancel,
+        artifacts::TaskArtifacts,
+        coalescer::ProgressCoalescer,
+        eta::{StepClock, StepProgress, StepTimings},
+        queue_store::TaskQueueStore,
+        scheduler::{ConcurrencyLimits, SlotClass, TaskScheduler},
+        timeline::ProgressTimeline,
+        transfer::TransferTarget,
+    },
+    transfer_stats::TransferStatistics,
 };
 
 pub(crate) struct TaskManager {
-    pub(super) adb_semaphore: Semaphore,
-    pub(super) download_semaphore: Semaphore,
+    pub(super) scheduler: TaskScheduler,
     id_counter: AtomicU64,
     tasks: Mutex<TaskRegistry>,
     tasks_changed: Notify,
-    shutdown_token: CancellationToken,
+    /// Absent when tasks should not outlive the process, e.g. in the CLI
+    queue_store: Option<TaskQueueStore>,
+    progress_tx: broadcast::Sender<TaskProgress>,
+    /// Latest progress of every queued task, replayed to a UI that reconnects
+    latest_progress: std::sync::Mutex<HashMap<u64, TaskProgress>>,
+    step_timings: StepTimings,
+    /// Update All batches waiting for the user to confirm their canary app, by batch ID
+    pub(super) paused_batches: std::sync::Mutex<HashMap<u64, oneshot::Sender<bool>>>,
+    /// Transfers waiting for the user to confirm replacing data on the target, by task ID
+    pub(super) transfer_reviews: std::sync::Mutex<HashMap<u64, oneshot::Sender<bool>>>,
+    pub(super) shutdown_token: CancellationToken,
+    /// Held by every running task, taken exclusively to keep tasks from starting
+    task_gate: RwLock<()>,
     pub(super) adb_service: Arc<AdbService>,
     pub(super) downloader_manager: Arc<DownloaderManager>,
     pub(super) downloads_catalog: Arc<DownloadsCatalog>,
+    pub(super) mods_library: Arc<ModsLibrary>,
+    install_history: Arc<InstallHistory>,
+    pub(super) install_recipes: Arc<InstallRecipes>,
+    pub(super) transfer_stats: Arc<TransferStatistics>,
     pub(super) settings: RwLock<Settings>,
 }
 
 struct TaskRegistry {
     accepting_tasks: bool,
     tasks: HashMap<u64, (Task, CancellationToken)>,
+    /// Tasks loaded from the persisted queue that have not been re-queued yet
+    pending_restore: Vec<Task>,
+    /// Restorable tasks cancelled by shutdown, kept to be restored on next start
+    interrupted: Vec<(u64, Task)>,
 }
 
 impl Default for TaskRegistry {
     fn default() -> Self {
-        Self { accepting_tasks: true, tasks: HashMap::new() }
+        Self {
+            accepting_tasks: true,
+            tasks: HashMap::new(),
+            pending_restore: Vec::new(),
+            interrupted: Vec::new(),
+        }
     }
 }
 
@@ -65,6 +110,79 @@ impl TaskRegistry {
         true
     }
 
+    /// Returns restorable tasks that should survive an app restart, oldest first.
+    fn persisted_tasks(&self) -> Vec<&Task> {
+        let mut active: Vec<_> = self
+            .tasks
+            .iter()
+            .map(|(id, (task, _))| (*id, task))
+            .chain(self.interrupted.iter().map(|(id, task)| (*id, task)))
+            .filter(|(_, task)| task.is_restorable())
+            .collect();
+        active.sort_by_key(|(id, _)| *id);
+        self.pending_restore.iter().chain(active.into_iter().map(|(_, task)| task)).collect()
+    }
+
+    /// Removes a finished task. Restorable tasks cancelled by shutdown stay persisted, any
+    /// other outcome drops the task from the persisted queue.
+    ///
+    /// Returns whether the persisted queue changed.
+    fn finish(&mut self, id: u64, status: TaskStatus) -> bool {
+        let Some((task, _)) = self.tasks.remove(&id) else {
+            return false;
+        };
+        if !task.is_restorable() {
+            return false;
+        }
+        if status == TaskStatus::Cancelled && !self.accepting_tasks {
+            self.interrupted.push((id, task));
+            return false;
+        }
+        true
+    }
+
+    /// Latest progress of queued tasks that have not finished, oldest first. Tasks that have not
+    /// reported progress yet are described as waiting.
+    fn unfinished_progress(
+        &self,
+        latest: &HashMap<u64, TaskProgress>,
+        priority: impl Fn(u64) -> TaskPriority,
+    ) -> Vec<TaskProgress> {
+        let mut tasks = self
+            .tasks
+            .iter()
+            .filter_map(|(id, (task, _))| match latest.get(id) {
+                Some(progress)
+                    if matches!(
+                        progress.status,
+                        TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
+                    ) =>
+                {
+                    None
+                }
+                Some(progress) => Some(progress.clone()),
+                None => Some(TaskProgress {
+                    task_id: *id,
+                    task_kind: TaskKind::from(task),
+                    task_name: task.task_name().ok(),
+                    status: TaskStatus::Waiting,
+                    priority: priority(*id),
+                    total_progress: 0.0,
+                    message: "Queued".into(),
+                    current_step: 1,
+                    total_steps: task.total_steps().into(),
+                    step_progress: None,
+                    artifacts: Vec::new(),
+                    download_summary: None,
+                    eta_secs: None,
+                    transfer: None,
+                }),
+            })
+            .collect::<Vec<_>>();
+        tasks.sort_by_key(|progress| progress.task_id);
+        tasks
+    }
+
     fn start_shutdown(&mut self) -> usize {
         self.accepting_tasks = false;
         for (_, token) in self.tasks.values() {
@@ -81,25 +199,49 @@ pub(crate) struct TaskShutdownResult {
 }
 
 impl TaskManager {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         adb_service: Arc<AdbService>,
         downloader_manager: Arc<DownloaderManager>,
         downloads_catalog: Arc<DownloadsCatalog>,
+        mods_library: Arc<ModsLibrary>,
+        install_history: Arc<InstallHistory>,
+        install_recipes: Arc<InstallRecipes>,
+        transfer_stats: Arc<TransferStatistics>,
+        app_dir: PathBuf,
+        persist_queue: bool,
         mut settings_stream: WatchStream<Settings>,
     ) -> Arc<Self> {
         let initial_settings = futures::executor::block_on(settings_stream.next())
             .expect("Settings stream closed on task manager init");
 
+        let queue_store =
+            persist_queue.then(|| TaskQueueStore::new(app_dir.join("task_queue.json")));
+        let registry = TaskRegistry {
+            pending_restore: queue_store.as_ref().map(TaskQueueStore::load).unwrap_or_default(),
+            ..TaskRegistry::default()
+        };
+
         let handle = Arc::new(Self {
-            adb_semaphore: Semaphore::new(1),
-            download_semaphore: Semaphore::new(1),
+            scheduler: TaskScheduler::new(ConcurrencyLimits::from_settings(&initial_settings)),
             id_counter: AtomicU64::new(0),
-            tasks: Mutex::new(TaskRegistry::default()),
+            tasks: Mutex::new(registry),
             tasks_changed: Notify::new(),
+            queue_store,
+            progress_tx: broadcast::Sender::new(256),
+            latest_progress: std::sync::Mutex::new(HashMap::new()),
+            step_timings: StepTimings::load(app_dir.join("task_step_timings.json")),
+            paused_batches: std::sync::Mutex::new(HashMap::new()),
+            transfer_reviews: std::sync::Mutex::new(HashMap::new()),
             shutdown_token: CancellationToken::new(),
+            task_gate: RwLock::new(()),
             adb_service,
             downloader_manager,
             downloads_catalog,
+            mods_library,
+            install_history,
+            install_recipes,
+            transfer_stats,
             settings: RwLock::new(initial_settings),
         });
 
@@ -110,6 +252,13 @@ impl TaskManager {
             }
         });
 
+        tokio::spawn({
+            let handle = handle.clone();
+            async move {
+                handle.restore_persisted_tasks().await;
+            }
+        });
+
         // Listen for settings updates
         tokio::spawn({
             let handle = handle.clone();
@@ -120,6 +269,9 @@ impl TaskManager {
                         _ = handle.shutdown_token.cancelled() => break,
                         settings = stream.next() => {
                             if let Some(settings) = settings {
+                                handle
+                                    .scheduler
+                                    .set_limits(ConcurrencyLimits::from_settings(&settings));
                                 *handle.settings.write().await = settings;
                             } else {
                                 break;
@@ -137,6 +289,10 @@ impl TaskManager {
     async fn receive_requests(self: Arc<Self>) {
         let request_receiver = TaskRequest::get_dart_signal_receiver();
         let cancel_request_receiver = TaskCancelRequest::get_dart_signal_receiver();
+        let reorder_request_receiver = TaskReorderRequest::get_dart_signal_receiver();
+        let update_all_receiver = UpdateAllRequest::get_dart_signal_receiver();
+        let continue_update_all_receiver = ContinueUpdateAllRequest::get_dart_signal_receiver();
+        let transfer_decision_receiver = TransferOverwriteDecision::get_dart_signal_receiver();
 
         loop {
             tokio::select! {
@@ -146,7 +302,8 @@ impl TaskManager {
                 }
                 request = request_receiver.recv() => {
                     if let Some(request) = request {
-                        self.clone().enqueue_task(request.message.task).await;
+                        let TaskRequest { task, priority } = request.message;
+                        self.clone().enqueue_task(task, priority).await;
                     } else {
                         panic!("TaskRequest receiver closed");
                     }
@@ -158,12 +315,53 @@ impl TaskManager {
                         panic!("TaskCancelRequest receiver closed");
                     }
                 }
+                reorder_request = reorder_request_receiver.recv() => {
+                    if let Some(reorder_request) = reorder_request {
+                        let TaskReorderRequest { task_id, priority } = reorder_request.message;
+                        self.reprioritize_task(task_id, priority);
+                    } else {
+                        panic!("TaskReorderRequest receiver closed");
+                    }
+                }
+                update_all = update_all_receiver.recv() => {
+                    if let Some(update_all) = update_all {
+                        tokio::spawn(self.clone().update_all(update_all.message));
+                    } else {
+                        panic!("UpdateAllRequest receiver closed");
+                    }
+                }
+                continue_request = continue_update_all_receiver.recv() => {
+                    if let Some(continue_request) = continue_request {
+                        let ContinueUpdateAllRequest { batch_id, proceed } =
+                            continue_request.message;
+                        self.continue_update_all(batch_id, proceed);
+                    } else {
+                        panic!("ContinueUpdateAllRequest receiver closed");
+                    }
+                }
+                decision = transfer_decision_receiver.recv() => {
+                    if let Some(decision) = decision {
+                        let TransferOverwriteDecision { task_id, approved } = decision.message;
+                        self.decide_transfer_overwrite(task_id, approved);
+                    } else {
+                        panic!("TransferOverwriteDecision receiver closed");
+                    }
+                }
             }
         }
     }
 
+    /// Receives the progress of every task, as sent to Dart
+    pub(crate) fn subscribe_progress(&self) -> broadcast::Receiver<TaskProgress> {
+        self.progress_tx.subscribe()
+    }
+
     #[instrument(level = "debug", skip(self))]
-    async fn enqueue_task(self: Arc<Self>, task: Task) -> Option<u64> {
+    pub(crate) async fn enqueue_task(
+        self: Arc<Self>,
+        task: Task,
+        priority: TaskPriority,
+    ) -> Option<u64> {
         let id = self.id_counter.fetch_add(1, Ordering::Relaxed);
         let token = CancellationToken::new();
 
@@ -174,25 +372,101 @@ impl TaskManager {
         if !registry.insert(id, task.clone(), token.clone()) {
             return None;
         }
+        if task.is_restorable() {
+            self.persist_queue(&registry);
+        }
+        self.send_task_list(&registry);
         drop(registry);
 
         debug!(task_id = id, active_tasks = active_tasks_count + 1, "Task added to queue");
 
+        self.spawn_task(id, task, priority, token);
+        Some(id)
+    }
+
+    fn spawn_task(
+        self: &Arc<Self>,
+        id: u64,
+        task: Task,
+        priority: TaskPriority,
+        token: CancellationToken,
+    ) {
         tokio::spawn({
             let handle = self.clone();
             async move {
-                handle.process_task(id, task, token).await;
+                let gate = handle.task_gate.read().await;
+                let status = handle
+                    .scheduler
+                    .run_task(id, priority, handle.process_task(id, task, token))
+                    .await;
+                drop(gate);
 
                 let mut registry = handle.tasks.lock().await;
-                registry.tasks.remove(&id);
+                handle.latest_progress().remove(&id);
+                if registry.finish(id, status) {
+                    handle.persist_queue(&registry);
+                }
                 let remaining_tasks = registry.tasks.len();
+                handle.send_task_list(&registry);
                 drop(registry);
                 handle.tasks_changed.notify_one();
                 debug!(task_id = id, remaining_tasks = remaining_tasks, "Task removed from queue");
             }
         });
+    }
 
-        Some(id)
+    fn persist_queue(&self, registry: &TaskRegistry) {
+        let Some(queue_store) = &self.queue_store else {
+            return;
+        };
+        if let Err(e) = queue_store.save(registry.persisted_tasks()) {
+            error!(error = e.as_ref() as &dyn Error, "Failed to persist task queue");
+        }
+    }
+
+    /// Re-queues tasks persisted by a previous session once the downloader is available.
+    #[instrument(level = "debug", skip(self))]
+    async fn restore_persisted_tasks(self: Arc<Self>) {
+        if self.tasks.lock().await.pending_restore.is_empty() {
+            return;
+        }
+
+        tokio::select! {
+            _ = self.shutdown_token.cancelled() => return,
+            _ = self.downloader_manager.wait_available() => {}
+        }
+
+        let mut registry = self.tasks.lock().await;
+        let pending = std::mem::take(&mut registry.pending_restore);
+        let mut restored = Vec::with_capacity(pending.len());
+        for task in pending {
+            let id = self.id_counter.fetch_add(1, Ordering::Relaxed);
+            let token = CancellationToken::new();
+            if !registry.insert(id, task.clone(), token.clone()) {
+                return;
+            }
+            restored.push((id, task, token));
+        }
+        self.persist_queue(&registry);
+        drop(registry);
+
+        info!(count = restored.len(), "Restoring tasks from previous session");
+        TasksRestored {
+            tasks: restored
+                .iter()
+                .map(|(id, task, _)| RestoredTask {
+                    task_id: *id,
+                    task_kind: TaskKind::from(task),
+                    task_name: task.task_name().ok(),
+                })
+                .collect(),
+        }
+        .send_signal_to_dart();
+        self.send_task_list(&*self.tasks.lock().await);
+
+        for (id, task, token) in restored {
+            self.spawn_task(id, task, TaskPriority::Normal, token);
+        }
     }
 
     #[instrument(level = "debug", skip(self))]
@@ -215,9 +489,39 @@ impl TaskManager {
         }
     }
 
+    /// Changes the priority of an unfinished task
+    #[instrument(level = "debug", skip(self))]
+    fn reprioritize_task(&self, task_id: u64, priority: TaskPriority) {
+        if self.scheduler.set_priority(task_id, priority) {
+            if let Some(progress) = self.latest_progress().get_mut(&task_id) {
+                progress.priority = priority;
+            }
+            info!(task_id, ?priority, "Task priority changed");
+        } else {
+            warn!(task_id, "Task not found for reordering - may have already completed");
+        }
+    }
+
+    /// Number of queued and running tasks
+    pub(crate) async fn active_task_count(&self) -> usize {
+        self.tasks.lock().await.tasks.len()
+    }
+
+    /// Keeps tasks from starting until the guard is dropped, tasks queued meanwhile wait. Fails
+    /// if any task is queued or running.
+    pub(crate) async fn pause_tasks(&self) -> Result<RwLockWriteGuard<'_, ()>> {
+        let gate =
+            self.task_gate.try_write().map_err(|_| anyhow!("Wait for tasks to finish first"))?;
+        let active_tasks = self.active_task_count().await;
+        ensure!(active_tasks == 0, "Wait for {active_tasks} queued task(s) to finish first");
+        Ok(gate)
+    }
+
     pub(crate) async fn shutdown(&self, wait_timeout: Duration) -> TaskShutdownResult {
         let active_tasks = {
             let mut registry = self.tasks.lock().await;
+            // Saved before cancelling, interrupted tasks are restored on next start
+            self.persist_queue(&registry);
             registry.start_shutdown()
         };
         self.shutdown_token.cancel();
@@ -238,8 +542,21 @@ impl TaskManager {
         result
     }
 
-    #[instrument(level = "debug", skip(self, token))]
-    async fn process_task(&self, id: u64, task: Task, token: CancellationToken) {
+    /// Size of the catalog app a task downloads, used to scale step time estimates
+    async fn task_app_size(&self, task: &Task) -> Option<u64> {
+        let app_full_name = match task {
+            Task::Download(name, ..)
+            | Task::DownloadInstall(name, ..)
+            | Task::ResumeDownload(name) => name,
+            _ => return None,
+        };
+        let downloader = self.downloader_manager.get().await?;
+        downloader.get_app_by_full_name(app_full_name).await.map(|app| app.size)
+    }
+
+    /// Runs a task to the end and returns its final status
+    #[instrument(level = "debug", skip(self, id, token), fields(task_id = id))]
+    async fn process_task(&self, id: u64, task: Task, token: CancellationToken) -> TaskStatus {
         let start_time = std::time::Instant::now();
         let task_kind = TaskKind::from(&task);
 
@@ -254,16 +571,21 @@ impl TaskManager {
             }
             Err(e) => {
                 error!(task_id = id, error = e.as_ref() as &dyn Error, "Failed to get task name");
-                send_progress(TaskProgress {
+                self.send_progress(TaskProgress {
                     task_id: id,
                     task_kind,
                     task_name: None,
                     status: TaskStatus::Failed,
+                    priority: self.scheduler.priority(id).unwrap_or_default(),
                     total_progress: 0.0,
                     message: format!("Failed to initialize task: {e:#}"),
                     current_step: 1,
                     total_steps: 1,
                     step_progress: None,
+                    artifacts: Vec::new(),
+                    download_summary: None,
+                    eta_secs: None,
+                    transfer: None,
                 });
 
                 // Log task cleanup
@@ -273,13 +595,22 @@ impl TaskManager {
                     duration_ms = duration.as_millis(),
                     "Task failed during initialization"
                 );
-                return;
+                return TaskStatus::Failed;
             }
         };
         let total_steps = task.total_steps();
+        let artifacts = TaskArtifacts::default();
+        let app_size = self.task_app_size(&task).await;
+        let step_clock = std::sync::Mutex::new(StepClock::default());
+        let timeline = std::sync::Mutex::new(ProgressTimeline::default());
+        let coalescer = std::sync::Mutex::new(ProgressCoalescer::default());
 
         let task_name_clone = task_name.clone();
-        let update_progress = move |u: ProgressUpdate| {
+        let artifacts_ref = &artifacts;
+        let step_clock_ref = &step_clock;
+        let timeline_ref = &timeline;
+        let coalescer_ref = &coalescer;
+        let send_update = move |u: ProgressUpdate| {
             // debug!(
             //     task_id = id,
             //     status = ?status,
@@ -292,25 +623,74 @@ impl TaskManager {
             let completed_steps = u.step_number.saturating_sub(1) as f32;
             let sp = u.step_progress.unwrap_or(0.0).clamp(0.0, 1.0);
             let total_progress = (completed_steps + sp) / safe_total;
+            let (artifacts, download_summary) = match u.status {
+                TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled => {
+                    (artifacts_ref.snapshot(), artifacts_ref.download_summary())
+                }
+                TaskStatus::Waiting | TaskStatus::Running => (Vec::new(), None),
+            };
+            let eta_secs = {
+                let mut clock = step_clock_ref.lock().expect("step clock lock poisoned");
+                let now = std::time::Instant::now();
+                if let Some((step, duration)) = clock.advance(u.step_number, u.status, now) {
+                    self.step_timings.record(task_kind, step, duration, app_size);
+                }
+                clock
+                    .elapsed(now)
+                    .filter(|_| matches!(u.status, TaskStatus::Running))
+                    .and_then(|elapsed| {
+                        self.step_timings.estimate_remaining(
+                            task_kind,
+                            StepProgress {
+                                step: u.step_number,
+                                total_steps,
+                                elapsed,
+                                progress: u.step_progress,
+                            },
+                            app_size,
+                        )
+                    })
+                    .map(|eta| eta.as_secs())
+            };
+            timeline_ref.lock().expect("timeline lock poisoned").observe(
+                std::time::Instant::now(),
+                u.status,
+                u.step_number.into(),
+                total_progress,
+                &u.message,
+            );
 
-            send_progress(TaskProgress {
+            self.send_progress(TaskProgress {
                 task_id: id,
                 task_kind,
                 task_name: Some(task_name_clone.clone()),
                 status: u.status,
+                priority: self.scheduler.priority(id).unwrap_or_default(),
                 total_progress,
                 message: u.message,
                 current_step: u.step_number.into(),
                 total_steps: total_steps.into(),
                 step_progress: u.step_progress,
+                artifacts,
+                download_summary,
+                eta_secs,
+                transfer: u.transfer,
             });
         };
+        let update_progress = move |u: ProgressUpdate| {
+            let admitted = coalescer_ref
+                .lock()
+                .expect("progress coalescer lock poisoned")
+                .admit(std::time::Instant::now(), u);
+            admitted.for_each(&send_update);
+        };
 
         update_progress(ProgressUpdate {
             status: TaskStatus::Waiting,
             step_number: 1,
             step_progress: None,
             message: "Starting...".into(),
+            transfer: None,
         });
 
         Toast::send(
@@ -320,42 +700,99 @@ impl TaskManager {
             Some(Duration::from_secs(2)),
         );
 
+        let history_action = task.history_action();
+        let version_before = match history_action {
+            Some(HistoryAction::Uninstall | HistoryAction::Backup) => {
+                self.installed_version(task.package_name()).await
+            }
+            _ => None,
+        };
+
         let result = async {
+            let _kind_slot = if self.scheduler.is_limited(SlotClass::Kind(task_kind)) {
+                update_progress(ProgressUpdate {
+                    status: TaskStatus::Waiting,
+                    step_number: 1,
+                    step_progress: None,
+                    message: "Waiting for other tasks of this kind...".into(),
+                    transfer: None,
+                });
+                Some(acquire_permit_or_cancel!(
+                    self.scheduler,
+                    SlotClass::Kind(task_kind),
+                    token,
+                    "task kind"
+                ))
+            } else {
+                None
+            };
+
             match &task {
-                Task::Download(app, package) => {
+                Task::Download(app, package, files) => {
                     info!(task_id = id, "Executing download task");
                     self.handle_download(
                         app.clone(),
                         PackageName::parse(package.clone())?,
+                        files.clone(),
                         &update_progress,
+                        &artifacts,
                         token.clone(),
                     )
                     .await
                 }
-                Task::DownloadInstall(app, package) => {
+                Task::DownloadInstall(app, package, files) => {
                     info!(task_id = id, "Executing download and install task");
                     self.handle_download_install(
                         app.clone(),
                         PackageName::parse(package.clone())?,
+                        files.clone(),
                         &update_progress,
+                        &artifacts,
+                        token.clone(),
+                    )
+                    .await
+                }
+                Task::ResumeDownload(app) => {
+                    info!(task_id = id, "Executing resume download task");
+                    self.handle_resume_download(
+                        app.clone(),
+                        &update_progress,
+                        &artifacts,
                         token.clone(),
                     )
                     .await
                 }
                 Task::InstallApk(apk_path) => {
                     info!(task_id = id, "Executing APK install task");
-                    self.handle_install_apk(apk_path.clone(), &update_progress, token.clone()).await
+                    self.handle_install_apk(
+                        apk_path.clone(),
+                        &update_progress,
+                        &artifacts,
+                        token.clone(),
+                    )
+                    .await
                 }
                 Task::InstallLocalApp(app_path) => {
                     info!(task_id = id, "Executing local app install task");
-                    self.handle_install_local_app(app_path.clone(), &update_progress, token.clone())
-                        .await
+                    self.handle_install_local_app(
+                        app_path.clone(),
+                        &update_progress,
+                        &artifacts,
+                        token.clone(),
+                    )
+                    .await
                 }
-                Task::Uninstall { package_name, .. } => {
+                Task::Uninstall { package_name, display_name } => {
                     info!(task_id = id, "Executing uninstall task");
                     async {
                         let package = PackageName::parse(package_name)?;
-                        self.handle_uninstall(package, &update_progress, token.clone()).await
+                        self.handle_uninstall(
+                            package,
+                            display_name.clone(),
+                            &update_progress,
+                            token.clone(),
+                        )
+                        .await
                     }
                     .await
                 }
@@ -366,6 +803,7 @@ impl TaskManager {
                     backup_data,
                     backup_obb,
                     backup_name_append,
+                    compress,
                 } => {
                     info!(task_id = id, "Executing backup task");
                     self.handle_backup(
@@ -376,15 +814,104 @@ impl TaskManager {
                             backup_data: *backup_data,
                             backup_obb: *backup_obb,
                             backup_name_append: backup_name_append.clone(),
+                            compress: *compress,
+                        },
+                        &update_progress,
+                        &artifacts,
+                        token.clone(),
+                    )
+                    .await
+                }
+                Task::BackupAll { backup_apk, backup_data, backup_obb, compress } => {
+                    info!(task_id = id, "Executing backup all task");
+                    self.handle_backup_all(
+                        BackupOptions {
+                            name_append: None,
+                            backup_apk: *backup_apk,
+                            backup_data: *backup_data,
+                            backup_obb: *backup_obb,
+                            require_private_data: false,
+                            compress: *compress,
                         },
                         &update_progress,
+                        &artifacts,
                         token.clone(),
                     )
                     .await
                 }
-                Task::RestoreBackup(path) => {
+                Task::RestoreBackup { path, target_package } => {
                     info!(task_id = id, "Executing restore backup task");
-                    self.handle_restore(path.clone(), &update_progress, token.clone()).await
+                    async {
+                        let target_package =
+                            target_package.as_deref().map(PackageName::parse).transpose()?;
+                        self.handle_restore(
+                            path.clone(),
+                            target_package,
+                            &update_progress,
+                            &artifacts,
+                            token.clone(),
+                        )
+                        .await
+                    }
+                    .await
+                }
+                Task::ReinstallFromTrash { path, .. } => {
+                    info!(task_id = id, "Executing reinstall from trash task");
+                    self.handle_reinstall_from_trash(
+                        path.clone(),
+                        &update_progress,
+                        &artifacts,
+                        token.clone(),
+                    )
+                    .await
+                }
+                Task::ReceiveFromPeer { address, share_id, name } => {
+                    info!(task_id = id, "Executing receive from peer task");
+                    self.handle_receive_from_peer(
+                        address,
+                        share_id,
+                        name,
+                        &update_progress,
+                        &artifacts,
+                        token.clone(),
+                    )
+                    .await
+                }
+                Task::TransferAppData {
+                    package_name,
+                    display_name,
+                    target_serial,
+                    target_name,
+                } => {
+                    info!(task_id = id, "Executing app data transfer task");
+                    async {
+                        let package = PackageName::parse(package_name)?;
+                        self.handle_transfer_app_data(
+                            id,
+                            package,
+                            display_name.clone(),
+                            TransferTarget { serial: target_serial, name: target_name.as_deref() },
+                            &update_progress,
+                            &artifacts,
+                            token.clone(),
+                        )
+                        .await
+                    }
+                    .await
+                }
+                Task::PushObb { package_name, local_dir } => {
+                    info!(task_id = id, "Executing OBB push task");
+                    async {
+                        let package = PackageName::parse(package_name)?;
+                        self.handle_push_obb(
+                            package,
+                            local_dir.clone(),
+                            &update_progress,
+                            token.clone(),
+                        )
+                        .await
+                    }
+                    .await
                 }
                 Task::DonateApp { package_name, display_name } => {
                     info!(task_id = id, "Executing app donation task");
@@ -394,18 +921,87 @@ impl TaskManager {
                             package,
                             display_name.clone(),
                             &update_progress,
+                            &artifacts,
                             token.clone(),
                         )
                         .await
                     }
                     .await
                 }
+                Task::CaptureModSet { package_name, set_name, .. } => {
+                    info!(task_id = id, "Executing mod set capture task");
+                    self.handle_capture_mod_set(
+                        package_name.clone(),
+                        set_name.clone(),
+                        &update_progress,
+                        token.clone(),
+                    )
+                    .await
+                }
+                Task::ApplyModSet { package_name, set_name, .. } => {
+                    info!(task_id = id, "Executing mod set apply task");
+                    self.handle_apply_mod_set(
+                        package_name.clone(),
+                        set_name.clone(),
+                        &update_progress,
+                        token.clone(),
+                    )
+                    .await
+                }
             }
         }
         .await;
 
         let duration = start_time.elapsed();
 
+        if let Some(summary) = artifacts.download_summary() {
+            self.transfer_stats.record_download(&summary);
+        }
+        if history_action == Some(HistoryAction::Install) && result.is_ok() {
+            self.transfer_stats.record_install();
+        }
+
+        if let Some(action) = history_action {
+            // Install and restore tasks of local files only learn the package while running
+            let package_name =
+                task.package_name().map(str::to_string).or_else(|| artifacts.package_name());
+            let version = match action {
+                HistoryAction::Install | HistoryAction::Restore => {
+                    self.installed_version(package_name.as_deref()).await
+                }
+                _ => version_before,
+            };
+            let (outcome, error) = match &result {
+                Ok(_) => (HistoryOutcome::Succeeded, None),
+                Err(_) if token.is_cancelled() => (HistoryOutcome::Cancelled, None),
+                Err(e) => (HistoryOutcome::Failed, Some(format!("{e:#}"))),
+            };
+            let entry = HistoryEntry {
+                action,
+                name: task_name.clone(),
+                package_name,
+                version_code: version.as_ref().map(|(code, _)| *code),
+                version_name: version.map(|(_, name)| name),
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0),
+                duration_ms: duration.as_millis() as u64,
+                outcome,
+                error,
+                download_summary: artifacts.download_summary(),
+                timeline: timeline.lock().expect("timeline lock poisoned").snapshots(),
+                artifacts: artifacts.snapshot(),
+            };
+            if let Err(e) = self.install_history.record(entry) {
+                error!(
+                    task_id = id,
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to record install history"
+                );
+            }
+        }
+
         match result {
             Ok(_) => {
                 info!(
@@ -419,8 +1015,13 @@ impl TaskManager {
                     step_number: total_steps,
                     step_progress: Some(1.0),
                     message: "Done".into(),
+                    transfer: None,
                 });
+                if let Err(e) = self.step_timings.save() {
+                    warn!(error = e.as_ref() as &dyn Error, "Failed to save task step timings");
+                }
                 Toast::send(task_name, format!("{}: completed", task.kind_label()), false, None);
+                TaskStatus::Completed
             }
             Err(e) => {
                 // TODO: check error type?
@@ -436,6 +1037,7 @@ impl TaskManager {
                         step_number: total_steps,
                         step_progress: None,
                         message: "Cancelled".into(),
+                        transfer: None,
                     });
                     Toast::send(
                         task_name,
@@ -443,6 +1045,7 @@ impl TaskManager {
                         false,
                         None,
                     );
+                    TaskStatus::Cancelled
                 } else {
                     error!(
                         task_id = id,
@@ -457,6 +1060,7 @@ impl TaskManager {
                         step_number: total_steps,
                         step_progress: None,
                         message: format!("Task failed: {e:#}"),
+                        transfer: None,
                     });
                     Toast::send(
                         task_name,
@@ -464,10 +1068,108 @@ impl TaskManager {
                         true,
                         Some(Duration::from_secs(10)),
                     );
+                    TaskStatus::Failed
                 }
             }
         }
     }
+
+    fn send_progress(&self, progress: TaskProgress) {
+        // Log significant status changes (not every progress update to avoid spam)
+        match progress.status {
+            TaskStatus::Waiting
+            | TaskStatus::Completed
+            | TaskStatus::Failed
+            | TaskStatus::Cancelled => {
+                debug!(
+                    task_id = progress.task_id,
+                    task_kind = ?progress.task_kind,
+                    task_name = ?progress.task_name,
+                    status = ?progress.status,
+                    progress = progress.total_progress,
+                    progress_message = %progress.message,
+                    "Sending progress signal to Dart"
+                );
+            }
+            TaskStatus::Running => {
+                // if progress.total_progress == 0.0
+                //     || (0.25..0.26).contains(&progress.total_progress)
+                //     || (0.5..0.51).contains(&progress.total_progress)
+                //     || (0.75..0.76).contains(&progress.total_progress)
+                // {
+                //     debug!(
+                //         task_id = progress.task_id,
+                //         progress = progress.total_progress,
+                //         "Task progress milestone"
+                //     );
+                // }
+            }
+        }
+
+        self.latest_progress().insert(progress.task_id, progress.clone());
+        if self.progress_tx.receiver_count() > 0 {
+            let _ = self.progress_tx.send(progress.clone());
+        }
+        progress.send_signal_to_dart();
+    }
+
+    /// Sends the state of every unfinished task
+    fn send_task_list(&self, registry: &TaskRegistry) {
+        task_list_snapshot(&self.unfinished_tasks(registry)).send_signal_to_dart();
+    }
+
+    fn unfinished_tasks(&self, registry: &TaskRegistry) -> Vec<TaskProgress> {
+        registry.unfinished_progress(&self.latest_progress(), |id| {
+            self.scheduler.priority(id).unwrap_or_default()
+        })
+    }
+
+    fn latest_progress(&self) -> std::sync::MutexGuard<'_, HashMap<u64, TaskProgress>> {
+        self.latest_progress.lock().expect("latest progress lock poisoned")
+    }
+
+    /// Sends unfinished tasks again for a UI that started while they kept running.
+    ///
+    /// They are announced like tasks restored from a previous session, followed by a task list
+    /// snapshot and their latest progress. Tasks that have not reported any progress yet are sent
+    /// as waiting.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) async fn resync(&self) {
+        let tasks = self.unfinished_tasks(&*self.tasks.lock().await);
+        if !tasks.is_empty() {
+            info!(count = tasks.len(), "Replaying unfinished tasks to the UI");
+            TasksRestored {
+                tasks: tasks
+                    .iter()
+                    .map(|progress| RestoredTask {
+                        task_id: progress.task_id,
+                        task_kind: progress.task_kind,
+                        task_name: progress.task_name.clone(),
+                    })
+                    .collect(),
+            }
+            .send_signal_to_dart();
+        }
+        task_list_snapshot(&tasks).send_signal_to_dart();
+        for progress in tasks {
+            progress.send_signal_to_dart();
+        }
+    }
+
+    /// Returns the version code and name of `package` on the current device, if it is installed
+    async fn installed_version(&self, package: Option<&str>) -> Option<(u64, String)> {
+        let package = package?;
+        let device = self.adb_service.current_device().await.ok()?;
+        device
+            .installed_packages
+            .iter()
+            .find(|installed| installed.package_name() == package)
+            .map(|installed| (installed.version_code(), installed.version_name().to_string()))
+    }
+}
+
+fn task_list_snapshot(tasks: &[TaskProgress]) -> TaskListSnapshot {
+    TaskListSnapshot { tasks: tasks.iter().map(TaskSnapshot::from).collect() }
 }
 
 async fn wait_for_tasks(
@@ -491,53 +1193,18 @@ async fn wait_for_tasks(
     TaskShutdownResult { timed_out, remaining_tasks }
 }
 
-fn send_progress(progress: TaskProgress) {
-    // Log significant status changes (not every progress update to avoid spam)
-    match progress.status {
-        TaskStatus::Waiting
-        | TaskStatus::Completed
-        | TaskStatus::Failed
-        | TaskStatus::Cancelled => {
-            debug!(
-                task_id = progress.task_id,
-                task_kind = ?progress.task_kind,
-                task_name = ?progress.task_name,
-                status = ?progress.status,
-                progress = progress.total_progress,
-                progress_message = %progress.message,
-                "Sending progress signal to Dart"
-            );
-        }
-        TaskStatus::Running => {
-            // if progress.total_progress == 0.0
-            //     || (0.25..0.26).contains(&progress.total_progress)
-            //     || (0.5..0.51).contains(&progress.total_progress)
-            //     || (0.75..0.76).contains(&progress.total_progress)
-            // {
-            //     debug!(
-            //         task_id = progress.task_id,
-            //         progress = progress.total_progress,
-            //         "Task progress milestone"
-            //     );
-            // }
-        }
-    }
-
-    progress.send_signal_to_dart();
-}
-
 #[cfg(test)]
 mod tests {
-    use std::{sync::Arc, time::Duration};
+    use std::{collections::HashMap, sync::Arc, time::Duration};
 
     use tokio::sync::{Mutex, Notify};
     use tokio_util::sync::CancellationToken;
 
     use super::{TaskRegistry, wait_for_tasks};
-    use crate::models::signals::task::Task;
+    use crate::models::signals::task::{Task, TaskKind, TaskPriority, TaskProgress, TaskStatus};
 
     fn task(name: &str) -> Task {
-        Task::Download(name.to_string(), "com.example.app".to_string())
+        Task::Download(name.to_string(), "com.example.app".to_string(), None)
     }
 
     #[test]
@@ -553,6 +1220,74 @@ mod tests {
         assert!(second.is_cancelled());
     }
 
+    #[test]
+    fn persisted_tasks_keep_pending_and_restorable_tasks_in_order() {
+        let mut registry =
+            TaskRegistry { pending_restore: vec![task("Pending")], ..TaskRegistry::default() };
+        registry.insert(2, task("Second"), CancellationToken::new());
+        registry.insert(1, task("First"), CancellationToken::new());
+        registry.insert(3, Task::InstallApk("/tmp/app.apk".into()), CancellationToken::new());
+
+        let names: Vec<_> =
+            registry.persisted_tasks().into_iter().map(|t| t.task_name().unwrap()).collect();
+        assert_eq!(names, ["Pending", "First", "Second"]);
+    }
+
+    #[test]
+    fn only_tasks_interrupted_by_shutdown_stay_persisted() {
+        let mut registry = TaskRegistry::default();
+        registry.insert(1, task("Completed"), CancellationToken::new());
+        registry.insert(2, task("Interrupted"), CancellationToken::new());
+        registry.insert(3, task("Failed"), CancellationToken::new());
+        registry.insert(4, task("Cancelled"), CancellationToken::new());
+
+        assert!(registry.finish(4, TaskStatus::Cancelled));
+        registry.start_shutdown();
+        assert!(!registry.finish(2, TaskStatus::Cancelled));
+        assert!(registry.finish(1, TaskStatus::Completed));
+        assert!(registry.finish(3, TaskStatus::Failed));
+
+        let names: Vec<_> =
+            registry.persisted_tasks().into_iter().map(|t| t.task_name().unwrap()).collect();
+        assert_eq!(names, ["Interrupted"]);
+    }
+
+    #[test]
+    fn unfinished_progress_replays_latest_state_of_queued_tasks() {
+        let mut registry = TaskRegistry::default();
+        registry.insert(3, task("Accepted"), CancellationToken::new());
+        registry.insert(1, task("Running"), CancellationToken::new());
+        registry.insert(2, task("Finished"), CancellationToken::new());
+        let progress = |id, status| TaskProgress {
+            task_id: id,
+            task_kind: TaskKind::Download,
+            task_name: None,
+            status,
+            priority: TaskPriority::Normal,
+            total_progress: 0.5,
+            message: String::new(),
+            current_step: 1,
+            total_steps: 2,
+            step_progress: Some(0.5),
+            artifacts: Vec::new(),
+            download_summary: None,
+            eta_secs: None,
+            transfer: None,
+        };
+        let latest = HashMap::from([
+            (1, progress(1, TaskStatus::Running)),
+            (2, progress(2, TaskStatus::Completed)),
+        ]);
+
+        let replayed = registry.unfinished_progress(&latest, |_| TaskPriority::High);
+        assert_eq!(replayed.len(), 2);
+        assert_eq!((replayed[0].task_id, replayed[0].total_progress), (1, 0.5));
+        assert_eq!(replayed[1].task_id, 3);
+        assert!(matches!(replayed[1].status, TaskStatus::Waiting));
+        assert!(matches!(replayed[1].priority, TaskPriority::High));
+        assert_eq!(replayed[1].task_name.as_deref(), Some("Accepted"));
+    }
+
     #[test]
     fn shutdown_rejects_new_tasks() {
         let mut registry = TaskRegistry::default();
@@ -1,15 +1,27 @@
-use crate::models::signals::task::TaskStatus;
+use std::path::Path;
 
+use crate::models::signals::task::{TaskStatus, TransferProgress};
+
+mod artifacts;
 mod backup;
+mod coalescer;
 mod donate;
 mod download;
+mod eta;
 mod install;
+mod lan_receive;
 mod manager;
+mod mods;
+mod queue_store;
+mod scheduler;
+mod timeline;
+mod transfer;
+mod update_all;
 pub(crate) use donate::DONATE_TMP_DIR;
-pub(crate) use manager::TaskManager;
+pub(crate) use manager::{TaskManager, TaskShutdownResult};
 
 macro_rules! acquire_permit_or_cancel {
-    ($semaphore:expr, $token:expr, $semaphore_name:literal) => {{
+    ($scheduler:expr, $class:expr, $token:expr, $semaphore_name:literal) => {{
         if $token.is_cancelled() {
             info!(concat!("Task already cancelled before ", $semaphore_name, " semaphore acquisition"));
             return Err(anyhow::anyhow!(concat!("Task cancelled before ", $semaphore_name)));
@@ -17,7 +29,7 @@ macro_rules! acquire_permit_or_cancel {
 
         debug!(concat!("Waiting for ", $semaphore_name, " semaphore"));
         tokio::select! {
-            permit = $semaphore.acquire() => permit,
+            permit = $scheduler.acquire($class) => permit,
             _ = $token.cancelled() => {
                 info!(concat!("Task cancelled while waiting for ", $semaphore_name, " semaphore"));
                 return Err(anyhow::anyhow!(concat!("Task cancelled while waiting for ", $semaphore_name, " semaphore")));
@@ -33,12 +45,15 @@ struct ProgressUpdate {
     step_number: u8,
     step_progress: Option<f32>,
     message: String,
+    transfer: Option<TransferProgress>,
 }
 
 #[derive(Debug)]
 struct InstallStepConfig<'a> {
     step_number: u8,
     log_context: &'a str,
+    /// File or folder pushed to the device, counted in the transfer statistics
+    pushed_from: &'a Path,
 }
 
 #[derive(Debug)]
@@ -57,4 +72,5 @@ struct BackupStepConfig {
     backup_data: bool,
     backup_obb: bool,
     backup_name_append: Option<String>,
+    compress: bool,
 }
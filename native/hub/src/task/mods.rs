@@ -0,0 +1,135 @@
+use anyhow::{Result, bail, ensure};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use super::{AdbStepConfig, ProgressUpdate, TaskManager};
+use crate::{
+    adb::PackageName,
+    models::signals::task::TaskStatus,
+    mods_library::{ModProfileFile, VANILLA_SET},
+};
+
+impl TaskManager {
+    async fn load_mod_profile(&self, package: &PackageName) -> Result<ModProfileFile> {
+        let profile = self.mods_library.load_profile(package).await?;
+        ensure!(!profile.paths.is_empty(), "No mod paths configured for {package}");
+        Ok(profile)
+    }
+
+    #[instrument(skip(self, update_progress, token))]
+    pub(super) async fn handle_capture_mod_set(
+        &self,
+        package_name: String,
+        set_name: String,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: CancellationToken,
+    ) -> Result<()> {
+        let package = PackageName::parse(&package_name)?;
+        let profile = self.load_mod_profile(&package).await?;
+        let set_dir = self.mods_library.set_dir(&package, &set_name)?;
+
+        let adb_service = self.adb_service.clone();
+        let device = adb_service.current_device().await?;
+        let result = self
+            .run_adb_one_step(
+                AdbStepConfig {
+                    step_number: 1,
+                    waiting_msg: "Waiting to capture mod set...",
+                    running_msg: format!("Capturing mod set \"{set_name}\"..."),
+                    log_context: "mod set capture",
+                },
+                update_progress,
+                token,
+                || async { adb_service.capture_mod_set(&device, &profile.paths, &set_dir).await },
+            )
+            .await;
+
+        self.mods_library.send_profiles(None).await;
+        result
+    }
+
+    /// Applies a mod set, or restores the vanilla snapshot when `set_name` is `None`.
+    ///
+    /// The vanilla state is captured first if no snapshot exists yet and no mod set is applied.
+    #[instrument(skip(self, update_progress, token))]
+    pub(super) async fn handle_apply_mod_set(
+        &self,
+        package_name: String,
+        set_name: Option<String>,
+        update_progress: &impl Fn(ProgressUpdate),
+        token: CancellationToken,
+    ) -> Result<()> {
+        let package = PackageName::parse(&package_name)?;
+        let profile = self.load_mod_profile(&package).await?;
+        let set_name = set_name.unwrap_or_else(|| VANILLA_SET.to_string());
+        let set_dir = self.mods_library.set_dir(&package, &set_name)?;
+        let vanilla_dir = self.mods_library.set_dir(&package, VANILLA_SET)?;
+
+        let adb_service = self.adb_service.clone();
+        let device = adb_service.current_device().await?;
+
+        if vanilla_dir.is_dir() {
+            debug!("Vanilla snapshot already exists");
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Running,
+                step_number: 1,
+                step_progress: Some(1.0),
+                message: "Vanilla snapshot already exists".into(),
+                transfer: None,
+            });
+        } else if let Some(active_set) = &profile.active_set {
+            if set_name == VANILLA_SET {
+                bail!("No vanilla snapshot exists to restore");
+            }
+            warn!(active_set, "No vanilla snapshot and a mod set is applied, skipping snapshot");
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Running,
+                step_number: 1,
+                step_progress: Some(1.0),
+                message: "Skipped vanilla snapshot (mods already applied)".into(),
+                transfer: None,
+            });
+        } else {
+            info!("Capturing vanilla state before applying mods");
+            self.run_adb_one_step(
+                AdbStepConfig {
+                    step_number: 1,
+                    waiting_msg: "Waiting to snapshot vanilla state...",
+                    running_msg: "Snapshotting vanilla state...".into(),
+                    log_context: "vanilla snapshot",
+                },
+                update_progress,
+                token.clone(),
+                || async {
+                    adb_service.capture_mod_set(&device, &profile.paths, &vanilla_dir).await
+                },
+            )
+            .await?;
+        }
+
+        ensure!(set_dir.is_dir(), "Mod set not found: {set_name}");
+        let result = self
+            .run_adb_one_step(
+                AdbStepConfig {
+                    step_number: 2,
+                    waiting_msg: "Waiting to apply mod set...",
+                    running_msg: if set_name == VANILLA_SET {
+                        "Restoring vanilla state...".into()
+                    } else {
+                        format!("Applying mod set \"{set_name}\"...")
+                    },
+                    log_context: "mod set apply",
+                },
+                update_progress,
+                token,
+                || async {
+                    adb_service.apply_mod_set(&device, &profile.paths, &set_dir).await?;
+                    self.mods_library.set_active_set(&package, Some(&set_name)).await
+                },
+            )
+            .await;
+
+        self.mods_library.send_profiles(None).await;
+        result
+    }
+}
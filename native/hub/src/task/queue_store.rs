@@ -0,0 +1,115 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::models::signals::task::Task;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedQueue {
+    #[serde(default)]
+    tasks: Vec<Task>,
+}
+
+/// Persists unfinished restorable tasks so they can be re-queued after an app restart.
+#[derive(Debug)]
+pub(super) struct TaskQueueStore {
+    path: PathBuf,
+}
+
+impl TaskQueueStore {
+    pub(super) fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Loads persisted tasks. A missing or unreadable queue file yields no tasks.
+    pub(super) fn load(&self) -> Vec<Task> {
+        if !self.path.exists() {
+            return Vec::new();
+        }
+        match Self::read_queue(&self.path) {
+            Ok(queue) => {
+                let tasks: Vec<Task> =
+                    queue.tasks.into_iter().filter(Task::is_restorable).collect();
+                debug!(count = tasks.len(), path = %self.path.display(), "Loaded persisted task queue");
+                tasks
+            }
+            Err(e) => {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to load persisted task queue");
+                Vec::new()
+            }
+        }
+    }
+
+    fn read_queue(path: &Path) -> Result<PersistedQueue> {
+        let content = fs::read_to_string(path).context("Failed to read task queue file")?;
+        serde_json::from_str(&content).context("Failed to parse task queue file")
+    }
+
+    /// Replaces the persisted queue with `tasks`, removing the file when there is nothing to keep.
+    pub(super) fn save<'a>(&self, tasks: impl IntoIterator<Item = &'a Task>) -> Result<()> {
+        let queue = PersistedQueue { tasks: tasks.into_iter().cloned().collect() };
+        if queue.tasks.is_empty() {
+            if self.path.exists() {
+                fs::remove_file(&self.path).context("Failed to remove task queue file")?;
+            }
+            return Ok(());
+        }
+
+        let json =
+            serde_json::to_string_pretty(&queue).context("Failed to serialize task queue")?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).context("Failed to write task queue file")?;
+        fs::rename(&tmp_path, &self.path).context("Failed to replace task queue file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load_keeps_only_restorable_tasks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TaskQueueStore::new(dir.path().join("task_queue.json"));
+        let tasks = [
+            Task::Download("Game v1+1".into(), "com.example.game".into(), None),
+            Task::InstallApk("/tmp/app.apk".into()),
+            Task::DownloadInstall(
+                "Other v2+2".into(),
+                "com.example.other".into(),
+                Some(vec!["other.apk".into()]),
+            ),
+        ];
+
+        store.save(&tasks).unwrap();
+        let loaded = store.load();
+
+        assert_eq!(loaded.len(), 2);
+        assert!(matches!(&loaded[0], Task::Download(name, _, None) if name == "Game v1+1"));
+        assert!(matches!(
+            &loaded[1],
+            Task::DownloadInstall(name, _, Some(files)) if name == "Other v2+2" && files.len() == 1
+        ));
+
+        store.save([]).unwrap();
+        assert!(!dir.path().join("task_queue.json").exists());
+        assert!(store.load().is_empty());
+    }
+
+    #[test]
+    fn loads_downloads_queued_without_file_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("task_queue.json");
+        fs::write(&path, r#"{"tasks":[{"Download":["Game v1+1","com.example.game"]}]}"#).unwrap();
+
+        let loaded = TaskQueueStore::new(path).load();
+
+        assert!(matches!(&loaded[..], [Task::Download(name, _, None)] if name == "Game v1+1"));
+    }
+}
@@ -0,0 +1,389 @@
+use std::{cmp::Reverse, collections::HashMap, sync::Mutex};
+
+use tokio::sync::Notify;
+use tracing::debug;
+
+use crate::models::{
+    Settings,
+    signals::task::{TaskKind, TaskPriority},
+};
+
+tokio::task_local! {
+    /// Id of the task the current future runs for, used to look up its priority
+    static CURRENT_TASK: u64;
+}
+
+/// Something a task has to hold a slot of while it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum SlotClass {
+    /// Any operation talking to the device
+    Adb,
+    /// Any transfer from the downloader
+    Download,
+    /// A whole task of this kind
+    Kind(TaskKind),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ConcurrencyLimits {
+    adb: usize,
+    download: usize,
+    /// Kinds without an entry are unlimited
+    kinds: HashMap<TaskKind, usize>,
+}
+
+impl ConcurrencyLimits {
+    pub(super) fn from_settings(settings: &Settings) -> Self {
+        Self {
+            adb: settings.max_concurrent_adb_operations.max(1) as usize,
+            download: settings.max_concurrent_downloads.max(1) as usize,
+            kinds: settings
+                .task_kind_limits
+                .iter()
+                .map(|limit| (limit.kind, limit.max_active.max(1) as usize))
+                .collect(),
+        }
+    }
+
+    /// Returns `None` if `class` is unlimited
+    fn limit(&self, class: SlotClass) -> Option<usize> {
+        match class {
+            SlotClass::Adb => Some(self.adb),
+            SlotClass::Download => Some(self.download),
+            SlotClass::Kind(kind) => self.kinds.get(&kind).copied(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Waiter {
+    ticket: u64,
+    task: Option<u64>,
+}
+
+#[derive(Debug)]
+struct SchedulerState {
+    limits: ConcurrencyLimits,
+    active: HashMap<SlotClass, usize>,
+    /// Waiters per class in arrival order
+    queues: HashMap<SlotClass, Vec<Waiter>>,
+    /// Priorities of tasks known to the scheduler, others count as normal
+    priorities: HashMap<u64, TaskPriority>,
+    next_ticket: u64,
+}
+
+impl SchedulerState {
+    fn priority(&self, task: Option<u64>) -> TaskPriority {
+        task.and_then(|task| self.priorities.get(&task).copied()).unwrap_or_default()
+    }
+
+    /// Position of the waiter that is served next: highest priority, then earliest arrival
+    fn next_waiter(&self, queue: &[Waiter]) -> Option<usize> {
+        queue
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, waiter)| (self.priority(waiter.task), Reverse(waiter.ticket)))
+            .map(|(index, _)| index)
+    }
+
+    fn try_grant(&mut self, class: SlotClass, ticket: u64) -> bool {
+        let Some(queue) = self.queues.get(&class) else {
+            return false;
+        };
+        let Some(index) = self.next_waiter(queue).filter(|index| queue[*index].ticket == ticket)
+        else {
+            return false;
+        };
+        let active = self.active.entry(class).or_default();
+        if self.limits.limit(class).is_some_and(|limit| *active >= limit) {
+            return false;
+        }
+        *active += 1;
+        self.queues.entry(class).or_default().remove(index);
+        true
+    }
+}
+
+/// Limits how many task steps run at once, per device/downloader resource and per task kind.
+///
+/// Waiters for a class form a priority queue: slots go to the task with the highest priority,
+/// in request order among equal priorities. Limits and priorities can be changed at any time.
+#[derive(Debug)]
+pub(super) struct TaskScheduler {
+    state: Mutex<SchedulerState>,
+    /// Signalled whenever a slot might have become available
+    changed: Notify,
+}
+
+impl TaskScheduler {
+    pub(super) fn new(limits: ConcurrencyLimits) -> Self {
+        Self {
+            state: Mutex::new(SchedulerState {
+                limits,
+                active: HashMap::new(),
+                queues: HashMap::new(),
+                priorities: HashMap::new(),
+                next_ticket: 0,
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    pub(super) fn set_limits(&self, limits: ConcurrencyLimits) {
+        let mut state = self.lock();
+        if state.limits != limits {
+            debug!(?limits, "Task concurrency limits changed");
+            state.limits = limits;
+            drop(state);
+            self.changed.notify_waiters();
+        }
+    }
+
+    /// Runs `future` on behalf of task `task_id`, so the slots it waits for follow the task's
+    /// priority. The priority is forgotten once the future completes.
+    pub(super) async fn run_task<F: Future>(
+        &self,
+        task_id: u64,
+        priority: TaskPriority,
+        future: F,
+    ) -> F::Output {
+        self.lock().priorities.insert(task_id, priority);
+        let _forget = ForgetTask { scheduler: self, task_id };
+        CURRENT_TASK.scope(task_id, future).await
+    }
+
+    /// Returns the priority of a running task, `None` if the scheduler does not know it
+    pub(super) fn priority(&self, task_id: u64) -> Option<TaskPriority> {
+        self.lock().priorities.get(&task_id).copied()
+    }
+
+    /// Changes the priority of a running task, reordering the queues it waits in.
+    ///
+    /// Returns `false` if the task is unknown.
+    pub(super) fn set_priority(&self, task_id: u64, priority: TaskPriority) -> bool {
+        let mut state = self.lock();
+        let Some(current) = state.priorities.get_mut(&task_id) else {
+            return false;
+        };
+        if *current != priority {
+            debug!(task_id, ?priority, "Task priority changed");
+            *current = priority;
+            drop(state);
+            self.changed.notify_waiters();
+        }
+        true
+    }
+
+    /// Returns whether `class` has a limit at all
+    pub(super) fn is_limited(&self, class: SlotClass) -> bool {
+        self.lock().limits.limit(class).is_some()
+    }
+
+    /// Returns how many more slots of `class` can be taken right now, `usize::MAX` if unlimited
+    pub(super) fn available(&self, class: SlotClass) -> usize {
+        let state = self.lock();
+        let active = state.active.get(&class).copied().unwrap_or_default();
+        state.limits.limit(class).map_or(usize::MAX, |limit| limit.saturating_sub(active))
+    }
+
+    /// Waits for a slot of `class`. Dropping the future gives up the place in the queue.
+    pub(super) async fn acquire(&self, class: SlotClass) -> Slot<'_> {
+        let task = CURRENT_TASK.try_with(|task| *task).ok();
+        let mut ticket = {
+            let mut state = self.lock();
+            let id = state.next_ticket;
+            state.next_ticket += 1;
+            state.queues.entry(class).or_default().push(Waiter { ticket: id, task });
+            Ticket { scheduler: self, class, id, granted: false }
+        };
+
+        loop {
+            let notified = self.changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if self.lock().try_grant(class, ticket.id) {
+                ticket.granted = true;
+                // The next waiter may fit as well
+                self.changed.notify_waiters();
+                return Slot { scheduler: self, class };
+            }
+            notified.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SchedulerState> {
+        self.state.lock().expect("task scheduler lock poisoned")
+    }
+}
+
+/// Place in the queue of a class, removed if the waiter goes away
+struct Ticket<'a> {
+    scheduler: &'a TaskScheduler,
+    class: SlotClass,
+    id: u64,
+    granted: bool,
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+        if let Some(queue) = self.scheduler.lock().queues.get_mut(&self.class) {
+            queue.retain(|waiter| waiter.ticket != self.id);
+        }
+        self.scheduler.changed.notify_waiters();
+    }
+}
+
+/// Forgets the priority of a task once it stops running
+struct ForgetTask<'a> {
+    scheduler: &'a TaskScheduler,
+    task_id: u64,
+}
+
+impl Drop for ForgetTask<'_> {
+    fn drop(&mut self) {
+        self.scheduler.lock().priorities.remove(&self.task_id);
+    }
+}
+
+/// A held slot, released on drop
+#[derive(Debug)]
+pub(super) struct Slot<'a> {
+    scheduler: &'a TaskScheduler,
+    class: SlotClass,
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        if let Some(active) = self.scheduler.lock().active.get_mut(&self.class) {
+            *active = active.saturating_sub(1);
+        }
+        self.scheduler.changed.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+
+    fn limits(adb: usize, download: usize, kinds: &[(TaskKind, usize)]) -> ConcurrencyLimits {
+        ConcurrencyLimits { adb, download, kinds: kinds.iter().copied().collect() }
+    }
+
+    async fn acquired(future: impl Future) -> bool {
+        timeout(Duration::from_millis(50), future).await.is_ok()
+    }
+
+    #[tokio::test]
+    async fn classes_are_limited_independently() {
+        let scheduler = TaskScheduler::new(limits(1, 2, &[]));
+
+        let _adb = scheduler.acquire(SlotClass::Adb).await;
+        assert!(!acquired(scheduler.acquire(SlotClass::Adb)).await);
+
+        let _first = scheduler.acquire(SlotClass::Download).await;
+        let _second = scheduler.acquire(SlotClass::Download).await;
+        assert_eq!(scheduler.available(SlotClass::Download), 0);
+        assert!(!acquired(scheduler.acquire(SlotClass::Download)).await);
+
+        let kind = SlotClass::Kind(TaskKind::BackupApp);
+        assert!(!scheduler.is_limited(kind));
+        let _kinds = [scheduler.acquire(kind).await, scheduler.acquire(kind).await];
+        assert_eq!(scheduler.available(kind), usize::MAX);
+    }
+
+    #[tokio::test]
+    async fn released_slot_goes_to_first_waiter() {
+        let scheduler = TaskScheduler::new(limits(1, 1, &[]));
+        let held = scheduler.acquire(SlotClass::Adb).await;
+
+        let mut first = Box::pin(scheduler.acquire(SlotClass::Adb));
+        let mut second = Box::pin(scheduler.acquire(SlotClass::Adb));
+        assert!(!acquired(&mut first).await);
+        assert!(!acquired(&mut second).await);
+
+        drop(held);
+        assert!(!acquired(&mut second).await);
+        let first = first.await;
+        drop(first);
+        assert!(acquired(second).await);
+    }
+
+    #[tokio::test]
+    async fn abandoned_waiter_leaves_queue() {
+        let scheduler = TaskScheduler::new(limits(1, 1, &[]));
+        let held = scheduler.acquire(SlotClass::Adb).await;
+
+        assert!(!acquired(scheduler.acquire(SlotClass::Adb)).await);
+        let mut next = Box::pin(scheduler.acquire(SlotClass::Adb));
+        assert!(!acquired(&mut next).await);
+
+        drop(held);
+        assert!(acquired(next).await);
+    }
+
+    #[tokio::test]
+    async fn higher_priority_task_goes_first() {
+        let scheduler = TaskScheduler::new(limits(1, 1, &[]));
+        let held = scheduler.acquire(SlotClass::Download).await;
+
+        let mut big = Box::pin(scheduler.run_task(1, TaskPriority::Normal, async {
+            scheduler.acquire(SlotClass::Download).await;
+        }));
+        let mut small = Box::pin(scheduler.run_task(2, TaskPriority::High, async {
+            scheduler.acquire(SlotClass::Download).await;
+        }));
+        assert!(!acquired(&mut big).await);
+        assert!(!acquired(&mut small).await);
+
+        drop(held);
+        assert!(!acquired(&mut big).await);
+        assert!(acquired(small).await);
+        assert!(acquired(big).await);
+        assert_eq!(scheduler.priority(1), None);
+    }
+
+    #[tokio::test]
+    async fn reprioritized_task_moves_ahead() {
+        let scheduler = TaskScheduler::new(limits(1, 1, &[]));
+        let held = scheduler.acquire(SlotClass::Adb).await;
+
+        let mut first = Box::pin(scheduler.run_task(1, TaskPriority::Normal, async {
+            scheduler.acquire(SlotClass::Adb).await;
+        }));
+        let mut second = Box::pin(scheduler.run_task(2, TaskPriority::Normal, async {
+            scheduler.acquire(SlotClass::Adb).await;
+        }));
+        assert!(!acquired(&mut first).await);
+        assert!(!acquired(&mut second).await);
+
+        assert!(scheduler.set_priority(2, TaskPriority::High));
+        assert!(!scheduler.set_priority(3, TaskPriority::High));
+        assert_eq!(scheduler.priority(2), Some(TaskPriority::High));
+
+        drop(held);
+        assert!(!acquired(&mut first).await);
+        assert!(acquired(second).await);
+        assert!(acquired(first).await);
+    }
+
+    #[tokio::test]
+    async fn raising_limit_wakes_waiters() {
+        let kind = SlotClass::Kind(TaskKind::Download);
+        let scheduler = TaskScheduler::new(limits(1, 1, &[(TaskKind::Download, 1)]));
+        let _held = scheduler.acquire(kind).await;
+
+        let mut waiting = Box::pin(scheduler.acquire(kind));
+        assert!(!acquired(&mut waiting).await);
+
+        scheduler.set_limits(limits(1, 1, &[(TaskKind::Download, 2)]));
+        assert!(acquired(waiting).await);
+    }
+}
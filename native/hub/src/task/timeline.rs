@@ -0,0 +1,126 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::models::signals::{history::ProgressSnapshot, task::TaskStatus};
+
+/// Snapshots kept per task. Periodic ones are thinned out past this, status and step changes
+/// are always kept.
+const MAX_SNAPSHOTS: usize = 64;
+/// Time between periodic snapshots until the timeline fills up for the first time
+const START_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Low-rate record of a task's progress, kept in its history entry to show where time went
+#[derive(Debug)]
+pub(super) struct ProgressTimeline {
+    /// Snapshots with whether each marks a status or step change
+    snapshots: Vec<(bool, ProgressSnapshot)>,
+    interval: Duration,
+    last_at: Option<Instant>,
+    last_phase: Option<(TaskStatus, u32)>,
+}
+
+impl Default for ProgressTimeline {
+    fn default() -> Self {
+        Self { snapshots: Vec::new(), interval: START_INTERVAL, last_at: None, last_phase: None }
+    }
+}
+
+impl ProgressTimeline {
+    /// Records a snapshot if the phase changed or the snapshot interval passed
+    pub(super) fn observe(
+        &mut self,
+        now: Instant,
+        status: TaskStatus,
+        step: u32,
+        progress: f32,
+        message: &str,
+    ) {
+        let phase = (status, step);
+        let phase_changed = self.last_phase != Some(phase);
+        let due = self.last_at.is_none_or(|last| now.duration_since(last) >= self.interval);
+        if !phase_changed && !due {
+            return;
+        }
+        self.last_phase = Some(phase);
+        self.last_at = Some(now);
+        let snapshot = ProgressSnapshot {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            status,
+            step,
+            percent: (progress.clamp(0.0, 1.0) * 100.0).round() as u8,
+            message: message.to_string(),
+        };
+        self.snapshots.push((phase_changed, snapshot));
+        if self.snapshots.len() > MAX_SNAPSHOTS {
+            self.thin_out();
+        }
+    }
+
+    pub(super) fn snapshots(&self) -> Vec<ProgressSnapshot> {
+        self.snapshots.iter().map(|(_, snapshot)| snapshot.clone()).collect()
+    }
+
+    /// Drops every other periodic snapshot and halves the rate of new ones
+    fn thin_out(&mut self) {
+        let mut periodic = 0usize;
+        self.snapshots.retain(|(phase_change, _)| {
+            if *phase_change {
+                return true;
+            }
+            periodic += 1;
+            periodic.is_multiple_of(2)
+        });
+        self.interval *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observe(
+        timeline: &mut ProgressTimeline,
+        at: Instant,
+        status: TaskStatus,
+        step: u32,
+        percent: u8,
+    ) {
+        timeline.observe(at, status, step, f32::from(percent) / 100.0, "");
+    }
+
+    #[test]
+    fn records_phase_changes_and_periodic_progress() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut timeline = ProgressTimeline::default();
+
+        observe(&mut timeline, at(0), TaskStatus::Waiting, 1, 0);
+        observe(&mut timeline, at(1), TaskStatus::Running, 1, 1);
+        observe(&mut timeline, at(2), TaskStatus::Running, 1, 5);
+        observe(&mut timeline, at(6), TaskStatus::Running, 1, 20);
+        observe(&mut timeline, at(7), TaskStatus::Running, 2, 50);
+
+        let percents: Vec<u8> = timeline.snapshots().iter().map(|s| s.percent).collect();
+        assert_eq!(percents, [0, 1, 20, 50]);
+    }
+
+    #[test]
+    fn long_tasks_are_thinned_but_keep_phase_changes() {
+        let start = Instant::now();
+        let mut timeline = ProgressTimeline::default();
+        observe(&mut timeline, start, TaskStatus::Running, 1, 0);
+        for secs in 1..2000u64 {
+            let step = if secs < 1000 { 1 } else { 2 };
+            let at = start + Duration::from_secs(secs);
+            observe(&mut timeline, at, TaskStatus::Running, step, (secs / 20) as u8);
+        }
+
+        let snapshots = timeline.snapshots();
+        assert!(snapshots.len() <= MAX_SNAPSHOTS);
+        assert_eq!(snapshots[0].percent, 0);
+        assert!(snapshots.iter().any(|s| s.step == 2 && s.percent == 50));
+        assert!(snapshots.last().unwrap().percent >= 90);
+    }
+}
@@ -0,0 +1,331 @@
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result, bail, ensure};
+use rinf::RustSignal;
+use tokio::{sync::oneshot, time::timeout};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use super::{AdbStepConfig, ProgressUpdate, TaskManager, artifacts::TaskArtifacts};
+use crate::{
+    adb::{PackageName, device::BackupOptions},
+    models::signals::{
+        backups::BackupsChanged,
+        system::Toast,
+        task::{TaskArtifactKind, TaskStatus, TransferOverwriteReview},
+    },
+};
+
+/// How long the user has to confirm replacing data on the target before the transfer is stopped
+const OVERWRITE_REVIEW_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Device an app is transferred to
+#[derive(Debug)]
+pub(super) struct TransferTarget<'a> {
+    /// True or ADB serial
+    pub serial: &'a str,
+    pub name: Option<&'a str>,
+}
+
+/// How a transferred backup is restored, given what the target already has installed
+#[derive(Debug, PartialEq, Eq)]
+struct RestorePlan {
+    /// Install the APK from the backup, otherwise keep the installed app and restore data only
+    install_apk: bool,
+    /// The app is installed on the target, so its data there is replaced
+    replaces_data: bool,
+    warnings: Vec<String>,
+}
+
+fn restore_plan(source_version: u64, target_version: Option<u64>) -> RestorePlan {
+    let Some(target_version) = target_version else {
+        return RestorePlan { install_apk: true, replaces_data: false, warnings: Vec::new() };
+    };
+    let mut warnings = Vec::new();
+    if target_version > source_version {
+        warnings.push(format!(
+            "The target headset has a newer version ({target_version}) than the source \
+             ({source_version}), the data may not be compatible"
+        ));
+    }
+    RestorePlan { install_apk: target_version < source_version, replaces_data: true, warnings }
+}
+
+impl TaskManager {
+    /// Backs up an app with its data and OBB files from the current device and restores it to
+    /// another device, waiting for that device to become the current one.
+    ///
+    /// If the app is installed on the target, the user is asked before its data there is
+    /// replaced, and that data is backed up first so the transfer can be undone.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(self, update_progress, artifacts, token))]
+    pub(super) async fn handle_transfer_app_data(
+        &self,
+        task_id: u64,
+        package: PackageName,
+        display_name: Option<String>,
+        target: TransferTarget<'_>,
+        update_progress: &impl Fn(ProgressUpdate),
+        artifacts: &TaskArtifacts,
+        token: CancellationToken,
+    ) -> Result<()> {
+        let TransferTarget { serial: target_serial, name: target_name } = target;
+        let adb_service = self.adb_service.clone();
+        let source = adb_service.current_device().await?;
+        ensure!(
+            source.true_serial != target_serial && source.serial != target_serial,
+            "The target headset is the one the app is transferred from"
+        );
+        let source_version = source
+            .installed_packages
+            .iter()
+            .find(|installed| installed.package_name() == package.as_str())
+            .map(|installed| installed.version_code())
+            .with_context(|| format!("{package} is not installed"))?;
+        let name = display_name.clone().unwrap_or_else(|| package.to_string());
+        let target_label = target_name.unwrap_or(target_serial);
+
+        let options = BackupOptions {
+            name_append: Some("transfer".to_string()),
+            backup_apk: true,
+            backup_data: true,
+            backup_obb: true,
+            require_private_data: true,
+            compress: false,
+        };
+        let backups_path = self.settings.read().await.backups_location();
+        let backup_path: PathBuf = self
+            .run_adb_one_step(
+                AdbStepConfig {
+                    step_number: 1,
+                    waiting_msg: "Waiting to start backup...",
+                    running_msg: "Backing up app data...".to_string(),
+                    log_context: "transfer backup",
+                },
+                update_progress,
+                token.clone(),
+                || {
+                    let adb_service = adb_service.clone();
+                    let (package, display_name) = (package.clone(), display_name.clone());
+                    let token = token.clone();
+                    async move {
+                        adb_service
+                            .backup_app(
+                                &source,
+                                &package,
+                                display_name.as_deref(),
+                                &backups_path,
+                                &options,
+                                token,
+                            )
+                            .await
+                    }
+                },
+            )
+            .await?
+            .context("Nothing to back up for this app")?;
+        artifacts.register(TaskArtifactKind::Backup, &backup_path);
+        BackupsChanged {}.send_signal_to_dart();
+        info!(backup = %backup_path.display(), "Backed up app for transfer");
+
+        let mut device_changes = adb_service.subscribe_device_changes();
+        let target = loop {
+            if let Some(device) = adb_service.try_current_device().await
+                && (device.true_serial == target_serial || device.serial == target_serial)
+            {
+                break device;
+            }
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Waiting,
+                step_number: 2,
+                step_progress: None,
+                message: format!("Waiting for {target_label} to connect..."),
+                transfer: None,
+            });
+            tokio::select! {
+                _ = token.cancelled() => bail!("Task cancelled while waiting for the target headset"),
+                result = device_changes.changed() => result.context("Device watch closed")?,
+            }
+        };
+
+        let target_version = target
+            .installed_packages
+            .iter()
+            .find(|installed| installed.package_name() == package.as_str())
+            .map(|installed| installed.version_code());
+        let plan = restore_plan(source_version, target_version);
+        debug!(source_version, ?target_version, ?plan, "Planned transfer restore");
+
+        let mut previous_data = None;
+        if plan.replaces_data {
+            update_progress(ProgressUpdate {
+                status: TaskStatus::Waiting,
+                step_number: 3,
+                step_progress: None,
+                message: format!(
+                    "Waiting for confirmation to replace the data on {target_label}..."
+                ),
+                transfer: None,
+            });
+            let review = TransferOverwriteReview {
+                task_id,
+                app_name: name.clone(),
+                target_name: target_label.to_string(),
+                warnings: plan.warnings.clone(),
+            };
+            if !self.confirm_transfer_overwrite(review, &token).await {
+                bail!("Not replacing the data of {name} on {target_label}");
+            }
+
+            let options = BackupOptions {
+                name_append: Some("before transfer".to_string()),
+                backup_apk: true,
+                backup_data: true,
+                backup_obb: true,
+                require_private_data: false,
+                compress: false,
+            };
+            let backups_path = self.settings.read().await.backups_location();
+            previous_data = self
+                .run_adb_one_step(
+                    AdbStepConfig {
+                        step_number: 3,
+                        waiting_msg: "Waiting to back up the target...",
+                        running_msg: format!("Backing up the current data on {target_label}..."),
+                        log_context: "transfer target backup",
+                    },
+                    update_progress,
+                    token.clone(),
+                    || {
+                        let adb_service = adb_service.clone();
+                        let (target, package) = (target.clone(), package.clone());
+                        let display_name = display_name.clone();
+                        let (backups_path, token) = (backups_path.clone(), token.clone());
+                        async move {
+                            adb_service
+                                .backup_app(
+                                    &target,
+                                    &package,
+                                    display_name.as_deref(),
+                                    &backups_path,
+                                    &options,
+                                    token,
+                                )
+                                .await
+                        }
+                    },
+                )
+                .await?;
+            if let Some(path) = &previous_data {
+                artifacts.register(TaskArtifactKind::Backup, path);
+                BackupsChanged {}.send_signal_to_dart();
+                info!(backup = %path.display(), "Backed up target data before transfer");
+            }
+        }
+
+        self.run_adb_one_step(
+            AdbStepConfig {
+                step_number: 4,
+                waiting_msg: "Waiting to start restore...",
+                running_msg: format!("Restoring app data to {target_label}..."),
+                log_context: "transfer restore",
+            },
+            update_progress,
+            token,
+            || async {
+                let target_package = (!plan.install_apk).then_some(&package);
+                adb_service.restore_backup(&target, &backup_path, target_package).await
+            },
+        )
+        .await?;
+
+        if let Some(path) = previous_data {
+            let backup_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Toast::send(
+                format!("Transferred {name} to {target_label}"),
+                format!("The previous data is kept in the backup {backup_name}"),
+                false,
+                None,
+            );
+        }
+        Ok(())
+    }
+
+    /// Asks the user whether a transfer may replace the data on the target, declining when the
+    /// task is cancelled or nobody answers within [`OVERWRITE_REVIEW_TIMEOUT`]
+    async fn confirm_transfer_overwrite(
+        &self,
+        review: TransferOverwriteReview,
+        token: &CancellationToken,
+    ) -> bool {
+        /// Forgets the review if the transfer ends before it is answered
+        struct PendingReview<'a>(&'a TaskManager, u64);
+        impl Drop for PendingReview<'_> {
+            fn drop(&mut self) {
+                self.0.transfer_reviews().remove(&self.1);
+            }
+        }
+
+        let task_id = review.task_id;
+        let (approved_tx, approved_rx) = oneshot::channel();
+        self.transfer_reviews().insert(task_id, approved_tx);
+        let _pending = PendingReview(self, task_id);
+        review.send_signal_to_dart();
+
+        let approved = tokio::select! {
+            _ = token.cancelled() => false,
+            answer = timeout(OVERWRITE_REVIEW_TIMEOUT, approved_rx) => match answer {
+                Ok(answer) => answer.unwrap_or(false),
+                Err(_) => {
+                    warn!(task_id, "Transfer overwrite review timed out, declining");
+                    false
+                }
+            },
+        };
+        info!(task_id, approved, "Transfer overwrite reviewed");
+        approved
+    }
+
+    /// Resolves a transfer waiting for the user to confirm replacing data
+    pub(super) fn decide_transfer_overwrite(&self, task_id: u64, approved: bool) {
+        match self.transfer_reviews().remove(&task_id) {
+            Some(sender) => {
+                let _ = sender.send(approved);
+            }
+            None => warn!(task_id, "Transfer overwrite review not found, it may have ended"),
+        }
+    }
+
+    fn transfer_reviews(&self) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<bool>>> {
+        self.transfer_reviews.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_plan_keeps_newer_installed_versions() {
+        assert_eq!(
+            restore_plan(10, None),
+            RestorePlan { install_apk: true, replaces_data: false, warnings: Vec::new() }
+        );
+
+        let older = restore_plan(10, Some(9));
+        assert!(older.install_apk && older.replaces_data);
+        assert!(older.warnings.is_empty());
+
+        let same = restore_plan(10, Some(10));
+        assert!(!same.install_apk && same.replaces_data);
+        assert!(same.warnings.is_empty());
+
+        let newer = restore_plan(10, Some(11));
+        assert!(!newer.install_apk && newer.replaces_data);
+        assert_eq!(newer.warnings.len(), 1);
+        assert!(newer.warnings[0].contains("newer version (11)"));
+    }
+}
@@ -0,0 +1,165 @@
+use std::{collections::HashMap, error::Error, sync::Arc, time::Duration};
+
+use anyhow::{Result, anyhow, bail};
+use rinf::RustSignal;
+use tokio::sync::{broadcast, oneshot};
+use tracing::{info, instrument, warn};
+
+use super::TaskManager;
+use crate::{
+    adb::PackageName,
+    models::signals::task::{
+        Task, TaskPriority, TaskProgress, TaskStatus, UpdateAllApp, UpdateAllCanaryResult,
+        UpdateAllRequest,
+    },
+};
+
+/// Time the canary app gets to start before it is checked to be running
+const LAUNCH_SETTLE: Duration = Duration::from_secs(8);
+
+impl TaskManager {
+    /// Queues updates of an Update All batch.
+    ///
+    /// In canary mode the smallest app goes first on its own: it is updated, launched and
+    /// stopped, and the rest of the batch continues only once the user confirms.
+    #[instrument(skip(self, request), fields(apps = request.apps.len(), canary = request.canary))]
+    pub(super) async fn update_all(self: Arc<Self>, request: UpdateAllRequest) {
+        let UpdateAllRequest { mut apps, canary } = request;
+        if !canary || apps.len() < 2 {
+            self.enqueue_updates(apps).await;
+            return;
+        }
+
+        let sizes = self.app_sizes(&apps).await;
+        let canary = apps.remove(pick_canary(&sizes));
+
+        let mut progress = self.subscribe_progress();
+        let task =
+            Task::DownloadInstall(canary.full_name.clone(), canary.package_name.clone(), None);
+        let Some(batch_id) = self.clone().enqueue_task(task, TaskPriority::High).await else {
+            return;
+        };
+        info!(batch_id, app = %canary.full_name, remaining = apps.len(), "Updating canary app");
+
+        let error = self.check_canary(batch_id, &canary, &mut progress).await.err();
+        drop(progress);
+        if let Some(e) = &error {
+            warn!(batch_id, error = e.as_ref() as &dyn Error, "Canary update failed");
+        }
+
+        let (proceed_tx, proceed_rx) = oneshot::channel();
+        self.paused_batches().insert(batch_id, proceed_tx);
+        UpdateAllCanaryResult {
+            batch_id,
+            app_name: canary.full_name,
+            error: error.map(|e| format!("{e:#}")),
+            remaining: apps.len() as u32,
+        }
+        .send_signal_to_dart();
+
+        let proceed = tokio::select! {
+            _ = self.shutdown_token.cancelled() => false,
+            proceed = proceed_rx => proceed.unwrap_or(false),
+        };
+        self.paused_batches().remove(&batch_id);
+        if proceed {
+            info!(batch_id, count = apps.len(), "Continuing Update All batch");
+            self.enqueue_updates(apps).await;
+        } else {
+            info!(batch_id, "Update All batch stopped after canary");
+        }
+    }
+
+    /// Resolves a batch paused after its canary app
+    pub(super) fn continue_update_all(&self, batch_id: u64, proceed: bool) {
+        match self.paused_batches().remove(&batch_id) {
+            Some(sender) => {
+                let _ = sender.send(proceed);
+            }
+            None => warn!(batch_id, "Update All batch not found, it may have been stopped"),
+        }
+    }
+
+    fn paused_batches(&self) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<bool>>> {
+        self.paused_batches.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    async fn enqueue_updates(self: Arc<Self>, apps: Vec<UpdateAllApp>) {
+        for app in apps {
+            let task = Task::DownloadInstall(app.full_name, app.package_name, None);
+            if self.clone().enqueue_task(task, TaskPriority::Normal).await.is_none() {
+                return;
+            }
+        }
+    }
+
+    async fn app_sizes(&self, apps: &[UpdateAllApp]) -> Vec<Option<u64>> {
+        let Some(downloader) = self.downloader_manager.get().await else {
+            return vec![None; apps.len()];
+        };
+        let mut sizes = Vec::with_capacity(apps.len());
+        for app in apps {
+            sizes.push(downloader.get_app_by_full_name(&app.full_name).await.map(|app| app.size));
+        }
+        sizes
+    }
+
+    /// Waits for the canary task to finish, then checks that the updated app launches
+    async fn check_canary(
+        &self,
+        task_id: u64,
+        app: &UpdateAllApp,
+        progress: &mut broadcast::Receiver<TaskProgress>,
+    ) -> Result<()> {
+        wait_for_task(task_id, progress).await?;
+        let package = PackageName::parse(&app.package_name)?;
+        self.adb_service.check_launch(&package, LAUNCH_SETTLE).await
+    }
+}
+
+/// Index of the app to update first: the smallest one, apps of unknown size go last
+fn pick_canary(sizes: &[Option<u64>]) -> usize {
+    sizes
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, size)| size.unwrap_or(u64::MAX))
+        .map_or(0, |(index, _)| index)
+}
+
+async fn wait_for_task(
+    task_id: u64,
+    progress: &mut broadcast::Receiver<TaskProgress>,
+) -> Result<()> {
+    loop {
+        let update = match progress.recv().await {
+            Ok(update) => update,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                bail!("Lost track of the update after missing {skipped} progress updates");
+            }
+            Err(broadcast::error::RecvError::Closed) => {
+                bail!("Task progress channel closed");
+            }
+        };
+        if update.task_id != task_id {
+            continue;
+        }
+        match update.status {
+            TaskStatus::Completed => return Ok(()),
+            TaskStatus::Failed => return Err(anyhow!(update.message)),
+            TaskStatus::Cancelled => bail!("Update was cancelled"),
+            TaskStatus::Waiting | TaskStatus::Running => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canary_is_the_smallest_app_with_a_known_size() {
+        assert_eq!(pick_canary(&[Some(300), None, Some(100), Some(100)]), 2);
+        assert_eq!(pick_canary(&[None, Some(u64::MAX - 1)]), 1);
+        assert_eq!(pick_canary(&[None, None]), 0);
+    }
+}
@@ -0,0 +1,298 @@
+//! Lifetime and session totals of downloads, pushes to devices and installs.
+//!
+//! Lifetime totals are kept in a file in the app directory and survive restarts, session totals
+//! start over with every run. Both are sent to Dart every few seconds while they change.
+
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+use tokio::time;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::models::signals::{
+    stats::{
+        GetTransferStatsRequest, ResetTransferStatsRequest, TransferStatsUpdate, TransferTotals,
+    },
+    task::DownloadSummary,
+};
+
+/// How often changed totals are sent to Dart
+const SEND_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Raw counters of one period
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Counters {
+    #[serde(default)]
+    bytes_downloaded: u64,
+    #[serde(default)]
+    download_ms: u64,
+    #[serde(default)]
+    downloads_completed: u32,
+    #[serde(default)]
+    bytes_pushed: u64,
+    #[serde(default)]
+    push_ms: u64,
+    #[serde(default)]
+    installs_completed: u32,
+}
+
+impl Counters {
+    fn totals(&self) -> TransferTotals {
+        TransferTotals {
+            bytes_downloaded: self.bytes_downloaded,
+            downloads_completed: self.downloads_completed,
+            average_download_speed: bytes_per_sec(self.bytes_downloaded, self.download_ms),
+            bytes_pushed: self.bytes_pushed,
+            installs_completed: self.installs_completed,
+            average_push_speed: bytes_per_sec(self.bytes_pushed, self.push_ms),
+        }
+    }
+}
+
+fn bytes_per_sec(bytes: u64, millis: u64) -> u64 {
+    if millis == 0 {
+        return 0;
+    }
+    (bytes as u128 * 1000 / millis as u128) as u64
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedStats {
+    /// Milliseconds since Unix epoch when counting started
+    since: u64,
+    #[serde(default)]
+    counters: Counters,
+}
+
+#[derive(Debug)]
+struct State {
+    lifetime: PersistedStats,
+    session: Counters,
+}
+
+/// Records transfer totals and sends them to Dart
+#[derive(Debug)]
+pub(crate) struct TransferStatistics {
+    file_path: PathBuf,
+    state: Mutex<State>,
+    /// Whether the totals changed since they were last sent
+    changed: AtomicBool,
+}
+
+impl TransferStatistics {
+    /// Loads the lifetime totals from the app directory, starting over if there are none
+    #[instrument(level = "debug")]
+    pub(crate) fn load(app_dir: PathBuf) -> Self {
+        let file_path = app_dir.join("transfer_stats.json");
+        let lifetime = if file_path.exists() {
+            Self::read_stats(&file_path).unwrap_or_else(|e| {
+                warn!(
+                    error = e.as_ref() as &dyn Error,
+                    "Failed to load transfer statistics, starting over"
+                );
+                PersistedStats { since: now_millis(), counters: Counters::default() }
+            })
+        } else {
+            PersistedStats { since: now_millis(), counters: Counters::default() }
+        };
+        debug!(since = lifetime.since, "Loaded transfer statistics");
+
+        Self {
+            file_path,
+            state: Mutex::new(State { lifetime, session: Counters::default() }),
+            changed: AtomicBool::new(true),
+        }
+    }
+
+    /// Starts answering requests from Dart and sending changed totals
+    pub(crate) fn start(self) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.send_periodically().await }
+        });
+        handle
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let get_receiver = GetTransferStatsRequest::get_dart_signal_receiver();
+        let reset_receiver = ResetTransferStatsRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = get_receiver.recv() => {
+                    if request.is_none() {
+                        panic!("GetTransferStatsRequest receiver closed");
+                    }
+                    debug!("Received GetTransferStatsRequest");
+                    self.resend();
+                }
+                request = reset_receiver.recv() => {
+                    if request.is_none() {
+                        panic!("ResetTransferStatsRequest receiver closed");
+                    }
+                    debug!("Received ResetTransferStatsRequest");
+                    if let Err(e) = self.reset() {
+                        error!(error = e.as_ref() as &dyn Error, "Failed to reset transfer statistics");
+                    }
+                    self.resend();
+                }
+            }
+        }
+    }
+
+    async fn send_periodically(&self) {
+        let mut interval = time::interval(SEND_INTERVAL);
+        loop {
+            interval.tick().await;
+            if self.changed.swap(false, Ordering::Relaxed) {
+                self.update().send_signal_to_dart();
+            }
+        }
+    }
+
+    /// Sends the current totals to Dart right away
+    pub(crate) fn resend(&self) {
+        self.changed.store(false, Ordering::Relaxed);
+        self.update().send_signal_to_dart();
+    }
+
+    fn update(&self) -> TransferStatsUpdate {
+        let state = self.state.lock().expect("transfer statistics lock poisoned");
+        TransferStatsUpdate {
+            lifetime: state.lifetime.counters.totals(),
+            session: state.session.totals(),
+            lifetime_since: state.lifetime.since,
+        }
+    }
+
+    /// Adds a finished download step
+    pub(crate) fn record_download(&self, summary: &DownloadSummary) {
+        self.record(|counters| {
+            counters.bytes_downloaded += summary.total_bytes;
+            counters.download_ms += summary.duration_ms;
+            counters.downloads_completed += 1;
+        });
+    }
+
+    /// Adds files pushed to a device
+    pub(crate) fn record_push(&self, bytes: u64, duration: Duration) {
+        self.record(|counters| {
+            counters.bytes_pushed += bytes;
+            counters.push_ms += duration.as_millis() as u64;
+        });
+    }
+
+    /// Adds a successful install
+    pub(crate) fn record_install(&self) {
+        self.record(|counters| counters.installs_completed += 1);
+    }
+
+    fn record(&self, change: impl Fn(&mut Counters)) {
+        let mut state = self.state.lock().expect("transfer statistics lock poisoned");
+        change(&mut state.lifetime.counters);
+        change(&mut state.session);
+        self.changed.store(true, Ordering::Relaxed);
+        if let Err(e) = self.save(&state.lifetime) {
+            warn!(error = e.as_ref() as &dyn Error, "Failed to save transfer statistics");
+        }
+    }
+
+    /// Starts the lifetime totals over, the session totals are kept
+    #[instrument(level = "debug", skip(self), err)]
+    pub(crate) fn reset(&self) -> Result<()> {
+        let mut state = self.state.lock().expect("transfer statistics lock poisoned");
+        state.lifetime = PersistedStats { since: now_millis(), counters: Counters::default() };
+        self.changed.store(true, Ordering::Relaxed);
+        info!("Reset transfer statistics");
+        self.save(&state.lifetime)
+    }
+
+    fn read_stats(path: &Path) -> Result<PersistedStats> {
+        let content =
+            fs::read_to_string(path).context("Failed to read transfer statistics file")?;
+        serde_json::from_str(&content).context("Failed to parse transfer statistics file")
+    }
+
+    fn save(&self, stats: &PersistedStats) -> Result<()> {
+        let json = serde_json::to_string_pretty(stats)
+            .context("Failed to serialize transfer statistics")?;
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).context("Failed to write transfer statistics file")?;
+        fs::rename(&tmp_path, &self.file_path).context("Failed to replace transfer statistics file")
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(total_bytes: u64, duration_ms: u64) -> DownloadSummary {
+        DownloadSummary {
+            total_bytes,
+            average_speed: 0,
+            peak_speed: 0,
+            retries: 0,
+            source: None,
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn lifetime_totals_persist_and_session_totals_start_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = TransferStatistics::load(dir.path().to_path_buf());
+        stats.record_download(&summary(4_000, 2_000));
+        stats.record_download(&summary(2_000, 1_000));
+        stats.record_push(3_000, Duration::from_secs(3));
+        stats.record_install();
+
+        let update = stats.update();
+        assert_eq!(update.lifetime, update.session);
+        assert_eq!(update.lifetime.bytes_downloaded, 6_000);
+        assert_eq!(update.lifetime.downloads_completed, 2);
+        assert_eq!(update.lifetime.average_download_speed, 2_000);
+        assert_eq!(update.lifetime.average_push_speed, 1_000);
+        assert_eq!(update.lifetime.installs_completed, 1);
+
+        let reloaded = TransferStatistics::load(dir.path().to_path_buf());
+        let update = reloaded.update();
+        assert_eq!(update.lifetime.bytes_downloaded, 6_000);
+        assert_eq!(update.session, TransferTotals::default());
+    }
+
+    #[test]
+    fn reset_keeps_session_totals() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = TransferStatistics::load(dir.path().to_path_buf());
+        stats.record_install();
+
+        stats.reset().unwrap();
+
+        let update = stats.update();
+        assert_eq!(update.lifetime, TransferTotals::default());
+        assert_eq!(update.session.installs_completed, 1);
+        let reloaded = TransferStatistics::load(dir.path().to_path_buf());
+        assert_eq!(reloaded.update().lifetime, TransferTotals::default());
+    }
+}
@@ -0,0 +1,386 @@
+//! Safety backups taken before apps are uninstalled, so an uninstall can be undone until the
+//! backup expires.
+//!
+//! The trash lives in a `_trash` folder of the backups location, which the backups list skips,
+//! with an index of the trashed apps next to the backups. Dart lists the trash to reinstall or
+//! delete apps from it.
+
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, ensure};
+use rinf::{DartSignal, RustSignal};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, sync::watch};
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, debug, info, info_span, instrument, warn};
+
+use crate::{
+    adb::{
+        AdbService, PackageName,
+        device::{AdbDevice, BackupOptions},
+    },
+    models::{
+        Settings,
+        signals::trash::{
+            DeleteTrashedAppRequest, ListTrashRequest, TrashListResponse, TrashedApp,
+        },
+    },
+};
+
+pub(crate) const TRASH_DIR: &str = "_trash";
+const INDEX_FILE: &str = "trash.json";
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Serializes index updates of tasks and the background pruner
+static INDEX_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Index of the trash folder. Entries are saved with paths relative to the folder, so the index
+/// stays valid when the backups location moves, and loaded with full paths.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TrashIndex {
+    entries: Vec<TrashedApp>,
+}
+
+/// Trash folder of a backups location
+pub(crate) fn trash_dir(backups_location: &Path) -> PathBuf {
+    backups_location.join(TRASH_DIR)
+}
+
+/// Backs up an app's APK and data into the trash.
+///
+/// Returns `None` if there was nothing to back up.
+#[instrument(level = "debug", skip(adb_service, device, token))]
+pub(crate) async fn backup_to_trash(
+    adb_service: &AdbService,
+    device: &AdbDevice,
+    package: &PackageName,
+    display_name: Option<&str>,
+    trash_dir: &Path,
+    token: CancellationToken,
+) -> Result<Option<TrashedApp>> {
+    fs::create_dir_all(trash_dir)
+        .await
+        .with_context(|| format!("Failed to create trash directory {}", trash_dir.display()))?;
+    let options = BackupOptions { backup_apk: true, backup_data: true, ..Default::default() };
+    let Some(path) =
+        adb_service.backup_app(device, package, display_name, trash_dir, &options, token).await?
+    else {
+        return Ok(None);
+    };
+    let app = TrashedApp {
+        path: path.to_string_lossy().into_owned(),
+        package_name: package.to_string(),
+        display_name: display_name.map(str::to_string),
+        trashed_at: now_millis(),
+    };
+    let _guard = INDEX_LOCK.lock().await;
+    let mut index = load_index(trash_dir).await?;
+    index.entries.push(app.clone());
+    save_index(trash_dir, &index).await?;
+    info!(path = app.path, "App backed up to trash");
+    Ok(Some(app))
+}
+
+/// Apps in the trash, most recently trashed first
+pub(crate) async fn list(trash_dir: &Path) -> Result<Vec<TrashedApp>> {
+    let _guard = INDEX_LOCK.lock().await;
+    let mut apps = load_index(trash_dir).await?.entries;
+    apps.sort_by(|a, b| b.trashed_at.cmp(&a.trashed_at));
+    Ok(apps)
+}
+
+/// Finds a trashed app by its backup path
+pub(crate) async fn find(trash_dir: &Path, path: &str) -> Result<Option<TrashedApp>> {
+    let _guard = INDEX_LOCK.lock().await;
+    Ok(load_index(trash_dir).await?.entries.into_iter().find(|app| app.path == path))
+}
+
+/// Removes a trashed app and deletes its backup
+#[instrument(level = "debug")]
+pub(crate) async fn delete(trash_dir: &Path, path: &str) -> Result<()> {
+    let _guard = INDEX_LOCK.lock().await;
+    let mut index = load_index(trash_dir).await?;
+    index.entries.retain(|app| app.path != path);
+    delete_backup(Path::new(path)).await?;
+    save_index(trash_dir, &index).await
+}
+
+/// Deletes a trashed app requested from Dart, refusing paths the trash does not list
+pub(crate) async fn delete_listed(trash_dir: &Path, path: &str) -> Result<()> {
+    ensure!(find(trash_dir, path).await?.is_some(), "{path} is not in the trash");
+    delete(trash_dir, path).await?;
+    info!(path, "Deleted app from trash");
+    Ok(())
+}
+
+/// Deletes all trashed apps
+#[instrument(level = "debug")]
+pub(crate) async fn empty(trash_dir: &Path) -> Result<()> {
+    let _guard = INDEX_LOCK.lock().await;
+    let index = load_index(trash_dir).await?;
+    for app in &index.entries {
+        delete_backup(Path::new(&app.path)).await?;
+    }
+    match fs::remove_dir_all(trash_dir).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to delete {}", trash_dir.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Deletes trashed apps older than `retention_days`, returning how many were deleted
+#[instrument(level = "debug")]
+pub(crate) async fn prune(trash_dir: &Path, retention_days: u32) -> Result<usize> {
+    if retention_days == 0 || !fs::try_exists(trash_dir).await.unwrap_or(false) {
+        return Ok(0);
+    }
+    let _guard = INDEX_LOCK.lock().await;
+    let index = load_index(trash_dir).await?;
+    let max_age_millis = u64::from(retention_days) * 24 * 60 * 60 * 1000;
+    let (mut kept, expired) = split_expired(index.entries, max_age_millis, now_millis());
+    let mut deleted = 0;
+    for app in expired {
+        match delete_backup(Path::new(&app.path)).await {
+            Ok(()) => deleted += 1,
+            Err(e) => {
+                warn!(path = app.path, error = e.as_ref() as &dyn Error, "Failed to empty trash");
+                kept.push(app);
+            }
+        }
+    }
+    if deleted > 0 {
+        save_index(trash_dir, &TrashIndex { entries: kept }).await?;
+        info!(deleted, "Deleted expired apps from trash");
+    }
+    Ok(deleted)
+}
+
+/// Prunes expired trash on an interval
+pub(crate) fn start_pruner(settings: watch::Receiver<Settings>) {
+    tokio::spawn(
+        async move {
+            let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let (dir, retention_days) = {
+                    let settings = settings.borrow();
+                    (trash_dir(&settings.backups_location()), settings.trash_retention_days)
+                };
+                if let Err(e) = prune(&dir, retention_days).await {
+                    warn!(error = e.as_ref() as &dyn Error, "Failed to prune trash");
+                }
+            }
+        }
+        .instrument(info_span!("task_trash_pruner")),
+    );
+}
+
+/// Answers trash list and delete requests from Dart
+pub(crate) fn start_request_handler(settings: watch::Receiver<Settings>) {
+    tokio::spawn(receive_signals(settings).instrument(info_span!("task_trash_requests")));
+}
+
+async fn receive_signals(settings: watch::Receiver<Settings>) {
+    let list_receiver = ListTrashRequest::get_dart_signal_receiver();
+    let delete_receiver = DeleteTrashedAppRequest::get_dart_signal_receiver();
+
+    loop {
+        let (dir, retention_days) = {
+            let settings = settings.borrow();
+            (trash_dir(&settings.backups_location()), settings.trash_retention_days)
+        };
+        let delete_error = tokio::select! {
+            request = list_receiver.recv() => {
+                if request.is_none() {
+                    panic!("ListTrashRequest receiver closed");
+                }
+                None
+            }
+            request = delete_receiver.recv() => {
+                let Some(request) = request else {
+                    panic!("DeleteTrashedAppRequest receiver closed");
+                };
+                let path = request.message.path;
+                match delete_listed(&dir, &path).await {
+                    Ok(()) => None,
+                    Err(e) => {
+                        warn!(
+                            path,
+                            error = e.as_ref() as &dyn Error,
+                            "Failed to delete app from trash"
+                        );
+                        Some(format!("{e:#}"))
+                    }
+                }
+            }
+        };
+        let (apps, list_error) = match list(&dir).await {
+            Ok(apps) => (apps, None),
+            Err(e) => {
+                warn!(error = e.as_ref() as &dyn Error, "Failed to list trash");
+                (Vec::new(), Some(format!("{e:#}")))
+            }
+        };
+        TrashListResponse { apps, retention_days, error: delete_error.or(list_error) }
+            .send_signal_to_dart();
+    }
+}
+
+/// Splits apps into those to keep and those trashed more than `max_age_millis` ago
+fn split_expired(
+    apps: Vec<TrashedApp>,
+    max_age_millis: u64,
+    now_millis: u64,
+) -> (Vec<TrashedApp>, Vec<TrashedApp>) {
+    apps.into_iter().partition(|app| now_millis.saturating_sub(app.trashed_at) <= max_age_millis)
+}
+
+async fn load_index(trash_dir: &Path) -> Result<TrashIndex> {
+    let path = trash_dir.join(INDEX_FILE);
+    let mut index: TrashIndex = match fs::read(&path).await {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .with_context(|| format!("Failed to parse {}", path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(TrashIndex::default()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    // Older indexes hold full paths, of a trash folder that may have moved since
+    for app in &mut index.entries {
+        if let Some(name) = Path::new(&app.path).file_name() {
+            app.path = trash_dir.join(name).to_string_lossy().into_owned();
+        }
+    }
+    Ok(index)
+}
+
+async fn save_index(trash_dir: &Path, index: &TrashIndex) -> Result<()> {
+    let path = trash_dir.join(INDEX_FILE);
+    let entries = index
+        .entries
+        .iter()
+        .map(|app| {
+            let name = Path::new(&app.path).file_name().map(|name| name.to_string_lossy());
+            TrashedApp {
+                path: name.map_or(app.path.clone(), |name| name.into_owned()),
+                ..app.clone()
+            }
+        })
+        .collect();
+    let json = serde_json::to_vec_pretty(&TrashIndex { entries })
+        .context("Failed to serialize trash index")?;
+    fs::write(&path, json).await.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+async fn delete_backup(path: &Path) -> Result<()> {
+    let result = if fs::metadata(path).await.is_ok_and(|meta| meta.is_dir()) {
+        fs::remove_dir_all(path).await
+    } else {
+        fs::remove_file(path).await
+    };
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            debug!(path = %path.display(), "Trashed backup already gone");
+            Ok(())
+        }
+        Err(e) => Err(e).with_context(|| format!("Failed to delete {}", path.display())),
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(path: &str, trashed_at: u64) -> TrashedApp {
+        TrashedApp {
+            path: path.to_string(),
+            package_name: "com.example.app".to_string(),
+            display_name: None,
+            trashed_at,
+        }
+    }
+
+    #[test]
+    fn apps_past_retention_expire() {
+        let (kept, expired) = split_expired(
+            vec![app("old", 1_000), app("edge", 5_000), app("new", 9_000)],
+            5_000,
+            10_000,
+        );
+        assert_eq!(kept, [app("edge", 5_000), app("new", 9_000)]);
+        assert_eq!(expired, [app("old", 1_000)]);
+    }
+
+    #[tokio::test]
+    async fn prune_deletes_expired_backups_and_keeps_others() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("old");
+        let new = dir.path().join("new");
+        fs::create_dir_all(&old).await.unwrap();
+        fs::create_dir_all(&new).await.unwrap();
+        let day = 24 * 60 * 60 * 1000;
+        let index = TrashIndex {
+            entries: vec![
+                app(&old.to_string_lossy(), now_millis() - 3 * day),
+                app(&new.to_string_lossy(), now_millis()),
+            ],
+        };
+        save_index(dir.path(), &index).await.unwrap();
+
+        assert_eq!(prune(dir.path(), 2).await.unwrap(), 1);
+        assert!(!old.exists());
+        assert!(new.exists());
+        let remaining = load_index(dir.path()).await.unwrap().entries;
+        assert_eq!(remaining.len(), 1);
+        assert!(find(dir.path(), &new.to_string_lossy()).await.unwrap().is_some());
+
+        assert_eq!(prune(dir.path(), 0).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn index_follows_a_moved_trash_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_dir = dir.path().join("old");
+        fs::create_dir_all(old_dir.join("backup")).await.unwrap();
+        let index = TrashIndex { entries: vec![app(&old_dir.join("backup").to_string_lossy(), 0)] };
+        save_index(&old_dir, &index).await.unwrap();
+
+        let new_dir = dir.path().join("new");
+        fs::rename(&old_dir, &new_dir).await.unwrap();
+        let moved = new_dir.join("backup").to_string_lossy().into_owned();
+        assert_eq!(load_index(&new_dir).await.unwrap().entries, [app(&moved, 0)]);
+    }
+
+    #[tokio::test]
+    async fn only_listed_apps_are_deleted() {
+        let dir = tempfile::tempdir().unwrap();
+        let older = dir.path().join("older");
+        let newer = dir.path().join("newer");
+        let unlisted = dir.path().join("unlisted");
+        for path in [&older, &newer, &unlisted] {
+            fs::create_dir_all(path).await.unwrap();
+        }
+        let index = TrashIndex {
+            entries: vec![app(&older.to_string_lossy(), 1), app(&newer.to_string_lossy(), 2)],
+        };
+        save_index(dir.path(), &index).await.unwrap();
+
+        let listed = list(dir.path()).await.unwrap();
+        assert_eq!(listed, [app(&newer.to_string_lossy(), 2), app(&older.to_string_lossy(), 1)]);
+
+        assert!(delete_listed(dir.path(), &unlisted.to_string_lossy()).await.is_err());
+        assert!(unlisted.exists());
+        delete_listed(dir.path(), &older.to_string_lossy()).await.unwrap();
+        assert!(!older.exists());
+        assert_eq!(list(dir.path()).await.unwrap(), [app(&newer.to_string_lossy(), 2)]);
+    }
+}
@@ -0,0 +1,361 @@
+use std::{
+    cmp::Ordering,
+    error::Error,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use futures::StreamExt;
+use rinf::{DartSignal, RustSignal};
+use serde::Deserialize;
+use tokio::{fs, io::AsyncWriteExt};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::{built_info, downloader::tls, models::signals::update::*};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/skrimix/yaas/releases/latest";
+/// Startup check waits a bit so it does not compete with core initialization
+const STARTUP_CHECK_DELAY: Duration = Duration::from_secs(10);
+/// Minimum time between download progress signals
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    size: u64,
+}
+
+/// Suffix of the release asset built for the current platform
+fn platform_asset_suffix() -> Option<&'static str> {
+    if cfg!(target_os = "windows") {
+        Some("-windows-x64.zip")
+    } else if cfg!(target_os = "linux") {
+        Some("-linux-x86_64.AppImage")
+    } else if cfg!(target_os = "macos") {
+        Some("-macos.zip")
+    } else {
+        None
+    }
+}
+
+/// Splits a version like `v1.2.3-beta.1` into its numeric parts and whether it is a pre-release.
+fn parse_version(version: &str) -> Option<(Vec<u64>, bool)> {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let version = version.split_once('+').map_or(version, |(version, _)| version);
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, !pre.is_empty()),
+        None => (version, false),
+    };
+    let parts = core.split('.').map(|part| part.parse().ok()).collect::<Option<Vec<u64>>>()?;
+    (!parts.is_empty()).then_some((parts, pre))
+}
+
+/// Returns whether `candidate` is a newer version than `current`.
+///
+/// Missing components count as zero and a pre-release is older than its release.
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let (Some((candidate, candidate_pre)), Some((current, current_pre))) =
+        (parse_version(candidate), parse_version(current))
+    else {
+        return false;
+    };
+    let len = candidate.len().max(current.len());
+    let component = |parts: &[u64], index| parts.get(index).copied().unwrap_or(0);
+    let numeric = (0..len)
+        .map(|index| component(&candidate, index).cmp(&component(&current, index)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal);
+    match numeric {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => current_pre && !candidate_pre,
+    }
+}
+
+/// Builds update info from `release` if it is newer than `current_version`
+fn update_from_release(
+    release: GithubRelease,
+    current_version: &str,
+    asset_suffix: Option<&str>,
+) -> Option<UpdateInfo> {
+    if release.draft || release.prerelease || !is_newer(&release.tag_name, current_version) {
+        return None;
+    }
+    let asset = asset_suffix.and_then(|suffix| {
+        let suffix = suffix.to_ascii_lowercase();
+        release.assets.into_iter().find(|asset| asset.name.to_ascii_lowercase().ends_with(&suffix))
+    });
+    Some(UpdateInfo {
+        version: release.tag_name.trim_start_matches(['v', 'V']).to_string(),
+        current_version: current_version.to_string(),
+        changelog: release.body.unwrap_or_default(),
+        release_url: release.html_url,
+        download_url: asset.as_ref().map(|asset| asset.browser_download_url.clone()),
+        asset_name: asset.as_ref().map(|asset| asset.name.clone()),
+        asset_size: asset.map(|asset| asset.size),
+        published_at: release.published_at,
+    })
+}
+
+/// Checks GitHub releases for newer versions of YAAS and downloads their builds on demand
+#[derive(Debug)]
+pub(crate) struct Updater {
+    client: reqwest::Client,
+    /// Last update found by a check
+    latest: Mutex<Option<UpdateInfo>>,
+    /// Serializes update downloads
+    download_lock: tokio::sync::Mutex<()>,
+}
+
+impl Updater {
+    pub(crate) fn new() -> Result<Self> {
+        let mut builder = tls::http_client_builder(tls::ServerPins::default())?
+            .use_rustls_tls()
+            .connect_timeout(Duration::from_secs(10));
+        if let Some(proxy) = crate::utils::get_sys_proxy() {
+            builder = builder.proxy(reqwest::Proxy::all(&proxy)?);
+        }
+        let client = builder.build().context("Failed to build HTTP client")?;
+        Ok(Self { client, latest: Mutex::new(None), download_lock: tokio::sync::Mutex::new(()) })
+    }
+
+    /// Starts handling update requests from Dart, checking once shortly after startup if
+    /// `check_on_startup` is set.
+    pub(crate) fn start(self, check_on_startup: bool) -> Arc<Self> {
+        let handle = Arc::new(self);
+        tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.receive_signals().await }
+        });
+        if check_on_startup {
+            tokio::spawn({
+                let handle = handle.clone();
+                async move {
+                    tokio::time::sleep(STARTUP_CHECK_DELAY).await;
+                    if let Err(e) = handle.check().await {
+                        warn!(error = e.as_ref() as &dyn Error, "Startup update check failed");
+                    }
+                }
+            });
+        }
+        handle
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn receive_signals(self: Arc<Self>) {
+        let check_receiver = CheckForUpdateRequest::get_dart_signal_receiver();
+        let download_receiver = DownloadUpdateRequest::get_dart_signal_receiver();
+
+        loop {
+            tokio::select! {
+                request = check_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received CheckForUpdateRequest");
+                        let response = match self.check().await {
+                            Ok(update) => UpdateCheckResponse { update, error: None },
+                            Err(e) => {
+                                error!(error = e.as_ref() as &dyn Error, "Update check failed");
+                                UpdateCheckResponse { update: None, error: Some(format!("{e:#}")) }
+                            }
+                        };
+                        response.send_signal_to_dart();
+                    } else {
+                        panic!("CheckForUpdateRequest receiver closed");
+                    }
+                }
+                request = download_receiver.recv() => {
+                    if request.is_some() {
+                        debug!("Received DownloadUpdateRequest");
+                        // Downloads take a while, keep answering checks meanwhile
+                        tokio::spawn({
+                            let handle = self.clone();
+                            async move {
+                                let response = match handle.download().await {
+                                    Ok(path) => UpdateDownloadResponse {
+                                        path: Some(path.to_string_lossy().into_owned()),
+                                        error: None,
+                                    },
+                                    Err(e) => {
+                                        error!(
+                                            error = e.as_ref() as &dyn Error,
+                                            "Update download failed"
+                                        );
+                                        UpdateDownloadResponse {
+                                            path: None,
+                                            error: Some(format!("{e:#}")),
+                                        }
+                                    }
+                                };
+                                response.send_signal_to_dart();
+                            }
+                        });
+                    } else {
+                        panic!("DownloadUpdateRequest receiver closed");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fetches the latest release, notifying Dart with `UpdateAvailable` if it is newer.
+    #[instrument(level = "debug", skip(self), err)]
+    async fn check(&self) -> Result<Option<UpdateInfo>> {
+        let response = self
+            .client
+            .get(LATEST_RELEASE_URL)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await
+            .context("Failed to request latest release")?;
+        if !response.status().is_success() {
+            bail!("GitHub responded with status {}", response.status());
+        }
+        let release: GithubRelease =
+            response.json().await.context("Failed to parse latest release")?;
+        debug!(tag = %release.tag_name, "Fetched latest release");
+
+        let update = update_from_release(release, built_info::PKG_VERSION, platform_asset_suffix());
+        match &update {
+            Some(update) => {
+                info!(version = %update.version, "Update available");
+                UpdateAvailable { update: update.clone() }.send_signal_to_dart();
+            }
+            None => debug!(current = built_info::PKG_VERSION, "YAAS is up to date"),
+        }
+        *self.latest.lock().expect("updater lock poisoned") = update.clone();
+        Ok(update)
+    }
+
+    /// Downloads the build of the last found update into a temporary directory.
+    #[instrument(level = "debug", skip(self), err)]
+    async fn download(&self) -> Result<PathBuf> {
+        let _guard = self.download_lock.lock().await;
+        let update = self.latest.lock().expect("updater lock poisoned").clone();
+        let Some(update) = update else {
+            bail!("No update available, check for updates first");
+        };
+        let (Some(url), Some(asset_name)) = (update.download_url, update.asset_name) else {
+            bail!("Release {} has no build for this platform", update.version);
+        };
+        let file_name = sanitize_filename::sanitize(&asset_name);
+
+        let dir = std::env::temp_dir().join("yaas-update");
+        fs::create_dir_all(&dir).await.context("Failed to create update directory")?;
+        let path = dir.join(&file_name);
+        if let Some(size) = update.asset_size
+            && fs::metadata(&path).await.is_ok_and(|metadata| metadata.len() == size)
+        {
+            debug!(path = %path.display(), "Update already downloaded");
+            return Ok(path);
+        }
+
+        info!(url, path = %path.display(), "Downloading update");
+        let response = self.client.get(&url).send().await.context("Failed to request update")?;
+        if !response.status().is_success() {
+            bail!("Update download failed with status {}", response.status());
+        }
+        let total = response.content_length().or(update.asset_size);
+        let part_path = dir.join(format!("{file_name}.part"));
+        let mut file =
+            fs::File::create(&part_path).await.context("Failed to create update file")?;
+        let mut stream = response.bytes_stream();
+        let mut received = 0u64;
+        let mut last_progress = Instant::now();
+        UpdateDownloadProgress { received, total }.send_signal_to_dart();
+        while let Some(chunk) = stream.next().await.transpose().context("Network error")? {
+            file.write_all(&chunk).await.context("Failed to write update file")?;
+            received = received.saturating_add(chunk.len() as u64);
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                UpdateDownloadProgress { received, total }.send_signal_to_dart();
+            }
+        }
+        file.flush().await.context("Failed to write update file")?;
+        drop(file);
+        UpdateDownloadProgress { received, total }.send_signal_to_dart();
+
+        if let Some(total) = total
+            && received != total
+        {
+            let _ = fs::remove_file(&part_path).await;
+            bail!("Update download incomplete: got {received} of {total} bytes");
+        }
+        fs::rename(&part_path, &path).await.context("Failed to move downloaded update")?;
+        info!(path = %path.display(), "Update downloaded");
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_versions() {
+        assert!(is_newer("v1.2.0", "1.1.9"));
+        assert!(is_newer("1.10.0", "1.9.0"));
+        assert!(is_newer("1.2.1", "1.2"));
+        assert!(is_newer("1.2.0", "1.2.0-beta.2"));
+        assert!(!is_newer("1.2.0-beta.2", "1.2.0"));
+        assert!(!is_newer("1.2.0", "1.2.0"));
+        assert!(!is_newer("1.2.0+5", "1.2.0"));
+        assert!(!is_newer("1.1.0", "1.2.0"));
+        assert!(!is_newer("nightly", "1.2.0"));
+    }
+
+    #[test]
+    fn update_picks_platform_asset() {
+        let release = |tag: &str| -> GithubRelease {
+            serde_json::from_value(serde_json::json!({
+                "tag_name": tag,
+                "html_url": "https://github.com/skrimix/yaas/releases/tag/v2.0.0",
+                "body": "Changes",
+                "published_at": "2025-01-01T00:00:00Z",
+                "assets": [
+                    {
+                        "name": "YAAS-linux-x86_64.AppImage",
+                        "browser_download_url": "https://example.com/YAAS-linux-x86_64.AppImage",
+                        "size": 100
+                    },
+                    {
+                        "name": "YAAS-windows-x64.zip",
+                        "browser_download_url": "https://example.com/YAAS-windows-x64.zip",
+                        "size": 200
+                    }
+                ]
+            }))
+            .unwrap()
+        };
+
+        let update = update_from_release(release("v2.0.0"), "1.0.0", Some("-windows-x64.zip"))
+            .expect("newer release");
+        assert_eq!(update.version, "2.0.0");
+        assert_eq!(update.changelog, "Changes");
+        assert_eq!(update.asset_name.as_deref(), Some("YAAS-windows-x64.zip"));
+        assert_eq!(update.asset_size, Some(200));
+
+        let update = update_from_release(release("v2.0.0"), "1.0.0", Some("-macos.zip")).unwrap();
+        assert_eq!(update.download_url, None);
+
+        assert_eq!(update_from_release(release("v1.0.0"), "1.0.0", None), None);
+    }
+}
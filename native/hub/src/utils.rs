@@ -4,10 +4,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result, ensure};
 use sysproxy::Sysproxy;
 use tokio::fs;
-use tracing::{debug, instrument, trace, warn};
+use tracing::{Span, debug, instrument, trace, warn};
 
 #[instrument(level = "debug")]
 pub(crate) fn get_sys_proxy() -> Option<String> {
@@ -184,6 +184,89 @@ pub(crate) async fn dir_has_any_files(dir: &Path) -> Result<bool> {
     Ok(false)
 }
 
+/// Computes the total size of all files in a directory recursively, 0 if it does not exist
+#[instrument(level = "debug", fields(dir = %dir.display(), size), err)]
+pub(crate) async fn dir_size(dir: &Path) -> Result<u64> {
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+    let mut total: u64 = 0;
+    let mut stack: Vec<PathBuf> = vec![dir.to_path_buf()];
+    while let Some(path) = stack.pop() {
+        let mut rd = match fs::read_dir(&path).await {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        while let Some(entry) = rd.next_entry().await? {
+            let meta = match entry.metadata().await {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            if meta.is_file() {
+                total = total.saturating_add(meta.len());
+            } else if meta.is_dir() {
+                stack.push(entry.path());
+            }
+        }
+    }
+    Span::current().record("size", total);
+    Ok(total)
+}
+
+/// Lists all files under a directory with their sizes.
+///
+/// Paths are relative to `dir` with `/` separators, sorted.
+pub(crate) async fn list_files_relative(dir: &Path) -> Result<Vec<(String, u64)>> {
+    let mut files = Vec::new();
+    let mut stack: Vec<PathBuf> = vec![dir.to_path_buf()];
+    while let Some(path) = stack.pop() {
+        let mut rd = fs::read_dir(&path)
+            .await
+            .with_context(|| format!("Failed to read directory {}", path.display()))?;
+        while let Some(entry) = rd.next_entry().await? {
+            let meta = entry.metadata().await?;
+            let entry_path = entry.path();
+            if meta.is_dir() {
+                stack.push(entry_path);
+            } else if meta.is_file() {
+                let relative = entry_path.strip_prefix(dir).unwrap_or(&entry_path);
+                let components = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                files.push((components.join("/"), meta.len()));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Canonicalizes a path without the `\\?\` prefix Windows puts on canonical paths, which
+/// some tools and path comparisons do not expect. Paths that need the prefix keep it.
+pub(crate) async fn canonicalize(path: &Path) -> std::io::Result<PathBuf> {
+    let canonical = fs::canonicalize(path).await?;
+    if cfg!(windows)
+        && let Some(plain) = canonical.to_str().and_then(strip_verbatim_prefix)
+    {
+        return Ok(PathBuf::from(plain));
+    }
+    Ok(canonical)
+}
+
+/// Turns `\\?\C:\dir` into `C:\dir` and `\\?\UNC\server\share` into `\\server\share`.
+///
+/// Other verbatim paths, such as volume GUID paths, have no plain form and give None.
+fn strip_verbatim_prefix(path: &str) -> Option<String> {
+    if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        return Some(format!(r"\\{rest}"));
+    }
+    let rest = path.strip_prefix(r"\\?\")?;
+    let is_drive_path =
+        rest.as_bytes().get(1) == Some(&b':') && rest.as_bytes()[0].is_ascii_alphabetic();
+    is_drive_path.then(|| rest.to_string())
+}
+
 /// Removes a specific child directory if present. Errors are ignored.
 pub(crate) async fn remove_child_dir_if_exists(parent: &Path, child: &str) {
     let target = parent.join(child);
@@ -191,3 +274,78 @@ pub(crate) async fn remove_child_dir_if_exists(parent: &Path, child: &str) {
         let _ = fs::remove_dir_all(target).await;
     }
 }
+
+/// Moves a file or directory, copying it when it cannot be renamed, e.g. to another drive
+#[instrument(level = "debug", err)]
+pub(crate) async fn move_path(src: &Path, dst: &Path) -> Result<()> {
+    ensure!(!fs::try_exists(dst).await.unwrap_or(false), "{} already exists", dst.display());
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    if let Err(e) = fs::rename(src, dst).await {
+        debug!(error = &e as &dyn Error, "Rename failed, copying instead");
+        let is_dir = fs::metadata(src)
+            .await
+            .with_context(|| format!("Failed to read metadata of {}", src.display()))?
+            .is_dir();
+        if is_dir {
+            if let Err(e) = copy_dir(src, dst).await {
+                let _ = fs::remove_dir_all(dst).await;
+                return Err(e);
+            }
+            fs::remove_dir_all(src)
+                .await
+                .with_context(|| format!("Failed to remove {} after copying", src.display()))?;
+        } else {
+            if let Err(e) = fs::copy(src, dst).await {
+                let _ = fs::remove_file(dst).await;
+                return Err(e).with_context(|| format!("Failed to copy {}", src.display()));
+            }
+            fs::remove_file(src)
+                .await
+                .with_context(|| format!("Failed to remove {} after copying", src.display()))?;
+        }
+    }
+    Ok(())
+}
+
+async fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    let mut stack = vec![(src.to_path_buf(), dst.to_path_buf())];
+    while let Some((from, to)) = stack.pop() {
+        fs::create_dir_all(&to)
+            .await
+            .with_context(|| format!("Failed to create {}", to.display()))?;
+        let mut entries = fs::read_dir(&from)
+            .await
+            .with_context(|| format!("Failed to read {}", from.display()))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let target = to.join(entry.file_name());
+            if entry.file_type().await?.is_dir() {
+                stack.push((entry.path(), target));
+            } else {
+                fs::copy(entry.path(), &target)
+                    .await
+                    .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_verbatim_prefixes() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\Games\app").as_deref(), Some(r"C:\Games\app"));
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\app").as_deref(),
+            Some(r"\\server\share\app")
+        );
+        assert_eq!(strip_verbatim_prefix(r"\\?\Volume{1234}\app"), None);
+        assert_eq!(strip_verbatim_prefix(r"C:\Games\app"), None);
+    }
+}